@@ -0,0 +1,289 @@
+//! Load test harness: concurrent synthetic traffic against a server.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use blog_client::{BlogClient, ClientError};
+use blog_shared::{CreatePostRequest, LoginRequest, RegisterRequest};
+use clap::Args;
+use tokio::task::JoinSet;
+
+use crate::constants::{
+    BENCH_LIST_LIMIT, BENCH_PASSWORD, DEFAULT_BENCH_CONCURRENCY, DEFAULT_BENCH_MIX,
+    DEFAULT_BENCH_REQUESTS,
+};
+
+/// Arguments for the `bench` command.
+#[derive(Args, Debug)]
+pub struct BenchArgs {
+    /// Number of concurrent clients.
+    #[arg(long, default_value_t = DEFAULT_BENCH_CONCURRENCY)]
+    pub concurrency: usize,
+    /// Requests performed by each client.
+    #[arg(long, default_value_t = DEFAULT_BENCH_REQUESTS)]
+    pub requests: usize,
+    /// Weighted operation mix, e.g. "list=70,create=20,login=10,register=0".
+    #[arg(long, default_value = DEFAULT_BENCH_MIX, value_parser = Mix::parse)]
+    pub mix: Mix,
+}
+
+/// Operation performed by a synthetic client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Operation {
+    Register,
+    Login,
+    Create,
+    List,
+}
+
+impl Operation {
+    /// Parses an operation name from the mix string.
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "register" => Some(Self::Register),
+            "login" => Some(Self::Login),
+            "create" => Some(Self::Create),
+            "list" => Some(Self::List),
+            _ => None,
+        }
+    }
+
+    /// Returns the operation name for reporting.
+    fn name(self) -> &'static str {
+        match self {
+            Self::Register => "register",
+            Self::Login => "login",
+            Self::Create => "create",
+            Self::List => "list",
+        }
+    }
+}
+
+/// Result of a single timed operation.
+struct Sample {
+    operation: Operation,
+    latency: Duration,
+    ok: bool,
+}
+
+/// Runs the load test and prints a throughput/latency report.
+pub async fn run(server: String, grpc: bool, args: &BenchArgs) -> Result<(), ClientError> {
+    let run_id = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default();
+
+    println!(
+        "🚀 Running {} clients × {} requests against {}",
+        args.concurrency, args.requests, server
+    );
+
+    let started = Instant::now();
+    let mut tasks = JoinSet::new();
+    for client_id in 0..args.concurrency {
+        let server = server.clone();
+        let mix = args.mix.clone();
+        let requests = args.requests;
+        tasks.spawn(
+            async move { run_client(server, grpc, run_id, client_id, &mix, requests).await },
+        );
+    }
+
+    let mut samples = Vec::new();
+    while let Some(result) = tasks.join_next().await {
+        match result {
+            Ok(Ok(client_samples)) => samples.extend(client_samples),
+            Ok(Err(e)) => eprintln!("Client failed to start: {}", e),
+            Err(e) => eprintln!("Client task panicked: {}", e),
+        }
+    }
+
+    print_report(&samples, started.elapsed());
+    Ok(())
+}
+
+/// Runs one synthetic client: registers a user, then performs the mix.
+async fn run_client(
+    server: String,
+    grpc: bool,
+    run_id: u128,
+    client_id: usize,
+    mix: &Mix,
+    requests: usize,
+) -> Result<Vec<Sample>, ClientError> {
+    let mut client = if grpc {
+        BlogClient::grpc(&server).await?
+    } else {
+        BlogClient::http(&server)
+    };
+
+    let username = format!("bench_{}_{}", run_id, client_id);
+    let mut samples = Vec::with_capacity(requests + 1);
+    samples.push(register(&mut client, &username).await);
+
+    // Seed must be non-zero for xorshift
+    let mut rng = XorShift(((run_id as u64) << 16) | client_id as u64 | 1);
+    for seq in 0..requests {
+        let operation = mix.pick(rng.next());
+        let start = Instant::now();
+        let ok = match operation {
+            Operation::Register => {
+                let extra = format!("{}_{}", username, seq);
+                register(&mut client, &extra).await.ok
+            }
+            Operation::Login => client
+                .login(LoginRequest::new(username.clone(), BENCH_PASSWORD))
+                .await
+                .map(|resp| client.set_token(resp.token))
+                .is_ok(),
+            Operation::Create => client
+                .create_post(CreatePostRequest::new(
+                    format!("Bench post {} from {}", seq, username),
+                    "Synthetic load test content.",
+                ))
+                .await
+                .is_ok(),
+            Operation::List => client.list_posts(BENCH_LIST_LIMIT, 0).await.is_ok(),
+        };
+        samples.push(Sample {
+            operation,
+            latency: start.elapsed(),
+            ok,
+        });
+    }
+
+    Ok(samples)
+}
+
+/// Registers a user and keeps its token on the client.
+async fn register(client: &mut BlogClient, username: &str) -> Sample {
+    let start = Instant::now();
+    let result = client
+        .register(RegisterRequest::new(
+            username,
+            format!("{}@bench.local", username),
+            BENCH_PASSWORD,
+        ))
+        .await;
+    let ok = match result {
+        Ok(resp) => {
+            client.set_token(resp.token);
+            true
+        }
+        Err(_) => false,
+    };
+    Sample {
+        operation: Operation::Register,
+        latency: start.elapsed(),
+        ok,
+    }
+}
+
+/// Weighted operation mix parsed from "op=weight,..." syntax.
+#[derive(Debug, Clone)]
+pub struct Mix(Vec<(Operation, u64)>);
+
+impl Mix {
+    /// Parses a mix like "list=70,create=30" (used as a clap value parser).
+    fn parse(mix: &str) -> Result<Self, String> {
+        let mut weights = Vec::new();
+        for entry in mix.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (name, weight) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("expected op=weight, got '{}'", entry))?;
+            let operation = Operation::parse(name.trim())
+                .ok_or_else(|| format!("unknown operation '{}'", name.trim()))?;
+            let weight = weight
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid weight '{}'", weight.trim()))?;
+            weights.push((operation, weight));
+        }
+        if weights.iter().all(|(_, weight)| *weight == 0) {
+            return Err("mix must have at least one non-zero weight".to_string());
+        }
+        Ok(Self(weights))
+    }
+
+    /// Picks an operation using a random roll, proportionally to the weights.
+    fn pick(&self, roll: u64) -> Operation {
+        let total: u64 = self.0.iter().map(|(_, weight)| weight).sum();
+        let mut target = roll % total;
+        for (operation, weight) in &self.0 {
+            if target < *weight {
+                return *operation;
+            }
+            target -= weight;
+        }
+        // Unreachable while total > 0, which `parse` guarantees
+        self.0[0].0
+    }
+}
+
+/// Minimal xorshift PRNG giving each client its own operation sequence.
+struct XorShift(u64);
+
+impl XorShift {
+    /// Returns the next pseudo-random value.
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+/// Prints throughput and latency percentiles per operation and overall.
+fn print_report(samples: &[Sample], elapsed: Duration) {
+    let secs = elapsed.as_secs_f64().max(f64::EPSILON);
+    println!();
+    println!(
+        "📊 {} requests in {:.2}s ({:.1} req/s)",
+        samples.len(),
+        secs,
+        samples.len() as f64 / secs
+    );
+    println!(
+        "{:<10} {:>8} {:>8} {:>10} {:>10} {:>10} {:>10}",
+        "operation", "count", "errors", "p50 ms", "p90 ms", "p99 ms", "max ms"
+    );
+
+    let mut by_operation: BTreeMap<Operation, Vec<&Sample>> = BTreeMap::new();
+    for sample in samples {
+        by_operation
+            .entry(sample.operation)
+            .or_default()
+            .push(sample);
+    }
+    for (operation, group) in &by_operation {
+        print_row(operation.name(), group);
+    }
+    print_row("total", &samples.iter().collect::<Vec<_>>());
+}
+
+/// Prints one report row.
+fn print_row(label: &str, samples: &[&Sample]) {
+    let errors = samples.iter().filter(|s| !s.ok).count();
+    let mut latencies: Vec<Duration> = samples.iter().map(|s| s.latency).collect();
+    latencies.sort();
+    println!(
+        "{:<10} {:>8} {:>8} {:>10.1} {:>10.1} {:>10.1} {:>10.1}",
+        label,
+        samples.len(),
+        errors,
+        percentile_ms(&latencies, 50.0),
+        percentile_ms(&latencies, 90.0),
+        percentile_ms(&latencies, 99.0),
+        percentile_ms(&latencies, 100.0),
+    );
+}
+
+/// Returns the nearest-rank percentile of sorted latencies in milliseconds.
+fn percentile_ms(sorted: &[Duration], percentile: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (percentile / 100.0 * sorted.len() as f64).ceil() as usize;
+    let index = rank.clamp(1, sorted.len()) - 1;
+    sorted[index].as_secs_f64() * 1000.0
+}
@@ -0,0 +1,261 @@
+//! Interactive post browser for `list --interactive`.
+
+mod ui;
+
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::process::{Command, Stdio};
+
+use blog_client::{BlogClient, ClientError};
+use blog_shared::{PostDto, UpdatePostRequest};
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::widgets::ListState;
+
+use crate::constants::DEFAULT_EDITOR;
+use crate::sync::front_matter;
+
+/// State of the browser: the current page of posts and the selection.
+struct Browser<'a> {
+    client: &'a mut BlogClient,
+    site_url: String,
+    limit: i64,
+    offset: i64,
+    total: i64,
+    posts: Vec<PostDto>,
+    list: ListState,
+    /// Message shown in the status bar instead of the key help.
+    status: Option<String>,
+    /// Set while asking whether to delete the selected post.
+    confirm_delete: bool,
+}
+
+/// What a key press asks the event loop to do.
+enum Action {
+    Continue,
+    Edit,
+    Quit,
+}
+
+/// Shows posts `limit` at a time, starting at `offset`, until the user
+/// quits. Posts open in the browser under `site_url`.
+pub async fn run(
+    client: &mut BlogClient,
+    limit: i64,
+    offset: i64,
+    site_url: &str,
+) -> Result<(), ClientError> {
+    let mut browser = Browser {
+        client,
+        site_url: site_url.trim_end_matches('/').to_string(),
+        limit: limit.max(1),
+        offset: offset.max(0),
+        total: 0,
+        posts: Vec::new(),
+        list: ListState::default(),
+        status: None,
+        confirm_delete: false,
+    };
+    // Fail before taking over the terminal if the server is unreachable
+    browser.load().await?;
+
+    let terminal = ratatui::init();
+    let result = browser.event_loop(terminal).await;
+    ratatui::restore();
+    if let Err(e) = result {
+        eprintln!("❌ {e}");
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+impl Browser<'_> {
+    async fn event_loop(&mut self, mut terminal: DefaultTerminal) -> Result<(), Box<dyn Error>> {
+        loop {
+            terminal.draw(|frame| ui::draw(frame, self))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match self.handle_key(key.code).await {
+                Action::Continue => {}
+                Action::Quit => return Ok(()),
+                Action::Edit => {
+                    // The editor needs the terminal to itself
+                    ratatui::restore();
+                    let status = self.edit().await;
+                    terminal = ratatui::init();
+                    self.status = Some(status);
+                }
+            }
+        }
+    }
+
+    async fn handle_key(&mut self, code: KeyCode) -> Action {
+        if self.confirm_delete {
+            self.confirm_delete = false;
+            self.status = None;
+            if matches!(code, KeyCode::Char('y' | 'Y')) {
+                self.delete().await;
+            }
+            return Action::Continue;
+        }
+        self.status = None;
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Up | KeyCode::Char('k') => self.list.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.list.select_next(),
+            KeyCode::Right | KeyCode::PageDown | KeyCode::Char('n')
+                if self.offset + self.limit < self.total =>
+            {
+                self.offset += self.limit;
+                self.reload().await;
+            }
+            KeyCode::Left | KeyCode::PageUp | KeyCode::Char('p') if self.offset > 0 => {
+                self.offset = (self.offset - self.limit).max(0);
+                self.reload().await;
+            }
+            KeyCode::Char('r') => self.reload().await,
+            KeyCode::Enter | KeyCode::Char('o') => self.open(),
+            KeyCode::Char('e') if self.selected().is_some() => return Action::Edit,
+            KeyCode::Char('d') => {
+                if let Some(post) = self.selected() {
+                    self.status = Some(format!("Delete post {}? (y/n)", post.public_id));
+                    self.confirm_delete = true;
+                }
+            }
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    /// Returns the selected post, if the page has any.
+    fn selected(&self) -> Option<&PostDto> {
+        self.list.selected().and_then(|i| self.posts.get(i))
+    }
+
+    /// Fetches the current page, keeping the selection on the same row.
+    async fn load(&mut self) -> Result<(), ClientError> {
+        let page = self.client.list_posts(self.limit, self.offset).await?;
+        self.total = page.total;
+        self.posts = page.posts;
+        let row = self.list.selected().unwrap_or(0);
+        self.list.select(match self.posts.len() {
+            0 => None,
+            len => Some(row.min(len - 1)),
+        });
+        Ok(())
+    }
+
+    /// Like `load`, but reports failures in the status bar.
+    async fn reload(&mut self) {
+        if let Err(e) = self.load().await {
+            self.status = Some(format!("Failed to load posts: {e}"));
+        }
+    }
+
+    fn open(&mut self) {
+        let Some(post) = self.selected() else {
+            return;
+        };
+        let url = format!("{}/?post={}", self.site_url, post.public_id);
+        self.status = Some(match open_url(&url) {
+            Ok(()) => format!("Opened {url}"),
+            Err(e) => format!("Failed to open {url}: {e}"),
+        });
+    }
+
+    async fn delete(&mut self) {
+        let Some(id) = self.selected().map(|post| post.public_id.clone()) else {
+            return;
+        };
+        if let Err(e) = self.client.delete_post(&id).await {
+            self.status = Some(format!("Failed to delete post {id}: {e}"));
+            return;
+        }
+        // Step back a page when the last post on this one is gone
+        if self.posts.len() == 1 && self.offset > 0 {
+            self.offset = (self.offset - self.limit).max(0);
+        }
+        self.reload().await;
+        self.status.get_or_insert(format!("Deleted post {id}"));
+    }
+
+    /// Edits the selected post in `$VISUAL` / `$EDITOR`, with its title and
+    /// tags as front matter, and saves it if it changed. Returns the message
+    /// for the status bar.
+    async fn edit(&mut self) -> String {
+        let Some(post) = self.selected().cloned() else {
+            return String::new();
+        };
+        let editor = env::var("VISUAL")
+            .or_else(|_| env::var("EDITOR"))
+            .unwrap_or_else(|_| DEFAULT_EDITOR.to_string());
+        let text = match edit_file(&editor, &post) {
+            Ok(Some(text)) => text,
+            Ok(None) => return format!("{editor} failed; post {} not changed", post.public_id),
+            Err(e) => return format!("Failed to run {editor}: {e}"),
+        };
+        let doc = match front_matter::parse(&text) {
+            Ok(doc) => doc,
+            Err(e) => return format!("Post {} not changed: {e}", post.public_id),
+        };
+        let title = doc.title(&post.title);
+        if title == post.title && doc.body == post.content && doc.tags == post.tags {
+            return format!("Post {} not changed", post.public_id);
+        }
+
+        let req = UpdatePostRequest::default()
+            .with_title(title)
+            .with_content(doc.body)
+            .with_tags(doc.tags);
+        match self.client.update_post(&post.public_id, req).await {
+            Ok(_) => {
+                self.reload().await;
+                format!("Updated post {}", post.public_id)
+            }
+            Err(e) => format!("Failed to update post {}: {e}", post.public_id),
+        }
+    }
+}
+
+/// Opens a post in `editor` through a temporary file and returns the edited
+/// text, or `None` if the editor failed.
+fn edit_file(editor: &str, post: &PostDto) -> io::Result<Option<String>> {
+    let path = env::temp_dir().join(format!("blog-post-{}.md", post.public_id));
+    fs::write(
+        &path,
+        front_matter::render(&post.title, &post.tags, &post.content),
+    )?;
+    let status = Command::new(editor).arg(&path).status();
+    let text = fs::read_to_string(&path);
+    let _ = fs::remove_file(&path);
+    if !status?.success() {
+        return Ok(None);
+    }
+    text.map(Some)
+}
+
+/// Opens a URL in the default browser.
+fn open_url(url: &str) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    command
+        .arg(url)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(drop)
+}
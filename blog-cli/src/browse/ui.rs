@@ -0,0 +1,86 @@
+//! Rendering of the interactive post browser.
+
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, Paragraph, Wrap};
+
+use super::Browser;
+
+const HELP: &str = "↑↓ select  ←→ page  enter open  e edit  d delete  r reload  q quit";
+
+/// Draws the post list, the preview of the selected post, and the status bar.
+pub fn draw(frame: &mut Frame, browser: &mut Browser) {
+    let [main, status] =
+        Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+    let [list, preview] =
+        Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(main);
+
+    draw_list(frame, browser, list);
+    draw_preview(frame, browser, preview);
+
+    let status_line = match &browser.status {
+        Some(message) => Line::from(message.as_str()).bold(),
+        None => Line::from(HELP).dim(),
+    };
+    frame.render_widget(Paragraph::new(status_line), status);
+}
+
+fn draw_list(frame: &mut Frame, browser: &mut Browser, area: Rect) {
+    let items: Vec<ListItem> = browser
+        .posts
+        .iter()
+        .map(|post| {
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("[{}] ", post.public_id)).dim(),
+                Span::raw(post.title.as_str()),
+                Span::raw(format!(" by {}", post.byline())).dim(),
+            ]))
+        })
+        .collect();
+
+    let first = browser.offset + 1;
+    let last = browser.offset + browser.posts.len() as i64;
+    let title = if browser.posts.is_empty() {
+        format!(" Posts (0 of {}) ", browser.total)
+    } else {
+        format!(" Posts {first}-{last} of {} ", browser.total)
+    };
+
+    let list = List::new(items)
+        .block(Block::bordered().title(title))
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    frame.render_stateful_widget(list, area, &mut browser.list);
+}
+
+fn draw_preview(frame: &mut Frame, browser: &Browser, area: Rect) {
+    let block = Block::bordered().title(" Preview ");
+    let Some(post) = browser.selected() else {
+        frame.render_widget(Paragraph::new("No posts").block(block), area);
+        return;
+    };
+
+    let mut lines = vec![
+        Line::from(post.title.as_str()).bold(),
+        Line::from(format!(
+            "by {} · {} · updated {}",
+            post.byline(),
+            post.created_at.format("%Y-%m-%d %H:%M"),
+            post.updated_at.format("%Y-%m-%d %H:%M")
+        ))
+        .dim(),
+    ];
+    if !post.tags.is_empty() {
+        let tags: Vec<String> = post.tags.iter().map(|tag| format!("#{tag}")).collect();
+        lines.push(Line::from(tags.join(" ")).cyan());
+    }
+    lines.push(Line::default());
+    lines.extend(post.content.lines().map(Line::from));
+
+    let preview = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(preview, area);
+}
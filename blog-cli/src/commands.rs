@@ -1,85 +1,249 @@
 //! Command execution logic.
 
 use blog_client::{BlogClient, ClientError};
-use blog_shared::{CreatePostRequest, LoginRequest, RegisterRequest, UpdatePostRequest};
+use blog_shared::{CreatePostRequest, LoginRequest, PostAuthor, PostDto, RegisterRequest};
+use tokio_stream::StreamExt;
 
 use crate::Commands;
+use crate::browse;
+use crate::import::{self, Source};
+use crate::password;
+use crate::{history, sync, trash, update};
 
 /// Executes the given command using the provided client.
 /// Returns the token if login/register succeeded (for persistence).
+///
+/// With `quiet`, commands print only what a script needs: IDs of the
+/// users and posts they touch, or a post's content for `get`.
 pub async fn execute(
     client: &mut BlogClient,
     command: Commands,
+    quiet: bool,
 ) -> Result<Option<String>, ClientError> {
     match command {
         Commands::Register {
             username,
             email,
             password,
+            password_stdin,
+            invite,
         } => {
-            let req = RegisterRequest {
-                username,
-                email,
-                password,
-            };
+            let password = read_password(password, password_stdin, true);
+            let mut req = RegisterRequest::new(username, email, password);
+            req.invite_code = invite;
             let response = client.register(req).await?;
+            if quiet {
+                println!("{}", response.user.id);
+                return Ok(Some(response.token));
+            }
             println!("✅ Registered successfully!");
-            println!(
-                "User: {} (ID: {})",
-                response.user.username, response.user.id
-            );
+            println!("User: {}", response.user);
             println!("Token saved to ~/.blog_token");
+            if !response.recovery_codes.is_empty() {
+                println!("\nRecovery codes (each works once if you lose your password):");
+                for code in &response.recovery_codes {
+                    println!("  {code}");
+                }
+            }
             Ok(Some(response.token))
         }
-        Commands::Login { username, password } => {
-            let req = LoginRequest { username, password };
+        Commands::Login {
+            username,
+            password,
+            password_stdin,
+        } => {
+            let password = read_password(password, password_stdin, false);
+            let req = LoginRequest::new(username, password);
             let response = client.login(req).await?;
+            if quiet {
+                println!("{}", response.user.id);
+                return Ok(Some(response.token));
+            }
             println!("✅ Logged in successfully!");
-            println!(
-                "User: {} (ID: {})",
-                response.user.username, response.user.id
-            );
+            println!("User: {}", response.user);
             println!("Token saved to ~/.blog_token");
             Ok(Some(response.token))
         }
-        Commands::Create { title, content } => {
-            let req = CreatePostRequest { title, content };
+        Commands::Create {
+            title,
+            content,
+            org,
+        } => {
+            let mut req = CreatePostRequest::new(title, content);
+            req.org = org;
             let post = client.create_post(req).await?;
+            if quiet {
+                println!("{}", post.public_id);
+                return Ok(None);
+            }
             println!("✅ Post created!");
-            println!("ID: {}", post.id);
+            println!("ID: {}", post.public_id);
             println!("Title: {}", post.title);
             Ok(None)
         }
         Commands::Get { id } => {
-            let post = client.get_post(id).await?;
-            println!("📝 Post #{}", post.id);
+            let post = client.get_post(&id).await?;
+            if quiet {
+                println!("{}", post.content);
+                return Ok(None);
+            }
+            println!("📝 Post {}", post.public_id);
             println!("Title: {}", post.title);
             println!("Content: {}", post.content);
-            println!("Author: {} (ID: {})", post.author_username, post.author_id);
+            println!(
+                "Author: {} (ID: {})",
+                post.author_username, post.author_public_id
+            );
+            if let Some(PostAuthor::Org { name, slug, .. }) = &post.author {
+                println!("Organization: {name} ({slug})");
+            }
             println!("Created: {}", post.created_at);
             println!("Updated: {}", post.updated_at);
             Ok(None)
         }
-        Commands::List { limit, offset } => {
+        Commands::List {
+            limit,
+            offset,
+            interactive: true,
+            site_url,
+            ..
+        } => {
+            browse::run(client, limit, offset, &site_url).await?;
+            Ok(None)
+        }
+        Commands::List {
+            limit, all: true, ..
+        } => {
+            let mut posts = client.posts_iter(limit);
+            if !quiet {
+                println!("📚 All posts:");
+            }
+            while let Some(post) = posts.next().await {
+                print_post_line(&post?, quiet);
+            }
+            Ok(None)
+        }
+        Commands::List { limit, offset, .. } => {
             let response = client.list_posts(limit, offset).await?;
-            println!("📚 Posts ({} total):", response.total);
-            for post in response.posts {
-                println!("  [{}] {} by {}", post.id, post.title, post.author_username);
+            if !quiet {
+                println!("📚 Posts ({} total):", response.total);
+            }
+            for post in &response.posts {
+                print_post_line(post, quiet);
             }
             Ok(None)
         }
-        Commands::Update { id, title, content } => {
-            let req = UpdatePostRequest { title, content };
-            let post = client.update_post(id, req).await?;
-            println!("✅ Post updated!");
-            println!("ID: {}", post.id);
-            println!("Title: {}", post.title);
+        Commands::Update {
+            id,
+            title,
+            content,
+            dry_run,
+        } => {
+            update::run(client, id, title, content, dry_run, quiet).await?;
             Ok(None)
         }
         Commands::Delete { id } => {
-            client.delete_post(id).await?;
-            println!("✅ Post {} deleted!", id);
+            client.delete_post(&id).await?;
+            if !quiet {
+                println!("✅ Post {} moved to the trash!", id);
+            }
+            Ok(None)
+        }
+        Commands::Trash { action } => {
+            trash::run(client, action, quiet).await?;
+            Ok(None)
+        }
+        Commands::History { id, diff } => {
+            let diff = diff.map(|numbers| (numbers[0], numbers[1]));
+            history::run(client, id, diff, quiet).await?;
             Ok(None)
         }
+        Commands::CheckLinks => {
+            let response = client.broken_links().await?;
+            if quiet {
+                for link in &response.links {
+                    println!("{}\t{}", link.post_public_id, link.url);
+                }
+                return Ok(None);
+            }
+            if response.links.is_empty() {
+                println!("✅ No broken links found!");
+                return Ok(None);
+            }
+            println!("🔗 Broken links ({}):", response.links.len());
+            for link in response.links {
+                let reason = match (link.status_code, link.error) {
+                    (Some(status), _) => format!("HTTP {status}"),
+                    (None, Some(error)) => error,
+                    (None, None) => "unreachable".to_string(),
+                };
+                println!("  [{}] {}", link.post_public_id, link.post_title);
+                println!(
+                    "      {} ({}, checked {})",
+                    link.url, reason, link.checked_at
+                );
+            }
+            Ok(None)
+        }
+        Commands::ImportWordpress {
+            file,
+            authors,
+            dry_run,
+            concurrency,
+        } => {
+            import::run(
+                client,
+                Source::WordPress,
+                &file,
+                &authors,
+                dry_run,
+                concurrency,
+                quiet,
+            )
+            .await?;
+            Ok(None)
+        }
+        Commands::ImportMedium {
+            archive,
+            authors,
+            dry_run,
+            concurrency,
+        } => {
+            import::run(
+                client,
+                Source::Medium,
+                &archive,
+                &authors,
+                dry_run,
+                concurrency,
+                quiet,
+            )
+            .await?;
+            Ok(None)
+        }
+        Commands::Sync { dir, watch } => {
+            sync::run(client, &dir, watch).await?;
+            Ok(None)
+        }
+        // Dispatched in main before a single client is created
+        Commands::Bench(_) => Ok(None),
+    }
+}
+
+/// Resolves the password for `register` / `login`, exiting if none can be
+/// read.
+fn read_password(password: Option<String>, from_stdin: bool, confirm: bool) -> String {
+    password::resolve(password, from_stdin, confirm).unwrap_or_else(|e| {
+        eprintln!("❌ Failed to read password: {}", e);
+        std::process::exit(1);
+    })
+}
+
+/// Prints a post as a line of a listing, or just its public ID with `quiet`.
+fn print_post_line(post: &PostDto, quiet: bool) {
+    if quiet {
+        println!("{}", post.public_id);
+    } else {
+        println!("  {post}");
     }
 }
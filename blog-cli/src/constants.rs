@@ -1,10 +1,73 @@
 //! CLI constants.
 
-/// Default HTTP server URL.
-pub const DEFAULT_HTTP_URL: &str = "http://localhost:8080";
+/// Default HTTP and gRPC server URLs, and the frontend URL `list
+/// --interactive` opens posts under.
+pub use blog_shared::constants::{DEFAULT_GRPC_URL, DEFAULT_HTTP_URL, DEFAULT_SITE_URL};
 
-/// Default gRPC server URL.
-pub const DEFAULT_GRPC_URL: &str = "http://localhost:50051";
+/// Environment variable overriding the server URL (same as `--server`).
+pub const ENV_SERVER: &str = "BLOG_SERVER";
+
+/// Environment variable holding a token to use instead of the token file.
+pub const ENV_TOKEN: &str = "BLOG_TOKEN";
+
+/// Environment variable holding the password for `register` and `login`.
+pub const ENV_PASSWORD: &str = "BLOG_PASSWORD";
 
 /// Token file name in user's home directory.
 pub const TOKEN_FILE: &str = ".blog_token";
+
+/// Default number of concurrent clients for `bench`.
+pub const DEFAULT_BENCH_CONCURRENCY: usize = 10;
+
+/// Default number of requests per client for `bench`.
+pub const DEFAULT_BENCH_REQUESTS: usize = 100;
+
+/// Default weighted operation mix for `bench`.
+pub const DEFAULT_BENCH_MIX: &str = "list=70,create=20,login=10";
+
+/// Password used for synthetic `bench` users.
+pub const BENCH_PASSWORD: &str = "bench-password";
+
+/// Page size used by `bench` list requests.
+pub const BENCH_LIST_LIMIT: i64 = 10;
+
+/// Unchanged lines shown around each change in `update --dry-run` diffs.
+pub const DIFF_CONTEXT_LINES: usize = 3;
+
+/// Terminal escape codes coloring diffs as git does: removed lines, added
+/// lines, hunk headers, file headers, and back to plain text.
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_CYAN: &str = "\x1b[36m";
+pub const ANSI_BOLD: &str = "\x1b[1m";
+pub const ANSI_RESET: &str = "\x1b[0m";
+
+/// File in a synced directory listing the IDs of the posts synced from it.
+pub const SYNC_STATE_FILE: &str = ".blog-sync.json";
+
+/// How often `sync --watch` checks the directory for changes, in milliseconds.
+pub const SYNC_POLL_INTERVAL_MS: u64 = 1000;
+
+/// Editor used by `list --interactive` when `$VISUAL` and `$EDITOR` are unset.
+pub const DEFAULT_EDITOR: &str = "vi";
+
+/// Default number of import batches sent at once.
+pub const DEFAULT_IMPORT_CONCURRENCY: usize = 4;
+
+/// Progress bar layout while import batches are sent.
+pub const IMPORT_PROGRESS_TEMPLATE: &str = "{bar:40} {pos}/{len} posts ({elapsed})";
+
+/// Exit code for errors without a code of their own.
+pub const EXIT_FAILURE: u8 = 1;
+
+/// Exit code when not logged in, or the token is rejected or lacks access.
+pub const EXIT_AUTH: u8 = 3;
+
+/// Exit code when the post (or other resource) does not exist.
+pub const EXIT_NOT_FOUND: u8 = 4;
+
+/// Exit code when the server rejects the input (HTTP 400, 409, 413, 422).
+pub const EXIT_VALIDATION: u8 = 5;
+
+/// Exit code when the server cannot be reached, times out, or is unavailable.
+pub const EXIT_NETWORK: u8 = 6;
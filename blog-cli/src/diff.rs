@@ -0,0 +1,87 @@
+//! Line diffs in the style of `git diff`.
+
+use std::fmt::Write;
+
+use blog_shared::{DiffLineDto, DiffOp, diff_lines};
+
+use crate::constants::{
+    ANSI_BOLD, ANSI_CYAN, ANSI_GREEN, ANSI_RED, ANSI_RESET, DIFF_CONTEXT_LINES,
+};
+
+/// Renders the changes from `old` to `new` as a unified diff of `name`,
+/// with `DIFF_CONTEXT_LINES` unchanged lines around each change.
+///
+/// Returns `None` if the texts are equal. With `color`, removed lines are
+/// red and added lines green, as git prints them on a terminal.
+pub fn unified(name: &str, old: &str, new: &str, color: bool) -> Option<String> {
+    if old == new {
+        return None;
+    }
+    Some(render(name, &diff_lines(old, new), color))
+}
+
+/// Renders diff `lines` as a unified diff of `name`, like [`unified`].
+pub fn render(name: &str, lines: &[DiffLineDto], color: bool) -> String {
+    let paint = |style: &str, text: &str| {
+        if color {
+            format!("{style}{text}{ANSI_RESET}")
+        } else {
+            text.to_string()
+        }
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(out, "{}", paint(ANSI_BOLD, &format!("--- a/{name}")));
+    let _ = writeln!(out, "{}", paint(ANSI_BOLD, &format!("+++ b/{name}")));
+
+    for (start, end) in hunks(lines) {
+        let hunk = &lines[start..end];
+        // Line numbers in the hunk header are 1-based
+        let old_start = lines[..start]
+            .iter()
+            .filter(|l| l.op != DiffOp::Added)
+            .count();
+        let new_start = lines[..start]
+            .iter()
+            .filter(|l| l.op != DiffOp::Removed)
+            .count();
+        let old_len = hunk.iter().filter(|l| l.op != DiffOp::Added).count();
+        let new_len = hunk.iter().filter(|l| l.op != DiffOp::Removed).count();
+        let header = format!(
+            "@@ -{},{} +{},{} @@",
+            old_start + 1,
+            old_len,
+            new_start + 1,
+            new_len
+        );
+        let _ = writeln!(out, "{}", paint(ANSI_CYAN, &header));
+
+        for line in hunk {
+            let text = &line.text;
+            let _ = match line.op {
+                DiffOp::Same => writeln!(out, " {text}"),
+                DiffOp::Removed => writeln!(out, "{}", paint(ANSI_RED, &format!("-{text}"))),
+                DiffOp::Added => writeln!(out, "{}", paint(ANSI_GREEN, &format!("+{text}"))),
+            };
+        }
+    }
+    out
+}
+
+/// Groups changed lines into hunks, returned as `start..end` ranges of
+/// `lines`. Changes closer than twice the context are merged.
+fn hunks(lines: &[DiffLineDto]) -> Vec<(usize, usize)> {
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if line.op == DiffOp::Same {
+            continue;
+        }
+        let start = i.saturating_sub(DIFF_CONTEXT_LINES);
+        let end = (i + 1 + DIFF_CONTEXT_LINES).min(lines.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+    hunks
+}
@@ -0,0 +1,56 @@
+//! The `history` command: a post's revisions and the changes between them.
+
+use std::env;
+use std::io::{self, IsTerminal};
+
+use blog_client::{BlogClient, ClientError};
+use blog_shared::DiffOp;
+
+use crate::diff;
+
+/// Lists the revisions of post `id`, or with `diff` shows the changes from
+/// the first revision number to the second as a unified diff.
+///
+/// With `quiet`, the list prints only revision numbers, one per line.
+pub async fn run(
+    client: &mut BlogClient,
+    id: String,
+    diff: Option<(i64, i64)>,
+    quiet: bool,
+) -> Result<(), ClientError> {
+    let Some((from, to)) = diff else {
+        let response = client.list_revisions(&id).await?;
+        if quiet {
+            for revision in &response.revisions {
+                println!("{}", revision.number);
+            }
+            return Ok(());
+        }
+        println!("🕘 Revisions of post {id} ({}):", response.revisions.len());
+        for revision in &response.revisions {
+            println!("  {revision}");
+        }
+        return Ok(());
+    };
+
+    let response = client.diff_revisions(&id, from, to).await?;
+    let color = io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+    let changed: Vec<_> = [("title", &response.title), ("content", &response.content)]
+        .into_iter()
+        .filter(|(_, lines)| lines.iter().any(|line| line.op != DiffOp::Same))
+        .collect();
+
+    if changed.is_empty() {
+        if !quiet {
+            println!("No changes between r{from} and r{to} of post {id}.");
+        }
+        return Ok(());
+    }
+    if !quiet {
+        println!("📝 Post {id}: r{from} → r{to}");
+    }
+    for (name, lines) in changed {
+        print!("{}", diff::render(name, lines, color));
+    }
+    Ok(())
+}
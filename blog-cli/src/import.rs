@@ -0,0 +1,238 @@
+//! Import of WordPress and Medium exports through the bulk import API.
+
+mod medium;
+mod wordpress;
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+
+use blog_client::{BlogClient, ClientError};
+use blog_shared::constants::{MAX_IMPORT_POSTS, MAX_TAG_LENGTH, MAX_TAGS_PER_POST};
+use blog_shared::{ImportPostDto, ImportPostsRequest, ImportPostsResponse};
+use chrono::{DateTime, Utc};
+use indicatif::{ProgressBar, ProgressStyle};
+use tokio::task::{self, JoinError, JoinSet};
+
+use crate::constants::IMPORT_PROGRESS_TEMPLATE;
+
+/// Export formats read by the `import-*` commands.
+#[derive(Debug, Clone, Copy)]
+pub enum Source {
+    /// WordPress eXtended RSS (WXR) file from Tools → Export.
+    WordPress,
+    /// Zip archive from Medium's "Download your information".
+    Medium,
+}
+
+/// A post as found in an export, before conversion.
+#[derive(Debug, Default)]
+struct SourcePost {
+    title: String,
+    html: String,
+    /// Author login or handle on the source blog.
+    author: Option<String>,
+    /// Tag and category names.
+    tags: Vec<String>,
+    published_at: Option<DateTime<Utc>>,
+}
+
+/// Parses an `--author FROM=TO` mapping.
+pub fn parse_author_mapping(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((from, to)) if !from.trim().is_empty() && !to.trim().is_empty() => {
+            Ok((from.trim().to_string(), to.trim().to_string()))
+        }
+        _ => Err(format!("expected FROM=TO, got '{s}'")),
+    }
+}
+
+/// Imports the posts of an export file, in batches of `MAX_IMPORT_POSTS`
+/// with up to `concurrency` batches in flight.
+///
+/// `authors` maps source authors to usernames on this blog; posts of
+/// unmapped authors are imported as the current user. A failed batch does
+/// not stop the others; failures are listed at the end and the process
+/// exits with status 1. With `quiet`, only the IDs of created posts are
+/// printed, and rejected posts are reported on stderr.
+pub async fn run(
+    client: &mut BlogClient,
+    source: Source,
+    path: &Path,
+    authors: &[(String, String)],
+    dry_run: bool,
+    concurrency: usize,
+    quiet: bool,
+) -> Result<(), ClientError> {
+    let posts = load(source, path, authors).unwrap_or_else(|e| {
+        eprintln!("❌ Failed to read {}: {}", path.display(), e);
+        std::process::exit(1);
+    });
+    if posts.is_empty() {
+        if !quiet {
+            println!("No published posts found in {}", path.display());
+        }
+        return Ok(());
+    }
+
+    let progress = if quiet {
+        ProgressBar::hidden()
+    } else {
+        ProgressBar::new(posts.len() as u64).with_style(
+            ProgressStyle::with_template(IMPORT_PROGRESS_TEMPLATE)
+                .unwrap_or_else(|_| ProgressStyle::default_bar()),
+        )
+    };
+    let mut tasks = JoinSet::new();
+    // Position in the export and size of the batch each task sends
+    let mut batches = HashMap::new();
+    let mut results = Vec::new();
+    for (index, batch) in posts.chunks(MAX_IMPORT_POSTS).enumerate() {
+        if tasks.len() >= concurrency.max(1)
+            && let Some(joined) = tasks.join_next_with_id().await
+        {
+            results.push(finish_batch(joined, &batches, &progress));
+        }
+        let mut client = client.clone();
+        let req = ImportPostsRequest {
+            posts: batch.to_vec(),
+            dry_run,
+        };
+        let task = tasks.spawn(async move { client.import_posts(req).await });
+        batches.insert(task.id(), (index, batch.len()));
+    }
+    while let Some(joined) = tasks.join_next_with_id().await {
+        results.push(finish_batch(joined, &batches, &progress));
+    }
+    progress.finish_and_clear();
+    results.sort_by_key(|batch| batch.index);
+
+    let (mut imported, mut failed) = (0, 0);
+    let mut errors = Vec::new();
+    for batch in results {
+        let response = match batch.response {
+            Ok(response) => response,
+            Err(e) => {
+                let first = batch.index * MAX_IMPORT_POSTS + 1;
+                let last = first + batch.len - 1;
+                errors.push(format!("posts {first}-{last}: {e}"));
+                failed += batch.len as i64;
+                continue;
+            }
+        };
+        for post in response.posts {
+            match (post.post_public_id, post.error) {
+                (_, Some(error)) if quiet => eprintln!("{}: {}", post.title, error),
+                (Some(id), None) if quiet => println!("{id}"),
+                _ if quiet => {}
+                (_, Some(error)) => println!("  ❌ {}: {}", post.title, error),
+                (Some(id), None) => {
+                    println!("  ✅ [{}] {} by {}", id, post.title, post.author_username)
+                }
+                (None, None) => println!("  ✔ {} by {}", post.title, post.author_username),
+            }
+        }
+        imported += response.imported;
+        failed += response.failed;
+    }
+
+    if !quiet && dry_run {
+        println!("🔍 Dry run: {imported} posts would be imported, {failed} would fail");
+    } else if !quiet {
+        println!("📥 Imported {imported} posts, {failed} failed");
+    }
+    if !errors.is_empty() {
+        eprintln!("❌ {} batches could not be sent:", errors.len());
+        for error in errors {
+            eprintln!("  {error}");
+        }
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Outcome of one batch sent to the import API.
+struct BatchResult {
+    /// Position of the batch in the export.
+    index: usize,
+    /// Number of posts in the batch.
+    len: usize,
+    response: Result<ImportPostsResponse, String>,
+}
+
+/// Records a finished batch task and advances the progress bar.
+fn finish_batch(
+    joined: Result<(task::Id, Result<ImportPostsResponse, ClientError>), JoinError>,
+    batches: &HashMap<task::Id, (usize, usize)>,
+    progress: &ProgressBar,
+) -> BatchResult {
+    let (id, response) = match joined {
+        Ok((id, response)) => (id, response.map_err(|e| e.to_string())),
+        Err(e) => (e.id(), Err(e.to_string())),
+    };
+    let (index, len) = batches.get(&id).copied().unwrap_or_default();
+    progress.inc(len as u64);
+    BatchResult {
+        index,
+        len,
+        response,
+    }
+}
+
+/// Reads an export and converts its posts for the import API.
+fn load(
+    source: Source,
+    path: &Path,
+    authors: &[(String, String)],
+) -> io::Result<Vec<ImportPostDto>> {
+    let posts = match source {
+        Source::WordPress => wordpress::parse(&fs::read_to_string(path)?)?,
+        Source::Medium => medium::parse(File::open(path)?)?,
+    };
+    posts
+        .into_iter()
+        .map(|post| convert(post, authors))
+        .collect()
+}
+
+/// Converts a source post, turning its HTML into Markdown.
+fn convert(post: SourcePost, authors: &[(String, String)]) -> io::Result<ImportPostDto> {
+    let author = post.author.and_then(|login| {
+        authors
+            .iter()
+            .find(|(from, _)| *from == login)
+            .map(|(_, to)| to.clone())
+    });
+
+    Ok(ImportPostDto {
+        title: post.title.trim().to_string(),
+        content: htmd::convert(&post.html)?,
+        tags: to_tags(post.tags),
+        author,
+        published_at: post.published_at,
+    })
+}
+
+/// Turns tag and category names into at most `MAX_TAGS_PER_POST` valid tags
+/// ("Web Development" becomes "web-development").
+fn to_tags(names: Vec<String>) -> Vec<String> {
+    let mut tags = Vec::new();
+    for name in names {
+        let tag: String = name
+            .trim()
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_whitespace() { '-' } else { c })
+            .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+            .take(MAX_TAG_LENGTH)
+            .collect();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+        if tags.len() == MAX_TAGS_PER_POST {
+            break;
+        }
+    }
+    tags
+}
@@ -0,0 +1,89 @@
+//! Medium archive parsing.
+//!
+//! Medium exports each story as `posts/<date>_<slug>.html` (drafts as
+//! `posts/draft_<slug>.html`) with microformat classes marking its parts.
+
+use std::io::{self, Read, Seek};
+
+use chrono::{DateTime, Utc};
+use quick_xml::escape::unescape;
+use zip::ZipArchive;
+
+use super::SourcePost;
+
+/// Parses the published stories of a Medium archive.
+pub(super) fn parse(archive: impl Read + Seek) -> io::Result<Vec<SourcePost>> {
+    let mut archive = ZipArchive::new(archive)?;
+    let mut posts = Vec::new();
+
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        let name = file.name().to_string();
+        let Some(file_name) = name.strip_prefix("posts/") else {
+            continue;
+        };
+        if !file_name.ends_with(".html") || file_name.starts_with("draft_") {
+            continue;
+        }
+
+        let mut html = String::new();
+        file.read_to_string(&mut html)?;
+        posts.push(parse_story(&html));
+    }
+
+    Ok(posts)
+}
+
+/// Extracts a story's title, author handle, date, and body.
+fn parse_story(html: &str) -> SourcePost {
+    let title = between(html, "<title>", "</title>").unwrap_or_default();
+    let published_at = between(html, "class=\"dt-published\" datetime=\"", "\"")
+        .and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+        .map(|date| date.with_timezone(&Utc));
+    let body = html
+        .split_once("data-field=\"body\"")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .map(|(_, rest)| rest.split("<footer>").next().unwrap_or(rest))
+        .unwrap_or_default();
+
+    SourcePost {
+        title: unescape(title).map(Into::into).unwrap_or(title.to_string()),
+        html: without_title_heading(body),
+        author: author_handle(html),
+        tags: Vec::new(),
+        published_at,
+    }
+}
+
+/// Returns the `@handle` of the author link
+/// (`<a href="https://medium.com/@handle" class="p-author h-card">`).
+fn author_handle(html: &str) -> Option<String> {
+    let (before, _) = html.split_once("class=\"p-author h-card\"")?;
+    let (_, href) = before.rsplit_once("href=\"")?;
+    let href = href.split('"').next()?;
+    let (_, handle) = href.rsplit_once("/@")?;
+    Some(handle.to_string())
+}
+
+/// Removes the heading repeating the title at the top of the body.
+fn without_title_heading(body: &str) -> String {
+    let Some(marker) = body.find("graf--title") else {
+        return body.to_string();
+    };
+    let start = body[..marker].rfind('<').unwrap_or(marker);
+    match body[marker..].find("</h") {
+        Some(end) => {
+            // Skip past the closing tag, e.g. `</h3>`
+            let end = marker + end;
+            let close = body[end..].find('>').map_or(end, |i| end + i + 1);
+            format!("{}{}", &body[..start], &body[close..])
+        }
+        None => body.to_string(),
+    }
+}
+
+/// Returns the text between the first `start` and the following `end`.
+fn between<'a>(html: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let (_, rest) = html.split_once(start)?;
+    rest.split_once(end).map(|(inner, _)| inner)
+}
@@ -0,0 +1,134 @@
+//! WordPress eXtended RSS (WXR) export parsing.
+
+use std::io;
+use std::mem;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use quick_xml::Reader;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesRef, Event};
+
+use super::SourcePost;
+
+/// Format of `wp:post_date_gmt`.
+const WP_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Fields of an `<item>` the import uses.
+#[derive(Debug, Default)]
+struct Item {
+    post: SourcePost,
+    status: String,
+    post_type: String,
+    pub_date: String,
+}
+
+impl Item {
+    /// Stores the text of a finished child element.
+    fn set(&mut self, element: &str, text: String) {
+        match element {
+            "title" => self.post.title = text,
+            "content:encoded" => self.post.html = autop(&text),
+            "dc:creator" => self.post.author = Some(text).filter(|s| !s.is_empty()),
+            "category" => self.post.tags.push(text),
+            "pubDate" => self.pub_date = text,
+            "wp:post_date_gmt" => {
+                self.post.published_at = NaiveDateTime::parse_from_str(&text, WP_DATE_FORMAT)
+                    .ok()
+                    .map(|date| date.and_utc());
+            }
+            "wp:status" => self.status = text,
+            "wp:post_type" => self.post_type = text,
+            _ => {}
+        }
+    }
+
+    /// Returns the post if the item is a published blog post (not a page,
+    /// attachment, or draft).
+    fn into_published_post(mut self) -> Option<SourcePost> {
+        if self.post_type != "post" || self.status != "publish" {
+            return None;
+        }
+        // Older exports may leave the GMT date unset
+        if self.post.published_at.is_none() {
+            self.post.published_at = DateTime::parse_from_rfc2822(&self.pub_date)
+                .ok()
+                .map(|date| date.with_timezone(&Utc));
+        }
+        Some(self.post)
+    }
+}
+
+/// Parses the published posts of a WXR file.
+///
+/// Tags and categories both become tags, except WordPress's default
+/// "Uncategorized" category.
+pub(super) fn parse(xml: &str) -> io::Result<Vec<SourcePost>> {
+    let mut reader = Reader::from_str(xml);
+    let mut posts = Vec::new();
+    let mut item: Option<Item> = None;
+    let mut text = String::new();
+    let mut skip_category = false;
+
+    loop {
+        match reader.read_event().map_err(io::Error::other)? {
+            Event::Start(start) => {
+                let name = start.name();
+                if name.as_ref() == b"item" {
+                    item = Some(Item::default());
+                } else if name.as_ref() == b"category" {
+                    let nicename = start
+                        .try_get_attribute("nicename")
+                        .map_err(io::Error::other)?;
+                    skip_category =
+                        nicename.is_some_and(|attr| attr.value.as_ref() == b"uncategorized");
+                }
+                text.clear();
+            }
+            Event::Text(chunk) => text.push_str(&chunk.decode().map_err(io::Error::other)?),
+            Event::CData(chunk) => text.push_str(&chunk.decode().map_err(io::Error::other)?),
+            Event::GeneralRef(reference) => push_reference(&mut text, &reference)?,
+            Event::End(end) => {
+                let name = String::from_utf8_lossy(end.name().as_ref()).into_owned();
+                if name == "item" {
+                    posts.extend(item.take().and_then(Item::into_published_post));
+                } else if let Some(item) = item.as_mut()
+                    && !(name == "category" && skip_category)
+                {
+                    item.set(&name, mem::take(&mut text).trim().to_string());
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(posts)
+}
+
+/// Appends the text of an entity or character reference.
+fn push_reference(text: &mut String, reference: &BytesRef) -> io::Result<()> {
+    if let Some(c) = reference.resolve_char_ref().map_err(io::Error::other)? {
+        text.push(c);
+        return Ok(());
+    }
+    let name = reference.decode().map_err(io::Error::other)?;
+    match resolve_predefined_entity(&name) {
+        Some(value) => text.push_str(value),
+        None => text.push_str(&format!("&{name};")),
+    }
+    Ok(())
+}
+
+/// Wraps blank-line separated blocks in paragraphs, as WordPress does when
+/// rendering post content stored without `<p>` tags.
+fn autop(html: &str) -> String {
+    if html.contains("<p") {
+        return html.to_string();
+    }
+    html.split("\n\n")
+        .map(str::trim)
+        .filter(|block| !block.is_empty())
+        .map(|block| format!("<p>{}</p>", block.replace('\n', "<br>")))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -1,16 +1,31 @@
 //! Blog CLI entry point.
 
+mod bench;
+mod browse;
 mod commands;
 mod constants;
+mod diff;
+mod history;
+mod import;
+mod password;
+mod sync;
+mod trash;
+mod update;
 
+use std::env;
 use std::fs;
 use std::path::PathBuf;
+use std::process::ExitCode;
 
-use blog_client::{BlogClient, ClientError};
+use blog_client::{BlogClient, ClientError, WireFormat};
 use clap::{Parser, Subcommand};
 use tracing_subscriber::EnvFilter;
 
-use constants::{DEFAULT_GRPC_URL, DEFAULT_HTTP_URL, TOKEN_FILE};
+use constants::{
+    DEFAULT_GRPC_URL, DEFAULT_HTTP_URL, DEFAULT_IMPORT_CONCURRENCY, DEFAULT_SITE_URL, ENV_PASSWORD,
+    ENV_SERVER, ENV_TOKEN, EXIT_AUTH, EXIT_FAILURE, EXIT_NETWORK, EXIT_NOT_FOUND, EXIT_VALIDATION,
+    TOKEN_FILE,
+};
 
 /// Blog platform CLI client.
 #[derive(Parser)]
@@ -21,10 +36,19 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub grpc: bool,
 
+    /// Send and receive MessagePack instead of JSON over HTTP (smaller for
+    /// bulk imports and listings).
+    #[arg(long, global = true, conflicts_with = "grpc")]
+    pub msgpack: bool,
+
     /// Server URL (HTTP: http://localhost:8080, gRPC: http://localhost:50051).
-    #[arg(long, global = true)]
+    #[arg(long, global = true, env = ENV_SERVER)]
     pub server: Option<String>,
 
+    /// Print only IDs (or a post's content for `get`), for scripts.
+    #[arg(long, short, global = true)]
+    pub quiet: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -39,18 +63,27 @@ pub enum Commands {
         /// Email address.
         #[arg(long)]
         email: String,
-        /// Password.
+        /// Password; prompted for when not given.
+        #[arg(long, env = ENV_PASSWORD, hide_env_values = true)]
+        password: Option<String>,
+        /// Read the password from the first line of stdin.
+        #[arg(long, conflicts_with = "password")]
+        password_stdin: bool,
+        /// Invite code, needed when the server only registers invited users.
         #[arg(long)]
-        password: String,
+        invite: Option<String>,
     },
     /// Login to an existing account.
     Login {
-        /// Username.
+        /// Username or email address.
         #[arg(long)]
         username: String,
-        /// Password.
-        #[arg(long)]
-        password: String,
+        /// Password; prompted for when not given.
+        #[arg(long, env = ENV_PASSWORD, hide_env_values = true)]
+        password: Option<String>,
+        /// Read the password from the first line of stdin.
+        #[arg(long, conflicts_with = "password")]
+        password_stdin: bool,
     },
     /// Create a new post.
     Create {
@@ -60,12 +93,15 @@ pub enum Commands {
         /// Post content.
         #[arg(long)]
         content: String,
+        /// Publish as this organization (by slug); you must be a member.
+        #[arg(long)]
+        org: Option<String>,
     },
-    /// Get a post by ID.
+    /// Get a post by its public ID.
     Get {
-        /// Post ID.
+        /// Public ID of the post, as `list` shows it.
         #[arg(long)]
-        id: i64,
+        id: String,
     },
     /// List all posts.
     List {
@@ -75,43 +111,148 @@ pub enum Commands {
         /// Number of posts to skip.
         #[arg(long, default_value = "0")]
         offset: i64,
+        /// List every post, fetching `--limit` posts per request (ignores `--offset`).
+        #[arg(long, conflicts_with = "interactive")]
+        all: bool,
+        /// Browse posts in a terminal UI, `--limit` per page, with a preview
+        /// and keys to open, edit, and delete them.
+        #[arg(long)]
+        interactive: bool,
+        /// Frontend URL that `--interactive` opens posts under.
+        #[arg(long, default_value = DEFAULT_SITE_URL)]
+        site_url: String,
     },
     /// Update a post.
     Update {
-        /// Post ID.
+        /// Public ID of the post, as `list` shows it.
         #[arg(long)]
-        id: i64,
+        id: String,
         /// New title (optional).
         #[arg(long)]
         title: Option<String>,
         /// New content (optional).
         #[arg(long)]
         content: Option<String>,
+        /// Show the changes as a diff against the current post and ask
+        /// before applying them.
+        #[arg(long)]
+        dry_run: bool,
     },
-    /// Delete a post.
+    /// Delete a post, moving it to your trash.
     Delete {
-        /// Post ID.
+        /// Public ID of the post, as `list` shows it.
+        #[arg(long)]
+        id: String,
+    },
+    /// List or restore your deleted posts.
+    Trash {
+        #[command(subcommand)]
+        action: TrashAction,
+    },
+    /// List the revisions of a post (author or editors).
+    History {
+        /// Public ID of the post, as `list` shows it.
+        #[arg(long)]
+        id: String,
+        /// Show the changes between two revisions instead, e.g. `--diff 1 3`.
+        #[arg(long, num_args = 2, value_names = ["FROM", "TO"])]
+        diff: Option<Vec<i64>>,
+    },
+    /// List dead links in your posts, as found by the server's link checker.
+    CheckLinks,
+    /// Import published posts from a WordPress export (WXR) file.
+    ImportWordpress {
+        /// Path to the export file.
+        file: PathBuf,
+        /// Import posts of a WordPress user as a user of this blog (admins
+        /// only), e.g. "jdoe=john"; repeatable. Others are imported as you.
+        #[arg(long = "author", value_name = "FROM=TO", value_parser = import::parse_author_mapping)]
+        authors: Vec<(String, String)>,
+        /// Check the posts without creating them.
+        #[arg(long)]
+        dry_run: bool,
+        /// Number of batches sent at once.
+        #[arg(long, default_value_t = DEFAULT_IMPORT_CONCURRENCY)]
+        concurrency: usize,
+    },
+    /// Import published stories from a Medium archive.
+    ImportMedium {
+        /// Path to the archive zip.
+        archive: PathBuf,
+        /// Import stories of a Medium @handle as a user of this blog (admins
+        /// only), e.g. "jdoe=john"; repeatable. Others are imported as you.
+        #[arg(long = "author", value_name = "FROM=TO", value_parser = import::parse_author_mapping)]
+        authors: Vec<(String, String)>,
+        /// Check the posts without creating them.
+        #[arg(long)]
+        dry_run: bool,
+        /// Number of batches sent at once.
+        #[arg(long, default_value_t = DEFAULT_IMPORT_CONCURRENCY)]
+        concurrency: usize,
+    },
+    /// Sync a directory of Markdown files to posts: create, update, and
+    /// delete posts to match the files.
+    Sync {
+        /// Directory of `.md` files, searched recursively.
+        dir: PathBuf,
+        /// Keep running and sync files as they change.
+        #[arg(long)]
+        watch: bool,
+    },
+    /// Run a load test with concurrent synthetic clients.
+    Bench(bench::BenchArgs),
+}
+
+/// What to do with the trash.
+#[derive(Subcommand, Debug)]
+pub enum TrashAction {
+    /// List your deleted posts and when each is purged.
+    List,
+    /// Take a post out of the trash.
+    Restore {
+        /// Public ID of the post, as `list` shows it.
         #[arg(long)]
-        id: i64,
+        id: String,
     },
 }
 
 #[tokio::main]
-async fn main() -> Result<(), ClientError> {
+async fn main() -> ExitCode {
     tracing_subscriber::fmt()
         .with_env_filter(EnvFilter::from_default_env())
         .init();
 
     let cli = Cli::parse();
+    let quiet = cli.quiet;
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            if quiet {
+                eprintln!("error: {e}");
+            } else {
+                eprintln!("❌ {e}");
+            }
+            ExitCode::from(exit_code(&e))
+        }
+    }
+}
+
+/// Runs the parsed command.
+async fn run(cli: Cli) -> Result<(), ClientError> {
+    // Bench manages its own pool of clients
+    if let Commands::Bench(ref args) = cli.command {
+        return bench::run(server_url(&cli).to_string(), cli.grpc, args).await;
+    }
+
     let mut client = create_client(&cli).await?;
 
-    // Load saved token
+    // Load the token from BLOG_TOKEN or the token file
     if let Some(token) = load_token() {
         client.set_token(token);
     }
 
     // Execute command and save token if returned
-    if let Some(token) = commands::execute(&mut client, cli.command).await?
+    if let Some(token) = commands::execute(&mut client, cli.command, cli.quiet).await?
         && let Err(e) = save_token(&token)
     {
         eprintln!("Warning: Failed to save token: {}", e);
@@ -120,15 +261,38 @@ async fn main() -> Result<(), ClientError> {
     Ok(())
 }
 
+/// Maps an error to the exit code documented for its kind.
+fn exit_code(error: &ClientError) -> u8 {
+    match (error, error.status_code()) {
+        (ClientError::NotAuthenticated, _) | (_, Some(401 | 403)) => EXIT_AUTH,
+        (_, Some(404)) => EXIT_NOT_FOUND,
+        (_, Some(400 | 409 | 413 | 422)) => EXIT_VALIDATION,
+        (ClientError::Http(e), _) if e.is_connect() || e.is_timeout() => EXIT_NETWORK,
+        (_, Some(502..=504)) => EXIT_NETWORK,
+        _ => EXIT_FAILURE,
+    }
+}
+
+/// Returns the server URL from CLI flags, defaulting per transport.
+fn server_url(cli: &Cli) -> &str {
+    let default = if cli.grpc {
+        DEFAULT_GRPC_URL
+    } else {
+        DEFAULT_HTTP_URL
+    };
+    cli.server.as_deref().unwrap_or(default)
+}
+
 /// Creates a client based on CLI flags.
 async fn create_client(cli: &Cli) -> Result<BlogClient, ClientError> {
     if cli.grpc {
-        let url = cli.server.as_deref().unwrap_or(DEFAULT_GRPC_URL);
-        BlogClient::grpc(url).await
-    } else {
-        let url = cli.server.as_deref().unwrap_or(DEFAULT_HTTP_URL);
-        Ok(BlogClient::http(url))
+        return BlogClient::grpc(server_url(cli)).await;
+    }
+    let mut client = BlogClient::http(server_url(cli));
+    if cli.msgpack {
+        client.set_wire_format(WireFormat::MessagePack);
     }
+    Ok(client)
 }
 
 /// Returns the token file path.
@@ -136,8 +300,14 @@ fn token_path() -> Option<PathBuf> {
     dirs::home_dir().map(|h| h.join(TOKEN_FILE))
 }
 
-/// Loads token from file if it exists.
+/// Loads the token from `BLOG_TOKEN`, or else from the token file if it
+/// exists.
 fn load_token() -> Option<String> {
+    if let Ok(token) = env::var(ENV_TOKEN)
+        && !token.trim().is_empty()
+    {
+        return Some(token.trim().to_string());
+    }
     let path = token_path()?;
     fs::read_to_string(path)
         .ok()
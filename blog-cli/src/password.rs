@@ -0,0 +1,28 @@
+//! Password input for `register` and `login`, kept off the command line.
+
+use std::io::{self, BufRead, IsTerminal};
+
+/// Where `register` and `login` take the password from, in order: the
+/// `--password` flag or `BLOG_PASSWORD`, the first line of stdin with
+/// `--password-stdin`, or else a prompt that does not echo.
+pub fn resolve(password: Option<String>, from_stdin: bool, confirm: bool) -> io::Result<String> {
+    if let Some(password) = password {
+        return Ok(password);
+    }
+    if from_stdin {
+        let mut line = String::new();
+        io::stdin().lock().read_line(&mut line)?;
+        return Ok(line.trim_end_matches(['\r', '\n']).to_string());
+    }
+    if !io::stdin().is_terminal() {
+        return Err(io::Error::other(
+            "no password given; use --password-stdin or BLOG_PASSWORD",
+        ));
+    }
+
+    let password = rpassword::prompt_password("Password: ")?;
+    if confirm && rpassword::prompt_password("Confirm password: ")? != password {
+        return Err(io::Error::other("passwords do not match"));
+    }
+    Ok(password)
+}
@@ -0,0 +1,279 @@
+//! Sync of a directory of Markdown files to posts.
+//!
+//! Each `.md` file is one post, described by its front matter (see
+//! [`front_matter`]). A file without an `id` is created as a new post and
+//! the new public ID is written into it; a file with one updates that post
+//! when it differs. The IDs synced from a directory are kept in
+//! `SYNC_STATE_FILE`, so a post whose file was removed is deleted on the
+//! next sync.
+//!
+//! Files synced before public IDs name their post by its numeric ID. Such
+//! a post is updated without comparing, since only writes accept numeric
+//! IDs, and the public ID in the reply replaces the numeric one.
+
+pub mod front_matter;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use blog_client::{BlogClient, ClientError};
+use blog_shared::{CreatePostRequest, UpdatePostRequest};
+use serde_json::Value;
+
+use crate::constants::{SYNC_POLL_INTERVAL_MS, SYNC_STATE_FILE};
+use front_matter::Document;
+
+/// Modification times of the Markdown files in a directory.
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// What syncing one file did.
+enum Outcome {
+    Created(String),
+    /// Updated, and the file's numeric ID replaced by this public ID.
+    Migrated {
+        from: String,
+        to: String,
+    },
+    Updated,
+    Unchanged,
+}
+
+/// Counts of a sync pass, for its summary line.
+#[derive(Default)]
+struct Summary {
+    created: usize,
+    updated: usize,
+    deleted: usize,
+    failed: usize,
+}
+
+/// Syncs `dir` once, or with `watch` keeps syncing the files that change
+/// until interrupted.
+pub async fn run(client: &mut BlogClient, dir: &Path, watch: bool) -> Result<(), ClientError> {
+    let mut synced = load_state(dir).unwrap_or_else(|e| exit_with(dir, e));
+    sync_files(client, dir, &mut synced, None)
+        .await
+        .unwrap_or_else(|e| exit_with(dir, e));
+    if !watch {
+        return Ok(());
+    }
+
+    println!("👀 Watching {} (Ctrl-C to stop)", dir.display());
+    let mut seen = snapshot(dir).unwrap_or_else(|e| exit_with(dir, e));
+    loop {
+        tokio::time::sleep(Duration::from_millis(SYNC_POLL_INTERVAL_MS)).await;
+        let current = snapshot(dir).unwrap_or_else(|e| exit_with(dir, e));
+        if current == seen {
+            continue;
+        }
+        let changed: BTreeSet<PathBuf> = current
+            .iter()
+            .filter(|(path, modified)| seen.get(*path) != Some(modified))
+            .map(|(path, _)| path.clone())
+            .collect();
+        sync_files(client, dir, &mut synced, Some(&changed))
+            .await
+            .unwrap_or_else(|e| exit_with(dir, e));
+        // Taken after the pass, so IDs written into new files are not
+        // seen as edits
+        seen = snapshot(dir).unwrap_or_else(|e| exit_with(dir, e));
+    }
+}
+
+/// Syncs the files of `dir` (only those in `changed`, if given) and deletes
+/// the posts whose file is gone, then saves the synced IDs.
+///
+/// Deletions are skipped if any file could not be read, since its ID is
+/// unknown.
+async fn sync_files(
+    client: &mut BlogClient,
+    dir: &Path,
+    synced: &mut BTreeSet<String>,
+    changed: Option<&BTreeSet<PathBuf>>,
+) -> io::Result<()> {
+    let mut summary = Summary::default();
+    let mut ids = BTreeSet::new();
+    let mut complete = true;
+
+    for path in snapshot(dir)?.into_keys() {
+        let name = path
+            .strip_prefix(dir)
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+        let (doc, text) = match read(&path) {
+            Ok(file) => file,
+            Err(e) => {
+                println!("  ❌ {name}: {e}");
+                summary.failed += 1;
+                complete = false;
+                continue;
+            }
+        };
+        if let Some(id) = &doc.id {
+            ids.insert(id.clone());
+        }
+        if changed.is_some_and(|changed| !changed.contains(&path)) {
+            continue;
+        }
+
+        match sync_file(client, &path, doc, &text).await {
+            Ok(Outcome::Created(id)) => {
+                println!("  ✅ {name}: created post {id}");
+                ids.insert(id);
+                summary.created += 1;
+            }
+            Ok(Outcome::Migrated { from, to }) => {
+                println!("  ✏️  {name}: updated, now post {to}");
+                ids.remove(&from);
+                ids.insert(to);
+                summary.updated += 1;
+            }
+            Ok(Outcome::Updated) => {
+                println!("  ✏️  {name}: updated");
+                summary.updated += 1;
+            }
+            Ok(Outcome::Unchanged) => {}
+            Err(e) => {
+                println!("  ❌ {name}: {e}");
+                summary.failed += 1;
+            }
+        }
+    }
+
+    let gone: Vec<String> = synced.difference(&ids).cloned().collect();
+    if complete {
+        for id in gone {
+            match client.delete_post(&id).await {
+                Ok(()) => {
+                    println!("  🗑  deleted post {id}");
+                    summary.deleted += 1;
+                }
+                Err(e) if e.status_code() == Some(404) => {}
+                Err(e) => {
+                    println!("  ❌ post {id}: {e}");
+                    summary.failed += 1;
+                    ids.insert(id);
+                }
+            }
+        }
+    } else if !gone.is_empty() {
+        println!("  ⚠️  Not deleting posts until every file can be read");
+        ids.extend(gone);
+    }
+
+    *synced = ids;
+    save_state(dir, synced)?;
+    println!(
+        "🔄 {} created, {} updated, {} deleted, {} failed",
+        summary.created, summary.updated, summary.deleted, summary.failed
+    );
+    Ok(())
+}
+
+/// Reads a file and parses its front matter.
+fn read(path: &Path) -> Result<(Document, String), String> {
+    let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    Ok((front_matter::parse(&text)?, text))
+}
+
+/// Creates or updates the post of one file.
+async fn sync_file(
+    client: &mut BlogClient,
+    path: &Path,
+    doc: Document,
+    text: &str,
+) -> Result<Outcome, Box<dyn Error>> {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let title = doc.title(&stem);
+
+    let Some(id) = doc.id else {
+        let req = CreatePostRequest::new(title, doc.body).with_tags(doc.tags);
+        let post = client.create_post(req).await?;
+        fs::write(path, front_matter::set_id(text, &post.public_id))?;
+        return Ok(Outcome::Created(post.public_id));
+    };
+
+    let numeric = id.bytes().all(|byte| byte.is_ascii_digit());
+    if !numeric {
+        let post = client.get_post(&id).await?;
+        if post.title == title && post.content == doc.body && same_tags(&post.tags, &doc.tags) {
+            return Ok(Outcome::Unchanged);
+        }
+    }
+    let req = UpdatePostRequest::default()
+        .with_title(title)
+        .with_content(doc.body)
+        .with_tags(doc.tags);
+    let post = client.update_post(&id, req).await?;
+    if numeric {
+        fs::write(path, front_matter::set_id(text, &post.public_id))?;
+        return Ok(Outcome::Migrated {
+            from: id,
+            to: post.public_id,
+        });
+    }
+    Ok(Outcome::Updated)
+}
+
+/// Compares tags the way the server normalizes them.
+fn same_tags(saved: &[String], local: &[String]) -> bool {
+    let normalize = |tags: &[String]| -> BTreeSet<String> {
+        tags.iter().map(|tag| tag.trim().to_lowercase()).collect()
+    };
+    normalize(saved) == normalize(local)
+}
+
+/// Lists the Markdown files under `dir`, skipping hidden entries.
+fn snapshot(dir: &Path) -> io::Result<Snapshot> {
+    let mut files = Snapshot::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for entry in fs::read_dir(&current)? {
+            let entry = entry?;
+            let path = entry.path();
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let metadata = entry.metadata()?;
+            if metadata.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "md") {
+                files.insert(path, metadata.modified()?);
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Reads the IDs synced from `dir`; none if it was never synced. State
+/// saved before public IDs lists numbers, read as their digits.
+fn load_state(dir: &Path) -> io::Result<BTreeSet<String>> {
+    match fs::read_to_string(dir.join(SYNC_STATE_FILE)) {
+        Ok(text) => {
+            let ids: Vec<Value> = serde_json::from_str(&text)?;
+            Ok(ids
+                .into_iter()
+                .map(|id| match id {
+                    Value::String(id) => id,
+                    id => id.to_string(),
+                })
+                .collect())
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(BTreeSet::new()),
+        Err(e) => Err(e),
+    }
+}
+
+fn save_state(dir: &Path, ids: &BTreeSet<String>) -> io::Result<()> {
+    fs::write(dir.join(SYNC_STATE_FILE), serde_json::to_string(ids)?)
+}
+
+fn exit_with(dir: &Path, e: io::Error) -> ! {
+    eprintln!("❌ Failed to sync {}: {}", dir.display(), e);
+    std::process::exit(1);
+}
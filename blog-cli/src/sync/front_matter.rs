@@ -0,0 +1,161 @@
+//! Front matter of the Markdown files kept in sync by `sync`.
+//!
+//! A file may start with a block of `key: value` lines between `---`
+//! fences:
+//!
+//! ```text
+//! ---
+//! id: 0b8e4a52-2f0d-4c4e-9d8e-2a4c1f6b7e90
+//! title: Hello world
+//! slug: hello-world
+//! tags: [rust, web]
+//! ---
+//! Post content in Markdown.
+//! ```
+//!
+//! Unknown keys are ignored.
+
+const FENCE: &str = "---";
+
+/// A Markdown file split into its front matter and body.
+#[derive(Debug, Default, PartialEq)]
+pub struct Document {
+    /// Public ID of the post the file is synced to (numeric in files synced
+    /// before public IDs); unset until it is created.
+    pub id: Option<String>,
+    pub title: Option<String>,
+    /// Short name of the post; the title defaults to it.
+    pub slug: Option<String>,
+    pub tags: Vec<String>,
+    pub body: String,
+}
+
+impl Document {
+    /// Returns the post title: the `title` key, or else the slug (or the
+    /// file stem `fallback`) with dashes as spaces ("hello-world" becomes
+    /// "Hello world").
+    pub fn title(&self, fallback: &str) -> String {
+        if let Some(title) = &self.title {
+            return title.clone();
+        }
+        let words = self
+            .slug
+            .as_deref()
+            .unwrap_or(fallback)
+            .replace(['-', '_'], " ");
+        let mut chars = words.trim().chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => fallback.to_string(),
+        }
+    }
+}
+
+/// Parses a file's front matter and body.
+pub fn parse(text: &str) -> Result<Document, String> {
+    let Some((header, body)) = split(text) else {
+        return Ok(Document {
+            body: text.to_string(),
+            ..Document::default()
+        });
+    };
+
+    let mut doc = Document {
+        body: body.to_string(),
+        ..Document::default()
+    };
+    for (number, line) in header.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            return Err(format!(
+                "front matter line {}: expected 'key: value'",
+                number + 2
+            ));
+        };
+        let value = unquote(value.trim());
+        match key.trim() {
+            "id" if !value.is_empty() => doc.id = Some(value.to_string()),
+            "title" if !value.is_empty() => doc.title = Some(value.to_string()),
+            "slug" if !value.is_empty() => doc.slug = Some(value.to_string()),
+            "tags" => doc.tags = parse_list(value),
+            _ => {}
+        }
+    }
+    Ok(doc)
+}
+
+/// Returns `text` with `id` recorded in its front matter, adding a front
+/// matter block if there is none.
+pub fn set_id(text: &str, id: &str) -> String {
+    let Some((header, body)) = split(text) else {
+        return format!("{FENCE}\nid: {id}\n{FENCE}\n{text}");
+    };
+    let mut lines: Vec<String> = header
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("id:"))
+        .map(str::to_string)
+        .collect();
+    lines.insert(0, format!("id: {id}"));
+    format!("{FENCE}\n{}\n{FENCE}\n{body}", lines.join("\n"))
+}
+
+/// Renders a post as a file with `title` and `tags` front matter.
+pub fn render(title: &str, tags: &[String], body: &str) -> String {
+    format!(
+        "{FENCE}\ntitle: {title}\ntags: [{}]\n{FENCE}\n{body}",
+        tags.join(", ")
+    )
+}
+
+/// Splits a file into its front matter lines and body, if it has front
+/// matter.
+fn split(text: &str) -> Option<(&str, &str)> {
+    let rest = text
+        .strip_prefix(FENCE)?
+        .strip_prefix('\n')
+        .or_else(|| text.strip_prefix("---\r\n"))?;
+    if let Some(body) = rest.strip_prefix(FENCE) {
+        return Some(("", skip_line_end(body)));
+    }
+    let end = rest.find("\n---")?;
+    let body = &rest[end + 1 + FENCE.len()..];
+    if !(body.is_empty() || body.starts_with('\n') || body.starts_with("\r\n")) {
+        return None;
+    }
+    Some((&rest[..end], skip_line_end(body)))
+}
+
+fn skip_line_end(text: &str) -> &str {
+    text.strip_prefix("\r\n")
+        .or_else(|| text.strip_prefix('\n'))
+        .unwrap_or(text)
+}
+
+/// Strips matching single or double quotes around a value.
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|v| v.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+/// Parses `[a, b]` or `a, b` into its non-empty items.
+fn parse_list(value: &str) -> Vec<String> {
+    let value = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .unwrap_or(value);
+    value
+        .split(',')
+        .map(|item| unquote(item.trim()).to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
@@ -0,0 +1,48 @@
+//! The `trash` command: your deleted posts, and taking them back out.
+
+use blog_client::{BlogClient, ClientError};
+
+use crate::TrashAction;
+
+/// Lists the current user's deleted posts, or restores one.
+///
+/// With `quiet`, the list prints only public post IDs, one per line.
+pub async fn run(
+    client: &mut BlogClient,
+    action: TrashAction,
+    quiet: bool,
+) -> Result<(), ClientError> {
+    match action {
+        TrashAction::List => {
+            let response = client.list_trash().await?;
+            if quiet {
+                for post in &response.posts {
+                    println!("{}", post.public_id);
+                }
+                return Ok(());
+            }
+            if response.posts.is_empty() {
+                println!("🗑️  Your trash is empty.");
+                return Ok(());
+            }
+            println!("🗑️  Trash ({}):", response.posts.len());
+            for post in response.posts {
+                let purge = match post.days_until_purge {
+                    Some(0) => "purged at the next cleanup".to_string(),
+                    Some(1) => "purged in 1 day".to_string(),
+                    Some(days) => format!("purged in {days} days"),
+                    None => "kept until restored".to_string(),
+                };
+                println!("  {post}");
+                println!("      deleted {}, {purge}", post.deleted_at);
+            }
+        }
+        TrashAction::Restore { id } => {
+            client.restore_post(&id).await?;
+            if !quiet {
+                println!("✅ Post {id} restored!");
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,88 @@
+//! The `update` command, with an optional preview of the changes.
+
+use std::env;
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use blog_client::{BlogClient, ClientError};
+use blog_shared::UpdatePostRequest;
+
+use crate::diff;
+
+/// Updates a post, printing only its public ID with `quiet`.
+///
+/// With `dry_run`, the current post is fetched and the changes are shown as
+/// a diff first. They are applied only if the user confirms on a terminal;
+/// otherwise nothing is changed.
+pub async fn run(
+    client: &mut BlogClient,
+    id: String,
+    title: Option<String>,
+    content: Option<String>,
+    dry_run: bool,
+    quiet: bool,
+) -> Result<(), ClientError> {
+    if dry_run && !preview(client, &id, title.as_deref(), content.as_deref()).await? {
+        return Ok(());
+    }
+
+    let req = UpdatePostRequest {
+        title,
+        content,
+        ..UpdatePostRequest::default()
+    };
+    let post = client.update_post(&id, req).await?;
+    if quiet {
+        println!("{}", post.public_id);
+        return Ok(());
+    }
+    println!("✅ Post updated!");
+    println!("ID: {}", post.public_id);
+    println!("Title: {}", post.title);
+    Ok(())
+}
+
+/// Prints the diff between post `id` and its updated version, then asks
+/// whether to apply it. Returns true if the user said yes.
+async fn preview(
+    client: &mut BlogClient,
+    id: &str,
+    title: Option<&str>,
+    content: Option<&str>,
+) -> Result<bool, ClientError> {
+    let post = client.get_post(id).await?;
+    let color = io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+
+    let diffs: Vec<String> = [
+        ("title", post.title.as_str(), title),
+        ("content", post.content.as_str(), content),
+    ]
+    .into_iter()
+    .filter_map(|(name, old, new)| diff::unified(name, old, new.unwrap_or(old), color))
+    .collect();
+
+    if diffs.is_empty() {
+        println!("No changes to post {id}.");
+        return Ok(false);
+    }
+    println!("📝 Post {id}: {}", post.title);
+    for diff in diffs {
+        print!("{diff}");
+    }
+
+    if !io::stdin().is_terminal() {
+        println!("Dry run: no changes made.");
+        return Ok(false);
+    }
+    print!("Apply these changes? [y/N] ");
+    let _ = io::stdout().flush();
+    // A failed read counts as "no"
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return Ok(false);
+    }
+    let confirmed = matches!(answer.trim().to_lowercase().as_str(), "y" | "yes");
+    if !confirmed {
+        println!("Aborted: no changes made.");
+    }
+    Ok(confirmed)
+}
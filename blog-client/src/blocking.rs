@@ -0,0 +1,292 @@
+//! Blocking (synchronous) clients for the blog API.
+//!
+//! Each client wraps its async counterpart and runs it on a runtime of its
+//! own, shared by its clones, so it can be used from code without an async
+//! runtime (build scripts, simple tools). Like `reqwest::blocking`, these
+//! clients must not be created, used, or dropped inside an async context.
+
+use std::sync::Arc;
+
+use tokio::runtime::{Builder, Runtime};
+use tokio_stream::StreamExt;
+
+use blog_shared::{
+    AuthResponse, BrokenLinkListResponse, CommentDto, CommentListResponse, CreateCommentRequest,
+    CreatePostRequest, ImportPostsRequest, ImportPostsResponse, LoginRequest, NotificationDto,
+    NotificationListResponse, PostDto, PostListResponse, RegisterRequest, RevisionDiffResponse,
+    RevisionListResponse, TrashListResponse, UnreadCountResponse, UpdatePostRequest,
+};
+
+use crate::{CachePolicy, ClientError, CursorPages, PostPages, WireFormat};
+
+mod grpc_client;
+mod http_client;
+
+pub use grpc_client::{CommentStream, GrpcClient};
+pub use http_client::HttpClient;
+
+/// Starts the runtime a blocking client drives its async client on.
+///
+/// One worker keeps connections alive between calls; the calls themselves
+/// run on the caller's thread.
+fn runtime() -> Result<Arc<Runtime>, ClientError> {
+    let runtime = Builder::new_multi_thread()
+        .worker_threads(1)
+        .enable_all()
+        .build()
+        .map_err(ClientError::Runtime)?;
+    Ok(Arc::new(runtime))
+}
+
+/// Blocking counterpart of [`crate::BlogClient`].
+pub struct BlogClient {
+    inner: crate::BlogClient,
+    runtime: Arc<Runtime>,
+}
+
+impl BlogClient {
+    /// Creates a new HTTP client.
+    pub fn http(base_url: &str) -> Result<Self, ClientError> {
+        Ok(Self {
+            inner: crate::BlogClient::http(base_url),
+            runtime: runtime()?,
+        })
+    }
+
+    /// Creates a new gRPC client.
+    pub fn grpc(addr: &str) -> Result<Self, ClientError> {
+        let runtime = runtime()?;
+        let inner = runtime.block_on(crate::BlogClient::grpc(addr))?;
+        Ok(Self { inner, runtime })
+    }
+
+    /// Sets the authentication token.
+    pub fn set_token(&mut self, token: String) {
+        self.inner.set_token(token);
+    }
+
+    /// Clears the authentication token.
+    pub fn clear_token(&mut self) {
+        self.inner.clear_token();
+    }
+
+    /// Returns the current token if set.
+    pub fn token(&self) -> Option<&str> {
+        self.inner.token()
+    }
+
+    /// Sets how `get_post` and `list_posts` responses are cached.
+    pub fn set_cache_policy(&mut self, policy: CachePolicy) {
+        self.inner.set_cache_policy(policy);
+    }
+
+    /// Drops all cached responses.
+    pub fn clear_cache(&self) {
+        self.inner.clear_cache();
+    }
+
+    /// Sets the body encoding of HTTP requests and responses.
+    pub fn set_wire_format(&mut self, format: WireFormat) {
+        self.inner.set_wire_format(format);
+    }
+
+    /// Registers a new user.
+    pub fn register(&mut self, req: RegisterRequest) -> Result<AuthResponse, ClientError> {
+        self.runtime.block_on(self.inner.register(req))
+    }
+
+    /// Logs in an existing user.
+    pub fn login(&mut self, req: LoginRequest) -> Result<AuthResponse, ClientError> {
+        self.runtime.block_on(self.inner.login(req))
+    }
+
+    /// Creates a new post (requires authentication).
+    pub fn create_post(&mut self, req: CreatePostRequest) -> Result<PostDto, ClientError> {
+        self.runtime.block_on(self.inner.create_post(req))
+    }
+
+    /// Gets a post by its public ID.
+    pub fn get_post(&mut self, id: &str) -> Result<PostDto, ClientError> {
+        self.runtime.block_on(self.inner.get_post(id))
+    }
+
+    /// Lists posts with pagination.
+    pub fn list_posts(&mut self, limit: i64, offset: i64) -> Result<PostListResponse, ClientError> {
+        self.runtime.block_on(self.inner.list_posts(limit, offset))
+    }
+
+    /// Iterates over every post, fetching `limit` posts per request as the
+    /// iterator is consumed. The iterator uses a clone of this client.
+    pub fn posts_iter(&self, limit: i64) -> PostIter {
+        PostIter {
+            pages: self.inner.posts_iter(limit),
+            runtime: Arc::clone(&self.runtime),
+        }
+    }
+
+    /// Updates a post (author only).
+    pub fn update_post(
+        &mut self,
+        id: &str,
+        req: UpdatePostRequest,
+    ) -> Result<PostDto, ClientError> {
+        self.runtime.block_on(self.inner.update_post(id, req))
+    }
+
+    /// Deletes a post (author only).
+    pub fn delete_post(&mut self, id: &str) -> Result<(), ClientError> {
+        self.runtime.block_on(self.inner.delete_post(id))
+    }
+
+    /// Lists the current user's deleted posts (requires authentication).
+    pub fn list_trash(&mut self) -> Result<TrashListResponse, ClientError> {
+        self.runtime.block_on(self.inner.list_trash())
+    }
+
+    /// Takes a post out of the trash (author only).
+    pub fn restore_post(&mut self, id: &str) -> Result<(), ClientError> {
+        self.runtime.block_on(self.inner.restore_post(id))
+    }
+
+    /// Lists dead links in the current user's posts (requires authentication).
+    pub fn broken_links(&mut self) -> Result<BrokenLinkListResponse, ClientError> {
+        self.runtime.block_on(self.inner.broken_links())
+    }
+
+    /// Imports a batch of posts exported from another blog (requires authentication).
+    pub fn import_posts(
+        &mut self,
+        req: ImportPostsRequest,
+    ) -> Result<ImportPostsResponse, ClientError> {
+        self.runtime.block_on(self.inner.import_posts(req))
+    }
+
+    /// Lists the revisions of a post, newest first (author or editors).
+    pub fn list_revisions(&mut self, id: &str) -> Result<RevisionListResponse, ClientError> {
+        self.runtime.block_on(self.inner.list_revisions(id))
+    }
+
+    /// Diffs revision `from` of a post against revision `to` (author or editors).
+    pub fn diff_revisions(
+        &mut self,
+        id: &str,
+        from: i64,
+        to: i64,
+    ) -> Result<RevisionDiffResponse, ClientError> {
+        self.runtime
+            .block_on(self.inner.diff_revisions(id, from, to))
+    }
+
+    /// Adds a comment to a post (requires authentication).
+    pub fn create_comment(
+        &mut self,
+        post_id: &str,
+        req: CreateCommentRequest,
+    ) -> Result<CommentDto, ClientError> {
+        self.runtime
+            .block_on(self.inner.create_comment(post_id, req))
+    }
+
+    /// Lists comments on a post, oldest first.
+    pub fn list_comments(
+        &mut self,
+        post_id: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<CommentListResponse, ClientError> {
+        self.runtime
+            .block_on(self.inner.list_comments(post_id, limit, offset))
+    }
+
+    /// Lists a page of comments on a post, oldest first, starting after
+    /// `cursor` (the `next_cursor` of the previous page).
+    pub fn list_comments_after(
+        &mut self,
+        post_id: &str,
+        limit: i64,
+        cursor: Option<String>,
+    ) -> Result<CommentListResponse, ClientError> {
+        self.runtime
+            .block_on(self.inner.list_comments_after(post_id, limit, cursor))
+    }
+
+    /// Iterates over every comment on a post, fetching `limit` comments per
+    /// request as the iterator is consumed. The iterator uses a clone of
+    /// this client.
+    pub fn comments_iter(&self, post_id: &str, limit: i64) -> CursorIter<CommentDto> {
+        CursorIter {
+            pages: self.inner.comments_iter(post_id, limit),
+            runtime: Arc::clone(&self.runtime),
+        }
+    }
+
+    /// Deletes a comment (comment author or post author).
+    pub fn delete_comment(&mut self, id: i64) -> Result<(), ClientError> {
+        self.runtime.block_on(self.inner.delete_comment(id))
+    }
+
+    /// Lists a page of the current user's notifications, newest first,
+    /// starting after `cursor` (requires authentication).
+    pub fn list_notifications(
+        &mut self,
+        limit: i64,
+        cursor: Option<String>,
+    ) -> Result<NotificationListResponse, ClientError> {
+        self.runtime
+            .block_on(self.inner.list_notifications(limit, cursor))
+    }
+
+    /// Iterates over every notification of the current user, fetching
+    /// `limit` per request as the iterator is consumed (requires
+    /// authentication). The iterator uses a clone of this client.
+    pub fn notifications_iter(&self, limit: i64) -> CursorIter<NotificationDto> {
+        CursorIter {
+            pages: self.inner.notifications_iter(limit),
+            runtime: Arc::clone(&self.runtime),
+        }
+    }
+
+    /// Counts the current user's unread notifications (requires authentication).
+    pub fn unread_notification_count(&mut self) -> Result<UnreadCountResponse, ClientError> {
+        self.runtime
+            .block_on(self.inner.unread_notification_count())
+    }
+
+    /// Marks the current user's notifications as read, only up to
+    /// `up_to_id` if given (requires authentication).
+    pub fn mark_notifications_read(&mut self, up_to_id: Option<i64>) -> Result<(), ClientError> {
+        self.runtime
+            .block_on(self.inner.mark_notifications_read(up_to_id))
+    }
+}
+
+/// Iterator over all posts, returned by [`BlogClient::posts_iter`]; the
+/// blocking counterpart of [`PostPages`].
+pub struct PostIter {
+    pages: PostPages,
+    runtime: Arc<Runtime>,
+}
+
+impl Iterator for PostIter {
+    type Item = Result<PostDto, ClientError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.pages.next())
+    }
+}
+
+/// Iterator over all comments or notifications, returned by
+/// [`BlogClient::comments_iter`] and [`BlogClient::notifications_iter`];
+/// the blocking counterpart of [`CursorPages`].
+pub struct CursorIter<T> {
+    pages: CursorPages<T>,
+    runtime: Arc<Runtime>,
+}
+
+impl<T: Unpin> Iterator for CursorIter<T> {
+    type Item = Result<T, ClientError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.pages.next())
+    }
+}
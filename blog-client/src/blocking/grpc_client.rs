@@ -0,0 +1,229 @@
+//! Blocking gRPC client for the blog API.
+
+use std::pin::Pin;
+use std::sync::Arc;
+
+use tokio::runtime::Runtime;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::{Stream, StreamExt};
+
+use blog_shared::{
+    AuthResponse, BrokenLinkListResponse, CommentDto, CommentListResponse, CreateCommentRequest,
+    CreatePostRequest, ImportPostsRequest, ImportPostsResponse, LoginRequest,
+    NotificationListResponse, PostDto, PostListResponse, RegisterRequest, UnreadCountResponse,
+    UpdatePostRequest,
+};
+
+use super::runtime;
+use crate::constants::COMMENT_SEND_BUFFER;
+use crate::{CachePolicy, ClientError, CommentStreamEvent, GrpcClientBuilder};
+
+/// Blocking counterpart of [`crate::GrpcClient`].
+#[derive(Clone)]
+pub struct GrpcClient {
+    inner: crate::GrpcClient,
+    runtime: Arc<Runtime>,
+}
+
+impl GrpcClient {
+    /// Connects to the gRPC server.
+    pub fn connect(addr: &str) -> Result<Self, ClientError> {
+        Self::connect_with(crate::GrpcClient::builder().endpoint(addr))
+    }
+
+    /// Connects with custom channel settings or to several servers; the
+    /// blocking counterpart of [`GrpcClientBuilder::build`].
+    pub fn connect_with(builder: GrpcClientBuilder) -> Result<Self, ClientError> {
+        let runtime = runtime()?;
+        let inner = runtime.block_on(builder.build())?;
+        Ok(Self { inner, runtime })
+    }
+
+    /// Sets the authentication token.
+    pub fn set_token(&mut self, token: String) {
+        self.inner.set_token(token);
+    }
+
+    /// Clears the authentication token.
+    pub fn clear_token(&mut self) {
+        self.inner.clear_token();
+    }
+
+    /// Returns the current token if set.
+    pub fn token(&self) -> Option<&str> {
+        self.inner.token()
+    }
+
+    /// Sets how `get_post` and `list_posts` responses are cached, dropping
+    /// anything cached so far. Clones made afterwards share the cache.
+    pub fn set_cache_policy(&mut self, policy: CachePolicy) {
+        self.inner.set_cache_policy(policy);
+    }
+
+    /// Drops all cached responses.
+    pub fn clear_cache(&self) {
+        self.inner.clear_cache();
+    }
+
+    /// Registers a new user.
+    pub fn register(&mut self, req: RegisterRequest) -> Result<AuthResponse, ClientError> {
+        self.runtime.block_on(self.inner.register(req))
+    }
+
+    /// Logs in an existing user.
+    pub fn login(&mut self, req: LoginRequest) -> Result<AuthResponse, ClientError> {
+        self.runtime.block_on(self.inner.login(req))
+    }
+
+    /// Creates a new post (requires authentication).
+    pub fn create_post(&mut self, req: CreatePostRequest) -> Result<PostDto, ClientError> {
+        self.runtime.block_on(self.inner.create_post(req))
+    }
+
+    /// Gets a post by its public ID.
+    pub fn get_post(&mut self, id: &str) -> Result<PostDto, ClientError> {
+        self.runtime.block_on(self.inner.get_post(id))
+    }
+
+    /// Lists posts with pagination.
+    pub fn list_posts(&mut self, limit: i64, offset: i64) -> Result<PostListResponse, ClientError> {
+        self.runtime.block_on(self.inner.list_posts(limit, offset))
+    }
+
+    /// Updates a post (author only).
+    pub fn update_post(
+        &mut self,
+        id: &str,
+        req: UpdatePostRequest,
+    ) -> Result<PostDto, ClientError> {
+        self.runtime.block_on(self.inner.update_post(id, req))
+    }
+
+    /// Deletes a post (author only).
+    pub fn delete_post(&mut self, id: &str) -> Result<(), ClientError> {
+        self.runtime.block_on(self.inner.delete_post(id))
+    }
+
+    /// Lists dead links in the current user's posts (requires authentication).
+    pub fn broken_links(&mut self) -> Result<BrokenLinkListResponse, ClientError> {
+        self.runtime.block_on(self.inner.broken_links())
+    }
+
+    /// Imports a batch of posts exported from another blog (requires authentication).
+    pub fn import_posts(
+        &mut self,
+        req: ImportPostsRequest,
+    ) -> Result<ImportPostsResponse, ClientError> {
+        self.runtime.block_on(self.inner.import_posts(req))
+    }
+
+    /// Adds a comment to a post (requires authentication).
+    pub fn create_comment(
+        &mut self,
+        post_id: &str,
+        req: CreateCommentRequest,
+    ) -> Result<CommentDto, ClientError> {
+        self.runtime
+            .block_on(self.inner.create_comment(post_id, req))
+    }
+
+    /// Lists comments on a post, oldest first.
+    pub fn list_comments(
+        &mut self,
+        post_id: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<CommentListResponse, ClientError> {
+        self.runtime
+            .block_on(self.inner.list_comments(post_id, limit, offset))
+    }
+
+    /// Lists a page of comments on a post, oldest first, starting after
+    /// `cursor` (the `next_cursor` of the previous page).
+    pub fn list_comments_after(
+        &mut self,
+        post_id: &str,
+        limit: i64,
+        cursor: Option<String>,
+    ) -> Result<CommentListResponse, ClientError> {
+        self.runtime
+            .block_on(self.inner.list_comments_after(post_id, limit, cursor))
+    }
+
+    /// Deletes a comment (comment author or post author).
+    pub fn delete_comment(&mut self, id: i64) -> Result<(), ClientError> {
+        self.runtime.block_on(self.inner.delete_comment(id))
+    }
+
+    /// Lists a page of the current user's notifications, newest first,
+    /// starting after `cursor` (requires authentication).
+    pub fn list_notifications(
+        &mut self,
+        limit: i64,
+        cursor: Option<String>,
+    ) -> Result<NotificationListResponse, ClientError> {
+        self.runtime
+            .block_on(self.inner.list_notifications(limit, cursor))
+    }
+
+    /// Counts the current user's unread notifications (requires authentication).
+    pub fn unread_notification_count(&mut self) -> Result<UnreadCountResponse, ClientError> {
+        self.runtime
+            .block_on(self.inner.unread_notification_count())
+    }
+
+    /// Marks the current user's notifications as read, only up to
+    /// `up_to_id` if given (requires authentication).
+    pub fn mark_notifications_read(&mut self, up_to_id: Option<i64>) -> Result<(), ClientError> {
+        self.runtime
+            .block_on(self.inner.mark_notifications_read(up_to_id))
+    }
+
+    /// Follows the comments on a post as they are added.
+    ///
+    /// Unlike the async client, which takes a stream of comments to post,
+    /// this returns a [`CommentStream`] to both send comments on and iterate
+    /// over events.
+    pub fn stream_comments(&mut self, post_id: &str) -> Result<CommentStream, ClientError> {
+        let (sender, outgoing) = mpsc::channel(COMMENT_SEND_BUFFER);
+        let events = self.runtime.block_on(
+            self.inner
+                .stream_comments(post_id, ReceiverStream::new(outgoing)),
+        )?;
+
+        Ok(CommentStream {
+            sender,
+            events: Box::pin(events),
+            runtime: Arc::clone(&self.runtime),
+        })
+    }
+}
+
+/// A live comment stream opened by [`GrpcClient::stream_comments`].
+///
+/// Iterating blocks until the next event and ends when the server closes the
+/// stream; dropping it closes the stream.
+pub struct CommentStream {
+    sender: mpsc::Sender<String>,
+    events: Pin<Box<dyn Stream<Item = Result<CommentStreamEvent, ClientError>> + Send>>,
+    runtime: Arc<Runtime>,
+}
+
+impl CommentStream {
+    /// Posts a comment on the stream (requires authentication). The outcome
+    /// arrives as an event: the comment itself, or why it was rejected.
+    pub fn send(&self, content: String) -> Result<(), ClientError> {
+        self.runtime
+            .block_on(self.sender.send(content))
+            .map_err(|_| ClientError::Grpc(tonic::Status::unavailable("Comment stream closed")))
+    }
+}
+
+impl Iterator for CommentStream {
+    type Item = Result<CommentStreamEvent, ClientError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.events.next())
+    }
+}
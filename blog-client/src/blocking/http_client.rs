@@ -0,0 +1,168 @@
+//! Blocking HTTP client for the blog API.
+
+use std::sync::Arc;
+
+use tokio::runtime::Runtime;
+
+use blog_shared::{
+    AuthResponse, BrokenLinkListResponse, CommentDto, CommentListResponse, CreateCommentRequest,
+    CreatePostRequest, ImportPostsRequest, ImportPostsResponse, LoginRequest,
+    NotificationListResponse, PostDto, PostListResponse, RegisterRequest, UnreadCountResponse,
+    UpdatePostRequest,
+};
+
+use super::runtime;
+use crate::{CachePolicy, ClientError};
+
+/// Blocking counterpart of [`crate::HttpClient`].
+#[derive(Clone)]
+pub struct HttpClient {
+    inner: crate::HttpClient,
+    runtime: Arc<Runtime>,
+}
+
+impl HttpClient {
+    /// Creates a new HTTP client.
+    pub fn new(base_url: &str) -> Result<Self, ClientError> {
+        Ok(Self {
+            inner: crate::HttpClient::new(base_url),
+            runtime: runtime()?,
+        })
+    }
+
+    /// Sets the authentication token.
+    pub fn set_token(&mut self, token: String) {
+        self.inner.set_token(token);
+    }
+
+    /// Clears the authentication token.
+    pub fn clear_token(&mut self) {
+        self.inner.clear_token();
+    }
+
+    /// Returns the current token if set.
+    pub fn token(&self) -> Option<&str> {
+        self.inner.token()
+    }
+
+    /// Sets how `get_post` and `list_posts` responses are cached, dropping
+    /// anything cached so far. Clones made afterwards share the cache.
+    pub fn set_cache_policy(&mut self, policy: CachePolicy) {
+        self.inner.set_cache_policy(policy);
+    }
+
+    /// Drops all cached responses.
+    pub fn clear_cache(&self) {
+        self.inner.clear_cache();
+    }
+
+    /// Registers a new user.
+    pub fn register(&self, req: RegisterRequest) -> Result<AuthResponse, ClientError> {
+        self.runtime.block_on(self.inner.register(req))
+    }
+
+    /// Logs in an existing user.
+    pub fn login(&self, req: LoginRequest) -> Result<AuthResponse, ClientError> {
+        self.runtime.block_on(self.inner.login(req))
+    }
+
+    /// Creates a new post (requires authentication).
+    pub fn create_post(&self, req: CreatePostRequest) -> Result<PostDto, ClientError> {
+        self.runtime.block_on(self.inner.create_post(req))
+    }
+
+    /// Gets a post by its public ID.
+    pub fn get_post(&self, id: &str) -> Result<PostDto, ClientError> {
+        self.runtime.block_on(self.inner.get_post(id))
+    }
+
+    /// Lists posts with pagination.
+    pub fn list_posts(&self, limit: i64, offset: i64) -> Result<PostListResponse, ClientError> {
+        self.runtime.block_on(self.inner.list_posts(limit, offset))
+    }
+
+    /// Updates a post (author only).
+    pub fn update_post(&self, id: &str, req: UpdatePostRequest) -> Result<PostDto, ClientError> {
+        self.runtime.block_on(self.inner.update_post(id, req))
+    }
+
+    /// Deletes a post (author only).
+    pub fn delete_post(&self, id: &str) -> Result<(), ClientError> {
+        self.runtime.block_on(self.inner.delete_post(id))
+    }
+
+    /// Lists dead links in the current user's posts (requires authentication).
+    pub fn broken_links(&self) -> Result<BrokenLinkListResponse, ClientError> {
+        self.runtime.block_on(self.inner.broken_links())
+    }
+
+    /// Imports a batch of posts exported from another blog (requires authentication).
+    pub fn import_posts(
+        &self,
+        req: ImportPostsRequest,
+    ) -> Result<ImportPostsResponse, ClientError> {
+        self.runtime.block_on(self.inner.import_posts(req))
+    }
+
+    /// Adds a comment to a post (requires authentication).
+    pub fn create_comment(
+        &self,
+        post_id: &str,
+        req: CreateCommentRequest,
+    ) -> Result<CommentDto, ClientError> {
+        self.runtime
+            .block_on(self.inner.create_comment(post_id, req))
+    }
+
+    /// Lists comments on a post, oldest first.
+    pub fn list_comments(
+        &self,
+        post_id: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<CommentListResponse, ClientError> {
+        self.runtime
+            .block_on(self.inner.list_comments(post_id, limit, offset))
+    }
+
+    /// Lists a page of comments on a post, oldest first, starting after
+    /// `cursor` (the `next_cursor` of the previous page).
+    pub fn list_comments_after(
+        &self,
+        post_id: &str,
+        limit: i64,
+        cursor: Option<String>,
+    ) -> Result<CommentListResponse, ClientError> {
+        self.runtime
+            .block_on(self.inner.list_comments_after(post_id, limit, cursor))
+    }
+
+    /// Deletes a comment (comment author or post author).
+    pub fn delete_comment(&self, id: i64) -> Result<(), ClientError> {
+        self.runtime.block_on(self.inner.delete_comment(id))
+    }
+
+    /// Lists a page of the current user's notifications, newest first,
+    /// starting after `cursor` (requires authentication).
+    pub fn list_notifications(
+        &self,
+        limit: i64,
+        cursor: Option<String>,
+    ) -> Result<NotificationListResponse, ClientError> {
+        self.runtime
+            .block_on(self.inner.list_notifications(limit, cursor))
+    }
+
+    /// Counts the current user's unread notifications (requires authentication).
+    pub fn unread_notification_count(&self) -> Result<UnreadCountResponse, ClientError> {
+        self.runtime
+            .block_on(self.inner.unread_notification_count())
+    }
+
+    /// Marks the current user's notifications as read, only up to
+    /// `up_to_id` if given (requires authentication).
+    pub fn mark_notifications_read(&self, up_to_id: Option<i64>) -> Result<(), ClientError> {
+        self.runtime
+            .block_on(self.inner.mark_notifications_read(up_to_id))
+    }
+}
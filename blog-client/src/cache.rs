@@ -0,0 +1,136 @@
+//! In-memory response cache for post reads.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use blog_shared::{PostDto, PostListResponse};
+
+use crate::constants::MAX_CACHED_RESPONSES;
+
+/// Whether a client caches `get_post` and `list_posts` responses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CachePolicy {
+    /// Every read goes to the server.
+    #[default]
+    Disabled,
+    /// Responses are reused for this long. The client's own writes
+    /// invalidate them; changes by others show up once they expire.
+    Ttl(Duration),
+}
+
+/// Cached responses, shared by clones of a client.
+#[derive(Clone, Default)]
+pub(crate) struct ResponseCache {
+    policy: CachePolicy,
+    entries: Arc<Mutex<Entries>>,
+}
+
+#[derive(Default)]
+struct Entries {
+    /// Posts by public ID.
+    posts: HashMap<String, (Instant, PostDto)>,
+    /// Post lists by `(limit, offset)`.
+    lists: HashMap<(i64, i64), (Instant, PostListResponse)>,
+}
+
+impl ResponseCache {
+    /// Switches the policy, dropping everything cached so far.
+    pub(crate) fn set_policy(&mut self, policy: CachePolicy) {
+        self.policy = policy;
+        self.clear();
+    }
+
+    /// Drops all cached responses.
+    pub(crate) fn clear(&self) {
+        let mut entries = self.lock();
+        entries.posts.clear();
+        entries.lists.clear();
+    }
+
+    /// Returns the cached post, if fresh.
+    pub(crate) fn post(&self, id: &str) -> Option<PostDto> {
+        let ttl = self.ttl()?;
+        lookup(&mut self.lock().posts, id, ttl)
+    }
+
+    /// Caches a post.
+    pub(crate) fn store_post(&self, post: &PostDto) {
+        if let Some(ttl) = self.ttl() {
+            store(
+                &mut self.lock().posts,
+                post.public_id.clone(),
+                post.clone(),
+                ttl,
+            );
+        }
+    }
+
+    /// Returns the cached post list page, if fresh.
+    pub(crate) fn list(&self, limit: i64, offset: i64) -> Option<PostListResponse> {
+        let ttl = self.ttl()?;
+        lookup(&mut self.lock().lists, &(limit, offset), ttl)
+    }
+
+    /// Caches a post list page.
+    pub(crate) fn store_list(&self, limit: i64, offset: i64, list: &PostListResponse) {
+        if let Some(ttl) = self.ttl() {
+            store(&mut self.lock().lists, (limit, offset), list.clone(), ttl);
+        }
+    }
+
+    /// Forgets a written post and every post list, which may include it.
+    pub(crate) fn invalidate_post(&self, id: &str) {
+        let mut entries = self.lock();
+        entries.posts.remove(id);
+        entries.lists.clear();
+    }
+
+    /// Forgets every post list, after posts were added.
+    pub(crate) fn invalidate_lists(&self) {
+        self.lock().lists.clear();
+    }
+
+    fn ttl(&self) -> Option<Duration> {
+        match self.policy {
+            CachePolicy::Disabled => None,
+            CachePolicy::Ttl(ttl) => Some(ttl),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Entries> {
+        // The maps stay consistent even if a holder panicked
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Returns a clone of the entry if it is younger than `ttl`, dropping it
+/// otherwise.
+fn lookup<K: Borrow<Q> + Eq + Hash, Q: Eq + Hash + ?Sized, V: Clone>(
+    map: &mut HashMap<K, (Instant, V)>,
+    key: &Q,
+    ttl: Duration,
+) -> Option<V> {
+    match map.get(key) {
+        Some((stored_at, value)) if stored_at.elapsed() < ttl => Some(value.clone()),
+        Some(_) => {
+            map.remove(key);
+            None
+        }
+        None => None,
+    }
+}
+
+/// Inserts an entry, first dropping expired ones (or everything, if none
+/// have expired) when the map is full.
+fn store<K: Eq + Hash, V>(map: &mut HashMap<K, (Instant, V)>, key: K, value: V, ttl: Duration) {
+    if map.len() >= MAX_CACHED_RESPONSES {
+        map.retain(|_, (stored_at, _)| stored_at.elapsed() < ttl);
+        if map.len() >= MAX_CACHED_RESPONSES {
+            map.clear();
+        }
+    }
+    map.insert(key, (Instant::now(), value));
+}
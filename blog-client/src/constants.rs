@@ -0,0 +1,8 @@
+//! Client constants.
+
+/// Most responses of each kind kept before expired ones are dropped.
+pub const MAX_CACHED_RESPONSES: usize = 1024;
+
+/// Comments a blocking [`CommentStream`](crate::blocking::CommentStream)
+/// holds before `send` waits for the server.
+pub const COMMENT_SEND_BUFFER: usize = 16;
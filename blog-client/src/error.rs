@@ -1,6 +1,9 @@
 //! Client library errors.
 
+use blog_shared::ErrorResponse;
+use blog_shared::constants::ERROR_CODE_METADATA_KEY;
 use thiserror::Error;
+use tonic::Code;
 
 /// Errors that can occur when using the blog client.
 #[derive(Debug, Error)]
@@ -23,9 +26,100 @@ pub enum ClientError {
 
     /// Server returned an error response.
     #[error("Server error ({status}): {message}")]
-    Server { status: u16, message: String },
+    Server {
+        status: u16,
+        message: String,
+        /// Machine-readable code from the error body, if the server sent one.
+        code: Option<String>,
+    },
+
+    /// The blocking client could not start its runtime.
+    #[error("Failed to start client runtime: {0}")]
+    Runtime(#[source] std::io::Error),
+
+    /// Failed to encode a MessagePack request body.
+    #[error("Serialization failed: {0}")]
+    Serialization(#[from] rmp_serde::encode::Error),
 
     /// Failed to deserialize server response.
     #[error("Deserialization failed: {0}")]
     Deserialization(#[from] serde_json::Error),
 }
+
+impl ClientError {
+    /// Builds a `Server` error from an HTTP error response, reading the
+    /// message and code from an `ErrorResponse` body when there is one. The
+    /// offending field, if reported, is appended to the message.
+    pub(crate) fn from_response(status: u16, body: String) -> Self {
+        match serde_json::from_str::<ErrorResponse>(&body) {
+            Ok(response) => Self::Server {
+                status,
+                message: match response.pointer {
+                    Some(pointer) => format!("{} (at {pointer})", response.error),
+                    None => response.error,
+                },
+                code: response.code,
+            },
+            Err(_) => Self::Server {
+                status,
+                message: body,
+                code: None,
+            },
+        }
+    }
+
+    /// Returns the HTTP status of the failure, with gRPC codes mapped to
+    /// their HTTP equivalents (`NOT_FOUND` is 404, `UNAVAILABLE` 503, ...).
+    /// `None` when the server never answered, or the error is client-side.
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            Self::Server { status, .. } => Some(*status),
+            Self::Grpc(status) => Some(grpc_code_to_http(status.code())),
+            _ => None,
+        }
+    }
+
+    /// Returns the server's machine-readable error code (e.g.
+    /// `post_not_found`, `content_blocked`), the same over HTTP and gRPC.
+    pub fn error_code(&self) -> Option<&str> {
+        match self {
+            Self::Server { code, .. } => code.as_deref(),
+            Self::Grpc(status) => status
+                .metadata()
+                .get(ERROR_CODE_METADATA_KEY)
+                .and_then(|code| code.to_str().ok()),
+            _ => None,
+        }
+    }
+
+    /// Returns true if sending the request again may succeed: the server
+    /// could not be reached or timed out, was overloaded or unavailable, or
+    /// failed internally.
+    ///
+    /// The first attempt may still have been applied, so only retry writes
+    /// that are safe to repeat.
+    pub fn is_retryable(&self) -> bool {
+        if let Self::Http(e) = self {
+            return e.is_connect() || e.is_timeout();
+        }
+        matches!(self.status_code(), Some(408 | 429 | 500 | 502 | 503 | 504))
+    }
+}
+
+/// Maps a gRPC status code to the HTTP status with the same meaning.
+fn grpc_code_to_http(code: Code) -> u16 {
+    match code {
+        Code::Ok => 200,
+        Code::Cancelled => 499,
+        Code::InvalidArgument | Code::FailedPrecondition | Code::OutOfRange => 400,
+        Code::Unauthenticated => 401,
+        Code::PermissionDenied => 403,
+        Code::NotFound => 404,
+        Code::AlreadyExists | Code::Aborted => 409,
+        Code::ResourceExhausted => 429,
+        Code::Unimplemented => 501,
+        Code::Unavailable => 503,
+        Code::DeadlineExceeded => 504,
+        Code::Unknown | Code::Internal | Code::DataLoss => 500,
+    }
+}
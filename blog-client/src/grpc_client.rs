@@ -2,53 +2,84 @@
 
 use chrono::{DateTime, Utc};
 use serde::de::Error as _;
+use tokio_stream::{Stream, StreamExt};
 
+use blog_shared::constants::AUTH_METADATA_KEY;
 use blog_shared::{
-    AuthResponse, CreatePostRequest, LoginRequest, PostDto, PostListResponse, RegisterRequest,
+    AuthResponse, BrokenLinkDto, BrokenLinkListResponse, CommentDto, CommentListResponse,
+    CreateCommentRequest, CreatePostRequest, DiffLineDto, ImportPostsRequest, ImportPostsResponse,
+    ImportedPostDto, LinkPreviewDto, LoginRequest, NotificationDto, NotificationListResponse,
+    PostAuthor, PostDto, PostListResponse, RegisterRequest, RevisionDiffResponse, RevisionDto,
+    RevisionListResponse, TrashListResponse, TrashedPostDto, UnreadCountResponse,
     UpdatePostRequest, UserDto,
 };
 
 use crate::ClientError;
+use crate::cache::{CachePolicy, ResponseCache};
+
+mod builder;
+
+pub use builder::GrpcClientBuilder;
 
 /// Generated protobuf types and client stubs.
 pub mod proto {
     tonic::include_proto!("blog");
 }
 
+use proto::comment_stream_response::Event;
 use proto::{auth_service_client::AuthServiceClient, blog_service_client::BlogServiceClient};
 
+/// Something received on a live comment stream.
+#[derive(Debug, Clone)]
+pub enum CommentStreamEvent {
+    /// A new comment on the post, including ones sent on this stream.
+    Comment(CommentDto),
+    /// A comment sent on this stream was rejected, with the reason.
+    Rejected(String),
+}
+
 /// gRPC client for the blog API.
 #[derive(Clone)]
 pub struct GrpcClient {
     auth_client: AuthServiceClient<tonic::transport::Channel>,
     blog_client: BlogServiceClient<tonic::transport::Channel>,
     token: Option<String>,
+    cache: ResponseCache,
 }
 
 impl GrpcClient {
     /// Connects to the gRPC server.
     pub async fn connect(addr: &str) -> Result<Self, ClientError> {
-        let channel = tonic::transport::Channel::from_shared(addr.to_string())
-            .map_err(|e| ClientError::InvalidUrl(e.to_string()))?
-            .connect()
-            .await
-            .map_err(|e| ClientError::Grpc(tonic::Status::from_error(Box::new(e))))?;
+        Self::builder().endpoint(addr).build().await
+    }
 
-        Ok(Self {
+    /// Returns a builder for clients with custom channel settings or
+    /// several servers.
+    pub fn builder() -> GrpcClientBuilder {
+        GrpcClientBuilder::new()
+    }
+
+    /// Creates a client on a configured channel.
+    fn from_channel(channel: tonic::transport::Channel) -> Self {
+        Self {
             auth_client: AuthServiceClient::new(channel.clone()),
             blog_client: BlogServiceClient::new(channel),
             token: None,
-        })
+            cache: ResponseCache::default(),
+        }
     }
 
-    /// Sets the authentication token.
+    /// Sets the authentication token. Cached responses are dropped, as
+    /// posts carry per-viewer fields.
     pub fn set_token(&mut self, token: String) {
         self.token = Some(token);
+        self.cache.clear();
     }
 
-    /// Clears the authentication token.
+    /// Clears the authentication token and cached responses.
     pub fn clear_token(&mut self) {
         self.token = None;
+        self.cache.clear();
     }
 
     /// Returns the current token if set.
@@ -56,12 +87,24 @@ impl GrpcClient {
         self.token.as_deref()
     }
 
+    /// Sets how `get_post` and `list_posts` responses are cached, dropping
+    /// anything cached so far. Clones made afterwards share the cache.
+    pub fn set_cache_policy(&mut self, policy: CachePolicy) {
+        self.cache.set_policy(policy);
+    }
+
+    /// Drops all cached responses.
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+    }
+
     /// Registers a new user.
     pub async fn register(&mut self, req: RegisterRequest) -> Result<AuthResponse, ClientError> {
         let request = proto::RegisterRequest {
             username: req.username,
             email: req.email,
             password: req.password,
+            invite_code: req.invite_code,
         };
 
         let response = self.auth_client.register(request).await?;
@@ -86,17 +129,33 @@ impl GrpcClient {
             token,
             title: req.title,
             content: req.content,
+            tags: req.tags,
+            lang: req.lang,
+            canonical_url: req.canonical_url,
+            original_source: req.original_source,
+            org: req.org,
         };
 
         let response = self.blog_client.create_post(request).await?;
-        Self::convert_post(response.into_inner().post.unwrap())
+        let post = Self::convert_post(response.into_inner().post.unwrap())?;
+        self.cache.invalidate_lists();
+        Ok(post)
     }
 
-    /// Gets a post by ID.
-    pub async fn get_post(&mut self, id: i64) -> Result<PostDto, ClientError> {
-        let request = proto::GetPostRequest { id };
+    /// Gets a post by its public ID.
+    pub async fn get_post(&mut self, id: &str) -> Result<PostDto, ClientError> {
+        if let Some(post) = self.cache.post(id) {
+            return Ok(post);
+        }
+
+        let request = self.viewer_request(proto::GetPostRequest {
+            id: 0,
+            public_id: id.to_string(),
+        });
         let response = self.blog_client.get_post(request).await?;
-        Self::convert_post(response.into_inner().post.unwrap())
+        let post = Self::convert_post(response.into_inner().post.unwrap())?;
+        self.cache.store_post(&post);
+        Ok(post)
     }
 
     /// Lists posts with pagination.
@@ -105,7 +164,15 @@ impl GrpcClient {
         limit: i64,
         offset: i64,
     ) -> Result<PostListResponse, ClientError> {
-        let request = proto::ListPostsRequest { limit, offset };
+        if let Some(list) = self.cache.list(limit, offset) {
+            return Ok(list);
+        }
+
+        let request = self.viewer_request(proto::ListPostsRequest {
+            limit,
+            offset,
+            lang: None,
+        });
         let response = self.blog_client.list_posts(request).await?;
         let inner = response.into_inner();
 
@@ -115,66 +182,526 @@ impl GrpcClient {
             .map(Self::convert_post)
             .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(PostListResponse {
-            posts,
-            total: inner.total,
-        })
+        let list = PostListResponse::new(posts, inner.total);
+        self.cache.store_list(limit, offset, &list);
+        Ok(list)
     }
 
     /// Updates a post (author only).
     pub async fn update_post(
         &mut self,
-        id: i64,
+        id: &str,
         req: UpdatePostRequest,
     ) -> Result<PostDto, ClientError> {
         let token = self.token.clone().ok_or(ClientError::NotAuthenticated)?;
         let request = proto::UpdatePostRequest {
             token,
-            id,
+            id: 0,
+            public_id: id.to_string(),
             title: req.title,
             content: req.content,
+            tags: req.tags.map(|tags| proto::TagList { tags }),
+            canonical_url: req.canonical_url,
+            original_source: req.original_source,
         };
 
         let response = self.blog_client.update_post(request).await?;
-        Self::convert_post(response.into_inner().post.unwrap())
+        let post = Self::convert_post(response.into_inner().post.unwrap())?;
+        self.cache.invalidate_post(id);
+        Ok(post)
     }
 
     /// Deletes a post (author only).
-    pub async fn delete_post(&mut self, id: i64) -> Result<(), ClientError> {
+    pub async fn delete_post(&mut self, id: &str) -> Result<(), ClientError> {
         let token = self.token.clone().ok_or(ClientError::NotAuthenticated)?;
-        let request = proto::DeletePostRequest { token, id };
+        let request = proto::DeletePostRequest {
+            token,
+            id: 0,
+            public_id: id.to_string(),
+        };
         self.blog_client.delete_post(request).await?;
+        self.cache.invalidate_post(id);
         Ok(())
     }
 
+    /// Lists the current user's deleted posts (requires authentication).
+    pub async fn list_trash(&mut self) -> Result<TrashListResponse, ClientError> {
+        let token = self.token.clone().ok_or(ClientError::NotAuthenticated)?;
+        let request = proto::ListTrashRequest { token };
+        let response = self.blog_client.list_trash(request).await?;
+
+        let posts = response
+            .into_inner()
+            .posts
+            .into_iter()
+            .map(|post| {
+                Ok(TrashedPostDto {
+                    id: post.id,
+                    public_id: post.public_id,
+                    title: post.title,
+                    deleted_at: Self::parse_datetime(&post.deleted_at)?,
+                    purge_at: post
+                        .purge_at
+                        .map(|at| Self::parse_datetime(&at))
+                        .transpose()?,
+                    days_until_purge: post.days_until_purge,
+                })
+            })
+            .collect::<Result<Vec<_>, ClientError>>()?;
+
+        Ok(TrashListResponse { posts })
+    }
+
+    /// Takes a post out of the trash (author only).
+    pub async fn restore_post(&mut self, id: &str) -> Result<(), ClientError> {
+        let token = self.token.clone().ok_or(ClientError::NotAuthenticated)?;
+        let request = proto::RestorePostRequest {
+            token,
+            id: 0,
+            public_id: id.to_string(),
+        };
+        self.blog_client.restore_post(request).await?;
+        self.cache.invalidate_post(id);
+        Ok(())
+    }
+
+    /// Lists dead links in the current user's posts (requires authentication).
+    pub async fn broken_links(&mut self) -> Result<BrokenLinkListResponse, ClientError> {
+        let token = self.token.clone().ok_or(ClientError::NotAuthenticated)?;
+        let request = proto::ListBrokenLinksRequest { token };
+        let response = self.blog_client.list_broken_links(request).await?;
+
+        let links = response
+            .into_inner()
+            .links
+            .into_iter()
+            .map(|link| {
+                Ok(BrokenLinkDto {
+                    post_id: link.post_id,
+                    post_public_id: link.post_public_id,
+                    post_title: link.post_title,
+                    url: link.url,
+                    status_code: link.status_code.and_then(|s| u16::try_from(s).ok()),
+                    error: link.error,
+                    checked_at: Self::parse_datetime(&link.checked_at)?,
+                })
+            })
+            .collect::<Result<Vec<_>, ClientError>>()?;
+
+        Ok(BrokenLinkListResponse { links })
+    }
+
+    /// Imports a batch of posts exported from another blog (requires authentication).
+    pub async fn import_posts(
+        &mut self,
+        req: ImportPostsRequest,
+    ) -> Result<ImportPostsResponse, ClientError> {
+        let token = self.token.clone().ok_or(ClientError::NotAuthenticated)?;
+        let request = proto::ImportPostsRequest {
+            token,
+            posts: req
+                .posts
+                .into_iter()
+                .map(|post| proto::ImportPost {
+                    title: post.title,
+                    content: post.content,
+                    tags: post.tags,
+                    author: post.author,
+                    published_at: post.published_at.map(|date| date.to_rfc3339()),
+                })
+                .collect(),
+            dry_run: req.dry_run,
+        };
+
+        let response = self.blog_client.import_posts(request).await?.into_inner();
+        if !response.dry_run {
+            self.cache.invalidate_lists();
+        }
+        Ok(ImportPostsResponse {
+            dry_run: response.dry_run,
+            imported: response.imported,
+            failed: response.failed,
+            posts: response
+                .posts
+                .into_iter()
+                .map(|post| ImportedPostDto {
+                    title: post.title,
+                    author_username: post.author_username,
+                    post_id: post.post_id,
+                    post_public_id: post.post_public_id,
+                    error: post.error,
+                })
+                .collect(),
+        })
+    }
+
+    /// Lists the revisions of a post, newest first (author or editors).
+    pub async fn list_revisions(&mut self, id: &str) -> Result<RevisionListResponse, ClientError> {
+        let token = self.token.clone().ok_or(ClientError::NotAuthenticated)?;
+        let request = proto::ListRevisionsRequest {
+            token,
+            post_id: 0,
+            post_public_id: id.to_string(),
+        };
+        let response = self.blog_client.list_revisions(request).await?;
+
+        let revisions = response
+            .into_inner()
+            .revisions
+            .into_iter()
+            .map(|revision| {
+                Ok(RevisionDto {
+                    number: revision.number,
+                    title: revision.title,
+                    created_at: Self::parse_datetime(&revision.created_at)?,
+                })
+            })
+            .collect::<Result<Vec<_>, ClientError>>()?;
+
+        Ok(RevisionListResponse { revisions })
+    }
+
+    /// Diffs revision `from` of a post against revision `to` (author or editors).
+    pub async fn diff_revisions(
+        &mut self,
+        id: &str,
+        from: i64,
+        to: i64,
+    ) -> Result<RevisionDiffResponse, ClientError> {
+        let token = self.token.clone().ok_or(ClientError::NotAuthenticated)?;
+        let request = proto::DiffRevisionsRequest {
+            token,
+            post_id: 0,
+            post_public_id: id.to_string(),
+            from,
+            to,
+        };
+        let diff = self.blog_client.diff_revisions(request).await?.into_inner();
+
+        Ok(RevisionDiffResponse {
+            post_id: diff.post_id,
+            from: diff.from,
+            to: diff.to,
+            title: Self::diff_lines_from_proto(diff.title)?,
+            content: Self::diff_lines_from_proto(diff.content)?,
+            html: diff.html,
+        })
+    }
+
+    /// Adds a comment to a post (requires authentication).
+    pub async fn create_comment(
+        &mut self,
+        post_id: &str,
+        req: CreateCommentRequest,
+    ) -> Result<CommentDto, ClientError> {
+        let token = self.token.clone().ok_or(ClientError::NotAuthenticated)?;
+        let request = proto::CreateCommentRequest {
+            token,
+            post_id: 0,
+            post_public_id: post_id.to_string(),
+            content: req.content,
+        };
+        let response = self.blog_client.create_comment(request).await?;
+        let comment = response.into_inner().comment.ok_or_else(|| {
+            ClientError::Deserialization(serde_json::Error::custom("missing comment in response"))
+        })?;
+        Self::convert_comment(comment)
+    }
+
+    /// Lists comments on a post, oldest first.
+    pub async fn list_comments(
+        &mut self,
+        post_id: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<CommentListResponse, ClientError> {
+        self.fetch_comments(proto::ListCommentsRequest {
+            post_id: 0,
+            post_public_id: post_id.to_string(),
+            limit,
+            offset,
+            cursor: String::new(),
+        })
+        .await
+    }
+
+    /// Lists a page of comments on a post, oldest first, starting after
+    /// `cursor` (the `next_cursor` of the previous page).
+    pub async fn list_comments_after(
+        &mut self,
+        post_id: &str,
+        limit: i64,
+        cursor: Option<String>,
+    ) -> Result<CommentListResponse, ClientError> {
+        self.fetch_comments(proto::ListCommentsRequest {
+            post_id: 0,
+            post_public_id: post_id.to_string(),
+            limit,
+            offset: 0,
+            cursor: cursor.unwrap_or_default(),
+        })
+        .await
+    }
+
+    /// Sends a comment list request and converts the page.
+    async fn fetch_comments(
+        &mut self,
+        message: proto::ListCommentsRequest,
+    ) -> Result<CommentListResponse, ClientError> {
+        // Only offset pages and the first cursor page are counted
+        let counted = message.cursor.is_empty();
+        let request = self.viewer_request(message);
+        let inner = self.blog_client.list_comments(request).await?.into_inner();
+
+        let comments = inner
+            .comments
+            .into_iter()
+            .map(Self::convert_comment)
+            .collect::<Result<Vec<_>, ClientError>>()?;
+        Ok(
+            CommentListResponse::new(comments, counted.then_some(inner.total))
+                .with_next_cursor(Some(inner.next_cursor).filter(|cursor| !cursor.is_empty())),
+        )
+    }
+
+    /// Deletes a comment (comment author or post author).
+    pub async fn delete_comment(&mut self, id: i64) -> Result<(), ClientError> {
+        let token = self.token.clone().ok_or(ClientError::NotAuthenticated)?;
+        let request = proto::DeleteCommentRequest { token, id };
+        self.blog_client.delete_comment(request).await?;
+        Ok(())
+    }
+
+    /// Lists a page of the current user's notifications, newest first,
+    /// starting after `cursor` (requires authentication).
+    pub async fn list_notifications(
+        &mut self,
+        limit: i64,
+        cursor: Option<String>,
+    ) -> Result<NotificationListResponse, ClientError> {
+        let token = self.token.clone().ok_or(ClientError::NotAuthenticated)?;
+        let request = proto::ListNotificationsRequest {
+            token,
+            limit,
+            cursor: cursor.unwrap_or_default(),
+        };
+        let inner = self
+            .blog_client
+            .list_notifications(request)
+            .await?
+            .into_inner();
+
+        let notifications = inner
+            .notifications
+            .into_iter()
+            .map(|notification| {
+                Ok(NotificationDto {
+                    id: notification.id,
+                    kind: notification.kind.parse().map_err(|e: String| {
+                        ClientError::Deserialization(serde_json::Error::custom(e))
+                    })?,
+                    actor_id: notification.actor_id,
+                    actor_public_id: notification.actor_public_id,
+                    actor_username: notification.actor_username,
+                    post_id: notification.post_id,
+                    post_public_id: notification.post_public_id,
+                    comment_id: notification.comment_id,
+                    read: notification.read,
+                    created_at: Self::parse_datetime(&notification.created_at)?,
+                })
+            })
+            .collect::<Result<Vec<_>, ClientError>>()?;
+
+        Ok(NotificationListResponse {
+            notifications,
+            next_cursor: Some(inner.next_cursor).filter(|cursor| !cursor.is_empty()),
+        })
+    }
+
+    /// Counts the current user's unread notifications (requires authentication).
+    pub async fn unread_notification_count(&mut self) -> Result<UnreadCountResponse, ClientError> {
+        let token = self.token.clone().ok_or(ClientError::NotAuthenticated)?;
+        let request = proto::CountUnreadNotificationsRequest { token };
+        let response = self.blog_client.count_unread_notifications(request).await?;
+        Ok(UnreadCountResponse {
+            unread: response.into_inner().unread,
+        })
+    }
+
+    /// Marks the current user's notifications as read, only up to
+    /// `up_to_id` if given (requires authentication).
+    pub async fn mark_notifications_read(
+        &mut self,
+        up_to_id: Option<i64>,
+    ) -> Result<(), ClientError> {
+        let token = self.token.clone().ok_or(ClientError::NotAuthenticated)?;
+        let request = proto::MarkNotificationsReadRequest { token, up_to_id };
+        self.blog_client.mark_notifications_read(request).await?;
+        Ok(())
+    }
+
+    /// Follows the comments on a post as they are added, posting each item
+    /// of `outgoing` as a comment on the same stream.
+    ///
+    /// Posting requires authentication; without a token the stream only
+    /// receives. It stays open until the server closes it or the returned
+    /// stream is dropped, even after `outgoing` ends.
+    pub async fn stream_comments<S>(
+        &mut self,
+        post_id: &str,
+        outgoing: S,
+    ) -> Result<
+        impl Stream<Item = Result<CommentStreamEvent, ClientError>> + Send + use<S>,
+        ClientError,
+    >
+    where
+        S: Stream<Item = String> + Send + 'static,
+    {
+        let first = proto::CommentStreamRequest {
+            token: self.token.clone().unwrap_or_default(),
+            post_id: 0,
+            content: String::new(),
+            post_public_id: post_id.to_string(),
+        };
+        let requests =
+            tokio_stream::once(first).chain(outgoing.map(|content| proto::CommentStreamRequest {
+                content,
+                ..Default::default()
+            }));
+
+        let response = self.blog_client.stream_comments(requests).await?;
+        Ok(response.into_inner().filter_map(|message| match message {
+            Ok(proto::CommentStreamResponse { event: None }) => None,
+            Ok(proto::CommentStreamResponse { event: Some(event) }) => {
+                Some(Self::convert_comment_event(event))
+            }
+            Err(status) => Some(Err(ClientError::Grpc(status))),
+        }))
+    }
+
+    /// Converts a proto comment stream event to CommentStreamEvent.
+    fn convert_comment_event(event: Event) -> Result<CommentStreamEvent, ClientError> {
+        match event {
+            Event::Comment(comment) => {
+                Ok(CommentStreamEvent::Comment(Self::convert_comment(comment)?))
+            }
+            Event::Error(message) => Ok(CommentStreamEvent::Rejected(message)),
+        }
+    }
+
+    /// Converts proto Comment to shared CommentDto.
+    fn convert_comment(comment: proto::Comment) -> Result<CommentDto, ClientError> {
+        let mut dto = CommentDto::new(
+            comment.id,
+            comment.post_id,
+            comment.author_id,
+            comment.author_username,
+            comment.content,
+            Self::parse_datetime(&comment.created_at)?,
+            Self::parse_datetime(&comment.updated_at)?,
+        );
+        dto.post_public_id = comment.post_public_id;
+        dto.author_public_id = comment.author_public_id;
+        Ok(dto)
+    }
+
     /// Converts proto AuthResponse to shared AuthResponse.
     fn convert_auth_response(response: proto::AuthResponse) -> Result<AuthResponse, ClientError> {
         let user = response.user.unwrap();
-        Ok(AuthResponse {
-            token: response.token,
-            user: UserDto {
-                id: user.id,
-                username: user.username,
-                email: user.email,
-                created_at: Self::parse_datetime(&user.created_at)?,
-            },
-        })
+        let mut dto = UserDto::new(
+            user.id,
+            user.username,
+            user.email,
+            Self::parse_datetime(&user.created_at)?,
+        );
+        dto.public_id = user.public_id;
+        dto.role = user.role.parse().unwrap_or_default();
+
+        let mut auth = AuthResponse::new(response.token, dto);
+        auth.recovery_codes = response.recovery_codes;
+        Ok(auth)
+    }
+
+    /// Wraps a public read request, adding the token as `authorization`
+    /// metadata if one is set so posts include per-viewer fields.
+    fn viewer_request<T>(&self, message: T) -> tonic::Request<T> {
+        let mut request = tonic::Request::new(message);
+        let value = self
+            .token
+            .as_ref()
+            .and_then(|token| format!("Bearer {}", token).parse().ok());
+        if let Some(value) = value {
+            request.metadata_mut().insert(AUTH_METADATA_KEY, value);
+        }
+        request
     }
 
     /// Converts proto Post to shared PostDto.
     fn convert_post(post: proto::Post) -> Result<PostDto, ClientError> {
-        Ok(PostDto {
-            id: post.id,
-            title: post.title,
-            content: post.content,
-            author_id: post.author_id,
-            author_username: post.author_username,
-            created_at: Self::parse_datetime(&post.created_at)?,
-            updated_at: Self::parse_datetime(&post.updated_at)?,
-        })
+        let author = match post.org {
+            Some(org) => PostAuthor::Org {
+                id: org.id,
+                slug: org.slug,
+                name: org.name,
+            },
+            None => PostAuthor::User {
+                id: post.author_id,
+                username: post.author_username.clone(),
+            },
+        };
+        let mut dto = PostDto::new(
+            post.id,
+            post.title,
+            post.content,
+            post.author_id,
+            post.author_username,
+            Self::parse_datetime(&post.created_at)?,
+            Self::parse_datetime(&post.updated_at)?,
+        );
+        dto.public_id = post.public_id;
+        dto.author_public_id = post.author_public_id;
+        dto.lang = post.lang;
+        dto.translation_group_id = post.translation_group_id;
+        dto.canonical_url = post.canonical_url;
+        dto.original_source = post.original_source;
+        // Servers that predate custom fields send an empty string
+        dto.metadata = serde_json::from_str(&post.metadata).unwrap_or_default();
+        dto.status = post.status.parse().unwrap_or_default();
+        dto.content_html = post.content_html;
+        dto.author = Some(author);
+        dto.tags = post.tags;
+        dto.link_previews = post
+            .link_previews
+            .into_iter()
+            .map(|preview| LinkPreviewDto {
+                url: preview.url,
+                title: preview.title,
+                description: preview.description,
+                image_url: preview.image_url,
+                site_name: preview.site_name,
+            })
+            .collect();
+        dto.is_owner = post.is_owner;
+        Ok(dto)
     }
 
     /// Parses ISO 8601 datetime string.
+    fn diff_lines_from_proto(lines: Vec<proto::DiffLine>) -> Result<Vec<DiffLineDto>, ClientError> {
+        lines
+            .into_iter()
+            .map(|line| {
+                Ok(DiffLineDto {
+                    op: line.op.parse().map_err(|e: String| {
+                        ClientError::Deserialization(serde_json::Error::custom(e))
+                    })?,
+                    text: line.text,
+                    old_line: line.old_line,
+                    new_line: line.new_line,
+                })
+            })
+            .collect()
+    }
+
     fn parse_datetime(s: &str) -> Result<DateTime<Utc>, ClientError> {
         DateTime::parse_from_rfc3339(s)
             .map(|dt| dt.with_timezone(&Utc))
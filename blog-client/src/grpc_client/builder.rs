@@ -0,0 +1,110 @@
+//! Builder for gRPC clients with custom channel settings.
+
+use std::time::Duration;
+
+use tonic::transport::{Channel, Endpoint};
+
+use super::GrpcClient;
+use crate::{CachePolicy, ClientError};
+
+/// Configures and connects a [`GrpcClient`]: channel settings, and
+/// balancing calls over several server replicas.
+#[derive(Debug, Clone, Default)]
+pub struct GrpcClientBuilder {
+    endpoints: Vec<String>,
+    concurrency_limit: Option<usize>,
+    keep_alive_interval: Option<Duration>,
+    keep_alive_timeout: Option<Duration>,
+    keep_alive_while_idle: bool,
+    cache_policy: CachePolicy,
+}
+
+impl GrpcClientBuilder {
+    /// Creates a builder with no endpoints and tonic's default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a server address. With more than one, calls are balanced over
+    /// all of them.
+    pub fn endpoint(mut self, addr: impl Into<String>) -> Self {
+        self.endpoints.push(addr.into());
+        self
+    }
+
+    /// Limits the requests in flight to each server.
+    pub fn concurrency_limit(mut self, limit: usize) -> Self {
+        self.concurrency_limit = Some(limit);
+        self
+    }
+
+    /// Sends HTTP/2 keepalive pings at this interval, so dead connections
+    /// are noticed and idle ones survive proxies.
+    pub fn keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.keep_alive_interval = Some(interval);
+        self
+    }
+
+    /// Drops a connection whose keepalive ping is not answered in time.
+    pub fn keep_alive_timeout(mut self, timeout: Duration) -> Self {
+        self.keep_alive_timeout = Some(timeout);
+        self
+    }
+
+    /// Keeps pinging while no requests are in flight.
+    pub fn keep_alive_while_idle(mut self, enabled: bool) -> Self {
+        self.keep_alive_while_idle = enabled;
+        self
+    }
+
+    /// Sets how `get_post` and `list_posts` responses are cached.
+    pub fn cache_policy(mut self, policy: CachePolicy) -> Self {
+        self.cache_policy = policy;
+        self
+    }
+
+    /// Connects the client.
+    ///
+    /// A single endpoint is connected right away, so an unreachable server
+    /// fails here. Several endpoints are connected lazily and calls are
+    /// spread over them; a call that lands on a server that is down fails
+    /// with `UNAVAILABLE` (see [`ClientError::is_retryable`]).
+    pub async fn build(self) -> Result<GrpcClient, ClientError> {
+        let mut endpoints = self
+            .endpoints
+            .iter()
+            .map(|addr| self.configure(addr))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let channel = match endpoints.len() {
+            0 => return Err(ClientError::InvalidUrl("no endpoints".to_string())),
+            1 => endpoints
+                .remove(0)
+                .connect()
+                .await
+                .map_err(|e| ClientError::Grpc(tonic::Status::from_error(Box::new(e))))?,
+            _ => Channel::balance_list(endpoints.into_iter()),
+        };
+
+        let mut client = GrpcClient::from_channel(channel);
+        client.set_cache_policy(self.cache_policy);
+        Ok(client)
+    }
+
+    /// Parses an address into an endpoint with the configured settings.
+    fn configure(&self, addr: &str) -> Result<Endpoint, ClientError> {
+        let mut endpoint = Channel::from_shared(addr.to_string())
+            .map_err(|e| ClientError::InvalidUrl(e.to_string()))?
+            .keep_alive_while_idle(self.keep_alive_while_idle);
+        if let Some(limit) = self.concurrency_limit {
+            endpoint = endpoint.concurrency_limit(limit);
+        }
+        if let Some(interval) = self.keep_alive_interval {
+            endpoint = endpoint.http2_keep_alive_interval(interval);
+        }
+        if let Some(timeout) = self.keep_alive_timeout {
+            endpoint = endpoint.keep_alive_timeout(timeout);
+        }
+        Ok(endpoint)
+    }
+}
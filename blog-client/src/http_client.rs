@@ -1,20 +1,31 @@
 //! HTTP client for the blog API.
 
-use reqwest::Client;
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use reqwest::{Client, Method};
+use serde::Serialize;
+use serde::de::Error as _;
 
+use blog_shared::constants::MSGPACK_CONTENT_TYPE;
+use blog_shared::endpoint::paths;
 use blog_shared::{
-    AuthResponse, CreatePostRequest, LoginRequest, PostDto, PostListResponse, RegisterRequest,
-    UpdatePostRequest,
+    ApiBaseUrl, AuthResponse, BrokenLinkListResponse, CommentDto, CommentListResponse,
+    CreateCommentRequest, CreatePostRequest, CursorPage, ImportPostsRequest, ImportPostsResponse,
+    LoginRequest, MarkNotificationsReadRequest, NotificationListResponse, Pagination, PostDto,
+    PostListResponse, RegisterRequest, RevisionDiffResponse, RevisionListResponse,
+    TrashListResponse, UnreadCountResponse, UpdatePostRequest,
 };
 
-use crate::ClientError;
+use crate::cache::{CachePolicy, ResponseCache};
+use crate::{ClientError, WireFormat};
 
 /// HTTP client for the blog API.
 #[derive(Clone)]
 pub struct HttpClient {
     client: Client,
-    base_url: String,
+    base_url: ApiBaseUrl,
     token: Option<String>,
+    cache: ResponseCache,
+    wire_format: WireFormat,
 }
 
 impl HttpClient {
@@ -22,19 +33,24 @@ impl HttpClient {
     pub fn new(base_url: &str) -> Self {
         Self {
             client: Client::new(),
-            base_url: base_url.trim_end_matches('/').to_string(),
+            base_url: ApiBaseUrl::new(base_url),
             token: None,
+            cache: ResponseCache::default(),
+            wire_format: WireFormat::default(),
         }
     }
 
-    /// Sets the authentication token.
+    /// Sets the authentication token. Cached responses are dropped, as
+    /// posts carry per-viewer fields.
     pub fn set_token(&mut self, token: String) {
         self.token = Some(token);
+        self.cache.clear();
     }
 
-    /// Clears the authentication token.
+    /// Clears the authentication token and cached responses.
     pub fn clear_token(&mut self) {
         self.token = None;
+        self.cache.clear();
     }
 
     /// Returns the current token if set.
@@ -42,36 +58,62 @@ impl HttpClient {
         self.token.as_deref()
     }
 
+    /// Sets how `get_post` and `list_posts` responses are cached, dropping
+    /// anything cached so far. Clones made afterwards share the cache.
+    pub fn set_cache_policy(&mut self, policy: CachePolicy) {
+        self.cache.set_policy(policy);
+    }
+
+    /// Drops all cached responses.
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+    }
+
+    /// Sets the body encoding of requests and responses.
+    pub fn set_wire_format(&mut self, format: WireFormat) {
+        self.wire_format = format;
+    }
+
     /// Registers a new user.
     pub async fn register(&self, req: RegisterRequest) -> Result<AuthResponse, ClientError> {
-        let url = format!("{}/api/auth/register", self.base_url);
-        let response = self.client.post(&url).json(&req).send().await?;
+        let url = self.base_url.join(&paths::register());
+        let request = self.request(Method::POST, &url);
+        let response = self.encode_body(request, &req)?.send().await?;
         self.handle_response(response).await
     }
 
     /// Logs in an existing user.
     pub async fn login(&self, req: LoginRequest) -> Result<AuthResponse, ClientError> {
-        let url = format!("{}/api/auth/login", self.base_url);
-        let response = self.client.post(&url).json(&req).send().await?;
+        let url = self.base_url.join(&paths::login());
+        let request = self.request(Method::POST, &url);
+        let response = self.encode_body(request, &req)?.send().await?;
         self.handle_response(response).await
     }
 
     /// Creates a new post (requires authentication).
     pub async fn create_post(&self, req: CreatePostRequest) -> Result<PostDto, ClientError> {
-        let url = format!("{}/api/posts", self.base_url);
+        let url = self.base_url.join(&paths::posts());
+        let request = self.authorized_request(self.request(Method::POST, &url))?;
+        let response = self.encode_body(request, &req)?.send().await?;
+        let post: PostDto = self.handle_response(response).await?;
+        self.cache.invalidate_lists();
+        Ok(post)
+    }
+
+    /// Gets a post by its public ID.
+    pub async fn get_post(&self, id: &str) -> Result<PostDto, ClientError> {
+        if let Some(post) = self.cache.post(id) {
+            return Ok(post);
+        }
+
+        let url = self.base_url.join(&paths::post(id));
         let response = self
-            .authorized_request(self.client.post(&url))?
-            .json(&req)
+            .viewer_request(self.request(Method::GET, &url))
             .send()
             .await?;
-        self.handle_response(response).await
-    }
-
-    /// Gets a post by ID.
-    pub async fn get_post(&self, id: i64) -> Result<PostDto, ClientError> {
-        let url = format!("{}/api/posts/{}", self.base_url, id);
-        let response = self.client.get(&url).send().await?;
-        self.handle_response(response).await
+        let post = self.handle_response(response).await?;
+        self.cache.store_post(&post);
+        Ok(post)
     }
 
     /// Lists posts with pagination.
@@ -80,34 +122,186 @@ impl HttpClient {
         limit: i64,
         offset: i64,
     ) -> Result<PostListResponse, ClientError> {
-        let url = format!(
-            "{}/api/posts?limit={}&offset={}",
-            self.base_url, limit, offset
-        );
-        let response = self.client.get(&url).send().await?;
-        self.handle_response(response).await
+        if let Some(list) = self.cache.list(limit, offset) {
+            return Ok(list);
+        }
+
+        let url = self
+            .base_url
+            .join(&paths::posts_page(Pagination { limit, offset }));
+        let response = self
+            .viewer_request(self.request(Method::GET, &url))
+            .send()
+            .await?;
+        let list = self.handle_response(response).await?;
+        self.cache.store_list(limit, offset, &list);
+        Ok(list)
     }
 
     /// Updates a post (author only).
     pub async fn update_post(
         &self,
-        id: i64,
+        id: &str,
         req: UpdatePostRequest,
     ) -> Result<PostDto, ClientError> {
-        let url = format!("{}/api/posts/{}", self.base_url, id);
+        let url = self.base_url.join(&paths::post(id));
+        let request = self.authorized_request(self.request(Method::PUT, &url))?;
+        let response = self.encode_body(request, &req)?.send().await?;
+        let post = self.handle_response(response).await?;
+        self.cache.invalidate_post(id);
+        Ok(post)
+    }
+
+    /// Deletes a post (author only).
+    pub async fn delete_post(&self, id: &str) -> Result<(), ClientError> {
+        let url = self.base_url.join(&paths::post(id));
         let response = self
-            .authorized_request(self.client.put(&url))?
-            .json(&req)
+            .authorized_request(self.request(Method::DELETE, &url))?
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            self.cache.invalidate_post(id);
+            Ok(())
+        } else {
+            let status = response.status().as_u16();
+            let body = response.text().await.unwrap_or_default();
+            Err(ClientError::from_response(status, body))
+        }
+    }
+
+    /// Lists the current user's deleted posts (requires authentication).
+    pub async fn list_trash(&self) -> Result<TrashListResponse, ClientError> {
+        let url = self.base_url.join(&paths::trash());
+        let response = self
+            .authorized_request(self.request(Method::GET, &url))?
             .send()
             .await?;
         self.handle_response(response).await
     }
 
-    /// Deletes a post (author only).
-    pub async fn delete_post(&self, id: i64) -> Result<(), ClientError> {
-        let url = format!("{}/api/posts/{}", self.base_url, id);
+    /// Takes a post out of the trash (author only).
+    pub async fn restore_post(&self, id: &str) -> Result<(), ClientError> {
+        let url = self.base_url.join(&paths::post_restore(id));
+        let response = self
+            .authorized_request(self.request(Method::POST, &url))?
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            self.cache.invalidate_post(id);
+            Ok(())
+        } else {
+            let status = response.status().as_u16();
+            let body = response.text().await.unwrap_or_default();
+            Err(ClientError::from_response(status, body))
+        }
+    }
+
+    /// Lists dead links in the current user's posts (requires authentication).
+    pub async fn broken_links(&self) -> Result<BrokenLinkListResponse, ClientError> {
+        let url = self.base_url.join(&paths::broken_links());
+        let response = self
+            .authorized_request(self.request(Method::GET, &url))?
+            .send()
+            .await?;
+        self.handle_response(response).await
+    }
+
+    /// Imports a batch of posts exported from another blog (requires authentication).
+    pub async fn import_posts(
+        &self,
+        req: ImportPostsRequest,
+    ) -> Result<ImportPostsResponse, ClientError> {
+        let url = self.base_url.join(&paths::import_posts());
+        let request = self.authorized_request(self.request(Method::POST, &url))?;
+        let response = self.encode_body(request, &req)?.send().await?;
+        let report: ImportPostsResponse = self.handle_response(response).await?;
+        if !report.dry_run {
+            self.cache.invalidate_lists();
+        }
+        Ok(report)
+    }
+
+    /// Lists the revisions of a post, newest first (author or editors).
+    pub async fn list_revisions(&self, id: &str) -> Result<RevisionListResponse, ClientError> {
+        let url = self.base_url.join(&paths::post_revisions(id));
+        let response = self
+            .authorized_request(self.request(Method::GET, &url))?
+            .send()
+            .await?;
+        self.handle_response(response).await
+    }
+
+    /// Diffs revision `from` of a post against revision `to` (author or editors).
+    pub async fn diff_revisions(
+        &self,
+        id: &str,
+        from: i64,
+        to: i64,
+    ) -> Result<RevisionDiffResponse, ClientError> {
+        let url = self.base_url.join(&paths::revision_diff(id, from, to));
         let response = self
-            .authorized_request(self.client.delete(&url))?
+            .authorized_request(self.request(Method::GET, &url))?
+            .send()
+            .await?;
+        self.handle_response(response).await
+    }
+
+    /// Adds a comment to a post (requires authentication).
+    pub async fn create_comment(
+        &self,
+        post_id: &str,
+        req: CreateCommentRequest,
+    ) -> Result<CommentDto, ClientError> {
+        let url = self.base_url.join(&paths::post_comments(post_id));
+        let request = self.authorized_request(self.request(Method::POST, &url))?;
+        let response = self.encode_body(request, &req)?.send().await?;
+        self.handle_response(response).await
+    }
+
+    /// Lists comments on a post, oldest first.
+    pub async fn list_comments(
+        &self,
+        post_id: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<CommentListResponse, ClientError> {
+        let url = self.base_url.join(&paths::post_comments_page(
+            post_id,
+            Pagination { limit, offset },
+        ));
+        let response = self
+            .viewer_request(self.request(Method::GET, &url))
+            .send()
+            .await?;
+        self.handle_response(response).await
+    }
+
+    /// Lists a page of comments on a post, oldest first, starting after
+    /// `cursor` (the `next_cursor` of the previous page).
+    pub async fn list_comments_after(
+        &self,
+        post_id: &str,
+        limit: i64,
+        cursor: Option<String>,
+    ) -> Result<CommentListResponse, ClientError> {
+        let url = self.base_url.join(&paths::post_comments_after(
+            post_id,
+            &CursorPage { limit, cursor },
+        ));
+        let response = self
+            .viewer_request(self.request(Method::GET, &url))
+            .send()
+            .await?;
+        self.handle_response(response).await
+    }
+
+    /// Deletes a comment (comment author or post author).
+    pub async fn delete_comment(&self, id: i64) -> Result<(), ClientError> {
+        let url = self.base_url.join(&paths::comment(id));
+        let response = self
+            .authorized_request(self.request(Method::DELETE, &url))?
             .send()
             .await?;
 
@@ -115,8 +309,54 @@ impl HttpClient {
             Ok(())
         } else {
             let status = response.status().as_u16();
-            let message = response.text().await.unwrap_or_default();
-            Err(ClientError::Server { status, message })
+            let body = response.text().await.unwrap_or_default();
+            Err(ClientError::from_response(status, body))
+        }
+    }
+
+    /// Lists a page of the current user's notifications, newest first,
+    /// starting after `cursor` (requires authentication).
+    pub async fn list_notifications(
+        &self,
+        limit: i64,
+        cursor: Option<String>,
+    ) -> Result<NotificationListResponse, ClientError> {
+        let url = self
+            .base_url
+            .join(&paths::notifications_page(&CursorPage { limit, cursor }));
+        let response = self
+            .authorized_request(self.request(Method::GET, &url))?
+            .send()
+            .await?;
+        self.handle_response(response).await
+    }
+
+    /// Counts the current user's unread notifications (requires authentication).
+    pub async fn unread_notification_count(&self) -> Result<UnreadCountResponse, ClientError> {
+        let url = self.base_url.join(&paths::unread_notifications());
+        let response = self
+            .authorized_request(self.request(Method::GET, &url))?
+            .send()
+            .await?;
+        self.handle_response(response).await
+    }
+
+    /// Marks the current user's notifications as read, only up to
+    /// `up_to_id` if given (requires authentication).
+    pub async fn mark_notifications_read(&self, up_to_id: Option<i64>) -> Result<(), ClientError> {
+        let url = self.base_url.join(&paths::notifications_read());
+        let request = self.authorized_request(self.request(Method::POST, &url))?;
+        let response = self
+            .encode_body(request, &MarkNotificationsReadRequest { up_to_id })?
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            let status = response.status().as_u16();
+            let body = response.text().await.unwrap_or_default();
+            Err(ClientError::from_response(status, body))
         }
     }
 
@@ -129,18 +369,62 @@ impl HttpClient {
         Ok(builder.header("Authorization", format!("Bearer {}", token)))
     }
 
-    /// Handles response, extracting JSON or error.
+    /// Adds the authorization header if a token is set, so public reads
+    /// include per-viewer fields.
+    fn viewer_request(&self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.token {
+            Some(token) => builder.header("Authorization", format!("Bearer {}", token)),
+            None => builder,
+        }
+    }
+
+    /// Starts a request, asking for MessagePack responses if that is the
+    /// wire format.
+    fn request(&self, method: Method, url: &str) -> reqwest::RequestBuilder {
+        let builder = self.client.request(method, url);
+        match self.wire_format {
+            WireFormat::Json => builder,
+            WireFormat::MessagePack => builder.header(ACCEPT, MSGPACK_CONTENT_TYPE),
+        }
+    }
+
+    /// Sets the request body, encoded in the wire format.
+    fn encode_body<T: Serialize>(
+        &self,
+        builder: reqwest::RequestBuilder,
+        body: &T,
+    ) -> Result<reqwest::RequestBuilder, ClientError> {
+        Ok(match self.wire_format {
+            WireFormat::Json => builder.json(body),
+            WireFormat::MessagePack => builder
+                .header(CONTENT_TYPE, MSGPACK_CONTENT_TYPE)
+                .body(rmp_serde::to_vec_named(body)?),
+        })
+    }
+
+    /// Handles response, decoding a JSON or MessagePack body, or the error.
     async fn handle_response<T: serde::de::DeserializeOwned>(
         &self,
         response: reqwest::Response,
     ) -> Result<T, ClientError> {
         if response.status().is_success() {
-            let body = response.text().await?;
-            Ok(serde_json::from_str(&body)?)
+            let is_msgpack = response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|value| value.starts_with(MSGPACK_CONTENT_TYPE));
+            let body = response.bytes().await?;
+            if is_msgpack {
+                rmp_serde::from_slice(&body).map_err(|e| {
+                    ClientError::Deserialization(serde_json::Error::custom(e.to_string()))
+                })
+            } else {
+                Ok(serde_json::from_slice(&body)?)
+            }
         } else {
             let status = response.status().as_u16();
-            let message = response.text().await.unwrap_or_default();
-            Err(ClientError::Server { status, message })
+            let body = response.text().await.unwrap_or_default();
+            Err(ClientError::from_response(status, body))
         }
     }
 }
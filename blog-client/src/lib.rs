@@ -1,21 +1,33 @@
 //! Blog client library.
 //!
-//! Provides HTTP and gRPC clients for the blog API.
+//! Provides HTTP and gRPC clients for the blog API, and blocking versions
+//! of them in [`blocking`].
 
+pub mod blocking;
+mod cache;
+mod constants;
 mod error;
 mod grpc_client;
 mod http_client;
+mod pagination;
+mod wire_format;
 
+pub use cache::CachePolicy;
 pub use error::ClientError;
-pub use grpc_client::GrpcClient;
+pub use grpc_client::{CommentStreamEvent, GrpcClient, GrpcClientBuilder};
 pub use http_client::HttpClient;
+pub use pagination::{CommentPages, CursorPages, NotificationPages, PostPages};
+pub use wire_format::WireFormat;
 
 use blog_shared::{
-    AuthResponse, CreatePostRequest, LoginRequest, PostDto, PostListResponse, RegisterRequest,
-    UpdatePostRequest,
+    AuthResponse, BrokenLinkListResponse, CommentDto, CommentListResponse, CreateCommentRequest,
+    CreatePostRequest, ImportPostsRequest, ImportPostsResponse, LoginRequest,
+    NotificationListResponse, PostDto, PostListResponse, RegisterRequest, RevisionDiffResponse,
+    RevisionListResponse, TrashListResponse, UnreadCountResponse, UpdatePostRequest,
 };
 
 /// Unified blog client supporting both HTTP and gRPC transports.
+#[derive(Clone)]
 pub enum BlogClient {
     /// HTTP client variant.
     Http(HttpClient),
@@ -58,6 +70,30 @@ impl BlogClient {
         }
     }
 
+    /// Sets how `get_post` and `list_posts` responses are cached.
+    pub fn set_cache_policy(&mut self, policy: CachePolicy) {
+        match self {
+            Self::Http(client) => client.set_cache_policy(policy),
+            Self::Grpc(client) => client.set_cache_policy(policy),
+        }
+    }
+
+    /// Drops all cached responses.
+    pub fn clear_cache(&self) {
+        match self {
+            Self::Http(client) => client.clear_cache(),
+            Self::Grpc(client) => client.clear_cache(),
+        }
+    }
+
+    /// Sets the body encoding of HTTP requests and responses. gRPC always
+    /// uses protobuf, so this does nothing there.
+    pub fn set_wire_format(&mut self, format: WireFormat) {
+        if let Self::Http(client) = self {
+            client.set_wire_format(format);
+        }
+    }
+
     /// Registers a new user.
     pub async fn register(&mut self, req: RegisterRequest) -> Result<AuthResponse, ClientError> {
         match self {
@@ -82,8 +118,8 @@ impl BlogClient {
         }
     }
 
-    /// Gets a post by ID.
-    pub async fn get_post(&mut self, id: i64) -> Result<PostDto, ClientError> {
+    /// Gets a post by its public ID.
+    pub async fn get_post(&mut self, id: &str) -> Result<PostDto, ClientError> {
         match self {
             Self::Http(client) => client.get_post(id).await,
             Self::Grpc(client) => client.get_post(id).await,
@@ -102,10 +138,16 @@ impl BlogClient {
         }
     }
 
+    /// Streams every post, fetching `limit` posts per request as the stream
+    /// is consumed. The stream uses a clone of this client.
+    pub fn posts_iter(&self, limit: i64) -> PostPages {
+        PostPages::new(self.clone(), limit)
+    }
+
     /// Updates a post (author only).
     pub async fn update_post(
         &mut self,
-        id: i64,
+        id: &str,
         req: UpdatePostRequest,
     ) -> Result<PostDto, ClientError> {
         match self {
@@ -115,10 +157,160 @@ impl BlogClient {
     }
 
     /// Deletes a post (author only).
-    pub async fn delete_post(&mut self, id: i64) -> Result<(), ClientError> {
+    pub async fn delete_post(&mut self, id: &str) -> Result<(), ClientError> {
         match self {
             Self::Http(client) => client.delete_post(id).await,
             Self::Grpc(client) => client.delete_post(id).await,
         }
     }
+
+    /// Lists the current user's deleted posts (requires authentication).
+    pub async fn list_trash(&mut self) -> Result<TrashListResponse, ClientError> {
+        match self {
+            Self::Http(client) => client.list_trash().await,
+            Self::Grpc(client) => client.list_trash().await,
+        }
+    }
+
+    /// Takes a post out of the trash (author only).
+    pub async fn restore_post(&mut self, id: &str) -> Result<(), ClientError> {
+        match self {
+            Self::Http(client) => client.restore_post(id).await,
+            Self::Grpc(client) => client.restore_post(id).await,
+        }
+    }
+
+    /// Lists dead links in the current user's posts (requires authentication).
+    pub async fn broken_links(&mut self) -> Result<BrokenLinkListResponse, ClientError> {
+        match self {
+            Self::Http(client) => client.broken_links().await,
+            Self::Grpc(client) => client.broken_links().await,
+        }
+    }
+
+    /// Imports a batch of posts exported from another blog (requires authentication).
+    pub async fn import_posts(
+        &mut self,
+        req: ImportPostsRequest,
+    ) -> Result<ImportPostsResponse, ClientError> {
+        match self {
+            Self::Http(client) => client.import_posts(req).await,
+            Self::Grpc(client) => client.import_posts(req).await,
+        }
+    }
+
+    /// Lists the revisions of a post, newest first (author or editors).
+    pub async fn list_revisions(&mut self, id: &str) -> Result<RevisionListResponse, ClientError> {
+        match self {
+            Self::Http(client) => client.list_revisions(id).await,
+            Self::Grpc(client) => client.list_revisions(id).await,
+        }
+    }
+
+    /// Diffs revision `from` of a post against revision `to` (author or editors).
+    pub async fn diff_revisions(
+        &mut self,
+        id: &str,
+        from: i64,
+        to: i64,
+    ) -> Result<RevisionDiffResponse, ClientError> {
+        match self {
+            Self::Http(client) => client.diff_revisions(id, from, to).await,
+            Self::Grpc(client) => client.diff_revisions(id, from, to).await,
+        }
+    }
+
+    /// Adds a comment to a post (requires authentication).
+    pub async fn create_comment(
+        &mut self,
+        post_id: &str,
+        req: CreateCommentRequest,
+    ) -> Result<CommentDto, ClientError> {
+        match self {
+            Self::Http(client) => client.create_comment(post_id, req).await,
+            Self::Grpc(client) => client.create_comment(post_id, req).await,
+        }
+    }
+
+    /// Lists comments on a post, oldest first.
+    pub async fn list_comments(
+        &mut self,
+        post_id: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<CommentListResponse, ClientError> {
+        match self {
+            Self::Http(client) => client.list_comments(post_id, limit, offset).await,
+            Self::Grpc(client) => client.list_comments(post_id, limit, offset).await,
+        }
+    }
+
+    /// Lists a page of comments on a post, oldest first, starting after
+    /// `cursor` (the `next_cursor` of the previous page).
+    pub async fn list_comments_after(
+        &mut self,
+        post_id: &str,
+        limit: i64,
+        cursor: Option<String>,
+    ) -> Result<CommentListResponse, ClientError> {
+        match self {
+            Self::Http(client) => client.list_comments_after(post_id, limit, cursor).await,
+            Self::Grpc(client) => client.list_comments_after(post_id, limit, cursor).await,
+        }
+    }
+
+    /// Streams every comment on a post, fetching `limit` comments per
+    /// request as the stream is consumed. The stream uses a clone of this
+    /// client.
+    pub fn comments_iter(&self, post_id: &str, limit: i64) -> CommentPages {
+        CommentPages::comments(self.clone(), post_id, limit)
+    }
+
+    /// Deletes a comment (comment author or post author).
+    pub async fn delete_comment(&mut self, id: i64) -> Result<(), ClientError> {
+        match self {
+            Self::Http(client) => client.delete_comment(id).await,
+            Self::Grpc(client) => client.delete_comment(id).await,
+        }
+    }
+
+    /// Lists a page of the current user's notifications, newest first,
+    /// starting after `cursor` (requires authentication).
+    pub async fn list_notifications(
+        &mut self,
+        limit: i64,
+        cursor: Option<String>,
+    ) -> Result<NotificationListResponse, ClientError> {
+        match self {
+            Self::Http(client) => client.list_notifications(limit, cursor).await,
+            Self::Grpc(client) => client.list_notifications(limit, cursor).await,
+        }
+    }
+
+    /// Streams every notification of the current user, fetching `limit`
+    /// per request as the stream is consumed (requires authentication).
+    /// The stream uses a clone of this client.
+    pub fn notifications_iter(&self, limit: i64) -> NotificationPages {
+        NotificationPages::notifications(self.clone(), limit)
+    }
+
+    /// Counts the current user's unread notifications (requires authentication).
+    pub async fn unread_notification_count(&mut self) -> Result<UnreadCountResponse, ClientError> {
+        match self {
+            Self::Http(client) => client.unread_notification_count().await,
+            Self::Grpc(client) => client.unread_notification_count().await,
+        }
+    }
+
+    /// Marks the current user's notifications as read, only up to
+    /// `up_to_id` if given (requires authentication).
+    pub async fn mark_notifications_read(
+        &mut self,
+        up_to_id: Option<i64>,
+    ) -> Result<(), ClientError> {
+        match self {
+            Self::Http(client) => client.mark_notifications_read(up_to_id).await,
+            Self::Grpc(client) => client.mark_notifications_read(up_to_id).await,
+        }
+    }
 }
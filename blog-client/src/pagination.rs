@@ -0,0 +1,208 @@
+//! Streams over every post, comment or notification, fetched a page at a
+//! time.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio_stream::Stream;
+
+use blog_shared::{CommentDto, NotificationDto, PostDto, PostListResponse};
+
+use crate::{BlogClient, ClientError};
+
+/// A page request in flight, handing the client back with its result.
+type PageFuture =
+    Pin<Box<dyn Future<Output = (BlogClient, Result<PostListResponse, ClientError>)> + Send>>;
+
+/// Stream of all posts, newest first, returned by [`BlogClient::posts_iter`].
+///
+/// Pages are fetched by offset as the stream is consumed, so posts created
+/// or deleted meanwhile may shift a post into the next page (seen twice) or
+/// out of it (skipped). The stream ends after a short page, or after the
+/// first error.
+pub struct PostPages {
+    limit: i64,
+    offset: i64,
+    buffered: VecDeque<PostDto>,
+    state: State,
+}
+
+enum State {
+    Idle(BlogClient),
+    Fetching(PageFuture),
+    Done,
+}
+
+impl PostPages {
+    /// Starts at the first page, fetching `limit` posts per request.
+    pub(crate) fn new(client: BlogClient, limit: i64) -> Self {
+        Self {
+            limit: limit.max(1),
+            offset: 0,
+            buffered: VecDeque::new(),
+            state: State::Idle(client),
+        }
+    }
+}
+
+impl Stream for PostPages {
+    type Item = Result<PostDto, ClientError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if let Some(post) = this.buffered.pop_front() {
+                return Poll::Ready(Some(Ok(post)));
+            }
+
+            match std::mem::replace(&mut this.state, State::Done) {
+                State::Idle(mut client) => {
+                    let (limit, offset) = (this.limit, this.offset);
+                    this.state = State::Fetching(Box::pin(async move {
+                        let page = client.list_posts(limit, offset).await;
+                        (client, page)
+                    }));
+                }
+                State::Fetching(mut page) => match page.as_mut().poll(cx) {
+                    Poll::Pending => {
+                        this.state = State::Fetching(page);
+                        return Poll::Pending;
+                    }
+                    Poll::Ready((client, Ok(page))) => {
+                        let count = page.posts.len() as i64;
+                        this.offset += count;
+                        if count == this.limit && this.offset < page.total {
+                            this.state = State::Idle(client);
+                        }
+                        this.buffered.extend(page.posts);
+                    }
+                    Poll::Ready((_, Err(e))) => return Poll::Ready(Some(Err(e))),
+                },
+                State::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+/// A cursor page request in flight, handing the client back with the items
+/// and the cursor of the next page.
+type CursorPageFuture<T> = Pin<
+    Box<dyn Future<Output = (BlogClient, Result<(Vec<T>, Option<String>), ClientError>)> + Send>,
+>;
+
+/// Starts the request for the `limit` items after a cursor.
+type FetchPage<T> = Box<dyn Fn(BlogClient, i64, Option<String>) -> CursorPageFuture<T> + Send>;
+
+/// Stream of all comments on a post, oldest first, returned by
+/// [`BlogClient::comments_iter`].
+pub type CommentPages = CursorPages<CommentDto>;
+
+/// Stream of all the current user's notifications, newest first, returned
+/// by [`BlogClient::notifications_iter`].
+pub type NotificationPages = CursorPages<NotificationDto>;
+
+/// Stream of items fetched by cursor, see [`CommentPages`] and
+/// [`NotificationPages`].
+///
+/// Each page starts after the last item of the previous one, so items added
+/// or removed meanwhile never make the stream repeat or skip another item.
+/// The stream ends after the last page, or after the first error.
+pub struct CursorPages<T> {
+    limit: i64,
+    fetch: FetchPage<T>,
+    buffered: VecDeque<T>,
+    state: CursorState<T>,
+}
+
+enum CursorState<T> {
+    Idle(BlogClient, Option<String>),
+    Fetching(CursorPageFuture<T>),
+    Done,
+}
+
+impl<T> CursorPages<T> {
+    /// Starts at the first page, fetching `limit` items per request.
+    fn new(client: BlogClient, limit: i64, fetch: FetchPage<T>) -> Self {
+        Self {
+            limit: limit.max(1),
+            fetch,
+            buffered: VecDeque::new(),
+            state: CursorState::Idle(client, None),
+        }
+    }
+}
+
+impl CommentPages {
+    /// Streams the comments on `post_id`.
+    pub(crate) fn comments(client: BlogClient, post_id: &str, limit: i64) -> Self {
+        let post_id = post_id.to_string();
+        Self::new(
+            client,
+            limit,
+            Box::new(move |mut client, limit, cursor| {
+                let post_id = post_id.clone();
+                Box::pin(async move {
+                    let page = client
+                        .list_comments_after(&post_id, limit, cursor)
+                        .await
+                        .map(|page| (page.comments, page.next_cursor));
+                    (client, page)
+                })
+            }),
+        )
+    }
+}
+
+impl NotificationPages {
+    /// Streams the current user's notifications.
+    pub(crate) fn notifications(client: BlogClient, limit: i64) -> Self {
+        Self::new(
+            client,
+            limit,
+            Box::new(|mut client, limit, cursor| {
+                Box::pin(async move {
+                    let page = client
+                        .list_notifications(limit, cursor)
+                        .await
+                        .map(|page| (page.notifications, page.next_cursor));
+                    (client, page)
+                })
+            }),
+        )
+    }
+}
+
+impl<T: Unpin> Stream for CursorPages<T> {
+    type Item = Result<T, ClientError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if let Some(item) = this.buffered.pop_front() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+
+            match std::mem::replace(&mut this.state, CursorState::Done) {
+                CursorState::Idle(client, cursor) => {
+                    this.state = CursorState::Fetching((this.fetch)(client, this.limit, cursor));
+                }
+                CursorState::Fetching(mut page) => match page.as_mut().poll(cx) {
+                    Poll::Pending => {
+                        this.state = CursorState::Fetching(page);
+                        return Poll::Pending;
+                    }
+                    Poll::Ready((client, Ok((items, next_cursor)))) => {
+                        if let Some(cursor) = next_cursor {
+                            this.state = CursorState::Idle(client, Some(cursor));
+                        }
+                        this.buffered.extend(items);
+                    }
+                    Poll::Ready((_, Err(e))) => return Poll::Ready(Some(Err(e))),
+                },
+                CursorState::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
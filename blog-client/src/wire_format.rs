@@ -0,0 +1,13 @@
+//! Body encodings of the HTTP API.
+
+/// How `HttpClient` encodes request bodies and which response encoding it
+/// asks for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WireFormat {
+    /// JSON, readable and the default.
+    #[default]
+    Json,
+    /// MessagePack (`application/msgpack`), smaller and faster to parse for
+    /// bulk reads and imports of long posts. Error bodies stay JSON.
+    MessagePack,
+}
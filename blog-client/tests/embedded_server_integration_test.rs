@@ -0,0 +1,138 @@
+//! Black-box tests of both clients against the embedded server.
+
+use blog_client::{GrpcClient, HttpClient};
+use blog_server::test_server;
+use blog_shared::{CreateCommentRequest, CreatePostRequest, RegisterRequest};
+
+/// Test a post created over HTTP being read back over gRPC.
+#[tokio::test]
+async fn test_http_and_grpc_share_the_server() {
+    let server = test_server::spawn(test_server::config()).await.unwrap();
+
+    let mut http = HttpClient::new(&server.http_url());
+    let auth = http
+        .register(RegisterRequest {
+            username: "alice".to_string(),
+            email: "alice@example.com".to_string(),
+            password: "password123".to_string(),
+            invite_code: None,
+        })
+        .await
+        .unwrap();
+    http.set_token(auth.token);
+    let post = http
+        .create_post(CreatePostRequest {
+            title: "Hello".to_string(),
+            content: "From the embedded server".to_string(),
+            tags: Vec::new(),
+            lang: None,
+            canonical_url: None,
+            original_source: None,
+            org: None,
+        })
+        .await
+        .unwrap();
+
+    let mut grpc = GrpcClient::connect(&server.grpc_url()).await.unwrap();
+    let fetched = grpc.get_post(&post.public_id).await.unwrap();
+    assert_eq!(fetched.title, "Hello");
+    assert_eq!(fetched.author_username, "alice");
+
+    server.shutdown().await;
+}
+
+/// Test comments added over gRPC being listed and deleted over HTTP.
+#[tokio::test]
+async fn test_comments_over_both_transports() {
+    let server = test_server::spawn(test_server::config()).await.unwrap();
+
+    let mut http = HttpClient::new(&server.http_url());
+    let auth = http
+        .register(RegisterRequest {
+            username: "carol".to_string(),
+            email: "carol@example.com".to_string(),
+            password: "password123".to_string(),
+            invite_code: None,
+        })
+        .await
+        .unwrap();
+    http.set_token(auth.token.clone());
+    let post = http
+        .create_post(CreatePostRequest {
+            title: "Discuss".to_string(),
+            content: "Comments welcome".to_string(),
+            tags: Vec::new(),
+            lang: None,
+            canonical_url: None,
+            original_source: None,
+            org: None,
+        })
+        .await
+        .unwrap();
+
+    let mut grpc = GrpcClient::connect(&server.grpc_url()).await.unwrap();
+    grpc.set_token(auth.token);
+    let comment = grpc
+        .create_comment(
+            &post.public_id,
+            CreateCommentRequest {
+                content: "First!".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(comment.author_username, "carol");
+
+    let list = http.list_comments(&post.public_id, 10, 0).await.unwrap();
+    assert_eq!(list.total, Some(1));
+    assert_eq!(list.comments[0].content, "First!");
+
+    http.delete_comment(comment.id).await.unwrap();
+    assert_eq!(
+        grpc.list_comments(&post.public_id, 10, 0)
+            .await
+            .unwrap()
+            .total,
+        Some(0)
+    );
+
+    server.shutdown().await;
+}
+
+/// Test each spawned server getting its own database.
+#[tokio::test]
+async fn test_servers_are_isolated() {
+    let first = test_server::spawn(test_server::config()).await.unwrap();
+    let second = test_server::spawn(test_server::config()).await.unwrap();
+    assert_ne!(first.http_addr, second.http_addr);
+
+    let mut http = HttpClient::new(&first.http_url());
+    let auth = http
+        .register(RegisterRequest {
+            username: "bob".to_string(),
+            email: "bob@example.com".to_string(),
+            password: "password123".to_string(),
+            invite_code: None,
+        })
+        .await
+        .unwrap();
+    http.set_token(auth.token);
+    http.create_post(CreatePostRequest {
+        title: "Only here".to_string(),
+        content: "Not in the second server".to_string(),
+        tags: Vec::new(),
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
+    })
+    .await
+    .unwrap();
+
+    let other = HttpClient::new(&second.http_url());
+    assert_eq!(other.list_posts(10, 0).await.unwrap().total, 0);
+    assert_eq!(http.list_posts(10, 0).await.unwrap().total, 1);
+
+    first.shutdown().await;
+    second.shutdown().await;
+}
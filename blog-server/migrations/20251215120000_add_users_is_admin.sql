@@ -0,0 +1,2 @@
+-- Add admin flag to users (promote with: UPDATE users SET is_admin = 1 WHERE username = '...')
+ALTER TABLE users ADD COLUMN is_admin BOOLEAN NOT NULL DEFAULT 0;
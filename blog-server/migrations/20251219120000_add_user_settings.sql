@@ -0,0 +1,17 @@
+-- Profile and preference columns
+ALTER TABLE users ADD COLUMN bio TEXT NOT NULL DEFAULT '';
+ALTER TABLE users ADD COLUMN avatar_url TEXT;
+ALTER TABLE users ADD COLUMN theme TEXT NOT NULL DEFAULT 'system';
+ALTER TABLE users ADD COLUMN language TEXT NOT NULL DEFAULT 'en';
+
+-- One row per login; the id is the JWT `sid` claim. Deleting a row revokes its token.
+CREATE TABLE IF NOT EXISTS sessions (
+    id TEXT PRIMARY KEY,
+    user_id INTEGER NOT NULL,
+    user_agent TEXT,
+    created_at TEXT NOT NULL,
+    last_seen_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_sessions_user_id ON sessions(user_id);
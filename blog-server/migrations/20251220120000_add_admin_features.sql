@@ -0,0 +1,19 @@
+-- Replace the admin flag with a role (promote with: UPDATE users SET role = 'admin' WHERE username = '...')
+ALTER TABLE users ADD COLUMN role TEXT NOT NULL DEFAULT 'user';
+UPDATE users SET role = 'admin' WHERE is_admin = 1;
+ALTER TABLE users DROP COLUMN is_admin;
+
+-- Suspended users cannot log in
+ALTER TABLE users ADD COLUMN suspended_at TEXT;
+
+-- Runtime feature toggles managed from the admin dashboard
+CREATE TABLE IF NOT EXISTS feature_flags (
+    name TEXT PRIMARY KEY NOT NULL,
+    description TEXT NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT 1,
+    updated_at TEXT NOT NULL
+);
+
+INSERT INTO feature_flags (name, description, enabled, updated_at) VALUES
+    ('registration', 'Allow new users to sign up', 1, strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    ('comments', 'Allow new comments on posts', 1, strftime('%Y-%m-%dT%H:%M:%SZ', 'now'));
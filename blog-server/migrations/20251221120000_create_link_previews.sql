@@ -0,0 +1,21 @@
+-- External links of each post, in order of first appearance (rewritten on save)
+CREATE TABLE IF NOT EXISTS post_links (
+    post_id INTEGER NOT NULL,
+    url TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (post_id, url),
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_post_links_url ON post_links(url);
+
+-- Open Graph metadata per URL, filled in by the background fetcher.
+-- fetched_at is NULL until the first attempt; title stays NULL if it failed.
+CREATE TABLE IF NOT EXISTS link_previews (
+    url TEXT PRIMARY KEY NOT NULL,
+    title TEXT,
+    description TEXT,
+    image_url TEXT,
+    site_name TEXT,
+    fetched_at TEXT
+);
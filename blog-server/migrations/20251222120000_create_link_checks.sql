@@ -0,0 +1,11 @@
+-- Result of the latest status check per linked URL, written by the link checker.
+-- status_code is NULL when the request itself failed (see error).
+CREATE TABLE IF NOT EXISTS link_checks (
+    url TEXT PRIMARY KEY NOT NULL,
+    status_code INTEGER,
+    error TEXT,
+    is_broken INTEGER NOT NULL DEFAULT 0,
+    checked_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_link_checks_checked_at ON link_checks(checked_at);
@@ -0,0 +1,16 @@
+-- Shadow-banned users still see their own posts and comments; nobody else does
+ALTER TABLE users ADD COLUMN shadow_banned_at TEXT;
+
+-- Moderator actions, newest last (actor/target kept as NULL if the account is deleted)
+CREATE TABLE IF NOT EXISTS audit_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    actor_id INTEGER,
+    action TEXT NOT NULL,
+    target_user_id INTEGER,
+    reason TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (actor_id) REFERENCES users(id) ON DELETE SET NULL,
+    FOREIGN KEY (target_user_id) REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_created_at ON audit_log(created_at);
@@ -0,0 +1,13 @@
+-- Newsletter subscribers (double opt-in). The token is sent in the
+-- confirmation email and in every digest's unsubscribe link.
+CREATE TABLE IF NOT EXISTS subscriptions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    email TEXT NOT NULL UNIQUE,
+    token TEXT NOT NULL UNIQUE,
+    confirmed_at TEXT,
+    unsubscribed_at TEXT,
+    last_digest_at TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_subscriptions_confirmed_at ON subscriptions(confirmed_at);
@@ -0,0 +1,32 @@
+-- ActivityPub: one RSA key pair per author, created on first use
+CREATE TABLE IF NOT EXISTS actor_keys (
+    user_id INTEGER PRIMARY KEY,
+    private_key_pem TEXT NOT NULL,
+    public_key_pem TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Remote actors following an author; `actor_id` is the follower's actor URL
+CREATE TABLE IF NOT EXISTS ap_followers (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER NOT NULL,
+    actor_id TEXT NOT NULL,
+    inbox_url TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE (user_id, actor_id),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Delivery of a post's Create activity to a follower's inbox
+CREATE TABLE IF NOT EXISTS ap_deliveries (
+    post_id INTEGER NOT NULL,
+    follower_id INTEGER NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TEXT NOT NULL,
+    delivered_at TEXT,
+    last_error TEXT,
+    PRIMARY KEY (post_id, follower_id),
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+    FOREIGN KEY (follower_id) REFERENCES ap_followers(id) ON DELETE CASCADE
+);
@@ -0,0 +1,4 @@
+-- Personal site; doubles as the IndieAuth identity (`me`) for Micropub
+ALTER TABLE users ADD COLUMN website TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_website ON users(website) WHERE website IS NOT NULL;
@@ -0,0 +1,25 @@
+-- Per-user tokens for the services posts can be cross-posted to
+CREATE TABLE IF NOT EXISTS integration_tokens (
+    user_id INTEGER NOT NULL,
+    target TEXT NOT NULL,
+    token TEXT NOT NULL,
+    publication_id TEXT,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (user_id, target),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- A post's copy on another service; `synced_at` is the post's `updated_at`
+-- as of the last successful push
+CREATE TABLE IF NOT EXISTS crossposts (
+    post_id INTEGER NOT NULL,
+    target TEXT NOT NULL,
+    status TEXT NOT NULL,
+    remote_id TEXT,
+    remote_url TEXT,
+    error TEXT,
+    synced_at TEXT,
+    attempted_at TEXT NOT NULL,
+    PRIMARY KEY (post_id, target),
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE
+);
@@ -0,0 +1,10 @@
+-- Uploaded files, stored once per distinct content and named by its
+-- SHA-256 hash; `uploaded_by` is the first uploader
+CREATE TABLE IF NOT EXISTS attachments (
+    hash TEXT PRIMARY KEY NOT NULL,
+    content_type TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    uploaded_by INTEGER,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (uploaded_by) REFERENCES users(id) ON DELETE SET NULL
+);
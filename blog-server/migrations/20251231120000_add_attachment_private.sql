@@ -0,0 +1,2 @@
+-- Private attachments are only served through signed, expiring URLs
+ALTER TABLE attachments ADD COLUMN private INTEGER NOT NULL DEFAULT 0;
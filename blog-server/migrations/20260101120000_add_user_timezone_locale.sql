@@ -0,0 +1,3 @@
+-- Time zone (IANA name) and locale used when the server formats dates for a user
+ALTER TABLE users ADD COLUMN timezone TEXT NOT NULL DEFAULT 'UTC';
+ALTER TABLE users ADD COLUMN locale TEXT NOT NULL DEFAULT 'en-US';
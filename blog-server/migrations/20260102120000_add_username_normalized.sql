@@ -0,0 +1,14 @@
+-- Case-folded, NFC-normalized username; unique so "Bob" and "bob" cannot both register.
+-- Existing rows are backfilled with lower(); an account whose name collides with an
+-- older one gets its id appended so the index can be built (it can still sign in by email).
+ALTER TABLE users ADD COLUMN username_normalized TEXT NOT NULL DEFAULT '';
+
+UPDATE users SET username_normalized = lower(trim(username));
+
+UPDATE users SET username_normalized = username_normalized || '#' || id
+WHERE EXISTS (
+    SELECT 1 FROM users older
+    WHERE older.username_normalized = users.username_normalized AND older.id < users.id
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_username_normalized ON users(username_normalized);
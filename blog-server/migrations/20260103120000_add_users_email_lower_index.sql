@@ -0,0 +1,2 @@
+-- Email lookups (login, registration, email changes) ignore case.
+CREATE INDEX IF NOT EXISTS idx_users_email_lower ON users(lower(email));
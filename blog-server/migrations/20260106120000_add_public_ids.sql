@@ -0,0 +1,19 @@
+-- Random UUIDv4 public IDs for users and posts, used in API URLs and DTOs so
+-- the sequential integer keys (which reveal volume and invite enumeration)
+-- stay internal. New rows get theirs from the application.
+ALTER TABLE users ADD COLUMN public_id TEXT NOT NULL DEFAULT '';
+ALTER TABLE posts ADD COLUMN public_id TEXT NOT NULL DEFAULT '';
+
+UPDATE users SET public_id = lower(
+    hex(randomblob(4)) || '-' || hex(randomblob(2)) || '-4' || substr(hex(randomblob(2)), 2)
+    || '-' || substr('89ab', 1 + (abs(random()) % 4), 1) || substr(hex(randomblob(2)), 2)
+    || '-' || hex(randomblob(6))
+);
+UPDATE posts SET public_id = lower(
+    hex(randomblob(4)) || '-' || hex(randomblob(2)) || '-4' || substr(hex(randomblob(2)), 2)
+    || '-' || substr('89ab', 1 + (abs(random()) % 4), 1) || substr(hex(randomblob(2)), 2)
+    || '-' || hex(randomblob(6))
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_public_id ON users(public_id);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_posts_public_id ON posts(public_id);
@@ -0,0 +1,8 @@
+-- Language variants of a post share a translation group, named by the
+-- public ID of the group's first post; a group has one post per language
+ALTER TABLE posts ADD COLUMN lang TEXT NOT NULL DEFAULT 'en';
+ALTER TABLE posts ADD COLUMN translation_group_id TEXT NOT NULL DEFAULT '';
+UPDATE posts SET translation_group_id = public_id;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_posts_translation_group_lang ON posts(translation_group_id, lang);
+CREATE INDEX IF NOT EXISTS idx_posts_lang ON posts(lang);
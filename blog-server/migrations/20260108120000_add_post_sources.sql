@@ -0,0 +1,4 @@
+-- Where a syndicated post was first published: the URL search engines
+-- should treat as canonical, and a link to the original
+ALTER TABLE posts ADD COLUMN canonical_url TEXT;
+ALTER TABLE posts ADD COLUMN original_source TEXT;
@@ -0,0 +1,2 @@
+-- Custom fields attached to a post by integrations, as a JSON object
+ALTER TABLE posts ADD COLUMN metadata TEXT NOT NULL DEFAULT '{}';
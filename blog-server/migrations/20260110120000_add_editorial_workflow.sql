@@ -0,0 +1,29 @@
+-- Editorial workflow: posts are published at once unless the
+-- editorial_review flag is on, in which case they start as drafts and
+-- need an editor's approval before they can be published
+ALTER TABLE posts ADD COLUMN status TEXT NOT NULL DEFAULT 'published';
+CREATE INDEX IF NOT EXISTS idx_posts_status ON posts(status);
+
+-- Every status change, with the editor's comment on review decisions
+CREATE TABLE IF NOT EXISTS post_transitions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    post_id INTEGER NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    actor_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    from_status TEXT NOT NULL,
+    to_status TEXT NOT NULL,
+    comment TEXT,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_post_transitions_post ON post_transitions(post_id);
+
+-- Review comments on single lines of the post content
+CREATE TABLE IF NOT EXISTS post_review_notes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    transition_id INTEGER NOT NULL REFERENCES post_transitions(id) ON DELETE CASCADE,
+    line INTEGER NOT NULL,
+    body TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_post_review_notes_transition ON post_review_notes(transition_id);
+
+INSERT INTO feature_flags (name, description, enabled, updated_at) VALUES
+    ('editorial_review', 'Require editor approval before posts are published', 0, strftime('%Y-%m-%dT%H:%M:%SZ', 'now'));
@@ -0,0 +1,15 @@
+-- Saved versions of a post's title and content, numbered from 1 per post.
+-- Content is sealed like posts.content when encryption at rest is on
+CREATE TABLE IF NOT EXISTS post_revisions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    post_id INTEGER NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    number INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    content TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE (post_id, number)
+);
+
+-- Existing posts start with their current version
+INSERT INTO post_revisions (post_id, number, title, content, created_at)
+    SELECT id, 1, title, content, updated_at FROM posts;
@@ -0,0 +1,4 @@
+-- Deleted posts go to their author's trash: hidden everywhere but the
+-- trash until restored, or purged by the retention job
+ALTER TABLE posts ADD COLUMN deleted_at TEXT;
+CREATE INDEX IF NOT EXISTS idx_posts_deleted_at ON posts(deleted_at);
@@ -0,0 +1,2 @@
+-- What a tag is about, shown on its landing page
+ALTER TABLE tags ADD COLUMN description TEXT;
@@ -0,0 +1,19 @@
+-- Offers to hand a post over to another user
+CREATE TABLE IF NOT EXISTS post_transfers (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    post_id INTEGER NOT NULL,
+    from_user_id INTEGER NOT NULL,
+    to_user_id INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',  -- pending, accepted, declined, cancelled
+    created_at TEXT NOT NULL,
+    resolved_at TEXT,
+    FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+    FOREIGN KEY (from_user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (to_user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- At most one open offer per post
+CREATE UNIQUE INDEX IF NOT EXISTS idx_post_transfers_pending
+    ON post_transfers(post_id) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_post_transfers_to_user_id ON post_transfers(to_user_id);
+CREATE INDEX IF NOT EXISTS idx_post_transfers_from_user_id ON post_transfers(from_user_id);
@@ -0,0 +1,26 @@
+-- Organizations that publish posts collectively
+CREATE TABLE IF NOT EXISTS organizations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    slug TEXT NOT NULL UNIQUE,  -- lowercase, used in URLs
+    name TEXT NOT NULL,
+    bio TEXT,
+    created_at TEXT NOT NULL
+);
+
+-- Members of organizations and what they may do
+CREATE TABLE IF NOT EXISTS organization_members (
+    org_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    role TEXT NOT NULL,  -- owner or writer
+    joined_at TEXT NOT NULL,
+    PRIMARY KEY (org_id, user_id),
+    FOREIGN KEY (org_id) REFERENCES organizations(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_organization_members_user_id ON organization_members(user_id);
+
+-- Organization a post is published under, if any
+ALTER TABLE posts ADD COLUMN org_id INTEGER REFERENCES organizations(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_posts_org_id ON posts(org_id);
@@ -0,0 +1,6 @@
+-- Site-wide settings managed by admins, one JSON value per key
+CREATE TABLE IF NOT EXISTS site_settings (
+    key TEXT PRIMARY KEY NOT NULL,
+    value TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
@@ -0,0 +1,13 @@
+-- Daily quotas for service accounts (NULL: unlimited), and their usage per
+-- UTC day. Counters are kept in memory and written here periodically.
+ALTER TABLE service_accounts ADD COLUMN requests_per_day INTEGER;
+ALTER TABLE service_accounts ADD COLUMN writes_per_day INTEGER;
+
+CREATE TABLE IF NOT EXISTS service_account_usage (
+    service_account_id INTEGER NOT NULL,
+    day TEXT NOT NULL,
+    requests INTEGER NOT NULL DEFAULT 0,
+    writes INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (service_account_id, day),
+    FOREIGN KEY (service_account_id) REFERENCES service_accounts(id) ON DELETE CASCADE
+);
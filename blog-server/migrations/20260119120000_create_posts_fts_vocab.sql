@@ -0,0 +1,2 @@
+-- Indexed terms of posts_fts, for suggesting corrections of misspelled search words
+CREATE VIRTUAL TABLE IF NOT EXISTS posts_fts_vocab USING fts5vocab(posts_fts, 'row');
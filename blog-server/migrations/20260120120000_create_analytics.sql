@@ -0,0 +1,20 @@
+-- Anonymous client events waiting for the nightly rollup. Nothing about the
+-- reader is stored; `weight` scales sampled events back up (1 / rate).
+CREATE TABLE IF NOT EXISTS analytics_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL,
+    path TEXT NOT NULL,
+    weight REAL NOT NULL DEFAULT 1,
+    day TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_analytics_events_day ON analytics_events(day);
+
+-- Estimated event counts per UTC day and page, behind the admin dashboard
+CREATE TABLE IF NOT EXISTS daily_stats (
+    day TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    path TEXT NOT NULL,
+    count INTEGER NOT NULL,
+    PRIMARY KEY (day, kind, path)
+);
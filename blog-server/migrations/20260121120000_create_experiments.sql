@@ -0,0 +1,31 @@
+-- A/B experiments managed by admins. Signed-in users are split between the
+-- variants by a hash of the experiment name and their ID.
+CREATE TABLE IF NOT EXISTS experiments (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT NOT NULL DEFAULT '',
+    enabled BOOLEAN NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- Variants in assignment order; weights are percentages summing to 100
+CREATE TABLE IF NOT EXISTS experiment_variants (
+    experiment_id INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    weight INTEGER NOT NULL,
+    PRIMARY KEY (experiment_id, position),
+    FOREIGN KEY (experiment_id) REFERENCES experiments(id) ON DELETE CASCADE
+);
+
+-- The first time each user was shown their variant
+CREATE TABLE IF NOT EXISTS experiment_exposures (
+    experiment_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    variant TEXT NOT NULL,
+    exposed_at TEXT NOT NULL,
+    PRIMARY KEY (experiment_id, user_id),
+    FOREIGN KEY (experiment_id) REFERENCES experiments(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
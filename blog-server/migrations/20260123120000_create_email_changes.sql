@@ -0,0 +1,9 @@
+-- Email changes waiting for the new address to be confirmed; at most one
+-- per user, replaced by a newer request
+CREATE TABLE IF NOT EXISTS email_changes (
+    user_id INTEGER PRIMARY KEY,
+    new_email TEXT NOT NULL,
+    token TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
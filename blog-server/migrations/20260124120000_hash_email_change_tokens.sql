@@ -0,0 +1,11 @@
+-- Confirmation tokens of email changes are stored as SHA-256 hashes; pending
+-- changes holding plain tokens are dropped and must be requested again
+DROP TABLE IF EXISTS email_changes;
+
+CREATE TABLE email_changes (
+    user_id INTEGER PRIMARY KEY,
+    new_email TEXT NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
@@ -0,0 +1,6 @@
+-- Data migrations run in Rust after the SQL ones (`database::run_migrations`),
+-- for backfills SQLite cannot compute; each is recorded once applied
+CREATE TABLE IF NOT EXISTS data_migrations (
+    name TEXT PRIMARY KEY,
+    applied_at TEXT NOT NULL
+);
@@ -0,0 +1,20 @@
+-- Uploads of attachments, one per uploader and content, each with its own
+-- URL and private flag; the stored file stays shared. Existing attachments
+-- become their first uploader's upload, keeping the hash as its ID so URLs
+-- already handed out still resolve
+CREATE TABLE IF NOT EXISTS uploads (
+    id TEXT PRIMARY KEY NOT NULL,
+    hash TEXT NOT NULL,
+    uploaded_by INTEGER,
+    private INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (hash) REFERENCES attachments(hash),
+    FOREIGN KEY (uploaded_by) REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_uploads_hash_uploaded_by ON uploads(hash, uploaded_by);
+
+INSERT INTO uploads (id, hash, uploaded_by, private, created_at)
+SELECT hash, hash, uploaded_by, private, created_at FROM attachments;
+
+ALTER TABLE attachments DROP COLUMN private;
@@ -0,0 +1,6 @@
+-- When anything a post's API response shows last changed, including what
+-- leaves updated_at alone (trash, transfers, tags, link previews, shadow
+-- bans); drives Last-Modified on post reads
+ALTER TABLE posts ADD COLUMN modified_at TEXT;
+UPDATE posts SET modified_at = MAX(updated_at, COALESCE(deleted_at, updated_at));
+CREATE INDEX IF NOT EXISTS idx_posts_modified_at ON posts(modified_at);
@@ -0,0 +1,7 @@
+-- Each upload keeps the content type its uploader sent; the shared
+-- attachment's is only the first uploader's. Existing uploads take the
+-- attachment's
+ALTER TABLE uploads ADD COLUMN content_type TEXT NOT NULL DEFAULT '';
+
+UPDATE uploads
+SET content_type = (SELECT content_type FROM attachments WHERE attachments.hash = uploads.hash);
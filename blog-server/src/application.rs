@@ -1,7 +1,61 @@
 //! Application layer: business logic services.
 
+mod account_service;
+mod activitypub_service;
+mod admin_service;
+mod analytics_service;
 mod auth_service;
 mod blog_service;
+mod comment_service;
+mod crosspost_service;
+mod diagnostics_service;
+mod experiment_service;
+mod feed_service;
+mod import_service;
+mod link_check_service;
+mod link_preview_service;
+mod message_service;
+mod micropub_service;
+mod moderation_service;
+mod newsletter_service;
+mod notification_service;
+mod org_service;
+mod presence_service;
+mod quota_service;
+mod retention_service;
+mod search_index_service;
+mod settings_service;
+mod site_service;
+mod telemetry_service;
+mod transfer_service;
+mod upload_service;
 
+pub use account_service::AccountService;
+pub use activitypub_service::{ActivityPubService, InboxRequest};
+pub use admin_service::AdminService;
+pub use analytics_service::AnalyticsService;
 pub use auth_service::AuthService;
 pub use blog_service::BlogService;
+pub use comment_service::CommentService;
+pub use crosspost_service::CrosspostService;
+pub use diagnostics_service::DiagnosticsService;
+pub use experiment_service::ExperimentService;
+pub use feed_service::FeedService;
+pub use import_service::ImportService;
+pub use link_check_service::LinkCheckService;
+pub use link_preview_service::LinkPreviewService;
+pub use message_service::MessageService;
+pub use micropub_service::MicropubService;
+pub use moderation_service::ModerationService;
+pub use newsletter_service::NewsletterService;
+pub use notification_service::NotificationService;
+pub use org_service::OrgService;
+pub use presence_service::PresenceService;
+pub use quota_service::QuotaService;
+pub use retention_service::RetentionService;
+pub use search_index_service::SearchIndexService;
+pub use settings_service::SettingsService;
+pub use site_service::SiteService;
+pub use telemetry_service::TelemetryService;
+pub use transfer_service::TransferService;
+pub use upload_service::{SignedAccess, UploadService};
@@ -0,0 +1,74 @@
+//! Account service: the current user's overview for client bootstrap.
+
+use std::sync::Arc;
+
+use blog_shared::{MeResponse, UsageDto};
+use tracing::instrument;
+
+use crate::application::{AuthService, SettingsService};
+use crate::data::{AccountRepository, FeatureFlagRepository, MessageRepository};
+use crate::domain::AppError;
+
+/// Service gathering what a client needs to know about the signed-in user.
+#[derive(Clone)]
+pub struct AccountService {
+    auth_service: AuthService,
+    settings_service: SettingsService,
+    account_repo: Arc<AccountRepository>,
+    message_repo: Arc<MessageRepository>,
+    flag_repo: Arc<FeatureFlagRepository>,
+}
+
+impl AccountService {
+    /// Creates a new AccountService.
+    pub fn new(
+        auth_service: AuthService,
+        settings_service: SettingsService,
+        account_repo: Arc<AccountRepository>,
+        message_repo: Arc<MessageRepository>,
+        flag_repo: Arc<FeatureFlagRepository>,
+    ) -> Self {
+        Self {
+            auth_service,
+            settings_service,
+            account_repo,
+            message_repo,
+            flag_repo,
+        }
+    }
+
+    /// Gets the user with their permissions, usage, unread message count,
+    /// preferences, and the feature flags.
+    #[instrument(skip(self))]
+    pub async fn me(&self, user_id: i64) -> Result<MeResponse, AppError> {
+        let user = self.auth_service.get_user_by_id(user_id).await?;
+        let settings = self.settings_service.get_settings(user_id).await?;
+        let usage = self.account_repo.usage(user_id).await?;
+        let unread_messages = self.message_repo.count_unread(user_id).await?;
+        let feature_flags = self
+            .flag_repo
+            .list()
+            .await?
+            .into_iter()
+            .map(|flag| (flag.name, flag.enabled))
+            .collect();
+
+        Ok(MeResponse {
+            permissions: user
+                .role
+                .permissions()
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+            user,
+            usage: UsageDto {
+                posts: usage.posts,
+                uploads: usage.uploads,
+                upload_bytes: usage.upload_bytes,
+            },
+            unread_messages,
+            feature_flags,
+            preferences: settings.preferences,
+        })
+    }
+}
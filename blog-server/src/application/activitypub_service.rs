@@ -0,0 +1,266 @@
+//! ActivityPub federation: actor documents for authors, their outbox, and a
+//! background job delivering new posts to remote followers.
+
+mod inbox;
+
+pub use inbox::InboxRequest;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::Url;
+use serde_json::{Value, json};
+use tracing::{debug, info, instrument, warn};
+
+use crate::constants::{
+    AP_DELIVERY_BATCH_SIZE, AP_DELIVERY_INTERVAL_SECS, AP_MAX_DELIVERY_ATTEMPTS, AP_OUTBOX_SIZE,
+    AP_RETRY_BASE_SECS,
+};
+use crate::data::{ActivityPubRepository, PostRepository};
+use crate::domain::{
+    ActorKey, ApUrls, AppError, PublicProfile, PublicRef, actor_document, create_activity,
+    followers_collection, note_object, outbox_collection, parse_acct, webfinger_document,
+};
+use crate::infrastructure::clock::{Clock, SystemClock};
+use crate::infrastructure::http_fetcher::HttpFetcher;
+use crate::infrastructure::http_signature::{digest_header, generate_key_pair, http_date, sign};
+
+/// Service publishing authors and their posts to the Fediverse.
+#[derive(Clone)]
+pub struct ActivityPubService {
+    ap_repo: Arc<ActivityPubRepository>,
+    post_repo: Arc<PostRepository>,
+    fetcher: HttpFetcher,
+    urls: ApUrls,
+    clock: Arc<dyn Clock>,
+}
+
+impl ActivityPubService {
+    /// Creates a new ActivityPubService.
+    pub fn new(
+        ap_repo: Arc<ActivityPubRepository>,
+        post_repo: Arc<PostRepository>,
+        fetcher: HttpFetcher,
+        urls: ApUrls,
+    ) -> Self {
+        Self {
+            ap_repo,
+            post_repo,
+            fetcher,
+            urls,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Resolves a WebFinger resource (`acct:alice@host` or an actor ID).
+    #[instrument(skip(self))]
+    pub async fn webfinger(&self, resource: &str) -> Result<Value, AppError> {
+        let username = parse_acct(resource, self.urls.host())
+            .or_else(|| self.urls.username_of(resource))
+            .ok_or(AppError::UserNotFound)?;
+        let profile = self.find_actor(username).await?;
+
+        Ok(webfinger_document(&self.urls, &profile.username))
+    }
+
+    /// Returns an author's actor document, creating their key pair on first use.
+    #[instrument(skip(self))]
+    pub async fn actor(&self, username: &str) -> Result<Value, AppError> {
+        let profile = self.find_actor(username).await?;
+        let key = self.actor_key(profile.id).await?;
+
+        Ok(actor_document(&self.urls, &profile, &key.public_key_pem))
+    }
+
+    /// Returns an author's outbox with Create activities for their newest posts.
+    #[instrument(skip(self))]
+    pub async fn outbox(&self, username: &str) -> Result<Value, AppError> {
+        let profile = self.find_actor(username).await?;
+        let posts = self.ap_repo.list_posts(profile.id, AP_OUTBOX_SIZE).await?;
+        let total = self.ap_repo.count_posts(profile.id).await?;
+        let items = posts
+            .iter()
+            .map(|post| create_activity(&self.urls, &profile.username, post))
+            .collect();
+
+        Ok(outbox_collection(
+            &self.urls,
+            &profile.username,
+            items,
+            total,
+        ))
+    }
+
+    /// Returns an author's followers collection (the count only).
+    #[instrument(skip(self))]
+    pub async fn followers(&self, username: &str) -> Result<Value, AppError> {
+        let profile = self.find_actor(username).await?;
+        let total = self.ap_repo.count_followers(profile.id).await?;
+
+        Ok(followers_collection(&self.urls, &profile.username, total))
+    }
+
+    /// Returns the Note object of a post.
+    #[instrument(skip(self))]
+    pub async fn note(&self, post: &PublicRef) -> Result<Value, AppError> {
+        let post_id = self
+            .post_repo
+            .resolve_id(&post.clone().into())
+            .await?
+            .ok_or(AppError::PostNotFound)?;
+        let post = self
+            .post_repo
+            .find_visible(post_id, None)
+            .await?
+            .ok_or(AppError::PostNotFound)?;
+        let username = self.post_repo.find_author_username(post.author_id).await?;
+
+        let mut note = note_object(&self.urls, &username, &post);
+        note["@context"] = json!("https://www.w3.org/ns/activitystreams");
+        Ok(note)
+    }
+
+    /// Runs the delivery job forever, one batch every `AP_DELIVERY_INTERVAL_SECS`.
+    pub async fn run(self) {
+        let mut ticker = tokio::time::interval(Duration::from_secs(AP_DELIVERY_INTERVAL_SECS));
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.deliver_due().await {
+                warn!(error = %e, "ActivityPub delivery job failed");
+            }
+        }
+    }
+
+    /// Delivers one batch of new posts to followers' inboxes. Returns how
+    /// many deliveries succeeded.
+    ///
+    /// Failed deliveries are retried with exponential backoff, up to
+    /// `AP_MAX_DELIVERY_ATTEMPTS` times.
+    #[instrument(skip(self))]
+    pub async fn deliver_due(&self) -> Result<usize, AppError> {
+        let deliveries = self
+            .ap_repo
+            .list_due_deliveries(
+                self.clock.now(),
+                AP_MAX_DELIVERY_ATTEMPTS,
+                AP_DELIVERY_BATCH_SIZE,
+            )
+            .await?;
+
+        let mut delivered = 0;
+        for delivery in &deliveries {
+            let activity = create_activity(&self.urls, &delivery.author_username, &delivery.post());
+            let result = self
+                .send_activity(
+                    delivery.author_id,
+                    &delivery.author_username,
+                    &delivery.inbox_url,
+                    &activity,
+                )
+                .await;
+            match result {
+                Ok(()) => {
+                    self.ap_repo
+                        .mark_delivered(delivery.post_id, delivery.follower_id, self.clock.now())
+                        .await?;
+                    delivered += 1;
+                }
+                Err(e) => {
+                    debug!(inbox = %delivery.inbox_url, error = %e, "Delivery failed");
+                    let backoff = AP_RETRY_BASE_SECS << delivery.attempts.min(16);
+                    let next_attempt_at = self.clock.now() + chrono::Duration::seconds(backoff);
+                    self.ap_repo
+                        .mark_failed(
+                            delivery.post_id,
+                            delivery.follower_id,
+                            &e.to_string(),
+                            next_attempt_at,
+                        )
+                        .await?;
+                }
+            }
+        }
+
+        if !deliveries.is_empty() {
+            info!(
+                attempted = deliveries.len(),
+                delivered, "Activities delivered"
+            );
+        }
+
+        Ok(delivered)
+    }
+
+    /// Finds a federated author or fails with `UserNotFound`.
+    async fn find_actor(&self, username: &str) -> Result<PublicProfile, AppError> {
+        self.ap_repo
+            .find_actor(username)
+            .await?
+            .ok_or(AppError::UserNotFound)
+    }
+
+    /// Returns an author's key pair, generating it on first use.
+    async fn actor_key(&self, user_id: i64) -> Result<ActorKey, AppError> {
+        if let Some(key) = self.ap_repo.find_key(user_id).await? {
+            return Ok(key);
+        }
+
+        let pair = tokio::task::spawn_blocking(generate_key_pair)
+            .await
+            .map_err(|e| AppError::Internal(e.to_string()))??;
+        info!(user_id, "Actor key pair generated");
+        self.ap_repo
+            .insert_key(
+                user_id,
+                &pair.private_key_pem,
+                &pair.public_key_pem,
+                self.clock.now(),
+            )
+            .await
+    }
+
+    /// Signs an activity with the author's key and posts it to an inbox.
+    async fn send_activity(
+        &self,
+        user_id: i64,
+        username: &str,
+        inbox_url: &str,
+        activity: &Value,
+    ) -> Result<(), AppError> {
+        let url = Url::parse(inbox_url).map_err(|e| AppError::Fetch(e.to_string()))?;
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{host}:{port}"),
+            (Some(host), None) => host.to_string(),
+            (None, _) => return Err(AppError::Fetch("missing host".to_string())),
+        };
+        let path = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+
+        let body = serde_json::to_vec(activity).map_err(|e| AppError::Internal(e.to_string()))?;
+        let date = http_date(self.clock.now());
+        let digest = digest_header(&body);
+        let signed = [
+            ("(request-target)".to_string(), format!("post {path}")),
+            ("host".to_string(), host),
+            ("date".to_string(), date.clone()),
+            ("digest".to_string(), digest.clone()),
+        ];
+        let key = self.actor_key(user_id).await?;
+        let signature = sign(&key.private_key_pem, &self.urls.key_id(username), &signed)?;
+
+        let headers = [
+            ("date".to_string(), date),
+            ("digest".to_string(), digest),
+            ("signature".to_string(), signature),
+        ];
+        self.fetcher.post_activity(inbox_url, &headers, body).await
+    }
+}
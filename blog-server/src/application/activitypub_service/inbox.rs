@@ -0,0 +1,147 @@
+//! Author inboxes: signature verification and Follow / Undo handling.
+
+use std::collections::HashMap;
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use serde_json::Value;
+use tracing::{debug, info, instrument, warn};
+
+use super::ActivityPubService;
+use crate::constants::{AP_REQUIRED_SIGNED_HEADERS, AP_SIGNATURE_MAX_AGE_SECS};
+use crate::domain::{AppError, accept_activity, actor_public_key};
+use crate::infrastructure::http_signature::{
+    SignatureHeader, digest_header, parse_http_date, signing_string, verify,
+};
+
+/// Request received by an inbox, as needed to check its signature.
+#[derive(Debug, Clone)]
+pub struct InboxRequest {
+    pub method: String,
+    /// Path and query string.
+    pub path: String,
+    /// Headers by lowercase name.
+    pub headers: HashMap<String, String>,
+}
+
+impl ActivityPubService {
+    /// Handles an activity posted to an author's inbox.
+    ///
+    /// The request must be signed by the activity's actor. Follows are
+    /// accepted automatically; other activities are ignored.
+    #[instrument(skip(self, request, body))]
+    pub async fn handle_inbox(
+        &self,
+        username: &str,
+        request: &InboxRequest,
+        body: &[u8],
+    ) -> Result<(), AppError> {
+        let profile = self.find_actor(username).await?;
+        let activity: Value = serde_json::from_slice(body)
+            .map_err(|_| AppError::Validation("Invalid activity".to_string()))?;
+        let actor_id = id_of(&activity["actor"])
+            .ok_or_else(|| AppError::Validation("Activity has no actor".to_string()))?;
+        let actor = self.verify_signature(request, body, actor_id).await?;
+
+        let actor_url = self.urls.actor(&profile.username);
+        match activity["type"].as_str() {
+            Some("Follow") if id_of(&activity["object"]) == Some(actor_url.as_str()) => {
+                let inbox_url = actor["inbox"]
+                    .as_str()
+                    .ok_or_else(|| AppError::Validation("Follower has no inbox".to_string()))?;
+                self.ap_repo
+                    .add_follower(profile.id, actor_id, inbox_url, self.clock.now())
+                    .await?;
+                info!(
+                    user_id = profile.id,
+                    follower = actor_id,
+                    "Remote follow accepted"
+                );
+
+                let accept_id = format!("{:016x}", OsRng.next_u64());
+                let accept = accept_activity(&self.urls, &profile.username, &accept_id, &activity);
+                if let Err(e) = self
+                    .send_activity(profile.id, &profile.username, inbox_url, &accept)
+                    .await
+                {
+                    warn!(follower = actor_id, error = %e, "Accept not delivered");
+                }
+            }
+            Some("Undo") if activity["object"]["type"].as_str() == Some("Follow") => {
+                if self.ap_repo.remove_follower(profile.id, actor_id).await? {
+                    info!(user_id = profile.id, follower = actor_id, "Remote unfollow");
+                }
+            }
+            other => debug!(activity_type = ?other, "Activity ignored"),
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the request is signed with a key `actor_id`'s document
+    /// publishes, is recent, and that its body matches the signed `Digest`.
+    /// Returns the actor document.
+    async fn verify_signature(
+        &self,
+        request: &InboxRequest,
+        body: &[u8],
+        actor_id: &str,
+    ) -> Result<Value, AppError> {
+        let header = |name: &str| request.headers.get(name).map(String::as_str);
+        let signature = SignatureHeader::parse(
+            header("signature").ok_or_else(|| invalid("missing Signature header"))?,
+        )?;
+        if let Some(missing) = AP_REQUIRED_SIGNED_HEADERS
+            .iter()
+            .find(|name| !signature.headers.iter().any(|signed| signed == *name))
+        {
+            return Err(invalid(&format!("{missing} is not signed")));
+        }
+
+        let date = header("date")
+            .and_then(parse_http_date)
+            .ok_or_else(|| invalid("missing or malformed Date"))?;
+        if (self.clock.now() - date).num_seconds().abs() > AP_SIGNATURE_MAX_AGE_SECS {
+            return Err(invalid("Date is too far from now"));
+        }
+        if header("digest") != Some(digest_header(body).as_str()) {
+            return Err(invalid("Digest does not match the body"));
+        }
+
+        let signed = signature
+            .headers
+            .iter()
+            .map(|name| {
+                let value = if name == "(request-target)" {
+                    format!("{} {}", request.method.to_lowercase(), request.path)
+                } else {
+                    header(name)
+                        .ok_or_else(|| invalid(&format!("missing {name} header")))?
+                        .to_string()
+                };
+                Ok((name.clone(), value))
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
+
+        // Fetch the actor, not the key ID the sender chose
+        let actor = self
+            .fetcher
+            .fetch_activity(actor_id)
+            .await
+            .map_err(|e| invalid(&format!("cannot fetch actor: {e}")))?;
+        let pem = actor_public_key(&actor, actor_id, &signature.key_id)
+            .ok_or_else(|| invalid("key does not belong to the actor"))?;
+
+        verify(pem, &signing_string(&signed), &signature.signature)?;
+        Ok(actor)
+    }
+}
+
+/// Returns the ID of an embedded object or a bare ID string.
+fn id_of(value: &Value) -> Option<&str> {
+    value.as_str().or_else(|| value["id"].as_str())
+}
+
+/// Builds an `InvalidSignature` error.
+fn invalid(reason: &str) -> AppError {
+    AppError::InvalidSignature(reason.to_string())
+}
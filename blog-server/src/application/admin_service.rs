@@ -0,0 +1,297 @@
+//! Admin service: dashboard stats, user management, moderation, and flags.
+
+use std::sync::Arc;
+
+use blog_shared::{
+    AdminStatsDto, AdminUserDto, AdminUserListResponse, AuditLogEntryDto, AuditLogResponse,
+    CommentListResponse, FeatureFlagDto, FeatureFlagListResponse, Role, UpdateUserRequest,
+};
+use chrono::Duration;
+use tracing::{info, instrument};
+
+use crate::application::comment_service::comment_to_dto;
+use crate::constants::JWT_EXPIRY_HOURS;
+use crate::data::{AdminRepository, CommentRepository, FeatureFlagRepository, UserRepository};
+use crate::domain::{AppError, AuditAction, AuditEntry, FeatureFlag, UserSummary, policy};
+use crate::infrastructure::clock::{Clock, SystemClock};
+
+/// Service for admin and moderator operations.
+///
+/// Callers must check the acting user's role before calling these methods.
+#[derive(Clone)]
+pub struct AdminService {
+    admin_repo: Arc<AdminRepository>,
+    user_repo: Arc<UserRepository>,
+    comment_repo: Arc<CommentRepository>,
+    flag_repo: Arc<FeatureFlagRepository>,
+    clock: Arc<dyn Clock>,
+}
+
+impl AdminService {
+    /// Creates a new AdminService.
+    pub fn new(
+        admin_repo: Arc<AdminRepository>,
+        user_repo: Arc<UserRepository>,
+        comment_repo: Arc<CommentRepository>,
+        flag_repo: Arc<FeatureFlagRepository>,
+    ) -> Self {
+        Self {
+            admin_repo,
+            user_repo,
+            comment_repo,
+            flag_repo,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Returns site-wide counters.
+    #[instrument(skip(self))]
+    pub async fn stats(&self) -> Result<AdminStatsDto, AppError> {
+        let active_since = self.clock.now() - Duration::hours(JWT_EXPIRY_HOURS);
+        let stats = self.admin_repo.stats(active_since).await?;
+
+        Ok(AdminStatsDto {
+            users: stats.users,
+            suspended_users: stats.suspended_users,
+            posts: stats.posts,
+            comments: stats.comments,
+            tags: stats.tags,
+            active_sessions: stats.active_sessions,
+        })
+    }
+
+    /// Lists users for the user management table.
+    #[instrument(skip(self))]
+    pub async fn list_users(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<AdminUserListResponse, AppError> {
+        let users = self.admin_repo.list_users(limit, offset).await?;
+        let total = self.admin_repo.count_users().await?;
+
+        Ok(AdminUserListResponse {
+            users: users.into_iter().map(user_summary_to_dto).collect(),
+            total,
+        })
+    }
+
+    /// Changes the role or suspension of a user.
+    ///
+    /// Suspending signs the user out everywhere. Admins cannot change their
+    /// own account, so the site cannot lose its last admin by accident.
+    #[instrument(skip(self, req), fields(actor_id = actor_id, user_id = user_id))]
+    pub async fn update_user(
+        &self,
+        actor_id: i64,
+        user_id: i64,
+        req: UpdateUserRequest,
+    ) -> Result<AdminUserDto, AppError> {
+        if !policy::can_manage_user(actor_id, user_id) {
+            return Err(AppError::Validation(
+                "You cannot change your own role or suspension".to_string(),
+            ));
+        }
+        self.find_user(user_id).await?;
+
+        if let Some(role) = req.role {
+            self.user_repo.update_role(user_id, role.as_str()).await?;
+            info!(role = %role, "User role changed");
+        }
+        match req.suspended {
+            Some(true) => {
+                self.user_repo
+                    .update_suspended_at(user_id, Some(self.clock.now()))
+                    .await?;
+                self.user_repo.delete_other_sessions(user_id, None).await?;
+                info!("User suspended");
+            }
+            Some(false) => {
+                self.user_repo.update_suspended_at(user_id, None).await?;
+                info!("User unsuspended");
+            }
+            None => {}
+        }
+
+        self.find_user(user_id).await.map(user_summary_to_dto)
+    }
+
+    /// Hides a user's posts and comments from everyone but the user.
+    ///
+    /// Moderators and admins cannot be shadow-banned. The action and its
+    /// reason are recorded in the audit log.
+    #[instrument(skip(self, reason), fields(actor_id = actor_id, user_id = user_id))]
+    pub async fn shadow_ban(
+        &self,
+        actor_id: i64,
+        user_id: i64,
+        reason: Option<&str>,
+    ) -> Result<AdminUserDto, AppError> {
+        if !policy::can_manage_user(actor_id, user_id) {
+            return Err(AppError::Validation(
+                "You cannot shadow-ban yourself".to_string(),
+            ));
+        }
+        let user = self.find_user(user_id).await?;
+        if !policy::can_be_shadow_banned(user.role.parse::<Role>().unwrap_or_default()) {
+            return Err(AppError::Validation(
+                "Moderators and admins cannot be shadow-banned".to_string(),
+            ));
+        }
+
+        if user.shadow_banned_at.is_none() {
+            let now = self.clock.now();
+            self.user_repo
+                .update_shadow_banned_at(user_id, Some(now), now)
+                .await?;
+            self.admin_repo
+                .log_action(actor_id, AuditAction::ShadowBan, user_id, reason, now)
+                .await?;
+            info!(reason, "User shadow-banned");
+        }
+
+        self.find_user(user_id).await.map(user_summary_to_dto)
+    }
+
+    /// Lifts a shadow ban and records it in the audit log.
+    #[instrument(skip(self), fields(actor_id = actor_id, user_id = user_id))]
+    pub async fn lift_shadow_ban(
+        &self,
+        actor_id: i64,
+        user_id: i64,
+    ) -> Result<AdminUserDto, AppError> {
+        let user = self.find_user(user_id).await?;
+
+        if user.shadow_banned_at.is_some() {
+            let now = self.clock.now();
+            self.user_repo
+                .update_shadow_banned_at(user_id, None, now)
+                .await?;
+            self.admin_repo
+                .log_action(actor_id, AuditAction::ShadowUnban, user_id, None, now)
+                .await?;
+            info!("User shadow ban lifted");
+        }
+
+        self.find_user(user_id).await.map(user_summary_to_dto)
+    }
+
+    /// Lists moderator actions, newest first.
+    #[instrument(skip(self))]
+    pub async fn audit_log(&self, limit: i64, offset: i64) -> Result<AuditLogResponse, AppError> {
+        let entries = self.admin_repo.list_audit_log(limit, offset).await?;
+        let total = self.admin_repo.count_audit_log().await?;
+
+        Ok(AuditLogResponse {
+            entries: entries.into_iter().map(audit_entry_to_dto).collect(),
+            total,
+        })
+    }
+
+    /// Lists comments across all posts, newest first.
+    #[instrument(skip(self))]
+    pub async fn moderation_queue(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<CommentListResponse, AppError> {
+        let comments = self.comment_repo.list_recent(limit, offset).await?;
+        let total = self.comment_repo.count_all().await?;
+
+        Ok(CommentListResponse::new(
+            comments.into_iter().map(comment_to_dto).collect(),
+            Some(total),
+        ))
+    }
+
+    /// Removes any comment.
+    #[instrument(skip(self))]
+    pub async fn delete_comment(&self, id: i64) -> Result<(), AppError> {
+        self.comment_repo.delete(id).await?;
+
+        info!(comment_id = id, "Comment removed by moderator");
+
+        Ok(())
+    }
+
+    /// Lists all feature flags.
+    #[instrument(skip(self))]
+    pub async fn list_flags(&self) -> Result<FeatureFlagListResponse, AppError> {
+        let flags = self.flag_repo.list().await?;
+
+        Ok(FeatureFlagListResponse {
+            flags: flags.into_iter().map(flag_to_dto).collect(),
+        })
+    }
+
+    /// Turns a feature flag on or off.
+    #[instrument(skip(self))]
+    pub async fn set_flag(&self, name: &str, enabled: bool) -> Result<FeatureFlagDto, AppError> {
+        if !self
+            .flag_repo
+            .set_enabled(name, enabled, self.clock.now())
+            .await?
+        {
+            return Err(AppError::FeatureFlagNotFound);
+        }
+
+        info!(enabled, "Feature flag changed");
+
+        self.flag_repo
+            .find(name)
+            .await?
+            .map(flag_to_dto)
+            .ok_or(AppError::FeatureFlagNotFound)
+    }
+
+    /// Loads a user summary or returns `UserNotFound`.
+    async fn find_user(&self, user_id: i64) -> Result<UserSummary, AppError> {
+        self.admin_repo
+            .find_user(user_id)
+            .await?
+            .ok_or(AppError::UserNotFound)
+    }
+}
+
+/// Converts a UserSummary to AdminUserDto.
+fn user_summary_to_dto(user: UserSummary) -> AdminUserDto {
+    AdminUserDto {
+        id: user.id,
+        username: user.username,
+        email: user.email,
+        role: user.role.parse().unwrap_or_default(),
+        suspended: user.suspended_at.is_some(),
+        shadow_banned: user.shadow_banned_at.is_some(),
+        post_count: user.post_count,
+        created_at: user.created_at,
+    }
+}
+
+/// Converts an AuditEntry to AuditLogEntryDto.
+fn audit_entry_to_dto(entry: AuditEntry) -> AuditLogEntryDto {
+    AuditLogEntryDto {
+        id: entry.id,
+        actor_id: entry.actor_id,
+        actor_username: entry.actor_username,
+        action: entry.action,
+        target_user_id: entry.target_user_id,
+        target_username: entry.target_username,
+        reason: entry.reason,
+        created_at: entry.created_at,
+    }
+}
+
+/// Converts a FeatureFlag to FeatureFlagDto.
+fn flag_to_dto(flag: FeatureFlag) -> FeatureFlagDto {
+    FeatureFlagDto {
+        name: flag.name,
+        description: flag.description,
+        enabled: flag.enabled,
+    }
+}
@@ -0,0 +1,156 @@
+//! Anonymous analytics: event ingestion, the nightly rollup job, and the
+//! dashboard report.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use blog_shared::{AnalyticsBatchRequest, AnalyticsReportResponse, DailyStatsDto, PageStatsDto};
+use chrono::Days;
+use tracing::{info, instrument, warn};
+
+use crate::constants::{
+    ANALYTICS_ROLLUP_INTERVAL_SECS, ANALYTICS_TOP_PAGES, MAX_ANALYTICS_BATCH_EVENTS,
+    MAX_ANALYTICS_REPORT_DAYS,
+};
+use crate::data::AnalyticsRepository;
+use crate::domain::{AnalyticsEvent, AppError};
+use crate::infrastructure::clock::{Clock, SystemClock};
+use crate::infrastructure::config::AnalyticsConfig;
+
+/// Service collecting anonymous client events and aggregating them into
+/// daily stats.
+///
+/// Events are stored with their UTC day only and are removed once their
+/// day has been rolled up, so no per-visit record outlives the day.
+#[derive(Clone)]
+pub struct AnalyticsService {
+    analytics_repo: Arc<AnalyticsRepository>,
+    config: AnalyticsConfig,
+    clock: Arc<dyn Clock>,
+}
+
+impl AnalyticsService {
+    /// Creates a new AnalyticsService.
+    pub fn new(analytics_repo: Arc<AnalyticsRepository>, config: AnalyticsConfig) -> Self {
+        Self {
+            analytics_repo,
+            config,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Runs the job forever, every `ANALYTICS_ROLLUP_INTERVAL_SECS`. A day
+    /// is rolled up on the first run after it ends.
+    pub async fn run(self) {
+        let mut ticker = tokio::time::interval(Duration::from_secs(ANALYTICS_ROLLUP_INTERVAL_SECS));
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.rollup().await {
+                warn!(error = %e, "Analytics rollup failed");
+            }
+        }
+    }
+
+    /// Validates a batch and stores a sample of its events. Nothing is
+    /// stored while analytics are off or the reader opted out of tracking.
+    /// Returns how many events were stored.
+    #[instrument(skip(self, batch), fields(events = batch.events.len()))]
+    pub async fn record(
+        &self,
+        batch: AnalyticsBatchRequest,
+        opted_out: bool,
+    ) -> Result<usize, AppError> {
+        if batch.events.len() > MAX_ANALYTICS_BATCH_EVENTS {
+            return Err(AppError::Validation(format!(
+                "A batch can have at most {MAX_ANALYTICS_BATCH_EVENTS} events"
+            )));
+        }
+        let events = batch
+            .events
+            .into_iter()
+            .map(AnalyticsEvent::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        if !self.config.enabled || opted_out {
+            return Ok(0);
+        }
+
+        let sample_rate = self.config.sample_rate;
+        let sampled: Vec<AnalyticsEvent> = events
+            .into_iter()
+            .filter(|_| sample_rate >= 1.0 || roll(sample_rate))
+            .collect();
+        if sampled.is_empty() {
+            return Ok(0);
+        }
+        let today = self.clock.now().date_naive();
+        self.analytics_repo
+            .insert_events(&sampled, 1.0 / sample_rate, today)
+            .await?;
+
+        Ok(sampled.len())
+    }
+
+    /// Adds the events of finished days to the daily stats. Returns how many
+    /// events were rolled up.
+    #[instrument(skip(self))]
+    pub async fn rollup(&self) -> Result<u64, AppError> {
+        let today = self.clock.now().date_naive();
+        let rolled = self.analytics_repo.rollup_before(today).await?;
+        if rolled > 0 {
+            info!(rolled, "Analytics events rolled up");
+        }
+
+        Ok(rolled)
+    }
+
+    /// Reports the daily stats of the last `days` days, today included.
+    #[instrument(skip(self))]
+    pub async fn report(&self, days: u32) -> Result<AnalyticsReportResponse, AppError> {
+        if days == 0 || days > MAX_ANALYTICS_REPORT_DAYS {
+            return Err(AppError::Validation(format!(
+                "Days must be between 1 and {MAX_ANALYTICS_REPORT_DAYS}"
+            )));
+        }
+        let today = self.clock.now().date_naive();
+        let since = today
+            .checked_sub_days(Days::new(u64::from(days - 1)))
+            .unwrap_or(today);
+
+        let days = self
+            .analytics_repo
+            .daily(since)
+            .await?
+            .into_iter()
+            .map(|stats| DailyStatsDto {
+                day: stats.day,
+                page_views: stats.page_views,
+                read_completions: stats.read_completions,
+            })
+            .collect();
+        let top_pages = self
+            .analytics_repo
+            .top_pages(since, ANALYTICS_TOP_PAGES)
+            .await?
+            .into_iter()
+            .map(|stats| PageStatsDto {
+                path: stats.path,
+                page_views: stats.page_views,
+                read_completions: stats.read_completions,
+            })
+            .collect();
+
+        Ok(AnalyticsReportResponse { days, top_pages })
+    }
+}
+
+/// Returns true with probability `rate`.
+fn roll(rate: f64) -> bool {
+    (OsRng.next_u32() as f64 / u32::MAX as f64) < rate
+}
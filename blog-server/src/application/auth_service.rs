@@ -1,43 +1,155 @@
 //! Authentication service.
 
+mod email_changes;
+mod invites;
+mod recovery;
+mod service_accounts;
+
 use std::sync::Arc;
 
 use argon2::password_hash::SaltString;
-use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::rand_core::{OsRng, RngCore};
 use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
-use blog_shared::{AuthResponse, LoginRequest, RegisterRequest, UserDto};
+use blog_shared::{
+    AuthResponse, ChangePasswordRequest, DeleteAccountRequest, LoginRequest, RegisterRequest,
+    RegistrationMode, Role, UserDto,
+};
+use chrono::Duration;
 use tracing::{info, instrument};
 
-use crate::data::UserRepository;
-use crate::domain::AppError;
-use crate::infrastructure::jwt;
+use crate::application::{ExperimentService, SiteService};
+use crate::constants::{JWT_EXPIRY_HOURS, SESSION_TOUCH_INTERVAL_SECS};
+use crate::data::{
+    AdminRepository, EmailChangeRepository, FeatureFlagRepository, InviteRepository,
+    RecoveryCodeRepository, ServiceAccountRepository, UserRepository,
+};
+use crate::domain::{AppError, User, clean_username, normalize_username};
+use crate::infrastructure::clock::{Clock, SystemClock};
+use crate::infrastructure::jwt::{self, Claims, JwtConfig, TokenType};
+use crate::infrastructure::mailer::Mailer;
+use email_changes::EmailChanges;
 
 /// Service for authentication operations.
 #[derive(Clone)]
 pub struct AuthService {
     user_repo: Arc<UserRepository>,
-    jwt_secret: String,
+    flag_repo: Arc<FeatureFlagRepository>,
+    recovery_repo: Arc<RecoveryCodeRepository>,
+    service_account_repo: Arc<ServiceAccountRepository>,
+    jwt: JwtConfig,
+    /// Normalized usernames nobody can register.
+    reserved_usernames: Vec<String>,
+    clock: Arc<dyn Clock>,
+    /// Set once invite codes are set up.
+    invite_repo: Option<Arc<InviteRepository>>,
+    /// Source of the registration mode, when admins may change it.
+    site: Option<SiteService>,
+    /// Assigns signed-in users to experiment variants in the features.
+    experiments: Option<ExperimentService>,
+    /// Set once email changes can be confirmed by email.
+    email_changes: Option<EmailChanges>,
 }
 
 impl AuthService {
     /// Creates a new AuthService.
-    pub fn new(user_repo: Arc<UserRepository>, jwt_secret: String) -> Self {
+    pub fn new(
+        user_repo: Arc<UserRepository>,
+        flag_repo: Arc<FeatureFlagRepository>,
+        recovery_repo: Arc<RecoveryCodeRepository>,
+        service_account_repo: Arc<ServiceAccountRepository>,
+        jwt: JwtConfig,
+        reserved_usernames: Vec<String>,
+    ) -> Self {
         Self {
             user_repo,
-            jwt_secret,
+            flag_repo,
+            recovery_repo,
+            service_account_repo,
+            jwt,
+            reserved_usernames,
+            clock: Arc::new(SystemClock),
+            invite_repo: None,
+            site: None,
+            experiments: None,
+            email_changes: None,
         }
     }
 
-    /// Registers a new user.
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Sets up invite codes, which users create with
+    /// [`AuthService::create_invite`] while registration is invite-only.
+    /// Without site settings, this makes registration invite-only.
+    pub fn with_invites(mut self, invite_repo: Arc<InviteRepository>) -> Self {
+        self.invite_repo = Some(invite_repo);
+        self
+    }
+
+    /// Takes the registration mode from the site settings.
+    pub fn with_site(mut self, site: SiteService) -> Self {
+        self.site = Some(site);
+        self
+    }
+
+    /// Reports the signed-in user's experiment variants with the features.
+    pub fn with_experiments(mut self, experiments: ExperimentService) -> Self {
+        self.experiments = Some(experiments);
+        self
+    }
+
+    /// Lets users change their email: [`AuthService::change_email`] mails
+    /// a link to the web app at `site_url` to the new address, where it is
+    /// confirmed, and the previous one is told once it is. Both steps are
+    /// written to the audit log. Without this, changing email is disabled.
+    pub fn with_email_changes(
+        mut self,
+        email_change_repo: Arc<EmailChangeRepository>,
+        admin_repo: Arc<AdminRepository>,
+        mailer: Mailer,
+        site_url: String,
+    ) -> Self {
+        self.email_changes = Some(EmailChanges {
+            email_change_repo,
+            admin_repo,
+            mailer,
+            site_url,
+        });
+        self
+    }
+
+    /// Registers a new user and signs them in, returning their first
+    /// recovery codes. While registration is invite-only, the request's
+    /// invite code is used up.
     #[instrument(skip(self, req), fields(username = %req.username, email = %req.email))]
-    pub async fn register(&self, req: RegisterRequest) -> Result<AuthResponse, AppError> {
-        // Check if username exists
+    pub async fn register(
+        &self,
+        req: RegisterRequest,
+        user_agent: Option<&str>,
+    ) -> Result<AuthResponse, AppError> {
+        let mode = self.registration_mode().await?;
+        if mode == RegistrationMode::Closed {
+            return Err(AppError::FeatureDisabled("Registration".to_string()));
+        }
+
+        let username = clean_username(&req.username);
+        if username.is_empty() {
+            return Err(AppError::Validation("Username is required".to_string()));
+        }
         if self
-            .user_repo
-            .find_by_username(&req.username)
-            .await?
-            .is_some()
+            .reserved_usernames
+            .contains(&normalize_username(&username))
         {
+            return Err(AppError::Validation(format!(
+                "Username '{username}' is reserved"
+            )));
+        }
+
+        // Check if username exists, ignoring case
+        if self.user_repo.find_by_username(&username).await?.is_some() {
             return Err(AppError::UsernameExists);
         }
 
@@ -49,21 +161,29 @@ impl AuthService {
         // Hash password
         let password_hash = hash_password(&req.password)?;
 
-        // Create user
-        let user = self
+        // Create user, with the invite held for them
+        let invite_id = self.claim_invite(mode, req.invite_code.as_deref()).await?;
+        let user = match self
             .user_repo
-            .create(&req.username, &req.email, &password_hash)
-            .await?;
+            .create(&username, &req.email, &password_hash, self.clock.now())
+            .await
+        {
+            Ok(user) => user,
+            Err(e) => {
+                self.release_invite(invite_id).await?;
+                return Err(e);
+            }
+        };
+        self.redeem_invite(invite_id, user.id).await?;
 
-        // Generate token
-        let token = jwt::create_token(user.id, &self.jwt_secret)?;
+        let recovery_codes = self.issue_recovery_codes(user.id).await?;
+        let token = self.start_session(user.id, user_agent).await?;
 
         info!(user_id = user.id, "User registered");
 
-        Ok(AuthResponse {
-            token,
-            user: user_to_dto(&user),
-        })
+        let mut response = AuthResponse::new(token, user_to_dto(&user));
+        response.recovery_codes = recovery_codes;
+        Ok(response)
     }
 
     /// Gets a user by ID (for session restoration).
@@ -78,28 +198,159 @@ impl AuthService {
         Ok(user_to_dto(&user))
     }
 
-    /// Logs in an existing user.
+    /// Returns the role of a user (`User` if the user no longer exists).
+    #[instrument(skip(self))]
+    pub async fn role(&self, user_id: i64) -> Result<Role, AppError> {
+        let user = self.user_repo.find_by_id(user_id).await?;
+        Ok(user.map(|u| u.role()).unwrap_or_default())
+    }
+
+    /// Checks whether a user has admin privileges.
+    pub async fn is_admin(&self, user_id: i64) -> Result<bool, AppError> {
+        Ok(self.role(user_id).await? == Role::Admin)
+    }
+
+    /// Logs in an existing user, starting a new session.
     #[instrument(skip(self, req), fields(username = %req.username))]
-    pub async fn login(&self, req: LoginRequest) -> Result<AuthResponse, AppError> {
-        // Find user
+    pub async fn login(
+        &self,
+        req: LoginRequest,
+        user_agent: Option<&str>,
+    ) -> Result<AuthResponse, AppError> {
         let user = self
-            .user_repo
-            .find_by_username(&req.username)
+            .find_by_login(req.username.trim())
             .await?
             .ok_or(AppError::InvalidCredentials)?;
 
         // Verify password
         verify_password(&req.password, &user.password_hash)?;
 
-        // Generate token
-        let token = jwt::create_token(user.id, &self.jwt_secret)?;
+        if user.suspended_at.is_some() {
+            return Err(AppError::AccountSuspended);
+        }
+
+        let token = self.start_session(user.id, user_agent).await?;
 
         info!(user_id = user.id, "User logged in");
 
-        Ok(AuthResponse {
-            token,
-            user: user_to_dto(&user),
-        })
+        Ok(AuthResponse::new(token, user_to_dto(&user)))
+    }
+
+    /// Finds the user signing in with `login`, an email address or a
+    /// username. Addresses are tried as usernames too, since a username may
+    /// contain '@'.
+    async fn find_by_login(&self, login: &str) -> Result<Option<User>, AppError> {
+        if login.contains('@')
+            && let Some(user) = self.user_repo.find_by_email(login).await?
+        {
+            return Ok(Some(user));
+        }
+        self.user_repo.find_by_username(login).await
+    }
+
+    /// Validates a token and checks that its session was not revoked.
+    pub async fn authenticate(&self, token: &str) -> Result<Claims, AppError> {
+        let claims = jwt::validate_token(token, self.clock.now(), &self.jwt)?;
+        self.verify_session(&claims).await?;
+
+        Ok(claims)
+    }
+
+    /// Checks that the session of already validated claims is still active.
+    ///
+    /// Tokens without a session ID predate session tracking and stay valid
+    /// until they expire. Service tokens need their account to be active.
+    pub async fn verify_session(&self, claims: &Claims) -> Result<(), AppError> {
+        let Some(session_id) = claims.sid.as_deref() else {
+            return Ok(());
+        };
+        if claims.typ == TokenType::Service {
+            return self.verify_service_account(claims.sub, session_id).await;
+        }
+        let session = self
+            .user_repo
+            .find_session(session_id)
+            .await?
+            .filter(|session| session.user_id == claims.sub)
+            .ok_or(AppError::InvalidCredentials)?;
+
+        // Only write when the recorded activity is noticeably stale
+        if self.clock.now() - session.last_seen_at > Duration::seconds(SESSION_TOUCH_INTERVAL_SECS)
+        {
+            self.user_repo
+                .touch_session(session_id, self.clock.now())
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Changes the password of a user and signs out their other sessions.
+    #[instrument(skip(self, req))]
+    pub async fn change_password(
+        &self,
+        user_id: i64,
+        current_session: Option<&str>,
+        req: ChangePasswordRequest,
+    ) -> Result<(), AppError> {
+        let user = self
+            .user_repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+        verify_password(&req.current_password, &user.password_hash)?;
+
+        let password_hash = hash_password(&req.new_password)?;
+        self.user_repo
+            .update_password_hash(user_id, &password_hash)
+            .await?;
+        self.user_repo
+            .delete_other_sessions(user_id, current_session)
+            .await?;
+
+        info!("Password changed");
+
+        Ok(())
+    }
+
+    /// Deletes a user's account (requires their current password).
+    #[instrument(skip(self, req))]
+    pub async fn delete_account(
+        &self,
+        user_id: i64,
+        req: DeleteAccountRequest,
+    ) -> Result<(), AppError> {
+        let user = self
+            .user_repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+        verify_password(&req.current_password, &user.password_hash)?;
+
+        self.user_repo.delete(user_id).await?;
+
+        info!("Account deleted");
+
+        Ok(())
+    }
+
+    /// Records a new session and issues a token bound to it.
+    async fn start_session(
+        &self,
+        user_id: i64,
+        user_agent: Option<&str>,
+    ) -> Result<String, AppError> {
+        let now = self.clock.now();
+        self.user_repo
+            .delete_expired_sessions(now - Duration::hours(JWT_EXPIRY_HOURS))
+            .await?;
+
+        let session_id = format!("{:016x}{:016x}", OsRng.next_u64(), OsRng.next_u64());
+        self.user_repo
+            .create_session(&session_id, user_id, user_agent, now)
+            .await?;
+
+        jwt::create_token(user_id, &session_id, now, &self.jwt)
     }
 }
 
@@ -125,10 +376,13 @@ fn verify_password(password: &str, hash: &str) -> Result<(), AppError> {
 
 /// Converts a User domain entity to UserDto.
 fn user_to_dto(user: &crate::domain::User) -> UserDto {
-    UserDto {
-        id: user.id,
-        username: user.username.clone(),
-        email: user.email.clone(),
-        created_at: user.created_at,
-    }
+    let mut dto = UserDto::new(
+        user.id,
+        user.username.clone(),
+        user.email.clone(),
+        user.created_at,
+    );
+    dto.public_id = user.public_id.clone();
+    dto.role = user.role();
+    dto
 }
@@ -0,0 +1,174 @@
+//! Email changes, switched over only once the new address is confirmed.
+
+use std::sync::Arc;
+
+use blog_shared::constants::EMAIL_CHANGE_QUERY_PARAM;
+use blog_shared::{ChangeEmailRequest, PendingEmailChangeResponse, UserDto};
+use tracing::{info, instrument, warn};
+
+use super::{AuthService, verify_password};
+use crate::data::{AdminRepository, EmailChangeRepository};
+use crate::domain::{
+    AppError, AuditAction, EmailChange, generate_email_change_token, hash_email_change_token,
+    render_email_change_confirmation, render_email_changed_notice,
+};
+use crate::infrastructure::mailer::{Email, Mailer};
+
+/// What email changes need besides the user repository.
+#[derive(Clone)]
+pub(super) struct EmailChanges {
+    pub(super) email_change_repo: Arc<EmailChangeRepository>,
+    /// Where requests and switch-overs are recorded.
+    pub(super) admin_repo: Arc<AdminRepository>,
+    pub(super) mailer: Mailer,
+    /// Base URL of the web app, whose page confirms the change.
+    pub(super) site_url: String,
+}
+
+impl AuthService {
+    /// Starts changing the email of a user after re-checking their password.
+    ///
+    /// The account keeps its current email until the link emailed to the new
+    /// address is opened; a new request replaces an unconfirmed one.
+    #[instrument(skip(self, req))]
+    pub async fn change_email(
+        &self,
+        user_id: i64,
+        req: ChangeEmailRequest,
+    ) -> Result<PendingEmailChangeResponse, AppError> {
+        let changes = self.email_changes()?;
+        let user = self
+            .user_repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+        verify_password(&req.current_password, &user.password_hash)?;
+
+        // Only an identical address is a no-op; changing just its case is allowed
+        if user.email == req.new_email {
+            return Err(AppError::Validation(
+                "That is already your email address".to_string(),
+            ));
+        }
+        // Lookups ignore case, so the user's own row matches a case-only change
+        if self
+            .user_repo
+            .find_by_email(&req.new_email)
+            .await?
+            .is_some_and(|existing| existing.id != user_id)
+        {
+            return Err(AppError::EmailExists);
+        }
+
+        // Only the hash is stored, so the link is the sole copy of the token
+        let token = generate_email_change_token();
+        let change = EmailChange {
+            user_id,
+            new_email: req.new_email,
+            token_hash: hash_email_change_token(&token),
+            created_at: self.clock.now(),
+        };
+        changes.email_change_repo.upsert(&change).await?;
+
+        // The link opens a page that posts the token, so link scanners and
+        // prefetchers that follow it do not confirm the change
+        let confirm_url = format!("{}/?{EMAIL_CHANGE_QUERY_PARAM}={token}", changes.site_url);
+        let body = render_email_change_confirmation(&user.username, &confirm_url);
+        changes
+            .mailer
+            .send(Email {
+                to: change.new_email.clone(),
+                subject: "Confirm your new email address".to_string(),
+                text: body.text,
+                html: body.html,
+                unsubscribe_url: None,
+            })
+            .await?;
+
+        changes
+            .admin_repo
+            .log_action(
+                user_id,
+                AuditAction::EmailChangeRequested,
+                user_id,
+                Some(&format!("To {}", change.new_email)),
+                self.clock.now(),
+            )
+            .await?;
+
+        info!("Email change requested");
+
+        Ok(PendingEmailChangeResponse {
+            expires_at: change.expires_at(),
+            new_email: change.new_email,
+        })
+    }
+
+    /// Switches a user's email to the address the confirmation link with
+    /// `token` was sent to, then tells the previous address.
+    #[instrument(skip(self, token))]
+    pub async fn confirm_email_change(&self, token: &str) -> Result<UserDto, AppError> {
+        let changes = self.email_changes()?;
+        let change = changes
+            .email_change_repo
+            .find_by_token_hash(&hash_email_change_token(token))
+            .await?
+            .ok_or(AppError::InvalidEmailChange)?;
+        if change.expires_at() <= self.clock.now() {
+            changes.email_change_repo.delete(change.user_id).await?;
+            return Err(AppError::InvalidEmailChange);
+        }
+
+        let user = self
+            .user_repo
+            .find_by_id(change.user_id)
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+        // Someone may have taken the address since the request
+        if self
+            .user_repo
+            .find_by_email(&change.new_email)
+            .await?
+            .is_some_and(|existing| existing.id != user.id)
+        {
+            changes.email_change_repo.delete(change.user_id).await?;
+            return Err(AppError::EmailExists);
+        }
+
+        changes.email_change_repo.apply(&change).await?;
+        changes
+            .admin_repo
+            .log_action(
+                user.id,
+                AuditAction::EmailChanged,
+                user.id,
+                Some(&format!("From {} to {}", user.email, change.new_email)),
+                self.clock.now(),
+            )
+            .await?;
+
+        info!(user_id = user.id, "Email changed");
+
+        let body = render_email_changed_notice(&user.username, &change.new_email);
+        let notice = Email {
+            to: user.email,
+            subject: "Your email address was changed".to_string(),
+            text: body.text,
+            html: body.html,
+            unsubscribe_url: None,
+        };
+        // The change is done; a lost notice must not undo it
+        if let Err(e) = changes.mailer.send(notice).await {
+            warn!(user_id = user.id, error = %e, "Email change notice failed");
+        }
+
+        self.get_user_by_id(change.user_id).await
+    }
+
+    /// Returns the email change setup, if any.
+    fn email_changes(&self) -> Result<&EmailChanges, AppError> {
+        self.email_changes
+            .as_ref()
+            .ok_or_else(|| AppError::FeatureDisabled("Changing email".to_string()))
+    }
+}
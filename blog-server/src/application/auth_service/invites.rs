@@ -0,0 +1,122 @@
+//! Invite codes for invite-only registration.
+
+use std::collections::BTreeMap;
+
+use blog_shared::{FeaturesResponse, InviteDto, RegistrationMode};
+use tracing::{info, instrument};
+
+use super::AuthService;
+use crate::constants::FEATURE_REGISTRATION;
+use crate::domain::{AppError, generate_invite_code, hash_invite_code};
+
+impl AuthService {
+    /// Reports whether registration is open and whether it needs an invite,
+    /// plus the experiment variants of `viewer_id`.
+    pub async fn features(&self, viewer_id: Option<i64>) -> Result<FeaturesResponse, AppError> {
+        let mode = self.registration_mode().await?;
+        let experiments = match (&self.experiments, viewer_id) {
+            (Some(experiments), Some(user_id)) => experiments.assignments(user_id).await?,
+            _ => BTreeMap::new(),
+        };
+
+        Ok(FeaturesResponse {
+            registration: mode != RegistrationMode::Closed,
+            invite_only: mode == RegistrationMode::InviteOnly,
+            experiments,
+        })
+    }
+
+    /// Returns the registration mode in force: closed while the
+    /// `registration` flag is off, else the site setting, else invite-only
+    /// once invite codes are set up.
+    pub(super) async fn registration_mode(&self) -> Result<RegistrationMode, AppError> {
+        if !self.flag_repo.is_enabled(FEATURE_REGISTRATION).await? {
+            return Ok(RegistrationMode::Closed);
+        }
+
+        Ok(match &self.site {
+            Some(site) => site.settings().await?.registration_mode,
+            None if self.invite_repo.is_some() => RegistrationMode::InviteOnly,
+            None => RegistrationMode::Open,
+        })
+    }
+
+    /// Creates a single-use invite code for `user_id` to hand out. Only
+    /// available while registration is invite-only.
+    #[instrument(skip(self))]
+    pub async fn create_invite(&self, user_id: i64) -> Result<InviteDto, AppError> {
+        let invite_repo = match &self.invite_repo {
+            Some(invite_repo)
+                if self.registration_mode().await? == RegistrationMode::InviteOnly =>
+            {
+                invite_repo
+            }
+            _ => {
+                return Err(AppError::FeatureDisabled(
+                    "Invite-only registration".to_string(),
+                ));
+            }
+        };
+
+        let code = generate_invite_code();
+        let created_at = invite_repo
+            .create(&hash_invite_code(&code), user_id, self.clock.now())
+            .await?;
+
+        info!("Invite created");
+
+        Ok(InviteDto { code, created_at })
+    }
+
+    /// Claims the invite a registration in `mode` came with. Returns `None`
+    /// unless registration is invite-only, and for the very first user, who
+    /// has nobody to invite them.
+    pub(super) async fn claim_invite(
+        &self,
+        mode: RegistrationMode,
+        code: Option<&str>,
+    ) -> Result<Option<i64>, AppError> {
+        if mode != RegistrationMode::InviteOnly || !self.user_repo.any_exist().await? {
+            return Ok(None);
+        }
+        let Some(invite_repo) = &self.invite_repo else {
+            return Err(AppError::FeatureDisabled(
+                "Invite-only registration".to_string(),
+            ));
+        };
+        let code = code.map(str::trim).filter(|code| !code.is_empty());
+        let Some(code) = code else {
+            return Err(AppError::Validation(
+                "An invite code is required to register".to_string(),
+            ));
+        };
+
+        invite_repo
+            .claim(&hash_invite_code(code), self.clock.now())
+            .await?
+            .map(Some)
+            .ok_or(AppError::InvalidInvite)
+    }
+
+    /// Records who registered with a claimed invite.
+    pub(super) async fn redeem_invite(
+        &self,
+        invite_id: Option<i64>,
+        user_id: i64,
+    ) -> Result<(), AppError> {
+        if let (Some(invite_repo), Some(id)) = (&self.invite_repo, invite_id) {
+            invite_repo.set_used_by(id, user_id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Frees a claimed invite after the registration failed.
+    pub(super) async fn release_invite(&self, invite_id: Option<i64>) -> Result<(), AppError> {
+        if let (Some(invite_repo), Some(id)) = (&self.invite_repo, invite_id) {
+            invite_repo.release(id).await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+//! Account recovery with one-time codes, issued at registration and
+//! regenerated from the settings page.
+
+use blog_shared::{AuthResponse, RecoverAccountRequest, RegenerateRecoveryCodesRequest};
+use tracing::{info, instrument};
+
+use super::{AuthService, hash_password, user_to_dto, verify_password};
+use crate::domain::{AppError, generate_recovery_codes, hash_recovery_code};
+
+impl AuthService {
+    /// Spends a recovery code to set a new password and sign in. All other
+    /// sessions are signed out.
+    #[instrument(skip(self, req), fields(username = %req.username))]
+    pub async fn recover(
+        &self,
+        req: RecoverAccountRequest,
+        user_agent: Option<&str>,
+    ) -> Result<AuthResponse, AppError> {
+        let user = self
+            .find_by_login(req.username.trim())
+            .await?
+            .ok_or(AppError::InvalidCredentials)?;
+        if user.suspended_at.is_some() {
+            return Err(AppError::AccountSuspended);
+        }
+        if !self
+            .recovery_repo
+            .redeem(user.id, &hash_recovery_code(&req.code), self.clock.now())
+            .await?
+        {
+            return Err(AppError::InvalidCredentials);
+        }
+
+        let password_hash = hash_password(&req.new_password)?;
+        self.user_repo
+            .update_password_hash(user.id, &password_hash)
+            .await?;
+        self.user_repo.delete_other_sessions(user.id, None).await?;
+        let token = self.start_session(user.id, user_agent).await?;
+
+        info!(user_id = user.id, "Account recovered");
+
+        Ok(AuthResponse::new(token, user_to_dto(&user)))
+    }
+
+    /// Replaces a user's recovery codes (requires their current password).
+    #[instrument(skip(self, req))]
+    pub async fn regenerate_recovery_codes(
+        &self,
+        user_id: i64,
+        req: RegenerateRecoveryCodesRequest,
+    ) -> Result<Vec<String>, AppError> {
+        let user = self
+            .user_repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+        verify_password(&req.current_password, &user.password_hash)?;
+
+        let codes = self.issue_recovery_codes(user_id).await?;
+
+        info!("Recovery codes regenerated");
+
+        Ok(codes)
+    }
+
+    /// Generates new recovery codes for a user, replacing the old ones.
+    pub(super) async fn issue_recovery_codes(&self, user_id: i64) -> Result<Vec<String>, AppError> {
+        let codes = generate_recovery_codes();
+        let hashes: Vec<String> = codes.iter().map(|code| hash_recovery_code(code)).collect();
+        self.recovery_repo
+            .replace(user_id, &hashes, self.clock.now())
+            .await?;
+
+        Ok(codes)
+    }
+}
@@ -0,0 +1,187 @@
+//! Service accounts: long-lived, scoped tokens for bots and integrations,
+//! managed by admins.
+
+use blog_shared::constants::MAX_SERVICE_ACCOUNT_NAME_LENGTH;
+use blog_shared::{
+    CreateServiceAccountRequest, CreatedServiceAccountResponse, ServiceAccountDto,
+    ServiceAccountListResponse, ServiceAccountQuotaRequest, ServiceScope,
+};
+use chrono::Duration;
+use tracing::{info, instrument};
+
+use super::AuthService;
+use crate::constants::SERVICE_TOKEN_EXPIRY_DAYS;
+use crate::domain::{ApiQuota, AppError, ServiceAccount};
+use crate::infrastructure::jwt;
+
+impl AuthService {
+    /// Creates a service account acting as a user and mints its token,
+    /// which is only returned here. Without scopes, the token is read-only.
+    #[instrument(skip(self, req), fields(name = %req.name, user_id = req.user_id))]
+    pub async fn create_service_account(
+        &self,
+        created_by: i64,
+        req: CreateServiceAccountRequest,
+    ) -> Result<CreatedServiceAccountResponse, AppError> {
+        let name = validate_name(&req.name)?;
+        self.user_repo
+            .find_by_id(req.user_id)
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+
+        let mut scopes = req.scopes;
+        if scopes.is_empty() {
+            scopes.push(ServiceScope::Read);
+        }
+        scopes.sort();
+        scopes.dedup();
+        let stored_scopes: Vec<&str> = scopes.iter().map(ServiceScope::as_str).collect();
+
+        let now = self.clock.now();
+        let expires_at = now + Duration::days(SERVICE_TOKEN_EXPIRY_DAYS);
+        let account = self
+            .service_account_repo
+            .create(
+                &name,
+                req.user_id,
+                &stored_scopes.join(","),
+                created_by,
+                expires_at,
+                now,
+            )
+            .await?;
+        let token = jwt::create_service_token(
+            account.user_id,
+            account.id,
+            scopes,
+            now,
+            expires_at,
+            &self.jwt,
+        )?;
+
+        info!(service_account_id = account.id, "Service account created");
+
+        Ok(CreatedServiceAccountResponse {
+            account: service_account_to_dto(&account),
+            token,
+        })
+    }
+
+    /// Lists all service accounts, newest first, including revoked ones.
+    #[instrument(skip(self))]
+    pub async fn list_service_accounts(&self) -> Result<ServiceAccountListResponse, AppError> {
+        let accounts = self.service_account_repo.list().await?;
+
+        Ok(ServiceAccountListResponse {
+            service_accounts: accounts.iter().map(service_account_to_dto).collect(),
+        })
+    }
+
+    /// Revokes a service account; its token stops working immediately.
+    #[instrument(skip(self))]
+    pub async fn revoke_service_account(&self, id: i64) -> Result<(), AppError> {
+        if !self
+            .service_account_repo
+            .revoke(id, self.clock.now())
+            .await?
+        {
+            return Err(AppError::ServiceAccountNotFound);
+        }
+
+        info!("Service account revoked");
+
+        Ok(())
+    }
+
+    /// Sets a service account's daily quotas; they apply from its next
+    /// request.
+    #[instrument(skip(self, req))]
+    pub async fn set_service_account_quota(
+        &self,
+        id: i64,
+        req: ServiceAccountQuotaRequest,
+    ) -> Result<ServiceAccountDto, AppError> {
+        let quota = ApiQuota {
+            requests_per_day: validate_limit(req.requests_per_day)?,
+            writes_per_day: validate_limit(req.writes_per_day)?,
+        };
+        if !self.service_account_repo.set_quota(id, quota).await? {
+            return Err(AppError::ServiceAccountNotFound);
+        }
+        let account = self
+            .service_account_repo
+            .find(id)
+            .await?
+            .ok_or(AppError::ServiceAccountNotFound)?;
+
+        info!(
+            requests_per_day = ?quota.requests_per_day,
+            writes_per_day = ?quota.writes_per_day,
+            "Service account quota set"
+        );
+
+        Ok(service_account_to_dto(&account))
+    }
+
+    /// Checks that the service account a token was minted for still exists,
+    /// acts as the token's user, and is neither revoked nor expired.
+    pub(super) async fn verify_service_account(
+        &self,
+        user_id: i64,
+        account_id: &str,
+    ) -> Result<(), AppError> {
+        let account_id: i64 = account_id
+            .parse()
+            .map_err(|_| AppError::InvalidCredentials)?;
+        self.service_account_repo
+            .find(account_id)
+            .await?
+            .filter(|account| account.user_id == user_id && account.is_active(self.clock.now()))
+            .ok_or(AppError::InvalidCredentials)?;
+
+        Ok(())
+    }
+}
+
+/// Trims a service account name and checks its length.
+fn validate_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation(
+            "Service account name cannot be empty".to_string(),
+        ));
+    }
+    if name.chars().count() > MAX_SERVICE_ACCOUNT_NAME_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Service account name must be at most {MAX_SERVICE_ACCOUNT_NAME_LENGTH} characters"
+        )));
+    }
+
+    Ok(name.to_string())
+}
+
+/// Checks that a daily limit is not negative (0 blocks all such requests).
+fn validate_limit(limit: Option<i64>) -> Result<Option<i64>, AppError> {
+    match limit {
+        Some(limit) if limit < 0 => Err(AppError::Validation(
+            "Quota limits cannot be negative".to_string(),
+        )),
+        limit => Ok(limit),
+    }
+}
+
+/// Converts a ServiceAccount domain entity to ServiceAccountDto.
+fn service_account_to_dto(account: &ServiceAccount) -> ServiceAccountDto {
+    ServiceAccountDto {
+        id: account.id,
+        name: account.name.clone(),
+        user_id: account.user_id,
+        username: account.username.clone(),
+        scopes: account.scopes(),
+        created_at: account.created_at,
+        expires_at: account.expires_at,
+        revoked_at: account.revoked_at,
+        requests_per_day: account.requests_per_day,
+        writes_per_day: account.writes_per_day,
+    }
+}
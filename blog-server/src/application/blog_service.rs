@@ -1,72 +1,375 @@
 //! Blog service for post operations.
 
+mod orgs;
+mod revisions;
+mod search;
+mod tags;
+mod trash;
+mod workflow;
+
 use std::sync::Arc;
 
-use blog_shared::{CreatePostRequest, PostDto, PostListResponse, UpdatePostRequest};
+use blog_shared::constants::DEFAULT_LANGUAGE;
+use blog_shared::{
+    CreatePostRequest, CreateTranslationRequest, LinkPreviewDto, PostAuthor, PostDto,
+    PostListResponse, PostStatus, TagDto, TagListResponse, TagSort, TranslationDto,
+    TranslationListResponse, UpdatePostRequest,
+};
+use chrono::{DateTime, Duration, Utc};
 use tracing::{info, instrument};
 
-use crate::data::PostRepository;
-use crate::domain::{AppError, Post};
+use crate::application::ModerationService;
+use crate::constants::{FEATURE_EDITORIAL_REVIEW, POPULAR_TAGS_DAYS};
+use crate::data::{FeatureFlagRepository, FtsSearchIndex, OrgRepository, PostRepository};
+use crate::domain::{
+    AppError, AuthorName, DomainEvent, EmbedProvider, EntityRef, LinkPreview, NewPost, Post,
+    PostMetadata, SearchIndex, WorkflowAction, external_links, merge_metadata, parse_lang,
+    parse_metadata, parse_source_url, policy, render_markdown,
+};
+use crate::infrastructure::clock::{Clock, SystemClock};
+use crate::infrastructure::event_bus::EventBus;
 
 /// Service for blog post operations.
 #[derive(Clone)]
 pub struct BlogService {
     post_repo: Arc<PostRepository>,
+    embed_providers: Vec<EmbedProvider>,
+    moderation: ModerationService,
+    flag_repo: Option<Arc<FeatureFlagRepository>>,
+    trash_days: Option<u32>,
+    org_repo: Option<Arc<OrgRepository>>,
+    search_index: Arc<dyn SearchIndex>,
+    events: Option<EventBus>,
+    clock: Arc<dyn Clock>,
 }
 
 impl BlogService {
-    /// Creates a new BlogService.
-    pub fn new(post_repo: Arc<PostRepository>) -> Self {
-        Self { post_repo }
+    /// Creates a new BlogService that embeds URLs of `embed_providers` and
+    /// screens new and edited posts with `moderation`.
+    pub fn new(
+        post_repo: Arc<PostRepository>,
+        embed_providers: Vec<EmbedProvider>,
+        moderation: ModerationService,
+    ) -> Self {
+        Self {
+            search_index: Arc::new(FtsSearchIndex::new(Arc::clone(&post_repo))),
+            post_repo,
+            embed_providers,
+            moderation,
+            flag_repo: None,
+            trash_days: None,
+            org_repo: None,
+            events: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Reads feature flags from `flag_repo`. Without it, editorial review
+    /// is off and new posts are published at once.
+    pub fn with_flags(mut self, flag_repo: Arc<FeatureFlagRepository>) -> Self {
+        self.flag_repo = Some(flag_repo);
+        self
+    }
+
+    /// Reports deleted posts as purged after `trash_days` (see
+    /// `RetentionConfig::trash_days`); `None` keeps them forever.
+    pub fn with_trash_days(mut self, trash_days: Option<u32>) -> Self {
+        self.trash_days = trash_days;
+        self
     }
 
-    /// Creates a new post.
+    /// Lets members post as organizations and edit their posts. Without
+    /// it, posts are always their author's own.
+    pub fn with_orgs(mut self, org_repo: Arc<OrgRepository>) -> Self {
+        self.org_repo = Some(org_repo);
+        self
+    }
+
+    /// Searches `search_index` instead of the database's full-text index.
+    pub fn with_search_index(mut self, search_index: Arc<dyn SearchIndex>) -> Self {
+        self.search_index = search_index;
+        self
+    }
+
+    /// Publishes `DomainEvent::PostChanged` on `events` whenever a post is
+    /// created, edited, moved in or out of the trash, or changes status.
+    pub fn with_events(mut self, events: EventBus) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Tells subscribers, such as the search indexer, that a post changed.
+    pub fn post_changed(&self, post_id: i64) {
+        if let Some(events) = &self.events {
+            events.publish(DomainEvent::PostChanged { post_id });
+        }
+    }
+
+    /// Renders Markdown as post content is rendered.
+    pub fn render_content(&self, source: &str) -> String {
+        render_markdown(source, &self.embed_providers)
+    }
+
+    /// Creates a new post, published under the organization `req.org` names
+    /// if given (the author must be a member).
     #[instrument(skip(self, req), fields(author_id = author_id))]
     pub async fn create_post(
         &self,
         author_id: i64,
         req: CreatePostRequest,
     ) -> Result<PostDto, AppError> {
+        let lang = match req.lang.as_deref() {
+            Some(lang) => parse_lang(lang)?,
+            None => DEFAULT_LANGUAGE.to_string(),
+        };
+        let canonical_url = source_url("Canonical URL", req.canonical_url.as_deref())?;
+        let original_source = source_url("Original source", req.original_source.as_deref())?;
+        let org = match req.org.as_deref() {
+            Some(slug) => Some(self.posting_org(author_id, slug).await?),
+            None => None,
+        };
+
+        let mut post = self
+            .insert_post(author_id, &req.title, &req.content, &req.tags, &lang, None)
+            .await?;
+        if canonical_url.is_some() || original_source.is_some() {
+            self.post_repo
+                .set_sources(
+                    post.id,
+                    canonical_url.as_deref(),
+                    original_source.as_deref(),
+                )
+                .await?;
+            post.canonical_url = canonical_url;
+            post.original_source = original_source;
+        }
+        if let Some(org) = org {
+            self.post_repo.set_org(post.id, org.id).await?;
+            post.author = Some(PostAuthor::Org {
+                id: org.id,
+                slug: org.slug,
+                name: org.name,
+            });
+        }
+
+        Ok(post)
+    }
+
+    /// Adds a language variant of post `id`. Only those who may edit the
+    /// post can translate it, and each language is allowed once per post.
+    #[instrument(skip(self, req), fields(post_id = id, author_id = author_id))]
+    pub async fn create_translation(
+        &self,
+        id: i64,
+        author_id: i64,
+        req: CreateTranslationRequest,
+    ) -> Result<PostDto, AppError> {
+        let source = self
+            .post_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(AppError::PostNotFound)?;
+        self.authorize_edit(author_id, &source).await?;
+
+        let lang = parse_lang(&req.lang)?;
+        if self
+            .post_repo
+            .has_translation(&source.translation_group_id, &lang)
+            .await?
+        {
+            return Err(AppError::Validation(format!(
+                "The post already has a `{lang}` version"
+            )));
+        }
+
+        self.insert_post(
+            author_id,
+            &req.title,
+            &req.content,
+            &req.tags,
+            &lang,
+            Some(&source.translation_group_id),
+        )
+        .await
+    }
+
+    /// Lists the language variants of post `id` visible to `viewer_id`,
+    /// including the post itself.
+    #[instrument(skip(self))]
+    pub async fn list_translations(
+        &self,
+        id: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<TranslationListResponse, AppError> {
+        let post = self
+            .post_repo
+            .find_visible(id, viewer_id)
+            .await?
+            .ok_or(AppError::PostNotFound)?;
+        let posts = self
+            .post_repo
+            .list_translations(&post.translation_group_id, viewer_id)
+            .await?;
+
+        Ok(TranslationListResponse {
+            translations: posts
+                .into_iter()
+                .map(|post| TranslationDto {
+                    id: post.id,
+                    public_id: post.public_id,
+                    lang: post.lang,
+                    title: post.title,
+                })
+                .collect(),
+        })
+    }
+
+    /// Screens and stores a post with its tags and links.
+    async fn insert_post(
+        &self,
+        author_id: i64,
+        title: &str,
+        content: &str,
+        tags: &[String],
+        lang: &str,
+        translation_group_id: Option<&str>,
+    ) -> Result<PostDto, AppError> {
+        let flagged_by = self
+            .moderation
+            .screen(&format!("{title}\n\n{content}"))
+            .await?;
+
+        let status = self.initial_status().await?;
         let post = self
             .post_repo
-            .create(&req.title, &req.content, author_id)
+            .create(NewPost {
+                title,
+                content,
+                author_id,
+                lang,
+                translation_group_id,
+                status,
+                now: self.clock.now(),
+            })
+            .await?;
+        if !tags.is_empty() {
+            self.post_repo.set_tags(post.id, tags).await?;
+        }
+        self.post_repo
+            .set_links(post.id, &external_links(&post.content))
             .await?;
-        let author_username = self.post_repo.find_author_username(post.author_id).await?;
+        self.moderation.flag_post(post.id, &flagged_by).await?;
 
-        info!(post_id = post.id, "Post created");
+        info!(post_id = post.id, lang, %status, "Post created");
+        self.post_changed(post.id);
 
-        Ok(post_to_dto(&post, author_username))
+        self.to_dto(&post, Some(author_id)).await
     }
 
-    /// Gets a post by ID.
+    /// Finds the ID of a post named in an API path.
+    pub async fn resolve_post(&self, post: &EntityRef) -> Result<i64, AppError> {
+        self.post_repo
+            .resolve_id(post)
+            .await?
+            .ok_or(AppError::PostNotFound)
+    }
+
+    /// Gets a post by ID if it is visible to `viewer_id`.
+    ///
+    /// Posts of shadow-banned authors are `PostNotFound` for everyone else.
     #[instrument(skip(self))]
-    pub async fn get_post(&self, id: i64) -> Result<PostDto, AppError> {
+    pub async fn get_post(&self, id: i64, viewer_id: Option<i64>) -> Result<PostDto, AppError> {
         let post = self
             .post_repo
-            .find_by_id(id)
+            .find_visible(id, viewer_id)
             .await?
             .ok_or(AppError::PostNotFound)?;
-        let author_username = self.post_repo.find_author_username(post.author_id).await?;
 
-        Ok(post_to_dto(&post, author_username))
+        self.to_dto(&post, viewer_id).await
+    }
+
+    /// Finds when anything shown about post `id` last changed: edits, status,
+    /// trash, transfers, tags and link previews.
+    pub async fn post_modified_at(&self, id: i64) -> Result<Option<DateTime<Utc>>, AppError> {
+        self.post_repo.modified_at(id).await
+    }
+
+    /// Finds when any post last changed, which bounds every post list.
+    pub async fn posts_modified_at(&self) -> Result<Option<DateTime<Utc>>, AppError> {
+        self.post_repo.latest_modification().await
     }
 
-    /// Lists posts with pagination.
+    /// Lists posts visible to `viewer_id` with pagination, only those in
+    /// `lang` if given.
     #[instrument(skip(self))]
-    pub async fn list_posts(&self, limit: i64, offset: i64) -> Result<PostListResponse, AppError> {
-        let posts = self.post_repo.list(limit, offset).await?;
-        let total = self.post_repo.count().await?;
+    pub async fn list_posts(
+        &self,
+        limit: i64,
+        offset: i64,
+        viewer_id: Option<i64>,
+        lang: Option<&str>,
+    ) -> Result<PostListResponse, AppError> {
+        let lang = lang.map(parse_lang).transpose()?;
+        let lang = lang.as_deref();
+        let posts = self.post_repo.list(limit, offset, viewer_id, lang).await?;
+        let total = self.post_repo.count(viewer_id, lang).await?;
 
-        // Convert posts to DTOs with author usernames
-        let mut post_dtos = Vec::with_capacity(posts.len());
-        for post in posts {
-            let author_username = self.post_repo.find_author_username(post.author_id).await?;
-            post_dtos.push(post_to_dto(&post, author_username));
-        }
+        self.to_list_response(posts, total, viewer_id).await
+    }
 
-        Ok(PostListResponse {
-            posts: post_dtos,
-            total,
+    /// Lists posts with a tag visible to `viewer_id`, with pagination, only
+    /// those in `lang` if given.
+    #[instrument(skip(self))]
+    pub async fn list_posts_by_tag(
+        &self,
+        tag: &str,
+        limit: i64,
+        offset: i64,
+        viewer_id: Option<i64>,
+        lang: Option<&str>,
+    ) -> Result<PostListResponse, AppError> {
+        let tag = tag.trim().to_lowercase();
+        let lang = lang.map(parse_lang).transpose()?;
+        let lang = lang.as_deref();
+        let posts = self
+            .post_repo
+            .list_by_tag(&tag, limit, offset, viewer_id, lang)
+            .await?;
+        let total = self.post_repo.count_by_tag(&tag, viewer_id, lang).await?;
+
+        self.to_list_response(posts, total, viewer_id).await
+    }
+
+    /// Lists tags of posts visible to `viewer_id` with their post counts in
+    /// `sort` order.
+    #[instrument(skip(self))]
+    pub async fn list_tags(
+        &self,
+        limit: i64,
+        viewer_id: Option<i64>,
+        sort: TagSort,
+    ) -> Result<TagListResponse, AppError> {
+        let recent_since = self.clock.now() - Duration::days(POPULAR_TAGS_DAYS);
+        let tags = self
+            .post_repo
+            .list_tags(limit, viewer_id, sort, recent_since)
+            .await?;
+
+        Ok(TagListResponse {
+            tags: tags
+                .into_iter()
+                .map(|tag| TagDto {
+                    name: tag.name,
+                    count: tag.post_count,
+                    description: tag.description,
+                })
+                .collect(),
         })
     }
 
@@ -78,62 +381,199 @@ impl BlogService {
         user_id: i64,
         req: UpdatePostRequest,
     ) -> Result<PostDto, AppError> {
-        // Check if post exists and user is the author
         let post = self
             .post_repo
             .find_by_id(id)
             .await?
             .ok_or(AppError::PostNotFound)?;
+        self.authorize_edit(user_id, &post).await?;
+        let canonical_url = match req.canonical_url.as_deref() {
+            Some(url) => parse_source_url("Canonical URL", url)?,
+            None => post.canonical_url.clone(),
+        };
+        let original_source = match req.original_source.as_deref() {
+            Some(url) => parse_source_url("Original source", url)?,
+            None => post.original_source.clone(),
+        };
 
-        if post.author_id != user_id {
-            return Err(AppError::Forbidden);
-        }
+        let flagged_by = if req.title.is_some() || req.content.is_some() {
+            let title = req.title.as_deref().unwrap_or(&post.title);
+            let content = req.content.as_deref().unwrap_or(&post.content);
+            self.moderation
+                .screen(&format!("{title}\n\n{content}"))
+                .await?
+        } else {
+            Vec::new()
+        };
 
-        let updated_post = self
+        if req.canonical_url.is_some() || req.original_source.is_some() {
+            self.post_repo
+                .set_sources(id, canonical_url.as_deref(), original_source.as_deref())
+                .await?;
+        }
+        let mut updated_post = self
             .post_repo
-            .update(id, req.title.as_deref(), req.content.as_deref())
+            .update(
+                id,
+                req.title.as_deref(),
+                req.content.as_deref(),
+                self.clock.now(),
+            )
             .await?;
-        let author_username = self
+        if post.status() == PostStatus::Approved {
+            let status = self
+                .transition(&post, user_id, WorkflowAction::Revise, None, &[])
+                .await?;
+            updated_post.status = status.as_str().to_string();
+        }
+        if let Some(tags) = req.tags {
+            self.post_repo.set_tags(id, &tags).await?;
+        }
+        if req.content.is_some() {
+            self.post_repo
+                .set_links(id, &external_links(&updated_post.content))
+                .await?;
+        }
+        self.moderation.flag_post(id, &flagged_by).await?;
+
+        info!("Post updated");
+        self.post_changed(id);
+
+        self.to_dto(&updated_post, Some(user_id)).await
+    }
+
+    /// Sets or removes custom fields of a post (a `null` value removes its
+    /// key), leaving the others alone. Only those who may edit the post can
+    /// change them.
+    /// Returns all fields after the change.
+    #[instrument(skip(self, changes), fields(post_id = id, user_id = user_id))]
+    pub async fn update_metadata(
+        &self,
+        id: i64,
+        user_id: i64,
+        changes: PostMetadata,
+    ) -> Result<PostMetadata, AppError> {
+        let post = self
             .post_repo
-            .find_author_username(updated_post.author_id)
+            .find_by_id(id)
+            .await?
+            .ok_or(AppError::PostNotFound)?;
+        self.authorize_edit(user_id, &post).await?;
+
+        let (metadata, stored) = merge_metadata(&post.metadata, changes)?;
+        self.post_repo
+            .set_metadata(id, &stored, self.clock.now())
             .await?;
 
-        info!("Post updated");
+        info!(keys = metadata.len(), "Post metadata updated");
 
-        Ok(post_to_dto(&updated_post, author_username))
+        Ok(metadata)
     }
 
-    /// Deletes a post. Only the author can delete their own posts.
+    /// Moves a post to its author's trash. Only the author, or an owner of
+    /// the post's organization, can delete it.
     #[instrument(skip(self), fields(post_id = id, user_id = user_id))]
     pub async fn delete_post(&self, id: i64, user_id: i64) -> Result<(), AppError> {
-        // Check if post exists and user is the author
         let post = self
             .post_repo
             .find_by_id(id)
             .await?
             .ok_or(AppError::PostNotFound)?;
+        self.authorize_delete(user_id, &post).await?;
 
-        if post.author_id != user_id {
-            return Err(AppError::Forbidden);
-        }
-
-        self.post_repo.delete(id).await?;
+        self.post_repo.delete(id, self.clock.now()).await?;
 
-        info!("Post deleted");
+        info!("Post moved to trash");
+        self.post_changed(id);
 
         Ok(())
     }
+
+    /// Converts a post to a DTO for `viewer_id`, loading its author's names
+    /// and organization, tags, and link previews.
+    async fn to_dto(&self, post: &Post, viewer_id: Option<i64>) -> Result<PostDto, AppError> {
+        let author_name = self.post_repo.find_author(post.author_id).await?;
+        let author = self.post_author(post, &author_name.username).await?;
+        let tags = self.post_repo.find_tags(post.id).await?;
+        let link_previews = self.post_repo.find_link_previews(post.id).await?;
+
+        let content_html = self.render_content(&post.content);
+
+        Ok(post_to_dto(
+            post,
+            author_name,
+            author,
+            tags,
+            content_html,
+            link_previews,
+            policy::is_author(viewer_id, post),
+        ))
+    }
+
+    /// Converts a page of posts to a list response for `viewer_id`.
+    async fn to_list_response(
+        &self,
+        posts: Vec<Post>,
+        total: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<PostListResponse, AppError> {
+        let mut post_dtos = Vec::with_capacity(posts.len());
+        for post in posts {
+            post_dtos.push(self.to_dto(&post, viewer_id).await?);
+        }
+
+        Ok(PostListResponse::new(post_dtos, total))
+    }
 }
 
 /// Converts a Post domain entity to PostDto.
-fn post_to_dto(post: &Post, author_username: String) -> PostDto {
-    PostDto {
-        id: post.id,
-        title: post.title.clone(),
-        content: post.content.clone(),
-        author_id: post.author_id,
-        author_username,
-        created_at: post.created_at,
-        updated_at: post.updated_at,
-    }
+fn post_to_dto(
+    post: &Post,
+    author_name: AuthorName,
+    author: PostAuthor,
+    tags: Vec<String>,
+    content_html: String,
+    link_previews: Vec<LinkPreview>,
+    is_owner: bool,
+) -> PostDto {
+    let mut dto = PostDto::new(
+        post.id,
+        post.title.clone(),
+        post.content.clone(),
+        post.author_id,
+        author_name.username,
+        post.created_at,
+        post.updated_at,
+    );
+    dto.public_id = post.public_id.clone();
+    dto.author_public_id = author_name.public_id;
+    dto.content_html = content_html;
+    dto.author = Some(author);
+    dto.tags = tags;
+    dto.link_previews = link_previews
+        .into_iter()
+        .map(|preview| LinkPreviewDto {
+            url: preview.url,
+            title: preview.title,
+            description: preview.description,
+            image_url: preview.image_url,
+            site_name: preview.site_name,
+        })
+        .collect();
+    dto.is_owner = is_owner;
+    dto.lang = post.lang.clone();
+    dto.translation_group_id = post.translation_group_id.clone();
+    dto.canonical_url = post.canonical_url.clone();
+    dto.original_source = post.original_source.clone();
+    dto.metadata = parse_metadata(&post.metadata);
+    dto.status = post.status();
+    dto
+}
+
+/// Validates an optional source URL of a new post.
+fn source_url(field: &str, url: Option<&str>) -> Result<Option<String>, AppError> {
+    Ok(url
+        .map(|url| parse_source_url(field, url))
+        .transpose()?
+        .flatten())
 }
@@ -0,0 +1,108 @@
+//! Posts published under organizations, and what their members may do with
+//! them.
+
+use blog_shared::{OrgRole, PostAuthor, PostListResponse};
+use tracing::instrument;
+
+use super::BlogService;
+use crate::domain::{AppError, Organization, Post, policy};
+
+impl BlogService {
+    /// Lists posts of an organization visible to `viewer_id` with pagination.
+    #[instrument(skip(self))]
+    pub async fn list_posts_by_org(
+        &self,
+        org_id: i64,
+        limit: i64,
+        offset: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<PostListResponse, AppError> {
+        let posts = self
+            .post_repo
+            .list_by_org(org_id, limit, offset, viewer_id)
+            .await?;
+        let total = self.post_repo.count_by_org(org_id, viewer_id).await?;
+
+        self.to_list_response(posts, total, viewer_id).await
+    }
+
+    /// Finds the organization `slug` names for a new post by `author_id`,
+    /// who must be one of its members.
+    pub(super) async fn posting_org(
+        &self,
+        author_id: i64,
+        slug: &str,
+    ) -> Result<Organization, AppError> {
+        let Some(org_repo) = &self.org_repo else {
+            return Err(AppError::Validation(
+                "Organizations are not available".to_string(),
+            ));
+        };
+        let org = org_repo
+            .find_by_slug(&slug.trim().to_lowercase())
+            .await?
+            .ok_or(AppError::OrgNotFound)?;
+        let role = org_repo.member_role(org.id, author_id).await?;
+        policy::authorize(policy::can_post_as_org(
+            role.and_then(|role| role.parse().ok()),
+        ))?;
+
+        Ok(org)
+    }
+
+    /// Checks that `user_id` may edit a post: its author, or a member of the
+    /// organization it is published under.
+    pub(super) async fn authorize_edit(&self, user_id: i64, post: &Post) -> Result<(), AppError> {
+        if policy::can_edit_post(user_id, post) {
+            return Ok(());
+        }
+        let role = self.org_role(user_id, post).await?;
+        policy::authorize(policy::can_edit_org_post(role))
+    }
+
+    /// Checks that `user_id` may delete or restore a post: its author, or an
+    /// owner of the organization it is published under.
+    pub(super) async fn authorize_delete(&self, user_id: i64, post: &Post) -> Result<(), AppError> {
+        if policy::can_delete_post(user_id, post) {
+            return Ok(());
+        }
+        let role = self.org_role(user_id, post).await?;
+        policy::authorize(policy::can_delete_org_post(role))
+    }
+
+    /// Returns the role of `user_id` in the organization a post is published
+    /// under, if any.
+    async fn org_role(&self, user_id: i64, post: &Post) -> Result<Option<OrgRole>, AppError> {
+        let Some(org_repo) = &self.org_repo else {
+            return Ok(None);
+        };
+        let role = org_repo.post_member_role(post.id, user_id).await?;
+
+        Ok(role.and_then(|role| role.parse().ok()))
+    }
+
+    /// Returns who a post is shown as written by: its organization, if it
+    /// is published under one, or else its author.
+    pub(super) async fn post_author(
+        &self,
+        post: &Post,
+        author_username: &str,
+    ) -> Result<PostAuthor, AppError> {
+        let org = match &self.org_repo {
+            Some(org_repo) => org_repo.find_post_org(post.id).await?,
+            None => None,
+        };
+
+        Ok(match org {
+            Some(org) => PostAuthor::Org {
+                id: org.id,
+                slug: org.slug,
+                name: org.name,
+            },
+            None => PostAuthor::User {
+                id: post.author_id,
+                username: author_username.to_string(),
+            },
+        })
+    }
+}
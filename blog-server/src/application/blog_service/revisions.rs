@@ -0,0 +1,72 @@
+//! Post revisions: the saved versions of a post and the changes between
+//! them.
+
+use blog_shared::{RevisionDiffResponse, RevisionDto, RevisionListResponse, Role, diff_lines};
+use tracing::instrument;
+
+use super::BlogService;
+use crate::domain::{AppError, PostRevision, policy, render_diff_html};
+
+impl BlogService {
+    /// Lists the revisions of a post, newest first, to its author and
+    /// reviewers.
+    #[instrument(skip(self), fields(post_id = id, user_id = user_id))]
+    pub async fn list_revisions(
+        &self,
+        id: i64,
+        user_id: i64,
+        role: Role,
+    ) -> Result<RevisionListResponse, AppError> {
+        let post = self.find_post(id).await?;
+        policy::authorize(policy::can_view_history(user_id, role, &post))?;
+
+        let revisions = self.post_repo.list_revisions(id).await?;
+
+        Ok(RevisionListResponse {
+            revisions: revisions
+                .into_iter()
+                .map(|revision| RevisionDto {
+                    number: revision.number,
+                    title: revision.title,
+                    created_at: revision.created_at,
+                })
+                .collect(),
+        })
+    }
+
+    /// Diffs revision `from` of a post against revision `to`, line by
+    /// line, for its author and reviewers.
+    #[instrument(skip(self), fields(post_id = id, user_id = user_id))]
+    pub async fn diff_revisions(
+        &self,
+        id: i64,
+        user_id: i64,
+        role: Role,
+        from: i64,
+        to: i64,
+    ) -> Result<RevisionDiffResponse, AppError> {
+        let post = self.find_post(id).await?;
+        policy::authorize(policy::can_view_history(user_id, role, &post))?;
+
+        let old = self.find_revision(id, from).await?;
+        let new = self.find_revision(id, to).await?;
+        let content = diff_lines(&old.content, &new.content);
+
+        Ok(RevisionDiffResponse {
+            post_id: id,
+            from,
+            to,
+            title: diff_lines(&old.title, &new.title),
+            html: render_diff_html(&content),
+            content,
+        })
+    }
+
+    /// Finds revision `number` of post `id`.
+    async fn find_revision(&self, id: i64, number: i64) -> Result<PostRevision, AppError> {
+        self.post_repo
+            .find_revision(id, number)
+            .await?
+            .ok_or(AppError::RevisionNotFound)
+    }
+}
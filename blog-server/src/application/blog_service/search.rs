@@ -0,0 +1,59 @@
+//! Full-text search with filters and snippets.
+
+use blog_shared::PostListResponse;
+use tracing::instrument;
+
+use super::BlogService;
+use crate::domain::{
+    AppError, SearchFilters, normalize_tag, normalize_username, parse_lang, render_snippet,
+};
+
+impl BlogService {
+    /// Searches posts visible to `viewer_id` by title and content with
+    /// pagination, best matches first, narrowed by `filters`.
+    ///
+    /// Matching is up to the search index; the default one tolerates typos
+    /// (see `FtsSearchIndex`). Each post comes with a `snippet` of its best
+    /// matching passage.
+    #[instrument(skip(self))]
+    pub async fn search_posts(
+        &self,
+        query: &str,
+        filters: SearchFilters,
+        limit: i64,
+        offset: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<PostListResponse, AppError> {
+        let filters = SearchFilters {
+            lang: filters.lang.as_deref().map(parse_lang).transpose()?,
+            author: filters.author.as_deref().map(normalize_username),
+            tag: filters.tag.as_deref().map(normalize_tag).transpose()?,
+            ..filters
+        };
+        if let (Some(from), Some(until)) = (filters.from, filters.until)
+            && from >= until
+        {
+            return Err(AppError::Validation(
+                "Search start date must not be after its end date".to_string(),
+            ));
+        }
+
+        let results = self
+            .search_index
+            .search(query, &filters, limit, offset, viewer_id)
+            .await?;
+
+        let mut posts = Vec::with_capacity(results.hits.len());
+        for hit in results.hits {
+            // External indexes may be stale, or unaware of shadow bans
+            let Some(post) = self.post_repo.find_visible(hit.post_id, viewer_id).await? else {
+                continue;
+            };
+            let mut dto = self.to_dto(&post, viewer_id).await?;
+            dto.snippet = Some(render_snippet(&hit.snippet));
+            posts.push(dto);
+        }
+
+        Ok(PostListResponse::new(posts, results.total))
+    }
+}
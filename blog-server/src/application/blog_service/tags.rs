@@ -0,0 +1,174 @@
+//! Tag landing pages, tag autocompletion, and tag management by admins.
+
+use blog_shared::constants::MAX_TAG_DESCRIPTION_LENGTH;
+use blog_shared::{
+    TagDto, TagPageResponse, TagSuggestionDto, TagSuggestionResponse, UpdateTagRequest,
+};
+use tracing::{info, instrument};
+
+use super::BlogService;
+use crate::constants::SUGGESTION_LIMIT;
+use crate::domain::{AppError, Tag, normalize_tag};
+
+impl BlogService {
+    /// Returns the landing page of tag `name`: its description and a page of
+    /// its posts visible to `viewer_id`, only those in `lang` if given.
+    #[instrument(skip(self))]
+    pub async fn tag_page(
+        &self,
+        name: &str,
+        limit: i64,
+        offset: i64,
+        viewer_id: Option<i64>,
+        lang: Option<&str>,
+    ) -> Result<TagPageResponse, AppError> {
+        let tag = self.find_tag(name).await?;
+        let list = self
+            .list_posts_by_tag(&tag.name, limit, offset, viewer_id, lang)
+            .await?;
+
+        Ok(TagPageResponse {
+            name: tag.name,
+            description: tag.description,
+            posts: list.posts,
+            total: list.total,
+        })
+    }
+
+    /// Suggests tags to complete a `#tag` starting with `prefix` (a leading
+    /// `#` ignored), at most `SUGGESTION_LIMIT`. A prefix no tag could start
+    /// with gets no suggestions rather than an error, as it is still being
+    /// typed.
+    #[instrument(skip(self))]
+    pub async fn suggest_tags(&self, prefix: &str) -> Result<TagSuggestionResponse, AppError> {
+        let prefix = prefix.trim().trim_start_matches('#');
+        let prefix = match prefix {
+            "" => String::new(),
+            prefix => match normalize_tag(prefix) {
+                Ok(prefix) => prefix,
+                Err(_) => return Ok(TagSuggestionResponse::default()),
+            },
+        };
+        let tags = self
+            .post_repo
+            .suggest_tags(&prefix, SUGGESTION_LIMIT)
+            .await?;
+
+        Ok(TagSuggestionResponse {
+            tags: tags
+                .into_iter()
+                .map(|tag| TagSuggestionDto {
+                    name: tag.name,
+                    count: tag.post_count,
+                })
+                .collect(),
+        })
+    }
+
+    /// Renames tag `name` and/or changes its description. Renaming onto an
+    /// existing tag fails; merge the tags instead.
+    #[instrument(skip(self, req))]
+    pub async fn update_tag(&self, name: &str, req: UpdateTagRequest) -> Result<TagDto, AppError> {
+        let tag = self.find_tag(name).await?;
+        let new_name = match req.name.as_deref() {
+            Some(new_name) => normalize_tag(new_name)?,
+            None => tag.name.clone(),
+        };
+        if new_name != tag.name && self.post_repo.find_tag(&new_name).await?.is_some() {
+            return Err(AppError::TagExists);
+        }
+        let description = match req.description {
+            Some(description) => tag_description(&description)?,
+            None => tag.description,
+        };
+
+        let updated = self
+            .post_repo
+            .update_tag(tag.id, &new_name, description.as_deref(), self.clock.now())
+            .await?;
+
+        info!(new_name = %updated.name, "Tag updated");
+        self.tagged_posts_changed(tag.id).await?;
+
+        self.tag_dto(updated).await
+    }
+
+    /// Merges tag `name` into tag `into`: posts with `name` get `into`
+    /// instead, and `name` is deleted.
+    #[instrument(skip(self))]
+    pub async fn merge_tags(&self, name: &str, into: &str) -> Result<TagDto, AppError> {
+        let source = self.find_tag(name).await?;
+        let target = self.find_tag(into).await?;
+        if source.id == target.id {
+            return Err(AppError::Validation(
+                "A tag cannot be merged into itself".to_string(),
+            ));
+        }
+
+        let tagged = self.post_repo.list_tagged_post_ids(source.id).await?;
+        self.post_repo
+            .merge_tags(source.id, target.id, self.clock.now())
+            .await?;
+
+        info!(into = %target.name, "Tags merged");
+        tagged.into_iter().for_each(|id| self.post_changed(id));
+
+        let merged = self.find_tag(&target.name).await?;
+        self.tag_dto(merged).await
+    }
+
+    /// Deletes tag `name`, removing it from all posts.
+    #[instrument(skip(self))]
+    pub async fn delete_tag(&self, name: &str) -> Result<(), AppError> {
+        let tag = self.find_tag(name).await?;
+        let tagged = self.post_repo.list_tagged_post_ids(tag.id).await?;
+        self.post_repo.delete_tag(tag.id, self.clock.now()).await?;
+
+        info!("Tag deleted");
+        tagged.into_iter().for_each(|id| self.post_changed(id));
+
+        Ok(())
+    }
+
+    /// Reports every post with tag `tag_id` as changed.
+    async fn tagged_posts_changed(&self, tag_id: i64) -> Result<(), AppError> {
+        if self.events.is_some() {
+            for id in self.post_repo.list_tagged_post_ids(tag_id).await? {
+                self.post_changed(id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Finds a tag by name as given by users, normalized like post tags.
+    async fn find_tag(&self, name: &str) -> Result<Tag, AppError> {
+        let name = normalize_tag(name)?;
+        self.post_repo
+            .find_tag(&name)
+            .await?
+            .ok_or(AppError::TagNotFound)
+    }
+
+    /// Converts a tag to its DTO with the number of published posts using it.
+    async fn tag_dto(&self, tag: Tag) -> Result<TagDto, AppError> {
+        let count = self.post_repo.count_by_tag(&tag.name, None, None).await?;
+
+        Ok(TagDto {
+            name: tag.name,
+            count,
+            description: tag.description,
+        })
+    }
+}
+
+/// Validates a tag description; a blank one clears it.
+fn tag_description(description: &str) -> Result<Option<String>, AppError> {
+    let description = description.trim();
+    if description.chars().count() > MAX_TAG_DESCRIPTION_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Tag descriptions must be at most {MAX_TAG_DESCRIPTION_LENGTH} characters"
+        )));
+    }
+
+    Ok((!description.is_empty()).then(|| description.to_string()))
+}
@@ -0,0 +1,54 @@
+//! Authors' trash: deleted posts that can still be restored until the
+//! retention job purges them.
+
+use blog_shared::{TrashListResponse, TrashedPostDto};
+use tracing::{info, instrument};
+
+use super::BlogService;
+use crate::domain::{AppError, days_until};
+
+impl BlogService {
+    /// Lists the deleted posts of `user_id`, most recently deleted first,
+    /// with when each is purged.
+    #[instrument(skip(self), fields(user_id = user_id))]
+    pub async fn list_trash(&self, user_id: i64) -> Result<TrashListResponse, AppError> {
+        let now = self.clock.now();
+        let posts = self.post_repo.list_trash(user_id).await?;
+
+        Ok(TrashListResponse {
+            posts: posts
+                .into_iter()
+                .map(|post| {
+                    let purge_at = post.purge_at(self.trash_days);
+                    TrashedPostDto {
+                        id: post.id,
+                        public_id: post.public_id,
+                        title: post.title,
+                        deleted_at: post.deleted_at,
+                        purge_at,
+                        days_until_purge: purge_at.map(|at| days_until(at, now)),
+                    }
+                })
+                .collect(),
+        })
+    }
+
+    /// Takes a post out of the trash. Only the author, or an owner of the
+    /// post's organization, can restore it.
+    #[instrument(skip(self), fields(post_id = id, user_id = user_id))]
+    pub async fn restore_post(&self, id: i64, user_id: i64) -> Result<(), AppError> {
+        let post = self
+            .post_repo
+            .find_trashed(id)
+            .await?
+            .ok_or(AppError::PostNotFound)?;
+        self.authorize_delete(user_id, &post).await?;
+
+        self.post_repo.restore(id, self.clock.now()).await?;
+
+        info!("Post restored from trash");
+        self.post_changed(id);
+
+        Ok(())
+    }
+}
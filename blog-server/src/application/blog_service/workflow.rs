@@ -0,0 +1,225 @@
+//! Editorial workflow: submitting posts for review, review decisions, and
+//! publishing approved posts.
+
+use blog_shared::constants::{MAX_REVIEW_COMMENT_LENGTH, MAX_REVIEW_NOTES};
+use blog_shared::{
+    PostDto, PostHistoryResponse, PostListResponse, PostStatus, PostTransitionDto, ReviewDecision,
+    ReviewNoteDto, ReviewPostRequest, Role,
+};
+use tracing::{info, instrument};
+
+use super::{BlogService, FEATURE_EDITORIAL_REVIEW};
+use crate::domain::{AppError, NewTransition, Post, WorkflowAction, policy};
+
+impl BlogService {
+    /// Submits a draft or a post with requested changes for review. Only
+    /// those who may edit a post can submit it.
+    #[instrument(skip(self), fields(post_id = id, user_id = user_id))]
+    pub async fn submit_post(&self, id: i64, user_id: i64) -> Result<PostDto, AppError> {
+        let post = self.find_post(id).await?;
+        self.authorize_edit(user_id, &post).await?;
+
+        self.transition(&post, user_id, WorkflowAction::Submit, None, &[])
+            .await?;
+
+        self.reload(id, user_id).await
+    }
+
+    /// Approves a post in review or sends it back to its author with
+    /// comments. Editors cannot review their own posts.
+    #[instrument(skip(self, req), fields(post_id = id, reviewer_id = reviewer_id))]
+    pub async fn review_post(
+        &self,
+        id: i64,
+        reviewer_id: i64,
+        role: Role,
+        req: ReviewPostRequest,
+    ) -> Result<PostDto, AppError> {
+        let post = self.find_post(id).await?;
+        policy::authorize(policy::can_review_post(reviewer_id, role, &post))?;
+
+        let comment = req.comment.trim();
+        validate_review(&post, comment, &req.notes)?;
+        let action = match req.decision {
+            ReviewDecision::Approve => WorkflowAction::Approve,
+            ReviewDecision::RequestChanges if comment.is_empty() && req.notes.is_empty() => {
+                return Err(AppError::Validation(
+                    "Requesting changes needs a comment".into(),
+                ));
+            }
+            ReviewDecision::RequestChanges => WorkflowAction::RequestChanges,
+        };
+
+        let comment = (!comment.is_empty()).then_some(comment);
+        self.transition(&post, reviewer_id, action, comment, &req.notes)
+            .await?;
+
+        self.reload(id, reviewer_id).await
+    }
+
+    /// Publishes an approved post. The author and reviewers can publish it.
+    #[instrument(skip(self), fields(post_id = id, user_id = user_id))]
+    pub async fn publish_post(
+        &self,
+        id: i64,
+        user_id: i64,
+        role: Role,
+    ) -> Result<PostDto, AppError> {
+        let post = self.find_post(id).await?;
+        policy::authorize(policy::can_publish_post(user_id, role, &post))?;
+
+        self.transition(&post, user_id, WorkflowAction::Publish, None, &[])
+            .await?;
+
+        self.reload(id, user_id).await
+    }
+
+    /// Lists the status changes of a post with their review comments,
+    /// oldest first. Only the author and reviewers can see them.
+    #[instrument(skip(self))]
+    pub async fn post_history(
+        &self,
+        id: i64,
+        user_id: i64,
+        role: Role,
+    ) -> Result<PostHistoryResponse, AppError> {
+        let post = self.find_post(id).await?;
+        policy::authorize(policy::can_view_history(user_id, role, &post))?;
+
+        let transitions = self.post_repo.list_transitions(id).await?;
+        let notes = self.post_repo.list_review_notes(id).await?;
+
+        Ok(PostHistoryResponse {
+            transitions: transitions
+                .into_iter()
+                .map(|transition| PostTransitionDto {
+                    id: transition.id,
+                    actor_id: transition.actor_id,
+                    actor_username: transition.actor_username,
+                    from_status: transition.from_status.parse().unwrap_or_default(),
+                    to_status: transition.to_status.parse().unwrap_or_default(),
+                    comment: transition.comment,
+                    notes: notes
+                        .iter()
+                        .filter(|note| note.transition_id == transition.id)
+                        .map(|note| ReviewNoteDto {
+                            line: note.line as u32,
+                            body: note.body.clone(),
+                        })
+                        .collect(),
+                    created_at: transition.created_at,
+                })
+                .collect(),
+        })
+    }
+
+    /// Lists posts waiting for review, longest waiting first.
+    #[instrument(skip(self))]
+    pub async fn review_queue(
+        &self,
+        limit: i64,
+        offset: i64,
+        reviewer_id: i64,
+    ) -> Result<PostListResponse, AppError> {
+        let posts = self.post_repo.list_in_review(limit, offset).await?;
+        let total = self.post_repo.count_in_review().await?;
+
+        self.to_list_response(posts, total, Some(reviewer_id)).await
+    }
+
+    /// Status of new posts: drafts while editorial review is on.
+    pub(super) async fn initial_status(&self) -> Result<PostStatus, AppError> {
+        let review = match &self.flag_repo {
+            Some(flag_repo) => flag_repo.is_enabled(FEATURE_EDITORIAL_REVIEW).await?,
+            None => false,
+        };
+
+        Ok(if review {
+            PostStatus::Draft
+        } else {
+            PostStatus::Published
+        })
+    }
+
+    /// Takes a workflow step on `post` and logs it. Returns the new status.
+    pub(super) async fn transition(
+        &self,
+        post: &Post,
+        actor_id: i64,
+        action: WorkflowAction,
+        comment: Option<&str>,
+        notes: &[ReviewNoteDto],
+    ) -> Result<PostStatus, AppError> {
+        let from = post.status();
+        let to = action.apply(from)?;
+        self.post_repo
+            .record_transition(NewTransition {
+                post_id: post.id,
+                actor_id,
+                from,
+                to,
+                comment,
+                notes,
+                now: self.clock.now(),
+            })
+            .await?;
+
+        info!(
+            post_id = post.id,
+            actor_id,
+            %from,
+            %to,
+            "Post status changed"
+        );
+        self.post_changed(post.id);
+
+        Ok(to)
+    }
+
+    /// Finds a post regardless of its visibility.
+    pub(super) async fn find_post(&self, id: i64) -> Result<Post, AppError> {
+        self.post_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(AppError::PostNotFound)
+    }
+
+    /// Reads a post back after a status change, for `viewer_id`.
+    async fn reload(&self, id: i64, viewer_id: i64) -> Result<PostDto, AppError> {
+        let post = self.find_post(id).await?;
+        self.to_dto(&post, Some(viewer_id)).await
+    }
+}
+
+/// Checks the length of a review comment and that every note points at a
+/// line of the post.
+fn validate_review(post: &Post, comment: &str, notes: &[ReviewNoteDto]) -> Result<(), AppError> {
+    if comment.chars().count() > MAX_REVIEW_COMMENT_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Review comment must be at most {MAX_REVIEW_COMMENT_LENGTH} characters"
+        )));
+    }
+    if notes.len() > MAX_REVIEW_NOTES {
+        return Err(AppError::Validation(format!(
+            "A review can have at most {MAX_REVIEW_NOTES} line comments"
+        )));
+    }
+
+    let lines = post.content.lines().count().max(1);
+    for note in notes {
+        if note.line == 0 || note.line as usize > lines {
+            return Err(AppError::Validation(format!(
+                "Line {} is not in the post (it has {lines} lines)",
+                note.line
+            )));
+        }
+        let body = note.body.trim();
+        if body.is_empty() || body.chars().count() > MAX_REVIEW_COMMENT_LENGTH {
+            return Err(AppError::Validation(format!(
+                "Line comments must be 1 to {MAX_REVIEW_COMMENT_LENGTH} characters"
+            )));
+        }
+    }
+
+    Ok(())
+}
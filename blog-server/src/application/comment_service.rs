@@ -0,0 +1,264 @@
+//! Comment service for comment operations.
+
+use std::sync::Arc;
+
+use blog_shared::{
+    CommentDto, CommentListResponse, CreateCommentRequest, CursorPage, NotificationKind,
+    UpdateCommentRequest,
+};
+use tracing::{info, instrument, warn};
+
+use crate::application::{ModerationService, NotificationService};
+use crate::constants::FEATURE_COMMENTS;
+use crate::data::{CommentRepository, FeatureFlagRepository, PostRepository};
+use crate::domain::{AppError, Comment, DomainEvent, PageCursor, Post, policy};
+use crate::infrastructure::clock::{Clock, SystemClock};
+use crate::infrastructure::event_bus::EventBus;
+
+/// Service for comment operations.
+#[derive(Clone)]
+pub struct CommentService {
+    comment_repo: Arc<CommentRepository>,
+    post_repo: Arc<PostRepository>,
+    flag_repo: Arc<FeatureFlagRepository>,
+    moderation: ModerationService,
+    events: EventBus,
+    notifications: Option<NotificationService>,
+    clock: Arc<dyn Clock>,
+}
+
+impl CommentService {
+    /// Creates a new CommentService that screens comments with `moderation`
+    /// and announces new ones on `events`.
+    pub fn new(
+        comment_repo: Arc<CommentRepository>,
+        post_repo: Arc<PostRepository>,
+        flag_repo: Arc<FeatureFlagRepository>,
+        moderation: ModerationService,
+        events: EventBus,
+    ) -> Self {
+        Self {
+            comment_repo,
+            post_repo,
+            flag_repo,
+            moderation,
+            events,
+            notifications: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Notifies post authors of new comments through `notifications`.
+    /// Without it, nobody is notified.
+    pub fn with_notifications(mut self, notifications: NotificationService) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
+    /// Adds a comment to a post.
+    #[instrument(skip(self, req), fields(post_id = post_id, author_id = author_id))]
+    pub async fn create_comment(
+        &self,
+        post_id: i64,
+        author_id: i64,
+        req: CreateCommentRequest,
+    ) -> Result<CommentDto, AppError> {
+        if !self.flag_repo.is_enabled(FEATURE_COMMENTS).await? {
+            return Err(AppError::FeatureDisabled("Commenting".to_string()));
+        }
+        let post = self.find_post(post_id, Some(author_id)).await?;
+        let flagged_by = self.moderation.screen(&req.content).await?;
+
+        let comment = self
+            .comment_repo
+            .create(post_id, author_id, &req.content, self.clock.now())
+            .await?;
+        self.moderation
+            .flag_comment(post_id, comment.id, &flagged_by)
+            .await?;
+
+        let hidden = self.comment_repo.is_hidden(comment.id).await?;
+
+        info!(comment_id = comment.id, "Comment created");
+
+        // Comments hidden by a shadow ban notify nobody
+        if !hidden {
+            self.notify_post_author(&post, &comment).await;
+        }
+
+        let comment = comment_to_dto(comment);
+        self.events.publish(DomainEvent::CommentCreated {
+            comment: comment.clone(),
+            post_id: comment.post_id,
+            author_id: comment.author_id,
+            hidden,
+        });
+
+        Ok(comment)
+    }
+
+    /// Lists comments on a post visible to `viewer_id` with pagination.
+    #[instrument(skip(self))]
+    pub async fn list_comments(
+        &self,
+        post_id: i64,
+        limit: i64,
+        offset: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<CommentListResponse, AppError> {
+        self.ensure_post_exists(post_id, viewer_id).await?;
+
+        let comments = self
+            .comment_repo
+            .list_by_post(post_id, limit, offset, viewer_id)
+            .await?;
+        let total = self.comment_repo.count_by_post(post_id, viewer_id).await?;
+
+        Ok(CommentListResponse::new(
+            comments.into_iter().map(comment_to_dto).collect(),
+            Some(total),
+        ))
+    }
+
+    /// Lists comments on a post visible to `viewer_id`, oldest first, a page
+    /// at a time, with the cursor of the next page. Only the first page (no
+    /// cursor) carries the total.
+    #[instrument(skip(self))]
+    pub async fn list_comments_after(
+        &self,
+        post_id: i64,
+        page: &CursorPage,
+        viewer_id: Option<i64>,
+    ) -> Result<CommentListResponse, AppError> {
+        let after = page.cursor.as_deref().map(PageCursor::parse).transpose()?;
+        self.ensure_post_exists(post_id, viewer_id).await?;
+
+        let mut comments = self
+            .comment_repo
+            .list_by_post_after(post_id, page.limit + 1, after, viewer_id)
+            .await?;
+        let next_cursor = PageCursor::next_page(&mut comments, page.limit, |c| {
+            PageCursor::new(c.created_at, c.id)
+        });
+        let total = match page.cursor {
+            None => Some(self.comment_repo.count_by_post(post_id, viewer_id).await?),
+            Some(_) => None,
+        };
+
+        Ok(
+            CommentListResponse::new(comments.into_iter().map(comment_to_dto).collect(), total)
+                .with_next_cursor(next_cursor.map(|cursor| cursor.encode())),
+        )
+    }
+
+    /// Updates a comment. Only the comment author can edit it.
+    #[instrument(skip(self, req), fields(comment_id = id, user_id = user_id))]
+    pub async fn update_comment(
+        &self,
+        id: i64,
+        user_id: i64,
+        req: UpdateCommentRequest,
+    ) -> Result<CommentDto, AppError> {
+        let comment = self
+            .comment_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(AppError::CommentNotFound)?;
+        policy::authorize(policy::can_edit_comment(user_id, &comment))?;
+
+        let flagged_by = self.moderation.screen(&req.content).await?;
+
+        let updated = self
+            .comment_repo
+            .update(id, &req.content, self.clock.now())
+            .await?;
+        self.moderation
+            .flag_comment(updated.post_id, id, &flagged_by)
+            .await?;
+
+        info!("Comment updated");
+
+        Ok(comment_to_dto(updated))
+    }
+
+    /// Deletes a comment. Allowed for the comment author and the post author.
+    #[instrument(skip(self), fields(comment_id = id, user_id = user_id))]
+    pub async fn delete_comment(&self, id: i64, user_id: i64) -> Result<(), AppError> {
+        let comment = self
+            .comment_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(AppError::CommentNotFound)?;
+        let post = self
+            .post_repo
+            .find_by_id(comment.post_id)
+            .await?
+            .ok_or(AppError::PostNotFound)?;
+        policy::authorize(policy::can_delete_comment(user_id, &comment, &post))?;
+
+        self.comment_repo.delete(id).await?;
+
+        info!("Comment deleted");
+
+        Ok(())
+    }
+
+    /// Returns `PostNotFound` unless the post exists and is visible to `viewer_id`.
+    pub async fn ensure_post_exists(
+        &self,
+        post_id: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<(), AppError> {
+        self.find_post(post_id, viewer_id).await.map(|_| ())
+    }
+
+    /// Finds a post visible to `viewer_id`.
+    async fn find_post(&self, post_id: i64, viewer_id: Option<i64>) -> Result<Post, AppError> {
+        self.post_repo
+            .find_visible(post_id, viewer_id)
+            .await?
+            .ok_or(AppError::PostNotFound)
+    }
+
+    /// Notifies the author of `post` of a new comment. Failures are only
+    /// logged, as the comment is already saved.
+    async fn notify_post_author(&self, post: &Post, comment: &Comment) {
+        let Some(notifications) = &self.notifications else {
+            return;
+        };
+        if let Err(e) = notifications
+            .notify(
+                post.author_id,
+                NotificationKind::Comment,
+                comment.author_id,
+                post.id,
+                Some(comment.id),
+            )
+            .await
+        {
+            warn!(comment_id = comment.id, error = %e, "Comment notification failed");
+        }
+    }
+}
+
+/// Converts a Comment domain entity to CommentDto.
+pub(crate) fn comment_to_dto(comment: Comment) -> CommentDto {
+    let mut dto = CommentDto::new(
+        comment.id,
+        comment.post_id,
+        comment.author_id,
+        comment.author_username,
+        comment.content,
+        comment.created_at,
+        comment.updated_at,
+    );
+    dto.post_public_id = comment.post_public_id;
+    dto.author_public_id = comment.author_public_id;
+    dto
+}
@@ -0,0 +1,324 @@
+//! Cross-post service: integration tokens, publishing posts to DEV and
+//! Hashnode, and the job pushing later edits.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use blog_shared::{
+    CrosspostDto, CrosspostListResponse, CrosspostTarget, IntegrationDto, IntegrationListResponse,
+    IntegrationTokenRequest, PostDto, PostStatus,
+};
+use reqwest::Method;
+use tracing::{info, instrument, warn};
+
+use crate::application::BlogService;
+use crate::constants::{CROSSPOST_SYNC_BATCH_SIZE, CROSSPOST_SYNC_INTERVAL_SECS};
+use crate::data::CrosspostRepository;
+use crate::domain::{
+    AppError, Crosspost, CrosspostApis, Integration, RemotePost, devto_article, hashnode_request,
+    parse_devto_article, parse_hashnode_post, policy,
+};
+use crate::infrastructure::clock::{Clock, SystemClock};
+use crate::infrastructure::http_fetcher::HttpFetcher;
+
+/// Service for cross-posting to other blogging services.
+#[derive(Clone)]
+pub struct CrosspostService {
+    crosspost_repo: Arc<CrosspostRepository>,
+    blog_service: BlogService,
+    fetcher: HttpFetcher,
+    apis: CrosspostApis,
+    site_url: String,
+    clock: Arc<dyn Clock>,
+}
+
+impl CrosspostService {
+    /// Creates a new CrosspostService.
+    pub fn new(
+        crosspost_repo: Arc<CrosspostRepository>,
+        blog_service: BlogService,
+        fetcher: HttpFetcher,
+        apis: CrosspostApis,
+        site_url: String,
+    ) -> Self {
+        Self {
+            crosspost_repo,
+            blog_service,
+            fetcher,
+            apis,
+            site_url,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Lists the user's integrations.
+    #[instrument(skip(self))]
+    pub async fn list_integrations(
+        &self,
+        user_id: i64,
+    ) -> Result<IntegrationListResponse, AppError> {
+        let integrations = self.crosspost_repo.list_integrations(user_id).await?;
+
+        Ok(IntegrationListResponse {
+            integrations: integrations.iter().map(integration_to_dto).collect(),
+        })
+    }
+
+    /// Saves the user's token for a target (validated by the handler).
+    #[instrument(skip(self, req))]
+    pub async fn set_integration(
+        &self,
+        user_id: i64,
+        target: CrosspostTarget,
+        req: IntegrationTokenRequest,
+    ) -> Result<IntegrationDto, AppError> {
+        let integration = self
+            .crosspost_repo
+            .upsert_integration(
+                user_id,
+                target.as_str(),
+                &req.token,
+                req.publication_id.as_deref(),
+                self.clock.now(),
+            )
+            .await?;
+
+        info!(%target, "Integration saved");
+
+        Ok(integration_to_dto(&integration))
+    }
+
+    /// Removes the user's token for a target.
+    #[instrument(skip(self))]
+    pub async fn delete_integration(
+        &self,
+        user_id: i64,
+        target: CrosspostTarget,
+    ) -> Result<(), AppError> {
+        if !self
+            .crosspost_repo
+            .delete_integration(user_id, target.as_str())
+            .await?
+        {
+            return Err(AppError::IntegrationNotFound);
+        }
+
+        info!(%target, "Integration deleted");
+
+        Ok(())
+    }
+
+    /// Lists a post's cross-posts (author only).
+    #[instrument(skip(self))]
+    pub async fn list_crossposts(
+        &self,
+        post_id: i64,
+        user_id: i64,
+    ) -> Result<CrosspostListResponse, AppError> {
+        self.authored_post(post_id, user_id).await?;
+        let crossposts = self.crosspost_repo.list_for_post(post_id).await?;
+
+        Ok(CrosspostListResponse {
+            crossposts: crossposts.iter().map(crosspost_to_dto).collect(),
+        })
+    }
+
+    /// Publishes a post to a target, or updates the copy published earlier
+    /// (author only).
+    ///
+    /// A failure at the target is recorded and returned as a `failed`
+    /// cross-post rather than an error.
+    #[instrument(skip(self))]
+    pub async fn crosspost(
+        &self,
+        post_id: i64,
+        user_id: i64,
+        target: CrosspostTarget,
+    ) -> Result<CrosspostDto, AppError> {
+        let post = self.authored_post(post_id, user_id).await?;
+        if post.status != PostStatus::Published {
+            return Err(AppError::Validation(
+                "Only published posts can be cross-posted".into(),
+            ));
+        }
+        let integration = self
+            .crosspost_repo
+            .find_integration(user_id, target.as_str())
+            .await?
+            .ok_or_else(|| {
+                AppError::Validation(format!("Add a {target} token in your settings first"))
+            })?;
+        let remote_id = self
+            .crosspost_repo
+            .find(post_id, target.as_str())
+            .await?
+            .and_then(|crosspost| crosspost.remote_id);
+
+        self.sync(&post, &integration, remote_id.as_deref()).await?;
+
+        self.crosspost_repo
+            .find(post_id, target.as_str())
+            .await?
+            .map(|crosspost| crosspost_to_dto(&crosspost))
+            .ok_or_else(|| AppError::Internal("cross-post not stored".to_string()))
+    }
+
+    /// Runs the job forever, pushing edited posts to their cross-posts every
+    /// `CROSSPOST_SYNC_INTERVAL_SECS`.
+    pub async fn run(self) {
+        let mut ticker = tokio::time::interval(Duration::from_secs(CROSSPOST_SYNC_INTERVAL_SECS));
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.sync_edits().await {
+                warn!(error = %e, "Cross-post sync job failed");
+            }
+        }
+    }
+
+    /// Pushes one batch of posts edited since their last cross-post attempt.
+    /// Returns how many were attempted.
+    #[instrument(skip(self))]
+    pub async fn sync_edits(&self) -> Result<usize, AppError> {
+        let stale = self
+            .crosspost_repo
+            .list_stale(CROSSPOST_SYNC_BATCH_SIZE)
+            .await?;
+
+        for crosspost in &stale {
+            let post = self
+                .blog_service
+                .get_post(crosspost.post_id, Some(crosspost.author_id))
+                .await?;
+            match self
+                .crosspost_repo
+                .find_integration(crosspost.author_id, &crosspost.target)
+                .await?
+            {
+                Some(integration) => {
+                    self.sync(&post, &integration, crosspost.remote_id.as_deref())
+                        .await?;
+                }
+                None => {
+                    self.crosspost_repo
+                        .record_failure(
+                            post.id,
+                            &crosspost.target,
+                            "Integration removed",
+                            self.clock.now(),
+                        )
+                        .await?;
+                }
+            }
+        }
+
+        if !stale.is_empty() {
+            info!(count = stale.len(), "Cross-posts synced");
+        }
+
+        Ok(stale.len())
+    }
+
+    /// Pushes a post to the integration's target and records the outcome.
+    async fn sync(
+        &self,
+        post: &PostDto,
+        integration: &Integration,
+        remote_id: Option<&str>,
+    ) -> Result<(), AppError> {
+        let target = integration.target();
+        match self.push(post, integration, remote_id).await {
+            Ok(remote) => {
+                self.crosspost_repo
+                    .record_success(
+                        post.id,
+                        target.as_str(),
+                        &remote,
+                        post.updated_at,
+                        self.clock.now(),
+                    )
+                    .await
+            }
+            Err(e) => {
+                warn!(post_id = post.id, %target, error = %e, "Cross-post failed");
+                self.crosspost_repo
+                    .record_failure(post.id, target.as_str(), &e.to_string(), self.clock.now())
+                    .await
+            }
+        }
+    }
+
+    /// Creates or updates the post's copy through the target's API. The
+    /// copy names the post's own canonical URL, or its page here.
+    async fn push(
+        &self,
+        post: &PostDto,
+        integration: &Integration,
+        remote_id: Option<&str>,
+    ) -> Result<RemotePost, AppError> {
+        let canonical_url = post
+            .canonical_url
+            .clone()
+            .unwrap_or_else(|| format!("{}/?post={}", self.site_url, post.public_id));
+        match integration.target() {
+            CrosspostTarget::Devto => {
+                let (method, url) = match remote_id {
+                    Some(id) => (Method::PUT, format!("{}/articles/{id}", self.apis.devto)),
+                    None => (Method::POST, format!("{}/articles", self.apis.devto)),
+                };
+                let body = devto_article(post, &canonical_url);
+                let headers = [("api-key", integration.token.as_str())];
+                let response = self
+                    .fetcher
+                    .send_json(method, &url, &headers, &body)
+                    .await?;
+                parse_devto_article(&response)
+            }
+            CrosspostTarget::Hashnode => {
+                let publication_id = integration.publication_id.as_deref().unwrap_or_default();
+                let body = hashnode_request(post, &canonical_url, publication_id, remote_id);
+                let headers = [("Authorization", integration.token.as_str())];
+                let response = self
+                    .fetcher
+                    .send_json(Method::POST, &self.apis.hashnode, &headers, &body)
+                    .await?;
+                parse_hashnode_post(&response)
+            }
+        }
+    }
+
+    /// Loads a post, checking the user wrote it.
+    async fn authored_post(&self, post_id: i64, user_id: i64) -> Result<PostDto, AppError> {
+        let post = self.blog_service.get_post(post_id, Some(user_id)).await?;
+        policy::authorize(policy::can_edit_post(user_id, &post))?;
+
+        Ok(post)
+    }
+}
+
+/// Converts an Integration to IntegrationDto, leaving out the token.
+fn integration_to_dto(integration: &Integration) -> IntegrationDto {
+    IntegrationDto {
+        target: integration.target(),
+        publication_id: integration.publication_id.clone(),
+        updated_at: integration.updated_at,
+    }
+}
+
+/// Converts a Crosspost to CrosspostDto.
+fn crosspost_to_dto(crosspost: &Crosspost) -> CrosspostDto {
+    CrosspostDto {
+        post_id: crosspost.post_id,
+        target: crosspost.target(),
+        status: crosspost.status(),
+        remote_url: crosspost.remote_url.clone(),
+        error: crosspost.error.clone(),
+        synced_at: crosspost.synced_at,
+        attempted_at: crosspost.attempted_at,
+    }
+}
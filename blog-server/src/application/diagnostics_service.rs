@@ -0,0 +1,71 @@
+//! Admin diagnostics: an in-memory ring buffer of failed write requests, to
+//! debug reports like "my post didn't save".
+//!
+//! Snapshots are redacted before they get here and never leave the process;
+//! they are lost on restart and not shared between replicas.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use blog_shared::{DiagnosticSnapshotDto, DiagnosticsResponse};
+
+use crate::infrastructure::config::DiagnosticsConfig;
+
+/// Service keeping the latest diagnostic snapshots.
+#[derive(Clone, Default)]
+pub struct DiagnosticsService {
+    config: DiagnosticsConfig,
+    snapshots: Arc<Mutex<VecDeque<DiagnosticSnapshotDto>>>,
+}
+
+impl DiagnosticsService {
+    /// Creates a new DiagnosticsService.
+    pub fn new(config: DiagnosticsConfig) -> Self {
+        Self {
+            config,
+            snapshots: Arc::default(),
+        }
+    }
+
+    /// Whether failed write requests are recorded.
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Keeps a snapshot, dropping the oldest once the buffer is full. Does
+    /// nothing when disabled.
+    pub fn record(&self, snapshot: DiagnosticSnapshotDto) {
+        if !self.is_enabled() {
+            return;
+        }
+        let mut snapshots = self.lock();
+        if snapshots.len() >= self.config.capacity {
+            snapshots.pop_front();
+        }
+        snapshots.push_back(snapshot);
+    }
+
+    /// Returns the kept snapshots, newest first.
+    pub fn list(&self) -> DiagnosticsResponse {
+        DiagnosticsResponse {
+            enabled: self.is_enabled(),
+            capacity: self.config.capacity,
+            snapshots: self.lock().iter().rev().cloned().collect(),
+        }
+    }
+
+    /// Drops every snapshot, returning how many there were.
+    pub fn clear(&self) -> usize {
+        let mut snapshots = self.lock();
+        let count = snapshots.len();
+        snapshots.clear();
+        count
+    }
+
+    /// Locks the buffer, ignoring poisoning: every push leaves it valid.
+    fn lock(&self) -> MutexGuard<'_, VecDeque<DiagnosticSnapshotDto>> {
+        self.snapshots
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
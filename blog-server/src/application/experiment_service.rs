@@ -0,0 +1,190 @@
+//! A/B experiments: admin management, variant assignment and exposures.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use blog_shared::{
+    CreateExperimentRequest, ExperimentDto, ExperimentListResponse, ExperimentVariantDto,
+    UpdateExperimentRequest,
+};
+use tracing::{info, instrument};
+
+use crate::data::ExperimentRepository;
+use crate::domain::{
+    AppError, Experiment, ExperimentVariant, assign_variant, parse_experiment_description,
+    parse_experiment_name, parse_variants,
+};
+use crate::infrastructure::clock::{Clock, SystemClock};
+
+/// Service for experiments.
+///
+/// Only signed-in users take part: their variant follows from their ID, so
+/// it is the same on every device and needs no storage until they see it.
+#[derive(Clone)]
+pub struct ExperimentService {
+    experiment_repo: Arc<ExperimentRepository>,
+    clock: Arc<dyn Clock>,
+}
+
+impl ExperimentService {
+    /// Creates a new ExperimentService.
+    pub fn new(experiment_repo: Arc<ExperimentRepository>) -> Self {
+        Self {
+            experiment_repo,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Returns the user's variant of each enabled experiment, by experiment
+    /// name.
+    pub async fn assignments(&self, user_id: i64) -> Result<BTreeMap<String, String>, AppError> {
+        let mut assignments = BTreeMap::new();
+        for experiment in self.experiment_repo.list(true).await? {
+            let variants = self.experiment_repo.list_variants(experiment.id).await?;
+            if let Some(variant) = assign_variant(&experiment.name, &variants, user_id) {
+                assignments.insert(experiment.name, variant.name.clone());
+            }
+        }
+
+        Ok(assignments)
+    }
+
+    /// Records that the user was shown their variant of an enabled
+    /// experiment. The variant is worked out again here rather than taken
+    /// from the client.
+    #[instrument(skip(self))]
+    pub async fn record_exposure(&self, user_id: i64, name: &str) -> Result<(), AppError> {
+        let experiment = self.find(name).await?;
+        if !experiment.enabled {
+            return Err(AppError::ExperimentNotFound);
+        }
+        let variants = self.experiment_repo.list_variants(experiment.id).await?;
+        let Some(variant) = assign_variant(&experiment.name, &variants, user_id) else {
+            return Err(AppError::ExperimentNotFound);
+        };
+
+        self.experiment_repo
+            .record_exposure(experiment.id, user_id, &variant.name, self.clock.now())
+            .await
+    }
+
+    /// Lists all experiments with their exposure counts.
+    #[instrument(skip(self))]
+    pub async fn list(&self) -> Result<ExperimentListResponse, AppError> {
+        let mut experiments = Vec::new();
+        for experiment in self.experiment_repo.list(false).await? {
+            experiments.push(self.to_dto(experiment).await?);
+        }
+
+        Ok(ExperimentListResponse { experiments })
+    }
+
+    /// Creates an experiment, enabled right away.
+    #[instrument(skip(self, req), fields(name = %req.name))]
+    pub async fn create(&self, req: CreateExperimentRequest) -> Result<ExperimentDto, AppError> {
+        let name = parse_experiment_name(&req.name)?;
+        let description = parse_experiment_description(&req.description)?;
+        let variants = parse_variants(req.variants)?;
+        if self.experiment_repo.find_by_name(&name).await?.is_some() {
+            return Err(AppError::ExperimentExists);
+        }
+
+        let experiment = self
+            .experiment_repo
+            .create(&name, &description, &variants, self.clock.now())
+            .await?;
+
+        info!(experiment_id = experiment.id, name = %experiment.name, "Experiment created");
+
+        self.to_dto(experiment).await
+    }
+
+    /// Changes an experiment's description, switch or variants.
+    #[instrument(skip(self, req))]
+    pub async fn update(
+        &self,
+        name: &str,
+        req: UpdateExperimentRequest,
+    ) -> Result<ExperimentDto, AppError> {
+        let experiment = self.find(name).await?;
+        let description = match req.description {
+            Some(description) => parse_experiment_description(&description)?,
+            None => experiment.description,
+        };
+        let enabled = req.enabled.unwrap_or(experiment.enabled);
+        let variants = req.variants.map(parse_variants).transpose()?;
+
+        self.experiment_repo
+            .update(
+                experiment.id,
+                &description,
+                enabled,
+                variants.as_deref(),
+                self.clock.now(),
+            )
+            .await?;
+
+        info!(experiment_id = experiment.id, enabled, "Experiment updated");
+
+        let experiment = self.find(name).await?;
+        self.to_dto(experiment).await
+    }
+
+    /// Deletes an experiment and its exposures.
+    #[instrument(skip(self))]
+    pub async fn delete(&self, name: &str) -> Result<(), AppError> {
+        let experiment = self.find(name).await?;
+        if !self.experiment_repo.delete(experiment.id).await? {
+            return Err(AppError::ExperimentNotFound);
+        }
+
+        info!(experiment_id = experiment.id, "Experiment deleted");
+
+        Ok(())
+    }
+
+    /// Finds an experiment by name, ignoring case.
+    async fn find(&self, name: &str) -> Result<Experiment, AppError> {
+        let name = parse_experiment_name(name).map_err(|_| AppError::ExperimentNotFound)?;
+        self.experiment_repo
+            .find_by_name(&name)
+            .await?
+            .ok_or(AppError::ExperimentNotFound)
+    }
+
+    /// Converts an experiment to its DTO with variants and exposure counts.
+    async fn to_dto(&self, experiment: Experiment) -> Result<ExperimentDto, AppError> {
+        let variants = self.experiment_repo.list_variants(experiment.id).await?;
+        let exposures = self
+            .experiment_repo
+            .count_exposures(experiment.id)
+            .await?
+            .into_iter()
+            .collect();
+
+        Ok(ExperimentDto {
+            id: experiment.id,
+            name: experiment.name,
+            description: experiment.description,
+            enabled: experiment.enabled,
+            variants: variants.into_iter().map(variant_to_dto).collect(),
+            exposures,
+            created_at: experiment.created_at,
+            updated_at: experiment.updated_at,
+        })
+    }
+}
+
+/// Converts a variant to its DTO.
+fn variant_to_dto(variant: ExperimentVariant) -> ExperimentVariantDto {
+    ExperimentVariantDto {
+        name: variant.name,
+        weight: variant.weight as u32,
+    }
+}
@@ -0,0 +1,152 @@
+//! Feed service: RSS/Atom feeds and the job pinging the WebSub hub.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use blog_shared::{PostDto, SiteSettingsDto};
+use chrono::{DateTime, Utc};
+use tracing::{info, instrument, warn};
+
+use crate::application::{BlogService, SiteService};
+use crate::constants::{FEED_SIZE, WEBSUB_PING_INTERVAL_SECS};
+use crate::data::{PostRepository, UserRepository};
+use crate::domain::{AppError, AuthorTimezones, FeedUrls, parse_timezone, render_atom, render_rss};
+use crate::infrastructure::http_fetcher::HttpFetcher;
+
+/// Service for the public feeds.
+#[derive(Clone)]
+pub struct FeedService {
+    blog_service: BlogService,
+    post_repo: Arc<PostRepository>,
+    user_repo: Arc<UserRepository>,
+    fetcher: HttpFetcher,
+    urls: FeedUrls,
+    /// Source of the feed title and description.
+    site: Option<SiteService>,
+}
+
+impl FeedService {
+    /// Creates a new FeedService.
+    pub fn new(
+        blog_service: BlogService,
+        post_repo: Arc<PostRepository>,
+        user_repo: Arc<UserRepository>,
+        fetcher: HttpFetcher,
+        urls: FeedUrls,
+    ) -> Self {
+        Self {
+            blog_service,
+            post_repo,
+            user_repo,
+            fetcher,
+            urls,
+            site: None,
+        }
+    }
+
+    /// Names the feeds after the site settings instead of the defaults.
+    pub fn with_site(mut self, site: SiteService) -> Self {
+        self.site = Some(site);
+        self
+    }
+
+    /// Addresses of the feeds and their hub.
+    pub fn urls(&self) -> &FeedUrls {
+        &self.urls
+    }
+
+    /// Renders the newest public posts as RSS.
+    #[instrument(skip(self))]
+    pub async fn rss(&self) -> Result<String, AppError> {
+        let posts = self
+            .blog_service
+            .list_posts(FEED_SIZE, 0, None, None)
+            .await?;
+        let timezones = self.author_timezones(&posts.posts).await?;
+        let site = self.site_settings().await?;
+        Ok(render_rss(&posts.posts, &site, &self.urls, &timezones))
+    }
+
+    /// Renders the newest public posts as Atom.
+    #[instrument(skip(self))]
+    pub async fn atom(&self) -> Result<String, AppError> {
+        let posts = self
+            .blog_service
+            .list_posts(FEED_SIZE, 0, None, None)
+            .await?;
+        let timezones = self.author_timezones(&posts.posts).await?;
+        let site = self.site_settings().await?;
+        Ok(render_atom(&posts.posts, &site, &self.urls, &timezones))
+    }
+
+    /// Runs the WebSub job forever, checking for changed posts every
+    /// `WEBSUB_PING_INTERVAL_SECS`. Returns at once if no hub is configured.
+    ///
+    /// The first run pings whenever posts exist, so changes made while a
+    /// ping was failing are announced after a restart.
+    pub async fn run(self) {
+        if self.urls.hub.is_none() {
+            return;
+        }
+
+        let mut announced = None;
+        let mut ticker = tokio::time::interval(Duration::from_secs(WEBSUB_PING_INTERVAL_SECS));
+        loop {
+            ticker.tick().await;
+            match self.ping_hub(announced).await {
+                Ok(latest) => announced = latest,
+                Err(e) => warn!(error = %e, "WebSub hub ping failed"),
+            }
+        }
+    }
+
+    /// Pings the hub for both feeds if a post changed after `announced`.
+    ///
+    /// Returns the change time now announced, which the caller passes back
+    /// on the next run.
+    #[instrument(skip(self))]
+    pub async fn ping_hub(
+        &self,
+        announced: Option<DateTime<Utc>>,
+    ) -> Result<Option<DateTime<Utc>>, AppError> {
+        let Some(ref hub) = self.urls.hub else {
+            return Ok(announced);
+        };
+        let latest = self.post_repo.latest_update().await?;
+        if latest <= announced {
+            return Ok(announced);
+        }
+
+        for topic in [&self.urls.rss, &self.urls.atom] {
+            self.fetcher.publish_to_hub(hub, topic).await?;
+        }
+
+        info!("WebSub hub pinged");
+
+        Ok(latest)
+    }
+
+    /// Returns the site settings, or the defaults without a site service.
+    async fn site_settings(&self) -> Result<SiteSettingsDto, AppError> {
+        match &self.site {
+            Some(site) => site.settings().await,
+            None => Ok(SiteSettingsDto::default()),
+        }
+    }
+
+    /// Looks up the time zone of each author of `posts`.
+    async fn author_timezones(&self, posts: &[PostDto]) -> Result<AuthorTimezones, AppError> {
+        let mut timezones = AuthorTimezones::new();
+        for post in posts {
+            if timezones.contains_key(&post.author_id) {
+                continue;
+            }
+            let settings = self.user_repo.find_settings(post.author_id).await?;
+            if let Some(timezone) = settings.and_then(|s| parse_timezone(&s.timezone)) {
+                timezones.insert(post.author_id, timezone);
+            }
+        }
+
+        Ok(timezones)
+    }
+}
@@ -0,0 +1,170 @@
+//! Import service: bulk creation of posts exported from other blogs.
+
+use std::sync::Arc;
+
+use blog_shared::{
+    CreatePostRequest, ImportPostDto, ImportPostsRequest, ImportPostsResponse, ImportedPostDto,
+};
+use tracing::{info, instrument};
+
+use crate::application::{BlogService, ModerationService};
+use crate::data::{PostRepository, UserRepository};
+use crate::domain::{AppError, Role, User, normalize_tags, policy};
+
+/// Service for bulk post imports.
+#[derive(Clone)]
+pub struct ImportService {
+    user_repo: Arc<UserRepository>,
+    post_repo: Arc<PostRepository>,
+    blog_service: BlogService,
+    moderation: ModerationService,
+}
+
+impl ImportService {
+    /// Creates a new ImportService.
+    pub fn new(
+        user_repo: Arc<UserRepository>,
+        post_repo: Arc<PostRepository>,
+        blog_service: BlogService,
+        moderation: ModerationService,
+    ) -> Self {
+        Self {
+            user_repo,
+            post_repo,
+            blog_service,
+            moderation,
+        }
+    }
+
+    /// Imports posts one by one, keeping their publication dates.
+    ///
+    /// A rejected post (invalid, blocked by a moderation rule, or by an
+    /// author the importer may not post as) is reported and skipped; the rest
+    /// are still imported. A dry run performs the same checks without
+    /// creating anything.
+    ///
+    /// `role` is the one the caller authenticated with, so a service token
+    /// bound to an admin imports with user rights.
+    #[instrument(skip(self, req), fields(posts = req.posts.len(), dry_run = req.dry_run))]
+    pub async fn import_posts(
+        &self,
+        importer_id: i64,
+        role: Role,
+        req: ImportPostsRequest,
+    ) -> Result<ImportPostsResponse, AppError> {
+        let importer = self
+            .user_repo
+            .find_by_id(importer_id)
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+
+        let mut posts = Vec::with_capacity(req.posts.len());
+        for post in req.posts {
+            let title = post.title.clone();
+            let author_username = post
+                .author
+                .clone()
+                .unwrap_or_else(|| importer.username.clone());
+            let (created, error) = match self.import_one(&importer, role, post, req.dry_run).await {
+                Ok(created) => (created, None),
+                Err(e) if is_rejection(&e) => (None, Some(e.to_string())),
+                Err(e) => return Err(e),
+            };
+            let (post_id, post_public_id) = created.unzip();
+            posts.push(ImportedPostDto {
+                title,
+                author_username,
+                post_id,
+                post_public_id,
+                error,
+            });
+        }
+
+        let failed = posts.iter().filter(|post| post.error.is_some()).count() as i64;
+        let imported = posts.len() as i64 - failed;
+        if !req.dry_run {
+            info!(imported, failed, "Posts imported");
+        }
+
+        Ok(ImportPostsResponse {
+            dry_run: req.dry_run,
+            imported,
+            failed,
+            posts,
+        })
+    }
+
+    /// Checks and (unless `dry_run`) creates one post, returning its numeric
+    /// and public IDs.
+    async fn import_one(
+        &self,
+        importer: &User,
+        role: Role,
+        post: ImportPostDto,
+        dry_run: bool,
+    ) -> Result<Option<(i64, String)>, AppError> {
+        let author_id = self
+            .resolve_author(importer, role, post.author.as_deref())
+            .await?;
+        let req = CreatePostRequest {
+            title: post.title.trim().to_string(),
+            content: post.content,
+            tags: normalize_tags(post.tags)?,
+            lang: None,
+            canonical_url: None,
+            original_source: None,
+            org: None,
+        };
+        if req.title.is_empty() {
+            return Err(AppError::Validation("Title cannot be empty".to_string()));
+        }
+        if req.content.trim().is_empty() {
+            return Err(AppError::Validation("Content cannot be empty".to_string()));
+        }
+
+        if dry_run {
+            self.moderation
+                .screen(&format!("{}\n\n{}", req.title, req.content))
+                .await?;
+            return Ok(None);
+        }
+
+        let created = self.blog_service.create_post(author_id, req).await?;
+        if let Some(published_at) = post.published_at {
+            self.post_repo.backdate(created.id, published_at).await?;
+            self.blog_service.post_changed(created.id);
+        }
+
+        Ok(Some((created.id, created.public_id)))
+    }
+
+    /// Finds the user a post is imported as; only admins may name other users.
+    async fn resolve_author(
+        &self,
+        importer: &User,
+        role: Role,
+        author: Option<&str>,
+    ) -> Result<i64, AppError> {
+        let Some(username) = author.filter(|username| *username != importer.username) else {
+            return Ok(importer.id);
+        };
+        policy::authorize(policy::can_import_as_other(role))?;
+
+        self.user_repo
+            .find_by_username(username)
+            .await?
+            .map(|user| user.id)
+            .ok_or(AppError::UserNotFound)
+    }
+}
+
+/// Returns true for errors that reject a single post rather than the import.
+fn is_rejection(error: &AppError) -> bool {
+    matches!(
+        error,
+        AppError::Validation(_)
+            | AppError::ContentBlocked
+            | AppError::Forbidden
+            | AppError::UserNotFound
+    )
+}
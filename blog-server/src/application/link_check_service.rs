@@ -0,0 +1,111 @@
+//! Background job that checks links in posts and reports broken ones.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use blog_shared::{BrokenLinkDto, BrokenLinkListResponse};
+use tracing::{debug, info, instrument, warn};
+
+use crate::constants::{LINK_CHECK_BATCH_SIZE, LINK_CHECK_INTERVAL_SECS, LINK_CHECK_TTL_HOURS};
+use crate::data::LinkCheckRepository;
+use crate::domain::{AppError, BrokenLink, LinkCheck};
+use crate::infrastructure::clock::{Clock, SystemClock};
+use crate::infrastructure::http_fetcher::HttpFetcher;
+
+/// Service that records the HTTP status of linked URLs.
+#[derive(Clone)]
+pub struct LinkCheckService {
+    check_repo: Arc<LinkCheckRepository>,
+    fetcher: HttpFetcher,
+    clock: Arc<dyn Clock>,
+}
+
+impl LinkCheckService {
+    /// Creates a new LinkCheckService.
+    pub fn new(check_repo: Arc<LinkCheckRepository>, fetcher: HttpFetcher) -> Self {
+        Self {
+            check_repo,
+            fetcher,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Runs the job forever, one batch every `LINK_CHECK_INTERVAL_SECS`.
+    pub async fn run(self) {
+        let mut ticker = tokio::time::interval(Duration::from_secs(LINK_CHECK_INTERVAL_SECS));
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.check_due().await {
+                warn!(error = %e, "Link check job failed");
+            }
+        }
+    }
+
+    /// Checks one batch of unchecked or stale URLs. Returns how many are broken.
+    #[instrument(skip(self))]
+    pub async fn check_due(&self) -> Result<usize, AppError> {
+        let stale_before = self.clock.now() - chrono::Duration::hours(LINK_CHECK_TTL_HOURS);
+        let urls = self
+            .check_repo
+            .list_due(stale_before, LINK_CHECK_BATCH_SIZE)
+            .await?;
+
+        let mut broken = 0;
+        for url in urls.iter().cloned() {
+            let (status_code, error) = match self.fetcher.check_status(&url).await {
+                Ok(status) => (Some(status), None),
+                Err(e) => {
+                    debug!(url, error = %e, "Link check failed");
+                    (None, Some(e.to_string()))
+                }
+            };
+            let check = LinkCheck {
+                url,
+                status_code,
+                error,
+                checked_at: self.clock.now(),
+            };
+            if check.is_broken() {
+                broken += 1;
+            }
+            self.check_repo.record(&check).await?;
+        }
+
+        if !urls.is_empty() {
+            info!(checked = urls.len(), broken, "Links checked");
+        }
+
+        Ok(broken)
+    }
+
+    /// Lists broken links in the author's posts.
+    #[instrument(skip(self))]
+    pub async fn broken_links(&self, author_id: i64) -> Result<BrokenLinkListResponse, AppError> {
+        let links = self.check_repo.find_broken_by_author(author_id).await?;
+
+        Ok(BrokenLinkListResponse {
+            links: links.into_iter().map(broken_link_to_dto).collect(),
+        })
+    }
+}
+
+/// Converts a BrokenLink to its DTO.
+fn broken_link_to_dto(link: BrokenLink) -> BrokenLinkDto {
+    BrokenLinkDto {
+        post_id: link.post_id,
+        post_public_id: link.post_public_id,
+        post_title: link.post_title,
+        url: link.url,
+        status_code: link
+            .status_code
+            .and_then(|status| u16::try_from(status).ok()),
+        error: link.error,
+        checked_at: link.checked_at,
+    }
+}
@@ -0,0 +1,89 @@
+//! Background job that fetches Open Graph metadata for links in posts.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tracing::{debug, info, instrument, warn};
+
+use crate::constants::{
+    LINK_PREVIEW_BATCH_SIZE, LINK_PREVIEW_INTERVAL_SECS, LINK_PREVIEW_TTL_DAYS,
+};
+use crate::data::LinkPreviewRepository;
+use crate::domain::{AppError, parse_open_graph};
+use crate::infrastructure::clock::{Clock, SystemClock};
+use crate::infrastructure::http_fetcher::HttpFetcher;
+
+/// Service that keeps the link preview cache filled.
+#[derive(Clone)]
+pub struct LinkPreviewService {
+    preview_repo: Arc<LinkPreviewRepository>,
+    fetcher: HttpFetcher,
+    clock: Arc<dyn Clock>,
+}
+
+impl LinkPreviewService {
+    /// Creates a new LinkPreviewService.
+    pub fn new(preview_repo: Arc<LinkPreviewRepository>, fetcher: HttpFetcher) -> Self {
+        Self {
+            preview_repo,
+            fetcher,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Runs the job forever, one batch every `LINK_PREVIEW_INTERVAL_SECS`.
+    pub async fn run(self) {
+        let mut ticker = tokio::time::interval(Duration::from_secs(LINK_PREVIEW_INTERVAL_SECS));
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.refresh_due().await {
+                warn!(error = %e, "Link preview job failed");
+            }
+        }
+    }
+
+    /// Fetches one batch of new or stale URLs. Returns how many were fetched.
+    #[instrument(skip(self))]
+    pub async fn refresh_due(&self) -> Result<usize, AppError> {
+        let stale_before = self.clock.now() - chrono::Duration::days(LINK_PREVIEW_TTL_DAYS);
+        let urls = self
+            .preview_repo
+            .list_due(stale_before, LINK_PREVIEW_BATCH_SIZE)
+            .await?;
+
+        let mut fetched = 0;
+        for url in &urls {
+            let preview = self
+                .fetcher
+                .fetch_html(url)
+                .await
+                .map(|html| parse_open_graph(url, &html));
+            match preview {
+                Ok(Some(preview)) => {
+                    self.preview_repo.save(&preview, self.clock.now()).await?;
+                    fetched += 1;
+                }
+                Ok(None) => {
+                    debug!(url, "No preview metadata");
+                    self.preview_repo.mark_failed(url, self.clock.now()).await?;
+                }
+                Err(e) => {
+                    debug!(url, error = %e, "Link preview fetch failed");
+                    self.preview_repo.mark_failed(url, self.clock.now()).await?;
+                }
+            }
+        }
+
+        if !urls.is_empty() {
+            info!(due = urls.len(), fetched, "Link previews refreshed");
+        }
+
+        Ok(fetched)
+    }
+}
@@ -0,0 +1,186 @@
+//! Message service for private conversations between users.
+
+use std::sync::Arc;
+
+use blog_shared::{
+    ConversationDto, ConversationListResponse, MessageDto, MessageListResponse,
+    UnreadCountResponse, excerpt,
+};
+use tracing::{info, instrument};
+
+use crate::constants::MESSAGE_PREVIEW_CHARS;
+use crate::data::{MessageRepository, UserRepository};
+use crate::domain::{AppError, Conversation, Message};
+use crate::infrastructure::clock::{Clock, SystemClock};
+
+/// Service for private messaging.
+#[derive(Clone)]
+pub struct MessageService {
+    message_repo: Arc<MessageRepository>,
+    user_repo: Arc<UserRepository>,
+    clock: Arc<dyn Clock>,
+}
+
+impl MessageService {
+    /// Creates a new MessageService.
+    pub fn new(message_repo: Arc<MessageRepository>, user_repo: Arc<UserRepository>) -> Self {
+        Self {
+            message_repo,
+            user_repo,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Sends a message to `username`, starting a conversation with them unless
+    /// one already exists.
+    #[instrument(skip(self, content), fields(sender_id = sender_id))]
+    pub async fn start_conversation(
+        &self,
+        sender_id: i64,
+        username: &str,
+        content: &str,
+    ) -> Result<ConversationDto, AppError> {
+        let recipient = self
+            .user_repo
+            .find_by_username(username)
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+        if recipient.id == sender_id {
+            return Err(AppError::Validation(
+                "Cannot send messages to yourself".to_string(),
+            ));
+        }
+
+        let now = self.clock.now();
+        let conversation_id = self
+            .message_repo
+            .find_or_create_conversation(sender_id, recipient.id, now)
+            .await?;
+        self.message_repo
+            .create_message(conversation_id, sender_id, content, now)
+            .await?;
+
+        info!(conversation_id, "Conversation started");
+
+        self.get_conversation(conversation_id, sender_id).await
+    }
+
+    /// Lists the user's conversations, most recently active first.
+    #[instrument(skip(self))]
+    pub async fn list_conversations(
+        &self,
+        user_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<ConversationListResponse, AppError> {
+        let conversations = self
+            .message_repo
+            .list_conversations(user_id, limit, offset)
+            .await?;
+        let total = self.message_repo.count_conversations(user_id).await?;
+
+        Ok(ConversationListResponse {
+            conversations: conversations.into_iter().map(conversation_to_dto).collect(),
+            total,
+        })
+    }
+
+    /// Sends a message in one of the user's conversations.
+    #[instrument(skip(self, content), fields(conversation_id = conversation_id, sender_id = sender_id))]
+    pub async fn send_message(
+        &self,
+        conversation_id: i64,
+        sender_id: i64,
+        content: &str,
+    ) -> Result<MessageDto, AppError> {
+        self.get_conversation(conversation_id, sender_id).await?;
+
+        let message = self
+            .message_repo
+            .create_message(conversation_id, sender_id, content, self.clock.now())
+            .await?;
+
+        info!(message_id = message.id, "Message sent");
+
+        Ok(message_to_dto(message))
+    }
+
+    /// Lists messages of one of the user's conversations, newest first, and
+    /// marks the conversation as read.
+    #[instrument(skip(self))]
+    pub async fn list_messages(
+        &self,
+        conversation_id: i64,
+        user_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<MessageListResponse, AppError> {
+        self.get_conversation(conversation_id, user_id).await?;
+
+        let messages = self
+            .message_repo
+            .list_messages(conversation_id, limit, offset)
+            .await?;
+        let total = self.message_repo.count_messages(conversation_id).await?;
+        self.message_repo
+            .mark_read(conversation_id, user_id)
+            .await?;
+
+        Ok(MessageListResponse {
+            messages: messages.into_iter().map(message_to_dto).collect(),
+            total,
+        })
+    }
+
+    /// Counts the user's unread messages across all conversations.
+    #[instrument(skip(self))]
+    pub async fn unread_count(&self, user_id: i64) -> Result<UnreadCountResponse, AppError> {
+        let unread = self.message_repo.count_unread(user_id).await?;
+
+        Ok(UnreadCountResponse { unread })
+    }
+
+    /// Gets a conversation of the user; others' conversations are `ConversationNotFound`.
+    async fn get_conversation(&self, id: i64, user_id: i64) -> Result<ConversationDto, AppError> {
+        self.message_repo
+            .find_conversation(id, user_id)
+            .await?
+            .map(conversation_to_dto)
+            .ok_or(AppError::ConversationNotFound)
+    }
+}
+
+/// Converts a Conversation domain entity to ConversationDto, shortening the last message.
+fn conversation_to_dto(conversation: Conversation) -> ConversationDto {
+    ConversationDto {
+        id: conversation.id,
+        other_user_id: conversation.other_user_id,
+        other_user_public_id: conversation.other_user_public_id,
+        other_username: conversation.other_username,
+        last_message: conversation
+            .last_message
+            .map(|message| excerpt(&message, MESSAGE_PREVIEW_CHARS)),
+        last_message_at: conversation.last_message_at,
+        unread_count: conversation.unread_count,
+        created_at: conversation.created_at,
+    }
+}
+
+/// Converts a Message domain entity to MessageDto.
+fn message_to_dto(message: Message) -> MessageDto {
+    MessageDto {
+        id: message.id,
+        conversation_id: message.conversation_id,
+        sender_id: message.sender_id,
+        sender_public_id: message.sender_public_id,
+        sender_username: message.sender_username,
+        content: message.content,
+        created_at: message.created_at,
+    }
+}
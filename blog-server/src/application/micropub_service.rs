@@ -0,0 +1,99 @@
+//! Micropub service: publishing posts from IndieWeb clients.
+
+use std::sync::Arc;
+
+use blog_shared::CreatePostRequest;
+use tracing::{info, instrument};
+
+use crate::application::BlogService;
+use crate::data::UserRepository;
+use crate::domain::{AppError, IndieAuthToken, MicropubEntry, normalize_tags};
+use crate::infrastructure::http_fetcher::HttpFetcher;
+
+/// Service for Micropub requests.
+#[derive(Clone)]
+pub struct MicropubService {
+    user_repo: Arc<UserRepository>,
+    blog_service: BlogService,
+    fetcher: HttpFetcher,
+    /// IndieAuth token endpoint; without one only local tokens are accepted.
+    token_endpoint: Option<String>,
+    /// Base URL of the frontend, for the `Location` of created posts.
+    site_url: String,
+}
+
+impl MicropubService {
+    /// Creates a new MicropubService.
+    pub fn new(
+        user_repo: Arc<UserRepository>,
+        blog_service: BlogService,
+        fetcher: HttpFetcher,
+        token_endpoint: Option<String>,
+        site_url: String,
+    ) -> Self {
+        Self {
+            user_repo,
+            blog_service,
+            fetcher,
+            token_endpoint,
+            site_url,
+        }
+    }
+
+    /// Verifies an IndieAuth token with the token endpoint and returns the
+    /// ID of the user whose website is the token's `me`.
+    ///
+    /// The token needs the `create` scope; suspended users are refused.
+    #[instrument(skip(self, token))]
+    pub async fn authorize(&self, token: &str) -> Result<i64, AppError> {
+        let endpoint = self
+            .token_endpoint
+            .as_deref()
+            .ok_or(AppError::InvalidCredentials)?;
+        let token = self
+            .fetcher
+            .verify_token(endpoint, token)
+            .await?
+            .as_ref()
+            .and_then(IndieAuthToken::from_json)
+            .ok_or(AppError::InvalidCredentials)?;
+        if !token.can_create() {
+            return Err(AppError::Forbidden);
+        }
+
+        let user_id = self
+            .user_repo
+            .find_id_by_website(&token.me)
+            .await?
+            .ok_or(AppError::Forbidden)?;
+        let user = self
+            .user_repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+        if user.suspended_at.is_some() {
+            return Err(AppError::AccountSuspended);
+        }
+
+        Ok(user_id)
+    }
+
+    /// Publishes an entry as a post and returns its URL on the frontend.
+    #[instrument(skip(self, entry))]
+    pub async fn publish(&self, user_id: i64, entry: MicropubEntry) -> Result<String, AppError> {
+        let req = CreatePostRequest {
+            title: entry.title(),
+            tags: normalize_tags(entry.categories)?,
+            content: entry.content,
+            lang: None,
+            canonical_url: None,
+            original_source: None,
+            org: None,
+        };
+        let post = self.blog_service.create_post(user_id, req).await?;
+
+        info!(post_id = post.id, "Post published via Micropub");
+
+        Ok(format!("{}/?post={}", self.site_url, post.public_id))
+    }
+}
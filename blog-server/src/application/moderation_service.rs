@@ -0,0 +1,331 @@
+//! Moderation service for automatic content rules and the flagged content queue.
+
+use std::sync::{Arc, RwLock};
+
+use blog_shared::constants::{MAX_RULE_NAME_LENGTH, MAX_RULE_PATTERN_LENGTH};
+use blog_shared::{
+    ContentKind, CreateModerationRuleRequest, FlaggedContentDto, FlaggedContentListResponse,
+    ModerationRuleDto, ModerationRuleListResponse, RuleAction, UpdateModerationRuleRequest,
+};
+use tracing::{info, instrument};
+
+use crate::data::ModerationRepository;
+use crate::domain::{AppError, FlaggedContent, ModerationRule, NewRule, RuleSet, compile_rule};
+use crate::infrastructure::clock::{Clock, SystemClock};
+
+/// Service for moderation rules and flagged content.
+///
+/// Enabled rules are compiled once and cached until a rule changes.
+#[derive(Clone)]
+pub struct ModerationService {
+    moderation_repo: Arc<ModerationRepository>,
+    rule_set: Arc<RwLock<Option<Arc<RuleSet>>>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl ModerationService {
+    /// Creates a new ModerationService.
+    pub fn new(moderation_repo: Arc<ModerationRepository>) -> Self {
+        Self {
+            moderation_repo,
+            rule_set: Arc::new(RwLock::new(None)),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Checks new or edited content against the enabled rules.
+    ///
+    /// Returns `ContentBlocked` if a blocking rule matches; otherwise the IDs
+    /// of the flagging rules that matched, for [`Self::flag_post`] and
+    /// [`Self::flag_comment`] once the content is saved.
+    #[instrument(skip(self, text))]
+    pub async fn screen(&self, text: &str) -> Result<Vec<i64>, AppError> {
+        let rule_set = self.rule_set().await?;
+        if rule_set.rule_ids().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let matches = rule_set.matches(text);
+        self.moderation_repo.record_checks().await?;
+        let now = self.clock.now();
+        for (rule_id, _) in &matches {
+            self.moderation_repo.record_hit(*rule_id, now).await?;
+        }
+
+        if let Some((rule_id, _)) = matches
+            .iter()
+            .find(|(_, action)| *action == RuleAction::Block)
+        {
+            info!(rule_id, "Content blocked");
+            return Err(AppError::ContentBlocked);
+        }
+
+        Ok(matches.into_iter().map(|(rule_id, _)| rule_id).collect())
+    }
+
+    /// Adds a post to the flagged content queue once per matching rule.
+    pub async fn flag_post(&self, post_id: i64, rule_ids: &[i64]) -> Result<(), AppError> {
+        let now = self.clock.now();
+        for rule_id in rule_ids {
+            self.moderation_repo
+                .flag(post_id, None, *rule_id, now)
+                .await?;
+        }
+        if !rule_ids.is_empty() {
+            info!(post_id, "Post flagged");
+        }
+
+        Ok(())
+    }
+
+    /// Adds a comment to the flagged content queue once per matching rule.
+    pub async fn flag_comment(
+        &self,
+        post_id: i64,
+        comment_id: i64,
+        rule_ids: &[i64],
+    ) -> Result<(), AppError> {
+        let now = self.clock.now();
+        for rule_id in rule_ids {
+            self.moderation_repo
+                .flag(post_id, Some(comment_id), *rule_id, now)
+                .await?;
+        }
+        if !rule_ids.is_empty() {
+            info!(comment_id, "Comment flagged");
+        }
+
+        Ok(())
+    }
+
+    /// Lists all rules with their hit rates.
+    #[instrument(skip(self))]
+    pub async fn list_rules(&self) -> Result<ModerationRuleListResponse, AppError> {
+        let rules = self.moderation_repo.list_rules().await?;
+
+        Ok(ModerationRuleListResponse {
+            rules: rules.iter().map(rule_to_dto).collect(),
+        })
+    }
+
+    /// Creates a rule after checking that its pattern compiles.
+    #[instrument(skip(self, req))]
+    pub async fn create_rule(
+        &self,
+        req: CreateModerationRuleRequest,
+    ) -> Result<ModerationRuleDto, AppError> {
+        let name = validate_name(&req.name)?;
+        validate_pattern(&req.pattern)?;
+        compile_rule(req.kind, &req.pattern)?;
+
+        let rule = self
+            .moderation_repo
+            .create_rule(NewRule {
+                name: &name,
+                kind: req.kind,
+                pattern: &req.pattern,
+                action: req.action,
+                enabled: req.enabled,
+                now: self.clock.now(),
+            })
+            .await?;
+        self.invalidate();
+
+        info!(rule_id = rule.id, "Moderation rule created");
+
+        Ok(rule_to_dto(&rule))
+    }
+
+    /// Changes a rule's definition; its statistics are kept.
+    #[instrument(skip(self, req))]
+    pub async fn update_rule(
+        &self,
+        id: i64,
+        req: UpdateModerationRuleRequest,
+    ) -> Result<ModerationRuleDto, AppError> {
+        let rule = self
+            .moderation_repo
+            .find_rule(id)
+            .await?
+            .ok_or(AppError::ModerationRuleNotFound)?;
+
+        let name = match req.name {
+            Some(name) => validate_name(&name)?,
+            None => rule.name.clone(),
+        };
+        let kind = req.kind.unwrap_or(rule.kind());
+        let pattern = req.pattern.unwrap_or_else(|| rule.pattern.clone());
+        let action = req.action.unwrap_or(rule.action());
+        let enabled = req.enabled.unwrap_or(rule.enabled);
+        validate_pattern(&pattern)?;
+        compile_rule(kind, &pattern)?;
+
+        let updated = self
+            .moderation_repo
+            .update_rule(
+                id,
+                NewRule {
+                    name: &name,
+                    kind,
+                    pattern: &pattern,
+                    action,
+                    enabled,
+                    now: self.clock.now(),
+                },
+            )
+            .await?;
+        self.invalidate();
+
+        info!("Moderation rule updated");
+
+        Ok(rule_to_dto(&updated))
+    }
+
+    /// Deletes a rule along with the flags it raised.
+    #[instrument(skip(self))]
+    pub async fn delete_rule(&self, id: i64) -> Result<(), AppError> {
+        if !self.moderation_repo.delete_rule(id).await? {
+            return Err(AppError::ModerationRuleNotFound);
+        }
+        self.invalidate();
+
+        info!("Moderation rule deleted");
+
+        Ok(())
+    }
+
+    /// Lists flagged posts and comments, newest first.
+    #[instrument(skip(self))]
+    pub async fn flagged(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<FlaggedContentListResponse, AppError> {
+        let flags = self.moderation_repo.list_flags(limit, offset).await?;
+        let total = self.moderation_repo.count_flags().await?;
+
+        Ok(FlaggedContentListResponse {
+            items: flags.into_iter().map(flagged_to_dto).collect(),
+            total,
+        })
+    }
+
+    /// Removes an item from the flagged content queue, leaving the content in place.
+    #[instrument(skip(self))]
+    pub async fn dismiss_flag(&self, id: i64) -> Result<(), AppError> {
+        if !self.moderation_repo.delete_flag(id).await? {
+            return Err(AppError::ContentFlagNotFound);
+        }
+
+        info!("Content flag dismissed");
+
+        Ok(())
+    }
+
+    /// Returns the cached rule set, loading it on first use.
+    async fn rule_set(&self) -> Result<Arc<RuleSet>, AppError> {
+        if let Some(rule_set) = self.cached() {
+            return Ok(rule_set);
+        }
+
+        let rules = self.moderation_repo.list_rules().await?;
+        let rule_set = Arc::new(RuleSet::compile(&rules));
+        *self.rule_set.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::clone(&rule_set));
+
+        Ok(rule_set)
+    }
+
+    /// Returns the cached rule set, if loaded.
+    fn cached(&self) -> Option<Arc<RuleSet>> {
+        self.rule_set
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Drops the cached rule set so the next check reloads it.
+    fn invalidate(&self) {
+        *self.rule_set.write().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+/// Trims a rule name and checks its length.
+fn validate_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation(
+            "Rule name cannot be empty".to_string(),
+        ));
+    }
+    if name.chars().count() > MAX_RULE_NAME_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Rule name must be at most {MAX_RULE_NAME_LENGTH} characters"
+        )));
+    }
+
+    Ok(name.to_string())
+}
+
+/// Checks a rule pattern's length.
+fn validate_pattern(pattern: &str) -> Result<(), AppError> {
+    if pattern.trim().is_empty() {
+        return Err(AppError::Validation("Pattern cannot be empty".to_string()));
+    }
+    if pattern.chars().count() > MAX_RULE_PATTERN_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Pattern must be at most {MAX_RULE_PATTERN_LENGTH} characters"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Converts a ModerationRule domain entity to ModerationRuleDto.
+fn rule_to_dto(rule: &ModerationRule) -> ModerationRuleDto {
+    let hit_rate = if rule.checked_count > 0 {
+        rule.hit_count as f64 / rule.checked_count as f64
+    } else {
+        0.0
+    };
+
+    ModerationRuleDto {
+        id: rule.id,
+        name: rule.name.clone(),
+        kind: rule.kind(),
+        pattern: rule.pattern.clone(),
+        action: rule.action(),
+        enabled: rule.enabled,
+        checked_count: rule.checked_count,
+        hit_count: rule.hit_count,
+        hit_rate,
+        last_hit_at: rule.last_hit_at,
+        created_at: rule.created_at,
+    }
+}
+
+/// Converts a FlaggedContent domain entity to FlaggedContentDto.
+fn flagged_to_dto(flag: FlaggedContent) -> FlaggedContentDto {
+    FlaggedContentDto {
+        id: flag.id,
+        kind: if flag.comment_id.is_some() {
+            ContentKind::Comment
+        } else {
+            ContentKind::Post
+        },
+        post_id: flag.post_id,
+        post_public_id: flag.post_public_id,
+        comment_id: flag.comment_id,
+        author_id: flag.author_id,
+        author_username: flag.author_username,
+        excerpt: flag.excerpt,
+        rule_id: flag.rule_id,
+        rule_name: flag.rule_name,
+        created_at: flag.created_at,
+    }
+}
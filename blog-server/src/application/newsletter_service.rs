@@ -0,0 +1,209 @@
+//! Newsletter service: double opt-in subscriptions and the weekly digest job.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use blog_shared::{SubscriptionStatus, SubscriptionStatusResponse};
+use tracing::{info, instrument, warn};
+
+use crate::constants::{
+    NEWSLETTER_DIGEST_INTERVAL_DAYS, NEWSLETTER_JOB_INTERVAL_SECS, NEWSLETTER_TOP_POSTS,
+};
+use crate::data::NewsletterRepository;
+use crate::domain::{AppError, Subscription, render_confirmation, render_digest};
+use crate::infrastructure::clock::{Clock, SystemClock};
+use crate::infrastructure::mailer::{Email, Mailer};
+
+/// Service for newsletter subscriptions and digests.
+#[derive(Clone)]
+pub struct NewsletterService {
+    newsletter_repo: Arc<NewsletterRepository>,
+    mailer: Mailer,
+    /// Base URL of the API, for confirmation and unsubscribe links.
+    public_url: String,
+    /// Base URL of the frontend, for post links.
+    site_url: String,
+    clock: Arc<dyn Clock>,
+}
+
+impl NewsletterService {
+    /// Creates a new NewsletterService.
+    pub fn new(
+        newsletter_repo: Arc<NewsletterRepository>,
+        mailer: Mailer,
+        public_url: String,
+        site_url: String,
+    ) -> Self {
+        Self {
+            newsletter_repo,
+            mailer,
+            public_url,
+            site_url,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Subscribes an email address pending confirmation and sends the
+    /// confirmation email.
+    ///
+    /// Always reports `pending`, so the endpoint does not reveal who is
+    /// already subscribed; confirmed subscribers get no new email.
+    #[instrument(skip(self, email))]
+    pub async fn subscribe(&self, email: &str) -> Result<SubscriptionStatusResponse, AppError> {
+        let pending = SubscriptionStatusResponse {
+            status: SubscriptionStatus::Pending,
+        };
+        if let Some(existing) = self.newsletter_repo.find_by_email(email).await?
+            && existing.status() == SubscriptionStatus::Confirmed
+        {
+            return Ok(pending);
+        }
+
+        let token = format!("{:016x}{:016x}", OsRng.next_u64(), OsRng.next_u64());
+        self.newsletter_repo
+            .upsert_pending(email, &token, self.clock.now())
+            .await?;
+
+        let body = render_confirmation(&self.newsletter_url("confirm", &token));
+        self.mailer
+            .send(Email {
+                to: email.to_string(),
+                subject: "Confirm your newsletter subscription".to_string(),
+                text: body.text,
+                html: body.html,
+                unsubscribe_url: None,
+            })
+            .await?;
+
+        info!("Newsletter subscription pending confirmation");
+
+        Ok(pending)
+    }
+
+    /// Confirms the subscription with the given token.
+    ///
+    /// Confirming twice is harmless; an unsubscribed address stays unsubscribed.
+    #[instrument(skip(self, token))]
+    pub async fn confirm(&self, token: &str) -> Result<SubscriptionStatusResponse, AppError> {
+        let subscription = self.find_by_token(token).await?;
+        let status = match subscription.status() {
+            SubscriptionStatus::Pending => {
+                self.newsletter_repo
+                    .confirm(subscription.id, self.clock.now())
+                    .await?;
+                info!(
+                    subscription_id = subscription.id,
+                    "Newsletter subscription confirmed"
+                );
+                SubscriptionStatus::Confirmed
+            }
+            status => status,
+        };
+
+        Ok(SubscriptionStatusResponse { status })
+    }
+
+    /// Unsubscribes the subscription with the given token.
+    #[instrument(skip(self, token))]
+    pub async fn unsubscribe(&self, token: &str) -> Result<SubscriptionStatusResponse, AppError> {
+        let subscription = self.find_by_token(token).await?;
+        self.newsletter_repo
+            .unsubscribe(subscription.id, self.clock.now())
+            .await?;
+
+        info!(subscription_id = subscription.id, "Newsletter unsubscribed");
+
+        Ok(SubscriptionStatusResponse {
+            status: SubscriptionStatus::Unsubscribed,
+        })
+    }
+
+    /// Runs the digest job forever, checking for due subscribers every
+    /// `NEWSLETTER_JOB_INTERVAL_SECS`.
+    pub async fn run(self) {
+        let mut ticker = tokio::time::interval(Duration::from_secs(NEWSLETTER_JOB_INTERVAL_SECS));
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.send_digests().await {
+                warn!(error = %e, "Newsletter digest job failed");
+            }
+        }
+    }
+
+    /// Sends the digest of the week's top posts to every confirmed subscriber
+    /// who has not received one in `NEWSLETTER_DIGEST_INTERVAL_DAYS`. Returns
+    /// how many were sent.
+    ///
+    /// Nothing is sent (or recorded) in weeks without new posts.
+    #[instrument(skip(self))]
+    pub async fn send_digests(&self) -> Result<usize, AppError> {
+        let now = self.clock.now();
+        let week_ago = now - chrono::Duration::days(NEWSLETTER_DIGEST_INTERVAL_DAYS);
+        let subscriptions = self.newsletter_repo.list_due(week_ago).await?;
+        if subscriptions.is_empty() {
+            return Ok(0);
+        }
+
+        let posts = self
+            .newsletter_repo
+            .top_posts(week_ago, NEWSLETTER_TOP_POSTS)
+            .await?;
+        if posts.is_empty() {
+            return Ok(0);
+        }
+
+        let mut sent = 0;
+        for subscription in subscriptions {
+            let unsubscribe_url = self.newsletter_url("unsubscribe", &subscription.token);
+            let dates = self
+                .newsletter_repo
+                .date_format(&subscription.email)
+                .await?;
+            let body = render_digest(
+                &posts,
+                |public_id| format!("{}/?post={}", self.site_url, public_id),
+                &unsubscribe_url,
+                &dates,
+            );
+            let email = Email {
+                to: subscription.email,
+                subject: "Top posts of the week".to_string(),
+                text: body.text,
+                html: body.html,
+                unsubscribe_url: Some(unsubscribe_url),
+            };
+            if let Err(e) = self.mailer.send(email).await {
+                warn!(subscription_id = subscription.id, error = %e, "Digest not sent");
+                continue;
+            }
+            self.newsletter_repo
+                .mark_digest_sent(subscription.id, now)
+                .await?;
+            sent += 1;
+        }
+
+        info!(sent, posts = posts.len(), "Newsletter digest sent");
+
+        Ok(sent)
+    }
+
+    /// Finds a subscription by token or fails with `SubscriptionNotFound`.
+    async fn find_by_token(&self, token: &str) -> Result<Subscription, AppError> {
+        self.newsletter_repo
+            .find_by_token(token)
+            .await?
+            .ok_or(AppError::SubscriptionNotFound)
+    }
+
+    /// Builds a link to a newsletter endpoint carrying the subscription token.
+    fn newsletter_url(&self, action: &str, token: &str) -> String {
+        format!("{}/api/newsletter/{action}?token={token}", self.public_url)
+    }
+}
@@ -0,0 +1,127 @@
+//! Notification service for activity on users' content.
+
+use std::sync::Arc;
+
+use blog_shared::{
+    CursorPage, NotificationDto, NotificationKind, NotificationListResponse, UnreadCountResponse,
+};
+use tracing::{info, instrument};
+
+use crate::data::NotificationRepository;
+use crate::domain::{AppError, Notification, PageCursor};
+use crate::infrastructure::clock::{Clock, SystemClock};
+
+/// Service for user notifications.
+#[derive(Clone)]
+pub struct NotificationService {
+    notification_repo: Arc<NotificationRepository>,
+    clock: Arc<dyn Clock>,
+}
+
+impl NotificationService {
+    /// Creates a new NotificationService.
+    pub fn new(notification_repo: Arc<NotificationRepository>) -> Self {
+        Self {
+            notification_repo,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Notifies `user_id` of something `actor_id` did on one of their posts.
+    /// Nobody is notified of their own actions.
+    #[instrument(skip(self))]
+    pub async fn notify(
+        &self,
+        user_id: i64,
+        kind: NotificationKind,
+        actor_id: i64,
+        post_id: i64,
+        comment_id: Option<i64>,
+    ) -> Result<(), AppError> {
+        if user_id == actor_id {
+            return Ok(());
+        }
+        let id = self
+            .notification_repo
+            .create(
+                user_id,
+                kind.as_str(),
+                actor_id,
+                post_id,
+                comment_id,
+                self.clock.now(),
+            )
+            .await?;
+
+        info!(notification_id = id, "Notification created");
+
+        Ok(())
+    }
+
+    /// Lists the user's notifications, newest first, a page at a time.
+    #[instrument(skip(self))]
+    pub async fn list(
+        &self,
+        user_id: i64,
+        page: &CursorPage,
+    ) -> Result<NotificationListResponse, AppError> {
+        let before = page.cursor.as_deref().map(PageCursor::parse).transpose()?;
+        let mut notifications = self
+            .notification_repo
+            .list_before(user_id, page.limit + 1, before)
+            .await?;
+        let next_cursor = PageCursor::next_page(&mut notifications, page.limit, |n| {
+            PageCursor::new(n.created_at, n.id)
+        });
+
+        Ok(NotificationListResponse {
+            notifications: notifications
+                .into_iter()
+                .map(notification_to_dto)
+                .collect::<Result<_, _>>()?,
+            next_cursor: next_cursor.map(|cursor| cursor.encode()),
+        })
+    }
+
+    /// Returns the number of the user's unread notifications.
+    pub async fn unread_count(&self, user_id: i64) -> Result<UnreadCountResponse, AppError> {
+        let unread = self.notification_repo.count_unread(user_id).await?;
+        Ok(UnreadCountResponse { unread })
+    }
+
+    /// Marks the user's notifications as read, only up to `up_to_id` if
+    /// given.
+    #[instrument(skip(self))]
+    pub async fn mark_read(&self, user_id: i64, up_to_id: Option<i64>) -> Result<(), AppError> {
+        let marked = self
+            .notification_repo
+            .mark_read(user_id, up_to_id, self.clock.now())
+            .await?;
+
+        info!(marked, "Notifications marked as read");
+
+        Ok(())
+    }
+}
+
+/// Converts a Notification domain entity to NotificationDto.
+fn notification_to_dto(notification: Notification) -> Result<NotificationDto, AppError> {
+    Ok(NotificationDto {
+        id: notification.id,
+        kind: notification.kind.parse().map_err(AppError::Internal)?,
+        actor_id: notification.actor_id,
+        actor_public_id: notification.actor_public_id,
+        actor_username: notification.actor_username,
+        post_id: notification.post_id,
+        post_public_id: notification.post_public_id,
+        comment_id: notification.comment_id,
+        read: notification.read_at.is_some(),
+        created_at: notification.created_at,
+    })
+}
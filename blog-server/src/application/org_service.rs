@@ -0,0 +1,228 @@
+//! Organization service: organizations, their members, and profile pages.
+
+use std::sync::Arc;
+
+use blog_shared::{
+    CreateOrgRequest, MembershipDto, MembershipListResponse, OrgDto, OrgMemberDto,
+    OrgProfileResponse, OrgRole,
+};
+use tracing::{info, instrument};
+
+use crate::application::BlogService;
+use crate::data::{OrgRepository, UserRepository};
+use crate::domain::{AppError, Organization, parse_org_profile, parse_org_slug, policy};
+use crate::infrastructure::clock::{Clock, SystemClock};
+
+/// Service for organizations and their members.
+#[derive(Clone)]
+pub struct OrgService {
+    org_repo: Arc<OrgRepository>,
+    user_repo: Arc<UserRepository>,
+    blog_service: BlogService,
+    clock: Arc<dyn Clock>,
+}
+
+impl OrgService {
+    /// Creates a new OrgService, listing organization posts through
+    /// `blog_service`.
+    pub fn new(
+        org_repo: Arc<OrgRepository>,
+        user_repo: Arc<UserRepository>,
+        blog_service: BlogService,
+    ) -> Self {
+        Self {
+            org_repo,
+            user_repo,
+            blog_service,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Creates an organization with `user_id` as its owner.
+    #[instrument(skip(self, req), fields(user_id = user_id))]
+    pub async fn create_org(
+        &self,
+        user_id: i64,
+        req: CreateOrgRequest,
+    ) -> Result<OrgDto, AppError> {
+        let slug = parse_org_slug(&req.slug)?;
+        let (name, bio) = parse_org_profile(&req.name, req.bio.as_deref())?;
+        if self.org_repo.find_by_slug(&slug).await?.is_some() {
+            return Err(AppError::OrgExists);
+        }
+
+        let org = self
+            .org_repo
+            .create(&slug, &name, bio.as_deref(), user_id, self.clock.now())
+            .await?;
+
+        info!(org_id = org.id, slug = %org.slug, "Organization created");
+
+        Ok(org_to_dto(org))
+    }
+
+    /// Gets an organization's profile: its members and a page of its posts
+    /// visible to `viewer_id`.
+    #[instrument(skip(self))]
+    pub async fn org_profile(
+        &self,
+        slug: &str,
+        limit: i64,
+        offset: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<OrgProfileResponse, AppError> {
+        let org = self.find(slug).await?;
+        let members = self.org_repo.list_members(org.id).await?;
+        let posts = self
+            .blog_service
+            .list_posts_by_org(org.id, limit, offset, viewer_id)
+            .await?;
+
+        Ok(OrgProfileResponse {
+            org: org_to_dto(org),
+            members: members
+                .into_iter()
+                .map(|member| OrgMemberDto {
+                    role: member.role(),
+                    user_id: member.user_id,
+                    public_id: member.public_id,
+                    username: member.username,
+                    joined_at: member.joined_at,
+                })
+                .collect(),
+            posts: posts.posts,
+            total: posts.total,
+        })
+    }
+
+    /// Adds `username` to an organization, or changes their role. Only
+    /// owners can manage members, and the last owner cannot step down.
+    #[instrument(skip(self), fields(actor_id = actor_id))]
+    pub async fn set_member(
+        &self,
+        slug: &str,
+        actor_id: i64,
+        username: &str,
+        role: OrgRole,
+    ) -> Result<(), AppError> {
+        let org = self.find(slug).await?;
+        self.authorize_manage(&org, actor_id).await?;
+        let user = self
+            .user_repo
+            .find_by_username(username)
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+        if role != OrgRole::Owner {
+            self.keep_an_owner(&org, user.id).await?;
+        }
+
+        self.org_repo
+            .set_member(org.id, user.id, role.as_str(), self.clock.now())
+            .await?;
+
+        info!(org_id = org.id, user_id = user.id, %role, "Organization member set");
+
+        Ok(())
+    }
+
+    /// Removes `username` from an organization. Only owners can remove
+    /// members, and the last owner cannot be removed.
+    #[instrument(skip(self), fields(actor_id = actor_id))]
+    pub async fn remove_member(
+        &self,
+        slug: &str,
+        actor_id: i64,
+        username: &str,
+    ) -> Result<(), AppError> {
+        let org = self.find(slug).await?;
+        self.authorize_manage(&org, actor_id).await?;
+        let user = self
+            .user_repo
+            .find_by_username(username)
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+        self.keep_an_owner(&org, user.id).await?;
+
+        if !self.org_repo.remove_member(org.id, user.id).await? {
+            return Err(AppError::UserNotFound);
+        }
+
+        info!(
+            org_id = org.id,
+            user_id = user.id,
+            "Organization member removed"
+        );
+
+        Ok(())
+    }
+
+    /// Lists the organizations `user_id` belongs to.
+    #[instrument(skip(self))]
+    pub async fn list_memberships(&self, user_id: i64) -> Result<MembershipListResponse, AppError> {
+        let memberships = self.org_repo.list_memberships(user_id).await?;
+
+        Ok(MembershipListResponse {
+            orgs: memberships
+                .into_iter()
+                .map(|membership| MembershipDto {
+                    role: membership.role(),
+                    org: OrgDto {
+                        id: membership.id,
+                        slug: membership.slug,
+                        name: membership.name,
+                        bio: membership.bio,
+                        created_at: membership.created_at,
+                    },
+                })
+                .collect(),
+        })
+    }
+
+    /// Finds an organization by slug.
+    async fn find(&self, slug: &str) -> Result<Organization, AppError> {
+        self.org_repo
+            .find_by_slug(&slug.trim().to_lowercase())
+            .await?
+            .ok_or(AppError::OrgNotFound)
+    }
+
+    /// Checks that `user_id` may manage the members of `org`.
+    async fn authorize_manage(&self, org: &Organization, user_id: i64) -> Result<(), AppError> {
+        let role = self.org_repo.member_role(org.id, user_id).await?;
+        policy::authorize(policy::can_manage_org(
+            role.and_then(|role| role.parse().ok()),
+        ))
+    }
+
+    /// Fails if `user_id` is the only owner of `org`, who would leave it
+    /// without one.
+    async fn keep_an_owner(&self, org: &Organization, user_id: i64) -> Result<(), AppError> {
+        let role = self.org_repo.member_role(org.id, user_id).await?;
+        if role.as_deref() == Some(OrgRole::Owner.as_str())
+            && self.org_repo.count_owners(org.id).await? <= 1
+        {
+            return Err(AppError::Validation(
+                "An organization needs at least one owner".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts an Organization domain entity to OrgDto.
+fn org_to_dto(org: Organization) -> OrgDto {
+    OrgDto {
+        id: org.id,
+        slug: org.slug,
+        name: org.name,
+        bio: org.bio,
+        created_at: org.created_at,
+    }
+}
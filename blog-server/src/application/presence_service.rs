@@ -0,0 +1,222 @@
+//! Co-editing presence: who has a post open for editing right now.
+//!
+//! Presence is a soft lock: it warns editors about each other but blocks
+//! nothing. It lives in memory only, so it is per server process and
+//! forgotten on restart.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use blog_shared::{EditingPresenceDto, EditorDto};
+use chrono::{DateTime, Duration, Utc};
+use tokio::sync::broadcast;
+use tracing::{debug, instrument, warn};
+
+use crate::constants::EDITING_PRESENCE_TTL_SECS;
+use crate::data::PostRepository;
+use crate::domain::{AppError, DomainEvent, Post, policy};
+use crate::infrastructure::clock::{Clock, SystemClock};
+use crate::infrastructure::event_bus::EventBus;
+
+/// User editing a post, with their latest heartbeat.
+#[derive(Debug, Clone)]
+struct Editor {
+    public_id: String,
+    username: String,
+    since: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+}
+
+/// Editors by user ID, by post ID.
+type EditorsByPost = HashMap<i64, HashMap<i64, Editor>>;
+
+/// Service tracking editing heartbeats and announcing changes on the
+/// event bus.
+#[derive(Clone)]
+pub struct PresenceService {
+    post_repo: Arc<PostRepository>,
+    events: EventBus,
+    editors: Arc<Mutex<EditorsByPost>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl PresenceService {
+    /// Creates a new PresenceService that announces changes on `events`.
+    pub fn new(post_repo: Arc<PostRepository>, events: EventBus) -> Self {
+        Self {
+            post_repo,
+            events,
+            editors: Arc::new(Mutex::new(HashMap::new())),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Records that `user_id` is editing post `id` and returns everyone
+    /// editing it, themselves included. Only users who may edit the post
+    /// can send heartbeats.
+    #[instrument(skip(self), fields(post_id = id, user_id = user_id))]
+    pub async fn heartbeat(&self, id: i64, user_id: i64) -> Result<EditingPresenceDto, AppError> {
+        let post = self.authorize(id, user_id).await?;
+        let author = self.post_repo.find_author(user_id).await?;
+
+        let now = self.clock.now();
+        let (presence, changed) = {
+            let mut editors = self.lock();
+            let mut changed = expire(&mut editors, now);
+            let post_editors = editors.entry(id).or_default();
+            match post_editors.get_mut(&user_id) {
+                Some(editor) => editor.last_seen = now,
+                None => {
+                    post_editors.insert(
+                        user_id,
+                        Editor {
+                            public_id: author.public_id,
+                            username: author.username,
+                            since: now,
+                            last_seen: now,
+                        },
+                    );
+                    changed.push(id);
+                }
+            }
+            (snapshot(&editors, id, &post.public_id), changed)
+        };
+
+        self.announce(changed).await;
+
+        Ok(presence)
+    }
+
+    /// Records that `user_id` closed post `id`.
+    #[instrument(skip(self), fields(post_id = id, user_id = user_id))]
+    pub async fn stop_editing(&self, id: i64, user_id: i64) -> Result<(), AppError> {
+        let changed = {
+            let mut editors = self.lock();
+            let mut changed = expire(&mut editors, self.clock.now());
+            if let Some(post_editors) = editors.get_mut(&id)
+                && post_editors.remove(&user_id).is_some()
+            {
+                if post_editors.is_empty() {
+                    editors.remove(&id);
+                }
+                changed.push(id);
+            }
+            changed
+        };
+
+        self.announce(changed).await;
+
+        Ok(())
+    }
+
+    /// Returns everyone editing post `id`, for a user who may edit it.
+    #[instrument(skip(self), fields(post_id = id, user_id = user_id))]
+    pub async fn presence(&self, id: i64, user_id: i64) -> Result<EditingPresenceDto, AppError> {
+        let post = self.authorize(id, user_id).await?;
+
+        let (presence, changed) = {
+            let mut editors = self.lock();
+            let changed = expire(&mut editors, self.clock.now());
+            (snapshot(&editors, id, &post.public_id), changed)
+        };
+
+        self.announce(changed).await;
+
+        Ok(presence)
+    }
+
+    /// Subscribes to events published from now on, including presence
+    /// changes.
+    pub fn subscribe(&self) -> broadcast::Receiver<DomainEvent> {
+        self.events.subscribe()
+    }
+
+    /// Checks that `user_id` may edit post `id`, returning the post.
+    async fn authorize(&self, id: i64, user_id: i64) -> Result<Post, AppError> {
+        let post = self
+            .post_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(AppError::PostNotFound)?;
+        policy::authorize(policy::can_edit_post(user_id, &post))?;
+        Ok(post)
+    }
+
+    /// Publishes the current editors of each post in `changed`.
+    async fn announce(&self, mut changed: Vec<i64>) {
+        changed.sort_unstable();
+        changed.dedup();
+        for id in changed {
+            // Presence names the post by public ID; a post deleted since
+            // has no editor left to tell
+            let public_id = match self.post_repo.find_by_id(id).await {
+                Ok(Some(post)) => post.public_id,
+                Ok(None) => continue,
+                Err(e) => {
+                    warn!(error = %e, post_id = id, "Failed to load post for editing presence");
+                    continue;
+                }
+            };
+            let presence = snapshot(&self.lock(), id, &public_id);
+            debug!(
+                post_id = id,
+                editors = presence.editors.len(),
+                "Editing presence changed"
+            );
+            self.events.publish(DomainEvent::EditingChanged {
+                post_id: id,
+                presence,
+            });
+        }
+    }
+
+    /// Locks the editor map; a panic while holding it cannot leave it
+    /// inconsistent, so a poisoned lock is used as is.
+    fn lock(&self) -> MutexGuard<'_, EditorsByPost> {
+        self.editors.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Drops editors without a heartbeat in `EDITING_PRESENCE_TTL_SECS`,
+/// returning the posts that lost one.
+fn expire(editors: &mut EditorsByPost, now: DateTime<Utc>) -> Vec<i64> {
+    let cutoff = now - Duration::seconds(EDITING_PRESENCE_TTL_SECS);
+    let mut changed = Vec::new();
+    editors.retain(|&id, post_editors| {
+        let before = post_editors.len();
+        post_editors.retain(|_, editor| editor.last_seen > cutoff);
+        if post_editors.len() != before {
+            changed.push(id);
+        }
+        !post_editors.is_empty()
+    });
+    changed
+}
+
+/// Lists the editors of post `id`, longest editing first.
+fn snapshot(editors: &EditorsByPost, id: i64, public_id: &str) -> EditingPresenceDto {
+    let mut list: Vec<EditorDto> = editors
+        .get(&id)
+        .into_iter()
+        .flatten()
+        .map(|(&user_id, editor)| EditorDto {
+            user_id,
+            user_public_id: editor.public_id.clone(),
+            username: editor.username.clone(),
+            since: editor.since,
+        })
+        .collect();
+    list.sort_by_key(|editor| (editor.since, editor.user_id));
+
+    EditingPresenceDto {
+        post_id: id,
+        post_public_id: public_id.to_string(),
+        editors: list,
+    }
+}
@@ -0,0 +1,231 @@
+//! Quota service: daily request quotas of service accounts.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use blog_shared::{ApiUsageDto, ApiUsageResponse};
+use chrono::NaiveDate;
+use tracing::{instrument, warn};
+
+use crate::constants::QUOTA_FLUSH_INTERVAL_SECS;
+use crate::data::ServiceAccountRepository;
+#[cfg(feature = "redis")]
+use crate::domain::ApiQuota;
+use crate::domain::{ApiUsage, AppError, QuotaStatus, next_reset};
+use crate::infrastructure::clock::{Clock, SystemClock};
+#[cfg(feature = "redis")]
+use crate::infrastructure::redis_quota::RedisQuotaCounter;
+
+/// A service account's counters for one day.
+#[derive(Debug, Clone, Copy)]
+struct Counter {
+    usage: ApiUsage,
+    /// Changed since last written to the database.
+    dirty: bool,
+}
+
+/// Counters by service account ID and UTC day.
+type Counters = HashMap<(i64, NaiveDate), Counter>;
+
+/// Service counting service account requests against their daily quotas.
+///
+/// Counters live in memory and are written to the database every
+/// `QUOTA_FLUSH_INTERVAL_SECS` by [`QuotaService::run`], so a restart loses
+/// at most that much usage. Limits are read on every request, so changes
+/// apply immediately.
+///
+/// With Redis (`with_redis`), requests are counted there so every replica
+/// enforces the same limits; the in-memory counters then mirror the shared
+/// ones and take over while Redis is unreachable.
+#[derive(Clone)]
+pub struct QuotaService {
+    service_account_repo: Arc<ServiceAccountRepository>,
+    counters: Arc<Mutex<Counters>>,
+    clock: Arc<dyn Clock>,
+    #[cfg(feature = "redis")]
+    redis: Option<Arc<RedisQuotaCounter>>,
+}
+
+impl QuotaService {
+    /// Creates a new QuotaService.
+    pub fn new(service_account_repo: Arc<ServiceAccountRepository>) -> Self {
+        Self {
+            service_account_repo,
+            counters: Arc::new(Mutex::new(HashMap::new())),
+            clock: Arc::new(SystemClock),
+            #[cfg(feature = "redis")]
+            redis: None,
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Counts usage in Redis, shared by every replica.
+    #[cfg(feature = "redis")]
+    pub fn with_redis(mut self, counter: RedisQuotaCounter) -> Self {
+        self.redis = Some(Arc::new(counter));
+        self
+    }
+
+    /// Runs the job writing usage counters to the database. Never returns.
+    pub async fn run(self) {
+        let mut ticker = tokio::time::interval(Duration::from_secs(QUOTA_FLUSH_INTERVAL_SECS));
+        loop {
+            ticker.tick().await;
+            self.flush().await;
+        }
+    }
+
+    /// Counts a request of a service account, returning where it stands
+    /// against its tightest limit (`None` when unlimited).
+    ///
+    /// Returns `AppError::QuotaExceeded` without counting the request once a
+    /// limit is reached.
+    pub async fn record(
+        &self,
+        account_id: i64,
+        is_write: bool,
+    ) -> Result<Option<QuotaStatus>, AppError> {
+        let Some(account) = self.service_account_repo.find(account_id).await? else {
+            return Ok(None);
+        };
+        let quota = account.quota();
+        let now = self.clock.now();
+        let key = (account_id, now.date_naive());
+        if !self.lock().contains_key(&key) {
+            let stored = self.load_usage(key.0, key.1).await?;
+            self.lock().entry(key).or_insert(Counter {
+                usage: stored,
+                dirty: false,
+            });
+        }
+
+        #[cfg(feature = "redis")]
+        let shared = self.consume_shared(key, quota, is_write).await;
+        #[cfg(not(feature = "redis"))]
+        let shared: Option<ApiUsage> = None;
+
+        let mut counters = self.lock();
+        let counter = counters.entry(key).or_insert(Counter {
+            usage: ApiUsage::empty(key.1),
+            dirty: false,
+        });
+        if let Some(usage) = shared {
+            counter.usage = usage;
+        }
+        let status = quota
+            .consume(&mut counter.usage, is_write)
+            .map_err(|status| AppError::QuotaExceeded {
+                status,
+                retry_after_secs: status.seconds_until_reset(now),
+            })?;
+        counter.dirty = true;
+
+        Ok(status)
+    }
+
+    /// Counts a request in Redis, returning the shared usage from before it,
+    /// or `None` to count in memory (no Redis, or Redis unreachable).
+    #[cfg(feature = "redis")]
+    async fn consume_shared(
+        &self,
+        key: (i64, NaiveDate),
+        quota: ApiQuota,
+        is_write: bool,
+    ) -> Option<ApiUsage> {
+        let redis = self.redis.as_ref()?;
+        let seed = self
+            .lock()
+            .get(&key)
+            .map_or(ApiUsage::empty(key.1), |c| c.usage);
+        match redis.consume(key.0, quota, seed, is_write).await {
+            Ok(usage) => Some(usage),
+            Err(e) => {
+                warn!(error = %e, "Counting API usage in memory, Redis unavailable");
+                None
+            }
+        }
+    }
+
+    /// Reports today's usage of the user's active service accounts.
+    #[instrument(skip(self))]
+    pub async fn usage(&self, user_id: i64) -> Result<ApiUsageResponse, AppError> {
+        let now = self.clock.now();
+        let today = now.date_naive();
+        let accounts = self
+            .service_account_repo
+            .list_active_for_user(user_id, now)
+            .await?;
+
+        let mut service_accounts = Vec::with_capacity(accounts.len());
+        for account in accounts {
+            let counted = self
+                .lock()
+                .get(&(account.id, today))
+                .map(|counter| counter.usage);
+            let usage = match counted {
+                Some(usage) => usage,
+                None => self.load_usage(account.id, today).await?,
+            };
+            service_accounts.push(ApiUsageDto {
+                service_account_id: account.id,
+                name: account.name,
+                day: today,
+                requests: usage.requests,
+                writes: usage.writes,
+                requests_per_day: account.requests_per_day,
+                writes_per_day: account.writes_per_day,
+                resets_at: next_reset(today),
+            });
+        }
+
+        Ok(ApiUsageResponse { service_accounts })
+    }
+
+    /// Writes changed counters to the database and drops those of past days.
+    pub async fn flush(&self) {
+        let today = self.clock.now().date_naive();
+        let dirty: Vec<(i64, ApiUsage)> = {
+            let mut counters = self.lock();
+            let dirty = counters
+                .iter_mut()
+                .filter(|(_, counter)| counter.dirty)
+                .map(|(&(id, _), counter)| {
+                    counter.dirty = false;
+                    (id, counter.usage)
+                })
+                .collect();
+            counters.retain(|&(_, day), _| day == today);
+            dirty
+        };
+
+        for (id, usage) in dirty {
+            if let Err(e) = self.service_account_repo.save_usage(id, usage).await {
+                warn!(service_account_id = id, error = %e, "Failed to save API usage");
+                // Try again next time (past days are gone from memory by now)
+                if let Some(counter) = self.lock().get_mut(&(id, usage.day)) {
+                    counter.dirty = true;
+                }
+            }
+        }
+    }
+
+    /// Loads a day's stored usage, or none.
+    async fn load_usage(&self, account_id: i64, day: NaiveDate) -> Result<ApiUsage, AppError> {
+        Ok(self
+            .service_account_repo
+            .find_usage(account_id, day)
+            .await?
+            .unwrap_or_else(|| ApiUsage::empty(day)))
+    }
+
+    /// Locks the counters, ignoring poisoning: they are always valid.
+    fn lock(&self) -> MutexGuard<'_, Counters> {
+        self.counters.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
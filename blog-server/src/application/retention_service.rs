@@ -0,0 +1,108 @@
+//! Background job that removes data past its retention time.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use blog_shared::{RetentionPolicyDto, RetentionReportDto};
+use chrono::{DateTime, Months, Utc};
+use tracing::{info, instrument, warn};
+
+use crate::constants::RETENTION_JOB_INTERVAL_SECS;
+use crate::data::RetentionRepository;
+use crate::domain::{AppError, RetentionPolicy};
+use crate::infrastructure::clock::{Clock, SystemClock};
+use crate::infrastructure::config::RetentionConfig;
+
+/// Service that applies the configured retention policies.
+#[derive(Clone)]
+pub struct RetentionService {
+    retention_repo: Arc<RetentionRepository>,
+    config: RetentionConfig,
+    clock: Arc<dyn Clock>,
+}
+
+impl RetentionService {
+    /// Creates a new RetentionService.
+    pub fn new(retention_repo: Arc<RetentionRepository>, config: RetentionConfig) -> Self {
+        Self {
+            retention_repo,
+            config,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Runs the job forever, every `RETENTION_JOB_INTERVAL_SECS`.
+    pub async fn run(self) {
+        let mut ticker = tokio::time::interval(Duration::from_secs(RETENTION_JOB_INTERVAL_SECS));
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.purge_due().await {
+                warn!(error = %e, "Retention job failed");
+            }
+        }
+    }
+
+    /// Reports what the next run would remove, without removing anything.
+    #[instrument(skip(self))]
+    pub async fn report(&self) -> Result<RetentionReportDto, AppError> {
+        let mut policies = Vec::new();
+        for policy in RetentionPolicy::ALL {
+            let cutoff = self.cutoff(policy);
+            let due = match cutoff {
+                Some(cutoff) => self.retention_repo.count_due(policy, cutoff).await?,
+                None => 0,
+            };
+            policies.push(RetentionPolicyDto {
+                name: policy.as_str().to_string(),
+                cutoff,
+                due,
+            });
+        }
+
+        Ok(RetentionReportDto { policies })
+    }
+
+    /// Removes everything past its retention time. Returns how many rows
+    /// were removed.
+    #[instrument(skip(self))]
+    pub async fn purge_due(&self) -> Result<u64, AppError> {
+        let mut removed = 0;
+        for policy in RetentionPolicy::ALL {
+            let Some(cutoff) = self.cutoff(policy) else {
+                continue;
+            };
+            let count = self.retention_repo.purge(policy, cutoff).await?;
+            if count > 0 {
+                info!(policy = policy.as_str(), count, "Expired data removed");
+            }
+            removed += count;
+        }
+
+        Ok(removed)
+    }
+
+    /// Time before which `policy` removes data; `None` while it is off.
+    fn cutoff(&self, policy: RetentionPolicy) -> Option<DateTime<Utc>> {
+        let now = self.clock.now();
+        match policy {
+            RetentionPolicy::AuditLog => self
+                .config
+                .audit_log_months
+                .and_then(|months| now.checked_sub_months(Months::new(months))),
+            RetentionPolicy::UnsubscribedEmails => self
+                .config
+                .unsubscribed_days
+                .map(|days| now - chrono::Duration::days(i64::from(days))),
+            RetentionPolicy::Trash => self
+                .config
+                .trash_days
+                .map(|days| now - chrono::Duration::days(i64::from(days))),
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! Background job keeping an external search index in step with the posts.
+
+use std::sync::Arc;
+
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, instrument, warn};
+
+use crate::data::PostRepository;
+use crate::domain::{AppError, DomainEvent, SearchDocument, SearchIndex, normalize_username};
+use crate::infrastructure::event_bus::EventBus;
+
+/// Service feeding post changes to a search index that the database does
+/// not update itself.
+///
+/// Changes are taken from `DomainEvent::PostChanged`. Events missed while
+/// the server was down, or skipped by a lagging subscriber, are only caught
+/// up by `reindex` (the `reindex_search` binary).
+#[derive(Clone)]
+pub struct SearchIndexService {
+    index: Arc<dyn SearchIndex>,
+    post_repo: Arc<PostRepository>,
+    events: EventBus,
+}
+
+impl SearchIndexService {
+    /// Creates a new SearchIndexService.
+    pub fn new(
+        index: Arc<dyn SearchIndex>,
+        post_repo: Arc<PostRepository>,
+        events: EventBus,
+    ) -> Self {
+        Self {
+            index,
+            post_repo,
+            events,
+        }
+    }
+
+    /// Runs the job until the event bus closes, indexing each changed post.
+    pub async fn run(self) {
+        let mut events = self.events.subscribe();
+        loop {
+            match events.recv().await {
+                Ok(DomainEvent::PostChanged { post_id }) => {
+                    if let Err(e) = self.sync(post_id).await {
+                        warn!(post_id, error = %e, "Search indexing failed");
+                    }
+                }
+                Ok(_) => {}
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(skipped, "Search indexer fell behind; run reindex_search");
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
+
+    /// Indexes every post not in the trash. Returns how many were indexed.
+    #[instrument(skip(self))]
+    pub async fn reindex(&self) -> Result<u64, AppError> {
+        let mut indexed = 0;
+        for post_id in self.post_repo.list_ids().await? {
+            self.sync(post_id).await?;
+            indexed += 1;
+        }
+
+        info!(indexed, "Search index rebuilt");
+        Ok(indexed)
+    }
+
+    /// Puts the current state of a post in the index, or removes it once
+    /// trashed.
+    async fn sync(&self, post_id: i64) -> Result<(), AppError> {
+        let Some(post) = self.post_repo.find_by_id(post_id).await? else {
+            return self.index.remove(post_id).await;
+        };
+        let author = self.post_repo.find_author_username(post.author_id).await?;
+        let tags = self.post_repo.find_tags(post.id).await?;
+
+        self.index
+            .upsert(SearchDocument::new(
+                &post,
+                normalize_username(&author),
+                tags,
+            ))
+            .await
+    }
+}
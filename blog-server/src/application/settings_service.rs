@@ -0,0 +1,196 @@
+//! Settings service for profile, preferences, and sessions.
+
+use std::sync::Arc;
+
+use blog_shared::{
+    PreferencesDto, ProfileDto, PublicUserDto, SessionDto, SessionListResponse, SettingsDto, Theme,
+    UserSuggestionDto, UserSuggestionResponse,
+};
+use chrono::Duration;
+use tracing::{info, instrument};
+
+use crate::constants::{JWT_EXPIRY_HOURS, SUGGESTION_LIMIT};
+use crate::data::UserRepository;
+use crate::domain::{AppError, EntityRef, UserSettings, normalize_username};
+use crate::infrastructure::clock::{Clock, SystemClock};
+
+/// Service for the current user's settings.
+#[derive(Clone)]
+pub struct SettingsService {
+    user_repo: Arc<UserRepository>,
+    clock: Arc<dyn Clock>,
+}
+
+impl SettingsService {
+    /// Creates a new SettingsService.
+    pub fn new(user_repo: Arc<UserRepository>) -> Self {
+        Self {
+            user_repo,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Gets all settings of a user.
+    #[instrument(skip(self))]
+    pub async fn get_settings(&self, user_id: i64) -> Result<SettingsDto, AppError> {
+        let settings = self
+            .user_repo
+            .find_settings(user_id)
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+
+        Ok(settings_to_dto(settings))
+    }
+
+    /// Gets the public profile of any user.
+    #[instrument(skip(self))]
+    pub async fn public_profile(&self, user: &EntityRef) -> Result<PublicUserDto, AppError> {
+        let user_id = self
+            .user_repo
+            .resolve_id(user)
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+        let profile = self
+            .user_repo
+            .find_public_profile(user_id)
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+
+        Ok(PublicUserDto {
+            id: profile.id,
+            public_id: profile.public_id,
+            username: profile.username,
+            bio: profile.bio,
+            avatar_url: profile.avatar_url,
+        })
+    }
+
+    /// Suggests users to complete an `@mention` starting with `prefix`
+    /// (case-insensitive, a leading `@` ignored), at most `SUGGESTION_LIMIT`.
+    #[instrument(skip(self))]
+    pub async fn suggest_users(&self, prefix: &str) -> Result<UserSuggestionResponse, AppError> {
+        let prefix = normalize_username(prefix.trim().trim_start_matches('@'));
+        let users = self.user_repo.suggest(&prefix, SUGGESTION_LIMIT).await?;
+
+        Ok(UserSuggestionResponse {
+            users: users
+                .into_iter()
+                .map(|user| UserSuggestionDto {
+                    username: user.username,
+                    avatar_url: user.avatar_url,
+                })
+                .collect(),
+        })
+    }
+
+    /// Replaces the public profile of a user.
+    ///
+    /// A website can belong to only one user since it identifies them to Micropub.
+    #[instrument(skip(self, profile))]
+    pub async fn update_profile(
+        &self,
+        user_id: i64,
+        profile: ProfileDto,
+    ) -> Result<ProfileDto, AppError> {
+        if let Some(ref website) = profile.website {
+            let owner = self.user_repo.find_id_by_website(website).await?;
+            if owner.is_some_and(|owner| owner != user_id) {
+                return Err(AppError::Validation(
+                    "Website is already used by another account".to_string(),
+                ));
+            }
+        }
+
+        self.user_repo
+            .update_profile(
+                user_id,
+                &profile.bio,
+                profile.avatar_url.as_deref(),
+                profile.website.as_deref(),
+            )
+            .await?;
+
+        info!("Profile updated");
+
+        Ok(profile)
+    }
+
+    /// Replaces the display preferences of a user.
+    #[instrument(skip(self))]
+    pub async fn update_preferences(
+        &self,
+        user_id: i64,
+        preferences: PreferencesDto,
+    ) -> Result<PreferencesDto, AppError> {
+        self.user_repo
+            .update_preferences(
+                user_id,
+                preferences.theme.as_str(),
+                &preferences.language,
+                &preferences.timezone,
+                &preferences.locale,
+            )
+            .await?;
+
+        Ok(preferences)
+    }
+
+    /// Lists the unexpired sessions of a user, marking the current one.
+    #[instrument(skip(self))]
+    pub async fn list_sessions(
+        &self,
+        user_id: i64,
+        current_session: Option<&str>,
+    ) -> Result<SessionListResponse, AppError> {
+        let since = self.clock.now() - Duration::hours(JWT_EXPIRY_HOURS);
+        let sessions = self.user_repo.list_sessions(user_id, since).await?;
+
+        Ok(SessionListResponse {
+            sessions: sessions
+                .into_iter()
+                .map(|session| SessionDto {
+                    current: current_session == Some(session.id.as_str()),
+                    id: session.id,
+                    user_agent: session.user_agent,
+                    created_at: session.created_at,
+                    last_seen_at: session.last_seen_at,
+                })
+                .collect(),
+        })
+    }
+
+    /// Revokes one of the user's sessions; its token stops working immediately.
+    #[instrument(skip(self))]
+    pub async fn revoke_session(&self, user_id: i64, session_id: &str) -> Result<(), AppError> {
+        self.user_repo.delete_session(session_id, user_id).await?;
+
+        info!("Session revoked");
+
+        Ok(())
+    }
+}
+
+/// Converts stored settings to SettingsDto.
+fn settings_to_dto(settings: UserSettings) -> SettingsDto {
+    SettingsDto {
+        email: settings.email,
+        profile: ProfileDto {
+            bio: settings.bio,
+            avatar_url: settings.avatar_url,
+            website: settings.website,
+        },
+        preferences: PreferencesDto {
+            // Stored values are validated on write; fall back rather than fail
+            theme: settings.theme.parse().unwrap_or(Theme::System),
+            language: settings.language,
+            timezone: settings.timezone,
+            locale: settings.locale,
+        },
+    }
+}
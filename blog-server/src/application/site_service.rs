@@ -0,0 +1,164 @@
+//! Site service: site-wide settings managed by admins, such as the site
+//! title and the announcement banner.
+
+use std::sync::Arc;
+
+use blog_shared::{
+    AnnouncementDto, AnnouncementResponse, RegistrationMode, SetAnnouncementRequest,
+    SiteSettingsDto, UpdateSiteSettingsRequest,
+};
+use chrono::{DateTime, Utc};
+use tracing::{info, instrument, warn};
+
+use crate::constants::{SITE_SETTING_ANNOUNCEMENT, SITE_SETTING_GENERAL};
+use crate::data::SiteSettingsRepository;
+use crate::domain::{Announcement, AppError, SiteSetting, SiteSettings};
+use crate::infrastructure::clock::{Clock, SystemClock};
+
+/// Service for site-wide settings.
+///
+/// Callers must check that the acting user is an admin before changing
+/// settings.
+#[derive(Clone)]
+pub struct SiteService {
+    settings_repo: Arc<SiteSettingsRepository>,
+    clock: Arc<dyn Clock>,
+    /// Registration mode until an admin picks one.
+    default_registration_mode: RegistrationMode,
+}
+
+impl SiteService {
+    /// Creates a new SiteService.
+    pub fn new(settings_repo: Arc<SiteSettingsRepository>) -> Self {
+        Self {
+            settings_repo,
+            clock: Arc::new(SystemClock),
+            default_registration_mode: RegistrationMode::Open,
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Sets the registration mode in force until an admin changes the
+    /// settings (invite-only with `INVITE_ONLY=true`).
+    pub fn with_default_registration_mode(mut self, mode: RegistrationMode) -> Self {
+        self.default_registration_mode = mode;
+        self
+    }
+
+    /// Returns the site settings, with defaults for those never set.
+    #[instrument(skip(self))]
+    pub async fn settings(&self) -> Result<SiteSettingsDto, AppError> {
+        let (settings, updated_at) = self.stored_settings().await?;
+        Ok(settings.into_dto(updated_at))
+    }
+
+    /// Changes the given site settings, keeping the others.
+    #[instrument(skip(self, req))]
+    pub async fn update_settings(
+        &self,
+        req: UpdateSiteSettingsRequest,
+    ) -> Result<SiteSettingsDto, AppError> {
+        let (settings, _) = self.stored_settings().await?;
+        let settings = settings.apply(req)?;
+        let value =
+            serde_json::to_string(&settings).map_err(|e| AppError::Internal(e.to_string()))?;
+        let setting = self
+            .settings_repo
+            .set(SITE_SETTING_GENERAL, &value, self.clock.now())
+            .await?;
+
+        info!(registration_mode = %settings.registration_mode, "Site settings updated");
+
+        Ok(settings.into_dto(Some(setting.updated_at)))
+    }
+
+    /// Returns the announcement, unless none is set or it has expired.
+    #[instrument(skip(self))]
+    pub async fn announcement(&self) -> Result<AnnouncementResponse, AppError> {
+        let setting = self.settings_repo.find(SITE_SETTING_ANNOUNCEMENT).await?;
+        let announcement = setting
+            .and_then(announcement_to_dto)
+            .filter(|announcement| announcement.is_active(self.clock.now()));
+
+        Ok(AnnouncementResponse { announcement })
+    }
+
+    /// Sets the announcement, replacing the previous one.
+    #[instrument(skip(self, req))]
+    pub async fn set_announcement(
+        &self,
+        req: SetAnnouncementRequest,
+    ) -> Result<AnnouncementDto, AppError> {
+        let now = self.clock.now();
+        let announcement = Announcement::parse(req, now)?;
+        let value =
+            serde_json::to_string(&announcement).map_err(|e| AppError::Internal(e.to_string()))?;
+        let setting = self
+            .settings_repo
+            .set(SITE_SETTING_ANNOUNCEMENT, &value, now)
+            .await?;
+
+        info!(severity = %announcement.severity, "Announcement set");
+
+        Ok(AnnouncementDto {
+            text: announcement.text,
+            severity: announcement.severity,
+            expires_at: announcement.expires_at,
+            updated_at: setting.updated_at,
+        })
+    }
+
+    /// Removes the announcement, if any.
+    #[instrument(skip(self))]
+    pub async fn clear_announcement(&self) -> Result<(), AppError> {
+        self.settings_repo.delete(SITE_SETTING_ANNOUNCEMENT).await?;
+
+        info!("Announcement cleared");
+
+        Ok(())
+    }
+
+    /// Reads the stored settings and when they were set; an unreadable
+    /// value counts as never set.
+    async fn stored_settings(&self) -> Result<(SiteSettings, Option<DateTime<Utc>>), AppError> {
+        let defaults = SiteSettings {
+            registration_mode: self.default_registration_mode,
+            ..SiteSettings::default()
+        };
+        let Some(setting) = self.settings_repo.find(SITE_SETTING_GENERAL).await? else {
+            return Ok((defaults, None));
+        };
+
+        match serde_json::from_str(&setting.value) {
+            Ok(settings) => Ok((settings, Some(setting.updated_at))),
+            Err(e) => {
+                warn!(error = %e, "Ignoring unreadable site settings");
+                Ok((defaults, None))
+            }
+        }
+    }
+}
+
+/// Converts the stored announcement setting to a DTO; an unreadable value
+/// counts as no announcement.
+fn announcement_to_dto(setting: SiteSetting) -> Option<AnnouncementDto> {
+    let announcement: Announcement = match serde_json::from_str(&setting.value) {
+        Ok(announcement) => announcement,
+        Err(e) => {
+            warn!(error = %e, "Ignoring unreadable announcement setting");
+            return None;
+        }
+    };
+
+    Some(AnnouncementDto {
+        text: announcement.text,
+        severity: announcement.severity,
+        expires_at: announcement.expires_at,
+        updated_at: setting.updated_at,
+    })
+}
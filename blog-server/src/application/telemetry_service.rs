@@ -0,0 +1,89 @@
+//! Error telemetry: panics, 5xx responses, and client crashes forwarded to
+//! an error tracker.
+//!
+//! Reports are sent in the background so a slow or failing tracker never
+//! delays a response; delivery failures are only logged.
+
+use std::panic;
+use std::sync::Arc;
+
+use blog_shared::{ClientErrorReport, truncate};
+use tokio::runtime::Handle;
+use tracing::warn;
+
+use crate::constants::MAX_CLIENT_ERROR_MESSAGE_LEN;
+use crate::domain::{ErrorReport, ErrorReporter, ErrorSource, RequestContext};
+use crate::infrastructure::panic_guard::{self, panic_message};
+
+/// Service capturing errors for the configured [`ErrorReporter`], if any.
+#[derive(Clone, Default)]
+pub struct TelemetryService {
+    reporter: Option<Arc<dyn ErrorReporter>>,
+}
+
+impl TelemetryService {
+    /// Creates a TelemetryService that drops every report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends reports to `reporter`, e.g. a `SentryReporter`.
+    pub fn with_reporter(mut self, reporter: Arc<dyn ErrorReporter>) -> Self {
+        self.reporter = Some(reporter);
+        self
+    }
+
+    /// Whether reports go anywhere.
+    pub fn is_enabled(&self) -> bool {
+        self.reporter.is_some()
+    }
+
+    /// Sends a report in the background. Dropped when disabled or outside a
+    /// Tokio runtime.
+    pub fn capture(&self, report: ErrorReport) {
+        let Some(reporter) = &self.reporter else {
+            return;
+        };
+        let Ok(runtime) = Handle::try_current() else {
+            warn!(message = %report.message, "no runtime to report error from");
+            return;
+        };
+        let reporter = Arc::clone(reporter);
+        runtime.spawn(async move {
+            if let Err(e) = reporter.report(&report).await {
+                warn!(error = %e, source = %report.source, "failed to report error");
+            }
+        });
+    }
+
+    /// Forwards a crash reported by a client, with its message shortened.
+    pub fn capture_client_error(&self, report: ClientErrorReport, request: RequestContext) {
+        let message = truncate(&report.message, MAX_CLIENT_ERROR_MESSAGE_LEN);
+        self.capture(
+            ErrorReport::new(ErrorSource::Client, message)
+                .with_location(report.location)
+                .with_url(report.url)
+                .with_request(request),
+        );
+    }
+
+    /// Reports every panic in the process, then runs the previous panic hook.
+    /// Panics caught by `panic_guard::catch_panic` are left to its caller,
+    /// which reports them with their request. Does nothing when disabled.
+    pub fn install_panic_hook(&self) {
+        if !self.is_enabled() {
+            return;
+        }
+        let telemetry = self.clone();
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !panic_guard::is_catching() {
+                telemetry.capture(
+                    ErrorReport::new(ErrorSource::Panic, panic_message(info.payload()))
+                        .with_location(info.location().map(ToString::to_string)),
+                );
+            }
+            previous(info);
+        }));
+    }
+}
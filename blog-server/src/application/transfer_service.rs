@@ -0,0 +1,292 @@
+//! Transfer service: authors handing posts over to other users.
+
+use std::sync::Arc;
+
+use blog_shared::{PostTransferDto, TransferListResponse, TransferStatus};
+use tracing::{info, instrument, warn};
+
+use crate::data::{
+    AdminRepository, MessageRepository, PostRepository, TransferRepository, UserRepository,
+};
+use crate::domain::{AppError, AuditAction, DomainEvent, PostTransfer, policy};
+use crate::infrastructure::clock::{Clock, SystemClock};
+use crate::infrastructure::event_bus::EventBus;
+
+/// Service for post transfers.
+///
+/// Each step is announced to the other party as a private message, which
+/// shows up in their unread count.
+#[derive(Clone)]
+pub struct TransferService {
+    transfer_repo: Arc<TransferRepository>,
+    post_repo: Arc<PostRepository>,
+    user_repo: Arc<UserRepository>,
+    message_repo: Arc<MessageRepository>,
+    admin_repo: Arc<AdminRepository>,
+    events: Option<EventBus>,
+    clock: Arc<dyn Clock>,
+}
+
+impl TransferService {
+    /// Creates a new TransferService.
+    pub fn new(
+        transfer_repo: Arc<TransferRepository>,
+        post_repo: Arc<PostRepository>,
+        user_repo: Arc<UserRepository>,
+        message_repo: Arc<MessageRepository>,
+        admin_repo: Arc<AdminRepository>,
+    ) -> Self {
+        Self {
+            transfer_repo,
+            post_repo,
+            user_repo,
+            message_repo,
+            admin_repo,
+            events: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Publishes `DomainEvent::PostChanged` on `events` when a post changes
+    /// hands.
+    pub fn with_events(mut self, events: EventBus) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Offers post `post_id` of `user_id` to `username`. The post keeps its
+    /// author until the recipient accepts; a post has at most one pending
+    /// transfer.
+    #[instrument(skip(self), fields(post_id = post_id, user_id = user_id))]
+    pub async fn transfer_post(
+        &self,
+        post_id: i64,
+        user_id: i64,
+        username: &str,
+    ) -> Result<PostTransferDto, AppError> {
+        let post = self
+            .post_repo
+            .find_by_id(post_id)
+            .await?
+            .ok_or(AppError::PostNotFound)?;
+        policy::authorize(policy::can_transfer_post(user_id, &post))?;
+        let recipient = self
+            .user_repo
+            .find_by_username(username)
+            .await?
+            .ok_or(AppError::UserNotFound)?;
+        if recipient.id == user_id {
+            return Err(AppError::Validation(
+                "Cannot transfer a post to yourself".to_string(),
+            ));
+        }
+        if self.transfer_repo.has_pending(post_id).await? {
+            return Err(AppError::Validation(
+                "This post already has a pending transfer".to_string(),
+            ));
+        }
+
+        let id = self
+            .transfer_repo
+            .create(post_id, user_id, recipient.id, self.clock.now())
+            .await?;
+        let transfer = self.find(id).await?;
+
+        info!(
+            transfer_id = id,
+            to_user_id = recipient.id,
+            "Post transfer offered"
+        );
+
+        self.notify(
+            user_id,
+            recipient.id,
+            &format!(
+                "I would like to hand my post \"{}\" over to you. You can accept or decline it under your pending transfers.",
+                transfer.post_title
+            ),
+        )
+        .await;
+
+        Ok(transfer_to_dto(transfer))
+    }
+
+    /// Lists the pending transfers sent or received by `user_id`.
+    #[instrument(skip(self))]
+    pub async fn list_transfers(&self, user_id: i64) -> Result<TransferListResponse, AppError> {
+        let (incoming, outgoing): (Vec<_>, Vec<_>) = self
+            .transfer_repo
+            .list_pending(user_id)
+            .await?
+            .into_iter()
+            .partition(|transfer| transfer.to_user_id == user_id);
+
+        Ok(TransferListResponse {
+            incoming: incoming.into_iter().map(transfer_to_dto).collect(),
+            outgoing: outgoing.into_iter().map(transfer_to_dto).collect(),
+        })
+    }
+
+    /// Accepts a transfer offered to `user_id`, who becomes the post's
+    /// author. Recorded in the audit log.
+    #[instrument(skip(self), fields(transfer_id = id, user_id = user_id))]
+    pub async fn accept_transfer(
+        &self,
+        id: i64,
+        user_id: i64,
+    ) -> Result<PostTransferDto, AppError> {
+        let transfer = self.find_pending(id, user_id).await?;
+        policy::authorize(policy::can_answer_transfer(user_id, &transfer))?;
+
+        let now = self.clock.now();
+        self.transfer_repo.accept(&transfer, now).await?;
+        self.admin_repo
+            .log_action(
+                transfer.from_user_id,
+                AuditAction::PostTransfer,
+                transfer.to_user_id,
+                Some(&format!("Post {}", transfer.post_id)),
+                now,
+            )
+            .await?;
+
+        info!(post_id = transfer.post_id, "Post transfer accepted");
+        if let Some(events) = &self.events {
+            events.publish(DomainEvent::PostChanged {
+                post_id: transfer.post_id,
+            });
+        }
+
+        self.notify(
+            user_id,
+            transfer.from_user_id,
+            &format!(
+                "I accepted your post \"{}\"; it is mine now.",
+                transfer.post_title
+            ),
+        )
+        .await;
+
+        self.find(id).await.map(transfer_to_dto)
+    }
+
+    /// Declines a transfer offered to `user_id`.
+    #[instrument(skip(self), fields(transfer_id = id, user_id = user_id))]
+    pub async fn decline_transfer(
+        &self,
+        id: i64,
+        user_id: i64,
+    ) -> Result<PostTransferDto, AppError> {
+        let transfer = self.find_pending(id, user_id).await?;
+        policy::authorize(policy::can_answer_transfer(user_id, &transfer))?;
+
+        self.transfer_repo
+            .close(id, TransferStatus::Declined, self.clock.now())
+            .await?;
+
+        info!("Post transfer declined");
+
+        self.notify(
+            user_id,
+            transfer.from_user_id,
+            &format!(
+                "I declined your offer of the post \"{}\".",
+                transfer.post_title
+            ),
+        )
+        .await;
+
+        self.find(id).await.map(transfer_to_dto)
+    }
+
+    /// Withdraws a transfer sent by `user_id`.
+    #[instrument(skip(self), fields(transfer_id = id, user_id = user_id))]
+    pub async fn cancel_transfer(
+        &self,
+        id: i64,
+        user_id: i64,
+    ) -> Result<PostTransferDto, AppError> {
+        let transfer = self.find_pending(id, user_id).await?;
+        policy::authorize(policy::can_cancel_transfer(user_id, &transfer))?;
+
+        self.transfer_repo
+            .close(id, TransferStatus::Cancelled, self.clock.now())
+            .await?;
+
+        info!("Post transfer cancelled");
+
+        self.notify(
+            user_id,
+            transfer.to_user_id,
+            &format!(
+                "I withdrew my offer of the post \"{}\".",
+                transfer.post_title
+            ),
+        )
+        .await;
+
+        self.find(id).await.map(transfer_to_dto)
+    }
+
+    /// Finds a transfer by ID.
+    async fn find(&self, id: i64) -> Result<PostTransfer, AppError> {
+        self.transfer_repo
+            .find(id)
+            .await?
+            .ok_or(AppError::TransferNotFound)
+    }
+
+    /// Finds a pending transfer `user_id` takes part in; others' transfers
+    /// and closed ones are `TransferNotFound`.
+    async fn find_pending(&self, id: i64, user_id: i64) -> Result<PostTransfer, AppError> {
+        let transfer = self.find(id).await?;
+        if !transfer.involves(user_id) || transfer.status() != TransferStatus::Pending {
+            return Err(AppError::TransferNotFound);
+        }
+
+        Ok(transfer)
+    }
+
+    /// Sends `content` from `sender_id` to `recipient_id` as a private
+    /// message. A failed notice is logged and does not undo the transfer step.
+    async fn notify(&self, sender_id: i64, recipient_id: i64, content: &str) {
+        let sent = async {
+            let now = self.clock.now();
+            let conversation_id = self
+                .message_repo
+                .find_or_create_conversation(sender_id, recipient_id, now)
+                .await?;
+            self.message_repo
+                .create_message(conversation_id, sender_id, content, now)
+                .await
+        };
+        if let Err(e) = sent.await {
+            warn!(error = %e, recipient_id, "Failed to send transfer notice");
+        }
+    }
+}
+
+/// Converts a PostTransfer domain entity to PostTransferDto.
+fn transfer_to_dto(transfer: PostTransfer) -> PostTransferDto {
+    PostTransferDto {
+        status: transfer.status(),
+        id: transfer.id,
+        post_id: transfer.post_id,
+        post_public_id: transfer.post_public_id,
+        post_title: transfer.post_title,
+        from_user_id: transfer.from_user_id,
+        from_user_public_id: transfer.from_user_public_id,
+        from_username: transfer.from_username,
+        to_user_id: transfer.to_user_id,
+        to_user_public_id: transfer.to_user_public_id,
+        to_username: transfer.to_username,
+        created_at: transfer.created_at,
+        resolved_at: transfer.resolved_at,
+    }
+}
@@ -0,0 +1,210 @@
+//! Upload service: content-addressed file storage shared by all users.
+
+use std::sync::Arc;
+
+use blog_shared::UploadDto;
+use chrono::{DateTime, Duration};
+use tracing::{info, instrument, warn};
+
+use crate::constants::UPLOAD_URL_TTL_SECS;
+use crate::data::AttachmentRepository;
+use crate::domain::{AppError, Attachment, Upload, check_upload, content_hash};
+use crate::infrastructure::blob_store::BlobStore;
+use crate::infrastructure::clamav::{ClamAv, ScanVerdict};
+use crate::infrastructure::clock::{Clock, SystemClock};
+use crate::infrastructure::url_signing::{sign_upload, verify_upload};
+
+/// Signature of a URL to a private upload, from its query string.
+#[derive(Debug, Clone, Copy)]
+pub struct SignedAccess<'a> {
+    /// Unix time the URL stops working.
+    pub expires: i64,
+    pub signature: &'a str,
+}
+
+/// Service for storing and serving uploaded files.
+#[derive(Clone)]
+pub struct UploadService {
+    attachment_repo: Arc<AttachmentRepository>,
+    store: BlobStore,
+    public_url: String,
+    signing_key: String,
+    scanner: Option<ClamAv>,
+    clock: Arc<dyn Clock>,
+}
+
+impl UploadService {
+    /// Creates a new UploadService.
+    pub fn new(
+        attachment_repo: Arc<AttachmentRepository>,
+        store: BlobStore,
+        public_url: String,
+        signing_key: String,
+        scanner: Option<ClamAv>,
+    ) -> Self {
+        Self {
+            attachment_repo,
+            store,
+            public_url,
+            signing_key,
+            scanner,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Replaces the system clock, e.g. with a `MockClock` in tests.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Stores a file of an accepted type (checked by the handler) under the
+    /// SHA-256 hash of its content.
+    ///
+    /// The content must match the type and its size limit, and pass the
+    /// virus scan when one is configured, before anything is stored.
+    ///
+    /// Content uploaded before, by anyone, is not stored again; the existing
+    /// attachment is reused. Each user gets their own upload of it, with the
+    /// content type they sent and its own creation time, whose URL is
+    /// private or public as they last chose; changing that moves the upload
+    /// to a new URL and the old one stops resolving. Private uploads come
+    /// back with a signed URL valid for `UPLOAD_URL_TTL_SECS`.
+    #[instrument(skip(self, bytes), fields(size = bytes.len()))]
+    pub async fn upload(
+        &self,
+        user_id: i64,
+        content_type: &str,
+        bytes: &[u8],
+        private: bool,
+    ) -> Result<UploadDto, AppError> {
+        check_upload(content_type, bytes)?;
+        if let Some(scanner) = &self.scanner
+            && let ScanVerdict::Infected(signature) = scanner.scan(bytes).await?
+        {
+            warn!(user_id, %signature, "Infected upload rejected");
+            return Err(AppError::Validation(format!(
+                "File rejected by virus scan ({signature})"
+            )));
+        }
+
+        let hash = content_hash(bytes);
+        // The blob goes first so a stored attachment always has its file
+        self.store.put(&hash, bytes).await?;
+        let size = i64::try_from(bytes.len()).map_err(|e| AppError::Internal(e.to_string()))?;
+        let (attachment, upload) = self
+            .attachment_repo
+            .insert_or_get(
+                &hash,
+                content_type,
+                size,
+                user_id,
+                private,
+                self.clock.now(),
+            )
+            .await?;
+
+        info!(%hash, upload_id = %upload.id, private = upload.private, "File uploaded");
+
+        self.upload_to_dto(&attachment, &upload)
+    }
+
+    /// Returns an uploaded file with its attachment and upload.
+    ///
+    /// Private uploads need a valid, unexpired signature; a public upload of
+    /// the same content by someone else does not open them.
+    #[instrument(skip(self, access))]
+    pub async fn open(
+        &self,
+        id: &str,
+        access: Option<SignedAccess<'_>>,
+    ) -> Result<(Attachment, Upload, Vec<u8>), AppError> {
+        let upload = self
+            .attachment_repo
+            .find_upload(id)
+            .await?
+            .ok_or(AppError::UploadNotFound)?;
+        if upload.private {
+            let valid = access.is_some_and(|access| {
+                access.expires > self.clock.now().timestamp()
+                    && verify_upload(id, access.expires, access.signature, &self.signing_key)
+            });
+            if !valid {
+                return Err(AppError::Forbidden);
+            }
+        }
+        let hash = &upload.hash;
+        let attachment = self
+            .attachment_repo
+            .find(hash)
+            .await?
+            .ok_or(AppError::UploadNotFound)?;
+        let Some(bytes) = self.store.get(hash).await? else {
+            warn!(%hash, "Attachment without a stored file");
+            return Err(AppError::UploadNotFound);
+        };
+
+        Ok((attachment, upload, bytes))
+    }
+
+    /// Returns one of the user's uploads, with a freshly signed URL when it
+    /// is private.
+    ///
+    /// Other users' uploads are reported as not found.
+    #[instrument(skip(self))]
+    pub async fn get(&self, user_id: i64, id: &str) -> Result<UploadDto, AppError> {
+        let upload = self
+            .attachment_repo
+            .find_upload(id)
+            .await?
+            .filter(|upload| upload.uploaded_by == Some(user_id))
+            .ok_or(AppError::UploadNotFound)?;
+        let attachment = self
+            .attachment_repo
+            .find(&upload.hash)
+            .await?
+            .ok_or(AppError::UploadNotFound)?;
+
+        self.upload_to_dto(&attachment, &upload)
+    }
+
+    /// Returns the seconds left until a signed URL expires (0 once expired).
+    pub fn seconds_left(&self, access: SignedAccess<'_>) -> u32 {
+        u32::try_from(access.expires - self.clock.now().timestamp()).unwrap_or(0)
+    }
+
+    /// Converts an upload of an Attachment to UploadDto, signing the URL of
+    /// private uploads.
+    ///
+    /// The content type and creation time are the upload's, not the shared
+    /// attachment's.
+    fn upload_to_dto(
+        &self,
+        attachment: &Attachment,
+        upload: &Upload,
+    ) -> Result<UploadDto, AppError> {
+        let mut url = format!("{}/uploads/{}", self.public_url, upload.id);
+        let mut expires_at = None;
+        if upload.private {
+            let expires = self.clock.now() + Duration::seconds(UPLOAD_URL_TTL_SECS);
+            let signature = sign_upload(&upload.id, expires.timestamp(), &self.signing_key)?;
+            url = format!(
+                "{url}?expires={}&signature={signature}",
+                expires.timestamp()
+            );
+            // Signed expiry times have one-second precision
+            expires_at = DateTime::from_timestamp(expires.timestamp(), 0);
+        }
+
+        Ok(UploadDto {
+            id: upload.id.clone(),
+            hash: attachment.hash.clone(),
+            url,
+            content_type: upload.content_type.clone(),
+            size: attachment.size,
+            private: upload.private,
+            expires_at,
+            created_at: upload.created_at,
+        })
+    }
+}
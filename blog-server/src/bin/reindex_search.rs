@@ -0,0 +1,50 @@
+//! Rebuilds the external search index from the posts in the database.
+//!
+//! Run after first setting `SEARCH_BACKEND=meilisearch`, after restoring
+//! the search server or the database, or when the server logs that its
+//! search indexer fell behind. Safe to run while the server is up.
+
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use blog_server::application::SearchIndexService;
+use blog_server::data::PostRepository;
+use blog_server::infrastructure::config::{Config, SearchBackend};
+use blog_server::infrastructure::database;
+use blog_server::infrastructure::event_bus::EventBus;
+use blog_server::infrastructure::http_fetcher::HttpFetcher;
+use blog_server::infrastructure::meilisearch::MeiliSearchIndex;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match reindex().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Feeds every post not in the trash to the configured search server.
+async fn reindex() -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::from_env()?;
+    let SearchBackend::Meilisearch { url, api_key } = config.search_backend else {
+        return Err("SEARCH_BACKEND=sqlite is kept up to date by the database".into());
+    };
+
+    let pool = database::create_pool(&config.database_url).await?;
+    database::run_migrations(&pool).await?;
+    let index = MeiliSearchIndex::new(HttpFetcher::new()?, url, api_key);
+    index.configure().await?;
+    let indexed = SearchIndexService::new(
+        Arc::new(index),
+        Arc::new(PostRepository::new(pool)),
+        EventBus::new(),
+    )
+    .reindex()
+    .await?;
+
+    println!("Indexed {indexed} posts");
+    Ok(())
+}
@@ -0,0 +1,57 @@
+//! Re-encrypts post content under the active content key.
+//!
+//! Run after putting a new key first in `CONTENT_ENCRYPTION_KEYS` (keeping
+//! the old ones listed after it), or after setting keys on a database with
+//! plaintext posts. Once it reports completion, retired keys can be removed.
+//!
+//! `rotate_content_keys --generate-key` prints a fresh random key instead.
+
+use std::env;
+use std::process::ExitCode;
+
+use blog_server::data::PostRepository;
+use blog_server::infrastructure::config::Config;
+use blog_server::infrastructure::content_cipher::ContentCipher;
+use blog_server::infrastructure::database;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let result = if env::args().any(|arg| arg == "--generate-key") {
+        generate_key()
+    } else {
+        rotate().await
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Prints a fresh random key.
+fn generate_key() -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", ContentCipher::generate_key()?);
+    Ok(())
+}
+
+/// Re-seals every post and revision not yet under the active key.
+async fn rotate() -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::from_env()?;
+    let cipher = config
+        .content_cipher
+        .ok_or("CONTENT_ENCRYPTION_KEYS must be set")?;
+    let key_id = cipher.active_key_id().to_string();
+
+    let pool = database::create_pool(&config.database_url).await?;
+    database::run_migrations(&pool).await?;
+    let resealed = PostRepository::new(pool)
+        .with_cipher(cipher)
+        .reseal_all()
+        .await?;
+
+    println!("Re-encrypted {resealed} posts and revisions with key `{key_id}`");
+    Ok(())
+}
@@ -1,7 +1,55 @@
 //! Data layer: repositories for database operations.
 
+mod account_repository;
+mod activitypub_repository;
+mod admin_repository;
+mod analytics_repository;
+mod attachment_repository;
+mod comment_repository;
+mod crosspost_repository;
+mod email_change_repository;
+mod experiment_repository;
+mod feature_flag_repository;
+mod fts_search_index;
+mod invite_repository;
+mod link_check_repository;
+mod link_preview_repository;
+mod message_repository;
+mod moderation_repository;
+mod newsletter_repository;
+mod notification_repository;
+mod org_repository;
 mod post_repository;
+mod recovery_code_repository;
+mod retention_repository;
+mod service_account_repository;
+mod site_settings_repository;
+mod transfer_repository;
 mod user_repository;
 
+pub use account_repository::AccountRepository;
+pub use activitypub_repository::ActivityPubRepository;
+pub use admin_repository::AdminRepository;
+pub use analytics_repository::AnalyticsRepository;
+pub use attachment_repository::AttachmentRepository;
+pub use comment_repository::CommentRepository;
+pub use crosspost_repository::CrosspostRepository;
+pub use email_change_repository::EmailChangeRepository;
+pub use experiment_repository::ExperimentRepository;
+pub use feature_flag_repository::FeatureFlagRepository;
+pub use fts_search_index::FtsSearchIndex;
+pub use invite_repository::InviteRepository;
+pub use link_check_repository::LinkCheckRepository;
+pub use link_preview_repository::LinkPreviewRepository;
+pub use message_repository::MessageRepository;
+pub use moderation_repository::ModerationRepository;
+pub use newsletter_repository::NewsletterRepository;
+pub use notification_repository::NotificationRepository;
+pub use org_repository::OrgRepository;
 pub use post_repository::PostRepository;
+pub use recovery_code_repository::RecoveryCodeRepository;
+pub use retention_repository::RetentionRepository;
+pub use service_account_repository::ServiceAccountRepository;
+pub use site_settings_repository::SiteSettingsRepository;
+pub use transfer_repository::TransferRepository;
 pub use user_repository::UserRepository;
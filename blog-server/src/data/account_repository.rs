@@ -0,0 +1,40 @@
+//! Account repository: cross-table queries about a single user.
+
+use sqlx::SqlitePool;
+
+use crate::domain::{AppError, UserUsage};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for per-user account queries.
+#[derive(Clone)]
+pub struct AccountRepository {
+    pool: SqlitePool,
+}
+
+impl AccountRepository {
+    /// Creates a new AccountRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Counts a user's posts and the uploads recorded under their name.
+    pub async fn usage(&self, user_id: i64) -> Result<UserUsage, AppError> {
+        let query = sqlx::query_as!(
+            UserUsage,
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM posts WHERE author_id = ? AND deleted_at IS NULL) as "posts!: i64",
+                (SELECT COUNT(*) FROM uploads WHERE uploaded_by = ?) as "uploads!: i64",
+                (SELECT COALESCE(SUM(a.size), 0) FROM uploads u
+                 JOIN attachments a ON a.hash = u.hash
+                 WHERE u.uploaded_by = ?) as "upload_bytes!: i64"
+            "#,
+            user_id,
+            user_id,
+            user_id
+        );
+        let usage = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(usage)
+    }
+}
@@ -0,0 +1,256 @@
+//! ActivityPub repository: author keys, remote followers, and deliveries.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::domain::{ActorKey, AppError, PendingDelivery, Post, PublicProfile};
+use crate::infrastructure::content_cipher::{self, ContentCipher};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for ActivityPub database operations.
+///
+/// Shadow-banned authors are not federated: they have no actor, and their
+/// posts are neither listed nor delivered.
+#[derive(Clone)]
+pub struct ActivityPubRepository {
+    pool: SqlitePool,
+    cipher: Option<ContentCipher>,
+}
+
+impl ActivityPubRepository {
+    /// Creates a new ActivityPubRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool, cipher: None }
+    }
+
+    /// Decrypts post content encrypted at rest with `cipher`.
+    pub fn with_cipher(mut self, cipher: ContentCipher) -> Self {
+        self.cipher = Some(cipher);
+        self
+    }
+
+    /// Finds the public profile of a federated author by username.
+    pub async fn find_actor(&self, username: &str) -> Result<Option<PublicProfile>, AppError> {
+        let query = sqlx::query_as!(
+            PublicProfile,
+            r#"
+            SELECT id as "id!", public_id, username, bio, avatar_url
+            FROM users
+            WHERE username = ? AND shadow_banned_at IS NULL
+            "#,
+            username
+        );
+        let profile = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(profile)
+    }
+
+    /// Finds an author's key pair.
+    pub async fn find_key(&self, user_id: i64) -> Result<Option<ActorKey>, AppError> {
+        let query = sqlx::query_as!(
+            ActorKey,
+            r#"
+            SELECT user_id as "user_id!", private_key_pem, public_key_pem
+            FROM actor_keys
+            WHERE user_id = ?
+            "#,
+            user_id
+        );
+        let key = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(key)
+    }
+
+    /// Stores an author's key pair unless one exists, returning the stored pair.
+    pub async fn insert_key(
+        &self,
+        user_id: i64,
+        private_key_pem: &str,
+        public_key_pem: &str,
+        now: DateTime<Utc>,
+    ) -> Result<ActorKey, AppError> {
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO actor_keys (user_id, private_key_pem, public_key_pem, created_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (user_id) DO NOTHING
+            "#,
+            user_id,
+            private_key_pem,
+            public_key_pem,
+            now
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        self.find_key(user_id)
+            .await?
+            .ok_or_else(|| AppError::Internal("actor key not stored".to_string()))
+    }
+
+    /// Adds (or updates the inbox of) a remote follower.
+    pub async fn add_follower(
+        &self,
+        user_id: i64,
+        actor_id: &str,
+        inbox_url: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO ap_followers (user_id, actor_id, inbox_url, created_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (user_id, actor_id) DO UPDATE SET inbox_url = excluded.inbox_url
+            "#,
+            user_id,
+            actor_id,
+            inbox_url,
+            now
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Removes a remote follower. Returns false if they were not following.
+    pub async fn remove_follower(&self, user_id: i64, actor_id: &str) -> Result<bool, AppError> {
+        let query = sqlx::query!(
+            "DELETE FROM ap_followers WHERE user_id = ? AND actor_id = ?",
+            user_id,
+            actor_id
+        );
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Counts an author's remote followers.
+    pub async fn count_followers(&self, user_id: i64) -> Result<i64, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count: i64" FROM ap_followers WHERE user_id = ?"#,
+            user_id
+        );
+        let count = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(count)
+    }
+
+    /// Lists an author's newest posts.
+    pub async fn list_posts(&self, author_id: i64, limit: i64) -> Result<Vec<Post>, AppError> {
+        let query = sqlx::query_as!(
+            Post,
+            r#"
+            SELECT id as "id!", public_id, title, content, author_id, lang, translation_group_id,
+                   canonical_url, original_source, metadata, status, created_at as "created_at: _", updated_at as "updated_at: _"
+            FROM posts
+            WHERE author_id = ? AND status = 'published' AND deleted_at IS NULL
+            ORDER BY created_at DESC, id DESC
+            LIMIT ?
+            "#,
+            author_id,
+            limit
+        );
+        let mut posts = timed_query(query.fetch_all(&self.pool)).await?;
+        for post in &mut posts {
+            content_cipher::open(self.cipher.as_ref(), &mut post.content)?;
+        }
+
+        Ok(posts)
+    }
+
+    /// Counts an author's posts.
+    pub async fn count_posts(&self, author_id: i64) -> Result<i64, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count: i64" FROM posts WHERE author_id = ? AND status = 'published' AND deleted_at IS NULL"#,
+            author_id
+        );
+        let count = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(count)
+    }
+
+    /// Lists posts to deliver: those published since a follower started
+    /// following, not yet delivered to them, and whose retry time has come.
+    pub async fn list_due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        max_attempts: i64,
+        limit: i64,
+    ) -> Result<Vec<PendingDelivery>, AppError> {
+        let query = sqlx::query_as!(
+            PendingDelivery,
+            r#"
+            SELECT p.id as "post_id!", p.public_id as post_public_id, f.id as "follower_id!", f.inbox_url,
+                   u.id as "author_id!", u.username as author_username, p.title, p.content,
+                   p.lang, p.translation_group_id, p.canonical_url, p.original_source, p.metadata, p.status,
+                   COALESCE(d.attempts, 0) as "attempts!: i64",
+                   p.created_at as "created_at: _", p.updated_at as "updated_at: _"
+            FROM ap_followers f
+            JOIN posts p ON p.author_id = f.user_id AND p.created_at >= f.created_at
+            JOIN users u ON u.id = p.author_id
+            LEFT JOIN ap_deliveries d ON d.post_id = p.id AND d.follower_id = f.id
+            WHERE p.deleted_at IS NULL AND u.shadow_banned_at IS NULL AND p.status = 'published'
+              AND (d.post_id IS NULL
+                   OR (d.delivered_at IS NULL AND d.attempts < ?1 AND d.next_attempt_at <= ?2))
+            ORDER BY p.id, f.id
+            LIMIT ?3
+            "#,
+            max_attempts,
+            now,
+            limit
+        );
+        let mut deliveries = timed_query(query.fetch_all(&self.pool)).await?;
+        for delivery in &mut deliveries {
+            content_cipher::open(self.cipher.as_ref(), &mut delivery.content)?;
+        }
+
+        Ok(deliveries)
+    }
+
+    /// Records a successful delivery.
+    pub async fn mark_delivered(
+        &self,
+        post_id: i64,
+        follower_id: i64,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO ap_deliveries (post_id, follower_id, attempts, next_attempt_at, delivered_at)
+            VALUES (?1, ?2, 1, ?3, ?3)
+            ON CONFLICT (post_id, follower_id) DO UPDATE
+            SET attempts = attempts + 1, delivered_at = ?3, last_error = NULL
+            "#,
+            post_id,
+            follower_id,
+            now
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Records a failed delivery to be retried at `next_attempt_at`.
+    pub async fn mark_failed(
+        &self,
+        post_id: i64,
+        follower_id: i64,
+        error: &str,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO ap_deliveries (post_id, follower_id, attempts, next_attempt_at, last_error)
+            VALUES (?1, ?2, 1, ?3, ?4)
+            ON CONFLICT (post_id, follower_id) DO UPDATE
+            SET attempts = attempts + 1, next_attempt_at = ?3, last_error = ?4
+            "#,
+            post_id,
+            follower_id,
+            next_attempt_at,
+            error
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+}
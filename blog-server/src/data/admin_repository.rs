@@ -0,0 +1,150 @@
+//! Admin repository: cross-table queries for the admin dashboard.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::domain::{AppError, AuditAction, AuditEntry, SiteStats, UserSummary};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for admin dashboard queries.
+#[derive(Clone)]
+pub struct AdminRepository {
+    pool: SqlitePool,
+}
+
+impl AdminRepository {
+    /// Creates a new AdminRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Counts users, content, and sessions seen since `active_since`.
+    pub async fn stats(&self, active_since: DateTime<Utc>) -> Result<SiteStats, AppError> {
+        let query = sqlx::query_as!(
+            SiteStats,
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM users) as "users!: i64",
+                (SELECT COUNT(*) FROM users WHERE suspended_at IS NOT NULL) as "suspended_users!: i64",
+                (SELECT COUNT(*) FROM posts WHERE deleted_at IS NULL) as "posts!: i64",
+                (SELECT COUNT(*) FROM comments) as "comments!: i64",
+                (SELECT COUNT(DISTINCT tag_id) FROM post_tags) as "tags!: i64",
+                (SELECT COUNT(*) FROM sessions WHERE last_seen_at >= ?) as "active_sessions!: i64"
+            "#,
+            active_since
+        );
+        let stats = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(stats)
+    }
+
+    /// Lists users with their post counts, oldest account first.
+    pub async fn list_users(&self, limit: i64, offset: i64) -> Result<Vec<UserSummary>, AppError> {
+        let query = sqlx::query_as!(
+            UserSummary,
+            r#"
+            SELECT u.id as "id!", u.username, u.email, u.role,
+                   u.suspended_at as "suspended_at: _",
+                   u.shadow_banned_at as "shadow_banned_at: _",
+                   (SELECT COUNT(*) FROM posts p WHERE p.author_id = u.id AND p.deleted_at IS NULL) as "post_count!: i64",
+                   u.created_at as "created_at: _"
+            FROM users u
+            ORDER BY u.id
+            LIMIT ? OFFSET ?
+            "#,
+            limit,
+            offset
+        );
+        let users = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(users)
+    }
+
+    /// Finds the summary row of a single user.
+    pub async fn find_user(&self, id: i64) -> Result<Option<UserSummary>, AppError> {
+        let query = sqlx::query_as!(
+            UserSummary,
+            r#"
+            SELECT u.id as "id!", u.username, u.email, u.role,
+                   u.suspended_at as "suspended_at: _",
+                   u.shadow_banned_at as "shadow_banned_at: _",
+                   (SELECT COUNT(*) FROM posts p WHERE p.author_id = u.id AND p.deleted_at IS NULL) as "post_count!: i64",
+                   u.created_at as "created_at: _"
+            FROM users u
+            WHERE u.id = ?
+            "#,
+            id
+        );
+        let user = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(user)
+    }
+
+    /// Counts all users.
+    pub async fn count_users(&self) -> Result<i64, AppError> {
+        let query = sqlx::query_scalar!(r#"SELECT COUNT(*) as "count: i64" FROM users"#);
+        let result = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(result)
+    }
+
+    /// Records a moderator action in the audit log.
+    pub async fn log_action(
+        &self,
+        actor_id: i64,
+        action: AuditAction,
+        target_user_id: i64,
+        reason: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let action = action.as_str();
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO audit_log (actor_id, action, target_user_id, reason, created_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+            actor_id,
+            action,
+            target_user_id,
+            reason,
+            now
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Lists audit log entries, newest first.
+    pub async fn list_audit_log(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<AuditEntry>, AppError> {
+        let query = sqlx::query_as!(
+            AuditEntry,
+            r#"
+            SELECT a.id as "id!", a.actor_id, actor.username as "actor_username?", a.action,
+                   a.target_user_id, target.username as "target_username?", a.reason,
+                   a.created_at as "created_at: _"
+            FROM audit_log a
+            LEFT JOIN users actor ON actor.id = a.actor_id
+            LEFT JOIN users target ON target.id = a.target_user_id
+            ORDER BY a.created_at DESC, a.id DESC
+            LIMIT ? OFFSET ?
+            "#,
+            limit,
+            offset
+        );
+        let entries = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(entries)
+    }
+
+    /// Counts audit log entries.
+    pub async fn count_audit_log(&self) -> Result<i64, AppError> {
+        let query = sqlx::query_scalar!(r#"SELECT COUNT(*) as "count: i64" FROM audit_log"#);
+        let result = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,120 @@
+//! Analytics repository: stores client events and rolls them up into daily
+//! stats.
+
+use chrono::NaiveDate;
+use sqlx::SqlitePool;
+
+use crate::domain::{AnalyticsEvent, AppError, DailyStats, PageStats};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for analytics events and daily stats.
+#[derive(Clone)]
+pub struct AnalyticsRepository {
+    pool: SqlitePool,
+}
+
+impl AnalyticsRepository {
+    /// Creates a new AnalyticsRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Stores events that happened on `day`, each standing for `weight`
+    /// events after sampling.
+    pub async fn insert_events(
+        &self,
+        events: &[AnalyticsEvent],
+        weight: f64,
+        day: NaiveDate,
+    ) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        for event in events {
+            let kind = event.kind.as_str();
+            let query = sqlx::query!(
+                "INSERT INTO analytics_events (kind, path, weight, day) VALUES (?, ?, ?, ?)",
+                kind,
+                event.path,
+                weight,
+                day
+            );
+            timed_query(query.execute(&mut *tx)).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Adds the events of days before `day` to the daily stats and removes
+    /// them. Returns how many events were rolled up.
+    pub async fn rollup_before(&self, day: NaiveDate) -> Result<u64, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO daily_stats (day, kind, path, count)
+            SELECT day, kind, path, CAST(ROUND(SUM(weight)) AS INTEGER)
+            FROM analytics_events
+            WHERE day < ?
+            GROUP BY day, kind, path
+            ON CONFLICT (day, kind, path) DO UPDATE SET count = count + excluded.count
+            "#,
+            day
+        );
+        timed_query(query.execute(&mut *tx)).await?;
+
+        let query = sqlx::query!("DELETE FROM analytics_events WHERE day < ?", day);
+        let result = timed_query(query.execute(&mut *tx)).await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Lists site-wide totals of the days since `since`, oldest first.
+    pub async fn daily(&self, since: NaiveDate) -> Result<Vec<DailyStats>, AppError> {
+        let query = sqlx::query_as!(
+            DailyStats,
+            r#"
+            SELECT day as "day: NaiveDate",
+                   SUM(CASE WHEN kind = 'page_view' THEN count ELSE 0 END) as "page_views!: i64",
+                   SUM(CASE WHEN kind = 'read_complete' THEN count ELSE 0 END) as "read_completions!: i64"
+            FROM daily_stats
+            WHERE day >= ?
+            GROUP BY day
+            ORDER BY day
+            "#,
+            since
+        );
+        let days = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(days)
+    }
+
+    /// Lists the most viewed pages since `since`.
+    pub async fn top_pages(
+        &self,
+        since: NaiveDate,
+        limit: i64,
+    ) -> Result<Vec<PageStats>, AppError> {
+        let query = sqlx::query_as!(
+            PageStats,
+            r#"
+            SELECT path,
+                   SUM(CASE WHEN kind = 'page_view' THEN count ELSE 0 END) as "page_views!: i64",
+                   SUM(CASE WHEN kind = 'read_complete' THEN count ELSE 0 END) as "read_completions!: i64"
+            FROM daily_stats
+            WHERE day >= ?
+            GROUP BY path
+            ORDER BY 2 DESC, path
+            LIMIT ?
+            "#,
+            since,
+            limit
+        );
+        let pages = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(pages)
+    }
+}
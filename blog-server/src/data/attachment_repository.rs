@@ -0,0 +1,124 @@
+//! Attachment repository for content-addressed uploads.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::domain::{AppError, Attachment, Upload, new_public_id};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for attachment database operations.
+#[derive(Clone)]
+pub struct AttachmentRepository {
+    pool: SqlitePool,
+}
+
+impl AttachmentRepository {
+    /// Creates a new AttachmentRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Finds an attachment by content hash.
+    pub async fn find(&self, hash: &str) -> Result<Option<Attachment>, AppError> {
+        let query = sqlx::query_as!(
+            Attachment,
+            r#"
+            SELECT hash as "hash!", content_type, size, uploaded_by,
+                   created_at as "created_at: _"
+            FROM attachments
+            WHERE hash = ?
+            "#,
+            hash
+        );
+        let attachment = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(attachment)
+    }
+
+    /// Finds an upload by ID.
+    pub async fn find_upload(&self, id: &str) -> Result<Option<Upload>, AppError> {
+        let query = sqlx::query_as!(
+            Upload,
+            r#"
+            SELECT id as "id!", hash, content_type, uploaded_by,
+                   private as "private: bool", created_at as "created_at: _"
+            FROM uploads
+            WHERE id = ?
+            "#,
+            id
+        );
+        let upload = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(upload)
+    }
+
+    /// Records an upload, returning the existing attachment when the same
+    /// content was uploaded before, along with the user's upload of it.
+    ///
+    /// A user uploading the same content again keeps their upload (and URL)
+    /// while its private flag stays the same. Changing the flag gives the
+    /// upload a new ID and retires the old one, since a public URL may be
+    /// cached as immutable. Other users' uploads of it are untouched.
+    pub async fn insert_or_get(
+        &self,
+        hash: &str,
+        content_type: &str,
+        size: i64,
+        uploaded_by: i64,
+        private: bool,
+        now: DateTime<Utc>,
+    ) -> Result<(Attachment, Upload), AppError> {
+        let id = new_public_id();
+        let mut tx = self.pool.begin().await?;
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO attachments (hash, content_type, size, uploaded_by, created_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT (hash) DO NOTHING
+            "#,
+            hash,
+            content_type,
+            size,
+            uploaded_by,
+            now
+        );
+        timed_query(query.execute(&mut *tx)).await?;
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO uploads (id, hash, content_type, uploaded_by, private, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT (hash, uploaded_by) DO UPDATE
+                SET id = excluded.id, content_type = excluded.content_type,
+                    private = excluded.private, created_at = excluded.created_at
+                WHERE uploads.private != excluded.private
+            "#,
+            id,
+            hash,
+            content_type,
+            uploaded_by,
+            private,
+            now
+        );
+        timed_query(query.execute(&mut *tx)).await?;
+        let query = sqlx::query_as!(
+            Upload,
+            r#"
+            SELECT id as "id!", hash, content_type, uploaded_by,
+                   private as "private: bool", created_at as "created_at: _"
+            FROM uploads
+            WHERE hash = ? AND uploaded_by = ?
+            "#,
+            hash,
+            uploaded_by
+        );
+        let upload = timed_query(query.fetch_one(&mut *tx)).await?;
+        tx.commit().await?;
+
+        let attachment = self
+            .find(hash)
+            .await?
+            .ok_or_else(|| AppError::Internal("attachment not stored".to_string()))?;
+
+        Ok((attachment, upload))
+    }
+}
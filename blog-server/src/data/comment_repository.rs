@@ -0,0 +1,236 @@
+//! Comment repository for database operations.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::domain::{AppError, Comment, PageCursor};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for comment-related database operations.
+#[derive(Clone)]
+pub struct CommentRepository {
+    pool: SqlitePool,
+}
+
+impl CommentRepository {
+    /// Creates a new CommentRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Creates a new comment on a post.
+    pub async fn create(
+        &self,
+        post_id: i64,
+        author_id: i64,
+        content: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Comment, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            INSERT INTO comments (post_id, author_id, content, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            RETURNING id as "id!"
+            "#,
+            post_id,
+            author_id,
+            content,
+            now,
+            now
+        );
+        let id = timed_query(query.fetch_one(&self.pool)).await?;
+
+        self.find_by_id(id).await?.ok_or(AppError::CommentNotFound)
+    }
+
+    /// Returns true if the comment's author is shadow-banned, hiding it from
+    /// everyone else.
+    pub async fn is_hidden(&self, id: i64) -> Result<bool, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            SELECT u.shadow_banned_at IS NOT NULL as "hidden!: bool"
+            FROM comments c
+            JOIN users u ON u.id = c.author_id
+            WHERE c.id = ?
+            "#,
+            id
+        );
+        let hidden = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(hidden.unwrap_or(false))
+    }
+
+    /// Finds a comment by ID.
+    pub async fn find_by_id(&self, id: i64) -> Result<Option<Comment>, AppError> {
+        let query = sqlx::query_as!(
+            Comment,
+            r#"
+            SELECT c.id as "id!", c.post_id, c.author_id, p.public_id as post_public_id,
+                   u.public_id as author_public_id, u.username as author_username, c.content,
+                   c.created_at as "created_at: _", c.updated_at as "updated_at: _"
+            FROM comments c
+            JOIN posts p ON p.id = c.post_id
+            JOIN users u ON u.id = c.author_id
+            WHERE c.id = ?
+            "#,
+            id
+        );
+        let comment = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(comment)
+    }
+
+    /// Lists comments on a post visible to `viewer_id` with pagination, oldest first.
+    ///
+    /// Comments of shadow-banned users are only visible to the users themselves.
+    pub async fn list_by_post(
+        &self,
+        post_id: i64,
+        limit: i64,
+        offset: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<Vec<Comment>, AppError> {
+        let query = sqlx::query_as!(
+            Comment,
+            r#"
+            SELECT c.id as "id!", c.post_id, c.author_id, p.public_id as post_public_id,
+                   u.public_id as author_public_id, u.username as author_username, c.content,
+                   c.created_at as "created_at: _", c.updated_at as "updated_at: _"
+            FROM comments c
+            JOIN posts p ON p.id = c.post_id
+            JOIN users u ON u.id = c.author_id
+            WHERE c.post_id = ? AND (u.shadow_banned_at IS NULL OR c.author_id = ?)
+            ORDER BY c.created_at ASC, c.id ASC
+            LIMIT ? OFFSET ?
+            "#,
+            post_id,
+            viewer_id,
+            limit,
+            offset
+        );
+        let comments = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(comments)
+    }
+
+    /// Lists up to `limit` comments on a post visible to `viewer_id` that
+    /// come after `after`, oldest first; `None` starts at the first comment.
+    pub async fn list_by_post_after(
+        &self,
+        post_id: i64,
+        limit: i64,
+        after: Option<PageCursor>,
+        viewer_id: Option<i64>,
+    ) -> Result<Vec<Comment>, AppError> {
+        let after_created_at = after.map(|cursor| cursor.created_at);
+        let after_id = after.map(|cursor| cursor.id);
+        let query = sqlx::query_as!(
+            Comment,
+            r#"
+            SELECT c.id as "id!", c.post_id, c.author_id, p.public_id as post_public_id,
+                   u.public_id as author_public_id, u.username as author_username, c.content,
+                   c.created_at as "created_at: _", c.updated_at as "updated_at: _"
+            FROM comments c
+            JOIN posts p ON p.id = c.post_id
+            JOIN users u ON u.id = c.author_id
+            WHERE c.post_id = ?1 AND (u.shadow_banned_at IS NULL OR c.author_id = ?2)
+              AND (?3 IS NULL OR c.created_at > ?3 OR (c.created_at = ?3 AND c.id > ?4))
+            ORDER BY c.created_at ASC, c.id ASC
+            LIMIT ?5
+            "#,
+            post_id,
+            viewer_id,
+            after_created_at,
+            after_id,
+            limit
+        );
+        let comments = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(comments)
+    }
+
+    /// Counts comments on a post visible to `viewer_id`.
+    pub async fn count_by_post(
+        &self,
+        post_id: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<i64, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count: i64"
+            FROM comments c
+            JOIN users u ON u.id = c.author_id
+            WHERE c.post_id = ? AND (u.shadow_banned_at IS NULL OR c.author_id = ?)
+            "#,
+            post_id,
+            viewer_id
+        );
+        let result = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(result)
+    }
+
+    /// Lists comments across all posts, newest first (moderation queue).
+    pub async fn list_recent(&self, limit: i64, offset: i64) -> Result<Vec<Comment>, AppError> {
+        let query = sqlx::query_as!(
+            Comment,
+            r#"
+            SELECT c.id as "id!", c.post_id, c.author_id, p.public_id as post_public_id,
+                   u.public_id as author_public_id, u.username as author_username, c.content,
+                   c.created_at as "created_at: _", c.updated_at as "updated_at: _"
+            FROM comments c
+            JOIN posts p ON p.id = c.post_id
+            JOIN users u ON u.id = c.author_id
+            ORDER BY c.created_at DESC, c.id DESC
+            LIMIT ? OFFSET ?
+            "#,
+            limit,
+            offset
+        );
+        let comments = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(comments)
+    }
+
+    /// Counts all comments.
+    pub async fn count_all(&self) -> Result<i64, AppError> {
+        let query = sqlx::query_scalar!(r#"SELECT COUNT(*) as "count: i64" FROM comments"#);
+        let result = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(result)
+    }
+
+    /// Updates a comment's content.
+    pub async fn update(
+        &self,
+        id: i64,
+        content: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Comment, AppError> {
+        let query = sqlx::query!(
+            "UPDATE comments SET content = ?, updated_at = ? WHERE id = ?",
+            content,
+            now,
+            id
+        );
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::CommentNotFound);
+        }
+
+        self.find_by_id(id).await?.ok_or(AppError::CommentNotFound)
+    }
+
+    /// Deletes a comment by ID.
+    pub async fn delete(&self, id: i64) -> Result<(), AppError> {
+        let query = sqlx::query!("DELETE FROM comments WHERE id = ?", id);
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::CommentNotFound);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,228 @@
+//! Cross-post repository: integration tokens and posts copied to other
+//! services.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::domain::{AppError, Crosspost, Integration, RemotePost};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for cross-posting database operations.
+#[derive(Clone)]
+pub struct CrosspostRepository {
+    pool: SqlitePool,
+}
+
+impl CrosspostRepository {
+    /// Creates a new CrosspostRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Lists a user's integrations by target.
+    pub async fn list_integrations(&self, user_id: i64) -> Result<Vec<Integration>, AppError> {
+        let query = sqlx::query_as!(
+            Integration,
+            r#"
+            SELECT user_id as "user_id!", target, token, publication_id,
+                   updated_at as "updated_at: _"
+            FROM integration_tokens
+            WHERE user_id = ?
+            ORDER BY target
+            "#,
+            user_id
+        );
+        let integrations = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(integrations)
+    }
+
+    /// Finds a user's integration for a target.
+    pub async fn find_integration(
+        &self,
+        user_id: i64,
+        target: &str,
+    ) -> Result<Option<Integration>, AppError> {
+        let query = sqlx::query_as!(
+            Integration,
+            r#"
+            SELECT user_id as "user_id!", target, token, publication_id,
+                   updated_at as "updated_at: _"
+            FROM integration_tokens
+            WHERE user_id = ? AND target = ?
+            "#,
+            user_id,
+            target
+        );
+        let integration = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(integration)
+    }
+
+    /// Stores (or replaces) a user's token for a target.
+    pub async fn upsert_integration(
+        &self,
+        user_id: i64,
+        target: &str,
+        token: &str,
+        publication_id: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<Integration, AppError> {
+        let query = sqlx::query_as!(
+            Integration,
+            r#"
+            INSERT INTO integration_tokens (user_id, target, token, publication_id, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT (user_id, target) DO UPDATE SET
+                token = excluded.token,
+                publication_id = excluded.publication_id,
+                updated_at = excluded.updated_at
+            RETURNING user_id as "user_id!", target, token, publication_id,
+                      updated_at as "updated_at: _"
+            "#,
+            user_id,
+            target,
+            token,
+            publication_id,
+            now
+        );
+        let integration = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(integration)
+    }
+
+    /// Deletes a user's token for a target. Returns false if there was none.
+    pub async fn delete_integration(&self, user_id: i64, target: &str) -> Result<bool, AppError> {
+        let query = sqlx::query!(
+            "DELETE FROM integration_tokens WHERE user_id = ? AND target = ?",
+            user_id,
+            target
+        );
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Finds a post's cross-post to a target.
+    pub async fn find(&self, post_id: i64, target: &str) -> Result<Option<Crosspost>, AppError> {
+        let query = sqlx::query_as!(
+            Crosspost,
+            r#"
+            SELECT c.post_id as "post_id!", p.author_id, c.target, c.status, c.remote_id,
+                   c.remote_url, c.error, c.synced_at as "synced_at: _",
+                   c.attempted_at as "attempted_at: _"
+            FROM crossposts c
+            JOIN posts p ON p.id = c.post_id
+            WHERE c.post_id = ? AND c.target = ?
+            "#,
+            post_id,
+            target
+        );
+        let crosspost = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(crosspost)
+    }
+
+    /// Lists a post's cross-posts by target.
+    pub async fn list_for_post(&self, post_id: i64) -> Result<Vec<Crosspost>, AppError> {
+        let query = sqlx::query_as!(
+            Crosspost,
+            r#"
+            SELECT c.post_id as "post_id!", p.author_id, c.target, c.status, c.remote_id,
+                   c.remote_url, c.error, c.synced_at as "synced_at: _",
+                   c.attempted_at as "attempted_at: _"
+            FROM crossposts c
+            JOIN posts p ON p.id = c.post_id
+            WHERE c.post_id = ?
+            ORDER BY c.target
+            "#,
+            post_id
+        );
+        let crossposts = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(crossposts)
+    }
+
+    /// Lists cross-posts whose post was edited after the last attempt,
+    /// least recently attempted first.
+    pub async fn list_stale(&self, limit: i64) -> Result<Vec<Crosspost>, AppError> {
+        let query = sqlx::query_as!(
+            Crosspost,
+            r#"
+            SELECT c.post_id as "post_id!", p.author_id, c.target, c.status, c.remote_id,
+                   c.remote_url, c.error, c.synced_at as "synced_at: _",
+                   c.attempted_at as "attempted_at: _"
+            FROM crossposts c
+            JOIN posts p ON p.id = c.post_id
+            WHERE c.attempted_at < p.updated_at AND p.status = 'published' AND p.deleted_at IS NULL
+            ORDER BY c.attempted_at
+            LIMIT ?
+            "#,
+            limit
+        );
+        let crossposts = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(crossposts)
+    }
+
+    /// Records a successful push of the post version `synced_at`.
+    pub async fn record_success(
+        &self,
+        post_id: i64,
+        target: &str,
+        remote: &RemotePost,
+        synced_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO crossposts
+                (post_id, target, status, remote_id, remote_url, synced_at, attempted_at)
+            VALUES (?, ?, 'published', ?, ?, ?, ?)
+            ON CONFLICT (post_id, target) DO UPDATE SET
+                status = 'published',
+                remote_id = excluded.remote_id,
+                remote_url = excluded.remote_url,
+                error = NULL,
+                synced_at = excluded.synced_at,
+                attempted_at = excluded.attempted_at
+            "#,
+            post_id,
+            target,
+            remote.id,
+            remote.url,
+            synced_at,
+            now
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Records a failed push, keeping any copy published earlier.
+    pub async fn record_failure(
+        &self,
+        post_id: i64,
+        target: &str,
+        error: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO crossposts (post_id, target, status, error, attempted_at)
+            VALUES (?, ?, 'failed', ?, ?)
+            ON CONFLICT (post_id, target) DO UPDATE SET
+                status = 'failed',
+                error = excluded.error,
+                attempted_at = excluded.attempted_at
+            "#,
+            post_id,
+            target,
+            error,
+            now
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+}
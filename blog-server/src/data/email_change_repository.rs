@@ -0,0 +1,91 @@
+//! Email change repository for database operations.
+
+use sqlx::SqlitePool;
+
+use crate::domain::{AppError, EmailChange};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for email changes waiting for confirmation.
+#[derive(Clone)]
+pub struct EmailChangeRepository {
+    pool: SqlitePool,
+}
+
+impl EmailChangeRepository {
+    /// Creates a new EmailChangeRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Stores a pending change, replacing any earlier one of the user.
+    pub async fn upsert(&self, change: &EmailChange) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO email_changes (user_id, new_email, token_hash, created_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT(user_id) DO UPDATE SET
+                new_email = ?2, token_hash = ?3, created_at = ?4
+            "#,
+            change.user_id,
+            change.new_email,
+            change.token_hash,
+            change.created_at
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Finds a pending change by the hash of its confirmation token.
+    pub async fn find_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<EmailChange>, AppError> {
+        let query = sqlx::query_as!(
+            EmailChange,
+            r#"
+            SELECT user_id as "user_id!", new_email, token_hash, created_at as "created_at: _"
+            FROM email_changes
+            WHERE token_hash = ?
+            "#,
+            token_hash
+        );
+        let change = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(change)
+    }
+
+    /// Switches the user's email to the confirmed address and drops the
+    /// pending change, in one transaction.
+    pub async fn apply(&self, change: &EmailChange) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let query = sqlx::query!(
+            "UPDATE users SET email = ? WHERE id = ?",
+            change.new_email,
+            change.user_id
+        );
+        let result = timed_query(query.execute(&mut *tx)).await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::UserNotFound);
+        }
+
+        let query = sqlx::query!(
+            "DELETE FROM email_changes WHERE user_id = ?",
+            change.user_id
+        );
+        timed_query(query.execute(&mut *tx)).await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Drops the user's pending change, if any.
+    pub async fn delete(&self, user_id: i64) -> Result<(), AppError> {
+        let query = sqlx::query!("DELETE FROM email_changes WHERE user_id = ?", user_id);
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+}
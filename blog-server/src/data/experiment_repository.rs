@@ -0,0 +1,226 @@
+//! Experiment repository for database operations.
+
+use chrono::{DateTime, Utc};
+use sqlx::{Sqlite, SqlitePool, Transaction};
+
+use crate::domain::{AppError, Experiment, ExperimentVariant};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for experiments, their variants and exposures.
+#[derive(Clone)]
+pub struct ExperimentRepository {
+    pool: SqlitePool,
+}
+
+impl ExperimentRepository {
+    /// Creates a new ExperimentRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Lists experiments by name, optionally only the enabled ones.
+    pub async fn list(&self, enabled_only: bool) -> Result<Vec<Experiment>, AppError> {
+        let query = sqlx::query_as!(
+            Experiment,
+            r#"
+            SELECT id as "id!", name, description, enabled,
+                   created_at as "created_at: _", updated_at as "updated_at: _"
+            FROM experiments
+            WHERE enabled = 1 OR ? = 0
+            ORDER BY name
+            "#,
+            enabled_only
+        );
+        let experiments = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(experiments)
+    }
+
+    /// Finds an experiment by its normalized name.
+    pub async fn find_by_name(&self, name: &str) -> Result<Option<Experiment>, AppError> {
+        let query = sqlx::query_as!(
+            Experiment,
+            r#"
+            SELECT id as "id!", name, description, enabled,
+                   created_at as "created_at: _", updated_at as "updated_at: _"
+            FROM experiments
+            WHERE name = ?
+            "#,
+            name
+        );
+        let experiment = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(experiment)
+    }
+
+    /// Lists an experiment's variants in assignment order.
+    pub async fn list_variants(
+        &self,
+        experiment_id: i64,
+    ) -> Result<Vec<ExperimentVariant>, AppError> {
+        let query = sqlx::query_as!(
+            ExperimentVariant,
+            r#"
+            SELECT name, weight
+            FROM experiment_variants
+            WHERE experiment_id = ?
+            ORDER BY position
+            "#,
+            experiment_id
+        );
+        let variants = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(variants)
+    }
+
+    /// Creates an enabled experiment with its variants, in one transaction.
+    /// Expects `name` normalized and not taken.
+    pub async fn create(
+        &self,
+        name: &str,
+        description: &str,
+        variants: &[ExperimentVariant],
+        now: DateTime<Utc>,
+    ) -> Result<Experiment, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let query = sqlx::query_as!(
+            Experiment,
+            r#"
+            INSERT INTO experiments (name, description, enabled, created_at, updated_at)
+            VALUES (?, ?, 1, ?, ?)
+            RETURNING id as "id!", name, description, enabled,
+                      created_at as "created_at: _", updated_at as "updated_at: _"
+            "#,
+            name,
+            description,
+            now,
+            now
+        );
+        let experiment = timed_query(query.fetch_one(&mut *tx)).await?;
+        insert_variants(&mut tx, experiment.id, variants).await?;
+
+        tx.commit().await?;
+
+        Ok(experiment)
+    }
+
+    /// Updates an experiment's description and switch, and replaces its
+    /// variants when given, clearing the exposures recorded under the old
+    /// split.
+    pub async fn update(
+        &self,
+        id: i64,
+        description: &str,
+        enabled: bool,
+        variants: Option<&[ExperimentVariant]>,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let query = sqlx::query!(
+            "UPDATE experiments SET description = ?, enabled = ?, updated_at = ? WHERE id = ?",
+            description,
+            enabled,
+            now,
+            id
+        );
+        timed_query(query.execute(&mut *tx)).await?;
+
+        if let Some(variants) = variants {
+            let query = sqlx::query!(
+                "DELETE FROM experiment_variants WHERE experiment_id = ?",
+                id
+            );
+            timed_query(query.execute(&mut *tx)).await?;
+            let query = sqlx::query!(
+                "DELETE FROM experiment_exposures WHERE experiment_id = ?",
+                id
+            );
+            timed_query(query.execute(&mut *tx)).await?;
+            insert_variants(&mut tx, id, variants).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Deletes an experiment with its variants and exposures. Returns
+    /// `false` if no such experiment exists.
+    pub async fn delete(&self, id: i64) -> Result<bool, AppError> {
+        let query = sqlx::query!("DELETE FROM experiments WHERE id = ?", id);
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Records that a user was shown a variant, keeping the first exposure.
+    pub async fn record_exposure(
+        &self,
+        experiment_id: i64,
+        user_id: i64,
+        variant: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            r#"
+            INSERT OR IGNORE INTO experiment_exposures (experiment_id, user_id, variant, exposed_at)
+            VALUES (?, ?, ?, ?)
+            "#,
+            experiment_id,
+            user_id,
+            variant,
+            now
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Counts exposed users per variant of an experiment.
+    pub async fn count_exposures(
+        &self,
+        experiment_id: i64,
+    ) -> Result<Vec<(String, i64)>, AppError> {
+        let query = sqlx::query!(
+            r#"
+            SELECT variant, COUNT(*) as "count!: i64"
+            FROM experiment_exposures
+            WHERE experiment_id = ?
+            GROUP BY variant
+            "#,
+            experiment_id
+        );
+        let rows = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.variant, row.count))
+            .collect())
+    }
+}
+
+/// Inserts an experiment's variants in order.
+async fn insert_variants(
+    tx: &mut Transaction<'_, Sqlite>,
+    experiment_id: i64,
+    variants: &[ExperimentVariant],
+) -> Result<(), AppError> {
+    for (position, variant) in variants.iter().enumerate() {
+        let position = position as i64;
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO experiment_variants (experiment_id, position, name, weight)
+            VALUES (?, ?, ?, ?)
+            "#,
+            experiment_id,
+            position,
+            variant.name,
+            variant.weight
+        );
+        timed_query(query.execute(&mut **tx)).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+//! Feature flag repository for database operations.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::domain::{AppError, FeatureFlag};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for feature flag database operations.
+#[derive(Clone)]
+pub struct FeatureFlagRepository {
+    pool: SqlitePool,
+}
+
+impl FeatureFlagRepository {
+    /// Creates a new FeatureFlagRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Lists all feature flags by name.
+    pub async fn list(&self) -> Result<Vec<FeatureFlag>, AppError> {
+        let query = sqlx::query_as!(
+            FeatureFlag,
+            r#"
+            SELECT name as "name!", description, enabled, updated_at as "updated_at: _"
+            FROM feature_flags
+            ORDER BY name
+            "#
+        );
+        let flags = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(flags)
+    }
+
+    /// Finds a feature flag by name.
+    pub async fn find(&self, name: &str) -> Result<Option<FeatureFlag>, AppError> {
+        let query = sqlx::query_as!(
+            FeatureFlag,
+            r#"
+            SELECT name as "name!", description, enabled, updated_at as "updated_at: _"
+            FROM feature_flags
+            WHERE name = ?
+            "#,
+            name
+        );
+        let flag = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(flag)
+    }
+
+    /// Turns a feature flag on or off. Returns `false` if no such flag exists.
+    pub async fn set_enabled(
+        &self,
+        name: &str,
+        enabled: bool,
+        now: DateTime<Utc>,
+    ) -> Result<bool, AppError> {
+        let query = sqlx::query!(
+            "UPDATE feature_flags SET enabled = ?, updated_at = ? WHERE name = ?",
+            enabled,
+            now,
+            name
+        );
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Whether a feature is enabled (unknown flags count as enabled).
+    pub async fn is_enabled(&self, name: &str) -> Result<bool, AppError> {
+        Ok(self.find(name).await?.is_none_or(|flag| flag.enabled))
+    }
+}
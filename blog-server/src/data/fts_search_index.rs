@@ -0,0 +1,96 @@
+//! Search index kept by SQLite FTS5 triggers in the posts database.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::constants::SEARCH_MAX_TYPO_TERMS;
+use crate::data::PostRepository;
+use crate::domain::{
+    AppError, SearchDocument, SearchFilters, SearchIndex, SearchResults, edit_distance, match_expr,
+    search_words, typo_budget,
+};
+
+/// The default search index: the `posts_fts` table, updated by triggers,
+/// so there is nothing to feed it.
+pub struct FtsSearchIndex {
+    post_repo: Arc<PostRepository>,
+}
+
+impl FtsSearchIndex {
+    /// Creates an index searching the posts of `post_repo`.
+    pub fn new(post_repo: Arc<PostRepository>) -> Self {
+        Self { post_repo }
+    }
+
+    /// Finds indexed terms that `word` may be a misspelling of: same first
+    /// character, within its typo budget, and not already matched by it as
+    /// a prefix. The most common come first.
+    async fn typo_alternatives(&self, word: &str) -> Result<Vec<String>, AppError> {
+        let budget = typo_budget(word);
+        let Some(first) = word.chars().next().filter(|_| budget > 0) else {
+            return Ok(Vec::new());
+        };
+        let len = word.chars().count() as i64;
+        let slack = budget as i64;
+        let terms = self
+            .post_repo
+            .list_indexed_terms(first, len - slack, len + slack)
+            .await?;
+
+        Ok(terms
+            .into_iter()
+            .filter(|term| {
+                !term.term.starts_with(word) && edit_distance(word, &term.term) <= budget
+            })
+            .take(SEARCH_MAX_TYPO_TERMS)
+            .map(|term| term.term)
+            .collect())
+    }
+}
+
+#[async_trait]
+impl SearchIndex for FtsSearchIndex {
+    /// Each word in `query` is matched as a prefix, or as an indexed term
+    /// within its `typo_budget`; all words must match.
+    async fn search(
+        &self,
+        query: &str,
+        filters: &SearchFilters,
+        limit: i64,
+        offset: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<SearchResults, AppError> {
+        let mut words = Vec::new();
+        for word in search_words(query) {
+            let alternatives = self.typo_alternatives(&word).await?;
+            words.push((word, alternatives));
+        }
+        let Some(match_expr) = match_expr(&words) else {
+            return Ok(SearchResults::default());
+        };
+
+        let hits = self
+            .post_repo
+            .search(&match_expr, filters, limit, offset, viewer_id)
+            .await?;
+        let total = self
+            .post_repo
+            .count_search(&match_expr, filters, viewer_id)
+            .await?;
+
+        Ok(SearchResults { hits, total })
+    }
+
+    fn is_external(&self) -> bool {
+        false
+    }
+
+    async fn upsert(&self, _document: SearchDocument) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    async fn remove(&self, _post_id: i64) -> Result<(), AppError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+//! Invite repository for database operations.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::domain::AppError;
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for invite codes (stored hashed).
+#[derive(Clone)]
+pub struct InviteRepository {
+    pool: SqlitePool,
+}
+
+impl InviteRepository {
+    /// Creates a new InviteRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Stores a new unused invite made by `created_by`, returning when it
+    /// was created.
+    pub async fn create(
+        &self,
+        code_hash: &str,
+        created_by: i64,
+        now: DateTime<Utc>,
+    ) -> Result<DateTime<Utc>, AppError> {
+        let query = sqlx::query!(
+            "INSERT INTO invites (code_hash, created_by, created_at) VALUES (?, ?, ?)",
+            code_hash,
+            created_by,
+            now
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(now)
+    }
+
+    /// Marks an unused invite as used, so no one else can register with it.
+    /// Returns its ID, or `None` if there is no such unused invite.
+    pub async fn claim(
+        &self,
+        code_hash: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<i64>, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            UPDATE invites SET used_at = ?
+            WHERE code_hash = ? AND used_at IS NULL
+            RETURNING id as "id!"
+            "#,
+            now,
+            code_hash
+        );
+        let id = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(id)
+    }
+
+    /// Records the user who registered with a claimed invite.
+    pub async fn set_used_by(&self, id: i64, user_id: i64) -> Result<(), AppError> {
+        let query = sqlx::query!("UPDATE invites SET used_by = ? WHERE id = ?", user_id, id);
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Makes a claimed invite usable again, after the registration that
+    /// claimed it failed.
+    pub async fn release(&self, id: i64) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            "UPDATE invites SET used_at = NULL WHERE id = ? AND used_by IS NULL",
+            id
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+//! Link check repository for database operations.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::domain::{AppError, BrokenLink, LinkCheck};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for link status checks, used by the broken link checker.
+#[derive(Clone)]
+pub struct LinkCheckRepository {
+    pool: SqlitePool,
+}
+
+impl LinkCheckRepository {
+    /// Creates a new LinkCheckRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Lists linked URLs never checked or last checked before `stale_before`.
+    pub async fn list_due(
+        &self,
+        stale_before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<String>, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            SELECT DISTINCT pl.url as "url!"
+            FROM post_links pl
+            LEFT JOIN link_checks lc ON lc.url = pl.url
+            WHERE lc.checked_at IS NULL OR lc.checked_at < ?
+            ORDER BY lc.checked_at IS NOT NULL, lc.checked_at
+            LIMIT ?
+            "#,
+            stale_before,
+            limit
+        );
+        let urls = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(urls)
+    }
+
+    /// Stores the latest check of a URL, replacing the previous one.
+    pub async fn record(&self, check: &LinkCheck) -> Result<(), AppError> {
+        let status_code = check.status_code.map(i64::from);
+        let is_broken = check.is_broken();
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO link_checks (url, status_code, error, is_broken, checked_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(url) DO UPDATE SET
+                status_code = excluded.status_code,
+                error = excluded.error,
+                is_broken = excluded.is_broken,
+                checked_at = excluded.checked_at
+            "#,
+            check.url,
+            status_code,
+            check.error,
+            is_broken,
+            check.checked_at
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Lists broken links in the author's posts, newest post first.
+    pub async fn find_broken_by_author(&self, author_id: i64) -> Result<Vec<BrokenLink>, AppError> {
+        let query = sqlx::query_as!(
+            BrokenLink,
+            r#"
+            SELECT p.id as "post_id!", p.public_id as "post_public_id", p.title as "post_title",
+                   pl.url,
+                   lc.status_code, lc.error, lc.checked_at as "checked_at: _"
+            FROM posts p
+            JOIN post_links pl ON pl.post_id = p.id
+            JOIN link_checks lc ON lc.url = pl.url
+            WHERE p.author_id = ? AND p.deleted_at IS NULL AND lc.is_broken = 1
+            ORDER BY p.created_at DESC, p.id DESC, pl.position
+            "#,
+            author_id
+        );
+        let links = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(links)
+    }
+}
@@ -0,0 +1,95 @@
+//! Link preview repository for database operations.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::domain::{AppError, LinkPreview};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for the link preview cache, used by the background fetcher.
+#[derive(Clone)]
+pub struct LinkPreviewRepository {
+    pool: SqlitePool,
+}
+
+impl LinkPreviewRepository {
+    /// Creates a new LinkPreviewRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Lists linked URLs never fetched or last fetched before `stale_before`.
+    pub async fn list_due(
+        &self,
+        stale_before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<String>, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            SELECT lp.url
+            FROM link_previews lp
+            WHERE (lp.fetched_at IS NULL OR lp.fetched_at < ?)
+              AND EXISTS (SELECT 1 FROM post_links pl WHERE pl.url = lp.url)
+            ORDER BY lp.fetched_at IS NOT NULL, lp.fetched_at
+            LIMIT ?
+            "#,
+            stale_before,
+            limit
+        );
+        let urls = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(urls)
+    }
+
+    /// Stores fetched metadata for a URL.
+    pub async fn save(&self, preview: &LinkPreview, now: DateTime<Utc>) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            r#"
+            UPDATE link_previews
+            SET title = ?, description = ?, image_url = ?, site_name = ?, fetched_at = ?
+            WHERE url = ?
+            "#,
+            preview.title,
+            preview.description,
+            preview.image_url,
+            preview.site_name,
+            now,
+            preview.url
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        self.touch_posts_linking(&preview.url, now).await
+    }
+
+    /// Records a failed fetch so the URL is not retried until it goes stale.
+    pub async fn mark_failed(&self, url: &str, now: DateTime<Utc>) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            r#"
+            UPDATE link_previews
+            SET title = NULL, description = NULL, image_url = NULL, site_name = NULL,
+                fetched_at = ?
+            WHERE url = ?
+            "#,
+            now,
+            url
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        self.touch_posts_linking(url, now).await
+    }
+
+    /// Marks the posts linking to `url` modified, as their previews changed.
+    async fn touch_posts_linking(&self, url: &str, now: DateTime<Utc>) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            r#"
+            UPDATE posts SET modified_at = ?
+            WHERE id IN (SELECT post_id FROM post_links WHERE url = ?)
+            "#,
+            now,
+            url
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+}
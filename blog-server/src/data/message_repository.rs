@@ -0,0 +1,262 @@
+//! Private message repository for database operations.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::domain::{AppError, Conversation, Message};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for conversation and message database operations.
+///
+/// Conversations store their participants as `(user_a_id, user_b_id)` with
+/// `user_a_id < user_b_id`; queries take the current user's ID and read the
+/// matching side.
+#[derive(Clone)]
+pub struct MessageRepository {
+    pool: SqlitePool,
+}
+
+impl MessageRepository {
+    /// Creates a new MessageRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Returns the ID of the conversation between two users, creating it if needed.
+    pub async fn find_or_create_conversation(
+        &self,
+        user_id: i64,
+        other_id: i64,
+        now: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        let (user_a_id, user_b_id) = (user_id.min(other_id), user_id.max(other_id));
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO conversations (user_a_id, user_b_id, created_at, updated_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (user_a_id, user_b_id) DO NOTHING
+            "#,
+            user_a_id,
+            user_b_id,
+            now,
+            now
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        let query = sqlx::query_scalar!(
+            r#"SELECT id as "id!" FROM conversations WHERE user_a_id = ? AND user_b_id = ?"#,
+            user_a_id,
+            user_b_id
+        );
+        let id = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(id)
+    }
+
+    /// Finds a conversation of `user_id` by ID (`None` if they are not a participant).
+    pub async fn find_conversation(
+        &self,
+        id: i64,
+        user_id: i64,
+    ) -> Result<Option<Conversation>, AppError> {
+        let query = sqlx::query_as!(
+            Conversation,
+            r#"
+            SELECT c.id as "id!", u.id as "other_user_id!", u.public_id as other_user_public_id,
+                   u.username as other_username,
+                   m.content as "last_message?", m.created_at as "last_message_at?: _",
+                   (SELECT COUNT(*) FROM messages x
+                    WHERE x.conversation_id = c.id AND x.sender_id != ?1
+                      AND x.id > CASE WHEN c.user_a_id = ?1
+                                      THEN c.user_a_last_read_id
+                                      ELSE c.user_b_last_read_id END) as "unread_count!: i64",
+                   c.created_at as "created_at: _"
+            FROM conversations c
+            JOIN users u ON u.id = CASE WHEN c.user_a_id = ?1 THEN c.user_b_id ELSE c.user_a_id END
+            LEFT JOIN messages m
+                ON m.id = (SELECT MAX(id) FROM messages WHERE conversation_id = c.id)
+            WHERE c.id = ?2 AND (c.user_a_id = ?1 OR c.user_b_id = ?1)
+            "#,
+            user_id,
+            id
+        );
+        let conversation = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(conversation)
+    }
+
+    /// Lists conversations of `user_id`, most recently active first.
+    pub async fn list_conversations(
+        &self,
+        user_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Conversation>, AppError> {
+        let query = sqlx::query_as!(
+            Conversation,
+            r#"
+            SELECT c.id as "id!", u.id as "other_user_id!", u.public_id as other_user_public_id,
+                   u.username as other_username,
+                   m.content as "last_message?", m.created_at as "last_message_at?: _",
+                   (SELECT COUNT(*) FROM messages x
+                    WHERE x.conversation_id = c.id AND x.sender_id != ?1
+                      AND x.id > CASE WHEN c.user_a_id = ?1
+                                      THEN c.user_a_last_read_id
+                                      ELSE c.user_b_last_read_id END) as "unread_count!: i64",
+                   c.created_at as "created_at: _"
+            FROM conversations c
+            JOIN users u ON u.id = CASE WHEN c.user_a_id = ?1 THEN c.user_b_id ELSE c.user_a_id END
+            LEFT JOIN messages m
+                ON m.id = (SELECT MAX(id) FROM messages WHERE conversation_id = c.id)
+            WHERE c.user_a_id = ?1 OR c.user_b_id = ?1
+            ORDER BY c.updated_at DESC, c.id DESC
+            LIMIT ?2 OFFSET ?3
+            "#,
+            user_id,
+            limit,
+            offset
+        );
+        let conversations = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(conversations)
+    }
+
+    /// Counts conversations of `user_id`.
+    pub async fn count_conversations(&self, user_id: i64) -> Result<i64, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count: i64"
+            FROM conversations
+            WHERE user_a_id = ?1 OR user_b_id = ?1
+            "#,
+            user_id
+        );
+        let result = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(result)
+    }
+
+    /// Adds a message to a conversation and bumps the conversation's activity time.
+    pub async fn create_message(
+        &self,
+        conversation_id: i64,
+        sender_id: i64,
+        content: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Message, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let query = sqlx::query_scalar!(
+            r#"
+            INSERT INTO messages (conversation_id, sender_id, content, created_at)
+            VALUES (?, ?, ?, ?)
+            RETURNING id as "id!"
+            "#,
+            conversation_id,
+            sender_id,
+            content,
+            now
+        );
+        let id = timed_query(query.fetch_one(&mut *tx)).await?;
+
+        let query = sqlx::query!(
+            "UPDATE conversations SET updated_at = ? WHERE id = ?",
+            now,
+            conversation_id
+        );
+        timed_query(query.execute(&mut *tx)).await?;
+
+        tx.commit().await?;
+
+        let query = sqlx::query_as!(
+            Message,
+            r#"
+            SELECT m.id as "id!", m.conversation_id, m.sender_id, u.public_id as sender_public_id,
+                   u.username as sender_username,
+                   m.content, m.created_at as "created_at: _"
+            FROM messages m
+            JOIN users u ON u.id = m.sender_id
+            WHERE m.id = ?
+            "#,
+            id
+        );
+        let message = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(message)
+    }
+
+    /// Lists messages of a conversation, newest first.
+    pub async fn list_messages(
+        &self,
+        conversation_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Message>, AppError> {
+        let query = sqlx::query_as!(
+            Message,
+            r#"
+            SELECT m.id as "id!", m.conversation_id, m.sender_id, u.public_id as sender_public_id,
+                   u.username as sender_username,
+                   m.content, m.created_at as "created_at: _"
+            FROM messages m
+            JOIN users u ON u.id = m.sender_id
+            WHERE m.conversation_id = ?
+            ORDER BY m.id DESC
+            LIMIT ? OFFSET ?
+            "#,
+            conversation_id,
+            limit,
+            offset
+        );
+        let messages = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(messages)
+    }
+
+    /// Counts messages of a conversation.
+    pub async fn count_messages(&self, conversation_id: i64) -> Result<i64, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count: i64" FROM messages WHERE conversation_id = ?"#,
+            conversation_id
+        );
+        let result = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(result)
+    }
+
+    /// Marks every message of a conversation as read by `user_id`.
+    pub async fn mark_read(&self, conversation_id: i64, user_id: i64) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            r#"
+            UPDATE conversations
+            SET user_a_last_read_id = CASE WHEN user_a_id = ?1 THEN last.id ELSE user_a_last_read_id END,
+                user_b_last_read_id = CASE WHEN user_b_id = ?1 THEN last.id ELSE user_b_last_read_id END
+            FROM (SELECT COALESCE(MAX(id), 0) as id FROM messages WHERE conversation_id = ?2) as last
+            WHERE conversations.id = ?2
+            "#,
+            user_id,
+            conversation_id
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Counts messages sent to `user_id` that they have not read.
+    pub async fn count_unread(&self, user_id: i64) -> Result<i64, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count: i64"
+            FROM messages m
+            JOIN conversations c ON c.id = m.conversation_id
+            WHERE m.sender_id != ?1
+              AND ((c.user_a_id = ?1 AND m.id > c.user_a_last_read_id)
+                OR (c.user_b_id = ?1 AND m.id > c.user_b_last_read_id))
+            "#,
+            user_id
+        );
+        let result = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(result)
+    }
+}
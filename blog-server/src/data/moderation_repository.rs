@@ -0,0 +1,224 @@
+//! Moderation rule and content flag repository for database operations.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::domain::{AppError, FlaggedContent, ModerationRule, NewRule};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for moderation rule and content flag database operations.
+#[derive(Clone)]
+pub struct ModerationRepository {
+    pool: SqlitePool,
+}
+
+impl ModerationRepository {
+    /// Creates a new ModerationRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Lists all rules, oldest first.
+    pub async fn list_rules(&self) -> Result<Vec<ModerationRule>, AppError> {
+        let query = sqlx::query_as!(
+            ModerationRule,
+            r#"
+            SELECT id as "id!", name, kind, pattern, action, enabled as "enabled: bool",
+                   checked_count, hit_count,
+                   last_hit_at as "last_hit_at: _", created_at as "created_at: _",
+                   updated_at as "updated_at: _"
+            FROM moderation_rules
+            ORDER BY id
+            "#
+        );
+        let rules = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(rules)
+    }
+
+    /// Finds a rule by ID.
+    pub async fn find_rule(&self, id: i64) -> Result<Option<ModerationRule>, AppError> {
+        let query = sqlx::query_as!(
+            ModerationRule,
+            r#"
+            SELECT id as "id!", name, kind, pattern, action, enabled as "enabled: bool",
+                   checked_count, hit_count,
+                   last_hit_at as "last_hit_at: _", created_at as "created_at: _",
+                   updated_at as "updated_at: _"
+            FROM moderation_rules
+            WHERE id = ?
+            "#,
+            id
+        );
+        let rule = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(rule)
+    }
+
+    /// Creates a rule.
+    pub async fn create_rule(&self, rule: NewRule<'_>) -> Result<ModerationRule, AppError> {
+        let NewRule {
+            name,
+            kind,
+            pattern,
+            action,
+            enabled,
+            now,
+        } = rule;
+        let (kind, action) = (kind.as_str(), action.as_str());
+        let query = sqlx::query_scalar!(
+            r#"
+            INSERT INTO moderation_rules (name, kind, pattern, action, enabled, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            RETURNING id as "id!"
+            "#,
+            name,
+            kind,
+            pattern,
+            action,
+            enabled,
+            now,
+            now
+        );
+        let id = timed_query(query.fetch_one(&self.pool)).await?;
+
+        self.find_rule(id)
+            .await?
+            .ok_or(AppError::ModerationRuleNotFound)
+    }
+
+    /// Replaces a rule's definition, keeping its statistics.
+    pub async fn update_rule(
+        &self,
+        id: i64,
+        rule: NewRule<'_>,
+    ) -> Result<ModerationRule, AppError> {
+        let NewRule {
+            name,
+            kind,
+            pattern,
+            action,
+            enabled,
+            now,
+        } = rule;
+        let (kind, action) = (kind.as_str(), action.as_str());
+        let query = sqlx::query!(
+            r#"
+            UPDATE moderation_rules
+            SET name = ?, kind = ?, pattern = ?, action = ?, enabled = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+            name,
+            kind,
+            pattern,
+            action,
+            enabled,
+            now,
+            id
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        self.find_rule(id)
+            .await?
+            .ok_or(AppError::ModerationRuleNotFound)
+    }
+
+    /// Deletes a rule and its flags. Returns `false` if no such rule exists.
+    pub async fn delete_rule(&self, id: i64) -> Result<bool, AppError> {
+        let query = sqlx::query!("DELETE FROM moderation_rules WHERE id = ?", id);
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Counts a check against every enabled rule.
+    pub async fn record_checks(&self) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            "UPDATE moderation_rules SET checked_count = checked_count + 1 WHERE enabled = 1"
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Counts a hit for a rule.
+    pub async fn record_hit(&self, id: i64, now: DateTime<Utc>) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            "UPDATE moderation_rules SET hit_count = hit_count + 1, last_hit_at = ? WHERE id = ?",
+            now,
+            id
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Adds a post, or a comment on it, to the flagged content queue.
+    pub async fn flag(
+        &self,
+        post_id: i64,
+        comment_id: Option<i64>,
+        rule_id: i64,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO content_flags (post_id, comment_id, rule_id, created_at)
+            VALUES (?, ?, ?, ?)
+            "#,
+            post_id,
+            comment_id,
+            rule_id,
+            now
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Lists flagged content, newest first.
+    pub async fn list_flags(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<FlaggedContent>, AppError> {
+        let query = sqlx::query_as!(
+            FlaggedContent,
+            r#"
+            SELECT f.id as "id!", f.post_id, p.public_id as post_public_id, f.comment_id,
+                   u.id as "author_id!",
+                   u.username as author_username,
+                   COALESCE(c.content, p.title) as "excerpt!: String",
+                   f.rule_id, r.name as rule_name, f.created_at as "created_at: _"
+            FROM content_flags f
+            JOIN moderation_rules r ON r.id = f.rule_id
+            JOIN posts p ON p.id = f.post_id
+            LEFT JOIN comments c ON c.id = f.comment_id
+            JOIN users u ON u.id = COALESCE(c.author_id, p.author_id)
+            ORDER BY f.created_at DESC, f.id DESC
+            LIMIT ? OFFSET ?
+            "#,
+            limit,
+            offset
+        );
+        let flags = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(flags)
+    }
+
+    /// Counts flagged content.
+    pub async fn count_flags(&self) -> Result<i64, AppError> {
+        let query = sqlx::query_scalar!(r#"SELECT COUNT(*) as "count: i64" FROM content_flags"#);
+        let result = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(result)
+    }
+
+    /// Removes a flag from the queue. Returns `false` if no such flag exists.
+    pub async fn delete_flag(&self, id: i64) -> Result<bool, AppError> {
+        let query = sqlx::query!("DELETE FROM content_flags WHERE id = ?", id);
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
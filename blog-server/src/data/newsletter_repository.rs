@@ -0,0 +1,187 @@
+//! Newsletter subscription repository for database operations.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::domain::{AppError, DateFormat, DigestPost, Subscription};
+use crate::infrastructure::content_cipher::{self, ContentCipher};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for newsletter subscriptions and digest content.
+#[derive(Clone)]
+pub struct NewsletterRepository {
+    pool: SqlitePool,
+    cipher: Option<ContentCipher>,
+}
+
+impl NewsletterRepository {
+    /// Creates a new NewsletterRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool, cipher: None }
+    }
+
+    /// Decrypts post content encrypted at rest with `cipher`.
+    pub fn with_cipher(mut self, cipher: ContentCipher) -> Self {
+        self.cipher = Some(cipher);
+        self
+    }
+
+    /// Finds a subscription by email address.
+    pub async fn find_by_email(&self, email: &str) -> Result<Option<Subscription>, AppError> {
+        let query = sqlx::query_as!(
+            Subscription,
+            r#"
+            SELECT id as "id!", email, token, confirmed_at as "confirmed_at: _",
+                   unsubscribed_at as "unsubscribed_at: _", last_digest_at as "last_digest_at: _",
+                   created_at as "created_at: _"
+            FROM subscriptions
+            WHERE email = ?
+            "#,
+            email
+        );
+        let subscription = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(subscription)
+    }
+
+    /// Finds a subscription by its confirmation/unsubscribe token.
+    pub async fn find_by_token(&self, token: &str) -> Result<Option<Subscription>, AppError> {
+        let query = sqlx::query_as!(
+            Subscription,
+            r#"
+            SELECT id as "id!", email, token, confirmed_at as "confirmed_at: _",
+                   unsubscribed_at as "unsubscribed_at: _", last_digest_at as "last_digest_at: _",
+                   created_at as "created_at: _"
+            FROM subscriptions
+            WHERE token = ?
+            "#,
+            token
+        );
+        let subscription = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(subscription)
+    }
+
+    /// Creates a pending subscription, or makes an existing one pending again
+    /// with a new token (re-subscribing after unsubscribing).
+    pub async fn upsert_pending(
+        &self,
+        email: &str,
+        token: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO subscriptions (email, token, created_at)
+            VALUES (?, ?, ?)
+            ON CONFLICT (email) DO UPDATE
+            SET token = excluded.token, confirmed_at = NULL, unsubscribed_at = NULL
+            "#,
+            email,
+            token,
+            now
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Marks a subscription as confirmed.
+    pub async fn confirm(&self, id: i64, now: DateTime<Utc>) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            "UPDATE subscriptions SET confirmed_at = ? WHERE id = ? AND confirmed_at IS NULL",
+            now,
+            id
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Marks a subscription as unsubscribed.
+    pub async fn unsubscribe(&self, id: i64, now: DateTime<Utc>) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            "UPDATE subscriptions SET unsubscribed_at = ? WHERE id = ? AND unsubscribed_at IS NULL",
+            now,
+            id
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Lists confirmed subscriptions that have not received a digest since `before`.
+    pub async fn list_due(&self, before: DateTime<Utc>) -> Result<Vec<Subscription>, AppError> {
+        let query = sqlx::query_as!(
+            Subscription,
+            r#"
+            SELECT id as "id!", email, token, confirmed_at as "confirmed_at: _",
+                   unsubscribed_at as "unsubscribed_at: _", last_digest_at as "last_digest_at: _",
+                   created_at as "created_at: _"
+            FROM subscriptions
+            WHERE confirmed_at IS NOT NULL AND unsubscribed_at IS NULL
+              AND (last_digest_at IS NULL OR last_digest_at < ?)
+            ORDER BY id
+            "#,
+            before
+        );
+        let subscriptions = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(subscriptions)
+    }
+
+    /// Records that a subscriber was sent a digest.
+    pub async fn mark_digest_sent(&self, id: i64, sent_at: DateTime<Utc>) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            "UPDATE subscriptions SET last_digest_at = ? WHERE id = ?",
+            sent_at,
+            id
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Returns how dates are shown to a subscriber: the preferences of the
+    /// user with the same email, or the defaults if there is none.
+    pub async fn date_format(&self, email: &str) -> Result<DateFormat, AppError> {
+        let query = sqlx::query!("SELECT timezone, locale FROM users WHERE email = ?", email);
+        let row = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(row
+            .map(|row| DateFormat::new(&row.timezone, &row.locale))
+            .unwrap_or_default())
+    }
+
+    /// Lists the most commented posts published since `since`.
+    ///
+    /// Posts of shadow-banned authors are left out.
+    pub async fn top_posts(
+        &self,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<DigestPost>, AppError> {
+        let query = sqlx::query_as!(
+            DigestPost,
+            r#"
+            SELECT p.public_id, p.title, p.content, u.username as author_username,
+                   COUNT(c.id) as "comment_count!: i64", p.created_at as "created_at: _"
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            LEFT JOIN comments c ON c.post_id = p.id
+            WHERE p.created_at >= ? AND p.deleted_at IS NULL AND u.shadow_banned_at IS NULL AND p.status = 'published'
+            GROUP BY p.id
+            ORDER BY COUNT(c.id) DESC, p.created_at DESC
+            LIMIT ?
+            "#,
+            since,
+            limit
+        );
+        let mut posts = timed_query(query.fetch_all(&self.pool)).await?;
+        for post in &mut posts {
+            content_cipher::open(self.cipher.as_ref(), &mut post.content)?;
+        }
+
+        Ok(posts)
+    }
+}
@@ -0,0 +1,119 @@
+//! Notification repository for database operations.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::domain::{AppError, Notification, PageCursor};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for notification-related database operations.
+#[derive(Clone)]
+pub struct NotificationRepository {
+    pool: SqlitePool,
+}
+
+impl NotificationRepository {
+    /// Creates a new NotificationRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Stores an unread notification for `user_id`.
+    pub async fn create(
+        &self,
+        user_id: i64,
+        kind: &str,
+        actor_id: i64,
+        post_id: i64,
+        comment_id: Option<i64>,
+        now: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            INSERT INTO notifications (user_id, kind, actor_id, post_id, comment_id, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            RETURNING id as "id!"
+            "#,
+            user_id,
+            kind,
+            actor_id,
+            post_id,
+            comment_id,
+            now
+        );
+        let id = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(id)
+    }
+
+    /// Lists up to `limit` notifications of `user_id` that come before
+    /// `before`, newest first; `None` starts at the newest.
+    pub async fn list_before(
+        &self,
+        user_id: i64,
+        limit: i64,
+        before: Option<PageCursor>,
+    ) -> Result<Vec<Notification>, AppError> {
+        let before_created_at = before.map(|cursor| cursor.created_at);
+        let before_id = before.map(|cursor| cursor.id);
+        let query = sqlx::query_as!(
+            Notification,
+            r#"
+            SELECT n.id as "id!", n.user_id, n.kind, n.actor_id, u.public_id as actor_public_id,
+                   u.username as actor_username, n.post_id, p.public_id as post_public_id,
+                   n.comment_id, n.read_at as "read_at: _", n.created_at as "created_at: _"
+            FROM notifications n
+            JOIN users u ON u.id = n.actor_id
+            JOIN posts p ON p.id = n.post_id
+            WHERE n.user_id = ?1
+              AND (?2 IS NULL OR n.created_at < ?2 OR (n.created_at = ?2 AND n.id < ?3))
+            ORDER BY n.created_at DESC, n.id DESC
+            LIMIT ?4
+            "#,
+            user_id,
+            before_created_at,
+            before_id,
+            limit
+        );
+        let notifications = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(notifications)
+    }
+
+    /// Counts unread notifications of `user_id`.
+    pub async fn count_unread(&self, user_id: i64) -> Result<i64, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count: i64"
+            FROM notifications
+            WHERE user_id = ? AND read_at IS NULL
+            "#,
+            user_id
+        );
+        let result = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(result)
+    }
+
+    /// Marks unread notifications of `user_id` as read, only up to
+    /// `up_to_id` if given. Returns how many were marked.
+    pub async fn mark_read(
+        &self,
+        user_id: i64,
+        up_to_id: Option<i64>,
+        now: DateTime<Utc>,
+    ) -> Result<u64, AppError> {
+        let query = sqlx::query!(
+            r#"
+            UPDATE notifications SET read_at = ?1
+            WHERE user_id = ?2 AND read_at IS NULL AND (?3 IS NULL OR id <= ?3)
+            "#,
+            now,
+            user_id,
+            up_to_id
+        );
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,216 @@
+//! Organization repository: organizations and their members.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::domain::{AppError, Membership, OrgMember, Organization};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for organization database operations.
+#[derive(Clone)]
+pub struct OrgRepository {
+    pool: SqlitePool,
+}
+
+impl OrgRepository {
+    /// Creates a new OrgRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Creates an organization with `owner_id` as its first owner, in one
+    /// transaction. Expects `slug` normalized and not taken.
+    pub async fn create(
+        &self,
+        slug: &str,
+        name: &str,
+        bio: Option<&str>,
+        owner_id: i64,
+        now: DateTime<Utc>,
+    ) -> Result<Organization, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let query = sqlx::query_as!(
+            Organization,
+            r#"
+            INSERT INTO organizations (slug, name, bio, created_at)
+            VALUES (?, ?, ?, ?)
+            RETURNING id as "id!", slug, name, bio, created_at as "created_at: _"
+            "#,
+            slug,
+            name,
+            bio,
+            now
+        );
+        let org = timed_query(query.fetch_one(&mut *tx)).await?;
+
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO organization_members (org_id, user_id, role, joined_at)
+            VALUES (?, ?, 'owner', ?)
+            "#,
+            org.id,
+            owner_id,
+            now
+        );
+        timed_query(query.execute(&mut *tx)).await?;
+
+        tx.commit().await?;
+
+        Ok(org)
+    }
+
+    /// Finds an organization by its normalized slug.
+    pub async fn find_by_slug(&self, slug: &str) -> Result<Option<Organization>, AppError> {
+        let query = sqlx::query_as!(
+            Organization,
+            r#"
+            SELECT id as "id!", slug, name, bio, created_at as "created_at: _"
+            FROM organizations
+            WHERE slug = ?
+            "#,
+            slug
+        );
+        let org = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(org)
+    }
+
+    /// Finds the organization a post is published under, if any.
+    pub async fn find_post_org(&self, post_id: i64) -> Result<Option<Organization>, AppError> {
+        let query = sqlx::query_as!(
+            Organization,
+            r#"
+            SELECT o.id as "id!", o.slug, o.name, o.bio, o.created_at as "created_at: _"
+            FROM organizations o
+            JOIN posts p ON p.org_id = o.id
+            WHERE p.id = ?
+            "#,
+            post_id
+        );
+        let org = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(org)
+    }
+
+    /// Returns the stored role of `user_id` in organization `org_id`, if a member.
+    pub async fn member_role(&self, org_id: i64, user_id: i64) -> Result<Option<String>, AppError> {
+        let query = sqlx::query_scalar!(
+            "SELECT role FROM organization_members WHERE org_id = ? AND user_id = ?",
+            org_id,
+            user_id
+        );
+        let role = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(role)
+    }
+
+    /// Returns the stored role of `user_id` in the organization post
+    /// `post_id` is published under, if the post has one and they are a member.
+    pub async fn post_member_role(
+        &self,
+        post_id: i64,
+        user_id: i64,
+    ) -> Result<Option<String>, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            SELECT m.role
+            FROM organization_members m
+            JOIN posts p ON p.org_id = m.org_id
+            WHERE p.id = ? AND m.user_id = ?
+            "#,
+            post_id,
+            user_id
+        );
+        let role = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(role)
+    }
+
+    /// Lists the members of an organization, owners first, then by username.
+    pub async fn list_members(&self, org_id: i64) -> Result<Vec<OrgMember>, AppError> {
+        let query = sqlx::query_as!(
+            OrgMember,
+            r#"
+            SELECT m.user_id, u.public_id, u.username, m.role, m.joined_at as "joined_at: _"
+            FROM organization_members m
+            JOIN users u ON u.id = m.user_id
+            WHERE m.org_id = ?
+            ORDER BY m.role = 'owner' DESC, u.username
+            "#,
+            org_id
+        );
+        let members = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(members)
+    }
+
+    /// Adds a member to an organization, or changes the role of one.
+    pub async fn set_member(
+        &self,
+        org_id: i64,
+        user_id: i64,
+        role: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO organization_members (org_id, user_id, role, joined_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (org_id, user_id) DO UPDATE SET role = excluded.role
+            "#,
+            org_id,
+            user_id,
+            role,
+            now
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Removes a member from an organization. Returns whether they were one.
+    pub async fn remove_member(&self, org_id: i64, user_id: i64) -> Result<bool, AppError> {
+        let query = sqlx::query!(
+            "DELETE FROM organization_members WHERE org_id = ? AND user_id = ?",
+            org_id,
+            user_id
+        );
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Counts the owners of an organization.
+    pub async fn count_owners(&self, org_id: i64) -> Result<i64, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count!: i64"
+            FROM organization_members
+            WHERE org_id = ? AND role = 'owner'
+            "#,
+            org_id
+        );
+        let count = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(count)
+    }
+
+    /// Lists the organizations a user belongs to, by name.
+    pub async fn list_memberships(&self, user_id: i64) -> Result<Vec<Membership>, AppError> {
+        let query = sqlx::query_as!(
+            Membership,
+            r#"
+            SELECT o.id as "id!", o.slug, o.name, o.bio, o.created_at as "created_at: _", m.role
+            FROM organizations o
+            JOIN organization_members m ON m.org_id = o.id
+            WHERE m.user_id = ?
+            ORDER BY o.name, o.id
+            "#,
+            user_id
+        );
+        let memberships = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(memberships)
+    }
+}
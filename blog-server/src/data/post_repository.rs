@@ -1,132 +1,472 @@
 //! Post repository for database operations.
 
+mod links;
+mod orgs;
+mod revisions;
+mod search;
+mod tags;
+mod transitions;
+mod trash;
+
+use blog_shared::TagSort;
+use chrono::{DateTime, Utc};
 use sqlx::SqlitePool;
 
-use crate::domain::{AppError, Post};
+use crate::domain::{AppError, AuthorName, EntityRef, NewPost, Post, TagUsage, new_public_id};
+use crate::infrastructure::content_cipher::{self, ContentCipher};
+use crate::infrastructure::metrics::timed_query;
 
 /// Repository for post-related database operations.
 #[derive(Clone)]
 pub struct PostRepository {
     pool: SqlitePool,
+    cipher: Option<ContentCipher>,
 }
 
 impl PostRepository {
     /// Creates a new PostRepository.
     pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+        Self { pool, cipher: None }
     }
 
-    /// Creates a new post.
-    pub async fn create(
-        &self,
-        title: &str,
-        content: &str,
-        author_id: i64,
-    ) -> Result<Post, AppError> {
-        let now = chrono::Utc::now();
-        let post = sqlx::query_as!(
+    /// Encrypts post content at rest with `cipher`.
+    pub fn with_cipher(mut self, cipher: ContentCipher) -> Self {
+        self.cipher = Some(cipher);
+        self
+    }
+
+    /// Decrypts the content of posts read from the database.
+    fn open(&self, mut posts: Vec<Post>) -> Result<Vec<Post>, AppError> {
+        for post in &mut posts {
+            content_cipher::open(self.cipher.as_ref(), &mut post.content)?;
+        }
+        Ok(posts)
+    }
+
+    /// Decrypts the content of a post read from the database.
+    fn open_one(&self, post: Option<Post>) -> Result<Option<Post>, AppError> {
+        Ok(self.open(post.into_iter().collect())?.pop())
+    }
+
+    /// Creates a new post in its language and workflow status, as a
+    /// translation in its translation group or else in a group of its own.
+    /// The post as created is saved as revision 1.
+    pub async fn create(&self, new: NewPost<'_>) -> Result<Post, AppError> {
+        let NewPost {
+            title,
+            content,
+            author_id,
+            lang,
+            translation_group_id,
+            status,
+            now,
+        } = new;
+        let public_id = new_public_id();
+        let translation_group_id = translation_group_id.unwrap_or(&public_id);
+        let stored = content_cipher::seal(self.cipher.as_ref(), content)?;
+        let status = status.as_str();
+        let query = sqlx::query_as!(
             Post,
             r#"
-            INSERT INTO posts (title, content, author_id, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?)
-            RETURNING id as "id!", title, content, author_id, created_at as "created_at: _", updated_at as "updated_at: _"
+            INSERT INTO posts (public_id, title, content, author_id, lang, translation_group_id, status, created_at, updated_at, modified_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            RETURNING id as "id!", public_id, title, content, author_id, lang, translation_group_id, canonical_url, original_source, metadata, status, created_at as "created_at: _", updated_at as "updated_at: _"
             "#,
+            public_id,
             title,
-            content,
+            stored,
             author_id,
+            lang,
+            translation_group_id,
+            status,
+            now,
             now,
             now
-        )
-        .fetch_one(&self.pool)
-        .await?;
+        );
+        let mut tx = self.pool.begin().await?;
+        let mut post = timed_query(query.fetch_one(&mut *tx)).await?;
+        revisions::insert_revision(&mut tx, post.id, title, &stored, now).await?;
+        tx.commit().await?;
+        post.content = content.to_string();
 
         Ok(post)
     }
 
-    /// Finds a post by ID.
+    /// Finds a post by ID, unless it is in the trash.
     pub async fn find_by_id(&self, id: i64) -> Result<Option<Post>, AppError> {
-        let post = sqlx::query_as!(
+        let query = sqlx::query_as!(
             Post,
             r#"
-            SELECT id as "id!", title, content, author_id, created_at as "created_at: _", updated_at as "updated_at: _"
+            SELECT id as "id!", public_id, title, content, author_id, lang, translation_group_id, canonical_url, original_source, metadata, status, created_at as "created_at: _", updated_at as "updated_at: _"
             FROM posts
-            WHERE id = ?
+            WHERE id = ? AND deleted_at IS NULL
             "#,
             id
-        )
-        .fetch_optional(&self.pool)
-        .await?;
+        );
+        let post = timed_query(query.fetch_optional(&self.pool)).await?;
 
-        Ok(post)
+        self.open_one(post)
+    }
+
+    /// Finds the ID of a post named by public or numeric ID; numeric IDs
+    /// are returned as is.
+    pub async fn resolve_id(&self, post: &EntityRef) -> Result<Option<i64>, AppError> {
+        let public_id = match post {
+            EntityRef::Id(id) => return Ok(Some(*id)),
+            EntityRef::Public(public_id) => public_id,
+        };
+        let query = sqlx::query_scalar!(
+            r#"SELECT id as "id!" FROM posts WHERE public_id = ?"#,
+            public_id
+        );
+        let id = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(id)
     }
 
-    /// Lists posts with pagination, ordered by created_at descending.
-    pub async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Post>, AppError> {
-        let posts = sqlx::query_as!(
+    /// Finds a post by ID unless it is in the trash, or its author is
+    /// shadow-banned and not `viewer_id`.
+    pub async fn find_visible(
+        &self,
+        id: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<Option<Post>, AppError> {
+        let query = sqlx::query_as!(
             Post,
             r#"
-            SELECT id as "id!", title, content, author_id, created_at as "created_at: _", updated_at as "updated_at: _"
-            FROM posts
-            ORDER BY created_at DESC
+            SELECT p.id as "id!", p.public_id, p.title, p.content, p.author_id,
+                   p.lang, p.translation_group_id, p.canonical_url, p.original_source, p.metadata, p.status,
+                   p.created_at as "created_at: _", p.updated_at as "updated_at: _"
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            WHERE p.id = ? AND p.deleted_at IS NULL AND ((u.shadow_banned_at IS NULL AND p.status = 'published') OR p.author_id = ?)
+            "#,
+            id,
+            viewer_id
+        );
+        let post = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        self.open_one(post)
+    }
+
+    /// Lists posts visible to `viewer_id` with pagination, ordered by created_at descending.
+    /// With `lang`, only posts in that language are listed.
+    ///
+    /// Posts of shadow-banned authors are only visible to the authors themselves.
+    pub async fn list(
+        &self,
+        limit: i64,
+        offset: i64,
+        viewer_id: Option<i64>,
+        lang: Option<&str>,
+    ) -> Result<Vec<Post>, AppError> {
+        let query = sqlx::query_as!(
+            Post,
+            r#"
+            SELECT p.id as "id!", p.public_id, p.title, p.content, p.author_id,
+                   p.lang, p.translation_group_id, p.canonical_url, p.original_source, p.metadata, p.status,
+                   p.created_at as "created_at: _", p.updated_at as "updated_at: _"
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            WHERE p.deleted_at IS NULL AND ((u.shadow_banned_at IS NULL AND p.status = 'published') OR p.author_id = ?) AND (? IS NULL OR p.lang = ?)
+            ORDER BY p.created_at DESC
             LIMIT ? OFFSET ?
             "#,
+            viewer_id,
+            lang,
+            lang,
             limit,
             offset
-        )
-        .fetch_all(&self.pool)
-        .await?;
+        );
+        let posts = timed_query(query.fetch_all(&self.pool)).await?;
 
-        Ok(posts)
+        self.open(posts)
     }
 
-    /// Counts total posts.
-    pub async fn count(&self) -> Result<i64, AppError> {
-        let result = sqlx::query_scalar!(r#"SELECT COUNT(*) as "count: i64" FROM posts"#)
-            .fetch_one(&self.pool)
-            .await?;
+    /// Finds when the newest change to a publicly visible post was made.
+    pub async fn latest_update(&self) -> Result<Option<DateTime<Utc>>, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            SELECT MAX(p.updated_at) as "updated_at: DateTime<Utc>"
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            WHERE p.deleted_at IS NULL AND u.shadow_banned_at IS NULL AND p.status = 'published'
+            "#
+        );
+        let updated_at = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(updated_at)
+    }
+
+    /// Finds when anything shown about a post last changed.
+    pub async fn modified_at(&self, id: i64) -> Result<Option<DateTime<Utc>>, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"SELECT modified_at as "modified_at: DateTime<Utc>" FROM posts WHERE id = ?"#,
+            id
+        );
+        let modified_at = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(modified_at.flatten())
+    }
+
+    /// Finds when any post last changed, trashed ones included, so a post
+    /// leaving the lists counts too.
+    pub async fn latest_modification(&self) -> Result<Option<DateTime<Utc>>, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"SELECT MAX(modified_at) as "modified_at: DateTime<Utc>" FROM posts"#
+        );
+        let modified_at = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(modified_at)
+    }
+
+    /// Counts posts visible to `viewer_id`, only those in `lang` if given.
+    pub async fn count(&self, viewer_id: Option<i64>, lang: Option<&str>) -> Result<i64, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count: i64"
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            WHERE p.deleted_at IS NULL AND ((u.shadow_banned_at IS NULL AND p.status = 'published') OR p.author_id = ?) AND (? IS NULL OR p.lang = ?)
+            "#,
+            viewer_id,
+            lang,
+            lang
+        );
+        let result = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(result)
+    }
+
+    /// Lists posts with a tag visible to `viewer_id`, ordered by created_at descending.
+    pub async fn list_by_tag(
+        &self,
+        tag: &str,
+        limit: i64,
+        offset: i64,
+        viewer_id: Option<i64>,
+        lang: Option<&str>,
+    ) -> Result<Vec<Post>, AppError> {
+        let query = sqlx::query_as!(
+            Post,
+            r#"
+            SELECT p.id as "id!", p.public_id, p.title, p.content, p.author_id,
+                   p.lang, p.translation_group_id, p.canonical_url, p.original_source, p.metadata, p.status,
+                   p.created_at as "created_at: _", p.updated_at as "updated_at: _"
+            FROM posts p
+            JOIN post_tags pt ON pt.post_id = p.id
+            JOIN tags t ON t.id = pt.tag_id
+            JOIN users u ON u.id = p.author_id
+            WHERE t.name = ? AND p.deleted_at IS NULL AND ((u.shadow_banned_at IS NULL AND p.status = 'published') OR p.author_id = ?)
+              AND (? IS NULL OR p.lang = ?)
+            ORDER BY p.created_at DESC
+            LIMIT ? OFFSET ?
+            "#,
+            tag,
+            viewer_id,
+            lang,
+            lang,
+            limit,
+            offset
+        );
+        let posts = timed_query(query.fetch_all(&self.pool)).await?;
+
+        self.open(posts)
+    }
+
+    /// Counts posts with a tag visible to `viewer_id`.
+    pub async fn count_by_tag(
+        &self,
+        tag: &str,
+        viewer_id: Option<i64>,
+        lang: Option<&str>,
+    ) -> Result<i64, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count: i64"
+            FROM post_tags pt
+            JOIN tags t ON t.id = pt.tag_id
+            JOIN posts p ON p.id = pt.post_id
+            JOIN users u ON u.id = p.author_id
+            WHERE t.name = ? AND p.deleted_at IS NULL AND ((u.shadow_banned_at IS NULL AND p.status = 'published') OR p.author_id = ?)
+              AND (? IS NULL OR p.lang = ?)
+            "#,
+            tag,
+            viewer_id,
+            lang,
+            lang
+        );
+        let result = timed_query(query.fetch_one(&self.pool)).await?;
 
         Ok(result)
     }
 
-    /// Updates a post. Only provided fields are updated.
+    /// Lists the language variants in a translation group visible to
+    /// `viewer_id`, by language.
+    pub async fn list_translations(
+        &self,
+        translation_group_id: &str,
+        viewer_id: Option<i64>,
+    ) -> Result<Vec<Post>, AppError> {
+        let query = sqlx::query_as!(
+            Post,
+            r#"
+            SELECT p.id as "id!", p.public_id, p.title, p.content, p.author_id,
+                   p.lang, p.translation_group_id, p.canonical_url, p.original_source, p.metadata, p.status,
+                   p.created_at as "created_at: _", p.updated_at as "updated_at: _"
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            WHERE p.translation_group_id = ? AND p.deleted_at IS NULL AND ((u.shadow_banned_at IS NULL AND p.status = 'published') OR p.author_id = ?)
+            ORDER BY p.lang
+            "#,
+            translation_group_id,
+            viewer_id
+        );
+        let posts = timed_query(query.fetch_all(&self.pool)).await?;
+
+        self.open(posts)
+    }
+
+    /// Whether a translation group already has a post in `lang`.
+    pub async fn has_translation(
+        &self,
+        translation_group_id: &str,
+        lang: &str,
+    ) -> Result<bool, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count: i64" FROM posts WHERE translation_group_id = ? AND lang = ?"#,
+            translation_group_id,
+            lang
+        );
+        let count = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(count > 0)
+    }
+
+    /// Sets the creation and update time of an imported post.
+    pub async fn backdate(&self, id: i64, published_at: DateTime<Utc>) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            "UPDATE posts SET created_at = ?, updated_at = ? WHERE id = ?",
+            published_at,
+            published_at,
+            id
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Updates a post. Only provided fields are updated; a changed title or
+    /// content is saved as a new revision.
     pub async fn update(
         &self,
         id: i64,
         title: Option<&str>,
         content: Option<&str>,
+        now: DateTime<Utc>,
     ) -> Result<Post, AppError> {
-        let now = chrono::Utc::now();
-
         // Get current post to preserve unchanged fields
         let current = self.find_by_id(id).await?.ok_or(AppError::PostNotFound)?;
 
         let new_title = title.unwrap_or(&current.title);
         let new_content = content.unwrap_or(&current.content);
+        let stored = content_cipher::seal(self.cipher.as_ref(), new_content)?;
 
-        let post = sqlx::query_as!(
+        let query = sqlx::query_as!(
             Post,
             r#"
             UPDATE posts
-            SET title = ?, content = ?, updated_at = ?
+            SET title = ?, content = ?, updated_at = ?, modified_at = ?
             WHERE id = ?
-            RETURNING id as "id!", title, content, author_id, created_at as "created_at: _", updated_at as "updated_at: _"
+            RETURNING id as "id!", public_id, title, content, author_id, lang, translation_group_id, canonical_url, original_source, metadata, status, created_at as "created_at: _", updated_at as "updated_at: _"
             "#,
             new_title,
-            new_content,
+            stored,
+            now,
             now,
             id
-        )
-        .fetch_one(&self.pool)
-        .await?;
+        );
+        let mut tx = self.pool.begin().await?;
+        let mut post = timed_query(query.fetch_one(&mut *tx)).await?;
+        if new_title != current.title || new_content != current.content {
+            revisions::insert_revision(&mut tx, id, new_title, &stored, now).await?;
+        }
+        tx.commit().await?;
+        post.content = new_content.to_string();
 
         Ok(post)
     }
 
-    /// Deletes a post by ID.
-    pub async fn delete(&self, id: i64) -> Result<(), AppError> {
-        let result = sqlx::query!("DELETE FROM posts WHERE id = ?", id)
-            .execute(&self.pool)
-            .await?;
+    /// Replaces the canonical URL and original source of a post, leaving
+    /// `updated_at` alone.
+    pub async fn set_sources(
+        &self,
+        id: i64,
+        canonical_url: Option<&str>,
+        original_source: Option<&str>,
+    ) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            "UPDATE posts SET canonical_url = ?, original_source = ? WHERE id = ?",
+            canonical_url,
+            original_source,
+            id
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Replaces the custom fields of a post (a JSON object), leaving
+    /// `updated_at` alone.
+    pub async fn set_metadata(
+        &self,
+        id: i64,
+        metadata: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            "UPDATE posts SET metadata = ?, modified_at = ? WHERE id = ?",
+            metadata,
+            now,
+            id
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Re-encrypts every post and revision not yet sealed with the active
+    /// key, leaving `updated_at` alone. Returns how many rows were rewritten.
+    pub async fn reseal_all(&self) -> Result<u64, AppError> {
+        let Some(cipher) = &self.cipher else {
+            return Ok(0);
+        };
+        let query = sqlx::query!(r#"SELECT id as "id!", content FROM posts"#);
+        let rows = timed_query(query.fetch_all(&self.pool)).await?;
+
+        let mut resealed = 0;
+        for row in rows
+            .into_iter()
+            .filter(|row| !cipher.is_current(&row.content))
+        {
+            let content = cipher.seal(&cipher.open(&row.content)?)?;
+            let query = sqlx::query!("UPDATE posts SET content = ? WHERE id = ?", content, row.id);
+            timed_query(query.execute(&self.pool)).await?;
+            resealed += 1;
+        }
+
+        Ok(resealed + self.reseal_revisions().await?)
+    }
+
+    /// Moves a post to its author's trash.
+    pub async fn delete(&self, id: i64, now: DateTime<Utc>) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            "UPDATE posts SET deleted_at = ?, modified_at = ? WHERE id = ? AND deleted_at IS NULL",
+            now,
+            now,
+            id
+        );
+        let result = timed_query(query.execute(&self.pool)).await?;
 
         if result.rows_affected() == 0 {
             return Err(AppError::PostNotFound);
@@ -135,14 +475,107 @@ impl PostRepository {
         Ok(())
     }
 
+    /// Finds the tags of a post, alphabetically.
+    pub async fn find_tags(&self, post_id: i64) -> Result<Vec<String>, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            SELECT t.name
+            FROM tags t
+            JOIN post_tags pt ON pt.tag_id = t.id
+            WHERE pt.post_id = ?
+            ORDER BY t.name
+            "#,
+            post_id
+        );
+        let tags = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(tags)
+    }
+
+    /// Replaces the tags of a post, creating unknown tags.
+    ///
+    /// Expects tags already normalized (see `domain::normalize_tags`).
+    pub async fn set_tags(&self, post_id: i64, tags: &[String]) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let query = sqlx::query!("DELETE FROM post_tags WHERE post_id = ?", post_id);
+        timed_query(query.execute(&mut *tx)).await?;
+
+        for tag in tags {
+            let query = sqlx::query!(
+                "INSERT INTO tags (name) VALUES (?) ON CONFLICT(name) DO NOTHING",
+                tag
+            );
+            timed_query(query.execute(&mut *tx)).await?;
+
+            let query = sqlx::query!(
+                "INSERT INTO post_tags (post_id, tag_id) SELECT ?, id FROM tags WHERE name = ?",
+                post_id,
+                tag
+            );
+            timed_query(query.execute(&mut *tx)).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Lists tags of posts visible to `viewer_id` with their post counts in
+    /// `sort` order; `popular` ranks by posts created since `recent_since`.
+    pub async fn list_tags(
+        &self,
+        limit: i64,
+        viewer_id: Option<i64>,
+        sort: TagSort,
+        recent_since: DateTime<Utc>,
+    ) -> Result<Vec<TagUsage>, AppError> {
+        let sort = sort.as_str();
+        let query = sqlx::query_as!(
+            TagUsage,
+            r#"
+            SELECT t.name, t.description, COUNT(*) as "post_count: i64"
+            FROM tags t
+            JOIN post_tags pt ON pt.tag_id = t.id
+            JOIN posts p ON p.id = pt.post_id
+            JOIN users u ON u.id = p.author_id
+            WHERE p.deleted_at IS NULL AND ((u.shadow_banned_at IS NULL AND p.status = 'published') OR p.author_id = ?)
+            GROUP BY t.id
+            ORDER BY CASE WHEN ? = 'popular' THEN SUM(p.created_at >= ?) END DESC,
+                     CASE WHEN ? = 'name' THEN t.name END,
+                     COUNT(*) DESC, t.name
+            LIMIT ?
+            "#,
+            viewer_id,
+            sort,
+            recent_since,
+            sort,
+            limit
+        );
+        let tags = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(tags)
+    }
+
     /// Finds the author username for a given author_id.
     pub async fn find_author_username(&self, author_id: i64) -> Result<String, AppError> {
-        let result = sqlx::query_scalar!(
+        let query = sqlx::query_scalar!(
             r#"SELECT username as "username!" FROM users WHERE id = ?"#,
             author_id
-        )
-        .fetch_optional(&self.pool)
-        .await?;
+        );
+        let result = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        result.ok_or(AppError::UserNotFound)
+    }
+
+    /// Finds the username and public ID of the user with `author_id`.
+    pub async fn find_author(&self, author_id: i64) -> Result<AuthorName, AppError> {
+        let query = sqlx::query_as!(
+            AuthorName,
+            "SELECT public_id, username FROM users WHERE id = ?",
+            author_id
+        );
+        let result = timed_query(query.fetch_optional(&self.pool)).await?;
 
         result.ok_or(AppError::UserNotFound)
     }
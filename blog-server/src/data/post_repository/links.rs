@@ -0,0 +1,54 @@
+//! External links of posts and their cached previews.
+
+use crate::data::PostRepository;
+use crate::domain::{AppError, LinkPreview};
+use crate::infrastructure::metrics::timed_query;
+
+impl PostRepository {
+    /// Replaces the external links of a post, queueing unseen URLs for preview.
+    pub async fn set_links(&self, post_id: i64, urls: &[String]) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let query = sqlx::query!("DELETE FROM post_links WHERE post_id = ?", post_id);
+        timed_query(query.execute(&mut *tx)).await?;
+
+        for (position, url) in urls.iter().enumerate() {
+            let position = position as i64;
+            let query = sqlx::query!(
+                "INSERT INTO post_links (post_id, url, position) VALUES (?, ?, ?)",
+                post_id,
+                url,
+                position
+            );
+            timed_query(query.execute(&mut *tx)).await?;
+
+            let query = sqlx::query!(
+                "INSERT INTO link_previews (url) VALUES (?) ON CONFLICT(url) DO NOTHING",
+                url
+            );
+            timed_query(query.execute(&mut *tx)).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Finds the fetched previews of a post's links, in link order.
+    pub async fn find_link_previews(&self, post_id: i64) -> Result<Vec<LinkPreview>, AppError> {
+        let query = sqlx::query_as!(
+            LinkPreview,
+            r#"
+            SELECT lp.url, lp.title as "title!", lp.description, lp.image_url, lp.site_name
+            FROM post_links pl
+            JOIN link_previews lp ON lp.url = pl.url
+            WHERE pl.post_id = ? AND lp.title IS NOT NULL
+            ORDER BY pl.position
+            "#,
+            post_id
+        );
+        let previews = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(previews)
+    }
+}
@@ -0,0 +1,66 @@
+//! Posts published under organizations.
+
+use crate::data::PostRepository;
+use crate::domain::{AppError, Post};
+use crate::infrastructure::metrics::timed_query;
+
+impl PostRepository {
+    /// Publishes a post under organization `org_id`.
+    pub async fn set_org(&self, post_id: i64, org_id: i64) -> Result<(), AppError> {
+        let query = sqlx::query!("UPDATE posts SET org_id = ? WHERE id = ?", org_id, post_id);
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::PostNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Lists posts of an organization visible to `viewer_id`, newest first.
+    pub async fn list_by_org(
+        &self,
+        org_id: i64,
+        limit: i64,
+        offset: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<Vec<Post>, AppError> {
+        let query = sqlx::query_as!(
+            Post,
+            r#"
+            SELECT p.id as "id!", p.public_id, p.title, p.content, p.author_id,
+                   p.lang, p.translation_group_id, p.canonical_url, p.original_source, p.metadata, p.status,
+                   p.created_at as "created_at: _", p.updated_at as "updated_at: _"
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            WHERE p.org_id = ? AND p.deleted_at IS NULL AND ((u.shadow_banned_at IS NULL AND p.status = 'published') OR p.author_id = ?)
+            ORDER BY p.created_at DESC
+            LIMIT ? OFFSET ?
+            "#,
+            org_id,
+            viewer_id,
+            limit,
+            offset
+        );
+        let posts = timed_query(query.fetch_all(&self.pool)).await?;
+
+        self.open(posts)
+    }
+
+    /// Counts posts of an organization visible to `viewer_id`.
+    pub async fn count_by_org(&self, org_id: i64, viewer_id: Option<i64>) -> Result<i64, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count: i64"
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            WHERE p.org_id = ? AND p.deleted_at IS NULL AND ((u.shadow_banned_at IS NULL AND p.status = 'published') OR p.author_id = ?)
+            "#,
+            org_id,
+            viewer_id
+        );
+        let count = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(count)
+    }
+}
@@ -0,0 +1,109 @@
+//! Saved versions of posts.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+
+use crate::data::PostRepository;
+use crate::domain::{AppError, PostRevision};
+use crate::infrastructure::content_cipher;
+use crate::infrastructure::metrics::timed_query;
+
+impl PostRepository {
+    /// Lists the revisions of a post, newest first.
+    pub async fn list_revisions(&self, post_id: i64) -> Result<Vec<PostRevision>, AppError> {
+        let query = sqlx::query_as!(
+            PostRevision,
+            r#"
+            SELECT post_id, number, title, content, created_at as "created_at: _"
+            FROM post_revisions
+            WHERE post_id = ?
+            ORDER BY number DESC
+            "#,
+            post_id
+        );
+        let mut revisions = timed_query(query.fetch_all(&self.pool)).await?;
+        for revision in &mut revisions {
+            content_cipher::open(self.cipher.as_ref(), &mut revision.content)?;
+        }
+
+        Ok(revisions)
+    }
+
+    /// Finds revision `number` of a post.
+    pub async fn find_revision(
+        &self,
+        post_id: i64,
+        number: i64,
+    ) -> Result<Option<PostRevision>, AppError> {
+        let query = sqlx::query_as!(
+            PostRevision,
+            r#"
+            SELECT post_id, number, title, content, created_at as "created_at: _"
+            FROM post_revisions
+            WHERE post_id = ? AND number = ?
+            "#,
+            post_id,
+            number
+        );
+        let mut revision = timed_query(query.fetch_optional(&self.pool)).await?;
+        if let Some(revision) = &mut revision {
+            content_cipher::open(self.cipher.as_ref(), &mut revision.content)?;
+        }
+
+        Ok(revision)
+    }
+
+    /// Re-encrypts every revision not yet sealed with the active key.
+    /// Returns how many revisions were rewritten.
+    pub(super) async fn reseal_revisions(&self) -> Result<u64, AppError> {
+        let Some(cipher) = &self.cipher else {
+            return Ok(0);
+        };
+        let query = sqlx::query!(r#"SELECT id as "id!", content FROM post_revisions"#);
+        let rows = timed_query(query.fetch_all(&self.pool)).await?;
+
+        let mut resealed = 0;
+        for row in rows
+            .into_iter()
+            .filter(|row| !cipher.is_current(&row.content))
+        {
+            let content = cipher.seal(&cipher.open(&row.content)?)?;
+            let query = sqlx::query!(
+                "UPDATE post_revisions SET content = ? WHERE id = ?",
+                content,
+                row.id
+            );
+            timed_query(query.execute(&self.pool)).await?;
+            resealed += 1;
+        }
+
+        Ok(resealed)
+    }
+}
+
+/// Saves a title and (already sealed) content as the next revision of
+/// post `post_id`, inside the caller's transaction.
+pub(super) async fn insert_revision(
+    conn: &mut SqliteConnection,
+    post_id: i64,
+    title: &str,
+    stored_content: &str,
+    now: DateTime<Utc>,
+) -> Result<(), AppError> {
+    let query = sqlx::query!(
+        r#"
+        INSERT INTO post_revisions (post_id, number, title, content, created_at)
+        SELECT ?, COALESCE(MAX(number), 0) + 1, ?, ?, ?
+        FROM post_revisions
+        WHERE post_id = ?
+        "#,
+        post_id,
+        title,
+        stored_content,
+        now,
+        post_id
+    );
+    timed_query(query.execute(conn)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,152 @@
+//! Full-text search over post titles and content.
+
+use crate::constants::{
+    SEARCH_SNIPPET_ELLIPSIS, SEARCH_SNIPPET_MARK_END, SEARCH_SNIPPET_MARK_START,
+    SEARCH_SNIPPET_TOKENS, SEARCH_TITLE_WEIGHT,
+};
+use crate::data::PostRepository;
+use crate::domain::{AppError, IndexedTerm, SearchFilters, SearchHit};
+use crate::infrastructure::metrics::timed_query;
+
+impl PostRepository {
+    /// Full-text searches titles and content, best matches first (BM25,
+    /// titles weighted `SEARCH_TITLE_WEIGHT`), each with its best matching
+    /// passage from FTS5 `snippet()`.
+    ///
+    /// `match_expr` must be a valid FTS5 query (see `domain::match_expr`).
+    pub async fn search(
+        &self,
+        match_expr: &str,
+        filters: &SearchFilters,
+        limit: i64,
+        offset: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<Vec<SearchHit>, AppError> {
+        // Encrypted content is indexed as ciphertext, so only titles make
+        // readable snippets
+        let snippet_column: i64 = if self.cipher.is_some() { 0 } else { -1 };
+        let query = sqlx::query_as!(
+            SearchHit,
+            r#"
+            SELECT p.id as "post_id!", snippet(posts_fts, ?, ?, ?, ?, ?) as "snippet!: String"
+            FROM posts_fts
+            JOIN posts p ON p.id = posts_fts.rowid
+            JOIN users u ON u.id = p.author_id
+            WHERE posts_fts MATCH ? AND p.deleted_at IS NULL AND ((u.shadow_banned_at IS NULL AND p.status = 'published') OR p.author_id = ?)
+              AND (? IS NULL OR p.lang = ?)
+              AND (? IS NULL OR u.username_normalized = ?)
+              AND (? IS NULL OR EXISTS (
+                  SELECT 1 FROM post_tags pt JOIN tags t ON t.id = pt.tag_id
+                  WHERE pt.post_id = p.id AND t.name = ?))
+              AND (? IS NULL OR p.created_at >= ?)
+              AND (? IS NULL OR p.created_at < ?)
+            ORDER BY bm25(posts_fts, ?, 1.0), p.created_at DESC
+            LIMIT ? OFFSET ?
+            "#,
+            snippet_column,
+            SEARCH_SNIPPET_MARK_START,
+            SEARCH_SNIPPET_MARK_END,
+            SEARCH_SNIPPET_ELLIPSIS,
+            SEARCH_SNIPPET_TOKENS,
+            match_expr,
+            viewer_id,
+            filters.lang,
+            filters.lang,
+            filters.author,
+            filters.author,
+            filters.tag,
+            filters.tag,
+            filters.from,
+            filters.from,
+            filters.until,
+            filters.until,
+            SEARCH_TITLE_WEIGHT,
+            limit,
+            offset
+        );
+        let hits = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(hits)
+    }
+
+    /// Counts posts visible to `viewer_id` matching a full-text query and
+    /// filters.
+    pub async fn count_search(
+        &self,
+        match_expr: &str,
+        filters: &SearchFilters,
+        viewer_id: Option<i64>,
+    ) -> Result<i64, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count: i64"
+            FROM posts_fts
+            JOIN posts p ON p.id = posts_fts.rowid
+            JOIN users u ON u.id = p.author_id
+            WHERE posts_fts MATCH ? AND p.deleted_at IS NULL AND ((u.shadow_banned_at IS NULL AND p.status = 'published') OR p.author_id = ?)
+              AND (? IS NULL OR p.lang = ?)
+              AND (? IS NULL OR u.username_normalized = ?)
+              AND (? IS NULL OR EXISTS (
+                  SELECT 1 FROM post_tags pt JOIN tags t ON t.id = pt.tag_id
+                  WHERE pt.post_id = p.id AND t.name = ?))
+              AND (? IS NULL OR p.created_at >= ?)
+              AND (? IS NULL OR p.created_at < ?)
+            "#,
+            match_expr,
+            viewer_id,
+            filters.lang,
+            filters.lang,
+            filters.author,
+            filters.author,
+            filters.tag,
+            filters.tag,
+            filters.from,
+            filters.from,
+            filters.until,
+            filters.until
+        );
+        let result = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(result)
+    }
+
+    /// Lists indexed terms starting with `first` whose length is within
+    /// `min_len..=max_len` characters, the most common first.
+    pub async fn list_indexed_terms(
+        &self,
+        first: char,
+        min_len: i64,
+        max_len: i64,
+    ) -> Result<Vec<IndexedTerm>, AppError> {
+        let start = first.to_string();
+        let end = char::from_u32(u32::from(first) + 1)
+            .unwrap_or(char::MAX)
+            .to_string();
+        let query = sqlx::query_as!(
+            IndexedTerm,
+            r#"
+            SELECT term as "term!: String", doc as "posts!: i64"
+            FROM posts_fts_vocab
+            WHERE term >= ? AND term < ? AND length(term) BETWEEN ? AND ?
+            ORDER BY doc DESC
+            "#,
+            start,
+            end,
+            min_len,
+            max_len
+        );
+        let terms = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(terms)
+    }
+
+    /// Lists the IDs of all posts not in the trash, for reindexing.
+    pub async fn list_ids(&self) -> Result<Vec<i64>, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"SELECT id as "id!" FROM posts WHERE deleted_at IS NULL ORDER BY id"#
+        );
+        let ids = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(ids)
+    }
+}
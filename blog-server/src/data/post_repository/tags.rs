@@ -0,0 +1,173 @@
+//! Tag management: descriptions, renames, merges and deletion, and
+//! autocompletion.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+
+use crate::data::PostRepository;
+use crate::domain::{AppError, Tag, TagUsage, prefix_bound};
+use crate::infrastructure::metrics::timed_query;
+
+impl PostRepository {
+    /// Finds a tag by its normalized name.
+    pub async fn find_tag(&self, name: &str) -> Result<Option<Tag>, AppError> {
+        let query = sqlx::query_as!(
+            Tag,
+            r#"SELECT id as "id!", name, description FROM tags WHERE name = ?"#,
+            name
+        );
+        let tag = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(tag)
+    }
+
+    /// Suggests tags starting with `prefix` (normalized) among published
+    /// posts anyone can see: an exact match first, then the most used.
+    pub async fn suggest_tags(&self, prefix: &str, limit: i64) -> Result<Vec<TagUsage>, AppError> {
+        let bound = prefix_bound(prefix);
+        let query = sqlx::query_as!(
+            TagUsage,
+            r#"
+            SELECT t.name, t.description, COUNT(*) as "post_count: i64"
+            FROM tags t
+            JOIN post_tags pt ON pt.tag_id = t.id
+            JOIN posts p ON p.id = pt.post_id
+            JOIN users u ON u.id = p.author_id
+            WHERE t.name >= ? AND t.name < ?
+              AND p.deleted_at IS NULL AND p.status = 'published' AND u.shadow_banned_at IS NULL
+            GROUP BY t.id
+            ORDER BY t.name = ? DESC, COUNT(*) DESC, t.name
+            LIMIT ?
+            "#,
+            prefix,
+            bound,
+            prefix,
+            limit
+        );
+        let tags = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(tags)
+    }
+
+    /// Lists the IDs of the posts with tag `tag_id`.
+    pub async fn list_tagged_post_ids(&self, tag_id: i64) -> Result<Vec<i64>, AppError> {
+        let query = sqlx::query_scalar!("SELECT post_id FROM post_tags WHERE tag_id = ?", tag_id);
+        let ids = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(ids)
+    }
+
+    /// Renames a tag and sets its description. Expects `name` normalized and
+    /// not taken by another tag.
+    pub async fn update_tag(
+        &self,
+        id: i64,
+        name: &str,
+        description: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<Tag, AppError> {
+        let query = sqlx::query_as!(
+            Tag,
+            r#"
+            UPDATE tags SET name = ?, description = ? WHERE id = ?
+            RETURNING id as "id!", name, description
+            "#,
+            name,
+            description,
+            id
+        );
+        let mut tx = self.pool.begin().await?;
+        let tag = timed_query(query.fetch_optional(&mut *tx)).await?;
+        let tag = tag.ok_or(AppError::TagNotFound)?;
+        touch_tagged(&mut tx, id, now).await?;
+        tx.commit().await?;
+
+        Ok(tag)
+    }
+
+    /// Moves the posts of tag `source_id` to tag `target_id` and deletes the
+    /// source tag, in one transaction. The target keeps its description, or
+    /// takes the source's if it has none.
+    pub async fn merge_tags(
+        &self,
+        source_id: i64,
+        target_id: i64,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        touch_tagged(&mut tx, source_id, now).await?;
+
+        // Posts already tagged with both keep a single link
+        let query = sqlx::query!(
+            r#"
+            INSERT OR IGNORE INTO post_tags (post_id, tag_id)
+            SELECT post_id, ? FROM post_tags WHERE tag_id = ?
+            "#,
+            target_id,
+            source_id
+        );
+        timed_query(query.execute(&mut *tx)).await?;
+
+        let query = sqlx::query!(
+            r#"
+            UPDATE tags
+            SET description = COALESCE(description, (SELECT description FROM tags WHERE id = ?))
+            WHERE id = ?
+            "#,
+            source_id,
+            target_id
+        );
+        timed_query(query.execute(&mut *tx)).await?;
+
+        let query = sqlx::query!("DELETE FROM post_tags WHERE tag_id = ?", source_id);
+        timed_query(query.execute(&mut *tx)).await?;
+        let query = sqlx::query!("DELETE FROM tags WHERE id = ?", source_id);
+        let result = timed_query(query.execute(&mut *tx)).await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::TagNotFound);
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Deletes a tag and removes it from all posts.
+    pub async fn delete_tag(&self, id: i64, now: DateTime<Utc>) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        touch_tagged(&mut tx, id, now).await?;
+
+        let query = sqlx::query!("DELETE FROM post_tags WHERE tag_id = ?", id);
+        timed_query(query.execute(&mut *tx)).await?;
+        let query = sqlx::query!("DELETE FROM tags WHERE id = ?", id);
+        let result = timed_query(query.execute(&mut *tx)).await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::TagNotFound);
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
+
+/// Marks the posts with tag `tag_id` modified, as their tags change.
+async fn touch_tagged(
+    conn: &mut SqliteConnection,
+    tag_id: i64,
+    now: DateTime<Utc>,
+) -> Result<(), AppError> {
+    let query = sqlx::query!(
+        r#"
+        UPDATE posts SET modified_at = ?
+        WHERE id IN (SELECT post_id FROM post_tags WHERE tag_id = ?)
+        "#,
+        now,
+        tag_id
+    );
+    timed_query(query.execute(conn)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+//! Editorial workflow status changes of posts and their review notes.
+
+use blog_shared::PostStatus;
+
+use crate::data::PostRepository;
+use crate::domain::{AppError, NewTransition, Post, PostTransition, ReviewNote};
+use crate::infrastructure::metrics::timed_query;
+
+impl PostRepository {
+    /// Moves a post from `from` to `to` and logs the change by `actor_id`
+    /// with an optional comment and line notes, all in one transaction.
+    ///
+    /// Publishing also sets the creation and update time to now, so the
+    /// post shows up in lists and feeds as new.
+    pub async fn record_transition(&self, transition: NewTransition<'_>) -> Result<(), AppError> {
+        let NewTransition {
+            post_id,
+            actor_id,
+            from,
+            to,
+            comment,
+            notes,
+            now,
+        } = transition;
+        let from = from.as_str();
+        let to_str = to.as_str();
+        let mut tx = self.pool.begin().await?;
+
+        if to == PostStatus::Published {
+            let query = sqlx::query!(
+                "UPDATE posts SET status = ?, created_at = ?, updated_at = ?, modified_at = ? WHERE id = ?",
+                to_str,
+                now,
+                now,
+                now,
+                post_id
+            );
+            timed_query(query.execute(&mut *tx)).await?;
+        } else {
+            let query = sqlx::query!(
+                "UPDATE posts SET status = ?, modified_at = ? WHERE id = ?",
+                to_str,
+                now,
+                post_id
+            );
+            timed_query(query.execute(&mut *tx)).await?;
+        }
+
+        let query = sqlx::query_scalar!(
+            r#"
+            INSERT INTO post_transitions (post_id, actor_id, from_status, to_status, comment, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            RETURNING id as "id!"
+            "#,
+            post_id,
+            actor_id,
+            from,
+            to_str,
+            comment,
+            now
+        );
+        let transition_id = timed_query(query.fetch_one(&mut *tx)).await?;
+
+        for note in notes {
+            let query = sqlx::query!(
+                "INSERT INTO post_review_notes (transition_id, line, body) VALUES (?, ?, ?)",
+                transition_id,
+                note.line,
+                note.body
+            );
+            timed_query(query.execute(&mut *tx)).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Lists the status changes of a post, oldest first.
+    pub async fn list_transitions(&self, post_id: i64) -> Result<Vec<PostTransition>, AppError> {
+        let query = sqlx::query_as!(
+            PostTransition,
+            r#"
+            SELECT t.id as "id!", t.post_id, t.actor_id, u.username as actor_username,
+                   t.from_status, t.to_status, t.comment,
+                   t.created_at as "created_at: _"
+            FROM post_transitions t
+            JOIN users u ON u.id = t.actor_id
+            WHERE t.post_id = ?
+            ORDER BY t.id
+            "#,
+            post_id
+        );
+        let transitions = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(transitions)
+    }
+
+    /// Lists the review notes of a post's status changes, in line order.
+    pub async fn list_review_notes(&self, post_id: i64) -> Result<Vec<ReviewNote>, AppError> {
+        let query = sqlx::query_as!(
+            ReviewNote,
+            r#"
+            SELECT n.transition_id, n.line, n.body
+            FROM post_review_notes n
+            JOIN post_transitions t ON t.id = n.transition_id
+            WHERE t.post_id = ?
+            ORDER BY n.transition_id, n.line, n.id
+            "#,
+            post_id
+        );
+        let notes = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(notes)
+    }
+
+    /// Lists posts waiting for review, longest waiting first.
+    pub async fn list_in_review(&self, limit: i64, offset: i64) -> Result<Vec<Post>, AppError> {
+        let query = sqlx::query_as!(
+            Post,
+            r#"
+            SELECT id as "id!", public_id, title, content, author_id, lang, translation_group_id, canonical_url, original_source, metadata, status, created_at as "created_at: _", updated_at as "updated_at: _"
+            FROM posts
+            WHERE status = 'in_review' AND deleted_at IS NULL
+            ORDER BY (SELECT MAX(t.id) FROM post_transitions t WHERE t.post_id = posts.id)
+            LIMIT ? OFFSET ?
+            "#,
+            limit,
+            offset
+        );
+        let posts = timed_query(query.fetch_all(&self.pool)).await?;
+
+        self.open(posts)
+    }
+
+    /// Counts posts waiting for review.
+    pub async fn count_in_review(&self) -> Result<i64, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count: i64" FROM posts WHERE status = 'in_review' AND deleted_at IS NULL"#
+        );
+        let count = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(count)
+    }
+}
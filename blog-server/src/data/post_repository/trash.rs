@@ -0,0 +1,58 @@
+//! Deleted posts waiting in their authors' trash.
+
+use chrono::{DateTime, Utc};
+
+use crate::data::PostRepository;
+use crate::domain::{AppError, Post, TrashedPost};
+use crate::infrastructure::metrics::timed_query;
+
+impl PostRepository {
+    /// Lists an author's deleted posts, most recently deleted first.
+    pub async fn list_trash(&self, author_id: i64) -> Result<Vec<TrashedPost>, AppError> {
+        let query = sqlx::query_as!(
+            TrashedPost,
+            r#"
+            SELECT id as "id!", public_id, title, deleted_at as "deleted_at!: _"
+            FROM posts
+            WHERE author_id = ? AND deleted_at IS NOT NULL
+            ORDER BY deleted_at DESC, id DESC
+            "#,
+            author_id
+        );
+        let posts = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(posts)
+    }
+
+    /// Finds a post by ID if it is in the trash.
+    pub async fn find_trashed(&self, id: i64) -> Result<Option<Post>, AppError> {
+        let query = sqlx::query_as!(
+            Post,
+            r#"
+            SELECT id as "id!", public_id, title, content, author_id, lang, translation_group_id, canonical_url, original_source, metadata, status, created_at as "created_at: _", updated_at as "updated_at: _"
+            FROM posts
+            WHERE id = ? AND deleted_at IS NOT NULL
+            "#,
+            id
+        );
+        let post = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        self.open_one(post)
+    }
+
+    /// Takes a post out of the trash, leaving `updated_at` alone.
+    pub async fn restore(&self, id: i64, now: DateTime<Utc>) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            "UPDATE posts SET deleted_at = NULL, modified_at = ? WHERE id = ? AND deleted_at IS NOT NULL",
+            now,
+            id
+        );
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::PostNotFound);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+//! Recovery code repository for database operations.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::domain::AppError;
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for users' one-time recovery codes (stored hashed).
+#[derive(Clone)]
+pub struct RecoveryCodeRepository {
+    pool: SqlitePool,
+}
+
+impl RecoveryCodeRepository {
+    /// Creates a new RecoveryCodeRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Replaces all of a user's codes, used or not, with `code_hashes`.
+    pub async fn replace(
+        &self,
+        user_id: i64,
+        code_hashes: &[String],
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let query = sqlx::query!("DELETE FROM recovery_codes WHERE user_id = ?", user_id);
+        timed_query(query.execute(&mut *tx)).await?;
+
+        for code_hash in code_hashes {
+            let query = sqlx::query!(
+                "INSERT INTO recovery_codes (user_id, code_hash, created_at) VALUES (?, ?, ?)",
+                user_id,
+                code_hash,
+                now
+            );
+            timed_query(query.execute(&mut *tx)).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Marks an unused code of the user as used. Returns false if the user
+    /// has no such unused code.
+    pub async fn redeem(
+        &self,
+        user_id: i64,
+        code_hash: &str,
+        now: DateTime<Utc>,
+    ) -> Result<bool, AppError> {
+        let query = sqlx::query!(
+            r#"
+            UPDATE recovery_codes SET used_at = ?
+            WHERE user_id = ? AND code_hash = ? AND used_at IS NULL
+            "#,
+            now,
+            user_id,
+            code_hash
+        );
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,81 @@
+//! Retention repository: counts and removes rows past their retention time.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::domain::{AppError, RetentionPolicy};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for data retention jobs.
+#[derive(Clone)]
+pub struct RetentionRepository {
+    pool: SqlitePool,
+}
+
+impl RetentionRepository {
+    /// Creates a new RetentionRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Counts rows `policy` would remove for `cutoff`.
+    pub async fn count_due(
+        &self,
+        policy: RetentionPolicy,
+        cutoff: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        let count = match policy {
+            RetentionPolicy::AuditLog => {
+                let query = sqlx::query_scalar!(
+                    r#"SELECT COUNT(*) as "count: i64" FROM audit_log WHERE created_at < ?"#,
+                    cutoff
+                );
+                timed_query(query.fetch_one(&self.pool)).await?
+            }
+            RetentionPolicy::UnsubscribedEmails => {
+                let query = sqlx::query_scalar!(
+                    r#"SELECT COUNT(*) as "count: i64" FROM subscriptions WHERE unsubscribed_at < ?"#,
+                    cutoff
+                );
+                timed_query(query.fetch_one(&self.pool)).await?
+            }
+            RetentionPolicy::Trash => {
+                let query = sqlx::query_scalar!(
+                    r#"SELECT COUNT(*) as "count: i64" FROM posts WHERE deleted_at < ?"#,
+                    cutoff
+                );
+                timed_query(query.fetch_one(&self.pool)).await?
+            }
+        };
+
+        Ok(count)
+    }
+
+    /// Removes rows `policy` covers that are older than `cutoff`, returning
+    /// how many were removed.
+    pub async fn purge(
+        &self,
+        policy: RetentionPolicy,
+        cutoff: DateTime<Utc>,
+    ) -> Result<u64, AppError> {
+        let result = match policy {
+            RetentionPolicy::AuditLog => {
+                let query = sqlx::query!("DELETE FROM audit_log WHERE created_at < ?", cutoff);
+                timed_query(query.execute(&self.pool)).await?
+            }
+            RetentionPolicy::UnsubscribedEmails => {
+                let query = sqlx::query!(
+                    "DELETE FROM subscriptions WHERE unsubscribed_at < ?",
+                    cutoff
+                );
+                timed_query(query.execute(&self.pool)).await?
+            }
+            RetentionPolicy::Trash => {
+                let query = sqlx::query!("DELETE FROM posts WHERE deleted_at < ?", cutoff);
+                timed_query(query.execute(&self.pool)).await?
+            }
+        };
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,180 @@
+//! Service account repository for database operations.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::SqlitePool;
+
+use crate::domain::{ApiQuota, ApiUsage, AppError, ServiceAccount};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for service accounts and the state of their tokens.
+#[derive(Clone)]
+pub struct ServiceAccountRepository {
+    pool: SqlitePool,
+}
+
+impl ServiceAccountRepository {
+    /// Creates a new ServiceAccountRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Creates a service account acting as `user_id`.
+    pub async fn create(
+        &self,
+        name: &str,
+        user_id: i64,
+        scopes: &str,
+        created_by: i64,
+        expires_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<ServiceAccount, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            INSERT INTO service_accounts (name, user_id, scopes, created_by, created_at, expires_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            RETURNING id as "id!"
+            "#,
+            name,
+            user_id,
+            scopes,
+            created_by,
+            now,
+            expires_at
+        );
+        let id = timed_query(query.fetch_one(&self.pool)).await?;
+
+        self.find(id).await?.ok_or(AppError::ServiceAccountNotFound)
+    }
+
+    /// Finds a service account by ID.
+    pub async fn find(&self, id: i64) -> Result<Option<ServiceAccount>, AppError> {
+        let query = sqlx::query_as!(
+            ServiceAccount,
+            r#"
+            SELECT s.id as "id!", s.name, s.user_id, u.username, s.scopes,
+                   s.created_at as "created_at: _", s.expires_at as "expires_at: _",
+                   s.revoked_at as "revoked_at: _", s.requests_per_day, s.writes_per_day
+            FROM service_accounts s
+            JOIN users u ON u.id = s.user_id
+            WHERE s.id = ?
+            "#,
+            id
+        );
+        let account = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(account)
+    }
+
+    /// Lists all service accounts, newest first.
+    pub async fn list(&self) -> Result<Vec<ServiceAccount>, AppError> {
+        let query = sqlx::query_as!(
+            ServiceAccount,
+            r#"
+            SELECT s.id as "id!", s.name, s.user_id, u.username, s.scopes,
+                   s.created_at as "created_at: _", s.expires_at as "expires_at: _",
+                   s.revoked_at as "revoked_at: _", s.requests_per_day, s.writes_per_day
+            FROM service_accounts s
+            JOIN users u ON u.id = s.user_id
+            ORDER BY s.id DESC
+            "#
+        );
+        let accounts = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(accounts)
+    }
+
+    /// Revokes a service account. Returns false if there is no such account;
+    /// revoking twice keeps the first revocation time.
+    pub async fn revoke(&self, id: i64, now: DateTime<Utc>) -> Result<bool, AppError> {
+        let query = sqlx::query!(
+            "UPDATE service_accounts SET revoked_at = COALESCE(revoked_at, ?) WHERE id = ?",
+            now,
+            id
+        );
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Lists the active (unrevoked, unexpired) service accounts acting as
+    /// `user_id`, newest first.
+    pub async fn list_active_for_user(
+        &self,
+        user_id: i64,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<ServiceAccount>, AppError> {
+        let query = sqlx::query_as!(
+            ServiceAccount,
+            r#"
+            SELECT s.id as "id!", s.name, s.user_id, u.username, s.scopes,
+                   s.created_at as "created_at: _", s.expires_at as "expires_at: _",
+                   s.revoked_at as "revoked_at: _", s.requests_per_day, s.writes_per_day
+            FROM service_accounts s
+            JOIN users u ON u.id = s.user_id
+            WHERE s.user_id = ? AND s.revoked_at IS NULL AND s.expires_at > ?
+            ORDER BY s.id DESC
+            "#,
+            user_id,
+            now
+        );
+        let accounts = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(accounts)
+    }
+
+    /// Sets a service account's daily quotas. Returns false if there is no
+    /// such account.
+    pub async fn set_quota(&self, id: i64, quota: ApiQuota) -> Result<bool, AppError> {
+        let query = sqlx::query!(
+            "UPDATE service_accounts SET requests_per_day = ?, writes_per_day = ? WHERE id = ?",
+            quota.requests_per_day,
+            quota.writes_per_day,
+            id
+        );
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Finds a service account's recorded usage on `day`.
+    pub async fn find_usage(&self, id: i64, day: NaiveDate) -> Result<Option<ApiUsage>, AppError> {
+        let query = sqlx::query!(
+            r#"
+            SELECT requests, writes
+            FROM service_account_usage
+            WHERE service_account_id = ? AND day = ?
+            "#,
+            id,
+            day
+        );
+        let row = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(row.map(|row| ApiUsage {
+            day,
+            requests: row.requests,
+            writes: row.writes,
+        }))
+    }
+
+    /// Records a service account's usage counters for a day, keeping the
+    /// higher of each stored and given count (replicas may save older
+    /// counts). Usage of deleted accounts is dropped.
+    pub async fn save_usage(&self, id: i64, usage: ApiUsage) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO service_account_usage (service_account_id, day, requests, writes)
+            SELECT id, ?, ?, ? FROM service_accounts WHERE id = ?
+            ON CONFLICT (service_account_id, day)
+            DO UPDATE SET requests = MAX(requests, excluded.requests),
+                          writes = MAX(writes, excluded.writes)
+            "#,
+            usage.day,
+            usage.requests,
+            usage.writes,
+            id
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+}
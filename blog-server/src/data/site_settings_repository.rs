@@ -0,0 +1,68 @@
+//! Site settings repository for database operations.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::domain::{AppError, SiteSetting};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for site-wide settings, one JSON value per key.
+#[derive(Clone)]
+pub struct SiteSettingsRepository {
+    pool: SqlitePool,
+}
+
+impl SiteSettingsRepository {
+    /// Creates a new SiteSettingsRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Finds the setting stored under `key`.
+    pub async fn find(&self, key: &str) -> Result<Option<SiteSetting>, AppError> {
+        let query = sqlx::query_as!(
+            SiteSetting,
+            r#"
+            SELECT value, updated_at as "updated_at: _"
+            FROM site_settings
+            WHERE key = ?
+            "#,
+            key
+        );
+        let setting = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(setting)
+    }
+
+    /// Stores `value` under `key`, replacing any previous value.
+    pub async fn set(
+        &self,
+        key: &str,
+        value: &str,
+        now: DateTime<Utc>,
+    ) -> Result<SiteSetting, AppError> {
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO site_settings (key, value, updated_at) VALUES (?, ?, ?)
+            ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at
+            "#,
+            key,
+            value,
+            now
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(SiteSetting {
+            value: value.to_string(),
+            updated_at: now,
+        })
+    }
+
+    /// Removes the setting under `key`, if any.
+    pub async fn delete(&self, key: &str) -> Result<(), AppError> {
+        let query = sqlx::query!("DELETE FROM site_settings WHERE key = ?", key);
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+}
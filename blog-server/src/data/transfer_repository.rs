@@ -0,0 +1,174 @@
+//! Transfer repository: offers to hand posts over to other users.
+
+use blog_shared::TransferStatus;
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::domain::{AppError, PostTransfer};
+use crate::infrastructure::metrics::timed_query;
+
+/// Repository for post transfer database operations.
+#[derive(Clone)]
+pub struct TransferRepository {
+    pool: SqlitePool,
+}
+
+impl TransferRepository {
+    /// Creates a new TransferRepository.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Offers post `post_id` to `to_user_id`, returning the pending transfer's ID.
+    pub async fn create(
+        &self,
+        post_id: i64,
+        from_user_id: i64,
+        to_user_id: i64,
+        now: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            INSERT INTO post_transfers (post_id, from_user_id, to_user_id, status, created_at)
+            VALUES (?, ?, ?, 'pending', ?)
+            RETURNING id as "id!"
+            "#,
+            post_id,
+            from_user_id,
+            to_user_id,
+            now
+        );
+        let id = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(id)
+    }
+
+    /// Finds a transfer of a post outside the trash.
+    pub async fn find(&self, id: i64) -> Result<Option<PostTransfer>, AppError> {
+        let query = sqlx::query_as!(
+            PostTransfer,
+            r#"
+            SELECT t.id as "id!", t.post_id, p.public_id as post_public_id,
+                   p.title as post_title, t.from_user_id, f.public_id as from_user_public_id,
+                   f.username as from_username, t.to_user_id, r.public_id as to_user_public_id,
+                   r.username as to_username, t.status,
+                   t.created_at as "created_at: _", t.resolved_at as "resolved_at: _"
+            FROM post_transfers t
+            JOIN posts p ON p.id = t.post_id
+            JOIN users f ON f.id = t.from_user_id
+            JOIN users r ON r.id = t.to_user_id
+            WHERE t.id = ? AND p.deleted_at IS NULL
+            "#,
+            id
+        );
+        let transfer = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(transfer)
+    }
+
+    /// Whether a post already has a pending transfer.
+    pub async fn has_pending(&self, post_id: i64) -> Result<bool, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM post_transfers WHERE post_id = ? AND status = 'pending'
+            ) as "exists!: bool"
+            "#,
+            post_id
+        );
+        let exists = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(exists)
+    }
+
+    /// Lists pending transfers sent or received by `user_id`, newest first,
+    /// leaving out posts in the trash.
+    pub async fn list_pending(&self, user_id: i64) -> Result<Vec<PostTransfer>, AppError> {
+        let query = sqlx::query_as!(
+            PostTransfer,
+            r#"
+            SELECT t.id as "id!", t.post_id, p.public_id as post_public_id,
+                   p.title as post_title, t.from_user_id, f.public_id as from_user_public_id,
+                   f.username as from_username, t.to_user_id, r.public_id as to_user_public_id,
+                   r.username as to_username, t.status,
+                   t.created_at as "created_at: _", t.resolved_at as "resolved_at: _"
+            FROM post_transfers t
+            JOIN posts p ON p.id = t.post_id
+            JOIN users f ON f.id = t.from_user_id
+            JOIN users r ON r.id = t.to_user_id
+            WHERE t.status = 'pending' AND (t.from_user_id = ? OR t.to_user_id = ?)
+              AND p.deleted_at IS NULL
+            ORDER BY t.created_at DESC, t.id DESC
+            "#,
+            user_id,
+            user_id
+        );
+        let transfers = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(transfers)
+    }
+
+    /// Accepts a pending transfer and makes its recipient the post's author,
+    /// in one transaction.
+    pub async fn accept(
+        &self,
+        transfer: &PostTransfer,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let query = sqlx::query!(
+            r#"
+            UPDATE post_transfers SET status = 'accepted', resolved_at = ?
+            WHERE id = ? AND status = 'pending'
+            "#,
+            now,
+            transfer.id
+        );
+        let result = timed_query(query.execute(&mut *tx)).await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::TransferNotFound);
+        }
+
+        let query = sqlx::query!(
+            "UPDATE posts SET author_id = ?, modified_at = ? WHERE id = ? AND author_id = ?",
+            transfer.to_user_id,
+            now,
+            transfer.post_id,
+            transfer.from_user_id
+        );
+        let result = timed_query(query.execute(&mut *tx)).await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::PostNotFound);
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Closes a pending transfer as declined or cancelled.
+    pub async fn close(
+        &self,
+        id: i64,
+        status: TransferStatus,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let status = status.as_str();
+        let query = sqlx::query!(
+            r#"
+            UPDATE post_transfers SET status = ?, resolved_at = ?
+            WHERE id = ? AND status = 'pending'
+            "#,
+            status,
+            now,
+            id
+        );
+        let result = timed_query(query.execute(&self.pool)).await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::TransferNotFound);
+        }
+
+        Ok(())
+    }
+}
@@ -1,8 +1,13 @@
 //! User repository for database operations.
 
+use chrono::{DateTime, Utc};
 use sqlx::SqlitePool;
 
-use crate::domain::{AppError, User};
+use crate::domain::{
+    AppError, EntityRef, PublicProfile, Session, User, UserSettings, UserSuggestion, new_public_id,
+    normalize_username, prefix_bound,
+};
+use crate::infrastructure::metrics::timed_query;
 
 /// Repository for user-related database operations.
 #[derive(Clone)]
@@ -18,51 +23,52 @@ impl UserRepository {
 
     /// Finds a user by ID.
     pub async fn find_by_id(&self, id: i64) -> Result<Option<User>, AppError> {
-        let user = sqlx::query_as!(
+        let query = sqlx::query_as!(
             User,
             r#"
-            SELECT id as "id!", username, email, password_hash, created_at as "created_at: _"
+            SELECT id as "id!", public_id, username, email, password_hash, role,
+                   suspended_at as "suspended_at: _", created_at as "created_at: _"
             FROM users
             WHERE id = ?
             "#,
             id
-        )
-        .fetch_optional(&self.pool)
-        .await?;
+        );
+        let user = timed_query(query.fetch_optional(&self.pool)).await?;
 
         Ok(user)
     }
 
-    /// Finds a user by username.
+    /// Finds a user by username, ignoring case and Unicode normalization.
     pub async fn find_by_username(&self, username: &str) -> Result<Option<User>, AppError> {
-        let user = sqlx::query_as!(
+        let normalized = normalize_username(username);
+        let query = sqlx::query_as!(
             User,
             r#"
-            SELECT id as "id!", username, email, password_hash, created_at as "created_at: _"
+            SELECT id as "id!", public_id, username, email, password_hash, role,
+                   suspended_at as "suspended_at: _", created_at as "created_at: _"
             FROM users
-            WHERE username = ?
+            WHERE username_normalized = ?
             "#,
-            username
-        )
-        .fetch_optional(&self.pool)
-        .await?;
+            normalized
+        );
+        let user = timed_query(query.fetch_optional(&self.pool)).await?;
 
         Ok(user)
     }
 
-    /// Finds a user by email.
+    /// Finds a user by email, ignoring case.
     pub async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError> {
-        let user = sqlx::query_as!(
+        let query = sqlx::query_as!(
             User,
             r#"
-            SELECT id as "id!", username, email, password_hash, created_at as "created_at: _"
+            SELECT id as "id!", public_id, username, email, password_hash, role,
+                   suspended_at as "suspended_at: _", created_at as "created_at: _"
             FROM users
-            WHERE email = ?
+            WHERE lower(email) = lower(?)
             "#,
             email
-        )
-        .fetch_optional(&self.pool)
-        .await?;
+        );
+        let user = timed_query(query.fetch_optional(&self.pool)).await?;
 
         Ok(user)
     }
@@ -73,23 +79,378 @@ impl UserRepository {
         username: &str,
         email: &str,
         password_hash: &str,
+        now: DateTime<Utc>,
     ) -> Result<User, AppError> {
-        let now = chrono::Utc::now();
-        let user = sqlx::query_as!(
+        let normalized = normalize_username(username);
+        let public_id = new_public_id();
+        let query = sqlx::query_as!(
             User,
             r#"
-            INSERT INTO users (username, email, password_hash, created_at)
-            VALUES (?, ?, ?, ?)
-            RETURNING id as "id!", username, email, password_hash, created_at as "created_at: _"
+            INSERT INTO users (public_id, username, username_normalized, email, password_hash, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            RETURNING id as "id!", public_id, username, email, password_hash, role,
+                   suspended_at as "suspended_at: _", created_at as "created_at: _"
             "#,
+            public_id,
             username,
+            normalized,
             email,
             password_hash,
             now
-        )
-        .fetch_one(&self.pool)
-        .await?;
+        );
+        let user = timed_query(query.fetch_one(&self.pool)).await?;
 
         Ok(user)
     }
+
+    /// Finds the profile and preference fields of a user.
+    pub async fn find_settings(&self, id: i64) -> Result<Option<UserSettings>, AppError> {
+        let query = sqlx::query_as!(
+            UserSettings,
+            r#"
+            SELECT email, bio, avatar_url, website, theme, language, timezone, locale
+            FROM users
+            WHERE id = ?
+            "#,
+            id
+        );
+        let settings = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(settings)
+    }
+
+    /// Finds the ID of a user named by public or numeric ID; numeric IDs
+    /// are returned as is.
+    pub async fn resolve_id(&self, user: &EntityRef) -> Result<Option<i64>, AppError> {
+        let public_id = match user {
+            EntityRef::Id(id) => return Ok(Some(*id)),
+            EntityRef::Public(public_id) => public_id,
+        };
+        let query = sqlx::query_scalar!(
+            r#"SELECT id as "id!" FROM users WHERE public_id = ?"#,
+            public_id
+        );
+        let id = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(id)
+    }
+
+    /// Finds the public profile of a user.
+    pub async fn find_public_profile(&self, id: i64) -> Result<Option<PublicProfile>, AppError> {
+        let query = sqlx::query_as!(
+            PublicProfile,
+            r#"
+            SELECT id as "id!", public_id, username, bio, avatar_url
+            FROM users
+            WHERE id = ?
+            "#,
+            id
+        );
+        let profile = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(profile)
+    }
+
+    /// Suggests active users whose normalized username starts with `prefix`:
+    /// an exact match first, then those with the most published posts.
+    pub async fn suggest(&self, prefix: &str, limit: i64) -> Result<Vec<UserSuggestion>, AppError> {
+        let bound = prefix_bound(prefix);
+        let query = sqlx::query_as!(
+            UserSuggestion,
+            r#"
+            SELECT u.username, u.avatar_url
+            FROM users u
+            WHERE u.username_normalized >= ? AND u.username_normalized < ?
+              AND u.suspended_at IS NULL AND u.shadow_banned_at IS NULL
+            ORDER BY u.username_normalized = ? DESC,
+                     (SELECT COUNT(*) FROM posts p
+                      WHERE p.author_id = u.id AND p.status = 'published' AND p.deleted_at IS NULL) DESC,
+                     u.username_normalized
+            LIMIT ?
+            "#,
+            prefix,
+            bound,
+            prefix,
+            limit
+        );
+        let users = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(users)
+    }
+
+    /// Finds the ID of the user whose website is `website` (canonical form).
+    pub async fn find_id_by_website(&self, website: &str) -> Result<Option<i64>, AppError> {
+        let query = sqlx::query_scalar!(
+            r#"SELECT id as "id!" FROM users WHERE website = ?"#,
+            website
+        );
+        let id = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(id)
+    }
+
+    /// Updates the public profile of a user.
+    pub async fn update_profile(
+        &self,
+        id: i64,
+        bio: &str,
+        avatar_url: Option<&str>,
+        website: Option<&str>,
+    ) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            "UPDATE users SET bio = ?, avatar_url = ?, website = ? WHERE id = ?",
+            bio,
+            avatar_url,
+            website,
+            id
+        );
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::UserNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Updates the display preferences of a user.
+    pub async fn update_preferences(
+        &self,
+        id: i64,
+        theme: &str,
+        language: &str,
+        timezone: &str,
+        locale: &str,
+    ) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            "UPDATE users SET theme = ?, language = ?, timezone = ?, locale = ? WHERE id = ?",
+            theme,
+            language,
+            timezone,
+            locale,
+            id
+        );
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::UserNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Changes the email of a user.
+    pub async fn update_email(&self, id: i64, email: &str) -> Result<(), AppError> {
+        let query = sqlx::query!("UPDATE users SET email = ? WHERE id = ?", email, id);
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Replaces the password hash of a user.
+    pub async fn update_password_hash(&self, id: i64, password_hash: &str) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            "UPDATE users SET password_hash = ? WHERE id = ?",
+            password_hash,
+            id
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Whether any user has registered yet.
+    pub async fn any_exist(&self) -> Result<bool, AppError> {
+        let query = sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM users) as "exists!: bool""#);
+        let exists = timed_query(query.fetch_one(&self.pool)).await?;
+
+        Ok(exists)
+    }
+
+    /// Deletes a user; their posts, comments, and sessions cascade.
+    pub async fn delete(&self, id: i64) -> Result<(), AppError> {
+        let query = sqlx::query!("DELETE FROM users WHERE id = ?", id);
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::UserNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Changes the role of a user.
+    pub async fn update_role(&self, id: i64, role: &str) -> Result<(), AppError> {
+        let query = sqlx::query!("UPDATE users SET role = ? WHERE id = ?", role, id);
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::UserNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Suspends a user (`Some(since)`) or lifts the suspension (`None`).
+    pub async fn update_suspended_at(
+        &self,
+        id: i64,
+        suspended_at: Option<DateTime<Utc>>,
+    ) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            "UPDATE users SET suspended_at = ? WHERE id = ?",
+            suspended_at,
+            id
+        );
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::UserNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Shadow-bans a user (`Some(since)`) or lifts the ban (`None`), marking
+    /// their posts modified as they leave or rejoin the lists.
+    pub async fn update_shadow_banned_at(
+        &self,
+        id: i64,
+        shadow_banned_at: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+        let query = sqlx::query!(
+            "UPDATE users SET shadow_banned_at = ? WHERE id = ?",
+            shadow_banned_at,
+            id
+        );
+        let result = timed_query(query.execute(&mut *tx)).await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::UserNotFound);
+        }
+
+        let query = sqlx::query!(
+            "UPDATE posts SET modified_at = ? WHERE author_id = ?",
+            now,
+            id
+        );
+        timed_query(query.execute(&mut *tx)).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Records a new session for a user.
+    pub async fn create_session(
+        &self,
+        id: &str,
+        user_id: i64,
+        user_agent: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO sessions (id, user_id, user_agent, created_at, last_seen_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+            id,
+            user_id,
+            user_agent,
+            now,
+            now
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Finds a session by ID.
+    pub async fn find_session(&self, id: &str) -> Result<Option<Session>, AppError> {
+        let query = sqlx::query_as!(
+            Session,
+            r#"
+            SELECT id as "id!", user_id, user_agent,
+                   created_at as "created_at: _", last_seen_at as "last_seen_at: _"
+            FROM sessions
+            WHERE id = ?
+            "#,
+            id
+        );
+        let session = timed_query(query.fetch_optional(&self.pool)).await?;
+
+        Ok(session)
+    }
+
+    /// Lists sessions of a user created after `since`, most recently used first.
+    pub async fn list_sessions(
+        &self,
+        user_id: i64,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Session>, AppError> {
+        let query = sqlx::query_as!(
+            Session,
+            r#"
+            SELECT id as "id!", user_id, user_agent,
+                   created_at as "created_at: _", last_seen_at as "last_seen_at: _"
+            FROM sessions
+            WHERE user_id = ? AND created_at > ?
+            ORDER BY last_seen_at DESC
+            "#,
+            user_id,
+            since
+        );
+        let sessions = timed_query(query.fetch_all(&self.pool)).await?;
+
+        Ok(sessions)
+    }
+
+    /// Updates the last activity time of a session.
+    pub async fn touch_session(&self, id: &str, now: DateTime<Utc>) -> Result<(), AppError> {
+        let query = sqlx::query!("UPDATE sessions SET last_seen_at = ? WHERE id = ?", now, id);
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Deletes a session of a user.
+    pub async fn delete_session(&self, id: &str, user_id: i64) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            "DELETE FROM sessions WHERE id = ? AND user_id = ?",
+            id,
+            user_id
+        );
+        let result = timed_query(query.execute(&self.pool)).await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::SessionNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Deletes all sessions of a user except `keep` (if given).
+    pub async fn delete_other_sessions(
+        &self,
+        user_id: i64,
+        keep: Option<&str>,
+    ) -> Result<(), AppError> {
+        let query = sqlx::query!(
+            "DELETE FROM sessions WHERE user_id = ? AND id IS NOT ?",
+            user_id,
+            keep
+        );
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
+
+    /// Deletes sessions whose tokens have expired (created before `cutoff`).
+    pub async fn delete_expired_sessions(&self, cutoff: DateTime<Utc>) -> Result<(), AppError> {
+        let query = sqlx::query!("DELETE FROM sessions WHERE created_at <= ?", cutoff);
+        timed_query(query.execute(&self.pool)).await?;
+
+        Ok(())
+    }
 }
@@ -1,9 +1,112 @@
 //! Domain layer: entities and business logic.
 
+mod activitypub;
+mod analytics;
+mod attachment;
+mod audit;
+mod comment;
+mod crosspost;
+mod cursor;
+mod date_format;
+mod diagnostics;
+mod email_change;
+mod embed;
 mod error;
+mod error_report;
+mod event;
+mod experiment;
+mod feature_flag;
+mod feed;
+mod invite;
+mod link_check;
+mod link_preview;
+mod markdown;
+mod message;
+mod micropub;
+mod moderation_rule;
+mod newsletter;
+mod notification;
+mod org;
+pub mod policy;
 mod post;
+mod post_metadata;
+mod public_id;
+mod quota;
+mod recovery_code;
+mod retention;
+mod revision;
+mod search;
+mod service_account;
+mod session;
+mod site_setting;
+mod site_stats;
+mod tag;
+mod transfer;
 mod user;
+mod workflow;
 
+pub use activitypub::{
+    ActorKey, ApUrls, PendingDelivery, accept_activity, actor_document, actor_public_key,
+    create_activity, followers_collection, note_object, outbox_collection, parse_acct,
+    webfinger_document,
+};
+pub use analytics::{AnalyticsEvent, DailyStats, PageStats};
+pub use attachment::{
+    Attachment, Upload, check_upload, content_hash, is_content_hash, is_executable,
+    sniff_content_type,
+};
+pub use audit::{AuditAction, AuditEntry};
+pub use comment::Comment;
+pub use crosspost::{
+    Crosspost, CrosspostApis, Integration, RemotePost, devto_article, hashnode_request,
+    parse_devto_article, parse_hashnode_post,
+};
+pub use cursor::PageCursor;
+pub use date_format::{DateFormat, is_supported_locale, parse_timezone};
+pub use diagnostics::{DiagnosticBody, is_sensitive_field};
+pub use email_change::{
+    EmailChange, generate_email_change_token, hash_email_change_token,
+    render_email_change_confirmation, render_email_changed_notice,
+};
+pub use embed::EmbedProvider;
 pub use error::AppError;
-pub use post::Post;
-pub use user::User;
+pub use error_report::{ErrorReport, ErrorReporter, ErrorSource, RequestContext};
+pub use event::DomainEvent;
+pub use experiment::{
+    Experiment, ExperimentVariant, assign_variant, parse_experiment_description,
+    parse_experiment_name, parse_variants,
+};
+pub use feature_flag::FeatureFlag;
+pub use feed::{AuthorTimezones, FeedUrls, render_atom, render_rss};
+pub use invite::{generate_invite_code, hash_invite_code};
+pub use link_check::{BrokenLink, LinkCheck};
+pub use link_preview::{LinkPreview, parse_open_graph};
+pub use markdown::{external_links, render_markdown};
+pub use message::{Conversation, Message};
+pub use micropub::{IndieAuthToken, MicropubEntry, canonical_profile_url};
+pub use moderation_rule::{FlaggedContent, ModerationRule, NewRule, RuleSet, compile_rule};
+pub use newsletter::{DigestPost, EmailBody, Subscription, render_confirmation, render_digest};
+pub use notification::Notification;
+pub use org::{Membership, OrgMember, Organization, parse_org_profile, parse_org_slug};
+pub use post::{NewPost, Post, TrashedPost, days_until, parse_lang, parse_source_url};
+pub use post_metadata::{PostMetadata, merge_metadata, parse_metadata};
+pub use public_id::{EntityRef, PublicRef, new_public_id};
+pub use quota::{ApiQuota, ApiUsage, QuotaStatus, next_reset};
+pub use recovery_code::{generate_recovery_codes, hash_recovery_code};
+pub use retention::RetentionPolicy;
+pub use revision::{PostRevision, render_diff_html};
+pub use search::{
+    IndexedTerm, SearchDocument, SearchFilters, SearchHit, SearchIndex, SearchResults,
+    edit_distance, match_expr, prefix_bound, render_snippet, search_words, typo_budget,
+};
+pub use service_account::ServiceAccount;
+pub use session::Session;
+pub use site_setting::{Announcement, SiteSetting, SiteSettings};
+pub use site_stats::SiteStats;
+pub use tag::{Tag, TagUsage, normalize_tag, normalize_tags};
+pub use transfer::PostTransfer;
+pub use user::{
+    AuthorName, PublicProfile, User, UserSettings, UserSuggestion, UserSummary, UserUsage,
+    clean_username, normalize_username,
+};
+pub use workflow::{NewTransition, PostTransition, ReviewNote, WorkflowAction};
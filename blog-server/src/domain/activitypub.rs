@@ -0,0 +1,283 @@
+//! ActivityPub identifiers and JSON documents for authors and their posts.
+
+use chrono::{DateTime, Utc};
+use serde_json::{Value, json};
+
+use super::markdown::escape_into;
+use super::{Post, PublicProfile, render_markdown};
+use crate::constants::{AP_ACTIVITYSTREAMS_CONTEXT, AP_PUBLIC_ADDRESSEE, AP_SECURITY_CONTEXT};
+
+/// RSA key pair an author signs outgoing activities with.
+#[derive(Debug, Clone)]
+pub struct ActorKey {
+    pub user_id: i64,
+    pub private_key_pem: String,
+    pub public_key_pem: String,
+}
+
+/// Post waiting to be delivered to one follower's inbox.
+#[derive(Debug, Clone)]
+pub struct PendingDelivery {
+    pub post_id: i64,
+    pub post_public_id: String,
+    pub follower_id: i64,
+    pub inbox_url: String,
+    pub author_id: i64,
+    pub author_username: String,
+    pub title: String,
+    pub content: String,
+    pub lang: String,
+    pub translation_group_id: String,
+    pub canonical_url: Option<String>,
+    pub original_source: Option<String>,
+    pub metadata: String,
+    pub status: String,
+    pub attempts: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PendingDelivery {
+    /// Returns the post being delivered.
+    pub fn post(&self) -> Post {
+        Post {
+            id: self.post_id,
+            public_id: self.post_public_id.clone(),
+            title: self.title.clone(),
+            content: self.content.clone(),
+            author_id: self.author_id,
+            lang: self.lang.clone(),
+            translation_group_id: self.translation_group_id.clone(),
+            canonical_url: self.canonical_url.clone(),
+            original_source: self.original_source.clone(),
+            metadata: self.metadata.clone(),
+            status: self.status.clone(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+/// Builds this server's ActivityPub IDs, all under the public base URL.
+#[derive(Debug, Clone)]
+pub struct ApUrls {
+    base: String,
+    site: String,
+}
+
+impl ApUrls {
+    /// Creates IDs under `base` (the API server) linking to posts on `site` (the frontend).
+    pub fn new(base: &str, site: &str) -> Self {
+        Self {
+            base: base.trim_end_matches('/').to_string(),
+            site: site.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Host (and port, if any) used in `acct:` handles.
+    pub fn host(&self) -> &str {
+        let rest = self
+            .base
+            .split_once("://")
+            .map_or(&*self.base, |(_, rest)| rest);
+        rest.split('/').next().unwrap_or(rest)
+    }
+
+    /// Actor ID of an author.
+    pub fn actor(&self, username: &str) -> String {
+        format!("{}/ap/users/{}", self.base, username)
+    }
+
+    /// ID of an author's public key.
+    pub fn key_id(&self, username: &str) -> String {
+        format!("{}#main-key", self.actor(username))
+    }
+
+    /// Inbox of an author.
+    pub fn inbox(&self, username: &str) -> String {
+        format!("{}/inbox", self.actor(username))
+    }
+
+    /// Outbox of an author.
+    pub fn outbox(&self, username: &str) -> String {
+        format!("{}/outbox", self.actor(username))
+    }
+
+    /// Followers collection of an author.
+    pub fn followers(&self, username: &str) -> String {
+        format!("{}/followers", self.actor(username))
+    }
+
+    /// ID of the Note object of a post.
+    pub fn note(&self, post_public_id: &str) -> String {
+        format!("{}/ap/posts/{}", self.base, post_public_id)
+    }
+
+    /// ID of the Create activity that published a post.
+    pub fn create(&self, post_public_id: &str) -> String {
+        format!("{}/activity", self.note(post_public_id))
+    }
+
+    /// Page showing a post on the frontend.
+    pub fn permalink(&self, post_public_id: &str) -> String {
+        format!("{}/?post={}", self.site, post_public_id)
+    }
+
+    /// Returns the username of one of this server's actor IDs.
+    pub fn username_of<'a>(&self, actor_id: &'a str) -> Option<&'a str> {
+        let prefix = format!("{}/ap/users/", self.base);
+        actor_id
+            .strip_prefix(&prefix)
+            .filter(|username| !username.is_empty() && !username.contains('/'))
+    }
+}
+
+/// Returns the username of a WebFinger resource (`acct:alice@host`) on `host`.
+pub fn parse_acct<'a>(resource: &'a str, host: &str) -> Option<&'a str> {
+    let (username, resource_host) = resource
+        .strip_prefix("acct:")
+        .unwrap_or(resource)
+        .split_once('@')?;
+    resource_host
+        .eq_ignore_ascii_case(host)
+        .then_some(username)
+        .filter(|username| !username.is_empty())
+}
+
+/// WebFinger response pointing at an author's actor.
+pub fn webfinger_document(urls: &ApUrls, username: &str) -> Value {
+    json!({
+        "subject": format!("acct:{}@{}", username, urls.host()),
+        "aliases": [urls.actor(username)],
+        "links": [{
+            "rel": "self",
+            "type": "application/activity+json",
+            "href": urls.actor(username),
+        }],
+    })
+}
+
+/// Actor (Person) document of an author, with their public key.
+pub fn actor_document(urls: &ApUrls, profile: &PublicProfile, public_key_pem: &str) -> Value {
+    let username = &profile.username;
+    let mut summary = String::new();
+    if !profile.bio.is_empty() {
+        summary.push_str("<p>");
+        escape_into(&mut summary, &profile.bio);
+        summary.push_str("</p>");
+    }
+
+    let mut actor = json!({
+        "@context": [AP_ACTIVITYSTREAMS_CONTEXT, AP_SECURITY_CONTEXT],
+        "id": urls.actor(username),
+        "type": "Person",
+        "preferredUsername": username,
+        "name": username,
+        "summary": summary,
+        "url": urls.site,
+        "inbox": urls.inbox(username),
+        "outbox": urls.outbox(username),
+        "followers": urls.followers(username),
+        "publicKey": {
+            "id": urls.key_id(username),
+            "owner": urls.actor(username),
+            "publicKeyPem": public_key_pem,
+        },
+    });
+    if let Some(avatar_url) = &profile.avatar_url {
+        actor["icon"] = json!({ "type": "Image", "url": avatar_url });
+    }
+
+    actor
+}
+
+/// Note object of a post: the title in bold above the rendered Markdown,
+/// also under the post's language in `contentMap`.
+///
+/// Embeds are left as links, since remote servers strip iframes.
+pub fn note_object(urls: &ApUrls, username: &str, post: &Post) -> Value {
+    let mut content = String::from("<p><strong>");
+    escape_into(&mut content, &post.title);
+    content.push_str("</strong></p>");
+    content.push_str(&render_markdown(&post.content, &[]));
+
+    json!({
+        "id": urls.note(&post.public_id),
+        "type": "Note",
+        "attributedTo": urls.actor(username),
+        "content": content,
+        "contentMap": { post.lang.as_str(): content },
+        "url": urls.permalink(&post.public_id),
+        "published": post.created_at.to_rfc3339(),
+        "to": [AP_PUBLIC_ADDRESSEE],
+        "cc": [urls.followers(username)],
+    })
+}
+
+/// Create activity publishing a post.
+pub fn create_activity(urls: &ApUrls, username: &str, post: &Post) -> Value {
+    json!({
+        "@context": AP_ACTIVITYSTREAMS_CONTEXT,
+        "id": urls.create(&post.public_id),
+        "type": "Create",
+        "actor": urls.actor(username),
+        "published": post.created_at.to_rfc3339(),
+        "to": [AP_PUBLIC_ADDRESSEE],
+        "cc": [urls.followers(username)],
+        "object": note_object(urls, username, post),
+    })
+}
+
+/// Public key PEM `key_id` from the document of `actor_id`, if the actor
+/// publishes that key.
+///
+/// Keys are only trusted from the actor's own document: a key document
+/// hosted anywhere else could name any actor as its owner.
+pub fn actor_public_key<'a>(actor: &'a Value, actor_id: &str, key_id: &str) -> Option<&'a str> {
+    if actor["id"].as_str() != Some(actor_id) {
+        return None;
+    }
+    let keys = match &actor["publicKey"] {
+        Value::Array(keys) => keys.iter().collect(),
+        key => vec![key],
+    };
+    let key = keys
+        .into_iter()
+        .find(|key| key["id"].as_str() == Some(key_id))?;
+    if key["owner"].as_str().is_some_and(|owner| owner != actor_id) {
+        return None;
+    }
+    key["publicKeyPem"].as_str()
+}
+
+/// Accept activity answering a Follow.
+pub fn accept_activity(urls: &ApUrls, username: &str, accept_id: &str, follow: &Value) -> Value {
+    json!({
+        "@context": AP_ACTIVITYSTREAMS_CONTEXT,
+        "id": format!("{}#accepts/{}", urls.actor(username), accept_id),
+        "type": "Accept",
+        "actor": urls.actor(username),
+        "object": follow,
+    })
+}
+
+/// Outbox with the author's newest Create activities.
+pub fn outbox_collection(urls: &ApUrls, username: &str, items: Vec<Value>, total: i64) -> Value {
+    json!({
+        "@context": AP_ACTIVITYSTREAMS_CONTEXT,
+        "id": urls.outbox(username),
+        "type": "OrderedCollection",
+        "totalItems": total,
+        "orderedItems": items,
+    })
+}
+
+/// Followers collection; only the count is public.
+pub fn followers_collection(urls: &ApUrls, username: &str, total: i64) -> Value {
+    json!({
+        "@context": AP_ACTIVITYSTREAMS_CONTEXT,
+        "id": urls.followers(username),
+        "type": "OrderedCollection",
+        "totalItems": total,
+    })
+}
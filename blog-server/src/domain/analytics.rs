@@ -0,0 +1,54 @@
+//! Anonymous page analytics.
+
+use blog_shared::{AnalyticsEventDto, AnalyticsEventKind};
+use chrono::NaiveDate;
+
+use crate::constants::MAX_ANALYTICS_PATH_LEN;
+use crate::domain::AppError;
+
+/// A validated client event, reduced to what the stats need.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalyticsEvent {
+    pub kind: AnalyticsEventKind,
+    pub path: String,
+}
+
+impl AnalyticsEvent {
+    /// Validates a reported event, dropping the query and fragment of its
+    /// path so that tokens or search terms never reach storage.
+    pub fn parse(dto: AnalyticsEventDto) -> Result<Self, AppError> {
+        let end = dto.path.find(['?', '#']).unwrap_or(dto.path.len());
+        let path = &dto.path[..end];
+        if !path.starts_with('/') {
+            return Err(AppError::Validation(
+                "Analytics paths must start with '/'".to_string(),
+            ));
+        }
+        if path.len() > MAX_ANALYTICS_PATH_LEN {
+            return Err(AppError::Validation(format!(
+                "Analytics paths must be at most {MAX_ANALYTICS_PATH_LEN} bytes"
+            )));
+        }
+
+        Ok(Self {
+            kind: dto.kind,
+            path: path.to_string(),
+        })
+    }
+}
+
+/// Site-wide totals of one day.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct DailyStats {
+    pub day: NaiveDate,
+    pub page_views: i64,
+    pub read_completions: i64,
+}
+
+/// Totals of one page.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct PageStats {
+    pub path: String,
+    pub page_views: i64,
+    pub read_completions: i64,
+}
@@ -0,0 +1,102 @@
+//! Uploaded files addressed by content hash.
+
+use blog_shared::constants::UPLOAD_TYPES;
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+use super::AppError;
+use crate::constants::EXECUTABLE_MAGIC;
+
+/// An uploaded file; identical content shares one attachment.
+#[derive(Debug, Clone)]
+pub struct Attachment {
+    /// Lowercase hex SHA-256 of the content.
+    pub hash: String,
+    pub content_type: String,
+    pub size: i64,
+    /// First uploader (unset once their account is deleted).
+    pub uploaded_by: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One user's upload of an attachment, served at its own URL.
+#[derive(Debug, Clone)]
+pub struct Upload {
+    /// Random ID in the upload's URL (the hash, for uploads from before
+    /// per-upload IDs).
+    pub id: String,
+    pub hash: String,
+    /// Content type this uploader sent, which may differ from the shared
+    /// attachment's (the first uploader's).
+    pub content_type: String,
+    /// Unset once the uploader's account is deleted.
+    pub uploaded_by: Option<i64>,
+    /// Only served through signed URLs.
+    pub private: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Returns the lowercase hex SHA-256 of `bytes`.
+pub fn content_hash(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Returns true if `s` looks like a hash from [`content_hash`].
+pub fn is_content_hash(s: &str) -> bool {
+    s.len() == 64
+        && s.bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+/// Returns the upload media type `bytes` are, judging by their magic bytes.
+pub fn sniff_content_type(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.starts_with(b"\xff\xd8\xff") {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
+
+/// Returns true if `bytes` start like a native executable or a script.
+pub fn is_executable(bytes: &[u8]) -> bool {
+    EXECUTABLE_MAGIC
+        .iter()
+        .any(|magic| bytes.starts_with(magic))
+}
+
+/// Checks an upload declared as `content_type` (one of `UPLOAD_TYPES`):
+/// no executables, magic bytes matching the declared type, and the type's
+/// size limit.
+pub fn check_upload(content_type: &str, bytes: &[u8]) -> Result<(), AppError> {
+    if is_executable(bytes) {
+        return Err(AppError::Validation(
+            "Executable files are not allowed".to_string(),
+        ));
+    }
+    if sniff_content_type(bytes) != Some(content_type) {
+        return Err(AppError::Validation(format!(
+            "File content is not {content_type}"
+        )));
+    }
+    let limit = UPLOAD_TYPES
+        .iter()
+        .find(|(media_type, _)| *media_type == content_type)
+        .map(|(_, limit)| *limit)
+        .unwrap_or(0);
+    if bytes.len() > limit {
+        return Err(AppError::Validation(format!(
+            "{content_type} files are limited to {limit} bytes"
+        )));
+    }
+
+    Ok(())
+}
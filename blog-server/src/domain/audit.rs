@@ -0,0 +1,42 @@
+//! Audit log of moderator actions, post transfers, and email changes.
+
+use chrono::{DateTime, Utc};
+
+/// Action recorded in the audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    ShadowBan,
+    ShadowUnban,
+    /// A post changed hands; the actor is the previous author.
+    PostTransfer,
+    /// A user asked to change their email; it stays until confirmed.
+    EmailChangeRequested,
+    /// A user's email changed after the new address was confirmed.
+    EmailChanged,
+}
+
+impl AuditAction {
+    /// Returns the stored name of the action.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AuditAction::ShadowBan => "shadow_ban",
+            AuditAction::ShadowUnban => "shadow_unban",
+            AuditAction::PostTransfer => "post_transfer",
+            AuditAction::EmailChangeRequested => "email_change_requested",
+            AuditAction::EmailChanged => "email_changed",
+        }
+    }
+}
+
+/// Audit log entry with the usernames of the accounts involved.
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub actor_id: Option<i64>,
+    pub actor_username: Option<String>,
+    pub action: String,
+    pub target_user_id: Option<i64>,
+    pub target_username: Option<String>,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,17 @@
+//! Comment domain entity.
+
+use chrono::{DateTime, Utc};
+
+/// Comment entity, loaded together with its author's username.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Comment {
+    pub id: i64,
+    pub post_id: i64,
+    pub author_id: i64,
+    pub post_public_id: String,
+    pub author_public_id: String,
+    pub author_username: String,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,172 @@
+//! Cross-posting to DEV (dev.to) and Hashnode: stored integrations and
+//! cross-posts, and the target API payloads.
+
+use blog_shared::{CrosspostStatus, CrosspostTarget, PostDto};
+use chrono::{DateTime, Utc};
+use serde_json::{Value, json};
+
+use super::AppError;
+use crate::constants::DEVTO_MAX_TAGS;
+
+/// A user's token for a cross-posting target.
+#[derive(Debug, Clone)]
+pub struct Integration {
+    pub user_id: i64,
+    /// Stored target name; read through [`Integration::target`].
+    pub target: String,
+    pub token: String,
+    pub publication_id: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Integration {
+    /// Returns the integration's target (unknown stored values count as DEV).
+    pub fn target(&self) -> CrosspostTarget {
+        self.target.parse().unwrap_or(CrosspostTarget::Devto)
+    }
+}
+
+/// A post's copy on a cross-posting target.
+#[derive(Debug, Clone)]
+pub struct Crosspost {
+    pub post_id: i64,
+    /// Author of the post, whose integration is used.
+    pub author_id: i64,
+    /// Stored target name; read through [`Crosspost::target`].
+    pub target: String,
+    /// Stored status name; read through [`Crosspost::status`].
+    pub status: String,
+    /// ID of the copy on the target.
+    pub remote_id: Option<String>,
+    pub remote_url: Option<String>,
+    pub error: Option<String>,
+    pub synced_at: Option<DateTime<Utc>>,
+    pub attempted_at: DateTime<Utc>,
+}
+
+impl Crosspost {
+    /// Returns the cross-post's target (unknown stored values count as DEV).
+    pub fn target(&self) -> CrosspostTarget {
+        self.target.parse().unwrap_or(CrosspostTarget::Devto)
+    }
+
+    /// Returns the cross-post's status (unknown stored values count as failed).
+    pub fn status(&self) -> CrosspostStatus {
+        self.status.parse().unwrap_or(CrosspostStatus::Failed)
+    }
+}
+
+/// Base URLs of the target APIs.
+#[derive(Debug, Clone)]
+pub struct CrosspostApis {
+    /// DEV REST API, e.g. `https://dev.to/api`.
+    pub devto: String,
+    /// Hashnode GraphQL endpoint, e.g. `https://gql.hashnode.com`.
+    pub hashnode: String,
+}
+
+/// The copy of a post on a target, as reported by its API.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemotePost {
+    pub id: String,
+    pub url: String,
+}
+
+/// Builds the body of a DEV article create or update.
+///
+/// DEV allows at most `DEVTO_MAX_TAGS` alphanumeric tags.
+pub fn devto_article(post: &PostDto, canonical_url: &str) -> Value {
+    let tags: Vec<String> = post
+        .tags
+        .iter()
+        .map(|tag| tag.chars().filter(|c| c.is_alphanumeric()).collect())
+        .filter(|tag: &String| !tag.is_empty())
+        .take(DEVTO_MAX_TAGS)
+        .collect();
+
+    json!({
+        "article": {
+            "title": post.title,
+            "body_markdown": post.content,
+            "published": true,
+            "tags": tags,
+            "canonical_url": canonical_url,
+        }
+    })
+}
+
+/// Reads the article ID and URL from a DEV API response.
+pub fn parse_devto_article(response: &Value) -> Result<RemotePost, AppError> {
+    let id = response.get("id").and_then(Value::as_i64);
+    let url = response.get("url").and_then(Value::as_str);
+    match (id, url) {
+        (Some(id), Some(url)) => Ok(RemotePost {
+            id: id.to_string(),
+            url: url.to_string(),
+        }),
+        _ => Err(AppError::Fetch("unexpected DEV response".to_string())),
+    }
+}
+
+/// Builds a Hashnode GraphQL request publishing the post, or updating the
+/// copy `remote_id`.
+pub fn hashnode_request(
+    post: &PostDto,
+    canonical_url: &str,
+    publication_id: &str,
+    remote_id: Option<&str>,
+) -> Value {
+    let tags: Vec<Value> = post
+        .tags
+        .iter()
+        .map(|tag| json!({"slug": tag, "name": tag}))
+        .collect();
+    let mut input = json!({
+        "title": post.title,
+        "contentMarkdown": post.content,
+        "originalArticleURL": canonical_url,
+        "tags": tags,
+    });
+
+    let query = match remote_id {
+        Some(id) => {
+            input["id"] = json!(id);
+            "mutation UpdatePost($input: UpdatePostInput!) { updatePost(input: $input) { post { id url } } }"
+        }
+        None => {
+            input["publicationId"] = json!(publication_id);
+            "mutation PublishPost($input: PublishPostInput!) { publishPost(input: $input) { post { id url } } }"
+        }
+    };
+
+    json!({"query": query, "variables": {"input": input}})
+}
+
+/// Reads the post ID and URL from a Hashnode GraphQL response, turning
+/// GraphQL errors into an error.
+pub fn parse_hashnode_post(response: &Value) -> Result<RemotePost, AppError> {
+    if let Some(message) = response
+        .get("errors")
+        .and_then(|errors| errors.get(0))
+        .and_then(|error| error.get("message"))
+        .and_then(Value::as_str)
+    {
+        return Err(AppError::Fetch(format!("Hashnode: {message}")));
+    }
+
+    let post = response
+        .get("data")
+        .and_then(|data| data.get("publishPost").or_else(|| data.get("updatePost")))
+        .and_then(|result| result.get("post"));
+    let id = post.and_then(|post| post.get("id")).and_then(Value::as_str);
+    let url = post
+        .and_then(|post| post.get("url"))
+        .and_then(Value::as_str);
+    match (id, url) {
+        (Some(id), Some(url)) => Ok(RemotePost {
+            id: id.to_string(),
+            url: url.to_string(),
+        }),
+        _ => Err(AppError::Fetch("unexpected Hashnode response".to_string())),
+    }
+}
@@ -0,0 +1,61 @@
+//! Opaque cursors for keyset pagination.
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Utc};
+
+use crate::domain::AppError;
+
+/// Position after the last item of a page, in the list's sort order of
+/// creation time and then ID.
+///
+/// Encoded as URL-safe base64 of `<RFC 3339 time>|<id>`; clients must treat
+/// it as opaque.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: i64,
+}
+
+impl PageCursor {
+    /// Cursor after an item.
+    pub fn new(created_at: DateTime<Utc>, id: i64) -> Self {
+        Self { created_at, id }
+    }
+
+    /// Returns the string handed to clients as `next_cursor`.
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!("{}|{}", self.created_at.to_rfc3339(), self.id))
+    }
+
+    /// Reads a cursor sent back by a client.
+    pub fn parse(cursor: &str) -> Result<Self, AppError> {
+        let invalid = || AppError::Validation("Invalid cursor".to_string());
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+        let text = String::from_utf8(bytes).map_err(|_| invalid())?;
+        let (created_at, id) = text.split_once('|').ok_or_else(invalid)?;
+
+        Ok(Self {
+            created_at: DateTime::parse_from_rfc3339(created_at)
+                .map_err(|_| invalid())?
+                .with_timezone(&Utc),
+            id: id.parse().map_err(|_| invalid())?,
+        })
+    }
+
+    /// Cursor of the next page, given one item more than the page `limit`
+    /// was fetched: drops that extra item and points after the last one
+    /// kept, or returns `None` when there was no extra item.
+    pub fn next_page<T>(
+        items: &mut Vec<T>,
+        limit: i64,
+        position: impl Fn(&T) -> Self,
+    ) -> Option<Self> {
+        let limit = usize::try_from(limit).unwrap_or(0);
+        if items.len() <= limit {
+            return None;
+        }
+        items.truncate(limit);
+        items.last().map(position)
+    }
+}
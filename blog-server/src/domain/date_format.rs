@@ -0,0 +1,57 @@
+//! Dates formatted for a reader's time zone and locale.
+
+use blog_shared::constants::{DEFAULT_LOCALE, SUPPORTED_LOCALES};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+
+use crate::constants::LOCALE_DATE_FORMATS;
+
+/// How dates are shown to one reader.
+#[derive(Debug, Clone, Copy)]
+pub struct DateFormat {
+    pub timezone: Tz,
+    pattern: &'static str,
+}
+
+impl DateFormat {
+    /// Builds the format from stored preferences. Unknown values fall back
+    /// to UTC and `DEFAULT_LOCALE` rather than failing.
+    pub fn new(timezone: &str, locale: &str) -> Self {
+        Self {
+            timezone: parse_timezone(timezone).unwrap_or(Tz::UTC),
+            pattern: locale_pattern(locale)
+                .or_else(|| locale_pattern(DEFAULT_LOCALE))
+                .unwrap_or("%Y-%m-%d %H:%M %Z"),
+        }
+    }
+
+    /// Formats a date and time for the reader.
+    pub fn format(&self, time: DateTime<Utc>) -> String {
+        time.with_timezone(&self.timezone)
+            .format(self.pattern)
+            .to_string()
+    }
+}
+
+impl Default for DateFormat {
+    fn default() -> Self {
+        Self::new("UTC", DEFAULT_LOCALE)
+    }
+}
+
+/// Parses an IANA time zone name such as `Europe/Berlin`.
+pub fn parse_timezone(name: &str) -> Option<Tz> {
+    name.parse().ok()
+}
+
+/// Whether dates can be formatted for `locale`.
+pub fn is_supported_locale(locale: &str) -> bool {
+    SUPPORTED_LOCALES.contains(&locale) && locale_pattern(locale).is_some()
+}
+
+fn locale_pattern(locale: &str) -> Option<&'static str> {
+    LOCALE_DATE_FORMATS
+        .iter()
+        .find(|(name, _)| *name == locale)
+        .map(|(_, pattern)| *pattern)
+}
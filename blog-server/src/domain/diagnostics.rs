@@ -0,0 +1,78 @@
+//! Redaction of the request bodies kept by the admin diagnostics.
+
+use std::borrow::Cow;
+
+use blog_shared::truncate;
+use serde_json::Value;
+
+use crate::constants::{MAX_DIAGNOSTIC_BODY_LEN, REDACTED, SENSITIVE_FIELD_MARKERS};
+
+/// Request body format the diagnostics can redact and keep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticBody {
+    Json,
+    Form,
+}
+
+impl DiagnosticBody {
+    /// Format of a body with this `Content-Type`; `None` for bodies that are
+    /// only summarized by their size, like uploads.
+    pub fn of(content_type: &str) -> Option<Self> {
+        match content_type.split(';').next().map(str::trim) {
+            Some("application/json") => Some(Self::Json),
+            Some("application/x-www-form-urlencoded") => Some(Self::Form),
+            _ => None,
+        }
+    }
+
+    /// The body with the values of sensitive fields replaced by
+    /// [`REDACTED`], cut to `MAX_DIAGNOSTIC_BODY_LEN` characters. Invalid
+    /// JSON is only summarized, as it cannot be redacted.
+    pub fn redact(self, body: &[u8]) -> String {
+        let redacted = match self {
+            Self::Json => match serde_json::from_slice::<Value>(body) {
+                Ok(mut value) => {
+                    redact_json(&mut value);
+                    value.to_string()
+                }
+                Err(_) => format!("[{} bytes of invalid JSON]", body.len()),
+            },
+            Self::Form => form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(form_urlencoded::parse(body).map(|(name, value)| {
+                    let value = if is_sensitive_field(&name) {
+                        Cow::Borrowed(REDACTED)
+                    } else {
+                        value
+                    };
+                    (name, value)
+                }))
+                .finish(),
+        };
+        truncate(&redacted, MAX_DIAGNOSTIC_BODY_LEN)
+    }
+}
+
+/// Whether a field may hold a credential, judged by its name.
+pub fn is_sensitive_field(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    SENSITIVE_FIELD_MARKERS
+        .iter()
+        .any(|marker| name.contains(marker))
+}
+
+/// Redacts sensitive fields at any depth.
+fn redact_json(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            for (name, field) in fields {
+                if is_sensitive_field(name) {
+                    *field = Value::String(REDACTED.to_string());
+                } else {
+                    redact_json(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
@@ -0,0 +1,97 @@
+//! Account email changes confirmed from the new address.
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use chrono::{DateTime, Duration, Utc};
+use sha2::{Digest, Sha256};
+
+use super::markdown::escape_into;
+use super::newsletter::EmailBody;
+use crate::constants::EMAIL_CHANGE_EXPIRY_HOURS;
+
+/// Email change waiting for the new address to be confirmed.
+#[derive(Debug, Clone)]
+pub struct EmailChange {
+    pub user_id: i64,
+    pub new_email: String,
+    /// SHA-256 of the secret in the confirmation link.
+    pub token_hash: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl EmailChange {
+    /// Returns when the confirmation link stops working.
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        self.created_at + Duration::hours(EMAIL_CHANGE_EXPIRY_HOURS)
+    }
+}
+
+/// Generates the secret of a confirmation link.
+pub fn generate_email_change_token() -> String {
+    format!("{:016x}{:016x}", OsRng.next_u64(), OsRng.next_u64())
+}
+
+/// Returns the hash a confirmation token is stored under.
+pub fn hash_email_change_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Renders the email asking the recipient to confirm their address as the
+/// new one of `username`'s account.
+pub fn render_email_change_confirmation(username: &str, confirm_url: &str) -> EmailBody {
+    let text = format!(
+        "To make this the email address of your account {username}, open this link \
+         within {EMAIL_CHANGE_EXPIRY_HOURS} hours:\n\n\
+         {confirm_url}\n\n\
+         If you did not ask for this, ignore this email; nothing changes.\n"
+    );
+
+    let mut html = String::from("<p>To make this the email address of your account <b>");
+    escape_into(&mut html, username);
+    html.push_str(&format!(
+        "</b>, confirm it within {EMAIL_CHANGE_EXPIRY_HOURS} hours:</p>\n<p><a href=\""
+    ));
+    escape_into(&mut html, confirm_url);
+    html.push_str(
+        "\">Confirm email address</a></p>\n\
+         <p>If you did not ask for this, ignore this email; nothing changes.</p>\n",
+    );
+
+    EmailBody { text, html }
+}
+
+/// Renders the notice sent to the previous address of `username`'s account
+/// once its email has changed, showing the new address masked.
+pub fn render_email_changed_notice(username: &str, new_email: &str) -> EmailBody {
+    let masked = mask_email(new_email);
+    let text = format!(
+        "The email address of your account {username} was changed to {masked}.\n\n\
+         If you did not do this, reset your password with a recovery code and \
+         contact the site administrators.\n"
+    );
+
+    let mut html = String::from("<p>The email address of your account <b>");
+    escape_into(&mut html, username);
+    html.push_str("</b> was changed to ");
+    escape_into(&mut html, &masked);
+    html.push_str(
+        ".</p>\n<p>If you did not do this, reset your password with a recovery code \
+         and contact the site administrators.</p>\n",
+    );
+
+    EmailBody { text, html }
+}
+
+/// Hides all but the first character of an address's local part, so the
+/// notice does not hand the new address to whoever reads the old mailbox.
+fn mask_email(email: &str) -> String {
+    match email.split_once('@') {
+        Some((local, domain)) => {
+            let first: String = local.chars().take(1).collect();
+            format!("{first}***@{domain}")
+        }
+        None => "***".to_string(),
+    }
+}
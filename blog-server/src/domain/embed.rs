@@ -0,0 +1,115 @@
+//! Embeds for whitelisted providers.
+//!
+//! A bare URL alone in a paragraph becomes an iframe when its provider is
+//! enabled. The iframe source is rebuilt from the IDs parsed out of the URL,
+//! never copied from it, so only known embed pages can be framed.
+
+use std::str::FromStr;
+
+/// Provider whose URLs can be embedded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbedProvider {
+    YouTube,
+    Gist,
+}
+
+impl EmbedProvider {
+    /// Every supported provider.
+    pub const ALL: [EmbedProvider; 2] = [EmbedProvider::YouTube, EmbedProvider::Gist];
+
+    /// Returns the iframe for `url` if it is an embeddable link of this provider.
+    fn embed(self, url: &str) -> Option<String> {
+        let (host, path, query) = split_url(url)?;
+        match self {
+            EmbedProvider::YouTube => {
+                let id = match host {
+                    "youtu.be" => path.trim_start_matches('/'),
+                    "youtube.com" | "www.youtube.com" | "m.youtube.com" => {
+                        match path.strip_prefix('/')?.split_once('/') {
+                            Some(("embed" | "shorts", id)) => id,
+                            _ if path == "/watch" => query_param(query, "v")?,
+                            _ => return None,
+                        }
+                    }
+                    _ => return None,
+                };
+                is_video_id(id).then(|| {
+                    format!(
+                        "<div class=\"embed embed-youtube\"><iframe \
+                         src=\"https://www.youtube-nocookie.com/embed/{id}\" \
+                         title=\"YouTube video\" loading=\"lazy\" \
+                         allow=\"encrypted-media; picture-in-picture\" allowfullscreen \
+                         referrerpolicy=\"strict-origin-when-cross-origin\" \
+                         sandbox=\"allow-scripts allow-same-origin allow-presentation allow-popups\">\
+                         </iframe></div>\n"
+                    )
+                })
+            }
+            EmbedProvider::Gist => {
+                if host != "gist.github.com" {
+                    return None;
+                }
+                let (user, id) = path.strip_prefix('/')?.split_once('/')?;
+                let valid = is_slug(user) && id.chars().all(|c| c.is_ascii_hexdigit());
+                (valid && !id.is_empty()).then(|| {
+                    format!(
+                        "<div class=\"embed embed-gist\"><iframe \
+                         src=\"https://gist.github.com/{user}/{id}.pibb\" \
+                         title=\"GitHub Gist\" loading=\"lazy\" \
+                         referrerpolicy=\"no-referrer\" sandbox=\"allow-popups\">\
+                         </iframe></div>\n"
+                    )
+                })
+            }
+        }
+    }
+}
+
+impl FromStr for EmbedProvider {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "youtube" => Ok(EmbedProvider::YouTube),
+            "gist" => Ok(EmbedProvider::Gist),
+            other => Err(format!("Unknown embed provider: {other}")),
+        }
+    }
+}
+
+/// Returns the embed HTML for `url` using the first enabled provider that matches.
+pub fn embed_html(url: &str, providers: &[EmbedProvider]) -> Option<String> {
+    providers.iter().find_map(|provider| provider.embed(url))
+}
+
+/// Splits an http(s) URL into host, path, and query; the fragment is dropped.
+fn split_url(url: &str) -> Option<(&str, &str, &str)> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let rest = rest.split('#').next().unwrap_or(rest);
+    let (rest, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let (host, path) = rest.find('/').map_or((rest, ""), |i| rest.split_at(i));
+    let path = path.trim_end_matches('/');
+    Some((host, path, query))
+}
+
+/// Returns the value of a query parameter.
+fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find_map(|(key, value)| (key == name).then_some(value))
+}
+
+/// Returns true for an 11-character YouTube video ID.
+fn is_video_id(id: &str) -> bool {
+    id.len() == 11 && is_slug(id)
+}
+
+/// Returns true for a non-empty run of letters, digits, `-`, and `_`.
+fn is_slug(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
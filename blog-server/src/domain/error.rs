@@ -1,8 +1,13 @@
 //! Application error types.
 
+use actix_web::http::StatusCode;
+use actix_web::http::header::RETRY_AFTER;
 use actix_web::{HttpResponse, ResponseError};
+use blog_shared::ErrorResponse;
 use thiserror::Error;
 
+use crate::domain::QuotaStatus;
+
 /// Application-level errors.
 #[derive(Debug, Error)]
 pub enum AppError {
@@ -12,21 +17,109 @@ pub enum AppError {
     #[error("Post not found")]
     PostNotFound,
 
+    #[error("Comment not found")]
+    CommentNotFound,
+
+    #[error("Session not found")]
+    SessionNotFound,
+
+    #[error("Feature flag not found")]
+    FeatureFlagNotFound,
+
+    #[error("Conversation not found")]
+    ConversationNotFound,
+
+    #[error("Moderation rule not found")]
+    ModerationRuleNotFound,
+
+    #[error("Flagged content not found")]
+    ContentFlagNotFound,
+
+    #[error("Subscription not found")]
+    SubscriptionNotFound,
+
+    #[error("Integration not found")]
+    IntegrationNotFound,
+
+    #[error("Upload not found")]
+    UploadNotFound,
+
+    #[error("Service account not found")]
+    ServiceAccountNotFound,
+
+    #[error("Revision not found")]
+    RevisionNotFound,
+
+    #[error("Tag not found")]
+    TagNotFound,
+
+    #[error("Transfer not found")]
+    TransferNotFound,
+
+    #[error("Organization not found")]
+    OrgNotFound,
+
+    #[error("Experiment not found")]
+    ExperimentNotFound,
+
     #[error("Invalid credentials")]
     InvalidCredentials,
 
+    #[error("Invalid or used invite code")]
+    InvalidInvite,
+
+    #[error("Invalid or expired email confirmation link")]
+    InvalidEmailChange,
+
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+
     #[error("Forbidden")]
     Forbidden,
 
+    #[error("Account suspended")]
+    AccountSuspended,
+
+    #[error("{0} is disabled")]
+    FeatureDisabled(String),
+
+    #[error("Content was blocked by moderation rules")]
+    ContentBlocked,
+
+    #[error("API quota exceeded")]
+    QuotaExceeded {
+        status: QuotaStatus,
+        /// Seconds until the limit resets, counted when the quota was
+        /// checked (`Retry-After`).
+        retry_after_secs: i64,
+    },
+
     #[error("Username already exists")]
     UsernameExists,
 
     #[error("Email already exists")]
     EmailExists,
 
+    #[error("Tag already exists")]
+    TagExists,
+
+    #[error("Organization already exists")]
+    OrgExists,
+
+    #[error("Experiment already exists")]
+    ExperimentExists,
+
     #[error("Validation error: {0}")]
     Validation(String),
 
+    /// Malformed JSON body or query string.
+    #[error("Invalid request: {message}")]
+    InvalidRequest {
+        message: String,
+        /// JSON pointer to the offending field, if known.
+        pointer: Option<String>,
+    },
+
     #[error("Configuration error: {0}")]
     Config(String),
 
@@ -39,29 +132,134 @@ pub enum AppError {
     #[error("Password hashing error")]
     PasswordHash,
 
+    #[error("Fetch error: {0}")]
+    Fetch(String),
+
+    #[error("Mail error: {0}")]
+    Mail(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
 
+impl AppError {
+    /// Returns the stable, machine-readable code clients branch on. Server
+    /// faults all share `internal`, matching their generic message.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::UserNotFound => "user_not_found",
+            AppError::PostNotFound => "post_not_found",
+            AppError::CommentNotFound => "comment_not_found",
+            AppError::SessionNotFound => "session_not_found",
+            AppError::FeatureFlagNotFound => "feature_flag_not_found",
+            AppError::ConversationNotFound => "conversation_not_found",
+            AppError::ModerationRuleNotFound => "moderation_rule_not_found",
+            AppError::ContentFlagNotFound => "content_flag_not_found",
+            AppError::SubscriptionNotFound => "subscription_not_found",
+            AppError::IntegrationNotFound => "integration_not_found",
+            AppError::UploadNotFound => "upload_not_found",
+            AppError::ServiceAccountNotFound => "service_account_not_found",
+            AppError::RevisionNotFound => "revision_not_found",
+            AppError::TagNotFound => "tag_not_found",
+            AppError::TransferNotFound => "transfer_not_found",
+            AppError::OrgNotFound => "org_not_found",
+            AppError::ExperimentNotFound => "experiment_not_found",
+            AppError::InvalidCredentials | AppError::Jwt(_) => "invalid_credentials",
+            AppError::InvalidInvite => "invalid_invite",
+            AppError::InvalidEmailChange => "invalid_email_change",
+            AppError::InvalidSignature(_) => "invalid_signature",
+            AppError::Forbidden => "forbidden",
+            AppError::AccountSuspended => "account_suspended",
+            AppError::FeatureDisabled(_) => "feature_disabled",
+            AppError::ContentBlocked => "content_blocked",
+            AppError::QuotaExceeded { .. } => "quota_exceeded",
+            AppError::UsernameExists => "username_exists",
+            AppError::EmailExists => "email_exists",
+            AppError::TagExists => "tag_exists",
+            AppError::OrgExists => "org_exists",
+            AppError::ExperimentExists => "experiment_exists",
+            AppError::Validation(_) => "validation",
+            AppError::InvalidRequest { .. } => "invalid_request",
+            AppError::Config(_)
+            | AppError::Database(_)
+            | AppError::PasswordHash
+            | AppError::Fetch(_)
+            | AppError::Mail(_)
+            | AppError::Internal(_) => "internal",
+        }
+    }
+}
+
 impl ResponseError for AppError {
     fn error_response(&self) -> HttpResponse {
-        match self {
-            AppError::UserNotFound | AppError::PostNotFound => {
-                HttpResponse::NotFound().json(serde_json::json!({"error": self.to_string()}))
+        let (status, error) = match self {
+            AppError::UserNotFound
+            | AppError::PostNotFound
+            | AppError::CommentNotFound
+            | AppError::SessionNotFound
+            | AppError::FeatureFlagNotFound
+            | AppError::ConversationNotFound
+            | AppError::ModerationRuleNotFound
+            | AppError::ContentFlagNotFound
+            | AppError::SubscriptionNotFound
+            | AppError::IntegrationNotFound
+            | AppError::UploadNotFound
+            | AppError::ServiceAccountNotFound
+            | AppError::RevisionNotFound
+            | AppError::TagNotFound
+            | AppError::TransferNotFound
+            | AppError::OrgNotFound
+            | AppError::ExperimentNotFound => (StatusCode::NOT_FOUND, self.to_string()),
+            AppError::InvalidCredentials | AppError::Jwt(_) => {
+                (StatusCode::UNAUTHORIZED, "Invalid credentials".to_string())
             }
-            AppError::InvalidCredentials | AppError::Jwt(_) => HttpResponse::Unauthorized()
-                .json(serde_json::json!({"error": "Invalid credentials"})),
-            AppError::Forbidden => {
-                HttpResponse::Forbidden().json(serde_json::json!({"error": self.to_string()}))
-            }
-            AppError::UsernameExists | AppError::EmailExists | AppError::Validation(_) => {
-                HttpResponse::BadRequest().json(serde_json::json!({"error": self.to_string()}))
+            AppError::InvalidSignature(_) => (StatusCode::UNAUTHORIZED, self.to_string()),
+            AppError::Forbidden | AppError::AccountSuspended | AppError::FeatureDisabled(_) => {
+                (StatusCode::FORBIDDEN, self.to_string())
             }
+            AppError::UsernameExists
+            | AppError::EmailExists
+            | AppError::TagExists
+            | AppError::OrgExists
+            | AppError::ExperimentExists
+            | AppError::InvalidInvite
+            | AppError::InvalidEmailChange
+            | AppError::Validation(_)
+            | AppError::InvalidRequest { .. } => (StatusCode::BAD_REQUEST, self.to_string()),
+            AppError::ContentBlocked => (StatusCode::UNPROCESSABLE_ENTITY, self.to_string()),
+            AppError::QuotaExceeded { .. } => (StatusCode::TOO_MANY_REQUESTS, self.to_string()),
             AppError::Config(_)
             | AppError::Database(_)
             | AppError::PasswordHash
-            | AppError::Internal(_) => HttpResponse::InternalServerError()
-                .json(serde_json::json!({"error": "Internal server error"})),
+            | AppError::Fetch(_)
+            | AppError::Mail(_)
+            | AppError::Internal(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Internal server error".to_string(),
+            ),
+        };
+
+        let pointer = match self {
+            AppError::InvalidRequest { pointer, .. } => pointer.clone(),
+            _ => None,
+        };
+
+        let mut builder = HttpResponse::build(status);
+        if let AppError::QuotaExceeded {
+            status,
+            retry_after_secs,
+        } = self
+        {
+            for header in status.headers() {
+                builder.insert_header(header);
+            }
+            builder.insert_header((RETRY_AFTER, retry_after_secs.to_string()));
         }
+
+        builder.json(
+            ErrorResponse::new(error)
+                .with_code(self.code())
+                .with_pointer(pointer),
+        )
     }
 }
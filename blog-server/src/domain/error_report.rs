@@ -0,0 +1,100 @@
+//! Error reports sent to an error tracker such as Sentry.
+
+use std::fmt;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::AppError;
+
+/// Where a reported error happened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorSource {
+    /// A request the server answered with a 5xx status.
+    Server,
+    /// A panic in the server.
+    Panic,
+    /// A crash reported by a client, e.g. a WASM panic.
+    Client,
+}
+
+impl ErrorSource {
+    /// Returns the lowercase name used as a tag.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorSource::Server => "server",
+            ErrorSource::Panic => "panic",
+            ErrorSource::Client => "client",
+        }
+    }
+}
+
+impl fmt::Display for ErrorSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The HTTP request an error happened in, without credentials or bodies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestContext {
+    pub method: String,
+    /// Path without the query string, which may carry tokens.
+    pub path: String,
+    pub request_id: Option<String>,
+    /// Status of the response, when one was produced.
+    pub status: Option<u16>,
+    pub user_agent: Option<String>,
+}
+
+/// An error worth a human's attention.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorReport {
+    pub source: ErrorSource,
+    pub message: String,
+    /// Source location, e.g. `src/server.rs:42:7`.
+    pub location: Option<String>,
+    pub request: Option<RequestContext>,
+    /// Page a client was on.
+    pub url: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl ErrorReport {
+    /// Report of `message` from `source`, happening now.
+    pub fn new(source: ErrorSource, message: impl Into<String>) -> Self {
+        Self {
+            source,
+            message: message.into(),
+            location: None,
+            request: None,
+            url: None,
+            occurred_at: Utc::now(),
+        }
+    }
+
+    /// Sets the source location.
+    pub fn with_location(mut self, location: Option<String>) -> Self {
+        self.location = location;
+        self
+    }
+
+    /// Sets the request the error happened in.
+    pub fn with_request(mut self, request: RequestContext) -> Self {
+        self.request = Some(request);
+        self
+    }
+
+    /// Sets the page a client was on.
+    pub fn with_url(mut self, url: Option<String>) -> Self {
+        self.url = url;
+        self
+    }
+}
+
+/// Where error reports go.
+#[async_trait]
+pub trait ErrorReporter: Send + Sync {
+    /// Delivers a report to the tracker.
+    async fn report(&self, report: &ErrorReport) -> Result<(), AppError>;
+}
@@ -0,0 +1,29 @@
+//! Events published to live subscribers.
+
+use blog_shared::{CommentDto, EditingPresenceDto};
+use serde::{Deserialize, Serialize};
+
+/// Something that happened, for subscribers of the event bus.
+///
+/// Serializable so the bus can relay it between server replicas.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DomainEvent {
+    /// A comment was added. `hidden` when its author is shadow-banned, so
+    /// only the author may see it. The post and author IDs travel beside
+    /// the comment, which does not serialize its own.
+    CommentCreated {
+        comment: CommentDto,
+        post_id: i64,
+        author_id: i64,
+        hidden: bool,
+    },
+    /// The set of users editing a post changed. The post ID travels beside
+    /// the presence, like in `CommentCreated`.
+    EditingChanged {
+        post_id: i64,
+        presence: EditingPresenceDto,
+    },
+    /// A post was created, edited, trashed, restored, retagged or changed
+    /// status; subscribers reload it.
+    PostChanged { post_id: i64 },
+}
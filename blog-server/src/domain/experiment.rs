@@ -0,0 +1,118 @@
+//! A/B experiments and the assignment of users to their variants.
+
+use std::collections::HashSet;
+
+use blog_shared::ExperimentVariantDto;
+use blog_shared::constants::{
+    MAX_EXPERIMENT_DESCRIPTION_LENGTH, MAX_EXPERIMENT_NAME_LENGTH, MAX_EXPERIMENT_VARIANTS,
+};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+use crate::constants::EXPERIMENT_BUCKETS;
+use crate::domain::AppError;
+
+/// Experiment managed by admins.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Experiment {
+    pub id: i64,
+    pub name: String,
+    pub description: String,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A variant and the percentage of users it gets.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct ExperimentVariant {
+    pub name: String,
+    pub weight: i64,
+}
+
+/// Normalizes an experiment or variant name: trimmed and lowercased, 1 to
+/// `MAX_EXPERIMENT_NAME_LENGTH` letters, digits, `-` and `_`.
+pub fn parse_experiment_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim().to_lowercase();
+    if name.is_empty() || name.chars().count() > MAX_EXPERIMENT_NAME_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Experiment and variant names must be 1 to {MAX_EXPERIMENT_NAME_LENGTH} characters"
+        )));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(AppError::Validation(
+            "Experiment and variant names may only contain letters, digits, '-' and '_'"
+                .to_string(),
+        ));
+    }
+
+    Ok(name)
+}
+
+/// Validates an experiment description, returning it trimmed.
+pub fn parse_experiment_description(description: &str) -> Result<String, AppError> {
+    let description = description.trim();
+    if description.chars().count() > MAX_EXPERIMENT_DESCRIPTION_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Experiment descriptions must be at most {MAX_EXPERIMENT_DESCRIPTION_LENGTH} characters"
+        )));
+    }
+
+    Ok(description.to_string())
+}
+
+/// Validates the traffic split: 2 to `MAX_EXPERIMENT_VARIANTS` distinct
+/// variants whose weights sum to 100.
+pub fn parse_variants(
+    variants: Vec<ExperimentVariantDto>,
+) -> Result<Vec<ExperimentVariant>, AppError> {
+    if !(2..=MAX_EXPERIMENT_VARIANTS).contains(&variants.len()) {
+        return Err(AppError::Validation(format!(
+            "An experiment needs 2 to {MAX_EXPERIMENT_VARIANTS} variants"
+        )));
+    }
+
+    let mut names = HashSet::new();
+    let mut parsed = Vec::with_capacity(variants.len());
+    for variant in variants {
+        let name = parse_experiment_name(&variant.name)?;
+        if !names.insert(name.clone()) {
+            return Err(AppError::Validation(format!(
+                "Variant '{name}' is listed twice"
+            )));
+        }
+        parsed.push(ExperimentVariant {
+            name,
+            weight: i64::from(variant.weight),
+        });
+    }
+    if parsed.iter().map(|variant| variant.weight).sum::<i64>() != EXPERIMENT_BUCKETS {
+        return Err(AppError::Validation(format!(
+            "Variant weights must sum to {EXPERIMENT_BUCKETS}"
+        )));
+    }
+
+    Ok(parsed)
+}
+
+/// Picks a user's variant. The same user always lands in the same bucket
+/// of an experiment, while buckets are independent across experiments.
+pub fn assign_variant<'a>(
+    experiment: &str,
+    variants: &'a [ExperimentVariant],
+    user_id: i64,
+) -> Option<&'a ExperimentVariant> {
+    let digest = Sha256::digest(format!("{experiment}:{user_id}").as_bytes());
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&digest[..8]);
+    let bucket = (u64::from_be_bytes(prefix) % EXPERIMENT_BUCKETS as u64) as i64;
+
+    let mut upper = 0;
+    variants.iter().find(|variant| {
+        upper += variant.weight;
+        bucket < upper
+    })
+}
@@ -0,0 +1,12 @@
+//! Feature flag domain entity.
+
+use chrono::{DateTime, Utc};
+
+/// Runtime toggle for a site feature, managed by admins.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct FeatureFlag {
+    pub name: String,
+    pub description: String,
+    pub enabled: bool,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,198 @@
+//! RSS 2.0 and Atom 1.0 feeds of the newest posts.
+
+use std::collections::HashMap;
+
+use blog_shared::{PostDto, SiteSettingsDto};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+
+use super::markdown::escape_into;
+
+/// Addresses a feed links to.
+#[derive(Debug, Clone)]
+pub struct FeedUrls {
+    /// URL of the RSS feed itself.
+    pub rss: String,
+    /// URL of the Atom feed itself.
+    pub atom: String,
+    /// Base URL of the frontend, for post links.
+    pub site: String,
+    /// WebSub hub announced to subscribers.
+    pub hub: Option<String>,
+}
+
+impl FeedUrls {
+    /// Builds the feed URLs from the API and frontend base URLs.
+    pub fn new(public_url: &str, site_url: &str, hub: Option<String>) -> Self {
+        Self {
+            rss: format!("{public_url}/api/feed.rss"),
+            atom: format!("{public_url}/api/feed.atom"),
+            site: site_url.to_string(),
+            hub,
+        }
+    }
+
+    /// Frontend URL of a post, named by its public ID.
+    pub fn post(&self, public_id: &str) -> String {
+        format!("{}/?post={public_id}", self.site)
+    }
+}
+
+/// Time zone of each post author, by user ID; dates of other authors are
+/// in UTC.
+pub type AuthorTimezones = HashMap<i64, Tz>;
+
+/// Renders posts (newest first) as an RSS 2.0 channel named after the
+/// site, with dates in their authors' time zones. Items link to a post's
+/// canonical URL when it has one, and to its original source as
+/// `rel="via"`.
+pub fn render_rss(
+    posts: &[PostDto],
+    site: &SiteSettingsDto,
+    urls: &FeedUrls,
+    timezones: &AuthorTimezones,
+) -> String {
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+         <rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\" \
+         xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n<channel>\n",
+    );
+    element(&mut out, "title", &site.title);
+    element(&mut out, "link", &urls.site);
+    element(&mut out, "description", &description(site));
+    element(&mut out, "language", &site.default_language);
+    link(&mut out, "atom:link", "self", &urls.rss);
+    if let Some(ref hub) = urls.hub {
+        link(&mut out, "atom:link", "hub", hub);
+    }
+    if let Some(post) = posts.first() {
+        let date = local_time(post.created_at, post.author_id, timezones);
+        element(&mut out, "lastBuildDate", &date.to_rfc2822());
+    }
+
+    for post in posts {
+        let url = urls.post(&post.public_id);
+        out.push_str("<item>\n");
+        element(&mut out, "title", &post.title);
+        element(
+            &mut out,
+            "link",
+            post.canonical_url.as_deref().unwrap_or(&url),
+        );
+        element(&mut out, "guid", &url);
+        if let Some(ref source) = post.original_source {
+            link(&mut out, "atom:link", "via", source);
+        }
+        element(&mut out, "dc:creator", &post.author_username);
+        let date = local_time(post.created_at, post.author_id, timezones);
+        element(&mut out, "pubDate", &date.to_rfc2822());
+        for tag in &post.tags {
+            element(&mut out, "category", tag);
+        }
+        element(&mut out, "description", &post.content_html);
+        out.push_str("</item>\n");
+    }
+
+    out.push_str("</channel>\n</rss>\n");
+    out
+}
+
+/// Renders posts (newest first) as an Atom 1.0 feed, with dates in their
+/// authors' time zones. Title and links are as in [`render_rss`].
+pub fn render_atom(
+    posts: &[PostDto],
+    site: &SiteSettingsDto,
+    urls: &FeedUrls,
+    timezones: &AuthorTimezones,
+) -> String {
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+         <feed xmlns=\"http://www.w3.org/2005/Atom\">\n",
+    );
+    element(&mut out, "id", &urls.atom);
+    element(&mut out, "title", &site.title);
+    element(&mut out, "subtitle", &description(site));
+    link(&mut out, "link", "self", &urls.atom);
+    link(&mut out, "link", "alternate", &urls.site);
+    if let Some(ref hub) = urls.hub {
+        link(&mut out, "link", "hub", hub);
+    }
+    // Atom requires `updated`; an empty feed uses the epoch
+    let updated = posts
+        .iter()
+        .map(|post| post.updated_at)
+        .max()
+        .unwrap_or_default();
+    element(&mut out, "updated", &updated.to_rfc3339());
+
+    for post in posts {
+        let url = urls.post(&post.public_id);
+        out.push_str("<entry>\n");
+        element(&mut out, "id", &url);
+        element(&mut out, "title", &post.title);
+        link(
+            &mut out,
+            "link",
+            "alternate",
+            post.canonical_url.as_deref().unwrap_or(&url),
+        );
+        if let Some(ref source) = post.original_source {
+            link(&mut out, "link", "via", source);
+        }
+        let published = local_time(post.created_at, post.author_id, timezones);
+        let updated = local_time(post.updated_at, post.author_id, timezones);
+        element(&mut out, "published", &published.to_rfc3339());
+        element(&mut out, "updated", &updated.to_rfc3339());
+        out.push_str("<author>");
+        element(&mut out, "name", &post.author_username);
+        out.push_str("</author>\n");
+        for tag in &post.tags {
+            out.push_str("<category term=\"");
+            escape_into(&mut out, tag);
+            out.push_str("\"/>\n");
+        }
+        out.push_str("<content type=\"html\">");
+        escape_into(&mut out, &post.content_html);
+        out.push_str("</content>\n</entry>\n");
+    }
+
+    out.push_str("</feed>\n");
+    out
+}
+
+/// The feed description: the site's, or a generic one while it has none.
+fn description(site: &SiteSettingsDto) -> String {
+    if site.description.is_empty() {
+        format!("Newest posts on {}", site.title)
+    } else {
+        site.description.clone()
+    }
+}
+
+/// Converts a post date to its author's time zone.
+fn local_time(time: DateTime<Utc>, author_id: i64, timezones: &AuthorTimezones) -> DateTime<Tz> {
+    let timezone = timezones.get(&author_id).copied().unwrap_or(Tz::UTC);
+    time.with_timezone(&timezone)
+}
+
+/// Appends `<name>text</name>` with the text escaped.
+fn element(out: &mut String, name: &str, text: &str) {
+    out.push('<');
+    out.push_str(name);
+    out.push('>');
+    escape_into(out, text);
+    out.push_str("</");
+    out.push_str(name);
+    out.push_str(">\n");
+}
+
+/// Appends `<name rel="rel" href="href"/>`.
+fn link(out: &mut String, name: &str, rel: &str, href: &str) {
+    out.push('<');
+    out.push_str(name);
+    out.push_str(" rel=\"");
+    out.push_str(rel);
+    out.push_str("\" href=\"");
+    escape_into(out, href);
+    out.push_str("\"/>\n");
+}
@@ -0,0 +1,15 @@
+//! Invite codes for invite-only registration.
+
+use crate::domain::hash_recovery_code;
+use crate::domain::recovery_code::generate_code;
+
+/// Generates an invite code, formatted like a recovery code (`XXXXX-XXXXX`).
+pub fn generate_invite_code() -> String {
+    generate_code()
+}
+
+/// Returns the hash an invite code is stored under, ignoring case, dashes,
+/// and spaces as recovery codes do.
+pub fn hash_invite_code(code: &str) -> String {
+    hash_recovery_code(code)
+}
@@ -0,0 +1,35 @@
+//! Link check results for the broken link report.
+
+use chrono::{DateTime, Utc};
+
+/// Outcome of checking one linked URL.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkCheck {
+    pub url: String,
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl LinkCheck {
+    /// Returns true if the link looks dead: the request failed or the server
+    /// answered with an error. Rate limiting (429) is not counted as broken.
+    pub fn is_broken(&self) -> bool {
+        match self.status_code {
+            Some(status) => status >= 400 && status != 429,
+            None => true,
+        }
+    }
+}
+
+/// A broken link found in one of an author's posts.
+#[derive(Debug, Clone)]
+pub struct BrokenLink {
+    pub post_id: i64,
+    pub post_public_id: String,
+    pub post_title: String,
+    pub url: String,
+    pub status_code: Option<i64>,
+    pub error: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
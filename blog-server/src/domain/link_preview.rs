@@ -0,0 +1,130 @@
+//! Link preview entity and Open Graph parsing.
+
+use std::collections::HashMap;
+
+use blog_shared::truncate;
+
+use crate::constants::{MAX_PREVIEW_DESCRIPTION_LEN, MAX_PREVIEW_TITLE_LEN};
+
+/// Open Graph metadata of an external page linked from a post.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkPreview {
+    pub url: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub site_name: Option<String>,
+}
+
+/// Extracts a preview from a page's HTML.
+///
+/// Prefers `og:*` meta tags, falling back to `twitter:*`, the `description`
+/// meta tag, and `<title>`. Returns `None` when the page has no title.
+pub fn parse_open_graph(url: &str, html: &str) -> Option<LinkPreview> {
+    let meta = meta_tags(html);
+    let get = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| meta.get(*key))
+            .map(|value| decode_entities(value.trim()))
+            .filter(|value| !value.is_empty())
+    };
+
+    let title = get(&["og:title", "twitter:title"]).or_else(|| title_tag(html))?;
+    let image_url = get(&["og:image", "og:image:url", "twitter:image"])
+        .map(|image| resolve_url(url, &image))
+        .filter(|image| image.starts_with("https://") || image.starts_with("http://"));
+
+    Some(LinkPreview {
+        url: url.to_string(),
+        title: truncate(&title, MAX_PREVIEW_TITLE_LEN),
+        description: get(&["og:description", "twitter:description", "description"])
+            .map(|description| truncate(&description, MAX_PREVIEW_DESCRIPTION_LEN)),
+        image_url,
+        site_name: get(&["og:site_name"]),
+    })
+}
+
+/// Collects `<meta property|name=... content=...>` pairs (first one wins).
+fn meta_tags(html: &str) -> HashMap<String, String> {
+    let mut tags = HashMap::new();
+    let lower = html.to_ascii_lowercase();
+    let mut offset = 0;
+    while let Some(found) = lower[offset..].find("<meta") {
+        let start = offset + found;
+        let end = lower[start..]
+            .find('>')
+            .map_or(html.len(), |len| start + len);
+        let attrs = attributes(&html[start + "<meta".len()..end]);
+        let key = attrs.get("property").or_else(|| attrs.get("name"));
+        if let (Some(key), Some(content)) = (key, attrs.get("content")) {
+            tags.entry(key.to_ascii_lowercase())
+                .or_insert_with(|| content.clone());
+        }
+        offset = end;
+    }
+    tags
+}
+
+/// Parses `name="value"` attributes (quoted or bare) with lowercase names.
+fn attributes(tag: &str) -> HashMap<String, String> {
+    let mut attrs = HashMap::new();
+    let mut rest = tag.trim_start();
+    while let Some(eq) = rest.find('=') {
+        let name = rest[..eq].trim().rsplit(char::is_whitespace).next();
+        let after = rest[eq + 1..].trim_start();
+        let (value, remaining) = match after.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let body = &after[1..];
+                let close = body.find(quote).unwrap_or(body.len());
+                (&body[..close], body.get(close + 1..).unwrap_or(""))
+            }
+            _ => {
+                let close = after
+                    .find(|c: char| c.is_whitespace() || c == '/')
+                    .unwrap_or(after.len());
+                (&after[..close], &after[close..])
+            }
+        };
+        if let Some(name) = name.filter(|name| !name.is_empty()) {
+            attrs.insert(name.to_ascii_lowercase(), value.to_string());
+        }
+        rest = remaining;
+    }
+    attrs
+}
+
+/// Returns the text of the `<title>` element.
+fn title_tag(html: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let open = lower.find("<title")?;
+    let start = open + lower[open..].find('>')? + 1;
+    let end = start + lower[start..].find("</title")?;
+    Some(decode_entities(html[start..end].trim())).filter(|title| !title.is_empty())
+}
+
+/// Resolves a root-relative or protocol-relative URL against the page URL.
+fn resolve_url(page: &str, url: &str) -> String {
+    if let Some(rest) = url.strip_prefix("//") {
+        return format!("https://{rest}");
+    }
+    if url.starts_with('/') {
+        let scheme_end = page.find("://").map_or(0, |i| i + 3);
+        let origin_end = page[scheme_end..]
+            .find('/')
+            .map_or(page.len(), |i| scheme_end + i);
+        return format!("{}{url}", &page[..origin_end]);
+    }
+    url.to_string()
+}
+
+/// Decodes the handful of HTML entities common in metadata.
+fn decode_entities(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
@@ -0,0 +1,351 @@
+//! Markdown rendering with GFM extensions.
+//!
+//! Post content is stored as Markdown and rendered to HTML on read. The
+//! renderer doubles as the sanitizer: it only emits the tags it generates
+//! itself, escapes raw HTML from the source, and drops link and image URLs
+//! whose scheme is not http(s) or mailto, so its output is safe to insert
+//! into a page as-is. The only other HTML it emits is provider embeds from
+//! [`embed_html`], which build their own URLs.
+
+use std::collections::HashMap;
+
+use pulldown_cmark::{
+    Alignment, CodeBlockKind, Event, Options, Parser, Tag, TagEnd, TextMergeStream,
+};
+
+use crate::constants::{MARKDOWN_URL_SCHEMES, MAX_LINKS_PER_POST};
+use crate::domain::embed::{EmbedProvider, embed_html};
+
+mod autolink;
+
+use autolink::{autolink_into, bare_urls};
+
+/// Renders Markdown to sanitized HTML.
+///
+/// A paragraph holding only a URL of one of `embeds` becomes that embed.
+pub fn render_markdown(source: &str, embeds: &[EmbedProvider]) -> String {
+    let events: Vec<Event> = parse(source).collect();
+    let mut renderer = Renderer::default();
+    let mut i = 0;
+    while i < events.len() {
+        if let [
+            Event::Start(Tag::Paragraph),
+            Event::Text(text),
+            Event::End(TagEnd::Paragraph),
+            ..,
+        ] = &events[i..]
+            && let Some(embed) = embed_html(text.trim(), embeds)
+        {
+            renderer.out.push_str(&embed);
+            i += 3;
+            continue;
+        }
+        renderer.event(events[i].clone());
+        i += 1;
+    }
+    renderer.out
+}
+
+/// Returns the distinct external (http/https) link targets in Markdown, in
+/// order of appearance: link destinations and bare URLs outside code.
+pub fn external_links(source: &str) -> Vec<String> {
+    let mut links: Vec<String> = Vec::new();
+    let mut add = |url: &str| {
+        let external = url.starts_with("https://") || url.starts_with("http://");
+        if external && links.len() < MAX_LINKS_PER_POST && !links.iter().any(|l| l == url) {
+            links.push(url.to_string());
+        }
+    };
+    let mut in_code_block = false;
+    let mut link_depth = 0;
+    for event in parse(source) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                add(&dest_url);
+                link_depth += 1;
+            }
+            Event::End(TagEnd::Link) => link_depth -= 1,
+            Event::Text(text) if !in_code_block && link_depth == 0 => {
+                bare_urls(&text).into_iter().for_each(&mut add);
+            }
+            _ => {}
+        }
+    }
+    links
+}
+
+/// Parses Markdown with the supported extensions, merging adjacent text.
+fn parse(source: &str) -> TextMergeStream<'_, Parser<'_>> {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS;
+    TextMergeStream::new(Parser::new_ext(source, options))
+}
+
+/// HTML writer state.
+#[derive(Default)]
+struct Renderer {
+    out: String,
+    /// Whether each open link was emitted as `<a>` (unsafe URLs are not).
+    links: Vec<bool>,
+    /// Open image: its `src`/`title` and the alt text collected so far.
+    image: Option<(String, String, String)>,
+    in_code_block: bool,
+    table_alignments: Vec<Alignment>,
+    table_cell: usize,
+    in_table_head: bool,
+    /// Footnote numbers by label, in order of first use.
+    footnotes: HashMap<String, usize>,
+}
+
+impl Renderer {
+    /// Writes one parser event.
+    fn event(&mut self, event: Event) {
+        if let Some((_, _, alt)) = self.image.as_mut() {
+            match event {
+                Event::End(TagEnd::Image) => self.close_image(),
+                Event::Text(text) | Event::Code(text) => alt.push_str(&text),
+                _ => {}
+            }
+            return;
+        }
+
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => {
+                if self.in_code_block || !self.links.is_empty() {
+                    escape_into(&mut self.out, &text);
+                } else {
+                    autolink_into(&mut self.out, &text);
+                }
+            }
+            Event::Code(code) => {
+                self.out.push_str("<code>");
+                escape_into(&mut self.out, &code);
+                self.out.push_str("</code>");
+            }
+            // Raw HTML is shown as text, never interpreted
+            Event::Html(html)
+            | Event::InlineHtml(html)
+            | Event::InlineMath(html)
+            | Event::DisplayMath(html) => escape_into(&mut self.out, &html),
+            Event::FootnoteReference(label) => {
+                let number = self.footnote_number(&label);
+                let id = footnote_id(&label);
+                self.out.push_str(&format!(
+                    "<sup class=\"footnote-ref\"><a href=\"#fn-{id}\" id=\"fnref-{id}\">{number}</a></sup>"
+                ));
+            }
+            Event::SoftBreak => self.out.push('\n'),
+            Event::HardBreak => self.out.push_str("<br>\n"),
+            Event::Rule => self.out.push_str("<hr>\n"),
+            Event::TaskListMarker(checked) => {
+                let checked = if checked { " checked" } else { "" };
+                self.out
+                    .push_str(&format!("<input type=\"checkbox\" disabled{checked}> "));
+            }
+        }
+    }
+
+    /// Writes an opening tag.
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => self.out.push_str("<p>"),
+            Tag::Heading { level, .. } => self.out.push_str(&format!("<{level}>")),
+            Tag::BlockQuote(_) => self.out.push_str("<blockquote>\n"),
+            Tag::CodeBlock(kind) => {
+                self.in_code_block = true;
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => info
+                        .split_whitespace()
+                        .next()
+                        .filter(|lang| {
+                            lang.chars()
+                                .all(|c| c.is_ascii_alphanumeric() || "-_+#".contains(c))
+                        })
+                        .map(str::to_string),
+                    CodeBlockKind::Indented => None,
+                };
+                match language {
+                    Some(lang) => self
+                        .out
+                        .push_str(&format!("<pre><code class=\"language-{lang}\">")),
+                    None => self.out.push_str("<pre><code>"),
+                }
+            }
+            Tag::HtmlBlock | Tag::MetadataBlock(_) => {}
+            Tag::List(Some(1)) => self.out.push_str("<ol>\n"),
+            Tag::List(Some(start)) => self.out.push_str(&format!("<ol start=\"{start}\">\n")),
+            Tag::List(None) => self.out.push_str("<ul>\n"),
+            Tag::Item => self.out.push_str("<li>"),
+            Tag::FootnoteDefinition(label) => {
+                let number = self.footnote_number(&label);
+                let id = footnote_id(&label);
+                self.out.push_str(&format!(
+                    "<div class=\"footnote\" id=\"fn-{id}\"><sup>{number}</sup> "
+                ));
+            }
+            Tag::DefinitionList => self.out.push_str("<dl>\n"),
+            Tag::DefinitionListTitle => self.out.push_str("<dt>"),
+            Tag::DefinitionListDefinition => self.out.push_str("<dd>"),
+            Tag::Table(alignments) => {
+                self.table_alignments = alignments;
+                self.out.push_str("<table>\n");
+            }
+            Tag::TableHead => {
+                self.in_table_head = true;
+                self.table_cell = 0;
+                self.out.push_str("<thead>\n<tr>");
+            }
+            Tag::TableRow => {
+                self.table_cell = 0;
+                self.out.push_str("<tr>");
+            }
+            Tag::TableCell => {
+                let cell = if self.in_table_head { "th" } else { "td" };
+                let align = match self.table_alignments.get(self.table_cell) {
+                    Some(Alignment::Left) => " style=\"text-align: left\"",
+                    Some(Alignment::Center) => " style=\"text-align: center\"",
+                    Some(Alignment::Right) => " style=\"text-align: right\"",
+                    _ => "",
+                };
+                self.out.push_str(&format!("<{cell}{align}>"));
+            }
+            Tag::Emphasis => self.out.push_str("<em>"),
+            Tag::Strong => self.out.push_str("<strong>"),
+            Tag::Strikethrough => self.out.push_str("<del>"),
+            Tag::Superscript => self.out.push_str("<sup>"),
+            Tag::Subscript => self.out.push_str("<sub>"),
+            Tag::Link {
+                dest_url, title, ..
+            } => {
+                let safe = is_safe_url(&dest_url);
+                if safe {
+                    self.out.push_str("<a href=\"");
+                    escape_into(&mut self.out, &dest_url);
+                    self.out.push('"');
+                    if !title.is_empty() {
+                        self.out.push_str(" title=\"");
+                        escape_into(&mut self.out, &title);
+                        self.out.push('"');
+                    }
+                    self.out.push_str(" rel=\"nofollow noopener\">");
+                }
+                self.links.push(safe);
+            }
+            Tag::Image {
+                dest_url, title, ..
+            } => {
+                self.image = Some((dest_url.to_string(), title.to_string(), String::new()));
+            }
+        }
+    }
+
+    /// Writes a closing tag.
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => self.out.push_str("</p>\n"),
+            TagEnd::Heading(level) => self.out.push_str(&format!("</{level}>\n")),
+            TagEnd::BlockQuote(_) => self.out.push_str("</blockquote>\n"),
+            TagEnd::CodeBlock => {
+                self.in_code_block = false;
+                self.out.push_str("</code></pre>\n");
+            }
+            TagEnd::HtmlBlock | TagEnd::MetadataBlock(_) | TagEnd::Image => {}
+            TagEnd::List(true) => self.out.push_str("</ol>\n"),
+            TagEnd::List(false) => self.out.push_str("</ul>\n"),
+            TagEnd::Item => self.out.push_str("</li>\n"),
+            TagEnd::FootnoteDefinition => self.out.push_str("</div>\n"),
+            TagEnd::DefinitionList => self.out.push_str("</dl>\n"),
+            TagEnd::DefinitionListTitle => self.out.push_str("</dt>\n"),
+            TagEnd::DefinitionListDefinition => self.out.push_str("</dd>\n"),
+            TagEnd::Table => self.out.push_str("</tbody>\n</table>\n"),
+            TagEnd::TableHead => {
+                self.in_table_head = false;
+                self.out.push_str("</tr>\n</thead>\n<tbody>\n");
+            }
+            TagEnd::TableRow => self.out.push_str("</tr>\n"),
+            TagEnd::TableCell => {
+                self.out
+                    .push_str(if self.in_table_head { "</th>" } else { "</td>" });
+                self.table_cell += 1;
+            }
+            TagEnd::Emphasis => self.out.push_str("</em>"),
+            TagEnd::Strong => self.out.push_str("</strong>"),
+            TagEnd::Strikethrough => self.out.push_str("</del>"),
+            TagEnd::Superscript => self.out.push_str("</sup>"),
+            TagEnd::Subscript => self.out.push_str("</sub>"),
+            TagEnd::Link => {
+                if self.links.pop() == Some(true) {
+                    self.out.push_str("</a>");
+                }
+            }
+        }
+    }
+
+    /// Writes the open image, or just its alt text if the URL is unsafe.
+    fn close_image(&mut self) {
+        let Some((src, title, alt)) = self.image.take() else {
+            return;
+        };
+        if !is_safe_url(&src) {
+            escape_into(&mut self.out, &alt);
+            return;
+        }
+        self.out.push_str("<img src=\"");
+        escape_into(&mut self.out, &src);
+        self.out.push_str("\" alt=\"");
+        escape_into(&mut self.out, &alt);
+        self.out.push('"');
+        if !title.is_empty() {
+            self.out.push_str(" title=\"");
+            escape_into(&mut self.out, &title);
+            self.out.push('"');
+        }
+        self.out.push_str(" loading=\"lazy\">");
+    }
+
+    /// Returns the number of a footnote, assigning the next one on first use.
+    fn footnote_number(&mut self, label: &str) -> usize {
+        let next = self.footnotes.len() + 1;
+        *self.footnotes.entry(label.to_string()).or_insert(next)
+    }
+}
+
+/// Returns true for relative URLs and those with an allowed scheme.
+fn is_safe_url(url: &str) -> bool {
+    let url = url.trim();
+    // A scheme is whatever precedes the first ':' if no '/', '?', or '#' comes first
+    match url.find([':', '/', '?', '#']) {
+        Some(i) if url[i..].starts_with(':') => MARKDOWN_URL_SCHEMES
+            .iter()
+            .any(|scheme| url[..i].eq_ignore_ascii_case(scheme)),
+        _ => true,
+    }
+}
+
+/// Makes a footnote label usable in an `id` attribute.
+fn footnote_id(label: &str) -> String {
+    label
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// Appends text with HTML special characters escaped.
+pub(crate) fn escape_into(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! Bare URL detection for autolinks.
+
+use crate::constants::AUTOLINK_PREFIXES;
+
+use super::escape_into;
+
+/// Appends escaped text, turning bare http(s) URLs into links.
+pub(super) fn autolink_into(out: &mut String, text: &str) {
+    let mut rest = text;
+    while let Some((start, url)) = next_url(rest) {
+        escape_into(out, &rest[..start]);
+        out.push_str("<a href=\"");
+        escape_into(out, url);
+        out.push_str("\" rel=\"nofollow noopener\">");
+        escape_into(out, url);
+        out.push_str("</a>");
+        rest = &rest[start + url.len()..];
+    }
+    escape_into(out, rest);
+}
+
+/// Returns the bare http(s) URLs in plain text.
+pub(super) fn bare_urls(text: &str) -> Vec<&str> {
+    let mut urls = Vec::new();
+    let mut rest = text;
+    while let Some((start, url)) = next_url(rest) {
+        urls.push(url);
+        rest = &rest[start + url.len()..];
+    }
+    urls
+}
+
+/// Finds the next bare URL: its byte offset and the URL itself.
+fn next_url(text: &str) -> Option<(usize, &str)> {
+    let mut offset = 0;
+    while let Some(found) = find_url_start(&text[offset..]) {
+        let start = offset + found;
+        let end = text[start..]
+            .find(|c: char| c.is_whitespace() || c == '<')
+            .map_or(text.len(), |len| start + len);
+        let url = trim_url(&text[start..end]);
+        // A bare prefix ("https://") is not a link
+        if !AUTOLINK_PREFIXES.contains(&url) {
+            return Some((start, url));
+        }
+        offset = start + url.len();
+    }
+    None
+}
+
+/// Finds the next URL prefix that starts a word.
+fn find_url_start(text: &str) -> Option<usize> {
+    AUTOLINK_PREFIXES
+        .iter()
+        .filter_map(|prefix| {
+            text.match_indices(prefix)
+                .map(|(i, _)| i)
+                .find(|&i| !text[..i].ends_with(|c: char| c.is_alphanumeric()))
+        })
+        .min()
+}
+
+/// Drops trailing punctuation and unbalanced closing parentheses.
+fn trim_url(url: &str) -> &str {
+    let mut url = url.trim_end_matches(['.', ',', ':', ';', '!', '?', '\'', '"']);
+    while url.ends_with(')') && url.matches(')').count() > url.matches('(').count() {
+        url = url[..url.len() - 1].trim_end_matches(['.', ',', ':', ';', '!', '?', '\'', '"']);
+    }
+    url
+}
@@ -0,0 +1,28 @@
+//! Private messaging domain entities.
+
+use chrono::{DateTime, Utc};
+
+/// Conversation as seen by one of its two participants.
+#[derive(Debug, Clone)]
+pub struct Conversation {
+    pub id: i64,
+    pub other_user_id: i64,
+    pub other_user_public_id: String,
+    pub other_username: String,
+    pub last_message: Option<String>,
+    pub last_message_at: Option<DateTime<Utc>>,
+    pub unread_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Message entity, loaded together with its sender's username.
+#[derive(Debug, Clone)]
+pub struct Message {
+    pub id: i64,
+    pub conversation_id: i64,
+    pub sender_id: i64,
+    pub sender_public_id: String,
+    pub sender_username: String,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,176 @@
+//! Micropub create requests and IndieAuth token verification responses.
+
+use blog_shared::truncate;
+use serde_json::Value;
+
+use super::AppError;
+use crate::constants::MICROPUB_TITLE_LEN;
+
+/// An `h-entry` to publish, parsed from a Micropub create request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MicropubEntry {
+    pub name: Option<String>,
+    pub content: String,
+    pub categories: Vec<String>,
+}
+
+impl MicropubEntry {
+    /// Parses a form-encoded request (`h=entry&content=...&category[]=...`).
+    ///
+    /// Unknown properties such as `mp-slug` or `published` are ignored.
+    pub fn from_form(pairs: &[(String, String)]) -> Result<Self, AppError> {
+        let mut name = None;
+        let mut content = None;
+        let mut categories = Vec::new();
+        for (key, value) in pairs {
+            match key.as_str() {
+                "action" => check_action(value)?,
+                "h" if value != "entry" => {
+                    return Err(AppError::Validation(format!("Unsupported type h-{value}")));
+                }
+                "name" => name = Some(value.clone()),
+                "content" => content = Some(value.clone()),
+                "category" | "category[]" => categories.push(value.clone()),
+                _ => {}
+            }
+        }
+
+        Self::new(name, content, categories)
+    }
+
+    /// Parses a JSON request in microformats2 syntax
+    /// (`{"type": ["h-entry"], "properties": {...}}`).
+    pub fn from_json(body: &Value) -> Result<Self, AppError> {
+        if let Some(action) = body.get("action").and_then(Value::as_str) {
+            check_action(action)?;
+        }
+        let kind = body["type"][0].as_str().unwrap_or("h-entry");
+        if kind != "h-entry" {
+            return Err(AppError::Validation(format!("Unsupported type {kind}")));
+        }
+
+        let properties = &body["properties"];
+        let name = properties["name"][0].as_str().map(str::to_string);
+        let content = match &properties["content"][0] {
+            Value::String(text) => Some(text.clone()),
+            // `{"html": ...}` is refused: posts are Markdown, not HTML
+            Value::Object(object) => match object.get("value").and_then(Value::as_str) {
+                Some(text) => Some(text.to_string()),
+                None => {
+                    return Err(AppError::Validation(
+                        "HTML content is not supported; send plain text or Markdown".to_string(),
+                    ));
+                }
+            },
+            _ => None,
+        };
+        let categories = properties["category"]
+            .as_array()
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self::new(name, content, categories)
+    }
+
+    fn new(
+        name: Option<String>,
+        content: Option<String>,
+        categories: Vec<String>,
+    ) -> Result<Self, AppError> {
+        let content = content
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty())
+            .ok_or_else(|| AppError::Validation("Missing content".to_string()))?;
+        let name = name
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty());
+
+        Ok(Self {
+            name,
+            content,
+            categories,
+        })
+    }
+
+    /// Post title: the entry name, or the start of the first line for notes.
+    pub fn title(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => {
+                let first_line = self.content.lines().next().unwrap_or_default();
+                truncate(first_line.trim(), MICROPUB_TITLE_LEN)
+            }
+        }
+    }
+}
+
+/// Only `create` is supported; updates and deletes go through the blog API.
+fn check_action(action: &str) -> Result<(), AppError> {
+    if action == "create" {
+        Ok(())
+    } else {
+        Err(AppError::Validation(format!("Unsupported action {action}")))
+    }
+}
+
+/// A token confirmed by the IndieAuth token endpoint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndieAuthToken {
+    /// Canonical profile URL of the token's owner.
+    pub me: String,
+    pub scopes: Vec<String>,
+}
+
+impl IndieAuthToken {
+    /// Reads a token endpoint verification response; `None` if `me` is missing.
+    pub fn from_json(body: &Value) -> Option<Self> {
+        let me = canonical_profile_url(body["me"].as_str()?)?;
+        let scopes = body["scope"]
+            .as_str()
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(str::to_string)
+            .collect();
+
+        Some(Self { me, scopes })
+    }
+
+    /// Returns true if the token may publish posts (`post` is the legacy name).
+    pub fn can_create(&self) -> bool {
+        self.scopes
+            .iter()
+            .any(|scope| scope == "create" || scope == "post")
+    }
+}
+
+/// Canonicalizes a profile URL so `https://Example.com` and
+/// `https://example.com/` compare equal.
+///
+/// Returns `None` for non-http(s) URLs and URLs with a fragment.
+pub fn canonical_profile_url(url: &str) -> Option<String> {
+    let url = url.trim();
+    let (scheme, rest) = url.split_once("://")?;
+    let scheme = scheme.to_ascii_lowercase();
+    if !matches!(scheme.as_str(), "http" | "https") || url.contains('#') {
+        return None;
+    }
+
+    let split = rest.find(['/', '?']).unwrap_or(rest.len());
+    let (host, path) = rest.split_at(split);
+    if host.is_empty() {
+        return None;
+    }
+    let path = if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("/{path}")
+    };
+
+    Some(format!("{scheme}://{}{path}", host.to_ascii_lowercase()))
+}
@@ -0,0 +1,132 @@
+//! Automatic moderation rules and the content they flag.
+
+use blog_shared::{RuleAction, RuleKind};
+use chrono::{DateTime, Utc};
+use regex::{Regex, RegexBuilder};
+use tracing::warn;
+
+use crate::constants::MODERATION_RULE_SIZE_LIMIT;
+use crate::domain::AppError;
+
+/// Stored moderation rule with its hit statistics.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ModerationRule {
+    pub id: i64,
+    pub name: String,
+    /// Stored kind name; read through [`ModerationRule::kind`].
+    pub kind: String,
+    pub pattern: String,
+    /// Stored action name; read through [`ModerationRule::action`].
+    pub action: String,
+    pub enabled: bool,
+    pub checked_count: i64,
+    pub hit_count: i64,
+    pub last_hit_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A rule's definition as an admin writes it, stamped `now`.
+#[derive(Debug, Clone, Copy)]
+pub struct NewRule<'a> {
+    pub name: &'a str,
+    pub kind: RuleKind,
+    pub pattern: &'a str,
+    pub action: RuleAction,
+    pub enabled: bool,
+    pub now: DateTime<Utc>,
+}
+
+impl ModerationRule {
+    /// Returns the rule's kind (unknown stored values count as `Keyword`).
+    pub fn kind(&self) -> RuleKind {
+        self.kind.parse().unwrap_or(RuleKind::Keyword)
+    }
+
+    /// Returns the rule's action (unknown stored values count as `Flag`).
+    pub fn action(&self) -> RuleAction {
+        self.action.parse().unwrap_or(RuleAction::Flag)
+    }
+}
+
+/// Flagged post or comment with its author and the rule that matched.
+#[derive(Debug, Clone)]
+pub struct FlaggedContent {
+    pub id: i64,
+    pub post_id: i64,
+    pub post_public_id: String,
+    pub comment_id: Option<i64>,
+    pub author_id: i64,
+    pub author_username: String,
+    pub excerpt: String,
+    pub rule_id: i64,
+    pub rule_name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Compiles a rule pattern, rejecting invalid or oversized patterns.
+///
+/// Keyword lists become a case-insensitive whole-word alternation.
+pub fn compile_rule(kind: RuleKind, pattern: &str) -> Result<Regex, AppError> {
+    let source = match kind {
+        RuleKind::Keyword => {
+            let keywords: Vec<String> = pattern
+                .split([',', '\n'])
+                .map(str::trim)
+                .filter(|keyword| !keyword.is_empty())
+                .map(regex::escape)
+                .collect();
+            if keywords.is_empty() {
+                return Err(AppError::Validation(
+                    "Keyword list cannot be empty".to_string(),
+                ));
+            }
+            format!(r"(?i)\b(?:{})\b", keywords.join("|"))
+        }
+        RuleKind::Regex => pattern.to_string(),
+    };
+
+    RegexBuilder::new(&source)
+        .size_limit(MODERATION_RULE_SIZE_LIMIT)
+        .build()
+        .map_err(|e| AppError::Validation(format!("Invalid pattern: {e}")))
+}
+
+/// Enabled rules compiled for matching.
+#[derive(Debug, Default)]
+pub struct RuleSet {
+    rules: Vec<(i64, RuleAction, Regex)>,
+}
+
+impl RuleSet {
+    /// Compiles the enabled rules, skipping (and logging) any that no longer compile.
+    pub fn compile(rules: &[ModerationRule]) -> Self {
+        let rules = rules
+            .iter()
+            .filter(|rule| rule.enabled)
+            .filter_map(|rule| match compile_rule(rule.kind(), &rule.pattern) {
+                Ok(regex) => Some((rule.id, rule.action(), regex)),
+                Err(e) => {
+                    warn!(rule_id = rule.id, error = %e, "Skipping moderation rule");
+                    None
+                }
+            })
+            .collect();
+
+        Self { rules }
+    }
+
+    /// IDs of all rules in the set.
+    pub fn rule_ids(&self) -> Vec<i64> {
+        self.rules.iter().map(|(id, _, _)| *id).collect()
+    }
+
+    /// Rules that match `text`, with their actions.
+    pub fn matches(&self, text: &str) -> Vec<(i64, RuleAction)> {
+        self.rules
+            .iter()
+            .filter(|(_, _, regex)| regex.is_match(text))
+            .map(|(id, action, _)| (*id, *action))
+            .collect()
+    }
+}
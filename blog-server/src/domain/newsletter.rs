@@ -0,0 +1,117 @@
+//! Newsletter subscriptions and the weekly digest email.
+
+use std::fmt::Write;
+
+use blog_shared::{SubscriptionStatus, excerpt};
+use chrono::{DateTime, Utc};
+
+use super::date_format::DateFormat;
+use super::markdown::escape_into;
+use crate::constants::NEWSLETTER_EXCERPT_CHARS;
+
+/// Newsletter subscription entity.
+#[derive(Debug, Clone)]
+pub struct Subscription {
+    pub id: i64,
+    pub email: String,
+    /// Secret used in confirmation and unsubscribe links.
+    pub token: String,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub unsubscribed_at: Option<DateTime<Utc>>,
+    pub last_digest_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Subscription {
+    /// Returns the subscription's current state.
+    pub fn status(&self) -> SubscriptionStatus {
+        if self.unsubscribed_at.is_some() {
+            SubscriptionStatus::Unsubscribed
+        } else if self.confirmed_at.is_some() {
+            SubscriptionStatus::Confirmed
+        } else {
+            SubscriptionStatus::Pending
+        }
+    }
+}
+
+/// Post featured in a digest, with the number of comments it received.
+#[derive(Debug, Clone)]
+pub struct DigestPost {
+    pub public_id: String,
+    pub title: String,
+    pub content: String,
+    pub author_username: String,
+    pub comment_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Rendered email bodies.
+#[derive(Debug, Clone)]
+pub struct EmailBody {
+    pub text: String,
+    pub html: String,
+}
+
+/// Renders the email asking a new subscriber to confirm their address.
+pub fn render_confirmation(confirm_url: &str) -> EmailBody {
+    let text = format!(
+        "Please confirm your subscription to the weekly digest by opening this link:\n\n\
+         {confirm_url}\n\n\
+         If you did not subscribe, ignore this email.\n"
+    );
+
+    let mut url = String::new();
+    escape_into(&mut url, confirm_url);
+    let html = format!(
+        "<p>Please confirm your subscription to the weekly digest:</p>\n\
+         <p><a href=\"{url}\">Confirm subscription</a></p>\n\
+         <p>If you did not subscribe, ignore this email.</p>\n"
+    );
+
+    EmailBody { text, html }
+}
+
+/// Renders the weekly digest of top posts, with dates in the subscriber's
+/// time zone and locale.
+///
+/// `post_url` turns a post's public ID into a link to the post on the site.
+pub fn render_digest(
+    posts: &[DigestPost],
+    post_url: impl Fn(&str) -> String,
+    unsubscribe_url: &str,
+    dates: &DateFormat,
+) -> EmailBody {
+    let mut text = String::from("Top posts of the week\n\n");
+    let mut html = String::from("<h1>Top posts of the week</h1>\n<ul>\n");
+
+    for post in posts {
+        let url = post_url(&post.public_id);
+        let excerpt = excerpt(&post.content, NEWSLETTER_EXCERPT_CHARS);
+        let date = dates.format(post.created_at);
+        let _ = write!(
+            text,
+            "{}\nby {} · {} · {} comments\n{}\n{}\n\n",
+            post.title, post.author_username, date, post.comment_count, excerpt, url
+        );
+
+        html.push_str("<li>\n<h2><a href=\"");
+        escape_into(&mut html, &url);
+        html.push_str("\">");
+        escape_into(&mut html, &post.title);
+        html.push_str("</a></h2>\n<p><small>by ");
+        escape_into(&mut html, &post.author_username);
+        html.push_str(" · ");
+        escape_into(&mut html, &date);
+        let _ = write!(html, " · {} comments</small></p>\n<p>", post.comment_count);
+        escape_into(&mut html, &excerpt);
+        html.push_str("</p>\n</li>\n");
+    }
+
+    let _ = write!(text, "--\nUnsubscribe: {unsubscribe_url}\n");
+    html.push_str("</ul>\n<hr>\n<p><small><a href=\"");
+    escape_into(&mut html, unsubscribe_url);
+    html.push_str("\">Unsubscribe</a></small></p>\n");
+
+    EmailBody { text, html }
+}
@@ -0,0 +1,20 @@
+//! Notification domain entity.
+
+use chrono::{DateTime, Utc};
+
+/// Activity on a user's content, loaded with the actor's username.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Notification {
+    pub id: i64,
+    pub user_id: i64,
+    /// A `NotificationKind` name.
+    pub kind: String,
+    pub actor_id: i64,
+    pub actor_public_id: String,
+    pub actor_username: String,
+    pub post_id: i64,
+    pub post_public_id: String,
+    pub comment_id: Option<i64>,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
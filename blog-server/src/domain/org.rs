@@ -0,0 +1,98 @@
+//! Organizations: accounts whose members publish posts collectively.
+
+use blog_shared::OrgRole;
+use blog_shared::constants::{MAX_BIO_LENGTH, MAX_ORG_NAME_LENGTH, MAX_ORG_SLUG_LENGTH};
+use chrono::{DateTime, Utc};
+
+use crate::domain::AppError;
+
+/// An organization.
+#[derive(Debug, Clone)]
+pub struct Organization {
+    pub id: i64,
+    pub slug: String,
+    pub name: String,
+    pub bio: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A member of an organization.
+#[derive(Debug, Clone)]
+pub struct OrgMember {
+    pub user_id: i64,
+    pub public_id: String,
+    pub username: String,
+    /// Stored role name; read through [`OrgMember::role`].
+    pub role: String,
+    pub joined_at: DateTime<Utc>,
+}
+
+impl OrgMember {
+    /// Returns the member's role (unknown stored values count as writer).
+    pub fn role(&self) -> OrgRole {
+        self.role.parse().unwrap_or(OrgRole::Writer)
+    }
+}
+
+/// An organization with the role of one of its members.
+#[derive(Debug, Clone)]
+pub struct Membership {
+    pub id: i64,
+    pub slug: String,
+    pub name: String,
+    pub bio: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Stored role name; read through [`Membership::role`].
+    pub role: String,
+}
+
+impl Membership {
+    /// Returns the member's role (unknown stored values count as writer).
+    pub fn role(&self) -> OrgRole {
+        self.role.parse().unwrap_or(OrgRole::Writer)
+    }
+}
+
+/// Normalizes an organization slug: trimmed and lowercased, 2 to
+/// `MAX_ORG_SLUG_LENGTH` letters, digits and inner `-`.
+pub fn parse_org_slug(slug: &str) -> Result<String, AppError> {
+    let slug = slug.trim().to_lowercase();
+    let len = slug.chars().count();
+    if !(2..=MAX_ORG_SLUG_LENGTH).contains(&len) {
+        return Err(AppError::Validation(format!(
+            "Organization slugs must be 2 to {MAX_ORG_SLUG_LENGTH} characters"
+        )));
+    }
+    if !slug.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        || slug.starts_with('-')
+        || slug.ends_with('-')
+    {
+        return Err(AppError::Validation(
+            "Organization slugs may only contain letters, digits and inner '-'".to_string(),
+        ));
+    }
+
+    Ok(slug)
+}
+
+/// Validates an organization's display name and bio, returning them trimmed
+/// (a blank bio is none).
+pub fn parse_org_profile(
+    name: &str,
+    bio: Option<&str>,
+) -> Result<(String, Option<String>), AppError> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_ORG_NAME_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Organization names must be 1 to {MAX_ORG_NAME_LENGTH} characters"
+        )));
+    }
+    let bio = bio.map(str::trim).filter(|bio| !bio.is_empty());
+    if bio.is_some_and(|bio| bio.chars().count() > MAX_BIO_LENGTH) {
+        return Err(AppError::Validation(format!(
+            "Bio must be at most {MAX_BIO_LENGTH} characters"
+        )));
+    }
+
+    Ok((name.to_string(), bio.map(str::to_string)))
+}
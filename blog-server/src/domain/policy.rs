@@ -0,0 +1,149 @@
+//! Authorization policies: who may act on which content and accounts.
+//!
+//! Services check these instead of comparing IDs inline, so HTTP and gRPC
+//! callers get the same rules.
+
+use blog_shared::{CommentDto, OrgRole, PostDto, Role};
+
+use crate::domain::{AppError, Comment, Post, PostTransfer};
+
+/// Content with an author, as an entity or a DTO.
+pub trait Authored {
+    /// ID of the user who wrote the content.
+    fn author_id(&self) -> i64;
+}
+
+impl Authored for Post {
+    fn author_id(&self) -> i64 {
+        self.author_id
+    }
+}
+
+impl Authored for PostDto {
+    fn author_id(&self) -> i64 {
+        self.author_id
+    }
+}
+
+impl Authored for Comment {
+    fn author_id(&self) -> i64 {
+        self.author_id
+    }
+}
+
+impl Authored for CommentDto {
+    fn author_id(&self) -> i64 {
+        self.author_id
+    }
+}
+
+/// Only the author may edit a post, including cross-posting it.
+pub fn can_edit_post(user_id: i64, post: &impl Authored) -> bool {
+    post.author_id() == user_id
+}
+
+/// Only the author may delete a post.
+pub fn can_delete_post(user_id: i64, post: &impl Authored) -> bool {
+    post.author_id() == user_id
+}
+
+/// Members of an organization may also edit the posts published under it;
+/// `role` is the user's role there, if any.
+pub fn can_edit_org_post(role: Option<OrgRole>) -> bool {
+    role.is_some()
+}
+
+/// Owners of an organization may also delete the posts published under it.
+pub fn can_delete_org_post(role: Option<OrgRole>) -> bool {
+    role == Some(OrgRole::Owner)
+}
+
+/// Any member may post as an organization.
+pub fn can_post_as_org(role: Option<OrgRole>) -> bool {
+    role.is_some()
+}
+
+/// Only owners may manage an organization's members.
+pub fn can_manage_org(role: Option<OrgRole>) -> bool {
+    role == Some(OrgRole::Owner)
+}
+
+/// Only the author may hand a post over to another user.
+pub fn can_transfer_post(user_id: i64, post: &impl Authored) -> bool {
+    post.author_id() == user_id
+}
+
+/// Only the recipient may accept or decline a transfer.
+pub fn can_answer_transfer(user_id: i64, transfer: &PostTransfer) -> bool {
+    transfer.to_user_id == user_id
+}
+
+/// Only the sender may cancel a transfer.
+pub fn can_cancel_transfer(user_id: i64, transfer: &PostTransfer) -> bool {
+    transfer.from_user_id == user_id
+}
+
+/// Only the author may edit a comment.
+pub fn can_edit_comment(user_id: i64, comment: &impl Authored) -> bool {
+    comment.author_id() == user_id
+}
+
+/// A comment may be deleted by its author and by the author of its post.
+pub fn can_delete_comment(user_id: i64, comment: &impl Authored, post: &impl Authored) -> bool {
+    comment.author_id() == user_id || post.author_id() == user_id
+}
+
+/// Editors, moderators, and admins may review posts submitted for publication.
+pub fn can_review_posts(role: Role) -> bool {
+    role.can_review()
+}
+
+/// Reviewers may decide on any post but their own.
+pub fn can_review_post(user_id: i64, role: Role, post: &impl Authored) -> bool {
+    can_review_posts(role) && post.author_id() != user_id
+}
+
+/// An approved post may be published by its author and by reviewers.
+pub fn can_publish_post(user_id: i64, role: Role, post: &impl Authored) -> bool {
+    post.author_id() == user_id || can_review_posts(role)
+}
+
+/// The workflow history and revisions of a post are open to its author
+/// and to reviewers.
+pub fn can_view_history(user_id: i64, role: Role, post: &impl Authored) -> bool {
+    post.author_id() == user_id || can_review_posts(role)
+}
+
+/// Whether the (possibly anonymous) viewer wrote the content.
+pub fn is_author(viewer_id: Option<i64>, content: &impl Authored) -> bool {
+    viewer_id == Some(content.author_id())
+}
+
+/// Content of a shadow-banned author (`hidden`) is only visible to them.
+pub fn can_see(viewer_id: Option<i64>, content: &impl Authored, hidden: bool) -> bool {
+    !hidden || is_author(viewer_id, content)
+}
+
+/// Only admins may import posts as another user.
+pub fn can_import_as_other(role: Role) -> bool {
+    role == Role::Admin
+}
+
+/// Moderators may not change their own role, suspension, or shadow ban.
+pub fn can_manage_user(actor_id: i64, user_id: i64) -> bool {
+    actor_id != user_id
+}
+
+/// Moderators and admins cannot be shadow-banned.
+pub fn can_be_shadow_banned(role: Role) -> bool {
+    !role.can_moderate()
+}
+
+/// Turns a denied policy decision into `AppError::Forbidden`.
+pub fn authorize(allowed: bool) -> Result<(), AppError> {
+    if allowed {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden)
+    }
+}
@@ -1,14 +1,134 @@
 //! Post domain entity.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+
+use blog_shared::PostStatus;
+use blog_shared::constants::MAX_SOURCE_URL_LENGTH;
+
+use crate::domain::AppError;
 
 /// Post entity.
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct Post {
     pub id: i64,
+    /// Random ID used in API URLs instead of `id`.
+    pub public_id: String,
     pub title: String,
     pub content: String,
     pub author_id: i64,
+    /// Language code, e.g. `en` or `pt-BR`.
+    pub lang: String,
+    /// Shared by all language variants of the post; the public ID of the
+    /// first one.
+    pub translation_group_id: String,
+    /// URL search engines should treat as the post's canonical address,
+    /// for posts syndicated from elsewhere.
+    pub canonical_url: Option<String>,
+    /// Link to where the post was first published.
+    pub original_source: Option<String>,
+    /// Custom fields as a JSON object (see `merge_metadata`).
+    pub metadata: String,
+    /// Editorial workflow status (see [`Post::status`]).
+    pub status: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
+
+impl Post {
+    /// Returns the workflow status; unknown stored values read as published.
+    pub fn status(&self) -> PostStatus {
+        self.status.parse().unwrap_or_default()
+    }
+}
+
+/// A post to create, stamped `now`.
+#[derive(Debug, Clone, Copy)]
+pub struct NewPost<'a> {
+    pub title: &'a str,
+    pub content: &'a str,
+    pub author_id: i64,
+    pub lang: &'a str,
+    /// Translation group to join; `None` starts a group of its own.
+    pub translation_group_id: Option<&'a str>,
+    pub status: PostStatus,
+    pub now: DateTime<Utc>,
+}
+
+/// A post in its author's trash.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct TrashedPost {
+    pub id: i64,
+    pub public_id: String,
+    pub title: String,
+    pub deleted_at: DateTime<Utc>,
+}
+
+impl TrashedPost {
+    /// Returns when the retention job purges the post, if trash is kept for
+    /// `trash_days` (`None` keeps it forever).
+    pub fn purge_at(&self, trash_days: Option<u32>) -> Option<DateTime<Utc>> {
+        trash_days.map(|days| self.deleted_at + Duration::days(i64::from(days)))
+    }
+}
+
+/// Returns the whole days from `now` until `at`, rounded up; zero once `at`
+/// has passed.
+pub fn days_until(at: DateTime<Utc>, now: DateTime<Utc>) -> i64 {
+    let left = (at - now).max(Duration::zero());
+    left.num_days() + i64::from(left > Duration::days(left.num_days()))
+}
+
+/// Normalizes a post language code: a two- or three-letter language,
+/// optionally followed by a two-letter region (`pt-br` becomes `pt-BR`).
+pub fn parse_lang(lang: &str) -> Result<String, AppError> {
+    let lang = lang.trim();
+    let (language, region) = match lang.split_once(['-', '_']) {
+        Some((language, region)) => (language, Some(region)),
+        None => (lang, None),
+    };
+    let valid = (2..=3).contains(&language.len())
+        && language.chars().all(|c| c.is_ascii_alphabetic())
+        && region.is_none_or(|r| r.len() == 2 && r.chars().all(|c| c.is_ascii_alphabetic()));
+    if !valid {
+        return Err(AppError::Validation(
+            "Language must be a code like `en` or `pt-BR`".to_string(),
+        ));
+    }
+
+    Ok(match region {
+        Some(region) => format!(
+            "{}-{}",
+            language.to_ascii_lowercase(),
+            region.to_ascii_uppercase()
+        ),
+        None => language.to_ascii_lowercase(),
+    })
+}
+
+/// Validates an optional post source URL named `field` in errors: an
+/// absolute http(s) URL of at most `MAX_SOURCE_URL_LENGTH` characters.
+/// Blank input means no URL.
+pub fn parse_source_url(field: &str, url: &str) -> Result<Option<String>, AppError> {
+    let url = url.trim();
+    if url.is_empty() {
+        return Ok(None);
+    }
+    if url.len() > MAX_SOURCE_URL_LENGTH {
+        return Err(AppError::Validation(format!(
+            "{field} must be at most {MAX_SOURCE_URL_LENGTH} characters"
+        )));
+    }
+    let host = url
+        .split_once("://")
+        .filter(|(scheme, _)| matches!(scheme.to_ascii_lowercase().as_str(), "http" | "https"))
+        .map(|(_, rest)| &rest[..rest.find(['/', '?', '#']).unwrap_or(rest.len())]);
+    let valid = host.is_some_and(|host| !host.is_empty())
+        && !url.chars().any(|c| c.is_whitespace() || c.is_control());
+    if !valid {
+        return Err(AppError::Validation(format!(
+            "{field} must be an http:// or https:// URL"
+        )));
+    }
+
+    Ok(Some(url.to_string()))
+}
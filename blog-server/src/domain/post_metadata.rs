@@ -0,0 +1,68 @@
+//! Custom fields that integrations attach to posts.
+
+use std::collections::HashMap;
+
+use blog_shared::constants::{MAX_METADATA_BYTES, MAX_METADATA_KEY_LENGTH, MAX_METADATA_KEYS};
+use serde_json::Value;
+
+use super::AppError;
+
+/// Custom fields of a post, by key.
+pub type PostMetadata = HashMap<String, Value>;
+
+/// Reads stored custom fields. They are validated on write, so anything
+/// unreadable is treated as none rather than failing the post.
+pub fn parse_metadata(stored: &str) -> PostMetadata {
+    serde_json::from_str(stored).unwrap_or_default()
+}
+
+/// Applies `changes` to the stored fields `current`: each key is set to its
+/// new value, or removed when the value is `null`. Returns the merged fields
+/// and their stored form.
+///
+/// Keys are 1 to `MAX_METADATA_KEY_LENGTH` lowercase letters, digits, `_`,
+/// `-`, `.`, or `:`, starting with a letter, so integrations can namespace
+/// them (`seo.description`, `series:order`). A post holds at most
+/// `MAX_METADATA_KEYS` keys in at most `MAX_METADATA_BYTES` of JSON.
+pub fn merge_metadata(
+    current: &str,
+    changes: PostMetadata,
+) -> Result<(PostMetadata, String), AppError> {
+    let mut metadata = parse_metadata(current);
+    for (key, value) in changes {
+        if !is_valid_key(&key) {
+            return Err(AppError::Validation(format!(
+                "Invalid metadata key `{key}`: use up to {MAX_METADATA_KEY_LENGTH} lowercase letters, digits, _, -, . or :, starting with a letter"
+            )));
+        }
+        if value.is_null() {
+            metadata.remove(&key);
+        } else {
+            metadata.insert(key, value);
+        }
+    }
+
+    if metadata.len() > MAX_METADATA_KEYS {
+        return Err(AppError::Validation(format!(
+            "A post can have at most {MAX_METADATA_KEYS} metadata keys"
+        )));
+    }
+    let stored = serde_json::to_string(&metadata)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize metadata: {e}")))?;
+    if stored.len() > MAX_METADATA_BYTES {
+        return Err(AppError::Validation(format!(
+            "Post metadata must be at most {MAX_METADATA_BYTES} bytes of JSON"
+        )));
+    }
+
+    Ok((metadata, stored))
+}
+
+/// Whether `key` is a valid metadata key.
+fn is_valid_key(key: &str) -> bool {
+    key.len() <= MAX_METADATA_KEY_LENGTH
+        && key.starts_with(|c: char| c.is_ascii_lowercase())
+        && key
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "_-.:".contains(c))
+}
@@ -0,0 +1,44 @@
+//! Public identifiers of users and posts.
+
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// Generates a random public ID (a lowercase hyphenated UUIDv4).
+pub fn new_public_id() -> String {
+    Uuid::new_v4().to_string()
+}
+
+/// A user or post as named in the path of an authenticated route.
+///
+/// Public IDs are the canonical form; numeric IDs are still accepted there
+/// so signed-in clients from before public IDs keep working. Routes open to
+/// anonymous callers take [`PublicRef`] instead.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(from = "String")]
+pub enum EntityRef {
+    /// Internal numeric ID.
+    Id(i64),
+    /// Public ID.
+    Public(String),
+}
+
+impl From<String> for EntityRef {
+    fn from(value: String) -> Self {
+        match value.parse() {
+            Ok(id) => Self::Id(id),
+            Err(_) => Self::Public(value),
+        }
+    }
+}
+
+/// A user or post as named in the path of a route open to anonymous
+/// callers: only its public ID, so IDs cannot be guessed by counting.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct PublicRef(pub String);
+
+impl From<PublicRef> for EntityRef {
+    fn from(value: PublicRef) -> Self {
+        Self::Public(value.0)
+    }
+}
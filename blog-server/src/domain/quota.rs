@@ -0,0 +1,114 @@
+//! Daily request quotas of service accounts.
+
+use chrono::{DateTime, Days, NaiveDate, NaiveTime, Utc};
+
+use crate::constants::{
+    RATE_LIMIT_LIMIT_HEADER, RATE_LIMIT_REMAINING_HEADER, RATE_LIMIT_RESET_HEADER,
+};
+
+/// Daily limits of a service account (`None`: unlimited).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ApiQuota {
+    pub requests_per_day: Option<i64>,
+    /// Limit on requests other than `GET`, `HEAD` and `OPTIONS`, which also
+    /// count as requests.
+    pub writes_per_day: Option<i64>,
+}
+
+/// A service account's usage on one UTC day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApiUsage {
+    pub day: NaiveDate,
+    pub requests: i64,
+    pub writes: i64,
+}
+
+/// Where a service account stands against its tightest applicable limit,
+/// sent in the `X-RateLimit-*` headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuotaStatus {
+    pub limit: i64,
+    pub remaining: i64,
+    pub reset_at: DateTime<Utc>,
+}
+
+impl QuotaStatus {
+    /// Returns the `X-RateLimit-Limit`, `-Remaining` and `-Reset` (Unix
+    /// time) headers.
+    pub fn headers(&self) -> [(&'static str, String); 3] {
+        [
+            (RATE_LIMIT_LIMIT_HEADER, self.limit.to_string()),
+            (RATE_LIMIT_REMAINING_HEADER, self.remaining.to_string()),
+            (
+                RATE_LIMIT_RESET_HEADER,
+                self.reset_at.timestamp().to_string(),
+            ),
+        ]
+    }
+
+    /// Returns the seconds from `now` until the limit resets (0 once past).
+    pub fn seconds_until_reset(&self, now: DateTime<Utc>) -> i64 {
+        (self.reset_at - now).num_seconds().max(0)
+    }
+}
+
+impl ApiQuota {
+    /// Counts a request against `usage`, returning the tightest limit that
+    /// applies (`None` when unlimited), or the exhausted limit without
+    /// counting anything.
+    pub fn consume(
+        &self,
+        usage: &mut ApiUsage,
+        is_write: bool,
+    ) -> Result<Option<QuotaStatus>, QuotaStatus> {
+        let reset_at = next_reset(usage.day);
+        let mut limits = Vec::with_capacity(2);
+        if let Some(limit) = self.requests_per_day {
+            limits.push((limit, usage.requests));
+        }
+        if let (true, Some(limit)) = (is_write, self.writes_per_day) {
+            limits.push((limit, usage.writes));
+        }
+
+        if let Some(&(limit, _)) = limits.iter().find(|(limit, used)| used >= limit) {
+            return Err(QuotaStatus {
+                limit,
+                remaining: 0,
+                reset_at,
+            });
+        }
+
+        usage.requests += 1;
+        if is_write {
+            usage.writes += 1;
+        }
+
+        Ok(limits
+            .into_iter()
+            .map(|(limit, used)| QuotaStatus {
+                limit,
+                remaining: limit - used - 1,
+                reset_at,
+            })
+            .min_by_key(|status| status.remaining))
+    }
+}
+
+impl ApiUsage {
+    /// No usage yet on `day`.
+    pub fn empty(day: NaiveDate) -> Self {
+        Self {
+            day,
+            requests: 0,
+            writes: 0,
+        }
+    }
+}
+
+/// Returns when the counters of `day` start over: the following UTC midnight.
+pub fn next_reset(day: NaiveDate) -> DateTime<Utc> {
+    day.checked_add_days(Days::new(1))
+        .unwrap_or(day)
+        .and_time(NaiveTime::MIN)
+        .and_utc()
+}
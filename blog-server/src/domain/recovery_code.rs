@@ -0,0 +1,40 @@
+//! One-time account recovery codes.
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+
+use crate::constants::{RECOVERY_CODE_ALPHABET, RECOVERY_CODE_COUNT, RECOVERY_CODE_LENGTH};
+
+/// Generates a fresh set of recovery codes, formatted `XXXXX-XXXXX`.
+pub fn generate_recovery_codes() -> Vec<String> {
+    (0..RECOVERY_CODE_COUNT).map(|_| generate_code()).collect()
+}
+
+/// Returns the hash a code is stored under. Case, dashes, and spaces are
+/// ignored so codes can be typed loosely. Codes are random, so a plain
+/// SHA-256 is enough (unlike passwords).
+pub fn hash_recovery_code(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    Sha256::digest(normalized.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Generates one code, formatted `XXXXX-XXXXX`.
+pub(super) fn generate_code() -> String {
+    let mut code = String::with_capacity(RECOVERY_CODE_LENGTH + 1);
+    for i in 0..RECOVERY_CODE_LENGTH {
+        if i == RECOVERY_CODE_LENGTH / 2 {
+            code.push('-');
+        }
+        // The alphabet length divides 2^32, so this is unbiased
+        let index = OsRng.next_u32() as usize % RECOVERY_CODE_ALPHABET.len();
+        code.push(char::from(RECOVERY_CODE_ALPHABET[index]));
+    }
+    code
+}
@@ -0,0 +1,31 @@
+//! Data retention policies.
+
+/// Kind of data removed once it is older than its configured retention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetentionPolicy {
+    /// Audit log entries, kept for a number of months.
+    AuditLog,
+    /// Email addresses of unsubscribed newsletter readers, kept for a number
+    /// of days after unsubscribing.
+    UnsubscribedEmails,
+    /// Deleted posts, kept in their authors' trash for a number of days.
+    Trash,
+}
+
+impl RetentionPolicy {
+    /// All policies, in report order.
+    pub const ALL: [RetentionPolicy; 3] = [
+        RetentionPolicy::AuditLog,
+        RetentionPolicy::UnsubscribedEmails,
+        RetentionPolicy::Trash,
+    ];
+
+    /// Returns the name of the policy in reports.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RetentionPolicy::AuditLog => "audit_log",
+            RetentionPolicy::UnsubscribedEmails => "unsubscribed_emails",
+            RetentionPolicy::Trash => "trash",
+        }
+    }
+}
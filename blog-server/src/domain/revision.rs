@@ -0,0 +1,41 @@
+//! Post revisions and how their differences are shown.
+
+use blog_shared::{DiffLineDto, DiffOp};
+use chrono::{DateTime, Utc};
+
+use super::markdown::escape_into;
+
+/// Saved version of a post's title and content.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PostRevision {
+    pub post_id: i64,
+    pub number: i64,
+    pub title: String,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Renders a line diff as an HTML table with a row per line: old and new
+/// line numbers, a `-`/`+` marker, and the escaped text. Rows have the
+/// class `diff-same`, `diff-removed`, or `diff-added`.
+pub fn render_diff_html(lines: &[DiffLineDto]) -> String {
+    let mut html = String::from("<table class=\"diff\"><tbody>");
+    for line in lines {
+        let marker = match line.op {
+            DiffOp::Same => " ",
+            DiffOp::Removed => "-",
+            DiffOp::Added => "+",
+        };
+        let number = |n: Option<u32>| n.map(|n| n.to_string()).unwrap_or_default();
+        html.push_str(&format!(
+            "<tr class=\"diff-{}\"><td class=\"diff-number\">{}</td><td class=\"diff-number\">{}</td><td class=\"diff-marker\">{marker}</td><td class=\"diff-text\">",
+            line.op.as_str(),
+            number(line.old_line),
+            number(line.new_line),
+        ));
+        escape_into(&mut html, &line.text);
+        html.push_str("</td></tr>");
+    }
+    html.push_str("</tbody></table>");
+    html
+}
@@ -0,0 +1,194 @@
+//! Full-text search: query parsing, typo tolerance and snippets.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{
+    SEARCH_ONE_TYPO_MIN_LEN, SEARCH_SNIPPET_MARK_END, SEARCH_SNIPPET_MARK_START,
+    SEARCH_TWO_TYPOS_MIN_LEN,
+};
+use crate::domain::markdown::escape_into;
+use crate::domain::{AppError, Post};
+
+/// Narrows a search besides its words; `None` fields do not filter.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchFilters {
+    pub lang: Option<String>,
+    /// Username of the post's writer.
+    pub author: Option<String>,
+    /// Normalized tag name.
+    pub tag: Option<String>,
+    /// Created at or after.
+    pub from: Option<DateTime<Utc>>,
+    /// Created before.
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// A post matching a search, with the best matching passage.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub post_id: i64,
+    /// Raw passage, matched terms between `SEARCH_SNIPPET_MARK_START` and
+    /// `SEARCH_SNIPPET_MARK_END`.
+    pub snippet: String,
+}
+
+/// A page of search hits, best first, and how many posts match in all.
+#[derive(Debug, Clone, Default)]
+pub struct SearchResults {
+    pub hits: Vec<SearchHit>,
+    pub total: i64,
+}
+
+/// What an external search index stores about a post.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchDocument {
+    pub id: i64,
+    pub title: String,
+    pub content: String,
+    pub author_id: i64,
+    /// Normalized username of the post's writer.
+    pub author: String,
+    pub tags: Vec<String>,
+    pub lang: String,
+    pub status: String,
+    /// Unix time, so range filters work.
+    pub created_at: i64,
+}
+
+impl SearchDocument {
+    /// Builds the document of a post.
+    pub fn new(post: &Post, author: String, tags: Vec<String>) -> Self {
+        Self {
+            id: post.id,
+            title: post.title.clone(),
+            content: post.content.clone(),
+            author_id: post.author_id,
+            author,
+            tags,
+            lang: post.lang.clone(),
+            status: post.status.clone(),
+            created_at: post.created_at.timestamp(),
+        }
+    }
+}
+
+/// Where posts are searched.
+///
+/// External indexes may lag behind the database and know nothing of shadow
+/// bans, so callers load every hit through the visibility rules.
+#[async_trait]
+pub trait SearchIndex: Send + Sync {
+    /// Finds posts visible to `viewer_id` matching `query` and `filters`
+    /// (already normalized), best first.
+    async fn search(
+        &self,
+        query: &str,
+        filters: &SearchFilters,
+        limit: i64,
+        offset: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<SearchResults, AppError>;
+
+    /// Whether the index must be fed posts through `upsert` and `remove`;
+    /// false when the database keeps it up to date.
+    fn is_external(&self) -> bool;
+
+    /// Adds a post to the index, or replaces it.
+    async fn upsert(&self, document: SearchDocument) -> Result<(), AppError>;
+
+    /// Removes a post from the index, if there.
+    async fn remove(&self, post_id: i64) -> Result<(), AppError>;
+}
+
+/// A term in the search index and the number of posts containing it.
+#[derive(Debug, Clone)]
+pub struct IndexedTerm {
+    pub term: String,
+    pub posts: i64,
+}
+
+/// Splits a search query into lowercased words.
+pub fn search_words(query: &str) -> Vec<String> {
+    query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Returns the typos tolerated in a word of this length.
+pub fn typo_budget(word: &str) -> usize {
+    match word.chars().count() {
+        n if n >= SEARCH_TWO_TYPOS_MIN_LEN => 2,
+        n if n >= SEARCH_ONE_TYPO_MIN_LEN => 1,
+        _ => 0,
+    }
+}
+
+/// Levenshtein distance between two words, in characters.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substituted = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substituted.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Builds an FTS5 query from search words, each with the indexed terms
+/// accepted in its place (from typos): every word must match, as a prefix
+/// or as one of its alternatives. All terms are quoted, so user input can
+/// never be parsed as FTS5 syntax.
+///
+/// Returns `None` when there are no words.
+pub fn match_expr(words: &[(String, Vec<String>)]) -> Option<String> {
+    let terms: Vec<String> = words
+        .iter()
+        .map(|(word, alternatives)| {
+            let prefix = format!("\"{word}\"*");
+            if alternatives.is_empty() {
+                return prefix;
+            }
+            let alternatives: Vec<String> = alternatives
+                .iter()
+                .map(|term| format!("\"{term}\""))
+                .collect();
+            format!("({prefix} OR {})", alternatives.join(" OR "))
+        })
+        .collect();
+
+    (!terms.is_empty()).then(|| terms.join(" AND "))
+}
+
+/// Returns the upper bound of the strings starting with `prefix`, so they
+/// can be matched as `value >= prefix AND value < bound` using an index.
+pub fn prefix_bound(prefix: &str) -> String {
+    format!("{prefix}{}", char::MAX)
+}
+
+/// Renders a raw snippet as HTML: text escaped, matched terms in `<mark>`.
+pub fn render_snippet(raw: &str) -> String {
+    let mut html = String::with_capacity(raw.len());
+    for (i, part) in raw.split(SEARCH_SNIPPET_MARK_START).enumerate() {
+        if i == 0 {
+            escape_into(&mut html, part);
+            continue;
+        }
+        let (marked, rest) = part
+            .split_once(SEARCH_SNIPPET_MARK_END)
+            .unwrap_or((part, ""));
+        html.push_str("<mark>");
+        escape_into(&mut html, marked);
+        html.push_str("</mark>");
+        escape_into(&mut html, rest);
+    }
+    html
+}
@@ -0,0 +1,46 @@
+//! Service account domain entity.
+
+use blog_shared::ServiceScope;
+use chrono::{DateTime, Utc};
+
+use crate::domain::ApiQuota;
+
+/// Long-lived, scoped token identity acting as a user; its ID is embedded in
+/// the service token as `sid`.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ServiceAccount {
+    pub id: i64,
+    pub name: String,
+    pub user_id: i64,
+    pub username: String,
+    /// Comma-separated scope names; read through [`ServiceAccount::scopes`].
+    pub scopes: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub requests_per_day: Option<i64>,
+    pub writes_per_day: Option<i64>,
+}
+
+impl ServiceAccount {
+    /// Returns the granted scopes, skipping unknown stored names.
+    pub fn scopes(&self) -> Vec<ServiceScope> {
+        self.scopes
+            .split(',')
+            .filter_map(|scope| scope.parse().ok())
+            .collect()
+    }
+
+    /// Returns the account's daily limits.
+    pub fn quota(&self) -> ApiQuota {
+        ApiQuota {
+            requests_per_day: self.requests_per_day,
+            writes_per_day: self.writes_per_day,
+        }
+    }
+
+    /// Whether the account's token is still accepted at `now`.
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at > now
+    }
+}
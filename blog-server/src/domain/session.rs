@@ -0,0 +1,13 @@
+//! Session domain entity.
+
+use chrono::{DateTime, Utc};
+
+/// Signed-in session; its ID is embedded in the JWT as `sid`.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Session {
+    pub id: String,
+    pub user_id: i64,
+    pub user_agent: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+}
@@ -0,0 +1,139 @@
+//! Site-wide settings and the announcement banner stored among them.
+
+use blog_shared::constants::{
+    MAX_ANNOUNCEMENT_LENGTH, MAX_POSTS_PER_PAGE, MAX_SITE_DESCRIPTION_LENGTH,
+    MAX_SITE_TITLE_LENGTH, SUPPORTED_LANGUAGES,
+};
+use blog_shared::{
+    AnnouncementSeverity, RegistrationMode, SetAnnouncementRequest, SiteSettingsDto,
+    UpdateSiteSettingsRequest,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::AppError;
+
+/// A stored site setting: a JSON value under a key.
+#[derive(Debug, Clone)]
+pub struct SiteSetting {
+    pub value: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The announcement banner, as stored in its site setting.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Announcement {
+    pub text: String,
+    pub severity: AnnouncementSeverity,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl Announcement {
+    /// Validates a new announcement at `now`: trimmed text of at most
+    /// `MAX_ANNOUNCEMENT_LENGTH` characters, and an expiry in the future.
+    pub fn parse(req: SetAnnouncementRequest, now: DateTime<Utc>) -> Result<Self, AppError> {
+        let text = req.text.trim();
+        if text.is_empty() || text.chars().count() > MAX_ANNOUNCEMENT_LENGTH {
+            return Err(AppError::Validation(format!(
+                "Announcements must be 1 to {MAX_ANNOUNCEMENT_LENGTH} characters"
+            )));
+        }
+        if req.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            return Err(AppError::Validation(
+                "Announcement expiry must be in the future".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            text: text.to_string(),
+            severity: req.severity,
+            expires_at: req.expires_at,
+        })
+    }
+}
+
+/// The general site settings, as stored in their site setting. Fields
+/// missing from a stored value take their defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SiteSettings {
+    pub title: String,
+    pub description: String,
+    pub posts_per_page: i64,
+    pub default_language: String,
+    pub registration_mode: RegistrationMode,
+}
+
+impl Default for SiteSettings {
+    fn default() -> Self {
+        let defaults = SiteSettingsDto::default();
+        Self {
+            title: defaults.title,
+            description: defaults.description,
+            posts_per_page: defaults.posts_per_page,
+            default_language: defaults.default_language,
+            registration_mode: defaults.registration_mode,
+        }
+    }
+}
+
+impl SiteSettings {
+    /// Applies an admin's changes, validating each given field: a title of
+    /// 1 to `MAX_SITE_TITLE_LENGTH` characters, a description of at most
+    /// `MAX_SITE_DESCRIPTION_LENGTH`, 1 to `MAX_POSTS_PER_PAGE` posts per
+    /// page, and a supported language.
+    pub fn apply(mut self, req: UpdateSiteSettingsRequest) -> Result<Self, AppError> {
+        if let Some(title) = req.title {
+            let title = title.trim();
+            if title.is_empty() || title.chars().count() > MAX_SITE_TITLE_LENGTH {
+                return Err(AppError::Validation(format!(
+                    "Site title must be 1 to {MAX_SITE_TITLE_LENGTH} characters"
+                )));
+            }
+            self.title = title.to_string();
+        }
+        if let Some(description) = req.description {
+            let description = description.trim();
+            if description.chars().count() > MAX_SITE_DESCRIPTION_LENGTH {
+                return Err(AppError::Validation(format!(
+                    "Site description must be at most {MAX_SITE_DESCRIPTION_LENGTH} characters"
+                )));
+            }
+            self.description = description.to_string();
+        }
+        if let Some(posts_per_page) = req.posts_per_page {
+            if !(1..=MAX_POSTS_PER_PAGE).contains(&posts_per_page) {
+                return Err(AppError::Validation(format!(
+                    "Posts per page must be 1 to {MAX_POSTS_PER_PAGE}"
+                )));
+            }
+            self.posts_per_page = posts_per_page;
+        }
+        if let Some(language) = req.default_language {
+            if !SUPPORTED_LANGUAGES.contains(&language.as_str()) {
+                return Err(AppError::Validation(format!(
+                    "Language must be one of: {}",
+                    SUPPORTED_LANGUAGES.join(", ")
+                )));
+            }
+            self.default_language = language;
+        }
+        if let Some(registration_mode) = req.registration_mode {
+            self.registration_mode = registration_mode;
+        }
+
+        Ok(self)
+    }
+
+    /// Converts the settings to their DTO, last changed at `updated_at`.
+    pub fn into_dto(self, updated_at: Option<DateTime<Utc>>) -> SiteSettingsDto {
+        SiteSettingsDto {
+            title: self.title,
+            description: self.description,
+            posts_per_page: self.posts_per_page,
+            default_language: self.default_language,
+            registration_mode: self.registration_mode,
+            updated_at,
+        }
+    }
+}
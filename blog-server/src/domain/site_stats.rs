@@ -0,0 +1,12 @@
+//! Site statistics for the admin dashboard.
+
+/// Site-wide counters.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SiteStats {
+    pub users: i64,
+    pub suspended_users: i64,
+    pub posts: i64,
+    pub comments: i64,
+    pub tags: i64,
+    pub active_sessions: i64,
+}
@@ -0,0 +1,64 @@
+//! Tag domain entity and normalization rules.
+
+use blog_shared::constants::{MAX_TAG_LENGTH, MAX_TAGS_PER_POST};
+
+use crate::domain::AppError;
+
+/// A tag and its description.
+#[derive(Debug, Clone)]
+pub struct Tag {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// Tag with the number of posts using it.
+#[derive(Debug, Clone)]
+pub struct TagUsage {
+    pub name: String,
+    pub description: Option<String>,
+    pub post_count: i64,
+}
+
+/// Normalizes tags for storage: trimmed, lowercased, blanks and duplicates
+/// dropped (first occurrence wins).
+///
+/// Tags may contain letters, digits, `-` and `_`.
+pub fn normalize_tags(tags: Vec<String>) -> Result<Vec<String>, AppError> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() || normalized.contains(&tag) {
+            continue;
+        }
+        if tag.chars().count() > MAX_TAG_LENGTH {
+            return Err(AppError::Validation(format!(
+                "Tags must be at most {MAX_TAG_LENGTH} characters"
+            )));
+        }
+        if !tag
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(AppError::Validation(format!(
+                "Tag '{tag}' may only contain letters, digits, '-' and '_'"
+            )));
+        }
+        normalized.push(tag);
+    }
+
+    if normalized.len() > MAX_TAGS_PER_POST {
+        return Err(AppError::Validation(format!(
+            "A post can have at most {MAX_TAGS_PER_POST} tags"
+        )));
+    }
+
+    Ok(normalized)
+}
+
+/// Normalizes a single tag name like [`normalize_tags`], rejecting a blank one.
+pub fn normalize_tag(name: &str) -> Result<String, AppError> {
+    normalize_tags(vec![name.to_string()])?
+        .pop()
+        .ok_or_else(|| AppError::Validation("Tag name must not be empty".to_string()))
+}
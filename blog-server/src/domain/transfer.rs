@@ -0,0 +1,36 @@
+//! Post transfers: an author handing a post over to another user.
+
+use blog_shared::TransferStatus;
+use chrono::{DateTime, Utc};
+
+/// An offer to transfer a post, with the title and usernames involved.
+#[derive(Debug, Clone)]
+pub struct PostTransfer {
+    pub id: i64,
+    pub post_id: i64,
+    pub post_public_id: String,
+    pub post_title: String,
+    pub from_user_id: i64,
+    pub from_user_public_id: String,
+    pub from_username: String,
+    pub to_user_id: i64,
+    pub to_user_public_id: String,
+    pub to_username: String,
+    /// Stored status name; read through [`PostTransfer::status`].
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl PostTransfer {
+    /// Returns the transfer's status (unknown stored values count as
+    /// cancelled).
+    pub fn status(&self) -> TransferStatus {
+        self.status.parse().unwrap_or(TransferStatus::Cancelled)
+    }
+
+    /// Whether `user_id` sent or received the offer.
+    pub fn involves(&self, user_id: i64) -> bool {
+        self.from_user_id == user_id || self.to_user_id == user_id
+    }
+}
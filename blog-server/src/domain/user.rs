@@ -1,13 +1,97 @@
 //! User domain entity.
 
+use blog_shared::Role;
 use chrono::{DateTime, Utc};
+use unicode_normalization::UnicodeNormalization;
 
 /// User entity with password hash (internal use only).
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct User {
     pub id: i64,
+    /// Random ID used in API URLs instead of `id`.
+    pub public_id: String,
     pub username: String,
     pub email: String,
     pub password_hash: String,
+    /// Stored role name; read through [`User::role`].
+    pub role: String,
+    pub suspended_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
 }
+
+impl User {
+    /// Returns the user's role (unknown stored values count as `User`).
+    pub fn role(&self) -> Role {
+        self.role.parse().unwrap_or_default()
+    }
+}
+
+/// Cleans up a username as typed: surrounding whitespace removed and
+/// Unicode in NFC, so visually identical names are stored the same way.
+pub fn clean_username(username: &str) -> String {
+    username.trim().nfc().collect()
+}
+
+/// Returns the form usernames are compared in: cleaned and case-folded,
+/// so "Bob" and "bob" are the same user.
+pub fn normalize_username(username: &str) -> String {
+    clean_username(username).to_lowercase().nfc().collect()
+}
+
+/// User row for the admin user management table.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct UserSummary {
+    pub id: i64,
+    pub username: String,
+    pub email: String,
+    pub role: String,
+    pub suspended_at: Option<DateTime<Utc>>,
+    pub shadow_banned_at: Option<DateTime<Utc>>,
+    pub post_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Publicly visible fields of a user.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PublicProfile {
+    pub id: i64,
+    pub public_id: String,
+    pub username: String,
+    pub bio: String,
+    pub avatar_url: Option<String>,
+}
+
+/// Names of the author of a post, for its DTO.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct AuthorName {
+    pub public_id: String,
+    pub username: String,
+}
+
+/// User offered to complete an `@mention`.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct UserSuggestion {
+    pub username: String,
+    pub avatar_url: Option<String>,
+}
+
+/// Profile and preference fields of a user.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct UserSettings {
+    pub email: String,
+    pub bio: String,
+    pub avatar_url: Option<String>,
+    pub website: Option<String>,
+    pub theme: String,
+    pub language: String,
+    pub timezone: String,
+    pub locale: String,
+}
+
+/// What a user has stored on the site.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct UserUsage {
+    pub posts: i64,
+    pub uploads: i64,
+    pub upload_bytes: i64,
+}
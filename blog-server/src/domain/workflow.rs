@@ -0,0 +1,89 @@
+//! Editorial workflow: the status changes a post may go through.
+
+use blog_shared::{PostStatus, ReviewNoteDto};
+use chrono::{DateTime, Utc};
+
+use super::AppError;
+
+/// Step of the editorial workflow, taken by the author or an editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkflowAction {
+    /// The author asks for review of a draft or a revised post.
+    Submit,
+    /// An editor accepts a post in review.
+    Approve,
+    /// An editor sends a post in review back to its author.
+    RequestChanges,
+    /// The author or an editor makes an approved post public.
+    Publish,
+    /// The author edits an approved post, which then needs review again.
+    Revise,
+}
+
+impl WorkflowAction {
+    /// Returns the verb used in error messages and logs.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WorkflowAction::Submit => "submit",
+            WorkflowAction::Approve => "approve",
+            WorkflowAction::RequestChanges => "request changes to",
+            WorkflowAction::Publish => "publish",
+            WorkflowAction::Revise => "revise",
+        }
+    }
+
+    /// Returns the status a post in `from` moves to, or a validation error
+    /// if the action does not apply to it.
+    pub fn apply(self, from: PostStatus) -> Result<PostStatus, AppError> {
+        let to = match (self, from) {
+            (WorkflowAction::Submit, PostStatus::Draft | PostStatus::ChangesRequested) => {
+                PostStatus::InReview
+            }
+            (WorkflowAction::Approve, PostStatus::InReview) => PostStatus::Approved,
+            (WorkflowAction::RequestChanges, PostStatus::InReview) => PostStatus::ChangesRequested,
+            (WorkflowAction::Publish, PostStatus::Approved) => PostStatus::Published,
+            (WorkflowAction::Revise, PostStatus::Approved) => PostStatus::Draft,
+            _ => {
+                return Err(AppError::Validation(format!(
+                    "Cannot {} a post that is {from}",
+                    self.as_str()
+                )));
+            }
+        };
+
+        Ok(to)
+    }
+}
+
+/// Status change of a post to record, stamped `now`.
+#[derive(Debug, Clone, Copy)]
+pub struct NewTransition<'a> {
+    pub post_id: i64,
+    pub actor_id: i64,
+    pub from: PostStatus,
+    pub to: PostStatus,
+    pub comment: Option<&'a str>,
+    pub notes: &'a [ReviewNoteDto],
+    pub now: DateTime<Utc>,
+}
+
+/// Status change of a post, loaded with the actor's username.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PostTransition {
+    pub id: i64,
+    pub post_id: i64,
+    pub actor_id: i64,
+    pub actor_username: String,
+    pub from_status: String,
+    pub to_status: String,
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Review comment on one line of a post, attached to a transition.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ReviewNote {
+    pub transition_id: i64,
+    pub line: i64,
+    pub body: String,
+}
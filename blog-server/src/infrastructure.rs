@@ -1,5 +1,21 @@
 //! Infrastructure layer: config, database, external services.
 
+pub mod blob_store;
+pub mod clamav;
+pub mod clock;
 pub mod config;
+pub mod content_cipher;
 pub mod database;
+pub mod event_bus;
+pub mod http_fetcher;
+pub mod http_signature;
 pub mod jwt;
+pub mod mailer;
+pub mod meilisearch;
+pub mod metrics;
+pub mod panic_guard;
+#[cfg(feature = "redis")]
+pub mod redis_quota;
+pub mod sentry;
+pub mod tls;
+pub mod url_signing;
@@ -0,0 +1,50 @@
+//! File storage for uploads, one file per content hash.
+
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use tokio::fs;
+
+use crate::domain::AppError;
+
+/// Directory of uploaded files, each named by its content hash.
+#[derive(Debug, Clone)]
+pub struct BlobStore {
+    dir: PathBuf,
+}
+
+impl BlobStore {
+    /// Creates a store in `dir`, which is created on first write.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Stores `bytes` under `hash` unless a file with that hash exists.
+    ///
+    /// Writes to a temporary file first so readers never see a partial blob.
+    pub async fn put(&self, hash: &str, bytes: &[u8]) -> Result<(), AppError> {
+        let path = self.dir.join(hash);
+        if fs::try_exists(&path).await.map_err(io_error)? {
+            return Ok(());
+        }
+
+        fs::create_dir_all(&self.dir).await.map_err(io_error)?;
+        let partial = self.dir.join(format!(".{hash}.partial"));
+        fs::write(&partial, bytes).await.map_err(io_error)?;
+        fs::rename(&partial, &path).await.map_err(io_error)
+    }
+
+    /// Reads the file stored under `hash`, or `None` if there is none.
+    pub async fn get(&self, hash: &str) -> Result<Option<Vec<u8>>, AppError> {
+        match fs::read(self.dir.join(hash)).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(io_error(e)),
+        }
+    }
+}
+
+/// Maps a filesystem error to an internal error.
+fn io_error(e: std::io::Error) -> AppError {
+    AppError::Internal(format!("blob store: {e}"))
+}
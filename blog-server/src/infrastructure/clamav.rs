@@ -0,0 +1,79 @@
+//! Virus scanning with ClamAV, through clamd's `INSTREAM` command on its
+//! Unix socket.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::UnixStream;
+
+use crate::constants::{CLAMD_CHUNK_SIZE, CLAMD_TIMEOUT_SECS};
+use crate::domain::AppError;
+
+/// Outcome of a scan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanVerdict {
+    Clean,
+    /// Infected, with the name of the matched signature.
+    Infected(String),
+}
+
+/// Client for a clamd daemon.
+#[derive(Debug, Clone)]
+pub struct ClamAv {
+    socket: PathBuf,
+}
+
+impl ClamAv {
+    /// Creates a client for the clamd listening on `socket`.
+    pub fn new(socket: impl Into<PathBuf>) -> Self {
+        Self {
+            socket: socket.into(),
+        }
+    }
+
+    /// Scans `bytes`, failing if clamd cannot be reached or reports an error.
+    pub async fn scan(&self, bytes: &[u8]) -> Result<ScanVerdict, AppError> {
+        let reply = tokio::time::timeout(
+            Duration::from_secs(CLAMD_TIMEOUT_SECS),
+            self.instream(bytes),
+        )
+        .await
+        .map_err(|_| AppError::Internal("clamd: timed out".to_string()))?
+        .map_err(|e| AppError::Internal(format!("clamd: {e}")))?;
+
+        parse_reply(&reply)
+    }
+
+    /// Sends `bytes` in length-prefixed chunks and returns clamd's reply.
+    async fn instream(&self, bytes: &[u8]) -> std::io::Result<String> {
+        let mut stream = UnixStream::connect(&self.socket).await?;
+        // `z` commands are NUL-terminated, and so is the reply
+        stream.write_all(b"zINSTREAM\0").await?;
+        for chunk in bytes.chunks(CLAMD_CHUNK_SIZE) {
+            let len = u32::try_from(chunk.len()).map_err(std::io::Error::other)?;
+            stream.write_all(&len.to_be_bytes()).await?;
+            stream.write_all(chunk).await?;
+        }
+        stream.write_all(&0u32.to_be_bytes()).await?;
+
+        let mut reply = Vec::new();
+        stream.read_to_end(&mut reply).await?;
+        Ok(String::from_utf8_lossy(&reply)
+            .trim_end_matches('\0')
+            .trim()
+            .to_string())
+    }
+}
+
+/// Parses a reply like `stream: OK` or `stream: Eicar-Signature FOUND`.
+fn parse_reply(reply: &str) -> Result<ScanVerdict, AppError> {
+    let result = reply.strip_prefix("stream: ").unwrap_or(reply);
+    if result == "OK" {
+        Ok(ScanVerdict::Clean)
+    } else if let Some(signature) = result.strip_suffix(" FOUND") {
+        Ok(ScanVerdict::Infected(signature.to_string()))
+    } else {
+        Err(AppError::Internal(format!("clamd: {reply}")))
+    }
+}
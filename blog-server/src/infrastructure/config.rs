@@ -1,21 +1,622 @@
 //! Application configuration.
 
 use std::env;
+use std::str::FromStr;
 
 use blog_shared::constants::{
     DEFAULT_GRPC_PORT, DEFAULT_HTTP_PORT, ENV_DATABASE_URL, ENV_GRPC_PORT, ENV_HTTP_PORT,
     ENV_JWT_SECRET,
 };
 
-use crate::domain::AppError;
+use crate::constants::{
+    DEFAULT_ANNOUNCEMENT_CACHE_MAX_AGE_SECS, DEFAULT_CHAOS_LATENCY_MS, DEFAULT_DEVTO_API_URL,
+    DEFAULT_DIAGNOSTICS_CAPACITY, DEFAULT_EMBED_PROVIDERS, DEFAULT_HASHNODE_API_URL,
+    DEFAULT_HTTP_BACKLOG, DEFAULT_HTTP_CLIENT_DISCONNECT_TIMEOUT_MS,
+    DEFAULT_HTTP_CLIENT_REQUEST_TIMEOUT_MS, DEFAULT_HTTP_KEEP_ALIVE_SECS,
+    DEFAULT_HTTP_MAX_CONNECTIONS, DEFAULT_JWT_AUDIENCE, DEFAULT_MAIL_FROM,
+    DEFAULT_POST_CACHE_MAX_AGE_SECS, DEFAULT_POST_LIST_CACHE_MAX_AGE_SECS, DEFAULT_PUBLIC_URL,
+    DEFAULT_RESERVED_USERNAMES, DEFAULT_SENTRY_ENVIRONMENT,
+    DEFAULT_SITE_SETTINGS_CACHE_MAX_AGE_SECS, DEFAULT_SITE_URL,
+    DEFAULT_SUGGESTION_CACHE_MAX_AGE_SECS, DEFAULT_UPLOAD_DIR, ENV_ANALYTICS_ENABLED,
+    ENV_ANALYTICS_SAMPLE_RATE, ENV_ANNOUNCEMENT_CACHE_MAX_AGE_SECS, ENV_CHAOS_DROP_RATE,
+    ENV_CHAOS_ERROR_RATE, ENV_CHAOS_LATENCY_MS, ENV_CHAOS_LATENCY_RATE, ENV_CLAMD_SOCKET,
+    ENV_CONTENT_ENCRYPTION_KEYS, ENV_DEVTO_API_URL, ENV_DIAGNOSTICS_CAPACITY,
+    ENV_DIAGNOSTICS_ENABLED, ENV_EMBED_PROVIDERS, ENV_HASHNODE_API_URL, ENV_HTTP_BACKLOG,
+    ENV_HTTP_CLIENT_DISCONNECT_TIMEOUT_MS, ENV_HTTP_CLIENT_REQUEST_TIMEOUT_MS,
+    ENV_HTTP_KEEP_ALIVE_SECS, ENV_HTTP_MAX_CONNECTIONS, ENV_HTTP_WORKERS,
+    ENV_INDIEAUTH_TOKEN_ENDPOINT, ENV_INVITE_ONLY, ENV_JWT_AUDIENCE, ENV_JWT_ISSUER, ENV_MAIL_FROM,
+    ENV_MEILISEARCH_API_KEY, ENV_MEILISEARCH_URL, ENV_POST_CACHE_MAX_AGE_SECS,
+    ENV_POST_LIST_CACHE_MAX_AGE_SECS, ENV_PUBLIC_URL, ENV_QUOTA_BACKEND, ENV_REDIS_URL,
+    ENV_RESERVED_USERNAMES, ENV_RETENTION_AUDIT_LOG_MONTHS, ENV_RETENTION_TRASH_DAYS,
+    ENV_RETENTION_UNSUBSCRIBED_DAYS, ENV_SEARCH_BACKEND, ENV_SENTRY_DSN, ENV_SENTRY_ENVIRONMENT,
+    ENV_SITE_SETTINGS_CACHE_MAX_AGE_SECS, ENV_SITE_URL, ENV_SMTP_URL,
+    ENV_SUGGESTION_CACHE_MAX_AGE_SECS, ENV_TLS_CERT_PATH, ENV_TLS_KEY_PATH, ENV_UPLOAD_DIR,
+    ENV_UPLOAD_SIGNING_KEY, ENV_WEBSUB_HUB_URL,
+};
+use crate::domain::{AppError, EmbedProvider, normalize_username};
+use crate::infrastructure::content_cipher::ContentCipher;
+use crate::infrastructure::sentry::SentryDsn;
+use crate::infrastructure::url_signing::derive_upload_key;
 
 /// Application configuration loaded from environment.
 #[derive(Clone)]
 pub struct Config {
     pub database_url: String,
     pub jwt_secret: String,
+    /// `iss` claim of issued tokens; only tokens with it are accepted.
+    pub jwt_issuer: String,
+    /// `aud` claim of issued tokens; only tokens with it are accepted.
+    pub jwt_audience: String,
     pub http_port: u16,
+    pub http_server: HttpServerConfig,
     pub grpc_port: u16,
+    pub chaos: ChaosConfig,
+    pub cache: CacheConfig,
+    /// Providers whose URLs are embedded in rendered posts.
+    pub embed_providers: Vec<EmbedProvider>,
+    /// Usernames nobody can register, in normalized form.
+    pub reserved_usernames: Vec<String>,
+    /// New users need an invite code to register.
+    pub invite_only: bool,
+    pub mail: MailConfig,
+    /// Base URL of this server as seen from outside, without a trailing slash.
+    pub public_url: String,
+    /// URL of the web frontend, without a trailing slash.
+    pub site_url: String,
+    /// IndieAuth token endpoint used to verify Micropub tokens.
+    pub indieauth_token_endpoint: Option<String>,
+    /// WebSub hub pinged when posts change.
+    pub websub_hub_url: Option<String>,
+    /// DEV API base URL used for cross-posting.
+    pub devto_api_url: String,
+    /// Hashnode GraphQL endpoint used for cross-posting.
+    pub hashnode_api_url: String,
+    /// Directory uploaded files are stored in.
+    pub upload_dir: String,
+    /// Key signing URLs to private uploads (by default derived from the
+    /// JWT secret, never the secret itself).
+    pub upload_signing_key: String,
+    /// clamd socket uploads are scanned through.
+    pub clamd_socket: Option<String>,
+    /// Keys post content is encrypted at rest with; plaintext when unset.
+    pub content_cipher: Option<ContentCipher>,
+    /// Redis server relaying live events between replicas.
+    pub redis_url: Option<String>,
+    pub quota_backend: QuotaBackend,
+    pub search_backend: SearchBackend,
+    pub retention: RetentionConfig,
+    pub analytics: AnalyticsConfig,
+    pub telemetry: TelemetryConfig,
+    pub diagnostics: DiagnosticsConfig,
+}
+
+/// How long data is kept before the retention job removes it; `None` keeps
+/// it forever.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RetentionConfig {
+    /// Months audit log entries are kept.
+    pub audit_log_months: Option<u32>,
+    /// Days an unsubscribed newsletter email is kept.
+    pub unsubscribed_days: Option<u32>,
+    /// Days a deleted post stays in its author's trash.
+    pub trash_days: Option<u32>,
+}
+
+impl RetentionConfig {
+    /// Load retention settings from environment variables.
+    fn from_env() -> Result<Self, AppError> {
+        Ok(Self {
+            audit_log_months: optional_number_from_env(ENV_RETENTION_AUDIT_LOG_MONTHS)?,
+            unsubscribed_days: optional_number_from_env(ENV_RETENTION_UNSUBSCRIBED_DAYS)?,
+            trash_days: optional_number_from_env(ENV_RETENTION_TRASH_DAYS)?,
+        })
+    }
+}
+
+/// Collection of anonymous client analytics.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnalyticsConfig {
+    /// Events are stored; otherwise they are accepted and dropped.
+    pub enabled: bool,
+    /// Share of events kept, in `(0.0, 1.0]`.
+    pub sample_rate: f64,
+}
+
+impl Default for AnalyticsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sample_rate: 1.0,
+        }
+    }
+}
+
+impl AnalyticsConfig {
+    /// Load analytics settings from environment variables.
+    fn from_env() -> Result<Self, AppError> {
+        let sample_rate = match env::var(ENV_ANALYTICS_SAMPLE_RATE) {
+            Ok(value) if !value.is_empty() => value
+                .parse::<f64>()
+                .ok()
+                .filter(|rate| *rate > 0.0 && *rate <= 1.0)
+                .ok_or_else(|| {
+                    AppError::Config(format!(
+                        "{ENV_ANALYTICS_SAMPLE_RATE} must be above 0.0 and at most 1.0"
+                    ))
+                })?,
+            _ => 1.0,
+        };
+
+        Ok(Self {
+            enabled: switch_from_env(ENV_ANALYTICS_ENABLED)?,
+            sample_rate,
+        })
+    }
+}
+
+/// Error reporting to Sentry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TelemetryConfig {
+    /// Project panics, 5xx responses, and client crashes are reported to;
+    /// nothing is reported when unset.
+    pub sentry_dsn: Option<SentryDsn>,
+    /// Environment tag of the reports.
+    pub environment: String,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            sentry_dsn: None,
+            environment: DEFAULT_SENTRY_ENVIRONMENT.to_string(),
+        }
+    }
+}
+
+impl TelemetryConfig {
+    /// Load error reporting settings from environment variables.
+    fn from_env() -> Result<Self, AppError> {
+        let sentry_dsn = env::var(ENV_SENTRY_DSN)
+            .ok()
+            .filter(|dsn| !dsn.is_empty())
+            .map(|dsn| {
+                SentryDsn::parse(&dsn)
+                    .map_err(|e| AppError::Config(format!("{ENV_SENTRY_DSN}: {e}")))
+            })
+            .transpose()?;
+        let environment = env::var(ENV_SENTRY_ENVIRONMENT)
+            .ok()
+            .filter(|environment| !environment.is_empty())
+            .unwrap_or_else(|| DEFAULT_SENTRY_ENVIRONMENT.to_string());
+
+        Ok(Self {
+            sentry_dsn,
+            environment,
+        })
+    }
+}
+
+/// Admin diagnostics of failed write requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiagnosticsConfig {
+    /// Snapshots are recorded; off by default, as they hold request bodies.
+    pub enabled: bool,
+    /// Snapshots kept before the oldest is dropped.
+    pub capacity: usize,
+}
+
+impl Default for DiagnosticsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            capacity: DEFAULT_DIAGNOSTICS_CAPACITY,
+        }
+    }
+}
+
+impl DiagnosticsConfig {
+    /// Load diagnostics settings from environment variables.
+    fn from_env() -> Result<Self, AppError> {
+        let capacity = optional_number_from_env(ENV_DIAGNOSTICS_CAPACITY)?
+            .unwrap_or(DEFAULT_DIAGNOSTICS_CAPACITY);
+        if capacity == 0 {
+            return Err(AppError::Config(format!(
+                "{ENV_DIAGNOSTICS_CAPACITY} must be at least 1"
+            )));
+        }
+
+        Ok(Self {
+            enabled: switch_from_env(ENV_DIAGNOSTICS_ENABLED)?,
+            capacity,
+        })
+    }
+}
+
+/// Where service account usage is counted against quotas.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuotaBackend {
+    /// In this process, so each replica enforces the limits on its own.
+    #[default]
+    Memory,
+    /// In Redis, shared by every replica; counted in memory while Redis is
+    /// unreachable.
+    Redis,
+}
+
+impl QuotaBackend {
+    /// Load the quota backend from the environment; `redis` needs a Redis
+    /// server.
+    fn from_env(redis_url: Option<&str>) -> Result<Self, AppError> {
+        match env::var(ENV_QUOTA_BACKEND).ok().as_deref() {
+            None | Some("") | Some("memory") => Ok(Self::Memory),
+            Some("redis") if redis_url.is_some() => Ok(Self::Redis),
+            Some("redis") => Err(AppError::Config(format!(
+                "{ENV_QUOTA_BACKEND}=redis needs {ENV_REDIS_URL}"
+            ))),
+            Some(other) => Err(AppError::Config(format!(
+                "{ENV_QUOTA_BACKEND} must be memory or redis, not {other}"
+            ))),
+        }
+    }
+}
+
+/// Where posts are searched.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SearchBackend {
+    /// The database's FTS5 index, kept up to date by triggers.
+    #[default]
+    Sqlite,
+    /// A Meilisearch server, fed from post change events.
+    Meilisearch {
+        url: String,
+        api_key: Option<String>,
+    },
+}
+
+impl SearchBackend {
+    /// Load the search backend from the environment. Meilisearch stores
+    /// content as plaintext, so it is refused when content is encrypted.
+    fn from_env(encrypted: bool) -> Result<Self, AppError> {
+        match env::var(ENV_SEARCH_BACKEND).ok().as_deref() {
+            None | Some("") | Some("sqlite") => Ok(Self::Sqlite),
+            Some("meilisearch") => {
+                if encrypted {
+                    return Err(AppError::Config(format!(
+                        "{ENV_SEARCH_BACKEND}=meilisearch would store post content unencrypted; \
+                         unset {ENV_CONTENT_ENCRYPTION_KEYS} or use sqlite"
+                    )));
+                }
+                let url = env::var(ENV_MEILISEARCH_URL)
+                    .ok()
+                    .filter(|url| !url.is_empty())
+                    .ok_or_else(|| {
+                        AppError::Config(format!(
+                            "{ENV_SEARCH_BACKEND}=meilisearch needs {ENV_MEILISEARCH_URL}"
+                        ))
+                    })?;
+                let api_key = env::var(ENV_MEILISEARCH_API_KEY)
+                    .ok()
+                    .filter(|key| !key.is_empty());
+                Ok(Self::Meilisearch {
+                    url: url.trim_end_matches('/').to_string(),
+                    api_key,
+                })
+            }
+            Some(other) => Err(AppError::Config(format!(
+                "{ENV_SEARCH_BACKEND} must be sqlite or meilisearch, not {other}"
+            ))),
+        }
+    }
+}
+
+/// Outgoing email settings.
+#[derive(Debug, Clone)]
+pub struct MailConfig {
+    /// SMTP server URL; emails are only logged when unset.
+    pub smtp_url: Option<String>,
+    /// Sender mailbox, e.g. `Blog <noreply@example.com>`.
+    pub from: String,
+}
+
+impl Default for MailConfig {
+    fn default() -> Self {
+        Self {
+            smtp_url: None,
+            from: DEFAULT_MAIL_FROM.to_string(),
+        }
+    }
+}
+
+impl MailConfig {
+    /// Load mail settings from environment variables.
+    fn from_env() -> Self {
+        Self {
+            smtp_url: env::var(ENV_SMTP_URL).ok().filter(|url| !url.is_empty()),
+            from: env::var(ENV_MAIL_FROM).unwrap_or_else(|_| DEFAULT_MAIL_FROM.to_string()),
+        }
+    }
+}
+
+/// HTTP server tuning, and the certificate that turns on HTTPS.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpServerConfig {
+    /// Worker threads; `None` starts one per CPU core.
+    pub workers: Option<usize>,
+    /// Keep-alive timeout in seconds; zero disables keep-alive.
+    pub keep_alive_secs: u64,
+    /// Time a client has to send request headers, in milliseconds.
+    pub client_request_timeout_ms: u64,
+    /// Time a client has to close a connection being shut down, in milliseconds.
+    pub client_disconnect_timeout_ms: u64,
+    /// Maximum open connections per worker.
+    pub max_connections: usize,
+    /// Maximum pending connections on the listener.
+    pub backlog: u32,
+    /// Serve HTTPS (HTTP/2 negotiated via ALPN) instead of plain HTTP.
+    pub tls: Option<TlsConfig>,
+}
+
+impl Default for HttpServerConfig {
+    fn default() -> Self {
+        Self {
+            workers: None,
+            keep_alive_secs: DEFAULT_HTTP_KEEP_ALIVE_SECS,
+            client_request_timeout_ms: DEFAULT_HTTP_CLIENT_REQUEST_TIMEOUT_MS,
+            client_disconnect_timeout_ms: DEFAULT_HTTP_CLIENT_DISCONNECT_TIMEOUT_MS,
+            max_connections: DEFAULT_HTTP_MAX_CONNECTIONS,
+            backlog: DEFAULT_HTTP_BACKLOG,
+            tls: None,
+        }
+    }
+}
+
+impl HttpServerConfig {
+    /// Load HTTP server settings from environment variables.
+    fn from_env() -> Result<Self, AppError> {
+        let workers = match env::var(ENV_HTTP_WORKERS) {
+            Ok(value) => Some(
+                value
+                    .parse()
+                    .ok()
+                    .filter(|workers| *workers > 0)
+                    .ok_or_else(|| {
+                        AppError::Config(format!("{ENV_HTTP_WORKERS} must be a positive number"))
+                    })?,
+            ),
+            Err(_) => None,
+        };
+
+        let tls = match (env::var(ENV_TLS_CERT_PATH), env::var(ENV_TLS_KEY_PATH)) {
+            (Ok(cert_path), Ok(key_path)) => Some(TlsConfig {
+                cert_path,
+                key_path,
+            }),
+            (Err(_), Err(_)) => None,
+            _ => {
+                return Err(AppError::Config(format!(
+                    "{ENV_TLS_CERT_PATH} and {ENV_TLS_KEY_PATH} must be set together"
+                )));
+            }
+        };
+
+        Ok(Self {
+            workers,
+            keep_alive_secs: number_from_env(
+                ENV_HTTP_KEEP_ALIVE_SECS,
+                DEFAULT_HTTP_KEEP_ALIVE_SECS,
+            )?,
+            client_request_timeout_ms: number_from_env(
+                ENV_HTTP_CLIENT_REQUEST_TIMEOUT_MS,
+                DEFAULT_HTTP_CLIENT_REQUEST_TIMEOUT_MS,
+            )?,
+            client_disconnect_timeout_ms: number_from_env(
+                ENV_HTTP_CLIENT_DISCONNECT_TIMEOUT_MS,
+                DEFAULT_HTTP_CLIENT_DISCONNECT_TIMEOUT_MS,
+            )?,
+            max_connections: number_from_env(
+                ENV_HTTP_MAX_CONNECTIONS,
+                DEFAULT_HTTP_MAX_CONNECTIONS,
+            )?,
+            backlog: number_from_env(ENV_HTTP_BACKLOG, DEFAULT_HTTP_BACKLOG)?,
+            tls,
+        })
+    }
+}
+
+/// PEM files of the HTTPS certificate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TlsConfig {
+    /// Certificate chain, leaf first.
+    pub cert_path: String,
+    /// Private key of the leaf certificate.
+    pub key_path: String,
+}
+
+/// `Cache-Control` max-ages (seconds) of cacheable JSON responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// A single post.
+    pub post_max_age_secs: u32,
+    /// The post list.
+    pub post_list_max_age_secs: u32,
+    /// The announcement banner.
+    pub announcement_max_age_secs: u32,
+    /// The site settings.
+    pub site_settings_max_age_secs: u32,
+    /// `@mention` and `#tag` suggestions.
+    pub suggestion_max_age_secs: u32,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            post_max_age_secs: DEFAULT_POST_CACHE_MAX_AGE_SECS,
+            post_list_max_age_secs: DEFAULT_POST_LIST_CACHE_MAX_AGE_SECS,
+            announcement_max_age_secs: DEFAULT_ANNOUNCEMENT_CACHE_MAX_AGE_SECS,
+            site_settings_max_age_secs: DEFAULT_SITE_SETTINGS_CACHE_MAX_AGE_SECS,
+            suggestion_max_age_secs: DEFAULT_SUGGESTION_CACHE_MAX_AGE_SECS,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Load cache max-ages from environment variables.
+    fn from_env() -> Result<Self, AppError> {
+        Ok(Self {
+            post_max_age_secs: number_from_env(
+                ENV_POST_CACHE_MAX_AGE_SECS,
+                DEFAULT_POST_CACHE_MAX_AGE_SECS,
+            )?,
+            post_list_max_age_secs: number_from_env(
+                ENV_POST_LIST_CACHE_MAX_AGE_SECS,
+                DEFAULT_POST_LIST_CACHE_MAX_AGE_SECS,
+            )?,
+            announcement_max_age_secs: number_from_env(
+                ENV_ANNOUNCEMENT_CACHE_MAX_AGE_SECS,
+                DEFAULT_ANNOUNCEMENT_CACHE_MAX_AGE_SECS,
+            )?,
+            site_settings_max_age_secs: number_from_env(
+                ENV_SITE_SETTINGS_CACHE_MAX_AGE_SECS,
+                DEFAULT_SITE_SETTINGS_CACHE_MAX_AGE_SECS,
+            )?,
+            suggestion_max_age_secs: number_from_env(
+                ENV_SUGGESTION_CACHE_MAX_AGE_SECS,
+                DEFAULT_SUGGESTION_CACHE_MAX_AGE_SECS,
+            )?,
+        })
+    }
+}
+
+/// Fault injection settings for exercising client retry logic.
+///
+/// All rates default to zero, which disables injection entirely.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChaosConfig {
+    /// Probability of delaying a request.
+    pub latency_rate: f64,
+    /// Upper bound of the injected delay in milliseconds.
+    pub latency_ms: u64,
+    /// Probability of failing a request with a server error.
+    pub error_rate: f64,
+    /// Probability of dropping the connection instead of responding.
+    pub drop_rate: f64,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            latency_rate: 0.0,
+            latency_ms: DEFAULT_CHAOS_LATENCY_MS,
+            error_rate: 0.0,
+            drop_rate: 0.0,
+        }
+    }
+}
+
+impl ChaosConfig {
+    /// Returns true if any fault has a non-zero rate.
+    pub fn is_enabled(&self) -> bool {
+        self.latency_rate > 0.0 || self.error_rate > 0.0 || self.drop_rate > 0.0
+    }
+
+    /// Load chaos settings from environment variables.
+    ///
+    /// Injection is refused in release builds so it cannot reach production.
+    fn from_env() -> Result<Self, AppError> {
+        let chaos = Self {
+            latency_rate: rate_from_env(ENV_CHAOS_LATENCY_RATE)?,
+            latency_ms: env::var(ENV_CHAOS_LATENCY_MS)
+                .unwrap_or_else(|_| DEFAULT_CHAOS_LATENCY_MS.to_string())
+                .parse()
+                .map_err(|_| {
+                    AppError::Config(format!("{ENV_CHAOS_LATENCY_MS} must be a number"))
+                })?,
+            error_rate: rate_from_env(ENV_CHAOS_ERROR_RATE)?,
+            drop_rate: rate_from_env(ENV_CHAOS_DROP_RATE)?,
+        };
+
+        if chaos.is_enabled() && !cfg!(debug_assertions) {
+            return Err(AppError::Config(
+                "fault injection is only available in debug builds".to_string(),
+            ));
+        }
+
+        Ok(chaos)
+    }
+}
+
+/// Parses an optional probability in `0.0..=1.0`, defaulting to zero.
+fn rate_from_env(name: &str) -> Result<f64, AppError> {
+    let Ok(value) = env::var(name) else {
+        return Ok(0.0);
+    };
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|rate| (0.0..=1.0).contains(rate))
+        .ok_or_else(|| AppError::Config(format!("{name} must be between 0.0 and 1.0")))
+}
+
+/// Parses an optional number, falling back to `default`.
+fn number_from_env<T: FromStr>(name: &str, default: T) -> Result<T, AppError> {
+    match env::var(name) {
+        Ok(value) => value
+            .parse()
+            .map_err(|_| AppError::Config(format!("{name} must be a number"))),
+        Err(_) => Ok(default),
+    }
+}
+
+/// Parses an optional number, `None` when unset or empty.
+fn optional_number_from_env<T: FromStr>(name: &str) -> Result<Option<T>, AppError> {
+    match env::var(name) {
+        Ok(value) if !value.is_empty() => value
+            .parse()
+            .map(Some)
+            .map_err(|_| AppError::Config(format!("{name} must be a number"))),
+        _ => Ok(None),
+    }
+}
+
+/// Parses an optional `true`/`false` switch, defaulting to `false`.
+fn switch_from_env(name: &str) -> Result<bool, AppError> {
+    match env::var(name).as_deref() {
+        Err(_) | Ok("") | Ok("false") | Ok("0") => Ok(false),
+        Ok("true") | Ok("1") => Ok(true),
+        Ok(_) => Err(AppError::Config(format!("{name} must be true or false"))),
+    }
+}
+
+/// Parses the comma-separated embed provider whitelist.
+fn embed_providers_from_env() -> Result<Vec<EmbedProvider>, AppError> {
+    env::var(ENV_EMBED_PROVIDERS)
+        .unwrap_or_else(|_| DEFAULT_EMBED_PROVIDERS.to_string())
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            name.parse()
+                .map_err(|e| AppError::Config(format!("{ENV_EMBED_PROVIDERS}: {e}")))
+        })
+        .collect()
+}
+
+/// Parses the comma-separated reserved username list.
+fn reserved_usernames_from_env() -> Vec<String> {
+    env::var(ENV_RESERVED_USERNAMES)
+        .unwrap_or_else(|_| DEFAULT_RESERVED_USERNAMES.to_string())
+        .split(',')
+        .map(normalize_username)
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Reads a base URL, dropping any trailing slash.
+fn url_from_env(name: &str, default: &str) -> String {
+    env::var(name)
+        .unwrap_or_else(|_| default.to_string())
+        .trim_end_matches('/')
+        .to_string()
 }
 
 impl Config {
@@ -42,11 +643,94 @@ impl Config {
             .parse()
             .map_err(|_| AppError::Config(format!("{ENV_GRPC_PORT} must be a number")))?;
 
+        let http_server = HttpServerConfig::from_env()?;
+        let chaos = ChaosConfig::from_env()?;
+        let cache = CacheConfig::from_env()?;
+        let embed_providers = embed_providers_from_env()?;
+        let reserved_usernames = reserved_usernames_from_env();
+        let invite_only = switch_from_env(ENV_INVITE_ONLY)?;
+        let mail = MailConfig::from_env();
+        let public_url = url_from_env(ENV_PUBLIC_URL, DEFAULT_PUBLIC_URL);
+        let site_url = url_from_env(ENV_SITE_URL, DEFAULT_SITE_URL);
+        let jwt_issuer = env::var(ENV_JWT_ISSUER)
+            .ok()
+            .filter(|issuer| !issuer.is_empty())
+            .unwrap_or_else(|| public_url.clone());
+        let jwt_audience =
+            env::var(ENV_JWT_AUDIENCE).unwrap_or_else(|_| DEFAULT_JWT_AUDIENCE.to_string());
+        let indieauth_token_endpoint = env::var(ENV_INDIEAUTH_TOKEN_ENDPOINT)
+            .ok()
+            .filter(|url| !url.is_empty());
+        let websub_hub_url = env::var(ENV_WEBSUB_HUB_URL)
+            .ok()
+            .filter(|url| !url.is_empty());
+        let devto_api_url = url_from_env(ENV_DEVTO_API_URL, DEFAULT_DEVTO_API_URL);
+        let hashnode_api_url = url_from_env(ENV_HASHNODE_API_URL, DEFAULT_HASHNODE_API_URL);
+        let upload_dir =
+            env::var(ENV_UPLOAD_DIR).unwrap_or_else(|_| DEFAULT_UPLOAD_DIR.to_string());
+        // Never sign uploads with the JWT secret itself
+        let upload_signing_key = match env::var(ENV_UPLOAD_SIGNING_KEY)
+            .ok()
+            .filter(|key| !key.is_empty())
+        {
+            Some(key) => key,
+            None => derive_upload_key(&jwt_secret)?,
+        };
+        let clamd_socket = env::var(ENV_CLAMD_SOCKET)
+            .ok()
+            .filter(|path| !path.is_empty());
+        let redis_url = env::var(ENV_REDIS_URL).ok().filter(|url| !url.is_empty());
+        if redis_url.is_some() && !cfg!(feature = "redis") {
+            return Err(AppError::Config(format!(
+                "{ENV_REDIS_URL} needs a server built with the `redis` feature"
+            )));
+        }
+        let quota_backend = QuotaBackend::from_env(redis_url.as_deref())?;
+        let retention = RetentionConfig::from_env()?;
+        let analytics = AnalyticsConfig::from_env()?;
+        let telemetry = TelemetryConfig::from_env()?;
+        let diagnostics = DiagnosticsConfig::from_env()?;
+        let content_cipher = env::var(ENV_CONTENT_ENCRYPTION_KEYS)
+            .ok()
+            .filter(|keys| !keys.is_empty())
+            .map(|keys| {
+                ContentCipher::parse(&keys)
+                    .map_err(|e| AppError::Config(format!("{ENV_CONTENT_ENCRYPTION_KEYS}: {e}")))
+            })
+            .transpose()?;
+        let search_backend = SearchBackend::from_env(content_cipher.is_some())?;
+
         Ok(Self {
             database_url,
             jwt_secret,
+            jwt_issuer,
+            jwt_audience,
             http_port,
+            http_server,
             grpc_port,
+            chaos,
+            cache,
+            embed_providers,
+            reserved_usernames,
+            invite_only,
+            mail,
+            public_url,
+            site_url,
+            indieauth_token_endpoint,
+            websub_hub_url,
+            devto_api_url,
+            hashnode_api_url,
+            upload_dir,
+            upload_signing_key,
+            clamd_socket,
+            content_cipher,
+            redis_url,
+            quota_backend,
+            search_backend,
+            retention,
+            analytics,
+            telemetry,
+            diagnostics,
         })
     }
 }
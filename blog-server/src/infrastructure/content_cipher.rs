@@ -0,0 +1,147 @@
+//! AES-256-GCM encryption of post content at rest.
+//!
+//! Sealed values look like `enc:v1:{key_id}:{base64(nonce || ciphertext)}`.
+//! Values without the prefix were written before encryption was turned on
+//! and are returned as they are, so enabling it needs no migration;
+//! `rotate_content_keys` re-seals old rows under the active key.
+
+use std::sync::Arc;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::constants::{CONTENT_KEY_LEN, SEALED_CONTENT_PREFIX};
+use crate::domain::AppError;
+
+/// Seals and opens post content with a list of named keys.
+///
+/// The first key seals; all of them open, so retired keys stay listed
+/// until every row is rotated off them.
+#[derive(Clone)]
+pub struct ContentCipher {
+    keys: Arc<Vec<(String, LessSafeKey)>>,
+    rng: SystemRandom,
+}
+
+impl ContentCipher {
+    /// Parses comma-separated `id:base64key` pairs, active key first.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut keys: Vec<(String, LessSafeKey)> = Vec::new();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (id, key) = entry
+                .split_once(':')
+                .ok_or_else(|| "each key must be `id:base64key`".to_string())?;
+            if id.is_empty() || keys.iter().any(|(known, _)| known == id) {
+                return Err(format!("key ID `{id}` must be non-empty and unique"));
+            }
+            let bytes = STANDARD
+                .decode(key)
+                .ok()
+                .filter(|bytes| bytes.len() == CONTENT_KEY_LEN)
+                .ok_or_else(|| format!("key `{id}` must be {CONTENT_KEY_LEN} bytes of base64"))?;
+            let key = UnboundKey::new(&AES_256_GCM, &bytes)
+                .map_err(|_| format!("key `{id}` is invalid"))?;
+            keys.push((id.to_string(), LessSafeKey::new(key)));
+        }
+        if keys.is_empty() {
+            return Err("no keys given".to_string());
+        }
+
+        Ok(Self {
+            keys: Arc::new(keys),
+            rng: SystemRandom::new(),
+        })
+    }
+
+    /// Generates a random key in the base64 form [`parse`](Self::parse) takes.
+    pub fn generate_key() -> Result<String, AppError> {
+        let mut key = [0u8; CONTENT_KEY_LEN];
+        SystemRandom::new()
+            .fill(&mut key)
+            .map_err(|_| AppError::Internal("random generator failed".to_string()))?;
+        Ok(STANDARD.encode(key))
+    }
+
+    /// ID of the key new values are sealed with.
+    pub fn active_key_id(&self) -> &str {
+        &self.keys[0].0
+    }
+
+    /// Encrypts `plaintext` with the active key.
+    pub fn seal(&self, plaintext: &str) -> Result<String, AppError> {
+        let (id, key) = &self.keys[0];
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| AppError::Internal("random generator failed".to_string()))?;
+
+        let mut data = plaintext.as_bytes().to_vec();
+        key.seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut data)
+            .map_err(|_| AppError::Internal("content encryption failed".to_string()))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.append(&mut data);
+        Ok(format!(
+            "{SEALED_CONTENT_PREFIX}{id}:{}",
+            STANDARD.encode(sealed)
+        ))
+    }
+
+    /// Decrypts a value from [`seal`](Self::seal); unsealed values are
+    /// returned unchanged.
+    pub fn open(&self, stored: &str) -> Result<String, AppError> {
+        let Some(sealed) = stored.strip_prefix(SEALED_CONTENT_PREFIX) else {
+            return Ok(stored.to_string());
+        };
+        let (id, payload) = sealed
+            .split_once(':')
+            .ok_or_else(|| AppError::Internal("malformed encrypted content".to_string()))?;
+        let key = self
+            .keys
+            .iter()
+            .find(|(known, _)| known == id)
+            .map(|(_, key)| key)
+            .ok_or_else(|| AppError::Internal(format!("unknown content key `{id}`")))?;
+
+        let mut data = STANDARD
+            .decode(payload)
+            .ok()
+            .filter(|data| data.len() >= NONCE_LEN)
+            .ok_or_else(|| AppError::Internal("malformed encrypted content".to_string()))?;
+        let mut ciphertext = data.split_off(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(&data)
+            .map_err(|_| AppError::Internal("malformed encrypted content".to_string()))?;
+
+        let plaintext = key
+            .open_in_place(nonce, Aad::empty(), &mut ciphertext)
+            .map_err(|_| AppError::Internal("content decryption failed".to_string()))?;
+        String::from_utf8(plaintext.to_vec())
+            .map_err(|_| AppError::Internal("decrypted content is not UTF-8".to_string()))
+    }
+
+    /// Whether `stored` is already sealed with the active key.
+    pub fn is_current(&self, stored: &str) -> bool {
+        stored
+            .strip_prefix(SEALED_CONTENT_PREFIX)
+            .and_then(|sealed| sealed.split_once(':'))
+            .is_some_and(|(id, _)| id == self.active_key_id())
+    }
+}
+
+/// Seals `content` when a cipher is configured.
+pub fn seal(cipher: Option<&ContentCipher>, content: &str) -> Result<String, AppError> {
+    match cipher {
+        Some(cipher) => cipher.seal(content),
+        None => Ok(content.to_string()),
+    }
+}
+
+/// Opens `content` in place when a cipher is configured.
+pub fn open(cipher: Option<&ContentCipher>, content: &mut String) -> Result<(), AppError> {
+    if let Some(cipher) = cipher {
+        *content = cipher.open(content)?;
+    }
+    Ok(())
+}
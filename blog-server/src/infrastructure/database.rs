@@ -1,10 +1,15 @@
 //! Database connection and pool management.
 
-use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use std::collections::HashSet;
 use std::str::FromStr;
+
+use chrono::Utc;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sqlx::{Sqlite, Transaction};
 use tracing::info;
 
-use crate::constants::DB_MAX_CONNECTIONS;
+use crate::constants::{DB_MAX_CONNECTIONS, NORMALIZE_USERNAMES_MIGRATION};
+use crate::domain::normalize_username;
 
 /// Creates a SQLite connection pool.
 pub async fn create_pool(database_url: &str) -> Result<SqlitePool, sqlx::Error> {
@@ -21,10 +26,83 @@ pub async fn create_pool(database_url: &str) -> Result<SqlitePool, sqlx::Error>
     Ok(pool)
 }
 
-/// Runs database migrations.
+/// Runs database migrations, then the data migrations not applied yet.
 pub async fn run_migrations(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     info!("Running database migrations");
     sqlx::migrate!().run(pool).await?;
+
+    let mut tx = pool.begin().await?;
+    let applied = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM data_migrations WHERE name = ?",
+        NORMALIZE_USERNAMES_MIGRATION
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    if applied == 0 {
+        normalize_usernames(&mut tx).await?;
+        let now = Utc::now();
+        sqlx::query!(
+            "INSERT INTO data_migrations (name, applied_at) VALUES (?, ?)",
+            NORMALIZE_USERNAMES_MIGRATION,
+            now
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+
     info!("Database migrations completed");
     Ok(())
 }
+
+/// Recomputes `users.username_normalized` with [`normalize_username`].
+///
+/// The SQL backfill used `lower(trim(...))`, which only folds ASCII and
+/// skips NFC, so lookups missed non-ASCII names. As there, a name colliding
+/// with an older account's gets `#{id}` appended.
+async fn normalize_usernames(tx: &mut Transaction<'_, Sqlite>) -> Result<(), sqlx::Error> {
+    let users =
+        sqlx::query!(r#"SELECT id as "id!", username, username_normalized FROM users ORDER BY id"#)
+            .fetch_all(&mut **tx)
+            .await?;
+
+    let mut taken = HashSet::new();
+    let mut changed = Vec::new();
+    for user in users {
+        let mut normalized = normalize_username(&user.username);
+        if !taken.insert(normalized.clone()) {
+            normalized = format!("{normalized}#{}", user.id);
+            taken.insert(normalized.clone());
+        }
+        if normalized != user.username_normalized {
+            changed.push((user.id, normalized));
+        }
+    }
+
+    // Park changed rows on values no name can have first, so swapping two
+    // names does not trip the unique index halfway
+    for (id, _) in &changed {
+        let parked = format!("\u{0}{id}");
+        sqlx::query!(
+            "UPDATE users SET username_normalized = ? WHERE id = ?",
+            parked,
+            id
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+    for (id, normalized) in &changed {
+        sqlx::query!(
+            "UPDATE users SET username_normalized = ? WHERE id = ?",
+            normalized,
+            id
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    if !changed.is_empty() {
+        info!(count = changed.len(), "Normalized usernames");
+    }
+    Ok(())
+}
@@ -0,0 +1,80 @@
+//! Event bus for live updates, in-process or relayed through Redis.
+
+#[cfg(feature = "redis")]
+mod redis;
+
+use tokio::sync::broadcast;
+#[cfg(feature = "redis")]
+use tokio::sync::mpsc;
+
+use crate::constants::EVENT_BUS_CAPACITY;
+use crate::domain::DomainEvent;
+
+#[cfg(feature = "redis")]
+pub use self::redis::RedisRelay;
+
+/// Broadcasts events to every current subscriber.
+///
+/// Events are not stored: subscribers only see events published after they
+/// subscribed, and a subscriber more than `EVENT_BUS_CAPACITY` events behind
+/// skips the oldest.
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<DomainEvent>,
+    /// Outbox of the Redis relay, which echoes events back to every replica.
+    #[cfg(feature = "redis")]
+    relay: Option<mpsc::UnboundedSender<DomainEvent>>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus {
+    /// Creates an event bus delivering within this process.
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUS_CAPACITY);
+        Self {
+            sender,
+            #[cfg(feature = "redis")]
+            relay: None,
+        }
+    }
+
+    /// Creates an event bus relayed through the Redis server at `url`, so
+    /// subscribers on every replica see events published on any of them.
+    ///
+    /// The returned relay does the Redis traffic and must be run.
+    #[cfg(feature = "redis")]
+    pub fn with_redis(url: &str) -> Result<(Self, RedisRelay), crate::domain::AppError> {
+        let (sender, _) = broadcast::channel(EVENT_BUS_CAPACITY);
+        let (outbox, inbox) = mpsc::unbounded_channel();
+        let relay = RedisRelay::new(url, sender.clone(), inbox)?;
+        let bus = Self {
+            sender,
+            relay: Some(outbox),
+        };
+        Ok((bus, relay))
+    }
+
+    /// Publishes an event; it is dropped when nobody is subscribed.
+    pub fn publish(&self, event: DomainEvent) {
+        #[cfg(feature = "redis")]
+        let event = match &self.relay {
+            // Delivered locally once Redis echoes it back
+            Some(relay) => match relay.send(event) {
+                Ok(()) => return,
+                Err(mpsc::error::SendError(event)) => event,
+            },
+            None => event,
+        };
+        let _ = self.sender.send(event);
+    }
+
+    /// Subscribes to events published from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<DomainEvent> {
+        self.sender.subscribe()
+    }
+}
@@ -0,0 +1,119 @@
+//! Relays event bus traffic through Redis pub/sub.
+
+use std::time::Duration;
+
+use redis::aio::MultiplexedConnection;
+use redis::{AsyncCommands, Client};
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::StreamExt;
+use tracing::{info, warn};
+
+use crate::constants::{ENV_REDIS_URL, REDIS_EVENTS_CHANNEL, REDIS_RECONNECT_DELAY_SECS};
+use crate::domain::{AppError, DomainEvent};
+
+/// Publishes local events to Redis and delivers every event Redis carries,
+/// including this replica's own, to local subscribers.
+///
+/// While Redis is unreachable, events published here still reach local
+/// subscribers, and events from other replicas are missed.
+pub struct RedisRelay {
+    client: Client,
+    local: broadcast::Sender<DomainEvent>,
+    outbox: mpsc::UnboundedReceiver<DomainEvent>,
+}
+
+impl RedisRelay {
+    pub(super) fn new(
+        url: &str,
+        local: broadcast::Sender<DomainEvent>,
+        outbox: mpsc::UnboundedReceiver<DomainEvent>,
+    ) -> Result<Self, AppError> {
+        let client =
+            Client::open(url).map_err(|e| AppError::Config(format!("{ENV_REDIS_URL}: {e}")))?;
+        Ok(Self {
+            client,
+            local,
+            outbox,
+        })
+    }
+
+    /// Relays events until the job is stopped.
+    pub async fn run(self) {
+        let Self {
+            client,
+            local,
+            outbox,
+        } = self;
+        tokio::join!(publish(&client, &local, outbox), subscribe(&client, &local));
+    }
+}
+
+/// Sends local events to Redis, delivering them locally when it fails.
+async fn publish(
+    client: &Client,
+    local: &broadcast::Sender<DomainEvent>,
+    mut outbox: mpsc::UnboundedReceiver<DomainEvent>,
+) {
+    let mut connection: Option<MultiplexedConnection> = None;
+    while let Some(event) = outbox.recv().await {
+        let payload = match serde_json::to_string(&event) {
+            Ok(payload) => payload,
+            Err(e) => {
+                warn!(error = %e, "Failed to encode event");
+                let _ = local.send(event);
+                continue;
+            }
+        };
+        let sent = match connection.take() {
+            Some(conn) => Ok(conn),
+            None => client.get_multiplexed_async_connection().await,
+        };
+        let sent = match sent {
+            Ok(mut conn) => {
+                let sent = conn
+                    .publish::<_, _, ()>(REDIS_EVENTS_CHANNEL, payload)
+                    .await;
+                connection = sent.is_ok().then_some(conn);
+                sent
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = sent {
+            warn!(error = %e, "Failed to publish event to Redis");
+            let _ = local.send(event);
+        }
+    }
+}
+
+/// Delivers events from Redis locally, reconnecting when the subscription
+/// drops.
+async fn subscribe(client: &Client, local: &broadcast::Sender<DomainEvent>) {
+    loop {
+        match relay_incoming(client, local).await {
+            Ok(()) => warn!("Redis event subscription closed"),
+            Err(e) => warn!(error = %e, "Redis event subscription failed"),
+        }
+        tokio::time::sleep(Duration::from_secs(REDIS_RECONNECT_DELAY_SECS)).await;
+    }
+}
+
+async fn relay_incoming(
+    client: &Client,
+    local: &broadcast::Sender<DomainEvent>,
+) -> redis::RedisResult<()> {
+    let mut pubsub = client.get_async_pubsub().await?;
+    pubsub.subscribe(REDIS_EVENTS_CHANNEL).await?;
+    info!(channel = REDIS_EVENTS_CHANNEL, "Subscribed to Redis events");
+
+    let mut messages = pubsub.on_message();
+    while let Some(message) = messages.next().await {
+        let payload: String = message.get_payload()?;
+        match serde_json::from_str::<DomainEvent>(&payload) {
+            Ok(event) => {
+                let _ = local.send(event);
+            }
+            Err(e) => warn!(error = %e, "Ignoring malformed event from Redis"),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,295 @@
+//! Outbound HTTP for background jobs that visit links found in posts, for
+//! talking to other ActivityPub servers, for verifying IndieAuth tokens, for
+//! pinging the WebSub hub, for cross-posting, and for feeding the search
+//! server.
+//!
+//! URLs come from user content or remote servers, so requests are refused for
+//! hosts that resolve to loopback, private, or link-local addresses, and
+//! redirects to such IP literals are not followed. The IndieAuth token
+//! endpoint, the WebSub hub, the cross-posting APIs, and the search server are
+//! configured by the operator and exempt from the host check.
+
+use std::net::IpAddr;
+use std::time::Duration;
+
+use reqwest::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::{Client, Method, StatusCode, Url, redirect};
+
+use crate::constants::{
+    ACTIVITY_ACCEPT, ACTIVITY_JSON, FETCH_MAX_BODY_BYTES, FETCH_MAX_REDIRECTS, FETCH_TIMEOUT_SECS,
+    FETCH_USER_AGENT,
+};
+use crate::domain::AppError;
+
+/// HTTP client for fetching external pages.
+#[derive(Clone)]
+pub struct HttpFetcher {
+    client: Client,
+}
+
+impl HttpFetcher {
+    /// Creates a new HttpFetcher.
+    pub fn new() -> Result<Self, AppError> {
+        let policy = redirect::Policy::custom(|attempt| {
+            if attempt.previous().len() >= FETCH_MAX_REDIRECTS {
+                attempt.error("too many redirects")
+            } else if is_private_literal(attempt.url()) {
+                attempt.stop()
+            } else {
+                attempt.follow()
+            }
+        });
+        let client = Client::builder()
+            .timeout(Duration::from_secs(FETCH_TIMEOUT_SECS))
+            .user_agent(FETCH_USER_AGENT)
+            .redirect(policy)
+            .build()
+            .map_err(|e| AppError::Config(format!("HTTP client: {e}")))?;
+
+        Ok(Self { client })
+    }
+
+    /// Fetches an HTML page, reading at most `FETCH_MAX_BODY_BYTES`.
+    pub async fn fetch_html(&self, url: &str) -> Result<String, AppError> {
+        let url = check_public(url).await?;
+        let mut response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| AppError::Fetch(e.to_string()))?;
+
+        let is_html = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("text/html"));
+        if !is_html {
+            return Err(AppError::Fetch("not an HTML page".to_string()));
+        }
+
+        let body = read_limited(&mut response).await?;
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+
+    /// Fetches an ActivityPub document (actor, key) as JSON.
+    pub async fn fetch_activity(&self, url: &str) -> Result<serde_json::Value, AppError> {
+        let url = check_public(url).await?;
+        let mut response = self
+            .client
+            .get(url)
+            .header(ACCEPT, ACTIVITY_ACCEPT)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| AppError::Fetch(e.to_string()))?;
+
+        let body = read_limited(&mut response).await?;
+        serde_json::from_slice(&body).map_err(|e| AppError::Fetch(e.to_string()))
+    }
+
+    /// Posts an activity to a remote inbox with the given (signature) headers.
+    pub async fn post_activity(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        body: Vec<u8>,
+    ) -> Result<(), AppError> {
+        let url = check_public(url).await?;
+        let mut request = self
+            .client
+            .post(url)
+            .header(CONTENT_TYPE, ACTIVITY_JSON)
+            .body(body);
+        for (name, value) in headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        request
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| AppError::Fetch(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Asks an IndieAuth token endpoint about a bearer token.
+    ///
+    /// Returns `None` if the endpoint rejects the token.
+    pub async fn verify_token(
+        &self,
+        endpoint: &str,
+        token: &str,
+    ) -> Result<Option<serde_json::Value>, AppError> {
+        let mut response = self
+            .client
+            .get(endpoint)
+            .header(ACCEPT, "application/json")
+            .header(AUTHORIZATION, format!("Bearer {token}"))
+            .send()
+            .await
+            .map_err(|e| AppError::Fetch(e.to_string()))?;
+        if response.status().is_client_error() {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(AppError::Fetch(format!(
+                "token endpoint returned {}",
+                response.status()
+            )));
+        }
+
+        let body = read_limited(&mut response).await?;
+        serde_json::from_slice(&body)
+            .map(Some)
+            .map_err(|e| AppError::Fetch(e.to_string()))
+    }
+
+    /// Tells a WebSub hub that a topic (feed) has new content.
+    pub async fn publish_to_hub(&self, hub: &str, topic: &str) -> Result<(), AppError> {
+        self.client
+            .post(hub)
+            .form(&[("hub.mode", "publish"), ("hub.url", topic)])
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| AppError::Fetch(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Sends a JSON request to an operator-configured API (cross-posting, the
+    /// search server) and returns its JSON reply.
+    pub async fn send_json(
+        &self,
+        method: Method,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: &serde_json::Value,
+    ) -> Result<serde_json::Value, AppError> {
+        let mut request = self
+            .client
+            .request(method, url)
+            .header(ACCEPT, "application/json")
+            .json(body);
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        let mut response = request
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| AppError::Fetch(e.to_string()))?;
+
+        let body = read_limited(&mut response).await?;
+        serde_json::from_slice(&body).map_err(|e| AppError::Fetch(e.to_string()))
+    }
+
+    /// Returns the final HTTP status of a URL without reading its body.
+    ///
+    /// Sends `HEAD` first and retries with `GET` when the server does not
+    /// support it.
+    pub async fn check_status(&self, url: &str) -> Result<u16, AppError> {
+        let url = check_public(url).await?;
+        let head = self
+            .client
+            .head(url.clone())
+            .send()
+            .await
+            .map_err(|e| AppError::Fetch(e.to_string()))?;
+        let status = head.status();
+        if status != StatusCode::METHOD_NOT_ALLOWED && status != StatusCode::NOT_IMPLEMENTED {
+            return Ok(status.as_u16());
+        }
+
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| AppError::Fetch(e.to_string()))?;
+        Ok(response.status().as_u16())
+    }
+}
+
+/// Reads a response body, keeping at most `FETCH_MAX_BODY_BYTES`.
+async fn read_limited(response: &mut reqwest::Response) -> Result<Vec<u8>, AppError> {
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| AppError::Fetch(e.to_string()))?
+    {
+        body.extend_from_slice(&chunk);
+        if body.len() >= FETCH_MAX_BODY_BYTES {
+            body.truncate(FETCH_MAX_BODY_BYTES);
+            break;
+        }
+    }
+
+    Ok(body)
+}
+
+/// Parses an http(s) URL and checks that its host resolves to public addresses.
+async fn check_public(url: &str) -> Result<Url, AppError> {
+    let parsed = Url::parse(url).map_err(|e| AppError::Fetch(e.to_string()))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(AppError::Fetch("unsupported scheme".to_string()));
+    }
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| AppError::Fetch("missing host".to_string()))?;
+    let port = parsed.port_or_known_default().unwrap_or(80);
+
+    let addrs: Vec<_> = tokio::net::lookup_host((host.trim_matches(['[', ']']), port))
+        .await
+        .map_err(|e| AppError::Fetch(e.to_string()))?
+        .collect();
+    if addrs.is_empty() || addrs.iter().any(|addr| !is_public(addr.ip())) {
+        return Err(AppError::Fetch(format!("{host} is not a public host")));
+    }
+
+    Ok(parsed)
+}
+
+/// Returns true if the URL names localhost or a non-public IP directly.
+fn is_private_literal(url: &Url) -> bool {
+    let Some(host) = url.host_str() else {
+        return true;
+    };
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .trim_matches(['[', ']'])
+            .parse::<IpAddr>()
+            .is_ok_and(|ip| !is_public(ip))
+}
+
+/// Returns true for addresses reachable on the public internet.
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                // Shared address space (100.64.0.0/10)
+                || (ip.octets()[0] == 100 && ip.octets()[1] & 0xc0 == 64))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(v4) => is_public(IpAddr::V4(v4)),
+            None => {
+                let first = ip.segments()[0];
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    // Unique local (fc00::/7) and link-local (fe80::/10)
+                    || first & 0xfe00 == 0xfc00
+                    || first & 0xffc0 == 0xfe80)
+            }
+        },
+    }
+}
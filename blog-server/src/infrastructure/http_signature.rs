@@ -0,0 +1,160 @@
+//! HTTP signatures (`rsa-sha256`, draft-cavage) as used between ActivityPub
+//! servers, plus the `Digest` header they cover.
+
+use argon2::password_hash::rand_core::OsRng;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chrono::{DateTime, Utc};
+use rsa::pkcs1::DecodeRsaPublicKey;
+use rsa::pkcs1v15::{Signature, SigningKey, VerifyingKey};
+use rsa::pkcs8::{
+    DecodePrivateKey, DecodePublicKey, EncodePrivateKey, EncodePublicKey, LineEnding,
+};
+use rsa::signature::{SignatureEncoding, Signer, Verifier};
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use sha2::{Digest, Sha256};
+
+use crate::constants::ACTOR_KEY_BITS;
+use crate::domain::AppError;
+
+/// PEM-encoded RSA key pair (PKCS#8 private key, SPKI public key).
+#[derive(Debug, Clone)]
+pub struct KeyPair {
+    pub private_key_pem: String,
+    pub public_key_pem: String,
+}
+
+/// Generates a new RSA key pair. CPU-heavy: call from a blocking task.
+pub fn generate_key_pair() -> Result<KeyPair, AppError> {
+    let private_key = RsaPrivateKey::new(&mut OsRng, ACTOR_KEY_BITS)
+        .map_err(|e| AppError::Internal(format!("key generation failed: {e}")))?;
+    let private_key_pem = private_key
+        .to_pkcs8_pem(LineEnding::LF)
+        .map_err(|e| AppError::Internal(e.to_string()))?
+        .to_string();
+    let public_key_pem = private_key
+        .to_public_key()
+        .to_public_key_pem(LineEnding::LF)
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
+    Ok(KeyPair {
+        private_key_pem,
+        public_key_pem,
+    })
+}
+
+/// Returns the `Digest` header value of a request body.
+pub fn digest_header(body: &[u8]) -> String {
+    format!("SHA-256={}", BASE64.encode(Sha256::digest(body)))
+}
+
+/// Formats a time as an HTTP `Date` header value.
+pub fn http_date(time: DateTime<Utc>) -> String {
+    time.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// Parses an HTTP `Date` header value.
+pub fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+/// Builds the string that is signed: one `name: value` line per header, in order.
+pub fn signing_string(headers: &[(String, String)]) -> String {
+    headers
+        .iter()
+        .map(|(name, value)| format!("{name}: {value}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Signs `headers` and returns the `Signature` header value.
+pub fn sign(
+    private_key_pem: &str,
+    key_id: &str,
+    headers: &[(String, String)],
+) -> Result<String, AppError> {
+    let private_key = RsaPrivateKey::from_pkcs8_pem(private_key_pem)
+        .map_err(|e| AppError::Internal(format!("invalid private key: {e}")))?;
+    let signature = SigningKey::<Sha256>::new(private_key).sign(signing_string(headers).as_bytes());
+    let names = headers
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Ok(format!(
+        r#"keyId="{key_id}",algorithm="rsa-sha256",headers="{names}",signature="{}""#,
+        BASE64.encode(signature.to_bytes())
+    ))
+}
+
+/// Parsed `Signature` header.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureHeader {
+    pub key_id: String,
+    /// Lowercase names of the signed headers, in signing order.
+    pub headers: Vec<String>,
+    pub signature: Vec<u8>,
+}
+
+impl SignatureHeader {
+    /// Parses a `Signature` header value (`keyId="...",headers="...",signature="..."`).
+    pub fn parse(value: &str) -> Result<Self, AppError> {
+        let mut key_id = None;
+        let mut headers = None;
+        let mut signature = None;
+        for param in value.split(',') {
+            let Some((name, raw)) = param.trim().split_once('=') else {
+                continue;
+            };
+            let raw = raw.trim_matches('"');
+            match name {
+                "keyId" => key_id = Some(raw.to_string()),
+                "headers" => {
+                    headers = Some(raw.split_whitespace().map(str::to_lowercase).collect())
+                }
+                "signature" => {
+                    signature = Some(
+                        BASE64
+                            .decode(raw)
+                            .map_err(|_| invalid("signature is not base64"))?,
+                    )
+                }
+                "algorithm" if !matches!(raw, "rsa-sha256" | "hs2019") => {
+                    return Err(invalid("unsupported algorithm"));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            key_id: key_id.ok_or_else(|| invalid("missing keyId"))?,
+            // Without a list, only the Date header is signed
+            headers: headers.unwrap_or_else(|| vec!["date".to_string()]),
+            signature: signature.ok_or_else(|| invalid("missing signature"))?,
+        })
+    }
+}
+
+/// Verifies a signature over `signing_string` with a PEM public key (SPKI or PKCS#1).
+pub fn verify(
+    public_key_pem: &str,
+    signing_string: &str,
+    signature: &[u8],
+) -> Result<(), AppError> {
+    let public_key = RsaPublicKey::from_public_key_pem(public_key_pem)
+        .or_else(|_| RsaPublicKey::from_pkcs1_pem(public_key_pem))
+        .map_err(|_| invalid("unreadable public key"))?;
+    let signature = Signature::try_from(signature).map_err(|_| invalid("malformed signature"))?;
+
+    VerifyingKey::<Sha256>::new(public_key)
+        .verify(signing_string.as_bytes(), &signature)
+        .map_err(|_| invalid("signature does not match"))
+}
+
+/// Builds an `InvalidSignature` error.
+fn invalid(reason: &str) -> AppError {
+    AppError::InvalidSignature(reason.to_string())
+}
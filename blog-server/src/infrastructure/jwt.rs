@@ -1,11 +1,39 @@
 //! JWT token handling.
 
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use blog_shared::ServiceScope;
+use chrono::{DateTime, Utc};
+use jsonwebtoken::errors::ErrorKind;
 use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
 use serde::{Deserialize, Serialize};
 
 use crate::constants::JWT_EXPIRY_HOURS;
 use crate::domain::AppError;
 
+/// Key and environment tokens are signed for.
+///
+/// Tokens are only accepted with the same issuer and audience, so a token
+/// minted by another environment sharing the secret is rejected.
+#[derive(Debug, Clone)]
+pub struct JwtConfig {
+    pub secret: String,
+    /// `iss` claim: the server minting tokens.
+    pub issuer: String,
+    /// `aud` claim: who the tokens are meant for.
+    pub audience: String,
+}
+
+/// Kind of principal a token was issued to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenType {
+    /// A user signed in with their password; `sid` is their session.
+    #[default]
+    User,
+    /// A service account acting as `sub`; `sid` is the account ID.
+    Service,
+}
+
 /// JWT claims structure.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
@@ -13,32 +41,128 @@ pub struct Claims {
     pub sub: i64,
     /// Expiration time (Unix timestamp).
     pub exp: usize,
+    /// Issue time (Unix timestamp).
+    pub iat: usize,
+    /// Issuer (`JwtConfig::issuer`).
+    pub iss: String,
+    /// Audience (`JwtConfig::audience`).
+    pub aud: String,
+    /// Unique token ID.
+    pub jti: String,
+    /// Session ID (absent in tokens issued before sessions were tracked).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
+    /// Token type (absent in user tokens issued before service accounts).
+    #[serde(default)]
+    pub typ: TokenType,
+    /// Scopes of a service token (empty for user tokens).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<ServiceScope>,
+}
+
+impl Claims {
+    /// Whether the token may make requests that change data.
+    pub fn can_write(&self) -> bool {
+        self.typ == TokenType::User || self.scopes.contains(&ServiceScope::Write)
+    }
+}
+
+/// Creates a JWT token for the given user and session, issued at `now`.
+pub fn create_token(
+    user_id: i64,
+    session_id: &str,
+    now: DateTime<Utc>,
+    config: &JwtConfig,
+) -> Result<String, AppError> {
+    let expires_at = now + chrono::Duration::hours(JWT_EXPIRY_HOURS);
+    sign(
+        user_id,
+        session_id,
+        TokenType::User,
+        Vec::new(),
+        now,
+        expires_at,
+        config,
+    )
 }
 
-/// Creates a JWT token for the given user ID.
-pub fn create_token(user_id: i64, secret: &str) -> Result<String, AppError> {
-    let expiration = chrono::Utc::now() + chrono::Duration::hours(JWT_EXPIRY_HOURS);
+/// Creates a service account token acting as `user_id`, issued at `now`.
+pub fn create_service_token(
+    user_id: i64,
+    account_id: i64,
+    scopes: Vec<ServiceScope>,
+    now: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    config: &JwtConfig,
+) -> Result<String, AppError> {
+    sign(
+        user_id,
+        &account_id.to_string(),
+        TokenType::Service,
+        scopes,
+        now,
+        expires_at,
+        config,
+    )
+}
+
+/// Signs claims for a token of the given type.
+fn sign(
+    user_id: i64,
+    sid: &str,
+    typ: TokenType,
+    scopes: Vec<ServiceScope>,
+    issued_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    config: &JwtConfig,
+) -> Result<String, AppError> {
     let claims = Claims {
         sub: user_id,
-        exp: expiration.timestamp() as usize,
+        exp: expires_at.timestamp() as usize,
+        iat: issued_at.timestamp() as usize,
+        iss: config.issuer.clone(),
+        aud: config.audience.clone(),
+        jti: format!("{:016x}{:016x}", OsRng.next_u64(), OsRng.next_u64()),
+        sid: Some(sid.to_string()),
+        typ,
+        scopes,
     };
 
     encode(
         &Header::default(),
         &claims,
-        &EncodingKey::from_secret(secret.as_bytes()),
+        &EncodingKey::from_secret(config.secret.as_bytes()),
     )
     .map_err(AppError::Jwt)
 }
 
-/// Validates a JWT token and returns the claims.
-pub fn validate_token(token: &str, secret: &str) -> Result<Claims, AppError> {
-    let token_data = decode::<Claims>(
+/// Validates a JWT token, including its issuer and audience, and returns
+/// the claims.
+///
+/// Expiry is checked against `now` rather than the system time, with the
+/// usual leeway.
+pub fn validate_token(
+    token: &str,
+    now: DateTime<Utc>,
+    config: &JwtConfig,
+) -> Result<Claims, AppError> {
+    let mut validation = Validation::default();
+    validation.set_issuer(&[&config.issuer]);
+    validation.set_audience(&[&config.audience]);
+    validation.set_required_spec_claims(&["exp", "iss", "aud"]);
+    validation.validate_exp = false;
+
+    let claims = decode::<Claims>(
         token,
-        &DecodingKey::from_secret(secret.as_bytes()),
-        &Validation::default(),
+        &DecodingKey::from_secret(config.secret.as_bytes()),
+        &validation,
     )
-    .map_err(AppError::Jwt)?;
+    .map_err(AppError::Jwt)?
+    .claims;
+
+    if (claims.exp as i64) < now.timestamp() - validation.leeway as i64 {
+        return Err(AppError::Jwt(ErrorKind::ExpiredSignature.into()));
+    }
 
-    Ok(token_data.claims)
+    Ok(claims)
 }
@@ -0,0 +1,103 @@
+//! Outgoing email over SMTP.
+
+use lettre::message::header::{ContentType, HeaderName, HeaderValue};
+use lettre::message::{Mailbox, MultiPart, SinglePart};
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use tracing::{debug, info};
+
+use crate::domain::AppError;
+use crate::infrastructure::config::MailConfig;
+
+/// Email ready to be sent, with plain text and HTML bodies.
+#[derive(Debug, Clone)]
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+    /// Sent as a one-click `List-Unsubscribe` (RFC 8058), so mail clients can
+    /// offer an unsubscribe button.
+    pub unsubscribe_url: Option<String>,
+}
+
+/// Sends email through the configured SMTP server.
+///
+/// Without `SMTP_URL` nothing is delivered: emails are only logged, which is
+/// enough for development and tests.
+#[derive(Clone)]
+pub struct Mailer {
+    from: Mailbox,
+    transport: Option<AsyncSmtpTransport<Tokio1Executor>>,
+}
+
+impl Mailer {
+    /// Creates a mailer from the mail configuration.
+    pub fn new(config: &MailConfig) -> Result<Self, AppError> {
+        let from = config
+            .from
+            .parse()
+            .map_err(|e| AppError::Config(format!("invalid sender address: {e}")))?;
+        let transport = config
+            .smtp_url
+            .as_deref()
+            .map(|url| {
+                AsyncSmtpTransport::<Tokio1Executor>::from_url(url)
+                    .map(|builder| builder.build())
+                    .map_err(|e| AppError::Config(format!("invalid SMTP URL: {e}")))
+            })
+            .transpose()?;
+
+        Ok(Self { from, transport })
+    }
+
+    /// Sends an email, or logs it if no SMTP server is configured.
+    pub async fn send(&self, email: Email) -> Result<(), AppError> {
+        let Some(transport) = &self.transport else {
+            info!(to = %email.to, subject = %email.subject, "SMTP not configured, email not sent");
+            debug!(body = %email.text, "Unsent email");
+            return Ok(());
+        };
+
+        let to: Mailbox = email
+            .to
+            .parse()
+            .map_err(|e| AppError::Mail(format!("invalid recipient: {e}")))?;
+        let mut builder = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(email.subject);
+        if let Some(url) = email.unsubscribe_url {
+            builder = builder
+                .raw_header(HeaderValue::new(
+                    HeaderName::new_from_ascii_str("List-Unsubscribe"),
+                    format!("<{url}>"),
+                ))
+                .raw_header(HeaderValue::new(
+                    HeaderName::new_from_ascii_str("List-Unsubscribe-Post"),
+                    "List-Unsubscribe=One-Click".to_string(),
+                ));
+        }
+        let message = builder
+            .multipart(
+                MultiPart::alternative()
+                    .singlepart(
+                        SinglePart::builder()
+                            .header(ContentType::TEXT_PLAIN)
+                            .body(email.text),
+                    )
+                    .singlepart(
+                        SinglePart::builder()
+                            .header(ContentType::TEXT_HTML)
+                            .body(email.html),
+                    ),
+            )
+            .map_err(|e| AppError::Mail(e.to_string()))?;
+
+        transport
+            .send(message)
+            .await
+            .map_err(|e| AppError::Mail(e.to_string()))?;
+
+        Ok(())
+    }
+}
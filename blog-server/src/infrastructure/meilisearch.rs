@@ -0,0 +1,162 @@
+//! Search index on a Meilisearch server, for deployments outgrowing FTS5.
+
+use async_trait::async_trait;
+use reqwest::Method;
+use serde_json::{Value, json};
+
+use crate::constants::{
+    MEILISEARCH_INDEX, SEARCH_SNIPPET_ELLIPSIS, SEARCH_SNIPPET_MARK_END, SEARCH_SNIPPET_MARK_START,
+    SEARCH_SNIPPET_TOKENS,
+};
+use crate::domain::{
+    AppError, SearchDocument, SearchFilters, SearchHit, SearchIndex, SearchResults,
+};
+use crate::infrastructure::http_fetcher::HttpFetcher;
+
+/// Searches posts on a Meilisearch server, which handles ranking, typos and
+/// highlighting itself.
+///
+/// The server only knows what it is fed, so documents are kept up to date
+/// from `DomainEvent::PostChanged` by `SearchIndexService`.
+#[derive(Clone)]
+pub struct MeiliSearchIndex {
+    fetcher: HttpFetcher,
+    url: String,
+    api_key: Option<String>,
+}
+
+impl MeiliSearchIndex {
+    /// Creates an index on the Meilisearch server at `url` (without a
+    /// trailing slash).
+    pub fn new(fetcher: HttpFetcher, url: String, api_key: Option<String>) -> Self {
+        Self {
+            fetcher,
+            url,
+            api_key,
+        }
+    }
+
+    /// Sets which attributes are searched (titles first, so they rank
+    /// higher) and filtered on, creating the index if missing.
+    pub async fn configure(&self) -> Result<(), AppError> {
+        let settings = json!({
+            "searchableAttributes": ["title", "content"],
+            "filterableAttributes": ["author", "author_id", "tags", "lang", "status", "created_at"],
+        });
+        self.send(Method::PATCH, "settings", &settings).await?;
+
+        Ok(())
+    }
+
+    /// Sends a request to `path` under the posts index.
+    async fn send(&self, method: Method, path: &str, body: &Value) -> Result<Value, AppError> {
+        let url = format!("{}/indexes/{MEILISEARCH_INDEX}/{path}", self.url);
+        let authorization = self.api_key.as_ref().map(|key| format!("Bearer {key}"));
+        let headers: Vec<(&str, &str)> = authorization
+            .as_deref()
+            .map(|value| ("Authorization", value))
+            .into_iter()
+            .collect();
+        self.fetcher.send_json(method, &url, &headers, body).await
+    }
+}
+
+#[async_trait]
+impl SearchIndex for MeiliSearchIndex {
+    async fn search(
+        &self,
+        query: &str,
+        filters: &SearchFilters,
+        limit: i64,
+        offset: i64,
+        viewer_id: Option<i64>,
+    ) -> Result<SearchResults, AppError> {
+        let request = json!({
+            "q": query,
+            "offset": offset,
+            "limit": limit,
+            "filter": filter_expr(filters, viewer_id),
+            "attributesToRetrieve": ["id"],
+            "attributesToHighlight": ["title", "content"],
+            "attributesToCrop": ["content"],
+            "cropLength": SEARCH_SNIPPET_TOKENS,
+            "cropMarker": SEARCH_SNIPPET_ELLIPSIS,
+            "highlightPreTag": SEARCH_SNIPPET_MARK_START,
+            "highlightPostTag": SEARCH_SNIPPET_MARK_END,
+        });
+        let response = self.send(Method::POST, "search", &request).await?;
+
+        let hits = response["hits"]
+            .as_array()
+            .ok_or_else(|| AppError::Fetch("Meilisearch reply has no hits".to_string()))?
+            .iter()
+            .filter_map(|hit| {
+                let post_id = hit["id"].as_i64()?;
+                let formatted = &hit["_formatted"];
+                let content = formatted["content"].as_str().unwrap_or_default();
+                // Like FTS5, fall back to the title when the content has no match
+                let snippet = if content.contains(SEARCH_SNIPPET_MARK_START) {
+                    content
+                } else {
+                    formatted["title"].as_str().unwrap_or_default()
+                };
+                Some(SearchHit {
+                    post_id,
+                    snippet: snippet.to_string(),
+                })
+            })
+            .collect();
+        let total = response["estimatedTotalHits"].as_i64().unwrap_or_default();
+
+        Ok(SearchResults { hits, total })
+    }
+
+    fn is_external(&self) -> bool {
+        true
+    }
+
+    async fn upsert(&self, document: SearchDocument) -> Result<(), AppError> {
+        let documents = json!([document]);
+        self.send(Method::POST, "documents?primaryKey=id", &documents)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn remove(&self, post_id: i64) -> Result<(), AppError> {
+        self.send(Method::POST, "documents/delete-batch", &json!([post_id]))
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Builds a Meilisearch filter: posts visible to `viewer_id` (published, or
+/// their own) narrowed by `filters`.
+fn filter_expr(filters: &SearchFilters, viewer_id: Option<i64>) -> String {
+    let mut clauses = vec![match viewer_id {
+        Some(id) => format!("(status = \"published\" OR author_id = {id})"),
+        None => "status = \"published\"".to_string(),
+    }];
+    if let Some(lang) = &filters.lang {
+        clauses.push(format!("lang = {}", quoted(lang)));
+    }
+    if let Some(author) = &filters.author {
+        clauses.push(format!("author = {}", quoted(author)));
+    }
+    if let Some(tag) = &filters.tag {
+        clauses.push(format!("tags = {}", quoted(tag)));
+    }
+    if let Some(from) = filters.from {
+        clauses.push(format!("created_at >= {}", from.timestamp()));
+    }
+    if let Some(until) = filters.until {
+        clauses.push(format!("created_at < {}", until.timestamp()));
+    }
+    clauses.join(" AND ")
+}
+
+/// Quotes a string for a Meilisearch filter.
+fn quoted(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
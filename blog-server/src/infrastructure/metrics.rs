@@ -0,0 +1,68 @@
+//! Per-request timing metrics for debug responses.
+//!
+//! Metrics are only collected inside [`scope`]; outside of it the recording
+//! helpers are no-ops, so repositories can call them unconditionally.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+tokio::task_local! {
+    static METRICS: Arc<RequestMetrics>;
+}
+
+/// Counters accumulated while handling a single request.
+#[derive(Debug, Default)]
+pub struct RequestMetrics {
+    query_count: AtomicU64,
+    db_micros: AtomicU64,
+    serialization_micros: AtomicU64,
+}
+
+impl RequestMetrics {
+    /// Number of database queries executed.
+    pub fn query_count(&self) -> u64 {
+        self.query_count.load(Ordering::Relaxed)
+    }
+
+    /// Total time spent waiting on the database.
+    pub fn db_time(&self) -> Duration {
+        Duration::from_micros(self.db_micros.load(Ordering::Relaxed))
+    }
+
+    /// Total time spent serializing response bodies.
+    pub fn serialization_time(&self) -> Duration {
+        Duration::from_micros(self.serialization_micros.load(Ordering::Relaxed))
+    }
+}
+
+/// Runs `fut` with `metrics` as the current request's metrics.
+pub async fn scope<F: Future>(metrics: Arc<RequestMetrics>, fut: F) -> F::Output {
+    METRICS.scope(metrics, fut).await
+}
+
+/// Awaits a database query, recording its duration and count.
+pub async fn timed_query<F: Future>(fut: F) -> F::Output {
+    let start = Instant::now();
+    let output = fut.await;
+    let elapsed = elapsed_micros(start);
+    let _ = METRICS.try_with(|m| {
+        m.query_count.fetch_add(1, Ordering::Relaxed);
+        m.db_micros.fetch_add(elapsed, Ordering::Relaxed);
+    });
+    output
+}
+
+/// Records time spent serializing a response body.
+pub fn record_serialization(start: Instant) {
+    let elapsed = elapsed_micros(start);
+    let _ = METRICS.try_with(|m| {
+        m.serialization_micros.fetch_add(elapsed, Ordering::Relaxed);
+    });
+}
+
+/// Microseconds elapsed since `start`, saturating at `u64::MAX`.
+fn elapsed_micros(start: Instant) -> u64 {
+    u64::try_from(start.elapsed().as_micros()).unwrap_or(u64::MAX)
+}
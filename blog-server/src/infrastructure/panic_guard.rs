@@ -0,0 +1,106 @@
+//! Catching panics in request handlers.
+//!
+//! [`catch_panic`] polls a future under `catch_unwind`. While it runs, the
+//! panic hook it installs records where a panic happened instead of
+//! printing it, so the caller can log and report the panic with its request.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::Once;
+use std::task::{Context, Poll};
+
+tokio::task_local! {
+    /// Location of the last panic inside the current `catch_panic`.
+    static CAUGHT_LOCATION: RefCell<Option<String>>;
+}
+
+/// A panic caught by [`catch_panic`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaughtPanic {
+    pub message: String,
+    /// Source location, e.g. `src/presentation/http_handlers.rs:42:9`.
+    pub location: Option<String>,
+}
+
+impl fmt::Display for CaughtPanic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "{} at {location}", self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+/// Runs `future`, turning a panic while polling it into [`CaughtPanic`].
+///
+/// The panic is neither printed nor passed to the panic hooks installed
+/// before this one; the caller logs and reports it.
+pub async fn catch_panic<F: Future>(future: F) -> Result<F::Output, CaughtPanic> {
+    install_hook();
+    CAUGHT_LOCATION
+        .scope(
+            RefCell::new(None),
+            CatchUnwind {
+                future: Box::pin(future),
+            },
+        )
+        .await
+}
+
+/// Whether the current panic happens inside [`catch_panic`], so whoever
+/// catches it reports it.
+pub fn is_catching() -> bool {
+    CAUGHT_LOCATION.try_with(|_| ()).is_ok()
+}
+
+/// Message a panic was raised with.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panic with a non-string payload".to_string())
+}
+
+/// Installs, once per process, the hook recording the location of caught
+/// panics; other panics go to the previous hook.
+fn install_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let location = info.location().map(ToString::to_string);
+            let recorded = CAUGHT_LOCATION.try_with(|slot| *slot.borrow_mut() = location);
+            if recorded.is_err() {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Future returned inside [`catch_panic`].
+struct CatchUnwind<F> {
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, CaughtPanic>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let future = self.future.as_mut();
+        match panic::catch_unwind(AssertUnwindSafe(|| future.poll(cx))) {
+            Ok(poll) => poll.map(Ok),
+            Err(payload) => Poll::Ready(Err(CaughtPanic {
+                message: panic_message(payload.as_ref()),
+                location: CAUGHT_LOCATION
+                    .try_with(|slot| slot.borrow_mut().take())
+                    .ok()
+                    .flatten(),
+            })),
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Service account usage counted in Redis, shared by every replica.
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use chrono::NaiveDate;
+use redis::aio::MultiplexedConnection;
+use redis::{AsyncConnectionConfig, Client, RedisResult, Script};
+use tokio::time::Instant;
+
+use crate::constants::{
+    ENV_REDIS_URL, REDIS_QUOTA_CONSUME_SCRIPT, REDIS_QUOTA_KEY_PREFIX, REDIS_QUOTA_TIMEOUT_MS,
+    REDIS_QUOTA_TTL_SECS, REDIS_RECONNECT_DELAY_SECS,
+};
+use crate::domain::{ApiQuota, ApiUsage, AppError};
+
+#[derive(Default)]
+struct State {
+    connection: Option<MultiplexedConnection>,
+    /// Redis is not tried again before this, after failing.
+    retry_at: Option<Instant>,
+}
+
+/// Counts service account requests in Redis so quotas hold across replicas.
+///
+/// After a failure Redis is skipped for `REDIS_RECONNECT_DELAY_SECS`, so
+/// callers fall back quickly instead of waiting on every request.
+pub struct RedisQuotaCounter {
+    client: Client,
+    script: Script,
+    state: Mutex<State>,
+}
+
+impl RedisQuotaCounter {
+    /// Creates a counter for the Redis server at `url`, connecting lazily.
+    pub fn new(url: &str) -> Result<Self, AppError> {
+        let client =
+            Client::open(url).map_err(|e| AppError::Config(format!("{ENV_REDIS_URL}: {e}")))?;
+        Ok(Self {
+            client,
+            script: Script::new(REDIS_QUOTA_CONSUME_SCRIPT),
+            state: Mutex::new(State::default()),
+        })
+    }
+
+    /// Counts a request against `quota` unless a limit is used up, and
+    /// returns the shared usage from before it. `seed` is the usage to start
+    /// from when Redis has none for the day (e.g. after a Redis restart).
+    pub async fn consume(
+        &self,
+        account_id: i64,
+        quota: ApiQuota,
+        seed: ApiUsage,
+        is_write: bool,
+    ) -> RedisResult<ApiUsage> {
+        let result = self.try_consume(account_id, quota, seed, is_write).await;
+        if result.is_err() {
+            let mut state = self.lock();
+            state.connection = None;
+            state.retry_at = Some(Instant::now() + Duration::from_secs(REDIS_RECONNECT_DELAY_SECS));
+        }
+        result
+    }
+
+    async fn try_consume(
+        &self,
+        account_id: i64,
+        quota: ApiQuota,
+        seed: ApiUsage,
+        is_write: bool,
+    ) -> RedisResult<ApiUsage> {
+        let mut connection = self.connection().await?;
+        let (requests, writes): (i64, i64) = self
+            .script
+            .key(usage_key(account_id, seed.day))
+            .arg(seed.requests)
+            .arg(seed.writes)
+            .arg(i64::from(is_write))
+            .arg(quota.requests_per_day.unwrap_or(-1))
+            .arg(quota.writes_per_day.unwrap_or(-1))
+            .arg(REDIS_QUOTA_TTL_SECS)
+            .invoke_async(&mut connection)
+            .await?;
+
+        Ok(ApiUsage {
+            day: seed.day,
+            requests,
+            writes,
+        })
+    }
+
+    /// Returns the shared connection, connecting unless backing off.
+    async fn connection(&self) -> RedisResult<MultiplexedConnection> {
+        {
+            let state = self.lock();
+            if let Some(connection) = &state.connection {
+                return Ok(connection.clone());
+            }
+            if state.retry_at.is_some_and(|at| Instant::now() < at) {
+                return Err(redis::RedisError::from((
+                    redis::ErrorKind::IoError,
+                    "Redis recently unavailable",
+                )));
+            }
+        }
+
+        let timeout = Duration::from_millis(REDIS_QUOTA_TIMEOUT_MS);
+        let config = AsyncConnectionConfig::new()
+            .set_connection_timeout(timeout)
+            .set_response_timeout(timeout);
+        let connection = self
+            .client
+            .get_multiplexed_async_connection_with_config(&config)
+            .await?;
+        self.lock().connection = Some(connection.clone());
+        Ok(connection)
+    }
+
+    /// Locks the state, ignoring poisoning: it is always valid.
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Key of the hash counting an account's usage on `day`.
+fn usage_key(account_id: i64, day: NaiveDate) -> String {
+    format!("{REDIS_QUOTA_KEY_PREFIX}:{account_id}:{day}")
+}
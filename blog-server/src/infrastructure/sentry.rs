@@ -0,0 +1,144 @@
+//! Error reports delivered to Sentry through its store API.
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use async_trait::async_trait;
+use reqwest::{Method, Url};
+use serde_json::json;
+
+use crate::domain::{AppError, ErrorReport, ErrorReporter, ErrorSource};
+use crate::infrastructure::http_fetcher::HttpFetcher;
+
+/// Identifier of the reporting client in the auth header.
+const SENTRY_CLIENT: &str = concat!("blog-server/", env!("CARGO_PKG_VERSION"));
+
+/// A parsed Sentry DSN, `<scheme>://<public key>@<host>[/<path>]/<project>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentryDsn {
+    public_key: String,
+    store_url: String,
+}
+
+impl SentryDsn {
+    /// Parses a DSN as shown in the Sentry project settings.
+    pub fn parse(dsn: &str) -> Result<Self, String> {
+        let url = Url::parse(dsn).map_err(|e| format!("invalid URL: {e}"))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err("scheme must be http or https".to_string());
+        }
+        let public_key = url.username();
+        if public_key.is_empty() {
+            return Err("missing public key".to_string());
+        }
+        let host = url.host_str().ok_or("missing host")?;
+        let path = url.path().trim_end_matches('/');
+        let (prefix, project) = path.rsplit_once('/').unwrap_or(("", path));
+        if project.is_empty() {
+            return Err("missing project ID".to_string());
+        }
+        let port = url
+            .port()
+            .map(|port| format!(":{port}"))
+            .unwrap_or_default();
+
+        Ok(Self {
+            public_key: public_key.to_string(),
+            store_url: format!(
+                "{}://{host}{port}{prefix}/api/{project}/store/",
+                url.scheme()
+            ),
+        })
+    }
+
+    /// Endpoint events are posted to.
+    pub fn store_url(&self) -> &str {
+        &self.store_url
+    }
+
+    /// `X-Sentry-Auth` header value.
+    fn auth_header(&self) -> String {
+        format!(
+            "Sentry sentry_version=7, sentry_client={SENTRY_CLIENT}, sentry_key={}",
+            self.public_key
+        )
+    }
+}
+
+/// Reports errors to a Sentry project.
+#[derive(Clone)]
+pub struct SentryReporter {
+    fetcher: HttpFetcher,
+    dsn: SentryDsn,
+    environment: String,
+}
+
+impl SentryReporter {
+    /// Creates a reporter tagging events with `environment`.
+    pub fn new(fetcher: HttpFetcher, dsn: SentryDsn, environment: String) -> Self {
+        Self {
+            fetcher,
+            dsn,
+            environment,
+        }
+    }
+}
+
+#[async_trait]
+impl ErrorReporter for SentryReporter {
+    async fn report(&self, report: &ErrorReport) -> Result<(), AppError> {
+        let auth = self.dsn.auth_header();
+        self.fetcher
+            .send_json(
+                Method::POST,
+                self.dsn.store_url(),
+                &[("X-Sentry-Auth", &auth)],
+                &sentry_event(report, &self.environment),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+/// Sentry event for a report.
+fn sentry_event(report: &ErrorReport, environment: &str) -> serde_json::Value {
+    let mut tags = json!({ "source": report.source.as_str() });
+    let mut event = json!({
+        "event_id": format!("{:016x}{:016x}", OsRng.next_u64(), OsRng.next_u64()),
+        "timestamp": report.occurred_at.timestamp(),
+        "platform": match report.source {
+            ErrorSource::Client => "javascript",
+            ErrorSource::Server | ErrorSource::Panic => "rust",
+        },
+        "level": match report.source {
+            ErrorSource::Panic => "fatal",
+            ErrorSource::Server | ErrorSource::Client => "error",
+        },
+        "logger": "blog-server",
+        "environment": environment,
+        "message": { "formatted": report.message },
+    });
+    if let Some(location) = &report.location {
+        event["culprit"] = json!(location);
+    }
+    if let Some(url) = &report.url {
+        tags["url"] = json!(url);
+    }
+    if let Some(request) = &report.request {
+        event["request"] = json!({
+            "method": request.method,
+            "url": request.path,
+            "headers": request
+                .user_agent
+                .as_ref()
+                .map(|agent| json!({ "User-Agent": agent }))
+                .unwrap_or_else(|| json!({})),
+        });
+        if let Some(id) = &request.request_id {
+            tags["request_id"] = json!(id);
+        }
+        if let Some(status) = request.status {
+            tags["status"] = json!(status.to_string());
+        }
+    }
+    event["tags"] = tags;
+    event
+}
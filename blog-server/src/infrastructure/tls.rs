@@ -0,0 +1,27 @@
+//! TLS settings of the HTTPS listener.
+
+use std::sync::Arc;
+
+use rustls::ServerConfig;
+use rustls::crypto::ring;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+
+use crate::domain::AppError;
+use crate::infrastructure::config::TlsConfig;
+
+/// Loads the certificate chain and key into a rustls server config.
+///
+/// ALPN is left to actix, which offers HTTP/2 and HTTP/1.1.
+pub fn server_config(tls: &TlsConfig) -> Result<ServerConfig, AppError> {
+    let certs = CertificateDer::pem_file_iter(&tls.cert_path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| AppError::Config(format!("TLS certificate {}: {e}", tls.cert_path)))?;
+    let key = PrivateKeyDer::from_pem_file(&tls.key_path)
+        .map_err(|e| AppError::Config(format!("TLS key {}: {e}", tls.key_path)))?;
+
+    ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .and_then(|builder| builder.with_no_client_auth().with_single_cert(certs, key))
+        .map_err(|e| AppError::Config(format!("TLS: {e}")))
+}
@@ -0,0 +1,46 @@
+//! HMAC-SHA256 signatures for expiring upload URLs.
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::constants::UPLOAD_KEY_HKDF_INFO;
+use crate::domain::AppError;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Derives an upload signing key from `secret` (HKDF-SHA256), for when no
+/// key of its own is configured.
+pub fn derive_upload_key(secret: &str) -> Result<String, AppError> {
+    let mut key = [0u8; 32];
+    Hkdf::<Sha256>::new(None, secret.as_bytes())
+        .expand(UPLOAD_KEY_HKDF_INFO, &mut key)
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+    Ok(URL_SAFE_NO_PAD.encode(key))
+}
+
+/// Signs access to upload `id` until the Unix time `expires`, returning a
+/// URL-safe signature.
+pub fn sign_upload(id: &str, expires: i64, key: &str) -> Result<String, AppError> {
+    let mac = mac(id, expires, key)?;
+    Ok(URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes()))
+}
+
+/// Checks a signature from [`sign_upload`] in constant time. Expiry is
+/// checked by the caller.
+pub fn verify_upload(id: &str, expires: i64, signature: &str, key: &str) -> bool {
+    let Ok(signature) = URL_SAFE_NO_PAD.decode(signature) else {
+        return false;
+    };
+    mac(id, expires, key).is_ok_and(|mac| mac.verify_slice(&signature).is_ok())
+}
+
+/// Starts the MAC over `{id}:{expires}`.
+fn mac(id: &str, expires: i64, key: &str) -> Result<HmacSha256, AppError> {
+    let mut mac = HmacSha256::new_from_slice(key.as_bytes())
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+    mac.update(format!("{id}:{expires}").as_bytes());
+    Ok(mac)
+}
@@ -6,3 +6,5 @@ pub mod data;
 pub mod domain;
 pub mod infrastructure;
 pub mod presentation;
+pub mod server;
+pub mod test_server;
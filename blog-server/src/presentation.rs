@@ -1,8 +1,45 @@
 //! Presentation layer: HTTP handlers and routes.
 
+pub mod activitypub_handlers;
+pub mod admin_handlers;
+pub mod analytics_handlers;
+pub mod chaos;
+pub mod crosspost_handlers;
+pub mod debug_envelope;
+pub mod diagnostics;
+pub mod editing_handlers;
+pub mod experiment_handlers;
+pub mod feed_handlers;
 pub mod grpc_service;
+pub mod http_cache;
 pub mod http_handlers;
+pub mod import_handlers;
+pub mod link_check_handlers;
+pub mod markdown_handlers;
+pub mod message_handlers;
+pub mod micropub_handlers;
 pub mod middleware;
+pub mod moderation_handlers;
+pub mod msgpack;
+pub mod newsletter_handlers;
+pub mod notification_handlers;
+pub mod org_handlers;
+pub mod panics;
+pub mod payload;
+pub mod quota;
+pub mod request_id;
+pub mod review_handlers;
+pub mod revision_handlers;
+pub mod routes;
+pub mod service_account_handlers;
+pub mod settings_handlers;
+pub mod site_handlers;
+pub mod suggest_handlers;
+pub mod tag_handlers;
+pub mod telemetry;
+pub mod transfer_handlers;
+pub mod trash_handlers;
+pub mod upload_handlers;
 
 pub use http_handlers::api_routes;
-pub use middleware::{AuthenticatedUser, JwtSecret, OptionalUser};
+pub use middleware::{AdminUser, AuthenticatedUser, EditorUser, ModeratorUser, OptionalUser};
@@ -0,0 +1,111 @@
+//! ActivityPub and WebFinger endpoints (served outside `/api`, at the paths
+//! other Fediverse servers expect).
+
+use std::collections::HashMap;
+
+use actix_web::{HttpRequest, HttpResponse, Responder, Scope, get, post, web};
+use serde::Deserialize;
+
+use crate::application::{ActivityPubService, InboxRequest};
+use crate::constants::{ACTIVITY_JSON, JRD_JSON};
+use crate::domain::{AppError, PublicRef};
+use crate::presentation::payload::Query;
+
+/// Creates the ActivityPub routes (public; inboxes require HTTP signatures).
+pub fn activitypub_routes() -> Scope {
+    web::scope("/ap")
+        .service(actor)
+        .service(outbox)
+        .service(followers)
+        .service(inbox)
+        .service(note)
+}
+
+/// Query parameters of a WebFinger lookup.
+#[derive(Debug, Deserialize)]
+pub struct WebFingerQuery {
+    pub resource: String,
+}
+
+/// Resolves `acct:username@host` to an author's actor.
+#[get("/.well-known/webfinger")]
+pub async fn webfinger(
+    service: web::Data<ActivityPubService>,
+    query: Query<WebFingerQuery>,
+) -> Result<impl Responder, AppError> {
+    let document = service.webfinger(&query.resource).await?;
+    Ok(HttpResponse::Ok().content_type(JRD_JSON).json(document))
+}
+
+/// Returns an author's actor document.
+#[get("/users/{username}")]
+async fn actor(
+    service: web::Data<ActivityPubService>,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    let document = service.actor(&path).await?;
+    Ok(activity_response(document))
+}
+
+/// Returns an author's outbox.
+#[get("/users/{username}/outbox")]
+async fn outbox(
+    service: web::Data<ActivityPubService>,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    let document = service.outbox(&path).await?;
+    Ok(activity_response(document))
+}
+
+/// Returns an author's followers collection.
+#[get("/users/{username}/followers")]
+async fn followers(
+    service: web::Data<ActivityPubService>,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    let document = service.followers(&path).await?;
+    Ok(activity_response(document))
+}
+
+/// Receives a signed activity for an author.
+#[post("/users/{username}/inbox")]
+async fn inbox(
+    req: HttpRequest,
+    service: web::Data<ActivityPubService>,
+    path: web::Path<String>,
+    body: web::Bytes,
+) -> Result<impl Responder, AppError> {
+    let headers: HashMap<String, String> = req
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| Some((name.as_str().to_string(), value.to_str().ok()?.into())))
+        .collect();
+    let request = InboxRequest {
+        method: req.method().to_string(),
+        path: req
+            .uri()
+            .path_and_query()
+            .map_or_else(|| req.path().to_string(), |path| path.to_string()),
+        headers,
+    };
+
+    service.handle_inbox(&path, &request, &body).await?;
+    Ok(HttpResponse::Accepted().finish())
+}
+
+/// Returns the Note object of a post.
+#[get("/posts/{id}")]
+async fn note(
+    service: web::Data<ActivityPubService>,
+    path: web::Path<PublicRef>,
+) -> Result<impl Responder, AppError> {
+    let document = service.note(&path).await?;
+    Ok(activity_response(document))
+}
+
+/// Responds with an ActivityPub document.
+fn activity_response(document: serde_json::Value) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type(ACTIVITY_JSON)
+        .json(document)
+}
@@ -0,0 +1,203 @@
+//! HTTP handlers for the admin dashboard.
+
+use actix_web::{HttpResponse, Responder, Scope, delete, get, put, web};
+use blog_shared::constants::MAX_AUDIT_REASON_LENGTH;
+use blog_shared::{Pagination, ShadowBanRequest, UpdateFeatureFlagRequest, UpdateUserRequest};
+use serde::Deserialize;
+
+use crate::application::{AdminService, RetentionService};
+use crate::domain::AppError;
+use crate::presentation::analytics_handlers::analytics_admin_routes;
+use crate::presentation::diagnostics::diagnostics_admin_routes;
+use crate::presentation::experiment_handlers::experiment_admin_routes;
+use crate::presentation::middleware::{AdminUser, ModeratorUser};
+use crate::presentation::moderation_handlers::{flagged_content_routes, moderation_rule_routes};
+use crate::presentation::payload::{Json, Query};
+use crate::presentation::review_handlers::review_queue;
+use crate::presentation::service_account_handlers::service_account_routes;
+use crate::presentation::site_handlers::{announcement_admin_routes, site_settings_admin_routes};
+use crate::presentation::tag_handlers::tag_admin_routes;
+
+/// Creates the admin routes.
+///
+/// Moderation (queues, shadow bans, audit log) is open to moderators and
+/// the review queue to editors; everything else, including moderation rules,
+/// service accounts, tags, the site settings and announcement banner,
+/// analytics, experiments and diagnostics, is admin-only.
+pub fn admin_routes() -> Scope {
+    web::scope("/admin")
+        .service(get_stats)
+        .service(list_users)
+        .service(update_user)
+        .service(shadow_ban_user)
+        .service(lift_shadow_ban)
+        .service(audit_log)
+        .service(moderation_queue)
+        .service(remove_comment)
+        .service(flagged_content_routes())
+        .service(moderation_rule_routes())
+        .service(service_account_routes())
+        .service(list_flags)
+        .service(update_flag)
+        .service(retention_report)
+        .service(review_queue)
+        .service(tag_admin_routes())
+        .service(announcement_admin_routes())
+        .service(site_settings_admin_routes())
+        .service(analytics_admin_routes())
+        .service(experiment_admin_routes())
+        .service(diagnostics_admin_routes())
+}
+
+/// Query parameters for paginated admin lists.
+#[derive(Debug, Deserialize)]
+pub struct AdminListQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// Returns site-wide counters.
+#[get("/stats")]
+async fn get_stats(
+    _admin: AdminUser,
+    service: web::Data<AdminService>,
+) -> Result<impl Responder, AppError> {
+    let stats = service.stats().await?;
+    Ok(HttpResponse::Ok().json(stats))
+}
+
+/// Lists users for the user management table.
+#[get("/users")]
+async fn list_users(
+    _admin: AdminUser,
+    service: web::Data<AdminService>,
+    query: Query<AdminListQuery>,
+) -> Result<impl Responder, AppError> {
+    let Pagination { limit, offset } = Pagination::clamp(query.limit, query.offset);
+    let users = service.list_users(limit, offset).await?;
+    Ok(HttpResponse::Ok().json(users))
+}
+
+/// Changes a user's role or suspension.
+#[put("/users/{id}")]
+async fn update_user(
+    admin: AdminUser,
+    service: web::Data<AdminService>,
+    path: web::Path<i64>,
+    payload: Json<UpdateUserRequest>,
+) -> Result<impl Responder, AppError> {
+    let user = service
+        .update_user(admin.0.user_id, path.into_inner(), payload.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok().json(user))
+}
+
+/// Hides a user's posts and comments from everyone else.
+#[put("/users/{id}/shadow-ban")]
+async fn shadow_ban_user(
+    moderator: ModeratorUser,
+    service: web::Data<AdminService>,
+    path: web::Path<i64>,
+    payload: Json<ShadowBanRequest>,
+) -> Result<impl Responder, AppError> {
+    let reason = payload
+        .into_inner()
+        .reason
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty());
+    if reason
+        .as_ref()
+        .is_some_and(|reason| reason.chars().count() > MAX_AUDIT_REASON_LENGTH)
+    {
+        return Err(AppError::Validation(format!(
+            "Reason must be at most {MAX_AUDIT_REASON_LENGTH} characters"
+        )));
+    }
+
+    let user = service
+        .shadow_ban(moderator.0.user_id, path.into_inner(), reason.as_deref())
+        .await?;
+    Ok(HttpResponse::Ok().json(user))
+}
+
+/// Lifts a user's shadow ban.
+#[delete("/users/{id}/shadow-ban")]
+async fn lift_shadow_ban(
+    moderator: ModeratorUser,
+    service: web::Data<AdminService>,
+    path: web::Path<i64>,
+) -> Result<impl Responder, AppError> {
+    let user = service
+        .lift_shadow_ban(moderator.0.user_id, path.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok().json(user))
+}
+
+/// Lists moderator actions, newest first.
+#[get("/audit-log")]
+async fn audit_log(
+    _moderator: ModeratorUser,
+    service: web::Data<AdminService>,
+    query: Query<AdminListQuery>,
+) -> Result<impl Responder, AppError> {
+    let Pagination { limit, offset } = Pagination::clamp(query.limit, query.offset);
+    let entries = service.audit_log(limit, offset).await?;
+    Ok(HttpResponse::Ok().json(entries))
+}
+
+/// Lists the newest comments across all posts for review.
+#[get("/moderation/comments")]
+async fn moderation_queue(
+    _moderator: ModeratorUser,
+    service: web::Data<AdminService>,
+    query: Query<AdminListQuery>,
+) -> Result<impl Responder, AppError> {
+    let Pagination { limit, offset } = Pagination::clamp(query.limit, query.offset);
+    let comments = service.moderation_queue(limit, offset).await?;
+    Ok(HttpResponse::Ok().json(comments))
+}
+
+/// Removes any comment.
+#[delete("/moderation/comments/{id}")]
+async fn remove_comment(
+    _moderator: ModeratorUser,
+    service: web::Data<AdminService>,
+    path: web::Path<i64>,
+) -> Result<impl Responder, AppError> {
+    service.delete_comment(path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Lists all feature flags.
+#[get("/flags")]
+async fn list_flags(
+    _admin: AdminUser,
+    service: web::Data<AdminService>,
+) -> Result<impl Responder, AppError> {
+    let flags = service.list_flags().await?;
+    Ok(HttpResponse::Ok().json(flags))
+}
+
+/// Turns a feature flag on or off.
+#[put("/flags/{name}")]
+async fn update_flag(
+    _admin: AdminUser,
+    service: web::Data<AdminService>,
+    path: web::Path<String>,
+    payload: Json<UpdateFeatureFlagRequest>,
+) -> Result<impl Responder, AppError> {
+    let flag = service
+        .set_flag(&path.into_inner(), payload.enabled)
+        .await?;
+    Ok(HttpResponse::Ok().json(flag))
+}
+
+/// Reports what the retention job would remove, without removing anything.
+#[get("/retention")]
+async fn retention_report(
+    _admin: AdminUser,
+    service: web::Data<RetentionService>,
+) -> Result<impl Responder, AppError> {
+    let report = service.report().await?;
+    Ok(HttpResponse::Ok().json(report))
+}
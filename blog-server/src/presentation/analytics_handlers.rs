@@ -0,0 +1,65 @@
+//! HTTP handlers for anonymous analytics: event ingestion (public) and the
+//! aggregated report (nested under `/admin`).
+
+use actix_web::{HttpRequest, HttpResponse, Responder, Scope, get, post, web};
+use blog_shared::AnalyticsBatchRequest;
+use serde::Deserialize;
+
+use crate::application::AnalyticsService;
+use crate::constants::{
+    DEFAULT_ANALYTICS_REPORT_DAYS, DO_NOT_TRACK_HEADER, GLOBAL_PRIVACY_CONTROL_HEADER,
+};
+use crate::domain::AppError;
+use crate::presentation::middleware::AdminUser;
+use crate::presentation::payload::{Json, Query};
+
+/// Creates the public analytics routes.
+pub fn analytics_routes() -> Scope {
+    web::scope("/analytics").service(record_events)
+}
+
+/// Creates the analytics report routes.
+pub fn analytics_admin_routes() -> Scope {
+    web::scope("/analytics").service(analytics_report)
+}
+
+/// Query parameters for the analytics report.
+#[derive(Debug, Deserialize)]
+pub struct AnalyticsReportQuery {
+    pub days: Option<u32>,
+}
+
+/// Accepts a batch of client events. Answers 202 whether or not they are
+/// kept, so clients cannot tell sampling or opt-outs apart.
+#[post("/events")]
+async fn record_events(
+    req: HttpRequest,
+    service: web::Data<AnalyticsService>,
+    body: Json<AnalyticsBatchRequest>,
+) -> Result<impl Responder, AppError> {
+    service.record(body.into_inner(), opted_out(&req)).await?;
+    Ok(HttpResponse::Accepted().finish())
+}
+
+/// Returns daily totals and the most viewed pages.
+#[get("")]
+async fn analytics_report(
+    _admin: AdminUser,
+    service: web::Data<AnalyticsService>,
+    query: Query<AnalyticsReportQuery>,
+) -> Result<impl Responder, AppError> {
+    let days = query.days.unwrap_or(DEFAULT_ANALYTICS_REPORT_DAYS);
+    let report = service.report(days).await?;
+    Ok(HttpResponse::Ok().json(report))
+}
+
+/// Returns true if the browser sends `DNT: 1` or `Sec-GPC: 1`.
+fn opted_out(req: &HttpRequest) -> bool {
+    [DO_NOT_TRACK_HEADER, GLOBAL_PRIVACY_CONTROL_HEADER]
+        .iter()
+        .any(|name| {
+            req.headers()
+                .get(*name)
+                .is_some_and(|value| value.as_bytes() == b"1")
+        })
+}
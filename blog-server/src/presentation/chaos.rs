@@ -0,0 +1,171 @@
+//! Fault injection for HTTP and gRPC, used to exercise client retry logic.
+//!
+//! Enabled through [`ChaosConfig`]; with all rates at zero both the HTTP
+//! middleware and the gRPC layer pass requests straight through.
+
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::web::{Bytes, Data};
+use actix_web::{Error, HttpResponse};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use tonic::Status;
+use tonic::codegen::http;
+use tower::{Layer, Service};
+use tracing::warn;
+
+use crate::domain::AppError;
+use crate::infrastructure::config::ChaosConfig;
+
+/// Fault replacing the normal response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Fault {
+    /// Fail with a server error.
+    Error,
+    /// Abort the connection (HTTP) or report the service unavailable (gRPC).
+    Drop,
+}
+
+/// Returns a random delay if the latency roll hits.
+fn roll_delay(config: &ChaosConfig) -> Option<Duration> {
+    if !roll(config.latency_rate) {
+        return None;
+    }
+    let millis = OsRng.next_u64() % (config.latency_ms + 1);
+    Some(Duration::from_millis(millis))
+}
+
+/// Returns the fault to inject, if any.
+fn roll_fault(config: &ChaosConfig) -> Option<Fault> {
+    if roll(config.drop_rate) {
+        Some(Fault::Drop)
+    } else if roll(config.error_rate) {
+        Some(Fault::Error)
+    } else {
+        None
+    }
+}
+
+/// Returns true with probability `rate`.
+fn roll(rate: f64) -> bool {
+    rate > 0.0 && (OsRng.next_u32() as f64 / u32::MAX as f64) < rate
+}
+
+/// HTTP middleware injecting latency, 500s, and dropped connections.
+///
+/// Reads [`ChaosConfig`] from app data; without it requests pass through.
+pub async fn chaos(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let config = match req.app_data::<Data<ChaosConfig>>() {
+        Some(config) if config.is_enabled() => *config.get_ref(),
+        _ => return Ok(next.call(req).await?.map_into_boxed_body()),
+    };
+
+    if let Some(delay) = roll_delay(&config) {
+        tokio::time::sleep(delay).await;
+    }
+
+    match roll_fault(&config) {
+        Some(Fault::Error) => {
+            warn!(path = %req.path(), "chaos: injecting server error");
+            Ok(req
+                .error_response(AppError::Internal("injected fault".to_string()))
+                .map_into_boxed_body())
+        }
+        Some(Fault::Drop) => {
+            warn!(path = %req.path(), "chaos: dropping connection");
+            // A body stream that fails makes actix-web abort the connection
+            // after the headers, so clients see a truncated response
+            let body = tokio_stream::once(Err::<Bytes, _>(io::Error::other("injected drop")));
+            Ok(req
+                .into_response(HttpResponse::Ok().streaming(body))
+                .map_into_boxed_body())
+        }
+        None => Ok(next.call(req).await?.map_into_boxed_body()),
+    }
+}
+
+/// Tower layer applying [`ChaosConfig`] to the gRPC server.
+#[derive(Debug, Clone, Copy)]
+pub struct ChaosLayer {
+    config: ChaosConfig,
+}
+
+impl ChaosLayer {
+    /// Create a new layer with the given settings.
+    pub fn new(config: ChaosConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl<S> Layer<S> for ChaosLayer {
+    type Service = ChaosService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ChaosService {
+            inner,
+            config: self.config,
+        }
+    }
+}
+
+/// gRPC service wrapper created by [`ChaosLayer`].
+#[derive(Debug, Clone)]
+pub struct ChaosService<S> {
+    inner: S,
+    config: ChaosConfig,
+}
+
+impl<S, ReqBody, ResBody> Service<http::Request<ReqBody>> for ChaosService<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+    ResBody: Default,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<ReqBody>) -> Self::Future {
+        // Take the service that was driven to readiness, leaving a clone behind
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let config = self.config;
+
+        Box::pin(async move {
+            if !config.is_enabled() {
+                return inner.call(req).await;
+            }
+
+            if let Some(delay) = roll_delay(&config) {
+                tokio::time::sleep(delay).await;
+            }
+
+            let path = req.uri().path().to_string();
+            match roll_fault(&config) {
+                Some(Fault::Error) => {
+                    warn!(%path, "chaos: injecting gRPC internal error");
+                    Ok(Status::internal("injected fault").into_http())
+                }
+                Some(Fault::Drop) => {
+                    warn!(%path, "chaos: injecting gRPC unavailable");
+                    Ok(Status::unavailable("injected drop").into_http())
+                }
+                None => inner.call(req).await,
+            }
+        })
+    }
+}
@@ -0,0 +1,92 @@
+//! HTTP handlers for cross-posting to DEV and Hashnode.
+
+use actix_web::{HttpResponse, Responder, delete, get, post, put, web};
+use blog_shared::constants::MAX_INTEGRATION_TOKEN_LENGTH;
+use blog_shared::{CrosspostQuery, CrosspostTarget, IntegrationTokenRequest};
+
+use crate::application::{BlogService, CrosspostService};
+use crate::domain::{AppError, EntityRef};
+use crate::presentation::middleware::AuthenticatedUser;
+use crate::presentation::payload::{Json, Query};
+
+/// Lists the current user's integrations (registered under `/settings`).
+#[get("/integrations")]
+pub async fn list_integrations(
+    auth: AuthenticatedUser,
+    service: web::Data<CrosspostService>,
+) -> Result<impl Responder, AppError> {
+    let integrations = service.list_integrations(auth.user_id).await?;
+    Ok(HttpResponse::Ok().json(integrations))
+}
+
+/// Saves the current user's token for a target (registered under `/settings`).
+#[put("/integrations/{target}")]
+pub async fn set_integration(
+    auth: AuthenticatedUser,
+    service: web::Data<CrosspostService>,
+    path: web::Path<CrosspostTarget>,
+    payload: Json<IntegrationTokenRequest>,
+) -> Result<impl Responder, AppError> {
+    let target = path.into_inner();
+    let mut req = payload.into_inner();
+    req.token = req.token.trim().to_string();
+    req.publication_id = req
+        .publication_id
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty());
+    if req.token.is_empty() || req.token.len() > MAX_INTEGRATION_TOKEN_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Token must be 1 to {MAX_INTEGRATION_TOKEN_LENGTH} characters"
+        )));
+    }
+    if target == CrosspostTarget::Hashnode && req.publication_id.is_none() {
+        return Err(AppError::Validation(
+            "Hashnode needs a publication_id".to_string(),
+        ));
+    }
+
+    let integration = service.set_integration(auth.user_id, target, req).await?;
+    Ok(HttpResponse::Ok().json(integration))
+}
+
+/// Removes the current user's token for a target (registered under `/settings`).
+#[delete("/integrations/{target}")]
+pub async fn delete_integration(
+    auth: AuthenticatedUser,
+    service: web::Data<CrosspostService>,
+    path: web::Path<CrosspostTarget>,
+) -> Result<impl Responder, AppError> {
+    service
+        .delete_integration(auth.user_id, path.into_inner())
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Publishes a post to a target, or updates its copy there (author only).
+#[post("/posts/{id}/crosspost")]
+pub async fn crosspost_post(
+    auth: AuthenticatedUser,
+    service: web::Data<CrosspostService>,
+    posts: web::Data<BlogService>,
+    path: web::Path<EntityRef>,
+    query: Query<CrosspostQuery>,
+) -> Result<impl Responder, AppError> {
+    let post_id = posts.resolve_post(&path).await?;
+    let crosspost = service
+        .crosspost(post_id, auth.user_id, query.target)
+        .await?;
+    Ok(HttpResponse::Ok().json(crosspost))
+}
+
+/// Lists a post's cross-posts with their status (author only).
+#[get("/posts/{id}/crossposts")]
+pub async fn list_crossposts(
+    auth: AuthenticatedUser,
+    service: web::Data<CrosspostService>,
+    posts: web::Data<BlogService>,
+    path: web::Path<EntityRef>,
+) -> Result<impl Responder, AppError> {
+    let post_id = posts.resolve_post(&path).await?;
+    let crossposts = service.list_crossposts(post_id, auth.user_id).await?;
+    Ok(HttpResponse::Ok().json(crossposts))
+}
@@ -0,0 +1,105 @@
+//! Admin-only debug envelope (`?debug=1`).
+//!
+//! Wraps the JSON response as `{"data": ..., "debug": {...}}` with the request
+//! ID, timing breakdown, and query count. Non-admin requests are served as usual.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use actix_web::body::{self, BoxBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::error::ErrorInternalServerError;
+use actix_web::http::StatusCode;
+use actix_web::http::header::{self, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::{Error, HttpMessage, HttpRequest, web};
+use blog_shared::Role;
+use serde_json::{Value, json};
+
+use crate::application::AuthService;
+use crate::constants::DEBUG_QUERY_PARAM;
+use crate::infrastructure::metrics::{self, RequestMetrics};
+use crate::presentation::middleware::extract_user;
+use crate::presentation::request_id::RequestId;
+
+/// Wraps responses in a debug envelope for admins who pass `?debug=1`.
+pub async fn debug_envelope(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    if !debug_requested(req.query_string()) || !is_admin_request(req.request()).await {
+        return next
+            .call(req)
+            .await
+            .map(ServiceResponse::map_into_boxed_body);
+    }
+
+    let request_id = req.extensions().get::<RequestId>().map(|id| id.0.clone());
+    let request_metrics = Arc::new(RequestMetrics::default());
+    let start = Instant::now();
+    let res = metrics::scope(Arc::clone(&request_metrics), next.call(req)).await?;
+    let total = start.elapsed();
+
+    if matches!(
+        res.status(),
+        StatusCode::NO_CONTENT | StatusCode::NOT_MODIFIED
+    ) {
+        return Ok(res.map_into_boxed_body());
+    }
+
+    let (req, res) = res.into_parts();
+    let (mut res, body) = res.into_parts();
+    let bytes = body::to_bytes(body).await.map_err(|e| {
+        let err: Box<dyn std::error::Error> = e.into();
+        ErrorInternalServerError(err.to_string())
+    })?;
+
+    let data = serde_json::from_slice::<Value>(&bytes)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&bytes).into_owned()));
+    let envelope = json!({
+        "data": data,
+        "debug": {
+            "request_id": request_id,
+            "total_ms": millis(total),
+            "db_ms": millis(request_metrics.db_time()),
+            "serialization_ms": millis(request_metrics.serialization_time()),
+            "query_count": request_metrics.query_count(),
+        },
+    });
+
+    // Validators describe the unwrapped body, so they must not leak to caches
+    let headers = res.headers_mut();
+    headers.remove(header::ETAG);
+    headers.remove(header::LAST_MODIFIED);
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+
+    let res = res.set_body(envelope.to_string()).map_into_boxed_body();
+    Ok(ServiceResponse::new(req, res))
+}
+
+/// Checks whether the query string contains `debug=1`.
+fn debug_requested(query: &str) -> bool {
+    query
+        .split('&')
+        .any(|pair| pair.split_once('=') == Some((DEBUG_QUERY_PARAM, "1")))
+}
+
+/// Checks whether the request carries a valid token of an admin user.
+async fn is_admin_request(req: &HttpRequest) -> bool {
+    let Some(service) = req.app_data::<web::Data<AuthService>>() else {
+        return false;
+    };
+    let Ok(user) = extract_user(req).await else {
+        return false;
+    };
+    matches!(user.role(service).await, Ok(Role::Admin))
+}
+
+/// Converts a duration to fractional milliseconds.
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
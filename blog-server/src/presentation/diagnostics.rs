@@ -0,0 +1,174 @@
+//! Admin diagnostics: recording failed write requests, and the routes
+//! listing them (nested under `/admin`).
+
+use actix_web::body::{self, BoxBody, MessageBody};
+use actix_web::dev::{Payload, ServiceRequest, ServiceResponse};
+use actix_web::error::{ErrorInternalServerError, PayloadError};
+use actix_web::http::header::{AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, HeaderName};
+use actix_web::http::{Method, StatusCode};
+use actix_web::middleware::Next;
+use actix_web::{Error, HttpMessage, HttpResponse, Responder, Scope, delete, get, web};
+use blog_shared::{DiagnosticSnapshotDto, truncate};
+use chrono::Utc;
+use tokio_stream::StreamExt;
+
+use crate::application::{AuthService, DiagnosticsService};
+use crate::constants::{MAX_DIAGNOSTIC_BODY_LEN, MSGPACK_BODY_LIMIT};
+use crate::domain::{AppError, DiagnosticBody};
+use crate::presentation::middleware::AdminUser;
+use crate::presentation::request_id::RequestId;
+
+/// Creates the diagnostics routes (admin only).
+pub fn diagnostics_admin_routes() -> Scope {
+    web::scope("/diagnostics")
+        .service(list_diagnostics)
+        .service(clear_diagnostics)
+}
+
+/// Records a redacted snapshot of write requests that fail validation (400,
+/// 422) or with a server error, when the diagnostics are on. Must be wrapped
+/// inside `request_id`, and outside `catch_panics` to see panics.
+///
+/// JSON and form bodies are buffered to be redacted and then handed on;
+/// other bodies are left alone and only their size is kept.
+pub async fn record_failed_writes(
+    mut req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let Some(diagnostics) = req
+        .app_data::<web::Data<DiagnosticsService>>()
+        .filter(|diagnostics| diagnostics.is_enabled() && is_write(req.method()))
+        .cloned()
+    else {
+        return next
+            .call(req)
+            .await
+            .map(ServiceResponse::map_into_boxed_body);
+    };
+
+    let auth_service = req.app_data::<web::Data<AuthService>>().cloned();
+    let token = header(&req, AUTHORIZATION)
+        .and_then(|value| value.strip_prefix("Bearer ").map(str::to_string));
+    let content_type = header(&req, CONTENT_TYPE);
+    let request_body = match content_type.as_deref().and_then(DiagnosticBody::of) {
+        Some(format) => {
+            let body = read_body(&mut req).await?;
+            let redacted = format.redact(&body);
+            req.set_payload(Payload::from(body.freeze()));
+            Some(redacted)
+        }
+        None => header(&req, CONTENT_LENGTH).map(|length| format!("[{length} bytes]")),
+    };
+    let mut snapshot = DiagnosticSnapshotDto {
+        request_id: req.extensions().get::<RequestId>().map(|id| id.0.clone()),
+        method: req.method().to_string(),
+        path: req.path().to_string(),
+        user_id: None,
+        status: 0,
+        content_type,
+        request_body,
+        response_body: None,
+        recorded_at: Utc::now(),
+    };
+
+    let result = match next.call(req).await {
+        Ok(res) if is_failure(res.status()) => {
+            let (req, res) = res.into_parts();
+            let (res, body) = res.into_parts();
+            let bytes = body::to_bytes(body).await.map_err(|e| {
+                let err: Box<dyn std::error::Error> = e.into();
+                ErrorInternalServerError(err.to_string())
+            })?;
+            snapshot.status = res.status().as_u16();
+            snapshot.response_body = body_text(&bytes);
+            Ok(ServiceResponse::new(
+                req,
+                res.set_body(bytes).map_into_boxed_body(),
+            ))
+        }
+        Ok(res) => return Ok(res.map_into_boxed_body()),
+        Err(error) if is_failure(error.as_response_error().status_code()) => {
+            let res = error.error_response();
+            snapshot.status = res.status().as_u16();
+            snapshot.response_body = body::to_bytes(res.into_body())
+                .await
+                .ok()
+                .and_then(|bytes| body_text(&bytes));
+            Err(error)
+        }
+        Err(error) => return Err(error),
+    };
+
+    if let (Some(auth_service), Some(token)) = (auth_service, token) {
+        snapshot.user_id = auth_service
+            .authenticate(&token)
+            .await
+            .ok()
+            .map(|claims| claims.sub);
+    }
+    diagnostics.record(snapshot);
+    result
+}
+
+/// Lists the recorded failures, newest first.
+#[get("")]
+async fn list_diagnostics(
+    _admin: AdminUser,
+    service: web::Data<DiagnosticsService>,
+) -> Result<impl Responder, AppError> {
+    Ok(HttpResponse::Ok().json(service.list()))
+}
+
+/// Drops the recorded failures.
+#[delete("")]
+async fn clear_diagnostics(
+    _admin: AdminUser,
+    service: web::Data<DiagnosticsService>,
+) -> Result<impl Responder, AppError> {
+    service.clear();
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Whether a request may change data.
+fn is_write(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    )
+}
+
+/// Whether a response is a failure worth a snapshot.
+fn is_failure(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY
+    ) || status.is_server_error()
+}
+
+/// Value of a text header.
+fn header(req: &ServiceRequest, name: HeaderName) -> Option<String> {
+    req.headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Reads the whole request body, up to `MSGPACK_BODY_LIMIT` bytes, the
+/// largest any handler accepts.
+async fn read_body(req: &mut ServiceRequest) -> Result<web::BytesMut, Error> {
+    let mut payload = req.take_payload();
+    let mut body = web::BytesMut::new();
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk?;
+        if body.len() + chunk.len() > MSGPACK_BODY_LIMIT {
+            return Err(PayloadError::Overflow.into());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// A response body as kept in a snapshot.
+fn body_text(bytes: &[u8]) -> Option<String> {
+    (!bytes.is_empty()).then(|| truncate(&String::from_utf8_lossy(bytes), MAX_DIAGNOSTIC_BODY_LEN))
+}
@@ -0,0 +1,174 @@
+//! HTTP handlers for co-editing presence: heartbeats and the WebSocket
+//! pushing presence changes.
+
+use std::convert::Infallible;
+
+use actix_codec::{Decoder, Encoder};
+use actix_http::ws;
+use actix_web::http::header;
+use actix_web::web::{Bytes, BytesMut};
+use actix_web::{HttpRequest, HttpResponse, Responder, web};
+use blog_shared::EditingPresenceDto;
+use serde::Deserialize;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::warn;
+
+use crate::application::{AuthService, BlogService, PresenceService};
+use crate::constants::EDITING_SOCKET_BUFFER;
+use crate::domain::{AppError, DomainEvent, EntityRef};
+use crate::presentation::middleware::AuthenticatedUser;
+use crate::presentation::payload::Query;
+
+/// Query of the editing WebSocket. Browsers cannot set headers on
+/// WebSocket requests, so the token comes in the URL.
+#[derive(Debug, Deserialize)]
+pub struct EditingSocketQuery {
+    pub token: String,
+}
+
+/// Marks the post as open in the caller's editor and returns everyone
+/// editing it (author only).
+pub async fn editing_heartbeat(
+    auth: AuthenticatedUser,
+    blog_service: web::Data<BlogService>,
+    service: web::Data<PresenceService>,
+    path: web::Path<EntityRef>,
+) -> Result<impl Responder, AppError> {
+    let id = blog_service.resolve_post(&path).await?;
+    let presence = service.heartbeat(id, auth.user_id).await?;
+    Ok(HttpResponse::Ok().json(presence))
+}
+
+/// Marks the post as closed in the caller's editor.
+pub async fn stop_editing(
+    auth: AuthenticatedUser,
+    blog_service: web::Data<BlogService>,
+    service: web::Data<PresenceService>,
+    path: web::Path<EntityRef>,
+) -> Result<impl Responder, AppError> {
+    let id = blog_service.resolve_post(&path).await?;
+    service.stop_editing(id, auth.user_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Opens a WebSocket that sends the post's `EditingPresenceDto` as a JSON
+/// text frame now and whenever its editors change (author only).
+pub async fn editing_socket(
+    req: HttpRequest,
+    payload: web::Payload,
+    auth_service: web::Data<AuthService>,
+    blog_service: web::Data<BlogService>,
+    service: web::Data<PresenceService>,
+    path: web::Path<EntityRef>,
+    query: Query<EditingSocketQuery>,
+) -> Result<HttpResponse, AppError> {
+    let user_id = auth_service.authenticate(&query.token).await?.sub;
+    let id = blog_service.resolve_post(&path).await?;
+    ws::verify_handshake(req.head()).map_err(|e| AppError::Validation(e.to_string()))?;
+    let key = req
+        .headers()
+        .get(header::SEC_WEBSOCKET_KEY)
+        .map(|key| ws::hash_key(key.as_bytes()))
+        .ok_or_else(|| AppError::Validation("Missing WebSocket key".into()))?;
+
+    let events = service.subscribe();
+    let presence = service.presence(id, user_id).await?;
+    let (tx, rx) = mpsc::channel(EDITING_SOCKET_BUFFER);
+    actix_web::rt::spawn(relay(id, presence, events, payload, tx));
+
+    Ok(HttpResponse::SwitchingProtocols()
+        .upgrade("websocket")
+        .insert_header((header::SEC_WEBSOCKET_ACCEPT, &key[..]))
+        .streaming(ReceiverStream::new(rx).map(Ok::<_, Infallible>)))
+}
+
+/// Sends `presence`, then every presence change of post `id`, until the
+/// client closes the socket or goes away. Pings are answered; other client
+/// messages are ignored.
+async fn relay(
+    id: i64,
+    presence: EditingPresenceDto,
+    mut events: tokio::sync::broadcast::Receiver<DomainEvent>,
+    mut payload: web::Payload,
+    tx: mpsc::Sender<Bytes>,
+) {
+    let mut codec = ws::Codec::new();
+    let mut inbound = BytesMut::new();
+    if send(&mut codec, &tx, presence_message(&presence))
+        .await
+        .is_err()
+    {
+        return;
+    }
+
+    loop {
+        let message = tokio::select! {
+            event = events.recv() => match event {
+                Ok(DomainEvent::EditingChanged { post_id, presence }) if post_id == id => {
+                    presence_message(&presence)
+                }
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(post_id = id, skipped, "Editing socket fell behind");
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            },
+            chunk = payload.next() => match chunk {
+                Some(Ok(chunk)) => {
+                    inbound.extend_from_slice(&chunk);
+                    match reply(&mut codec, &mut inbound) {
+                        Ok(Some(message)) => {
+                            let close = matches!(message, ws::Message::Close(_));
+                            if send(&mut codec, &tx, message).await.is_err() || close {
+                                break;
+                            }
+                            continue;
+                        }
+                        Ok(None) => continue,
+                        Err(()) => break,
+                    }
+                }
+                Some(Err(_)) | None => break,
+            },
+            () = tx.closed() => break,
+        };
+        if send(&mut codec, &tx, message).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Decodes buffered client frames, returning the answer to the last ping
+/// or close among them. `Err` on a protocol error.
+fn reply(codec: &mut ws::Codec, inbound: &mut BytesMut) -> Result<Option<ws::Message>, ()> {
+    let mut answer = None;
+    while let Some(frame) = codec.decode(inbound).map_err(|_| ())? {
+        match frame {
+            ws::Frame::Ping(data) => answer = Some(ws::Message::Pong(data)),
+            ws::Frame::Close(reason) => return Ok(Some(ws::Message::Close(reason))),
+            _ => {}
+        }
+    }
+    Ok(answer)
+}
+
+/// Text frame carrying a presence as JSON.
+fn presence_message(presence: &EditingPresenceDto) -> ws::Message {
+    let json = serde_json::to_string(presence).unwrap_or_default();
+    ws::Message::Text(json.into())
+}
+
+/// Encodes and queues a frame for the client; `Err` once it is gone.
+async fn send(
+    codec: &mut ws::Codec,
+    tx: &mpsc::Sender<Bytes>,
+    message: ws::Message,
+) -> Result<(), ()> {
+    let mut frame = BytesMut::new();
+    codec.encode(message, &mut frame).map_err(|_| ())?;
+    tx.send(frame.freeze()).await.map_err(|_| ())
+}
@@ -0,0 +1,81 @@
+//! HTTP handlers for A/B experiments: exposure logging (requires auth) and
+//! their admin management (nested under `/admin`).
+//!
+//! Users learn their variants from `GET /api/features`.
+
+use actix_web::{HttpResponse, Responder, Scope, delete, get, post, put, web};
+use blog_shared::{CreateExperimentRequest, UpdateExperimentRequest};
+
+use crate::application::ExperimentService;
+use crate::domain::AppError;
+use crate::presentation::middleware::{AdminUser, AuthenticatedUser};
+use crate::presentation::payload::Json;
+
+/// Creates the experiment routes for users.
+pub fn experiment_routes() -> Scope {
+    web::scope("/experiments").service(record_exposure)
+}
+
+/// Creates the experiment management routes.
+pub fn experiment_admin_routes() -> Scope {
+    web::scope("/experiments")
+        .service(list_experiments)
+        .service(create_experiment)
+        .service(update_experiment)
+        .service(delete_experiment)
+}
+
+/// Records that the current user was shown their variant of an experiment.
+#[post("/{name}/exposures")]
+async fn record_exposure(
+    auth: AuthenticatedUser,
+    service: web::Data<ExperimentService>,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    service.record_exposure(auth.user_id, &path).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Lists all experiments with their exposure counts.
+#[get("")]
+async fn list_experiments(
+    _admin: AdminUser,
+    service: web::Data<ExperimentService>,
+) -> Result<impl Responder, AppError> {
+    let experiments = service.list().await?;
+    Ok(HttpResponse::Ok().json(experiments))
+}
+
+/// Creates an experiment.
+#[post("")]
+async fn create_experiment(
+    _admin: AdminUser,
+    service: web::Data<ExperimentService>,
+    payload: Json<CreateExperimentRequest>,
+) -> Result<impl Responder, AppError> {
+    let experiment = service.create(payload.into_inner()).await?;
+    Ok(HttpResponse::Created().json(experiment))
+}
+
+/// Changes an experiment's description, switch or variants.
+#[put("/{name}")]
+async fn update_experiment(
+    _admin: AdminUser,
+    service: web::Data<ExperimentService>,
+    path: web::Path<String>,
+    payload: Json<UpdateExperimentRequest>,
+) -> Result<impl Responder, AppError> {
+    let experiment = service.update(&path, payload.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(experiment))
+}
+
+/// Deletes an experiment and its exposures.
+#[delete("/{name}")]
+async fn delete_experiment(
+    _admin: AdminUser,
+    service: web::Data<ExperimentService>,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    service.delete(&path).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
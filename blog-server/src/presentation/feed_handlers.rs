@@ -0,0 +1,52 @@
+//! HTTP handlers for the RSS and Atom feeds.
+
+use actix_web::http::header;
+use actix_web::{HttpResponse, Responder, get, web};
+
+use crate::application::FeedService;
+use crate::constants::{ATOM_CONTENT_TYPE, RSS_CONTENT_TYPE};
+use crate::domain::AppError;
+
+/// RSS feed of the newest posts (public).
+#[get("/feed.rss")]
+pub async fn rss_feed(service: web::Data<FeedService>) -> Result<impl Responder, AppError> {
+    let body = service.rss().await?;
+    let urls = service.urls();
+    Ok(feed_response(
+        RSS_CONTENT_TYPE,
+        &urls.rss,
+        urls.hub.as_deref(),
+        body,
+    ))
+}
+
+/// Atom feed of the newest posts (public).
+#[get("/feed.atom")]
+pub async fn atom_feed(service: web::Data<FeedService>) -> Result<impl Responder, AppError> {
+    let body = service.atom().await?;
+    let urls = service.urls();
+    Ok(feed_response(
+        ATOM_CONTENT_TYPE,
+        &urls.atom,
+        urls.hub.as_deref(),
+        body,
+    ))
+}
+
+/// Builds a feed response with WebSub discovery `Link` headers.
+fn feed_response(
+    content_type: &str,
+    self_url: &str,
+    hub: Option<&str>,
+    body: String,
+) -> HttpResponse {
+    let mut links = format!("<{self_url}>; rel=\"self\"");
+    if let Some(hub) = hub {
+        links.push_str(&format!(", <{hub}>; rel=\"hub\""));
+    }
+
+    HttpResponse::Ok()
+        .content_type(content_type)
+        .insert_header((header::LINK, links))
+        .body(body)
+}
@@ -1,10 +1,28 @@
 //! gRPC service implementations.
 
-use tonic::{Request, Response, Status};
-
-use crate::application::{AuthService, BlogService};
-use crate::constants::{DEFAULT_LIMIT, DEFAULT_OFFSET};
-use crate::infrastructure::jwt;
+use blog_shared::constants::{AUTH_METADATA_KEY, MAX_IMPORT_POSTS};
+use blog_shared::{CursorPage, Pagination, Role};
+use tonic::metadata::MetadataMap;
+use tonic::{Request, Response, Status, Streaming};
+
+use crate::application::{
+    AuthService, BlogService, CommentService, ImportService, LinkCheckService, NotificationService,
+    QuotaService,
+};
+use crate::domain::{EntityRef, normalize_tags};
+use crate::infrastructure::event_bus::EventBus;
+use crate::infrastructure::jwt::{Claims, TokenType};
+use crate::presentation::http_handlers::validate_comment_content;
+
+mod comment_stream;
+mod convert;
+
+use comment_stream::{CommentStream, CommentSubscription};
+use convert::{
+    app_error_to_status, comment_dto_to_proto, import_post_from_proto, import_response_to_proto,
+    notification_list_to_proto, post_dto_to_proto, revision_diff_to_proto, revision_list_to_proto,
+    trash_list_to_proto, user_dto_to_proto,
+};
 
 /// Generated protobuf types and service traits.
 pub mod proto {
@@ -36,23 +54,26 @@ impl GrpcAuthServiceTrait for GrpcAuthService {
         &self,
         request: Request<proto::RegisterRequest>,
     ) -> Result<Response<proto::AuthResponse>, Status> {
+        let user_agent = user_agent(&request);
         let req = request.into_inner();
 
         let shared_req = blog_shared::RegisterRequest {
             username: req.username,
             email: req.email,
             password: req.password,
+            invite_code: req.invite_code,
         };
 
         let result = self
             .auth_service
-            .register(shared_req)
+            .register(shared_req, user_agent.as_deref())
             .await
             .map_err(app_error_to_status)?;
 
         Ok(Response::new(proto::AuthResponse {
             token: result.token,
             user: Some(user_dto_to_proto(&result.user)),
+            recovery_codes: result.recovery_codes,
         }))
     }
 
@@ -60,6 +81,7 @@ impl GrpcAuthServiceTrait for GrpcAuthService {
         &self,
         request: Request<proto::LoginRequest>,
     ) -> Result<Response<proto::AuthResponse>, Status> {
+        let user_agent = user_agent(&request);
         let req = request.into_inner();
 
         let shared_req = blog_shared::LoginRequest {
@@ -69,13 +91,14 @@ impl GrpcAuthServiceTrait for GrpcAuthService {
 
         let result = self
             .auth_service
-            .login(shared_req)
+            .login(shared_req, user_agent.as_deref())
             .await
             .map_err(app_error_to_status)?;
 
         Ok(Response::new(proto::AuthResponse {
             token: result.token,
             user: Some(user_dto_to_proto(&result.user)),
+            recovery_codes: result.recovery_codes,
         }))
     }
 }
@@ -87,23 +110,142 @@ impl GrpcAuthServiceTrait for GrpcAuthService {
 /// gRPC implementation of BlogService.
 pub struct GrpcBlogService {
     blog_service: BlogService,
-    jwt_secret: String,
+    auth_service: AuthService,
+    link_check_service: LinkCheckService,
+    import_service: ImportService,
+    comment_service: CommentService,
+    notification_service: NotificationService,
+    events: EventBus,
+    /// Counts service token calls against their account's quotas; without
+    /// one (most tests), nothing is counted.
+    quota_service: Option<QuotaService>,
 }
 
 impl GrpcBlogService {
     /// Creates a new GrpcBlogService.
-    pub fn new(blog_service: BlogService, jwt_secret: String) -> Self {
+    pub fn new(
+        blog_service: BlogService,
+        auth_service: AuthService,
+        link_check_service: LinkCheckService,
+        import_service: ImportService,
+        comment_service: CommentService,
+        notification_service: NotificationService,
+        events: EventBus,
+    ) -> Self {
         Self {
             blog_service,
-            jwt_secret,
+            auth_service,
+            link_check_service,
+            import_service,
+            comment_service,
+            notification_service,
+            events,
+            quota_service: None,
+        }
+    }
+
+    /// Counts service token calls against their account's daily quotas.
+    pub fn with_quotas(mut self, quota_service: QuotaService) -> Self {
+        self.quota_service = Some(quota_service);
+        self
+    }
+
+    /// Validates a JWT token (and its session) and returns its claims; a
+    /// service token call counts as a request against its quotas.
+    async fn validate_token(&self, token: &str) -> Result<Claims, Status> {
+        let claims = self.authenticate(token).await?;
+        self.count_call(&claims, false).await?;
+        Ok(claims)
+    }
+
+    /// Validates a JWT token (and its session) without counting the call.
+    async fn authenticate(&self, token: &str) -> Result<Claims, Status> {
+        self.auth_service
+            .authenticate(token)
+            .await
+            .map_err(|_| Status::unauthenticated("Invalid token"))
+    }
+
+    /// Records a service token call against its account's quotas, failing
+    /// with `RESOURCE_EXHAUSTED` once a limit is reached. Other tokens are
+    /// not counted.
+    async fn count_call(&self, claims: &Claims, is_write: bool) -> Result<(), Status> {
+        let Some(account_id) = quota_account(claims) else {
+            return Ok(());
+        };
+        let Some(quota_service) = &self.quota_service else {
+            return Ok(());
+        };
+        quota_service
+            .record(account_id, is_write)
+            .await
+            .map(|_| ())
+            .map_err(app_error_to_status)
+    }
+
+    /// Returns the caller's current role, or `User` for service tokens, which
+    /// never get a role's powers whoever they act as.
+    async fn role(&self, claims: &Claims) -> Result<Role, Status> {
+        if claims.typ == TokenType::Service {
+            return Ok(Role::User);
+        }
+        self.auth_service
+            .role(claims.sub)
+            .await
+            .map_err(app_error_to_status)
+    }
+
+    /// Validates a token that may change data and returns the user ID;
+    /// read-only service tokens are refused.
+    async fn validate_write_token(&self, token: &str) -> Result<i64, Status> {
+        Ok(self.validate_write_claims(token).await?.sub)
+    }
+
+    /// Like `validate_write_token`, but returns the claims for calls that
+    /// also need the caller's role.
+    async fn validate_write_claims(&self, token: &str) -> Result<Claims, Status> {
+        let claims = self.authenticate(token).await?;
+        if !claims.can_write() {
+            return Err(Status::permission_denied("Token is read-only"));
         }
+        self.count_call(&claims, true).await?;
+        Ok(claims)
     }
 
-    /// Validates a JWT token and returns the user ID.
-    fn validate_token(&self, token: &str) -> Result<i64, Status> {
-        let claims = jwt::validate_token(token, &self.jwt_secret)
-            .map_err(|_| Status::unauthenticated("Invalid token"))?;
-        Ok(claims.sub)
+    /// Resolves the post a request names: by `public_id`, or by its numeric
+    /// `id` from clients that predate public IDs.
+    async fn post_id(&self, id: i64, public_id: String) -> Result<i64, Status> {
+        let post = if public_id.is_empty() {
+            EntityRef::Id(id)
+        } else {
+            EntityRef::Public(public_id)
+        };
+        self.blog_service
+            .resolve_post(&post)
+            .await
+            .map_err(app_error_to_status)
+    }
+
+    /// Resolves the post a request open to anonymous callers names, which
+    /// only goes by public ID.
+    async fn public_post_id(&self, public_id: String) -> Result<i64, Status> {
+        self.blog_service
+            .resolve_post(&EntityRef::Public(public_id))
+            .await
+            .map_err(app_error_to_status)
+    }
+
+    /// Returns the user ID from an optional `authorization: Bearer` metadata
+    /// entry; a missing or invalid token means an anonymous viewer.
+    async fn optional_viewer(&self, metadata: &MetadataMap) -> Option<i64> {
+        let token = metadata
+            .get(AUTH_METADATA_KEY)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))?;
+        self.validate_token(token)
+            .await
+            .ok()
+            .map(|claims| claims.sub)
     }
 }
 
@@ -114,11 +256,16 @@ impl GrpcBlogServiceTrait for GrpcBlogService {
         request: Request<proto::CreatePostRequest>,
     ) -> Result<Response<proto::PostResponse>, Status> {
         let req = request.into_inner();
-        let user_id = self.validate_token(&req.token)?;
+        let user_id = self.validate_write_token(&req.token).await?;
 
         let shared_req = blog_shared::CreatePostRequest {
             title: req.title,
             content: req.content,
+            tags: normalize_tags(req.tags).map_err(app_error_to_status)?,
+            lang: req.lang,
+            canonical_url: req.canonical_url,
+            original_source: req.original_source,
+            org: req.org,
         };
 
         let post = self
@@ -136,11 +283,13 @@ impl GrpcBlogServiceTrait for GrpcBlogService {
         &self,
         request: Request<proto::GetPostRequest>,
     ) -> Result<Response<proto::PostResponse>, Status> {
+        let viewer_id = self.optional_viewer(request.metadata()).await;
         let req = request.into_inner();
+        let id = self.public_post_id(req.public_id).await?;
 
         let post = self
             .blog_service
-            .get_post(req.id)
+            .get_post(id, viewer_id)
             .await
             .map_err(app_error_to_status)?;
 
@@ -153,21 +302,13 @@ impl GrpcBlogServiceTrait for GrpcBlogService {
         &self,
         request: Request<proto::ListPostsRequest>,
     ) -> Result<Response<proto::ListPostsResponse>, Status> {
+        let viewer_id = self.optional_viewer(request.metadata()).await;
         let req = request.into_inner();
-        let limit = if req.limit > 0 {
-            req.limit
-        } else {
-            DEFAULT_LIMIT
-        };
-        let offset = if req.offset >= 0 {
-            req.offset
-        } else {
-            DEFAULT_OFFSET
-        };
+        let Pagination { limit, offset } = Pagination::clamp(Some(req.limit), Some(req.offset));
 
         let result = self
             .blog_service
-            .list_posts(limit, offset)
+            .list_posts(limit, offset, viewer_id, req.lang.as_deref())
             .await
             .map_err(app_error_to_status)?;
 
@@ -184,16 +325,25 @@ impl GrpcBlogServiceTrait for GrpcBlogService {
         request: Request<proto::UpdatePostRequest>,
     ) -> Result<Response<proto::PostResponse>, Status> {
         let req = request.into_inner();
-        let user_id = self.validate_token(&req.token)?;
+        let user_id = self.validate_write_token(&req.token).await?;
+        let id = self.post_id(req.id, req.public_id).await?;
 
+        let tags = req
+            .tags
+            .map(|list| normalize_tags(list.tags))
+            .transpose()
+            .map_err(app_error_to_status)?;
         let shared_req = blog_shared::UpdatePostRequest {
             title: req.title,
             content: req.content,
+            tags,
+            canonical_url: req.canonical_url,
+            original_source: req.original_source,
         };
 
         let post = self
             .blog_service
-            .update_post(req.id, user_id, shared_req)
+            .update_post(id, user_id, shared_req)
             .await
             .map_err(app_error_to_status)?;
 
@@ -207,55 +357,324 @@ impl GrpcBlogServiceTrait for GrpcBlogService {
         request: Request<proto::DeletePostRequest>,
     ) -> Result<Response<proto::Empty>, Status> {
         let req = request.into_inner();
-        let user_id = self.validate_token(&req.token)?;
+        let user_id = self.validate_write_token(&req.token).await?;
+        let id = self.post_id(req.id, req.public_id).await?;
 
         self.blog_service
-            .delete_post(req.id, user_id)
+            .delete_post(id, user_id)
             .await
             .map_err(app_error_to_status)?;
 
         Ok(Response::new(proto::Empty {}))
     }
-}
 
-// ============================================================================
-// Conversion Helpers
-// ============================================================================
+    async fn list_trash(
+        &self,
+        request: Request<proto::ListTrashRequest>,
+    ) -> Result<Response<proto::ListTrashResponse>, Status> {
+        let req = request.into_inner();
+        let user_id = self.validate_token(&req.token).await?.sub;
+
+        let result = self
+            .blog_service
+            .list_trash(user_id)
+            .await
+            .map_err(app_error_to_status)?;
+
+        Ok(Response::new(trash_list_to_proto(result)))
+    }
+
+    async fn restore_post(
+        &self,
+        request: Request<proto::RestorePostRequest>,
+    ) -> Result<Response<proto::Empty>, Status> {
+        let req = request.into_inner();
+        let user_id = self.validate_write_token(&req.token).await?;
+        let id = self.post_id(req.id, req.public_id).await?;
+
+        self.blog_service
+            .restore_post(id, user_id)
+            .await
+            .map_err(app_error_to_status)?;
+
+        Ok(Response::new(proto::Empty {}))
+    }
+
+    async fn list_broken_links(
+        &self,
+        request: Request<proto::ListBrokenLinksRequest>,
+    ) -> Result<Response<proto::ListBrokenLinksResponse>, Status> {
+        let req = request.into_inner();
+        let user_id = self.validate_token(&req.token).await?.sub;
 
-/// Converts AppError to gRPC Status.
-fn app_error_to_status(err: crate::domain::AppError) -> Status {
-    use crate::domain::AppError;
+        let result = self
+            .link_check_service
+            .broken_links(user_id)
+            .await
+            .map_err(app_error_to_status)?;
 
-    match err {
-        AppError::UserNotFound | AppError::PostNotFound => Status::not_found(err.to_string()),
-        AppError::InvalidCredentials => Status::unauthenticated(err.to_string()),
-        AppError::Forbidden => Status::permission_denied(err.to_string()),
-        AppError::UsernameExists | AppError::EmailExists | AppError::Validation(_) => {
-            Status::invalid_argument(err.to_string())
+        let links = result
+            .links
+            .iter()
+            .map(|link| proto::BrokenLink {
+                post_id: link.post_id,
+                post_public_id: link.post_public_id.clone(),
+                post_title: link.post_title.clone(),
+                url: link.url.clone(),
+                status_code: link.status_code.map(u32::from),
+                error: link.error.clone(),
+                checked_at: link.checked_at.to_rfc3339(),
+            })
+            .collect();
+
+        Ok(Response::new(proto::ListBrokenLinksResponse { links }))
+    }
+
+    async fn import_posts(
+        &self,
+        request: Request<proto::ImportPostsRequest>,
+    ) -> Result<Response<proto::ImportPostsResponse>, Status> {
+        let req = request.into_inner();
+        let claims = self.validate_write_claims(&req.token).await?;
+        let role = self.role(&claims).await?;
+
+        if req.posts.is_empty() || req.posts.len() > MAX_IMPORT_POSTS {
+            return Err(Status::invalid_argument(format!(
+                "An import must contain 1 to {MAX_IMPORT_POSTS} posts"
+            )));
         }
-        _ => Status::internal("Internal server error"),
+        let shared_req = blog_shared::ImportPostsRequest {
+            posts: req
+                .posts
+                .into_iter()
+                .map(import_post_from_proto)
+                .collect::<Result<_, _>>()?,
+            dry_run: req.dry_run,
+        };
+
+        let result = self
+            .import_service
+            .import_posts(claims.sub, role, shared_req)
+            .await
+            .map_err(app_error_to_status)?;
+
+        Ok(Response::new(import_response_to_proto(result)))
+    }
+
+    async fn list_revisions(
+        &self,
+        request: Request<proto::ListRevisionsRequest>,
+    ) -> Result<Response<proto::ListRevisionsResponse>, Status> {
+        let req = request.into_inner();
+        let claims = self.validate_token(&req.token).await?;
+        let user_id = claims.sub;
+        let role = self.role(&claims).await?;
+        let post_id = self.post_id(req.post_id, req.post_public_id).await?;
+
+        let result = self
+            .blog_service
+            .list_revisions(post_id, user_id, role)
+            .await
+            .map_err(app_error_to_status)?;
+
+        Ok(Response::new(revision_list_to_proto(result)))
+    }
+
+    async fn diff_revisions(
+        &self,
+        request: Request<proto::DiffRevisionsRequest>,
+    ) -> Result<Response<proto::DiffRevisionsResponse>, Status> {
+        let req = request.into_inner();
+        let claims = self.validate_token(&req.token).await?;
+        let user_id = claims.sub;
+        let role = self.role(&claims).await?;
+        let post_id = self.post_id(req.post_id, req.post_public_id).await?;
+
+        let result = self
+            .blog_service
+            .diff_revisions(post_id, user_id, role, req.from, req.to)
+            .await
+            .map_err(app_error_to_status)?;
+
+        Ok(Response::new(revision_diff_to_proto(result)))
+    }
+
+    async fn create_comment(
+        &self,
+        request: Request<proto::CreateCommentRequest>,
+    ) -> Result<Response<proto::CommentResponse>, Status> {
+        let req = request.into_inner();
+        let user_id = self.validate_write_token(&req.token).await?;
+        validate_comment_content(&req.content).map_err(app_error_to_status)?;
+        let post_id = self.post_id(req.post_id, req.post_public_id).await?;
+
+        let comment = self
+            .comment_service
+            .create_comment(
+                post_id,
+                user_id,
+                blog_shared::CreateCommentRequest {
+                    content: req.content,
+                },
+            )
+            .await
+            .map_err(app_error_to_status)?;
+
+        Ok(Response::new(proto::CommentResponse {
+            comment: Some(comment_dto_to_proto(&comment)),
+        }))
+    }
+
+    async fn list_comments(
+        &self,
+        request: Request<proto::ListCommentsRequest>,
+    ) -> Result<Response<proto::ListCommentsResponse>, Status> {
+        let viewer_id = self.optional_viewer(request.metadata()).await;
+        let req = request.into_inner();
+        let post_id = self.public_post_id(req.post_public_id).await?;
+
+        // Offsets are only read from older clients that send no cursor
+        let result = if req.cursor.is_empty() && req.offset > 0 {
+            let Pagination { limit, offset } = Pagination::clamp(Some(req.limit), Some(req.offset));
+            self.comment_service
+                .list_comments(post_id, limit, offset, viewer_id)
+                .await
+        } else {
+            let page = CursorPage::clamp(Some(req.limit), Some(req.cursor));
+            self.comment_service
+                .list_comments_after(post_id, &page, viewer_id)
+                .await
+        }
+        .map_err(app_error_to_status)?;
+
+        Ok(Response::new(proto::ListCommentsResponse {
+            comments: result.comments.iter().map(comment_dto_to_proto).collect(),
+            total: result.total.unwrap_or_default(),
+            next_cursor: result.next_cursor.unwrap_or_default(),
+        }))
+    }
+
+    async fn delete_comment(
+        &self,
+        request: Request<proto::DeleteCommentRequest>,
+    ) -> Result<Response<proto::Empty>, Status> {
+        let req = request.into_inner();
+        let user_id = self.validate_write_token(&req.token).await?;
+
+        self.comment_service
+            .delete_comment(req.id, user_id)
+            .await
+            .map_err(app_error_to_status)?;
+
+        Ok(Response::new(proto::Empty {}))
     }
-}
 
-/// Converts UserDto to proto User.
-fn user_dto_to_proto(user: &blog_shared::UserDto) -> proto::User {
-    proto::User {
-        id: user.id,
-        username: user.username.clone(),
-        email: user.email.clone(),
-        created_at: user.created_at.to_rfc3339(),
+    async fn list_notifications(
+        &self,
+        request: Request<proto::ListNotificationsRequest>,
+    ) -> Result<Response<proto::ListNotificationsResponse>, Status> {
+        let req = request.into_inner();
+        let user_id = self.validate_token(&req.token).await?.sub;
+        let page = CursorPage::clamp(Some(req.limit), Some(req.cursor));
+
+        let result = self
+            .notification_service
+            .list(user_id, &page)
+            .await
+            .map_err(app_error_to_status)?;
+
+        Ok(Response::new(notification_list_to_proto(result)))
+    }
+
+    async fn count_unread_notifications(
+        &self,
+        request: Request<proto::CountUnreadNotificationsRequest>,
+    ) -> Result<Response<proto::UnreadCountResponse>, Status> {
+        let req = request.into_inner();
+        let user_id = self.validate_token(&req.token).await?.sub;
+
+        let result = self
+            .notification_service
+            .unread_count(user_id)
+            .await
+            .map_err(app_error_to_status)?;
+
+        Ok(Response::new(proto::UnreadCountResponse {
+            unread: result.unread,
+        }))
+    }
+
+    async fn mark_notifications_read(
+        &self,
+        request: Request<proto::MarkNotificationsReadRequest>,
+    ) -> Result<Response<proto::Empty>, Status> {
+        let req = request.into_inner();
+        let user_id = self.validate_write_token(&req.token).await?;
+
+        self.notification_service
+            .mark_read(user_id, req.up_to_id)
+            .await
+            .map_err(app_error_to_status)?;
+
+        Ok(Response::new(proto::Empty {}))
+    }
+
+    type StreamCommentsStream = CommentStream;
+
+    async fn stream_comments(
+        &self,
+        request: Request<Streaming<proto::CommentStreamRequest>>,
+    ) -> Result<Response<Self::StreamCommentsStream>, Status> {
+        let mut inbound = request.into_inner();
+        let first = inbound
+            .message()
+            .await?
+            .ok_or_else(|| Status::invalid_argument("Expected a message naming the post"))?;
+        let claims = match first.token.as_str() {
+            "" => None,
+            token => Some(self.validate_token(token).await?),
+        };
+        let user_id = claims.as_ref().map(|claims| claims.sub);
+        let post_id = self.public_post_id(first.post_public_id).await?;
+
+        self.comment_service
+            .ensure_post_exists(post_id, user_id)
+            .await
+            .map_err(app_error_to_status)?;
+
+        let subscription = CommentSubscription {
+            comment_service: self.comment_service.clone(),
+            events: self.events.subscribe(),
+            post_id,
+            user_id,
+            can_write: claims.as_ref().is_some_and(Claims::can_write),
+            quota: self
+                .quota_service
+                .clone()
+                .zip(claims.as_ref().and_then(quota_account)),
+        };
+        Ok(Response::new(subscription.open(first.content, inbound)))
     }
 }
 
-/// Converts PostDto to proto Post.
-fn post_dto_to_proto(post: &blog_shared::PostDto) -> proto::Post {
-    proto::Post {
-        id: post.id,
-        title: post.title.clone(),
-        content: post.content.clone(),
-        author_id: post.author_id,
-        author_username: post.author_username.clone(),
-        created_at: post.created_at.to_rfc3339(),
-        updated_at: post.updated_at.to_rfc3339(),
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Returns the service account a token's calls count against, if it is a
+/// service token.
+fn quota_account(claims: &Claims) -> Option<i64> {
+    if claims.typ != TokenType::Service {
+        return None;
     }
+    claims.sid.as_deref().and_then(|sid| sid.parse().ok())
+}
+
+/// Returns the client's `user-agent` metadata, recorded with new sessions.
+fn user_agent<T>(request: &Request<T>) -> Option<String> {
+    request
+        .metadata()
+        .get("user-agent")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
 }
@@ -0,0 +1,155 @@
+//! Live comment stream behind `StreamComments`.
+
+use std::pin::Pin;
+
+use blog_shared::{CommentDto, CreateCommentRequest};
+use tokio::sync::broadcast::Receiver;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::{Stream, StreamExt};
+use tonic::{Status, Streaming};
+use tracing::warn;
+
+use super::convert::{app_error_to_status, comment_dto_to_proto};
+use super::proto;
+use super::proto::comment_stream_response::Event;
+use crate::application::{CommentService, QuotaService};
+use crate::constants::COMMENT_STREAM_BUFFER;
+use crate::domain::{DomainEvent, policy};
+use crate::presentation::http_handlers::validate_comment_content;
+
+/// Messages sent to a `StreamComments` client.
+pub(super) type CommentStream =
+    Pin<Box<dyn Stream<Item = Result<proto::CommentStreamResponse, Status>> + Send>>;
+
+/// One client's subscription to the comments of a post.
+pub(super) struct CommentSubscription {
+    pub(super) comment_service: CommentService,
+    pub(super) events: Receiver<DomainEvent>,
+    pub(super) post_id: i64,
+    /// The signed-in client, if any; only they may post comments.
+    pub(super) user_id: Option<i64>,
+    /// False for read-only service tokens, which may not post.
+    pub(super) can_write: bool,
+    /// For service tokens, the quotas and account each posted comment counts
+    /// against as a write.
+    pub(super) quota: Option<(QuotaService, i64)>,
+}
+
+impl CommentSubscription {
+    /// Starts relaying new comments to the client and posting the comments
+    /// it sends, beginning with `content` from its first message.
+    ///
+    /// The subscription ends when the client goes away; a client that stops
+    /// sending keeps receiving.
+    pub(super) fn open(
+        self,
+        content: String,
+        inbound: Streaming<proto::CommentStreamRequest>,
+    ) -> CommentStream {
+        let (tx, rx) = mpsc::channel(COMMENT_STREAM_BUFFER);
+        tokio::spawn(self.run(content, inbound, tx));
+        Box::pin(ReceiverStream::new(rx))
+    }
+
+    async fn run(
+        mut self,
+        content: String,
+        mut inbound: Streaming<proto::CommentStreamRequest>,
+        tx: mpsc::Sender<Result<proto::CommentStreamResponse, Status>>,
+    ) {
+        if let Some(error) = self.post(content).await
+            && send(&tx, Event::Error(error)).await.is_err()
+        {
+            return;
+        }
+
+        let mut receiving = true;
+        loop {
+            let event = tokio::select! {
+                event = self.events.recv() => match event {
+                    Ok(DomainEvent::CommentCreated {
+                        mut comment,
+                        post_id,
+                        author_id,
+                        hidden,
+                    }) => {
+                        // Lost on the way through a relay between replicas
+                        (comment.post_id, comment.author_id) = (post_id, author_id);
+                        if !self.sees(&comment, hidden) {
+                            continue;
+                        }
+                        Event::Comment(comment_dto_to_proto(&comment))
+                    }
+                    Ok(DomainEvent::EditingChanged { .. } | DomainEvent::PostChanged { .. }) => continue,
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(post_id = self.post_id, skipped, "Comment stream fell behind");
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                message = inbound.next(), if receiving => match message {
+                    Some(Ok(message)) => match self.post(message.content).await {
+                        Some(error) => Event::Error(error),
+                        None => continue,
+                    },
+                    Some(Err(_)) | None => {
+                        receiving = false;
+                        continue;
+                    }
+                },
+                () = tx.closed() => break,
+            };
+            if send(&tx, event).await.is_err() {
+                break;
+            }
+        }
+    }
+
+    /// Posts a comment from the client. Returns why it was rejected, if it
+    /// was; empty content posts nothing.
+    async fn post(&self, content: String) -> Option<String> {
+        if content.is_empty() {
+            return None;
+        }
+        let Some(user_id) = self.user_id else {
+            return Some("Sign in to comment".to_string());
+        };
+        if !self.can_write {
+            return Some("Token is read-only".to_string());
+        }
+        if let Some((quota_service, account_id)) = &self.quota
+            && let Err(e) = quota_service.record(*account_id, true).await
+        {
+            return Some(app_error_to_status(e).message().to_string());
+        }
+        let result = match validate_comment_content(&content) {
+            Ok(()) => {
+                self.comment_service
+                    .create_comment(self.post_id, user_id, CreateCommentRequest { content })
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        result
+            .err()
+            .map(|e| app_error_to_status(e).message().to_string())
+    }
+
+    /// Returns true if the comment belongs on this stream: it is on the post,
+    /// and a shadow-banned author's comment only reaches its author.
+    fn sees(&self, comment: &CommentDto, hidden: bool) -> bool {
+        comment.post_id == self.post_id && policy::can_see(self.user_id, comment, hidden)
+    }
+}
+
+/// Sends an event to the client; fails once the client has gone away.
+async fn send(
+    tx: &mpsc::Sender<Result<proto::CommentStreamResponse, Status>>,
+    event: Event,
+) -> Result<(), ()> {
+    tx.send(Ok(proto::CommentStreamResponse { event: Some(event) }))
+        .await
+        .map_err(|_| ())
+}
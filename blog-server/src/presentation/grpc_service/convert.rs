@@ -0,0 +1,254 @@
+//! Conversions between gRPC messages and application types.
+
+use blog_shared::constants::ERROR_CODE_METADATA_KEY;
+use chrono::DateTime;
+use tonic::Status;
+use tonic::metadata::MetadataValue;
+
+use super::proto;
+use crate::domain::AppError;
+
+/// Converts AppError to gRPC Status, with the error's code in the
+/// `ERROR_CODE_METADATA_KEY` metadata.
+pub(super) fn app_error_to_status(err: AppError) -> Status {
+    let code = err.code();
+    let mut status = match err {
+        AppError::UserNotFound
+        | AppError::PostNotFound
+        | AppError::CommentNotFound
+        | AppError::SessionNotFound
+        | AppError::FeatureFlagNotFound
+        | AppError::ConversationNotFound
+        | AppError::ModerationRuleNotFound
+        | AppError::ContentFlagNotFound
+        | AppError::SubscriptionNotFound
+        | AppError::IntegrationNotFound
+        | AppError::UploadNotFound
+        | AppError::ServiceAccountNotFound
+        | AppError::RevisionNotFound
+        | AppError::TagNotFound
+        | AppError::TransferNotFound
+        | AppError::OrgNotFound
+        | AppError::ExperimentNotFound => Status::not_found(err.to_string()),
+        AppError::InvalidCredentials | AppError::InvalidSignature(_) => {
+            Status::unauthenticated(err.to_string())
+        }
+        AppError::Forbidden | AppError::AccountSuspended | AppError::FeatureDisabled(_) => {
+            Status::permission_denied(err.to_string())
+        }
+        AppError::UsernameExists
+        | AppError::EmailExists
+        | AppError::TagExists
+        | AppError::OrgExists
+        | AppError::ExperimentExists
+        | AppError::InvalidInvite
+        | AppError::InvalidEmailChange
+        | AppError::Validation(_)
+        | AppError::InvalidRequest { .. }
+        | AppError::ContentBlocked => Status::invalid_argument(err.to_string()),
+        AppError::QuotaExceeded { .. } => Status::resource_exhausted(err.to_string()),
+        _ => Status::internal("Internal server error"),
+    };
+    status
+        .metadata_mut()
+        .insert(ERROR_CODE_METADATA_KEY, MetadataValue::from_static(code));
+    status
+}
+
+/// Converts UserDto to proto User.
+pub(super) fn user_dto_to_proto(user: &blog_shared::UserDto) -> proto::User {
+    proto::User {
+        id: user.id,
+        username: user.username.clone(),
+        email: user.email.clone(),
+        created_at: user.created_at.to_rfc3339(),
+        role: user.role.to_string(),
+        public_id: user.public_id.clone(),
+    }
+}
+
+/// Converts PostDto to proto Post.
+pub(super) fn post_dto_to_proto(post: &blog_shared::PostDto) -> proto::Post {
+    proto::Post {
+        id: post.id,
+        public_id: post.public_id.clone(),
+        lang: post.lang.clone(),
+        translation_group_id: post.translation_group_id.clone(),
+        canonical_url: post.canonical_url.clone(),
+        original_source: post.original_source.clone(),
+        metadata: serde_json::to_string(&post.metadata).unwrap_or_default(),
+        status: post.status.to_string(),
+        title: post.title.clone(),
+        content: post.content.clone(),
+        content_html: post.content_html.clone(),
+        author_id: post.author_id,
+        author_public_id: post.author_public_id.clone(),
+        author_username: post.author_username.clone(),
+        created_at: post.created_at.to_rfc3339(),
+        updated_at: post.updated_at.to_rfc3339(),
+        tags: post.tags.clone(),
+        link_previews: post
+            .link_previews
+            .iter()
+            .map(|preview| proto::LinkPreview {
+                url: preview.url.clone(),
+                title: preview.title.clone(),
+                description: preview.description.clone(),
+                image_url: preview.image_url.clone(),
+                site_name: preview.site_name.clone(),
+            })
+            .collect(),
+        is_owner: post.is_owner,
+        org: match &post.author {
+            Some(blog_shared::PostAuthor::Org { id, slug, name }) => Some(proto::PostOrg {
+                id: *id,
+                slug: slug.clone(),
+                name: name.clone(),
+            }),
+            _ => None,
+        },
+    }
+}
+
+/// Converts CommentDto to proto Comment.
+pub(super) fn comment_dto_to_proto(comment: &blog_shared::CommentDto) -> proto::Comment {
+    proto::Comment {
+        id: comment.id,
+        post_id: comment.post_id,
+        author_id: comment.author_id,
+        post_public_id: comment.post_public_id.clone(),
+        author_public_id: comment.author_public_id.clone(),
+        author_username: comment.author_username.clone(),
+        content: comment.content.clone(),
+        created_at: comment.created_at.to_rfc3339(),
+        updated_at: comment.updated_at.to_rfc3339(),
+    }
+}
+
+/// Converts a proto ImportPost to ImportPostDto.
+pub(super) fn import_post_from_proto(
+    post: proto::ImportPost,
+) -> Result<blog_shared::ImportPostDto, Status> {
+    let published_at = post
+        .published_at
+        .map(|date| DateTime::parse_from_rfc3339(&date).map(|date| date.to_utc()))
+        .transpose()
+        .map_err(|_| Status::invalid_argument("Invalid published_at date"))?;
+
+    Ok(blog_shared::ImportPostDto {
+        title: post.title,
+        content: post.content,
+        tags: post.tags,
+        author: post.author,
+        published_at,
+    })
+}
+
+/// Converts ImportPostsResponse to proto ImportPostsResponse.
+pub(super) fn import_response_to_proto(
+    response: blog_shared::ImportPostsResponse,
+) -> proto::ImportPostsResponse {
+    proto::ImportPostsResponse {
+        dry_run: response.dry_run,
+        imported: response.imported,
+        failed: response.failed,
+        posts: response
+            .posts
+            .into_iter()
+            .map(|post| proto::ImportedPost {
+                title: post.title,
+                author_username: post.author_username,
+                post_id: post.post_id,
+                post_public_id: post.post_public_id,
+                error: post.error,
+            })
+            .collect(),
+    }
+}
+
+/// Converts a trash listing to its protobuf message.
+pub(super) fn trash_list_to_proto(
+    response: blog_shared::TrashListResponse,
+) -> proto::ListTrashResponse {
+    proto::ListTrashResponse {
+        posts: response
+            .posts
+            .into_iter()
+            .map(|post| proto::TrashedPost {
+                id: post.id,
+                public_id: post.public_id,
+                title: post.title,
+                deleted_at: post.deleted_at.to_rfc3339(),
+                purge_at: post.purge_at.map(|at| at.to_rfc3339()),
+                days_until_purge: post.days_until_purge,
+            })
+            .collect(),
+    }
+}
+
+/// Converts a page of notifications to its protobuf message.
+pub(super) fn notification_list_to_proto(
+    response: blog_shared::NotificationListResponse,
+) -> proto::ListNotificationsResponse {
+    proto::ListNotificationsResponse {
+        notifications: response
+            .notifications
+            .into_iter()
+            .map(|notification| proto::Notification {
+                id: notification.id,
+                kind: notification.kind.to_string(),
+                actor_id: notification.actor_id,
+                actor_public_id: notification.actor_public_id,
+                actor_username: notification.actor_username,
+                post_id: notification.post_id,
+                post_public_id: notification.post_public_id,
+                comment_id: notification.comment_id,
+                read: notification.read,
+                created_at: notification.created_at.to_rfc3339(),
+            })
+            .collect(),
+        next_cursor: response.next_cursor.unwrap_or_default(),
+    }
+}
+
+/// Converts a revision list to its protobuf message.
+pub(super) fn revision_list_to_proto(
+    response: blog_shared::RevisionListResponse,
+) -> proto::ListRevisionsResponse {
+    proto::ListRevisionsResponse {
+        revisions: response
+            .revisions
+            .into_iter()
+            .map(|revision| proto::Revision {
+                number: revision.number,
+                title: revision.title,
+                created_at: revision.created_at.to_rfc3339(),
+            })
+            .collect(),
+    }
+}
+
+/// Converts a revision diff to its protobuf message.
+pub(super) fn revision_diff_to_proto(
+    diff: blog_shared::RevisionDiffResponse,
+) -> proto::DiffRevisionsResponse {
+    let lines = |lines: Vec<blog_shared::DiffLineDto>| {
+        lines
+            .into_iter()
+            .map(|line| proto::DiffLine {
+                op: line.op.as_str().to_string(),
+                text: line.text,
+                old_line: line.old_line,
+                new_line: line.new_line,
+            })
+            .collect()
+    };
+    proto::DiffRevisionsResponse {
+        post_id: diff.post_id,
+        from: diff.from,
+        to: diff.to,
+        title: lines(diff.title),
+        content: lines(diff.content),
+        html: diff.html,
+    }
+}
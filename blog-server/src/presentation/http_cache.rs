@@ -0,0 +1,115 @@
+//! HTTP caching helpers: ETags, conditional requests, and `Allow` headers.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::{Instant, SystemTime};
+
+use actix_web::http::Method;
+use actix_web::http::header::{AUTHORIZATION, ContentType, VARY};
+use actix_web::http::header::{
+    Allow, CacheControl, CacheDirective, ETag, EntityTag, IfModifiedSince, IfNoneMatch,
+    LastModified,
+};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, web};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::domain::AppError;
+use crate::infrastructure::config::CacheConfig;
+use crate::infrastructure::metrics;
+
+/// Returns the [`CacheConfig`] from app data, or the defaults without it.
+pub fn cache_config(req: &HttpRequest) -> CacheConfig {
+    req.app_data::<web::Data<CacheConfig>>()
+        .map(|config| *config.get_ref())
+        .unwrap_or_default()
+}
+
+/// Serializes `value` as JSON and responds with cache validators.
+///
+/// Sets `ETag`, `Cache-Control: public, max-age=<max_age>`,
+/// `Vary: Authorization` and, when given, `Last-Modified`. Pass
+/// `last_modified` only for bodies that change with nothing else. Requests
+/// with `Authorization` get `private` instead of `public`, since the body
+/// may be the viewer's own (drafts, shadow-banned authors' hidden posts,
+/// `is_owner`) and must not be kept by shared caches.
+///
+/// Returns `304 Not Modified` when `If-None-Match` matches, or when it is
+/// absent and `If-Modified-Since` is not older than `last_modified`. HEAD
+/// requests get the same headers; actix-web drops the body on the wire.
+pub fn cached_json<T: Serialize>(
+    req: &HttpRequest,
+    value: &T,
+    last_modified: Option<DateTime<Utc>>,
+    max_age: u32,
+) -> Result<HttpResponse, AppError> {
+    let start = Instant::now();
+    let body = serde_json::to_vec(value).map_err(|e| AppError::Internal(e.to_string()))?;
+    metrics::record_serialization(start);
+    let etag = compute_etag(&body);
+    let visibility = if req.headers().contains_key(AUTHORIZATION) {
+        CacheDirective::Private
+    } else {
+        CacheDirective::Public
+    };
+    let cache_control = CacheControl(vec![visibility, CacheDirective::MaxAge(max_age)]);
+
+    let not_modified = is_not_modified(req, &etag, last_modified);
+
+    let mut builder = if not_modified {
+        HttpResponse::NotModified()
+    } else {
+        HttpResponse::Ok()
+    };
+    builder
+        .insert_header(ETag(etag))
+        .insert_header(cache_control)
+        .insert_header((VARY, "Authorization"));
+    if let Some(modified) = last_modified {
+        builder.insert_header(LastModified(SystemTime::from(modified).into()));
+    }
+
+    if not_modified {
+        return Ok(builder.finish());
+    }
+
+    Ok(builder.content_type(ContentType::json()).body(body))
+}
+
+/// Responds to an OPTIONS request with the `Allow` header for a resource.
+pub fn options_response(methods: &[Method]) -> HttpResponse {
+    HttpResponse::NoContent()
+        .insert_header(Allow(methods.to_vec()))
+        .finish()
+}
+
+/// Computes a strong ETag from the response body bytes.
+fn compute_etag(body: &[u8]) -> EntityTag {
+    let mut hasher = DefaultHasher::new();
+    body.hash(&mut hasher);
+    EntityTag::new_strong(format!("{:016x}", hasher.finish()))
+}
+
+/// Evaluates the request's conditional headers against the resource validators.
+///
+/// `If-None-Match` takes precedence over `If-Modified-Since` (RFC 9110).
+fn is_not_modified(
+    req: &HttpRequest,
+    etag: &EntityTag,
+    last_modified: Option<DateTime<Utc>>,
+) -> bool {
+    match req.get_header::<IfNoneMatch>() {
+        Some(IfNoneMatch::Any) => return true,
+        Some(IfNoneMatch::Items(tags)) => return tags.iter().any(|tag| tag.weak_eq(etag)),
+        None => {}
+    }
+
+    match (req.get_header::<IfModifiedSince>(), last_modified) {
+        (Some(IfModifiedSince(since)), Some(modified)) => {
+            // HTTP dates have one-second precision
+            let since = DateTime::<Utc>::from(SystemTime::from(since));
+            modified.timestamp() <= since.timestamp()
+        }
+        _ => false,
+    }
+}
@@ -1,13 +1,53 @@
 //! HTTP request handlers.
 
-use actix_web::{HttpResponse, Responder, Scope, delete, get, post, put, web};
-use blog_shared::{CreatePostRequest, LoginRequest, RegisterRequest, UpdatePostRequest};
+use actix_web::http::{Method, header};
+use actix_web::{HttpRequest, HttpResponse, Responder, Scope, get, patch, post, route, web};
+use blog_shared::constants::{MAX_COMMENT_LENGTH, MIN_PASSWORD_LENGTH};
+use blog_shared::routes;
+use blog_shared::{
+    CreateCommentRequest, CreatePostRequest, CreateTranslationRequest, CursorPage, LoginRequest,
+    Pagination, RecoverAccountRequest, RegisterRequest, TagSort, UpdateCommentRequest,
+    UpdatePostRequest,
+};
+use chrono::{NaiveDate, NaiveTime};
 use serde::Deserialize;
 
-use crate::application::{AuthService, BlogService};
-use crate::constants::{DEFAULT_LIMIT, DEFAULT_OFFSET};
-use crate::domain::AppError;
-use crate::presentation::middleware::AuthenticatedUser;
+use crate::application::{
+    AccountService, AuthService, BlogService, CommentService, SettingsService,
+};
+use crate::constants::{DEFAULT_TAG_LIMIT, MAX_SEARCH_QUERY_LEN};
+use crate::domain::{AppError, EntityRef, PostMetadata, PublicRef, SearchFilters, normalize_tags};
+use crate::presentation::admin_handlers::admin_routes;
+use crate::presentation::analytics_handlers::analytics_routes;
+use crate::presentation::crosspost_handlers::{crosspost_post, list_crossposts};
+use crate::presentation::editing_handlers::{editing_heartbeat, editing_socket, stop_editing};
+use crate::presentation::experiment_handlers::experiment_routes;
+use crate::presentation::feed_handlers::{atom_feed, rss_feed};
+use crate::presentation::http_cache::{cache_config, cached_json, options_response};
+use crate::presentation::import_handlers::import_routes;
+use crate::presentation::link_check_handlers::link_check_routes;
+use crate::presentation::markdown_handlers::markdown_routes;
+use crate::presentation::message_handlers::message_routes;
+use crate::presentation::micropub_handlers::micropub_routes;
+use crate::presentation::middleware::{AuthenticatedUser, OptionalUser};
+use crate::presentation::newsletter_handlers::newsletter_routes;
+use crate::presentation::notification_handlers::{
+    list_notifications, mark_notifications_read, unread_notification_count,
+};
+use crate::presentation::org_handlers::{my_orgs, org_routes};
+use crate::presentation::payload::{Json, Query};
+use crate::presentation::review_handlers::{post_history, publish_post, review_post, submit_post};
+use crate::presentation::revision_handlers::{diff_revisions, list_revisions};
+use crate::presentation::routes::{resource, resource_with_head};
+use crate::presentation::service_account_handlers::api_usage;
+use crate::presentation::settings_handlers::settings_routes;
+use crate::presentation::site_handlers::site_routes;
+use crate::presentation::suggest_handlers::{suggest_tags, suggest_users};
+use crate::presentation::tag_handlers::tag_page;
+use crate::presentation::telemetry::telemetry_routes;
+use crate::presentation::transfer_handlers::{transfer_post, transfer_routes};
+use crate::presentation::trash_handlers::{legacy_trash_routes, restore_post, trash_routes};
+use crate::presentation::upload_handlers::upload_routes;
 
 /// Creates all API routes.
 pub fn api_routes() -> Scope {
@@ -15,16 +55,105 @@ pub fn api_routes() -> Scope {
         // Health
         .service(health)
         // Auth (public)
-        .service(register)
-        .service(login)
+        .service(resource::<routes::Register>().to(register))
+        .service(resource::<routes::Login>().to(login))
+        .service(recover)
+        .service(resource::<routes::GetFeatures>().to(get_features))
         // Auth (protected)
-        .service(get_me)
+        .service(resource::<routes::GetMe>().to(get_me))
+        .service(create_invite)
+        // Users (public profiles; suggestions before `/users/{id}`)
+        .service(resource::<routes::SuggestUsers>().to(suggest_users))
+        .service(resource::<routes::GetUser>().to(get_user))
+        // Bulk import (requires auth; before `/posts/{id}`)
+        .service(import_routes())
+        // Trash of deleted posts (requires auth; before `/posts/{id}`)
+        .service(trash_routes())
         // Posts (mixed: list/get are public, create/update/delete require auth)
-        .service(list_posts)
-        .service(get_post)
-        .service(create_post)
-        .service(update_post)
-        .service(delete_post)
+        .service(resource_with_head::<routes::ListPosts>().to(list_posts))
+        .service(resource_with_head::<routes::GetPost>().to(get_post))
+        .service(resource::<routes::CreatePost>().to(create_post))
+        .service(resource::<routes::UpdatePost>().to(update_post))
+        .service(resource::<routes::DeletePost>().to(delete_post))
+        .service(resource::<routes::RestorePost>().to(restore_post))
+        .service(transfer_post)
+        .service(resource::<routes::ListTranslations>().to(list_translations))
+        .service(create_translation)
+        .service(update_post_metadata)
+        // Editorial workflow (author, or editors for review)
+        .service(submit_post)
+        .service(review_post)
+        .service(publish_post)
+        .service(post_history)
+        // Revisions (author or editors)
+        .service(resource::<routes::ListRevisions>().to(list_revisions))
+        .service(resource::<routes::DiffRevisions>().to(diff_revisions))
+        // Co-editing presence (author only)
+        .service(resource::<routes::EditingHeartbeat>().to(editing_heartbeat))
+        .service(resource::<routes::StopEditing>().to(stop_editing))
+        .service(resource::<routes::EditingSocket>().to(editing_socket))
+        .service(posts_options)
+        .service(post_options)
+        // Search and tags (public)
+        .service(search_posts)
+        .service(list_tags)
+        .service(suggest_tags)
+        .service(tag_page)
+        // RSS and Atom feeds (public)
+        .service(rss_feed)
+        .service(atom_feed)
+        // Comments (list is public, create/update/delete require auth)
+        .service(resource::<routes::ListComments>().to(list_comments))
+        .service(resource::<routes::CreateComment>().to(create_comment))
+        .service(resource::<routes::UpdateComment>().to(update_comment))
+        .service(resource::<routes::DeleteComment>().to(delete_comment))
+        // Cross-posting to DEV / Hashnode (author only)
+        .service(crosspost_post)
+        .service(list_crossposts)
+        // File uploads (requires auth; served at `/uploads/{id}`)
+        .service(upload_routes())
+        // Broken link report (requires auth)
+        .service(link_check_routes())
+        // Trash at its old path (requires auth)
+        .service(legacy_trash_routes())
+        // Post transfers between users (require auth)
+        .service(transfer_routes())
+        // Organizations (profiles are public, the rest require auth)
+        .service(org_routes())
+        .service(my_orgs)
+        // Markdown preview (public)
+        .service(markdown_routes())
+        // Private messages (require auth)
+        .service(message_routes())
+        // Notifications (require auth)
+        .service(resource::<routes::ListNotifications>().to(list_notifications))
+        .service(resource::<routes::CountUnreadNotifications>().to(unread_notification_count))
+        .service(resource::<routes::MarkNotificationsRead>().to(mark_notifications_read))
+        // Micropub for IndieWeb clients (requires a local or IndieAuth token)
+        .service(micropub_routes())
+        // Newsletter subscriptions (public)
+        .service(newsletter_routes())
+        // Site announcement banner (public)
+        .service(site_routes())
+        // Anonymous client analytics (public)
+        .service(analytics_routes())
+        // Client crash reports (public)
+        .service(telemetry_routes())
+        // Experiment exposures (requires auth)
+        .service(experiment_routes())
+        // Usage of the current user's service accounts (requires auth)
+        .service(api_usage)
+        // Settings (require auth)
+        .service(settings_routes())
+        // Admin dashboard (require admin or moderator role)
+        .service(admin_routes())
+}
+
+/// Returns the client's `User-Agent` header, recorded with new sessions.
+fn user_agent(req: &HttpRequest) -> Option<&str> {
+    req.headers()
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
 }
 
 /// Health check endpoint.
@@ -34,33 +163,84 @@ async fn health() -> impl Responder {
 }
 
 /// Handles user registration.
-#[post("/auth/register")]
 async fn register(
+    req: HttpRequest,
     service: web::Data<AuthService>,
-    payload: web::Json<RegisterRequest>,
+    payload: Json<RegisterRequest>,
 ) -> Result<impl Responder, AppError> {
-    let response = service.register(payload.into_inner()).await?;
+    let response = service
+        .register(payload.into_inner(), user_agent(&req))
+        .await?;
     Ok(HttpResponse::Created().json(response))
 }
 
 /// Handles user login.
-#[post("/auth/login")]
 async fn login(
+    req: HttpRequest,
     service: web::Data<AuthService>,
-    payload: web::Json<LoginRequest>,
+    payload: Json<LoginRequest>,
 ) -> Result<impl Responder, AppError> {
-    let response = service.login(payload.into_inner()).await?;
+    let response = service
+        .login(payload.into_inner(), user_agent(&req))
+        .await?;
     Ok(HttpResponse::Ok().json(response))
 }
 
-/// Returns the current authenticated user's info.
-#[get("/auth/me")]
-async fn get_me(
+/// Signs in with a recovery code, setting a new password.
+#[post("/auth/recover")]
+async fn recover(
+    req: HttpRequest,
+    service: web::Data<AuthService>,
+    payload: Json<RecoverAccountRequest>,
+) -> Result<impl Responder, AppError> {
+    let payload = payload.into_inner();
+    if payload.new_password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Password must be at least {MIN_PASSWORD_LENGTH} characters"
+        )));
+    }
+
+    let response = service.recover(payload, user_agent(&req)).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Reports whether registration is open and whether it is invite-only,
+/// with the signed-in user's experiment variants (public).
+async fn get_features(
+    user: OptionalUser,
+    service: web::Data<AuthService>,
+) -> Result<impl Responder, AppError> {
+    let features = service.features(user.user_id()).await?;
+    Ok(HttpResponse::Ok().json(features))
+}
+
+/// Creates a single-use invite code while registration is invite-only.
+#[post("/invites")]
+async fn create_invite(
     auth: AuthenticatedUser,
     service: web::Data<AuthService>,
 ) -> Result<impl Responder, AppError> {
-    let user = service.get_user_by_id(auth.user_id).await?;
-    Ok(HttpResponse::Ok().json(user))
+    let invite = service.create_invite(auth.user_id).await?;
+    Ok(HttpResponse::Created().json(invite))
+}
+
+/// Returns the current authenticated user's info, permissions, usage,
+/// unread message count, and feature flags.
+async fn get_me(
+    auth: AuthenticatedUser,
+    service: web::Data<AccountService>,
+) -> Result<impl Responder, AppError> {
+    let me = service.me(auth.user_id).await?;
+    Ok(HttpResponse::Ok().json(me))
+}
+
+/// Returns the public profile of a user.
+async fn get_user(
+    service: web::Data<SettingsService>,
+    path: web::Path<PublicRef>,
+) -> Result<impl Responder, AppError> {
+    let profile = service.public_profile(&path.into_inner().into()).await?;
+    Ok(HttpResponse::Ok().json(profile))
 }
 
 /// Query parameters for listing posts.
@@ -68,67 +248,316 @@ async fn get_me(
 pub struct ListPostsQuery {
     pub limit: Option<i64>,
     pub offset: Option<i64>,
+    /// Only posts with this tag.
+    pub tag: Option<String>,
+    /// Only posts in this language.
+    pub lang: Option<String>,
 }
 
-/// Lists posts with pagination (public).
-#[get("/posts")]
+/// Lists posts with pagination, optionally filtered by tag and language (public).
+/// Also answers HEAD.
 async fn list_posts(
+    req: HttpRequest,
+    viewer: OptionalUser,
     service: web::Data<BlogService>,
-    query: web::Query<ListPostsQuery>,
+    query: Query<ListPostsQuery>,
 ) -> Result<impl Responder, AppError> {
-    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
-    let offset = query.offset.unwrap_or(DEFAULT_OFFSET);
-    let response = service.list_posts(limit, offset).await?;
-    Ok(HttpResponse::Ok().json(response))
+    let Pagination { limit, offset } = Pagination::clamp(query.limit, query.offset);
+    let viewer_id = viewer.user_id();
+    let lang = query.lang.as_deref();
+    // Read before the page, so a change in between is never marked older
+    let last_modified = service.posts_modified_at().await?;
+    let response = match query.tag.as_deref() {
+        Some(tag) => {
+            service
+                .list_posts_by_tag(tag, limit, offset, viewer_id, lang)
+                .await?
+        }
+        None => service.list_posts(limit, offset, viewer_id, lang).await?,
+    };
+    let max_age = cache_config(&req).post_list_max_age_secs;
+    cached_json(&req, &response, last_modified, max_age)
 }
 
-/// Gets a single post by ID (public).
-#[get("/posts/{id}")]
+/// Gets a single post by ID (public). Also answers HEAD.
 async fn get_post(
+    req: HttpRequest,
+    viewer: OptionalUser,
     service: web::Data<BlogService>,
-    path: web::Path<i64>,
+    path: web::Path<PublicRef>,
 ) -> Result<impl Responder, AppError> {
-    let id = path.into_inner();
-    let post = service.get_post(id).await?;
-    Ok(HttpResponse::Ok().json(post))
+    let id = service.resolve_post(&path.into_inner().into()).await?;
+    let last_modified = service.post_modified_at(id).await?;
+    let post = service.get_post(id, viewer.user_id()).await?;
+    let max_age = cache_config(&req).post_max_age_secs;
+    cached_json(&req, &post, last_modified, max_age)
+}
+
+/// Query parameters for searching posts.
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    pub q: String,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    /// Only posts in this language.
+    pub lang: Option<String>,
+    /// Only posts written by this user.
+    pub author: Option<String>,
+    /// Only posts with this tag.
+    pub tag: Option<String>,
+    /// Only posts created on or after this UTC day.
+    pub from: Option<NaiveDate>,
+    /// Only posts created on or before this UTC day.
+    pub to: Option<NaiveDate>,
+}
+
+/// Full-text searches posts by title and content (public), best matches
+/// first with highlighted snippets.
+#[get("/search")]
+async fn search_posts(
+    viewer: OptionalUser,
+    service: web::Data<BlogService>,
+    query: Query<SearchQuery>,
+) -> Result<impl Responder, AppError> {
+    if query.q.chars().count() > MAX_SEARCH_QUERY_LEN {
+        return Err(AppError::Validation(format!(
+            "Search query must be at most {MAX_SEARCH_QUERY_LEN} characters"
+        )));
+    }
+    let Pagination { limit, offset } = Pagination::clamp(query.limit, query.offset);
+    let query = query.into_inner();
+    let filters = SearchFilters {
+        lang: query.lang,
+        author: query.author,
+        tag: query.tag,
+        from: query.from.map(|day| day.and_time(NaiveTime::MIN).and_utc()),
+        // Through the end of the day
+        until: query
+            .to
+            .and_then(|day| day.succ_opt())
+            .map(|day| day.and_time(NaiveTime::MIN).and_utc()),
+    };
+    let response = service
+        .search_posts(&query.q, filters, limit, offset, viewer.user_id())
+        .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Query parameters for listing tags.
+#[derive(Debug, Deserialize)]
+pub struct ListTagsQuery {
+    pub limit: Option<i64>,
+    /// `count` (default), `popular` or `name`.
+    #[serde(default)]
+    pub sort: TagSort,
+}
+
+/// Lists tags in use with their post counts, most used first unless
+/// `sort` says otherwise (public).
+#[get("/tags")]
+async fn list_tags(
+    viewer: OptionalUser,
+    service: web::Data<BlogService>,
+    query: Query<ListTagsQuery>,
+) -> Result<impl Responder, AppError> {
+    let limit = query.limit.unwrap_or(DEFAULT_TAG_LIMIT);
+    let response = service
+        .list_tags(limit, viewer.user_id(), query.sort)
+        .await?;
+    Ok(HttpResponse::Ok().json(response))
 }
 
 /// Creates a new post (requires authentication).
-#[post("/posts")]
 async fn create_post(
     auth: AuthenticatedUser,
     service: web::Data<BlogService>,
-    payload: web::Json<CreatePostRequest>,
+    payload: Json<CreatePostRequest>,
 ) -> Result<impl Responder, AppError> {
-    let post = service
-        .create_post(auth.user_id, payload.into_inner())
-        .await?;
+    let mut req = payload.into_inner();
+    req.tags = normalize_tags(req.tags)?;
+    let post = service.create_post(auth.user_id, req).await?;
     Ok(HttpResponse::Created().json(post))
 }
 
 /// Updates a post (author only).
-#[put("/posts/{id}")]
 async fn update_post(
     auth: AuthenticatedUser,
     service: web::Data<BlogService>,
-    path: web::Path<i64>,
-    payload: web::Json<UpdatePostRequest>,
+    path: web::Path<EntityRef>,
+    payload: Json<UpdatePostRequest>,
 ) -> Result<impl Responder, AppError> {
-    let id = path.into_inner();
-    let post = service
-        .update_post(id, auth.user_id, payload.into_inner())
-        .await?;
+    let id = service.resolve_post(&path).await?;
+    let mut req = payload.into_inner();
+    req.tags = req.tags.map(normalize_tags).transpose()?;
+    let post = service.update_post(id, auth.user_id, req).await?;
     Ok(HttpResponse::Ok().json(post))
 }
 
 /// Deletes a post (author only).
-#[delete("/posts/{id}")]
 async fn delete_post(
     auth: AuthenticatedUser,
     service: web::Data<BlogService>,
-    path: web::Path<i64>,
+    path: web::Path<EntityRef>,
 ) -> Result<impl Responder, AppError> {
-    let id = path.into_inner();
+    let id = service.resolve_post(&path).await?;
     service.delete_post(id, auth.user_id).await?;
     Ok(HttpResponse::NoContent().finish())
 }
+
+/// Lists the language variants of a post, including itself (public).
+async fn list_translations(
+    viewer: OptionalUser,
+    service: web::Data<BlogService>,
+    path: web::Path<PublicRef>,
+) -> Result<impl Responder, AppError> {
+    let id = service.resolve_post(&path.into_inner().into()).await?;
+    let translations = service.list_translations(id, viewer.user_id()).await?;
+    Ok(HttpResponse::Ok().json(translations))
+}
+
+/// Adds a language variant of a post (author only).
+#[post("/posts/{id}/translations")]
+async fn create_translation(
+    auth: AuthenticatedUser,
+    service: web::Data<BlogService>,
+    path: web::Path<EntityRef>,
+    payload: Json<CreateTranslationRequest>,
+) -> Result<impl Responder, AppError> {
+    let id = service.resolve_post(&path).await?;
+    let mut req = payload.into_inner();
+    req.tags = normalize_tags(req.tags)?;
+    let post = service.create_translation(id, auth.user_id, req).await?;
+    Ok(HttpResponse::Created().json(post))
+}
+
+/// Sets or, with `null`, removes custom fields of a post (author only).
+#[patch("/posts/{id}/metadata")]
+async fn update_post_metadata(
+    auth: AuthenticatedUser,
+    service: web::Data<BlogService>,
+    path: web::Path<EntityRef>,
+    payload: Json<PostMetadata>,
+) -> Result<impl Responder, AppError> {
+    let id = service.resolve_post(&path).await?;
+    let metadata = service
+        .update_metadata(id, auth.user_id, payload.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok().json(metadata))
+}
+
+/// Lists the methods supported on the posts collection.
+#[route("/posts", method = "OPTIONS")]
+async fn posts_options() -> impl Responder {
+    options_response(&[Method::GET, Method::HEAD, Method::POST, Method::OPTIONS])
+}
+
+/// Lists the methods supported on a single post.
+#[route("/posts/{id}", method = "OPTIONS")]
+async fn post_options() -> impl Responder {
+    options_response(&[
+        Method::GET,
+        Method::HEAD,
+        Method::PUT,
+        Method::DELETE,
+        Method::OPTIONS,
+    ])
+}
+
+/// Query parameters for listing comments.
+#[derive(Debug, Deserialize)]
+pub struct ListCommentsQuery {
+    pub limit: Option<i64>,
+    /// `next_cursor` of the previous page.
+    pub cursor: Option<String>,
+    /// Older clients' offset, used only without a cursor.
+    pub offset: Option<i64>,
+}
+
+/// Lists comments on a post, oldest first (public).
+///
+/// Pages follow `cursor`; a positive `offset` without one pages the old
+/// way, without a `next_cursor`.
+async fn list_comments(
+    viewer: OptionalUser,
+    service: web::Data<CommentService>,
+    posts: web::Data<BlogService>,
+    path: web::Path<PublicRef>,
+    query: Query<ListCommentsQuery>,
+) -> Result<impl Responder, AppError> {
+    let post_id = posts.resolve_post(&path.into_inner().into()).await?;
+    let ListCommentsQuery {
+        limit,
+        cursor,
+        offset,
+    } = query.into_inner();
+    let response = match (cursor, offset) {
+        (None, Some(offset)) if offset > 0 => {
+            let Pagination { limit, offset } = Pagination::clamp(limit, Some(offset));
+            service
+                .list_comments(post_id, limit, offset, viewer.user_id())
+                .await?
+        }
+        (cursor, _) => {
+            let page = CursorPage::clamp(limit, cursor);
+            service
+                .list_comments_after(post_id, &page, viewer.user_id())
+                .await?
+        }
+    };
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Adds a comment to a post (requires authentication).
+async fn create_comment(
+    auth: AuthenticatedUser,
+    service: web::Data<CommentService>,
+    posts: web::Data<BlogService>,
+    path: web::Path<EntityRef>,
+    payload: Json<CreateCommentRequest>,
+) -> Result<impl Responder, AppError> {
+    validate_comment_content(&payload.content)?;
+    let post_id = posts.resolve_post(&path).await?;
+    let comment = service
+        .create_comment(post_id, auth.user_id, payload.into_inner())
+        .await?;
+    Ok(HttpResponse::Created().json(comment))
+}
+
+/// Edits a comment (comment author only).
+async fn update_comment(
+    auth: AuthenticatedUser,
+    service: web::Data<CommentService>,
+    path: web::Path<i64>,
+    payload: Json<UpdateCommentRequest>,
+) -> Result<impl Responder, AppError> {
+    validate_comment_content(&payload.content)?;
+    let comment = service
+        .update_comment(path.into_inner(), auth.user_id, payload.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok().json(comment))
+}
+
+/// Deletes a comment (comment author or post author).
+async fn delete_comment(
+    auth: AuthenticatedUser,
+    service: web::Data<CommentService>,
+    path: web::Path<i64>,
+) -> Result<impl Responder, AppError> {
+    service
+        .delete_comment(path.into_inner(), auth.user_id)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Rejects empty or overly long comment content.
+pub(crate) fn validate_comment_content(content: &str) -> Result<(), AppError> {
+    if content.trim().is_empty() {
+        return Err(AppError::Validation("Comment cannot be empty".to_string()));
+    }
+    if content.chars().count() > MAX_COMMENT_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Comment must be at most {MAX_COMMENT_LENGTH} characters"
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,41 @@
+//! HTTP handlers for bulk post imports.
+
+use actix_web::{HttpResponse, Resource, Responder, web};
+use blog_shared::ImportPostsRequest;
+use blog_shared::constants::MAX_IMPORT_POSTS;
+use blog_shared::routes::{ImportPosts, Route};
+
+use crate::application::{AuthService, ImportService};
+use crate::constants::IMPORT_JSON_LIMIT;
+use crate::domain::AppError;
+use crate::presentation::middleware::AuthenticatedUser;
+use crate::presentation::payload::{Json, json_config};
+use crate::presentation::routes::route;
+
+/// Creates the bulk import route (requires authentication).
+///
+/// Must be registered before `/posts/{id}`.
+pub fn import_routes() -> Resource {
+    web::resource(ImportPosts::PATH)
+        .app_data(json_config().limit(IMPORT_JSON_LIMIT))
+        .route(route::<ImportPosts>().to(import_posts))
+}
+
+/// Imports a batch of posts, reporting the outcome of each.
+async fn import_posts(
+    auth: AuthenticatedUser,
+    auth_service: web::Data<AuthService>,
+    service: web::Data<ImportService>,
+    payload: Json<ImportPostsRequest>,
+) -> Result<impl Responder, AppError> {
+    let req = payload.into_inner();
+    if req.posts.is_empty() || req.posts.len() > MAX_IMPORT_POSTS {
+        return Err(AppError::Validation(format!(
+            "An import must contain 1 to {MAX_IMPORT_POSTS} posts"
+        )));
+    }
+
+    let role = auth.role(&auth_service).await?;
+    let response = service.import_posts(auth.user_id, role, req).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
@@ -0,0 +1,23 @@
+//! HTTP handlers for the broken link report.
+
+use actix_web::{HttpResponse, Resource, Responder, web};
+use blog_shared::routes::ListBrokenLinks;
+
+use crate::application::LinkCheckService;
+use crate::domain::AppError;
+use crate::presentation::middleware::AuthenticatedUser;
+use crate::presentation::routes::resource;
+
+/// Creates the current user's post report routes (require authentication).
+pub fn link_check_routes() -> Resource {
+    resource::<ListBrokenLinks>().to(list_broken_links)
+}
+
+/// Lists links in the current user's posts that failed their last check.
+async fn list_broken_links(
+    auth: AuthenticatedUser,
+    service: web::Data<LinkCheckService>,
+) -> Result<impl Responder, AppError> {
+    let links = service.broken_links(auth.user_id).await?;
+    Ok(HttpResponse::Ok().json(links))
+}
@@ -0,0 +1,39 @@
+//! HTTP handlers for Markdown rendering.
+
+use actix_web::{HttpResponse, Responder, Scope, get, web};
+use blog_shared::MarkdownPreviewResponse;
+use serde::Deserialize;
+
+use crate::application::BlogService;
+use crate::constants::{MARKDOWN_SYNTAX, MAX_MARKDOWN_PREVIEW_LEN};
+use crate::domain::AppError;
+use crate::presentation::payload::Query;
+
+/// Creates the Markdown routes (public).
+pub fn markdown_routes() -> Scope {
+    web::scope("/markdown").service(preview_markdown)
+}
+
+/// Query parameters for the preview endpoint.
+#[derive(Debug, Deserialize)]
+struct PreviewQuery {
+    #[serde(default)]
+    text: String,
+}
+
+/// Renders Markdown exactly as post content is rendered, for the editor preview.
+#[get("/preview")]
+async fn preview_markdown(
+    service: web::Data<BlogService>,
+    query: Query<PreviewQuery>,
+) -> Result<impl Responder, AppError> {
+    if query.text.chars().count() > MAX_MARKDOWN_PREVIEW_LEN {
+        return Err(AppError::Validation(format!(
+            "Preview text must be at most {MAX_MARKDOWN_PREVIEW_LEN} characters"
+        )));
+    }
+    Ok(HttpResponse::Ok().json(MarkdownPreviewResponse {
+        html: service.render_content(&query.text),
+        supported: MARKDOWN_SYNTAX.iter().map(|s| s.to_string()).collect(),
+    }))
+}
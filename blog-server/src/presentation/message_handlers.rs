@@ -0,0 +1,110 @@
+//! HTTP handlers for private messages.
+
+use actix_web::{HttpResponse, Responder, Scope, get, post, web};
+use blog_shared::constants::MAX_MESSAGE_LENGTH;
+use blog_shared::{Pagination, SendMessageRequest, StartConversationRequest};
+use serde::Deserialize;
+
+use crate::application::MessageService;
+use crate::domain::AppError;
+use crate::presentation::middleware::AuthenticatedUser;
+use crate::presentation::payload::{Json, Query};
+
+/// Creates the private message routes (all require authentication).
+pub fn message_routes() -> Scope {
+    web::scope("/conversations")
+        .service(unread_count)
+        .service(list_conversations)
+        .service(start_conversation)
+        .service(list_messages)
+        .service(send_message)
+}
+
+/// Query parameters for paginated conversation and message lists.
+#[derive(Debug, Deserialize)]
+pub struct MessageListQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// Returns the number of unread messages (for the notification bell).
+#[get("/unread")]
+async fn unread_count(
+    auth: AuthenticatedUser,
+    service: web::Data<MessageService>,
+) -> Result<impl Responder, AppError> {
+    let unread = service.unread_count(auth.user_id).await?;
+    Ok(HttpResponse::Ok().json(unread))
+}
+
+/// Lists the current user's conversations, most recently active first.
+#[get("")]
+async fn list_conversations(
+    auth: AuthenticatedUser,
+    service: web::Data<MessageService>,
+    query: Query<MessageListQuery>,
+) -> Result<impl Responder, AppError> {
+    let Pagination { limit, offset } = Pagination::clamp(query.limit, query.offset);
+    let conversations = service
+        .list_conversations(auth.user_id, limit, offset)
+        .await?;
+    Ok(HttpResponse::Ok().json(conversations))
+}
+
+/// Sends a first message to a user, starting a conversation with them.
+#[post("")]
+async fn start_conversation(
+    auth: AuthenticatedUser,
+    service: web::Data<MessageService>,
+    payload: Json<StartConversationRequest>,
+) -> Result<impl Responder, AppError> {
+    let req = payload.into_inner();
+    validate_message_content(&req.content)?;
+    let conversation = service
+        .start_conversation(auth.user_id, req.username.trim(), &req.content)
+        .await?;
+    Ok(HttpResponse::Created().json(conversation))
+}
+
+/// Lists messages of a conversation, newest first, marking it as read.
+#[get("/{id}/messages")]
+async fn list_messages(
+    auth: AuthenticatedUser,
+    service: web::Data<MessageService>,
+    path: web::Path<i64>,
+    query: Query<MessageListQuery>,
+) -> Result<impl Responder, AppError> {
+    let Pagination { limit, offset } = Pagination::clamp(query.limit, query.offset);
+    let messages = service
+        .list_messages(path.into_inner(), auth.user_id, limit, offset)
+        .await?;
+    Ok(HttpResponse::Ok().json(messages))
+}
+
+/// Sends a message in a conversation.
+#[post("/{id}/messages")]
+async fn send_message(
+    auth: AuthenticatedUser,
+    service: web::Data<MessageService>,
+    path: web::Path<i64>,
+    payload: Json<SendMessageRequest>,
+) -> Result<impl Responder, AppError> {
+    validate_message_content(&payload.content)?;
+    let message = service
+        .send_message(path.into_inner(), auth.user_id, &payload.content)
+        .await?;
+    Ok(HttpResponse::Created().json(message))
+}
+
+/// Rejects empty or overly long messages.
+fn validate_message_content(content: &str) -> Result<(), AppError> {
+    if content.trim().is_empty() {
+        return Err(AppError::Validation("Message cannot be empty".to_string()));
+    }
+    if content.chars().count() > MAX_MESSAGE_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Message must be at most {MAX_MESSAGE_LENGTH} characters"
+        )));
+    }
+    Ok(())
+}
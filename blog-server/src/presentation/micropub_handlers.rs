@@ -0,0 +1,92 @@
+//! HTTP handlers for the Micropub endpoint.
+
+use actix_web::http::header;
+use actix_web::{HttpRequest, HttpResponse, Responder, Scope, get, post, web};
+use serde::Deserialize;
+
+use crate::application::MicropubService;
+use crate::domain::{AppError, MicropubEntry};
+use crate::presentation::middleware::extract_user;
+use crate::presentation::payload::Query;
+
+/// Creates the Micropub routes.
+///
+/// Accepts this server's own access tokens and, when
+/// `INDIEAUTH_TOKEN_ENDPOINT` is set, IndieAuth tokens.
+pub fn micropub_routes() -> Scope {
+    web::scope("/micropub")
+        .service(micropub_query)
+        .service(micropub_create)
+}
+
+/// Query string of a Micropub `GET`.
+#[derive(Debug, Deserialize)]
+pub struct MicropubQuery {
+    pub q: String,
+}
+
+/// Answers configuration queries (`q=config`, `q=syndicate-to`).
+#[get("")]
+async fn micropub_query(
+    req: HttpRequest,
+    service: web::Data<MicropubService>,
+    query: Query<MicropubQuery>,
+) -> Result<impl Responder, AppError> {
+    authorize(&req, &service, None).await?;
+
+    match query.q.as_str() {
+        "config" | "syndicate-to" => {
+            Ok(HttpResponse::Ok().json(serde_json::json!({"syndicate-to": []})))
+        }
+        other => Err(AppError::Validation(format!("Unsupported query {other}"))),
+    }
+}
+
+/// Creates a post from a form-encoded or JSON `h-entry`.
+///
+/// Responds 201 with the post's URL in `Location`.
+#[post("")]
+async fn micropub_create(
+    req: HttpRequest,
+    service: web::Data<MicropubService>,
+    payload: web::Either<web::Json<serde_json::Value>, web::Form<Vec<(String, String)>>>,
+) -> Result<impl Responder, AppError> {
+    let (entry, body_token) = match payload {
+        web::Either::Left(json) => (MicropubEntry::from_json(&json)?, None),
+        web::Either::Right(form) => {
+            let token = form
+                .iter()
+                .find(|(key, _)| key == "access_token")
+                .map(|(_, value)| value.clone());
+            (MicropubEntry::from_form(&form)?, token)
+        }
+    };
+
+    let user_id = authorize(&req, &service, body_token.as_deref()).await?;
+    let location = service.publish(user_id, entry).await?;
+    Ok(HttpResponse::Created()
+        .insert_header((header::LOCATION, location))
+        .finish())
+}
+
+/// Resolves the author of a request: a local access token in
+/// `Authorization`, otherwise an IndieAuth token from the header or the
+/// form's `access_token`.
+async fn authorize(
+    req: &HttpRequest,
+    service: &MicropubService,
+    body_token: Option<&str>,
+) -> Result<i64, AppError> {
+    if let Ok(user) = extract_user(req).await {
+        return Ok(user.user_id);
+    }
+
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .or(body_token)
+        .ok_or(AppError::InvalidCredentials)?;
+    service.authorize(token).await
+}
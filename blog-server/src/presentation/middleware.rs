@@ -1,12 +1,16 @@
 //! Authentication middleware and extractors.
 
-use std::future::{Future, Ready, ready};
+use std::future::Future;
 use std::pin::Pin;
 
+use actix_web::http::Method;
 use actix_web::{FromRequest, HttpRequest, dev::Payload, web};
+use blog_shared::Role;
 
+use crate::application::AuthService;
 use crate::domain::AppError;
-use crate::infrastructure::jwt;
+use crate::infrastructure::jwt::TokenType;
+use crate::presentation::quota::count_request;
 
 /// Authenticated user extracted from JWT token in Authorization header.
 ///
@@ -21,23 +25,30 @@ use crate::infrastructure::jwt;
 pub struct AuthenticatedUser {
     /// The authenticated user's ID.
     pub user_id: i64,
+    /// Session the token belongs to (`None` for tokens issued before
+    /// sessions were tracked, and for service tokens).
+    pub session_id: Option<String>,
+    /// Whether a service account's token authenticated the request.
+    pub is_service: bool,
 }
 
-/// Wrapper for JWT secret to use as app data.
-#[derive(Clone)]
-pub struct JwtSecret(pub String);
-
 impl FromRequest for AuthenticatedUser {
     type Error = AppError;
-    type Future = Ready<Result<Self, Self::Error>>;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
 
     fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
-        ready(extract_user(req))
+        let req = req.clone();
+        Box::pin(async move { extract_user(&req).await })
     }
 }
 
 /// Extracts the authenticated user from the request.
-fn extract_user(req: &HttpRequest) -> Result<AuthenticatedUser, AppError> {
+///
+/// Rejects tokens whose session has been revoked, and read-only service
+/// tokens on requests other than `GET`, `HEAD` and `OPTIONS` (403). Service
+/// token requests count against the account's daily quotas (429 once used
+/// up).
+pub(crate) async fn extract_user(req: &HttpRequest) -> Result<AuthenticatedUser, AppError> {
     // Extract token from Authorization header
     let token = req
         .headers()
@@ -46,19 +57,40 @@ fn extract_user(req: &HttpRequest) -> Result<AuthenticatedUser, AppError> {
         .and_then(|h| h.strip_prefix("Bearer "))
         .ok_or(AppError::InvalidCredentials)?;
 
-    // Get JWT secret from app data
-    let jwt_secret = req
-        .app_data::<web::Data<JwtSecret>>()
-        .ok_or_else(|| AppError::Internal("JWT secret not configured".into()))?;
+    // Validate token (signature, issuer, audience, session) and extract claims
+    let auth_service = req
+        .app_data::<web::Data<AuthService>>()
+        .ok_or_else(|| AppError::Internal("Auth service not configured".into()))?;
+    let claims = auth_service.authenticate(token).await?;
 
-    // Validate token and extract claims
-    let claims = jwt::validate_token(token, &jwt_secret.0)?;
+    let read_only = matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS);
+    if !read_only && !claims.can_write() {
+        return Err(AppError::Forbidden);
+    }
+    if claims.typ == TokenType::Service
+        && let Some(account_id) = claims.sid.as_deref().and_then(|sid| sid.parse().ok())
+    {
+        count_request(req, account_id).await?;
+    }
 
     Ok(AuthenticatedUser {
         user_id: claims.sub,
+        session_id: claims.sid.filter(|_| claims.typ == TokenType::User),
+        is_service: claims.typ == TokenType::Service,
     })
 }
 
+impl AuthenticatedUser {
+    /// Returns the user's current role, or `User` for service tokens, which
+    /// never get a role's powers whoever they act as.
+    pub async fn role(&self, auth_service: &AuthService) -> Result<Role, AppError> {
+        if self.is_service {
+            return Ok(Role::User);
+        }
+        auth_service.role(self.user_id).await
+    }
+}
+
 /// Optional authentication extractor.
 ///
 /// Use this when authentication is optional (e.g., public endpoints that
@@ -71,7 +103,115 @@ impl FromRequest for OptionalUser {
     type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
 
     fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
-        let user = extract_user(req).ok();
-        Box::pin(async move { Ok(OptionalUser(user)) })
+        let req = req.clone();
+        Box::pin(async move { Ok(OptionalUser(extract_user(&req).await.ok())) })
+    }
+}
+
+impl OptionalUser {
+    /// Returns the ID of the authenticated user, if any.
+    pub fn user_id(&self) -> Option<i64> {
+        self.0.as_ref().map(|user| user.user_id)
+    }
+}
+
+/// User signed in with their password; service tokens get 403.
+///
+/// For the account itself (credentials, sessions, deletion), which a service
+/// account acting as the user must not touch.
+#[derive(Debug, Clone)]
+pub struct SessionUser(pub AuthenticatedUser);
+
+impl FromRequest for SessionUser {
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        Box::pin(async move {
+            let user = extract_user(&req).await?;
+            if user.is_service {
+                return Err(AppError::Forbidden);
+            }
+            Ok(SessionUser(user))
+        })
     }
 }
+
+/// Authenticated user with the `Admin` role; other users get 403.
+#[derive(Debug, Clone)]
+pub struct AdminUser(pub AuthenticatedUser);
+
+impl FromRequest for AdminUser {
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        Box::pin(async move {
+            extract_user_with_role(&req, |role| role == Role::Admin)
+                .await
+                .map(AdminUser)
+        })
+    }
+}
+
+/// Authenticated user who may moderate content (moderator or admin).
+#[derive(Debug, Clone)]
+pub struct ModeratorUser(pub AuthenticatedUser);
+
+impl FromRequest for ModeratorUser {
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        Box::pin(async move {
+            extract_user_with_role(&req, |role| role.can_moderate())
+                .await
+                .map(ModeratorUser)
+        })
+    }
+}
+
+/// Authenticated user who may review posts (editor, moderator, or admin).
+#[derive(Debug, Clone)]
+pub struct EditorUser(pub AuthenticatedUser);
+
+impl FromRequest for EditorUser {
+    type Error = AppError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        Box::pin(async move {
+            extract_user_with_role(&req, |role| role.can_review())
+                .await
+                .map(EditorUser)
+        })
+    }
+}
+
+/// Extracts the authenticated user and checks their current role.
+///
+/// The role is read from the database, so promotions and demotions apply
+/// without a new token. Service tokens never get a role's powers (403),
+/// whoever they act as.
+async fn extract_user_with_role(
+    req: &HttpRequest,
+    allowed: fn(Role) -> bool,
+) -> Result<AuthenticatedUser, AppError> {
+    let user = extract_user(req).await?;
+    if user.is_service {
+        return Err(AppError::Forbidden);
+    }
+    let auth_service = req
+        .app_data::<web::Data<AuthService>>()
+        .ok_or_else(|| AppError::Internal("Auth service not configured".into()))?;
+
+    if !allowed(auth_service.role(user.user_id).await?) {
+        return Err(AppError::Forbidden);
+    }
+
+    Ok(user)
+}
@@ -0,0 +1,95 @@
+//! HTTP handlers for moderation rules and the flagged content queue.
+
+use actix_web::{HttpResponse, Responder, Scope, delete, get, post, put, web};
+use blog_shared::{CreateModerationRuleRequest, Pagination, UpdateModerationRuleRequest};
+
+use crate::application::ModerationService;
+use crate::domain::AppError;
+use crate::presentation::admin_handlers::AdminListQuery;
+use crate::presentation::middleware::{AdminUser, ModeratorUser};
+use crate::presentation::payload::{Json, Query};
+
+/// Creates the moderation rule routes (admin-only), nested under `/admin`.
+pub fn moderation_rule_routes() -> Scope {
+    web::scope("/rules")
+        .service(list_rules)
+        .service(create_rule)
+        .service(update_rule)
+        .service(delete_rule)
+}
+
+/// Creates the flagged content routes (moderators), nested under `/admin`.
+pub fn flagged_content_routes() -> Scope {
+    web::scope("/moderation/flagged")
+        .service(list_flagged)
+        .service(dismiss_flag)
+}
+
+/// Lists all moderation rules with their hit rates.
+#[get("")]
+async fn list_rules(
+    _admin: AdminUser,
+    service: web::Data<ModerationService>,
+) -> Result<impl Responder, AppError> {
+    let rules = service.list_rules().await?;
+    Ok(HttpResponse::Ok().json(rules))
+}
+
+/// Creates a moderation rule.
+#[post("")]
+async fn create_rule(
+    _admin: AdminUser,
+    service: web::Data<ModerationService>,
+    payload: Json<CreateModerationRuleRequest>,
+) -> Result<impl Responder, AppError> {
+    let rule = service.create_rule(payload.into_inner()).await?;
+    Ok(HttpResponse::Created().json(rule))
+}
+
+/// Changes a moderation rule.
+#[put("/{id}")]
+async fn update_rule(
+    _admin: AdminUser,
+    service: web::Data<ModerationService>,
+    path: web::Path<i64>,
+    payload: Json<UpdateModerationRuleRequest>,
+) -> Result<impl Responder, AppError> {
+    let rule = service
+        .update_rule(path.into_inner(), payload.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok().json(rule))
+}
+
+/// Deletes a moderation rule.
+#[delete("/{id}")]
+async fn delete_rule(
+    _admin: AdminUser,
+    service: web::Data<ModerationService>,
+    path: web::Path<i64>,
+) -> Result<impl Responder, AppError> {
+    service.delete_rule(path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Lists posts and comments flagged by moderation rules, newest first.
+#[get("")]
+async fn list_flagged(
+    _moderator: ModeratorUser,
+    service: web::Data<ModerationService>,
+    query: Query<AdminListQuery>,
+) -> Result<impl Responder, AppError> {
+    let Pagination { limit, offset } = Pagination::clamp(query.limit, query.offset);
+    let flagged = service.flagged(limit, offset).await?;
+    Ok(HttpResponse::Ok().json(flagged))
+}
+
+/// Dismisses a flag, leaving the post or comment in place.
+#[delete("/{id}")]
+async fn dismiss_flag(
+    _moderator: ModeratorUser,
+    service: web::Data<ModerationService>,
+    path: web::Path<i64>,
+) -> Result<impl Responder, AppError> {
+    service.dismiss_flag(path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
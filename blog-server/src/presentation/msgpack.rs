@@ -0,0 +1,120 @@
+//! MessagePack content negotiation for the HTTP API.
+//!
+//! Handlers only speak JSON. Request bodies sent as `application/msgpack` are
+//! converted to JSON before they reach them, and successful JSON responses
+//! are converted to MessagePack for clients that send
+//! `Accept: application/msgpack`. Errors stay JSON.
+
+use actix_web::body::{self, BoxBody, MessageBody};
+use actix_web::dev::{Payload, ServiceRequest, ServiceResponse};
+use actix_web::error::{ErrorInternalServerError, PayloadError};
+use actix_web::http::header::{self, HeaderMap, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::{Error, HttpMessage, web};
+use blog_shared::constants::MSGPACK_CONTENT_TYPE;
+use serde_json::Value;
+use tokio_stream::StreamExt;
+
+use crate::constants::MSGPACK_BODY_LIMIT;
+use crate::domain::AppError;
+
+/// Converts MessagePack request bodies to JSON and JSON responses to
+/// MessagePack when the client accepts it.
+pub async fn msgpack_negotiation(
+    mut req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    if has_media_type(req.headers(), header::CONTENT_TYPE)
+        && let Err(err) = convert_body(&mut req).await
+    {
+        return Ok(req.error_response(err));
+    }
+    let accepts_msgpack = has_media_type(req.headers(), header::ACCEPT);
+
+    let mut res = next.call(req).await?;
+    let is_json = res
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    if !is_json {
+        return Ok(res.map_into_boxed_body());
+    }
+
+    // Caches must keep the two representations apart
+    res.headers_mut()
+        .append(header::VARY, HeaderValue::from_static("Accept"));
+    if !accepts_msgpack || !res.status().is_success() {
+        return Ok(res.map_into_boxed_body());
+    }
+
+    let (req, res) = res.into_parts();
+    let (mut res, body) = res.into_parts();
+    let bytes = body::to_bytes(body).await.map_err(|e| {
+        let err: Box<dyn std::error::Error> = e.into();
+        ErrorInternalServerError(err.to_string())
+    })?;
+    let value: Value = serde_json::from_slice(&bytes).map_err(ErrorInternalServerError)?;
+    let packed = rmp_serde::to_vec(&value).map_err(ErrorInternalServerError)?;
+
+    // Validators describe the JSON body, so they must not be reused for this one
+    let headers = res.headers_mut();
+    headers.remove(header::ETAG);
+    headers.remove(header::LAST_MODIFIED);
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(MSGPACK_CONTENT_TYPE),
+    );
+
+    let res = res.set_body(packed).map_into_boxed_body();
+    Ok(ServiceResponse::new(req, res))
+}
+
+/// Checks whether a media-type header (`Content-Type` or `Accept`) names
+/// MessagePack.
+fn has_media_type(headers: &HeaderMap, name: header::HeaderName) -> bool {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value
+                .split(',')
+                .any(|media| media.split(';').next().map(str::trim) == Some(MSGPACK_CONTENT_TYPE))
+        })
+}
+
+/// Replaces a MessagePack request body with the same value as JSON.
+async fn convert_body(req: &mut ServiceRequest) -> Result<(), Error> {
+    let json = msgpack_to_json(&read_body(req).await?)?;
+    let headers = req.headers_mut();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    headers.insert(header::CONTENT_LENGTH, HeaderValue::from(json.len()));
+    req.set_payload(Payload::from(json));
+    Ok(())
+}
+
+/// Reads the whole request body, up to `MSGPACK_BODY_LIMIT` bytes.
+async fn read_body(req: &mut ServiceRequest) -> Result<web::BytesMut, Error> {
+    let mut payload = req.take_payload();
+    let mut body = web::BytesMut::new();
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk?;
+        if body.len() + chunk.len() > MSGPACK_BODY_LIMIT {
+            return Err(PayloadError::Overflow.into());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// Re-encodes a MessagePack body as JSON.
+fn msgpack_to_json(body: &[u8]) -> Result<Vec<u8>, AppError> {
+    let value: Value = rmp_serde::from_slice(body).map_err(|e| AppError::InvalidRequest {
+        message: format!("Invalid MessagePack body: {e}"),
+        pointer: None,
+    })?;
+    serde_json::to_vec(&value).map_err(|e| AppError::Internal(e.to_string()))
+}
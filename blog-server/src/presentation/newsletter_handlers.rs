@@ -0,0 +1,62 @@
+//! HTTP handlers for newsletter subscriptions.
+
+use actix_web::{HttpResponse, Responder, Scope, get, post, route, web};
+use blog_shared::SubscribeRequest;
+use lettre::Address;
+use serde::Deserialize;
+
+use crate::application::NewsletterService;
+use crate::constants::MAX_SUBSCRIBER_EMAIL_LEN;
+use crate::domain::AppError;
+use crate::presentation::payload::{Json, Query};
+
+/// Creates the newsletter routes (public; confirm and unsubscribe are the
+/// links sent by email).
+pub fn newsletter_routes() -> Scope {
+    web::scope("/newsletter")
+        .service(subscribe)
+        .service(confirm)
+        .service(unsubscribe)
+}
+
+/// Query string of the links sent by email.
+#[derive(Debug, Deserialize)]
+pub struct TokenQuery {
+    pub token: String,
+}
+
+/// Subscribes an email address to the weekly digest and sends a confirmation email.
+#[post("/subscribe")]
+async fn subscribe(
+    service: web::Data<NewsletterService>,
+    payload: Json<SubscribeRequest>,
+) -> Result<impl Responder, AppError> {
+    let email = payload.email.trim().to_lowercase();
+    if email.len() > MAX_SUBSCRIBER_EMAIL_LEN || email.parse::<Address>().is_err() {
+        return Err(AppError::Validation("Invalid email address".to_string()));
+    }
+
+    let status = service.subscribe(&email).await?;
+    Ok(HttpResponse::Accepted().json(status))
+}
+
+/// Confirms a subscription (link from the confirmation email).
+#[get("/confirm")]
+async fn confirm(
+    service: web::Data<NewsletterService>,
+    query: Query<TokenQuery>,
+) -> Result<impl Responder, AppError> {
+    let status = service.confirm(&query.token).await?;
+    Ok(HttpResponse::Ok().json(status))
+}
+
+/// Unsubscribes (link in every digest). `POST` serves one-click unsubscribe
+/// from mail clients that honour `List-Unsubscribe`.
+#[route("/unsubscribe", method = "GET", method = "POST")]
+async fn unsubscribe(
+    service: web::Data<NewsletterService>,
+    query: Query<TokenQuery>,
+) -> Result<impl Responder, AppError> {
+    let status = service.unsubscribe(&query.token).await?;
+    Ok(HttpResponse::Ok().json(status))
+}
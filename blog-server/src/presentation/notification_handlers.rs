@@ -0,0 +1,49 @@
+//! HTTP handlers for the current user's notifications.
+
+use actix_web::{HttpResponse, Responder, web};
+use blog_shared::{CursorPage, MarkNotificationsReadRequest};
+use serde::Deserialize;
+
+use crate::application::NotificationService;
+use crate::domain::AppError;
+use crate::presentation::middleware::AuthenticatedUser;
+use crate::presentation::payload::{Json, Query};
+
+/// Query parameters for listing notifications.
+#[derive(Debug, Deserialize)]
+pub struct NotificationListQuery {
+    pub limit: Option<i64>,
+    /// `next_cursor` of the previous page.
+    pub cursor: Option<String>,
+}
+
+/// Lists the current user's notifications, newest first.
+pub async fn list_notifications(
+    auth: AuthenticatedUser,
+    service: web::Data<NotificationService>,
+    query: Query<NotificationListQuery>,
+) -> Result<impl Responder, AppError> {
+    let query = query.into_inner();
+    let page = CursorPage::clamp(query.limit, query.cursor);
+    let notifications = service.list(auth.user_id, &page).await?;
+    Ok(HttpResponse::Ok().json(notifications))
+}
+
+/// Returns the number of unread notifications (for the notification bell).
+pub async fn unread_notification_count(
+    auth: AuthenticatedUser,
+    service: web::Data<NotificationService>,
+) -> Result<impl Responder, AppError> {
+    let unread = service.unread_count(auth.user_id).await?;
+    Ok(HttpResponse::Ok().json(unread))
+}
+
+/// Marks the current user's notifications as read.
+pub async fn mark_notifications_read(
+    auth: AuthenticatedUser,
+    service: web::Data<NotificationService>,
+    payload: Json<MarkNotificationsReadRequest>,
+) -> Result<impl Responder, AppError> {
+    service.mark_read(auth.user_id, payload.up_to_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,95 @@
+//! HTTP handlers for organizations, their members, and profile pages.
+
+use actix_web::{HttpResponse, Responder, Scope, delete, get, post, put, web};
+use blog_shared::{CreateOrgRequest, Pagination, SetOrgMemberRequest};
+use serde::Deserialize;
+
+use crate::application::OrgService;
+use crate::domain::AppError;
+use crate::presentation::middleware::{AuthenticatedUser, OptionalUser};
+use crate::presentation::payload::{Json, Query};
+
+/// Creates the organization routes (profiles are public, the rest require
+/// authentication).
+pub fn org_routes() -> Scope {
+    web::scope("/orgs")
+        .service(create_org)
+        .service(org_profile)
+        .service(set_member)
+        .service(remove_member)
+}
+
+/// Query parameters for an organization's profile page.
+#[derive(Debug, Deserialize)]
+pub struct OrgProfileQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// Lists the organizations the current user belongs to.
+#[get("/me/orgs")]
+pub async fn my_orgs(
+    auth: AuthenticatedUser,
+    service: web::Data<OrgService>,
+) -> Result<impl Responder, AppError> {
+    let orgs = service.list_memberships(auth.user_id).await?;
+    Ok(HttpResponse::Ok().json(orgs))
+}
+
+/// Creates an organization owned by the current user.
+#[post("")]
+async fn create_org(
+    auth: AuthenticatedUser,
+    service: web::Data<OrgService>,
+    payload: Json<CreateOrgRequest>,
+) -> Result<impl Responder, AppError> {
+    let org = service
+        .create_org(auth.user_id, payload.into_inner())
+        .await?;
+    Ok(HttpResponse::Created().json(org))
+}
+
+/// Returns an organization's profile with its members and a page of its
+/// posts (public).
+#[get("/{slug}")]
+async fn org_profile(
+    viewer: OptionalUser,
+    service: web::Data<OrgService>,
+    path: web::Path<String>,
+    query: Query<OrgProfileQuery>,
+) -> Result<impl Responder, AppError> {
+    let Pagination { limit, offset } = Pagination::clamp(query.limit, query.offset);
+    let profile = service
+        .org_profile(&path, limit, offset, viewer.user_id())
+        .await?;
+    Ok(HttpResponse::Ok().json(profile))
+}
+
+/// Adds a member or changes their role (owners only).
+#[put("/{slug}/members/{username}")]
+async fn set_member(
+    auth: AuthenticatedUser,
+    service: web::Data<OrgService>,
+    path: web::Path<(String, String)>,
+    payload: Json<SetOrgMemberRequest>,
+) -> Result<impl Responder, AppError> {
+    let (slug, username) = path.into_inner();
+    service
+        .set_member(&slug, auth.user_id, &username, payload.role)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Removes a member (owners only).
+#[delete("/{slug}/members/{username}")]
+async fn remove_member(
+    auth: AuthenticatedUser,
+    service: web::Data<OrgService>,
+    path: web::Path<(String, String)>,
+) -> Result<impl Responder, AppError> {
+    let (slug, username) = path.into_inner();
+    service
+        .remove_member(&slug, auth.user_id, &username)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,200 @@
+//! Panic safety for HTTP and gRPC: a panicking handler or service answers
+//! with a 500 (gRPC `INTERNAL`) instead of dropping the connection.
+//!
+//! Caught panics are logged with `tracing` and reported to the error tracker
+//! with their request; the panic hook stays quiet about them.
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::StatusCode;
+use actix_web::http::header::{
+    ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS, ORIGIN, VARY,
+};
+use actix_web::middleware::Next;
+use actix_web::{Error, HttpMessage, HttpResponse, ResponseError};
+use blog_shared::ErrorResponse;
+use blog_shared::constants::CORS_ALLOWED_ORIGINS;
+use tonic::Status;
+use tonic::codegen::http;
+use tonic::metadata::MetadataValue;
+use tower::{Layer, Service};
+use tracing::error;
+
+use crate::application::TelemetryService;
+use crate::constants::REQUEST_ID_HEADER;
+use crate::domain::{AppError, ErrorReport, ErrorSource, RequestContext};
+use crate::infrastructure::panic_guard::{CaughtPanic, catch_panic};
+use crate::presentation::request_id::{RequestId, generate_request_id, is_valid_request_id};
+
+/// A panic in an HTTP handler, answered with a JSON 500 carrying the
+/// request ID.
+#[derive(Debug)]
+pub struct PanicError {
+    pub panic: CaughtPanic,
+    pub request_id: Option<String>,
+    /// `Origin` of the request, when CORS allows it. The CORS middleware
+    /// only decorates responses, so errors carry its headers themselves.
+    pub origin: Option<String>,
+}
+
+impl fmt::Display for PanicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "handler panicked: {}", self.panic)
+    }
+}
+
+impl ResponseError for PanicError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let mut builder = HttpResponse::InternalServerError();
+        if let Some(id) = &self.request_id {
+            builder.insert_header((REQUEST_ID_HEADER, id.as_str()));
+        }
+        if let Some(origin) = &self.origin {
+            builder
+                .insert_header((ACCESS_CONTROL_ALLOW_ORIGIN, origin.as_str()))
+                .insert_header((ACCESS_CONTROL_EXPOSE_HEADERS, REQUEST_ID_HEADER))
+                .insert_header((VARY, ORIGIN.as_str()));
+        }
+        builder.json(
+            ErrorResponse::new("Internal server error".to_string())
+                .with_code(AppError::Internal(String::new()).code())
+                .with_request_id(self.request_id.clone()),
+        )
+    }
+}
+
+/// HTTP middleware answering a panic with a [`PanicError`].
+///
+/// The request is gone with the panicking handler, so the error is returned
+/// rather than a response: middleware outside this one sees it as an `Err`
+/// (`report_server_errors` reports it as a panic). Wrap it inside
+/// `request_id`.
+pub async fn catch_panics(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let request_id = req.extensions().get::<RequestId>().map(|id| id.0.clone());
+    let method = req.method().clone();
+    let path = req.path().to_string();
+    let origin = req
+        .headers()
+        .get(ORIGIN)
+        .and_then(|value| value.to_str().ok())
+        .filter(|origin| CORS_ALLOWED_ORIGINS.contains(origin))
+        .map(str::to_string);
+    let panic = match catch_panic(next.call(req)).await {
+        Ok(result) => return result,
+        Err(panic) => panic,
+    };
+
+    error!(%panic, %method, %path, "request handler panicked");
+    Err(PanicError {
+        panic,
+        request_id,
+        origin,
+    }
+    .into())
+}
+
+/// Tower layer answering panics in the gRPC services with `INTERNAL`.
+#[derive(Clone, Default)]
+pub struct CatchPanicLayer {
+    telemetry: TelemetryService,
+}
+
+impl CatchPanicLayer {
+    /// Create a new layer reporting panics to `telemetry`.
+    pub fn new(telemetry: TelemetryService) -> Self {
+        Self { telemetry }
+    }
+}
+
+impl<S> Layer<S> for CatchPanicLayer {
+    type Service = CatchPanicService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CatchPanicService {
+            inner,
+            telemetry: self.telemetry.clone(),
+        }
+    }
+}
+
+/// gRPC service wrapper created by [`CatchPanicLayer`].
+#[derive(Clone)]
+pub struct CatchPanicService<S> {
+    inner: S,
+    telemetry: TelemetryService,
+}
+
+impl<S, ReqBody, ResBody> Service<http::Request<ReqBody>> for CatchPanicService<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+    ResBody: Default,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<ReqBody>) -> Self::Future {
+        // Take the service that was driven to readiness, leaving a clone behind
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let telemetry = self.telemetry.clone();
+
+        let path = req.uri().path().to_string();
+        let request_id = req
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .filter(|value| is_valid_request_id(value))
+            .map(str::to_string)
+            .unwrap_or_else(generate_request_id);
+        let user_agent = req
+            .headers()
+            .get(http::header::USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+
+        Box::pin(async move {
+            let panic = match catch_panic(async move { inner.call(req).await }).await {
+                Ok(result) => return result,
+                Err(panic) => panic,
+            };
+
+            error!(%panic, %path, %request_id, "gRPC handler panicked");
+            telemetry.capture(
+                ErrorReport::new(ErrorSource::Panic, panic.message)
+                    .with_location(panic.location)
+                    .with_request(RequestContext {
+                        method: "POST".to_string(),
+                        path,
+                        request_id: Some(request_id.clone()),
+                        status: None,
+                        user_agent,
+                    }),
+            );
+
+            let mut status = Status::internal("Internal server error");
+            if let Ok(value) = MetadataValue::try_from(request_id.as_str()) {
+                status.metadata_mut().insert(REQUEST_ID_HEADER, value);
+            }
+            Ok(status.into_http())
+        })
+    }
+}
@@ -0,0 +1,145 @@
+//! JSON body and query string extractors with field-level errors.
+//!
+//! `actix_web::web::Json` and `web::Query` answer malformed input with a
+//! bare 400. These extractors report it as an `ErrorResponse` with code
+//! `invalid_request` and a JSON pointer (RFC 6901) to the offending field,
+//! e.g. `/tags/1` or `/limit`.
+
+use std::fmt::Display;
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+
+use actix_web::error::JsonPayloadError;
+use actix_web::{FromRequest, HttpRequest, dev::Payload, web};
+use serde::de::DeserializeOwned;
+use serde_path_to_error::{Path, Segment};
+
+use crate::domain::AppError;
+
+/// JSON request body, deserialized with the offending field's path on
+/// error. Size limits come from the `JsonConfig` in app data.
+#[derive(Debug)]
+pub struct Json<T>(pub T);
+
+impl<T> Json<T> {
+    /// Unwraps the deserialized body.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Json<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Json<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: DeserializeOwned + 'static> FromRequest for Json<T> {
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        // Syntax, size, and content type errors surface here, through the
+        // `JsonConfig` error handler
+        let value = web::Json::<serde_json::Value>::from_request(req, payload);
+        Box::pin(async move {
+            let value = value.await?.into_inner();
+            serde_path_to_error::deserialize(value)
+                .map(Json)
+                .map_err(|e| invalid_field(e).into())
+        })
+    }
+}
+
+/// Query string, deserialized with the offending parameter on error.
+#[derive(Debug)]
+pub struct Query<T>(pub T);
+
+impl<T> Query<T> {
+    /// Unwraps the deserialized query.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Query<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: DeserializeOwned> FromRequest for Query<T> {
+    type Error = AppError;
+    type Future = std::future::Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let pairs = form_urlencoded::parse(req.query_string().as_bytes());
+        let result = serde_path_to_error::deserialize(serde_urlencoded::Deserializer::new(pairs))
+            .map(Query)
+            .map_err(invalid_field);
+        std::future::ready(result)
+    }
+}
+
+/// Returns the app-wide `JsonConfig`, whose error handler reports
+/// malformed JSON as `invalid_request`.
+pub fn json_config() -> web::JsonConfig {
+    web::JsonConfig::default().error_handler(|err, _req| match err {
+        JsonPayloadError::Deserialize(e) => AppError::InvalidRequest {
+            message: e.to_string(),
+            pointer: None,
+        }
+        .into(),
+        other => other.into(),
+    })
+}
+
+/// Converts a deserialization error into `AppError::InvalidRequest`,
+/// pointing at the field it occurred in.
+fn invalid_field<E: Display>(err: serde_path_to_error::Error<E>) -> AppError {
+    let mut pointer = json_pointer(err.path());
+    let message = err.into_inner().to_string();
+
+    // serde reports a missing field at its parent; point at the field itself
+    if let Some(field) = message
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split('`').next())
+    {
+        pointer.push('/');
+        pointer.push_str(&escape_token(field));
+    }
+
+    AppError::InvalidRequest {
+        message,
+        pointer: (!pointer.is_empty()).then_some(pointer),
+    }
+}
+
+/// Formats a serde path as a JSON pointer (empty for the root).
+fn json_pointer(path: &Path) -> String {
+    path.iter()
+        .filter_map(|segment| match segment {
+            Segment::Seq { index } => Some(index.to_string()),
+            Segment::Map { key } => Some(escape_token(key)),
+            Segment::Enum { variant } => Some(escape_token(variant)),
+            Segment::Unknown => None,
+        })
+        .map(|token| format!("/{token}"))
+        .collect()
+}
+
+/// Escapes a JSON pointer reference token (`~` and `/`).
+fn escape_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
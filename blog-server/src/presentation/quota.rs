@@ -0,0 +1,76 @@
+//! Daily quotas of service accounts: counting requests and the
+//! `X-RateLimit-*` response headers.
+
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::Method;
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::{Error, HttpMessage, HttpRequest, web};
+
+use crate::application::QuotaService;
+use crate::domain::{AppError, QuotaStatus};
+
+/// Outcome of counting the current request, stored in request extensions so
+/// a request is counted once however many extractors authenticate it. A
+/// refusal keeps the exhausted limit and the seconds until it resets.
+#[derive(Debug, Clone, Copy)]
+struct QuotaOutcome(Result<Option<QuotaStatus>, (QuotaStatus, i64)>);
+
+/// Rebuilds the error refusing a request from its stored outcome.
+fn exceeded((status, retry_after_secs): (QuotaStatus, i64)) -> AppError {
+    AppError::QuotaExceeded {
+        status,
+        retry_after_secs,
+    }
+}
+
+/// Counts a request made with a service token against the account's quotas.
+///
+/// Does nothing when no `QuotaService` is configured.
+pub(crate) async fn count_request(req: &HttpRequest, account_id: i64) -> Result<(), AppError> {
+    if let Some(QuotaOutcome(outcome)) = req.extensions().get::<QuotaOutcome>().copied() {
+        return outcome.map(|_| ()).map_err(exceeded);
+    }
+    let Some(quotas) = req.app_data::<web::Data<QuotaService>>() else {
+        return Ok(());
+    };
+
+    let is_write = !matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS);
+    let outcome = match quotas.record(account_id, is_write).await {
+        Ok(status) => Ok(status),
+        Err(AppError::QuotaExceeded {
+            status,
+            retry_after_secs,
+        }) => Err((status, retry_after_secs)),
+        Err(e) => return Err(e),
+    };
+    req.extensions_mut().insert(QuotaOutcome(outcome));
+
+    outcome.map(|_| ()).map_err(exceeded)
+}
+
+/// Adds the `X-RateLimit-*` headers to responses to requests counted
+/// against a quota (429 responses carry them already).
+pub async fn quota_headers(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let mut res = next.call(req).await?;
+
+    let status = res
+        .request()
+        .extensions()
+        .get::<QuotaOutcome>()
+        .and_then(|QuotaOutcome(outcome)| outcome.ok().flatten());
+    if let Some(status) = status {
+        for (name, value) in status.headers() {
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                res.headers_mut()
+                    .insert(HeaderName::from_static(name), value);
+            }
+        }
+    }
+
+    Ok(res)
+}
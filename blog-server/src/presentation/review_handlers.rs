@@ -0,0 +1,81 @@
+//! HTTP handlers for the editorial workflow.
+
+use actix_web::{HttpResponse, Responder, get, post, web};
+use blog_shared::{Pagination, ReviewPostRequest};
+
+use crate::application::{AuthService, BlogService};
+use crate::domain::{AppError, EntityRef};
+use crate::presentation::admin_handlers::AdminListQuery;
+use crate::presentation::middleware::{AuthenticatedUser, EditorUser};
+use crate::presentation::payload::{Json, Query};
+
+/// Submits a draft for review (author only).
+#[post("/posts/{id}/submit")]
+pub async fn submit_post(
+    auth: AuthenticatedUser,
+    service: web::Data<BlogService>,
+    path: web::Path<EntityRef>,
+) -> Result<impl Responder, AppError> {
+    let id = service.resolve_post(&path).await?;
+    let post = service.submit_post(id, auth.user_id).await?;
+    Ok(HttpResponse::Ok().json(post))
+}
+
+/// Approves a post in review or requests changes (editors).
+#[post("/posts/{id}/review")]
+pub async fn review_post(
+    editor: EditorUser,
+    auth_service: web::Data<AuthService>,
+    service: web::Data<BlogService>,
+    path: web::Path<EntityRef>,
+    payload: Json<ReviewPostRequest>,
+) -> Result<impl Responder, AppError> {
+    let id = service.resolve_post(&path).await?;
+    let role = editor.0.role(&auth_service).await?;
+    let post = service
+        .review_post(id, editor.0.user_id, role, payload.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok().json(post))
+}
+
+/// Publishes an approved post (author or editors).
+#[post("/posts/{id}/publish")]
+pub async fn publish_post(
+    auth: AuthenticatedUser,
+    auth_service: web::Data<AuthService>,
+    service: web::Data<BlogService>,
+    path: web::Path<EntityRef>,
+) -> Result<impl Responder, AppError> {
+    let id = service.resolve_post(&path).await?;
+    let role = auth.role(&auth_service).await?;
+    let post = service.publish_post(id, auth.user_id, role).await?;
+    Ok(HttpResponse::Ok().json(post))
+}
+
+/// Lists a post's status changes and review comments (author or editors).
+#[get("/posts/{id}/history")]
+pub async fn post_history(
+    auth: AuthenticatedUser,
+    auth_service: web::Data<AuthService>,
+    service: web::Data<BlogService>,
+    path: web::Path<EntityRef>,
+) -> Result<impl Responder, AppError> {
+    let id = service.resolve_post(&path).await?;
+    let role = auth.role(&auth_service).await?;
+    let history = service.post_history(id, auth.user_id, role).await?;
+    Ok(HttpResponse::Ok().json(history))
+}
+
+/// Lists posts waiting for review (registered under `/admin`).
+#[get("/review-queue")]
+pub async fn review_queue(
+    editor: EditorUser,
+    service: web::Data<BlogService>,
+    query: Query<AdminListQuery>,
+) -> Result<impl Responder, AppError> {
+    let Pagination { limit, offset } = Pagination::clamp(query.limit, query.offset);
+    let posts = service
+        .review_queue(limit, offset, editor.0.user_id)
+        .await?;
+    Ok(HttpResponse::Ok().json(posts))
+}
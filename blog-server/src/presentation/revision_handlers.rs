@@ -0,0 +1,36 @@
+//! HTTP handlers for post revisions.
+
+use actix_web::{HttpResponse, Responder, web};
+
+use crate::application::{AuthService, BlogService};
+use crate::domain::{AppError, EntityRef};
+use crate::presentation::middleware::AuthenticatedUser;
+
+/// Lists a post's revisions, newest first (author or editors).
+pub async fn list_revisions(
+    auth: AuthenticatedUser,
+    auth_service: web::Data<AuthService>,
+    service: web::Data<BlogService>,
+    path: web::Path<EntityRef>,
+) -> Result<impl Responder, AppError> {
+    let id = service.resolve_post(&path).await?;
+    let role = auth.role(&auth_service).await?;
+    let revisions = service.list_revisions(id, auth.user_id, role).await?;
+    Ok(HttpResponse::Ok().json(revisions))
+}
+
+/// Diffs two revisions of a post (author or editors).
+pub async fn diff_revisions(
+    auth: AuthenticatedUser,
+    auth_service: web::Data<AuthService>,
+    service: web::Data<BlogService>,
+    path: web::Path<(EntityRef, i64, i64)>,
+) -> Result<impl Responder, AppError> {
+    let (post, from, to) = path.into_inner();
+    let id = service.resolve_post(&post).await?;
+    let role = auth.role(&auth_service).await?;
+    let diff = service
+        .diff_revisions(id, auth.user_id, role, from, to)
+        .await?;
+    Ok(HttpResponse::Ok().json(diff))
+}
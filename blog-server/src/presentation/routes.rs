@@ -0,0 +1,38 @@
+//! Mounting handlers on the shared route catalog.
+//!
+//! Routes in [`blog_shared::routes`] are mounted through these helpers rather
+//! than with attribute macros, so the server serves exactly the paths and
+//! methods the clients build.
+
+use actix_web::http;
+use actix_web::{Resource, guard, web};
+use blog_shared::routes::{Method, Route};
+
+/// Resource answering only the route's method.
+///
+/// Like an attribute-macro handler, other methods fall through to the
+/// resources registered after it.
+pub fn resource<R: Route>() -> Resource {
+    web::resource(R::PATH).guard(guard::Method(method(R::METHOD)))
+}
+
+/// Resource for a cached GET route that also answers HEAD.
+pub fn resource_with_head<R: Route>() -> Resource {
+    debug_assert_eq!(R::METHOD, Method::Get);
+    web::resource(R::PATH).guard(guard::Any(guard::Get()).or(guard::Head()))
+}
+
+/// Method route for a resource that needs its own configuration, which
+/// answers `405 Method Not Allowed` to other methods.
+pub fn route<R: Route>() -> actix_web::Route {
+    web::method(method(R::METHOD))
+}
+
+fn method(method: Method) -> http::Method {
+    match method {
+        Method::Get => http::Method::GET,
+        Method::Post => http::Method::POST,
+        Method::Put => http::Method::PUT,
+        Method::Delete => http::Method::DELETE,
+    }
+}
@@ -0,0 +1,78 @@
+//! HTTP handlers for service accounts (admin-only, nested under `/admin`)
+//! and their owners' API usage.
+
+use actix_web::{HttpResponse, Responder, Scope, delete, get, post, put, web};
+use blog_shared::{CreateServiceAccountRequest, ServiceAccountQuotaRequest};
+
+use crate::application::{AuthService, QuotaService};
+use crate::domain::AppError;
+use crate::presentation::middleware::{AdminUser, AuthenticatedUser};
+use crate::presentation::payload::Json;
+
+/// Creates the service account routes.
+pub fn service_account_routes() -> Scope {
+    web::scope("/service-accounts")
+        .service(list_service_accounts)
+        .service(create_service_account)
+        .service(revoke_service_account)
+        .service(set_quota)
+}
+
+/// Reports today's usage and quotas of the service accounts acting as the
+/// current user.
+#[get("/me/api-usage")]
+pub async fn api_usage(
+    auth: AuthenticatedUser,
+    service: web::Data<QuotaService>,
+) -> Result<impl Responder, AppError> {
+    let usage = service.usage(auth.user_id).await?;
+    Ok(HttpResponse::Ok().json(usage))
+}
+
+/// Lists all service accounts, including revoked ones.
+#[get("")]
+async fn list_service_accounts(
+    _admin: AdminUser,
+    service: web::Data<AuthService>,
+) -> Result<impl Responder, AppError> {
+    let accounts = service.list_service_accounts().await?;
+    Ok(HttpResponse::Ok().json(accounts))
+}
+
+/// Creates a service account; the response holds its token.
+#[post("")]
+async fn create_service_account(
+    admin: AdminUser,
+    service: web::Data<AuthService>,
+    payload: Json<CreateServiceAccountRequest>,
+) -> Result<impl Responder, AppError> {
+    let created = service
+        .create_service_account(admin.0.user_id, payload.into_inner())
+        .await?;
+    Ok(HttpResponse::Created().json(created))
+}
+
+/// Revokes a service account's token.
+#[delete("/{id}")]
+async fn revoke_service_account(
+    _admin: AdminUser,
+    service: web::Data<AuthService>,
+    path: web::Path<i64>,
+) -> Result<impl Responder, AppError> {
+    service.revoke_service_account(path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Sets a service account's daily quotas.
+#[put("/{id}/quota")]
+async fn set_quota(
+    _admin: AdminUser,
+    service: web::Data<AuthService>,
+    path: web::Path<i64>,
+    payload: Json<ServiceAccountQuotaRequest>,
+) -> Result<impl Responder, AppError> {
+    let account = service
+        .set_service_account_quota(path.into_inner(), payload.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok().json(account))
+}
@@ -0,0 +1,238 @@
+//! HTTP handlers for the current user's settings.
+
+use actix_web::{HttpResponse, Responder, Scope, delete, get, post, put, web};
+use blog_shared::constants::{
+    MAX_AVATAR_URL_LENGTH, MAX_BIO_LENGTH, MAX_WEBSITE_LENGTH, MIN_PASSWORD_LENGTH,
+    SUPPORTED_LANGUAGES, SUPPORTED_LOCALES,
+};
+use blog_shared::{
+    ChangeEmailRequest, ChangePasswordRequest, ConfirmEmailChangeRequest, DeleteAccountRequest,
+    PreferencesDto, ProfileDto, RecoveryCodesResponse, RegenerateRecoveryCodesRequest,
+};
+
+use crate::application::{AuthService, SettingsService};
+use crate::domain::{AppError, canonical_profile_url, is_supported_locale, parse_timezone};
+use crate::presentation::crosspost_handlers::{
+    delete_integration, list_integrations, set_integration,
+};
+use crate::presentation::middleware::{AuthenticatedUser, SessionUser};
+use crate::presentation::payload::Json;
+
+/// Creates the settings routes (all but email confirmation require authentication).
+pub fn settings_routes() -> Scope {
+    web::scope("/settings")
+        .service(get_settings)
+        .service(update_profile)
+        .service(update_preferences)
+        .service(change_email)
+        .service(confirm_email_change)
+        .service(change_password)
+        .service(regenerate_recovery_codes)
+        .service(list_sessions)
+        .service(revoke_session)
+        .service(delete_account)
+        .service(list_integrations)
+        .service(set_integration)
+        .service(delete_integration)
+}
+
+/// Returns all settings of the current user.
+#[get("")]
+async fn get_settings(
+    auth: AuthenticatedUser,
+    service: web::Data<SettingsService>,
+) -> Result<impl Responder, AppError> {
+    let settings = service.get_settings(auth.user_id).await?;
+    Ok(HttpResponse::Ok().json(settings))
+}
+
+/// Replaces the current user's profile.
+#[put("/profile")]
+async fn update_profile(
+    auth: AuthenticatedUser,
+    service: web::Data<SettingsService>,
+    payload: Json<ProfileDto>,
+) -> Result<impl Responder, AppError> {
+    let mut profile = payload.into_inner();
+    profile.bio = profile.bio.trim().to_string();
+    profile.avatar_url = profile
+        .avatar_url
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty());
+    profile.website = match profile.website.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(url) => Some(canonical_profile_url(url).ok_or_else(|| {
+            AppError::Validation("Website must be an http:// or https:// URL".to_string())
+        })?),
+    };
+    validate_profile(&profile)?;
+
+    let profile = service.update_profile(auth.user_id, profile).await?;
+    Ok(HttpResponse::Ok().json(profile))
+}
+
+/// Replaces the current user's display preferences.
+#[put("/preferences")]
+async fn update_preferences(
+    auth: AuthenticatedUser,
+    service: web::Data<SettingsService>,
+    payload: Json<PreferencesDto>,
+) -> Result<impl Responder, AppError> {
+    let preferences = payload.into_inner();
+    if !SUPPORTED_LANGUAGES.contains(&preferences.language.as_str()) {
+        return Err(AppError::Validation(format!(
+            "Language must be one of: {}",
+            SUPPORTED_LANGUAGES.join(", ")
+        )));
+    }
+    if parse_timezone(&preferences.timezone).is_none() {
+        return Err(AppError::Validation(format!(
+            "Unknown time zone: {}",
+            preferences.timezone
+        )));
+    }
+    if !is_supported_locale(&preferences.locale) {
+        return Err(AppError::Validation(format!(
+            "Locale must be one of: {}",
+            SUPPORTED_LOCALES.join(", ")
+        )));
+    }
+
+    let preferences = service
+        .update_preferences(auth.user_id, preferences)
+        .await?;
+    Ok(HttpResponse::Ok().json(preferences))
+}
+
+/// Starts changing the current user's email (requires the current
+/// password); the new address gets a confirmation link.
+#[put("/email")]
+async fn change_email(
+    session: SessionUser,
+    service: web::Data<AuthService>,
+    payload: Json<ChangeEmailRequest>,
+) -> Result<impl Responder, AppError> {
+    let mut req = payload.into_inner();
+    req.new_email = req.new_email.trim().to_string();
+    if !req.new_email.contains('@') {
+        return Err(AppError::Validation("Invalid email address".to_string()));
+    }
+
+    let pending = service.change_email(session.0.user_id, req).await?;
+    Ok(HttpResponse::Accepted().json(pending))
+}
+
+/// Switches to the new email with the token from the confirmation link (no
+/// sign-in needed, as the token proves the address). A POST, so following
+/// the link alone changes nothing.
+#[post("/email/confirm")]
+async fn confirm_email_change(
+    service: web::Data<AuthService>,
+    payload: Json<ConfirmEmailChangeRequest>,
+) -> Result<impl Responder, AppError> {
+    let user = service.confirm_email_change(&payload.token).await?;
+    Ok(HttpResponse::Ok().json(user))
+}
+
+/// Changes the current user's password and signs out their other sessions.
+#[put("/password")]
+async fn change_password(
+    session: SessionUser,
+    service: web::Data<AuthService>,
+    payload: Json<ChangePasswordRequest>,
+) -> Result<impl Responder, AppError> {
+    let req = payload.into_inner();
+    if req.new_password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Password must be at least {MIN_PASSWORD_LENGTH} characters"
+        )));
+    }
+
+    service
+        .change_password(session.0.user_id, session.0.session_id.as_deref(), req)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Replaces the current user's recovery codes (requires the current
+/// password) and returns the new ones.
+#[post("/recovery-codes")]
+async fn regenerate_recovery_codes(
+    session: SessionUser,
+    service: web::Data<AuthService>,
+    payload: Json<RegenerateRecoveryCodesRequest>,
+) -> Result<impl Responder, AppError> {
+    let codes = service
+        .regenerate_recovery_codes(session.0.user_id, payload.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok().json(RecoveryCodesResponse { codes }))
+}
+
+/// Lists the current user's active sessions.
+#[get("/sessions")]
+async fn list_sessions(
+    session: SessionUser,
+    service: web::Data<SettingsService>,
+) -> Result<impl Responder, AppError> {
+    let sessions = service
+        .list_sessions(session.0.user_id, session.0.session_id.as_deref())
+        .await?;
+    Ok(HttpResponse::Ok().json(sessions))
+}
+
+/// Revokes one of the current user's sessions (signs that device out).
+#[delete("/sessions/{id}")]
+async fn revoke_session(
+    session: SessionUser,
+    service: web::Data<SettingsService>,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    service
+        .revoke_session(session.0.user_id, &path.into_inner())
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Deletes the current user's account with all their posts and comments.
+#[delete("/account")]
+async fn delete_account(
+    session: SessionUser,
+    service: web::Data<AuthService>,
+    payload: Json<DeleteAccountRequest>,
+) -> Result<impl Responder, AppError> {
+    service
+        .delete_account(session.0.user_id, payload.into_inner())
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Validates profile fields (already trimmed).
+fn validate_profile(profile: &ProfileDto) -> Result<(), AppError> {
+    if profile.bio.chars().count() > MAX_BIO_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Bio must be at most {MAX_BIO_LENGTH} characters"
+        )));
+    }
+    if let Some(ref url) = profile.avatar_url {
+        if url.len() > MAX_AVATAR_URL_LENGTH {
+            return Err(AppError::Validation(format!(
+                "Avatar URL must be at most {MAX_AVATAR_URL_LENGTH} characters"
+            )));
+        }
+        if !(url.starts_with("https://") || url.starts_with("http://")) {
+            return Err(AppError::Validation(
+                "Avatar URL must start with http:// or https://".to_string(),
+            ));
+        }
+    }
+    if profile
+        .website
+        .as_ref()
+        .is_some_and(|url| url.len() > MAX_WEBSITE_LENGTH)
+    {
+        return Err(AppError::Validation(format!(
+            "Website must be at most {MAX_WEBSITE_LENGTH} characters"
+        )));
+    }
+    Ok(())
+}
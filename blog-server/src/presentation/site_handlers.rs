@@ -0,0 +1,85 @@
+//! HTTP handlers for site-wide settings: the public settings and
+//! announcement banner, and their admin management (nested under `/admin`).
+
+use actix_web::{HttpRequest, HttpResponse, Responder, Scope, delete, get, put, web};
+use blog_shared::{SetAnnouncementRequest, UpdateSiteSettingsRequest};
+
+use crate::application::SiteService;
+use crate::domain::AppError;
+use crate::presentation::http_cache::{cache_config, cached_json};
+use crate::presentation::middleware::AdminUser;
+use crate::presentation::payload::Json;
+
+/// Creates the public site routes.
+pub fn site_routes() -> Scope {
+    web::scope("/site")
+        .service(get_settings)
+        .service(get_announcement)
+}
+
+/// Creates the site settings management routes.
+pub fn site_settings_admin_routes() -> Scope {
+    web::scope("/settings").service(update_settings)
+}
+
+/// Creates the announcement management routes.
+pub fn announcement_admin_routes() -> Scope {
+    web::scope("/announcement")
+        .service(set_announcement)
+        .service(clear_announcement)
+}
+
+/// Returns the site settings (public).
+#[get("/settings")]
+async fn get_settings(
+    req: HttpRequest,
+    service: web::Data<SiteService>,
+) -> Result<impl Responder, AppError> {
+    let settings = service.settings().await?;
+    let max_age = cache_config(&req).site_settings_max_age_secs;
+    cached_json(&req, &settings, settings.updated_at, max_age)
+}
+
+/// Returns the current announcement, if any (public).
+#[get("/announcement")]
+async fn get_announcement(
+    req: HttpRequest,
+    service: web::Data<SiteService>,
+) -> Result<impl Responder, AppError> {
+    let response = service.announcement().await?;
+    let last_modified = response.announcement.as_ref().map(|a| a.updated_at);
+    let max_age = cache_config(&req).announcement_max_age_secs;
+    cached_json(&req, &response, last_modified, max_age)
+}
+
+/// Sets the announcement banner.
+#[put("")]
+async fn set_announcement(
+    _admin: AdminUser,
+    service: web::Data<SiteService>,
+    body: Json<SetAnnouncementRequest>,
+) -> Result<impl Responder, AppError> {
+    let announcement = service.set_announcement(body.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(announcement))
+}
+
+/// Removes the announcement banner.
+#[delete("")]
+async fn clear_announcement(
+    _admin: AdminUser,
+    service: web::Data<SiteService>,
+) -> Result<impl Responder, AppError> {
+    service.clear_announcement().await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Changes the site settings.
+#[put("")]
+async fn update_settings(
+    _admin: AdminUser,
+    service: web::Data<SiteService>,
+    body: Json<UpdateSiteSettingsRequest>,
+) -> Result<impl Responder, AppError> {
+    let settings = service.update_settings(body.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(settings))
+}
@@ -0,0 +1,57 @@
+//! HTTP handlers suggesting users and tags as `@mentions` and `#tags` are
+//! typed (public).
+//!
+//! Answers depend only on the prefix, so they are small and cached by
+//! browsers and proxies for `CacheConfig::suggestion_max_age_secs`.
+
+use actix_web::{HttpRequest, Responder, get, web};
+use serde::Deserialize;
+
+use crate::application::{BlogService, SettingsService};
+use crate::constants::MAX_SUGGESTION_PREFIX_LEN;
+use crate::domain::AppError;
+use crate::presentation::http_cache::{cache_config, cached_json};
+use crate::presentation::payload::Query;
+
+/// Query parameters for suggestions.
+#[derive(Debug, Deserialize)]
+pub struct SuggestQuery {
+    /// What has been typed so far; empty suggests the most active.
+    #[serde(default)]
+    pub q: String,
+}
+
+impl SuggestQuery {
+    /// Returns the prefix, rejecting overly long ones.
+    fn prefix(&self) -> Result<&str, AppError> {
+        if self.q.chars().count() > MAX_SUGGESTION_PREFIX_LEN {
+            return Err(AppError::Validation(format!(
+                "Suggestion prefix must be at most {MAX_SUGGESTION_PREFIX_LEN} characters"
+            )));
+        }
+        Ok(&self.q)
+    }
+}
+
+/// Suggests users whose names start with `q`. Before `/users/{id}`.
+pub async fn suggest_users(
+    req: HttpRequest,
+    service: web::Data<SettingsService>,
+    query: Query<SuggestQuery>,
+) -> Result<impl Responder, AppError> {
+    let response = service.suggest_users(query.prefix()?).await?;
+    let max_age = cache_config(&req).suggestion_max_age_secs;
+    cached_json(&req, &response, None, max_age)
+}
+
+/// Suggests tags starting with `q`. Before `/tags/{name}`.
+#[get("/tags/suggest")]
+pub async fn suggest_tags(
+    req: HttpRequest,
+    service: web::Data<BlogService>,
+    query: Query<SuggestQuery>,
+) -> Result<impl Responder, AppError> {
+    let response = service.suggest_tags(query.prefix()?).await?;
+    let max_age = cache_config(&req).suggestion_max_age_secs;
+    cached_json(&req, &response, None, max_age)
+}
@@ -0,0 +1,84 @@
+//! HTTP handlers for tag landing pages and tag management (admin-only,
+//! nested under `/admin`).
+
+use actix_web::{HttpResponse, Responder, Scope, delete, get, post, put, web};
+use blog_shared::{MergeTagRequest, Pagination, UpdateTagRequest};
+use serde::Deserialize;
+
+use crate::application::BlogService;
+use crate::domain::AppError;
+use crate::presentation::middleware::{AdminUser, OptionalUser};
+use crate::presentation::payload::{Json, Query};
+
+/// Creates the tag management routes.
+pub fn tag_admin_routes() -> Scope {
+    web::scope("/tags")
+        .service(update_tag)
+        .service(merge_tag)
+        .service(delete_tag)
+}
+
+/// Query parameters for a tag's landing page.
+#[derive(Debug, Deserialize)]
+pub struct TagPageQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    /// Only posts in this language.
+    pub lang: Option<String>,
+}
+
+/// Returns a tag's description with a page of its posts (public).
+#[get("/tags/{name}")]
+pub async fn tag_page(
+    viewer: OptionalUser,
+    service: web::Data<BlogService>,
+    path: web::Path<String>,
+    query: Query<TagPageQuery>,
+) -> Result<impl Responder, AppError> {
+    let Pagination { limit, offset } = Pagination::clamp(query.limit, query.offset);
+    let page = service
+        .tag_page(
+            &path,
+            limit,
+            offset,
+            viewer.user_id(),
+            query.lang.as_deref(),
+        )
+        .await?;
+    Ok(HttpResponse::Ok().json(page))
+}
+
+/// Renames a tag or changes its description.
+#[put("/{name}")]
+async fn update_tag(
+    _admin: AdminUser,
+    service: web::Data<BlogService>,
+    path: web::Path<String>,
+    payload: Json<UpdateTagRequest>,
+) -> Result<impl Responder, AppError> {
+    let tag = service.update_tag(&path, payload.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(tag))
+}
+
+/// Merges a tag into another, moving its posts over.
+#[post("/{name}/merge")]
+async fn merge_tag(
+    _admin: AdminUser,
+    service: web::Data<BlogService>,
+    path: web::Path<String>,
+    payload: Json<MergeTagRequest>,
+) -> Result<impl Responder, AppError> {
+    let tag = service.merge_tags(&path, &payload.into).await?;
+    Ok(HttpResponse::Ok().json(tag))
+}
+
+/// Deletes a tag, removing it from all posts.
+#[delete("/{name}")]
+async fn delete_tag(
+    _admin: AdminUser,
+    service: web::Data<BlogService>,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    service.delete_tag(&path).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
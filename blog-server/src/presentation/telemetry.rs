@@ -0,0 +1,111 @@
+//! Error telemetry: reporting 5xx responses, and the endpoint clients report
+//! their crashes to.
+
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::StatusCode;
+use actix_web::http::header::USER_AGENT;
+use actix_web::middleware::Next;
+use actix_web::{Error, HttpMessage, HttpRequest, HttpResponse, Resource, Responder, web};
+use blog_shared::ClientErrorReport;
+use blog_shared::routes::ReportClientError;
+
+use crate::application::TelemetryService;
+use crate::constants::MAX_CLIENT_ERROR_BYTES;
+use crate::domain::{AppError, ErrorReport, ErrorSource, RequestContext};
+use crate::presentation::panics::PanicError;
+use crate::presentation::request_id::RequestId;
+use crate::presentation::routes::resource;
+
+/// Creates the client crash report route (public).
+pub fn telemetry_routes() -> Resource {
+    resource::<ReportClientError>().to(report_client_error)
+}
+
+/// Reports responses with a 5xx status, and errors that become one, with
+/// the request they answered; a `PanicError` from `catch_panics` is
+/// reported as a panic. Must be wrapped inside `request_id`.
+pub async fn report_server_errors(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let Some(telemetry) = req
+        .app_data::<web::Data<TelemetryService>>()
+        .filter(|telemetry| telemetry.is_enabled())
+        .cloned()
+    else {
+        return next.call(req).await;
+    };
+    let context = request_context(req.request());
+
+    let result = next.call(req).await;
+    if let Err(error) = &result
+        && let Some(PanicError { panic, .. }) = error.as_error::<PanicError>()
+    {
+        telemetry.capture(
+            ErrorReport::new(ErrorSource::Panic, panic.message.clone())
+                .with_location(panic.location.clone())
+                .with_request(RequestContext {
+                    status: Some(StatusCode::INTERNAL_SERVER_ERROR.as_u16()),
+                    ..context
+                }),
+        );
+        return result;
+    }
+    let (message, status) = match &result {
+        Ok(res) if res.status().is_server_error() => (
+            res.response()
+                .error()
+                .map_or_else(|| res.status().to_string(), |error| error.to_string()),
+            res.status(),
+        ),
+        Err(error) if error.as_response_error().status_code().is_server_error() => {
+            (error.to_string(), error.as_response_error().status_code())
+        }
+        _ => return result,
+    };
+    telemetry.capture(ErrorReport::new(ErrorSource::Server, message).with_request(
+        RequestContext {
+            status: Some(status.as_u16()),
+            ..context
+        },
+    ));
+    result
+}
+
+/// Forwards a client crash to the error tracker; dropped when none is
+/// configured.
+///
+/// Reads the JSON from the raw body, as `navigator.sendBeacon` can only
+/// send `text/plain` across origins without a preflight.
+async fn report_client_error(
+    req: HttpRequest,
+    telemetry: web::Data<TelemetryService>,
+    body: web::Bytes,
+) -> Result<impl Responder, AppError> {
+    if body.len() > MAX_CLIENT_ERROR_BYTES {
+        return Err(AppError::Validation(format!(
+            "Error reports are limited to {MAX_CLIENT_ERROR_BYTES} bytes"
+        )));
+    }
+    let report: ClientErrorReport = serde_json::from_slice(&body)
+        .map_err(|e| AppError::Validation(format!("Invalid error report: {e}")))?;
+
+    telemetry.capture_client_error(report, request_context(&req));
+    Ok(HttpResponse::Accepted().finish())
+}
+
+/// Method, path, request ID, and user agent of a request.
+fn request_context(req: &HttpRequest) -> RequestContext {
+    RequestContext {
+        method: req.method().to_string(),
+        path: req.path().to_string(),
+        request_id: req.extensions().get::<RequestId>().map(|id| id.0.clone()),
+        status: None,
+        user_agent: req
+            .headers()
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+    }
+}
@@ -0,0 +1,83 @@
+//! HTTP handlers for handing posts over to other users.
+
+use actix_web::{HttpResponse, Responder, Scope, get, post, web};
+use blog_shared::TransferPostRequest;
+
+use crate::application::{BlogService, TransferService};
+use crate::domain::{AppError, EntityRef};
+use crate::presentation::middleware::AuthenticatedUser;
+use crate::presentation::payload::Json;
+
+/// Creates the current user's transfer routes (require authentication).
+pub fn transfer_routes() -> Scope {
+    web::scope("/transfers")
+        .service(list_transfers)
+        .service(accept_transfer)
+        .service(decline_transfer)
+        .service(cancel_transfer)
+}
+
+/// Offers a post to another user (author only).
+#[post("/posts/{id}/transfer")]
+pub async fn transfer_post(
+    auth: AuthenticatedUser,
+    blog_service: web::Data<BlogService>,
+    service: web::Data<TransferService>,
+    path: web::Path<EntityRef>,
+    payload: Json<TransferPostRequest>,
+) -> Result<impl Responder, AppError> {
+    let id = blog_service.resolve_post(&path).await?;
+    let transfer = service
+        .transfer_post(id, auth.user_id, &payload.username)
+        .await?;
+    Ok(HttpResponse::Created().json(transfer))
+}
+
+/// Lists the current user's pending transfers, incoming and outgoing.
+#[get("")]
+async fn list_transfers(
+    auth: AuthenticatedUser,
+    service: web::Data<TransferService>,
+) -> Result<impl Responder, AppError> {
+    let transfers = service.list_transfers(auth.user_id).await?;
+    Ok(HttpResponse::Ok().json(transfers))
+}
+
+/// Accepts a transfer, taking over the post (recipient only).
+#[post("/{id}/accept")]
+async fn accept_transfer(
+    auth: AuthenticatedUser,
+    service: web::Data<TransferService>,
+    path: web::Path<i64>,
+) -> Result<impl Responder, AppError> {
+    let transfer = service
+        .accept_transfer(path.into_inner(), auth.user_id)
+        .await?;
+    Ok(HttpResponse::Ok().json(transfer))
+}
+
+/// Declines a transfer (recipient only).
+#[post("/{id}/decline")]
+async fn decline_transfer(
+    auth: AuthenticatedUser,
+    service: web::Data<TransferService>,
+    path: web::Path<i64>,
+) -> Result<impl Responder, AppError> {
+    let transfer = service
+        .decline_transfer(path.into_inner(), auth.user_id)
+        .await?;
+    Ok(HttpResponse::Ok().json(transfer))
+}
+
+/// Withdraws a transfer (sender only).
+#[post("/{id}/cancel")]
+async fn cancel_transfer(
+    auth: AuthenticatedUser,
+    service: web::Data<TransferService>,
+    path: web::Path<i64>,
+) -> Result<impl Responder, AppError> {
+    let transfer = service
+        .cancel_transfer(path.into_inner(), auth.user_id)
+        .await?;
+    Ok(HttpResponse::Ok().json(transfer))
+}
@@ -0,0 +1,40 @@
+//! HTTP handlers for authors' trash of deleted posts.
+
+use actix_web::{HttpResponse, Resource, Responder, web};
+use blog_shared::routes::ListTrash;
+
+use crate::application::BlogService;
+use crate::domain::{AppError, EntityRef};
+use crate::presentation::middleware::AuthenticatedUser;
+use crate::presentation::routes::resource;
+
+/// Creates the current user's trash routes (require authentication). Must
+/// be registered before `/posts/{id}`.
+pub fn trash_routes() -> Resource {
+    resource::<ListTrash>().to(list_trash)
+}
+
+/// Serves the trash at its old path, `/me/trash`, for older clients.
+pub fn legacy_trash_routes() -> Resource {
+    web::resource("/me/trash").route(web::get().to(list_trash))
+}
+
+/// Lists the current user's deleted posts and when each is purged.
+async fn list_trash(
+    auth: AuthenticatedUser,
+    service: web::Data<BlogService>,
+) -> Result<impl Responder, AppError> {
+    let trash = service.list_trash(auth.user_id).await?;
+    Ok(HttpResponse::Ok().json(trash))
+}
+
+/// Takes a post out of the trash (author only).
+pub async fn restore_post(
+    auth: AuthenticatedUser,
+    service: web::Data<BlogService>,
+    path: web::Path<EntityRef>,
+) -> Result<impl Responder, AppError> {
+    let id = service.resolve_post(&path).await?;
+    service.restore_post(id, auth.user_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
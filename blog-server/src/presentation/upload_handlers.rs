@@ -0,0 +1,128 @@
+//! HTTP handlers for content-addressed uploads.
+
+use actix_web::http::header::{self, CacheControl, CacheDirective, ETag, EntityTag, IfNoneMatch};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, Responder, Scope, get, web};
+use blog_shared::UploadQuery;
+use blog_shared::constants::{MAX_UPLOAD_BYTES, UPLOAD_TYPES};
+use serde::Deserialize;
+
+use crate::application::{SignedAccess, UploadService};
+use crate::constants::UPLOAD_CACHE_MAX_AGE_SECS;
+use crate::domain::AppError;
+use crate::presentation::middleware::AuthenticatedUser;
+use crate::presentation::payload::Query;
+
+/// Creates the upload routes (require authentication).
+pub fn upload_routes() -> Scope {
+    web::scope("/uploads")
+        .service(
+            web::resource("")
+                .app_data(web::PayloadConfig::new(MAX_UPLOAD_BYTES))
+                .route(web::post().to(upload)),
+        )
+        .service(web::resource("/{id}").route(web::get().to(get_upload)))
+}
+
+/// Stores the raw request body, typed by its `Content-Type`.
+async fn upload(
+    auth: AuthenticatedUser,
+    service: web::Data<UploadService>,
+    req: HttpRequest,
+    query: Query<UploadQuery>,
+    body: web::Bytes,
+) -> Result<impl Responder, AppError> {
+    let content_type = req
+        .mime_type()
+        .ok()
+        .flatten()
+        .map(|mime| mime.essence_str().to_string());
+    let accepted = |ct: &String| UPLOAD_TYPES.iter().any(|(media_type, _)| media_type == ct);
+    let Some(content_type) = content_type.filter(accepted) else {
+        let media_types: Vec<_> = UPLOAD_TYPES
+            .iter()
+            .map(|(media_type, _)| *media_type)
+            .collect();
+        return Err(AppError::Validation(format!(
+            "Content-Type must be one of {}",
+            media_types.join(", ")
+        )));
+    };
+    if body.is_empty() {
+        return Err(AppError::Validation("File cannot be empty".to_string()));
+    }
+
+    let upload = service
+        .upload(auth.user_id, &content_type, &body, query.private)
+        .await?;
+    Ok(HttpResponse::Created()
+        .insert_header((header::LOCATION, upload.url.clone()))
+        .json(upload))
+}
+
+/// Returns one of the current user's uploads, re-signing the URL of a
+/// private one, e.g. for a draft whose earlier URL has expired.
+async fn get_upload(
+    auth: AuthenticatedUser,
+    service: web::Data<UploadService>,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    let upload = service.get(auth.user_id, &path).await?;
+    Ok(HttpResponse::Ok().json(upload))
+}
+
+/// Signature of a URL to a private upload.
+#[derive(Debug, Deserialize)]
+pub struct SignedUrlQuery {
+    pub expires: Option<i64>,
+    pub signature: Option<String>,
+}
+
+/// Serves an uploaded file (public, outside `/api`).
+///
+/// Public uploads are cacheable forever. Private uploads need a signed URL
+/// and are only cached by the browser, until the URL expires.
+#[get("/uploads/{id}")]
+pub async fn serve_upload(
+    service: web::Data<UploadService>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    query: Query<SignedUrlQuery>,
+) -> Result<impl Responder, AppError> {
+    let access = match (query.expires, query.signature.as_deref()) {
+        (Some(expires), Some(signature)) => Some(SignedAccess { expires, signature }),
+        _ => None,
+    };
+    let (attachment, upload, bytes) = service.open(&path, access).await?;
+    let etag = EntityTag::new_strong(attachment.hash);
+    let cache_control = match access {
+        Some(access) if upload.private => CacheControl(vec![
+            CacheDirective::Private,
+            CacheDirective::MaxAge(service.seconds_left(access)),
+        ]),
+        _ => CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(UPLOAD_CACHE_MAX_AGE_SECS),
+            CacheDirective::Extension("immutable".to_string(), None),
+        ]),
+    };
+
+    let not_modified = match req.get_header::<IfNoneMatch>() {
+        Some(IfNoneMatch::Any) => true,
+        Some(IfNoneMatch::Items(tags)) => tags.iter().any(|tag| tag.weak_eq(&etag)),
+        None => false,
+    };
+    let mut builder = if not_modified {
+        HttpResponse::NotModified()
+    } else {
+        HttpResponse::Ok()
+    };
+    builder
+        .insert_header(ETag(etag))
+        .insert_header(cache_control)
+        .insert_header((header::X_CONTENT_TYPE_OPTIONS, "nosniff"));
+    if not_modified {
+        return Ok(builder.finish());
+    }
+
+    Ok(builder.content_type(upload.content_type).body(bytes))
+}
@@ -0,0 +1,546 @@
+//! Wiring of services and both transports into a runnable server.
+
+use std::error::Error;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_cors::Cors;
+use actix_web::dev::ServerHandle;
+use actix_web::http::KeepAlive;
+use actix_web::middleware::from_fn;
+use actix_web::{App, HttpServer, http, web};
+use blog_shared::RegistrationMode;
+use blog_shared::constants::CORS_ALLOWED_ORIGINS;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::Server as GrpcServer;
+use tonic_reflection::server::Builder as ReflectionBuilder;
+use tracing::{info, warn};
+
+use crate::application::{
+    AccountService, ActivityPubService, AdminService, AnalyticsService, AuthService, BlogService,
+    CommentService, CrosspostService, DiagnosticsService, ExperimentService, FeedService,
+    ImportService, LinkCheckService, LinkPreviewService, MessageService, MicropubService,
+    ModerationService, NewsletterService, NotificationService, OrgService, PresenceService,
+    QuotaService, RetentionService, SearchIndexService, SettingsService, SiteService,
+    TelemetryService, TransferService, UploadService,
+};
+use crate::constants;
+use crate::data::{
+    AccountRepository, ActivityPubRepository, AdminRepository, AnalyticsRepository,
+    AttachmentRepository, CommentRepository, CrosspostRepository, EmailChangeRepository,
+    ExperimentRepository, FeatureFlagRepository, FtsSearchIndex, InviteRepository,
+    LinkCheckRepository, LinkPreviewRepository, MessageRepository, ModerationRepository,
+    NewsletterRepository, NotificationRepository, OrgRepository, PostRepository,
+    RecoveryCodeRepository, RetentionRepository, ServiceAccountRepository, SiteSettingsRepository,
+    TransferRepository, UserRepository,
+};
+use crate::domain::{ApUrls, CrosspostApis, FeedUrls, SearchIndex};
+use crate::infrastructure::blob_store::BlobStore;
+use crate::infrastructure::clamav::ClamAv;
+use crate::infrastructure::event_bus::EventBus;
+use crate::infrastructure::http_fetcher::HttpFetcher;
+use crate::infrastructure::jwt::JwtConfig;
+use crate::infrastructure::mailer::Mailer;
+use crate::infrastructure::meilisearch::MeiliSearchIndex;
+use crate::infrastructure::sentry::SentryReporter;
+#[cfg(feature = "redis")]
+use crate::infrastructure::{config::QuotaBackend, redis_quota::RedisQuotaCounter};
+use crate::infrastructure::{
+    config::{Config, SearchBackend},
+    database, tls,
+};
+use crate::presentation::activitypub_handlers::{activitypub_routes, webfinger};
+use crate::presentation::api_routes;
+use crate::presentation::chaos::{ChaosLayer, chaos};
+use crate::presentation::debug_envelope::debug_envelope;
+use crate::presentation::diagnostics::record_failed_writes;
+use crate::presentation::grpc_service::proto::auth_service_server::AuthServiceServer;
+use crate::presentation::grpc_service::proto::blog_service_server::BlogServiceServer;
+use crate::presentation::grpc_service::{GrpcAuthService, GrpcBlogService};
+use crate::presentation::msgpack::msgpack_negotiation;
+use crate::presentation::panics::{CatchPanicLayer, catch_panics};
+use crate::presentation::payload::json_config;
+use crate::presentation::quota::quota_headers;
+use crate::presentation::request_id::request_id;
+use crate::presentation::telemetry::report_server_errors;
+use crate::presentation::upload_handlers::serve_upload;
+
+/// File descriptor set for gRPC reflection.
+const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("blog_descriptor");
+
+/// Error starting or running the server.
+pub type ServerError = Box<dyn Error + Send + Sync>;
+
+/// Both transports, bound but not yet serving, and the background jobs.
+pub struct Server {
+    /// Address the HTTP listener is bound to.
+    pub http_addr: SocketAddr,
+    /// Address the gRPC listener is bound to.
+    pub grpc_addr: SocketAddr,
+    http: actix_web::dev::Server,
+    grpc: Pin<Box<dyn Future<Output = Result<(), tonic::transport::Error>> + Send>>,
+    jobs: Vec<JoinHandle<()>>,
+}
+
+impl Server {
+    /// Handle stopping the HTTP server.
+    pub fn http_handle(&self) -> ServerHandle {
+        self.http.handle()
+    }
+
+    /// Serves both transports until either stops, then stops the jobs.
+    pub async fn run(self) -> Result<(), ServerError> {
+        let result = tokio::select! {
+            result = self.http => result.map_err(ServerError::from),
+            result = self.grpc => result.map_err(ServerError::from),
+        };
+        for job in self.jobs {
+            job.abort();
+        }
+        result
+    }
+}
+
+/// Connects the database, starts the background jobs, and binds both
+/// transports. The gRPC server stops once `grpc_shutdown` completes.
+pub async fn start(
+    config: Config,
+    http_addr: SocketAddr,
+    grpc_addr: SocketAddr,
+    grpc_shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<Server, ServerError> {
+    // Create database pool and run migrations
+    let pool = database::create_pool(&config.database_url).await?;
+    database::run_migrations(&pool).await?;
+
+    // Create repositories
+    let user_repo = Arc::new(UserRepository::new(pool.clone()));
+    let mut post_repo = PostRepository::new(pool.clone());
+    let mut newsletter_repo = NewsletterRepository::new(pool.clone());
+    let mut ap_repo = ActivityPubRepository::new(pool.clone());
+    if let Some(cipher) = &config.content_cipher {
+        post_repo = post_repo.with_cipher(cipher.clone());
+        newsletter_repo = newsletter_repo.with_cipher(cipher.clone());
+        ap_repo = ap_repo.with_cipher(cipher.clone());
+    }
+    let post_repo = Arc::new(post_repo);
+    let comment_repo = Arc::new(CommentRepository::new(pool.clone()));
+    let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+    let admin_repo = Arc::new(AdminRepository::new(pool.clone()));
+    let preview_repo = Arc::new(LinkPreviewRepository::new(pool.clone()));
+    let check_repo = Arc::new(LinkCheckRepository::new(pool.clone()));
+    let moderation_repo = Arc::new(ModerationRepository::new(pool.clone()));
+    let message_repo = Arc::new(MessageRepository::new(pool.clone()));
+    let newsletter_repo = Arc::new(newsletter_repo);
+    let ap_repo = Arc::new(ap_repo);
+    let crosspost_repo = Arc::new(CrosspostRepository::new(pool.clone()));
+    let attachment_repo = Arc::new(AttachmentRepository::new(pool.clone()));
+    let account_repo = Arc::new(AccountRepository::new(pool.clone()));
+    let recovery_repo = Arc::new(RecoveryCodeRepository::new(pool.clone()));
+    let service_account_repo = Arc::new(ServiceAccountRepository::new(pool.clone()));
+    let transfer_repo = Arc::new(TransferRepository::new(pool.clone()));
+    let org_repo = Arc::new(OrgRepository::new(pool.clone()));
+
+    // Outgoing email for newsletters and email changes
+    let mailer = Mailer::new(&config.mail)?;
+    if config.mail.smtp_url.is_none() {
+        warn!("SMTP_URL not set, emails will be logged instead of sent");
+    }
+
+    // Create services
+    // INVITE_ONLY sets the registration mode until admins pick one
+    let default_registration_mode = if config.invite_only {
+        RegistrationMode::InviteOnly
+    } else {
+        RegistrationMode::Open
+    };
+    let site_service = SiteService::new(Arc::new(SiteSettingsRepository::new(pool.clone())))
+        .with_default_registration_mode(default_registration_mode);
+    let experiment_service =
+        ExperimentService::new(Arc::new(ExperimentRepository::new(pool.clone())));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        Arc::clone(&flag_repo),
+        recovery_repo,
+        Arc::clone(&service_account_repo),
+        JwtConfig {
+            secret: config.jwt_secret.clone(),
+            issuer: config.jwt_issuer.clone(),
+            audience: config.jwt_audience.clone(),
+        },
+        config.reserved_usernames.clone(),
+    )
+    .with_invites(Arc::new(InviteRepository::new(pool.clone())))
+    .with_site(site_service.clone())
+    .with_experiments(experiment_service.clone())
+    .with_email_changes(
+        Arc::new(EmailChangeRepository::new(pool.clone())),
+        Arc::clone(&admin_repo),
+        mailer.clone(),
+        config.site_url.clone(),
+    );
+    let moderation_service = ModerationService::new(moderation_repo);
+    // Live events, relayed between replicas through Redis when configured
+    #[cfg(feature = "redis")]
+    let (events, event_relay) = match &config.redis_url {
+        Some(url) => {
+            let (events, relay) = EventBus::with_redis(url)?;
+            info!("Relaying live events through Redis");
+            (events, Some(relay))
+        }
+        None => (EventBus::new(), None),
+    };
+    #[cfg(not(feature = "redis"))]
+    let events = EventBus::new();
+    // Search index: the database's own, or a Meilisearch server fed from
+    // post change events
+    let fetcher = HttpFetcher::new()?;
+    // Error reports to Sentry, including every panic, when a DSN is set
+    let mut telemetry_service = TelemetryService::new();
+    if let Some(dsn) = config.telemetry.sentry_dsn.clone() {
+        info!(url = dsn.store_url(), "Reporting errors to Sentry");
+        telemetry_service = telemetry_service.with_reporter(Arc::new(SentryReporter::new(
+            fetcher.clone(),
+            dsn,
+            config.telemetry.environment.clone(),
+        )));
+        telemetry_service.install_panic_hook();
+    }
+    let search_index: Arc<dyn SearchIndex> = match &config.search_backend {
+        SearchBackend::Sqlite => Arc::new(FtsSearchIndex::new(Arc::clone(&post_repo))),
+        SearchBackend::Meilisearch { url, api_key } => {
+            let index = MeiliSearchIndex::new(fetcher.clone(), url.clone(), api_key.clone());
+            index.configure().await?;
+            info!(url, "Searching posts with Meilisearch");
+            Arc::new(index)
+        }
+    };
+    let blog_service = BlogService::new(
+        Arc::clone(&post_repo),
+        config.embed_providers.clone(),
+        moderation_service.clone(),
+    )
+    .with_flags(Arc::clone(&flag_repo))
+    .with_trash_days(config.retention.trash_days)
+    .with_orgs(Arc::clone(&org_repo))
+    .with_search_index(Arc::clone(&search_index))
+    .with_events(events.clone());
+    let notification_service =
+        NotificationService::new(Arc::new(NotificationRepository::new(pool.clone())));
+    let comment_service = CommentService::new(
+        Arc::clone(&comment_repo),
+        Arc::clone(&post_repo),
+        Arc::clone(&flag_repo),
+        moderation_service.clone(),
+        events.clone(),
+    )
+    .with_notifications(notification_service.clone());
+    let presence_service = PresenceService::new(Arc::clone(&post_repo), events.clone());
+    let settings_service = SettingsService::new(Arc::clone(&user_repo));
+    let message_service = MessageService::new(Arc::clone(&message_repo), Arc::clone(&user_repo));
+    let transfer_service = TransferService::new(
+        transfer_repo,
+        Arc::clone(&post_repo),
+        Arc::clone(&user_repo),
+        Arc::clone(&message_repo),
+        Arc::clone(&admin_repo),
+    )
+    .with_events(events.clone());
+    let org_service = OrgService::new(org_repo, Arc::clone(&user_repo), blog_service.clone());
+    let account_service = AccountService::new(
+        auth_service.clone(),
+        settings_service.clone(),
+        account_repo,
+        message_repo,
+        Arc::clone(&flag_repo),
+    );
+    let admin_service = AdminService::new(
+        admin_repo,
+        Arc::clone(&user_repo),
+        Arc::clone(&comment_repo),
+        flag_repo,
+    );
+
+    let mut jobs = Vec::new();
+    #[cfg(feature = "redis")]
+    if let Some(relay) = event_relay {
+        jobs.push(tokio::spawn(relay.run()));
+    }
+
+    if search_index.is_external() {
+        let search_index_service =
+            SearchIndexService::new(search_index, Arc::clone(&post_repo), events.clone());
+        jobs.push(tokio::spawn(search_index_service.run()));
+    }
+
+    // Background jobs visiting links found in posts
+    let link_preview_service = LinkPreviewService::new(preview_repo, fetcher.clone());
+    jobs.push(tokio::spawn(link_preview_service.run()));
+    let link_check_service = LinkCheckService::new(check_repo, fetcher.clone());
+    jobs.push(tokio::spawn(link_check_service.clone().run()));
+
+    // ActivityPub federation and the job delivering new posts to followers
+    let activitypub_service = ActivityPubService::new(
+        ap_repo,
+        Arc::clone(&post_repo),
+        fetcher.clone(),
+        ApUrls::new(&config.public_url, &config.site_url),
+    );
+    jobs.push(tokio::spawn(activitypub_service.clone().run()));
+
+    // RSS/Atom feeds and the job pinging the WebSub hub when posts change
+    let feed_service = FeedService::new(
+        blog_service.clone(),
+        Arc::clone(&post_repo),
+        Arc::clone(&user_repo),
+        fetcher.clone(),
+        FeedUrls::new(
+            &config.public_url,
+            &config.site_url,
+            config.websub_hub_url.clone(),
+        ),
+    )
+    .with_site(site_service.clone());
+    jobs.push(tokio::spawn(feed_service.clone().run()));
+
+    // Cross-posting to DEV / Hashnode and the job pushing later edits
+    let crosspost_service = CrosspostService::new(
+        crosspost_repo,
+        blog_service.clone(),
+        fetcher.clone(),
+        CrosspostApis {
+            devto: config.devto_api_url.clone(),
+            hashnode: config.hashnode_api_url.clone(),
+        },
+        config.site_url.clone(),
+    );
+    jobs.push(tokio::spawn(crosspost_service.clone().run()));
+
+    // Micropub publishing for IndieWeb clients
+    let micropub_service = MicropubService::new(
+        Arc::clone(&user_repo),
+        blog_service.clone(),
+        fetcher,
+        config.indieauth_token_endpoint.clone(),
+        config.site_url.clone(),
+    );
+
+    // Bulk import of posts exported from other blogs
+    let import_service = ImportService::new(
+        Arc::clone(&user_repo),
+        Arc::clone(&post_repo),
+        blog_service.clone(),
+        moderation_service.clone(),
+    );
+
+    // Content-addressed uploads, virus-scanned when clamd is configured
+    let upload_service = UploadService::new(
+        attachment_repo,
+        BlobStore::new(&config.upload_dir),
+        config.public_url.clone(),
+        config.upload_signing_key.clone(),
+        config.clamd_socket.as_ref().map(ClamAv::new),
+    );
+
+    // Newsletter subscriptions and the weekly digest job
+    let newsletter_service = NewsletterService::new(
+        newsletter_repo,
+        mailer,
+        config.public_url.clone(),
+        config.site_url.clone(),
+    );
+    jobs.push(tokio::spawn(newsletter_service.clone().run()));
+
+    // Retention job removing data past its configured lifetime
+    let retention_service = RetentionService::new(
+        Arc::new(RetentionRepository::new(pool.clone())),
+        config.retention,
+    );
+    jobs.push(tokio::spawn(retention_service.clone().run()));
+
+    // Anonymous analytics and the job rolling finished days up into stats
+    let analytics_service = AnalyticsService::new(
+        Arc::new(AnalyticsRepository::new(pool.clone())),
+        config.analytics,
+    );
+    jobs.push(tokio::spawn(analytics_service.clone().run()));
+
+    // Redacted snapshots of failed write requests, when diagnostics are on
+    let diagnostics_service = DiagnosticsService::new(config.diagnostics);
+    if diagnostics_service.is_enabled() {
+        info!(
+            capacity = config.diagnostics.capacity,
+            "Recording failed write requests for diagnostics"
+        );
+    }
+
+    // Service account quotas and the job saving their usage counters
+    let quota_service = QuotaService::new(Arc::clone(&service_account_repo));
+    #[cfg(feature = "redis")]
+    let quota_service = match (config.quota_backend, &config.redis_url) {
+        (QuotaBackend::Redis, Some(url)) => {
+            info!("Counting API usage in Redis");
+            quota_service.with_redis(RedisQuotaCounter::new(url)?)
+        }
+        _ => quota_service,
+    };
+    jobs.push(tokio::spawn(quota_service.clone().run()));
+
+    // Fault injection (debug builds only, rejected by config otherwise)
+    let chaos_config = config.chaos;
+    let cache_config = config.cache;
+    if chaos_config.is_enabled() {
+        warn!(?chaos_config, "fault injection enabled");
+    }
+
+    // Clone services for gRPC
+    let grpc_auth_service = GrpcAuthService::new(auth_service.clone());
+    let grpc_blog_service = GrpcBlogService::new(
+        blog_service.clone(),
+        auth_service.clone(),
+        link_check_service.clone(),
+        import_service.clone(),
+        comment_service.clone(),
+        notification_service.clone(),
+        events,
+    )
+    .with_quotas(quota_service.clone());
+
+    // Create reflection service for gRPC
+    let reflection_service = ReflectionBuilder::configure()
+        .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+        .build_v1()?;
+
+    // Bind gRPC listener first to log when ready
+    let grpc_listener = TcpListener::bind(grpc_addr).await?;
+    let grpc_addr = grpc_listener.local_addr()?;
+    info!(port = grpc_addr.port(), "gRPC server listening");
+
+    let grpc = GrpcServer::builder()
+        .layer(CatchPanicLayer::new(telemetry_service.clone()))
+        .layer(ChaosLayer::new(chaos_config))
+        .add_service(AuthServiceServer::new(grpc_auth_service))
+        .add_service(BlogServiceServer::new(grpc_blog_service))
+        .add_service(reflection_service)
+        .serve_with_incoming_shutdown(TcpListenerStream::new(grpc_listener), grpc_shutdown);
+
+    // Load the HTTPS certificate before binding anything
+    let http_tuning = config.http_server;
+    let tls_config = http_tuning
+        .tls
+        .as_ref()
+        .map(tls::server_config)
+        .transpose()?;
+
+    // Start HTTP server with CORS
+    let mut http_server = HttpServer::new(move || {
+        // Configure CORS for WASM frontend (multiple origins)
+        let mut cors = Cors::default()
+            .allowed_methods(vec!["GET", "HEAD", "POST", "PUT", "DELETE", "OPTIONS"])
+            .allowed_headers(vec![
+                http::header::AUTHORIZATION,
+                http::header::CONTENT_TYPE,
+                http::header::IF_NONE_MATCH,
+                http::header::IF_MODIFIED_SINCE,
+                http::header::HeaderName::from_static(constants::REQUEST_ID_HEADER),
+            ])
+            .expose_headers(vec![
+                http::header::ETAG,
+                http::header::LAST_MODIFIED,
+                http::header::RETRY_AFTER,
+                http::header::HeaderName::from_static(constants::REQUEST_ID_HEADER),
+                http::header::HeaderName::from_static(constants::RATE_LIMIT_LIMIT_HEADER),
+                http::header::HeaderName::from_static(constants::RATE_LIMIT_REMAINING_HEADER),
+                http::header::HeaderName::from_static(constants::RATE_LIMIT_RESET_HEADER),
+            ])
+            .max_age(3600);
+
+        for origin in CORS_ALLOWED_ORIGINS {
+            cors = cors.allowed_origin(origin);
+        }
+
+        App::new()
+            .wrap(from_fn(catch_panics))
+            .wrap(from_fn(record_failed_writes))
+            .wrap(from_fn(chaos))
+            .wrap(from_fn(quota_headers))
+            .wrap(from_fn(debug_envelope))
+            .wrap(from_fn(msgpack_negotiation))
+            .wrap(from_fn(report_server_errors))
+            .wrap(from_fn(request_id))
+            .wrap(cors)
+            .app_data(json_config())
+            .app_data(web::Data::new(chaos_config))
+            .app_data(web::Data::new(cache_config))
+            .app_data(web::Data::new(auth_service.clone()))
+            .app_data(web::Data::new(experiment_service.clone()))
+            .app_data(web::Data::new(account_service.clone()))
+            .app_data(web::Data::new(blog_service.clone()))
+            .app_data(web::Data::new(comment_service.clone()))
+            .app_data(web::Data::new(presence_service.clone()))
+            .app_data(web::Data::new(settings_service.clone()))
+            .app_data(web::Data::new(admin_service.clone()))
+            .app_data(web::Data::new(site_service.clone()))
+            .app_data(web::Data::new(link_check_service.clone()))
+            .app_data(web::Data::new(moderation_service.clone()))
+            .app_data(web::Data::new(message_service.clone()))
+            .app_data(web::Data::new(notification_service.clone()))
+            .app_data(web::Data::new(transfer_service.clone()))
+            .app_data(web::Data::new(org_service.clone()))
+            .app_data(web::Data::new(newsletter_service.clone()))
+            .app_data(web::Data::new(activitypub_service.clone()))
+            .app_data(web::Data::new(micropub_service.clone()))
+            .app_data(web::Data::new(feed_service.clone()))
+            .app_data(web::Data::new(import_service.clone()))
+            .app_data(web::Data::new(crosspost_service.clone()))
+            .app_data(web::Data::new(upload_service.clone()))
+            .app_data(web::Data::new(retention_service.clone()))
+            .app_data(web::Data::new(analytics_service.clone()))
+            .app_data(web::Data::new(quota_service.clone()))
+            .app_data(web::Data::new(telemetry_service.clone()))
+            .app_data(web::Data::new(diagnostics_service.clone()))
+            .service(web::scope("/api").service(api_routes()))
+            .service(webfinger)
+            .service(serve_upload)
+            .service(activitypub_routes())
+    })
+    .keep_alive(match http_tuning.keep_alive_secs {
+        0 => KeepAlive::Disabled,
+        secs => KeepAlive::Timeout(Duration::from_secs(secs)),
+    })
+    .client_request_timeout(Duration::from_millis(http_tuning.client_request_timeout_ms))
+    .client_disconnect_timeout(Duration::from_millis(
+        http_tuning.client_disconnect_timeout_ms,
+    ))
+    .max_connections(http_tuning.max_connections)
+    .backlog(http_tuning.backlog);
+    if let Some(workers) = http_tuning.workers {
+        http_server = http_server.workers(workers);
+    }
+
+    // HTTPS negotiates HTTP/2 via ALPN; plain HTTP stays on HTTP/1.1
+    let http_server = match tls_config {
+        Some(tls_config) => http_server.bind_rustls_0_23(http_addr, tls_config)?,
+        None => http_server.bind(http_addr)?,
+    };
+    let http_addr = http_server.addrs()[0];
+
+    info!(
+        port = http_addr.port(),
+        tls = http_tuning.tls.is_some(),
+        "HTTP server listening"
+    );
+
+    Ok(Server {
+        http_addr,
+        grpc_addr,
+        http: http_server.run(),
+        grpc: Box::pin(grpc),
+        jobs,
+    })
+}
@@ -0,0 +1,137 @@
+//! Embedded server for black-box tests and demos.
+//!
+//! [`spawn`] boots HTTP and gRPC on ephemeral localhost ports inside the
+//! current Tokio runtime:
+//!
+//! ```no_run
+//! # async fn demo() -> Result<(), blog_server::server::ServerError> {
+//! let server = blog_server::test_server::spawn(blog_server::test_server::config()).await?;
+//! let url = server.http_url(); // e.g. http://127.0.0.1:41234
+//! server.shutdown().await;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::env;
+use std::net::SocketAddr;
+
+use actix_web::dev::ServerHandle;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+use crate::constants::{
+    DEFAULT_DEVTO_API_URL, DEFAULT_HASHNODE_API_URL, DEFAULT_JWT_AUDIENCE, DEFAULT_PUBLIC_URL,
+    DEFAULT_RESERVED_USERNAMES, DEFAULT_SITE_URL,
+};
+use crate::domain::{EmbedProvider, normalize_username};
+use crate::infrastructure::config::{
+    AnalyticsConfig, CacheConfig, ChaosConfig, Config, DiagnosticsConfig, HttpServerConfig,
+    MailConfig, QuotaBackend, RetentionConfig, SearchBackend, TelemetryConfig,
+};
+use crate::server::{self, ServerError};
+
+/// JWT secret of [`config`].
+pub const TEST_JWT_SECRET: &str = "embedded-test-server-secret-at-least-32-characters";
+
+/// Key signing private upload URLs in [`config`].
+pub const TEST_UPLOAD_SIGNING_KEY: &str = "embedded-test-server-upload-signing-key";
+
+/// Configuration with an in-memory database, one HTTP worker, and emails
+/// logged instead of sent. Uploads go to a directory under the system temp dir.
+pub fn config() -> Config {
+    Config {
+        database_url: "sqlite::memory:".to_string(),
+        jwt_secret: TEST_JWT_SECRET.to_string(),
+        jwt_issuer: DEFAULT_PUBLIC_URL.to_string(),
+        jwt_audience: DEFAULT_JWT_AUDIENCE.to_string(),
+        http_port: 0,
+        http_server: HttpServerConfig {
+            workers: Some(1),
+            ..HttpServerConfig::default()
+        },
+        grpc_port: 0,
+        chaos: ChaosConfig::default(),
+        cache: CacheConfig::default(),
+        embed_providers: vec![EmbedProvider::YouTube, EmbedProvider::Gist],
+        reserved_usernames: DEFAULT_RESERVED_USERNAMES
+            .split(',')
+            .map(normalize_username)
+            .collect(),
+        invite_only: false,
+        mail: MailConfig::default(),
+        public_url: DEFAULT_PUBLIC_URL.to_string(),
+        site_url: DEFAULT_SITE_URL.to_string(),
+        indieauth_token_endpoint: None,
+        websub_hub_url: None,
+        devto_api_url: DEFAULT_DEVTO_API_URL.to_string(),
+        hashnode_api_url: DEFAULT_HASHNODE_API_URL.to_string(),
+        upload_dir: env::temp_dir()
+            .join("blog-test-server-uploads")
+            .to_string_lossy()
+            .into_owned(),
+        upload_signing_key: TEST_UPLOAD_SIGNING_KEY.to_string(),
+        clamd_socket: None,
+        content_cipher: None,
+        redis_url: None,
+        quota_backend: QuotaBackend::default(),
+        search_backend: SearchBackend::default(),
+        retention: RetentionConfig::default(),
+        analytics: AnalyticsConfig::default(),
+        telemetry: TelemetryConfig::default(),
+        diagnostics: DiagnosticsConfig::default(),
+    }
+}
+
+/// A server started by [`spawn`]; it keeps running until [`shutdown`](Self::shutdown)
+/// or the end of the runtime.
+pub struct RunningServer {
+    /// Address of the HTTP API.
+    pub http_addr: SocketAddr,
+    /// Address of the gRPC API.
+    pub grpc_addr: SocketAddr,
+    http: ServerHandle,
+    grpc_shutdown: oneshot::Sender<()>,
+    task: JoinHandle<Result<(), ServerError>>,
+}
+
+impl RunningServer {
+    /// Base URL of the HTTP API, as taken by `HttpClient::new`.
+    pub fn http_url(&self) -> String {
+        format!("http://{}", self.http_addr)
+    }
+
+    /// URL of the gRPC API, as taken by `GrpcClient::connect`.
+    pub fn grpc_url(&self) -> String {
+        format!("http://{}", self.grpc_addr)
+    }
+
+    /// Stops both transports gracefully and the background jobs.
+    pub async fn shutdown(self) {
+        self.http.stop(true).await;
+        let _ = self.grpc_shutdown.send(());
+        let _ = self.task.await;
+    }
+}
+
+/// Starts both transports on ephemeral `127.0.0.1` ports; the ports in
+/// `config` are ignored.
+pub async fn spawn(config: Config) -> Result<RunningServer, ServerError> {
+    let (grpc_shutdown, shutdown_rx) = oneshot::channel();
+    let server = server::start(
+        config,
+        SocketAddr::from(([127, 0, 0, 1], 0)),
+        SocketAddr::from(([127, 0, 0, 1], 0)),
+        async {
+            let _ = shutdown_rx.await;
+        },
+    )
+    .await?;
+
+    Ok(RunningServer {
+        http_addr: server.http_addr,
+        grpc_addr: server.grpc_addr,
+        http: server.http_handle(),
+        grpc_shutdown,
+        task: tokio::spawn(server.run()),
+    })
+}
@@ -0,0 +1,430 @@
+//! Integration tests for ActivityPub federation.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{AuthResponse, CreatePostRequest, PostDto, RegisterRequest};
+use chrono::{Duration, Utc};
+use serde_json::Value;
+use sqlx::SqlitePool;
+
+use blog_server::application::{ActivityPubService, AuthService, BlogService, ModerationService};
+use blog_server::data::{
+    ActivityPubRepository, FeatureFlagRepository, ModerationRepository, PostRepository,
+    RecoveryCodeRepository, ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::{ApUrls, EmbedProvider, actor_public_key};
+use blog_server::infrastructure::http_fetcher::HttpFetcher;
+use blog_server::infrastructure::http_signature::{
+    SignatureHeader, digest_header, generate_key_pair, http_date, sign, signing_string, verify,
+};
+use blog_server::presentation::activitypub_handlers::{activitypub_routes, webfinger};
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Actor ID of `alice` on the test server.
+const ALICE: &str = "http://api.test/ap/users/alice";
+
+/// Macro to build the test app; also returns the service, repository, and pool.
+macro_rules! init_app {
+    () => {{
+        let pool = setup_test_db().await;
+        let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+        let user_repo = Arc::new(UserRepository::new(pool.clone()));
+        let post_repo = Arc::new(PostRepository::new(pool.clone()));
+        let ap_repo = Arc::new(ActivityPubRepository::new(pool.clone()));
+        let auth_service = AuthService::new(
+            user_repo,
+            flag_repo,
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let moderation_service =
+            ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+        let blog_service = BlogService::new(
+            Arc::clone(&post_repo),
+            EmbedProvider::ALL.to_vec(),
+            moderation_service,
+        );
+        let activitypub_service = ActivityPubService::new(
+            Arc::clone(&ap_repo),
+            post_repo,
+            HttpFetcher::new().unwrap(),
+            ApUrls::new("http://api.test", "http://site.test"),
+        );
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(blog_service))
+                .app_data(web::Data::new(activitypub_service.clone()))
+                .service(web::scope("/api").service(api_routes()))
+                .service(webfinger)
+                .service(activitypub_routes()),
+        )
+        .await;
+        (app, activitypub_service, ap_repo, pool)
+    }};
+}
+
+/// Macro to register a user and get their token.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp.token
+    }};
+}
+
+/// Macro to create a post.
+macro_rules! create_post {
+    ($app:expr, $token:expr, $title:expr) => {{
+        let req = CreatePostRequest {
+            title: $title.to_string(),
+            content: "Hello *fediverse*".to_string(),
+            tags: vec![],
+            lang: None,
+            canonical_url: None,
+            original_source: None,
+            org: None,
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/posts")
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        let post: PostDto = test::read_body_json(resp).await;
+        post
+    }};
+}
+
+/// Macro to GET an ActivityPub document, returning the status, content type, and JSON.
+macro_rules! get_document {
+    ($app:expr, $uri:expr) => {{
+        let resp = test::TestRequest::get().uri($uri).send_request($app).await;
+        let status = resp.status().as_u16();
+        let content_type = resp
+            .headers()
+            .get("content-type")
+            .map(|value| value.to_str().unwrap().to_string())
+            .unwrap_or_default();
+        let body: Value = test::read_body_json(resp).await;
+        (status, content_type, body)
+    }};
+}
+
+/// Shadow-bans a user directly in the database.
+async fn shadow_ban(pool: &SqlitePool, username: &str) {
+    sqlx::query("UPDATE users SET shadow_banned_at = ? WHERE username = ?")
+        .bind(Utc::now())
+        .bind(username)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+/// Test WebFinger lookups of authors.
+#[tokio::test]
+async fn test_webfinger() {
+    let (app, _, _, _) = init_app!();
+    register_user!(&app, "alice");
+
+    let (status, content_type, body) =
+        get_document!(&app, "/.well-known/webfinger?resource=acct:alice@api.test");
+    assert_eq!(status, 200);
+    assert_eq!(content_type, "application/jrd+json");
+    assert_eq!(body["subject"], "acct:alice@api.test");
+    assert_eq!(body["links"][0]["rel"], "self");
+    assert_eq!(body["links"][0]["href"], ALICE);
+
+    for resource in ["acct:bob@api.test", "acct:alice@other.test", "alice"] {
+        let resp = test::TestRequest::get()
+            .uri(&format!("/.well-known/webfinger?resource={resource}"))
+            .send_request(&app)
+            .await;
+        assert_eq!(resp.status(), 404, "{resource}");
+    }
+}
+
+/// Test the actor document, its stable key, and the outbox.
+#[tokio::test]
+async fn test_actor_and_outbox() {
+    let (app, _, _, pool) = init_app!();
+    let token = register_user!(&app, "alice");
+    let post = create_post!(&app, token, "First <post>");
+
+    let (status, content_type, actor) = get_document!(&app, "/ap/users/alice");
+    assert_eq!(status, 200);
+    assert_eq!(content_type, "application/activity+json");
+    assert_eq!(actor["id"], ALICE);
+    assert_eq!(actor["type"], "Person");
+    assert_eq!(actor["preferredUsername"], "alice");
+    assert_eq!(actor["inbox"], format!("{ALICE}/inbox"));
+    assert_eq!(actor["publicKey"]["id"], format!("{ALICE}#main-key"));
+    let pem = actor["publicKey"]["publicKeyPem"].as_str().unwrap();
+    assert!(pem.starts_with("-----BEGIN PUBLIC KEY-----"));
+
+    // The key pair is created once
+    let (_, _, again) = get_document!(&app, "/ap/users/alice");
+    assert_eq!(again["publicKey"]["publicKeyPem"], pem);
+
+    let (status, _, outbox) = get_document!(&app, "/ap/users/alice/outbox");
+    assert_eq!(status, 200);
+    assert_eq!(outbox["totalItems"], 1);
+    let create = &outbox["orderedItems"][0];
+    assert_eq!(create["type"], "Create");
+    assert_eq!(create["actor"], ALICE);
+    let note = &create["object"];
+    assert_eq!(note["type"], "Note");
+    assert_eq!(
+        note["url"],
+        format!("http://site.test/?post={}", post.public_id)
+    );
+    let content = note["content"].as_str().unwrap();
+    assert!(content.contains("<strong>First &lt;post&gt;</strong>"));
+    assert!(content.contains("<em>fediverse</em>"));
+
+    let (status, _, object) = get_document!(&app, &format!("/ap/posts/{}", post.public_id));
+    assert_eq!(status, 200);
+    assert_eq!(object["id"], note["id"]);
+
+    // Notes are named by public ID only
+    let numeric: i64 = sqlx::query_scalar("SELECT id FROM posts WHERE public_id = ?")
+        .bind(&post.public_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let (status, _, _) = get_document!(&app, &format!("/ap/posts/{}", numeric));
+    assert_eq!(status, 404);
+
+    let (_, _, followers) = get_document!(&app, "/ap/users/alice/followers");
+    assert_eq!(followers["totalItems"], 0);
+
+    // Shadow-banned authors are not federated
+    shadow_ban(&pool, "alice").await;
+    let (status, _, _) = get_document!(&app, "/ap/users/alice");
+    assert_eq!(status, 404);
+    let (status, _, _) = get_document!(&app, &format!("/ap/posts/{}", post.public_id));
+    assert_eq!(status, 404);
+}
+
+/// Test that inbox requests without a valid signature are rejected.
+#[tokio::test]
+async fn test_inbox_requires_signature() {
+    let (app, _, _, _) = init_app!();
+    register_user!(&app, "alice");
+    let follow = serde_json::json!({
+        "type": "Follow",
+        "actor": "http://127.0.0.1:9/users/mallory",
+        "object": ALICE,
+    });
+    let body = serde_json::to_vec(&follow).unwrap();
+
+    // Unsigned
+    let resp = test::TestRequest::post()
+        .uri("/ap/users/alice/inbox")
+        .insert_header(("content-type", "application/activity+json"))
+        .set_payload(body.clone())
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 401);
+
+    // Properly signed, but the actor cannot be fetched from a private address
+    let keys = generate_key_pair().unwrap();
+    let date = http_date(Utc::now());
+    let digest = digest_header(&body);
+    let signed = [
+        (
+            "(request-target)".to_string(),
+            "post /ap/users/alice/inbox".to_string(),
+        ),
+        ("host".to_string(), "api.test".to_string()),
+        ("date".to_string(), date.clone()),
+        ("digest".to_string(), digest.clone()),
+    ];
+    let signature = sign(
+        &keys.private_key_pem,
+        "http://127.0.0.1:9/users/mallory#main-key",
+        &signed,
+    )
+    .unwrap();
+    let request = |digest: &str| {
+        test::TestRequest::post()
+            .uri("/ap/users/alice/inbox")
+            .insert_header(("host", "api.test"))
+            .insert_header(("date", date.clone()))
+            .insert_header(("digest", digest.to_string()))
+            .insert_header(("signature", signature.clone()))
+            .set_payload(body.clone())
+    };
+    let resp = request(&digest).send_request(&app).await;
+    assert_eq!(resp.status(), 401);
+    let error: Value = test::read_body_json(resp).await;
+    assert!(
+        error["error"]
+            .as_str()
+            .unwrap()
+            .contains("cannot fetch actor")
+    );
+
+    // Tampered body
+    let resp = request("SHA-256=AAAA").send_request(&app).await;
+    assert_eq!(resp.status(), 401);
+    let error: Value = test::read_body_json(resp).await;
+    assert!(error["error"].as_str().unwrap().contains("Digest"));
+
+    // Unknown author
+    let resp = test::TestRequest::post()
+        .uri("/ap/users/nobody/inbox")
+        .set_payload(body)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 404);
+}
+
+/// Test that a signing key is only trusted from the actor's own document, so
+/// a key hosted by an attacker cannot speak for another actor.
+#[tokio::test]
+async fn test_forged_key_is_rejected() {
+    let (app, _, _, _) = init_app!();
+    register_user!(&app, "alice");
+    let (_, _, actor) = get_document!(&app, "/ap/users/alice");
+    let key_id = format!("{ALICE}#main-key");
+    let pem = actor["publicKey"]["publicKeyPem"].as_str().unwrap();
+    assert_eq!(actor_public_key(&actor, ALICE, &key_id), Some(pem));
+
+    // Mallory hosts a key document naming alice as its owner
+    let mallory = generate_key_pair().unwrap();
+    let forged_key_id = "http://evil.test/keys/alice";
+    let forged = serde_json::json!({
+        "id": forged_key_id,
+        "owner": ALICE,
+        "publicKeyPem": mallory.public_key_pem,
+    });
+    assert_eq!(actor_public_key(&forged, ALICE, forged_key_id), None);
+    assert_eq!(actor_public_key(&actor, ALICE, forged_key_id), None);
+
+    // An actor document served for another ID is not alice's either
+    let mut impostor = actor.clone();
+    impostor["publicKey"]["publicKeyPem"] = mallory.public_key_pem.clone().into();
+    assert_eq!(
+        actor_public_key(&impostor, "http://evil.test/users/alice", &key_id),
+        None
+    );
+
+    // A key whose owner is someone else is not trusted
+    let mut disowned = actor.clone();
+    disowned["publicKey"]["owner"] = "http://evil.test/users/mallory".into();
+    assert_eq!(actor_public_key(&disowned, ALICE, &key_id), None);
+}
+
+/// Test that followers get posts published after they followed, with retries.
+#[tokio::test]
+async fn test_delivery_queue() {
+    let (app, service, repo, _) = init_app!();
+    let token = register_user!(&app, "alice");
+    create_post!(&app, token, "Before following");
+
+    let (_, _, actor) = get_document!(&app, "/ap/users/alice");
+    assert_eq!(actor["id"], ALICE);
+    let alice_id = 1; // first registered user
+    repo.add_follower(
+        alice_id,
+        "http://127.0.0.1:9/users/bob",
+        "http://127.0.0.1:9/inbox",
+        Utc::now(),
+    )
+    .await
+    .unwrap();
+    create_post!(&app, token, "After following");
+
+    let due = repo.list_due_deliveries(Utc::now(), 8, 10).await.unwrap();
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].title, "After following");
+
+    // The private inbox is refused, so the delivery fails and backs off
+    assert_eq!(service.deliver_due().await.unwrap(), 0);
+    assert!(
+        repo.list_due_deliveries(Utc::now(), 8, 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let retry = repo
+        .list_due_deliveries(Utc::now() + Duration::hours(1), 8, 10)
+        .await
+        .unwrap();
+    assert_eq!(retry.len(), 1);
+    assert_eq!(retry[0].attempts, 1);
+
+    // Given up after the maximum number of attempts
+    assert!(
+        repo.list_due_deliveries(Utc::now() + Duration::hours(1), 1, 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let (_, _, followers) = get_document!(&app, "/ap/users/alice/followers");
+    assert_eq!(followers["totalItems"], 1);
+    assert!(
+        repo.remove_follower(alice_id, "http://127.0.0.1:9/users/bob")
+            .await
+            .unwrap()
+    );
+}
+
+/// Test signing and verifying a request's headers.
+#[tokio::test]
+async fn test_http_signature_round_trip() {
+    let keys = generate_key_pair().unwrap();
+    let headers = vec![
+        ("(request-target)".to_string(), "post /inbox".to_string()),
+        ("host".to_string(), "remote.test".to_string()),
+        ("date".to_string(), http_date(Utc::now())),
+        ("digest".to_string(), digest_header(b"{}")),
+    ];
+
+    let value = sign(
+        &keys.private_key_pem,
+        "http://api.test/ap/users/alice#main-key",
+        &headers,
+    )
+    .unwrap();
+    let parsed = SignatureHeader::parse(&value).unwrap();
+    assert_eq!(parsed.key_id, "http://api.test/ap/users/alice#main-key");
+    assert_eq!(
+        parsed.headers,
+        vec!["(request-target)", "host", "date", "digest"]
+    );
+
+    let message = signing_string(&headers);
+    assert!(verify(&keys.public_key_pem, &message, &parsed.signature).is_ok());
+    assert!(
+        verify(
+            &keys.public_key_pem,
+            &message.replace("remote", "evil"),
+            &parsed.signature
+        )
+        .is_err()
+    );
+
+    let other = generate_key_pair().unwrap();
+    assert!(verify(&other.public_key_pem, &message, &parsed.signature).is_err());
+}
@@ -0,0 +1,388 @@
+//! Integration tests for admin dashboard endpoints.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    AdminStatsDto, AdminUserDto, AdminUserListResponse, AuthResponse, CommentListResponse,
+    CreateCommentRequest, CreatePostRequest, FeatureFlagDto, FeatureFlagListResponse, LoginRequest,
+    MeResponse, PostDto, RegisterRequest, Role, UpdateFeatureFlagRequest, UpdateUserRequest,
+};
+use sqlx::SqlitePool;
+
+use blog_server::application::{
+    AccountService, AdminService, AuthService, BlogService, CommentService, ModerationService,
+    SettingsService,
+};
+use blog_server::data::{
+    AccountRepository, AdminRepository, CommentRepository, FeatureFlagRepository,
+    MessageRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::infrastructure::event_bus::EventBus;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app with all services used by the dashboard.
+macro_rules! init_app {
+    ($pool:expr) => {{
+        let user_repo = Arc::new(UserRepository::new($pool.clone()));
+        let post_repo = Arc::new(PostRepository::new($pool.clone()));
+        let comment_repo = Arc::new(CommentRepository::new($pool.clone()));
+        let flag_repo = Arc::new(FeatureFlagRepository::new($pool.clone()));
+        let admin_repo = Arc::new(AdminRepository::new($pool.clone()));
+        let auth_service = AuthService::new(
+            Arc::clone(&user_repo),
+            Arc::clone(&flag_repo),
+            Arc::new(RecoveryCodeRepository::new($pool.clone())),
+            Arc::new(ServiceAccountRepository::new($pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let moderation_service =
+            ModerationService::new(Arc::new(ModerationRepository::new($pool.clone())));
+        let blog_service = BlogService::new(
+            Arc::clone(&post_repo),
+            EmbedProvider::ALL.to_vec(),
+            moderation_service.clone(),
+        );
+        let comment_service = CommentService::new(
+            Arc::clone(&comment_repo),
+            Arc::clone(&post_repo),
+            Arc::clone(&flag_repo),
+            moderation_service,
+            EventBus::new(),
+        );
+        let account_service = AccountService::new(
+            auth_service.clone(),
+            SettingsService::new(Arc::clone(&user_repo)),
+            Arc::new(AccountRepository::new($pool.clone())),
+            Arc::new(MessageRepository::new($pool.clone())),
+            Arc::clone(&flag_repo),
+        );
+        let admin_service = AdminService::new(admin_repo, user_repo, comment_repo, flag_repo);
+
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(account_service))
+                .app_data(web::Data::new(blog_service))
+                .app_data(web::Data::new(comment_service))
+                .app_data(web::Data::new(admin_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user and get the auth response.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp
+    }};
+}
+
+/// Macro to send an authenticated request with an optional JSON body.
+macro_rules! send {
+    ($app:expr, $method:ident, $uri:expr, $token:expr) => {{
+        test::TestRequest::$method()
+            .uri($uri)
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .send_request($app)
+            .await
+    }};
+    ($app:expr, $method:ident, $uri:expr, $token:expr, $body:expr) => {{
+        test::TestRequest::$method()
+            .uri($uri)
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json($body)
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Sets a user's role directly in the database.
+async fn set_role(pool: &SqlitePool, username: &str, role: &str) {
+    sqlx::query("UPDATE users SET role = ? WHERE username = ?")
+        .bind(role)
+        .bind(username)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+/// Test that the admin endpoints reject anonymous and regular users.
+#[tokio::test]
+async fn test_admin_routes_require_admin() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let user = register_user!(&app, "alice");
+
+    let resp = test::TestRequest::get()
+        .uri("/api/admin/stats")
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 401);
+
+    let resp = send!(&app, get, "/api/admin/stats", user.token);
+    assert_eq!(resp.status(), 403);
+    assert_eq!(user.user.role, Role::User);
+}
+
+/// Test that `/auth/me` reports the role and admins get stats.
+#[tokio::test]
+async fn test_admin_stats() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let admin = register_user!(&app, "admin");
+    let author = register_user!(&app, "author");
+    set_role(&pool, "admin", "admin").await;
+
+    let me: MeResponse = test::read_body_json(send!(&app, get, "/api/auth/me", admin.token)).await;
+    assert_eq!(me.user.role, Role::Admin);
+    assert!(me.permissions.iter().any(|p| p == "admin:dashboard"));
+
+    let post_req = CreatePostRequest {
+        title: "Hello".to_string(),
+        content: "World".to_string(),
+        tags: vec!["rust".to_string()],
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
+    };
+    let post: PostDto =
+        test::read_body_json(send!(&app, post, "/api/posts", author.token, &post_req)).await;
+    let comment_req = CreateCommentRequest {
+        content: "Nice".to_string(),
+    };
+    let uri = format!("/api/posts/{}/comments", post.public_id);
+    send!(&app, post, &uri, admin.token, &comment_req);
+
+    let resp = send!(&app, get, "/api/admin/stats", admin.token);
+    assert_eq!(resp.status(), 200);
+    let stats: AdminStatsDto = test::read_body_json(resp).await;
+    assert_eq!(stats.users, 2);
+    assert_eq!(stats.suspended_users, 0);
+    assert_eq!(stats.posts, 1);
+    assert_eq!(stats.comments, 1);
+    assert_eq!(stats.tags, 1);
+    assert_eq!(stats.active_sessions, 2);
+}
+
+/// Test changing roles, including the moderator's limited access.
+#[tokio::test]
+async fn test_change_role() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let admin = register_user!(&app, "admin");
+    let bob = register_user!(&app, "bob");
+    set_role(&pool, "admin", "admin").await;
+
+    let promote = UpdateUserRequest {
+        role: Some(Role::Moderator),
+        suspended: None,
+    };
+    let uri = format!("/api/admin/users/{}", bob.user.id);
+    let resp = send!(&app, put, &uri, admin.token, &promote);
+    assert_eq!(resp.status(), 200);
+    let updated: AdminUserDto = test::read_body_json(resp).await;
+    assert_eq!(updated.role, Role::Moderator);
+
+    // Moderators see the moderation queue but nothing else
+    let resp = send!(&app, get, "/api/admin/moderation/comments", bob.token);
+    assert_eq!(resp.status(), 200);
+    let resp = send!(&app, get, "/api/admin/users", bob.token);
+    assert_eq!(resp.status(), 403);
+
+    // Admins cannot demote themselves
+    let uri = format!("/api/admin/users/{}", admin.user.id);
+    let demote = UpdateUserRequest {
+        role: Some(Role::User),
+        suspended: None,
+    };
+    let resp = send!(&app, put, &uri, admin.token, &demote);
+    assert_eq!(resp.status(), 400);
+
+    let resp = send!(&app, put, "/api/admin/users/999", admin.token, &demote);
+    assert_eq!(resp.status(), 404);
+}
+
+/// Test that suspending signs the user out and blocks login until lifted.
+#[tokio::test]
+async fn test_suspend_user() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let admin = register_user!(&app, "admin");
+    let bob = register_user!(&app, "bob");
+    set_role(&pool, "admin", "admin").await;
+
+    let uri = format!("/api/admin/users/{}", bob.user.id);
+    let suspend = UpdateUserRequest {
+        role: None,
+        suspended: Some(true),
+    };
+    let resp = send!(&app, put, &uri, admin.token, &suspend);
+    assert_eq!(resp.status(), 200);
+    let updated: AdminUserDto = test::read_body_json(resp).await;
+    assert!(updated.suspended);
+
+    assert_eq!(send!(&app, get, "/api/auth/me", bob.token).status(), 401);
+
+    let login = LoginRequest {
+        username: "bob".to_string(),
+        password: "secret123".to_string(),
+    };
+    let resp = test::TestRequest::post()
+        .uri("/api/auth/login")
+        .set_json(&login)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 403);
+
+    let list: AdminUserListResponse =
+        test::read_body_json(send!(&app, get, "/api/admin/users", admin.token)).await;
+    assert_eq!(list.total, 2);
+    assert!(
+        list.users
+            .iter()
+            .any(|u| u.username == "bob" && u.suspended)
+    );
+
+    let lift = UpdateUserRequest {
+        role: None,
+        suspended: Some(false),
+    };
+    send!(&app, put, &uri, admin.token, &lift);
+    let resp = test::TestRequest::post()
+        .uri("/api/auth/login")
+        .set_json(&login)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 200);
+}
+
+/// Test the moderation queue lists newest comments first and can remove them.
+#[tokio::test]
+async fn test_moderation_queue() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let moderator = register_user!(&app, "moderator");
+    let author = register_user!(&app, "author");
+    set_role(&pool, "moderator", "moderator").await;
+
+    let post_req = CreatePostRequest {
+        title: "Hello".to_string(),
+        content: "World".to_string(),
+        tags: Vec::new(),
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
+    };
+    let post: PostDto =
+        test::read_body_json(send!(&app, post, "/api/posts", author.token, &post_req)).await;
+    let uri = format!("/api/posts/{}/comments", post.public_id);
+    for content in ["first", "second"] {
+        let req = CreateCommentRequest {
+            content: content.to_string(),
+        };
+        send!(&app, post, &uri, author.token, &req);
+    }
+
+    let queue: CommentListResponse = test::read_body_json(send!(
+        &app,
+        get,
+        "/api/admin/moderation/comments",
+        moderator.token
+    ))
+    .await;
+    assert_eq!(queue.total, Some(2));
+    assert_eq!(queue.comments[0].content, "second");
+
+    let uri = format!("/api/admin/moderation/comments/{}", queue.comments[0].id);
+    assert_eq!(send!(&app, delete, &uri, moderator.token).status(), 204);
+    assert_eq!(send!(&app, delete, &uri, moderator.token).status(), 404);
+}
+
+/// Test that feature flags can be toggled and gate registration and comments.
+#[tokio::test]
+async fn test_feature_flags() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let admin = register_user!(&app, "admin");
+    set_role(&pool, "admin", "admin").await;
+
+    let list: FeatureFlagListResponse =
+        test::read_body_json(send!(&app, get, "/api/admin/flags", admin.token)).await;
+    let names: Vec<_> = list.flags.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, vec!["comments", "editorial_review", "registration"]);
+    assert!(
+        list.flags
+            .iter()
+            .all(|f| f.enabled == (f.name != "editorial_review"))
+    );
+
+    let off = UpdateFeatureFlagRequest { enabled: false };
+    for name in ["registration", "comments"] {
+        let uri = format!("/api/admin/flags/{}", name);
+        let resp = send!(&app, put, &uri, admin.token, &off);
+        assert_eq!(resp.status(), 200);
+        let flag: FeatureFlagDto = test::read_body_json(resp).await;
+        assert!(!flag.enabled);
+    }
+
+    let req = RegisterRequest {
+        username: "late".to_string(),
+        email: "late@example.com".to_string(),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    let resp = test::TestRequest::post()
+        .uri("/api/auth/register")
+        .set_json(&req)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 403);
+
+    let post_req = CreatePostRequest {
+        title: "Hello".to_string(),
+        content: "World".to_string(),
+        tags: Vec::new(),
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
+    };
+    let post: PostDto =
+        test::read_body_json(send!(&app, post, "/api/posts", admin.token, &post_req)).await;
+    let uri = format!("/api/posts/{}/comments", post.public_id);
+    let comment_req = CreateCommentRequest {
+        content: "Hi".to_string(),
+    };
+    assert_eq!(
+        send!(&app, post, &uri, admin.token, &comment_req).status(),
+        403
+    );
+
+    let resp = send!(&app, put, "/api/admin/flags/unknown", admin.token, &off);
+    assert_eq!(resp.status(), 404);
+}
@@ -0,0 +1,266 @@
+//! Integration tests for anonymous analytics ingestion and the daily rollup.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    AnalyticsBatchRequest, AnalyticsEventDto, AnalyticsEventKind, AnalyticsReportResponse,
+    RegisterRequest,
+};
+use chrono::{Duration, Utc};
+use serde_json::json;
+use sqlx::SqlitePool;
+
+use blog_server::application::{AnalyticsService, AuthService};
+use blog_server::data::{
+    AnalyticsRepository, FeatureFlagRepository, RecoveryCodeRepository, ServiceAccountRepository,
+    UserRepository,
+};
+use blog_server::infrastructure::clock::MockClock;
+use blog_server::infrastructure::config::AnalyticsConfig;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Analytics on, keeping every event.
+const ENABLED: AnalyticsConfig = AnalyticsConfig {
+    enabled: true,
+    sample_rate: 1.0,
+};
+
+/// Builds the auth service on a test database.
+fn auth_service(pool: &SqlitePool) -> AuthService {
+    AuthService::new(
+        Arc::new(UserRepository::new(pool.clone())),
+        Arc::new(FeatureFlagRepository::new(pool.clone())),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    )
+}
+
+/// Builds an analytics service on `clock`.
+fn analytics_service(
+    pool: &SqlitePool,
+    config: AnalyticsConfig,
+    clock: &MockClock,
+) -> AnalyticsService {
+    AnalyticsService::new(Arc::new(AnalyticsRepository::new(pool.clone())), config)
+        .with_clock(Arc::new(clock.clone()))
+}
+
+/// Registers an admin, returning their token.
+async fn register_admin(pool: &SqlitePool, auth_service: &AuthService) -> String {
+    let req = RegisterRequest {
+        username: "admin".to_string(),
+        email: "admin@example.com".to_string(),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    let auth = auth_service.register(req, None).await.unwrap();
+    UserRepository::new(pool.clone())
+        .update_role(auth.user.id, "admin")
+        .await
+        .unwrap();
+    auth.token
+}
+
+/// Builds a batch of events on `path`.
+fn batch(kind: AnalyticsEventKind, path: &str, count: usize) -> AnalyticsBatchRequest {
+    AnalyticsBatchRequest {
+        events: vec![
+            AnalyticsEventDto {
+                kind,
+                path: path.to_string(),
+            };
+            count
+        ],
+    }
+}
+
+/// Macro to build the test app with an auth and analytics service.
+macro_rules! init_app {
+    ($pool:expr, $service:expr) => {
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service(&$pool)))
+                .app_data(web::Data::new($service.clone()))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    };
+}
+
+/// Test events being counted per day and page once their day is over.
+#[tokio::test]
+async fn test_events_are_rolled_up_into_daily_stats() {
+    let pool = setup_test_db().await;
+    let admin_token = register_admin(&pool, &auth_service(&pool)).await;
+    let now = Utc::now();
+    let clock = MockClock::new(now);
+    let service = analytics_service(&pool, ENABLED, &clock);
+    let app = init_app!(pool, service);
+    let day = now.date_naive();
+
+    let mut events = batch(AnalyticsEventKind::PageView, "/posts/1?utm_source=x", 3);
+    events.events.extend(
+        batch(AnalyticsEventKind::ReadComplete, "/posts/1#end", 1)
+            .events
+            .into_iter()
+            .chain(batch(AnalyticsEventKind::PageView, "/", 1).events),
+    );
+    let resp = test::TestRequest::post()
+        .uri("/api/analytics/events")
+        .set_json(&events)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 202);
+
+    // Today is not over yet
+    assert_eq!(service.rollup().await.unwrap(), 0);
+    assert!(service.report(30).await.unwrap().days.is_empty());
+
+    clock.advance(Duration::days(1));
+    assert_eq!(service.rollup().await.unwrap(), 5);
+    assert_eq!(service.rollup().await.unwrap(), 0);
+
+    // Next day's views of the same page add up in the report
+    service
+        .record(batch(AnalyticsEventKind::PageView, "/posts/1", 2), false)
+        .await
+        .unwrap();
+    clock.advance(Duration::days(1));
+    assert_eq!(service.rollup().await.unwrap(), 2);
+
+    let req = test::TestRequest::get()
+        .uri("/api/admin/analytics?days=7")
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .to_request();
+    let report: AnalyticsReportResponse = test::call_and_read_body_json(&app, req).await;
+    let days: Vec<_> = report
+        .days
+        .iter()
+        .map(|stats| (stats.day, stats.page_views, stats.read_completions))
+        .collect();
+    assert_eq!(
+        days,
+        vec![(day, 4, 1), (day + Duration::days(1), 2, 0)],
+        "oldest first"
+    );
+    let pages: Vec<_> = report
+        .top_pages
+        .iter()
+        .map(|page| (page.path.as_str(), page.page_views, page.read_completions))
+        .collect();
+    assert_eq!(pages, vec![("/posts/1", 5, 1), ("/", 1, 0)]);
+
+    // Days before the reported range are left out
+    clock.advance(Duration::days(1));
+    let report = service.report(1).await.unwrap();
+    assert!(report.days.is_empty());
+    assert!(report.top_pages.is_empty());
+}
+
+/// Test nothing being stored while analytics are off or the reader opts out.
+#[tokio::test]
+async fn test_disabled_and_opted_out_events_are_dropped() {
+    let pool = setup_test_db().await;
+    let clock = MockClock::new(Utc::now());
+    let disabled = analytics_service(&pool, AnalyticsConfig::default(), &clock);
+    let enabled = analytics_service(&pool, ENABLED, &clock);
+
+    let app = init_app!(pool, disabled);
+    let resp = test::TestRequest::post()
+        .uri("/api/analytics/events")
+        .set_json(batch(AnalyticsEventKind::PageView, "/", 1))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 202);
+
+    let app = init_app!(pool, enabled);
+    for header in [("DNT", "1"), ("Sec-GPC", "1")] {
+        let resp = test::TestRequest::post()
+            .uri("/api/analytics/events")
+            .insert_header(header)
+            .set_json(batch(AnalyticsEventKind::PageView, "/", 1))
+            .send_request(&app)
+            .await;
+        assert_eq!(resp.status(), 202);
+    }
+
+    clock.advance(Duration::days(1));
+    assert_eq!(enabled.rollup().await.unwrap(), 0);
+}
+
+/// Test sampled events standing for the events dropped by sampling.
+#[tokio::test]
+async fn test_sampled_events_are_scaled_up() {
+    let pool = setup_test_db().await;
+    let clock = MockClock::new(Utc::now());
+    let config = AnalyticsConfig {
+        enabled: true,
+        sample_rate: 0.5,
+    };
+    let service = analytics_service(&pool, config, &clock);
+
+    let mut stored = 0;
+    for _ in 0..4 {
+        stored += service
+            .record(batch(AnalyticsEventKind::PageView, "/", 50), false)
+            .await
+            .unwrap();
+    }
+    assert!(stored < 200, "some events are sampled out");
+
+    clock.advance(Duration::days(1));
+    service.rollup().await.unwrap();
+    let report = service.report(2).await.unwrap();
+    assert_eq!(report.days[0].page_views, 2 * stored as i64);
+}
+
+/// Test malformed batches being rejected and the report being admin-only.
+#[tokio::test]
+async fn test_invalid_requests_are_rejected() {
+    let pool = setup_test_db().await;
+    let auth_service = auth_service(&pool);
+    let admin_token = register_admin(&pool, &auth_service).await;
+    let req = RegisterRequest {
+        username: "reader".to_string(),
+        email: "reader@example.com".to_string(),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    let user_token = auth_service.register(req, None).await.unwrap().token;
+    let clock = MockClock::new(Utc::now());
+    let service = analytics_service(&pool, ENABLED, &clock);
+    let app = init_app!(pool, service);
+
+    let too_many = batch(AnalyticsEventKind::PageView, "/", 51);
+    let relative = batch(AnalyticsEventKind::PageView, "posts/1", 1);
+    let unknown = json!({"events": [{"kind": "click", "path": "/"}]});
+    for body in [json!(too_many), json!(relative), unknown] {
+        let resp = test::TestRequest::post()
+            .uri("/api/analytics/events")
+            .set_json(&body)
+            .send_request(&app)
+            .await;
+        assert_eq!(resp.status(), 400, "{body}");
+    }
+
+    let resp = test::TestRequest::get()
+        .uri("/api/admin/analytics")
+        .insert_header(("Authorization", format!("Bearer {user_token}")))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 403);
+
+    let resp = test::TestRequest::get()
+        .uri("/api/admin/analytics?days=0")
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 400);
+}
@@ -0,0 +1,186 @@
+//! Integration tests for the site announcement banner.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    AnnouncementDto, AnnouncementResponse, AnnouncementSeverity, AuthResponse, RegisterRequest,
+    SetAnnouncementRequest,
+};
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+
+use blog_server::application::{AuthService, SiteService};
+use blog_server::data::{
+    FeatureFlagRepository, RecoveryCodeRepository, ServiceAccountRepository,
+    SiteSettingsRepository, UserRepository,
+};
+use blog_server::infrastructure::clock::{Clock, MockClock};
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app with the site service on `$clock`.
+macro_rules! init_app {
+    ($pool:expr, $clock:expr) => {{
+        let auth_service = AuthService::new(
+            Arc::new(UserRepository::new($pool.clone())),
+            Arc::new(FeatureFlagRepository::new($pool.clone())),
+            Arc::new(RecoveryCodeRepository::new($pool.clone())),
+            Arc::new(ServiceAccountRepository::new($pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let site_service = SiteService::new(Arc::new(SiteSettingsRepository::new($pool.clone())))
+            .with_clock(Arc::new($clock.clone()));
+
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(site_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user and get the auth response.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp
+    }};
+}
+
+/// Macro to get the public announcement.
+macro_rules! announcement {
+    ($app:expr) => {{
+        let resp = test::TestRequest::get()
+            .uri("/api/site/announcement")
+            .send_request($app)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let body: AnnouncementResponse = test::read_body_json(resp).await;
+        body.announcement
+    }};
+}
+
+/// Macro to set the announcement as the user with `$token`.
+macro_rules! set_announcement {
+    ($app:expr, $token:expr, $req:expr) => {{
+        test::TestRequest::put()
+            .uri("/api/admin/announcement")
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json($req)
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Makes a user an admin directly in the database.
+async fn make_admin(pool: &SqlitePool, username: &str) {
+    sqlx::query("UPDATE users SET role = 'admin' WHERE username = ?")
+        .bind(username)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+/// Admins set and clear the banner; everyone can read it.
+#[tokio::test]
+async fn test_set_and_clear_announcement() {
+    let pool = setup_test_db().await;
+    let clock = MockClock::new(Utc::now());
+    let app = init_app!(pool, clock);
+    let admin = register_user!(&app, "admin");
+    make_admin(&pool, "admin").await;
+
+    assert!(announcement!(&app).is_none());
+
+    let req = SetAnnouncementRequest {
+        text: "  Maintenance tonight at 22:00 UTC  ".to_string(),
+        severity: AnnouncementSeverity::Warning,
+        expires_at: None,
+    };
+    let resp = set_announcement!(&app, admin.token, &req);
+    assert_eq!(resp.status(), 200);
+    let set: AnnouncementDto = test::read_body_json(resp).await;
+    assert_eq!(set.text, "Maintenance tonight at 22:00 UTC");
+
+    let shown = announcement!(&app).expect("announcement");
+    assert_eq!(shown, set);
+    assert_eq!(shown.severity, AnnouncementSeverity::Warning);
+
+    let resp = test::TestRequest::delete()
+        .uri("/api/admin/announcement")
+        .insert_header(("Authorization", format!("Bearer {}", admin.token)))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 204);
+    assert!(announcement!(&app).is_none());
+}
+
+/// An announcement disappears once it expires; past expiries and empty
+/// text are rejected.
+#[tokio::test]
+async fn test_announcement_expiry() {
+    let pool = setup_test_db().await;
+    let clock = MockClock::new(Utc::now());
+    let app = init_app!(pool, clock);
+    let admin = register_user!(&app, "admin");
+    make_admin(&pool, "admin").await;
+
+    let mut req = SetAnnouncementRequest {
+        text: "Sale ends soon".to_string(),
+        severity: AnnouncementSeverity::Info,
+        expires_at: Some(clock.now() - Duration::minutes(1)),
+    };
+    let resp = set_announcement!(&app, admin.token, &req);
+    assert_eq!(resp.status(), 400);
+
+    req.expires_at = Some(clock.now() + Duration::hours(1));
+    let resp = set_announcement!(&app, admin.token, &req);
+    assert_eq!(resp.status(), 200);
+    assert!(announcement!(&app).is_some());
+
+    clock.advance(Duration::hours(2));
+    assert!(announcement!(&app).is_none());
+
+    req.text = "   ".to_string();
+    req.expires_at = None;
+    let resp = set_announcement!(&app, admin.token, &req);
+    assert_eq!(resp.status(), 400);
+}
+
+/// Only admins may change the banner.
+#[tokio::test]
+async fn test_set_announcement_requires_admin() {
+    let pool = setup_test_db().await;
+    let clock = MockClock::new(Utc::now());
+    let app = init_app!(pool, clock);
+    let user = register_user!(&app, "alice");
+
+    let req = SetAnnouncementRequest {
+        text: "Hello".to_string(),
+        severity: AnnouncementSeverity::Critical,
+        expires_at: None,
+    };
+    let resp = set_announcement!(&app, user.token, &req);
+    assert_eq!(resp.status(), 403);
+    assert!(announcement!(&app).is_none());
+}
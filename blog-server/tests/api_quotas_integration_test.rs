@@ -0,0 +1,308 @@
+//! Integration tests for service account quotas.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::middleware::from_fn;
+use actix_web::{App, test, web};
+use blog_shared::{
+    ApiUsageResponse, AuthResponse, CreateServiceAccountRequest, CreatedServiceAccountResponse,
+    ErrorResponse, ProfileDto, RegisterRequest, ServiceAccountDto, ServiceAccountQuotaRequest,
+    ServiceScope,
+};
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+
+use blog_server::application::{AuthService, QuotaService, SettingsService};
+use blog_server::data::{
+    FeatureFlagRepository, RecoveryCodeRepository, ServiceAccountRepository, UserRepository,
+};
+use blog_server::infrastructure::clock::MockClock;
+use blog_server::presentation::http_handlers::api_routes;
+use blog_server::presentation::quota::quota_headers;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app with the given quota service.
+macro_rules! init_app {
+    ($pool:expr, $quota_service:expr) => {{
+        let user_repo = Arc::new(UserRepository::new($pool.clone()));
+        let auth_service = AuthService::new(
+            Arc::clone(&user_repo),
+            Arc::new(FeatureFlagRepository::new($pool.clone())),
+            Arc::new(RecoveryCodeRepository::new($pool.clone())),
+            Arc::new(ServiceAccountRepository::new($pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+
+        test::init_service(
+            App::new()
+                .wrap(from_fn(quota_headers))
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new($quota_service))
+                .app_data(web::Data::new(SettingsService::new(user_repo)))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user and get the auth response.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp
+    }};
+}
+
+/// Macro to send an authenticated request with an optional JSON body.
+macro_rules! send {
+    ($app:expr, $method:ident, $uri:expr, $token:expr) => {{
+        test::TestRequest::$method()
+            .uri($uri)
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .send_request($app)
+            .await
+    }};
+    ($app:expr, $method:ident, $uri:expr, $token:expr, $body:expr) => {{
+        test::TestRequest::$method()
+            .uri($uri)
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json($body)
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Macro to create a writing service account for `bot` with the given daily
+/// quotas, returning its ID and token.
+macro_rules! create_account {
+    ($app:expr, $admin_token:expr, $user_id:expr, $requests:expr, $writes:expr) => {{
+        let req = CreateServiceAccountRequest {
+            name: "Feed bot".to_string(),
+            user_id: $user_id,
+            scopes: vec![ServiceScope::Write],
+        };
+        let resp = send!(
+            $app,
+            post,
+            "/api/admin/service-accounts",
+            $admin_token,
+            &req
+        );
+        let created: CreatedServiceAccountResponse = test::read_body_json(resp).await;
+
+        let quota = ServiceAccountQuotaRequest {
+            requests_per_day: $requests,
+            writes_per_day: $writes,
+        };
+        let uri = format!("/api/admin/service-accounts/{}/quota", created.account.id);
+        let resp = send!($app, put, &uri, $admin_token, &quota);
+        assert_eq!(resp.status(), 200);
+        let account: ServiceAccountDto = test::read_body_json(resp).await;
+        assert_eq!(account.requests_per_day, $requests);
+        assert_eq!(account.writes_per_day, $writes);
+
+        (created.account.id, created.token)
+    }};
+}
+
+/// Macro to get a response header as a string.
+macro_rules! header {
+    ($resp:expr, $name:expr) => {
+        $resp
+            .headers()
+            .get($name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+}
+
+/// Sets a user's role directly in the database.
+async fn set_role(pool: &SqlitePool, username: &str, role: &str) {
+    sqlx::query("UPDATE users SET role = ? WHERE username = ?")
+        .bind(role)
+        .bind(username)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+/// Profile update, a request that changes data.
+fn profile() -> ProfileDto {
+    ProfileDto {
+        bio: "Beep boop".to_string(),
+        ..ProfileDto::default()
+    }
+}
+
+/// Requests and writes count against their limits, are reported in the
+/// `X-RateLimit-*` headers, and get 429 once used up.
+#[tokio::test]
+async fn test_quota_limits_requests_and_writes() {
+    let pool = setup_test_db().await;
+    let quota_service = QuotaService::new(Arc::new(ServiceAccountRepository::new(pool.clone())));
+    let app = init_app!(pool, quota_service);
+    let admin = register_user!(&app, "admin");
+    set_role(&pool, "admin", "admin").await;
+    let bot = register_user!(&app, "bot");
+    let (_, token) = create_account!(&app, admin.token, bot.user.id, Some(3), Some(1));
+
+    let resp = send!(&app, get, "/api/me/api-usage", token);
+    assert_eq!(resp.status(), 200);
+    assert_eq!(header!(resp, "x-ratelimit-limit").as_deref(), Some("3"));
+    assert_eq!(header!(resp, "x-ratelimit-remaining").as_deref(), Some("2"));
+    assert!(header!(resp, "x-ratelimit-reset").is_some());
+
+    // A write reports whichever limit is tighter
+    let uri = "/api/settings/profile";
+    let resp = send!(&app, put, uri, token, &profile());
+    assert_eq!(resp.status(), 200);
+    assert_eq!(header!(resp, "x-ratelimit-limit").as_deref(), Some("1"));
+    assert_eq!(header!(resp, "x-ratelimit-remaining").as_deref(), Some("0"));
+
+    let resp = send!(&app, put, uri, token, &profile());
+    assert_eq!(resp.status(), 429);
+    assert_eq!(header!(resp, "x-ratelimit-remaining").as_deref(), Some("0"));
+    assert!(header!(resp, "retry-after").is_some());
+    let body: ErrorResponse = test::read_body_json(resp).await;
+    assert_eq!(body.code.as_deref(), Some("quota_exceeded"));
+
+    // The rejected write was not counted, so one read is left
+    let resp = send!(&app, get, "/api/me/api-usage", token);
+    assert_eq!(resp.status(), 200);
+    assert_eq!(header!(resp, "x-ratelimit-remaining").as_deref(), Some("0"));
+    let resp = send!(&app, get, "/api/me/api-usage", token);
+    assert_eq!(resp.status(), 429);
+
+    // The owner's own token is not limited, and sees the usage
+    let resp = send!(&app, get, "/api/me/api-usage", bot.token);
+    assert_eq!(resp.status(), 200);
+    assert!(header!(resp, "x-ratelimit-limit").is_none());
+    let usage: ApiUsageResponse = test::read_body_json(resp).await;
+    assert_eq!(usage.service_accounts.len(), 1);
+    assert_eq!(usage.service_accounts[0].requests, 3);
+    assert_eq!(usage.service_accounts[0].writes, 1);
+    assert_eq!(usage.service_accounts[0].requests_per_day, Some(3));
+}
+
+/// Usage survives a restart once saved, and starts over the next UTC day.
+#[tokio::test]
+async fn test_quota_usage_persists_and_resets_daily() {
+    let pool = setup_test_db().await;
+    let a_minute_to_midnight = Utc::now()
+        .date_naive()
+        .and_hms_opt(23, 59, 0)
+        .unwrap()
+        .and_utc();
+    let clock = MockClock::new(a_minute_to_midnight);
+    let repo = Arc::new(ServiceAccountRepository::new(pool.clone()));
+    let quota_service = QuotaService::new(Arc::clone(&repo)).with_clock(Arc::new(clock.clone()));
+    let app = init_app!(pool, quota_service.clone());
+    let admin = register_user!(&app, "admin");
+    set_role(&pool, "admin", "admin").await;
+    let bot = register_user!(&app, "bot");
+    let (_, token) = create_account!(&app, admin.token, bot.user.id, Some(2), None);
+
+    let resp = send!(&app, get, "/api/me/api-usage", token);
+    assert_eq!(resp.status(), 200);
+    quota_service.flush().await;
+
+    // A new service (as after a restart) continues from the saved count
+    let restarted = QuotaService::new(repo).with_clock(Arc::new(clock.clone()));
+    let app = init_app!(pool, restarted);
+    let resp = send!(&app, get, "/api/me/api-usage", token);
+    assert_eq!(resp.status(), 200);
+    assert_eq!(header!(resp, "x-ratelimit-remaining").as_deref(), Some("0"));
+    let resp = send!(&app, get, "/api/me/api-usage", token);
+    assert_eq!(resp.status(), 429);
+    // Counted with the service's clock, up to the next UTC midnight
+    assert_eq!(header!(resp, "retry-after").as_deref(), Some("60"));
+
+    clock.advance(Duration::days(1));
+    let resp = send!(&app, get, "/api/me/api-usage", token);
+    assert_eq!(resp.status(), 200);
+    assert_eq!(header!(resp, "x-ratelimit-remaining").as_deref(), Some("1"));
+}
+
+/// Only admins set quotas, limits cannot be negative, and unknown accounts
+/// are 404.
+#[tokio::test]
+async fn test_set_quota_validation() {
+    let pool = setup_test_db().await;
+    let quota_service = QuotaService::new(Arc::new(ServiceAccountRepository::new(pool.clone())));
+    let app = init_app!(pool, quota_service);
+    let admin = register_user!(&app, "admin");
+    set_role(&pool, "admin", "admin").await;
+    let bot = register_user!(&app, "bot");
+    let (id, _) = create_account!(&app, admin.token, bot.user.id, None, None);
+
+    let uri = format!("/api/admin/service-accounts/{id}/quota");
+    let negative = ServiceAccountQuotaRequest {
+        requests_per_day: Some(-1),
+        writes_per_day: None,
+    };
+    let resp = send!(&app, put, &uri, admin.token, &negative);
+    assert_eq!(resp.status(), 400);
+
+    let quota = ServiceAccountQuotaRequest::default();
+    let resp = send!(&app, put, &uri, bot.token, &quota);
+    assert_eq!(resp.status(), 403);
+
+    let resp = send!(
+        &app,
+        put,
+        "/api/admin/service-accounts/999/quota",
+        admin.token,
+        &quota
+    );
+    assert_eq!(resp.status(), 404);
+}
+
+/// Replicas counting on their own each save their counts; the database
+/// keeps the higher one, whichever saves last.
+#[tokio::test]
+async fn test_saved_usage_keeps_highest_replica_count() {
+    let pool = setup_test_db().await;
+    let repo = Arc::new(ServiceAccountRepository::new(pool.clone()));
+    let busy = QuotaService::new(Arc::clone(&repo));
+    let idle = QuotaService::new(Arc::clone(&repo));
+    let app = init_app!(pool, busy.clone());
+    let admin = register_user!(&app, "admin");
+    set_role(&pool, "admin", "admin").await;
+    let bot = register_user!(&app, "bot");
+    let (_, token) = create_account!(&app, admin.token, bot.user.id, Some(3), None);
+
+    for _ in 0..2 {
+        let resp = send!(&app, get, "/api/me/api-usage", token);
+        assert_eq!(resp.status(), 200);
+    }
+    let other_app = init_app!(pool, idle.clone());
+    let resp = send!(&other_app, get, "/api/me/api-usage", token);
+    assert_eq!(resp.status(), 200);
+
+    // The replica with fewer requests saves last
+    busy.flush().await;
+    idle.flush().await;
+
+    let restarted = QuotaService::new(repo);
+    let app = init_app!(pool, restarted);
+    let resp = send!(&app, get, "/api/me/api-usage", token);
+    assert_eq!(resp.status(), 200);
+    assert_eq!(header!(resp, "x-ratelimit-remaining").as_deref(), Some("0"));
+}
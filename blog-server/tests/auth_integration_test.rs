@@ -5,21 +5,34 @@ mod common;
 use std::sync::Arc;
 
 use actix_web::{App, test, web};
-use blog_shared::{AuthResponse, LoginRequest, RegisterRequest, UserDto};
-
-use blog_server::application::AuthService;
-use blog_server::data::UserRepository;
-use blog_server::presentation::JwtSecret;
+use blog_shared::{AuthResponse, LoginRequest, MeResponse, RegisterRequest, Role};
+use chrono::Utc;
+
+use blog_server::application::{AccountService, AuthService, SettingsService};
+use blog_server::data::{
+    AccountRepository, FeatureFlagRepository, MessageRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::infrastructure::database;
+use blog_server::infrastructure::jwt::JwtConfig;
 use blog_server::presentation::http_handlers::api_routes;
 
-use common::{TEST_JWT_SECRET, setup_test_db};
+use common::{setup_test_db, test_jwt};
 
 /// Test user registration creates a new user.
 #[tokio::test]
 async fn test_register_creates_user() {
     let pool = setup_test_db().await;
-    let user_repo = Arc::new(UserRepository::new(pool));
-    let auth_service = AuthService::new(Arc::clone(&user_repo), TEST_JWT_SECRET.to_string());
+    let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+    let user_repo = Arc::new(UserRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        flag_repo,
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
 
     let app = test::init_service(
         App::new()
@@ -32,6 +45,7 @@ async fn test_register_creates_user() {
         username: "testuser".to_string(),
         email: "test@example.com".to_string(),
         password: "secret123".to_string(),
+        invite_code: None,
     };
 
     let resp = test::TestRequest::post()
@@ -52,8 +66,16 @@ async fn test_register_creates_user() {
 #[tokio::test]
 async fn test_register_duplicate_username_fails() {
     let pool = setup_test_db().await;
-    let user_repo = Arc::new(UserRepository::new(pool));
-    let auth_service = AuthService::new(Arc::clone(&user_repo), TEST_JWT_SECRET.to_string());
+    let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+    let user_repo = Arc::new(UserRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        flag_repo,
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
 
     let app = test::init_service(
         App::new()
@@ -66,6 +88,7 @@ async fn test_register_duplicate_username_fails() {
         username: "duplicate".to_string(),
         email: "first@example.com".to_string(),
         password: "secret123".to_string(),
+        invite_code: None,
     };
 
     // First registration should succeed
@@ -81,6 +104,7 @@ async fn test_register_duplicate_username_fails() {
         username: "duplicate".to_string(),
         email: "second@example.com".to_string(),
         password: "secret456".to_string(),
+        invite_code: None,
     };
 
     let resp2 = test::TestRequest::post()
@@ -92,12 +116,161 @@ async fn test_register_duplicate_username_fails() {
     assert!(resp2.status().is_client_error());
 }
 
+/// Usernames differing only in case or surrounding whitespace collide.
+#[tokio::test]
+async fn test_register_username_is_case_insensitive() {
+    let pool = setup_test_db().await;
+    let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+    let user_repo = Arc::new(UserRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        flag_repo,
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    let register = |username: &str, email: &str| RegisterRequest {
+        username: username.to_string(),
+        email: email.to_string(),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+
+    let resp = test::TestRequest::post()
+        .uri("/api/auth/register")
+        .set_json(register("  Bob ", "bob@example.com"))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 201);
+    let auth_resp: AuthResponse = test::read_body_json(resp).await;
+    assert_eq!(auth_resp.user.username, "Bob");
+
+    let resp = test::TestRequest::post()
+        .uri("/api/auth/register")
+        .set_json(register("bob", "other@example.com"))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 400);
+
+    let found = user_repo.find_by_username("BOB").await.unwrap();
+    assert_eq!(found.map(|u| u.username).as_deref(), Some("Bob"));
+}
+
+/// Test the data migration recomputing normalized usernames that the SQL
+/// backfill got wrong (non-ASCII case, NFD), suffixing later collisions.
+#[tokio::test]
+async fn test_migration_normalizes_usernames() {
+    let pool = setup_test_db().await;
+    let user_repo = UserRepository::new(pool.clone());
+    let older = user_repo
+        .create("Ärger", "older@example.com", "hash", Utc::now())
+        .await
+        .unwrap();
+    let decomposed = user_repo
+        .create("Cafe\u{301}", "cafe@example.com", "hash", Utc::now())
+        .await
+        .unwrap();
+    let newer = user_repo
+        .create("Other", "newer@example.com", "hash", Utc::now())
+        .await
+        .unwrap();
+
+    // Rows as the SQL backfill left them
+    sqlx::query("UPDATE users SET username = 'ärger' WHERE id = ?")
+        .bind(newer.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE users SET username = 'Cafe' || char(769) WHERE id = ?")
+        .bind(decomposed.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE users SET username_normalized = lower(trim(username))")
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert!(user_repo.find_by_username("café").await.unwrap().is_none());
+    sqlx::query("DELETE FROM data_migrations")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    database::run_migrations(&pool).await.unwrap();
+
+    let found = user_repo.find_by_username("ÄRGER").await.unwrap().unwrap();
+    assert_eq!(found.id, older.id);
+    let found = user_repo.find_by_username("café").await.unwrap().unwrap();
+    assert_eq!(found.id, decomposed.id);
+    let suffixed: String = sqlx::query_scalar("SELECT username_normalized FROM users WHERE id = ?")
+        .bind(newer.id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(suffixed, format!("ärger#{}", newer.id));
+}
+
+/// Reserved usernames are rejected in any case.
+#[tokio::test]
+async fn test_register_reserved_username_fails() {
+    let pool = setup_test_db().await;
+    let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+    let user_repo = Arc::new(UserRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        flag_repo,
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        vec!["admin".to_string()],
+    );
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    let req = RegisterRequest {
+        username: "Admin".to_string(),
+        email: "admin@example.com".to_string(),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+
+    let resp = test::TestRequest::post()
+        .uri("/api/auth/register")
+        .set_json(&req)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), 400);
+}
+
 /// Test successful login.
 #[tokio::test]
 async fn test_login_success() {
     let pool = setup_test_db().await;
-    let user_repo = Arc::new(UserRepository::new(pool));
-    let auth_service = AuthService::new(Arc::clone(&user_repo), TEST_JWT_SECRET.to_string());
+    let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+    let user_repo = Arc::new(UserRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        flag_repo,
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
 
     let app = test::init_service(
         App::new()
@@ -111,6 +284,7 @@ async fn test_login_success() {
         username: "loginuser".to_string(),
         email: "login@example.com".to_string(),
         password: "secret123".to_string(),
+        invite_code: None,
     };
 
     test::TestRequest::post()
@@ -138,12 +312,87 @@ async fn test_login_success() {
     assert_eq!(auth_resp.user.username, "loginuser");
 }
 
+/// Login accepts the email address or username in any case.
+#[tokio::test]
+async fn test_login_with_email_or_username_ignores_case() {
+    let pool = setup_test_db().await;
+    let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+    let user_repo = Arc::new(UserRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        flag_repo,
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    let register_req = RegisterRequest {
+        username: "CaseUser".to_string(),
+        email: "Case.User@Example.com".to_string(),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    test::TestRequest::post()
+        .uri("/api/auth/register")
+        .set_json(&register_req)
+        .send_request(&app)
+        .await;
+
+    for login in [
+        "caseuser",
+        "CASEUSER",
+        "case.user@example.com",
+        " Case.User@EXAMPLE.com ",
+    ] {
+        let login_req = LoginRequest {
+            username: login.to_string(),
+            password: "secret123".to_string(),
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/login")
+            .set_json(&login_req)
+            .send_request(&app)
+            .await;
+        assert_eq!(resp.status(), 200, "login as {login:?}");
+
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        assert_eq!(auth_resp.user.username, "CaseUser");
+    }
+
+    let login_req = LoginRequest {
+        username: "nobody@example.com".to_string(),
+        password: "secret123".to_string(),
+    };
+    let resp = test::TestRequest::post()
+        .uri("/api/auth/login")
+        .set_json(&login_req)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 401);
+}
+
 /// Test login fails with invalid credentials.
 #[tokio::test]
 async fn test_login_invalid_credentials_fails() {
     let pool = setup_test_db().await;
-    let user_repo = Arc::new(UserRepository::new(pool));
-    let auth_service = AuthService::new(Arc::clone(&user_repo), TEST_JWT_SECRET.to_string());
+    let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+    let user_repo = Arc::new(UserRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        flag_repo,
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
 
     let app = test::init_service(
         App::new()
@@ -157,6 +406,7 @@ async fn test_login_invalid_credentials_fails() {
         username: "validuser".to_string(),
         email: "valid@example.com".to_string(),
         password: "correctpassword".to_string(),
+        invite_code: None,
     };
 
     test::TestRequest::post()
@@ -184,14 +434,28 @@ async fn test_login_invalid_credentials_fails() {
 #[tokio::test]
 async fn test_get_me_with_valid_token() {
     let pool = setup_test_db().await;
-    let user_repo = Arc::new(UserRepository::new(pool));
-    let auth_service = AuthService::new(Arc::clone(&user_repo), TEST_JWT_SECRET.to_string());
-    let jwt_secret = JwtSecret(TEST_JWT_SECRET.to_string());
+    let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+    let user_repo = Arc::new(UserRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        Arc::clone(&flag_repo),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let account_service = AccountService::new(
+        auth_service.clone(),
+        SettingsService::new(user_repo),
+        Arc::new(AccountRepository::new(pool.clone())),
+        Arc::new(MessageRepository::new(pool)),
+        flag_repo,
+    );
 
     let app = test::init_service(
         App::new()
-            .app_data(web::Data::new(jwt_secret))
             .app_data(web::Data::new(auth_service.clone()))
+            .app_data(web::Data::new(account_service))
             .service(web::scope("/api").service(api_routes())),
     )
     .await;
@@ -201,6 +465,7 @@ async fn test_get_me_with_valid_token() {
         username: "meuser".to_string(),
         email: "me@example.com".to_string(),
         password: "secret123".to_string(),
+        invite_code: None,
     };
 
     let resp = test::TestRequest::post()
@@ -221,21 +486,35 @@ async fn test_get_me_with_valid_token() {
 
     assert_eq!(resp.status(), 200);
 
-    let user: UserDto = test::read_body_json(resp).await;
-    assert_eq!(user.username, "meuser");
+    let me: MeResponse = test::read_body_json(resp).await;
+    assert_eq!(me.user.username, "meuser");
+    assert_eq!(me.user.role, Role::User);
+    assert!(me.permissions.iter().any(|p| p == "posts:write"));
+    assert!(!me.permissions.iter().any(|p| p == "admin:dashboard"));
+    assert_eq!(me.usage.posts, 0);
+    assert_eq!(me.usage.upload_bytes, 0);
+    assert_eq!(me.unread_messages, 0);
+    assert!(!me.feature_flags.is_empty());
+    assert_eq!(me.preferences.timezone, "UTC");
 }
 
 /// Test /auth/me endpoint fails without token.
 #[tokio::test]
 async fn test_get_me_without_token_fails() {
     let pool = setup_test_db().await;
-    let user_repo = Arc::new(UserRepository::new(pool));
-    let auth_service = AuthService::new(Arc::clone(&user_repo), TEST_JWT_SECRET.to_string());
-    let jwt_secret = JwtSecret(TEST_JWT_SECRET.to_string());
+    let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+    let user_repo = Arc::new(UserRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        flag_repo,
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
 
     let app = test::init_service(
         App::new()
-            .app_data(web::Data::new(jwt_secret))
             .app_data(web::Data::new(auth_service))
             .service(web::scope("/api").service(api_routes())),
     )
@@ -249,3 +528,80 @@ async fn test_get_me_without_token_fails() {
 
     assert!(resp.status().is_client_error());
 }
+
+/// Tokens signed with the same secret but for another issuer or audience
+/// (another environment) are rejected.
+#[tokio::test]
+async fn test_token_from_other_environment_fails() {
+    let pool = setup_test_db().await;
+    let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+    let user_repo = Arc::new(UserRepository::new(pool.clone()));
+    let recovery_repo = Arc::new(RecoveryCodeRepository::new(pool.clone()));
+    let service_account_repo = Arc::new(ServiceAccountRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        Arc::clone(&flag_repo),
+        Arc::clone(&recovery_repo),
+        Arc::clone(&service_account_repo),
+        test_jwt(),
+        Vec::new(),
+    );
+    let account_service = AccountService::new(
+        auth_service.clone(),
+        SettingsService::new(Arc::clone(&user_repo)),
+        Arc::new(AccountRepository::new(pool.clone())),
+        Arc::new(MessageRepository::new(pool)),
+        Arc::clone(&flag_repo),
+    );
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service.clone()))
+            .app_data(web::Data::new(account_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    let register_req = RegisterRequest {
+        username: "envuser".to_string(),
+        email: "env@example.com".to_string(),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    auth_service.register(register_req, None).await.unwrap();
+
+    let other_issuer = JwtConfig {
+        issuer: "https://staging.example.com".to_string(),
+        ..test_jwt()
+    };
+    let other_audience = JwtConfig {
+        audience: "other-api".to_string(),
+        ..test_jwt()
+    };
+    for (jwt, expected) in [
+        (test_jwt(), 200),
+        (other_issuer, 401),
+        (other_audience, 401),
+    ] {
+        let service = AuthService::new(
+            Arc::clone(&user_repo),
+            Arc::clone(&flag_repo),
+            Arc::clone(&recovery_repo),
+            Arc::clone(&service_account_repo),
+            jwt,
+            Vec::new(),
+        );
+        let login_req = LoginRequest {
+            username: "envuser".to_string(),
+            password: "secret123".to_string(),
+        };
+        let token = service.login(login_req, None).await.unwrap().token;
+
+        let resp = test::TestRequest::get()
+            .uri("/api/auth/me")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .send_request(&app)
+            .await;
+        assert_eq!(resp.status(), expected);
+    }
+}
@@ -0,0 +1,88 @@
+//! Integration tests for the fault injection middleware.
+
+use std::time::{Duration, Instant};
+
+use actix_web::middleware::from_fn;
+use actix_web::{App, test, web};
+
+use blog_server::infrastructure::config::ChaosConfig;
+use blog_server::presentation::api_routes;
+use blog_server::presentation::chaos::chaos;
+
+/// Macro to build a test app with the given chaos settings.
+macro_rules! init_app {
+    ($config:expr) => {{
+        test::init_service(
+            App::new()
+                .wrap(from_fn(chaos))
+                .app_data(web::Data::new($config))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Test that requests pass through when all rates are zero.
+#[tokio::test]
+async fn test_chaos_disabled_passes_through() {
+    let app = init_app!(ChaosConfig::default());
+
+    let resp = test::TestRequest::get()
+        .uri("/api/health")
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), 200);
+}
+
+/// Test that an error rate of 1.0 fails every request with 500.
+#[tokio::test]
+async fn test_chaos_error_rate_returns_500() {
+    let app = init_app!(ChaosConfig {
+        error_rate: 1.0,
+        ..ChaosConfig::default()
+    });
+
+    let resp = test::TestRequest::get()
+        .uri("/api/health")
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), 500);
+}
+
+/// Test that a drop rate of 1.0 produces a body that fails mid-stream.
+#[tokio::test]
+async fn test_chaos_drop_rate_breaks_body() {
+    let app = init_app!(ChaosConfig {
+        drop_rate: 1.0,
+        ..ChaosConfig::default()
+    });
+
+    let resp = test::TestRequest::get()
+        .uri("/api/health")
+        .send_request(&app)
+        .await;
+
+    let body = resp.into_body();
+    assert!(actix_web::body::to_bytes(body).await.is_err());
+}
+
+/// Test that injected latency stays within the configured bound.
+#[tokio::test]
+async fn test_chaos_latency_is_bounded() {
+    let app = init_app!(ChaosConfig {
+        latency_rate: 1.0,
+        latency_ms: 20,
+        ..ChaosConfig::default()
+    });
+
+    let start = Instant::now();
+    let resp = test::TestRequest::get()
+        .uri("/api/health")
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), 200);
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
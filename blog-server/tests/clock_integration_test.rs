@@ -0,0 +1,89 @@
+//! Integration tests for time-dependent behavior under a mock clock.
+
+mod common;
+
+use std::sync::Arc;
+
+use blog_shared::RegisterRequest;
+use chrono::{Duration, Utc};
+
+use blog_server::application::{AuthService, SettingsService};
+use blog_server::data::{
+    FeatureFlagRepository, RecoveryCodeRepository, ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::AppError;
+use blog_server::infrastructure::clock::{Clock, MockClock};
+
+use common::{setup_test_db, test_jwt};
+
+/// Builds the auth and settings services on a shared mock clock.
+async fn services(clock: &MockClock) -> (AuthService, SettingsService) {
+    let pool = setup_test_db().await;
+    let user_repo = Arc::new(UserRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        Arc::new(FeatureFlagRepository::new(pool.clone())),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool)),
+        test_jwt(),
+        Vec::new(),
+    )
+    .with_clock(Arc::new(clock.clone()));
+    let settings_service = SettingsService::new(user_repo).with_clock(Arc::new(clock.clone()));
+    (auth_service, settings_service)
+}
+
+/// Registers a user, returning their token.
+async fn register(auth_service: &AuthService) -> String {
+    let req = RegisterRequest {
+        username: "alice".to_string(),
+        email: "alice@example.com".to_string(),
+        password: "password123".to_string(),
+        invite_code: None,
+    };
+    auth_service.register(req, None).await.unwrap().token
+}
+
+/// Test tokens expiring once the clock passes 24 hours.
+#[tokio::test]
+async fn test_token_expires_with_clock() {
+    let clock = MockClock::new(Utc::now());
+    let (auth_service, _) = services(&clock).await;
+    let token = register(&auth_service).await;
+
+    clock.advance(Duration::hours(23));
+    assert!(auth_service.authenticate(&token).await.is_ok());
+
+    clock.advance(Duration::hours(2));
+    assert!(matches!(
+        auth_service.authenticate(&token).await,
+        Err(AppError::Jwt(_))
+    ));
+}
+
+/// Test tokens being checked against the clock, not the system time.
+#[tokio::test]
+async fn test_token_valid_at_issue_time() {
+    let clock = MockClock::new(Utc::now() - Duration::days(2));
+    let (auth_service, _) = services(&clock).await;
+    let token = register(&auth_service).await;
+
+    let claims = auth_service.authenticate(&token).await.unwrap();
+    assert_eq!(claims.iat as i64, clock.now().timestamp());
+}
+
+/// Test sessions dropping out of the list once their token would expire.
+#[tokio::test]
+async fn test_sessions_listed_until_expiry() {
+    let clock = MockClock::new(Utc::now());
+    let (auth_service, settings_service) = services(&clock).await;
+    let token = register(&auth_service).await;
+    let user_id = auth_service.authenticate(&token).await.unwrap().sub;
+
+    let sessions = settings_service.list_sessions(user_id, None).await.unwrap();
+    assert_eq!(sessions.sessions.len(), 1);
+
+    clock.advance(Duration::hours(25));
+    let sessions = settings_service.list_sessions(user_id, None).await.unwrap();
+    assert!(sessions.sessions.is_empty());
+}
@@ -0,0 +1,405 @@
+//! Integration tests for the live comment stream over gRPC.
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use blog_shared::constants::ERROR_CODE_METADATA_KEY;
+use blog_shared::{
+    CreateCommentRequest, CreatePostRequest, CreateServiceAccountRequest, PostDto, RegisterRequest,
+    ServiceAccountQuotaRequest, ServiceScope,
+};
+use sqlx::SqlitePool;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tonic::transport::{Channel, Server};
+use tonic::{Code, Streaming};
+
+use blog_server::application::{
+    AuthService, BlogService, CommentService, ImportService, LinkCheckService, ModerationService,
+    NotificationService, QuotaService,
+};
+use blog_server::data::{
+    CommentRepository, FeatureFlagRepository, LinkCheckRepository, ModerationRepository,
+    NotificationRepository, PostRepository, RecoveryCodeRepository, ServiceAccountRepository,
+    UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::infrastructure::event_bus::EventBus;
+use blog_server::infrastructure::http_fetcher::HttpFetcher;
+use blog_server::presentation::grpc_service::GrpcBlogService;
+use blog_server::presentation::grpc_service::proto::blog_service_client::BlogServiceClient;
+use blog_server::presentation::grpc_service::proto::blog_service_server::BlogServiceServer;
+use blog_server::presentation::grpc_service::proto::comment_stream_response::Event;
+use blog_server::presentation::grpc_service::proto::{CommentStreamRequest, CommentStreamResponse};
+
+use common::{setup_test_db, test_jwt};
+
+/// The services a test drives directly, next to the gRPC server.
+struct TestServer {
+    client: BlogServiceClient<Channel>,
+    auth_service: AuthService,
+    blog_service: BlogService,
+    comment_service: CommentService,
+}
+
+/// Starts a gRPC server with the blog service on an ephemeral port.
+async fn start_server(pool: &SqlitePool) -> TestServer {
+    let user_repo = Arc::new(UserRepository::new(pool.clone()));
+    let post_repo = Arc::new(PostRepository::new(pool.clone()));
+    let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+    let service_account_repo = Arc::new(ServiceAccountRepository::new(pool.clone()));
+    let events = EventBus::new();
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        Arc::clone(&flag_repo),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::clone(&service_account_repo),
+        test_jwt(),
+        Vec::new(),
+    );
+    let moderation_service =
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+    let blog_service = BlogService::new(
+        Arc::clone(&post_repo),
+        EmbedProvider::ALL.to_vec(),
+        moderation_service.clone(),
+    );
+    let comment_service = CommentService::new(
+        Arc::new(CommentRepository::new(pool.clone())),
+        Arc::clone(&post_repo),
+        flag_repo,
+        moderation_service.clone(),
+        events.clone(),
+    );
+    let link_check_service = LinkCheckService::new(
+        Arc::new(LinkCheckRepository::new(pool.clone())),
+        HttpFetcher::new().unwrap(),
+    );
+    let import_service = ImportService::new(
+        user_repo,
+        post_repo,
+        blog_service.clone(),
+        moderation_service,
+    );
+    let grpc_service = GrpcBlogService::new(
+        blog_service.clone(),
+        auth_service.clone(),
+        link_check_service,
+        import_service,
+        comment_service.clone(),
+        NotificationService::new(Arc::new(NotificationRepository::new(pool.clone()))),
+        events,
+    )
+    .with_quotas(QuotaService::new(service_account_repo));
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        Server::builder()
+            .add_service(BlogServiceServer::new(grpc_service))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    let client = BlogServiceClient::connect(format!("http://{addr}"))
+        .await
+        .unwrap();
+
+    TestServer {
+        client,
+        auth_service,
+        blog_service,
+        comment_service,
+    }
+}
+
+/// Registers a user, returning their ID and token.
+async fn register(auth_service: &AuthService, username: &str) -> (i64, String) {
+    let req = RegisterRequest {
+        username: username.to_string(),
+        email: format!("{username}@example.com"),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    let auth = auth_service.register(req, None).await.unwrap();
+    (auth.user.id, auth.token)
+}
+
+/// Creates a post.
+async fn create_post(blog_service: &BlogService, author_id: i64) -> PostDto {
+    let req = CreatePostRequest {
+        title: "Live post".to_string(),
+        content: "Talk about it".to_string(),
+        tags: Vec::new(),
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
+    };
+    blog_service.create_post(author_id, req).await.unwrap()
+}
+
+/// Opens a comment stream on a post, returning the sender for comments and
+/// the stream of events.
+async fn open_stream(
+    client: &mut BlogServiceClient<Channel>,
+    post_public_id: &str,
+    token: &str,
+) -> Result<
+    (
+        mpsc::Sender<CommentStreamRequest>,
+        Streaming<CommentStreamResponse>,
+    ),
+    tonic::Status,
+> {
+    let (tx, rx) = mpsc::channel(8);
+    tx.send(CommentStreamRequest {
+        token: token.to_string(),
+        post_public_id: post_public_id.to_string(),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    let response = client.stream_comments(ReceiverStream::new(rx)).await?;
+    Ok((tx, response.into_inner()))
+}
+
+/// Sends a comment on an open stream.
+async fn send_comment(tx: &mpsc::Sender<CommentStreamRequest>, content: &str) {
+    tx.send(CommentStreamRequest {
+        content: content.to_string(),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+}
+
+/// Waits for the next event on a stream.
+async fn next_event(stream: &mut Streaming<CommentStreamResponse>) -> Event {
+    tokio::time::timeout(Duration::from_secs(5), stream.message())
+        .await
+        .expect("timed out waiting for a comment event")
+        .unwrap()
+        .and_then(|response| response.event)
+        .unwrap()
+}
+
+/// Returns the content of a comment event.
+fn comment_content(event: Event) -> String {
+    match event {
+        Event::Comment(comment) => comment.content,
+        Event::Error(error) => panic!("expected a comment, got error {error:?}"),
+    }
+}
+
+/// Test comments sent on a stream or through the API reach every subscriber.
+#[tokio::test]
+async fn test_stream_comments_live() {
+    let pool = setup_test_db().await;
+    let mut server = start_server(&pool).await;
+    let (alice_id, alice_token) = register(&server.auth_service, "alice").await;
+    let (bob_id, _) = register(&server.auth_service, "bob").await;
+    let post = create_post(&server.blog_service, alice_id).await;
+    let other_post = create_post(&server.blog_service, alice_id).await;
+
+    let (alice_tx, mut alice_events) =
+        open_stream(&mut server.client, &post.public_id, &alice_token)
+            .await
+            .unwrap();
+    let (_anon_tx, mut anon_events) = open_stream(&mut server.client, &post.public_id, "")
+        .await
+        .unwrap();
+
+    send_comment(&alice_tx, "Hello, live readers").await;
+    let Event::Comment(comment) = next_event(&mut alice_events).await else {
+        panic!("expected alice's comment");
+    };
+    assert_eq!(comment.post_id, post.id);
+    assert_eq!(comment.post_public_id, post.public_id);
+    assert_eq!(comment.author_username, "alice");
+    assert_eq!(
+        comment_content(next_event(&mut anon_events).await),
+        "Hello, live readers"
+    );
+
+    let comment = |content: &str| CreateCommentRequest {
+        content: content.to_string(),
+    };
+    server
+        .comment_service
+        .create_comment(other_post.id, bob_id, comment("Elsewhere"))
+        .await
+        .unwrap();
+    server
+        .comment_service
+        .create_comment(post.id, bob_id, comment("From the web"))
+        .await
+        .unwrap();
+    assert_eq!(
+        comment_content(next_event(&mut anon_events).await),
+        "From the web"
+    );
+    assert_eq!(
+        comment_content(next_event(&mut alice_events).await),
+        "From the web"
+    );
+}
+
+/// Test rejected comments are reported without closing the stream.
+#[tokio::test]
+async fn test_stream_comments_rejected() {
+    let pool = setup_test_db().await;
+    let mut server = start_server(&pool).await;
+    let (alice_id, alice_token) = register(&server.auth_service, "alice").await;
+    let post = create_post(&server.blog_service, alice_id).await;
+
+    let (anon_tx, mut anon_events) = open_stream(&mut server.client, &post.public_id, "")
+        .await
+        .unwrap();
+    send_comment(&anon_tx, "Let me in").await;
+    let Event::Error(error) = next_event(&mut anon_events).await else {
+        panic!("expected an error");
+    };
+    assert_eq!(error, "Sign in to comment");
+
+    let (alice_tx, mut alice_events) =
+        open_stream(&mut server.client, &post.public_id, &alice_token)
+            .await
+            .unwrap();
+    send_comment(&alice_tx, "   ").await;
+    let Event::Error(error) = next_event(&mut alice_events).await else {
+        panic!("expected an error");
+    };
+    assert!(error.contains("Comment cannot be empty"));
+
+    send_comment(&alice_tx, "Second try").await;
+    assert_eq!(
+        comment_content(next_event(&mut alice_events).await),
+        "Second try"
+    );
+    assert_eq!(
+        comment_content(next_event(&mut anon_events).await),
+        "Second try"
+    );
+}
+
+/// Test a shadow-banned author's comments only reach their own stream.
+#[tokio::test]
+async fn test_stream_comments_shadow_ban() {
+    let pool = setup_test_db().await;
+    let mut server = start_server(&pool).await;
+    let (alice_id, alice_token) = register(&server.auth_service, "alice").await;
+    let (_, carol_token) = register(&server.auth_service, "carol").await;
+    let post = create_post(&server.blog_service, alice_id).await;
+    sqlx::query("UPDATE users SET shadow_banned_at = CURRENT_TIMESTAMP WHERE username = 'carol'")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (carol_tx, mut carol_events) =
+        open_stream(&mut server.client, &post.public_id, &carol_token)
+            .await
+            .unwrap();
+    let (alice_tx, mut alice_events) =
+        open_stream(&mut server.client, &post.public_id, &alice_token)
+            .await
+            .unwrap();
+
+    send_comment(&carol_tx, "Spam").await;
+    assert_eq!(comment_content(next_event(&mut carol_events).await), "Spam");
+
+    send_comment(&alice_tx, "Welcome").await;
+    assert_eq!(
+        comment_content(next_event(&mut alice_events).await),
+        "Welcome"
+    );
+    assert_eq!(
+        comment_content(next_event(&mut carol_events).await),
+        "Welcome"
+    );
+}
+
+/// Test opening a stream needs a visible post and a valid token.
+#[tokio::test]
+async fn test_stream_comments_open_errors() {
+    let pool = setup_test_db().await;
+    let mut server = start_server(&pool).await;
+    let (alice_id, _) = register(&server.auth_service, "alice").await;
+    let post = create_post(&server.blog_service, alice_id).await;
+
+    let status = open_stream(
+        &mut server.client,
+        "00000000-0000-4000-8000-000000000000",
+        "",
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+    assert_eq!(
+        status.metadata().get(ERROR_CODE_METADATA_KEY).unwrap(),
+        "post_not_found"
+    );
+
+    let status = open_stream(&mut server.client, &post.public_id, "not-a-token")
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+}
+
+/// Test service token calls count against the account's quotas: opening a
+/// stream is a request, each comment posted on it a write.
+#[tokio::test]
+async fn test_stream_comments_count_service_quotas() {
+    let pool = setup_test_db().await;
+    let mut server = start_server(&pool).await;
+    let (alice_id, _) = register(&server.auth_service, "alice").await;
+    let post = create_post(&server.blog_service, alice_id).await;
+    let created = server
+        .auth_service
+        .create_service_account(
+            alice_id,
+            CreateServiceAccountRequest {
+                name: "Comment bot".to_string(),
+                user_id: alice_id,
+                scopes: vec![ServiceScope::Write],
+            },
+        )
+        .await
+        .unwrap();
+    server
+        .auth_service
+        .set_service_account_quota(
+            created.account.id,
+            ServiceAccountQuotaRequest {
+                requests_per_day: Some(3),
+                writes_per_day: Some(1),
+            },
+        )
+        .await
+        .unwrap();
+
+    let (tx, mut events) = open_stream(&mut server.client, &post.public_id, &created.token)
+        .await
+        .unwrap();
+    send_comment(&tx, "First").await;
+    assert_eq!(comment_content(next_event(&mut events).await), "First");
+    send_comment(&tx, "Second").await;
+    let Event::Error(error) = next_event(&mut events).await else {
+        panic!("expected an error");
+    };
+    assert!(error.contains("quota"));
+
+    // The rejected comment was not counted, so one more call is allowed
+    open_stream(&mut server.client, &post.public_id, &created.token)
+        .await
+        .unwrap();
+    let status = open_stream(&mut server.client, &post.public_id, &created.token)
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::ResourceExhausted);
+    assert_eq!(
+        status.metadata().get(ERROR_CODE_METADATA_KEY).unwrap(),
+        "quota_exceeded"
+    );
+}
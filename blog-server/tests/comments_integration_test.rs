@@ -0,0 +1,252 @@
+//! Integration tests for comments endpoints.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    AuthResponse, CommentDto, CommentListResponse, CreateCommentRequest, CreatePostRequest,
+    PostDto, RegisterRequest, UpdateCommentRequest,
+};
+
+use blog_server::application::{AuthService, BlogService, CommentService, ModerationService};
+use blog_server::data::{
+    CommentRepository, FeatureFlagRepository, ModerationRepository, PostRepository,
+    RecoveryCodeRepository, ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::infrastructure::event_bus::EventBus;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app with auth, blog, and comment services.
+macro_rules! init_app {
+    () => {{
+        let pool = setup_test_db().await;
+        let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+        let user_repo = Arc::new(UserRepository::new(pool.clone()));
+        let post_repo = Arc::new(PostRepository::new(pool.clone()));
+        let comment_repo = Arc::new(CommentRepository::new(pool.clone()));
+        let auth_service = AuthService::new(
+            Arc::clone(&user_repo),
+            Arc::clone(&flag_repo),
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let moderation_service =
+            ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+        let blog_service = BlogService::new(
+            Arc::clone(&post_repo),
+            EmbedProvider::ALL.to_vec(),
+            moderation_service.clone(),
+        );
+        let comment_service = CommentService::new(
+            comment_repo,
+            Arc::clone(&post_repo),
+            flag_repo,
+            moderation_service,
+            EventBus::new(),
+        );
+
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(blog_service))
+                .app_data(web::Data::new(comment_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user and get their token.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp.token
+    }};
+}
+
+/// Macro to create a post and return it.
+macro_rules! create_post {
+    ($app:expr, $token:expr) => {{
+        let req = CreatePostRequest {
+            title: "Commented Post".to_string(),
+            content: "Content".to_string(),
+            tags: Vec::new(),
+            lang: None,
+            canonical_url: None,
+            original_source: None,
+            org: None,
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/posts")
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        let post: PostDto = test::read_body_json(resp).await;
+        post
+    }};
+}
+
+/// Macro to add a comment and return the response.
+macro_rules! add_comment {
+    ($app:expr, $token:expr, $post_id:expr, $content:expr) => {{
+        test::TestRequest::post()
+            .uri(&format!("/api/posts/{}/comments", $post_id))
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json(&CreateCommentRequest {
+                content: $content.to_string(),
+            })
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Test creating and listing comments on a post.
+#[tokio::test]
+async fn test_create_and_list_comments() {
+    let app = init_app!();
+    let author = register_user!(&app, "author");
+    let reader = register_user!(&app, "reader");
+    let post = create_post!(&app, author);
+
+    let resp = add_comment!(&app, reader, post.public_id, "First!");
+    assert_eq!(resp.status(), 201);
+    let comment: CommentDto = test::read_body_json(resp).await;
+    assert_eq!(comment.author_username, "reader");
+    assert_eq!(comment.post_public_id, post.public_id);
+    assert_eq!(comment.author_public_id.len(), 36);
+
+    add_comment!(&app, author, post.public_id, "Thanks");
+
+    let resp = test::TestRequest::get()
+        .uri(&format!("/api/posts/{}/comments", post.public_id))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 200);
+
+    let list: CommentListResponse = test::read_body_json(resp).await;
+    assert_eq!(list.total, Some(2));
+    assert_eq!(list.comments[0].content, "First!");
+    assert_eq!(list.comments[1].content, "Thanks");
+}
+
+/// Test that commenting requires authentication.
+#[tokio::test]
+async fn test_create_comment_requires_auth() {
+    let app = init_app!();
+    let author = register_user!(&app, "author");
+    let post = create_post!(&app, author);
+
+    let resp = test::TestRequest::post()
+        .uri(&format!("/api/posts/{}/comments", post.public_id))
+        .set_json(&CreateCommentRequest {
+            content: "Anonymous".to_string(),
+        })
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), 401);
+}
+
+/// Test that empty comments are rejected.
+#[tokio::test]
+async fn test_create_comment_empty_rejected() {
+    let app = init_app!();
+    let author = register_user!(&app, "author");
+    let post = create_post!(&app, author);
+
+    let resp = add_comment!(&app, author, post.public_id, "   ");
+
+    assert_eq!(resp.status(), 400);
+}
+
+/// Test that commenting on a missing post returns 404.
+#[tokio::test]
+async fn test_create_comment_missing_post() {
+    let app = init_app!();
+    let reader = register_user!(&app, "reader");
+
+    let resp = add_comment!(&app, reader, 999, "Hello?");
+
+    assert_eq!(resp.status(), 404);
+}
+
+/// Test that only the comment author can edit a comment.
+#[tokio::test]
+async fn test_update_comment_author_only() {
+    let app = init_app!();
+    let author = register_user!(&app, "author");
+    let reader = register_user!(&app, "reader");
+    let post = create_post!(&app, author);
+    let comment: CommentDto =
+        test::read_body_json(add_comment!(&app, reader, post.public_id, "Typo hre")).await;
+
+    let update = UpdateCommentRequest {
+        content: "Typo here".to_string(),
+    };
+
+    let resp = test::TestRequest::put()
+        .uri(&format!("/api/comments/{}", comment.id))
+        .insert_header(("Authorization", format!("Bearer {}", author)))
+        .set_json(&update)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 403);
+
+    let resp = test::TestRequest::put()
+        .uri(&format!("/api/comments/{}", comment.id))
+        .insert_header(("Authorization", format!("Bearer {}", reader)))
+        .set_json(&update)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 200);
+    let updated: CommentDto = test::read_body_json(resp).await;
+    assert_eq!(updated.content, "Typo here");
+}
+
+/// Test that the post author can delete comments on their post.
+#[tokio::test]
+async fn test_delete_comment_by_post_author() {
+    let app = init_app!();
+    let author = register_user!(&app, "author");
+    let reader = register_user!(&app, "reader");
+    let other = register_user!(&app, "other");
+    let post = create_post!(&app, author);
+    let comment: CommentDto =
+        test::read_body_json(add_comment!(&app, reader, post.public_id, "Spam")).await;
+    let uri = format!("/api/comments/{}", comment.id);
+
+    let resp = test::TestRequest::delete()
+        .uri(&uri)
+        .insert_header(("Authorization", format!("Bearer {}", other)))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 403);
+
+    let resp = test::TestRequest::delete()
+        .uri(&uri)
+        .insert_header(("Authorization", format!("Bearer {}", author)))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 204);
+}
@@ -3,6 +3,7 @@
 use sqlx::SqlitePool;
 
 use blog_server::infrastructure::database;
+use blog_server::infrastructure::jwt::JwtConfig;
 
 /// Creates an in-memory SQLite database for testing.
 pub async fn setup_test_db() -> SqlitePool {
@@ -18,3 +19,12 @@ pub async fn setup_test_db() -> SqlitePool {
 /// Test JWT secret for integration tests.
 pub const TEST_JWT_SECRET: &str =
     "test-secret-key-for-integration-tests-minimum-32-characters-long";
+
+/// JWT settings for integration tests.
+pub fn test_jwt() -> JwtConfig {
+    JwtConfig {
+        secret: TEST_JWT_SECRET.to_string(),
+        issuer: "http://localhost:8080".to_string(),
+        audience: "blog-api".to_string(),
+    }
+}
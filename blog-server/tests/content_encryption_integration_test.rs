@@ -0,0 +1,162 @@
+//! Integration tests for post content encryption at rest.
+
+mod common;
+
+use std::sync::Arc;
+
+use blog_shared::{PostStatus, RegisterRequest};
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+use blog_server::application::AuthService;
+use blog_server::data::{
+    FeatureFlagRepository, PostRepository, RecoveryCodeRepository, ServiceAccountRepository,
+    UserRepository,
+};
+use blog_server::domain::NewPost;
+use blog_server::infrastructure::content_cipher::ContentCipher;
+
+use common::{setup_test_db, test_jwt};
+
+/// Builds a cipher from `(id, key)` pairs, active key first.
+fn cipher(keys: &[(&str, &str)]) -> ContentCipher {
+    let spec = keys
+        .iter()
+        .map(|(id, key)| format!("{id}:{key}"))
+        .collect::<Vec<_>>()
+        .join(",");
+    ContentCipher::parse(&spec).unwrap()
+}
+
+/// Registers an author, returning their ID.
+async fn author(pool: &SqlitePool) -> i64 {
+    let auth_service = AuthService::new(
+        Arc::new(UserRepository::new(pool.clone())),
+        Arc::new(FeatureFlagRepository::new(pool.clone())),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let req = RegisterRequest {
+        username: "alice".to_string(),
+        email: "alice@example.com".to_string(),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    auth_service.register(req, None).await.unwrap().user.id
+}
+
+/// Reads the stored content of a post, bypassing the repository.
+async fn stored_content(pool: &SqlitePool, id: i64) -> String {
+    sqlx::query_scalar("SELECT content FROM posts WHERE id = ?")
+        .bind(id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+/// Returns a published English post to create now.
+fn new_post<'a>(title: &'a str, content: &'a str, author_id: i64) -> NewPost<'a> {
+    NewPost {
+        title,
+        content,
+        author_id,
+        lang: "en",
+        translation_group_id: None,
+        status: PostStatus::Published,
+        now: Utc::now(),
+    }
+}
+
+/// Test content being stored sealed and read back as plaintext.
+#[tokio::test]
+async fn test_content_sealed_at_rest() {
+    let pool = setup_test_db().await;
+    let author_id = author(&pool).await;
+    let key = ContentCipher::generate_key().unwrap();
+    let repo = PostRepository::new(pool.clone()).with_cipher(cipher(&[("k1", &key)]));
+
+    let post = repo
+        .create(new_post("Title", "Secret body", author_id))
+        .await
+        .unwrap();
+    assert_eq!(post.content, "Secret body");
+
+    let stored = stored_content(&pool, post.id).await;
+    assert!(stored.starts_with("enc:v1:k1:"));
+    assert!(!stored.contains("Secret"));
+
+    let post = repo.find_by_id(post.id).await.unwrap().unwrap();
+    assert_eq!(post.content, "Secret body");
+
+    let post = repo
+        .update(post.id, None, Some("New body"), Utc::now())
+        .await
+        .unwrap();
+    assert_eq!(post.content, "New body");
+    let listed = repo.list(10, 0, None, None).await.unwrap();
+    assert_eq!(listed[0].content, "New body");
+}
+
+/// Test rotation re-sealing old and plaintext rows under the new key.
+#[tokio::test]
+async fn test_rotation_moves_rows_to_active_key() {
+    let pool = setup_test_db().await;
+    let author_id = author(&pool).await;
+    let old_key = ContentCipher::generate_key().unwrap();
+    let new_key = ContentCipher::generate_key().unwrap();
+
+    let plain = PostRepository::new(pool.clone())
+        .create(new_post("Plain", "Written before encryption", author_id))
+        .await
+        .unwrap();
+    let sealed = PostRepository::new(pool.clone())
+        .with_cipher(cipher(&[("old", &old_key)]))
+        .create(new_post("Sealed", "Written with the old key", author_id))
+        .await
+        .unwrap();
+
+    let repo = PostRepository::new(pool.clone())
+        .with_cipher(cipher(&[("new", &new_key), ("old", &old_key)]));
+    // Both posts and their first revisions
+    assert_eq!(repo.reseal_all().await.unwrap(), 4);
+    assert_eq!(repo.reseal_all().await.unwrap(), 0);
+
+    for id in [plain.id, sealed.id] {
+        assert!(stored_content(&pool, id).await.starts_with("enc:v1:new:"));
+    }
+
+    let repo = PostRepository::new(pool.clone()).with_cipher(cipher(&[("new", &new_key)]));
+    let post = repo.find_by_id(sealed.id).await.unwrap().unwrap();
+    assert_eq!(post.content, "Written with the old key");
+}
+
+/// Test content sealed with an unlisted key failing to read.
+#[tokio::test]
+async fn test_unknown_key_fails() {
+    let pool = setup_test_db().await;
+    let author_id = author(&pool).await;
+    let first = ContentCipher::generate_key().unwrap();
+    let second = ContentCipher::generate_key().unwrap();
+
+    let post = PostRepository::new(pool.clone())
+        .with_cipher(cipher(&[("first", &first)]))
+        .create(new_post("Title", "Body", author_id))
+        .await
+        .unwrap();
+
+    let repo = PostRepository::new(pool.clone()).with_cipher(cipher(&[("second", &second)]));
+    assert!(repo.find_by_id(post.id).await.is_err());
+}
+
+/// Test malformed key lists being rejected.
+#[test]
+fn test_parse_rejects_bad_keys() {
+    let key = ContentCipher::generate_key().unwrap();
+    assert!(ContentCipher::parse("").is_err());
+    assert!(ContentCipher::parse("nokey").is_err());
+    assert!(ContentCipher::parse("short:c2hvcnQ=").is_err());
+    assert!(ContentCipher::parse(&format!("a:{key},a:{key}")).is_err());
+    assert!(ContentCipher::parse(&format!("a:{key}, b:{key}")).is_ok());
+}
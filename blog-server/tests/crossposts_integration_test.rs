@@ -0,0 +1,396 @@
+//! Integration tests for cross-posting to DEV and Hashnode.
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use actix_web::{App, HttpRequest, HttpResponse, HttpServer, test, web};
+use blog_shared::{
+    AuthResponse, CreatePostRequest, CrosspostDto, CrosspostListResponse, CrosspostStatus,
+    CrosspostTarget, IntegrationDto, IntegrationListResponse, IntegrationTokenRequest, PostDto,
+    RegisterRequest, UpdatePostRequest,
+};
+use serde_json::{Value, json};
+
+use blog_server::application::{
+    AuthService, BlogService, CrosspostService, ModerationService, SettingsService,
+};
+use blog_server::data::{
+    CrosspostRepository, FeatureFlagRepository, ModerationRepository, PostRepository,
+    RecoveryCodeRepository, ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::{CrosspostApis, EmbedProvider};
+use blog_server::infrastructure::http_fetcher::HttpFetcher;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Requests received by the stub APIs: method, path, and body.
+type Received = Arc<Mutex<Vec<(String, String, Value)>>>;
+
+/// Macro to build the test app, returning it with its cross-post service.
+macro_rules! init_app {
+    ($apis:expr) => {{
+        let pool = setup_test_db().await;
+        let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+        let user_repo = Arc::new(UserRepository::new(pool.clone()));
+        let post_repo = Arc::new(PostRepository::new(pool.clone()));
+        let auth_service = AuthService::new(
+            Arc::clone(&user_repo),
+            flag_repo,
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let moderation_service =
+            ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+        let blog_service =
+            BlogService::new(post_repo, EmbedProvider::ALL.to_vec(), moderation_service);
+        let settings_service = SettingsService::new(user_repo);
+        let crosspost_service = CrosspostService::new(
+            Arc::new(CrosspostRepository::new(pool.clone())),
+            blog_service.clone(),
+            HttpFetcher::new().unwrap(),
+            $apis,
+            "http://site.test".to_string(),
+        );
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(blog_service))
+                .app_data(web::Data::new(settings_service))
+                .app_data(web::Data::new(crosspost_service.clone()))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await;
+        (app, crosspost_service)
+    }};
+}
+
+/// Macro to register a user and get their token.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp.token
+    }};
+}
+
+/// Macro to create a post and get its public ID.
+macro_rules! create_post {
+    ($app:expr, $token:expr) => {{
+        let req = CreatePostRequest {
+            title: "Hello".to_string(),
+            content: "First *post*".to_string(),
+            tags: vec!["rust".to_string(), "web-dev".to_string()],
+            lang: None,
+            canonical_url: None,
+            original_source: None,
+            org: None,
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/posts")
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        let post: PostDto = test::read_body_json(resp).await;
+        post.public_id
+    }};
+}
+
+/// Macro to save an integration token, returning the response.
+macro_rules! set_integration {
+    ($app:expr, $token:expr, $target:expr, $key:expr, $publication:expr) => {{
+        let req = IntegrationTokenRequest {
+            token: $key.to_string(),
+            publication_id: $publication,
+        };
+        test::TestRequest::put()
+            .uri(&format!("/api/settings/integrations/{}", $target))
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json(&req)
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Macro to cross-post a post, returning the response.
+macro_rules! crosspost {
+    ($app:expr, $token:expr, $id:expr, $target:expr) => {{
+        test::TestRequest::post()
+            .uri(&format!("/api/posts/{}/crosspost?target={}", $id, $target))
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Stub DEV API: accepts the `good` key and answers with article 42.
+async fn devto(
+    req: HttpRequest,
+    body: web::Json<Value>,
+    received: web::Data<Received>,
+) -> HttpResponse {
+    received.lock().unwrap().push((
+        req.method().to_string(),
+        req.path().to_string(),
+        body.into_inner(),
+    ));
+    if req.headers().get("api-key").unwrap() != "good" {
+        return HttpResponse::Unauthorized().finish();
+    }
+    HttpResponse::Ok().json(json!({"id": 42, "url": "https://dev.to/alice/hello-42"}))
+}
+
+/// Stub Hashnode API: publishes as post `h1`, or reports a GraphQL error
+/// for the `bad` token.
+async fn hashnode(
+    req: HttpRequest,
+    body: web::Json<Value>,
+    received: web::Data<Received>,
+) -> HttpResponse {
+    received.lock().unwrap().push((
+        req.method().to_string(),
+        req.path().to_string(),
+        body.into_inner(),
+    ));
+    if req.headers().get("Authorization").unwrap() == "bad" {
+        return HttpResponse::Ok().json(json!({"errors": [{"message": "Invalid token"}]}));
+    }
+    let post = json!({"post": {"id": "h1", "url": "https://alice.hashnode.dev/hello"}});
+    HttpResponse::Ok().json(json!({"data": {"publishPost": post}}))
+}
+
+/// Starts the stub APIs, returning their URLs and the requests they get.
+fn start_stubs() -> (CrosspostApis, Received) {
+    let received: Received = Arc::default();
+    let data = web::Data::from(Arc::new(Arc::clone(&received)));
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(data.clone())
+            .route("/devto/articles", web::post().to(devto))
+            .route("/devto/articles/{id}", web::put().to(devto))
+            .route("/hashnode", web::post().to(hashnode))
+    })
+    .workers(1)
+    .bind(("127.0.0.1", 0))
+    .unwrap();
+    let base = format!("http://{}", server.addrs()[0]);
+    tokio::spawn(server.run());
+
+    let apis = CrosspostApis {
+        devto: format!("{base}/devto"),
+        hashnode: format!("{base}/hashnode"),
+    };
+    (apis, received)
+}
+
+/// Test saving, listing, and removing integration tokens.
+#[tokio::test]
+async fn test_integrations() {
+    let (apis, _) = start_stubs();
+    let (app, _) = init_app!(apis);
+    let token = register_user!(&app, "alice");
+
+    let resp = set_integration!(&app, token, "devto", " good ", None);
+    assert_eq!(resp.status(), 200);
+    let integration: IntegrationDto = test::read_body_json(resp).await;
+    assert_eq!(integration.target, CrosspostTarget::Devto);
+
+    // Hashnode needs a publication, and tokens cannot be empty
+    let resp = set_integration!(&app, token, "hashnode", "key", None);
+    assert_eq!(resp.status(), 400);
+    let resp = set_integration!(&app, token, "devto", "  ", None);
+    assert_eq!(resp.status(), 400);
+    let publication = Some("pub1".to_string());
+    let resp = set_integration!(&app, token, "hashnode", "key", publication);
+    assert_eq!(resp.status(), 200);
+
+    let resp = test::TestRequest::get()
+        .uri("/api/settings/integrations")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .send_request(&app)
+        .await;
+    let body: Value = test::read_body_json(resp).await;
+    // Tokens are never sent back
+    assert!(!body.to_string().contains("good"));
+    let list: IntegrationListResponse = serde_json::from_value(body).unwrap();
+    let targets: Vec<_> = list.integrations.iter().map(|i| i.target).collect();
+    assert_eq!(
+        targets,
+        vec![CrosspostTarget::Devto, CrosspostTarget::Hashnode]
+    );
+    assert_eq!(list.integrations[1].publication_id.as_deref(), Some("pub1"));
+
+    for expected in [204, 404] {
+        let resp = test::TestRequest::delete()
+            .uri("/api/settings/integrations/devto")
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .send_request(&app)
+            .await;
+        assert_eq!(resp.status(), expected);
+    }
+}
+
+/// Test publishing to DEV with the canonical URL, then updating the copy.
+#[tokio::test]
+async fn test_crosspost_to_devto() {
+    let (apis, received) = start_stubs();
+    let (app, _) = init_app!(apis);
+    let token = register_user!(&app, "alice");
+    let id = create_post!(&app, token);
+
+    // No token configured yet
+    let resp = crosspost!(&app, token, id, "devto");
+    assert_eq!(resp.status(), 400);
+
+    set_integration!(&app, token, "devto", "good", None);
+    let resp = crosspost!(&app, token, id, "devto");
+    assert_eq!(resp.status(), 200);
+    let crosspost: CrosspostDto = test::read_body_json(resp).await;
+    assert_eq!(crosspost.status, CrosspostStatus::Published);
+    assert_eq!(
+        crosspost.remote_url.as_deref(),
+        Some("https://dev.to/alice/hello-42")
+    );
+
+    let resp = crosspost!(&app, token, id, "devto");
+    assert_eq!(resp.status(), 200);
+
+    let received = received.lock().unwrap();
+    let (method, path, body) = &received[0];
+    assert_eq!(
+        (method.as_str(), path.as_str()),
+        ("POST", "/devto/articles")
+    );
+    let article = &body["article"];
+    assert_eq!(article["title"], "Hello");
+    assert_eq!(article["body_markdown"], "First *post*");
+    assert_eq!(
+        article["canonical_url"],
+        format!("http://site.test/?post={id}")
+    );
+    assert_eq!(article["tags"], json!(["rust", "webdev"]));
+    // The second cross-post updates the existing article
+    assert_eq!(
+        (received[1].0.as_str(), received[1].1.as_str()),
+        ("PUT", "/devto/articles/42")
+    );
+}
+
+/// Test failures at the target are recorded rather than returned.
+#[tokio::test]
+async fn test_crosspost_failures() {
+    let (apis, _) = start_stubs();
+    let (app, _) = init_app!(apis);
+    let token = register_user!(&app, "alice");
+    let id = create_post!(&app, token);
+
+    set_integration!(&app, token, "devto", "revoked", None);
+    set_integration!(&app, token, "hashnode", "bad", Some("pub1".to_string()));
+    for target in ["devto", "hashnode"] {
+        let resp = crosspost!(&app, token, id, target);
+        assert_eq!(resp.status(), 200);
+        let crosspost: CrosspostDto = test::read_body_json(resp).await;
+        assert_eq!(crosspost.status, CrosspostStatus::Failed);
+        assert_eq!(crosspost.synced_at, None);
+    }
+
+    let resp = test::TestRequest::get()
+        .uri(&format!("/api/posts/{id}/crossposts"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .send_request(&app)
+        .await;
+    let list: CrosspostListResponse = test::read_body_json(resp).await;
+    assert_eq!(list.crossposts.len(), 2);
+    assert!(list.crossposts[0].error.as_ref().unwrap().contains("401"));
+    assert_eq!(
+        list.crossposts[1].error.as_deref(),
+        Some("Fetch error: Hashnode: Invalid token")
+    );
+}
+
+/// Test the sync job pushes edits made after the last cross-post.
+#[tokio::test]
+async fn test_sync_edits() {
+    let (apis, received) = start_stubs();
+    let (app, service) = init_app!(apis);
+    let token = register_user!(&app, "alice");
+    let id = create_post!(&app, token);
+
+    set_integration!(&app, token, "hashnode", "key", Some("pub1".to_string()));
+    crosspost!(&app, token, id, "hashnode");
+    assert_eq!(service.sync_edits().await.unwrap(), 0);
+
+    let req = UpdatePostRequest {
+        title: Some("Hello again".to_string()),
+        content: None,
+        tags: None,
+        canonical_url: None,
+        original_source: None,
+    };
+    test::TestRequest::put()
+        .uri(&format!("/api/posts/{id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(&req)
+        .send_request(&app)
+        .await;
+    assert_eq!(service.sync_edits().await.unwrap(), 1);
+    assert_eq!(service.sync_edits().await.unwrap(), 0);
+
+    let received = received.lock().unwrap();
+    assert_eq!(received.len(), 2);
+    let input = &received[0].2["variables"]["input"];
+    assert_eq!(input["publicationId"], "pub1");
+    assert_eq!(
+        input["originalArticleURL"],
+        format!("http://site.test/?post={id}")
+    );
+    let input = &received[1].2["variables"]["input"];
+    assert_eq!(input["id"], "h1");
+    assert_eq!(input["title"], "Hello again");
+}
+
+/// Test only the author may cross-post or see a post's cross-posts.
+#[tokio::test]
+async fn test_crosspost_author_only() {
+    let (apis, _) = start_stubs();
+    let (app, _) = init_app!(apis);
+    let alice = register_user!(&app, "alice");
+    let bob = register_user!(&app, "bob");
+    let id = create_post!(&app, alice);
+
+    set_integration!(&app, bob, "devto", "good", None);
+    let resp = crosspost!(&app, bob, id, "devto");
+    assert_eq!(resp.status(), 403);
+
+    let resp = test::TestRequest::get()
+        .uri(&format!("/api/posts/{id}/crossposts"))
+        .insert_header(("Authorization", format!("Bearer {bob}")))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 403);
+
+    let resp = crosspost!(&app, alice, id, "medium");
+    assert_eq!(resp.status(), 400);
+    let resp = test::TestRequest::post()
+        .uri(&format!("/api/posts/{id}/crosspost?target=devto"))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 401);
+}
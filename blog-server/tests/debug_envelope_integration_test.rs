@@ -0,0 +1,162 @@
+//! Integration tests for request IDs and the admin debug envelope.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::middleware::from_fn;
+use actix_web::{App, test, web};
+use blog_shared::{AuthResponse, PostListResponse, RegisterRequest};
+use serde_json::Value;
+
+use blog_server::application::{AuthService, BlogService, ModerationService};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::presentation::debug_envelope::debug_envelope;
+use blog_server::presentation::http_handlers::api_routes;
+use blog_server::presentation::request_id::request_id;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app with request ID and debug middleware.
+macro_rules! init_app {
+    ($pool:expr) => {{
+        let flag_repo = Arc::new(FeatureFlagRepository::new($pool.clone()));
+        let user_repo = Arc::new(UserRepository::new($pool.clone()));
+        let post_repo = Arc::new(PostRepository::new($pool.clone()));
+        let auth_service = AuthService::new(
+            Arc::clone(&user_repo),
+            flag_repo,
+            Arc::new(RecoveryCodeRepository::new($pool.clone())),
+            Arc::new(ServiceAccountRepository::new($pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let moderation_service =
+            ModerationService::new(Arc::new(ModerationRepository::new($pool.clone())));
+        let blog_service = BlogService::new(
+            Arc::clone(&post_repo),
+            EmbedProvider::ALL.to_vec(),
+            moderation_service.clone(),
+        );
+
+        test::init_service(
+            App::new()
+                .wrap(from_fn(debug_envelope))
+                .wrap(from_fn(request_id))
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(blog_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user and get their token.
+macro_rules! register_user {
+    ($app:expr, $username:expr, $email:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: $email.to_string(),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp.token
+    }};
+}
+
+/// Test that every response carries a generated request ID.
+#[tokio::test]
+async fn test_request_id_generated() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+
+    let resp = test::TestRequest::get()
+        .uri("/api/health")
+        .send_request(&app)
+        .await;
+
+    let id = resp
+        .headers()
+        .get("x-request-id")
+        .unwrap()
+        .to_str()
+        .unwrap();
+    assert_eq!(id.len(), 32);
+}
+
+/// Test that a client-supplied request ID is echoed back.
+#[tokio::test]
+async fn test_request_id_echoed() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+
+    let resp = test::TestRequest::get()
+        .uri("/api/health")
+        .insert_header(("X-Request-Id", "client-id-123"))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.headers().get("x-request-id").unwrap(), "client-id-123");
+}
+
+/// Test that admins get the debug envelope with metrics.
+#[tokio::test]
+async fn test_debug_envelope_for_admin() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+
+    let token = register_user!(&app, "admin", "admin@example.com");
+    sqlx::query("UPDATE users SET role = 'admin' WHERE username = 'admin'")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let resp = test::TestRequest::get()
+        .uri("/api/posts?debug=1")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .insert_header(("X-Request-Id", "debug-req"))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), 200);
+
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["data"]["total"], 0);
+    assert_eq!(body["debug"]["request_id"], "debug-req");
+    // Session lookup for the optional viewer, then the page and the count
+    assert_eq!(body["debug"]["query_count"], 3);
+    assert!(body["debug"]["db_ms"].is_number());
+    assert!(body["debug"]["serialization_ms"].is_number());
+}
+
+/// Test that non-admins get the plain response even with `?debug=1`.
+#[tokio::test]
+async fn test_debug_envelope_ignored_for_non_admin() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+
+    let token = register_user!(&app, "regular", "regular@example.com");
+
+    let resp = test::TestRequest::get()
+        .uri("/api/posts?debug=1")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), 200);
+
+    let list: PostListResponse = test::read_body_json(resp).await;
+    assert_eq!(list.total, 0);
+}
@@ -0,0 +1,231 @@
+//! Integration tests for the admin diagnostics of failed write requests.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::middleware::from_fn;
+use actix_web::{App, HttpResponse, http::StatusCode, test, web};
+use blog_shared::{DiagnosticsResponse, RegisterRequest};
+use serde_json::{Value, json};
+use sqlx::SqlitePool;
+
+use blog_server::application::{AuthService, DiagnosticsService};
+use blog_server::data::{
+    FeatureFlagRepository, RecoveryCodeRepository, ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::AppError;
+use blog_server::infrastructure::config::DiagnosticsConfig;
+use blog_server::presentation::diagnostics::record_failed_writes;
+use blog_server::presentation::http_handlers::api_routes;
+use blog_server::presentation::request_id::request_id;
+
+use common::{setup_test_db, test_jwt};
+
+/// Diagnostics on, keeping two snapshots.
+const ENABLED: DiagnosticsConfig = DiagnosticsConfig {
+    enabled: true,
+    capacity: 2,
+};
+
+/// Builds the auth service on a test database.
+fn auth_service(pool: &SqlitePool) -> AuthService {
+    AuthService::new(
+        Arc::new(UserRepository::new(pool.clone())),
+        Arc::new(FeatureFlagRepository::new(pool.clone())),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    )
+}
+
+/// Registers a user, making them an admin if asked, and returns their ID
+/// and token.
+async fn register(pool: &SqlitePool, username: &str, admin: bool) -> (i64, String) {
+    let req = RegisterRequest {
+        username: username.to_string(),
+        email: format!("{username}@example.com"),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    let auth = auth_service(pool).register(req, None).await.unwrap();
+    if admin {
+        UserRepository::new(pool.clone())
+            .update_role(auth.user.id, "admin")
+            .await
+            .unwrap();
+    }
+    (auth.user.id, auth.token)
+}
+
+/// Echoes a JSON body, rejecting an empty title.
+async fn save(body: web::Json<Value>) -> Result<HttpResponse, AppError> {
+    if body["title"] == "" {
+        return Err(AppError::Validation("Title is required".to_string()));
+    }
+    Ok(HttpResponse::Ok().json(body.into_inner()))
+}
+
+/// Fails after reading a form body.
+async fn fail(_form: web::Form<Vec<(String, String)>>) -> Result<HttpResponse, AppError> {
+    Err(AppError::Internal("database is on fire".to_string()))
+}
+
+/// Macro to build the test app with the diagnostics middleware.
+macro_rules! init_app {
+    ($pool:expr, $service:expr) => {
+        test::init_service(
+            App::new()
+                .wrap(from_fn(record_failed_writes))
+                .wrap(from_fn(request_id))
+                .app_data(web::Data::new(auth_service(&$pool)))
+                .app_data(web::Data::new($service.clone()))
+                .route("/save", web::post().to(save))
+                .route("/fail", web::post().to(fail))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    };
+}
+
+/// Test failed writes being kept redacted and listed to admins.
+#[actix_web::test]
+async fn test_failed_writes_are_recorded_redacted() {
+    let pool = setup_test_db().await;
+    let (_, admin_token) = register(&pool, "admin", true).await;
+    let (user_id, user_token) = register(&pool, "writer", false).await;
+    let service = DiagnosticsService::new(ENABLED);
+    let app = init_app!(pool, service);
+
+    // Successful writes and reads are not kept, and handlers still get the
+    // buffered body
+    let resp = test::TestRequest::post()
+        .uri("/save")
+        .set_json(json!({"title": "Hello"}))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["title"], "Hello");
+
+    let resp = test::TestRequest::post()
+        .uri("/save")
+        .insert_header(("Authorization", format!("Bearer {user_token}")))
+        .insert_header(("X-Request-Id", "req-42"))
+        .set_json(json!({
+            "title": "",
+            "content": "My draft",
+            "meta": {"api_key": "abc", "lang": "en"},
+            "current_password": "hunter2",
+        }))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = test::TestRequest::post()
+        .uri("/fail")
+        .set_form([("name", "draft"), ("token", "s3cret")])
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+    let resp = test::TestRequest::get()
+        .uri("/api/admin/diagnostics")
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let diagnostics: DiagnosticsResponse = test::read_body_json(resp).await;
+    assert!(diagnostics.enabled);
+    assert_eq!(diagnostics.snapshots.len(), 2);
+
+    // Newest first
+    let form = &diagnostics.snapshots[0];
+    assert_eq!(form.path, "/fail");
+    assert_eq!(form.status, 500);
+    assert_eq!(form.user_id, None);
+    assert_eq!(
+        form.request_body.as_deref(),
+        Some("name=draft&token=%5Bredacted%5D")
+    );
+
+    let json = &diagnostics.snapshots[1];
+    assert_eq!(json.method, "POST");
+    assert_eq!(json.path, "/save");
+    assert_eq!(json.status, 400);
+    assert_eq!(json.request_id.as_deref(), Some("req-42"));
+    assert_eq!(json.user_id, Some(user_id));
+    let request: Value = serde_json::from_str(json.request_body.as_deref().unwrap()).unwrap();
+    assert_eq!(request["content"], "My draft");
+    assert_eq!(request["current_password"], "[redacted]");
+    assert_eq!(request["meta"]["api_key"], "[redacted]");
+    assert_eq!(request["meta"]["lang"], "en");
+    assert!(
+        json.response_body
+            .as_deref()
+            .unwrap()
+            .contains("Title is required")
+    );
+
+    // The oldest snapshot is dropped once the buffer is full
+    test::TestRequest::post()
+        .uri("/fail")
+        .set_form([("name", "again")])
+        .send_request(&app)
+        .await;
+    let snapshots = service.list().snapshots;
+    assert_eq!(snapshots.len(), 2);
+    assert_eq!(snapshots[1].path, "/fail");
+}
+
+/// Test the diagnostics being admin-only and clearable.
+#[actix_web::test]
+async fn test_diagnostics_admin_only_and_cleared() {
+    let pool = setup_test_db().await;
+    let (_, admin_token) = register(&pool, "admin", true).await;
+    let (_, user_token) = register(&pool, "writer", false).await;
+    let service = DiagnosticsService::new(ENABLED);
+    let app = init_app!(pool, service);
+
+    test::TestRequest::post()
+        .uri("/save")
+        .set_json(json!({"title": ""}))
+        .send_request(&app)
+        .await;
+    assert_eq!(service.list().snapshots.len(), 1);
+
+    let resp = test::TestRequest::get()
+        .uri("/api/admin/diagnostics")
+        .insert_header(("Authorization", format!("Bearer {user_token}")))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = test::TestRequest::delete()
+        .uri("/api/admin/diagnostics")
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    assert!(service.list().snapshots.is_empty());
+}
+
+/// Test nothing being recorded while the diagnostics are off.
+#[actix_web::test]
+async fn test_nothing_recorded_when_disabled() {
+    let pool = setup_test_db().await;
+    let service = DiagnosticsService::new(DiagnosticsConfig::default());
+    let app = init_app!(pool, service);
+
+    let resp = test::TestRequest::post()
+        .uri("/save")
+        .set_json(json!({"title": ""}))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let diagnostics = service.list();
+    assert!(!diagnostics.enabled);
+    assert!(diagnostics.snapshots.is_empty());
+}
@@ -0,0 +1,160 @@
+//! Integration tests for co-editing presence.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{CreatePostRequest, EditingPresenceDto, RegisterRequest};
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+
+use blog_server::application::{AuthService, BlogService, ModerationService, PresenceService};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::{DomainEvent, EmbedProvider};
+use blog_server::infrastructure::clock::MockClock;
+use blog_server::infrastructure::event_bus::EventBus;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Builds the auth and blog services on a test database.
+fn services(pool: &SqlitePool) -> (AuthService, BlogService) {
+    let auth_service = AuthService::new(
+        Arc::new(UserRepository::new(pool.clone())),
+        Arc::new(FeatureFlagRepository::new(pool.clone())),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let blog_service = BlogService::new(
+        Arc::new(PostRepository::new(pool.clone())),
+        EmbedProvider::ALL.to_vec(),
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone()))),
+    );
+    (auth_service, blog_service)
+}
+
+/// Registers a user, returning their ID and token.
+async fn register(auth_service: &AuthService, username: &str) -> (i64, String) {
+    let req = RegisterRequest {
+        username: username.to_string(),
+        email: format!("{username}@example.com"),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    let auth = auth_service.register(req, None).await.unwrap();
+    (auth.user.id, auth.token)
+}
+
+/// Creates a post by `author_id`, returning its numeric and public IDs.
+async fn create_post(blog_service: &BlogService, author_id: i64) -> (i64, String) {
+    let post = blog_service
+        .create_post(
+            author_id,
+            CreatePostRequest {
+                title: "Hello".to_string(),
+                content: "World".to_string(),
+                tags: Vec::new(),
+                lang: None,
+                canonical_url: None,
+                original_source: None,
+                org: None,
+            },
+        )
+        .await
+        .unwrap();
+    (post.id, post.public_id)
+}
+
+/// Test heartbeats listing the editor and being limited to the author.
+#[tokio::test]
+async fn test_heartbeat_is_author_only() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service) = services(&pool);
+    let (author_id, author_token) = register(&auth_service, "alice").await;
+    let (_, other_token) = register(&auth_service, "mallory").await;
+    let (post_id, post_public_id) = create_post(&blog_service, author_id).await;
+    let presence_service =
+        PresenceService::new(Arc::new(PostRepository::new(pool.clone())), EventBus::new());
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(blog_service))
+            .app_data(web::Data::new(presence_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    let uri = format!("/api/posts/{post_id}/editing-heartbeat");
+    let req = test::TestRequest::post()
+        .uri(&uri)
+        .insert_header(("Authorization", format!("Bearer {author_token}")))
+        .to_request();
+    let presence: EditingPresenceDto = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(presence.post_public_id, post_public_id);
+    assert_eq!(presence.editors.len(), 1);
+    assert_eq!(presence.editors[0].username, "alice");
+    assert_eq!(presence.editors[0].user_id, 0);
+    assert!(!presence.editors[0].user_public_id.is_empty());
+
+    let req = test::TestRequest::post()
+        .uri(&uri)
+        .insert_header(("Authorization", format!("Bearer {other_token}")))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 403);
+
+    let req = test::TestRequest::delete()
+        .uri(&uri)
+        .insert_header(("Authorization", format!("Bearer {author_token}")))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 204);
+}
+
+/// Test editors joining, leaving, and expiring being announced on the
+/// event bus.
+#[tokio::test]
+async fn test_presence_changes_are_broadcast() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service) = services(&pool);
+    let (author_id, _) = register(&auth_service, "bob").await;
+    let (post_id, _) = create_post(&blog_service, author_id).await;
+    let clock = MockClock::new(Utc::now());
+    let service =
+        PresenceService::new(Arc::new(PostRepository::new(pool.clone())), EventBus::new())
+            .with_clock(Arc::new(clock.clone()));
+    let mut events = service.subscribe();
+
+    service.heartbeat(post_id, author_id).await.unwrap();
+    let Ok(DomainEvent::EditingChanged {
+        post_id: changed,
+        presence,
+    }) = events.try_recv()
+    else {
+        panic!("expected a presence change");
+    };
+    assert_eq!(changed, post_id);
+    assert_eq!(presence.editors[0].user_id, author_id);
+
+    clock.advance(Duration::seconds(10));
+    service.heartbeat(post_id, author_id).await.unwrap();
+    assert!(events.try_recv().is_err());
+
+    clock.advance(Duration::seconds(31));
+    let presence = service.presence(post_id, author_id).await.unwrap();
+    assert!(presence.editors.is_empty());
+    let Ok(DomainEvent::EditingChanged { presence, .. }) = events.try_recv() else {
+        panic!("expected the editor to expire");
+    };
+    assert!(presence.editors.is_empty());
+
+    service.heartbeat(post_id, author_id).await.unwrap();
+    service.stop_editing(post_id, author_id).await.unwrap();
+    let changes: Vec<_> = std::iter::from_fn(|| events.try_recv().ok()).collect();
+    assert_eq!(changes.len(), 2);
+}
@@ -0,0 +1,280 @@
+//! Integration tests for the editorial review workflow.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    CreatePostRequest, PostDto, PostHistoryResponse, PostListResponse, PostStatus, RegisterRequest,
+    UpdatePostRequest,
+};
+use chrono::Utc;
+use serde_json::json;
+use sqlx::SqlitePool;
+
+use blog_server::application::{AuthService, BlogService, ModerationService};
+use blog_server::constants::FEATURE_EDITORIAL_REVIEW;
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Builds the auth and blog services on a test database, with editorial
+/// review turned on.
+async fn services(pool: &SqlitePool) -> (AuthService, BlogService) {
+    let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+    flag_repo
+        .set_enabled(FEATURE_EDITORIAL_REVIEW, true, Utc::now())
+        .await
+        .unwrap();
+    let auth_service = AuthService::new(
+        Arc::new(UserRepository::new(pool.clone())),
+        Arc::clone(&flag_repo),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let blog_service = BlogService::new(
+        Arc::new(PostRepository::new(pool.clone())),
+        EmbedProvider::ALL.to_vec(),
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone()))),
+    )
+    .with_flags(flag_repo);
+    (auth_service, blog_service)
+}
+
+/// Registers a user with `role`, returning their ID and token.
+async fn register(
+    pool: &SqlitePool,
+    auth_service: &AuthService,
+    username: &str,
+    role: &str,
+) -> (i64, String) {
+    let req = RegisterRequest {
+        username: username.to_string(),
+        email: format!("{username}@example.com"),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    let auth = auth_service.register(req, None).await.unwrap();
+    UserRepository::new(pool.clone())
+        .update_role(auth.user.id, role)
+        .await
+        .unwrap();
+    (auth.user.id, auth.token)
+}
+
+/// Creates a post by `author_id`.
+async fn create_post(blog_service: &BlogService, author_id: i64) -> PostDto {
+    blog_service
+        .create_post(
+            author_id,
+            CreatePostRequest {
+                title: "Hello".to_string(),
+                content: "First line\nSecond line".to_string(),
+                tags: Vec::new(),
+                lang: None,
+                canonical_url: None,
+                original_source: None,
+                org: None,
+            },
+        )
+        .await
+        .unwrap()
+}
+
+/// Test a post going from draft through review to published, hidden from
+/// readers until then, with every step in its history.
+#[tokio::test]
+async fn test_review_cycle() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service) = services(&pool).await;
+    let (author_id, author_token) = register(&pool, &auth_service, "alice", "user").await;
+    let (_, editor_token) = register(&pool, &auth_service, "erin", "editor").await;
+    let post = create_post(&blog_service, author_id).await;
+    assert_eq!(post.status, PostStatus::Draft);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(blog_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/posts/{}", post.public_id))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 404);
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/api/posts/{}/publish", post.public_id))
+        .insert_header(("Authorization", format!("Bearer {author_token}")))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 400);
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/api/posts/{}/submit", post.public_id))
+        .insert_header(("Authorization", format!("Bearer {author_token}")))
+        .to_request();
+    let submitted: PostDto = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(submitted.status, PostStatus::InReview);
+
+    let req = test::TestRequest::get()
+        .uri("/api/admin/review-queue")
+        .insert_header(("Authorization", format!("Bearer {editor_token}")))
+        .to_request();
+    let queue: PostListResponse = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(queue.total, 1);
+    assert_eq!(queue.posts[0].public_id, post.public_id);
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/api/posts/{}/review", post.public_id))
+        .insert_header(("Authorization", format!("Bearer {editor_token}")))
+        .set_json(json!({
+            "decision": "request_changes",
+            "comment": "Almost there",
+            "notes": [{"line": 2, "body": "Expand this"}]
+        }))
+        .to_request();
+    let reviewed: PostDto = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(reviewed.status, PostStatus::ChangesRequested);
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/api/posts/{}/submit", post.public_id))
+        .insert_header(("Authorization", format!("Bearer {author_token}")))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/api/posts/{}/review", post.public_id))
+        .insert_header(("Authorization", format!("Bearer {author_token}")))
+        .set_json(json!({"decision": "approve"}))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 403);
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/api/posts/{}/review", post.public_id))
+        .insert_header(("Authorization", format!("Bearer {editor_token}")))
+        .set_json(json!({"decision": "approve"}))
+        .to_request();
+    let approved: PostDto = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(approved.status, PostStatus::Approved);
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/api/posts/{}/publish", post.public_id))
+        .insert_header(("Authorization", format!("Bearer {author_token}")))
+        .to_request();
+    let published: PostDto = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(published.status, PostStatus::Published);
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/posts/{}", post.public_id))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/posts/{}/history", post.public_id))
+        .insert_header(("Authorization", format!("Bearer {author_token}")))
+        .to_request();
+    let history: PostHistoryResponse = test::call_and_read_body_json(&app, req).await;
+    let steps: Vec<_> = history
+        .transitions
+        .iter()
+        .map(|transition| transition.to_status)
+        .collect();
+    assert_eq!(
+        steps,
+        [
+            PostStatus::InReview,
+            PostStatus::ChangesRequested,
+            PostStatus::InReview,
+            PostStatus::Approved,
+            PostStatus::Published,
+        ]
+    );
+    assert_eq!(history.transitions[1].actor_username, "erin");
+    assert_eq!(
+        history.transitions[1].comment.as_deref(),
+        Some("Almost there")
+    );
+    assert_eq!(history.transitions[1].notes[0].line, 2);
+}
+
+/// Test editing an approved post sending it back to draft.
+#[tokio::test]
+async fn test_editing_approved_post_needs_new_review() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service) = services(&pool).await;
+    let (author_id, _) = register(&pool, &auth_service, "bob", "user").await;
+    let (editor_id, _) = register(&pool, &auth_service, "eve", "editor").await;
+    let post = create_post(&blog_service, author_id).await;
+
+    blog_service.submit_post(post.id, author_id).await.unwrap();
+    let review = serde_json::from_value(json!({"decision": "approve"})).unwrap();
+    let role = auth_service.role(editor_id).await.unwrap();
+    blog_service
+        .review_post(post.id, editor_id, role, review)
+        .await
+        .unwrap();
+
+    let updated = blog_service
+        .update_post(
+            post.id,
+            author_id,
+            UpdatePostRequest {
+                title: Some("Hello again".to_string()),
+                content: None,
+                tags: None,
+                canonical_url: None,
+                original_source: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.status, PostStatus::Draft);
+}
+
+/// Test review decisions being limited to editors and validated.
+#[tokio::test]
+async fn test_review_requires_editor_and_valid_notes() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service) = services(&pool).await;
+    let (author_id, _) = register(&pool, &auth_service, "carol", "user").await;
+    let (_, user_token) = register(&pool, &auth_service, "dave", "user").await;
+    let (_, editor_token) = register(&pool, &auth_service, "frank", "editor").await;
+    let post = create_post(&blog_service, author_id).await;
+    blog_service.submit_post(post.id, author_id).await.unwrap();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(blog_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/admin/review-queue")
+        .insert_header(("Authorization", format!("Bearer {user_token}")))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 403);
+
+    for body in [
+        json!({"decision": "request_changes"}),
+        json!({"decision": "request_changes", "notes": [{"line": 9, "body": "No such line"}]}),
+    ] {
+        let req = test::TestRequest::post()
+            .uri(&format!("/api/posts/{}/review", post.public_id))
+            .insert_header(("Authorization", format!("Bearer {editor_token}")))
+            .set_json(body)
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 400);
+    }
+}
@@ -0,0 +1,298 @@
+//! Integration tests for confirming account email changes.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    ChangeEmailRequest, ConfirmEmailChangeRequest, RegisterRequest, SettingsDto, UserDto,
+};
+use chrono::{Duration, Utc};
+use serde_json::Value;
+use sqlx::SqlitePool;
+
+use blog_server::application::{AuthService, SettingsService};
+use blog_server::data::{
+    AdminRepository, EmailChangeRepository, FeatureFlagRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::hash_email_change_token;
+use blog_server::infrastructure::clock::MockClock;
+use blog_server::infrastructure::config::MailConfig;
+use blog_server::infrastructure::mailer::Mailer;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Builds the auth and settings services with email changes on a mock clock.
+///
+/// No SMTP server is configured, so emails are only logged.
+fn services(pool: &SqlitePool, clock: &MockClock) -> (AuthService, SettingsService) {
+    let user_repo = Arc::new(UserRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        Arc::new(FeatureFlagRepository::new(pool.clone())),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    )
+    .with_clock(Arc::new(clock.clone()))
+    .with_email_changes(
+        Arc::new(EmailChangeRepository::new(pool.clone())),
+        Arc::new(AdminRepository::new(pool.clone())),
+        Mailer::new(&MailConfig::default()).unwrap(),
+        "http://site.test".to_string(),
+    );
+    let settings_service = SettingsService::new(user_repo);
+    (auth_service, settings_service)
+}
+
+/// Registers a user, returning their ID and token.
+async fn register(auth_service: &AuthService, username: &str) -> (i64, String) {
+    let req = RegisterRequest {
+        username: username.to_string(),
+        email: format!("{username}@example.com"),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    let auth = auth_service.register(req, None).await.unwrap();
+    (auth.user.id, auth.token)
+}
+
+/// Returns a confirmation token for a user's pending change.
+///
+/// Only the hash of the emailed token is stored, so the stored hash is
+/// swapped for that of a token known to the test.
+async fn pending_token(pool: &SqlitePool, user_id: i64) -> String {
+    let token = format!("test-token-{}", Utc::now().timestamp_nanos_opt().unwrap());
+    let result = sqlx::query("UPDATE email_changes SET token_hash = ? WHERE user_id = ?")
+        .bind(hash_email_change_token(&token))
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    assert_eq!(result.rows_affected(), 1);
+    token
+}
+
+/// Macro to request an email change and assert it was accepted.
+macro_rules! request_change {
+    ($app:expr, $token:expr, $new_email:expr) => {{
+        let resp = test::TestRequest::put()
+            .uri("/api/settings/email")
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json(ChangeEmailRequest {
+                new_email: $new_email.to_string(),
+                current_password: "secret123".to_string(),
+            })
+            .send_request($app)
+            .await;
+        assert_eq!(resp.status(), 202);
+    }};
+}
+
+/// Macro to confirm a change with a token and return the response.
+macro_rules! confirm {
+    ($app:expr, $token:expr) => {{
+        test::TestRequest::post()
+            .uri("/api/settings/email/confirm")
+            .set_json(ConfirmEmailChangeRequest {
+                token: $token.to_string(),
+            })
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Test the email switching only once the new address confirms with a POST,
+/// with both steps in the audit log.
+#[tokio::test]
+async fn test_email_change_confirmed() {
+    let pool = setup_test_db().await;
+    let clock = MockClock::new(Utc::now());
+    let (auth_service, settings_service) = services(&pool, &clock);
+    let (alice_id, alice_token) = register(&auth_service, "alice").await;
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(settings_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    request_change!(&app, alice_token, "first@example.com");
+    // A second request replaces the first, whose link stops working
+    let first = pending_token(&pool, alice_id).await;
+    request_change!(&app, alice_token, "alice@new.example.com");
+    let token = pending_token(&pool, alice_id).await;
+    assert_eq!(confirm!(&app, first).status(), 400);
+
+    // Following the link with a GET changes nothing
+    let resp = test::TestRequest::get()
+        .uri(&format!("/api/settings/email/confirm?token={token}"))
+        .send_request(&app)
+        .await;
+    assert!(!resp.status().is_success());
+
+    let resp = confirm!(&app, token);
+    assert_eq!(resp.status(), 200);
+    let user: UserDto = test::read_body_json(resp).await;
+    assert_eq!(user.email, "alice@new.example.com");
+
+    let resp = test::TestRequest::get()
+        .uri("/api/settings")
+        .insert_header(("Authorization", format!("Bearer {alice_token}")))
+        .send_request(&app)
+        .await;
+    let settings: SettingsDto = test::read_body_json(resp).await;
+    assert_eq!(settings.email, "alice@new.example.com");
+
+    // Links work once
+    let resp = confirm!(&app, token);
+    assert_eq!(resp.status(), 400);
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], "invalid_email_change");
+
+    let log = AdminRepository::new(pool.clone())
+        .list_audit_log(10, 0)
+        .await
+        .unwrap();
+    let actions: Vec<&str> = log.iter().map(|e| e.action.as_str()).collect();
+    assert_eq!(
+        actions,
+        [
+            "email_changed",
+            "email_change_requested",
+            "email_change_requested"
+        ]
+    );
+    assert!(log.iter().all(|e| e.target_user_id == Some(alice_id)));
+    assert_eq!(
+        log[0].reason.as_deref(),
+        Some("From alice@example.com to alice@new.example.com")
+    );
+}
+
+/// Test confirmation links expiring after a day.
+#[tokio::test]
+async fn test_email_change_expires() {
+    let pool = setup_test_db().await;
+    let clock = MockClock::new(Utc::now());
+    let (auth_service, settings_service) = services(&pool, &clock);
+    let (alice_id, alice_token) = register(&auth_service, "alice").await;
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(settings_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    request_change!(&app, alice_token, "late@example.com");
+    let token = pending_token(&pool, alice_id).await;
+    clock.advance(Duration::hours(25));
+
+    assert_eq!(confirm!(&app, token).status(), 400);
+    let email: String = sqlx::query_scalar("SELECT email FROM users WHERE id = ?")
+        .bind(alice_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(email, "alice@example.com");
+}
+
+/// Test a confirmation failing once another account has taken the address.
+#[tokio::test]
+async fn test_email_change_address_taken_meanwhile() {
+    let pool = setup_test_db().await;
+    let clock = MockClock::new(Utc::now());
+    let (auth_service, settings_service) = services(&pool, &clock);
+    let (alice_id, alice_token) = register(&auth_service, "alice").await;
+    let (bob_id, bob_token) = register(&auth_service, "bob").await;
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(settings_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    request_change!(&app, alice_token, "shared@example.com");
+    let alice_link = pending_token(&pool, alice_id).await;
+    request_change!(&app, bob_token, "shared@example.com");
+    let bob_link = pending_token(&pool, bob_id).await;
+
+    assert_eq!(confirm!(&app, bob_link).status(), 200);
+    assert_eq!(confirm!(&app, alice_link).status(), 400);
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM email_changes")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+}
+
+/// Test changing only the case of one's own address going through, while an
+/// identical address is rejected.
+#[tokio::test]
+async fn test_email_change_case_only() {
+    let pool = setup_test_db().await;
+    let clock = MockClock::new(Utc::now());
+    let (auth_service, settings_service) = services(&pool, &clock);
+    let (alice_id, alice_token) = register(&auth_service, "alice").await;
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(settings_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    let resp = test::TestRequest::put()
+        .uri("/api/settings/email")
+        .insert_header(("Authorization", format!("Bearer {alice_token}")))
+        .set_json(ChangeEmailRequest {
+            new_email: "alice@example.com".to_string(),
+            current_password: "secret123".to_string(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 400);
+
+    request_change!(&app, alice_token, "Alice@Example.com");
+    let token = pending_token(&pool, alice_id).await;
+    let resp = confirm!(&app, token);
+    assert_eq!(resp.status(), 200);
+    let user: UserDto = test::read_body_json(resp).await;
+    assert_eq!(user.email, "Alice@Example.com");
+}
+
+/// Test confirmation tokens being stored only as hashes.
+#[tokio::test]
+async fn test_email_change_token_stored_hashed() {
+    let pool = setup_test_db().await;
+    let repo = EmailChangeRepository::new(pool.clone());
+    let clock = MockClock::new(Utc::now());
+    let (auth_service, _) = services(&pool, &clock);
+    let (alice_id, _) = register(&auth_service, "alice").await;
+    auth_service
+        .change_email(
+            alice_id,
+            ChangeEmailRequest {
+                new_email: "alice@new.example.com".to_string(),
+                current_password: "secret123".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+    let token = pending_token(&pool, alice_id).await;
+    assert!(repo.find_by_token_hash(&token).await.unwrap().is_none());
+    let change = repo
+        .find_by_token_hash(&hash_email_change_token(&token))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(change.user_id, alice_id);
+}
@@ -0,0 +1,350 @@
+//! Integration tests for A/B experiments.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    AuthResponse, CreateExperimentRequest, ErrorResponse, ExperimentDto, ExperimentListResponse,
+    ExperimentVariantDto, FeaturesResponse, RegisterRequest, UpdateExperimentRequest,
+};
+
+use blog_server::application::{AuthService, ExperimentService};
+use blog_server::data::{
+    ExperimentRepository, FeatureFlagRepository, RecoveryCodeRepository, ServiceAccountRepository,
+    UserRepository,
+};
+use blog_server::domain::{ExperimentVariant, assign_variant};
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app, returning it with the user repository.
+macro_rules! init_app {
+    () => {{
+        let pool = setup_test_db().await;
+        let user_repo = Arc::new(UserRepository::new(pool.clone()));
+        let experiment_service =
+            ExperimentService::new(Arc::new(ExperimentRepository::new(pool.clone())));
+        let auth_service = AuthService::new(
+            Arc::clone(&user_repo),
+            Arc::new(FeatureFlagRepository::new(pool.clone())),
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        )
+        .with_experiments(experiment_service.clone());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(experiment_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await;
+        (app, user_repo)
+    }};
+}
+
+/// Macro to register a user, returning their ID and token.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        let auth: AuthResponse = test::read_body_json(resp).await;
+        (auth.user.id, auth.token)
+    }};
+}
+
+/// Macro to register an admin, returning their token.
+macro_rules! register_admin {
+    ($app:expr, $user_repo:expr) => {{
+        let (id, token) = register_user!($app, "admin");
+        $user_repo.update_role(id, "admin").await.unwrap();
+        token
+    }};
+}
+
+/// Macro to create an experiment as an admin.
+macro_rules! create_experiment {
+    ($app:expr, $token:expr, $name:expr, [$(($variant:expr, $weight:expr)),*]) => {{
+        let req = CreateExperimentRequest {
+            name: $name.to_string(),
+            description: String::new(),
+            variants: vec![$(ExperimentVariantDto {
+                name: $variant.to_string(),
+                weight: $weight,
+            }),*],
+        };
+        test::TestRequest::post()
+            .uri("/api/admin/experiments")
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json(&req)
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Macro to get the features, as a user when given a token.
+macro_rules! get_features {
+    ($app:expr) => {{
+        let resp = test::TestRequest::get()
+            .uri("/api/features")
+            .send_request($app)
+            .await;
+        let features: FeaturesResponse = test::read_body_json(resp).await;
+        features
+    }};
+    ($app:expr, $token:expr) => {{
+        let resp = test::TestRequest::get()
+            .uri("/api/features")
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .send_request($app)
+            .await;
+        let features: FeaturesResponse = test::read_body_json(resp).await;
+        features
+    }};
+}
+
+/// Macro to record an exposure, returning the response status.
+macro_rules! expose {
+    ($app:expr, $token:expr, $name:expr) => {{
+        test::TestRequest::post()
+            .uri(&format!("/api/experiments/{}/exposures", $name))
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .send_request($app)
+            .await
+            .status()
+    }};
+}
+
+/// Macro to get an experiment from the admin list.
+macro_rules! find_experiment {
+    ($app:expr, $token:expr, $name:expr) => {{
+        let resp = test::TestRequest::get()
+            .uri("/api/admin/experiments")
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .send_request($app)
+            .await;
+        let list: ExperimentListResponse = test::read_body_json(resp).await;
+        list.experiments
+            .into_iter()
+            .find(|experiment| experiment.name == $name)
+    }};
+}
+
+#[tokio::test]
+async fn test_admin_manages_experiments() {
+    let (app, user_repo) = init_app!();
+    let admin_token = register_admin!(&app, user_repo);
+    let (_, user_token) = register_user!(&app, "reader");
+
+    let resp = create_experiment!(
+        &app,
+        admin_token,
+        " Hero-CTA ",
+        [("control", 50), ("bold", 50)]
+    );
+    assert_eq!(resp.status(), 201);
+    let experiment: ExperimentDto = test::read_body_json(resp).await;
+    assert_eq!(experiment.name, "hero-cta");
+    assert!(experiment.enabled);
+    assert!(experiment.exposures.is_empty());
+
+    let resp = create_experiment!(&app, admin_token, "hero-cta", [("a", 50), ("b", 50)]);
+    assert_eq!(resp.status(), 400);
+    let error: ErrorResponse = test::read_body_json(resp).await;
+    assert_eq!(error.code.as_deref(), Some("experiment_exists"));
+
+    // Weights must add up to 100, names must be distinct, one variant is no split
+    for variants in [
+        vec![("a", 50), ("b", 40)],
+        vec![("a", 50), ("A", 50)],
+        vec![("a", 100)],
+    ] {
+        let req = CreateExperimentRequest {
+            name: "other".to_string(),
+            description: String::new(),
+            variants: variants
+                .into_iter()
+                .map(|(name, weight)| ExperimentVariantDto {
+                    name: name.to_string(),
+                    weight,
+                })
+                .collect(),
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/admin/experiments")
+            .insert_header(("Authorization", format!("Bearer {admin_token}")))
+            .set_json(&req)
+            .send_request(&app)
+            .await;
+        assert_eq!(resp.status(), 400);
+    }
+
+    let resp = create_experiment!(&app, user_token, "mine", [("a", 50), ("b", 50)]);
+    assert_eq!(resp.status(), 403);
+
+    let req = UpdateExperimentRequest {
+        description: Some("Bolder call to action".to_string()),
+        enabled: Some(false),
+        variants: None,
+    };
+    let resp = test::TestRequest::put()
+        .uri("/api/admin/experiments/HERO-CTA")
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .set_json(&req)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 200);
+    let updated: ExperimentDto = test::read_body_json(resp).await;
+    assert_eq!(updated.description, "Bolder call to action");
+    assert!(!updated.enabled);
+    assert_eq!(updated.variants, experiment.variants);
+
+    let resp = test::TestRequest::delete()
+        .uri("/api/admin/experiments/hero-cta")
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 204);
+    assert!(find_experiment!(&app, admin_token, "hero-cta").is_none());
+
+    let resp = test::TestRequest::delete()
+        .uri("/api/admin/experiments/hero-cta")
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 404);
+}
+
+#[tokio::test]
+async fn test_features_report_stable_assignments() {
+    let (app, user_repo) = init_app!();
+    let admin_token = register_admin!(&app, user_repo);
+    create_experiment!(&app, admin_token, "layout", [("control", 50), ("grid", 50)]);
+    create_experiment!(&app, admin_token, "paused", [("control", 0), ("new", 100)]);
+    let req = UpdateExperimentRequest {
+        enabled: Some(false),
+        ..Default::default()
+    };
+    test::TestRequest::put()
+        .uri("/api/admin/experiments/paused")
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .set_json(&req)
+        .send_request(&app)
+        .await;
+
+    assert!(
+        get_features!(&app).experiments.is_empty(),
+        "guests take no part"
+    );
+
+    let variants = vec![
+        ExperimentVariant {
+            name: "control".to_string(),
+            weight: 50,
+        },
+        ExperimentVariant {
+            name: "grid".to_string(),
+            weight: 50,
+        },
+    ];
+    let mut seen = Vec::new();
+    for i in 0..20 {
+        let (id, token) = register_user!(&app, format!("user{i}"));
+        let features = get_features!(&app, token);
+        let variant = features.experiments.get("layout").unwrap().clone();
+        assert!(!features.experiments.contains_key("paused"));
+        assert_eq!(get_features!(&app, token).experiments["layout"], variant);
+        assert_eq!(
+            assign_variant("layout", &variants, id).unwrap().name,
+            variant
+        );
+        seen.push(variant);
+    }
+    assert!(seen.iter().any(|variant| variant == "control"));
+    assert!(seen.iter().any(|variant| variant == "grid"));
+}
+
+#[tokio::test]
+async fn test_exposures_are_counted_once_per_user() {
+    let (app, user_repo) = init_app!();
+    let admin_token = register_admin!(&app, user_repo);
+    let (_, alice_token) = register_user!(&app, "alice");
+    let (_, bob_token) = register_user!(&app, "bob");
+    create_experiment!(
+        &app,
+        admin_token,
+        "signup",
+        [("control", 0), ("short", 100)]
+    );
+
+    assert_eq!(expose!(&app, alice_token, "signup"), 204);
+    assert_eq!(expose!(&app, alice_token, "signup"), 204);
+    assert_eq!(expose!(&app, bob_token, "signup"), 204);
+    assert_eq!(expose!(&app, alice_token, "unknown"), 404);
+    let resp = test::TestRequest::post()
+        .uri("/api/experiments/signup/exposures")
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 401);
+
+    let experiment = find_experiment!(&app, admin_token, "signup").unwrap();
+    assert_eq!(experiment.exposures.get("short"), Some(&2));
+    assert_eq!(experiment.exposures.get("control"), None);
+
+    // A new split starts counting afresh
+    let req = UpdateExperimentRequest {
+        variants: Some(vec![
+            ExperimentVariantDto {
+                name: "control".to_string(),
+                weight: 100,
+            },
+            ExperimentVariantDto {
+                name: "short".to_string(),
+                weight: 0,
+            },
+        ]),
+        ..Default::default()
+    };
+    test::TestRequest::put()
+        .uri("/api/admin/experiments/signup")
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .set_json(&req)
+        .send_request(&app)
+        .await;
+    assert!(
+        find_experiment!(&app, admin_token, "signup")
+            .unwrap()
+            .exposures
+            .is_empty()
+    );
+    assert_eq!(expose!(&app, alice_token, "signup"), 204);
+    let experiment = find_experiment!(&app, admin_token, "signup").unwrap();
+    assert_eq!(experiment.exposures.get("control"), Some(&1));
+
+    // Disabled experiments are hidden from users
+    let req = UpdateExperimentRequest {
+        enabled: Some(false),
+        ..Default::default()
+    };
+    test::TestRequest::put()
+        .uri("/api/admin/experiments/signup")
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .set_json(&req)
+        .send_request(&app)
+        .await;
+    assert_eq!(expose!(&app, bob_token, "signup"), 404);
+}
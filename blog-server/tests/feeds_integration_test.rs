@@ -0,0 +1,282 @@
+//! Integration tests for the RSS/Atom feeds and WebSub hub pings.
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use actix_web::{App, HttpResponse, HttpServer, test, web};
+use blog_shared::{
+    AuthResponse, CreatePostRequest, PostDto, PreferencesDto, RegisterRequest, UpdatePostRequest,
+};
+
+use blog_server::application::{
+    AuthService, BlogService, FeedService, ModerationService, SettingsService,
+};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::{EmbedProvider, FeedUrls};
+use blog_server::infrastructure::http_fetcher::HttpFetcher;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app announcing the given hub; also returns the
+/// feed service.
+macro_rules! init_app {
+    ($hub:expr) => {{
+        let pool = setup_test_db().await;
+        let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+        let user_repo = Arc::new(UserRepository::new(pool.clone()));
+        let post_repo = Arc::new(PostRepository::new(pool.clone()));
+        let auth_service = AuthService::new(
+            Arc::clone(&user_repo),
+            flag_repo,
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let moderation_service =
+            ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+        let blog_service = BlogService::new(
+            Arc::clone(&post_repo),
+            EmbedProvider::ALL.to_vec(),
+            moderation_service,
+        );
+        let settings_service = SettingsService::new(Arc::clone(&user_repo));
+        let feed_service = FeedService::new(
+            blog_service.clone(),
+            post_repo,
+            user_repo,
+            HttpFetcher::new().unwrap(),
+            FeedUrls::new("http://api.test", "http://site.test", $hub),
+        );
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(blog_service))
+                .app_data(web::Data::new(settings_service))
+                .app_data(web::Data::new(feed_service.clone()))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await;
+        (app, feed_service)
+    }};
+}
+
+/// Macro to register a user and get their token.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp.token
+    }};
+}
+
+/// Macro to create a post with tags.
+macro_rules! create_post {
+    ($app:expr, $token:expr, $title:expr, $tags:expr) => {{
+        let req = CreatePostRequest {
+            title: $title.to_string(),
+            content: "Some **bold** text".to_string(),
+            tags: $tags,
+            lang: None,
+            canonical_url: None,
+            original_source: None,
+            org: None,
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/posts")
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        assert_eq!(resp.status(), 201);
+        let post: PostDto = test::read_body_json(resp).await;
+        post
+    }};
+}
+
+/// Macro to fetch a feed, returning its content type, `Link` header, and body.
+macro_rules! get_feed {
+    ($app:expr, $uri:expr) => {{
+        let resp = test::TestRequest::get().uri($uri).send_request($app).await;
+        assert_eq!(resp.status(), 200);
+        let header = |name: &str| {
+            resp.headers()
+                .get(name)
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .to_string()
+        };
+        let (content_type, link) = (header("Content-Type"), header("Link"));
+        let body = String::from_utf8(test::read_body(resp).await.to_vec()).unwrap();
+        (content_type, link, body)
+    }};
+}
+
+/// Test RSS and Atom feeds list posts with escaped HTML and hub links.
+#[tokio::test]
+async fn test_feeds() {
+    let (app, _) = init_app!(Some("https://hub.example/".to_string()));
+    let token = register_user!(&app, "alice");
+    let first = create_post!(&app, token, "First", vec!["rust".to_string()]);
+    let second = create_post!(&app, token, "Rust & <WASM>", vec![]);
+    let (first, second) = (
+        format!("?post={}", first.public_id),
+        format!("?post={}", second.public_id),
+    );
+
+    let (content_type, link, rss) = get_feed!(&app, "/api/feed.rss");
+    assert!(content_type.starts_with("application/rss+xml"));
+    assert_eq!(
+        link,
+        "<http://api.test/api/feed.rss>; rel=\"self\", <https://hub.example/>; rel=\"hub\""
+    );
+    assert!(rss.contains("<title>Blog Platform</title>"));
+    assert!(rss.contains("<atom:link rel=\"hub\" href=\"https://hub.example/\"/>"));
+    assert!(rss.contains("<title>Rust &amp; &lt;WASM&gt;</title>"));
+    assert!(rss.contains(&format!("<link>http://site.test/{first}</link>")));
+    assert!(rss.contains("<category>rust</category>"));
+    assert!(rss.contains("&lt;strong&gt;bold&lt;/strong&gt;"));
+    // Newest first
+    assert!(rss.find(&second).unwrap() < rss.find(&first).unwrap());
+
+    let (content_type, _, atom) = get_feed!(&app, "/api/feed.atom");
+    assert!(content_type.starts_with("application/atom+xml"));
+    assert!(atom.contains("<link rel=\"hub\" href=\"https://hub.example/\"/>"));
+    assert!(atom.contains(&format!("<id>http://site.test/{second}</id>")));
+    assert!(atom.contains("<category term=\"rust\"/>"));
+    assert!(atom.contains("<name>alice</name>"));
+}
+
+/// Test feed dates are given in the author's time zone.
+#[tokio::test]
+async fn test_feed_dates_use_author_timezone() {
+    let (app, _) = init_app!(None);
+    let alice = register_user!(&app, "alice");
+    let bob = register_user!(&app, "bob");
+    let prefs = PreferencesDto {
+        timezone: "Asia/Tokyo".to_string(),
+        ..PreferencesDto::default()
+    };
+    let resp = test::TestRequest::put()
+        .uri("/api/settings/preferences")
+        .insert_header(("Authorization", format!("Bearer {alice}")))
+        .set_json(&prefs)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 200);
+    create_post!(&app, alice, "Tokyo", vec![]);
+    create_post!(&app, bob, "Greenwich", vec![]);
+
+    let (_, _, rss) = get_feed!(&app, "/api/feed.rss");
+    let pub_dates: Vec<&str> = rss
+        .lines()
+        .filter(|line| line.starts_with("<pubDate>"))
+        .collect();
+    assert_eq!(pub_dates.len(), 2);
+    // Newest (bob's, in UTC) first
+    assert!(pub_dates[0].ends_with("+0000</pubDate>"));
+    assert!(pub_dates[1].ends_with("+0900</pubDate>"));
+
+    let (_, _, atom) = get_feed!(&app, "/api/feed.atom");
+    assert!(atom.contains("+09:00</published>"));
+}
+
+/// Test feeds without a hub do not announce one.
+#[tokio::test]
+async fn test_feeds_without_hub() {
+    let (app, feed_service) = init_app!(None);
+
+    let (_, link, atom) = get_feed!(&app, "/api/feed.atom");
+    assert_eq!(link, "<http://api.test/api/feed.atom>; rel=\"self\"");
+    assert!(!atom.contains("rel=\"hub\""));
+    assert!(atom.contains("<updated>1970-01-01T00:00:00+00:00</updated>"));
+
+    assert_eq!(feed_service.ping_hub(None).await.unwrap(), None);
+}
+
+/// Test the hub is pinged for both feeds once per change.
+#[tokio::test]
+async fn test_websub_ping() {
+    let pings = web::Data::new(Mutex::new(Vec::<String>::new()));
+    let recorded = pings.clone();
+    let server = HttpServer::new(move || {
+        App::new().app_data(recorded.clone()).route(
+            "/hub",
+            web::post().to(
+                |pings: web::Data<Mutex<Vec<String>>>,
+                 form: web::Form<Vec<(String, String)>>| async move {
+                    assert!(form.contains(&("hub.mode".to_string(), "publish".to_string())));
+                    if let Some((_, topic)) = form.iter().find(|(key, _)| key == "hub.url") {
+                        pings.lock().unwrap().push(topic.clone());
+                    }
+                    HttpResponse::NoContent().finish()
+                },
+            ),
+        )
+    })
+    .workers(1)
+    .bind(("127.0.0.1", 0))
+    .unwrap();
+    let hub = format!("http://{}/hub", server.addrs()[0]);
+    tokio::spawn(server.run());
+
+    let (app, feed_service) = init_app!(Some(hub));
+
+    // Nothing to announce yet
+    assert_eq!(feed_service.ping_hub(None).await.unwrap(), None);
+    assert!(pings.lock().unwrap().is_empty());
+
+    let token = register_user!(&app, "alice");
+    create_post!(&app, token, "Hello", vec![]);
+    let announced = feed_service.ping_hub(None).await.unwrap();
+    assert!(announced.is_some());
+    assert_eq!(
+        *pings.lock().unwrap(),
+        vec![
+            "http://api.test/api/feed.rss",
+            "http://api.test/api/feed.atom"
+        ]
+    );
+
+    // No change since the last ping
+    assert_eq!(feed_service.ping_hub(announced).await.unwrap(), announced);
+    assert_eq!(pings.lock().unwrap().len(), 2);
+
+    // Edits are announced too
+    let req = UpdatePostRequest {
+        title: Some("Hello again".to_string()),
+        content: None,
+        tags: None,
+        canonical_url: None,
+        original_source: None,
+    };
+    let resp = test::TestRequest::put()
+        .uri("/api/posts/1")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(&req)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 200);
+    let edited = feed_service.ping_hub(announced).await.unwrap();
+    assert!(edited > announced);
+    assert_eq!(pings.lock().unwrap().len(), 4);
+}
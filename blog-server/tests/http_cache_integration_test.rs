@@ -0,0 +1,331 @@
+//! Integration tests for HEAD, OPTIONS, and conditional requests on post routes.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::http::{Method, header};
+use actix_web::{App, test, web};
+use blog_shared::{AuthResponse, CreatePostRequest, PostDto, RegisterRequest};
+use chrono::{Duration, Utc};
+
+use blog_server::application::{AuthService, BlogService, ModerationService};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::infrastructure::clock::{MockClock, SystemClock};
+use blog_server::infrastructure::config::CacheConfig;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app with auth and blog services, optionally
+/// with cache max-ages and a clock.
+macro_rules! init_app {
+    () => {
+        init_app!(CacheConfig::default())
+    };
+    ($cache:expr) => {
+        init_app!($cache, SystemClock)
+    };
+    ($cache:expr, $clock:expr) => {{
+        let pool = setup_test_db().await;
+        let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+        let user_repo = Arc::new(UserRepository::new(pool.clone()));
+        let post_repo = Arc::new(PostRepository::new(pool.clone()));
+        let auth_service = AuthService::new(
+            Arc::clone(&user_repo),
+            flag_repo,
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let moderation_service =
+            ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+        let blog_service = BlogService::new(
+            Arc::clone(&post_repo),
+            EmbedProvider::ALL.to_vec(),
+            moderation_service.clone(),
+        )
+        .with_clock(Arc::new($clock));
+
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(blog_service))
+                .app_data(web::Data::new($cache))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user and create a post, returning the post and the
+/// author's token.
+macro_rules! create_post_as_author {
+    ($app:expr) => {{
+        let register = RegisterRequest {
+            username: "cacheuser".to_string(),
+            email: "cache@example.com".to_string(),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&register)
+            .send_request($app)
+            .await;
+        let auth: AuthResponse = test::read_body_json(resp).await;
+
+        let req = CreatePostRequest {
+            title: "Cached Post".to_string(),
+            content: "Cached content".to_string(),
+            tags: Vec::new(),
+            lang: None,
+            canonical_url: None,
+            original_source: None,
+            org: None,
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/posts")
+            .insert_header(("Authorization", format!("Bearer {}", auth.token)))
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        let post: PostDto = test::read_body_json(resp).await;
+        (post, auth.token)
+    }};
+}
+
+/// Macro to register a user and create a post, returning the post.
+macro_rules! create_post {
+    ($app:expr) => {{
+        let (post, _) = create_post_as_author!($app);
+        post
+    }};
+}
+
+/// Test that GET on a post returns an ETag header.
+#[tokio::test]
+async fn test_get_post_returns_etag() {
+    let app = init_app!();
+    let post = create_post!(&app);
+
+    let resp = test::TestRequest::get()
+        .uri(&format!("/api/posts/{}", post.public_id))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), 200);
+    assert!(resp.headers().contains_key(header::ETAG));
+}
+
+/// Test that HEAD on a post returns the same ETag as GET.
+#[tokio::test]
+async fn test_head_post_returns_same_etag() {
+    let app = init_app!();
+    let post = create_post!(&app);
+    let uri = format!("/api/posts/{}", post.public_id);
+
+    let get_resp = test::TestRequest::get().uri(&uri).send_request(&app).await;
+    let head_resp = test::TestRequest::default()
+        .method(Method::HEAD)
+        .uri(&uri)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(head_resp.status(), 200);
+    assert_eq!(
+        head_resp.headers().get(header::ETAG),
+        get_resp.headers().get(header::ETAG)
+    );
+}
+
+/// Test that HEAD on the posts collection succeeds.
+#[tokio::test]
+async fn test_head_list_posts() {
+    let app = init_app!();
+
+    let resp = test::TestRequest::default()
+        .method(Method::HEAD)
+        .uri("/api/posts")
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), 200);
+    assert!(resp.headers().contains_key(header::ETAG));
+}
+
+/// Test that a matching If-None-Match returns 304.
+#[tokio::test]
+async fn test_get_post_if_none_match_not_modified() {
+    let app = init_app!();
+    let post = create_post!(&app);
+    let uri = format!("/api/posts/{}", post.public_id);
+
+    let resp = test::TestRequest::get().uri(&uri).send_request(&app).await;
+    let etag = resp.headers().get(header::ETAG).unwrap().clone();
+
+    let resp = test::TestRequest::get()
+        .uri(&uri)
+        .insert_header((header::IF_NONE_MATCH, etag))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), 304);
+}
+
+/// Test that OPTIONS on the posts collection lists allowed methods.
+#[tokio::test]
+async fn test_options_posts_returns_allow() {
+    let app = init_app!();
+
+    let resp = test::TestRequest::default()
+        .method(Method::OPTIONS)
+        .uri("/api/posts")
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), 204);
+    let allow = resp.headers().get(header::ALLOW).unwrap().to_str().unwrap();
+    assert!(allow.contains("GET"));
+    assert!(allow.contains("HEAD"));
+    assert!(allow.contains("POST"));
+}
+
+/// Test that OPTIONS on a single post lists allowed methods.
+#[tokio::test]
+async fn test_options_post_returns_allow() {
+    let app = init_app!();
+
+    let resp = test::TestRequest::default()
+        .method(Method::OPTIONS)
+        .uri("/api/posts/1")
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), 204);
+    let allow = resp.headers().get(header::ALLOW).unwrap().to_str().unwrap();
+    assert!(allow.contains("PUT"));
+    assert!(allow.contains("DELETE"));
+}
+
+/// Test that GET on a post sets Last-Modified and Cache-Control.
+#[tokio::test]
+async fn test_get_post_returns_last_modified_and_cache_control() {
+    let app = init_app!();
+    let post = create_post!(&app);
+
+    let resp = test::TestRequest::get()
+        .uri(&format!("/api/posts/{}", post.public_id))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), 200);
+    assert!(resp.headers().contains_key(header::LAST_MODIFIED));
+    assert_eq!(
+        resp.headers().get(header::CACHE_CONTROL).unwrap(),
+        "public, max-age=60"
+    );
+}
+
+/// Test that responses to authenticated requests are only cached privately.
+#[tokio::test]
+async fn test_authenticated_get_is_private() {
+    let app = init_app!();
+    let (post, token) = create_post_as_author!(&app);
+
+    for uri in [
+        format!("/api/posts/{}", post.public_id),
+        "/api/posts".to_string(),
+    ] {
+        let resp = test::TestRequest::get()
+            .uri(&uri)
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .send_request(&app)
+            .await;
+
+        assert_eq!(resp.status(), 200);
+        let cache_control = resp.headers().get(header::CACHE_CONTROL).unwrap();
+        assert!(cache_control.to_str().unwrap().starts_with("private, "));
+    }
+}
+
+/// Test that the post max-age comes from the cache config.
+#[tokio::test]
+async fn test_get_post_uses_configured_max_age() {
+    let app = init_app!(CacheConfig {
+        post_max_age_secs: 5,
+        ..CacheConfig::default()
+    });
+    let post = create_post!(&app);
+
+    let resp = test::TestRequest::get()
+        .uri(&format!("/api/posts/{}", post.public_id))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(
+        resp.headers().get(header::CACHE_CONTROL).unwrap(),
+        "public, max-age=5"
+    );
+}
+
+/// Test that If-Modified-Since at or after the last change returns 304.
+#[tokio::test]
+async fn test_get_post_if_modified_since_not_modified() {
+    let app = init_app!();
+    let post = create_post!(&app);
+    let uri = format!("/api/posts/{}", post.public_id);
+
+    let resp = test::TestRequest::get().uri(&uri).send_request(&app).await;
+    let last_modified = resp.headers().get(header::LAST_MODIFIED).unwrap().clone();
+
+    let resp = test::TestRequest::get()
+        .uri(&uri)
+        .insert_header((header::IF_MODIFIED_SINCE, last_modified))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), 304);
+}
+
+/// Test that trashing a post moves the post list's Last-Modified, though no
+/// listed post changed.
+#[tokio::test]
+async fn test_list_posts_modified_by_trash() {
+    let clock = MockClock::new(Utc::now());
+    let app = init_app!(CacheConfig::default(), clock.clone());
+    let (post, token) = create_post_as_author!(&app);
+
+    let resp = test::TestRequest::get()
+        .uri("/api/posts")
+        .send_request(&app)
+        .await;
+    let last_modified = resp.headers().get(header::LAST_MODIFIED).unwrap().clone();
+
+    let resp = test::TestRequest::get()
+        .uri("/api/posts")
+        .insert_header((header::IF_MODIFIED_SINCE, last_modified.clone()))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 304);
+
+    clock.advance(Duration::minutes(1));
+    let resp = test::TestRequest::delete()
+        .uri(&format!("/api/posts/{}", post.public_id))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 204);
+
+    let resp = test::TestRequest::get()
+        .uri("/api/posts")
+        .insert_header((header::IF_MODIFIED_SINCE, last_modified))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 200);
+}
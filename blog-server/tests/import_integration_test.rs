@@ -0,0 +1,292 @@
+//! Integration tests for the bulk post import API.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::constants::MAX_IMPORT_POSTS;
+use blog_shared::{
+    AuthResponse, CreateServiceAccountRequest, CreatedServiceAccountResponse, ImportPostDto,
+    ImportPostsRequest, ImportPostsResponse, PostDto, PostListResponse, RegisterRequest,
+    ServiceScope,
+};
+use chrono::{TimeZone, Utc};
+use sqlx::SqlitePool;
+
+use blog_server::application::{AuthService, BlogService, ImportService, ModerationService};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app with the import service.
+macro_rules! init_app {
+    ($pool:expr) => {{
+        let flag_repo = Arc::new(FeatureFlagRepository::new($pool.clone()));
+        let user_repo = Arc::new(UserRepository::new($pool.clone()));
+        let post_repo = Arc::new(PostRepository::new($pool.clone()));
+        let auth_service = AuthService::new(
+            Arc::clone(&user_repo),
+            flag_repo,
+            Arc::new(RecoveryCodeRepository::new($pool.clone())),
+            Arc::new(ServiceAccountRepository::new($pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let moderation_service =
+            ModerationService::new(Arc::new(ModerationRepository::new($pool.clone())));
+        let blog_service = BlogService::new(
+            Arc::clone(&post_repo),
+            EmbedProvider::ALL.to_vec(),
+            moderation_service.clone(),
+        );
+        let import_service = ImportService::new(
+            user_repo,
+            post_repo,
+            blog_service.clone(),
+            moderation_service,
+        );
+
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(blog_service))
+                .app_data(web::Data::new(import_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user and get their token.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp.token
+    }};
+}
+
+/// Macro to post an import request, returning the response.
+macro_rules! import {
+    ($app:expr, $token:expr, $posts:expr, $dry_run:expr) => {{
+        let req = ImportPostsRequest {
+            posts: $posts,
+            dry_run: $dry_run,
+        };
+        test::TestRequest::post()
+            .uri("/api/posts/import")
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json(&req)
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Builds an imported post without author or date.
+fn post(title: &str) -> ImportPostDto {
+    ImportPostDto {
+        title: title.to_string(),
+        content: "Imported *content*".to_string(),
+        tags: Vec::new(),
+        author: None,
+        published_at: None,
+    }
+}
+
+/// Sets a user's role directly in the database.
+async fn set_role(pool: &SqlitePool, username: &str, role: &str) {
+    sqlx::query("UPDATE users SET role = ? WHERE username = ?")
+        .bind(role)
+        .bind(username)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+/// Test importing keeps dates and tags and reports invalid posts.
+#[tokio::test]
+async fn test_import_posts() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let token = register_user!(&app, "alice");
+
+    let published_at = Utc.with_ymd_and_hms(2019, 5, 1, 10, 0, 0).unwrap();
+    let old = ImportPostDto {
+        tags: vec!["Rust".to_string(), "wasm".to_string()],
+        published_at: Some(published_at),
+        ..post("Old post")
+    };
+    let resp = import!(&app, &token, vec![old, post("  ")], false);
+    assert_eq!(resp.status(), 200);
+    let report: ImportPostsResponse = test::read_body_json(resp).await;
+    assert!(!report.dry_run);
+    assert_eq!((report.imported, report.failed), (1, 1));
+    assert_eq!(report.posts[0].author_username, "alice");
+    assert_eq!(report.posts[1].post_id, None);
+    assert_eq!(
+        report.posts[1].error.as_deref(),
+        Some("Validation error: Title cannot be empty")
+    );
+
+    let id = report.posts[0].post_public_id.clone().unwrap();
+    let resp = test::TestRequest::get()
+        .uri(&format!("/api/posts/{id}"))
+        .send_request(&app)
+        .await;
+    let post: PostDto = test::read_body_json(resp).await;
+    assert_eq!(post.title, "Old post");
+    assert_eq!(post.tags, vec!["rust", "wasm"]);
+    assert_eq!(post.created_at, published_at);
+    assert_eq!(post.updated_at, published_at);
+}
+
+/// Test a dry run checks posts without creating them.
+#[tokio::test]
+async fn test_import_dry_run() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let token = register_user!(&app, "alice");
+
+    let bad_tags = ImportPostDto {
+        tags: vec!["not a tag".to_string()],
+        ..post("Bad tags")
+    };
+    let resp = import!(&app, &token, vec![post("First"), bad_tags], true);
+    let report: ImportPostsResponse = test::read_body_json(resp).await;
+    assert!(report.dry_run);
+    assert_eq!((report.imported, report.failed), (1, 1));
+    assert_eq!(report.posts[0].post_id, None);
+    assert_eq!(report.posts[0].error, None);
+    assert!(report.posts[1].error.is_some());
+
+    let resp = test::TestRequest::get()
+        .uri("/api/posts")
+        .send_request(&app)
+        .await;
+    let list: PostListResponse = test::read_body_json(resp).await;
+    assert_eq!(list.total, 0);
+}
+
+/// Test only admins may import posts as other users.
+#[tokio::test]
+async fn test_import_authors() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let token = register_user!(&app, "alice");
+    register_user!(&app, "bob");
+
+    let as_bob = ImportPostDto {
+        author: Some("bob".to_string()),
+        ..post("Bob's post")
+    };
+    let as_nobody = ImportPostDto {
+        author: Some("nobody".to_string()),
+        ..post("Nobody's post")
+    };
+    let as_alice = ImportPostDto {
+        author: Some("alice".to_string()),
+        ..post("Alice's post")
+    };
+
+    let posts = vec![as_bob.clone(), as_nobody.clone(), as_alice.clone()];
+    let resp = import!(&app, &token, posts, true);
+    let report: ImportPostsResponse = test::read_body_json(resp).await;
+    assert_eq!((report.imported, report.failed), (1, 2));
+    assert_eq!(report.posts[0].error.as_deref(), Some("Forbidden"));
+    assert_eq!(report.posts[2].error, None);
+
+    set_role(&pool, "alice", "admin").await;
+    let resp = import!(&app, &token, vec![as_bob, as_nobody, as_alice], false);
+    let report: ImportPostsResponse = test::read_body_json(resp).await;
+    assert_eq!((report.imported, report.failed), (2, 1));
+    assert_eq!(report.posts[1].error.as_deref(), Some("User not found"));
+
+    let id = report.posts[0].post_public_id.clone().unwrap();
+    let resp = test::TestRequest::get()
+        .uri(&format!("/api/posts/{id}"))
+        .send_request(&app)
+        .await;
+    let post: PostDto = test::read_body_json(resp).await;
+    assert_eq!(post.author_username, "bob");
+}
+
+/// Test a service token bound to an admin imports with user rights, so it
+/// may not name other authors.
+#[tokio::test]
+async fn test_import_service_token_cannot_import_as_others() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let admin_token = register_user!(&app, "alice");
+    register_user!(&app, "bob");
+    set_role(&pool, "alice", "admin").await;
+
+    let alice_id: i64 = sqlx::query_scalar("SELECT id FROM users WHERE username = 'alice'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let resp = test::TestRequest::post()
+        .uri("/api/admin/service-accounts")
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .set_json(CreateServiceAccountRequest {
+            name: "Importer".to_string(),
+            user_id: alice_id,
+            scopes: vec![ServiceScope::Write],
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 201);
+    let created: CreatedServiceAccountResponse = test::read_body_json(resp).await;
+
+    let as_bob = ImportPostDto {
+        author: Some("bob".to_string()),
+        ..post("Bob's post")
+    };
+    let resp = import!(&app, &created.token, vec![as_bob, post("Own post")], false);
+    let report: ImportPostsResponse = test::read_body_json(resp).await;
+    assert_eq!((report.imported, report.failed), (1, 1));
+    assert_eq!(report.posts[0].error.as_deref(), Some("Forbidden"));
+    assert_eq!(report.posts[1].author_username, "alice");
+}
+
+/// Test the batch size limits and authentication.
+#[tokio::test]
+async fn test_import_limits() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let token = register_user!(&app, "alice");
+
+    let resp = import!(&app, &token, Vec::new(), false);
+    assert_eq!(resp.status(), 400);
+
+    let posts = vec![post("Post"); MAX_IMPORT_POSTS + 1];
+    let resp = import!(&app, &token, posts, false);
+    assert_eq!(resp.status(), 400);
+
+    let resp = test::TestRequest::post()
+        .uri("/api/posts/import")
+        .set_json(ImportPostsRequest {
+            posts: vec![post("Post")],
+            dry_run: false,
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 401);
+}
@@ -0,0 +1,153 @@
+//! Integration tests for invite-only registration.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{AuthResponse, ErrorResponse, FeaturesResponse, InviteDto, RegisterRequest};
+
+use blog_server::application::AuthService;
+use blog_server::data::{
+    FeatureFlagRepository, InviteRepository, RecoveryCodeRepository, ServiceAccountRepository,
+    UserRepository,
+};
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app, with invite-only registration if `$invite_only`.
+macro_rules! init_app {
+    ($invite_only:expr) => {{
+        let pool = setup_test_db().await;
+        let mut auth_service = AuthService::new(
+            Arc::new(UserRepository::new(pool.clone())),
+            Arc::new(FeatureFlagRepository::new(pool.clone())),
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        if $invite_only {
+            auth_service = auth_service.with_invites(Arc::new(InviteRepository::new(pool)));
+        }
+
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user with an optional invite code and get the response.
+macro_rules! register {
+    ($app:expr, $username:expr, $invite_code:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: $invite_code,
+        };
+
+        test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Macro to create an invite as the user with `$token`.
+macro_rules! create_invite {
+    ($app:expr, $token:expr) => {{
+        test::TestRequest::post()
+            .uri("/api/invites")
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Macro to get the public feature switches.
+macro_rules! features {
+    ($app:expr) => {{
+        let resp = test::TestRequest::get()
+            .uri("/api/features")
+            .send_request($app)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let features: FeaturesResponse = test::read_body_json(resp).await;
+        features
+    }};
+}
+
+/// Without invite-only mode anyone registers and no invites can be made.
+#[tokio::test]
+async fn test_open_registration() {
+    let app = init_app!(false);
+    assert!(!features!(&app).invite_only);
+
+    let resp = register!(&app, "alice", None);
+    assert_eq!(resp.status(), 201);
+    let auth: AuthResponse = test::read_body_json(resp).await;
+
+    let resp = create_invite!(&app, auth.token);
+    assert_eq!(resp.status(), 403);
+}
+
+/// In invite-only mode the first user registers freely; everyone after
+/// needs an unused invite, and each invite works once.
+#[tokio::test]
+async fn test_invite_only_registration() {
+    let app = init_app!(true);
+    let features = features!(&app);
+    assert!(features.invite_only);
+    assert!(features.registration);
+
+    let resp = register!(&app, "alice", None);
+    assert_eq!(resp.status(), 201);
+    let alice: AuthResponse = test::read_body_json(resp).await;
+
+    let resp = register!(&app, "bob", None);
+    assert_eq!(resp.status(), 400);
+
+    let resp = register!(&app, "bob", Some("AAAAA-AAAAA".to_string()));
+    assert_eq!(resp.status(), 400);
+    let body: ErrorResponse = test::read_body_json(resp).await;
+    assert_eq!(body.code.as_deref(), Some("invalid_invite"));
+
+    let resp = create_invite!(&app, alice.token);
+    assert_eq!(resp.status(), 201);
+    let invite: InviteDto = test::read_body_json(resp).await;
+    assert_eq!(invite.code.len(), 11);
+
+    // Codes are matched ignoring case and surrounding space
+    let resp = register!(
+        &app,
+        "bob",
+        Some(format!(" {} ", invite.code.to_lowercase()))
+    );
+    assert_eq!(resp.status(), 201);
+
+    let resp = register!(&app, "carol", Some(invite.code.clone()));
+    assert_eq!(resp.status(), 400);
+}
+
+/// A rejected registration does not use up its invite.
+#[tokio::test]
+async fn test_failed_registration_keeps_invite() {
+    let app = init_app!(true);
+    let resp = register!(&app, "alice", None);
+    let alice: AuthResponse = test::read_body_json(resp).await;
+    let resp = create_invite!(&app, alice.token);
+    let invite: InviteDto = test::read_body_json(resp).await;
+
+    // Taken username
+    let resp = register!(&app, "alice", Some(invite.code.clone()));
+    assert_eq!(resp.status(), 400);
+
+    let resp = register!(&app, "bob", Some(invite.code.clone()));
+    assert_eq!(resp.status(), 201);
+}
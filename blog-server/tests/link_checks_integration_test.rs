@@ -0,0 +1,210 @@
+//! Integration tests for the broken link checker and report.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    AuthResponse, BrokenLinkListResponse, CreatePostRequest, PostDto, RegisterRequest,
+};
+use chrono::{Duration, Utc};
+
+use blog_server::application::{AuthService, BlogService, LinkCheckService, ModerationService};
+use blog_server::data::{
+    FeatureFlagRepository, LinkCheckRepository, ModerationRepository, PostRepository,
+    RecoveryCodeRepository, ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::{EmbedProvider, LinkCheck};
+use blog_server::infrastructure::http_fetcher::HttpFetcher;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app; also returns the link check repository.
+macro_rules! init_app {
+    () => {{
+        let pool = setup_test_db().await;
+        let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+        let user_repo = Arc::new(UserRepository::new(pool.clone()));
+        let post_repo = Arc::new(PostRepository::new(pool.clone()));
+        let check_repo = Arc::new(LinkCheckRepository::new(pool.clone()));
+        let auth_service = AuthService::new(
+            user_repo,
+            flag_repo,
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let moderation_service =
+            ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+        let blog_service = BlogService::new(
+            post_repo,
+            EmbedProvider::ALL.to_vec(),
+            moderation_service.clone(),
+        );
+        let link_check_service =
+            LinkCheckService::new(Arc::clone(&check_repo), HttpFetcher::new().unwrap());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(blog_service))
+                .app_data(web::Data::new(link_check_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await;
+        (app, check_repo)
+    }};
+}
+
+/// Macro to register a user and get their token.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp.token
+    }};
+}
+
+/// Macro to create a post with the given content.
+macro_rules! create_post {
+    ($app:expr, $token:expr, $content:expr) => {{
+        let req = CreatePostRequest {
+            title: "Links".to_string(),
+            content: $content.to_string(),
+            tags: vec![],
+            lang: None,
+            canonical_url: None,
+            original_source: None,
+            org: None,
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/posts")
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        let post: PostDto = test::read_body_json(resp).await;
+        post
+    }};
+}
+
+/// Macro to fetch the broken link report.
+macro_rules! broken_links {
+    ($app:expr, $token:expr) => {{
+        let resp = test::TestRequest::get()
+            .uri("/api/me/posts/broken-links")
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .send_request($app)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let list: BrokenLinkListResponse = test::read_body_json(resp).await;
+        list
+    }};
+}
+
+/// Returns a check result for a URL.
+fn check(url: &str, status_code: Option<u16>, error: Option<&str>) -> LinkCheck {
+    LinkCheck {
+        url: url.to_string(),
+        status_code,
+        error: error.map(str::to_string),
+        checked_at: Utc::now(),
+    }
+}
+
+/// Test that only the author's failed links are reported.
+#[tokio::test]
+async fn test_broken_links_report() {
+    let (app, check_repo) = init_app!();
+    let token = register_user!(&app, "author");
+    let other_token = register_user!(&app, "other");
+
+    let post = create_post!(
+        &app,
+        &token,
+        "https://ok.example https://gone.example https://down.example"
+    );
+    create_post!(&app, &other_token, "https://gone.example");
+
+    let due = check_repo.list_due(Utc::now(), 10).await.unwrap();
+    assert_eq!(due.len(), 3);
+
+    for result in [
+        check("https://ok.example", Some(200), None),
+        check("https://gone.example", Some(404), None),
+        check("https://down.example", None, Some("connection refused")),
+    ] {
+        check_repo.record(&result).await.unwrap();
+    }
+
+    let list = broken_links!(&app, &token);
+    assert_eq!(list.links.len(), 2);
+    assert_eq!(list.links[0].post_public_id, post.public_id);
+    assert_eq!(list.links[0].url, "https://gone.example");
+    assert_eq!(list.links[0].status_code, Some(404));
+    assert_eq!(list.links[1].url, "https://down.example");
+    assert_eq!(list.links[1].status_code, None);
+    assert_eq!(list.links[1].error.as_deref(), Some("connection refused"));
+
+    // Checked links are not due again until stale
+    let stale_before = Utc::now() - Duration::hours(1);
+    assert!(
+        check_repo
+            .list_due(stale_before, 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // A recovered link drops out of the report
+    check_repo
+        .record(&check("https://gone.example", Some(200), None))
+        .await
+        .unwrap();
+    assert_eq!(broken_links!(&app, &token).links.len(), 1);
+    assert_eq!(broken_links!(&app, &other_token).links.len(), 0);
+}
+
+/// Test that the report requires authentication.
+#[tokio::test]
+async fn test_broken_links_requires_auth() {
+    let (app, _check_repo) = init_app!();
+
+    let resp = test::TestRequest::get()
+        .uri("/api/me/posts/broken-links")
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 401);
+}
+
+/// Test which check results count as broken.
+#[tokio::test]
+async fn test_link_check_is_broken() {
+    assert!(!check("https://a.example", Some(200), None).is_broken());
+    assert!(!check("https://a.example", Some(301), None).is_broken());
+    assert!(!check("https://a.example", Some(429), None).is_broken());
+    assert!(check("https://a.example", Some(404), None).is_broken());
+    assert!(check("https://a.example", Some(503), None).is_broken());
+    assert!(check("https://a.example", None, Some("timed out")).is_broken());
+}
+
+/// Test that links to private hosts are refused rather than requested.
+#[tokio::test]
+async fn test_check_refuses_private_hosts() {
+    let fetcher = HttpFetcher::new().unwrap();
+    assert!(fetcher.check_status("http://127.0.0.1:1/").await.is_err());
+    assert!(fetcher.check_status("ftp://example.com/").await.is_err());
+}
@@ -0,0 +1,279 @@
+//! Integration tests for post links and link preview metadata.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::http::header;
+use actix_web::{App, test, web};
+use blog_shared::{AuthResponse, CreatePostRequest, PostDto, RegisterRequest, UpdatePostRequest};
+use chrono::{Duration, Utc};
+
+use blog_server::application::{AuthService, BlogService, ModerationService};
+use blog_server::data::{
+    FeatureFlagRepository, LinkPreviewRepository, ModerationRepository, PostRepository,
+    RecoveryCodeRepository, ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::{EmbedProvider, LinkPreview, parse_open_graph};
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app; also returns the link preview repository.
+macro_rules! init_app {
+    () => {{
+        let pool = setup_test_db().await;
+        let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+        let user_repo = Arc::new(UserRepository::new(pool.clone()));
+        let post_repo = Arc::new(PostRepository::new(pool.clone()));
+        let preview_repo = LinkPreviewRepository::new(pool.clone());
+        let auth_service = AuthService::new(
+            user_repo,
+            flag_repo,
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let moderation_service =
+            ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+        let blog_service = BlogService::new(
+            post_repo,
+            EmbedProvider::ALL.to_vec(),
+            moderation_service.clone(),
+        );
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(blog_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await;
+        (app, preview_repo)
+    }};
+}
+
+/// Macro to register a user and get their token.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp.token
+    }};
+}
+
+/// Macro to fetch a post.
+macro_rules! get_post {
+    ($app:expr, $id:expr) => {{
+        let resp = test::TestRequest::get()
+            .uri(&format!("/api/posts/{}", $id))
+            .send_request($app)
+            .await;
+        let post: PostDto = test::read_body_json(resp).await;
+        post
+    }};
+}
+
+/// Returns a fetched preview for a URL.
+fn preview(url: &str) -> LinkPreview {
+    LinkPreview {
+        url: url.to_string(),
+        title: "Example".to_string(),
+        description: Some("An example page".to_string()),
+        image_url: None,
+        site_name: Some("Example".to_string()),
+    }
+}
+
+/// Test that external links are queued and fetched previews returned with the post.
+#[tokio::test]
+async fn test_post_link_previews() {
+    let (app, preview_repo) = init_app!();
+    let token = register_user!(&app, "author");
+
+    let req = CreatePostRequest {
+        title: "Links".to_string(),
+        content: "See [docs](https://example.com/docs) and https://example.org/page.\n\n\
+                  `https://inline.test` [local](/posts) https://example.com/docs\n\n\
+                  ```\nhttps://code.test\n```"
+            .to_string(),
+        tags: vec![],
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
+    };
+    let resp = test::TestRequest::post()
+        .uri("/api/posts")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .set_json(&req)
+        .send_request(&app)
+        .await;
+    let post: PostDto = test::read_body_json(resp).await;
+    assert!(post.link_previews.is_empty());
+
+    let due = preview_repo.list_due(Utc::now(), 10).await.unwrap();
+    assert_eq!(
+        due,
+        vec!["https://example.com/docs", "https://example.org/page"]
+    );
+
+    preview_repo
+        .save(&preview("https://example.org/page"), Utc::now())
+        .await
+        .unwrap();
+    preview_repo
+        .mark_failed("https://example.com/docs", Utc::now())
+        .await
+        .unwrap();
+
+    let post = get_post!(&app, post.public_id);
+    assert_eq!(post.link_previews.len(), 1);
+    assert_eq!(post.link_previews[0].url, "https://example.org/page");
+    assert_eq!(post.link_previews[0].title, "Example");
+
+    // Both were attempted; neither is due until stale
+    let stale_before = Utc::now() - Duration::days(1);
+    assert!(
+        preview_repo
+            .list_due(stale_before, 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        preview_repo.list_due(Utc::now(), 10).await.unwrap().len(),
+        2
+    );
+}
+
+/// Test that a fetched preview moves the post's Last-Modified, so clients
+/// revalidating by date see it.
+#[tokio::test]
+async fn test_preview_moves_last_modified() {
+    let (app, preview_repo) = init_app!();
+    let token = register_user!(&app, "author");
+
+    let req = CreatePostRequest {
+        title: "Link".to_string(),
+        content: "See https://example.org/page".to_string(),
+        tags: vec![],
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
+    };
+    let resp = test::TestRequest::post()
+        .uri("/api/posts")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .set_json(&req)
+        .send_request(&app)
+        .await;
+    let post: PostDto = test::read_body_json(resp).await;
+    let uri = format!("/api/posts/{}", post.public_id);
+
+    let resp = test::TestRequest::get().uri(&uri).send_request(&app).await;
+    let last_modified = resp.headers().get(header::LAST_MODIFIED).unwrap().clone();
+
+    preview_repo
+        .save(
+            &preview("https://example.org/page"),
+            Utc::now() + Duration::minutes(1),
+        )
+        .await
+        .unwrap();
+
+    let resp = test::TestRequest::get()
+        .uri(&uri)
+        .insert_header((header::IF_MODIFIED_SINCE, last_modified))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 200);
+    let post: PostDto = test::read_body_json(resp).await;
+    assert_eq!(post.link_previews.len(), 1);
+}
+
+/// Test that editing the content replaces the post's links.
+#[tokio::test]
+async fn test_update_replaces_links() {
+    let (app, preview_repo) = init_app!();
+    let token = register_user!(&app, "author");
+
+    let req = CreatePostRequest {
+        title: "Links".to_string(),
+        content: "https://example.org/old".to_string(),
+        tags: vec![],
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
+    };
+    let resp = test::TestRequest::post()
+        .uri("/api/posts")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .set_json(&req)
+        .send_request(&app)
+        .await;
+    let post: PostDto = test::read_body_json(resp).await;
+    preview_repo
+        .save(&preview("https://example.org/old"), Utc::now())
+        .await
+        .unwrap();
+    assert_eq!(get_post!(&app, post.public_id).link_previews.len(), 1);
+
+    let req = UpdatePostRequest {
+        title: None,
+        content: Some("Now https://example.org/new".to_string()),
+        tags: None,
+        canonical_url: None,
+        original_source: None,
+    };
+    let resp = test::TestRequest::put()
+        .uri(&format!("/api/posts/{}", post.public_id))
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .set_json(&req)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 200);
+
+    assert!(get_post!(&app, post.public_id).link_previews.is_empty());
+    let due = preview_repo.list_due(Utc::now(), 10).await.unwrap();
+    assert_eq!(due, vec!["https://example.org/new"]);
+}
+
+/// Test extracting Open Graph metadata from a page.
+#[tokio::test]
+async fn test_parse_open_graph() {
+    let html = r#"<html><head>
+        <title>Fallback</title>
+        <meta property="og:title" content="Rust &amp; WebAssembly">
+        <META name='description' content='A book'>
+        <meta property="og:image" content="/cover.png" />
+        <meta property="og:site_name" content="Rust Docs">
+        </head></html>"#;
+    let preview = parse_open_graph("https://docs.example/book/intro", html).unwrap();
+    assert_eq!(preview.title, "Rust & WebAssembly");
+    assert_eq!(preview.description.as_deref(), Some("A book"));
+    assert_eq!(
+        preview.image_url.as_deref(),
+        Some("https://docs.example/cover.png")
+    );
+    assert_eq!(preview.site_name.as_deref(), Some("Rust Docs"));
+
+    let preview = parse_open_graph("https://a.example", "<title> Plain </title>").unwrap();
+    assert_eq!(preview.title, "Plain");
+    assert_eq!(preview.description, None);
+
+    assert!(parse_open_graph("https://a.example", "<p>No title</p>").is_none());
+}
@@ -0,0 +1,254 @@
+//! Integration tests for Markdown rendering and the preview endpoint.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    AuthResponse, CreatePostRequest, MarkdownPreviewResponse, PostDto, RegisterRequest,
+};
+
+use blog_server::application::{AuthService, BlogService, ModerationService};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app with auth and blog services.
+macro_rules! init_app {
+    () => {
+        init_app!(EmbedProvider::ALL.to_vec())
+    };
+    ($embeds:expr) => {{
+        let pool = setup_test_db().await;
+        let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+        let user_repo = Arc::new(UserRepository::new(pool.clone()));
+        let post_repo = Arc::new(PostRepository::new(pool.clone()));
+        let auth_service = AuthService::new(
+            user_repo,
+            flag_repo,
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let moderation_service =
+            ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+        let blog_service = BlogService::new(post_repo, $embeds, moderation_service.clone());
+
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(blog_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to render Markdown through the preview endpoint.
+macro_rules! preview {
+    ($app:expr, $text:expr) => {{
+        test::TestRequest::get()
+            .uri(&format!("/api/markdown/preview?text={}", encode($text)))
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Percent-encodes a query parameter value.
+fn encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+/// Test that the GFM extensions are rendered.
+#[tokio::test]
+async fn test_preview_gfm_extensions() {
+    let app = init_app!();
+
+    let text = "| a | b |\n|:--|--:|\n| 1 | 2 |\n\n\
+                - [x] done\n- [ ] todo\n\n\
+                ~~old~~ see https://example.com/page.\n\n\
+                Claim.[^src]\n\n[^src]: Source.";
+    let resp = preview!(&app, text);
+    assert_eq!(resp.status(), 200);
+    let preview: MarkdownPreviewResponse = test::read_body_json(resp).await;
+
+    assert!(
+        preview
+            .html
+            .contains("<th style=\"text-align: left\">a</th>")
+    );
+    assert!(
+        preview
+            .html
+            .contains("<td style=\"text-align: right\">2</td>")
+    );
+    assert!(
+        preview
+            .html
+            .contains("<input type=\"checkbox\" disabled checked> done")
+    );
+    assert!(
+        preview
+            .html
+            .contains("<input type=\"checkbox\" disabled> todo")
+    );
+    assert!(preview.html.contains("<del>old</del>"));
+    assert!(preview.html.contains(
+        "<a href=\"https://example.com/page\" rel=\"nofollow noopener\">https://example.com/page</a>."
+    ));
+    assert!(
+        preview
+            .html
+            .contains("<a href=\"#fn-src\" id=\"fnref-src\">1</a>")
+    );
+    assert!(
+        preview
+            .html
+            .contains("<div class=\"footnote\" id=\"fn-src\">")
+    );
+    assert!(preview.supported.iter().any(|s| s == "footnotes"));
+}
+
+/// Test that raw HTML is escaped and unsafe URLs are dropped.
+#[tokio::test]
+async fn test_preview_sanitizes_output() {
+    let app = init_app!();
+
+    let text = "<script>alert(1)</script>\n\n\
+                [click](javascript:alert(1)) ![img](data:image/png;base64,AAAA) \
+                [ok](/posts \"Posts\")";
+    let resp = preview!(&app, text);
+    let preview: MarkdownPreviewResponse = test::read_body_json(resp).await;
+
+    assert!(!preview.html.contains("<script>"));
+    assert!(preview.html.contains("&lt;script&gt;"));
+    assert!(!preview.html.contains("javascript:"));
+    assert!(!preview.html.contains("data:"));
+    assert!(preview.html.contains("click"));
+    assert!(preview.html.contains("img"));
+    assert!(
+        preview
+            .html
+            .contains("<a href=\"/posts\" title=\"Posts\" rel=\"nofollow noopener\">ok</a>")
+    );
+}
+
+/// Test that overly long preview text is rejected.
+#[tokio::test]
+async fn test_preview_too_long() {
+    let app = init_app!();
+
+    let resp = preview!(&app, &"a".repeat(8_001));
+    assert_eq!(resp.status(), 400);
+}
+
+/// Test that posts include their rendered content.
+#[tokio::test]
+async fn test_post_includes_content_html() {
+    let app = init_app!();
+    let req = RegisterRequest {
+        username: "author".to_string(),
+        email: "author@example.com".to_string(),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    let resp = test::TestRequest::post()
+        .uri("/api/auth/register")
+        .set_json(&req)
+        .send_request(&app)
+        .await;
+    let auth: AuthResponse = test::read_body_json(resp).await;
+
+    let req = CreatePostRequest {
+        title: "Markdown".to_string(),
+        content: "# Title\n\nSome *emphasis*.".to_string(),
+        tags: vec![],
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
+    };
+    let resp = test::TestRequest::post()
+        .uri("/api/posts")
+        .insert_header(("Authorization", format!("Bearer {}", auth.token)))
+        .set_json(&req)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 201);
+    let post: PostDto = test::read_body_json(resp).await;
+
+    assert_eq!(post.content, "# Title\n\nSome *emphasis*.");
+    assert_eq!(
+        post.content_html,
+        "<h1>Title</h1>\n<p>Some <em>emphasis</em>.</p>\n"
+    );
+}
+
+/// Test that whitelisted URLs alone in a paragraph become lazy iframes.
+#[tokio::test]
+async fn test_preview_embeds() {
+    let app = init_app!();
+
+    let text = "https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=42\n\n\
+                https://youtu.be/dQw4w9WgXcQ\n\n\
+                https://gist.github.com/octocat/6cad326836d38bd3a7ae\n\n\
+                Watch https://youtu.be/dQw4w9WgXcQ later.\n\n\
+                https://youtu.be/not-an-id\n\n\
+                https://example.com/video";
+    let resp = preview!(&app, text);
+    let preview: MarkdownPreviewResponse = test::read_body_json(resp).await;
+
+    let youtube = "<iframe src=\"https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ\"";
+    assert_eq!(preview.html.matches(youtube).count(), 2);
+    assert!(
+        preview
+            .html
+            .contains("<iframe src=\"https://gist.github.com/octocat/6cad326836d38bd3a7ae.pibb\"")
+    );
+    assert_eq!(preview.html.matches("loading=\"lazy\"").count(), 3);
+    assert!(
+        preview
+            .html
+            .contains("Watch <a href=\"https://youtu.be/dQw4w9WgXcQ\"")
+    );
+    assert!(
+        preview
+            .html
+            .contains("<a href=\"https://youtu.be/not-an-id\"")
+    );
+    assert!(
+        preview
+            .html
+            .contains("<a href=\"https://example.com/video\"")
+    );
+}
+
+/// Test that providers missing from the whitelist are rendered as links.
+#[tokio::test]
+async fn test_preview_embeds_whitelist() {
+    let app = init_app!(vec![EmbedProvider::Gist]);
+
+    let resp = preview!(&app, "https://youtu.be/dQw4w9WgXcQ");
+    let preview: MarkdownPreviewResponse = test::read_body_json(resp).await;
+
+    assert!(!preview.html.contains("<iframe"));
+    assert!(
+        preview
+            .html
+            .contains("<a href=\"https://youtu.be/dQw4w9WgXcQ\"")
+    );
+}
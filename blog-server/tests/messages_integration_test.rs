@@ -0,0 +1,211 @@
+//! Integration tests for private messages.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    AuthResponse, ConversationDto, ConversationListResponse, MessageDto, MessageListResponse,
+    RegisterRequest, SendMessageRequest, StartConversationRequest, UnreadCountResponse,
+};
+
+use blog_server::application::{AuthService, MessageService};
+use blog_server::data::{
+    FeatureFlagRepository, MessageRepository, RecoveryCodeRepository, ServiceAccountRepository,
+    UserRepository,
+};
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app with the services messaging needs.
+macro_rules! init_app {
+    () => {{
+        let pool = setup_test_db().await;
+        let user_repo = Arc::new(UserRepository::new(pool.clone()));
+        let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+        let message_repo = Arc::new(MessageRepository::new(pool.clone()));
+        let auth_service = AuthService::new(
+            Arc::clone(&user_repo),
+            flag_repo,
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let message_service = MessageService::new(message_repo, user_repo);
+
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(message_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user and get the auth response.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp
+    }};
+}
+
+/// Macro to send an authenticated request with an optional JSON body.
+macro_rules! send {
+    ($app:expr, $method:ident, $uri:expr, $token:expr) => {{
+        test::TestRequest::$method()
+            .uri($uri)
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .send_request($app)
+            .await
+    }};
+    ($app:expr, $method:ident, $uri:expr, $token:expr, $body:expr) => {{
+        test::TestRequest::$method()
+            .uri($uri)
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json($body)
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Returns a request starting a conversation with `username`.
+fn start_request(username: &str, content: &str) -> StartConversationRequest {
+    StartConversationRequest {
+        username: username.to_string(),
+        content: content.to_string(),
+    }
+}
+
+/// Test a conversation: start, reply, unread counters, and reading.
+#[tokio::test]
+async fn test_conversation_flow() {
+    let app = init_app!();
+    let alice = register_user!(&app, "alice");
+    let bob = register_user!(&app, "bob");
+
+    let resp = send!(
+        &app,
+        post,
+        "/api/conversations",
+        alice.token,
+        &start_request("bob", "Hi Bob")
+    );
+    assert_eq!(resp.status(), 201);
+    let conversation: ConversationDto = test::read_body_json(resp).await;
+    assert_eq!(conversation.other_username, "bob");
+    assert_eq!(conversation.last_message.as_deref(), Some("Hi Bob"));
+    assert_eq!(conversation.unread_count, 0);
+
+    // Starting again reuses the conversation
+    let resp = send!(
+        &app,
+        post,
+        "/api/conversations",
+        alice.token,
+        &start_request("bob", "Are you there?")
+    );
+    let again: ConversationDto = test::read_body_json(resp).await;
+    assert_eq!(again.id, conversation.id);
+
+    let unread: UnreadCountResponse =
+        test::read_body_json(send!(&app, get, "/api/conversations/unread", bob.token)).await;
+    assert_eq!(unread.unread, 2);
+    let unread: UnreadCountResponse =
+        test::read_body_json(send!(&app, get, "/api/conversations/unread", alice.token)).await;
+    assert_eq!(unread.unread, 0);
+
+    let inbox: ConversationListResponse =
+        test::read_body_json(send!(&app, get, "/api/conversations", bob.token)).await;
+    assert_eq!(inbox.total, 1);
+    assert_eq!(inbox.conversations[0].other_username, "alice");
+    assert_eq!(inbox.conversations[0].unread_count, 2);
+
+    // Reading the messages clears the counter
+    let uri = format!("/api/conversations/{}/messages", conversation.id);
+    let resp = send!(&app, get, &format!("{uri}?limit=1"), bob.token);
+    assert_eq!(resp.status(), 200);
+    let page: MessageListResponse = test::read_body_json(resp).await;
+    assert_eq!(page.total, 2);
+    assert_eq!(page.messages.len(), 1);
+    assert_eq!(page.messages[0].content, "Are you there?");
+    let unread: UnreadCountResponse =
+        test::read_body_json(send!(&app, get, "/api/conversations/unread", bob.token)).await;
+    assert_eq!(unread.unread, 0);
+
+    let reply = SendMessageRequest {
+        content: "Hi Alice".to_string(),
+    };
+    let resp = send!(&app, post, &uri, bob.token, &reply);
+    assert_eq!(resp.status(), 201);
+    let message: MessageDto = test::read_body_json(resp).await;
+    assert_eq!(message.sender_username, "bob");
+    let inbox: ConversationListResponse =
+        test::read_body_json(send!(&app, get, "/api/conversations", alice.token)).await;
+    assert_eq!(inbox.conversations[0].unread_count, 1);
+    assert_eq!(
+        inbox.conversations[0].last_message.as_deref(),
+        Some("Hi Alice")
+    );
+}
+
+/// Test that conversations are private and messages are validated.
+#[tokio::test]
+async fn test_conversation_access_and_validation() {
+    let app = init_app!();
+    let alice = register_user!(&app, "alice");
+    let _bob = register_user!(&app, "bob");
+    let eve = register_user!(&app, "eve");
+
+    let conversation: ConversationDto = test::read_body_json(send!(
+        &app,
+        post,
+        "/api/conversations",
+        alice.token,
+        &start_request("bob", "Secret")
+    ))
+    .await;
+
+    // Outsiders cannot read or post
+    let uri = format!("/api/conversations/{}/messages", conversation.id);
+    assert_eq!(send!(&app, get, &uri, eve.token).status(), 404);
+    let message = SendMessageRequest {
+        content: "Hello".to_string(),
+    };
+    assert_eq!(send!(&app, post, &uri, eve.token, &message).status(), 404);
+    let inbox: ConversationListResponse =
+        test::read_body_json(send!(&app, get, "/api/conversations", eve.token)).await;
+    assert_eq!(inbox.total, 0);
+
+    let cases = [
+        (start_request("alice", "Hi me"), 400),
+        (start_request("nobody", "Hi"), 404),
+        (start_request("bob", "   "), 400),
+        (start_request("bob", &"x".repeat(2001)), 400),
+    ];
+    for (req, status) in cases {
+        let resp = send!(&app, post, "/api/conversations", alice.token, &req);
+        assert_eq!(resp.status(), status);
+    }
+
+    let resp = test::TestRequest::get()
+        .uri("/api/conversations")
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 401);
+}
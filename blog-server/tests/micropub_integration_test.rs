@@ -0,0 +1,285 @@
+//! Integration tests for the Micropub endpoint.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, HttpRequest, HttpResponse, HttpServer, test, web};
+use blog_shared::{AuthResponse, PostDto, ProfileDto, RegisterRequest};
+
+use blog_server::application::{
+    AuthService, BlogService, MicropubService, ModerationService, SettingsService,
+};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::infrastructure::http_fetcher::HttpFetcher;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app, verifying IndieAuth tokens with the given
+/// token endpoint.
+macro_rules! init_app {
+    ($token_endpoint:expr) => {{
+        let pool = setup_test_db().await;
+        let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+        let user_repo = Arc::new(UserRepository::new(pool.clone()));
+        let post_repo = Arc::new(PostRepository::new(pool.clone()));
+        let auth_service = AuthService::new(
+            Arc::clone(&user_repo),
+            flag_repo,
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let moderation_service =
+            ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+        let blog_service =
+            BlogService::new(post_repo, EmbedProvider::ALL.to_vec(), moderation_service);
+        let settings_service = SettingsService::new(Arc::clone(&user_repo));
+        let micropub_service = MicropubService::new(
+            user_repo,
+            blog_service.clone(),
+            HttpFetcher::new().unwrap(),
+            $token_endpoint,
+            "http://site.test".to_string(),
+        );
+
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(blog_service))
+                .app_data(web::Data::new(settings_service))
+                .app_data(web::Data::new(micropub_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user and get their token.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp.token
+    }};
+}
+
+/// Macro to send a form-encoded Micropub request, returning the response.
+macro_rules! post_form {
+    ($app:expr, $token:expr, $body:expr) => {{
+        test::TestRequest::post()
+            .uri("/api/micropub")
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .insert_header(("Content-Type", "application/x-www-form-urlencoded"))
+            .set_payload($body)
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Macro to send a JSON Micropub request, returning the status code.
+macro_rules! post_json {
+    ($app:expr, $token:expr, $body:expr) => {{
+        let resp = test::TestRequest::post()
+            .uri("/api/micropub")
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json($body)
+            .send_request($app)
+            .await;
+        resp.status().as_u16()
+    }};
+}
+
+/// Macro to fetch the post a `Location` header points to.
+macro_rules! get_created_post {
+    ($app:expr, $resp:expr) => {{
+        let location = $resp
+            .headers()
+            .get("Location")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+        let id = location.strip_prefix("http://site.test/?post=").unwrap();
+        let resp = test::TestRequest::get()
+            .uri(&format!("/api/posts/{id}"))
+            .send_request($app)
+            .await;
+        let post: PostDto = test::read_body_json(resp).await;
+        post
+    }};
+}
+
+/// Stub IndieAuth token endpoint: `good` belongs to alice's site with the
+/// `create` scope, `readonly` lacks it, anything else is rejected.
+async fn token_endpoint(req: HttpRequest) -> HttpResponse {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    match token {
+        Some("good") => HttpResponse::Ok().json(serde_json::json!({
+            "me": "https://Alice.example",
+            "client_id": "https://client.example/",
+            "scope": "create media",
+        })),
+        Some("readonly") => HttpResponse::Ok().json(serde_json::json!({
+            "me": "https://alice.example/",
+            "scope": "read",
+        })),
+        _ => HttpResponse::Unauthorized().finish(),
+    }
+}
+
+/// Test publishing form-encoded notes with this server's own token.
+#[tokio::test]
+async fn test_create_from_form() {
+    let app = init_app!(None);
+    let token = register_user!(&app, "alice");
+
+    let resp = post_form!(
+        &app,
+        token,
+        "h=entry&content=Hello+from+my+phone%0A%0ASecond+line&category%5B%5D=Rust&category%5B%5D=indieweb"
+    );
+    assert_eq!(resp.status(), 201);
+    let post = get_created_post!(&app, resp);
+    // Notes have no name; the first line becomes the title
+    assert_eq!(post.title, "Hello from my phone");
+    assert_eq!(post.content, "Hello from my phone\n\nSecond line");
+    assert_eq!(post.tags, vec!["indieweb", "rust"]);
+
+    let resp = post_form!(&app, token, "h=entry&name=Article&content=Body");
+    assert_eq!(resp.status(), 201);
+    assert_eq!(get_created_post!(&app, resp).title, "Article");
+
+    let resp = post_form!(&app, token, "h=entry&name=Empty");
+    assert_eq!(resp.status(), 400);
+    let resp = post_form!(&app, token, "h=event&content=Party");
+    assert_eq!(resp.status(), 400);
+
+    let resp = post_form!(&app, "not-a-token", "h=entry&content=Hi");
+    assert_eq!(resp.status(), 401);
+}
+
+/// Test publishing JSON entries and rejecting unsupported requests.
+#[tokio::test]
+async fn test_create_from_json() {
+    let app = init_app!(None);
+    let token = register_user!(&app, "alice");
+
+    let entry = serde_json::json!({
+        "type": ["h-entry"],
+        "properties": {
+            "name": ["Weekend notes"],
+            "content": ["Some *Markdown*"],
+            "category": ["notes"],
+        },
+    });
+    assert_eq!(post_json!(&app, token, &entry), 201);
+
+    let html = serde_json::json!({
+        "type": ["h-entry"],
+        "properties": {"content": [{"html": "<p>Hi</p>"}]},
+    });
+    assert_eq!(post_json!(&app, token, &html), 400);
+
+    let delete = serde_json::json!({"action": "delete", "url": "http://site.test/?post=1"});
+    assert_eq!(post_json!(&app, token, &delete), 400);
+
+    let resp = test::TestRequest::get()
+        .uri("/api/micropub?q=config")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 200);
+    let config: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(config["syndicate-to"], serde_json::json!([]));
+
+    let resp = test::TestRequest::get()
+        .uri("/api/micropub?q=config")
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 401);
+}
+
+/// Test IndieAuth tokens: the token's `me` must be a user's website and it
+/// needs the `create` scope.
+#[tokio::test]
+async fn test_indieauth_tokens() {
+    let server = HttpServer::new(|| App::new().route("/token", web::get().to(token_endpoint)))
+        .workers(1)
+        .bind(("127.0.0.1", 0))
+        .unwrap();
+    let endpoint = format!("http://{}/token", server.addrs()[0]);
+    tokio::spawn(server.run());
+
+    let app = init_app!(Some(endpoint));
+    let token = register_user!(&app, "alice");
+
+    // No user has this website yet
+    let resp = post_form!(&app, "good", "h=entry&content=Hi");
+    assert_eq!(resp.status(), 403);
+
+    let profile = ProfileDto {
+        bio: String::new(),
+        avatar_url: None,
+        website: Some("HTTPS://alice.EXAMPLE".to_string()),
+    };
+    let resp = test::TestRequest::put()
+        .uri("/api/settings/profile")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(&profile)
+        .send_request(&app)
+        .await;
+    let saved: ProfileDto = test::read_body_json(resp).await;
+    assert_eq!(saved.website.as_deref(), Some("https://alice.example/"));
+
+    // Another user cannot claim the same website
+    let bob = register_user!(&app, "bob");
+    let resp = test::TestRequest::put()
+        .uri("/api/settings/profile")
+        .insert_header(("Authorization", format!("Bearer {bob}")))
+        .set_json(&profile)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 400);
+
+    let resp = post_form!(&app, "good", "h=entry&content=Posted+with+IndieAuth");
+    assert_eq!(resp.status(), 201);
+    let post = get_created_post!(&app, resp);
+    assert_eq!(post.author_username, "alice");
+
+    // The token may also travel in the form body
+    let resp = test::TestRequest::post()
+        .uri("/api/micropub")
+        .insert_header(("Content-Type", "application/x-www-form-urlencoded"))
+        .set_payload("h=entry&content=Hi&access_token=good")
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 201);
+
+    let resp = post_form!(&app, "readonly", "h=entry&content=Hi");
+    assert_eq!(resp.status(), 403);
+    let resp = post_form!(&app, "revoked", "h=entry&content=Hi");
+    assert_eq!(resp.status(), 401);
+}
@@ -0,0 +1,311 @@
+//! Integration tests for moderation rules and the flagged content queue.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    AuthResponse, ContentKind, CreateCommentRequest, CreateModerationRuleRequest,
+    CreatePostRequest, FlaggedContentListResponse, ModerationRuleDto, ModerationRuleListResponse,
+    PostDto, RegisterRequest, RuleAction, RuleKind, UpdateModerationRuleRequest,
+};
+use sqlx::SqlitePool;
+
+use blog_server::application::{
+    AdminService, AuthService, BlogService, CommentService, ModerationService,
+};
+use blog_server::data::{
+    AdminRepository, CommentRepository, FeatureFlagRepository, ModerationRepository,
+    PostRepository, RecoveryCodeRepository, ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::infrastructure::event_bus::EventBus;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app with the services moderation rules touch.
+macro_rules! init_app {
+    ($pool:expr) => {{
+        let user_repo = Arc::new(UserRepository::new($pool.clone()));
+        let post_repo = Arc::new(PostRepository::new($pool.clone()));
+        let comment_repo = Arc::new(CommentRepository::new($pool.clone()));
+        let flag_repo = Arc::new(FeatureFlagRepository::new($pool.clone()));
+        let admin_repo = Arc::new(AdminRepository::new($pool.clone()));
+        let auth_service = AuthService::new(
+            Arc::clone(&user_repo),
+            Arc::clone(&flag_repo),
+            Arc::new(RecoveryCodeRepository::new($pool.clone())),
+            Arc::new(ServiceAccountRepository::new($pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let moderation_service =
+            ModerationService::new(Arc::new(ModerationRepository::new($pool.clone())));
+        let blog_service = BlogService::new(
+            Arc::clone(&post_repo),
+            EmbedProvider::ALL.to_vec(),
+            moderation_service.clone(),
+        );
+        let comment_service = CommentService::new(
+            Arc::clone(&comment_repo),
+            Arc::clone(&post_repo),
+            Arc::clone(&flag_repo),
+            moderation_service.clone(),
+            EventBus::new(),
+        );
+        let admin_service = AdminService::new(admin_repo, user_repo, comment_repo, flag_repo);
+
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(blog_service))
+                .app_data(web::Data::new(comment_service))
+                .app_data(web::Data::new(admin_service))
+                .app_data(web::Data::new(moderation_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user and get the auth response.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp
+    }};
+}
+
+/// Macro to send an authenticated request with an optional JSON body.
+macro_rules! send {
+    ($app:expr, $method:ident, $uri:expr, $token:expr) => {{
+        test::TestRequest::$method()
+            .uri($uri)
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .send_request($app)
+            .await
+    }};
+    ($app:expr, $method:ident, $uri:expr, $token:expr, $body:expr) => {{
+        test::TestRequest::$method()
+            .uri($uri)
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json($body)
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Sets a user's role directly in the database.
+async fn set_role(pool: &SqlitePool, username: &str, role: &str) {
+    sqlx::query("UPDATE users SET role = ? WHERE username = ?")
+        .bind(role)
+        .bind(username)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+/// Returns a rule creation request.
+fn rule_request(
+    name: &str,
+    kind: RuleKind,
+    pattern: &str,
+    action: RuleAction,
+) -> CreateModerationRuleRequest {
+    CreateModerationRuleRequest {
+        name: name.to_string(),
+        kind,
+        pattern: pattern.to_string(),
+        action,
+        enabled: true,
+    }
+}
+
+/// Returns a post creation request.
+fn post_request(title: &str, content: &str) -> CreatePostRequest {
+    CreatePostRequest {
+        title: title.to_string(),
+        content: content.to_string(),
+        tags: Vec::new(),
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
+    }
+}
+
+/// Test that blocking rules reject content and flagging rules queue it, with hit metrics.
+#[tokio::test]
+async fn test_rules_block_and_flag_content() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let admin = register_user!(&app, "admin");
+    let moderator = register_user!(&app, "moderator");
+    let author = register_user!(&app, "author");
+    set_role(&pool, "admin", "admin").await;
+    set_role(&pool, "moderator", "moderator").await;
+
+    let resp = send!(
+        &app,
+        post,
+        "/api/admin/rules",
+        admin.token,
+        &rule_request(
+            "Gambling spam",
+            RuleKind::Keyword,
+            "casino,\nfree money",
+            RuleAction::Block
+        )
+    );
+    assert_eq!(resp.status(), 201);
+    let block: ModerationRuleDto = test::read_body_json(resp).await;
+    let resp = send!(
+        &app,
+        post,
+        "/api/admin/rules",
+        admin.token,
+        &rule_request(
+            "Sales pitch",
+            RuleKind::Regex,
+            r"(?i)buy\s+now",
+            RuleAction::Flag
+        )
+    );
+    assert_eq!(resp.status(), 201);
+    let flag: ModerationRuleDto = test::read_body_json(resp).await;
+
+    // Keywords match case-insensitively as whole words
+    let resp = send!(
+        &app,
+        post,
+        "/api/posts",
+        author.token,
+        &post_request("Offer", "Get FREE MONEY today")
+    );
+    assert_eq!(resp.status(), 422);
+    let resp = send!(
+        &app,
+        post,
+        "/api/posts",
+        author.token,
+        &post_request("Casinos", "A history of casinos")
+    );
+    assert_eq!(resp.status(), 201);
+    let post: PostDto = test::read_body_json(resp).await;
+
+    // Flagged comments are published and queued for review
+    let comments_uri = format!("/api/posts/{}/comments", post.public_id);
+    let comment = CreateCommentRequest {
+        content: "Buy   now, while it lasts".to_string(),
+    };
+    let resp = send!(&app, post, &comments_uri, author.token, &comment);
+    assert_eq!(resp.status(), 201);
+
+    let resp = send!(&app, get, "/api/admin/moderation/flagged", moderator.token);
+    assert_eq!(resp.status(), 200);
+    let flagged: FlaggedContentListResponse = test::read_body_json(resp).await;
+    assert_eq!(flagged.total, 1);
+    let item = &flagged.items[0];
+    assert_eq!(item.kind, ContentKind::Comment);
+    assert_eq!(item.post_public_id, post.public_id);
+    assert_eq!(item.rule_id, flag.id);
+    assert_eq!(item.author_username, "author");
+    assert_eq!(item.excerpt, "Buy   now, while it lasts");
+
+    // Every rule counted three checks
+    let rules: ModerationRuleListResponse =
+        test::read_body_json(send!(&app, get, "/api/admin/rules", admin.token)).await;
+    assert_eq!(rules.rules.len(), 2);
+    for rule in &rules.rules {
+        assert_eq!(rule.checked_count, 3);
+        assert_eq!(rule.hit_count, 1);
+        assert!((rule.hit_rate - 1.0 / 3.0).abs() < f64::EPSILON);
+        assert!(rule.last_hit_at.is_some());
+    }
+    assert_eq!(rules.rules[0].id, block.id);
+
+    // Dismissing a flag leaves the comment in place
+    let uri = format!("/api/admin/moderation/flagged/{}", item.id);
+    assert_eq!(send!(&app, delete, &uri, moderator.token).status(), 204);
+    assert_eq!(send!(&app, delete, &uri, moderator.token).status(), 404);
+    let flagged: FlaggedContentListResponse = test::read_body_json(send!(
+        &app,
+        get,
+        "/api/admin/moderation/flagged",
+        moderator.token
+    ))
+    .await;
+    assert_eq!(flagged.total, 0);
+}
+
+/// Test rule management: validation, admin-only access, disabling, and deletion.
+#[tokio::test]
+async fn test_rule_management() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let admin = register_user!(&app, "admin");
+    let moderator = register_user!(&app, "moderator");
+    let author = register_user!(&app, "author");
+    set_role(&pool, "admin", "admin").await;
+    set_role(&pool, "moderator", "moderator").await;
+
+    let rule = rule_request("Spam", RuleKind::Keyword, "spam", RuleAction::Block);
+    let resp = send!(&app, post, "/api/admin/rules", moderator.token, &rule);
+    assert_eq!(resp.status(), 403);
+    let resp = send!(&app, get, "/api/admin/rules", moderator.token);
+    assert_eq!(resp.status(), 403);
+
+    let invalid = rule_request("Broken", RuleKind::Regex, "(unclosed", RuleAction::Flag);
+    let resp = send!(&app, post, "/api/admin/rules", admin.token, &invalid);
+    assert_eq!(resp.status(), 400);
+    let empty = rule_request("Empty", RuleKind::Keyword, " , ", RuleAction::Flag);
+    let resp = send!(&app, post, "/api/admin/rules", admin.token, &empty);
+    assert_eq!(resp.status(), 400);
+
+    let created: ModerationRuleDto =
+        test::read_body_json(send!(&app, post, "/api/admin/rules", admin.token, &rule)).await;
+    let post = post_request("Hello", "no spam here");
+    let resp = send!(&app, post, "/api/posts", author.token, &post);
+    assert_eq!(resp.status(), 422);
+
+    // Disabled rules no longer apply
+    let uri = format!("/api/admin/rules/{}", created.id);
+    let update = UpdateModerationRuleRequest {
+        enabled: Some(false),
+        ..Default::default()
+    };
+    let resp = send!(&app, put, &uri, admin.token, &update);
+    assert_eq!(resp.status(), 200);
+    let updated: ModerationRuleDto = test::read_body_json(resp).await;
+    assert!(!updated.enabled);
+    assert_eq!(updated.pattern, "spam");
+    let resp = send!(&app, post, "/api/posts", author.token, &post);
+    assert_eq!(resp.status(), 201);
+
+    let invalid = UpdateModerationRuleRequest {
+        kind: Some(RuleKind::Regex),
+        pattern: Some("[".to_string()),
+        ..Default::default()
+    };
+    let resp = send!(&app, put, &uri, admin.token, &invalid);
+    assert_eq!(resp.status(), 400);
+
+    assert_eq!(send!(&app, delete, &uri, admin.token).status(), 204);
+    assert_eq!(send!(&app, delete, &uri, admin.token).status(), 404);
+    let rules: ModerationRuleListResponse =
+        test::read_body_json(send!(&app, get, "/api/admin/rules", admin.token)).await;
+    assert!(rules.rules.is_empty());
+}
@@ -0,0 +1,182 @@
+//! Integration tests for MessagePack content negotiation.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::http::header;
+use actix_web::middleware::from_fn;
+use actix_web::{App, test, web};
+use blog_shared::constants::MSGPACK_CONTENT_TYPE;
+use blog_shared::{AuthResponse, CreatePostRequest, ErrorResponse, PostDto, RegisterRequest};
+
+use blog_server::application::{AuthService, BlogService, ModerationService};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::presentation::http_handlers::api_routes;
+use blog_server::presentation::msgpack::msgpack_negotiation;
+use blog_server::presentation::payload::json_config;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app with the MessagePack middleware.
+macro_rules! init_app {
+    ($pool:expr) => {{
+        let auth_service = AuthService::new(
+            Arc::new(UserRepository::new($pool.clone())),
+            Arc::new(FeatureFlagRepository::new($pool.clone())),
+            Arc::new(RecoveryCodeRepository::new($pool.clone())),
+            Arc::new(ServiceAccountRepository::new($pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let moderation_service =
+            ModerationService::new(Arc::new(ModerationRepository::new($pool.clone())));
+        let blog_service = BlogService::new(
+            Arc::new(PostRepository::new($pool.clone())),
+            EmbedProvider::ALL.to_vec(),
+            moderation_service,
+        );
+
+        test::init_service(
+            App::new()
+                .wrap(from_fn(msgpack_negotiation))
+                .app_data(json_config())
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(blog_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Registers a user with a MessagePack body, returning their token.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+        let req = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .insert_header((header::CONTENT_TYPE, MSGPACK_CONTENT_TYPE))
+            .set_payload(rmp_serde::to_vec_named(&req).unwrap())
+            .to_request();
+        let auth: AuthResponse = test::call_and_read_body_json($app, req).await;
+        auth.token
+    }};
+}
+
+/// Test a MessagePack request body is accepted and a MessagePack response
+/// returned when asked for.
+#[tokio::test]
+async fn test_msgpack_round_trip() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let token = register_user!(&app, "alice");
+
+    let create = CreatePostRequest {
+        title: "Packed".to_string(),
+        content: "A long post body".to_string(),
+        tags: vec!["rust".to_string()],
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
+    };
+    let req = test::TestRequest::post()
+        .uri("/api/posts")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .insert_header((header::CONTENT_TYPE, MSGPACK_CONTENT_TYPE))
+        .insert_header((header::ACCEPT, MSGPACK_CONTENT_TYPE))
+        .set_payload(rmp_serde::to_vec_named(&create).unwrap())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), 201);
+    assert_eq!(
+        resp.headers().get(header::CONTENT_TYPE).unwrap(),
+        MSGPACK_CONTENT_TYPE
+    );
+    let post: PostDto = rmp_serde::from_slice(&test::read_body(resp).await).unwrap();
+    assert_eq!(post.title, "Packed");
+    assert_eq!(post.tags, vec!["rust"]);
+    assert!(post.is_owner);
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/posts/{}", post.public_id))
+        .insert_header((header::ACCEPT, MSGPACK_CONTENT_TYPE))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), 200);
+    assert!(resp.headers().get(header::ETAG).is_none());
+    assert!(
+        resp.headers()
+            .get_all(header::VARY)
+            .any(|value| value == "Accept")
+    );
+    let fetched: PostDto = rmp_serde::from_slice(&test::read_body(resp).await).unwrap();
+    assert_eq!(fetched.public_id, post.public_id);
+    assert_eq!(fetched.created_at, post.created_at);
+}
+
+/// Test responses stay JSON without `Accept: application/msgpack`.
+#[tokio::test]
+async fn test_json_by_default() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+
+    let req = test::TestRequest::get().uri("/api/posts").to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(
+        resp.headers().get(header::CONTENT_TYPE).unwrap(),
+        "application/json"
+    );
+    assert!(
+        resp.headers()
+            .get_all(header::VARY)
+            .any(|value| value == "Accept")
+    );
+}
+
+/// Test errors are JSON even when MessagePack is accepted.
+#[tokio::test]
+async fn test_errors_stay_json() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+
+    let req = test::TestRequest::get()
+        .uri("/api/posts/999")
+        .insert_header((header::ACCEPT, MSGPACK_CONTENT_TYPE))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), 404);
+    let error: ErrorResponse = test::read_body_json(resp).await;
+    assert_eq!(error.code.as_deref(), Some("post_not_found"));
+}
+
+/// Test a malformed MessagePack body is an `invalid_request`.
+#[tokio::test]
+async fn test_invalid_msgpack_body() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+
+    let req = test::TestRequest::post()
+        .uri("/api/auth/login")
+        .insert_header((header::CONTENT_TYPE, MSGPACK_CONTENT_TYPE))
+        .set_payload(vec![0xc1])
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), 400);
+    let error: ErrorResponse = test::read_body_json(resp).await;
+    assert_eq!(error.code.as_deref(), Some("invalid_request"));
+}
@@ -0,0 +1,288 @@
+//! Integration tests for newsletter subscriptions and the weekly digest.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    AuthResponse, CreatePostRequest, RegisterRequest, SubscribeRequest, SubscriptionStatus,
+};
+
+use blog_server::application::{AuthService, BlogService, ModerationService, NewsletterService};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, NewsletterRepository, PostRepository,
+    RecoveryCodeRepository, ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::{DateFormat, DigestPost, EmbedProvider, render_digest};
+use blog_server::infrastructure::config::MailConfig;
+use blog_server::infrastructure::mailer::Mailer;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app; also returns the newsletter service and repository.
+///
+/// No SMTP server is configured, so emails are only logged.
+macro_rules! init_app {
+    () => {{
+        let pool = setup_test_db().await;
+        let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+        let user_repo = Arc::new(UserRepository::new(pool.clone()));
+        let post_repo = Arc::new(PostRepository::new(pool.clone()));
+        let newsletter_repo = Arc::new(NewsletterRepository::new(pool.clone()));
+        let auth_service = AuthService::new(
+            user_repo,
+            flag_repo,
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let moderation_service =
+            ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+        let blog_service = BlogService::new(
+            post_repo,
+            EmbedProvider::ALL.to_vec(),
+            moderation_service.clone(),
+        );
+        let newsletter_service = NewsletterService::new(
+            Arc::clone(&newsletter_repo),
+            Mailer::new(&MailConfig::default()).unwrap(),
+            "http://api.test".to_string(),
+            "http://site.test".to_string(),
+        );
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(blog_service))
+                .app_data(web::Data::new(newsletter_service.clone()))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await;
+        (app, newsletter_service, newsletter_repo)
+    }};
+}
+
+/// Macro to subscribe an email address, returning the response status code.
+macro_rules! subscribe {
+    ($app:expr, $email:expr) => {{
+        let req = SubscribeRequest {
+            email: $email.to_string(),
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/newsletter/subscribe")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        resp.status().as_u16()
+    }};
+}
+
+/// Macro to follow a newsletter link, returning the status code and body.
+macro_rules! follow {
+    ($app:expr, $method:ident, $action:expr, $token:expr) => {{
+        let resp = test::TestRequest::$method()
+            .uri(&format!("/api/newsletter/{}?token={}", $action, $token))
+            .send_request($app)
+            .await;
+        let status = resp.status().as_u16();
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        (status, body)
+    }};
+}
+
+/// Macro to register a user and publish a post as them.
+macro_rules! publish_post {
+    ($app:expr, $username:expr, $title:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        let auth: AuthResponse = test::read_body_json(resp).await;
+
+        let req = CreatePostRequest {
+            title: $title.to_string(),
+            content: "Body".to_string(),
+            tags: vec![],
+            lang: None,
+            canonical_url: None,
+            original_source: None,
+            org: None,
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/posts")
+            .insert_header(("Authorization", format!("Bearer {}", auth.token)))
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        assert_eq!(resp.status(), 201);
+    }};
+}
+
+/// Test double opt-in: subscribe, confirm, unsubscribe and re-subscribe.
+#[tokio::test]
+async fn test_subscription_lifecycle() {
+    let (app, _, repo) = init_app!();
+
+    assert_eq!(subscribe!(&app, "  Reader@Example.com "), 202);
+    let pending = repo
+        .find_by_email("reader@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(pending.status(), SubscriptionStatus::Pending);
+
+    let (status, body) = follow!(&app, get, "confirm", pending.token);
+    assert_eq!(status, 200);
+    assert_eq!(body["status"], "confirmed");
+
+    // Confirming twice is harmless
+    let (status, body) = follow!(&app, get, "confirm", pending.token);
+    assert_eq!(status, 200);
+    assert_eq!(body["status"], "confirmed");
+
+    // Subscribing a confirmed address looks the same but changes nothing
+    assert_eq!(subscribe!(&app, "reader@example.com"), 202);
+    let confirmed = repo
+        .find_by_email("reader@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(confirmed.token, pending.token);
+    assert_eq!(confirmed.status(), SubscriptionStatus::Confirmed);
+
+    // One-click unsubscribe from a mail client
+    let (status, body) = follow!(&app, post, "unsubscribe", confirmed.token);
+    assert_eq!(status, 200);
+    assert_eq!(body["status"], "unsubscribed");
+
+    // The old confirmation link does not subscribe again
+    let (_, body) = follow!(&app, get, "confirm", confirmed.token);
+    assert_eq!(body["status"], "unsubscribed");
+
+    // Re-subscribing starts over with a new token
+    assert_eq!(subscribe!(&app, "reader@example.com"), 202);
+    let again = repo
+        .find_by_email("reader@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(again.status(), SubscriptionStatus::Pending);
+    assert_ne!(again.token, confirmed.token);
+    let (status, _) = follow!(&app, get, "unsubscribe", confirmed.token);
+    assert_eq!(status, 404);
+}
+
+/// Test that malformed addresses and unknown tokens are rejected.
+#[tokio::test]
+async fn test_subscription_validation() {
+    let (app, _, _) = init_app!();
+
+    assert_eq!(subscribe!(&app, "not-an-email"), 400);
+    assert_eq!(subscribe!(&app, ""), 400);
+    assert_eq!(
+        subscribe!(&app, format!("{}@example.com", "a".repeat(250))),
+        400
+    );
+
+    let (status, _) = follow!(&app, get, "confirm", "unknown");
+    assert_eq!(status, 404);
+    let (status, _) = follow!(&app, get, "unsubscribe", "unknown");
+    assert_eq!(status, 404);
+}
+
+/// Test that the digest goes once a week to confirmed subscribers only.
+#[tokio::test]
+async fn test_weekly_digest() {
+    let (app, service, repo) = init_app!();
+
+    for email in ["one@example.com", "two@example.com", "pending@example.com"] {
+        assert_eq!(subscribe!(&app, email), 202);
+    }
+    for email in ["one@example.com", "two@example.com"] {
+        let subscription = repo.find_by_email(email).await.unwrap().unwrap();
+        follow!(&app, get, "confirm", subscription.token);
+    }
+
+    // Nothing to send without posts
+    assert_eq!(service.send_digests().await.unwrap(), 0);
+
+    publish_post!(&app, "author", "Hello world");
+    assert_eq!(service.send_digests().await.unwrap(), 2);
+
+    let one = repo
+        .find_by_email("one@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(one.last_digest_at.is_some());
+    let pending = repo
+        .find_by_email("pending@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(pending.last_digest_at.is_none());
+
+    // Not due again until next week
+    assert_eq!(service.send_digests().await.unwrap(), 0);
+
+    // Unsubscribed addresses are skipped
+    let (status, _) = follow!(&app, get, "unsubscribe", one.token);
+    assert_eq!(status, 200);
+    let due = repo
+        .list_due(chrono::Utc::now() + chrono::Duration::days(1))
+        .await
+        .unwrap();
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].email, "two@example.com");
+}
+
+/// Test the digest template: links, escaping, dates and the unsubscribe footer.
+#[tokio::test]
+async fn test_render_digest() {
+    let posts = vec![DigestPost {
+        public_id: "7f3c".to_string(),
+        title: "Tips & <tricks>".to_string(),
+        content: "First line\n\nsecond line".to_string(),
+        author_username: "alice".to_string(),
+        comment_count: 3,
+        created_at: "2025-06-01T12:00:00Z".parse().unwrap(),
+    }];
+
+    let body = render_digest(
+        &posts,
+        |public_id| format!("http://site.test/?post={public_id}"),
+        "http://api.test/api/newsletter/unsubscribe?token=abc",
+        &DateFormat::new("Europe/Moscow", "ru-RU"),
+    );
+
+    assert!(body.text.contains("Tips & <tricks>"));
+    assert!(body.text.contains("First line second line"));
+    assert!(body.text.contains("http://site.test/?post=7f3c"));
+    assert!(body.text.contains("Unsubscribe: http://api.test"));
+    assert!(body.html.contains("Tips &amp; &lt;tricks&gt;"));
+    assert!(
+        body.text
+            .contains("by alice · 01.06.2025, 15:00 MSK · 3 comments")
+    );
+    assert!(
+        body.html
+            .contains("by alice · 01.06.2025, 15:00 MSK · 3 comments")
+    );
+    assert!(
+        body.html
+            .contains("href=\"http://api.test/api/newsletter/unsubscribe?token=abc\"")
+    );
+
+    let body = render_digest(&posts, str::to_string, "", &DateFormat::default());
+    assert!(body.text.contains("Jun 1, 2025, 12:00 PM UTC"));
+}
@@ -0,0 +1,302 @@
+//! Integration tests for cursor-paged comments and notifications.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    AuthResponse, CommentDto, CommentListResponse, CreateCommentRequest, CreatePostRequest,
+    MarkNotificationsReadRequest, NotificationKind, NotificationListResponse, PostDto,
+    RegisterRequest, UnreadCountResponse,
+};
+
+use blog_server::application::{
+    AuthService, BlogService, CommentService, ModerationService, NotificationService,
+};
+use blog_server::data::{
+    CommentRepository, FeatureFlagRepository, ModerationRepository, NotificationRepository,
+    PostRepository, RecoveryCodeRepository, ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::infrastructure::event_bus::EventBus;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app with comments that notify post authors.
+macro_rules! init_app {
+    () => {{
+        let pool = setup_test_db().await;
+        let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+        let user_repo = Arc::new(UserRepository::new(pool.clone()));
+        let post_repo = Arc::new(PostRepository::new(pool.clone()));
+        let auth_service = AuthService::new(
+            Arc::clone(&user_repo),
+            Arc::clone(&flag_repo),
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let moderation_service =
+            ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+        let blog_service = BlogService::new(
+            Arc::clone(&post_repo),
+            EmbedProvider::ALL.to_vec(),
+            moderation_service.clone(),
+        );
+        let notification_service =
+            NotificationService::new(Arc::new(NotificationRepository::new(pool.clone())));
+        let comment_service = CommentService::new(
+            Arc::new(CommentRepository::new(pool.clone())),
+            Arc::clone(&post_repo),
+            flag_repo,
+            moderation_service,
+            EventBus::new(),
+        )
+        .with_notifications(notification_service.clone());
+
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(blog_service))
+                .app_data(web::Data::new(comment_service))
+                .app_data(web::Data::new(notification_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user and get their token.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp.token
+    }};
+}
+
+/// Macro to create a post and return it.
+macro_rules! create_post {
+    ($app:expr, $token:expr) => {{
+        let req = CreatePostRequest {
+            title: "Busy Post".to_string(),
+            content: "Content".to_string(),
+            tags: Vec::new(),
+            lang: None,
+            canonical_url: None,
+            original_source: None,
+            org: None,
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/posts")
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        let post: PostDto = test::read_body_json(resp).await;
+        post
+    }};
+}
+
+/// Macro to add a comment and return the response.
+macro_rules! add_comment {
+    ($app:expr, $token:expr, $post_id:expr, $content:expr) => {{
+        let resp = test::TestRequest::post()
+            .uri(&format!("/api/posts/{}/comments", $post_id))
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json(&CreateCommentRequest {
+                content: $content.to_string(),
+            })
+            .send_request($app)
+            .await;
+        assert_eq!(resp.status(), 201);
+        resp
+    }};
+}
+
+/// Macro to GET a URI as a user and return the response.
+macro_rules! get_as {
+    ($app:expr, $token:expr, $uri:expr) => {{
+        test::TestRequest::get()
+            .uri($uri.as_ref())
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Test following `next_cursor` visits every comment once, in order.
+#[tokio::test]
+async fn test_comments_cursor_pages() {
+    let app = init_app!();
+    let author = register_user!(&app, "author");
+    let post = create_post!(&app, author);
+    for i in 0..5 {
+        add_comment!(&app, author, post.public_id, format!("Comment {i}"));
+    }
+
+    let mut contents = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let uri = match &cursor {
+            Some(cursor) => format!(
+                "/api/posts/{}/comments?limit=2&cursor={cursor}",
+                post.public_id
+            ),
+            None => format!("/api/posts/{}/comments?limit=2", post.public_id),
+        };
+        let resp = test::TestRequest::get().uri(&uri).send_request(&app).await;
+        assert_eq!(resp.status(), 200);
+        let page: CommentListResponse = test::read_body_json(resp).await;
+        // Only the first page is counted
+        let total = cursor.is_none().then_some(5);
+        assert_eq!(page.total, total);
+        contents.extend(page.comments.into_iter().map(|c| c.content));
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    let expected: Vec<String> = (0..5).map(|i| format!("Comment {i}")).collect();
+    assert_eq!(contents, expected);
+
+    // Offsets keep working for older clients
+    let resp = test::TestRequest::get()
+        .uri(&format!(
+            "/api/posts/{}/comments?limit=2&offset=4",
+            post.public_id
+        ))
+        .send_request(&app)
+        .await;
+    let page: CommentListResponse = test::read_body_json(resp).await;
+    assert_eq!(page.comments.len(), 1);
+    assert_eq!(page.comments[0].content, "Comment 4");
+    assert_eq!(page.total, Some(5));
+    assert!(page.next_cursor.is_none());
+}
+
+/// Test a malformed cursor is rejected.
+#[tokio::test]
+async fn test_comments_invalid_cursor() {
+    let app = init_app!();
+    let author = register_user!(&app, "author");
+    let post = create_post!(&app, author);
+
+    let resp = test::TestRequest::get()
+        .uri(&format!(
+            "/api/posts/{}/comments?cursor=not-a-cursor",
+            post.public_id
+        ))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 400);
+}
+
+/// Test post authors are notified of others' comments, newest first.
+#[tokio::test]
+async fn test_comment_notifications() {
+    let app = init_app!();
+    let author = register_user!(&app, "author");
+    let reader = register_user!(&app, "reader");
+    let post = create_post!(&app, author);
+
+    add_comment!(&app, reader, post.public_id, "First");
+    let latest: CommentDto =
+        test::read_body_json(add_comment!(&app, reader, post.public_id, "Second")).await;
+    add_comment!(&app, author, post.public_id, "Own comments notify nobody");
+
+    let resp = get_as!(&app, author, "/api/me/notifications/unread");
+    let count: UnreadCountResponse = test::read_body_json(resp).await;
+    assert_eq!(count.unread, 2);
+
+    let resp = get_as!(&app, author, "/api/me/notifications?limit=1");
+    assert_eq!(resp.status(), 200);
+    let first: NotificationListResponse = test::read_body_json(resp).await;
+    assert_eq!(first.notifications.len(), 1);
+    let newest = &first.notifications[0];
+    assert_eq!(newest.kind, NotificationKind::Comment);
+    assert_eq!(newest.actor_username, "reader");
+    assert_eq!(newest.comment_id, Some(latest.id));
+    assert!(!newest.read);
+
+    let cursor = first.next_cursor.clone().unwrap();
+    let resp = get_as!(
+        &app,
+        author,
+        format!("/api/me/notifications?limit=1&cursor={cursor}")
+    );
+    let second: NotificationListResponse = test::read_body_json(resp).await;
+    assert_eq!(second.notifications.len(), 1);
+    assert!(second.notifications[0].id < newest.id);
+    assert!(second.next_cursor.is_none());
+
+    let resp = get_as!(&app, reader, "/api/me/notifications");
+    let none: NotificationListResponse = test::read_body_json(resp).await;
+    assert!(none.notifications.is_empty());
+}
+
+/// Test marking notifications read, up to an ID and then all of them.
+#[tokio::test]
+async fn test_mark_notifications_read() {
+    let app = init_app!();
+    let author = register_user!(&app, "author");
+    let reader = register_user!(&app, "reader");
+    let post = create_post!(&app, author);
+    add_comment!(&app, reader, post.public_id, "First");
+    add_comment!(&app, reader, post.public_id, "Second");
+
+    let resp = get_as!(&app, author, "/api/me/notifications");
+    let list: NotificationListResponse = test::read_body_json(resp).await;
+    let oldest_id = list.notifications[1].id;
+
+    let resp = test::TestRequest::post()
+        .uri("/api/me/notifications/read")
+        .insert_header(("Authorization", format!("Bearer {}", author)))
+        .set_json(&MarkNotificationsReadRequest {
+            up_to_id: Some(oldest_id),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 204);
+
+    let resp = get_as!(&app, author, "/api/me/notifications/unread");
+    let count: UnreadCountResponse = test::read_body_json(resp).await;
+    assert_eq!(count.unread, 1);
+
+    let resp = test::TestRequest::post()
+        .uri("/api/me/notifications/read")
+        .insert_header(("Authorization", format!("Bearer {}", author)))
+        .set_json(MarkNotificationsReadRequest::default())
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 204);
+
+    let resp = get_as!(&app, author, "/api/me/notifications");
+    let list: NotificationListResponse = test::read_body_json(resp).await;
+    assert!(list.notifications.iter().all(|n| n.read));
+}
+
+/// Test notifications require authentication.
+#[tokio::test]
+async fn test_notifications_require_auth() {
+    let app = init_app!();
+
+    let resp = test::TestRequest::get()
+        .uri("/api/me/notifications")
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 401);
+}
@@ -0,0 +1,231 @@
+//! Integration tests for organizations and posting as them.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    CreatePostRequest, MembershipListResponse, OrgProfileResponse, OrgRole, PostAuthor, PostDto,
+    RegisterRequest,
+};
+use serde_json::json;
+use sqlx::SqlitePool;
+
+use blog_server::application::{AuthService, BlogService, ModerationService, OrgService};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, OrgRepository, PostRepository,
+    RecoveryCodeRepository, ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Builds the auth, blog, and organization services on a test database.
+fn services(pool: &SqlitePool) -> (AuthService, BlogService, OrgService) {
+    let user_repo = Arc::new(UserRepository::new(pool.clone()));
+    let org_repo = Arc::new(OrgRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        Arc::new(FeatureFlagRepository::new(pool.clone())),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let blog_service = BlogService::new(
+        Arc::new(PostRepository::new(pool.clone())),
+        EmbedProvider::ALL.to_vec(),
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone()))),
+    )
+    .with_orgs(Arc::clone(&org_repo));
+    let org_service = OrgService::new(org_repo, user_repo, blog_service.clone());
+    (auth_service, blog_service, org_service)
+}
+
+/// Registers a user, returning their ID and token.
+async fn register(auth_service: &AuthService, username: &str) -> (i64, String) {
+    let req = RegisterRequest {
+        username: username.to_string(),
+        email: format!("{username}@example.com"),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    let auth = auth_service.register(req, None).await.unwrap();
+    (auth.user.id, auth.token)
+}
+
+/// Returns a request for a post published as `org`.
+fn post_request(title: &str, org: Option<&str>) -> CreatePostRequest {
+    CreatePostRequest {
+        title: title.to_string(),
+        content: "Content".to_string(),
+        tags: Vec::new(),
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: org.map(str::to_string),
+    }
+}
+
+/// Test members posting as an organization, editing and deleting its posts
+/// by role, and the organization's profile page.
+#[tokio::test]
+async fn test_members_post_as_org() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service, org_service) = services(&pool);
+    let (_, owner_token) = register(&auth_service, "alice").await;
+    let (_, writer_token) = register(&auth_service, "bob").await;
+    let (_, other_writer_token) = register(&auth_service, "carol").await;
+    let (_, outsider_token) = register(&auth_service, "mallory").await;
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(blog_service))
+            .app_data(web::Data::new(org_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+    let bearer = |token: &str| ("Authorization", format!("Bearer {token}"));
+
+    let req = test::TestRequest::post()
+        .uri("/api/orgs")
+        .insert_header(bearer(&owner_token))
+        .set_json(json!({"slug": "Acme", "name": "Acme Inc", "bio": "We make things"}))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 201);
+    let req = test::TestRequest::post()
+        .uri("/api/orgs")
+        .insert_header(bearer(&outsider_token))
+        .set_json(json!({"slug": "acme", "name": "Not Acme"}))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 400);
+
+    for (username, token) in [("bob", &owner_token), ("carol", &owner_token)] {
+        let req = test::TestRequest::put()
+            .uri(&format!("/api/orgs/acme/members/{username}"))
+            .insert_header(bearer(token))
+            .set_json(json!({"role": "writer"}))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 204);
+    }
+    // Writers cannot manage members
+    let req = test::TestRequest::put()
+        .uri("/api/orgs/acme/members/mallory")
+        .insert_header(bearer(&writer_token))
+        .set_json(json!({"role": "writer"}))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 403);
+
+    let req = test::TestRequest::post()
+        .uri("/api/posts")
+        .insert_header(bearer(&outsider_token))
+        .set_json(post_request("Hijack", Some("acme")))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 403);
+    let req = test::TestRequest::post()
+        .uri("/api/posts")
+        .insert_header(bearer(&writer_token))
+        .set_json(post_request("Launch", Some("acme")))
+        .to_request();
+    let post: PostDto = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(post.author_username, "bob");
+    assert!(matches!(
+        post.author,
+        Some(PostAuthor::Org { ref slug, ref name, .. }) if slug == "acme" && name == "Acme Inc"
+    ));
+    assert_eq!(post.byline(), "Acme Inc");
+
+    let update = |token: &str| {
+        test::TestRequest::put()
+            .uri(&format!("/api/posts/{}", post.public_id))
+            .insert_header(bearer(token))
+            .set_json(json!({"title": "Launch day"}))
+            .to_request()
+    };
+    let resp = test::call_service(&app, update(&outsider_token)).await;
+    assert_eq!(resp.status(), 403);
+    let resp = test::call_service(&app, update(&other_writer_token)).await;
+    assert_eq!(resp.status(), 200);
+
+    let req = test::TestRequest::get().uri("/api/orgs/ACME").to_request();
+    let profile: OrgProfileResponse = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(profile.org.bio.as_deref(), Some("We make things"));
+    let members: Vec<_> = profile
+        .members
+        .iter()
+        .map(|member| (member.username.as_str(), member.role))
+        .collect();
+    assert_eq!(
+        members,
+        [
+            ("alice", OrgRole::Owner),
+            ("bob", OrgRole::Writer),
+            ("carol", OrgRole::Writer)
+        ]
+    );
+    assert_eq!(profile.total, 1);
+    assert_eq!(profile.posts[0].title, "Launch day");
+
+    let req = test::TestRequest::get()
+        .uri("/api/me/orgs")
+        .insert_header(bearer(&writer_token))
+        .to_request();
+    let memberships: MembershipListResponse = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(memberships.orgs.len(), 1);
+    assert_eq!(memberships.orgs[0].org.slug, "acme");
+    assert_eq!(memberships.orgs[0].role, OrgRole::Writer);
+
+    let delete = |token: &str| {
+        test::TestRequest::delete()
+            .uri(&format!("/api/posts/{}", post.public_id))
+            .insert_header(bearer(token))
+            .to_request()
+    };
+    let resp = test::call_service(&app, delete(&other_writer_token)).await;
+    assert_eq!(resp.status(), 403);
+    let resp = test::call_service(&app, delete(&owner_token)).await;
+    assert_eq!(resp.status(), 204);
+}
+
+/// Test that an organization always keeps an owner.
+#[tokio::test]
+async fn test_last_owner_cannot_leave() {
+    let pool = setup_test_db().await;
+    let (auth_service, _, org_service) = services(&pool);
+    let (owner_id, _) = register(&auth_service, "alice").await;
+    register(&auth_service, "bob").await;
+    org_service
+        .create_org(
+            owner_id,
+            serde_json::from_value(json!({"slug": "acme", "name": "Acme"})).unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert!(
+        org_service
+            .remove_member("acme", owner_id, "alice")
+            .await
+            .is_err()
+    );
+    assert!(
+        org_service
+            .set_member("acme", owner_id, "alice", OrgRole::Writer)
+            .await
+            .is_err()
+    );
+
+    org_service
+        .set_member("acme", owner_id, "bob", OrgRole::Owner)
+        .await
+        .unwrap();
+    org_service
+        .remove_member("acme", owner_id, "alice")
+        .await
+        .unwrap();
+    let memberships = org_service.list_memberships(owner_id).await.unwrap();
+    assert!(memberships.orgs.is_empty());
+}
@@ -0,0 +1,157 @@
+//! Integration tests for turning panics in handlers into 500s and gRPC
+//! `INTERNAL` errors.
+
+use std::convert::Infallible;
+use std::future::Ready;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use actix_web::body::to_bytes;
+use actix_web::middleware::from_fn;
+use actix_web::{App, HttpResponse, http::StatusCode, test, web};
+use async_trait::async_trait;
+use blog_shared::ErrorResponse;
+use blog_shared::constants::CORS_ALLOWED_ORIGINS;
+use tonic::codegen::http;
+use tower::{Layer, Service};
+
+use blog_server::application::TelemetryService;
+use blog_server::domain::{AppError, ErrorReport, ErrorReporter, ErrorSource};
+use blog_server::presentation::panics::{CatchPanicLayer, catch_panics};
+use blog_server::presentation::request_id::request_id;
+use blog_server::presentation::telemetry::report_server_errors;
+
+/// Reporter keeping reports in memory.
+#[derive(Default)]
+struct RecordingReporter {
+    reports: Mutex<Vec<ErrorReport>>,
+}
+
+#[async_trait]
+impl ErrorReporter for RecordingReporter {
+    async fn report(&self, report: &ErrorReport) -> Result<(), AppError> {
+        self.reports.lock().unwrap().push(report.clone());
+        Ok(())
+    }
+}
+
+impl RecordingReporter {
+    /// Waits for the background deliveries, then returns the reports.
+    async fn reports(&self, expected: usize) -> Vec<ErrorReport> {
+        for _ in 0..100 {
+            if self.reports.lock().unwrap().len() >= expected {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        self.reports.lock().unwrap().clone()
+    }
+}
+
+async fn panicking() -> HttpResponse {
+    panic!("handler exploded");
+}
+
+async fn healthy() -> HttpResponse {
+    HttpResponse::Ok().finish()
+}
+
+#[actix_web::test]
+async fn test_http_panic_becomes_structured_500() {
+    let reporter = Arc::new(RecordingReporter::default());
+    let telemetry = TelemetryService::new().with_reporter(reporter.clone());
+    let app = test::init_service(
+        App::new()
+            .wrap(from_fn(catch_panics))
+            .wrap(from_fn(report_server_errors))
+            .wrap(from_fn(request_id))
+            .app_data(web::Data::new(telemetry))
+            .route("/panic", web::get().to(panicking))
+            .route("/ok", web::get().to(healthy)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/panic")
+        .insert_header(("X-Request-Id", "req-panic-1"))
+        .insert_header(("Origin", CORS_ALLOWED_ORIGINS[0]))
+        .to_request();
+    // The server turns the error into its response
+    let error = test::try_call_service(&app, req).await.err().unwrap();
+    let resp = error.error_response();
+    assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(resp.headers().get("x-request-id").unwrap(), "req-panic-1");
+    assert_eq!(
+        resp.headers().get("access-control-allow-origin").unwrap(),
+        CORS_ALLOWED_ORIGINS[0]
+    );
+    let body = to_bytes(resp.into_body()).await.unwrap();
+    let body: ErrorResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body.error, "Internal server error");
+    assert_eq!(body.code.as_deref(), Some("internal"));
+    assert_eq!(body.request_id.as_deref(), Some("req-panic-1"));
+
+    // The worker keeps serving
+    let req = test::TestRequest::get().uri("/ok").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let reports = reporter.reports(1).await;
+    assert_eq!(reports.len(), 1, "the panic is reported once");
+    let report = &reports[0];
+    assert_eq!(report.source, ErrorSource::Panic);
+    assert_eq!(report.message, "handler exploded");
+    assert!(
+        report
+            .location
+            .as_deref()
+            .is_some_and(|location| location.contains("panics_integration_test.rs"))
+    );
+    let request = report.request.as_ref().unwrap();
+    assert_eq!(request.path, "/panic");
+    assert_eq!(request.request_id.as_deref(), Some("req-panic-1"));
+    assert_eq!(request.status, Some(500));
+}
+
+/// gRPC service that panics on every call.
+#[derive(Clone)]
+struct PanickingService;
+
+impl Service<http::Request<()>> for PanickingService {
+    type Response = http::Response<String>;
+    type Error = Infallible;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _req: http::Request<()>) -> Self::Future {
+        panic!("service exploded");
+    }
+}
+
+#[actix_web::test]
+async fn test_grpc_panic_becomes_internal() {
+    let reporter = Arc::new(RecordingReporter::default());
+    let telemetry = TelemetryService::new().with_reporter(reporter.clone());
+    let mut service = CatchPanicLayer::new(telemetry).layer(PanickingService);
+
+    let req = http::Request::builder()
+        .uri("/blog.BlogService/GetPost")
+        .header("x-request-id", "grpc-req-7")
+        .body(())
+        .unwrap();
+    let resp = service.call(req).await.unwrap();
+    assert_eq!(resp.headers().get("grpc-status").unwrap(), "13");
+    assert_eq!(resp.headers().get("x-request-id").unwrap(), "grpc-req-7");
+
+    let reports = reporter.reports(1).await;
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].source, ErrorSource::Panic);
+    assert_eq!(reports[0].message, "service exploded");
+    let request = reports[0].request.as_ref().unwrap();
+    assert_eq!(request.path, "/blog.BlogService/GetPost");
+    assert_eq!(request.request_id.as_deref(), Some("grpc-req-7"));
+}
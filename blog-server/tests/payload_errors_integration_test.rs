@@ -0,0 +1,152 @@
+//! Integration tests for field-level errors on malformed bodies and queries.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{AuthResponse, ErrorResponse, RegisterRequest};
+use serde_json::json;
+
+use blog_server::application::{AuthService, BlogService, ModerationService};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::presentation::http_handlers::api_routes;
+use blog_server::presentation::payload::json_config;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app with the auth and blog services.
+macro_rules! init_app {
+    () => {{
+        let pool = setup_test_db().await;
+        let auth_service = AuthService::new(
+            Arc::new(UserRepository::new(pool.clone())),
+            Arc::new(FeatureFlagRepository::new(pool.clone())),
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let blog_service = BlogService::new(
+            Arc::new(PostRepository::new(pool.clone())),
+            EmbedProvider::ALL.to_vec(),
+            ModerationService::new(Arc::new(ModerationRepository::new(pool))),
+        );
+
+        test::init_service(
+            App::new()
+                .app_data(json_config())
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(blog_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user with a JSON body and read the error response.
+macro_rules! register_error {
+    ($app:expr, $body:expr) => {{
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json($body)
+            .send_request($app)
+            .await;
+        assert_eq!(resp.status(), 400);
+
+        let error: ErrorResponse = test::read_body_json(resp).await;
+        assert_eq!(error.code.as_deref(), Some("invalid_request"));
+        error
+    }};
+}
+
+/// Wrongly typed and missing fields are reported with their pointer.
+#[tokio::test]
+async fn test_body_errors_point_at_field() {
+    let app = init_app!();
+
+    let error = register_error!(
+        &app,
+        json!({"username": 5, "email": "a@example.com", "password": "secret123"})
+    );
+    assert_eq!(error.pointer.as_deref(), Some("/username"));
+    assert!(error.error.contains("expected a string"));
+
+    let error = register_error!(&app, json!({"username": "alice", "password": "secret123"}));
+    assert_eq!(error.pointer.as_deref(), Some("/email"));
+    assert!(error.error.contains("missing field"));
+}
+
+/// Errors inside arrays point at the element.
+#[tokio::test]
+async fn test_body_errors_point_into_arrays() {
+    let app = init_app!();
+    let req = RegisterRequest {
+        username: "alice".to_string(),
+        email: "alice@example.com".to_string(),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    let resp = test::TestRequest::post()
+        .uri("/api/auth/register")
+        .set_json(&req)
+        .send_request(&app)
+        .await;
+    let auth: AuthResponse = test::read_body_json(resp).await;
+
+    let resp = test::TestRequest::post()
+        .uri("/api/posts")
+        .insert_header(("Authorization", format!("Bearer {}", auth.token)))
+        .set_json(json!({"title": "Hi", "content": "Body", "tags": ["rust", 5]}))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 400);
+
+    let error: ErrorResponse = test::read_body_json(resp).await;
+    assert_eq!(error.pointer.as_deref(), Some("/tags/1"));
+}
+
+/// Malformed JSON is a structured error without a pointer.
+#[tokio::test]
+async fn test_malformed_json() {
+    let app = init_app!();
+
+    let resp = test::TestRequest::post()
+        .uri("/api/auth/register")
+        .insert_header(("Content-Type", "application/json"))
+        .set_payload(r#"{"username": "alice""#)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 400);
+
+    let error: ErrorResponse = test::read_body_json(resp).await;
+    assert_eq!(error.code.as_deref(), Some("invalid_request"));
+    assert!(error.pointer.is_none());
+    assert!(error.error.contains("line 1"));
+}
+
+/// Unparseable query parameters point at the parameter.
+#[tokio::test]
+async fn test_query_errors_point_at_parameter() {
+    let app = init_app!();
+
+    let resp = test::TestRequest::get()
+        .uri("/api/posts?limit=ten")
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 400);
+
+    let error: ErrorResponse = test::read_body_json(resp).await;
+    assert_eq!(error.code.as_deref(), Some("invalid_request"));
+    assert_eq!(error.pointer.as_deref(), Some("/limit"));
+
+    let resp = test::TestRequest::get()
+        .uri("/api/posts?limit=5")
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 200);
+}
@@ -0,0 +1,171 @@
+//! Integration tests for custom post metadata.
+
+mod common;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::constants::MAX_METADATA_KEYS;
+use blog_shared::{CreatePostRequest, PostDto, RegisterRequest};
+use serde_json::{Value, json};
+use sqlx::SqlitePool;
+
+use blog_server::application::{AuthService, BlogService, ModerationService};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Builds the auth and blog services on a test database.
+fn services(pool: &SqlitePool) -> (AuthService, BlogService) {
+    let auth_service = AuthService::new(
+        Arc::new(UserRepository::new(pool.clone())),
+        Arc::new(FeatureFlagRepository::new(pool.clone())),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let blog_service = BlogService::new(
+        Arc::new(PostRepository::new(pool.clone())),
+        EmbedProvider::ALL.to_vec(),
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone()))),
+    );
+    (auth_service, blog_service)
+}
+
+/// Registers a user, returning their ID and token.
+async fn register(auth_service: &AuthService, username: &str) -> (i64, String) {
+    let req = RegisterRequest {
+        username: username.to_string(),
+        email: format!("{username}@example.com"),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    let auth = auth_service.register(req, None).await.unwrap();
+    (auth.user.id, auth.token)
+}
+
+/// Creates a post by `author_id`.
+async fn create_post(blog_service: &BlogService, author_id: i64) -> PostDto {
+    blog_service
+        .create_post(
+            author_id,
+            CreatePostRequest {
+                title: "Hello".to_string(),
+                content: "World".to_string(),
+                tags: Vec::new(),
+                lang: None,
+                canonical_url: None,
+                original_source: None,
+                org: None,
+            },
+        )
+        .await
+        .unwrap()
+}
+
+/// Test fields being merged, removed with `null`, and shown on the post.
+#[tokio::test]
+async fn test_metadata_merges_and_removes() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service) = services(&pool);
+    let (author_id, token) = register(&auth_service, "alice").await;
+    let post = create_post(&blog_service, author_id).await;
+    assert!(post.metadata.is_empty());
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(blog_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+    let uri = format!("/api/posts/{}/metadata", post.public_id);
+
+    let req = test::TestRequest::patch()
+        .uri(&uri)
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"series:order": 2, "seo.description": "A post"}))
+        .to_request();
+    let metadata: HashMap<String, Value> = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(metadata.len(), 2);
+
+    let req = test::TestRequest::patch()
+        .uri(&uri)
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(json!({"seo.description": null, "devto_tags": ["rust"]}))
+        .to_request();
+    let metadata: HashMap<String, Value> = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(
+        metadata,
+        HashMap::from([
+            ("series:order".to_string(), json!(2)),
+            ("devto_tags".to_string(), json!(["rust"])),
+        ])
+    );
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/posts/{}", post.public_id))
+        .to_request();
+    let fetched: PostDto = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(fetched.metadata, metadata);
+    assert_eq!(fetched.updated_at, post.updated_at);
+}
+
+/// Test bad keys, too many keys, oversized values, and other users being
+/// rejected without changing anything.
+#[tokio::test]
+async fn test_metadata_limits() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service) = services(&pool);
+    let (author_id, token) = register(&auth_service, "alice").await;
+    let (_, other_token) = register(&auth_service, "bob").await;
+    let post = create_post(&blog_service, author_id).await;
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(blog_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+    let uri = format!("/api/posts/{}/metadata", post.public_id);
+
+    let too_many: HashMap<String, Value> = (0..=MAX_METADATA_KEYS)
+        .map(|i| (format!("key{i}"), json!(i)))
+        .collect();
+    for body in [
+        json!({"Upper": 1}),
+        json!({"1st": 1}),
+        json!({"": 1}),
+        json!({"a".repeat(65): 1}),
+        json!(too_many),
+        json!({"big": "x".repeat(9000)}),
+    ] {
+        let req = test::TestRequest::patch()
+            .uri(&uri)
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .set_json(&body)
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 400);
+    }
+
+    let req = test::TestRequest::patch()
+        .uri(&uri)
+        .insert_header(("Authorization", format!("Bearer {other_token}")))
+        .set_json(json!({"series:order": 1}))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 403);
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/posts/{}", post.public_id))
+        .to_request();
+    let fetched: PostDto = test::call_and_read_body_json(&app, req).await;
+    assert!(fetched.metadata.is_empty());
+}
@@ -0,0 +1,169 @@
+//! Integration tests for post canonical URLs and original sources.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    CreatePostRequest, PostDto, RegisterRequest, SiteSettingsDto, UpdatePostRequest,
+};
+use sqlx::SqlitePool;
+
+use blog_server::application::{AuthService, BlogService, ModerationService};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::{AuthorTimezones, EmbedProvider, FeedUrls, render_atom, render_rss};
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+const CANONICAL: &str = "https://dev.example/alice/hello";
+const SOURCE: &str = "https://medium.example/@alice/hello";
+
+/// Builds the auth and blog services on a test database.
+fn services(pool: &SqlitePool) -> (AuthService, BlogService) {
+    let auth_service = AuthService::new(
+        Arc::new(UserRepository::new(pool.clone())),
+        Arc::new(FeatureFlagRepository::new(pool.clone())),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let blog_service = BlogService::new(
+        Arc::new(PostRepository::new(pool.clone())),
+        EmbedProvider::ALL.to_vec(),
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone()))),
+    );
+    (auth_service, blog_service)
+}
+
+/// Registers a user, returning their token.
+async fn register(auth_service: &AuthService, username: &str) -> String {
+    let req = RegisterRequest {
+        username: username.to_string(),
+        email: format!("{username}@example.com"),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    auth_service.register(req, None).await.unwrap().token
+}
+
+/// Builds a create request with the given source links.
+fn post_request(canonical_url: Option<&str>, original_source: Option<&str>) -> CreatePostRequest {
+    CreatePostRequest {
+        title: "Hello".to_string(),
+        content: "World".to_string(),
+        tags: Vec::new(),
+        lang: None,
+        canonical_url: canonical_url.map(str::to_string),
+        original_source: original_source.map(str::to_string),
+        org: None,
+    }
+}
+
+/// Test source links being stored, returned, and cleared.
+#[tokio::test]
+async fn test_sources_round_trip() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service) = services(&pool);
+    let token = register(&auth_service, "alice").await;
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(blog_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/posts")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(post_request(Some(CANONICAL), Some(&format!(" {SOURCE} "))))
+        .to_request();
+    let post: PostDto = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(post.canonical_url.as_deref(), Some(CANONICAL));
+    assert_eq!(post.original_source.as_deref(), Some(SOURCE));
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/posts/{}", post.public_id))
+        .to_request();
+    let fetched: PostDto = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(fetched.canonical_url.as_deref(), Some(CANONICAL));
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/api/posts/{}", post.public_id))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(UpdatePostRequest {
+            title: Some("Hello again".to_string()),
+            content: None,
+            tags: None,
+            canonical_url: Some(String::new()),
+            original_source: None,
+        })
+        .to_request();
+    let updated: PostDto = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(updated.canonical_url, None);
+    assert_eq!(updated.original_source.as_deref(), Some(SOURCE));
+}
+
+/// Test non-http(s) and malformed source links being rejected.
+#[tokio::test]
+async fn test_invalid_sources_rejected() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service) = services(&pool);
+    let token = register(&auth_service, "alice").await;
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(blog_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    let long = format!("https://example.com/{}", "a".repeat(500));
+    for (canonical_url, original_source) in [
+        (Some("javascript:alert(1)"), None),
+        (Some("https://"), None),
+        (None, Some("example.com/post")),
+        (None, Some("https://example.com/a b")),
+        (Some(long.as_str()), None),
+    ] {
+        let req = test::TestRequest::post()
+            .uri("/api/posts")
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .set_json(post_request(canonical_url, original_source))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 400, "{canonical_url:?} {original_source:?}");
+    }
+}
+
+/// Test feeds linking to the canonical URL and the original source.
+#[tokio::test]
+async fn test_feeds_link_sources() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service) = services(&pool);
+    register(&auth_service, "alice").await;
+    let post = blog_service
+        .create_post(1, post_request(Some(CANONICAL), Some(SOURCE)))
+        .await
+        .unwrap();
+    let urls = FeedUrls::new("http://api.test", "http://site.test", None);
+    let posts = [post];
+
+    let site = SiteSettingsDto::default();
+
+    let rss = render_rss(&posts, &site, &urls, &AuthorTimezones::new());
+    assert!(rss.contains(&format!("<link>{CANONICAL}</link>")));
+    let guid = format!("<guid>http://site.test/?post={}</guid>", posts[0].public_id);
+    assert!(rss.contains(&guid));
+    assert!(rss.contains(&format!("<atom:link rel=\"via\" href=\"{SOURCE}\"/>")));
+
+    let atom = render_atom(&posts, &site, &urls, &AuthorTimezones::new());
+    assert!(atom.contains(&format!("<link rel=\"alternate\" href=\"{CANONICAL}\"/>")));
+    assert!(atom.contains(&format!("<link rel=\"via\" href=\"{SOURCE}\"/>")));
+}
@@ -6,15 +6,19 @@ use std::sync::Arc;
 
 use actix_web::{App, test, web};
 use blog_shared::{
-    AuthResponse, CreatePostRequest, PostDto, PostListResponse, RegisterRequest, UpdatePostRequest,
+    AuthResponse, CreatePostRequest, ErrorResponse, PostDto, PostListResponse, RegisterRequest,
+    UpdatePostRequest,
 };
 
-use blog_server::application::{AuthService, BlogService};
-use blog_server::data::{PostRepository, UserRepository};
-use blog_server::presentation::JwtSecret;
+use blog_server::application::{AuthService, BlogService, ModerationService};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
 use blog_server::presentation::http_handlers::api_routes;
 
-use common::{TEST_JWT_SECRET, setup_test_db};
+use common::{setup_test_db, test_jwt};
 
 /// Macro to register a user and get their token.
 macro_rules! register_user {
@@ -23,6 +27,7 @@ macro_rules! register_user {
             username: $username.to_string(),
             email: $email.to_string(),
             password: $password.to_string(),
+            invite_code: None,
         };
 
         let resp = test::TestRequest::post()
@@ -40,8 +45,14 @@ macro_rules! register_user {
 #[tokio::test]
 async fn test_list_posts_empty() {
     let pool = setup_test_db().await;
-    let post_repo = Arc::new(PostRepository::new(pool));
-    let blog_service = BlogService::new(Arc::clone(&post_repo));
+    let post_repo = Arc::new(PostRepository::new(pool.clone()));
+    let moderation_service =
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+    let blog_service = BlogService::new(
+        Arc::clone(&post_repo),
+        EmbedProvider::ALL.to_vec(),
+        moderation_service.clone(),
+    );
 
     let app = test::init_service(
         App::new()
@@ -66,13 +77,17 @@ async fn test_list_posts_empty() {
 #[tokio::test]
 async fn test_create_post_requires_auth() {
     let pool = setup_test_db().await;
-    let post_repo = Arc::new(PostRepository::new(pool));
-    let blog_service = BlogService::new(Arc::clone(&post_repo));
-    let jwt_secret = JwtSecret(TEST_JWT_SECRET.to_string());
+    let post_repo = Arc::new(PostRepository::new(pool.clone()));
+    let moderation_service =
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+    let blog_service = BlogService::new(
+        Arc::clone(&post_repo),
+        EmbedProvider::ALL.to_vec(),
+        moderation_service.clone(),
+    );
 
     let app = test::init_service(
         App::new()
-            .app_data(web::Data::new(jwt_secret))
             .app_data(web::Data::new(blog_service))
             .service(web::scope("/api").service(api_routes())),
     )
@@ -81,6 +96,11 @@ async fn test_create_post_requires_auth() {
     let req = CreatePostRequest {
         title: "Test Post".to_string(),
         content: "Test content".to_string(),
+        tags: Vec::new(),
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
     };
 
     // Try to create post without token
@@ -97,15 +117,27 @@ async fn test_create_post_requires_auth() {
 #[tokio::test]
 async fn test_create_post_success() {
     let pool = setup_test_db().await;
+    let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
     let user_repo = Arc::new(UserRepository::new(pool.clone()));
-    let post_repo = Arc::new(PostRepository::new(pool));
-    let auth_service = AuthService::new(Arc::clone(&user_repo), TEST_JWT_SECRET.to_string());
-    let blog_service = BlogService::new(Arc::clone(&post_repo));
-    let jwt_secret = JwtSecret(TEST_JWT_SECRET.to_string());
+    let post_repo = Arc::new(PostRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        flag_repo,
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let moderation_service =
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+    let blog_service = BlogService::new(
+        Arc::clone(&post_repo),
+        EmbedProvider::ALL.to_vec(),
+        moderation_service.clone(),
+    );
 
     let app = test::init_service(
         App::new()
-            .app_data(web::Data::new(jwt_secret))
             .app_data(web::Data::new(auth_service))
             .app_data(web::Data::new(blog_service))
             .service(web::scope("/api").service(api_routes())),
@@ -117,6 +149,11 @@ async fn test_create_post_success() {
     let req = CreatePostRequest {
         title: "My First Post".to_string(),
         content: "This is the content of my first post.".to_string(),
+        tags: Vec::new(),
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
     };
 
     let resp = test::TestRequest::post()
@@ -138,15 +175,27 @@ async fn test_create_post_success() {
 #[tokio::test]
 async fn test_get_post_by_id() {
     let pool = setup_test_db().await;
+    let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
     let user_repo = Arc::new(UserRepository::new(pool.clone()));
-    let post_repo = Arc::new(PostRepository::new(pool));
-    let auth_service = AuthService::new(Arc::clone(&user_repo), TEST_JWT_SECRET.to_string());
-    let blog_service = BlogService::new(Arc::clone(&post_repo));
-    let jwt_secret = JwtSecret(TEST_JWT_SECRET.to_string());
+    let post_repo = Arc::new(PostRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        flag_repo,
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let moderation_service =
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+    let blog_service = BlogService::new(
+        Arc::clone(&post_repo),
+        EmbedProvider::ALL.to_vec(),
+        moderation_service.clone(),
+    );
 
     let app = test::init_service(
         App::new()
-            .app_data(web::Data::new(jwt_secret))
             .app_data(web::Data::new(auth_service))
             .app_data(web::Data::new(blog_service))
             .service(web::scope("/api").service(api_routes())),
@@ -159,6 +208,11 @@ async fn test_get_post_by_id() {
     let create_req = CreatePostRequest {
         title: "Post to Get".to_string(),
         content: "Content to retrieve".to_string(),
+        tags: Vec::new(),
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
     };
 
     let create_resp = test::TestRequest::post()
@@ -172,14 +226,14 @@ async fn test_get_post_by_id() {
 
     // Get the post by ID
     let get_resp = test::TestRequest::get()
-        .uri(&format!("/api/posts/{}", created_post.id))
+        .uri(&format!("/api/posts/{}", created_post.public_id))
         .send_request(&app)
         .await;
 
     assert_eq!(get_resp.status(), 200);
 
     let retrieved_post: PostDto = test::read_body_json(get_resp).await;
-    assert_eq!(retrieved_post.id, created_post.id);
+    assert_eq!(retrieved_post.public_id, created_post.public_id);
     assert_eq!(retrieved_post.title, "Post to Get");
 }
 
@@ -187,15 +241,27 @@ async fn test_get_post_by_id() {
 #[tokio::test]
 async fn test_update_post_by_author() {
     let pool = setup_test_db().await;
+    let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
     let user_repo = Arc::new(UserRepository::new(pool.clone()));
-    let post_repo = Arc::new(PostRepository::new(pool));
-    let auth_service = AuthService::new(Arc::clone(&user_repo), TEST_JWT_SECRET.to_string());
-    let blog_service = BlogService::new(Arc::clone(&post_repo));
-    let jwt_secret = JwtSecret(TEST_JWT_SECRET.to_string());
+    let post_repo = Arc::new(PostRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        flag_repo,
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let moderation_service =
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+    let blog_service = BlogService::new(
+        Arc::clone(&post_repo),
+        EmbedProvider::ALL.to_vec(),
+        moderation_service.clone(),
+    );
 
     let app = test::init_service(
         App::new()
-            .app_data(web::Data::new(jwt_secret))
             .app_data(web::Data::new(auth_service))
             .app_data(web::Data::new(blog_service))
             .service(web::scope("/api").service(api_routes())),
@@ -208,6 +274,11 @@ async fn test_update_post_by_author() {
     let create_req = CreatePostRequest {
         title: "Original Title".to_string(),
         content: "Original content".to_string(),
+        tags: Vec::new(),
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
     };
 
     let create_resp = test::TestRequest::post()
@@ -223,10 +294,13 @@ async fn test_update_post_by_author() {
     let update_req = UpdatePostRequest {
         title: Some("Updated Title".to_string()),
         content: None,
+        tags: None,
+        canonical_url: None,
+        original_source: None,
     };
 
     let update_resp = test::TestRequest::put()
-        .uri(&format!("/api/posts/{}", created_post.id))
+        .uri(&format!("/api/posts/{}", created_post.public_id))
         .insert_header(("Authorization", format!("Bearer {}", token)))
         .set_json(&update_req)
         .send_request(&app)
@@ -243,15 +317,27 @@ async fn test_update_post_by_author() {
 #[tokio::test]
 async fn test_update_post_by_non_author_fails() {
     let pool = setup_test_db().await;
+    let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
     let user_repo = Arc::new(UserRepository::new(pool.clone()));
-    let post_repo = Arc::new(PostRepository::new(pool));
-    let auth_service = AuthService::new(Arc::clone(&user_repo), TEST_JWT_SECRET.to_string());
-    let blog_service = BlogService::new(Arc::clone(&post_repo));
-    let jwt_secret = JwtSecret(TEST_JWT_SECRET.to_string());
+    let post_repo = Arc::new(PostRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        flag_repo,
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let moderation_service =
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+    let blog_service = BlogService::new(
+        Arc::clone(&post_repo),
+        EmbedProvider::ALL.to_vec(),
+        moderation_service.clone(),
+    );
 
     let app = test::init_service(
         App::new()
-            .app_data(web::Data::new(jwt_secret))
             .app_data(web::Data::new(auth_service))
             .app_data(web::Data::new(blog_service))
             .service(web::scope("/api").service(api_routes())),
@@ -265,6 +351,11 @@ async fn test_update_post_by_non_author_fails() {
     let create_req = CreatePostRequest {
         title: "Owner's Post".to_string(),
         content: "This is my post".to_string(),
+        tags: Vec::new(),
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
     };
 
     let create_resp = test::TestRequest::post()
@@ -280,31 +371,49 @@ async fn test_update_post_by_non_author_fails() {
     let update_req = UpdatePostRequest {
         title: Some("Hacked Title".to_string()),
         content: None,
+        tags: None,
+        canonical_url: None,
+        original_source: None,
     };
 
     let update_resp = test::TestRequest::put()
-        .uri(&format!("/api/posts/{}", created_post.id))
+        .uri(&format!("/api/posts/{}", created_post.public_id))
         .insert_header(("Authorization", format!("Bearer {}", other_token)))
         .set_json(&update_req)
         .send_request(&app)
         .await;
 
     assert_eq!(update_resp.status(), 403);
+    let error: ErrorResponse = test::read_body_json(update_resp).await;
+    assert_eq!(error.error, "Forbidden");
+    assert_eq!(error.code.as_deref(), Some("forbidden"));
 }
 
 /// Test deleting a post by the author.
 #[tokio::test]
 async fn test_delete_post_by_author() {
     let pool = setup_test_db().await;
+    let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
     let user_repo = Arc::new(UserRepository::new(pool.clone()));
-    let post_repo = Arc::new(PostRepository::new(pool));
-    let auth_service = AuthService::new(Arc::clone(&user_repo), TEST_JWT_SECRET.to_string());
-    let blog_service = BlogService::new(Arc::clone(&post_repo));
-    let jwt_secret = JwtSecret(TEST_JWT_SECRET.to_string());
+    let post_repo = Arc::new(PostRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        flag_repo,
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let moderation_service =
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+    let blog_service = BlogService::new(
+        Arc::clone(&post_repo),
+        EmbedProvider::ALL.to_vec(),
+        moderation_service.clone(),
+    );
 
     let app = test::init_service(
         App::new()
-            .app_data(web::Data::new(jwt_secret))
             .app_data(web::Data::new(auth_service))
             .app_data(web::Data::new(blog_service))
             .service(web::scope("/api").service(api_routes())),
@@ -317,6 +426,11 @@ async fn test_delete_post_by_author() {
     let create_req = CreatePostRequest {
         title: "Post to Delete".to_string(),
         content: "Will be deleted".to_string(),
+        tags: Vec::new(),
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
     };
 
     let create_resp = test::TestRequest::post()
@@ -330,7 +444,7 @@ async fn test_delete_post_by_author() {
 
     // Delete the post
     let delete_resp = test::TestRequest::delete()
-        .uri(&format!("/api/posts/{}", created_post.id))
+        .uri(&format!("/api/posts/{}", created_post.public_id))
         .insert_header(("Authorization", format!("Bearer {}", token)))
         .send_request(&app)
         .await;
@@ -339,7 +453,7 @@ async fn test_delete_post_by_author() {
 
     // Verify post is gone
     let get_resp = test::TestRequest::get()
-        .uri(&format!("/api/posts/{}", created_post.id))
+        .uri(&format!("/api/posts/{}", created_post.public_id))
         .send_request(&app)
         .await;
 
@@ -350,15 +464,27 @@ async fn test_delete_post_by_author() {
 #[tokio::test]
 async fn test_delete_post_by_non_author_fails() {
     let pool = setup_test_db().await;
+    let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
     let user_repo = Arc::new(UserRepository::new(pool.clone()));
-    let post_repo = Arc::new(PostRepository::new(pool));
-    let auth_service = AuthService::new(Arc::clone(&user_repo), TEST_JWT_SECRET.to_string());
-    let blog_service = BlogService::new(Arc::clone(&post_repo));
-    let jwt_secret = JwtSecret(TEST_JWT_SECRET.to_string());
+    let post_repo = Arc::new(PostRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        flag_repo,
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let moderation_service =
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+    let blog_service = BlogService::new(
+        Arc::clone(&post_repo),
+        EmbedProvider::ALL.to_vec(),
+        moderation_service.clone(),
+    );
 
     let app = test::init_service(
         App::new()
-            .app_data(web::Data::new(jwt_secret))
             .app_data(web::Data::new(auth_service))
             .app_data(web::Data::new(blog_service))
             .service(web::scope("/api").service(api_routes())),
@@ -372,6 +498,11 @@ async fn test_delete_post_by_non_author_fails() {
     let create_req = CreatePostRequest {
         title: "Protected Post".to_string(),
         content: "Cannot be deleted by others".to_string(),
+        tags: Vec::new(),
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
     };
 
     let create_resp = test::TestRequest::post()
@@ -385,7 +516,7 @@ async fn test_delete_post_by_non_author_fails() {
 
     // Other user tries to delete it
     let delete_resp = test::TestRequest::delete()
-        .uri(&format!("/api/posts/{}", created_post.id))
+        .uri(&format!("/api/posts/{}", created_post.public_id))
         .insert_header(("Authorization", format!("Bearer {}", other_token)))
         .send_request(&app)
         .await;
@@ -397,15 +528,27 @@ async fn test_delete_post_by_non_author_fails() {
 #[tokio::test]
 async fn test_list_posts_pagination() {
     let pool = setup_test_db().await;
+    let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
     let user_repo = Arc::new(UserRepository::new(pool.clone()));
-    let post_repo = Arc::new(PostRepository::new(pool));
-    let auth_service = AuthService::new(Arc::clone(&user_repo), TEST_JWT_SECRET.to_string());
-    let blog_service = BlogService::new(Arc::clone(&post_repo));
-    let jwt_secret = JwtSecret(TEST_JWT_SECRET.to_string());
+    let post_repo = Arc::new(PostRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        flag_repo,
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let moderation_service =
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+    let blog_service = BlogService::new(
+        Arc::clone(&post_repo),
+        EmbedProvider::ALL.to_vec(),
+        moderation_service.clone(),
+    );
 
     let app = test::init_service(
         App::new()
-            .app_data(web::Data::new(jwt_secret))
             .app_data(web::Data::new(auth_service))
             .app_data(web::Data::new(blog_service))
             .service(web::scope("/api").service(api_routes())),
@@ -419,6 +562,11 @@ async fn test_list_posts_pagination() {
         let req = CreatePostRequest {
             title: format!("Post {}", i),
             content: format!("Content {}", i),
+            tags: Vec::new(),
+            lang: None,
+            canonical_url: None,
+            original_source: None,
+            org: None,
         };
 
         test::TestRequest::post()
@@ -448,4 +596,13 @@ async fn test_list_posts_pagination() {
     let list_resp: PostListResponse = test::read_body_json(resp).await;
     assert_eq!(list_resp.posts.len(), 2);
     assert_eq!(list_resp.total, 5);
+
+    // A negative limit is not "no limit", and a negative offset starts over
+    let resp = test::TestRequest::get()
+        .uri("/api/posts?limit=-1&offset=-3")
+        .send_request(&app)
+        .await;
+
+    let list_resp: PostListResponse = test::read_body_json(resp).await;
+    assert_eq!(list_resp.posts.len(), 5);
 }
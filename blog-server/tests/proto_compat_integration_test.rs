@@ -0,0 +1,258 @@
+//! Compatibility tests for the gRPC protocol: messages encoded by older
+//! peers still decode, and fields are never renumbered or reused.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use prost::Message;
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{DescriptorProto, FileDescriptorSet};
+
+use blog_server::presentation::grpc_service::proto::{
+    AuthResponse, ListPostsResponse, Post, UpdatePostRequest,
+};
+
+const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("blog_descriptor");
+
+/// Frozen fields, one `<message> <number> <name> [label] <type>` per line.
+const FIELDS_LOCK: &str = include_str!("fixtures/proto/blog.fields");
+
+/// `Post` as the first release encoded it, before tags and rendered HTML.
+#[derive(Clone, PartialEq, Message)]
+struct PostV1 {
+    #[prost(int64, tag = "1")]
+    id: i64,
+    #[prost(string, tag = "2")]
+    title: String,
+    #[prost(string, tag = "3")]
+    content: String,
+    #[prost(int64, tag = "4")]
+    author_id: i64,
+    #[prost(string, tag = "5")]
+    author_username: String,
+    #[prost(string, tag = "6")]
+    created_at: String,
+    #[prost(string, tag = "7")]
+    updated_at: String,
+}
+
+/// Decodes a hex fixture into message bytes.
+fn fixture(hex: &str) -> Vec<u8> {
+    let hex = hex.trim();
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// A field or enum value as `<name> [label] <type>`, keyed by
+/// `(message, number)`.
+type Fields = BTreeMap<(String, i32), String>;
+
+/// Reserved field numbers and names, keyed by message.
+type Reserved = BTreeMap<String, (Vec<i32>, Vec<String>)>;
+
+/// Reads the current fields from the compiled descriptor, with each
+/// message's reserved numbers and names.
+fn current_fields() -> (Fields, Reserved) {
+    let set = FileDescriptorSet::decode(FILE_DESCRIPTOR_SET).unwrap();
+    let mut fields = Fields::new();
+    let mut reserved = Reserved::new();
+
+    for file in &set.file {
+        for message in &file.message_type {
+            collect_message(message, &mut fields, &mut reserved);
+        }
+        for enumeration in &file.enum_type {
+            for value in &enumeration.value {
+                fields.insert(
+                    (enumeration.name().to_string(), value.number()),
+                    format!("{} value", value.name()),
+                );
+            }
+        }
+    }
+
+    (fields, reserved)
+}
+
+/// Adds a message's fields and reserved entries.
+fn collect_message(message: &DescriptorProto, fields: &mut Fields, reserved: &mut Reserved) {
+    for field in &message.field {
+        let type_name = match field.r#type() {
+            Type::Message | Type::Enum => field.type_name().trim_start_matches(".blog.").into(),
+            other => other.as_str_name()["TYPE_".len()..].to_lowercase(),
+        };
+        let label = if field.label() == Label::Repeated {
+            "repeated "
+        } else if field.proto3_optional() {
+            "optional "
+        } else {
+            ""
+        };
+        fields.insert(
+            (message.name().to_string(), field.number()),
+            format!("{} {label}{type_name}", field.name()),
+        );
+    }
+
+    let numbers = message
+        .reserved_range
+        .iter()
+        .flat_map(|range| range.start()..range.end())
+        .collect();
+    reserved.insert(
+        message.name().to_string(),
+        (numbers, message.reserved_name.clone()),
+    );
+}
+
+/// Parses the lock file.
+fn locked_fields() -> Fields {
+    FIELDS_LOCK
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let mut parts = line.splitn(3, ' ');
+            let message = parts.next().unwrap().to_string();
+            let number = parts.next().unwrap().parse().unwrap();
+            ((message, number), parts.next().unwrap().to_string())
+        })
+        .collect()
+}
+
+/// Test every locked field is unchanged or removed with its number and name
+/// reserved, and every current field is locked.
+#[test]
+fn test_fields_match_lock() {
+    let (current, reserved) = current_fields();
+    let locked = locked_fields();
+
+    for ((message, number), entry) in &locked {
+        match current.get(&(message.clone(), *number)) {
+            Some(now) => assert_eq!(
+                now, entry,
+                "{message} field {number} changed; add a new field instead"
+            ),
+            None => {
+                let name = entry.split(' ').next().unwrap().to_string();
+                let (numbers, names) = reserved.get(message).cloned().unwrap_or_default();
+                assert!(
+                    numbers.contains(number) && names.contains(&name),
+                    "{message} field {number} ({name}) was removed without `reserved`"
+                );
+            }
+        }
+    }
+
+    let unlocked: BTreeSet<_> = current
+        .keys()
+        .filter(|key| !locked.contains_key(*key))
+        .collect();
+    assert!(
+        unlocked.is_empty(),
+        "fields missing from fixtures/proto/blog.fields: {unlocked:?}"
+    );
+}
+
+/// Test the client's copy of the proto is the server's.
+#[test]
+fn test_client_proto_matches_server() {
+    assert_eq!(
+        include_str!("../../blog-client/proto/blog.proto"),
+        include_str!("../proto/blog.proto")
+    );
+}
+
+/// Test a post from the first release decodes with defaults for newer fields.
+#[test]
+fn test_decodes_v1_post() {
+    let post =
+        Post::decode(fixture(include_str!("fixtures/proto/post_v1.hex")).as_slice()).unwrap();
+
+    assert_eq!(post.id, 7);
+    assert_eq!(post.title, "Hello");
+    assert_eq!(post.author_username, "alice");
+    assert_eq!(post.created_at, "2025-01-01T00:00:00+00:00");
+    assert!(post.tags.is_empty());
+    assert!(post.content_html.is_empty());
+    assert!(post.link_previews.is_empty());
+    assert!(!post.is_owner);
+}
+
+/// Test a v1 post list decodes.
+#[test]
+fn test_decodes_v1_list_posts_response() {
+    let bytes = fixture(include_str!("fixtures/proto/list_posts_response_v1.hex"));
+    let list = ListPostsResponse::decode(bytes.as_slice()).unwrap();
+
+    assert_eq!(list.total, 1);
+    assert_eq!(list.posts[0].title, "Hello");
+}
+
+/// Test a v1 auth response decodes without a role or recovery codes.
+#[test]
+fn test_decodes_v1_auth_response() {
+    let bytes = fixture(include_str!("fixtures/proto/auth_response_v1.hex"));
+    let auth = AuthResponse::decode(bytes.as_slice()).unwrap();
+
+    let user = auth.user.unwrap();
+    assert_eq!(auth.token, "token");
+    assert_eq!(user.email, "alice@example.com");
+    assert!(user.role.is_empty());
+    assert!(auth.recovery_codes.is_empty());
+}
+
+/// Test a v1 update leaves tags unset, so they are kept rather than cleared.
+#[test]
+fn test_decodes_v1_update_post_request() {
+    let bytes = fixture(include_str!("fixtures/proto/update_post_request_v1.hex"));
+    let req = UpdatePostRequest::decode(bytes.as_slice()).unwrap();
+
+    assert_eq!(req.title.as_deref(), Some("New title"));
+    assert_eq!(req.content, None);
+    assert_eq!(req.tags, None);
+}
+
+/// Test fields from a newer peer are skipped.
+#[test]
+fn test_skips_unknown_fields() {
+    let bytes = fixture(include_str!("fixtures/proto/post_unknown_fields.hex"));
+    let post = Post::decode(bytes.as_slice()).unwrap();
+
+    assert_eq!(post.id, 7);
+    assert_eq!(post.updated_at, "2025-01-02T00:00:00+00:00");
+}
+
+/// Test a first-release client decodes a post from the current server.
+#[test]
+fn test_v1_client_decodes_current_post() {
+    let post = Post {
+        id: 7,
+        title: "Hello".to_string(),
+        content: "First post".to_string(),
+        author_id: 3,
+        author_username: "alice".to_string(),
+        created_at: "2025-01-01T00:00:00+00:00".to_string(),
+        updated_at: "2025-01-02T00:00:00+00:00".to_string(),
+        tags: vec!["rust".to_string()],
+        content_html: "<p>First post</p>\n".to_string(),
+        link_previews: Vec::new(),
+        is_owner: true,
+        public_id: "0190a6b2-3c4d-4e5f-8a9b-0c1d2e3f4a5b".to_string(),
+        lang: "en".to_string(),
+        translation_group_id: "0190a6b2-3c4d-4e5f-8a9b-0c1d2e3f4a5b".to_string(),
+        canonical_url: Some("https://example.com/original".to_string()),
+        original_source: None,
+        metadata: r#"{"series:order":2}"#.to_string(),
+        status: "published".to_string(),
+        org: None,
+        author_public_id: "0190a6b2-3c4d-4e5f-8a9b-0c1d2e3f4a5c".to_string(),
+    };
+
+    let old = PostV1::decode(post.encode_to_vec().as_slice()).unwrap();
+
+    assert_eq!(
+        old.encode_to_vec(),
+        fixture(include_str!("fixtures/proto/post_v1.hex"))
+    );
+}
@@ -0,0 +1,205 @@
+//! Integration tests for public IDs in API paths.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{CreatePostRequest, PostDto, PublicUserDto, RegisterRequest};
+use sqlx::SqlitePool;
+
+use blog_server::application::{AuthService, BlogService, ModerationService, SettingsService};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Builds the auth, blog, and settings services on a test database.
+fn services(pool: &SqlitePool) -> (AuthService, BlogService, SettingsService) {
+    let user_repo = Arc::new(UserRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        Arc::new(FeatureFlagRepository::new(pool.clone())),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let blog_service = BlogService::new(
+        Arc::new(PostRepository::new(pool.clone())),
+        EmbedProvider::ALL.to_vec(),
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone()))),
+    );
+    (auth_service, blog_service, SettingsService::new(user_repo))
+}
+
+/// Test public routes resolving posts and users by public ID only, and
+/// leaving numeric IDs out of their JSON.
+#[tokio::test]
+async fn test_public_routes_use_public_ids_only() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service, settings_service) = services(&pool);
+    let auth = auth_service
+        .register(
+            RegisterRequest {
+                username: "alice".to_string(),
+                email: "alice@example.com".to_string(),
+                password: "secret123".to_string(),
+                invite_code: None,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let post = blog_service
+        .create_post(
+            auth.user.id,
+            CreatePostRequest {
+                title: "Hello".to_string(),
+                content: "World".to_string(),
+                tags: Vec::new(),
+                lang: None,
+                canonical_url: None,
+                original_source: None,
+                org: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(post.public_id.len(), 36);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(blog_service))
+            .app_data(web::Data::new(settings_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/posts/{}", post.public_id))
+        .to_request();
+    let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["public_id"], post.public_id.as_str());
+    assert_eq!(body["author_public_id"], auth.user.public_id.as_str());
+    assert!(body.get("id").is_none());
+    assert!(body.get("author_id").is_none());
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/users/{}", auth.user.public_id))
+        .to_request();
+    let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["username"], "alice");
+    assert!(body.get("id").is_none());
+    let profile: PublicUserDto = serde_json::from_value(body).unwrap();
+    assert_eq!(profile.public_id, auth.user.public_id);
+
+    for uri in [
+        format!("/api/posts/{}", post.id),
+        format!("/api/posts/{}/translations", post.id),
+        format!("/api/users/{}", auth.user.id),
+        "/api/posts/00000000-0000-4000-8000-000000000000".to_string(),
+    ] {
+        let req = test::TestRequest::get().uri(&uri).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 404, "{uri}");
+    }
+}
+
+/// Test authenticated routes still accepting a post's legacy numeric ID.
+#[tokio::test]
+async fn test_authenticated_routes_accept_numeric_id() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service, settings_service) = services(&pool);
+    let auth = auth_service
+        .register(
+            RegisterRequest {
+                username: "carol".to_string(),
+                email: "carol@example.com".to_string(),
+                password: "secret123".to_string(),
+                invite_code: None,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let post = blog_service
+        .create_post(
+            auth.user.id,
+            CreatePostRequest {
+                title: "Before".to_string(),
+                content: "Body".to_string(),
+                tags: Vec::new(),
+                lang: None,
+                canonical_url: None,
+                original_source: None,
+                org: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(blog_service))
+            .app_data(web::Data::new(settings_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/api/posts/{}", post.id))
+        .insert_header(("Authorization", format!("Bearer {}", auth.token)))
+        .set_json(serde_json::json!({"title": "After"}))
+        .to_request();
+    let updated: PostDto = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(updated.title, "After");
+    assert_eq!(updated.public_id, post.public_id);
+}
+
+/// Test public IDs being distinct per entity.
+#[tokio::test]
+async fn test_public_ids_are_unique() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service, _) = services(&pool);
+    let auth = auth_service
+        .register(
+            RegisterRequest {
+                username: "bob".to_string(),
+                email: "bob@example.com".to_string(),
+                password: "secret123".to_string(),
+                invite_code: None,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let mut ids = Vec::new();
+    for title in ["One", "Two"] {
+        let post = blog_service
+            .create_post(
+                auth.user.id,
+                CreatePostRequest {
+                    title: title.to_string(),
+                    content: "Body".to_string(),
+                    tags: Vec::new(),
+                    lang: None,
+                    canonical_url: None,
+                    original_source: None,
+                    org: None,
+                },
+            )
+            .await
+            .unwrap();
+        ids.push(post.public_id);
+    }
+    assert_ne!(ids[0], ids[1]);
+    assert_ne!(ids[0], auth.user.public_id);
+}
@@ -0,0 +1,221 @@
+//! Integration tests for account recovery codes.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    AuthResponse, LoginRequest, RecoverAccountRequest, RecoveryCodesResponse,
+    RegenerateRecoveryCodesRequest, RegisterRequest,
+};
+
+use blog_server::application::AuthService;
+use blog_server::data::{
+    FeatureFlagRepository, RecoveryCodeRepository, ServiceAccountRepository, UserRepository,
+};
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app with the auth service.
+macro_rules! init_app {
+    () => {{
+        let pool = setup_test_db().await;
+        let auth_service = AuthService::new(
+            Arc::new(UserRepository::new(pool.clone())),
+            Arc::new(FeatureFlagRepository::new(pool.clone())),
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool)),
+            test_jwt(),
+            Vec::new(),
+        );
+
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user and get the auth response.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        assert_eq!(resp.status(), 201);
+
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp
+    }};
+}
+
+/// Macro to redeem a recovery code and get the status.
+macro_rules! recover {
+    ($app:expr, $username:expr, $code:expr, $new_password:expr) => {{
+        let req = RecoverAccountRequest {
+            username: $username.to_string(),
+            code: $code.to_string(),
+            new_password: $new_password.to_string(),
+        };
+
+        test::TestRequest::post()
+            .uri("/api/auth/recover")
+            .set_json(&req)
+            .send_request($app)
+            .await
+            .status()
+    }};
+}
+
+/// Macro to log in and get the status.
+macro_rules! login_status {
+    ($app:expr, $username:expr, $password:expr) => {{
+        let req = LoginRequest {
+            username: $username.to_string(),
+            password: $password.to_string(),
+        };
+
+        test::TestRequest::post()
+            .uri("/api/auth/login")
+            .set_json(&req)
+            .send_request($app)
+            .await
+            .status()
+    }};
+}
+
+/// Registration returns a set of distinct recovery codes; logins do not.
+#[tokio::test]
+async fn test_register_returns_recovery_codes() {
+    let app = init_app!();
+    let auth = register_user!(&app, "alice");
+
+    assert_eq!(auth.recovery_codes.len(), 10);
+    let mut codes = auth.recovery_codes.clone();
+    codes.sort();
+    codes.dedup();
+    assert_eq!(codes.len(), 10);
+    assert!(auth.recovery_codes.iter().all(|code| code.len() == 11));
+
+    let req = LoginRequest {
+        username: "alice".to_string(),
+        password: "secret123".to_string(),
+    };
+    let resp = test::TestRequest::post()
+        .uri("/api/auth/login")
+        .set_json(&req)
+        .send_request(&app)
+        .await;
+    let login: AuthResponse = test::read_body_json(resp).await;
+    assert!(login.recovery_codes.is_empty());
+}
+
+/// A recovery code sets a new password once, and signs out old sessions.
+#[tokio::test]
+async fn test_recover_with_code() {
+    let app = init_app!();
+    let auth = register_user!(&app, "bob");
+    let code = &auth.recovery_codes[0];
+
+    // Codes may be typed in lower case, with or without the dash
+    let typed = code.to_lowercase().replace('-', " ");
+    assert_eq!(recover!(&app, "bob", typed, "new-secret"), 200);
+
+    assert_eq!(login_status!(&app, "bob", "secret123"), 401);
+    assert_eq!(login_status!(&app, "bob", "new-secret"), 200);
+
+    // The old token's session was revoked
+    let resp = test::TestRequest::post()
+        .uri("/api/settings/recovery-codes")
+        .insert_header(("Authorization", format!("Bearer {}", auth.token)))
+        .set_json(RegenerateRecoveryCodesRequest {
+            current_password: "new-secret".to_string(),
+        })
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 401);
+
+    // Spent codes cannot be reused; the others still work
+    assert_eq!(recover!(&app, "bob", code, "other-secret"), 401);
+    assert_eq!(
+        recover!(
+            &app,
+            "bob@example.com",
+            auth.recovery_codes[1],
+            "other-secret"
+        ),
+        200
+    );
+}
+
+/// Wrong codes, other users' codes, and short passwords are rejected.
+#[tokio::test]
+async fn test_recover_rejects_invalid_requests() {
+    let app = init_app!();
+    let carol = register_user!(&app, "carol");
+    let dave = register_user!(&app, "dave");
+
+    assert_eq!(recover!(&app, "carol", "AAAAA-AAAAA", "new-secret"), 401);
+    assert_eq!(
+        recover!(&app, "carol", dave.recovery_codes[0], "new-secret"),
+        401
+    );
+    assert_eq!(
+        recover!(&app, "nobody", carol.recovery_codes[0], "new-secret"),
+        401
+    );
+    assert_eq!(
+        recover!(&app, "carol", carol.recovery_codes[0], "short"),
+        400
+    );
+
+    // The rejected attempts did not spend the code
+    assert_eq!(
+        recover!(&app, "carol", carol.recovery_codes[0], "new-secret"),
+        200
+    );
+}
+
+/// Regenerating requires the password and invalidates the old codes.
+#[tokio::test]
+async fn test_regenerate_recovery_codes() {
+    let app = init_app!();
+    let auth = register_user!(&app, "erin");
+
+    let regenerate = |password: &str| {
+        test::TestRequest::post()
+            .uri("/api/settings/recovery-codes")
+            .insert_header(("Authorization", format!("Bearer {}", auth.token)))
+            .set_json(RegenerateRecoveryCodesRequest {
+                current_password: password.to_string(),
+            })
+    };
+
+    let resp = regenerate("wrong").send_request(&app).await;
+    assert_eq!(resp.status(), 401);
+
+    let resp = regenerate("secret123").send_request(&app).await;
+    assert_eq!(resp.status(), 200);
+    let fresh: RecoveryCodesResponse = test::read_body_json(resp).await;
+    assert_eq!(fresh.codes.len(), 10);
+    assert!(fresh.codes.iter().all(|c| !auth.recovery_codes.contains(c)));
+
+    assert_eq!(
+        recover!(&app, "erin", auth.recovery_codes[0], "new-secret"),
+        401
+    );
+    assert_eq!(recover!(&app, "erin", fresh.codes[0], "new-secret"), 200);
+}
@@ -0,0 +1,178 @@
+//! Integration tests for data retention policies.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{RegisterRequest, RetentionReportDto};
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+
+use blog_server::application::{AuthService, RetentionService};
+use blog_server::data::{
+    AdminRepository, FeatureFlagRepository, NewsletterRepository, RecoveryCodeRepository,
+    RetentionRepository, ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::AuditAction;
+use blog_server::infrastructure::clock::MockClock;
+use blog_server::infrastructure::config::RetentionConfig;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Retention of one year of audit log and 30 days of unsubscribed emails.
+const CONFIG: RetentionConfig = RetentionConfig {
+    audit_log_months: Some(12),
+    unsubscribed_days: Some(30),
+    trash_days: None,
+};
+
+/// Builds the auth service on a test database.
+fn auth_service(pool: &SqlitePool) -> AuthService {
+    AuthService::new(
+        Arc::new(UserRepository::new(pool.clone())),
+        Arc::new(FeatureFlagRepository::new(pool.clone())),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    )
+}
+
+/// Registers a user, returning their ID and token.
+async fn register(auth_service: &AuthService, username: &str) -> (i64, String) {
+    let req = RegisterRequest {
+        username: username.to_string(),
+        email: format!("{username}@example.com"),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    let auth = auth_service.register(req, None).await.unwrap();
+    (auth.user.id, auth.token)
+}
+
+/// Writes one audit log entry and one unsubscribed email, both dated now.
+async fn seed(pool: &SqlitePool, auth_service: &AuthService) {
+    let (actor_id, _) = register(auth_service, "moderator").await;
+    let (target_id, _) = register(auth_service, "spammer").await;
+    AdminRepository::new(pool.clone())
+        .log_action(
+            actor_id,
+            AuditAction::ShadowBan,
+            target_id,
+            None,
+            Utc::now(),
+        )
+        .await
+        .unwrap();
+
+    let newsletter_repo = NewsletterRepository::new(pool.clone());
+    newsletter_repo
+        .upsert_pending("reader@example.com", "token", Utc::now())
+        .await
+        .unwrap();
+    let subscription = newsletter_repo
+        .find_by_email("reader@example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    newsletter_repo
+        .unsubscribe(subscription.id, Utc::now())
+        .await
+        .unwrap();
+}
+
+/// Returns the `due` count of a policy in a report.
+fn due(report: &RetentionReportDto, name: &str) -> i64 {
+    report
+        .policies
+        .iter()
+        .find(|policy| policy.name == name)
+        .unwrap()
+        .due
+}
+
+/// Test data being reported and removed only once past its retention.
+#[tokio::test]
+async fn test_purges_data_past_retention() {
+    let pool = setup_test_db().await;
+    seed(&pool, &auth_service(&pool)).await;
+    let clock = MockClock::new(Utc::now() + Duration::days(31));
+    let service = RetentionService::new(Arc::new(RetentionRepository::new(pool.clone())), CONFIG)
+        .with_clock(Arc::new(clock.clone()));
+
+    let report = service.report().await.unwrap();
+    assert_eq!(due(&report, "audit_log"), 0);
+    assert_eq!(due(&report, "unsubscribed_emails"), 1);
+
+    assert_eq!(service.purge_due().await.unwrap(), 1);
+    let report = service.report().await.unwrap();
+    assert_eq!(due(&report, "unsubscribed_emails"), 0);
+
+    clock.advance(Duration::days(366));
+    let report = service.report().await.unwrap();
+    assert_eq!(due(&report, "audit_log"), 1);
+    assert_eq!(service.purge_due().await.unwrap(), 1);
+    assert_eq!(
+        AdminRepository::new(pool).count_audit_log().await.unwrap(),
+        0
+    );
+}
+
+/// Test policies without a configured retention keeping everything.
+#[tokio::test]
+async fn test_unset_policies_keep_data() {
+    let pool = setup_test_db().await;
+    seed(&pool, &auth_service(&pool)).await;
+    let clock = MockClock::new(Utc::now() + Duration::days(10 * 365));
+    let service = RetentionService::new(
+        Arc::new(RetentionRepository::new(pool.clone())),
+        RetentionConfig::default(),
+    )
+    .with_clock(Arc::new(clock));
+
+    let report = service.report().await.unwrap();
+    assert!(report.policies.iter().all(|policy| policy.cutoff.is_none()));
+    assert_eq!(service.purge_due().await.unwrap(), 0);
+}
+
+/// Test the dry-run report being admin-only and removing nothing.
+#[tokio::test]
+async fn test_report_endpoint_is_admin_only() {
+    let pool = setup_test_db().await;
+    let auth_service = auth_service(&pool);
+    seed(&pool, &auth_service).await;
+    let (admin_id, admin_token) = register(&auth_service, "admin").await;
+    let (_, user_token) = register(&auth_service, "reader").await;
+    UserRepository::new(pool.clone())
+        .update_role(admin_id, "admin")
+        .await
+        .unwrap();
+    let clock = MockClock::new(Utc::now() + Duration::days(31));
+    let service = RetentionService::new(Arc::new(RetentionRepository::new(pool.clone())), CONFIG)
+        .with_clock(Arc::new(clock));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(service.clone()))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/admin/retention")
+        .insert_header(("Authorization", format!("Bearer {user_token}")))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 403);
+
+    for _ in 0..2 {
+        let req = test::TestRequest::get()
+            .uri("/api/admin/retention")
+            .insert_header(("Authorization", format!("Bearer {admin_token}")))
+            .to_request();
+        let report: RetentionReportDto = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(due(&report, "unsubscribed_emails"), 1);
+    }
+}
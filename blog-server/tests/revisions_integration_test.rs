@@ -0,0 +1,146 @@
+//! Integration tests for post revisions and diffs between them.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    CreatePostRequest, DiffOp, RegisterRequest, RevisionDiffResponse, RevisionListResponse,
+    UpdatePostRequest,
+};
+use sqlx::SqlitePool;
+
+use blog_server::application::{AuthService, BlogService, ModerationService};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Builds the auth and blog services on a test database.
+fn services(pool: &SqlitePool) -> (AuthService, BlogService) {
+    let auth_service = AuthService::new(
+        Arc::new(UserRepository::new(pool.clone())),
+        Arc::new(FeatureFlagRepository::new(pool.clone())),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let blog_service = BlogService::new(
+        Arc::new(PostRepository::new(pool.clone())),
+        EmbedProvider::ALL.to_vec(),
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone()))),
+    );
+    (auth_service, blog_service)
+}
+
+/// Registers a user, returning their ID and token.
+async fn register(auth_service: &AuthService, username: &str) -> (i64, String) {
+    let req = RegisterRequest {
+        username: username.to_string(),
+        email: format!("{username}@example.com"),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    let auth = auth_service.register(req, None).await.unwrap();
+    (auth.user.id, auth.token)
+}
+
+/// Changes the title, content, or tags of a post.
+fn edit(
+    title: Option<&str>,
+    content: Option<&str>,
+    tags: Option<Vec<String>>,
+) -> UpdatePostRequest {
+    UpdatePostRequest {
+        title: title.map(str::to_string),
+        content: content.map(str::to_string),
+        tags,
+        canonical_url: None,
+        original_source: None,
+    }
+}
+
+/// Test edits saving revisions that can be listed and diffed.
+#[tokio::test]
+async fn test_revisions_are_saved_and_diffed() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service) = services(&pool);
+    let (author_id, author_token) = register(&auth_service, "alice").await;
+    let (_, other_token) = register(&auth_service, "mallory").await;
+    let post = blog_service
+        .create_post(
+            author_id,
+            CreatePostRequest {
+                title: "Draft".to_string(),
+                content: "one\ntwo\nthree".to_string(),
+                tags: Vec::new(),
+                lang: None,
+                canonical_url: None,
+                original_source: None,
+                org: None,
+            },
+        )
+        .await
+        .unwrap();
+    for req in [
+        edit(None, Some("one\n2 <b>two</b>\nthree"), None),
+        // Tags alone are not part of a revision
+        edit(None, None, Some(vec!["rust".to_string()])),
+        edit(Some("Final"), None, None),
+    ] {
+        blog_service
+            .update_post(post.id, author_id, req)
+            .await
+            .unwrap();
+    }
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(blog_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/posts/{}/revisions", post.id))
+        .insert_header(("Authorization", format!("Bearer {author_token}")))
+        .to_request();
+    let list: RevisionListResponse = test::call_and_read_body_json(&app, req).await;
+    let numbers: Vec<_> = list.revisions.iter().map(|r| r.number).collect();
+    assert_eq!(numbers, [3, 2, 1]);
+    assert_eq!(list.revisions[0].title, "Final");
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/posts/{}/revisions/1/diff/3", post.id))
+        .insert_header(("Authorization", format!("Bearer {author_token}")))
+        .to_request();
+    let diff: RevisionDiffResponse = test::call_and_read_body_json(&app, req).await;
+    let ops: Vec<_> = diff.content.iter().map(|line| line.op).collect();
+    assert_eq!(
+        ops,
+        [DiffOp::Same, DiffOp::Removed, DiffOp::Added, DiffOp::Same]
+    );
+    assert_eq!(diff.content[2].new_line, Some(2));
+    assert!(diff.title.iter().any(|line| line.text == "Final"));
+    assert!(diff.html.contains("2 &lt;b&gt;two&lt;/b&gt;"));
+    assert!(diff.html.contains(r#"<tr class="diff-added">"#));
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/posts/{}/revisions/1/diff/9", post.id))
+        .insert_header(("Authorization", format!("Bearer {author_token}")))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 404);
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/posts/{}/revisions", post.id))
+        .insert_header(("Authorization", format!("Bearer {other_token}")))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 403);
+}
@@ -0,0 +1,57 @@
+//! Integration tests for mounting the shared route catalog.
+
+use actix_web::http::{Method, StatusCode};
+use actix_web::{App, test, web};
+use blog_shared::routes::CATALOG;
+
+use blog_server::presentation::http_handlers::api_routes;
+
+#[actix_web::test]
+async fn test_every_catalog_route_is_mounted() {
+    // Without app data, a matched handler fails to extract its services or
+    // credentials; only a path or method nobody serves gets a 404 or 405.
+    let app =
+        test::init_service(App::new().service(web::scope("/api").service(api_routes()))).await;
+
+    for (method, path) in CATALOG {
+        let uri = format!("/api{}", fill_placeholders(path));
+        let req = test::TestRequest::default()
+            .method(Method::from_bytes(method.as_str().as_bytes()).unwrap())
+            .uri(&uri)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_ne!(resp.status(), StatusCode::NOT_FOUND, "{method} {uri}");
+        assert_ne!(
+            resp.status(),
+            StatusCode::METHOD_NOT_ALLOWED,
+            "{method} {uri}"
+        );
+    }
+}
+
+#[actix_web::test]
+async fn test_unlisted_method_falls_through() {
+    let app =
+        test::init_service(App::new().service(web::scope("/api").service(api_routes()))).await;
+
+    let req = test::TestRequest::patch()
+        .uri("/api/comments/1")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+/// Replaces each `{name}` segment of a path template with `1`.
+fn fill_placeholders(template: &str) -> String {
+    template
+        .split('/')
+        .map(|segment| {
+            if segment.starts_with('{') {
+                "1"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
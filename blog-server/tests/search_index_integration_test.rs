@@ -0,0 +1,247 @@
+//! Integration tests for external search indexes and their syncing.
+
+mod common;
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use blog_shared::{CreatePostRequest, RegisterRequest, UpdatePostRequest};
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+use blog_server::application::{AuthService, BlogService, ModerationService, SearchIndexService};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::{
+    AppError, EmbedProvider, SearchDocument, SearchFilters, SearchHit, SearchIndex, SearchResults,
+};
+use blog_server::infrastructure::event_bus::EventBus;
+
+use common::{setup_test_db, test_jwt};
+
+/// External index keeping documents in memory, matching titles containing
+/// the query and ignoring visibility, as a stale index might.
+#[derive(Default)]
+struct MemoryIndex {
+    documents: Mutex<BTreeMap<i64, SearchDocument>>,
+}
+
+impl MemoryIndex {
+    fn document(&self, post_id: i64) -> Option<SearchDocument> {
+        self.documents.lock().unwrap().get(&post_id).cloned()
+    }
+}
+
+#[async_trait]
+impl SearchIndex for MemoryIndex {
+    async fn search(
+        &self,
+        query: &str,
+        _filters: &SearchFilters,
+        _limit: i64,
+        _offset: i64,
+        _viewer_id: Option<i64>,
+    ) -> Result<SearchResults, AppError> {
+        let hits: Vec<SearchHit> = self
+            .documents
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|document| document.title.contains(query))
+            .map(|document| SearchHit {
+                post_id: document.id,
+                snippet: document.title.clone(),
+            })
+            .collect();
+        let total = hits.len() as i64;
+
+        Ok(SearchResults { hits, total })
+    }
+
+    fn is_external(&self) -> bool {
+        true
+    }
+
+    async fn upsert(&self, document: SearchDocument) -> Result<(), AppError> {
+        self.documents.lock().unwrap().insert(document.id, document);
+        Ok(())
+    }
+
+    async fn remove(&self, post_id: i64) -> Result<(), AppError> {
+        self.documents.lock().unwrap().remove(&post_id);
+        Ok(())
+    }
+}
+
+struct TestContext {
+    pool: SqlitePool,
+    auth_service: AuthService,
+    blog_service: BlogService,
+    index: Arc<MemoryIndex>,
+    indexer: SearchIndexService,
+}
+
+/// Builds a blog service searching a `MemoryIndex` fed from its events.
+async fn setup() -> TestContext {
+    let pool = setup_test_db().await;
+    let post_repo = Arc::new(PostRepository::new(pool.clone()));
+    let events = EventBus::new();
+    let index = Arc::new(MemoryIndex::default());
+    let auth_service = AuthService::new(
+        Arc::new(UserRepository::new(pool.clone())),
+        Arc::new(FeatureFlagRepository::new(pool.clone())),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let blog_service = BlogService::new(
+        Arc::clone(&post_repo),
+        EmbedProvider::ALL.to_vec(),
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone()))),
+    )
+    .with_search_index(index.clone())
+    .with_events(events.clone());
+    let indexer = SearchIndexService::new(index.clone(), post_repo, events);
+
+    TestContext {
+        pool,
+        auth_service,
+        blog_service,
+        index,
+        indexer,
+    }
+}
+
+async fn register(auth_service: &AuthService, username: &str) -> i64 {
+    let req = RegisterRequest {
+        username: username.to_string(),
+        email: format!("{username}@example.com"),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    auth_service.register(req, None).await.unwrap().user.id
+}
+
+async fn create_post(blog_service: &BlogService, author_id: i64, title: &str) -> i64 {
+    let req = CreatePostRequest {
+        title: title.to_string(),
+        content: "Body text".to_string(),
+        tags: vec!["rust".to_string()],
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
+    };
+    blog_service.create_post(author_id, req).await.unwrap().id
+}
+
+/// Waits until the index holds `expected` for a post, failing after a second.
+async fn wait_for(
+    index: &MemoryIndex,
+    post_id: i64,
+    expected: impl Fn(Option<SearchDocument>) -> bool,
+) {
+    for _ in 0..100 {
+        if expected(index.document(post_id)) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("search index never caught up with post {post_id}");
+}
+
+#[tokio::test]
+async fn test_post_changes_are_indexed_from_events() {
+    let ctx = setup().await;
+    tokio::spawn(ctx.indexer.clone().run());
+    let alice_id = register(&ctx.auth_service, "Alice").await;
+
+    let post_id = create_post(&ctx.blog_service, alice_id, "First draft").await;
+    wait_for(&ctx.index, post_id, |doc| doc.is_some()).await;
+    let document = ctx.index.document(post_id).unwrap();
+    assert_eq!(document.author, "alice");
+    assert_eq!(document.tags, vec!["rust".to_string()]);
+    assert_eq!(document.status, "published");
+
+    let req = UpdatePostRequest {
+        title: Some("Final words".to_string()),
+        content: None,
+        tags: None,
+        canonical_url: None,
+        original_source: None,
+    };
+    ctx.blog_service
+        .update_post(post_id, alice_id, req)
+        .await
+        .unwrap();
+    wait_for(&ctx.index, post_id, |doc| {
+        doc.is_some_and(|doc| doc.title == "Final words")
+    })
+    .await;
+
+    ctx.blog_service
+        .delete_post(post_id, alice_id)
+        .await
+        .unwrap();
+    wait_for(&ctx.index, post_id, |doc| doc.is_none()).await;
+}
+
+#[tokio::test]
+async fn test_reindex_feeds_all_posts() {
+    let ctx = setup().await;
+    let alice_id = register(&ctx.auth_service, "alice").await;
+    let first = create_post(&ctx.blog_service, alice_id, "One").await;
+    let second = create_post(&ctx.blog_service, alice_id, "Two").await;
+    // Nothing listens while the posts are written
+    assert!(ctx.index.document(first).is_none());
+
+    let indexed = ctx.indexer.reindex().await.unwrap();
+
+    assert_eq!(indexed, 2);
+    assert!(ctx.index.document(first).is_some());
+    assert!(ctx.index.document(second).is_some());
+}
+
+#[tokio::test]
+async fn test_external_hits_are_checked_against_visibility() {
+    let ctx = setup().await;
+    let alice_id = register(&ctx.auth_service, "alice").await;
+    let mallory_id = register(&ctx.auth_service, "mallory").await;
+    let visible = create_post(&ctx.blog_service, alice_id, "Rust tips").await;
+    let banned = create_post(&ctx.blog_service, mallory_id, "Rust spam").await;
+    ctx.indexer.reindex().await.unwrap();
+    // Stale entry of a post no longer in the database
+    let mut stale = ctx.index.document(visible).unwrap();
+    stale.id = 9999;
+    ctx.index.upsert(stale).await.unwrap();
+    UserRepository::new(ctx.pool.clone())
+        .update_shadow_banned_at(mallory_id, Some(Utc::now()), Utc::now())
+        .await
+        .unwrap();
+
+    let results = ctx
+        .blog_service
+        .search_posts("Rust", SearchFilters::default(), 10, 0, None)
+        .await
+        .unwrap();
+    let ids: Vec<i64> = results.posts.iter().map(|post| post.id).collect();
+    assert_eq!(ids, vec![visible]);
+    assert_eq!(
+        results.posts[0].snippet.as_deref(),
+        Some("Rust tips"),
+        "snippet comes from the index"
+    );
+
+    let results = ctx
+        .blog_service
+        .search_posts("Rust", SearchFilters::default(), 10, 0, Some(mallory_id))
+        .await
+        .unwrap();
+    let ids: Vec<i64> = results.posts.iter().map(|post| post.id).collect();
+    assert_eq!(ids, vec![visible, banned]);
+}
@@ -0,0 +1,315 @@
+//! Integration tests for the post search endpoint.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    AuthResponse, CreatePostRequest, PostDto, PostListResponse, RegisterRequest, UpdatePostRequest,
+};
+
+use chrono::Utc;
+
+use blog_server::application::{AuthService, BlogService, ModerationService};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app with auth and blog services.
+macro_rules! init_app {
+    () => {{
+        let pool = setup_test_db().await;
+        let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+        let user_repo = Arc::new(UserRepository::new(pool.clone()));
+        let post_repo = Arc::new(PostRepository::new(pool.clone()));
+        let auth_service = AuthService::new(
+            user_repo,
+            flag_repo,
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let moderation_service =
+            ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+        let blog_service = BlogService::new(
+            post_repo,
+            EmbedProvider::ALL.to_vec(),
+            moderation_service.clone(),
+        );
+
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(blog_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user and get their token.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp.token
+    }};
+}
+
+/// Macro to create a post, optionally with tags, and return it.
+macro_rules! create_post {
+    ($app:expr, $token:expr, $title:expr, $content:expr) => {
+        create_post!($app, $token, $title, $content, Vec::new())
+    };
+    ($app:expr, $token:expr, $title:expr, $content:expr, $tags:expr) => {{
+        let req = CreatePostRequest {
+            title: $title.to_string(),
+            content: $content.to_string(),
+            tags: $tags,
+            lang: None,
+            canonical_url: None,
+            original_source: None,
+            org: None,
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/posts")
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        let post: PostDto = test::read_body_json(resp).await;
+        post
+    }};
+}
+
+/// Macro to run a search and return the parsed response.
+macro_rules! search {
+    ($app:expr, $query:expr) => {{
+        let resp = test::TestRequest::get()
+            .uri(&format!("/api/search?q={}", $query))
+            .send_request($app)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let list: PostListResponse = test::read_body_json(resp).await;
+        list
+    }};
+}
+
+/// Test that search matches titles and content by word prefix.
+#[tokio::test]
+async fn test_search_matches_title_and_content() {
+    let app = init_app!();
+    let token = register_user!(&app, "author");
+    create_post!(&app, token, "Rust ownership", "Borrowing explained");
+    create_post!(
+        &app,
+        token,
+        "Gardening",
+        "Growing tomatoes with rusty tools"
+    );
+    create_post!(&app, token, "Cooking", "Pasta recipes");
+
+    let list = search!(&app, "rust");
+    assert_eq!(list.total, 2);
+    assert_eq!(list.posts.len(), 2);
+
+    let list = search!(&app, "borrow");
+    assert_eq!(list.total, 1);
+    assert_eq!(list.posts[0].title, "Rust ownership");
+    assert_eq!(list.posts[0].author_username, "author");
+}
+
+/// Test that every word in the query must match.
+#[tokio::test]
+async fn test_search_requires_all_terms() {
+    let app = init_app!();
+    let token = register_user!(&app, "author");
+    create_post!(&app, token, "Rust ownership", "Borrowing explained");
+    create_post!(&app, token, "Rust macros", "Declarative macros");
+
+    let list = search!(&app, "rust%20macro");
+    assert_eq!(list.total, 1);
+    assert_eq!(list.posts[0].title, "Rust macros");
+}
+
+/// Test that FTS5 syntax in the query is treated as plain text.
+#[tokio::test]
+async fn test_search_ignores_query_syntax() {
+    let app = init_app!();
+    let token = register_user!(&app, "author");
+    create_post!(&app, token, "Rust ownership", "Borrowing explained");
+
+    let list = search!(&app, "%22rust%22%20OR%20*");
+    assert_eq!(list.total, 0);
+
+    let list = search!(&app, "%22%28%29");
+    assert_eq!(list.total, 0);
+    assert!(list.posts.is_empty());
+}
+
+/// Test that the index follows post updates and deletes.
+#[tokio::test]
+async fn test_search_index_tracks_changes() {
+    let app = init_app!();
+    let token = register_user!(&app, "author");
+    let post = create_post!(&app, token, "Draft", "Nothing yet");
+
+    let req = UpdatePostRequest {
+        title: Some("Published".to_string()),
+        content: None,
+        tags: None,
+        canonical_url: None,
+        original_source: None,
+    };
+    test::TestRequest::put()
+        .uri(&format!("/api/posts/{}", post.public_id))
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .set_json(&req)
+        .send_request(&app)
+        .await;
+
+    assert_eq!(search!(&app, "draft").total, 0);
+    assert_eq!(search!(&app, "published").total, 1);
+
+    test::TestRequest::delete()
+        .uri(&format!("/api/posts/{}", post.public_id))
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(search!(&app, "published").total, 0);
+}
+
+/// Test that overly long queries are rejected.
+#[tokio::test]
+async fn test_search_query_too_long() {
+    let app = init_app!();
+
+    let resp = test::TestRequest::get()
+        .uri(&format!("/api/search?q={}", "a".repeat(201)))
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), 400);
+}
+
+/// Test that title matches rank first and results carry an escaped snippet
+/// with the matched terms highlighted.
+#[tokio::test]
+async fn test_search_ranks_titles_and_highlights_snippets() {
+    let app = init_app!();
+    let token = register_user!(&app, "author");
+    create_post!(
+        &app,
+        token,
+        "Gardening",
+        "Tomatoes <b>love</b> compost & sun"
+    );
+    create_post!(&app, token, "Compost", "Notes on heaps");
+
+    let list = search!(&app, "compost");
+    assert_eq!(list.total, 2);
+    assert_eq!(list.posts[0].title, "Compost");
+    assert_eq!(
+        list.posts[0].snippet.as_deref(),
+        Some("<mark>Compost</mark>")
+    );
+    assert_eq!(
+        list.posts[1].snippet.as_deref(),
+        Some("Tomatoes &lt;b&gt;love&lt;/b&gt; <mark>compost</mark> &amp; sun")
+    );
+
+    // Snippets only come with search results
+    let resp = test::TestRequest::get()
+        .uri("/api/posts")
+        .send_request(&app)
+        .await;
+    let list: PostListResponse = test::read_body_json(resp).await;
+    assert!(list.posts.iter().all(|post| post.snippet.is_none()));
+}
+
+/// Test that longer words also match indexed terms a typo away, and short
+/// words do not.
+#[tokio::test]
+async fn test_search_tolerates_typos() {
+    let app = init_app!();
+    let token = register_user!(&app, "author");
+    create_post!(&app, token, "Rust ownership", "Borrowing explained");
+    create_post!(&app, token, "Cooking", "Pasta recipes");
+
+    let list = search!(&app, "ownershp");
+    assert_eq!(list.total, 1);
+    assert_eq!(list.posts[0].title, "Rust ownership");
+    assert_eq!(
+        list.posts[0].snippet.as_deref(),
+        Some("Rust <mark>ownership</mark>")
+    );
+
+    assert_eq!(search!(&app, "borowing%20explaned").total, 1);
+    assert_eq!(search!(&app, "rsut").total, 0);
+}
+
+/// Test that results can be narrowed by author, tag and creation day.
+#[tokio::test]
+async fn test_search_filters() {
+    let app = init_app!();
+    let alice = register_user!(&app, "alice");
+    let bob = register_user!(&app, "bob");
+    create_post!(
+        &app,
+        alice,
+        "Rust tips",
+        "Iterators",
+        vec!["rust".to_string()]
+    );
+    create_post!(&app, alice, "Rust news", "Releases");
+    create_post!(
+        &app,
+        bob,
+        "Rust traits",
+        "Generics",
+        vec!["rust".to_string()]
+    );
+
+    assert_eq!(search!(&app, "rust").total, 3);
+    assert_eq!(search!(&app, "rust&author=Alice").total, 2);
+    assert_eq!(search!(&app, "rust&tag=rust").total, 2);
+    let list = search!(&app, "rust&author=bob&tag=rust");
+    assert_eq!(list.total, 1);
+    assert_eq!(list.posts[0].title, "Rust traits");
+
+    let today = Utc::now().date_naive();
+    let yesterday = today.pred_opt().unwrap();
+    let tomorrow = today.succ_opt().unwrap();
+    assert_eq!(
+        search!(&app, format!("rust&from={today}&to={today}")).total,
+        3
+    );
+    assert_eq!(search!(&app, format!("rust&from={tomorrow}")).total, 0);
+    assert_eq!(search!(&app, format!("rust&to={yesterday}")).total, 0);
+
+    let resp = test::TestRequest::get()
+        .uri(&format!("/api/search?q=rust&from={today}&to={yesterday}"))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 400);
+}
@@ -0,0 +1,328 @@
+//! Integration tests for service accounts and their scoped tokens.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    AuthResponse, CreateServiceAccountRequest, CreatedServiceAccountResponse, MeResponse,
+    ProfileDto, RegenerateRecoveryCodesRequest, RegisterRequest, ServiceAccountListResponse,
+    ServiceScope,
+};
+use sqlx::SqlitePool;
+
+use blog_server::application::{AccountService, AuthService, SettingsService};
+use blog_server::data::{
+    AccountRepository, FeatureFlagRepository, MessageRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app with the auth, account, and settings services.
+macro_rules! init_app {
+    ($pool:expr) => {{
+        let user_repo = Arc::new(UserRepository::new($pool.clone()));
+        let flag_repo = Arc::new(FeatureFlagRepository::new($pool.clone()));
+        let auth_service = AuthService::new(
+            Arc::clone(&user_repo),
+            Arc::clone(&flag_repo),
+            Arc::new(RecoveryCodeRepository::new($pool.clone())),
+            Arc::new(ServiceAccountRepository::new($pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let account_service = AccountService::new(
+            auth_service.clone(),
+            SettingsService::new(Arc::clone(&user_repo)),
+            Arc::new(AccountRepository::new($pool.clone())),
+            Arc::new(MessageRepository::new($pool.clone())),
+            flag_repo,
+        );
+
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(account_service))
+                .app_data(web::Data::new(SettingsService::new(user_repo)))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user and get the auth response.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp
+    }};
+}
+
+/// Macro to send an authenticated request with an optional JSON body.
+macro_rules! send {
+    ($app:expr, $method:ident, $uri:expr, $token:expr) => {{
+        test::TestRequest::$method()
+            .uri($uri)
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .send_request($app)
+            .await
+    }};
+    ($app:expr, $method:ident, $uri:expr, $token:expr, $body:expr) => {{
+        test::TestRequest::$method()
+            .uri($uri)
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json($body)
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Macro to create a service account as an admin and get the response.
+macro_rules! create_account {
+    ($app:expr, $admin_token:expr, $user_id:expr, $scopes:expr) => {{
+        let req = CreateServiceAccountRequest {
+            name: "Feed bot".to_string(),
+            user_id: $user_id,
+            scopes: $scopes,
+        };
+        let resp = send!(
+            $app,
+            post,
+            "/api/admin/service-accounts",
+            $admin_token,
+            &req
+        );
+        assert_eq!(resp.status(), 201);
+
+        let created: CreatedServiceAccountResponse = test::read_body_json(resp).await;
+        created
+    }};
+}
+
+/// Sets a user's role directly in the database.
+async fn set_role(pool: &SqlitePool, username: &str, role: &str) {
+    sqlx::query("UPDATE users SET role = ? WHERE username = ?")
+        .bind(role)
+        .bind(username)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+/// Profile update, a request that changes data.
+fn profile() -> ProfileDto {
+    ProfileDto {
+        bio: "Beep boop".to_string(),
+        ..ProfileDto::default()
+    }
+}
+
+/// Request replacing the user's recovery codes.
+fn regenerate_codes() -> RegenerateRecoveryCodesRequest {
+    RegenerateRecoveryCodesRequest {
+        current_password: "secret123".to_string(),
+    }
+}
+
+/// Only admins may manage service accounts.
+#[tokio::test]
+async fn test_service_accounts_require_admin() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let user = register_user!(&app, "alice");
+
+    let req = CreateServiceAccountRequest {
+        name: "Bot".to_string(),
+        user_id: user.user.id,
+        scopes: vec![ServiceScope::Write],
+    };
+    let resp = send!(&app, post, "/api/admin/service-accounts", user.token, &req);
+    assert_eq!(resp.status(), 403);
+
+    let resp = send!(&app, get, "/api/admin/service-accounts", user.token);
+    assert_eq!(resp.status(), 403);
+}
+
+/// A token without scopes is read-only: it may read as its user, but not
+/// change anything.
+#[tokio::test]
+async fn test_read_only_service_token() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let admin = register_user!(&app, "admin");
+    set_role(&pool, "admin", "admin").await;
+    let bot = register_user!(&app, "bot");
+
+    let created = create_account!(&app, admin.token, bot.user.id, Vec::new());
+    assert_eq!(created.account.scopes, vec![ServiceScope::Read]);
+    assert_eq!(created.account.username, "bot");
+    assert!(created.account.revoked_at.is_none());
+
+    let resp = send!(&app, get, "/api/auth/me", created.token);
+    assert_eq!(resp.status(), 200);
+    let me: MeResponse = test::read_body_json(resp).await;
+    assert_eq!(me.user.username, "bot");
+
+    let resp = send!(
+        &app,
+        put,
+        "/api/settings/profile",
+        created.token,
+        &profile()
+    );
+    assert_eq!(resp.status(), 403);
+}
+
+/// The `write` scope allows requests that change data.
+#[tokio::test]
+async fn test_write_scope_service_token() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let admin = register_user!(&app, "admin");
+    set_role(&pool, "admin", "admin").await;
+    let bot = register_user!(&app, "bot");
+
+    let created = create_account!(
+        &app,
+        admin.token,
+        bot.user.id,
+        vec![ServiceScope::Write, ServiceScope::Read, ServiceScope::Write]
+    );
+    assert_eq!(
+        created.account.scopes,
+        vec![ServiceScope::Read, ServiceScope::Write]
+    );
+
+    let resp = send!(
+        &app,
+        put,
+        "/api/settings/profile",
+        created.token,
+        &profile()
+    );
+    assert_eq!(resp.status(), 200);
+}
+
+/// Service tokens never get the role of the user they act as, and cannot
+/// touch the account's credentials or sessions.
+#[tokio::test]
+async fn test_service_token_has_no_role_or_account_access() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let admin = register_user!(&app, "admin");
+    set_role(&pool, "admin", "admin").await;
+
+    let created = create_account!(
+        &app,
+        admin.token,
+        admin.user.id,
+        vec![ServiceScope::Read, ServiceScope::Write]
+    );
+    let resp = send!(&app, get, "/api/auth/me", created.token);
+    assert_eq!(resp.status(), 200);
+
+    // Admin endpoints, including the one creating more service accounts
+    let resp = send!(&app, get, "/api/admin/service-accounts", created.token);
+    assert_eq!(resp.status(), 403);
+    let req = CreateServiceAccountRequest {
+        name: "Another bot".to_string(),
+        user_id: admin.user.id,
+        scopes: vec![ServiceScope::Write],
+    };
+    let resp = send!(
+        &app,
+        post,
+        "/api/admin/service-accounts",
+        created.token,
+        &req
+    );
+    assert_eq!(resp.status(), 403);
+
+    // Account and session endpoints
+    let resp = send!(&app, get, "/api/settings/sessions", created.token);
+    assert_eq!(resp.status(), 403);
+    let resp = send!(&app, delete, "/api/settings/sessions/any", created.token);
+    assert_eq!(resp.status(), 403);
+    let resp = send!(
+        &app,
+        post,
+        "/api/settings/recovery-codes",
+        created.token,
+        &regenerate_codes()
+    );
+    assert_eq!(resp.status(), 403);
+
+    // The admin's own session still works
+    let resp = send!(&app, get, "/api/admin/service-accounts", admin.token);
+    assert_eq!(resp.status(), 200);
+    let resp = send!(&app, get, "/api/settings/sessions", admin.token);
+    assert_eq!(resp.status(), 200);
+}
+
+/// Revoked tokens stop working at once; revoked accounts stay listed.
+#[tokio::test]
+async fn test_revoke_service_account() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let admin = register_user!(&app, "admin");
+    set_role(&pool, "admin", "admin").await;
+    let bot = register_user!(&app, "bot");
+
+    let created = create_account!(&app, admin.token, bot.user.id, Vec::new());
+    let uri = format!("/api/admin/service-accounts/{}", created.account.id);
+    let resp = send!(&app, delete, &uri, admin.token);
+    assert_eq!(resp.status(), 204);
+
+    let resp = send!(&app, get, "/api/auth/me", created.token);
+    assert_eq!(resp.status(), 401);
+
+    let resp = send!(&app, get, "/api/admin/service-accounts", admin.token);
+    assert_eq!(resp.status(), 200);
+    let list: ServiceAccountListResponse = test::read_body_json(resp).await;
+    assert_eq!(list.service_accounts.len(), 1);
+    assert!(list.service_accounts[0].revoked_at.is_some());
+
+    let resp = send!(&app, delete, "/api/admin/service-accounts/999", admin.token);
+    assert_eq!(resp.status(), 404);
+}
+
+/// Names must not be empty and the user must exist.
+#[tokio::test]
+async fn test_create_service_account_validation() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let admin = register_user!(&app, "admin");
+    set_role(&pool, "admin", "admin").await;
+
+    let req = CreateServiceAccountRequest {
+        name: "   ".to_string(),
+        user_id: admin.user.id,
+        scopes: Vec::new(),
+    };
+    let resp = send!(&app, post, "/api/admin/service-accounts", admin.token, &req);
+    assert_eq!(resp.status(), 400);
+
+    let req = CreateServiceAccountRequest {
+        name: "Bot".to_string(),
+        user_id: 999,
+        scopes: Vec::new(),
+    };
+    let resp = send!(&app, post, "/api/admin/service-accounts", admin.token, &req);
+    assert_eq!(resp.status(), 404);
+}
@@ -0,0 +1,404 @@
+//! Integration tests for settings endpoints (profile, account, sessions, preferences).
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    AuthResponse, ChangeEmailRequest, ChangePasswordRequest, DeleteAccountRequest, LoginRequest,
+    MeResponse, PendingEmailChangeResponse, PreferencesDto, ProfileDto, PublicUserDto,
+    RegisterRequest, SessionListResponse, SettingsDto, Theme, UserDto,
+};
+
+use blog_server::application::{AccountService, AuthService, SettingsService};
+use blog_server::data::{
+    AccountRepository, AdminRepository, EmailChangeRepository, FeatureFlagRepository,
+    MessageRepository, RecoveryCodeRepository, ServiceAccountRepository, UserRepository,
+};
+use blog_server::infrastructure::config::MailConfig;
+use blog_server::infrastructure::mailer::Mailer;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app with auth and settings services.
+macro_rules! init_app {
+    () => {{
+        let pool = setup_test_db().await;
+        let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+        let user_repo = Arc::new(UserRepository::new(pool.clone()));
+        let auth_service = AuthService::new(
+            Arc::clone(&user_repo),
+            Arc::clone(&flag_repo),
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        )
+        .with_email_changes(
+            Arc::new(EmailChangeRepository::new(pool.clone())),
+            Arc::new(AdminRepository::new(pool.clone())),
+            Mailer::new(&MailConfig::default()).unwrap(),
+            "http://site.test".to_string(),
+        );
+        let settings_service = SettingsService::new(user_repo);
+        let account_service = AccountService::new(
+            auth_service.clone(),
+            settings_service.clone(),
+            Arc::new(AccountRepository::new(pool.clone())),
+            Arc::new(MessageRepository::new(pool)),
+            flag_repo,
+        );
+
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(account_service))
+                .app_data(web::Data::new(settings_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user and get their token.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp.token
+    }};
+}
+
+/// Macro to log in (from a given user agent) and get the token.
+macro_rules! login {
+    ($app:expr, $username:expr, $password:expr, $agent:expr) => {{
+        let req = LoginRequest {
+            username: $username.to_string(),
+            password: $password.to_string(),
+        };
+
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/login")
+            .insert_header(("User-Agent", $agent))
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        assert_eq!(resp.status(), 200);
+
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp.token
+    }};
+}
+
+/// Macro to send an authenticated GET and return the response.
+macro_rules! get_with_token {
+    ($app:expr, $uri:expr, $token:expr) => {{
+        test::TestRequest::get()
+            .uri($uri)
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Macro to send an authenticated PUT with a JSON body and return the response.
+macro_rules! put_json {
+    ($app:expr, $uri:expr, $token:expr, $body:expr) => {{
+        test::TestRequest::put()
+            .uri($uri)
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json($body)
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Test that new users get default settings.
+#[tokio::test]
+async fn test_get_settings_defaults() {
+    let app = init_app!();
+    let token = register_user!(&app, "alice");
+
+    let resp = get_with_token!(&app, "/api/settings", token);
+    assert_eq!(resp.status(), 200);
+
+    let settings: SettingsDto = test::read_body_json(resp).await;
+    assert_eq!(settings.email, "alice@example.com");
+    assert_eq!(settings.profile, ProfileDto::default());
+    assert_eq!(settings.preferences.theme, Theme::System);
+    assert_eq!(settings.preferences.language, "en");
+}
+
+/// Test that settings require authentication.
+#[tokio::test]
+async fn test_settings_require_auth() {
+    let app = init_app!();
+
+    let resp = test::TestRequest::get()
+        .uri("/api/settings")
+        .send_request(&app)
+        .await;
+
+    assert_eq!(resp.status(), 401);
+}
+
+/// Test updating the profile, including validation of the avatar URL.
+#[tokio::test]
+async fn test_update_profile() {
+    let app = init_app!();
+    let token = register_user!(&app, "alice");
+
+    let profile = ProfileDto {
+        bio: "  Rustacean  ".to_string(),
+        avatar_url: Some("https://example.com/a.png".to_string()),
+        website: None,
+    };
+    let resp = put_json!(&app, "/api/settings/profile", token, &profile);
+    assert_eq!(resp.status(), 200);
+
+    let settings: SettingsDto =
+        test::read_body_json(get_with_token!(&app, "/api/settings", token)).await;
+    assert_eq!(settings.profile.bio, "Rustacean");
+    assert_eq!(
+        settings.profile.avatar_url.as_deref(),
+        Some("https://example.com/a.png")
+    );
+
+    let bad = ProfileDto {
+        bio: String::new(),
+        avatar_url: Some("javascript:alert(1)".to_string()),
+        website: None,
+    };
+    let resp = put_json!(&app, "/api/settings/profile", token, &bad);
+    assert_eq!(resp.status(), 400);
+}
+
+/// Test updating preferences and rejecting unsupported languages, time
+/// zones, and locales.
+#[tokio::test]
+async fn test_update_preferences() {
+    let app = init_app!();
+    let token = register_user!(&app, "alice");
+
+    let prefs = PreferencesDto {
+        theme: Theme::Dark,
+        language: "ru".to_string(),
+        timezone: "Europe/Moscow".to_string(),
+        locale: "ru-RU".to_string(),
+    };
+    let resp = put_json!(&app, "/api/settings/preferences", token, &prefs);
+    assert_eq!(resp.status(), 200);
+
+    let settings: SettingsDto =
+        test::read_body_json(get_with_token!(&app, "/api/settings", token)).await;
+    assert_eq!(settings.preferences, prefs);
+
+    let me: MeResponse = test::read_body_json(get_with_token!(&app, "/api/auth/me", token)).await;
+    assert_eq!(me.preferences, prefs);
+
+    for bad in [
+        PreferencesDto {
+            language: "xx".to_string(),
+            ..PreferencesDto::default()
+        },
+        PreferencesDto {
+            timezone: "Mars/Olympus_Mons".to_string(),
+            ..PreferencesDto::default()
+        },
+        PreferencesDto {
+            locale: "xx-XX".to_string(),
+            ..PreferencesDto::default()
+        },
+    ] {
+        let resp = put_json!(&app, "/api/settings/preferences", token, &bad);
+        assert_eq!(resp.status(), 400);
+    }
+}
+
+/// Test that changing email requires the password and a free address, and
+/// waits for the new address to be confirmed.
+#[tokio::test]
+async fn test_change_email() {
+    let app = init_app!();
+    let token = register_user!(&app, "alice");
+    register_user!(&app, "bob");
+
+    let wrong_password = ChangeEmailRequest {
+        new_email: "new@example.com".to_string(),
+        current_password: "nope".to_string(),
+    };
+    let resp = put_json!(&app, "/api/settings/email", token, &wrong_password);
+    assert_eq!(resp.status(), 401);
+
+    let taken = ChangeEmailRequest {
+        new_email: "bob@example.com".to_string(),
+        current_password: "secret123".to_string(),
+    };
+    let resp = put_json!(&app, "/api/settings/email", token, &taken);
+    assert_eq!(resp.status(), 400);
+
+    let ok = ChangeEmailRequest {
+        new_email: "new@example.com".to_string(),
+        current_password: "secret123".to_string(),
+    };
+    let resp = put_json!(&app, "/api/settings/email", token, &ok);
+    assert_eq!(resp.status(), 202);
+    let pending: PendingEmailChangeResponse = test::read_body_json(resp).await;
+    assert_eq!(pending.new_email, "new@example.com");
+
+    let resp = get_with_token!(&app, "/api/settings", token);
+    let settings: SettingsDto = test::read_body_json(resp).await;
+    assert_eq!(settings.email, "alice@example.com");
+}
+
+/// Test that changing the password signs out other sessions only.
+#[tokio::test]
+async fn test_change_password_revokes_other_sessions() {
+    let app = init_app!();
+    register_user!(&app, "alice");
+    let laptop = login!(&app, "alice", "secret123", "Laptop");
+    let phone = login!(&app, "alice", "secret123", "Phone");
+
+    let short = ChangePasswordRequest {
+        current_password: "secret123".to_string(),
+        new_password: "short".to_string(),
+    };
+    let resp = put_json!(&app, "/api/settings/password", laptop, &short);
+    assert_eq!(resp.status(), 400);
+
+    let req = ChangePasswordRequest {
+        current_password: "secret123".to_string(),
+        new_password: "much-better-secret".to_string(),
+    };
+    let resp = put_json!(&app, "/api/settings/password", laptop, &req);
+    assert_eq!(resp.status(), 204);
+
+    assert_eq!(get_with_token!(&app, "/api/auth/me", laptop).status(), 200);
+    assert_eq!(get_with_token!(&app, "/api/auth/me", phone).status(), 401);
+
+    login!(&app, "alice", "much-better-secret", "Phone");
+}
+
+/// Test listing sessions and revoking one of them.
+#[tokio::test]
+async fn test_list_and_revoke_sessions() {
+    let app = init_app!();
+    register_user!(&app, "alice");
+    let laptop = login!(&app, "alice", "secret123", "Laptop");
+    let phone = login!(&app, "alice", "secret123", "Phone");
+    let other = register_user!(&app, "bob");
+
+    let resp = get_with_token!(&app, "/api/settings/sessions", laptop);
+    assert_eq!(resp.status(), 200);
+    let list: SessionListResponse = test::read_body_json(resp).await;
+    // Registration, laptop, and phone logins
+    assert_eq!(list.sessions.len(), 3);
+    let current: Vec<_> = list.sessions.iter().filter(|s| s.current).collect();
+    assert_eq!(current.len(), 1);
+    assert_eq!(current[0].user_agent.as_deref(), Some("Laptop"));
+
+    let phone_session = list
+        .sessions
+        .iter()
+        .find(|s| s.user_agent.as_deref() == Some("Phone"))
+        .unwrap();
+    let uri = format!("/api/settings/sessions/{}", phone_session.id);
+
+    // Sessions of other users cannot be revoked
+    let resp = test::TestRequest::delete()
+        .uri(&uri)
+        .insert_header(("Authorization", format!("Bearer {}", other)))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 404);
+
+    let resp = test::TestRequest::delete()
+        .uri(&uri)
+        .insert_header(("Authorization", format!("Bearer {}", laptop)))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 204);
+
+    assert_eq!(get_with_token!(&app, "/api/auth/me", phone).status(), 401);
+    assert_eq!(get_with_token!(&app, "/api/auth/me", laptop).status(), 200);
+}
+
+/// Test that deleting the account requires the password and signs the user out.
+#[tokio::test]
+async fn test_delete_account() {
+    let app = init_app!();
+    let token = register_user!(&app, "alice");
+
+    let wrong = DeleteAccountRequest {
+        current_password: "wrong-password".to_string(),
+    };
+    let resp = test::TestRequest::delete()
+        .uri("/api/settings/account")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .set_json(&wrong)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 401);
+
+    let req = DeleteAccountRequest {
+        current_password: "secret123".to_string(),
+    };
+    let resp = test::TestRequest::delete()
+        .uri("/api/settings/account")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .set_json(&req)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 204);
+
+    assert_eq!(get_with_token!(&app, "/api/auth/me", token).status(), 401);
+
+    // The username is free again
+    register_user!(&app, "alice");
+}
+
+/// Test that profiles are publicly readable by user ID.
+#[tokio::test]
+async fn test_get_public_profile() {
+    let app = init_app!();
+    let token = register_user!(&app, "alice");
+
+    let profile = ProfileDto {
+        bio: "Rustacean".to_string(),
+        avatar_url: Some("https://example.com/alice.png".to_string()),
+        website: None,
+    };
+    let resp = put_json!(&app, "/api/settings/profile", token, &profile);
+    assert_eq!(resp.status(), 200);
+    let me: UserDto = test::read_body_json(get_with_token!(&app, "/api/auth/me", token)).await;
+
+    let resp = test::TestRequest::get()
+        .uri(&format!("/api/users/{}", me.public_id))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 200);
+    let user: PublicUserDto = test::read_body_json(resp).await;
+    assert_eq!(user.username, "alice");
+    assert_eq!(user.bio, "Rustacean");
+    assert_eq!(user.avatar_url, profile.avatar_url);
+
+    let resp = test::TestRequest::get()
+        .uri("/api/users/00000000-0000-4000-8000-000000000000")
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 404);
+}
@@ -0,0 +1,293 @@
+//! Integration tests for shadow bans and the moderator audit log.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    AdminUserDto, AuditLogResponse, AuthResponse, CommentListResponse, CreateCommentRequest,
+    CreatePostRequest, PostDto, PostListResponse, RegisterRequest, ShadowBanRequest,
+    TagListResponse,
+};
+use sqlx::SqlitePool;
+
+use blog_server::application::{
+    AdminService, AuthService, BlogService, CommentService, ModerationService,
+};
+use blog_server::data::{
+    AdminRepository, CommentRepository, FeatureFlagRepository, ModerationRepository,
+    PostRepository, RecoveryCodeRepository, ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::infrastructure::event_bus::EventBus;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app with the services shadow bans touch.
+macro_rules! init_app {
+    ($pool:expr) => {{
+        let user_repo = Arc::new(UserRepository::new($pool.clone()));
+        let post_repo = Arc::new(PostRepository::new($pool.clone()));
+        let comment_repo = Arc::new(CommentRepository::new($pool.clone()));
+        let flag_repo = Arc::new(FeatureFlagRepository::new($pool.clone()));
+        let admin_repo = Arc::new(AdminRepository::new($pool.clone()));
+        let auth_service = AuthService::new(
+            Arc::clone(&user_repo),
+            Arc::clone(&flag_repo),
+            Arc::new(RecoveryCodeRepository::new($pool.clone())),
+            Arc::new(ServiceAccountRepository::new($pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let moderation_service =
+            ModerationService::new(Arc::new(ModerationRepository::new($pool.clone())));
+        let blog_service = BlogService::new(
+            Arc::clone(&post_repo),
+            EmbedProvider::ALL.to_vec(),
+            moderation_service.clone(),
+        );
+        let comment_service = CommentService::new(
+            Arc::clone(&comment_repo),
+            Arc::clone(&post_repo),
+            Arc::clone(&flag_repo),
+            moderation_service,
+            EventBus::new(),
+        );
+        let admin_service = AdminService::new(admin_repo, user_repo, comment_repo, flag_repo);
+
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(blog_service))
+                .app_data(web::Data::new(comment_service))
+                .app_data(web::Data::new(admin_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user and get the auth response.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp
+    }};
+}
+
+/// Macro to send an authenticated request with an optional JSON body.
+macro_rules! send {
+    ($app:expr, $method:ident, $uri:expr, $token:expr) => {{
+        test::TestRequest::$method()
+            .uri($uri)
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .send_request($app)
+            .await
+    }};
+    ($app:expr, $method:ident, $uri:expr, $token:expr, $body:expr) => {{
+        test::TestRequest::$method()
+            .uri($uri)
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json($body)
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Macro to send an anonymous GET request.
+macro_rules! get_anon {
+    ($app:expr, $uri:expr) => {{ test::TestRequest::get().uri($uri).send_request($app).await }};
+}
+
+/// Sets a user's role directly in the database.
+async fn set_role(pool: &SqlitePool, username: &str, role: &str) {
+    sqlx::query("UPDATE users SET role = ? WHERE username = ?")
+        .bind(role)
+        .bind(username)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+/// Returns a post creation request with a tag.
+fn post_request(title: &str, tag: &str) -> CreatePostRequest {
+    CreatePostRequest {
+        title: title.to_string(),
+        content: format!("{title} content"),
+        tags: vec![tag.to_string()],
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
+    }
+}
+
+/// Test that a shadow-banned user's content is hidden from others but not from them.
+#[tokio::test]
+async fn test_shadow_ban_hides_content() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let moderator = register_user!(&app, "moderator");
+    let author = register_user!(&app, "author");
+    let spammer = register_user!(&app, "spammer");
+    set_role(&pool, "moderator", "moderator").await;
+
+    let good: PostDto = test::read_body_json(send!(
+        &app,
+        post,
+        "/api/posts",
+        author.token,
+        &post_request("Good", "rust")
+    ))
+    .await;
+
+    let uri = format!("/api/admin/users/{}/shadow-ban", spammer.user.id);
+    let ban = ShadowBanRequest {
+        reason: Some("  Link spam  ".to_string()),
+    };
+    let resp = send!(&app, put, &uri, moderator.token, &ban);
+    assert_eq!(resp.status(), 200);
+    let banned: AdminUserDto = test::read_body_json(resp).await;
+    assert!(banned.shadow_banned);
+
+    // Posting and commenting still appear to work for the spammer
+    let resp = send!(
+        &app,
+        post,
+        "/api/posts",
+        spammer.token,
+        &post_request("Cheap pills", "spam")
+    );
+    assert_eq!(resp.status(), 201);
+    let spam: PostDto = test::read_body_json(resp).await;
+    let comments_uri = format!("/api/posts/{}/comments", good.public_id);
+    let comment = CreateCommentRequest {
+        content: "Buy now".to_string(),
+    };
+    let resp = send!(&app, post, &comments_uri, spammer.token, &comment);
+    assert_eq!(resp.status(), 201);
+
+    // Others see neither the post nor the comment
+    let list: PostListResponse = test::read_body_json(get_anon!(&app, "/api/posts")).await;
+    assert_eq!(list.total, 1);
+    assert_eq!(list.posts[0].public_id, good.public_id);
+    let list: PostListResponse =
+        test::read_body_json(send!(&app, get, "/api/posts", author.token)).await;
+    assert_eq!(list.total, 1);
+    let spam_uri = format!("/api/posts/{}", spam.public_id);
+    assert_eq!(get_anon!(&app, &spam_uri).status(), 404);
+    let comments: CommentListResponse = test::read_body_json(get_anon!(&app, &comments_uri)).await;
+    assert_eq!(comments.total, Some(0));
+    let search: PostListResponse =
+        test::read_body_json(get_anon!(&app, "/api/search?q=pills")).await;
+    assert_eq!(search.total, 0);
+    let tagged: PostListResponse =
+        test::read_body_json(get_anon!(&app, "/api/posts?tag=spam")).await;
+    assert_eq!(tagged.total, 0);
+    let tags: TagListResponse = test::read_body_json(get_anon!(&app, "/api/tags")).await;
+    assert!(tags.tags.iter().all(|tag| tag.name != "spam"));
+
+    // The spammer sees everything as before
+    let list: PostListResponse =
+        test::read_body_json(send!(&app, get, "/api/posts", spammer.token)).await;
+    assert_eq!(list.total, 2);
+    assert_eq!(send!(&app, get, &spam_uri, spammer.token).status(), 200);
+    let comments: CommentListResponse =
+        test::read_body_json(send!(&app, get, &comments_uri, spammer.token)).await;
+    assert_eq!(comments.total, Some(1));
+    let search: PostListResponse =
+        test::read_body_json(send!(&app, get, "/api/search?q=pills", spammer.token)).await;
+    assert_eq!(search.total, 1);
+
+    // Lifting the ban makes the content public again
+    let resp = send!(&app, delete, &uri, moderator.token);
+    assert_eq!(resp.status(), 200);
+    let unbanned: AdminUserDto = test::read_body_json(resp).await;
+    assert!(!unbanned.shadow_banned);
+    let list: PostListResponse = test::read_body_json(get_anon!(&app, "/api/posts")).await;
+    assert_eq!(list.total, 2);
+    let comments: CommentListResponse = test::read_body_json(get_anon!(&app, &comments_uri)).await;
+    assert_eq!(comments.total, Some(1));
+}
+
+/// Test that bans and unbans are recorded in the audit log, newest first.
+#[tokio::test]
+async fn test_shadow_ban_audit_log() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let moderator = register_user!(&app, "moderator");
+    let spammer = register_user!(&app, "spammer");
+    set_role(&pool, "moderator", "moderator").await;
+
+    let uri = format!("/api/admin/users/{}/shadow-ban", spammer.user.id);
+    let ban = ShadowBanRequest {
+        reason: Some("Link spam".to_string()),
+    };
+    send!(&app, put, &uri, moderator.token, &ban);
+    // Repeating a ban is a no-op and is not logged again
+    send!(&app, put, &uri, moderator.token, &ban);
+    send!(&app, delete, &uri, moderator.token);
+
+    let resp = send!(&app, get, "/api/admin/audit-log", moderator.token);
+    assert_eq!(resp.status(), 200);
+    let log: AuditLogResponse = test::read_body_json(resp).await;
+    assert_eq!(log.total, 2);
+    assert_eq!(log.entries[0].action, "shadow_unban");
+    assert_eq!(log.entries[1].action, "shadow_ban");
+    assert_eq!(log.entries[1].reason.as_deref(), Some("Link spam"));
+    assert_eq!(log.entries[1].actor_id, Some(moderator.user.id));
+    assert_eq!(log.entries[1].actor_username.as_deref(), Some("moderator"));
+    assert_eq!(log.entries[1].target_username.as_deref(), Some("spammer"));
+
+    let resp = send!(&app, get, "/api/admin/audit-log", spammer.token);
+    assert_eq!(resp.status(), 403);
+}
+
+/// Test who can shadow-ban whom.
+#[tokio::test]
+async fn test_shadow_ban_permissions() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool);
+    let moderator = register_user!(&app, "moderator");
+    let admin = register_user!(&app, "admin");
+    let user = register_user!(&app, "user");
+    set_role(&pool, "moderator", "moderator").await;
+    set_role(&pool, "admin", "admin").await;
+    let ban = ShadowBanRequest::default();
+
+    let uri = format!("/api/admin/users/{}/shadow-ban", moderator.user.id);
+    assert_eq!(send!(&app, put, &uri, user.token, &ban).status(), 403);
+    assert_eq!(send!(&app, put, &uri, moderator.token, &ban).status(), 400);
+
+    let uri = format!("/api/admin/users/{}/shadow-ban", admin.user.id);
+    assert_eq!(send!(&app, put, &uri, moderator.token, &ban).status(), 400);
+
+    let resp = send!(
+        &app,
+        put,
+        "/api/admin/users/999/shadow-ban",
+        moderator.token,
+        &ban
+    );
+    assert_eq!(resp.status(), 404);
+
+    let uri = format!("/api/admin/users/{}/shadow-ban", user.user.id);
+    let long = ShadowBanRequest {
+        reason: Some("x".repeat(501)),
+    };
+    assert_eq!(send!(&app, put, &uri, moderator.token, &long).status(), 400);
+    assert_eq!(send!(&app, put, &uri, admin.token, &ban).status(), 200);
+}
@@ -0,0 +1,264 @@
+//! Integration tests for the site-wide settings.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    AuthResponse, ErrorResponse, FeaturesResponse, InviteDto, RegisterRequest, RegistrationMode,
+    SiteSettingsDto, UpdateSiteSettingsRequest,
+};
+
+use blog_server::application::{AuthService, SiteService};
+use blog_server::data::{
+    FeatureFlagRepository, InviteRepository, RecoveryCodeRepository, ServiceAccountRepository,
+    SiteSettingsRepository, UserRepository,
+};
+use blog_server::domain::{AuthorTimezones, FeedUrls, render_atom, render_rss};
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app, registering in `$mode` until an admin
+/// changes it.
+macro_rules! init_app {
+    ($pool:expr, $mode:expr) => {{
+        let site_service = SiteService::new(Arc::new(SiteSettingsRepository::new($pool.clone())))
+            .with_default_registration_mode($mode);
+        let auth_service = AuthService::new(
+            Arc::new(UserRepository::new($pool.clone())),
+            Arc::new(FeatureFlagRepository::new($pool.clone())),
+            Arc::new(RecoveryCodeRepository::new($pool.clone())),
+            Arc::new(ServiceAccountRepository::new($pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        )
+        .with_invites(Arc::new(InviteRepository::new($pool.clone())))
+        .with_site(site_service.clone());
+
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(site_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user with an optional invite code and get the response.
+macro_rules! register {
+    ($app:expr, $username:expr, $invite_code:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: $invite_code,
+        };
+
+        test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Macro to get the public site settings.
+macro_rules! settings {
+    ($app:expr) => {{
+        let resp = test::TestRequest::get()
+            .uri("/api/site/settings")
+            .send_request($app)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let settings: SiteSettingsDto = test::read_body_json(resp).await;
+        settings
+    }};
+}
+
+/// Macro to change the site settings as the user with `$token`.
+macro_rules! update_settings {
+    ($app:expr, $token:expr, $req:expr) => {{
+        test::TestRequest::put()
+            .uri("/api/admin/settings")
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json($req)
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Macro to get the public features.
+macro_rules! features {
+    ($app:expr) => {{
+        let resp = test::TestRequest::get()
+            .uri("/api/features")
+            .send_request($app)
+            .await;
+        let features: FeaturesResponse = test::read_body_json(resp).await;
+        features
+    }};
+}
+
+/// Macro to register the first user as an admin and get their token.
+macro_rules! register_admin {
+    ($app:expr, $pool:expr) => {{
+        let resp = register!($app, "admin", None);
+        assert_eq!(resp.status(), 201);
+        let auth: AuthResponse = test::read_body_json(resp).await;
+        sqlx::query("UPDATE users SET role = 'admin' WHERE username = 'admin'")
+            .execute(&$pool)
+            .await
+            .unwrap();
+        auth.token
+    }};
+}
+
+/// Admins change the settings field by field; everyone can read them.
+#[tokio::test]
+async fn test_update_site_settings() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool, RegistrationMode::Open);
+    let admin_token = register_admin!(&app, pool);
+
+    assert_eq!(settings!(&app), SiteSettingsDto::default());
+
+    let req = UpdateSiteSettingsRequest {
+        title: Some("  Rust Notes ".to_string()),
+        description: Some("Short posts about Rust".to_string()),
+        posts_per_page: Some(25),
+        ..Default::default()
+    };
+    let resp = update_settings!(&app, admin_token, &req);
+    assert_eq!(resp.status(), 200);
+    let updated: SiteSettingsDto = test::read_body_json(resp).await;
+    assert_eq!(updated.title, "Rust Notes");
+    assert!(updated.updated_at.is_some());
+    assert_eq!(settings!(&app), updated);
+
+    let req = UpdateSiteSettingsRequest {
+        default_language: Some("ru".to_string()),
+        ..Default::default()
+    };
+    update_settings!(&app, admin_token, &req);
+    let settings = settings!(&app);
+    assert_eq!(settings.default_language, "ru");
+    assert_eq!(settings.title, "Rust Notes");
+    assert_eq!(settings.posts_per_page, 25);
+
+    for req in [
+        UpdateSiteSettingsRequest {
+            title: Some(" ".to_string()),
+            ..Default::default()
+        },
+        UpdateSiteSettingsRequest {
+            posts_per_page: Some(0),
+            ..Default::default()
+        },
+        UpdateSiteSettingsRequest {
+            default_language: Some("xx".to_string()),
+            ..Default::default()
+        },
+    ] {
+        let resp = update_settings!(&app, admin_token, &req);
+        assert_eq!(resp.status(), 400);
+        let error: ErrorResponse = test::read_body_json(resp).await;
+        assert_eq!(error.code.as_deref(), Some("validation"));
+    }
+    assert_eq!(settings!(&app).title, "Rust Notes");
+
+    let resp = register!(&app, "reader", None);
+    let reader: AuthResponse = test::read_body_json(resp).await;
+    let req = UpdateSiteSettingsRequest {
+        title: Some("Mine".to_string()),
+        ..Default::default()
+    };
+    let resp = update_settings!(&app, reader.token, &req);
+    assert_eq!(resp.status(), 403);
+}
+
+/// The registration mode setting opens, closes, or gates registration.
+#[tokio::test]
+async fn test_registration_mode_setting() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool, RegistrationMode::Open);
+    let admin_token = register_admin!(&app, pool);
+
+    let closed = UpdateSiteSettingsRequest {
+        registration_mode: Some(RegistrationMode::Closed),
+        ..Default::default()
+    };
+    update_settings!(&app, admin_token, &closed);
+    assert!(!features!(&app).registration);
+    assert_eq!(register!(&app, "bob", None).status(), 403);
+
+    let invite_only = UpdateSiteSettingsRequest {
+        registration_mode: Some(RegistrationMode::InviteOnly),
+        ..Default::default()
+    };
+    update_settings!(&app, admin_token, &invite_only);
+    let features = features!(&app);
+    assert!(features.registration && features.invite_only);
+    assert_eq!(register!(&app, "bob", None).status(), 400);
+    let resp = test::TestRequest::post()
+        .uri("/api/invites")
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 201);
+    let invite: InviteDto = test::read_body_json(resp).await;
+    assert_eq!(register!(&app, "bob", Some(invite.code)).status(), 201);
+
+    let open = UpdateSiteSettingsRequest {
+        registration_mode: Some(RegistrationMode::Open),
+        ..Default::default()
+    };
+    update_settings!(&app, admin_token, &open);
+    assert!(!features!(&app).invite_only);
+    assert_eq!(register!(&app, "carol", None).status(), 201);
+    let resp = test::TestRequest::post()
+        .uri("/api/invites")
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 403);
+}
+
+/// Without a stored setting, the configured default mode applies.
+#[tokio::test]
+async fn test_default_registration_mode() {
+    let pool = setup_test_db().await;
+    let app = init_app!(pool, RegistrationMode::InviteOnly);
+
+    assert_eq!(
+        settings!(&app).registration_mode,
+        RegistrationMode::InviteOnly
+    );
+    assert!(features!(&app).invite_only);
+    // The very first user needs no invite
+    register_admin!(&app, pool);
+    assert_eq!(register!(&app, "bob", None).status(), 400);
+}
+
+/// Feeds carry the site's title, description and language.
+#[tokio::test]
+async fn test_feeds_use_site_settings() {
+    let urls = FeedUrls::new("http://api.test", "http://site.test", None);
+    let mut site = SiteSettingsDto {
+        title: "Rust & Notes".to_string(),
+        default_language: "ru".to_string(),
+        ..Default::default()
+    };
+
+    let rss = render_rss(&[], &site, &urls, &AuthorTimezones::new());
+    assert!(rss.contains("<title>Rust &amp; Notes</title>"));
+    assert!(rss.contains("<description>Newest posts on Rust &amp; Notes</description>"));
+    assert!(rss.contains("<language>ru</language>"));
+
+    site.description = "Short posts".to_string();
+    let atom = render_atom(&[], &site, &urls, &AuthorTimezones::new());
+    assert!(atom.contains("<title>Rust &amp; Notes</title>"));
+    assert!(atom.contains("<subtitle>Short posts</subtitle>"));
+}
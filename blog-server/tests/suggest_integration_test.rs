@@ -0,0 +1,220 @@
+//! Integration tests for the `@mention` and `#tag` autocomplete endpoints.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    AuthResponse, CreatePostRequest, RegisterRequest, TagSuggestionResponse, UserSuggestionResponse,
+};
+use chrono::Utc;
+
+use blog_server::application::{AuthService, BlogService, ModerationService, SettingsService};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app, returning it with its user repository.
+macro_rules! init_app {
+    () => {{
+        let pool = setup_test_db().await;
+        let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+        let user_repo = Arc::new(UserRepository::new(pool.clone()));
+        let auth_service = AuthService::new(
+            Arc::clone(&user_repo),
+            flag_repo,
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let moderation_service =
+            ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+        let blog_service = BlogService::new(
+            Arc::new(PostRepository::new(pool.clone())),
+            EmbedProvider::ALL.to_vec(),
+            moderation_service,
+        );
+        let settings_service = SettingsService::new(Arc::clone(&user_repo));
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(blog_service))
+                .app_data(web::Data::new(settings_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await;
+        (app, user_repo)
+    }};
+}
+
+/// Macro to register a user, returning their ID and token.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        (auth_resp.user.id, auth_resp.token)
+    }};
+}
+
+/// Macro to create a post with tags.
+macro_rules! create_post {
+    ($app:expr, $token:expr, [$($tag:expr),*]) => {{
+        let req = CreatePostRequest {
+            title: "Post".to_string(),
+            content: "Content".to_string(),
+            tags: vec![$($tag.to_string()),*],
+            lang: None,
+            canonical_url: None,
+            original_source: None,
+            org: None,
+        };
+        let resp = test::TestRequest::post()
+            .uri("/api/posts")
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json(&req)
+            .send_request($app)
+            .await;
+        assert_eq!(resp.status(), 201);
+    }};
+}
+
+/// Macro to get user suggestions as usernames.
+macro_rules! suggest_users {
+    ($app:expr, $query:expr) => {{
+        let resp = test::TestRequest::get()
+            .uri(&format!("/api/users/suggest?q={}", $query))
+            .send_request($app)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let body: UserSuggestionResponse = test::read_body_json(resp).await;
+        body.users
+            .into_iter()
+            .map(|user| user.username)
+            .collect::<Vec<_>>()
+    }};
+}
+
+/// Macro to get tag suggestions as names.
+macro_rules! suggest_tags {
+    ($app:expr, $query:expr) => {{
+        let resp = test::TestRequest::get()
+            .uri(&format!("/api/tags/suggest?q={}", $query))
+            .send_request($app)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let body: TagSuggestionResponse = test::read_body_json(resp).await;
+        body.tags
+            .into_iter()
+            .map(|tag| tag.name)
+            .collect::<Vec<_>>()
+    }};
+}
+
+/// Test that users are suggested by prefix: an exact match first, then the
+/// most prolific writers, without shadow-banned users.
+#[tokio::test]
+async fn test_suggest_users_ranking() {
+    let (app, user_repo) = init_app!();
+    let (_, al_token) = register_user!(&app, "Al");
+    let (_, alice_token) = register_user!(&app, "alice");
+    register_user!(&app, "alex");
+    let (mallory_id, _) = register_user!(&app, "almallory");
+    register_user!(&app, "bob");
+    create_post!(&app, alice_token, []);
+    create_post!(&app, alice_token, []);
+    create_post!(&app, al_token, []);
+    user_repo
+        .update_shadow_banned_at(mallory_id, Some(Utc::now()), Utc::now())
+        .await
+        .unwrap();
+
+    assert_eq!(suggest_users!(&app, "AL"), vec!["Al", "alice", "alex"]);
+    assert_eq!(suggest_users!(&app, "%40ali"), vec!["alice"]);
+    assert!(suggest_users!(&app, "zed").is_empty());
+}
+
+/// Test that tags are suggested by prefix, most used first, counting only
+/// published posts of visible authors.
+#[tokio::test]
+async fn test_suggest_tags_ranking() {
+    let (app, _) = init_app!();
+    let (_, token) = register_user!(&app, "author");
+    create_post!(&app, token, ["rust", "rustc"]);
+    create_post!(&app, token, ["rustc"]);
+    create_post!(&app, token, ["ruby", "web"]);
+
+    let resp = test::TestRequest::get()
+        .uri("/api/tags/suggest?q=Ru")
+        .send_request(&app)
+        .await;
+    let body: TagSuggestionResponse = test::read_body_json(resp).await;
+    let tags: Vec<(String, i64)> = body
+        .tags
+        .into_iter()
+        .map(|tag| (tag.name, tag.count))
+        .collect();
+    assert_eq!(
+        tags,
+        vec![
+            ("rustc".to_string(), 2),
+            ("ruby".to_string(), 1),
+            ("rust".to_string(), 1)
+        ]
+    );
+
+    // An exact match comes first; a leading `#` is ignored
+    assert_eq!(suggest_tags!(&app, "%23rust"), vec!["rust", "rustc"]);
+    // No tag can contain a space, so nothing matches rather than an error
+    assert!(suggest_tags!(&app, "ru%20st").is_empty());
+    assert_eq!(suggest_tags!(&app, "").len(), 4);
+}
+
+/// Test that suggestions are publicly cacheable and prefixes are bounded.
+#[tokio::test]
+async fn test_suggestions_are_cached_and_bounded() {
+    let (app, _) = init_app!();
+    register_user!(&app, "alice");
+
+    let resp = test::TestRequest::get()
+        .uri("/api/users/suggest?q=al")
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 200);
+    let cache_control = resp.headers().get("cache-control").unwrap();
+    assert_eq!(cache_control, "public, max-age=300");
+    let etag = resp.headers().get("etag").unwrap().clone();
+
+    let resp = test::TestRequest::get()
+        .uri("/api/users/suggest?q=al")
+        .insert_header(("If-None-Match", etag))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 304);
+
+    let long = "a".repeat(33);
+    let resp = test::TestRequest::get()
+        .uri(&format!("/api/tags/suggest?q={long}"))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 400);
+}
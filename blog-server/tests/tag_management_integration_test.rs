@@ -0,0 +1,216 @@
+//! Integration tests for tag sorting, tag landing pages and tag management.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    CreatePostRequest, MergeTagRequest, PostDto, RegisterRequest, TagDto, TagListResponse,
+    TagPageResponse, TagSort, UpdateTagRequest,
+};
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+
+use blog_server::application::{AuthService, BlogService, ModerationService};
+use blog_server::constants::POPULAR_TAGS_DAYS;
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::infrastructure::clock::MockClock;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Builds the auth and blog services on a test database.
+fn services(pool: &SqlitePool) -> (AuthService, BlogService) {
+    let auth_service = AuthService::new(
+        Arc::new(UserRepository::new(pool.clone())),
+        Arc::new(FeatureFlagRepository::new(pool.clone())),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let blog_service = BlogService::new(
+        Arc::new(PostRepository::new(pool.clone())),
+        EmbedProvider::ALL.to_vec(),
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone()))),
+    );
+    (auth_service, blog_service)
+}
+
+/// Registers a user, returning their ID and token.
+async fn register(auth_service: &AuthService, username: &str) -> (i64, String) {
+    let req = RegisterRequest {
+        username: username.to_string(),
+        email: format!("{username}@example.com"),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    let auth = auth_service.register(req, None).await.unwrap();
+    (auth.user.id, auth.token)
+}
+
+/// Creates a post by `author_id` with `tags`.
+async fn create_post(blog_service: &BlogService, author_id: i64, tags: &[&str]) -> PostDto {
+    let req = CreatePostRequest {
+        title: "Post".to_string(),
+        content: "Content".to_string(),
+        tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
+    };
+    blog_service.create_post(author_id, req).await.unwrap()
+}
+
+/// Test the tag list ordered by count, by recent use, and by name, with
+/// "recent" measured from the service's clock.
+#[tokio::test]
+async fn test_tags_sorted_by_popularity() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service) = services(&pool);
+    let clock = MockClock::new(Utc::now());
+    let blog_service = blog_service.with_clock(Arc::new(clock.clone()));
+    let (author_id, _) = register(&auth_service, "alice").await;
+    for _ in 0..3 {
+        create_post(&blog_service, author_id, &["classic"]).await;
+    }
+    sqlx::query("UPDATE posts SET created_at = ?")
+        .bind(Utc::now() - Duration::days(90))
+        .execute(&pool)
+        .await
+        .unwrap();
+    create_post(&blog_service, author_id, &["trending", "classic"]).await;
+    create_post(&blog_service, author_id, &["trending"]).await;
+    create_post(&blog_service, author_id, &["async"]).await;
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(blog_service.clone()))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+    let names = |list: TagListResponse| -> Vec<String> {
+        list.tags.into_iter().map(|tag| tag.name).collect()
+    };
+
+    for (uri, expected) in [
+        ("/api/tags", ["classic", "trending", "async"]),
+        ("/api/tags?sort=popular", ["trending", "classic", "async"]),
+        ("/api/tags?sort=name", ["async", "classic", "trending"]),
+    ] {
+        let req = test::TestRequest::get().uri(uri).to_request();
+        let list: TagListResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(names(list), expected, "{uri}");
+    }
+
+    let req = test::TestRequest::get()
+        .uri("/api/tags?sort=random")
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 400);
+
+    // Once no post is recent, popularity falls back to overall use
+    clock.advance(Duration::days(POPULAR_TAGS_DAYS + 1));
+    let list = blog_service
+        .list_tags(10, None, TagSort::Popular)
+        .await
+        .unwrap();
+    assert_eq!(names(list), ["classic", "trending", "async"]);
+}
+
+/// Test admins describing, renaming, merging and deleting tags, and the tag
+/// landing page showing the description with the tag's posts.
+#[tokio::test]
+async fn test_admin_manages_tags() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service) = services(&pool);
+    let (author_id, author_token) = register(&auth_service, "alice").await;
+    let (_, admin_token) = register(&auth_service, "admin").await;
+    sqlx::query("UPDATE users SET role = 'admin' WHERE username = 'admin'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let both = create_post(&blog_service, author_id, &["rust", "rustlang"]).await;
+    let only_alias = create_post(&blog_service, author_id, &["rustlang"]).await;
+    create_post(&blog_service, author_id, &["web"]).await;
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(blog_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+    let bearer = |token: &str| ("Authorization", format!("Bearer {token}"));
+    let update = |token: &str, name: &str, req: UpdateTagRequest| {
+        test::TestRequest::put()
+            .uri(&format!("/api/admin/tags/{name}"))
+            .insert_header(bearer(token))
+            .set_json(req)
+            .to_request()
+    };
+    let describe = |description: &str| UpdateTagRequest {
+        name: None,
+        description: Some(description.to_string()),
+    };
+
+    let resp = test::call_service(&app, update(&author_token, "rust", describe("Hi"))).await;
+    assert_eq!(resp.status(), 403);
+    let resp = test::call_service(&app, update(&admin_token, "missing", describe("Hi"))).await;
+    assert_eq!(resp.status(), 404);
+    let rename = UpdateTagRequest {
+        name: Some("Web".to_string()),
+        description: None,
+    };
+    let resp = test::call_service(&app, update(&admin_token, "rust", rename)).await;
+    assert_eq!(resp.status(), 400);
+
+    let req = update(&admin_token, "RUST", describe(" The Rust language "));
+    let tag: TagDto = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(tag.description.as_deref(), Some("The Rust language"));
+    assert_eq!(tag.count, 1);
+
+    let req = test::TestRequest::post()
+        .uri("/api/admin/tags/rustlang/merge")
+        .insert_header(bearer(&admin_token))
+        .set_json(MergeTagRequest {
+            into: "rust".to_string(),
+        })
+        .to_request();
+    let tag: TagDto = test::call_and_read_body_json(&app, req).await;
+    assert_eq!((tag.name.as_str(), tag.count), ("rust", 2));
+
+    let req = test::TestRequest::get().uri("/api/tags/rust").to_request();
+    let page: TagPageResponse = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(page.description.as_deref(), Some("The Rust language"));
+    let ids: Vec<_> = page.posts.iter().map(|post| &post.public_id).collect();
+    assert_eq!(
+        (ids, page.total),
+        (vec![&only_alias.public_id, &both.public_id], 2)
+    );
+    assert_eq!(page.posts[1].tags, ["rust"]);
+    let req = test::TestRequest::get()
+        .uri("/api/tags/rustlang")
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 404);
+
+    let req = test::TestRequest::delete()
+        .uri("/api/admin/tags/rust")
+        .insert_header(bearer(&admin_token))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 204);
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/posts/{}", both.public_id))
+        .to_request();
+    let post: PostDto = test::call_and_read_body_json(&app, req).await;
+    assert!(post.tags.is_empty());
+    let req = test::TestRequest::get().uri("/api/tags").to_request();
+    let list: TagListResponse = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(list.tags.len(), 1);
+    assert_eq!(list.tags[0].name, "web");
+}
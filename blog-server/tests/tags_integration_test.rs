@@ -0,0 +1,229 @@
+//! Integration tests for post tags and the tag list endpoint.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    AuthResponse, CreatePostRequest, PostDto, PostListResponse, RegisterRequest, TagListResponse,
+    UpdatePostRequest,
+};
+
+use blog_server::application::{AuthService, BlogService, ModerationService};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Macro to build the test app with auth and blog services.
+macro_rules! init_app {
+    () => {{
+        let pool = setup_test_db().await;
+        let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+        let user_repo = Arc::new(UserRepository::new(pool.clone()));
+        let post_repo = Arc::new(PostRepository::new(pool.clone()));
+        let auth_service = AuthService::new(
+            user_repo,
+            flag_repo,
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let moderation_service =
+            ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+        let blog_service = BlogService::new(
+            post_repo,
+            EmbedProvider::ALL.to_vec(),
+            moderation_service.clone(),
+        );
+
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(blog_service))
+                .service(web::scope("/api").service(api_routes())),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user and get their token.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp.token
+    }};
+}
+
+/// Macro to create a post with tags and return the response.
+macro_rules! create_post {
+    ($app:expr, $token:expr, $title:expr, [$($tag:expr),*]) => {{
+        let req = CreatePostRequest {
+            title: $title.to_string(),
+            content: "Content".to_string(),
+            tags: vec![$($tag.to_string()),*],
+            lang: None,
+            canonical_url: None,
+            original_source: None,
+            org: None,
+        };
+        test::TestRequest::post()
+            .uri("/api/posts")
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .set_json(&req)
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Test that tags are normalized and returned with the post.
+#[tokio::test]
+async fn test_create_post_with_tags() {
+    let app = init_app!();
+    let token = register_user!(&app, "author");
+
+    let resp = create_post!(&app, token, "Tagged", ["Rust", " web ", "rust", ""]);
+    assert_eq!(resp.status(), 201);
+    let post: PostDto = test::read_body_json(resp).await;
+    assert_eq!(post.tags, vec!["rust", "web"]);
+
+    let resp = test::TestRequest::get()
+        .uri(&format!("/api/posts/{}", post.public_id))
+        .send_request(&app)
+        .await;
+    let fetched: PostDto = test::read_body_json(resp).await;
+    assert_eq!(fetched.tags, vec!["rust", "web"]);
+}
+
+/// Test that invalid tags are rejected.
+#[tokio::test]
+async fn test_create_post_invalid_tags() {
+    let app = init_app!();
+    let token = register_user!(&app, "author");
+
+    let resp = create_post!(&app, token, "Bad", ["two words"]);
+    assert_eq!(resp.status(), 400);
+
+    let resp = create_post!(&app, token, "Too many", ["a", "b", "c", "d", "e", "f"]);
+    assert_eq!(resp.status(), 400);
+}
+
+/// Test filtering the post list by tag.
+#[tokio::test]
+async fn test_list_posts_by_tag() {
+    let app = init_app!();
+    let token = register_user!(&app, "author");
+    create_post!(&app, token, "Rust one", ["rust"]);
+    create_post!(&app, token, "Rust two", ["rust", "async"]);
+    create_post!(&app, token, "Untagged", []);
+
+    let resp = test::TestRequest::get()
+        .uri("/api/posts?tag=Rust")
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 200);
+    let list: PostListResponse = test::read_body_json(resp).await;
+    assert_eq!(list.total, 2);
+    assert!(
+        list.posts
+            .iter()
+            .all(|p| p.tags.contains(&"rust".to_string()))
+    );
+
+    let resp = test::TestRequest::get()
+        .uri("/api/posts?tag=missing")
+        .send_request(&app)
+        .await;
+    let list: PostListResponse = test::read_body_json(resp).await;
+    assert_eq!(list.total, 0);
+}
+
+/// Test that the tag list counts usage, most used first.
+#[tokio::test]
+async fn test_list_tags_with_counts() {
+    let app = init_app!();
+    let token = register_user!(&app, "author");
+    create_post!(&app, token, "One", ["rust", "web"]);
+    create_post!(&app, token, "Two", ["rust"]);
+
+    let resp = test::TestRequest::get()
+        .uri("/api/tags")
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 200);
+    let list: TagListResponse = test::read_body_json(resp).await;
+    let tags: Vec<(&str, i64)> = list
+        .tags
+        .iter()
+        .map(|t| (t.name.as_str(), t.count))
+        .collect();
+    assert_eq!(tags, vec![("rust", 2), ("web", 1)]);
+}
+
+/// Test that updating tags replaces them and leaves them alone when omitted.
+#[tokio::test]
+async fn test_update_post_tags() {
+    let app = init_app!();
+    let token = register_user!(&app, "author");
+    let post: PostDto = test::read_body_json(create_post!(&app, token, "Post", ["old"])).await;
+    let uri = format!("/api/posts/{}", post.public_id);
+
+    let req = UpdatePostRequest {
+        title: Some("Renamed".to_string()),
+        content: None,
+        tags: None,
+        canonical_url: None,
+        original_source: None,
+    };
+    let resp = test::TestRequest::put()
+        .uri(&uri)
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .set_json(&req)
+        .send_request(&app)
+        .await;
+    let updated: PostDto = test::read_body_json(resp).await;
+    assert_eq!(updated.tags, vec!["old"]);
+
+    let req = UpdatePostRequest {
+        title: None,
+        content: None,
+        tags: Some(vec!["new".to_string()]),
+        canonical_url: None,
+        original_source: None,
+    };
+    let resp = test::TestRequest::put()
+        .uri(&uri)
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .set_json(&req)
+        .send_request(&app)
+        .await;
+    let updated: PostDto = test::read_body_json(resp).await;
+    assert_eq!(updated.tags, vec!["new"]);
+
+    let resp = test::TestRequest::get()
+        .uri("/api/tags")
+        .send_request(&app)
+        .await;
+    let list: TagListResponse = test::read_body_json(resp).await;
+    assert_eq!(list.tags.len(), 1);
+    assert_eq!(list.tags[0].name, "new");
+}
@@ -0,0 +1,162 @@
+//! Integration tests for error telemetry: 5xx responses, client crash
+//! reports, and Sentry DSNs.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use actix_web::middleware::from_fn;
+use actix_web::{App, HttpResponse, http::StatusCode, test, web};
+use async_trait::async_trait;
+use blog_shared::ClientErrorReport;
+
+use blog_server::application::TelemetryService;
+use blog_server::domain::{AppError, ErrorReport, ErrorReporter, ErrorSource};
+use blog_server::infrastructure::sentry::SentryDsn;
+use blog_server::presentation::http_handlers::api_routes;
+use blog_server::presentation::request_id::request_id;
+use blog_server::presentation::telemetry::report_server_errors;
+
+/// Reporter keeping reports in memory.
+#[derive(Default)]
+struct RecordingReporter {
+    reports: Mutex<Vec<ErrorReport>>,
+}
+
+#[async_trait]
+impl ErrorReporter for RecordingReporter {
+    async fn report(&self, report: &ErrorReport) -> Result<(), AppError> {
+        self.reports.lock().unwrap().push(report.clone());
+        Ok(())
+    }
+}
+
+impl RecordingReporter {
+    /// Waits for the background deliveries, then returns the reports.
+    async fn reports(&self, expected: usize) -> Vec<ErrorReport> {
+        for _ in 0..100 {
+            if self.reports.lock().unwrap().len() >= expected {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        self.reports.lock().unwrap().clone()
+    }
+}
+
+async fn failing() -> Result<HttpResponse, AppError> {
+    Err(AppError::Internal("database is on fire".to_string()))
+}
+
+async fn missing() -> Result<HttpResponse, AppError> {
+    Err(AppError::PostNotFound)
+}
+
+#[actix_web::test]
+async fn test_server_errors_reported_with_request_context() {
+    let reporter = Arc::new(RecordingReporter::default());
+    let telemetry = TelemetryService::new().with_reporter(reporter.clone());
+    let app = test::init_service(
+        App::new()
+            .wrap(from_fn(report_server_errors))
+            .wrap(from_fn(request_id))
+            .app_data(web::Data::new(telemetry))
+            .route("/fail", web::get().to(failing))
+            .route("/missing", web::get().to(missing)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/missing").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let req = test::TestRequest::get()
+        .uri("/fail?token=secret")
+        .insert_header(("X-Request-Id", "req-42"))
+        .insert_header(("User-Agent", "test-agent"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+    let reports = reporter.reports(1).await;
+    assert_eq!(reports.len(), 1, "only the 5xx is reported");
+    let report = &reports[0];
+    assert_eq!(report.source, ErrorSource::Server);
+    assert!(report.message.contains("database is on fire"));
+    let request = report.request.as_ref().unwrap();
+    assert_eq!(request.method, "GET");
+    assert_eq!(request.path, "/fail");
+    assert_eq!(request.request_id.as_deref(), Some("req-42"));
+    assert_eq!(request.status, Some(500));
+    assert_eq!(request.user_agent.as_deref(), Some("test-agent"));
+}
+
+#[actix_web::test]
+async fn test_client_error_reports_forwarded() {
+    let reporter = Arc::new(RecordingReporter::default());
+    let telemetry = TelemetryService::new().with_reporter(reporter.clone());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(telemetry))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    let report = ClientErrorReport::new("x".repeat(5000))
+        .with_location("src/lib.rs:1:1")
+        .with_url("http://localhost:8081/posts/1");
+    let req = test::TestRequest::post()
+        .uri("/api/telemetry/errors")
+        .insert_header(("Content-Type", "text/plain;charset=UTF-8"))
+        .set_payload(serde_json::to_string(&report).unwrap())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+
+    let req = test::TestRequest::post()
+        .uri("/api/telemetry/errors")
+        .set_payload("not json")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let reports = reporter.reports(1).await;
+    assert_eq!(reports.len(), 1);
+    let forwarded = &reports[0];
+    assert_eq!(forwarded.source, ErrorSource::Client);
+    assert!(forwarded.message.chars().count() <= 2000);
+    assert_eq!(forwarded.location.as_deref(), Some("src/lib.rs:1:1"));
+    assert_eq!(
+        forwarded.url.as_deref(),
+        Some("http://localhost:8081/posts/1")
+    );
+}
+
+#[actix_web::test]
+async fn test_client_error_reports_dropped_without_reporter() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(TelemetryService::new()))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/telemetry/errors")
+        .set_payload(r#"{"message":"boom"}"#)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+}
+
+#[actix_web::test]
+async fn test_sentry_dsn_parsing() {
+    let dsn = SentryDsn::parse("https://abc123@o1.ingest.sentry.io/42").unwrap();
+    assert_eq!(dsn.store_url(), "https://o1.ingest.sentry.io/api/42/store/");
+
+    let dsn = SentryDsn::parse("http://key@localhost:9000/sentry/7").unwrap();
+    assert_eq!(dsn.store_url(), "http://localhost:9000/sentry/api/7/store/");
+
+    assert!(SentryDsn::parse("https://o1.ingest.sentry.io/42").is_err());
+    assert!(SentryDsn::parse("https://key@o1.ingest.sentry.io/").is_err());
+    assert!(SentryDsn::parse("not a url").is_err());
+}
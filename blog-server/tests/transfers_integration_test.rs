@@ -0,0 +1,254 @@
+//! Integration tests for handing posts over to other users.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    CreatePostRequest, PostDto, PostTransferDto, RegisterRequest, TransferListResponse,
+    TransferPostRequest, TransferStatus,
+};
+use sqlx::SqlitePool;
+
+use blog_server::application::{
+    AuthService, BlogService, MessageService, ModerationService, TransferService,
+};
+use blog_server::data::{
+    AdminRepository, FeatureFlagRepository, MessageRepository, ModerationRepository,
+    PostRepository, RecoveryCodeRepository, ServiceAccountRepository, TransferRepository,
+    UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Builds the services taking part in transfers on a test database.
+fn services(pool: &SqlitePool) -> (AuthService, BlogService, TransferService, MessageService) {
+    let user_repo = Arc::new(UserRepository::new(pool.clone()));
+    let post_repo = Arc::new(PostRepository::new(pool.clone()));
+    let message_repo = Arc::new(MessageRepository::new(pool.clone()));
+    let auth_service = AuthService::new(
+        Arc::clone(&user_repo),
+        Arc::new(FeatureFlagRepository::new(pool.clone())),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let blog_service = BlogService::new(
+        Arc::clone(&post_repo),
+        EmbedProvider::ALL.to_vec(),
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone()))),
+    );
+    let transfer_service = TransferService::new(
+        Arc::new(TransferRepository::new(pool.clone())),
+        post_repo,
+        Arc::clone(&user_repo),
+        Arc::clone(&message_repo),
+        Arc::new(AdminRepository::new(pool.clone())),
+    );
+    let message_service = MessageService::new(message_repo, user_repo);
+    (
+        auth_service,
+        blog_service,
+        transfer_service,
+        message_service,
+    )
+}
+
+/// Registers a user, returning their ID, public ID and token.
+async fn register(auth_service: &AuthService, username: &str) -> (i64, String, String) {
+    let req = RegisterRequest {
+        username: username.to_string(),
+        email: format!("{username}@example.com"),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    let auth = auth_service.register(req, None).await.unwrap();
+    (auth.user.id, auth.user.public_id, auth.token)
+}
+
+/// Test an author offering a post, the recipient accepting it, and both
+/// being notified, with the handover in the audit log.
+#[tokio::test]
+async fn test_post_transfer_accepted() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service, transfer_service, message_service) = services(&pool);
+    let (alice_id, _, alice_token) = register(&auth_service, "alice").await;
+    let (bob_id, bob_public_id, bob_token) = register(&auth_service, "bob").await;
+    let (_, _, mallory_token) = register(&auth_service, "mallory").await;
+    let post = blog_service
+        .create_post(
+            alice_id,
+            CreatePostRequest {
+                title: "Handover".to_string(),
+                content: "Content".to_string(),
+                tags: Vec::new(),
+                lang: None,
+                canonical_url: None,
+                original_source: None,
+                org: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(blog_service))
+            .app_data(web::Data::new(transfer_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+    let bearer = |token: &str| ("Authorization", format!("Bearer {token}"));
+    let offer = |token: &str, username: &str| {
+        test::TestRequest::post()
+            .uri(&format!("/api/posts/{}/transfer", post.public_id))
+            .insert_header(bearer(token))
+            .set_json(TransferPostRequest {
+                username: username.to_string(),
+            })
+            .to_request()
+    };
+    let answer = |token: &str, id: i64, action: &str| {
+        test::TestRequest::post()
+            .uri(&format!("/api/transfers/{id}/{action}"))
+            .insert_header(bearer(token))
+            .to_request()
+    };
+
+    let resp = test::call_service(&app, offer(&mallory_token, "mallory")).await;
+    assert_eq!(resp.status(), 403);
+    let resp = test::call_service(&app, offer(&alice_token, "alice")).await;
+    assert_eq!(resp.status(), 400);
+    let resp = test::call_service(&app, offer(&alice_token, "nobody")).await;
+    assert_eq!(resp.status(), 404);
+
+    let resp = test::call_service(&app, offer(&alice_token, "Bob")).await;
+    assert_eq!(resp.status(), 201);
+    let transfer: PostTransferDto = test::read_body_json(resp).await;
+    assert_eq!(transfer.status, TransferStatus::Pending);
+    assert_eq!(
+        (
+            transfer.to_user_public_id.as_str(),
+            transfer.post_title.as_str()
+        ),
+        (bob_public_id.as_str(), "Handover")
+    );
+    let resp = test::call_service(&app, offer(&alice_token, "mallory")).await;
+    assert_eq!(resp.status(), 400);
+    assert_eq!(
+        message_service.unread_count(bob_id).await.unwrap().unread,
+        1
+    );
+
+    let req = test::TestRequest::get()
+        .uri("/api/transfers")
+        .insert_header(bearer(&bob_token))
+        .to_request();
+    let list: TransferListResponse = test::call_and_read_body_json(&app, req).await;
+    assert_eq!((list.incoming.len(), list.outgoing.len()), (1, 0));
+    assert_eq!(list.incoming[0].from_username, "alice");
+
+    let resp = test::call_service(&app, answer(&mallory_token, transfer.id, "accept")).await;
+    assert_eq!(resp.status(), 404);
+    let resp = test::call_service(&app, answer(&alice_token, transfer.id, "accept")).await;
+    assert_eq!(resp.status(), 403);
+    let resp = test::call_service(&app, answer(&bob_token, transfer.id, "cancel")).await;
+    assert_eq!(resp.status(), 403);
+
+    let req = answer(&bob_token, transfer.id, "accept");
+    let accepted: PostTransferDto = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(accepted.status, TransferStatus::Accepted);
+    assert!(accepted.resolved_at.is_some());
+    assert_eq!(
+        message_service.unread_count(alice_id).await.unwrap().unread,
+        1
+    );
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/posts/{}", post.public_id))
+        .insert_header(bearer(&bob_token))
+        .to_request();
+    let fetched: PostDto = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(fetched.author_public_id, bob_public_id);
+    assert!(fetched.is_owner);
+
+    // The previous author lost the post; a closed transfer cannot be answered again
+    let req = test::TestRequest::delete()
+        .uri(&format!("/api/posts/{}", post.public_id))
+        .insert_header(bearer(&alice_token))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 403);
+    let resp = test::call_service(&app, answer(&bob_token, transfer.id, "decline")).await;
+    assert_eq!(resp.status(), 404);
+
+    let log = AdminRepository::new(pool.clone())
+        .list_audit_log(10, 0)
+        .await
+        .unwrap();
+    assert_eq!(log.len(), 1);
+    assert_eq!(log[0].action, "post_transfer");
+    assert_eq!(
+        (log[0].actor_id, log[0].target_user_id),
+        (Some(alice_id), Some(bob_id))
+    );
+}
+
+/// Test transfers being declined by the recipient and cancelled by the sender,
+/// leaving the post with its author.
+#[tokio::test]
+async fn test_post_transfer_declined_or_cancelled() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service, transfer_service, _) = services(&pool);
+    let (alice_id, _, _) = register(&auth_service, "alice").await;
+    let (bob_id, _, _) = register(&auth_service, "bob").await;
+    let post = blog_service
+        .create_post(
+            alice_id,
+            CreatePostRequest {
+                title: "Kept".to_string(),
+                content: "Content".to_string(),
+                tags: Vec::new(),
+                lang: None,
+                canonical_url: None,
+                original_source: None,
+                org: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let offered = transfer_service
+        .transfer_post(post.id, alice_id, "bob")
+        .await
+        .unwrap();
+    let declined = transfer_service
+        .decline_transfer(offered.id, bob_id)
+        .await
+        .unwrap();
+    assert_eq!(declined.status, TransferStatus::Declined);
+
+    let offered = transfer_service
+        .transfer_post(post.id, alice_id, "bob")
+        .await
+        .unwrap();
+    let cancelled = transfer_service
+        .cancel_transfer(offered.id, alice_id)
+        .await
+        .unwrap();
+    assert_eq!(cancelled.status, TransferStatus::Cancelled);
+
+    let list = transfer_service.list_transfers(alice_id).await.unwrap();
+    assert!(list.incoming.is_empty() && list.outgoing.is_empty());
+    let post = blog_service.get_post(post.id, None).await.unwrap();
+    assert_eq!(post.author_id, alice_id);
+    let log = AdminRepository::new(pool.clone())
+        .list_audit_log(10, 0)
+        .await
+        .unwrap();
+    assert!(log.is_empty());
+}
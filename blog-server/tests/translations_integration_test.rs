@@ -0,0 +1,213 @@
+//! Integration tests for post translations.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    CreatePostRequest, CreateTranslationRequest, PostDto, PostListResponse, RegisterRequest,
+    TranslationListResponse,
+};
+use sqlx::SqlitePool;
+
+use blog_server::application::{AuthService, BlogService, ModerationService};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Builds the auth and blog services on a test database.
+fn services(pool: &SqlitePool) -> (AuthService, BlogService) {
+    let auth_service = AuthService::new(
+        Arc::new(UserRepository::new(pool.clone())),
+        Arc::new(FeatureFlagRepository::new(pool.clone())),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let blog_service = BlogService::new(
+        Arc::new(PostRepository::new(pool.clone())),
+        EmbedProvider::ALL.to_vec(),
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone()))),
+    );
+    (auth_service, blog_service)
+}
+
+/// Registers a user, returning their ID and token.
+async fn register(auth_service: &AuthService, username: &str) -> (i64, String) {
+    let req = RegisterRequest {
+        username: username.to_string(),
+        email: format!("{username}@example.com"),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    let auth = auth_service.register(req, None).await.unwrap();
+    (auth.user.id, auth.token)
+}
+
+/// Builds a translation request.
+fn translation(lang: &str, title: &str) -> CreateTranslationRequest {
+    CreateTranslationRequest {
+        lang: lang.to_string(),
+        title: title.to_string(),
+        content: "Body".to_string(),
+        tags: Vec::new(),
+    }
+}
+
+/// Test adding a translation and listing the variants of either post.
+#[tokio::test]
+async fn test_translations_share_a_group() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service) = services(&pool);
+    let (author_id, token) = register(&auth_service, "alice").await;
+    let original = blog_service
+        .create_post(
+            author_id,
+            CreatePostRequest {
+                title: "Hello".to_string(),
+                content: "World".to_string(),
+                tags: Vec::new(),
+                lang: None,
+                canonical_url: None,
+                original_source: None,
+                org: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(original.lang, "en");
+    assert_eq!(original.translation_group_id, original.public_id);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(blog_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/api/posts/{}/translations", original.public_id))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(translation("pt-br", "Olá"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 201);
+    let translated: PostDto = test::read_body_json(resp).await;
+    assert_eq!(translated.lang, "pt-BR");
+    assert_eq!(
+        translated.translation_group_id,
+        original.translation_group_id
+    );
+
+    for id in [&original.public_id, &translated.public_id] {
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/posts/{id}/translations"))
+            .to_request();
+        let list: TranslationListResponse = test::call_and_read_body_json(&app, req).await;
+        let langs: Vec<_> = list.translations.iter().map(|t| t.lang.as_str()).collect();
+        assert_eq!(langs, ["en", "pt-BR"]);
+    }
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/api/posts/{}/translations", translated.public_id))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(translation("en", "Hello again"))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 400);
+}
+
+/// Test only the author being able to translate a post.
+#[tokio::test]
+async fn test_translation_requires_author() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service) = services(&pool);
+    let (author_id, _) = register(&auth_service, "alice").await;
+    let (_, other_token) = register(&auth_service, "bob").await;
+    let post = blog_service
+        .create_post(
+            author_id,
+            CreatePostRequest {
+                title: "Hello".to_string(),
+                content: "World".to_string(),
+                tags: Vec::new(),
+                lang: None,
+                canonical_url: None,
+                original_source: None,
+                org: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(blog_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/api/posts/{}/translations", post.public_id))
+        .insert_header(("Authorization", format!("Bearer {other_token}")))
+        .set_json(translation("de", "Hallo"))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 403);
+}
+
+/// Test filtering post lists by language and rejecting bad codes.
+#[tokio::test]
+async fn test_list_filters_by_language() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service) = services(&pool);
+    let (author_id, _) = register(&auth_service, "alice").await;
+    for (title, lang) in [("Hello", None), ("Bonjour", Some("fr"))] {
+        blog_service
+            .create_post(
+                author_id,
+                CreatePostRequest {
+                    title: title.to_string(),
+                    content: "Body".to_string(),
+                    tags: Vec::new(),
+                    lang: lang.map(str::to_string),
+                    canonical_url: None,
+                    original_source: None,
+                    org: None,
+                },
+            )
+            .await
+            .unwrap();
+    }
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(blog_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/posts?lang=fr")
+        .to_request();
+    let list: PostListResponse = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(list.total, 1);
+    assert_eq!(list.posts[0].title, "Bonjour");
+
+    let req = test::TestRequest::get().uri("/api/posts").to_request();
+    let list: PostListResponse = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(list.total, 2);
+
+    let req = test::TestRequest::get()
+        .uri("/api/posts?lang=english")
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 400);
+}
@@ -0,0 +1,206 @@
+//! Integration tests for deleting posts to the trash and restoring them.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::{
+    CreatePostRequest, PostDto, PostListResponse, RegisterRequest, TrashListResponse,
+};
+use chrono::{Duration, TimeZone, Utc};
+use sqlx::SqlitePool;
+
+use blog_server::application::{AuthService, BlogService, ModerationService, RetentionService};
+use blog_server::data::{
+    FeatureFlagRepository, ModerationRepository, PostRepository, RecoveryCodeRepository,
+    RetentionRepository, ServiceAccountRepository, UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::infrastructure::clock::MockClock;
+use blog_server::infrastructure::config::RetentionConfig;
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Deleted posts kept in the trash for 30 days.
+const CONFIG: RetentionConfig = RetentionConfig {
+    audit_log_months: None,
+    unsubscribed_days: None,
+    trash_days: Some(30),
+};
+
+/// Builds the auth and blog services on a test database.
+fn services(pool: &SqlitePool) -> (AuthService, BlogService) {
+    let auth_service = AuthService::new(
+        Arc::new(UserRepository::new(pool.clone())),
+        Arc::new(FeatureFlagRepository::new(pool.clone())),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let blog_service = BlogService::new(
+        Arc::new(PostRepository::new(pool.clone())),
+        EmbedProvider::ALL.to_vec(),
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone()))),
+    )
+    .with_trash_days(CONFIG.trash_days);
+    (auth_service, blog_service)
+}
+
+/// Registers a user, returning their ID and token.
+async fn register(auth_service: &AuthService, username: &str) -> (i64, String) {
+    let req = RegisterRequest {
+        username: username.to_string(),
+        email: format!("{username}@example.com"),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    let auth = auth_service.register(req, None).await.unwrap();
+    (auth.user.id, auth.token)
+}
+
+/// Creates a post by `author_id`.
+async fn create_post(blog_service: &BlogService, author_id: i64, title: &str) -> PostDto {
+    let req = CreatePostRequest {
+        title: title.to_string(),
+        content: "Content".to_string(),
+        tags: Vec::new(),
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
+    };
+    blog_service.create_post(author_id, req).await.unwrap()
+}
+
+/// Test deleted posts being hidden, listed in the author's trash, and
+/// restorable only by the author.
+#[tokio::test]
+async fn test_deleted_posts_can_be_restored() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service) = services(&pool);
+    let (author_id, author_token) = register(&auth_service, "alice").await;
+    let (_, other_token) = register(&auth_service, "mallory").await;
+    let kept = create_post(&blog_service, author_id, "Kept")
+        .await
+        .public_id;
+    let deleted = create_post(&blog_service, author_id, "Deleted")
+        .await
+        .public_id;
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(blog_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+    let bearer = |token: &str| ("Authorization", format!("Bearer {token}"));
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/api/posts/{deleted}"))
+        .insert_header(bearer(&author_token))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 204);
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/posts/{deleted}"))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 404);
+    let req = test::TestRequest::get().uri("/api/posts").to_request();
+    let list: PostListResponse = test::call_and_read_body_json(&app, req).await;
+    let ids: Vec<_> = list
+        .posts
+        .iter()
+        .map(|post| post.public_id.as_str())
+        .collect();
+    assert_eq!((ids, list.total), (vec![kept.as_str()], 1));
+
+    let req = test::TestRequest::get()
+        .uri("/api/posts/trash")
+        .insert_header(bearer(&author_token))
+        .to_request();
+    let trash: TrashListResponse = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(trash.posts.len(), 1);
+    assert_eq!(trash.posts[0].public_id, deleted);
+    assert_eq!(trash.posts[0].title, "Deleted");
+    assert_eq!(trash.posts[0].days_until_purge, Some(30));
+    assert_eq!(
+        trash.posts[0].purge_at,
+        Some(trash.posts[0].deleted_at + Duration::days(30))
+    );
+
+    let req = test::TestRequest::get()
+        .uri("/api/posts/trash")
+        .insert_header(bearer(&other_token))
+        .to_request();
+    let trash: TrashListResponse = test::call_and_read_body_json(&app, req).await;
+    assert!(trash.posts.is_empty());
+
+    // The old path still serves the same list
+    let req = test::TestRequest::get()
+        .uri("/api/me/trash")
+        .insert_header(bearer(&author_token))
+        .to_request();
+    let legacy: TrashListResponse = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(legacy.posts.len(), 1);
+    assert_eq!(legacy.posts[0].public_id, deleted);
+
+    let restore = |token: &str, id: &str| {
+        test::TestRequest::post()
+            .uri(&format!("/api/posts/{id}/restore"))
+            .insert_header(bearer(token))
+            .to_request()
+    };
+    let resp = test::call_service(&app, restore(&other_token, &deleted)).await;
+    assert_eq!(resp.status(), 403);
+    let resp = test::call_service(&app, restore(&author_token, &kept)).await;
+    assert_eq!(resp.status(), 404);
+    let resp = test::call_service(&app, restore(&author_token, &deleted)).await;
+    assert_eq!(resp.status(), 204);
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/posts/{deleted}"))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+    let req = test::TestRequest::get()
+        .uri("/api/posts/trash")
+        .insert_header(bearer(&author_token))
+        .to_request();
+    let trash: TrashListResponse = test::call_and_read_body_json(&app, req).await;
+    assert!(trash.posts.is_empty());
+}
+
+/// Test the retention job purging posts once their trash time is over.
+#[tokio::test]
+async fn test_trash_is_purged_after_retention() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service) = services(&pool);
+    let clock = MockClock::new(Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap());
+    let blog_service = blog_service.with_clock(Arc::new(clock.clone()));
+    let (author_id, _) = register(&auth_service, "alice").await;
+    let id = create_post(&blog_service, author_id, "Deleted").await.id;
+    blog_service.delete_post(id, author_id).await.unwrap();
+
+    let service = RetentionService::new(Arc::new(RetentionRepository::new(pool.clone())), CONFIG)
+        .with_clock(Arc::new(clock.clone()));
+    clock.advance(Duration::days(29));
+    assert_eq!(service.purge_due().await.unwrap(), 0);
+    let trash = blog_service.list_trash(author_id).await.unwrap();
+    assert_eq!(trash.posts.len(), 1);
+    assert_eq!(trash.posts[0].days_until_purge, Some(1));
+
+    clock.advance(Duration::days(2));
+    assert_eq!(service.purge_due().await.unwrap(), 1);
+    assert!(
+        blog_service
+            .list_trash(author_id)
+            .await
+            .unwrap()
+            .posts
+            .is_empty()
+    );
+    assert!(blog_service.restore_post(id, author_id).await.is_err());
+}
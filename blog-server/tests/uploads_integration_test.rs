@@ -0,0 +1,483 @@
+//! Integration tests for content-addressed uploads.
+
+mod common;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use actix_web::{App, test, web};
+use blog_shared::constants::MAX_UPLOAD_BYTES;
+use blog_shared::{AuthResponse, RegisterRequest, UploadDto};
+use chrono::{Duration, Utc};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::UnixListener;
+
+use blog_server::application::{AuthService, UploadService};
+use blog_server::constants::UPLOAD_URL_TTL_SECS;
+use blog_server::data::{
+    AttachmentRepository, FeatureFlagRepository, RecoveryCodeRepository, ServiceAccountRepository,
+    UserRepository,
+};
+use blog_server::domain::content_hash;
+use blog_server::infrastructure::blob_store::BlobStore;
+use blog_server::infrastructure::clamav::ClamAv;
+use blog_server::infrastructure::clock::{Clock, MockClock, SystemClock};
+use blog_server::infrastructure::url_signing::{derive_upload_key, sign_upload};
+use blog_server::presentation::http_handlers::api_routes;
+use blog_server::presentation::upload_handlers::serve_upload;
+
+use common::{setup_test_db, test_jwt};
+
+/// Key signing URLs to private uploads.
+const SIGNING_KEY: &str = "test-upload-signing-key";
+
+/// Bytes of a (truncated) PNG file.
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\nnot really an image";
+
+/// Returns a fresh upload directory for one test.
+fn upload_dir() -> PathBuf {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let n = NEXT.fetch_add(1, Ordering::Relaxed);
+    std::env::temp_dir().join(format!("blog-uploads-{}-{n}", std::process::id()))
+}
+
+/// Macro to build the test app storing uploads in `$dir`, optionally
+/// scanning them with `$scanner` and telling the time with `$clock`.
+macro_rules! init_app {
+    ($dir:expr) => {
+        init_app!($dir, None)
+    };
+    ($dir:expr, $scanner:expr) => {
+        init_app!($dir, $scanner, Arc::new(SystemClock))
+    };
+    ($dir:expr, $scanner:expr, $clock:expr) => {{
+        let pool = setup_test_db().await;
+        let flag_repo = Arc::new(FeatureFlagRepository::new(pool.clone()));
+        let user_repo = Arc::new(UserRepository::new(pool.clone()));
+        let auth_service = AuthService::new(
+            user_repo,
+            flag_repo,
+            Arc::new(RecoveryCodeRepository::new(pool.clone())),
+            Arc::new(ServiceAccountRepository::new(pool.clone())),
+            test_jwt(),
+            Vec::new(),
+        );
+        let upload_service = UploadService::new(
+            Arc::new(AttachmentRepository::new(pool.clone())),
+            BlobStore::new($dir),
+            "http://api.test".to_string(),
+            SIGNING_KEY.to_string(),
+            $scanner,
+        )
+        .with_clock($clock);
+
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new(auth_service))
+                .app_data(web::Data::new(upload_service))
+                .service(web::scope("/api").service(api_routes()))
+                .service(serve_upload),
+        )
+        .await
+    }};
+}
+
+/// Macro to register a user and get their token.
+macro_rules! register_user {
+    ($app:expr, $username:expr) => {{
+        let req = RegisterRequest {
+            username: $username.to_string(),
+            email: format!("{}@example.com", $username),
+            password: "secret123".to_string(),
+            invite_code: None,
+        };
+
+        let resp = test::TestRequest::post()
+            .uri("/api/auth/register")
+            .set_json(&req)
+            .send_request($app)
+            .await;
+
+        let auth_resp: AuthResponse = test::read_body_json(resp).await;
+        auth_resp.token
+    }};
+}
+
+/// Macro to upload a file, returning the response.
+macro_rules! upload {
+    ($app:expr, $token:expr, $content_type:expr, $body:expr) => {{ upload!($app, $token, $content_type, $body, "/api/uploads") }};
+    ($app:expr, $token:expr, $content_type:expr, $body:expr, $uri:expr) => {{
+        test::TestRequest::post()
+            .uri($uri)
+            .insert_header(("Authorization", format!("Bearer {}", $token)))
+            .insert_header(("Content-Type", $content_type))
+            .set_payload($body)
+            .send_request($app)
+            .await
+    }};
+}
+
+/// Test identical files from different users are stored once.
+#[tokio::test]
+async fn test_upload_deduplicates() {
+    let dir = upload_dir();
+    let clock = MockClock::new(Utc::now());
+    let app = init_app!(&dir, None, Arc::new(clock.clone()) as Arc<dyn Clock>);
+    let alice = register_user!(&app, "alice");
+    let bob = register_user!(&app, "bob");
+
+    let resp = upload!(&app, alice, "image/png", PNG);
+    assert_eq!(resp.status(), 201);
+    let hash = content_hash(PNG);
+    let location = resp.headers().get("Location").unwrap().clone();
+    let first: UploadDto = test::read_body_json(resp).await;
+    assert_eq!(first.hash, hash);
+    assert_eq!(first.url, format!("http://api.test/uploads/{}", first.id));
+    assert_eq!(location, first.url.as_str());
+    assert_eq!(first.size, PNG.len() as i64);
+
+    // Same bytes, another user: the first upload's attachment comes back,
+    // under an upload of their own, created when they uploaded it
+    clock.advance(Duration::minutes(1));
+    let resp = upload!(&app, bob, "image/png", PNG);
+    assert_eq!(resp.status(), 201);
+    let second: UploadDto = test::read_body_json(resp).await;
+    assert_eq!(second.hash, hash);
+    assert_eq!(second.content_type, "image/png");
+    assert_ne!(second.id, first.id);
+    assert_eq!(second.created_at, first.created_at + Duration::minutes(1));
+
+    // Same bytes, same user: the same upload
+    let resp = upload!(&app, alice, "image/png", PNG);
+    let again: UploadDto = test::read_body_json(resp).await;
+    assert_eq!(again.id, first.id);
+
+    let resp = upload!(&app, bob, "image/gif", "GIF89a other");
+    let other: UploadDto = test::read_body_json(resp).await;
+    assert_ne!(other.hash, hash);
+
+    let files = std::fs::read_dir(&dir).unwrap().count();
+    assert_eq!(files, 2);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test uploads are served with immutable cache headers.
+#[tokio::test]
+async fn test_serve_upload() {
+    let dir = upload_dir();
+    let app = init_app!(&dir);
+    let token = register_user!(&app, "alice");
+    let resp = upload!(&app, token, "image/png; charset=binary", PNG);
+    let upload: UploadDto = test::read_body_json(resp).await;
+    let hash = content_hash(PNG);
+
+    let resp = test::TestRequest::get()
+        .uri(&format!("/uploads/{}", upload.id))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("Content-Type").unwrap(), "image/png");
+    assert_eq!(
+        resp.headers().get("Cache-Control").unwrap(),
+        "public, max-age=31536000, immutable"
+    );
+    let etag = resp.headers().get("ETag").unwrap().clone();
+    assert_eq!(etag, format!("\"{hash}\"").as_str());
+    assert_eq!(test::read_body(resp).await.as_ref(), PNG);
+
+    let resp = test::TestRequest::get()
+        .uri(&format!("/uploads/{}", upload.id))
+        .insert_header(("If-None-Match", etag))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 304);
+
+    let unknown = content_hash(b"never uploaded");
+    for path in [unknown.as_str(), "../secret", "ABC"] {
+        let resp = test::TestRequest::get()
+            .uri(&format!("/uploads/{path}"))
+            .send_request(&app)
+            .await;
+        assert_eq!(resp.status(), 404);
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Macro to fetch an upload, returning the status code.
+macro_rules! fetch_status {
+    ($app:expr, $uri:expr) => {{
+        let resp = test::TestRequest::get().uri(&$uri).send_request($app).await;
+        resp.status().as_u16()
+    }};
+}
+
+/// Test private uploads are only served through signed, unexpired URLs.
+#[tokio::test]
+async fn test_private_upload() {
+    let dir = upload_dir();
+    let app = init_app!(&dir);
+    let alice = register_user!(&app, "alice");
+    let bob = register_user!(&app, "bob");
+
+    let resp = upload!(&app, alice, "image/png", PNG, "/api/uploads?private=true");
+    let upload: UploadDto = test::read_body_json(resp).await;
+    assert!(upload.private);
+    let id = upload.id;
+    let expires = upload.expires_at.unwrap().timestamp();
+    assert!(expires > Utc::now().timestamp());
+    let signed = upload.url.strip_prefix("http://api.test").unwrap();
+    assert!(signed.starts_with(&format!("/uploads/{id}?expires={expires}&signature=")));
+
+    let resp = test::TestRequest::get()
+        .uri(signed)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 200);
+    let cache_control = resp.headers().get("Cache-Control").unwrap();
+    assert!(
+        cache_control
+            .to_str()
+            .unwrap()
+            .starts_with("private, max-age=")
+    );
+
+    let unsigned = format!("/uploads/{id}");
+    assert_eq!(fetch_status!(&app, unsigned), 403);
+    let tampered = format!("/uploads/{id}?expires={}&signature=x", expires + 60);
+    assert_eq!(fetch_status!(&app, tampered), 403);
+    let past = Utc::now().timestamp() - 1;
+    let signature = sign_upload(&id, past, SIGNING_KEY).unwrap();
+    let expired = format!("/uploads/{id}?expires={past}&signature={signature}");
+    assert_eq!(fetch_status!(&app, expired), 403);
+    let hash = content_hash(PNG);
+    assert_eq!(fetch_status!(&app, format!("/uploads/{hash}")), 404);
+
+    // A public upload of the same content is public at its own URL only
+    let resp = upload!(&app, bob, "image/png", PNG);
+    let upload: UploadDto = test::read_body_json(resp).await;
+    assert!(!upload.private);
+    assert_eq!(upload.expires_at, None);
+    assert_eq!(fetch_status!(&app, format!("/uploads/{}", upload.id)), 200);
+    assert_eq!(fetch_status!(&app, unsigned), 403);
+    let resp = upload!(&app, alice, "image/png", PNG, "/api/uploads?private=true");
+    let upload: UploadDto = test::read_body_json(resp).await;
+    assert!(upload.private);
+    assert_eq!(upload.id, id);
+
+    // Turning an upload public or private moves it to a new URL
+    let resp = upload!(&app, alice, "image/png", PNG);
+    let public: UploadDto = test::read_body_json(resp).await;
+    assert!(!public.private);
+    assert_ne!(public.id, id);
+    assert_eq!(fetch_status!(&app, unsigned), 404);
+    let public_url = format!("/uploads/{}", public.id);
+    assert_eq!(fetch_status!(&app, public_url), 200);
+    let resp = upload!(&app, alice, "image/png", PNG, "/api/uploads?private=true");
+    let private: UploadDto = test::read_body_json(resp).await;
+    assert_ne!(private.id, public.id);
+    assert_eq!(fetch_status!(&app, public_url), 404);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test the uploader can get a freshly signed URL once the old one expires.
+#[tokio::test]
+async fn test_resign_private_upload() {
+    let dir = upload_dir();
+    let clock = MockClock::new(Utc::now());
+    let app = init_app!(&dir, None, Arc::new(clock.clone()) as Arc<dyn Clock>);
+    let alice = register_user!(&app, "alice");
+    let bob = register_user!(&app, "bob");
+
+    let resp = upload!(&app, alice, "image/png", PNG, "/api/uploads?private=true");
+    let upload: UploadDto = test::read_body_json(resp).await;
+    let old_url = upload
+        .url
+        .strip_prefix("http://api.test")
+        .unwrap()
+        .to_string();
+    assert_eq!(fetch_status!(&app, old_url), 200);
+
+    clock.advance(Duration::seconds(UPLOAD_URL_TTL_SECS + 1));
+    assert_eq!(fetch_status!(&app, old_url), 403);
+
+    let uri = format!("/api/uploads/{}", upload.id);
+    let resp = test::TestRequest::get()
+        .uri(&uri)
+        .insert_header(("Authorization", format!("Bearer {alice}")))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 200);
+    let resigned: UploadDto = test::read_body_json(resp).await;
+    assert_eq!(resigned.id, upload.id);
+    assert!(resigned.expires_at > upload.expires_at);
+    let new_url = resigned.url.strip_prefix("http://api.test").unwrap();
+    let resp = test::TestRequest::get()
+        .uri(new_url)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 200);
+    // Expiry is measured with the service's clock
+    let cache_control = resp.headers().get("Cache-Control").unwrap();
+    assert_eq!(
+        cache_control.to_str().unwrap(),
+        format!("private, max-age={UPLOAD_URL_TTL_SECS}")
+    );
+
+    // Only the uploader can re-sign
+    let resp = test::TestRequest::get()
+        .uri(&uri)
+        .insert_header(("Authorization", format!("Bearer {bob}")))
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 404);
+    let resp = test::TestRequest::get().uri(&uri).send_request(&app).await;
+    assert_eq!(resp.status(), 401);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test the fallback signing key is derived from, never equal to, the JWT
+/// secret.
+#[tokio::test]
+async fn test_derive_upload_key() {
+    let secret = "a-jwt-secret-at-least-32-characters-long";
+    let key = derive_upload_key(secret).unwrap();
+    assert_ne!(key, secret);
+    assert_eq!(key, derive_upload_key(secret).unwrap());
+    assert_ne!(
+        key,
+        derive_upload_key("another-jwt-secret-of-32-characters").unwrap()
+    );
+}
+
+/// Test upload validation, size limit, and authentication.
+#[tokio::test]
+async fn test_upload_validation() {
+    let dir = upload_dir();
+    let app = init_app!(&dir);
+    let token = register_user!(&app, "alice");
+
+    let resp = upload!(&app, token, "text/html", "<script>");
+    assert_eq!(resp.status(), 400);
+    let resp = upload!(&app, token, "image/png", "");
+    assert_eq!(resp.status(), 400);
+    let resp = upload!(&app, token, "image/png", vec![0u8; MAX_UPLOAD_BYTES + 1]);
+    assert_eq!(resp.status(), 413);
+
+    let resp = test::TestRequest::post()
+        .uri("/api/uploads")
+        .insert_header(("Content-Type", "image/png"))
+        .set_payload(PNG)
+        .send_request(&app)
+        .await;
+    assert_eq!(resp.status(), 401);
+    assert!(!dir.exists());
+}
+
+/// Test uploads must match their declared type and its size limit, and
+/// cannot be executables.
+#[tokio::test]
+async fn test_upload_content_checks() {
+    let dir = upload_dir();
+    let app = init_app!(&dir);
+    let token = register_user!(&app, "alice");
+
+    let resp = upload!(&app, token, "image/jpeg", PNG);
+    assert_eq!(resp.status(), 400);
+    let error: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(
+        error["error"],
+        "Validation error: File content is not image/jpeg"
+    );
+
+    for executable in [
+        &b"MZ\x90\x00 a PE file"[..],
+        b"\x7fELF\x02\x01",
+        b"#!/bin/sh\n",
+    ] {
+        let resp = upload!(&app, token, "image/png", executable);
+        let error: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(
+            error["error"],
+            "Validation error: Executable files are not allowed"
+        );
+    }
+
+    // GIFs have a lower limit than the request body
+    let mut gif = b"GIF89a".to_vec();
+    gif.resize(2 * 1024 * 1024 + 1, 0);
+    let resp = upload!(&app, token, "image/gif", gif);
+    assert_eq!(resp.status(), 400);
+
+    let mut webp = b"RIFF\0\0\0\0WEBPVP8 ".to_vec();
+    webp.resize(3 * 1024 * 1024, 0);
+    let resp = upload!(&app, token, "image/webp", webp);
+    assert_eq!(resp.status(), 201);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Starts a stub clamd on a Unix socket that finds `EICAR` in a stream,
+/// returning the socket path.
+fn start_clamd(dir: &PathBuf) -> PathBuf {
+    std::fs::create_dir_all(dir).unwrap();
+    let socket = dir.join("clamd.sock");
+    let listener = UnixListener::bind(&socket).unwrap();
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut command = [0u8; 10];
+            stream.read_exact(&mut command).await.unwrap();
+            assert_eq!(&command, b"zINSTREAM\0");
+            let mut data = Vec::new();
+            loop {
+                let len = stream.read_u32().await.unwrap() as usize;
+                if len == 0 {
+                    break;
+                }
+                let mut chunk = vec![0u8; len];
+                stream.read_exact(&mut chunk).await.unwrap();
+                data.extend(chunk);
+            }
+            let infected = data.windows(5).any(|window| window == b"EICAR");
+            let reply: &[u8] = if infected {
+                b"stream: Eicar-Test-Signature FOUND\0"
+            } else {
+                b"stream: OK\0"
+            };
+            stream.write_all(reply).await.unwrap();
+        }
+    });
+    socket
+}
+
+/// Test infected uploads are rejected before they are stored, and uploads
+/// fail while clamd is unreachable.
+#[tokio::test]
+async fn test_virus_scan() {
+    let dir = upload_dir();
+    let socket = start_clamd(&dir);
+    let store = dir.join("blobs");
+    let app = init_app!(&store, Some(ClamAv::new(&socket)));
+    let token = register_user!(&app, "alice");
+
+    let infected = [PNG, b"EICAR"].concat();
+    let resp = upload!(&app, token, "image/png", infected);
+    assert_eq!(resp.status(), 400);
+    let error: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(
+        error["error"],
+        "Validation error: File rejected by virus scan (Eicar-Test-Signature)"
+    );
+    assert!(!store.exists());
+
+    let resp = upload!(&app, token, "image/png", PNG);
+    assert_eq!(resp.status(), 201);
+
+    let offline = dir.join("offline");
+    let app = init_app!(&offline, Some(ClamAv::new(dir.join("missing.sock"))));
+    let token = register_user!(&app, "alice");
+    let resp = upload!(&app, token, "image/png", PNG);
+    assert_eq!(resp.status(), 500);
+    assert!(!offline.exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
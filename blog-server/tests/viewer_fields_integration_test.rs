@@ -0,0 +1,210 @@
+//! Integration tests for per-viewer post fields over HTTP and gRPC.
+
+mod common;
+
+use std::sync::Arc;
+
+use actix_web::{App, test, web};
+use blog_shared::constants::AUTH_METADATA_KEY;
+use blog_shared::{CreatePostRequest, PostDto, PostListResponse, RegisterRequest};
+use sqlx::SqlitePool;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::Request;
+use tonic::transport::Server;
+
+use blog_server::application::{
+    AuthService, BlogService, CommentService, ImportService, LinkCheckService, ModerationService,
+    NotificationService,
+};
+use blog_server::data::{
+    CommentRepository, FeatureFlagRepository, LinkCheckRepository, ModerationRepository,
+    NotificationRepository, PostRepository, RecoveryCodeRepository, ServiceAccountRepository,
+    UserRepository,
+};
+use blog_server::domain::EmbedProvider;
+use blog_server::infrastructure::event_bus::EventBus;
+use blog_server::infrastructure::http_fetcher::HttpFetcher;
+use blog_server::presentation::grpc_service::GrpcBlogService;
+use blog_server::presentation::grpc_service::proto::blog_service_client::BlogServiceClient;
+use blog_server::presentation::grpc_service::proto::blog_service_server::BlogServiceServer;
+use blog_server::presentation::grpc_service::proto::{GetPostRequest, ListPostsRequest};
+use blog_server::presentation::http_handlers::api_routes;
+
+use common::{setup_test_db, test_jwt};
+
+/// Builds the auth and blog services on a test database.
+fn services(pool: &SqlitePool) -> (AuthService, BlogService) {
+    let auth_service = AuthService::new(
+        Arc::new(UserRepository::new(pool.clone())),
+        Arc::new(FeatureFlagRepository::new(pool.clone())),
+        Arc::new(RecoveryCodeRepository::new(pool.clone())),
+        Arc::new(ServiceAccountRepository::new(pool.clone())),
+        test_jwt(),
+        Vec::new(),
+    );
+    let moderation_service =
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+    let blog_service = BlogService::new(
+        Arc::new(PostRepository::new(pool.clone())),
+        EmbedProvider::ALL.to_vec(),
+        moderation_service,
+    );
+    (auth_service, blog_service)
+}
+
+/// Registers a user, returning their ID and token.
+async fn register(auth_service: &AuthService, username: &str) -> (i64, String) {
+    let req = RegisterRequest {
+        username: username.to_string(),
+        email: format!("{username}@example.com"),
+        password: "secret123".to_string(),
+        invite_code: None,
+    };
+    let auth = auth_service.register(req, None).await.unwrap();
+    (auth.user.id, auth.token)
+}
+
+/// Creates a post, returning its public ID.
+async fn create_post(blog_service: &BlogService, author_id: i64) -> String {
+    let req = CreatePostRequest {
+        title: "Mine".to_string(),
+        content: "Written by me".to_string(),
+        tags: Vec::new(),
+        lang: None,
+        canonical_url: None,
+        original_source: None,
+        org: None,
+    };
+    blog_service
+        .create_post(author_id, req)
+        .await
+        .unwrap()
+        .public_id
+}
+
+/// Test `is_owner` is set only for the author on HTTP reads.
+#[tokio::test]
+async fn test_http_is_owner_follows_viewer() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service) = services(&pool);
+    let (alice_id, alice_token) = register(&auth_service, "alice").await;
+    let (_, bob_token) = register(&auth_service, "bob").await;
+    let post_id = create_post(&blog_service, alice_id).await;
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(auth_service))
+            .app_data(web::Data::new(blog_service))
+            .service(web::scope("/api").service(api_routes())),
+    )
+    .await;
+
+    for (token, expected) in [(&alice_token, true), (&bob_token, false)] {
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/posts/{post_id}"))
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .to_request();
+        let post: PostDto = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(post.is_owner, expected);
+    }
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/posts/{post_id}"))
+        .to_request();
+    let post: PostDto = test::call_and_read_body_json(&app, req).await;
+    assert!(!post.is_owner);
+
+    let req = test::TestRequest::get()
+        .uri("/api/posts")
+        .insert_header(("Authorization", format!("Bearer {alice_token}")))
+        .to_request();
+    let list: PostListResponse = test::call_and_read_body_json(&app, req).await;
+    assert!(list.posts[0].is_owner);
+
+    // An invalid token reads anonymously rather than failing
+    let req = test::TestRequest::get()
+        .uri("/api/posts")
+        .insert_header(("Authorization", "Bearer not-a-token"))
+        .to_request();
+    let list: PostListResponse = test::call_and_read_body_json(&app, req).await;
+    assert!(!list.posts[0].is_owner);
+}
+
+/// Test gRPC reads take the viewer from `authorization` metadata.
+#[tokio::test]
+async fn test_grpc_is_owner_from_metadata() {
+    let pool = setup_test_db().await;
+    let (auth_service, blog_service) = services(&pool);
+    let (alice_id, alice_token) = register(&auth_service, "alice").await;
+    let post_id = create_post(&blog_service, alice_id).await;
+
+    let user_repo = Arc::new(UserRepository::new(pool.clone()));
+    let post_repo = Arc::new(PostRepository::new(pool.clone()));
+    let moderation_service =
+        ModerationService::new(Arc::new(ModerationRepository::new(pool.clone())));
+    let events = EventBus::new();
+    let comment_service = CommentService::new(
+        Arc::new(CommentRepository::new(pool.clone())),
+        Arc::clone(&post_repo),
+        Arc::new(FeatureFlagRepository::new(pool.clone())),
+        moderation_service.clone(),
+        events.clone(),
+    );
+    let grpc_service = GrpcBlogService::new(
+        blog_service.clone(),
+        auth_service,
+        LinkCheckService::new(
+            Arc::new(LinkCheckRepository::new(pool.clone())),
+            HttpFetcher::new().unwrap(),
+        ),
+        ImportService::new(user_repo, post_repo, blog_service, moderation_service),
+        comment_service,
+        NotificationService::new(Arc::new(NotificationRepository::new(pool.clone()))),
+        events,
+    );
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        Server::builder()
+            .add_service(BlogServiceServer::new(grpc_service))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    let mut client = BlogServiceClient::connect(format!("http://{addr}"))
+        .await
+        .unwrap();
+
+    let mut request = Request::new(GetPostRequest {
+        id: 0,
+        public_id: post_id.clone(),
+    });
+    request.metadata_mut().insert(
+        AUTH_METADATA_KEY,
+        format!("Bearer {alice_token}").parse().unwrap(),
+    );
+    let post = client.get_post(request).await.unwrap().into_inner().post;
+    assert!(post.unwrap().is_owner);
+
+    let post = client
+        .get_post(GetPostRequest {
+            id: 0,
+            public_id: post_id,
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .post;
+    assert!(!post.unwrap().is_owner);
+
+    let mut request = Request::new(ListPostsRequest {
+        limit: 10,
+        offset: 0,
+        lang: None,
+    });
+    request
+        .metadata_mut()
+        .insert(AUTH_METADATA_KEY, "Bearer not-a-token".parse().unwrap());
+    let list = client.list_posts(request).await.unwrap().into_inner();
+    assert!(!list.posts[0].is_owner);
+}
@@ -0,0 +1,261 @@
+//! Admin dashboard data transfer objects.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::user::Role;
+
+/// Site-wide counters for the admin dashboard.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdminStatsDto {
+    pub users: i64,
+    pub suspended_users: i64,
+    pub posts: i64,
+    pub comments: i64,
+    /// Tags used by at least one post.
+    pub tags: i64,
+    /// Sessions active within the token lifetime.
+    pub active_sessions: i64,
+}
+
+/// User row in the admin user management table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdminUserDto {
+    pub id: i64,
+    pub username: String,
+    pub email: String,
+    pub role: Role,
+    pub suspended: bool,
+    /// Content hidden from everyone but the user.
+    #[serde(default)]
+    pub shadow_banned: bool,
+    pub post_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Paginated list of users.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdminUserListResponse {
+    pub users: Vec<AdminUserDto>,
+    pub total: i64,
+}
+
+/// Changes to a user's role or suspension (omitted fields stay unchanged).
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct UpdateUserRequest {
+    pub role: Option<Role>,
+    pub suspended: Option<bool>,
+}
+
+/// Shadow-ban request; the reason is kept in the audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ShadowBanRequest {
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Moderator action in the audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditLogEntryDto {
+    pub id: i64,
+    /// Acting moderator or user (absent once their account is deleted).
+    pub actor_id: Option<i64>,
+    pub actor_username: Option<String>,
+    /// Action name, e.g. `shadow_ban` or `shadow_unban`.
+    pub action: String,
+    pub target_user_id: Option<i64>,
+    pub target_username: Option<String>,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Paginated audit log, newest first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditLogResponse {
+    pub entries: Vec<AuditLogEntryDto>,
+    pub total: i64,
+}
+
+/// A retention policy in the dry-run report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionPolicyDto {
+    /// `audit_log` or `unsubscribed_emails`.
+    pub name: String,
+    /// Data older than this is removed; `None` while the policy is off.
+    pub cutoff: Option<DateTime<Utc>>,
+    /// Rows the next run would remove.
+    pub due: i64,
+}
+
+/// What the retention job would remove if it ran now.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionReportDto {
+    pub policies: Vec<RetentionPolicyDto>,
+}
+
+/// A failed write request recorded by the diagnostic mode.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiagnosticSnapshotDto {
+    /// ID the client was given in `X-Request-Id` and the error body.
+    pub request_id: Option<String>,
+    pub method: String,
+    pub path: String,
+    /// Signed-in user, if the request carried a valid token.
+    pub user_id: Option<i64>,
+    pub status: u16,
+    pub content_type: Option<String>,
+    /// JSON or form body with passwords, tokens, codes, and keys redacted;
+    /// other bodies only give their size.
+    pub request_body: Option<String>,
+    pub response_body: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Recent failed write requests, newest first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiagnosticsResponse {
+    /// Whether failures are being recorded (`DIAGNOSTICS_ENABLED`).
+    pub enabled: bool,
+    /// Snapshots kept before the oldest is dropped.
+    pub capacity: usize,
+    pub snapshots: Vec<DiagnosticSnapshotDto>,
+}
+
+/// Feature flag with its current state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeatureFlagDto {
+    pub name: String,
+    pub description: String,
+    pub enabled: bool,
+}
+
+/// All feature flags, by name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeatureFlagListResponse {
+    pub flags: Vec<FeatureFlagDto>,
+}
+
+/// Feature flag toggle request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateFeatureFlagRequest {
+    pub enabled: bool,
+}
+
+/// What a service account's token may do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceScope {
+    /// Read-only requests (`GET`, `HEAD` and `OPTIONS`).
+    Read,
+    /// Requests that create, change or delete data.
+    Write,
+}
+
+impl ServiceScope {
+    /// Returns the lowercase name used in JSON, tokens and storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ServiceScope::Read => "read",
+            ServiceScope::Write => "write",
+        }
+    }
+}
+
+impl fmt::Display for ServiceScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ServiceScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read" => Ok(ServiceScope::Read),
+            "write" => Ok(ServiceScope::Write),
+            other => Err(format!("Unknown scope: {other}")),
+        }
+    }
+}
+
+/// Service account: a long-lived, scoped token acting as a user, for bots
+/// and integrations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceAccountDto {
+    pub id: i64,
+    pub name: String,
+    /// User the token acts as.
+    pub user_id: i64,
+    pub username: String,
+    pub scopes: Vec<ServiceScope>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// Set once the account is revoked; its token no longer works.
+    pub revoked_at: Option<DateTime<Utc>>,
+    /// Requests allowed per UTC day (`None`: unlimited).
+    #[serde(default)]
+    pub requests_per_day: Option<i64>,
+    /// Writes (requests other than `GET`/`HEAD`/`OPTIONS`) allowed per UTC
+    /// day (`None`: unlimited).
+    #[serde(default)]
+    pub writes_per_day: Option<i64>,
+}
+
+/// Request to set a service account's daily quotas; an absent limit removes
+/// it.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ServiceAccountQuotaRequest {
+    #[serde(default)]
+    pub requests_per_day: Option<i64>,
+    #[serde(default)]
+    pub writes_per_day: Option<i64>,
+}
+
+/// A service account's usage today, against its quotas.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiUsageDto {
+    pub service_account_id: i64,
+    pub name: String,
+    /// The UTC day counted.
+    pub day: NaiveDate,
+    pub requests: i64,
+    pub writes: i64,
+    pub requests_per_day: Option<i64>,
+    pub writes_per_day: Option<i64>,
+    /// When the counters start over (the next UTC midnight).
+    pub resets_at: DateTime<Utc>,
+}
+
+/// Usage of the current user's active service accounts, newest first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiUsageResponse {
+    pub service_accounts: Vec<ApiUsageDto>,
+}
+
+/// Request to create a service account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreateServiceAccountRequest {
+    pub name: String,
+    /// User the token acts as.
+    pub user_id: i64,
+    /// Granted scopes (`read` when empty).
+    #[serde(default)]
+    pub scopes: Vec<ServiceScope>,
+}
+
+/// Newly created service account with its token, which is only shown once.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreatedServiceAccountResponse {
+    pub account: ServiceAccountDto,
+    pub token: String,
+}
+
+/// All service accounts, newest first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceAccountListResponse {
+    pub service_accounts: Vec<ServiceAccountDto>,
+}
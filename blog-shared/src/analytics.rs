@@ -0,0 +1,85 @@
+//! Anonymous analytics data transfer objects.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// What a client reports having happened on a page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalyticsEventKind {
+    /// A page was shown.
+    PageView,
+    /// A post was scrolled to its end.
+    ReadComplete,
+}
+
+impl AnalyticsEventKind {
+    /// Returns the snake_case name used in JSON and storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnalyticsEventKind::PageView => "page_view",
+            AnalyticsEventKind::ReadComplete => "read_complete",
+        }
+    }
+}
+
+impl fmt::Display for AnalyticsEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AnalyticsEventKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "page_view" => Ok(AnalyticsEventKind::PageView),
+            "read_complete" => Ok(AnalyticsEventKind::ReadComplete),
+            other => Err(format!("Unknown analytics event: {other}")),
+        }
+    }
+}
+
+/// A client event. Nothing identifies the reader.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalyticsEventDto {
+    pub kind: AnalyticsEventKind,
+    /// Path of the page, e.g. `/posts/42`; any query or fragment is dropped.
+    pub path: String,
+}
+
+/// Events a client collected since its last report.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalyticsBatchRequest {
+    pub events: Vec<AnalyticsEventDto>,
+}
+
+/// Site-wide totals of one UTC day, estimated from the sampled events.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyStatsDto {
+    pub day: NaiveDate,
+    pub page_views: i64,
+    pub read_completions: i64,
+}
+
+/// Totals of one page over the reported days.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageStatsDto {
+    pub path: String,
+    pub page_views: i64,
+    pub read_completions: i64,
+}
+
+/// Aggregated analytics for the admin dashboard. Events of the current day
+/// are only counted once the nightly rollup has run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnalyticsReportResponse {
+    /// Days with any events, oldest first.
+    pub days: Vec<DailyStatsDto>,
+    /// Most viewed pages, most views first.
+    pub top_pages: Vec<PageStatsDto>,
+}
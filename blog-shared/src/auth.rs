@@ -1,27 +1,111 @@
 //! Authentication data transfer objects.
 
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::UserDto;
 
 /// Response after successful login/register.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct AuthResponse {
     pub token: String,
     pub user: UserDto,
+    /// One-time recovery codes, only sent right after registration.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recovery_codes: Vec<String>,
+}
+
+impl AuthResponse {
+    /// Creates a response without recovery codes.
+    pub fn new(token: String, user: UserDto) -> Self {
+        Self {
+            token,
+            user,
+            recovery_codes: Vec::new(),
+        }
+    }
 }
 
 /// Registration request.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RegisterRequest {
     pub username: String,
     pub email: String,
     pub password: String,
+    /// Invite code, required while registration is invite-only.
+    #[serde(default)]
+    pub invite_code: Option<String>,
+}
+
+impl RegisterRequest {
+    /// Creates a request without an invite code.
+    pub fn new(
+        username: impl Into<String>,
+        email: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        Self {
+            username: username.into(),
+            email: email.into(),
+            password: password.into(),
+            invite_code: None,
+        }
+    }
+
+    /// Sets the invite code.
+    pub fn with_invite_code(mut self, code: impl Into<String>) -> Self {
+        self.invite_code = Some(code.into());
+        self
+    }
+}
+
+/// A new invite code; it is shown only once.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InviteDto {
+    pub code: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Public switches clients adapt their forms to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeaturesResponse {
+    /// Whether new users can register at all.
+    pub registration: bool,
+    /// Whether registering needs an invite code.
+    pub invite_only: bool,
+    /// Variant of each running experiment assigned to the signed-in user,
+    /// by experiment name; empty for guests.
+    #[serde(default)]
+    pub experiments: BTreeMap<String, String>,
 }
 
 /// Login request.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LoginRequest {
+    /// Username or email address, matched ignoring case.
     pub username: String,
     pub password: String,
 }
+
+impl LoginRequest {
+    /// Creates a request for a username or email address.
+    pub fn new(username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            password: password.into(),
+        }
+    }
+}
+
+/// Request to sign in with a recovery code and set a new password.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecoverAccountRequest {
+    /// Username or email address, matched ignoring case.
+    pub username: String,
+    /// One of the account's unused recovery codes.
+    pub code: String,
+    pub new_password: String,
+}
@@ -0,0 +1,105 @@
+//! Comment data transfer objects.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Comment data transfer object with author info.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct CommentDto {
+    pub id: i64,
+    /// Internal ID of the post, never sent over HTTP (0 when read from JSON).
+    #[serde(skip_serializing, default)]
+    pub post_id: i64,
+    /// Internal ID of the author, never sent over HTTP like `post_id`.
+    #[serde(skip_serializing, default)]
+    pub author_id: i64,
+    /// Public ID of the post (empty from servers that predate it).
+    #[serde(default)]
+    pub post_public_id: String,
+    /// Public ID of the author (empty from servers that predate it).
+    #[serde(default)]
+    pub author_public_id: String,
+    pub author_username: String,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CommentDto {
+    /// Creates a comment.
+    pub fn new(
+        id: i64,
+        post_id: i64,
+        author_id: i64,
+        author_username: String,
+        content: String,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            post_id,
+            author_id,
+            post_public_id: String::new(),
+            author_public_id: String::new(),
+            author_username,
+            content,
+            created_at,
+            updated_at,
+        }
+    }
+}
+
+/// Paginated list of comments on a post.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct CommentListResponse {
+    pub comments: Vec<CommentDto>,
+    /// Comments on the post the caller can see; only on offset pages and the
+    /// first page of a cursor listing, as counting every page is wasted work.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<i64>,
+    /// Cursor of the next page; absent on the last page, and from servers
+    /// that predate cursors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+impl CommentListResponse {
+    /// Creates a page of comments out of `total`, when counted.
+    pub fn new(comments: Vec<CommentDto>, total: Option<i64>) -> Self {
+        Self {
+            comments,
+            total,
+            next_cursor: None,
+        }
+    }
+
+    /// Sets the cursor of the next page.
+    pub fn with_next_cursor(mut self, next_cursor: Option<String>) -> Self {
+        self.next_cursor = next_cursor;
+        self
+    }
+}
+
+/// Create comment request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreateCommentRequest {
+    pub content: String,
+}
+
+impl CreateCommentRequest {
+    /// Creates a request for a comment with this text.
+    pub fn new(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+        }
+    }
+}
+
+/// Update comment request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateCommentRequest {
+    pub content: String,
+}
@@ -9,3 +9,147 @@ pub const ENV_GRPC_PORT: &str = "GRPC_PORT";
 // Default values
 pub const DEFAULT_HTTP_PORT: u16 = 8080;
 pub const DEFAULT_GRPC_PORT: u16 = 50051;
+/// Port the WASM frontend's dev server (`trunk serve`) listens on.
+pub const DEFAULT_SITE_PORT: u16 = 8081;
+
+// Local URLs of the servers on the default ports
+pub const DEFAULT_HTTP_URL: &str = "http://localhost:8080";
+pub const DEFAULT_GRPC_URL: &str = "http://localhost:50051";
+pub const DEFAULT_SITE_URL: &str = "http://localhost:8081";
+
+/// Origins of the WASM frontend's dev server allowed by CORS.
+/// `localhost.` (with trailing dot) is included because some browsers normalize localhost to localhost.
+pub const CORS_ALLOWED_ORIGINS: &[&str] = &[
+    "http://127.0.0.1:8081",
+    "http://localhost:8081",
+    "http://localhost.:8081",
+];
+
+// HTTP API
+/// Path prefix of every HTTP API endpoint.
+pub const API_PREFIX: &str = "/api";
+
+// Pagination of list endpoints
+pub const DEFAULT_LIMIT: i64 = 10;
+pub const DEFAULT_OFFSET: i64 = 0;
+/// Largest page a list endpoint returns; larger limits are cut to it.
+pub const MAX_LIMIT: i64 = 100;
+
+// Site settings used until an admin changes them
+pub const DEFAULT_SITE_TITLE: &str = "Blog Platform";
+pub const DEFAULT_POSTS_PER_PAGE: i64 = 10;
+
+// gRPC metadata key carrying the error code of a failed call
+pub const ERROR_CODE_METADATA_KEY: &str = "x-error-code";
+
+// gRPC metadata key carrying an optional "Bearer <token>" on public reads
+pub const AUTH_METADATA_KEY: &str = "authorization";
+
+// Media type of MessagePack bodies, the HTTP API's binary alternative to JSON
+pub const MSGPACK_CONTENT_TYPE: &str = "application/msgpack";
+
+// Limits
+pub const MAX_COMMENT_LENGTH: usize = 2000;
+pub const MAX_MESSAGE_LENGTH: usize = 2000;
+pub const MAX_TAGS_PER_POST: usize = 5;
+pub const MAX_TAG_LENGTH: usize = 32;
+pub const MAX_TAG_DESCRIPTION_LENGTH: usize = 500;
+pub const MAX_BIO_LENGTH: usize = 500;
+pub const MAX_ANNOUNCEMENT_LENGTH: usize = 500;
+pub const MAX_SITE_TITLE_LENGTH: usize = 100;
+pub const MAX_SITE_DESCRIPTION_LENGTH: usize = 500;
+pub const MAX_POSTS_PER_PAGE: i64 = 100;
+pub const MAX_ORG_SLUG_LENGTH: usize = 32;
+pub const MAX_ORG_NAME_LENGTH: usize = 100;
+pub const MAX_EXPERIMENT_NAME_LENGTH: usize = 64;
+pub const MAX_EXPERIMENT_DESCRIPTION_LENGTH: usize = 500;
+pub const MAX_EXPERIMENT_VARIANTS: usize = 8;
+pub const MAX_AVATAR_URL_LENGTH: usize = 500;
+pub const MAX_WEBSITE_LENGTH: usize = 500;
+pub const MAX_SOURCE_URL_LENGTH: usize = 500;
+pub const MAX_METADATA_KEYS: usize = 32;
+pub const MAX_METADATA_KEY_LENGTH: usize = 64;
+pub const MAX_METADATA_BYTES: usize = 8 * 1024;
+pub const MAX_REVIEW_COMMENT_LENGTH: usize = 2000;
+pub const MAX_REVIEW_NOTES: usize = 50;
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+pub const MAX_AUDIT_REASON_LENGTH: usize = 500;
+pub const MAX_RULE_NAME_LENGTH: usize = 100;
+pub const MAX_RULE_PATTERN_LENGTH: usize = 2000;
+pub const MAX_SERVICE_ACCOUNT_NAME_LENGTH: usize = 100;
+pub const MAX_IMPORT_POSTS: usize = 50;
+pub const MAX_INTEGRATION_TOKEN_LENGTH: usize = 200;
+pub const MAX_UPLOAD_BYTES: usize = 5 * 1024 * 1024;
+
+// Uploads: accepted media types with their size limits
+pub const UPLOAD_TYPES: &[(&str, usize)] = &[
+    ("image/png", 5 * 1024 * 1024),
+    ("image/jpeg", 5 * 1024 * 1024),
+    ("image/gif", 2 * 1024 * 1024),
+    ("image/webp", 5 * 1024 * 1024),
+];
+
+// Co-editing presence
+/// How often an open post form tells the server it is still editing.
+pub const EDITING_HEARTBEAT_INTERVAL_SECS: u32 = 10;
+
+// Revisions
+/// Largest line-comparison table a diff may build (old lines × new
+/// lines, after matching shared start and end lines).
+pub const MAX_DIFF_CELLS: usize = 4_000_000;
+
+// Text
+/// Marker appended to truncated text.
+pub const ELLIPSIS: char = '…';
+
+// Roles: actions each role may take, reported by `/auth/me`
+pub const USER_PERMISSIONS: &[&str] = &[
+    "posts:write",
+    "comments:write",
+    "messages:send",
+    "uploads:write",
+];
+
+pub const EDITOR_PERMISSIONS: &[&str] = &[
+    "posts:write",
+    "comments:write",
+    "messages:send",
+    "uploads:write",
+    "posts:review",
+];
+
+pub const MODERATOR_PERMISSIONS: &[&str] = &[
+    "posts:write",
+    "comments:write",
+    "messages:send",
+    "uploads:write",
+    "posts:review",
+    "moderation:review",
+];
+
+pub const ADMIN_PERMISSIONS: &[&str] = &[
+    "posts:write",
+    "comments:write",
+    "messages:send",
+    "uploads:write",
+    "posts:review",
+    "moderation:review",
+    "moderation:rules",
+    "admin:dashboard",
+    "users:manage",
+    "flags:manage",
+];
+
+// Preferences
+pub const SUPPORTED_LANGUAGES: &[&str] = &["en", "ru"];
+pub const DEFAULT_LANGUAGE: &str = "en";
+/// Locales the server formats dates for (in emails and similar).
+pub const SUPPORTED_LOCALES: &[&str] = &["en-US", "en-GB", "de-DE", "fr-FR", "ru-RU"];
+pub const DEFAULT_LOCALE: &str = "en-US";
+/// IANA time zone used until a user picks one.
+pub const DEFAULT_TIMEZONE: &str = "UTC";
+
+// Email changes
+/// Query string parameter of the web app page that confirms a new account
+/// email (`/?confirm_email=...`), linked from the confirmation email.
+pub const EMAIL_CHANGE_QUERY_PARAM: &str = "confirm_email";
@@ -0,0 +1,133 @@
+//! Cross-posting integration data transfer objects.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Blogging service posts can be cross-posted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CrosspostTarget {
+    /// DEV Community (dev.to), with an API key from its settings.
+    Devto,
+    /// Hashnode, with a personal access token and a publication ID.
+    Hashnode,
+}
+
+impl CrosspostTarget {
+    /// Returns the lowercase name used in URLs, JSON, and storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CrosspostTarget::Devto => "devto",
+            CrosspostTarget::Hashnode => "hashnode",
+        }
+    }
+}
+
+impl fmt::Display for CrosspostTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CrosspostTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "devto" => Ok(CrosspostTarget::Devto),
+            "hashnode" => Ok(CrosspostTarget::Hashnode),
+            other => Err(format!("Unknown cross-posting target: {other}")),
+        }
+    }
+}
+
+/// Outcome of the last attempt to publish or update a cross-post.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CrosspostStatus {
+    /// The target has the post as of `synced_at`.
+    Published,
+    /// The last attempt failed; retried on the next edit or request.
+    Failed,
+}
+
+impl CrosspostStatus {
+    /// Returns the lowercase name used in JSON and storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CrosspostStatus::Published => "published",
+            CrosspostStatus::Failed => "failed",
+        }
+    }
+}
+
+impl fmt::Display for CrosspostStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CrosspostStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "published" => Ok(CrosspostStatus::Published),
+            "failed" => Ok(CrosspostStatus::Failed),
+            other => Err(format!("Unknown cross-post status: {other}")),
+        }
+    }
+}
+
+/// Request body for saving the current user's token for a target.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrationTokenRequest {
+    pub token: String,
+    /// Publication to post to (required for Hashnode).
+    #[serde(default)]
+    pub publication_id: Option<String>,
+}
+
+/// A configured integration; the token itself is never returned.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrationDto {
+    pub target: CrosspostTarget,
+    pub publication_id: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The current user's integrations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrationListResponse {
+    pub integrations: Vec<IntegrationDto>,
+}
+
+/// Query parameters of the cross-post endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrosspostQuery {
+    pub target: CrosspostTarget,
+}
+
+/// A post's copy on another service.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrosspostDto {
+    pub post_id: i64,
+    pub target: CrosspostTarget,
+    pub status: CrosspostStatus,
+    /// URL of the copy (set once it was published).
+    pub remote_url: Option<String>,
+    /// Why the last attempt failed.
+    pub error: Option<String>,
+    /// Post version (`updated_at`) the copy was last synced to.
+    pub synced_at: Option<DateTime<Utc>>,
+    pub attempted_at: DateTime<Utc>,
+}
+
+/// Cross-posts of a post.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrosspostListResponse {
+    pub crossposts: Vec<CrosspostDto>,
+}
@@ -0,0 +1,142 @@
+//! Line diffs shared by the server and the clients.
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::MAX_DIFF_CELLS;
+
+/// What happened to a line between two texts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffOp {
+    /// In both texts.
+    Same,
+    /// Only in the old text.
+    Removed,
+    /// Only in the new text.
+    Added,
+}
+
+impl DiffOp {
+    /// Returns the snake_case name used in JSON.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiffOp::Same => "same",
+            DiffOp::Removed => "removed",
+            DiffOp::Added => "added",
+        }
+    }
+}
+
+impl FromStr for DiffOp {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "same" => Ok(DiffOp::Same),
+            "removed" => Ok(DiffOp::Removed),
+            "added" => Ok(DiffOp::Added),
+            other => Err(format!("Unknown diff op: {other}")),
+        }
+    }
+}
+
+/// One line of a diff, with its 1-based number in each text it is in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffLineDto {
+    pub op: DiffOp,
+    pub text: String,
+    pub old_line: Option<u32>,
+    pub new_line: Option<u32>,
+}
+
+/// Diffs two texts line by line through their longest common subsequence.
+///
+/// Lines shared at the start and end are matched first. If what remains
+/// would need more than `MAX_DIFF_CELLS` table cells, it is shown as
+/// removed then added in full, keeping huge texts cheap to compare.
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLineDto> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (old_mid, new_mid) = (
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+
+    let mut ops = vec![DiffOp::Same; prefix];
+    ops.extend(middle_ops(old_mid, new_mid));
+    ops.extend(std::iter::repeat_n(DiffOp::Same, suffix));
+
+    let (mut i, mut j) = (0, 0);
+    ops.into_iter()
+        .map(|op| {
+            let line = |n: usize| Some(n as u32 + 1);
+            let (text, old_line, new_line) = match op {
+                DiffOp::Same => (old[i], line(i), line(j)),
+                DiffOp::Removed => (old[i], line(i), None),
+                DiffOp::Added => (new[j], None, line(j)),
+            };
+            if op != DiffOp::Added {
+                i += 1;
+            }
+            if op != DiffOp::Removed {
+                j += 1;
+            }
+            DiffLineDto {
+                op,
+                text: text.to_string(),
+                old_line,
+                new_line,
+            }
+        })
+        .collect()
+}
+
+/// Diffs lines that differ at both ends.
+fn middle_ops(old: &[&str], new: &[&str]) -> Vec<DiffOp> {
+    if (old.len() + 1).saturating_mul(new.len() + 1) > MAX_DIFF_CELLS {
+        let mut ops = vec![DiffOp::Removed; old.len()];
+        ops.extend(std::iter::repeat_n(DiffOp::Added, new.len()));
+        return ops;
+    }
+
+    // common[i][j] is the LCS length of old[i..] and new[j..]
+    let mut common = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(old.len() + new.len());
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            ops.push(DiffOp::Same);
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            ops.push(DiffOp::Removed);
+            i += 1;
+        } else {
+            ops.push(DiffOp::Added);
+            j += 1;
+        }
+    }
+    ops.extend(std::iter::repeat_n(DiffOp::Removed, old.len() - i));
+    ops.extend(std::iter::repeat_n(DiffOp::Added, new.len() - j));
+    ops
+}
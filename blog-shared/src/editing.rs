@@ -0,0 +1,32 @@
+//! Co-editing presence data transfer objects.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// User with a post open for editing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditorDto {
+    /// Internal ID, never sent over HTTP (0 when read from JSON).
+    #[serde(skip_serializing, default)]
+    pub user_id: i64,
+    /// Public ID of the user (empty from servers that predate it).
+    #[serde(default)]
+    pub user_public_id: String,
+    pub username: String,
+    /// When they opened the post.
+    pub since: DateTime<Utc>,
+}
+
+/// Everyone currently editing a post, returned by the editing heartbeat
+/// and pushed over the editing WebSocket whenever it changes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditingPresenceDto {
+    /// Internal ID of the post, never sent over HTTP like
+    /// [`EditorDto::user_id`].
+    #[serde(skip_serializing, default)]
+    pub post_id: i64,
+    /// Public ID of the post (empty from servers that predate it).
+    #[serde(default)]
+    pub post_public_id: String,
+    pub editors: Vec<EditorDto>,
+}
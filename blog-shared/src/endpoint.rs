@@ -0,0 +1,412 @@
+//! API base URLs, pagination, and endpoint paths shared by the server and
+//! every client.
+
+use std::fmt;
+
+use crate::constants::{DEFAULT_LIMIT, DEFAULT_OFFSET, MAX_LIMIT};
+
+/// Root URL of the HTTP API, e.g. `http://localhost:8080`, without a
+/// trailing slash.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ApiBaseUrl(String);
+
+impl ApiBaseUrl {
+    /// Wraps a server URL, dropping trailing slashes.
+    pub fn new(url: &str) -> Self {
+        Self(url.trim_end_matches('/').to_string())
+    }
+
+    /// Plain HTTP URL of a server on `host` and `port`.
+    pub fn from_host(host: &str, port: u16) -> Self {
+        Self(format!("http://{}:{}", host, port))
+    }
+
+    /// Returns the URL as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Full URL of an endpoint path from [`paths`].
+    pub fn join(&self, path: &str) -> String {
+        format!("{}{}", self.0, path)
+    }
+}
+
+impl fmt::Display for ApiBaseUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for ApiBaseUrl {
+    fn from(url: &str) -> Self {
+        Self::new(url)
+    }
+}
+
+/// Limit and offset of a page of a list endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pagination {
+    pub limit: i64,
+    pub offset: i64,
+}
+
+impl Pagination {
+    /// Page for a request's `limit` and `offset`: missing or non-positive
+    /// limits become `DEFAULT_LIMIT`, larger ones than `MAX_LIMIT` are cut
+    /// to it, and missing or negative offsets start at the beginning.
+    pub fn clamp(limit: Option<i64>, offset: Option<i64>) -> Self {
+        let limit = match limit {
+            Some(limit) if limit > 0 => limit.min(MAX_LIMIT),
+            _ => DEFAULT_LIMIT,
+        };
+        let offset = offset
+            .filter(|offset| *offset >= 0)
+            .unwrap_or(DEFAULT_OFFSET);
+        Self { limit, offset }
+    }
+
+    /// Query string for the page, e.g. `limit=10&offset=20`.
+    pub fn query(&self) -> String {
+        format!("limit={}&offset={}", self.limit, self.offset)
+    }
+}
+
+impl Default for Pagination {
+    fn default() -> Self {
+        Self {
+            limit: DEFAULT_LIMIT,
+            offset: DEFAULT_OFFSET,
+        }
+    }
+}
+
+/// Limit and cursor of a page of a cursor-paginated list endpoint.
+///
+/// Cursors are opaque strings from a previous page's `next_cursor`; unlike
+/// offsets, they do not shift when items are added or removed meanwhile.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CursorPage {
+    pub limit: i64,
+    /// Start after this cursor; `None` is the first page.
+    pub cursor: Option<String>,
+}
+
+impl CursorPage {
+    /// Page for a request's `limit` and `cursor`: limits are clamped as by
+    /// [`Pagination::clamp`], and an empty cursor is the first page.
+    pub fn clamp(limit: Option<i64>, cursor: Option<String>) -> Self {
+        Self {
+            limit: Pagination::clamp(limit, None).limit,
+            cursor: cursor.filter(|cursor| !cursor.is_empty()),
+        }
+    }
+
+    /// Query string for the page, e.g. `limit=10&cursor=abc`. Cursors are
+    /// URL-safe, so they are not escaped.
+    pub fn query(&self) -> String {
+        match &self.cursor {
+            Some(cursor) => format!("limit={}&cursor={cursor}", self.limit),
+            None => format!("limit={}", self.limit),
+        }
+    }
+}
+
+/// Paths of HTTP API endpoints, relative to an [`ApiBaseUrl`], built from
+/// the [`routes`](crate::routes) catalog.
+///
+/// Posts are named by their public IDs; routes open to anonymous callers
+/// accept no other form.
+pub mod paths {
+    use super::{CursorPage, Pagination};
+    use crate::routes::{self, Route};
+
+    /// Registration.
+    pub fn register() -> String {
+        routes::Register::path(&[])
+    }
+
+    /// Login.
+    pub fn login() -> String {
+        routes::Login::path(&[])
+    }
+
+    /// The signed-in user.
+    pub fn me() -> String {
+        routes::GetMe::path(&[])
+    }
+
+    /// Post list and creation.
+    pub fn posts() -> String {
+        routes::ListPosts::path(&[])
+    }
+
+    /// Post list page, e.g. `/api/posts?limit=10&offset=0`.
+    pub fn posts_page(page: Pagination) -> String {
+        format!("{}?{}", posts(), page.query())
+    }
+
+    /// A single post, named by its public ID like every post path.
+    pub fn post(public_id: &str) -> String {
+        routes::GetPost::path(&[&public_id])
+    }
+
+    /// Translations of a post.
+    pub fn post_translations(public_id: &str) -> String {
+        routes::ListTranslations::path(&[&public_id])
+    }
+
+    /// Restoring a post from the trash.
+    pub fn post_restore(public_id: &str) -> String {
+        routes::RestorePost::path(&[&public_id])
+    }
+
+    /// Revisions of a post.
+    pub fn post_revisions(public_id: &str) -> String {
+        routes::ListRevisions::path(&[&public_id])
+    }
+
+    /// Diff of revision `from` of a post against revision `to`.
+    pub fn revision_diff(public_id: &str, from: i64, to: i64) -> String {
+        routes::DiffRevisions::path(&[&public_id, &from, &to])
+    }
+
+    /// Post import.
+    pub fn import_posts() -> String {
+        routes::ImportPosts::path(&[])
+    }
+
+    /// The signed-in user's deleted posts.
+    pub fn trash() -> String {
+        routes::ListTrash::path(&[])
+    }
+
+    /// Dead links in the signed-in user's posts.
+    pub fn broken_links() -> String {
+        routes::ListBrokenLinks::path(&[])
+    }
+
+    /// Comments on a post, and adding one.
+    pub fn post_comments(post_public_id: &str) -> String {
+        routes::ListComments::path(&[&post_public_id])
+    }
+
+    /// Page of the comments on a post.
+    pub fn post_comments_page(post_public_id: &str, page: Pagination) -> String {
+        format!("{}?{}", post_comments(post_public_id), page.query())
+    }
+
+    /// Page of the comments on a post after a cursor.
+    pub fn post_comments_after(post_public_id: &str, page: &CursorPage) -> String {
+        format!("{}?{}", post_comments(post_public_id), page.query())
+    }
+
+    /// Page of the signed-in user's notifications.
+    pub fn notifications_page(page: &CursorPage) -> String {
+        format!("{}?{}", routes::ListNotifications::path(&[]), page.query())
+    }
+
+    /// Number of the signed-in user's unread notifications.
+    pub fn unread_notifications() -> String {
+        routes::CountUnreadNotifications::path(&[])
+    }
+
+    /// Marking the signed-in user's notifications as read.
+    pub fn notifications_read() -> String {
+        routes::MarkNotificationsRead::path(&[])
+    }
+
+    /// Client crash reports.
+    pub fn client_errors() -> String {
+        routes::ReportClientError::path(&[])
+    }
+
+    /// A single comment.
+    pub fn comment(id: i64) -> String {
+        routes::UpdateComment::path(&[&id])
+    }
+
+    /// Registration and experiment features.
+    pub fn features() -> String {
+        routes::GetFeatures::path(&[])
+    }
+
+    /// A user's public profile, by public ID.
+    pub fn user(public_id: &str) -> String {
+        routes::GetUser::path(&[&public_id])
+    }
+
+    /// `@mention` suggestions.
+    pub fn suggest_users() -> String {
+        routes::SuggestUsers::path(&[])
+    }
+
+    /// Editing heartbeat of a post (`POST` to start, `DELETE` to stop).
+    pub fn editing_heartbeat(post_public_id: &str) -> String {
+        routes::EditingHeartbeat::path(&[&post_public_id])
+    }
+
+    /// WebSocket of a post's editors, relative to a `ws` base URL.
+    pub fn editing_socket(post_public_id: &str) -> String {
+        routes::EditingSocket::path(&[&post_public_id])
+    }
+
+    /// Public site settings.
+    pub fn site_settings() -> String {
+        routes::GetSiteSettings::path(&[])
+    }
+
+    /// The announcement banner.
+    pub fn announcement() -> String {
+        routes::GetAnnouncement::path(&[])
+    }
+
+    /// All settings of the signed-in user.
+    pub fn settings() -> String {
+        routes::GetSettings::path(&[])
+    }
+
+    /// The signed-in user's profile.
+    pub fn settings_profile() -> String {
+        routes::UpdateProfile::path(&[])
+    }
+
+    /// The signed-in user's display preferences.
+    pub fn settings_preferences() -> String {
+        routes::UpdatePreferences::path(&[])
+    }
+
+    /// Changing the signed-in user's email address.
+    pub fn settings_email() -> String {
+        routes::ChangeEmail::path(&[])
+    }
+
+    /// Confirming an email change.
+    pub fn settings_email_confirm() -> String {
+        routes::ConfirmEmailChange::path(&[])
+    }
+
+    /// Changing the signed-in user's password.
+    pub fn settings_password() -> String {
+        routes::ChangePassword::path(&[])
+    }
+
+    /// Replacing the signed-in user's recovery codes.
+    pub fn settings_recovery_codes() -> String {
+        routes::RegenerateRecoveryCodes::path(&[])
+    }
+
+    /// The signed-in user's sessions.
+    pub fn settings_sessions() -> String {
+        routes::ListSessions::path(&[])
+    }
+
+    /// One of the signed-in user's sessions.
+    pub fn settings_session(id: &str) -> String {
+        routes::RevokeSession::path(&[&id])
+    }
+
+    /// The signed-in user's account.
+    pub fn settings_account() -> String {
+        routes::DeleteAccount::path(&[])
+    }
+
+    /// The signed-in user's conversations, and starting one.
+    pub fn conversations() -> String {
+        routes::ListConversations::path(&[])
+    }
+
+    /// Page of the signed-in user's conversations.
+    pub fn conversations_page(page: Pagination) -> String {
+        format!("{}?{}", conversations(), page.query())
+    }
+
+    /// Number of the signed-in user's unread messages.
+    pub fn unread_messages() -> String {
+        routes::CountUnreadMessages::path(&[])
+    }
+
+    /// Messages of a conversation, and sending one.
+    pub fn conversation_messages(conversation_id: i64) -> String {
+        routes::ListMessages::path(&[&conversation_id])
+    }
+
+    /// Page of the messages of a conversation.
+    pub fn conversation_messages_page(conversation_id: i64, page: Pagination) -> String {
+        format!(
+            "{}?{}",
+            conversation_messages(conversation_id),
+            page.query()
+        )
+    }
+
+    /// Site-wide counters.
+    pub fn admin_stats() -> String {
+        routes::GetAdminStats::path(&[])
+    }
+
+    /// Page of users for management.
+    pub fn admin_users_page(page: Pagination) -> String {
+        format!("{}?{}", routes::ListAdminUsers::path(&[]), page.query())
+    }
+
+    /// A user, for management.
+    pub fn admin_user(id: i64) -> String {
+        routes::UpdateAdminUser::path(&[&id])
+    }
+
+    /// A user's shadow ban (`PUT` to ban, `DELETE` to lift).
+    pub fn shadow_ban(user_id: i64) -> String {
+        routes::ShadowBanUser::path(&[&user_id])
+    }
+
+    /// Page of the comment moderation queue.
+    pub fn moderation_queue_page(page: Pagination) -> String {
+        format!("{}?{}", routes::ModerationQueue::path(&[]), page.query())
+    }
+
+    /// A comment, for removal by a moderator.
+    pub fn moderated_comment(id: i64) -> String {
+        routes::RemoveComment::path(&[&id])
+    }
+
+    /// Page of content flagged by moderation rules.
+    pub fn flagged_content_page(page: Pagination) -> String {
+        format!("{}?{}", routes::ListFlaggedContent::path(&[]), page.query())
+    }
+
+    /// A flag, for dismissal.
+    pub fn flag(id: i64) -> String {
+        routes::DismissFlag::path(&[&id])
+    }
+
+    /// Moderation rules, and creating one.
+    pub fn moderation_rules() -> String {
+        routes::ListModerationRules::path(&[])
+    }
+
+    /// A single moderation rule.
+    pub fn moderation_rule(id: i64) -> String {
+        routes::UpdateModerationRule::path(&[&id])
+    }
+
+    /// All feature flags.
+    pub fn feature_flags() -> String {
+        routes::ListFeatureFlags::path(&[])
+    }
+
+    /// A single feature flag.
+    pub fn feature_flag(name: &str) -> String {
+        routes::UpdateFeatureFlag::path(&[&name])
+    }
+
+    /// Changing the site settings.
+    pub fn admin_site_settings() -> String {
+        routes::UpdateSiteSettings::path(&[])
+    }
+
+    /// Setting and clearing the announcement banner.
+    pub fn admin_announcement() -> String {
+        routes::SetAnnouncement::path(&[])
+    }
+}
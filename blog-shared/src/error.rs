@@ -0,0 +1,53 @@
+//! Error response data transfer objects.
+
+use serde::{Deserialize, Serialize};
+
+/// Body of an HTTP error response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ErrorResponse {
+    /// Human-readable message.
+    pub error: String,
+    /// Stable machine-readable code, e.g. `post_not_found` (also sent as
+    /// gRPC metadata under `ERROR_CODE_METADATA_KEY`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// JSON pointer (RFC 6901) to the request field that could not be read,
+    /// e.g. `/tags/1` in the body or `/limit` in the query string.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pointer: Option<String>,
+    /// ID of the failed request, on 500s from a panic; quote it when
+    /// reporting the problem.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl ErrorResponse {
+    /// Creates an error body with only a message.
+    pub fn new(error: String) -> Self {
+        Self {
+            error,
+            code: None,
+            pointer: None,
+            request_id: None,
+        }
+    }
+
+    /// Sets the machine-readable code.
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Sets the pointer to the field that could not be read.
+    pub fn with_pointer(mut self, pointer: Option<String>) -> Self {
+        self.pointer = pointer;
+        self
+    }
+
+    /// Sets the ID of the failed request.
+    pub fn with_request_id(mut self, request_id: Option<String>) -> Self {
+        self.request_id = request_id;
+        self
+    }
+}
@@ -0,0 +1,54 @@
+//! A/B experiment data transfer objects.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A variant of an experiment and its share of users.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExperimentVariantDto {
+    pub name: String,
+    /// Percentage of users assigned; the weights of an experiment sum to 100.
+    pub weight: u32,
+}
+
+/// Experiment with its variants and how many users saw each.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExperimentDto {
+    pub id: i64,
+    pub name: String,
+    pub description: String,
+    /// Disabled experiments assign nobody, so clients show their defaults.
+    pub enabled: bool,
+    /// In assignment order; the first is usually the control.
+    pub variants: Vec<ExperimentVariantDto>,
+    /// Users exposed to each variant, by variant name.
+    pub exposures: BTreeMap<String, i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// All experiments, by name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExperimentListResponse {
+    pub experiments: Vec<ExperimentDto>,
+}
+
+/// Request to create an experiment, enabled right away.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreateExperimentRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub variants: Vec<ExperimentVariantDto>,
+}
+
+/// Request to change an experiment; omitted fields are kept. New variants
+/// reassign users, so the exposures recorded so far are cleared.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct UpdateExperimentRequest {
+    pub description: Option<String>,
+    pub enabled: Option<bool>,
+    pub variants: Option<Vec<ExperimentVariantDto>>,
+}
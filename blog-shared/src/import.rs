@@ -0,0 +1,54 @@
+//! Bulk post import data transfer objects.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A post exported from another blog.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportPostDto {
+    pub title: String,
+    /// Markdown content.
+    pub content: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Username to publish as; only admins may import posts of other users.
+    #[serde(default)]
+    pub author: Option<String>,
+    /// Original publication time (defaults to the time of import).
+    #[serde(default)]
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+/// Bulk import request (at most `MAX_IMPORT_POSTS` posts).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportPostsRequest {
+    pub posts: Vec<ImportPostDto>,
+    /// Validate and report without creating anything.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Outcome of importing one post, in request order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportedPostDto {
+    pub title: String,
+    /// User the post is (or would be) published as.
+    pub author_username: String,
+    /// ID of the created post (`None` on dry runs and failures).
+    pub post_id: Option<i64>,
+    /// Public ID of the created post, set along with `post_id` (absent from
+    /// servers that predate it).
+    #[serde(default)]
+    pub post_public_id: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Bulk import report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportPostsResponse {
+    pub dry_run: bool,
+    /// Posts created, or that would be created on a dry run.
+    pub imported: i64,
+    pub failed: i64,
+    pub posts: Vec<ImportedPostDto>,
+}
@@ -2,28 +2,130 @@
 //!
 //! This crate contains DTOs shared between server, client, and CLI.
 
+mod admin;
+mod analytics;
 mod auth;
+mod comment;
 pub mod constants;
+mod crosspost;
+mod diff;
+mod editing;
+pub mod endpoint;
+mod error;
+mod experiment;
+mod import;
+mod markdown;
+mod message;
+mod moderation;
+mod newsletter;
+mod notification;
+mod org;
 mod post;
 mod request;
+mod review;
+mod revision;
+pub mod routes;
+mod settings;
+mod site;
+mod tag;
+mod telemetry;
+mod text;
+mod transfer;
+mod upload;
 mod user;
 
-pub use auth::{AuthResponse, LoginRequest, RegisterRequest};
-pub use post::{PostDto, PostListResponse};
-pub use request::{CreatePostRequest, UpdatePostRequest};
-pub use user::UserDto;
+pub use admin::{
+    AdminStatsDto, AdminUserDto, AdminUserListResponse, ApiUsageDto, ApiUsageResponse,
+    AuditLogEntryDto, AuditLogResponse, CreateServiceAccountRequest, CreatedServiceAccountResponse,
+    DiagnosticSnapshotDto, DiagnosticsResponse, FeatureFlagDto, FeatureFlagListResponse,
+    RetentionPolicyDto, RetentionReportDto, ServiceAccountDto, ServiceAccountListResponse,
+    ServiceAccountQuotaRequest, ServiceScope, ShadowBanRequest, UpdateFeatureFlagRequest,
+    UpdateUserRequest,
+};
+pub use analytics::{
+    AnalyticsBatchRequest, AnalyticsEventDto, AnalyticsEventKind, AnalyticsReportResponse,
+    DailyStatsDto, PageStatsDto,
+};
+pub use auth::{
+    AuthResponse, FeaturesResponse, InviteDto, LoginRequest, RecoverAccountRequest, RegisterRequest,
+};
+pub use comment::{CommentDto, CommentListResponse, CreateCommentRequest, UpdateCommentRequest};
+pub use crosspost::{
+    CrosspostDto, CrosspostListResponse, CrosspostQuery, CrosspostStatus, CrosspostTarget,
+    IntegrationDto, IntegrationListResponse, IntegrationTokenRequest,
+};
+pub use diff::{DiffLineDto, DiffOp, diff_lines};
+pub use editing::{EditingPresenceDto, EditorDto};
+pub use endpoint::{ApiBaseUrl, CursorPage, Pagination};
+pub use error::ErrorResponse;
+pub use experiment::{
+    CreateExperimentRequest, ExperimentDto, ExperimentListResponse, ExperimentVariantDto,
+    UpdateExperimentRequest,
+};
+pub use import::{ImportPostDto, ImportPostsRequest, ImportPostsResponse, ImportedPostDto};
+pub use markdown::MarkdownPreviewResponse;
+pub use message::{
+    ConversationDto, ConversationListResponse, MessageDto, MessageListResponse, SendMessageRequest,
+    StartConversationRequest, UnreadCountResponse,
+};
+pub use moderation::{
+    ContentKind, CreateModerationRuleRequest, FlaggedContentDto, FlaggedContentListResponse,
+    ModerationRuleDto, ModerationRuleListResponse, RuleAction, RuleKind,
+    UpdateModerationRuleRequest,
+};
+pub use newsletter::{SubscribeRequest, SubscriptionStatus, SubscriptionStatusResponse};
+pub use notification::{
+    MarkNotificationsReadRequest, NotificationDto, NotificationKind, NotificationListResponse,
+};
+pub use org::{
+    CreateOrgRequest, MembershipDto, MembershipListResponse, OrgDto, OrgMemberDto,
+    OrgProfileResponse, OrgRole, SetOrgMemberRequest,
+};
+pub use post::{
+    BrokenLinkDto, BrokenLinkListResponse, LinkPreviewDto, PostAuthor, PostDto, PostListResponse,
+    TranslationDto, TranslationListResponse, TrashListResponse, TrashedPostDto,
+};
+pub use request::{CreatePostRequest, CreateTranslationRequest, UpdatePostRequest};
+pub use review::{
+    PostHistoryResponse, PostStatus, PostTransitionDto, ReviewDecision, ReviewNoteDto,
+    ReviewPostRequest,
+};
+pub use revision::{RevisionDiffResponse, RevisionDto, RevisionListResponse};
+pub use settings::{
+    ChangeEmailRequest, ChangePasswordRequest, ConfirmEmailChangeRequest, DeleteAccountRequest,
+    PendingEmailChangeResponse, PreferencesDto, ProfileDto, RecoveryCodesResponse,
+    RegenerateRecoveryCodesRequest, SessionDto, SessionListResponse, SettingsDto, Theme,
+};
+pub use site::{
+    AnnouncementDto, AnnouncementResponse, AnnouncementSeverity, RegistrationMode,
+    SetAnnouncementRequest, SiteSettingsDto, UpdateSiteSettingsRequest,
+};
+pub use tag::{
+    MergeTagRequest, TagDto, TagListResponse, TagPageResponse, TagSort, TagSuggestionDto,
+    TagSuggestionResponse, UpdateTagRequest,
+};
+pub use telemetry::ClientErrorReport;
+pub use text::{excerpt, truncate};
+pub use transfer::{PostTransferDto, TransferListResponse, TransferPostRequest, TransferStatus};
+pub use upload::{UploadDto, UploadQuery};
+pub use user::{
+    MeResponse, PublicUserDto, Role, UsageDto, UserDto, UserSuggestionDto, UserSuggestionResponse,
+};
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::Utc;
+    use std::collections::HashMap;
 
     #[test]
     fn test_user_dto_serialization() {
         let user = UserDto {
             id: 1,
+            public_id: String::new(),
             username: "testuser".to_string(),
             email: "test@example.com".to_string(),
+            role: Role::Admin,
             created_at: Utc::now(),
         };
 
@@ -33,24 +135,226 @@ mod tests {
         let parsed: UserDto = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.id, 1);
         assert_eq!(parsed.username, "testuser");
+        assert_eq!(parsed.role, Role::Admin);
+    }
+
+    #[test]
+    fn test_role_ordering_and_parsing() {
+        assert!(Role::Admin.can_moderate());
+        assert!(Role::Moderator.can_moderate());
+        assert!(!Role::User.can_moderate());
+        assert_eq!("moderator".parse::<Role>(), Ok(Role::Moderator));
+        assert!("root".parse::<Role>().is_err());
+
+        let json = r#"{"id":1,"username":"a","email":"a@b.c","created_at":"2025-01-01T00:00:00Z"}"#;
+        let parsed: UserDto = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.role, Role::User);
+    }
+
+    #[test]
+    fn test_site_settings_defaults_and_registration_mode() {
+        let settings = SiteSettingsDto::default();
+        assert_eq!(settings.title, constants::DEFAULT_SITE_TITLE);
+        assert_eq!(settings.registration_mode, RegistrationMode::Open);
+
+        let req: UpdateSiteSettingsRequest =
+            serde_json::from_str(r#"{"registration_mode":"invite_only"}"#).unwrap();
+        assert_eq!(req.registration_mode, Some(RegistrationMode::InviteOnly));
+        assert!(req.title.is_none());
+        assert_eq!("closed".parse(), Ok(RegistrationMode::Closed));
+        assert!("invite-only".parse::<RegistrationMode>().is_err());
+    }
+
+    #[test]
+    fn test_analytics_event_kind_names() {
+        let batch = AnalyticsBatchRequest {
+            events: vec![AnalyticsEventDto {
+                kind: AnalyticsEventKind::ReadComplete,
+                path: "/posts/1".to_string(),
+            }],
+        };
+        let json = serde_json::to_string(&batch).unwrap();
+        assert!(json.contains(r#""kind":"read_complete""#));
+        assert_eq!(
+            "page_view".parse::<AnalyticsEventKind>(),
+            Ok(AnalyticsEventKind::PageView)
+        );
+        assert!("click".parse::<AnalyticsEventKind>().is_err());
+    }
+
+    #[test]
+    fn test_me_response_reads_as_user_dto() {
+        let me = MeResponse {
+            user: UserDto {
+                id: 7,
+                public_id: String::new(),
+                username: "mod".to_string(),
+                email: "mod@example.com".to_string(),
+                role: Role::Moderator,
+                created_at: Utc::now(),
+            },
+            permissions: Role::Moderator
+                .permissions()
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+            usage: UsageDto::default(),
+            unread_messages: 2,
+            feature_flags: [("comments".to_string(), true)].into(),
+            preferences: PreferencesDto::default(),
+        };
+
+        let json = serde_json::to_string(&me).unwrap();
+        let user: UserDto = serde_json::from_str(&json).unwrap();
+        assert_eq!(user.id, 7);
+        assert_eq!(user.role, Role::Moderator);
+
+        let parsed: MeResponse = serde_json::from_str(&json).unwrap();
+        assert!(
+            parsed
+                .permissions
+                .contains(&"moderation:review".to_string())
+        );
+        assert!(!parsed.permissions.contains(&"admin:dashboard".to_string()));
+        assert_eq!(parsed.feature_flags.get("comments"), Some(&true));
     }
 
     #[test]
     fn test_post_dto_serialization() {
         let post = PostDto {
             id: 1,
+            public_id: "0b8e4a52-2f0d-4c4e-9d8e-2a4c1f6b7e90".to_string(),
             title: "Test Post".to_string(),
             content: "Content".to_string(),
+            content_html: "<p>Content</p>\n".to_string(),
             author_id: 42,
+            author_public_id: "5d1c8f3e-7a2b-4e6f-8c9d-0e1f2a3b4c5d".to_string(),
             author_username: "author".to_string(),
+            author: Some(PostAuthor::Org {
+                id: 7,
+                slug: "acme".to_string(),
+                name: "Acme".to_string(),
+            }),
+            tags: vec!["rust".to_string()],
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            link_previews: vec![],
+            is_owner: true,
+            lang: "pt-BR".to_string(),
+            translation_group_id: "0b8e4a52-2f0d-4c4e-9d8e-2a4c1f6b7e90".to_string(),
+            canonical_url: Some("https://example.com/hello".to_string()),
+            original_source: None,
+            metadata: HashMap::from([("series:order".to_string(), serde_json::json!(2))]),
+            status: PostStatus::InReview,
+            snippet: None,
         };
 
         let json = serde_json::to_string(&post).unwrap();
         let parsed: PostDto = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.title, "Test Post");
-        assert_eq!(parsed.author_id, 42);
+        // Numeric IDs stay on the server
+        assert!(!json.contains(r#""id":1"#) && !json.contains("author_id"));
+        assert_eq!((parsed.id, parsed.author_id), (0, 0));
+        assert_eq!(parsed.author_public_id, post.author_public_id);
+        assert_eq!(parsed.tags, vec!["rust"]);
+        assert_eq!(parsed.public_id, post.public_id);
+        assert_eq!(parsed.lang, "pt-BR");
+        assert_eq!(parsed.canonical_url, post.canonical_url);
+        assert_eq!(parsed.metadata, post.metadata);
+        assert_eq!(parsed.status, PostStatus::InReview);
+        assert!(json.contains(r#""status":"in_review""#));
+        assert!(json.contains(r#""author":{"type":"org","id":7,"slug":"acme","name":"Acme"}"#));
+        assert_eq!(parsed.author, post.author);
+        assert_eq!(parsed.byline(), "Acme");
+        assert_eq!(parsed.snippet, None);
+        assert!(!json.contains("snippet"));
+    }
+
+    #[test]
+    fn test_post_dto_without_tags_deserializes() {
+        let json = r#"{"id":1,"title":"Old","content":"C","author_id":2,"author_username":"a","created_at":"2025-01-01T00:00:00Z","updated_at":"2025-01-01T00:00:00Z"}"#;
+
+        let parsed: PostDto = serde_json::from_str(json).unwrap();
+        assert!(parsed.public_id.is_empty());
+        assert!(parsed.lang.is_empty());
+        assert!(parsed.canonical_url.is_none());
+        assert!(parsed.metadata.is_empty());
+        assert_eq!(parsed.status, PostStatus::Published);
+        assert!(parsed.tags.is_empty());
+        assert!(parsed.content_html.is_empty());
+        assert!(parsed.link_previews.is_empty());
+        assert!(parsed.author.is_none());
+        assert_eq!(parsed.byline(), "a");
+    }
+
+    #[test]
+    fn test_old_and_newer_responses_deserialize() {
+        // Bodies from the first API version, before any optional field
+        let auth: AuthResponse = serde_json::from_str(
+            r#"{"token":"t","user":{"id":1,"username":"a","email":"a@b.c","created_at":"2025-01-01T00:00:00Z"}}"#,
+        )
+        .unwrap();
+        assert_eq!(auth.user.role, Role::User);
+        assert!(auth.user.public_id.is_empty());
+        assert!(auth.recovery_codes.is_empty());
+
+        let error: ErrorResponse = serde_json::from_str(r#"{"error":"Not found"}"#).unwrap();
+        assert_eq!(error, ErrorResponse::new("Not found".to_string()));
+
+        let comments: CommentListResponse = serde_json::from_str(
+            r#"{"comments":[{"id":1,"post_id":2,"author_id":3,"author_username":"a","content":"Hi","created_at":"2025-01-01T00:00:00Z","updated_at":"2025-01-01T00:00:00Z"}],"total":1}"#,
+        )
+        .unwrap();
+        assert_eq!(comments.comments[0].content, "Hi");
+        assert_eq!(comments.next_cursor, None);
+
+        // Fields added by a newer server are ignored
+        let posts: PostListResponse = serde_json::from_str(
+            r#"{"posts":[{"id":1,"title":"T","content":"C","author_id":2,"author_username":"a","created_at":"2025-01-01T00:00:00Z","updated_at":"2025-01-01T00:00:00Z","reading_time":3}],"total":1,"next_cursor":"abc"}"#,
+        )
+        .unwrap();
+        assert_eq!(posts.posts[0].title, "T");
+        assert_eq!(posts.total, 1);
+    }
+
+    #[test]
+    fn test_request_builders_and_summaries() {
+        let req = CreatePostRequest::new("Title", "Body")
+            .with_tags(vec!["rust".to_string()])
+            .with_org("acme");
+        assert_eq!(req.tags, vec!["rust"]);
+        assert_eq!(req.org.as_deref(), Some("acme"));
+        assert!(req.lang.is_none());
+
+        let update = UpdatePostRequest::default().with_title("New");
+        assert_eq!(update.title.as_deref(), Some("New"));
+        assert!(update.content.is_none() && update.tags.is_none());
+
+        let statuses: std::collections::HashSet<_> =
+            [PostStatus::Draft, PostStatus::Draft, PostStatus::Published].into();
+        assert_eq!(statuses.len(), 2);
+        assert!(AnnouncementSeverity::Critical > AnnouncementSeverity::Info);
+
+        let mut post = PostDto::new(
+            3,
+            "Hello".to_string(),
+            String::new(),
+            1,
+            "alice".to_string(),
+            Utc::now(),
+            Utc::now(),
+        );
+        post.public_id = "0b8e4a52".to_string();
+        assert_eq!(post.to_string(), "[0b8e4a52] Hello by alice");
+        post.author = Some(PostAuthor::Org {
+            id: 7,
+            slug: "acme".to_string(),
+            name: "Acme".to_string(),
+        });
+        assert_eq!(post.to_string(), "[0b8e4a52] Hello by Acme");
+
+        let user = UserDto::new(1, "alice".to_string(), "a@b.c".to_string(), Utc::now());
+        assert_eq!(user.to_string(), "alice (ID: 1)");
     }
 
     #[test]
@@ -59,13 +363,235 @@ mod tests {
             token: "jwt.token.here".to_string(),
             user: UserDto {
                 id: 1,
+                public_id: String::new(),
                 username: "user".to_string(),
                 email: "user@example.com".to_string(),
+                role: Role::User,
                 created_at: Utc::now(),
             },
+            recovery_codes: Vec::new(),
         };
 
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("jwt.token.here"));
+        assert!(!json.contains("recovery_codes"));
+    }
+
+    #[test]
+    fn test_theme_round_trip() {
+        let prefs = PreferencesDto {
+            theme: Theme::Dark,
+            ..PreferencesDto::default()
+        };
+
+        let json = serde_json::to_string(&prefs).unwrap();
+        assert!(json.contains(r#""theme":"dark""#));
+        assert!(json.contains(r#""timezone":"UTC""#));
+
+        // Clients that predate time zones and locales still parse
+        let old: PreferencesDto =
+            serde_json::from_str(r#"{"theme":"light","language":"ru"}"#).unwrap();
+        assert_eq!(old.timezone, "UTC");
+        assert_eq!(old.locale, "en-US");
+        assert_eq!("dark".parse::<Theme>(), Ok(Theme::Dark));
+        assert!("sepia".parse::<Theme>().is_err());
+    }
+
+    #[test]
+    fn test_crosspost_target_round_trip() {
+        let query: CrosspostQuery = serde_json::from_str(r#"{"target":"devto"}"#).unwrap();
+        assert_eq!(query.target, CrosspostTarget::Devto);
+        assert_eq!(
+            "hashnode".parse::<CrosspostTarget>(),
+            Ok(CrosspostTarget::Hashnode)
+        );
+        assert!("medium".parse::<CrosspostTarget>().is_err());
+        assert_eq!(CrosspostStatus::Failed.to_string(), "failed");
+    }
+
+    #[test]
+    fn test_comment_dto_serialization() {
+        let mut comment = CommentDto::new(
+            7,
+            1,
+            42,
+            "reader".to_string(),
+            "Nice post".to_string(),
+            Utc::now(),
+            Utc::now(),
+        );
+        comment.post_public_id = "0b8e4a52".to_string();
+        comment.author_public_id = "5d1c8f3e".to_string();
+
+        let json = serde_json::to_string(&comment).unwrap();
+        assert!(!json.contains("post_id\"") && !json.contains("author_id"));
+        let parsed: CommentDto = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            parsed,
+            CommentDto {
+                post_id: 0,
+                author_id: 0,
+                ..comment
+            }
+        );
+    }
+
+    #[test]
+    fn test_private_dtos_leave_out_numeric_ids() {
+        let message = MessageDto {
+            id: 3,
+            conversation_id: 2,
+            sender_id: 42,
+            sender_public_id: "5d1c8f3e".to_string(),
+            sender_username: "reader".to_string(),
+            content: "Hi".to_string(),
+            created_at: Utc::now(),
+        };
+        let json = serde_json::to_string(&message).unwrap();
+        assert!(!json.contains("conversation_id") && !json.contains("sender_id"));
+        let parsed: MessageDto = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            (parsed.sender_id, parsed.sender_public_id.as_str()),
+            (0, "5d1c8f3e")
+        );
+
+        let presence = EditingPresenceDto {
+            post_id: 1,
+            post_public_id: "0b8e4a52".to_string(),
+            editors: vec![EditorDto {
+                user_id: 42,
+                user_public_id: "5d1c8f3e".to_string(),
+                username: "reader".to_string(),
+                since: Utc::now(),
+            }],
+        };
+        let json = serde_json::to_string(&presence).unwrap();
+        assert!(!json.contains("post_id") && !json.contains("user_id"));
+    }
+
+    #[test]
+    fn test_route_catalog_paths() {
+        use routes::Route;
+
+        assert_eq!(
+            routes::DiffRevisions::path(&[&"abc", &2, &5]),
+            "/api/posts/abc/revisions/2/diff/5"
+        );
+        assert_eq!(routes::ListPosts::path(&[]), endpoint::paths::posts());
+        assert_eq!(routes::DeleteComment::METHOD, routes::Method::Delete);
+        assert_eq!(
+            endpoint::paths::conversation_messages_page(3, Pagination::default()),
+            "/api/conversations/3/messages?limit=10&offset=0"
+        );
+        assert_eq!(
+            endpoint::paths::shadow_ban(7),
+            "/api/admin/users/7/shadow-ban"
+        );
+
+        let mut seen = std::collections::HashSet::new();
+        for (method, path) in routes::CATALOG {
+            assert!(path.starts_with('/') && !path.starts_with(constants::API_PREFIX));
+            assert!(
+                seen.insert((method, path)),
+                "duplicate route {method} {path}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_pagination_clamp_and_paths() {
+        assert_eq!(Pagination::clamp(None, None), Pagination::default());
+        assert_eq!(
+            Pagination::clamp(Some(1000), Some(-5)),
+            Pagination {
+                limit: constants::MAX_LIMIT,
+                offset: 0
+            }
+        );
+        assert_eq!(
+            Pagination::clamp(Some(0), Some(20)).limit,
+            constants::DEFAULT_LIMIT
+        );
+
+        let base = ApiBaseUrl::new("http://localhost:8080/");
+        assert_eq!(
+            base.join(&endpoint::paths::post_comments_page(
+                "7f3c",
+                Pagination::clamp(Some(5), Some(10))
+            )),
+            "http://localhost:8080/api/posts/7f3c/comments?limit=5&offset=10"
+        );
+        assert_eq!(
+            ApiBaseUrl::from_host("localhost", constants::DEFAULT_HTTP_PORT).as_str(),
+            constants::DEFAULT_HTTP_URL
+        );
+        assert!(constants::DEFAULT_SITE_URL.ends_with(&constants::DEFAULT_SITE_PORT.to_string()));
+        assert!(constants::DEFAULT_GRPC_URL.ends_with(&constants::DEFAULT_GRPC_PORT.to_string()));
+    }
+
+    #[test]
+    fn test_cursor_page_clamp_and_paths() {
+        let first = CursorPage::clamp(Some(1000), Some(String::new()));
+        assert_eq!(first.limit, constants::MAX_LIMIT);
+        assert_eq!(first.cursor, None);
+        assert_eq!(
+            endpoint::paths::notifications_page(&first),
+            "/api/me/notifications?limit=100"
+        );
+        assert_eq!(
+            endpoint::paths::post_comments_after(
+                "7f3c",
+                &CursorPage::clamp(Some(5), Some("MjAyNQ".to_string()))
+            ),
+            "/api/posts/7f3c/comments?limit=5&cursor=MjAyNQ"
+        );
+
+        let json = serde_json::to_string(&NotificationKind::Comment).unwrap();
+        assert_eq!(json, r#""comment""#);
+        assert_eq!("comment".parse(), Ok(NotificationKind::Comment));
+        assert!("like".parse::<NotificationKind>().is_err());
+    }
+
+    #[test]
+    fn test_truncate_keeps_graphemes_whole() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("exactly", 7), "exactly");
+        assert_eq!(truncate("hello world", 7), "hello…");
+        assert_eq!(truncate("Привет, мир", 7), "Привет…");
+        // Family emoji (ZWJ sequence) and a flag count as one grapheme each
+        assert_eq!(truncate("👨‍👩‍👧🇯🇵abc", 3), "👨‍👩‍👧🇯🇵…");
+        // e + combining acute accent stays together
+        assert_eq!(truncate("cafe\u{301}s!", 5), "cafe\u{301}…");
+        assert_eq!(truncate("abc", 0), "");
+
+        assert_eq!(
+            excerpt("First line\n\n  second line", 100),
+            "First line second line"
+        );
+    }
+
+    #[test]
+    fn test_diff_lines_numbers_both_sides() {
+        let diff = diff_lines("a\nb\nc\nd", "a\nc\nx\nd");
+        let ops: Vec<_> = diff
+            .iter()
+            .map(|line| (line.op, line.text.as_str()))
+            .collect();
+        assert_eq!(
+            ops,
+            [
+                (DiffOp::Same, "a"),
+                (DiffOp::Removed, "b"),
+                (DiffOp::Same, "c"),
+                (DiffOp::Added, "x"),
+                (DiffOp::Same, "d"),
+            ]
+        );
+        assert_eq!((diff[1].old_line, diff[1].new_line), (Some(2), None));
+        assert_eq!((diff[3].old_line, diff[3].new_line), (None, Some(3)));
+        assert_eq!((diff[4].old_line, diff[4].new_line), (Some(4), Some(4)));
+
+        let json = serde_json::to_string(&diff[1]).unwrap();
+        assert!(json.contains(r#""op":"removed""#));
     }
 }
@@ -0,0 +1,12 @@
+//! Markdown preview data transfer objects.
+
+use serde::{Deserialize, Serialize};
+
+/// Markdown rendered for the editor preview.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarkdownPreviewResponse {
+    /// Sanitized HTML.
+    pub html: String,
+    /// Markdown syntax the renderer supports.
+    pub supported: Vec<String>,
+}
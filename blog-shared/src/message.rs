@@ -0,0 +1,77 @@
+//! Private message data transfer objects.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Conversation between the current user and one other user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversationDto {
+    pub id: i64,
+    /// Internal ID of the other user, never sent over HTTP (0 when read
+    /// from JSON).
+    #[serde(skip_serializing, default)]
+    pub other_user_id: i64,
+    /// Public ID of the other user (empty from servers that predate it).
+    #[serde(default)]
+    pub other_user_public_id: String,
+    pub other_username: String,
+    /// Start of the newest message.
+    pub last_message: Option<String>,
+    pub last_message_at: Option<DateTime<Utc>>,
+    /// Messages from the other user the current user has not seen.
+    pub unread_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Paginated inbox, most recently active conversation first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversationListResponse {
+    pub conversations: Vec<ConversationDto>,
+    pub total: i64,
+}
+
+/// Private message with sender info.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageDto {
+    pub id: i64,
+    /// Conversation of the message, never sent over HTTP: clients read
+    /// messages through its own path (0 when read from JSON).
+    #[serde(skip_serializing, default)]
+    pub conversation_id: i64,
+    /// Internal ID of the sender, never sent over HTTP like `conversation_id`.
+    #[serde(skip_serializing, default)]
+    pub sender_id: i64,
+    /// Public ID of the sender (empty from servers that predate it).
+    #[serde(default)]
+    pub sender_public_id: String,
+    pub sender_username: String,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Paginated messages of a conversation, newest first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageListResponse {
+    pub messages: Vec<MessageDto>,
+    pub total: i64,
+}
+
+/// Sends a first message to a user, starting (or reusing) the conversation with them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StartConversationRequest {
+    pub username: String,
+    pub content: String,
+}
+
+/// Sends a message in an existing conversation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SendMessageRequest {
+    pub content: String,
+}
+
+/// Number of unread messages across all conversations, or of unread
+/// notifications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnreadCountResponse {
+    pub unread: i64,
+}
@@ -0,0 +1,170 @@
+//! Content moderation rule and flag data transfer objects.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How a rule's pattern is matched against content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleKind {
+    /// Comma- or newline-separated words and phrases, matched
+    /// case-insensitively as whole words.
+    Keyword,
+    /// A regular expression (add `(?i)` for case-insensitive matching).
+    Regex,
+}
+
+impl RuleKind {
+    /// Returns the lowercase name used in JSON and storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleKind::Keyword => "keyword",
+            RuleKind::Regex => "regex",
+        }
+    }
+}
+
+impl fmt::Display for RuleKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RuleKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "keyword" => Ok(RuleKind::Keyword),
+            "regex" => Ok(RuleKind::Regex),
+            other => Err(format!("Unknown rule kind: {other}")),
+        }
+    }
+}
+
+/// What happens to content that matches a rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleAction {
+    /// Publish, but add to the moderation queue.
+    Flag,
+    /// Reject the post or comment.
+    Block,
+}
+
+impl RuleAction {
+    /// Returns the lowercase name used in JSON and storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleAction::Flag => "flag",
+            RuleAction::Block => "block",
+        }
+    }
+}
+
+impl fmt::Display for RuleAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RuleAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "flag" => Ok(RuleAction::Flag),
+            "block" => Ok(RuleAction::Block),
+            other => Err(format!("Unknown rule action: {other}")),
+        }
+    }
+}
+
+/// Automatic moderation rule with its hit statistics.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModerationRuleDto {
+    pub id: i64,
+    pub name: String,
+    pub kind: RuleKind,
+    pub pattern: String,
+    pub action: RuleAction,
+    pub enabled: bool,
+    /// Posts and comments checked while the rule was enabled.
+    pub checked_count: i64,
+    /// Checks the rule matched.
+    pub hit_count: i64,
+    /// `hit_count / checked_count` (0 before the first check).
+    pub hit_rate: f64,
+    pub last_hit_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// All moderation rules, oldest first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModerationRuleListResponse {
+    pub rules: Vec<ModerationRuleDto>,
+}
+
+/// New moderation rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreateModerationRuleRequest {
+    pub name: String,
+    pub kind: RuleKind,
+    pub pattern: String,
+    pub action: RuleAction,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+/// Changes to a moderation rule (omitted fields stay unchanged).
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct UpdateModerationRuleRequest {
+    pub name: Option<String>,
+    pub kind: Option<RuleKind>,
+    pub pattern: Option<String>,
+    pub action: Option<RuleAction>,
+    pub enabled: Option<bool>,
+}
+
+/// Type of flagged content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentKind {
+    Post,
+    Comment,
+}
+
+/// Post or comment flagged by a rule, waiting for review.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlaggedContentDto {
+    pub id: i64,
+    pub kind: ContentKind,
+    /// The flagged post, or the post the flagged comment belongs to.
+    pub post_id: i64,
+    /// Public ID of that post, for links to it.
+    #[serde(default)]
+    pub post_public_id: String,
+    pub comment_id: Option<i64>,
+    pub author_id: i64,
+    pub author_username: String,
+    /// Post title or comment text.
+    pub excerpt: String,
+    pub rule_id: i64,
+    pub rule_name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Paginated list of flagged content, newest first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlaggedContentListResponse {
+    pub items: Vec<FlaggedContentDto>,
+    pub total: i64,
+}
+
+/// Rules are enabled unless created disabled.
+fn default_enabled() -> bool {
+    true
+}
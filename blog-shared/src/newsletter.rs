@@ -0,0 +1,39 @@
+//! Newsletter subscription data transfer objects.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Request body for subscribing an email address to the weekly digest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubscribeRequest {
+    pub email: String,
+}
+
+/// State of a newsletter subscription.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubscriptionStatus {
+    /// Waiting for the subscriber to follow the confirmation link.
+    Pending,
+    /// Receiving the weekly digest.
+    Confirmed,
+    /// Opted out through an unsubscribe link.
+    Unsubscribed,
+}
+
+impl fmt::Display for SubscriptionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SubscriptionStatus::Pending => "pending",
+            SubscriptionStatus::Confirmed => "confirmed",
+            SubscriptionStatus::Unsubscribed => "unsubscribed",
+        })
+    }
+}
+
+/// Response of the subscribe, confirm and unsubscribe endpoints.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubscriptionStatusResponse {
+    pub status: SubscriptionStatus,
+}
@@ -0,0 +1,87 @@
+//! Notification data transfer objects.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// What a notification is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// Someone commented on one of the user's posts.
+    Comment,
+}
+
+impl NotificationKind {
+    /// Returns the snake_case name used in JSON and storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::Comment => "comment",
+        }
+    }
+}
+
+impl fmt::Display for NotificationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for NotificationKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "comment" => Ok(NotificationKind::Comment),
+            other => Err(format!("Unknown notification kind: {other}")),
+        }
+    }
+}
+
+/// Activity on the current user's content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationDto {
+    pub id: i64,
+    pub kind: NotificationKind,
+    /// User whose action caused the notification; internal, never sent
+    /// over HTTP (0 when read from JSON).
+    #[serde(skip_serializing, default)]
+    pub actor_id: i64,
+    /// Public ID of that user (empty from servers that predate it).
+    #[serde(default)]
+    pub actor_public_id: String,
+    pub actor_username: String,
+    /// Internal ID of the post, never sent over HTTP like `actor_id`.
+    #[serde(skip_serializing, default)]
+    pub post_id: i64,
+    /// Public ID of the post (empty from servers that predate it).
+    #[serde(default)]
+    pub post_public_id: String,
+    /// The new comment, for `comment` notifications.
+    pub comment_id: Option<i64>,
+    pub read: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Page of notifications, newest first.
+///
+/// There is no total: fetch pages with `next_cursor`, and the unread count
+/// from its own endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationListResponse {
+    pub notifications: Vec<NotificationDto>,
+    /// Cursor of the next page; absent on the last page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Marks notifications as read.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarkNotificationsReadRequest {
+    /// Newest notification the user has seen; absent marks all of them, so
+    /// pass the first ID shown to keep later arrivals unread.
+    #[serde(default)]
+    pub up_to_id: Option<i64>,
+}
@@ -0,0 +1,111 @@
+//! Organization data transfer objects.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::PostDto;
+
+/// What a member may do in an organization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrgRole {
+    /// Manages members, and may edit and delete all of the organization's posts.
+    Owner,
+    /// May post as the organization and edit its posts.
+    Writer,
+}
+
+impl OrgRole {
+    /// Returns the lowercase name used in JSON and storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrgRole::Owner => "owner",
+            OrgRole::Writer => "writer",
+        }
+    }
+}
+
+impl fmt::Display for OrgRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OrgRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "owner" => Ok(OrgRole::Owner),
+            "writer" => Ok(OrgRole::Writer),
+            other => Err(format!("Unknown organization role: {other}")),
+        }
+    }
+}
+
+/// An organization's public profile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrgDto {
+    pub id: i64,
+    /// Lowercase handle used in URLs and when posting as the organization.
+    pub slug: String,
+    pub name: String,
+    pub bio: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A member of an organization.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrgMemberDto {
+    /// Internal ID, never sent over HTTP (0 when read from JSON).
+    #[serde(skip_serializing, default)]
+    pub user_id: i64,
+    /// Public ID of the member (empty from servers that predate it).
+    #[serde(default)]
+    pub public_id: String,
+    pub username: String,
+    pub role: OrgRole,
+    pub joined_at: DateTime<Utc>,
+}
+
+/// Request body for creating an organization; the creator becomes its owner.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreateOrgRequest {
+    pub slug: String,
+    pub name: String,
+    #[serde(default)]
+    pub bio: Option<String>,
+}
+
+/// Request body for adding a member or changing their role.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetOrgMemberRequest {
+    pub role: OrgRole,
+}
+
+/// An organization's profile page: its members and a page of its posts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrgProfileResponse {
+    pub org: OrgDto,
+    /// Owners first, then by username.
+    pub members: Vec<OrgMemberDto>,
+    pub posts: Vec<PostDto>,
+    /// Number of the organization's posts across all pages.
+    pub total: i64,
+}
+
+/// An organization the current user belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MembershipDto {
+    pub org: OrgDto,
+    pub role: OrgRole,
+}
+
+/// The current user's organizations, by name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MembershipListResponse {
+    pub orgs: Vec<MembershipDto>,
+}
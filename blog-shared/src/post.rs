@@ -1,23 +1,240 @@
 //! Post data transfer objects.
 
+use std::collections::HashMap;
+use std::fmt;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::PostStatus;
+
 /// Post data transfer object with author info.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct PostDto {
+    /// Internal ID, never sent over HTTP (0 when read from JSON), where
+    /// posts are named by `public_id`.
+    #[serde(skip_serializing, default)]
     pub id: i64,
+    /// Random ID used in API URLs (empty from servers that predate it).
+    #[serde(default)]
+    pub public_id: String,
     pub title: String,
     pub content: String,
+    /// Content rendered from Markdown as sanitized HTML.
+    #[serde(default)]
+    pub content_html: String,
+    /// User who wrote the post, also for posts of an organization. Never
+    /// sent over HTTP, like `id`.
+    #[serde(skip_serializing, default)]
     pub author_id: i64,
+    /// Public ID of the user who wrote the post (empty from servers that
+    /// predate it).
+    #[serde(default)]
+    pub author_public_id: String,
     pub author_username: String,
+    /// Who the post is published under (`None` from servers that predate
+    /// organizations).
+    #[serde(default)]
+    pub author: Option<PostAuthor>,
+    #[serde(default)]
+    pub tags: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Metadata of external pages the post links to (once fetched).
+    #[serde(default)]
+    pub link_previews: Vec<LinkPreviewDto>,
+    /// Whether the requesting user wrote the post (false for anonymous viewers).
+    #[serde(default)]
+    pub is_owner: bool,
+    /// Language code, e.g. `en` or `pt-BR` (empty from servers that predate it).
+    #[serde(default)]
+    pub lang: String,
+    /// Shared by all language variants of the post.
+    #[serde(default)]
+    pub translation_group_id: String,
+    /// Address search engines should treat as canonical, for posts
+    /// syndicated from elsewhere.
+    #[serde(default)]
+    pub canonical_url: Option<String>,
+    /// Link to where the post was first published.
+    #[serde(default)]
+    pub original_source: Option<String>,
+    /// Custom fields attached by integrations.
+    #[serde(default)]
+    pub metadata: HashMap<String, serde_json::Value>,
+    /// Editorial workflow status (`published` from servers that predate it).
+    #[serde(default)]
+    pub status: PostStatus,
+    /// In search results, the best matching passage as escaped HTML with
+    /// the matched terms in `<mark>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+}
+
+impl PostDto {
+    /// Creates a post with the fields every server sends; the others start
+    /// empty, as when read from a server that predates them.
+    pub fn new(
+        id: i64,
+        title: String,
+        content: String,
+        author_id: i64,
+        author_username: String,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            public_id: String::new(),
+            title,
+            content,
+            content_html: String::new(),
+            author_id,
+            author_public_id: String::new(),
+            author_username,
+            author: None,
+            tags: Vec::new(),
+            created_at,
+            updated_at,
+            link_previews: Vec::new(),
+            is_owner: false,
+            lang: String::new(),
+            translation_group_id: String::new(),
+            canonical_url: None,
+            original_source: None,
+            metadata: HashMap::new(),
+            status: PostStatus::default(),
+            snippet: None,
+        }
+    }
+
+    /// Name to credit the post to: the organization's, or else the writer's.
+    pub fn byline(&self) -> &str {
+        match &self.author {
+            Some(PostAuthor::Org { name, .. }) => name,
+            _ => &self.author_username,
+        }
+    }
+}
+
+/// One-line summary for listings: `[public_id] title by byline`.
+impl fmt::Display for PostDto {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}] {} by {}",
+            self.public_id,
+            self.title,
+            self.byline()
+        )
+    }
+}
+
+/// Who a post is published under, tagged by `type` in JSON.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum PostAuthor {
+    /// A user's own post (`id` is never sent over HTTP).
+    User {
+        #[serde(skip_serializing, default)]
+        id: i64,
+        username: String,
+    },
+    /// A post of an organization; `PostDto::author_id` is the member who
+    /// wrote it.
+    Org { id: i64, slug: String, name: String },
+}
+
+/// Open Graph metadata of a linked page.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkPreviewDto {
+    pub url: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub site_name: Option<String>,
 }
 
 /// Paginated list of posts.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct PostListResponse {
     pub posts: Vec<PostDto>,
     pub total: i64,
 }
+
+impl PostListResponse {
+    /// Creates a page of posts out of `total`.
+    pub fn new(posts: Vec<PostDto>, total: i64) -> Self {
+        Self { posts, total }
+    }
+}
+
+/// A language variant of a post.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranslationDto {
+    /// Internal ID, never sent over HTTP (0 when read from JSON).
+    #[serde(skip_serializing, default)]
+    pub id: i64,
+    pub public_id: String,
+    pub lang: String,
+    pub title: String,
+}
+
+/// All language variants of a post, including itself, by language.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranslationListResponse {
+    pub translations: Vec<TranslationDto>,
+}
+
+/// A dead link in one of the current user's posts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BrokenLinkDto {
+    /// Internal ID of the post, never sent over HTTP (0 when read from JSON).
+    #[serde(skip_serializing, default)]
+    pub post_id: i64,
+    /// Public ID of the post (empty from servers that predate it).
+    #[serde(default)]
+    pub post_public_id: String,
+    pub post_title: String,
+    pub url: String,
+    /// HTTP status of the last check; absent when the request itself failed.
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Broken links in the current user's posts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BrokenLinkListResponse {
+    pub links: Vec<BrokenLinkDto>,
+}
+
+/// A deleted post of the current user, restorable until it is purged.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrashedPostDto {
+    /// Internal ID, never sent over HTTP (0 when read from JSON).
+    #[serde(skip_serializing, default)]
+    pub id: i64,
+    pub public_id: String,
+    pub title: String,
+    pub deleted_at: DateTime<Utc>,
+    /// When the post is removed for good; absent while trash is kept forever.
+    pub purge_at: Option<DateTime<Utc>>,
+    /// Whole days left before `purge_at`, rounded up.
+    pub days_until_purge: Option<i64>,
+}
+
+/// One-line summary for listings: `[public_id] title`.
+impl fmt::Display for TrashedPostDto {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.public_id, self.title)
+    }
+}
+
+/// The current user's deleted posts, most recently deleted first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrashListResponse {
+    pub posts: Vec<TrashedPostDto>,
+}
@@ -3,15 +3,127 @@
 use serde::{Deserialize, Serialize};
 
 /// Create post request.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CreatePostRequest {
     pub title: String,
     pub content: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Language code (default: `DEFAULT_LANGUAGE`).
+    #[serde(default)]
+    pub lang: Option<String>,
+    /// Canonical address of a post syndicated from elsewhere.
+    #[serde(default)]
+    pub canonical_url: Option<String>,
+    /// Link to where the post was first published.
+    #[serde(default)]
+    pub original_source: Option<String>,
+    /// Slug of an organization to publish the post under (members only).
+    #[serde(default)]
+    pub org: Option<String>,
 }
 
-/// Update post request (partial update).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl CreatePostRequest {
+    /// Creates a request for an untagged post in the default language.
+    pub fn new(title: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            content: content.into(),
+            tags: Vec::new(),
+            lang: None,
+            canonical_url: None,
+            original_source: None,
+            org: None,
+        }
+    }
+
+    /// Sets the tags.
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Sets the language code.
+    pub fn with_lang(mut self, lang: impl Into<String>) -> Self {
+        self.lang = Some(lang.into());
+        self
+    }
+
+    /// Sets the canonical address of a syndicated post.
+    pub fn with_canonical_url(mut self, url: impl Into<String>) -> Self {
+        self.canonical_url = Some(url.into());
+        self
+    }
+
+    /// Sets the link to where the post was first published.
+    pub fn with_original_source(mut self, url: impl Into<String>) -> Self {
+        self.original_source = Some(url.into());
+        self
+    }
+
+    /// Publishes the post under the organization with this slug.
+    pub fn with_org(mut self, slug: impl Into<String>) -> Self {
+        self.org = Some(slug.into());
+        self
+    }
+}
+
+/// Request to add a language variant of an existing post.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreateTranslationRequest {
+    /// Language code; the post must not have a variant in it yet.
+    pub lang: String,
+    pub title: String,
+    pub content: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Update post request (partial update); `UpdatePostRequest::default()`
+/// changes nothing.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct UpdatePostRequest {
     pub title: Option<String>,
     pub content: Option<String>,
+    /// Replaces all tags when present.
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    /// Replaces the canonical URL when present; empty removes it.
+    #[serde(default)]
+    pub canonical_url: Option<String>,
+    /// Replaces the original source when present; empty removes it.
+    #[serde(default)]
+    pub original_source: Option<String>,
+}
+
+impl UpdatePostRequest {
+    /// Replaces the title.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Replaces the content.
+    pub fn with_content(mut self, content: impl Into<String>) -> Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    /// Replaces all tags.
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+
+    /// Replaces the canonical URL; empty removes it.
+    pub fn with_canonical_url(mut self, url: impl Into<String>) -> Self {
+        self.canonical_url = Some(url.into());
+        self
+    }
+
+    /// Replaces the original source; empty removes it.
+    pub fn with_original_source(mut self, url: impl Into<String>) -> Self {
+        self.original_source = Some(url.into());
+        self
+    }
 }
@@ -0,0 +1,108 @@
+//! Editorial review data transfer objects.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Where a post is in the editorial workflow.
+///
+/// With editorial review off, posts are published as they are created.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostStatus {
+    /// Being written; only the author sees it.
+    Draft,
+    /// Submitted and waiting for an editor.
+    InReview,
+    /// Sent back by an editor; the author revises and resubmits.
+    ChangesRequested,
+    /// Approved by an editor and ready to publish.
+    Approved,
+    /// Visible to everyone.
+    #[default]
+    Published,
+}
+
+impl PostStatus {
+    /// Returns the snake_case name used in JSON and storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostStatus::Draft => "draft",
+            PostStatus::InReview => "in_review",
+            PostStatus::ChangesRequested => "changes_requested",
+            PostStatus::Approved => "approved",
+            PostStatus::Published => "published",
+        }
+    }
+}
+
+impl fmt::Display for PostStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PostStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "draft" => Ok(PostStatus::Draft),
+            "in_review" => Ok(PostStatus::InReview),
+            "changes_requested" => Ok(PostStatus::ChangesRequested),
+            "approved" => Ok(PostStatus::Approved),
+            "published" => Ok(PostStatus::Published),
+            other => Err(format!("Unknown post status: {other}")),
+        }
+    }
+}
+
+/// An editor's verdict on a post in review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewDecision {
+    Approve,
+    RequestChanges,
+}
+
+/// Review comment on one line of the post's content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewNoteDto {
+    /// 1-based line of the Markdown content.
+    pub line: u32,
+    pub body: String,
+}
+
+/// Request to approve a post in review or send it back.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewPostRequest {
+    pub decision: ReviewDecision,
+    /// Overall comment (required when requesting changes).
+    #[serde(default)]
+    pub comment: String,
+    /// Comments on specific lines.
+    #[serde(default)]
+    pub notes: Vec<ReviewNoteDto>,
+}
+
+/// One change of a post's workflow status.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PostTransitionDto {
+    pub id: i64,
+    pub actor_id: i64,
+    pub actor_username: String,
+    pub from_status: PostStatus,
+    pub to_status: PostStatus,
+    pub comment: Option<String>,
+    #[serde(default)]
+    pub notes: Vec<ReviewNoteDto>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Workflow history of a post, oldest first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PostHistoryResponse {
+    pub transitions: Vec<PostTransitionDto>,
+}
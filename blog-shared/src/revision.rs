@@ -0,0 +1,42 @@
+//! Post revision data transfer objects.
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::DiffLineDto;
+
+/// Saved version of a post's title and content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevisionDto {
+    /// 1 for the post as created, counting up with each edit.
+    pub number: i64,
+    pub title: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One-line summary for listings: `rN  created_at  title`.
+impl fmt::Display for RevisionDto {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "r{}  {}  {}", self.number, self.created_at, self.title)
+    }
+}
+
+/// Revisions of a post, newest first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevisionListResponse {
+    pub revisions: Vec<RevisionDto>,
+}
+
+/// Changes between two revisions of a post.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevisionDiffResponse {
+    pub post_id: i64,
+    pub from: i64,
+    pub to: i64,
+    pub title: Vec<DiffLineDto>,
+    pub content: Vec<DiffLineDto>,
+    /// The content diff as an HTML table, ready to show.
+    pub html: String,
+}
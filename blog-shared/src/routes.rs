@@ -0,0 +1,250 @@
+//! Typed catalog of the HTTP API routes clients call.
+//!
+//! Each route is a unit type naming its method, its path template under
+//! `API_PREFIX`, and its body types. The server mounts its handlers from
+//! these, and clients build URLs from them (through `endpoint::paths`), so
+//! the two cannot drift apart.
+
+use std::fmt::{self, Display};
+
+use crate::constants::API_PREFIX;
+use crate::{
+    AdminStatsDto, AdminUserDto, AdminUserListResponse, AnnouncementDto, AnnouncementResponse,
+    AuthResponse, BrokenLinkListResponse, ChangeEmailRequest, ChangePasswordRequest,
+    ClientErrorReport, CommentDto, CommentListResponse, ConfirmEmailChangeRequest, ConversationDto,
+    ConversationListResponse, CreateCommentRequest, CreateModerationRuleRequest, CreatePostRequest,
+    DeleteAccountRequest, EditingPresenceDto, FeatureFlagDto, FeatureFlagListResponse,
+    FeaturesResponse, FlaggedContentListResponse, ImportPostsRequest, ImportPostsResponse,
+    LoginRequest, MarkNotificationsReadRequest, MeResponse, MessageDto, MessageListResponse,
+    ModerationRuleDto, ModerationRuleListResponse, NotificationListResponse,
+    PendingEmailChangeResponse, PostDto, PostListResponse, PreferencesDto, ProfileDto,
+    PublicUserDto, RecoveryCodesResponse, RegenerateRecoveryCodesRequest, RegisterRequest,
+    RevisionDiffResponse, RevisionListResponse, SendMessageRequest, SessionListResponse,
+    SetAnnouncementRequest, SettingsDto, ShadowBanRequest, SiteSettingsDto,
+    StartConversationRequest, TranslationListResponse, TrashListResponse, UnreadCountResponse,
+    UpdateCommentRequest, UpdateFeatureFlagRequest, UpdateModerationRuleRequest, UpdatePostRequest,
+    UpdateSiteSettingsRequest, UpdateUserRequest, UserDto, UserSuggestionResponse,
+};
+
+/// HTTP method of a route.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+}
+
+impl Method {
+    /// Returns the uppercase name, e.g. `GET`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Delete => "DELETE",
+        }
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An HTTP API endpoint.
+pub trait Route {
+    const METHOD: Method;
+    /// Path under `API_PREFIX` with `{name}` placeholders, e.g.
+    /// `/posts/{id}`, in actix-web's syntax.
+    const PATH: &'static str;
+    /// Body sent with the request (`()` for none).
+    type Request;
+    /// Body of a successful response (`()` for `204 No Content`).
+    type Response;
+
+    /// Path with `API_PREFIX`, filling the placeholders in order.
+    ///
+    /// # Panics
+    ///
+    /// If the number of `params` does not match the placeholders.
+    fn path(params: &[&dyn Display]) -> String {
+        let mut path = String::from(API_PREFIX);
+        let mut params = params.iter();
+        let mut rest = Self::PATH;
+        while let Some(start) = rest.find('{') {
+            let end = rest[start..]
+                .find('}')
+                .map_or(rest.len(), |end| start + end + 1);
+            let param = params
+                .next()
+                .unwrap_or_else(|| panic!("missing parameter for {}", Self::PATH));
+            path.push_str(&rest[..start]);
+            path.push_str(&param.to_string());
+            rest = &rest[end..];
+        }
+        assert!(
+            params.next().is_none(),
+            "too many parameters for {}",
+            Self::PATH
+        );
+        path.push_str(rest);
+        path
+    }
+}
+
+macro_rules! routes {
+    ($($(#[$doc:meta])* $name:ident: $method:ident $path:literal, $req:ty => $res:ty;)*) => {
+        $(
+            $(#[$doc])*
+            #[derive(Debug, Clone, Copy)]
+            pub struct $name;
+
+            impl Route for $name {
+                const METHOD: Method = Method::$method;
+                const PATH: &'static str = $path;
+                type Request = $req;
+                type Response = $res;
+            }
+        )*
+
+        /// Method and path template of every route in the catalog.
+        pub const CATALOG: &[(Method, &str)] = &[$((Method::$method, $path)),*];
+    };
+}
+
+routes! {
+    /// Registers a user.
+    Register: Post "/auth/register", RegisterRequest => AuthResponse;
+    /// Signs in with a username or email address.
+    Login: Post "/auth/login", LoginRequest => AuthResponse;
+    /// The signed-in user, their permissions and usage.
+    GetMe: Get "/auth/me", () => MeResponse;
+    /// Page of posts (`limit`, `offset`, `tag`, `lang` query parameters).
+    ListPosts: Get "/posts", () => PostListResponse;
+    /// Creates a post.
+    CreatePost: Post "/posts", CreatePostRequest => PostDto;
+    /// Imports a batch of posts.
+    ImportPosts: Post "/posts/import", ImportPostsRequest => ImportPostsResponse;
+    /// A single post, by public ID.
+    GetPost: Get "/posts/{id}", () => PostDto;
+    /// Edits a post (author only).
+    UpdatePost: Put "/posts/{id}", UpdatePostRequest => PostDto;
+    /// Moves a post to the trash (author only).
+    DeletePost: Delete "/posts/{id}", () => ();
+    /// Takes a post out of the trash (author only).
+    RestorePost: Post "/posts/{id}/restore", () => ();
+    /// Language variants of a post.
+    ListTranslations: Get "/posts/{id}/translations", () => TranslationListResponse;
+    /// Revisions of a post, newest first.
+    ListRevisions: Get "/posts/{id}/revisions", () => RevisionListResponse;
+    /// Diff between two revisions of a post.
+    DiffRevisions: Get "/posts/{id}/revisions/{from}/diff/{to}", () => RevisionDiffResponse;
+    /// Page of the comments on a post (`limit`, and `cursor` or `offset`).
+    ListComments: Get "/posts/{id}/comments", () => CommentListResponse;
+    /// Adds a comment to a post.
+    CreateComment: Post "/posts/{id}/comments", CreateCommentRequest => CommentDto;
+    /// Edits a comment (comment author only).
+    UpdateComment: Put "/comments/{id}", UpdateCommentRequest => CommentDto;
+    /// Deletes a comment (comment author or post author).
+    DeleteComment: Delete "/comments/{id}", () => ();
+    /// The signed-in user's deleted posts.
+    ListTrash: Get "/posts/trash", () => TrashListResponse;
+    /// Dead links in the signed-in user's posts.
+    ListBrokenLinks: Get "/me/posts/broken-links", () => BrokenLinkListResponse;
+    /// Page of the signed-in user's notifications, newest first (`limit`,
+    /// `cursor`).
+    ListNotifications: Get "/me/notifications", () => NotificationListResponse;
+    /// Number of the signed-in user's unread notifications.
+    CountUnreadNotifications: Get "/me/notifications/unread", () => UnreadCountResponse;
+    /// Marks the signed-in user's notifications as read.
+    MarkNotificationsRead: Post "/me/notifications/read", MarkNotificationsReadRequest => ();
+    /// Forwards a client crash to the error tracker (public).
+    ReportClientError: Post "/telemetry/errors", ClientErrorReport => ();
+    /// Whether registration is open or invite-only, with the signed-in
+    /// user's experiment variants.
+    GetFeatures: Get "/features", () => FeaturesResponse;
+    /// Users whose names start with the `q` query parameter.
+    SuggestUsers: Get "/users/suggest", () => UserSuggestionResponse;
+    /// Public profile of a user, by public ID.
+    GetUser: Get "/users/{id}", () => PublicUserDto;
+    /// Marks a post as open in the caller's editor (author only).
+    EditingHeartbeat: Post "/posts/{id}/editing-heartbeat", () => EditingPresenceDto;
+    /// Marks a post as closed in the caller's editor.
+    StopEditing: Delete "/posts/{id}/editing-heartbeat", () => ();
+    /// WebSocket pushing a post's `EditingPresenceDto` as its editors change
+    /// (`token` query parameter).
+    EditingSocket: Get "/posts/{id}/editing/ws", () => ();
+    /// Site title, description, and page size.
+    GetSiteSettings: Get "/site/settings", () => SiteSettingsDto;
+    /// The announcement banner, if any.
+    GetAnnouncement: Get "/site/announcement", () => AnnouncementResponse;
+    /// All settings of the signed-in user.
+    GetSettings: Get "/settings", () => SettingsDto;
+    /// Replaces the signed-in user's profile.
+    UpdateProfile: Put "/settings/profile", ProfileDto => ProfileDto;
+    /// Replaces the signed-in user's display preferences.
+    UpdatePreferences: Put "/settings/preferences", PreferencesDto => PreferencesDto;
+    /// Starts changing the signed-in user's email address.
+    ChangeEmail: Put "/settings/email", ChangeEmailRequest => PendingEmailChangeResponse;
+    /// Confirms an email change with the emailed token (no sign-in needed).
+    ConfirmEmailChange: Post "/settings/email/confirm", ConfirmEmailChangeRequest => UserDto;
+    /// Changes the signed-in user's password.
+    ChangePassword: Put "/settings/password", ChangePasswordRequest => ();
+    /// Replaces the signed-in user's recovery codes.
+    RegenerateRecoveryCodes: Post "/settings/recovery-codes", RegenerateRecoveryCodesRequest => RecoveryCodesResponse;
+    /// The signed-in user's active sessions.
+    ListSessions: Get "/settings/sessions", () => SessionListResponse;
+    /// Revokes one of the signed-in user's sessions.
+    RevokeSession: Delete "/settings/sessions/{id}", () => ();
+    /// Deletes the signed-in user's account.
+    DeleteAccount: Delete "/settings/account", DeleteAccountRequest => ();
+    /// Number of the signed-in user's unread messages.
+    CountUnreadMessages: Get "/conversations/unread", () => UnreadCountResponse;
+    /// Page of the signed-in user's conversations (`limit`, `offset`).
+    ListConversations: Get "/conversations", () => ConversationListResponse;
+    /// Starts a conversation with a first message.
+    StartConversation: Post "/conversations", StartConversationRequest => ConversationDto;
+    /// Page of a conversation's messages, newest first (`limit`, `offset`).
+    ListMessages: Get "/conversations/{id}/messages", () => MessageListResponse;
+    /// Sends a message in a conversation.
+    SendMessage: Post "/conversations/{id}/messages", SendMessageRequest => MessageDto;
+    /// Site-wide counters (admin only).
+    GetAdminStats: Get "/admin/stats", () => AdminStatsDto;
+    /// Page of users for management (admin only; `limit`, `offset`).
+    ListAdminUsers: Get "/admin/users", () => AdminUserListResponse;
+    /// Changes a user's role or suspension (admin only).
+    UpdateAdminUser: Put "/admin/users/{id}", UpdateUserRequest => AdminUserDto;
+    /// Shadow-bans a user (moderators and admins).
+    ShadowBanUser: Put "/admin/users/{id}/shadow-ban", ShadowBanRequest => AdminUserDto;
+    /// Lifts a user's shadow ban (moderators and admins).
+    LiftShadowBan: Delete "/admin/users/{id}/shadow-ban", () => AdminUserDto;
+    /// Page of the newest comments across all posts (moderators and admins).
+    ModerationQueue: Get "/admin/moderation/comments", () => CommentListResponse;
+    /// Removes any comment (moderators and admins).
+    RemoveComment: Delete "/admin/moderation/comments/{id}", () => ();
+    /// Page of posts and comments flagged by moderation rules (moderators
+    /// and admins).
+    ListFlaggedContent: Get "/admin/moderation/flagged", () => FlaggedContentListResponse;
+    /// Dismisses a flag, leaving the content in place (moderators and admins).
+    DismissFlag: Delete "/admin/moderation/flagged/{id}", () => ();
+    /// Moderation rules with their hit rates (admin only).
+    ListModerationRules: Get "/admin/rules", () => ModerationRuleListResponse;
+    /// Creates a moderation rule (admin only).
+    CreateModerationRule: Post "/admin/rules", CreateModerationRuleRequest => ModerationRuleDto;
+    /// Changes a moderation rule (admin only).
+    UpdateModerationRule: Put "/admin/rules/{id}", UpdateModerationRuleRequest => ModerationRuleDto;
+    /// Deletes a moderation rule (admin only).
+    DeleteModerationRule: Delete "/admin/rules/{id}", () => ();
+    /// All feature flags (admin only).
+    ListFeatureFlags: Get "/admin/flags", () => FeatureFlagListResponse;
+    /// Turns a feature flag on or off (admin only).
+    UpdateFeatureFlag: Put "/admin/flags/{name}", UpdateFeatureFlagRequest => FeatureFlagDto;
+    /// Changes the site settings (admin only).
+    UpdateSiteSettings: Put "/admin/settings", UpdateSiteSettingsRequest => SiteSettingsDto;
+    /// Sets the announcement banner (admin only).
+    SetAnnouncement: Put "/admin/announcement", SetAnnouncementRequest => AnnouncementDto;
+    /// Removes the announcement banner (admin only).
+    ClearAnnouncement: Delete "/admin/announcement", () => ();
+}
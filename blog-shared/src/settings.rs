@@ -0,0 +1,165 @@
+//! User settings data transfer objects.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{DEFAULT_LANGUAGE, DEFAULT_LOCALE, DEFAULT_TIMEZONE};
+
+/// UI color theme.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    /// Follow the operating system setting.
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl Theme {
+    /// Returns the lowercase name used in JSON and storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Theme::System => "system",
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+        }
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "system" => Ok(Theme::System),
+            "light" => Ok(Theme::Light),
+            "dark" => Ok(Theme::Dark),
+            other => Err(format!("Unknown theme: {other}")),
+        }
+    }
+}
+
+/// Public profile fields (also the body of a profile update).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileDto {
+    pub bio: String,
+    pub avatar_url: Option<String>,
+    /// Personal site; also the IndieAuth identity (`me`) accepted by Micropub.
+    #[serde(default)]
+    pub website: Option<String>,
+}
+
+/// Display preferences (also the body of a preferences update).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreferencesDto {
+    pub theme: Theme,
+    /// Language code, one of `SUPPORTED_LANGUAGES`.
+    pub language: String,
+    /// IANA time zone name, such as `Europe/Berlin`.
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+    /// Locale for date formats, one of `SUPPORTED_LOCALES`.
+    #[serde(default = "default_locale")]
+    pub locale: String,
+}
+
+impl Default for PreferencesDto {
+    fn default() -> Self {
+        Self {
+            theme: Theme::default(),
+            language: DEFAULT_LANGUAGE.to_string(),
+            timezone: default_timezone(),
+            locale: default_locale(),
+        }
+    }
+}
+
+fn default_timezone() -> String {
+    DEFAULT_TIMEZONE.to_string()
+}
+
+fn default_locale() -> String {
+    DEFAULT_LOCALE.to_string()
+}
+
+/// All settings of the current user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SettingsDto {
+    pub email: String,
+    pub profile: ProfileDto,
+    pub preferences: PreferencesDto,
+}
+
+/// Change email request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeEmailRequest {
+    pub new_email: String,
+    pub current_password: String,
+}
+
+/// Confirms an email change with the token from the link sent to the new
+/// address.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfirmEmailChangeRequest {
+    pub token: String,
+}
+
+/// Email change waiting for the link sent to the new address; the account
+/// keeps its current email until then.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingEmailChangeResponse {
+    pub new_email: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Change password request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+/// Delete account request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeleteAccountRequest {
+    pub current_password: String,
+}
+
+/// Request to replace the current user's recovery codes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegenerateRecoveryCodesRequest {
+    pub current_password: String,
+}
+
+/// Fresh recovery codes; they are shown only once.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecoveryCodesResponse {
+    pub codes: Vec<String>,
+}
+
+/// Signed-in session (one per login).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionDto {
+    pub id: String,
+    pub user_agent: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    /// Whether this is the session making the request.
+    pub current: bool,
+}
+
+/// Active sessions of the current user, most recently used first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionListResponse {
+    pub sessions: Vec<SessionDto>,
+}
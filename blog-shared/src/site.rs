@@ -0,0 +1,169 @@
+//! Site-wide settings data transfer objects.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{DEFAULT_LANGUAGE, DEFAULT_POSTS_PER_PAGE, DEFAULT_SITE_TITLE};
+
+/// How prominently an announcement is shown.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum AnnouncementSeverity {
+    #[default]
+    Info,
+    Warning,
+    Critical,
+}
+
+impl AnnouncementSeverity {
+    /// Returns the lowercase name used in JSON and CSS classes.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnnouncementSeverity::Info => "info",
+            AnnouncementSeverity::Warning => "warning",
+            AnnouncementSeverity::Critical => "critical",
+        }
+    }
+}
+
+impl fmt::Display for AnnouncementSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AnnouncementSeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "info" => Ok(AnnouncementSeverity::Info),
+            "warning" => Ok(AnnouncementSeverity::Warning),
+            "critical" => Ok(AnnouncementSeverity::Critical),
+            other => Err(format!("Unknown announcement severity: {other}")),
+        }
+    }
+}
+
+/// The banner shown at the top of the site.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnnouncementDto {
+    pub text: String,
+    pub severity: AnnouncementSeverity,
+    /// When the banner stops showing; `None` keeps it until cleared.
+    pub expires_at: Option<DateTime<Utc>>,
+    /// When the banner was set; a dismissal applies to this version only.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AnnouncementDto {
+    /// Whether the banner should still be shown at `now`.
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+}
+
+/// The current announcement, if one is set and not expired.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnnouncementResponse {
+    pub announcement: Option<AnnouncementDto>,
+}
+
+/// Request body for setting the announcement (admin only).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetAnnouncementRequest {
+    pub text: String,
+    #[serde(default)]
+    pub severity: AnnouncementSeverity,
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Who may create an account.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistrationMode {
+    /// Anyone may register.
+    #[default]
+    Open,
+    /// New users need an invite code from an existing user.
+    InviteOnly,
+    /// Nobody may register.
+    Closed,
+}
+
+impl RegistrationMode {
+    /// Returns the snake_case name used in JSON.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RegistrationMode::Open => "open",
+            RegistrationMode::InviteOnly => "invite_only",
+            RegistrationMode::Closed => "closed",
+        }
+    }
+}
+
+impl fmt::Display for RegistrationMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RegistrationMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "open" => Ok(RegistrationMode::Open),
+            "invite_only" => Ok(RegistrationMode::InviteOnly),
+            "closed" => Ok(RegistrationMode::Closed),
+            other => Err(format!("Unknown registration mode: {other}")),
+        }
+    }
+}
+
+/// Site-wide settings: the site's name in the header, page title and
+/// feeds, and defaults for readers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SiteSettingsDto {
+    pub title: String,
+    /// Shown in the page meta description and the feeds; may be empty.
+    pub description: String,
+    /// Number of posts on each page of the post list.
+    pub posts_per_page: i64,
+    /// Language of the site for visitors without preferences.
+    pub default_language: String,
+    pub registration_mode: RegistrationMode,
+    /// When an admin last changed the settings; `None` while all are
+    /// defaults.
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl Default for SiteSettingsDto {
+    fn default() -> Self {
+        Self {
+            title: DEFAULT_SITE_TITLE.to_string(),
+            description: String::new(),
+            posts_per_page: DEFAULT_POSTS_PER_PAGE,
+            default_language: DEFAULT_LANGUAGE.to_string(),
+            registration_mode: RegistrationMode::default(),
+            updated_at: None,
+        }
+    }
+}
+
+/// Request body for changing site settings (admin only); omitted fields
+/// keep their current value.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct UpdateSiteSettingsRequest {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub posts_per_page: Option<i64>,
+    pub default_language: Option<String>,
+    pub registration_mode: Option<RegistrationMode>,
+}
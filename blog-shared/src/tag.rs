@@ -0,0 +1,86 @@
+//! Tag data transfer objects.
+
+use serde::{Deserialize, Serialize};
+
+use crate::PostDto;
+
+/// Tag with the number of posts using it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagDto {
+    pub name: String,
+    pub count: i64,
+    /// What the tag is about, set by admins.
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// List of tags in the requested order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagListResponse {
+    pub tags: Vec<TagDto>,
+}
+
+/// Tag completing a `#tag` being typed, with its number of posts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagSuggestionDto {
+    pub name: String,
+    pub count: i64,
+}
+
+/// Tags starting with the typed prefix, best first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagSuggestionResponse {
+    pub tags: Vec<TagSuggestionDto>,
+}
+
+/// Order of the tag list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagSort {
+    /// Most used first.
+    #[default]
+    Count,
+    /// Most used by recent posts first, then most used overall.
+    Popular,
+    /// Alphabetical.
+    Name,
+}
+
+impl TagSort {
+    /// Returns the lowercase name used in query strings.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TagSort::Count => "count",
+            TagSort::Popular => "popular",
+            TagSort::Name => "name",
+        }
+    }
+}
+
+/// Landing page of a tag: its description and a page of its posts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagPageResponse {
+    pub name: String,
+    pub description: Option<String>,
+    pub posts: Vec<PostDto>,
+    /// Number of posts with the tag across all pages.
+    pub total: i64,
+}
+
+/// Request to rename a tag or change its description (admin).
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct UpdateTagRequest {
+    /// New name, normalized like post tags.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// New description; an empty one clears it.
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Request to merge a tag into another one (admin).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeTagRequest {
+    /// Tag that takes over the merged tag's posts.
+    pub into: String,
+}
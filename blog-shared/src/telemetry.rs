@@ -0,0 +1,43 @@
+//! Error telemetry data transfer objects.
+
+use serde::{Deserialize, Serialize};
+
+/// A crash in a client, reported for the server to forward to its error
+/// tracker.
+///
+/// Browsers send it with `navigator.sendBeacon` as `text/plain`, so the
+/// server reads the JSON from the raw body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientErrorReport {
+    /// Panic or error message.
+    pub message: String,
+    /// Source location, e.g. `src/components/post_list.rs:42:17`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// Page the client was on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl ClientErrorReport {
+    /// Report of `message` without a location or page.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            location: None,
+            url: None,
+        }
+    }
+
+    /// Sets the source location.
+    pub fn with_location(mut self, location: impl Into<String>) -> Self {
+        self.location = Some(location.into());
+        self
+    }
+
+    /// Sets the page the client was on.
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+}
@@ -0,0 +1,36 @@
+//! Text helpers shared by the server and the clients.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::constants::ELLIPSIS;
+
+/// Shortens text to at most `max` grapheme clusters, ending with an
+/// ellipsis if it was cut.
+///
+/// Counting graphemes rather than bytes or chars keeps multi-byte letters,
+/// combining accents, and emoji (including ZWJ sequences and flags) whole.
+/// Whitespace before the ellipsis is dropped.
+pub fn truncate(text: &str, max: usize) -> String {
+    if max == 0 {
+        return String::new();
+    }
+    // Start of the last grapheme that fits; the ellipsis takes its place
+    let mut graphemes = text.grapheme_indices(true);
+    let Some((end, _)) = graphemes.nth(max - 1) else {
+        return text.to_string();
+    };
+    if graphemes.next().is_none() {
+        return text.to_string();
+    }
+
+    let mut cut = text[..end].trim_end().to_string();
+    cut.push(ELLIPSIS);
+    cut
+}
+
+/// Start of `content` as a single line of at most `max` graphemes, for
+/// summaries and previews.
+pub fn excerpt(content: &str, max: usize) -> String {
+    let flat = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    truncate(&flat, max)
+}
@@ -0,0 +1,100 @@
+//! Post transfer data transfer objects.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Where a post transfer stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferStatus {
+    /// Waiting for the recipient to accept or decline.
+    Pending,
+    /// The recipient took over the post.
+    Accepted,
+    /// The recipient turned the post down.
+    Declined,
+    /// The sender withdrew the offer.
+    Cancelled,
+}
+
+impl TransferStatus {
+    /// Returns the lowercase name used in JSON and storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransferStatus::Pending => "pending",
+            TransferStatus::Accepted => "accepted",
+            TransferStatus::Declined => "declined",
+            TransferStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl fmt::Display for TransferStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TransferStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(TransferStatus::Pending),
+            "accepted" => Ok(TransferStatus::Accepted),
+            "declined" => Ok(TransferStatus::Declined),
+            "cancelled" => Ok(TransferStatus::Cancelled),
+            other => Err(format!("Unknown transfer status: {other}")),
+        }
+    }
+}
+
+/// Request body for offering a post to another user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferPostRequest {
+    /// Username of the recipient.
+    pub username: String,
+}
+
+/// An offer to hand a post over to another user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PostTransferDto {
+    pub id: i64,
+    /// Internal ID of the post, never sent over HTTP (0 when read from JSON).
+    #[serde(skip_serializing, default)]
+    pub post_id: i64,
+    /// Public ID of the post (empty from servers that predate it).
+    #[serde(default)]
+    pub post_public_id: String,
+    pub post_title: String,
+    /// Internal ID of the offering user, never sent over HTTP like `post_id`.
+    #[serde(skip_serializing, default)]
+    pub from_user_id: i64,
+    /// Public ID of the offering user (empty from servers that predate it).
+    #[serde(default)]
+    pub from_user_public_id: String,
+    pub from_username: String,
+    /// Internal ID of the recipient, never sent over HTTP like `post_id`.
+    #[serde(skip_serializing, default)]
+    pub to_user_id: i64,
+    /// Public ID of the recipient (empty from servers that predate it).
+    #[serde(default)]
+    pub to_user_public_id: String,
+    pub to_username: String,
+    pub status: TransferStatus,
+    pub created_at: DateTime<Utc>,
+    /// When the transfer was accepted, declined, or cancelled.
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// The current user's pending transfers, newest first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferListResponse {
+    /// Posts offered to the user.
+    pub incoming: Vec<PostTransferDto>,
+    /// Posts the user offered to others.
+    pub outgoing: Vec<PostTransferDto>,
+}
@@ -0,0 +1,34 @@
+//! Upload data transfer objects.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// An uploaded file, addressed by the SHA-256 hash of its content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UploadDto {
+    /// The caller's upload of the file; identical content uploaded by the
+    /// same user keeps its ID.
+    pub id: String,
+    /// Lowercase hex SHA-256 of the file.
+    pub hash: String,
+    /// URL of the upload, `{PUBLIC_URL}/uploads/{id}`; signed and expiring
+    /// for private uploads.
+    pub url: String,
+    pub content_type: String,
+    pub size: i64,
+    /// Only served through signed URLs, whoever else uploads the same
+    /// content.
+    pub private: bool,
+    /// When a signed `url` stops working.
+    pub expires_at: Option<DateTime<Utc>>,
+    /// When the file was first uploaded, by anyone.
+    pub created_at: DateTime<Utc>,
+}
+
+/// Query parameters of an upload.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct UploadQuery {
+    /// Serve the file only through signed, expiring URLs.
+    #[serde(default)]
+    pub private: bool,
+}
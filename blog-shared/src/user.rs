@@ -1,13 +1,176 @@
 //! User data transfer objects.
 
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::PreferencesDto;
+use crate::constants::{
+    ADMIN_PERMISSIONS, EDITOR_PERMISSIONS, MODERATOR_PERMISSIONS, USER_PERMISSIONS,
+};
+
+/// User role, from least to most privileged.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    #[default]
+    User,
+    /// May review posts submitted for publication.
+    Editor,
+    /// May work the moderation queue.
+    Moderator,
+    /// Full access to the admin dashboard.
+    Admin,
+}
+
+impl Role {
+    /// Returns the lowercase name used in JSON and storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::User => "user",
+            Role::Editor => "editor",
+            Role::Moderator => "moderator",
+            Role::Admin => "admin",
+        }
+    }
+
+    /// Whether this role may approve posts in editorial review.
+    pub fn can_review(&self) -> bool {
+        *self >= Role::Editor
+    }
+
+    /// Whether this role may moderate content.
+    pub fn can_moderate(&self) -> bool {
+        *self >= Role::Moderator
+    }
+
+    /// Returns the names of the actions this role may take, for clients
+    /// deciding what to show.
+    pub fn permissions(&self) -> &'static [&'static str] {
+        match self {
+            Role::User => USER_PERMISSIONS,
+            Role::Editor => EDITOR_PERMISSIONS,
+            Role::Moderator => MODERATOR_PERMISSIONS,
+            Role::Admin => ADMIN_PERMISSIONS,
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "user" => Ok(Role::User),
+            "editor" => Ok(Role::Editor),
+            "moderator" => Ok(Role::Moderator),
+            "admin" => Ok(Role::Admin),
+            other => Err(format!("Unknown role: {other}")),
+        }
+    }
+}
+
 /// User data transfer object (no password_hash exposed).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct UserDto {
     pub id: i64,
+    /// Random ID used in API URLs (empty from servers that predate it).
+    #[serde(default)]
+    pub public_id: String,
     pub username: String,
     pub email: String,
+    #[serde(default)]
+    pub role: Role,
     pub created_at: DateTime<Utc>,
 }
+
+/// Summary as `username (ID: id)`.
+impl fmt::Display for UserDto {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (ID: {})", self.username, self.id)
+    }
+}
+
+impl UserDto {
+    /// Creates a user with the fields every server sends: no public ID yet,
+    /// and the default role.
+    pub fn new(id: i64, username: String, email: String, created_at: DateTime<Utc>) -> Self {
+        Self {
+            id,
+            public_id: String::new(),
+            username,
+            email,
+            role: Role::default(),
+            created_at,
+        }
+    }
+}
+
+/// Public profile of a user (shown next to their posts and comments).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicUserDto {
+    /// Internal ID, never sent over HTTP (0 when read from JSON).
+    #[serde(skip_serializing, default)]
+    pub id: i64,
+    /// Random ID used in API URLs (empty from servers that predate it).
+    #[serde(default)]
+    pub public_id: String,
+    pub username: String,
+    pub bio: String,
+    pub avatar_url: Option<String>,
+}
+
+/// User completing an `@mention` being typed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserSuggestionDto {
+    pub username: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+}
+
+/// Users whose names start with the typed prefix, best first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserSuggestionResponse {
+    pub users: Vec<UserSuggestionDto>,
+}
+
+/// What the current user has stored on the site.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageDto {
+    pub posts: i64,
+    pub uploads: i64,
+    pub upload_bytes: i64,
+}
+
+/// Everything a client needs to set up its UI for the current user.
+///
+/// The user's fields are flattened, so it also reads as a [`UserDto`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MeResponse {
+    #[serde(flatten)]
+    pub user: UserDto,
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    #[serde(default)]
+    pub usage: UsageDto,
+    #[serde(default)]
+    pub unread_messages: i64,
+    /// Whether each feature flag is enabled, by name.
+    #[serde(default)]
+    pub feature_flags: BTreeMap<String, bool>,
+    /// Theme, language, time zone, and locale.
+    #[serde(default)]
+    pub preferences: PreferencesDto,
+}
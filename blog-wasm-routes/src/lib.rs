@@ -0,0 +1,6 @@
+//! Route chunk of the WASM frontend.
+//!
+//! Builds the admin and editor pages of `blog-wasm` (its `route_chunk`
+//! module) into their own module; the main bundle imports it on first use.
+
+pub use blog_wasm::{RouteHandle, mount_route};
@@ -0,0 +1,40 @@
+// IndexedDB-backed queue of post actions made while offline.
+// Entries are opaque JSON strings; the Rust side owns their format.
+
+const DB_NAME = "blog-offline";
+const STORE = "pending_actions";
+
+function openDb() {
+    return new Promise((resolve, reject) => {
+        const request = indexedDB.open(DB_NAME, 1);
+        request.onupgradeneeded = () =>
+            request.result.createObjectStore(STORE, { keyPath: "key", autoIncrement: true });
+        request.onsuccess = () => resolve(request.result);
+        request.onerror = () => reject(request.error);
+    });
+}
+
+function withStore(mode, operation) {
+    return openDb().then((db) => new Promise((resolve, reject) => {
+        const tx = db.transaction(STORE, mode);
+        const request = operation(tx.objectStore(STORE));
+        tx.oncomplete = () => resolve(request.result);
+        tx.onerror = () => reject(tx.error);
+    }));
+}
+
+/** Appends an action, returning its key. */
+export function queuePush(json) {
+    return withStore("readwrite", (store) => store.add({ json }));
+}
+
+/** Returns all queued actions, oldest first, as a JSON array of {key, json}. */
+export function queueList() {
+    return withStore("readonly", (store) => store.getAll())
+        .then((rows) => JSON.stringify(rows));
+}
+
+/** Removes an action by key. */
+export function queueRemove(key) {
+    return withStore("readwrite", (store) => store.delete(key));
+}
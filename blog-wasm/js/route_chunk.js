@@ -0,0 +1,19 @@
+// Loader of the route chunk, the second WASM module holding the admin and
+// editor pages. It is imported and initialized once, on first use.
+
+let chunk;
+
+/** Imports and initializes the chunk module at `url`, returning it. */
+export function loadRouteChunk(url) {
+    chunk ??= import(url)
+        .then(async (module) => {
+            await module.default();
+            return module;
+        })
+        .catch((error) => {
+            // Let the next navigation try again
+            chunk = undefined;
+            throw error;
+        });
+    return chunk;
+}
@@ -1,31 +1,123 @@
 //! HTTP client for the blog API.
 
-use gloo_net::http::Request;
+#[cfg(feature = "admin")]
+mod admin;
+mod comments;
+#[cfg(feature = "editor")]
+mod editing;
+mod experiments;
+mod feeds;
+#[cfg(feature = "editor")]
+mod markdown;
+mod messages;
+#[cfg(feature = "admin")]
+mod moderation;
+mod newsletter;
+mod orgs;
+mod recovery;
+#[cfg(feature = "editor")]
+mod revisions;
+mod settings;
+mod site;
+mod telemetry;
+mod trash;
+mod users;
+
+use gloo_net::http::{Request, RequestBuilder};
 use gloo_storage::{LocalStorage, Storage};
-use web_sys::window;
+use web_sys::{RequestCache, window};
 
+#[cfg(feature = "editor")]
+use blog_shared::TagSuggestionResponse;
+use blog_shared::constants::DEFAULT_HTTP_PORT;
+use blog_shared::endpoint::paths;
 use blog_shared::{
-    AuthResponse, CreatePostRequest, LoginRequest, PostDto, PostListResponse, RegisterRequest,
-    UpdatePostRequest, UserDto,
+    ApiBaseUrl, AuthResponse, CreatePostRequest, ErrorResponse, FeaturesResponse, LoginRequest,
+    MeResponse, PostDto, PostListResponse, RegisterRequest, TagListResponse,
+    TranslationListResponse, UpdatePostRequest,
 };
 
-use crate::constants::{API_PORT, TOKEN_STORAGE_KEY};
+use crate::constants::{REQUEST_ID_HEADER, TOKEN_STORAGE_KEY};
+
+#[cfg(feature = "admin")]
+pub use admin::{
+    clear_announcement, get_admin_stats, lift_shadow_ban, list_admin_users, list_feature_flags,
+    moderation_queue, remove_comment, set_announcement, shadow_ban_user, update_admin_user,
+    update_feature_flag, update_site_settings,
+};
+pub use comments::{create_comment, delete_comment, list_comments, update_comment};
+#[cfg(feature = "editor")]
+pub use editing::{editing_heartbeat, editing_socket_url, stop_editing};
+pub use experiments::record_exposure;
+pub use feeds::{atom_feed_url, rss_feed_url};
+#[cfg(feature = "editor")]
+pub use markdown::preview_markdown;
+pub use messages::{
+    list_conversations, list_messages, send_message, start_conversation, unread_count,
+};
+#[cfg(feature = "admin")]
+pub use moderation::{
+    create_moderation_rule, delete_moderation_rule, dismiss_flag, list_flagged_content,
+    list_moderation_rules, update_moderation_rule,
+};
+pub use newsletter::subscribe_newsletter;
+pub use orgs::get_org;
+pub use recovery::recover_account;
+#[cfg(feature = "editor")]
+pub use revisions::{diff_revisions, list_revisions};
+pub use settings::{
+    change_email, change_password, confirm_email_change, delete_account, get_settings,
+    list_sessions, regenerate_recovery_codes, revoke_session, update_preferences, update_profile,
+};
+pub use site::{get_announcement, get_site_settings};
+pub use telemetry::report_crash;
+pub use trash::{list_trash, restore_post};
+pub use users::get_user;
+#[cfg(feature = "editor")]
+pub use users::suggest_users;
 
 /// Gets the API base URL dynamically based on current page hostname.
 /// This ensures CORS works correctly regardless of whether the page is accessed
 /// via localhost or 127.0.0.1.
-fn get_api_base_url() -> String {
+fn get_api_base_url() -> ApiBaseUrl {
     let hostname = window()
         .and_then(|w| w.location().hostname().ok())
         .unwrap_or_else(|| "127.0.0.1".to_string());
 
-    format!("http://{}:{}", hostname, API_PORT)
+    ApiBaseUrl::from_host(&hostname, DEFAULT_HTTP_PORT)
 }
 
 /// API client error.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ApiError {
     pub message: String,
+    /// HTTP status of the failed response; `None` when the request never
+    /// got one (network error, not signed in...).
+    pub status: Option<u16>,
+    /// Request ID the server tagged the failed response with, for error
+    /// reports.
+    pub request_id: Option<String>,
+}
+
+impl ApiError {
+    /// Creates an error without a response.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            status: None,
+            request_id: None,
+        }
+    }
+
+    /// Checks if the server answered 404.
+    pub fn is_not_found(&self) -> bool {
+        self.status == Some(404)
+    }
+
+    /// Checks if the server answered with a 5xx error.
+    pub fn is_server_error(&self) -> bool {
+        self.status.is_some_and(|status| status >= 500)
+    }
 }
 
 impl std::fmt::Display for ApiError {
@@ -54,147 +146,225 @@ pub fn is_authenticated() -> bool {
     get_token().is_some()
 }
 
-/// Gets the current authenticated user's info.
-pub async fn get_me() -> Result<UserDto, ApiError> {
-    let url = format!("{}/api/auth/me", get_api_base_url());
-    let token = get_token().ok_or(ApiError {
-        message: "Not authenticated".into(),
-    })?;
+/// Gets the current authenticated user's info, permissions, usage, and the
+/// feature flags.
+pub async fn get_me() -> Result<MeResponse, ApiError> {
+    let url = get_api_base_url().join(&paths::me());
+    let token = get_token().ok_or(ApiError::new("Not authenticated"))?;
 
     let response = Request::get(&url)
         .header("Authorization", &format!("Bearer {}", token))
         .send()
         .await
-        .map_err(|e| ApiError {
-            message: e.to_string(),
-        })?;
+        .map_err(|e| ApiError::new(e.to_string()))?;
 
     handle_response(response).await
 }
 
 /// Registers a new user.
 pub async fn register(req: RegisterRequest) -> Result<AuthResponse, ApiError> {
-    let url = format!("{}/api/auth/register", get_api_base_url());
+    let url = get_api_base_url().join(&paths::register());
     let response = Request::post(&url)
         .json(&req)
-        .map_err(|e| ApiError {
-            message: e.to_string(),
-        })?
+        .map_err(|e| ApiError::new(e.to_string()))?
         .send()
         .await
-        .map_err(|e| ApiError {
-            message: e.to_string(),
-        })?;
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Gets whether registration is open and whether it needs an invite code,
+/// plus the signed-in user's experiment variants.
+pub async fn get_features() -> Result<FeaturesResponse, ApiError> {
+    let url = get_api_base_url().join(&paths::features());
+    let response = with_viewer(Request::get(&url))
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
 
     handle_response(response).await
 }
 
 /// Logs in an existing user.
 pub async fn login(req: LoginRequest) -> Result<AuthResponse, ApiError> {
-    let url = format!("{}/api/auth/login", get_api_base_url());
+    let url = get_api_base_url().join(&paths::login());
     let response = Request::post(&url)
         .json(&req)
-        .map_err(|e| ApiError {
-            message: e.to_string(),
-        })?
+        .map_err(|e| ApiError::new(e.to_string()))?
         .send()
         .await
-        .map_err(|e| ApiError {
-            message: e.to_string(),
-        })?;
+        .map_err(|e| ApiError::new(e.to_string()))?;
 
     handle_response(response).await
 }
 
 /// Creates a new post.
 pub async fn create_post(req: CreatePostRequest) -> Result<PostDto, ApiError> {
-    let url = format!("{}/api/posts", get_api_base_url());
-    let token = get_token().ok_or(ApiError {
-        message: "Not authenticated".into(),
-    })?;
+    let url = get_api_base_url().join(&paths::posts());
+    let token = get_token().ok_or(ApiError::new("Not authenticated"))?;
 
     let response = Request::post(&url)
         .header("Authorization", &format!("Bearer {}", token))
         .json(&req)
-        .map_err(|e| ApiError {
-            message: e.to_string(),
-        })?
+        .map_err(|e| ApiError::new(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Gets a post by its public ID.
+///
+/// Always revalidates with the server (cheap via ETag) so edits and offline
+/// conflict checks see the current `updated_at`.
+pub async fn get_post(id: &str) -> Result<PostDto, ApiError> {
+    let url = get_api_base_url().join(&paths::post(id));
+    let response = with_viewer(Request::get(&url))
+        .cache(RequestCache::NoCache)
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Lists the translations of a post, including the post itself.
+pub async fn list_translations(id: &str) -> Result<TranslationListResponse, ApiError> {
+    let url = get_api_base_url().join(&paths::post_translations(id));
+    let response = with_viewer(Request::get(&url))
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Lists posts with pagination, optionally only those with a tag.
+pub async fn list_posts(
+    limit: i64,
+    offset: i64,
+    tag: Option<&str>,
+) -> Result<PostListResponse, ApiError> {
+    let url = get_api_base_url().join(&paths::posts());
+    let limit = limit.to_string();
+    let offset = offset.to_string();
+    let mut request =
+        with_viewer(Request::get(&url)).query([("limit", &limit), ("offset", &offset)]);
+    if let Some(tag) = tag {
+        request = request.query([("tag", tag)]);
+    }
+    let response = request
         .send()
         .await
-        .map_err(|e| ApiError {
-            message: e.to_string(),
-        })?;
+        .map_err(|e| ApiError::new(e.to_string()))?;
 
     handle_response(response).await
 }
 
-/// Gets a post by ID.
-pub async fn get_post(id: i64) -> Result<PostDto, ApiError> {
-    let url = format!("{}/api/posts/{}", get_api_base_url(), id);
-    let response = Request::get(&url).send().await.map_err(|e| ApiError {
-        message: e.to_string(),
-    })?;
+/// Lists tags in use with their post counts, most used first.
+pub async fn list_tags(limit: i64) -> Result<TagListResponse, ApiError> {
+    let url = format!("{}/api/tags?limit={}", get_api_base_url(), limit);
+    let response = Request::get(&url)
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
 
     handle_response(response).await
 }
 
-/// Lists posts with pagination.
-pub async fn list_posts(limit: i64, offset: i64) -> Result<PostListResponse, ApiError> {
-    let url = format!(
-        "{}/api/posts?limit={}&offset={}",
-        get_api_base_url(),
-        limit,
-        offset
-    );
-    let response = Request::get(&url).send().await.map_err(|e| ApiError {
-        message: e.to_string(),
-    })?;
+/// Suggests tags starting with `prefix`, most used first.
+#[cfg(feature = "editor")]
+pub async fn suggest_tags(prefix: &str) -> Result<TagSuggestionResponse, ApiError> {
+    let url = format!("{}/api/tags/suggest", get_api_base_url());
+    let response = Request::get(&url)
+        .query([("q", prefix)])
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Full-text searches posts by title and content.
+pub async fn search_posts(
+    query: &str,
+    limit: i64,
+    offset: i64,
+) -> Result<PostListResponse, ApiError> {
+    let url = format!("{}/api/search", get_api_base_url());
+    let limit = limit.to_string();
+    let offset = offset.to_string();
+    let response = with_viewer(Request::get(&url))
+        .query([("q", query), ("limit", &limit), ("offset", &offset)])
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
 
     handle_response(response).await
 }
 
 /// Updates a post.
-pub async fn update_post(id: i64, req: UpdatePostRequest) -> Result<PostDto, ApiError> {
-    let url = format!("{}/api/posts/{}", get_api_base_url(), id);
-    let token = get_token().ok_or(ApiError {
-        message: "Not authenticated".into(),
-    })?;
+pub async fn update_post(id: &str, req: UpdatePostRequest) -> Result<PostDto, ApiError> {
+    let url = get_api_base_url().join(&paths::post(id));
+    let token = get_token().ok_or(ApiError::new("Not authenticated"))?;
 
     let response = Request::put(&url)
         .header("Authorization", &format!("Bearer {}", token))
         .json(&req)
-        .map_err(|e| ApiError {
-            message: e.to_string(),
-        })?
+        .map_err(|e| ApiError::new(e.to_string()))?
         .send()
         .await
-        .map_err(|e| ApiError {
-            message: e.to_string(),
-        })?;
+        .map_err(|e| ApiError::new(e.to_string()))?;
 
     handle_response(response).await
 }
 
 /// Deletes a post.
-pub async fn delete_post(id: i64) -> Result<(), ApiError> {
-    let url = format!("{}/api/posts/{}", get_api_base_url(), id);
-    let token = get_token().ok_or(ApiError {
-        message: "Not authenticated".into(),
-    })?;
+pub async fn delete_post(id: &str) -> Result<(), ApiError> {
+    let url = get_api_base_url().join(&paths::post(id));
+    let token = get_token().ok_or(ApiError::new("Not authenticated"))?;
 
     let response = Request::delete(&url)
         .header("Authorization", &format!("Bearer {}", token))
         .send()
         .await
-        .map_err(|e| ApiError {
-            message: e.to_string(),
-        })?;
+        .map_err(|e| ApiError::new(e.to_string()))?;
 
     if response.ok() {
         Ok(())
     } else {
-        let text = response.text().await.unwrap_or_default();
-        Err(ApiError { message: text })
+        Err(error_from_response(response).await)
+    }
+}
+
+/// Adds the bearer token to a request.
+fn authorized(builder: RequestBuilder) -> Result<RequestBuilder, ApiError> {
+    let token = get_token().ok_or(ApiError::new("Not authenticated"))?;
+    Ok(builder.header("Authorization", &format!("Bearer {}", token)))
+}
+
+/// Adds the bearer token to a public read if signed in, so posts carry
+/// per-viewer fields such as `is_owner`.
+fn with_viewer(builder: RequestBuilder) -> RequestBuilder {
+    match get_token() {
+        Some(token) => builder.header("Authorization", &format!("Bearer {}", token)),
+        None => builder,
+    }
+}
+
+/// Sends a request whose success response has no body.
+async fn send_no_content(request: Request) -> Result<(), ApiError> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    if response.ok() {
+        Ok(())
+    } else {
+        Err(error_from_response(response).await)
     }
 }
 
@@ -203,11 +373,29 @@ async fn handle_response<T: serde::de::DeserializeOwned>(
     response: gloo_net::http::Response,
 ) -> Result<T, ApiError> {
     if response.ok() {
-        response.json().await.map_err(|e| ApiError {
-            message: e.to_string(),
-        })
+        response
+            .json()
+            .await
+            .map_err(|e| ApiError::new(e.to_string()))
     } else {
-        let text = response.text().await.unwrap_or_default();
-        Err(ApiError { message: text })
+        Err(error_from_response(response).await)
+    }
+}
+
+/// Converts a failed response to an error, taking the message from its
+/// `ErrorResponse` body when it has one.
+async fn error_from_response(response: gloo_net::http::Response) -> ApiError {
+    let status = response.status();
+    let request_id = response.headers().get(REQUEST_ID_HEADER);
+    let text = response.text().await.unwrap_or_default();
+    let message = match serde_json::from_str::<ErrorResponse>(&text) {
+        Ok(body) => body.error,
+        Err(_) => text,
+    };
+
+    ApiError {
+        message,
+        status: Some(status),
+        request_id,
     }
 }
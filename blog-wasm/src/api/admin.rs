@@ -0,0 +1,162 @@
+//! Admin dashboard endpoints (stats, users, moderation, feature flags,
+//! site settings, announcement banner).
+
+use gloo_net::http::Request;
+
+use blog_shared::endpoint::paths;
+use blog_shared::{
+    AdminStatsDto, AdminUserDto, AdminUserListResponse, AnnouncementDto, CommentListResponse,
+    FeatureFlagDto, FeatureFlagListResponse, Pagination, SetAnnouncementRequest, ShadowBanRequest,
+    SiteSettingsDto, UpdateFeatureFlagRequest, UpdateSiteSettingsRequest, UpdateUserRequest,
+};
+
+use super::{ApiError, authorized, get_api_base_url, handle_response, send_no_content};
+
+/// Gets site-wide counters (admin only).
+pub async fn get_admin_stats() -> Result<AdminStatsDto, ApiError> {
+    let response = authorized(Request::get(
+        &get_api_base_url().join(&paths::admin_stats()),
+    ))?
+    .send()
+    .await
+    .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Lists users for the user management table (admin only).
+pub async fn list_admin_users(limit: i64, offset: i64) -> Result<AdminUserListResponse, ApiError> {
+    let url = get_api_base_url().join(&paths::admin_users_page(Pagination { limit, offset }));
+    let response = authorized(Request::get(&url))?
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Changes a user's role or suspension (admin only).
+pub async fn update_admin_user(id: i64, req: UpdateUserRequest) -> Result<AdminUserDto, ApiError> {
+    let response = authorized(Request::put(
+        &get_api_base_url().join(&paths::admin_user(id)),
+    ))?
+    .json(&req)
+    .map_err(|e| ApiError::new(e.to_string()))?
+    .send()
+    .await
+    .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Shadow-bans a user (moderators and admins).
+pub async fn shadow_ban_user(id: i64) -> Result<AdminUserDto, ApiError> {
+    let url = get_api_base_url().join(&paths::shadow_ban(id));
+    let response = authorized(Request::put(&url))?
+        .json(&ShadowBanRequest::default())
+        .map_err(|e| ApiError::new(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Lifts a user's shadow ban (moderators and admins).
+pub async fn lift_shadow_ban(id: i64) -> Result<AdminUserDto, ApiError> {
+    let url = get_api_base_url().join(&paths::shadow_ban(id));
+    let response = authorized(Request::delete(&url))?
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Lists the newest comments across all posts (moderators and admins).
+pub async fn moderation_queue(limit: i64, offset: i64) -> Result<CommentListResponse, ApiError> {
+    let url = get_api_base_url().join(&paths::moderation_queue_page(Pagination { limit, offset }));
+    let response = authorized(Request::get(&url))?
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Removes any comment (moderators and admins).
+pub async fn remove_comment(id: i64) -> Result<(), ApiError> {
+    let url = get_api_base_url().join(&paths::moderated_comment(id));
+    let request = authorized(Request::delete(&url))?
+        .build()
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    send_no_content(request).await
+}
+
+/// Lists all feature flags (admin only).
+pub async fn list_feature_flags() -> Result<FeatureFlagListResponse, ApiError> {
+    let response = authorized(Request::get(
+        &get_api_base_url().join(&paths::feature_flags()),
+    ))?
+    .send()
+    .await
+    .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Turns a feature flag on or off (admin only).
+pub async fn update_feature_flag(name: &str, enabled: bool) -> Result<FeatureFlagDto, ApiError> {
+    let response = authorized(Request::put(
+        &get_api_base_url().join(&paths::feature_flag(name)),
+    ))?
+    .json(&UpdateFeatureFlagRequest { enabled })
+    .map_err(|e| ApiError::new(e.to_string()))?
+    .send()
+    .await
+    .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Changes the site settings (admin only).
+pub async fn update_site_settings(
+    req: UpdateSiteSettingsRequest,
+) -> Result<SiteSettingsDto, ApiError> {
+    let response = authorized(Request::put(
+        &get_api_base_url().join(&paths::admin_site_settings()),
+    ))?
+    .json(&req)
+    .map_err(|e| ApiError::new(e.to_string()))?
+    .send()
+    .await
+    .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Sets the announcement banner (admin only).
+pub async fn set_announcement(req: SetAnnouncementRequest) -> Result<AnnouncementDto, ApiError> {
+    let response = authorized(Request::put(
+        &get_api_base_url().join(&paths::admin_announcement()),
+    ))?
+    .json(&req)
+    .map_err(|e| ApiError::new(e.to_string()))?
+    .send()
+    .await
+    .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Removes the announcement banner (admin only).
+pub async fn clear_announcement() -> Result<(), ApiError> {
+    let request = authorized(Request::delete(
+        &get_api_base_url().join(&paths::admin_announcement()),
+    ))?
+    .build()
+    .map_err(|e| ApiError::new(e.to_string()))?;
+
+    send_no_content(request).await
+}
@@ -0,0 +1,81 @@
+//! Comment endpoints.
+
+use gloo_net::http::Request;
+
+use blog_shared::endpoint::paths;
+use blog_shared::{
+    CommentDto, CommentListResponse, CreateCommentRequest, Pagination, UpdateCommentRequest,
+};
+
+use super::{ApiError, error_from_response, get_api_base_url, get_token, handle_response};
+
+/// Lists comments on a post, oldest first.
+pub async fn list_comments(
+    post_id: &str,
+    limit: i64,
+    offset: i64,
+) -> Result<CommentListResponse, ApiError> {
+    let url = get_api_base_url().join(&paths::post_comments_page(
+        post_id,
+        Pagination { limit, offset },
+    ));
+    let response = Request::get(&url)
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Adds a comment to a post.
+pub async fn create_comment(
+    post_id: &str,
+    req: CreateCommentRequest,
+) -> Result<CommentDto, ApiError> {
+    let url = get_api_base_url().join(&paths::post_comments(post_id));
+    let token = get_token().ok_or(ApiError::new("Not authenticated"))?;
+
+    let response = Request::post(&url)
+        .header("Authorization", &format!("Bearer {}", token))
+        .json(&req)
+        .map_err(|e| ApiError::new(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Edits a comment.
+pub async fn update_comment(id: i64, req: UpdateCommentRequest) -> Result<CommentDto, ApiError> {
+    let url = get_api_base_url().join(&paths::comment(id));
+    let token = get_token().ok_or(ApiError::new("Not authenticated"))?;
+
+    let response = Request::put(&url)
+        .header("Authorization", &format!("Bearer {}", token))
+        .json(&req)
+        .map_err(|e| ApiError::new(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Deletes a comment.
+pub async fn delete_comment(id: i64) -> Result<(), ApiError> {
+    let url = get_api_base_url().join(&paths::comment(id));
+    let token = get_token().ok_or(ApiError::new("Not authenticated"))?;
+
+    let response = Request::delete(&url)
+        .header("Authorization", &format!("Bearer {}", token))
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    if response.ok() {
+        Ok(())
+    } else {
+        Err(error_from_response(response).await)
+    }
+}
@@ -0,0 +1,42 @@
+//! Co-editing presence endpoints.
+
+use gloo_net::http::Request;
+use web_sys::UrlSearchParams;
+
+use blog_shared::EditingPresenceDto;
+use blog_shared::endpoint::paths;
+
+use super::{ApiError, authorized, get_api_base_url, get_token, handle_response, send_no_content};
+
+/// Tells the server the post is open in this editor; returns everyone
+/// editing it, this user included.
+pub async fn editing_heartbeat(id: &str) -> Result<EditingPresenceDto, ApiError> {
+    let url = get_api_base_url().join(&paths::editing_heartbeat(id));
+    let response = authorized(Request::post(&url))?
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Tells the server the post was closed in this editor.
+pub async fn stop_editing(id: &str) -> Result<(), ApiError> {
+    let url = get_api_base_url().join(&paths::editing_heartbeat(id));
+    let request = authorized(Request::delete(&url))?
+        .build()
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    send_no_content(request).await
+}
+
+/// URL of the WebSocket pushing the post's editors as they change, with
+/// the token in the query (browsers cannot set headers on WebSockets).
+pub fn editing_socket_url(id: &str) -> Option<String> {
+    let token = get_token()?;
+    let base = get_api_base_url().as_str().replacen("http", "ws", 1);
+    let params = UrlSearchParams::new().ok()?;
+    params.append("token", &token);
+    let query: String = params.to_string().into();
+    Some(format!("{}{}?{}", base, paths::editing_socket(id), query))
+}
@@ -0,0 +1,19 @@
+//! A/B experiment endpoints (assignments come with `get_features`).
+
+use gloo_net::http::Request;
+
+use super::{ApiError, authorized, get_api_base_url, send_no_content};
+
+/// Records that the current user was shown their variant of an experiment.
+pub async fn record_exposure(experiment: &str) -> Result<(), ApiError> {
+    let url = format!(
+        "{}/api/experiments/{}/exposures",
+        get_api_base_url(),
+        experiment
+    );
+    let request = authorized(Request::post(&url))?
+        .build()
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    send_no_content(request).await
+}
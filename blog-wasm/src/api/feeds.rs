@@ -0,0 +1,13 @@
+//! RSS and Atom feed addresses.
+
+use super::get_api_base_url;
+
+/// URL of the RSS feed of the newest posts.
+pub fn rss_feed_url() -> String {
+    format!("{}/api/feed.rss", get_api_base_url())
+}
+
+/// URL of the Atom feed of the newest posts.
+pub fn atom_feed_url() -> String {
+    format!("{}/api/feed.atom", get_api_base_url())
+}
@@ -0,0 +1,19 @@
+//! Markdown preview endpoint.
+
+use gloo_net::http::Request;
+
+use blog_shared::MarkdownPreviewResponse;
+
+use super::{ApiError, get_api_base_url, handle_response};
+
+/// Renders Markdown on the server, as post content will be rendered.
+pub async fn preview_markdown(text: &str) -> Result<MarkdownPreviewResponse, ApiError> {
+    let url = format!("{}/api/markdown/preview", get_api_base_url());
+    let response = Request::get(&url)
+        .query([("text", text)])
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
@@ -0,0 +1,90 @@
+//! Private message endpoints.
+
+use gloo_net::http::Request;
+
+use blog_shared::endpoint::paths;
+use blog_shared::{
+    ConversationDto, ConversationListResponse, MessageDto, MessageListResponse, Pagination,
+    SendMessageRequest, StartConversationRequest, UnreadCountResponse,
+};
+
+use super::{ApiError, authorized, get_api_base_url, handle_response};
+
+/// Lists the current user's conversations, most recently active first.
+pub async fn list_conversations(
+    limit: i64,
+    offset: i64,
+) -> Result<ConversationListResponse, ApiError> {
+    let url = get_api_base_url().join(&paths::conversations_page(Pagination { limit, offset }));
+    let response = authorized(Request::get(&url))?
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Sends a first message to `username`, starting a conversation with them.
+pub async fn start_conversation(
+    username: &str,
+    content: &str,
+) -> Result<ConversationDto, ApiError> {
+    let req = StartConversationRequest {
+        username: username.to_string(),
+        content: content.to_string(),
+    };
+    let response = authorized(Request::post(
+        &get_api_base_url().join(&paths::conversations()),
+    ))?
+    .json(&req)
+    .map_err(|e| ApiError::new(e.to_string()))?
+    .send()
+    .await
+    .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Lists messages of a conversation, newest first (marks it as read).
+pub async fn list_messages(
+    conversation_id: i64,
+    limit: i64,
+    offset: i64,
+) -> Result<MessageListResponse, ApiError> {
+    let url = get_api_base_url().join(&paths::conversation_messages_page(
+        conversation_id,
+        Pagination { limit, offset },
+    ));
+    let response = authorized(Request::get(&url))?
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Sends a message in a conversation.
+pub async fn send_message(conversation_id: i64, content: &str) -> Result<MessageDto, ApiError> {
+    let url = get_api_base_url().join(&paths::conversation_messages(conversation_id));
+    let response = authorized(Request::post(&url))?
+        .json(&SendMessageRequest {
+            content: content.to_string(),
+        })
+        .map_err(|e| ApiError::new(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Gets the number of unread messages.
+pub async fn unread_count() -> Result<UnreadCountResponse, ApiError> {
+    let url = get_api_base_url().join(&paths::unread_messages());
+    let response = authorized(Request::get(&url))?
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
@@ -0,0 +1,91 @@
+//! Moderation rule and flagged content endpoints.
+
+use gloo_net::http::Request;
+
+use blog_shared::endpoint::paths;
+use blog_shared::{
+    CreateModerationRuleRequest, FlaggedContentListResponse, ModerationRuleDto,
+    ModerationRuleListResponse, Pagination, UpdateModerationRuleRequest,
+};
+
+use super::{ApiError, authorized, get_api_base_url, handle_response, send_no_content};
+
+/// Lists moderation rules with their hit rates (admin only).
+pub async fn list_moderation_rules() -> Result<ModerationRuleListResponse, ApiError> {
+    let response = authorized(Request::get(
+        &get_api_base_url().join(&paths::moderation_rules()),
+    ))?
+    .send()
+    .await
+    .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Creates a moderation rule (admin only).
+pub async fn create_moderation_rule(
+    req: CreateModerationRuleRequest,
+) -> Result<ModerationRuleDto, ApiError> {
+    let response = authorized(Request::post(
+        &get_api_base_url().join(&paths::moderation_rules()),
+    ))?
+    .json(&req)
+    .map_err(|e| ApiError::new(e.to_string()))?
+    .send()
+    .await
+    .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Changes a moderation rule (admin only).
+pub async fn update_moderation_rule(
+    id: i64,
+    req: UpdateModerationRuleRequest,
+) -> Result<ModerationRuleDto, ApiError> {
+    let response = authorized(Request::put(
+        &get_api_base_url().join(&paths::moderation_rule(id)),
+    ))?
+    .json(&req)
+    .map_err(|e| ApiError::new(e.to_string()))?
+    .send()
+    .await
+    .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Deletes a moderation rule (admin only).
+pub async fn delete_moderation_rule(id: i64) -> Result<(), ApiError> {
+    let request = authorized(Request::delete(
+        &get_api_base_url().join(&paths::moderation_rule(id)),
+    ))?
+    .build()
+    .map_err(|e| ApiError::new(e.to_string()))?;
+
+    send_no_content(request).await
+}
+
+/// Lists posts and comments flagged by moderation rules (moderators and admins).
+pub async fn list_flagged_content(
+    limit: i64,
+    offset: i64,
+) -> Result<FlaggedContentListResponse, ApiError> {
+    let url = get_api_base_url().join(&paths::flagged_content_page(Pagination { limit, offset }));
+    let response = authorized(Request::get(&url))?
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Dismisses a flag, leaving the content in place (moderators and admins).
+pub async fn dismiss_flag(id: i64) -> Result<(), ApiError> {
+    let url = get_api_base_url().join(&paths::flag(id));
+    let request = authorized(Request::delete(&url))?
+        .build()
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    send_no_content(request).await
+}
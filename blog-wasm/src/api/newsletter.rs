@@ -0,0 +1,23 @@
+//! Newsletter subscription endpoints.
+
+use gloo_net::http::Request;
+
+use blog_shared::{SubscribeRequest, SubscriptionStatusResponse};
+
+use super::{ApiError, get_api_base_url, handle_response};
+
+/// Subscribes an email address to the weekly digest (a confirmation email follows).
+pub async fn subscribe_newsletter(email: &str) -> Result<SubscriptionStatusResponse, ApiError> {
+    let url = format!("{}/api/newsletter/subscribe", get_api_base_url());
+    let req = SubscribeRequest {
+        email: email.to_string(),
+    };
+    let response = Request::post(&url)
+        .json(&req)
+        .map_err(|e| ApiError::new(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
@@ -0,0 +1,21 @@
+//! Organization endpoints.
+
+use gloo_net::http::Request;
+
+use blog_shared::OrgProfileResponse;
+
+use super::{ApiError, get_api_base_url, handle_response, with_viewer};
+
+/// Gets an organization's profile with its members and a page of its posts.
+pub async fn get_org(slug: &str, limit: i64, offset: i64) -> Result<OrgProfileResponse, ApiError> {
+    let url = format!("{}/api/orgs/{}", get_api_base_url(), slug);
+    let limit = limit.to_string();
+    let offset = offset.to_string();
+    let response = with_viewer(Request::get(&url))
+        .query([("limit", &limit), ("offset", &offset)])
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
@@ -0,0 +1,20 @@
+//! Account recovery with one-time codes.
+
+use gloo_net::http::Request;
+
+use blog_shared::{AuthResponse, RecoverAccountRequest};
+
+use super::{ApiError, get_api_base_url, handle_response};
+
+/// Signs in with a recovery code, setting a new password.
+pub async fn recover_account(req: RecoverAccountRequest) -> Result<AuthResponse, ApiError> {
+    let url = format!("{}/api/auth/recover", get_api_base_url());
+    let response = Request::post(&url)
+        .json(&req)
+        .map_err(|e| ApiError::new(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
@@ -0,0 +1,34 @@
+//! Post revision endpoints.
+
+use gloo_net::http::Request;
+
+use blog_shared::endpoint::paths;
+use blog_shared::{RevisionDiffResponse, RevisionListResponse};
+
+use super::{ApiError, authorized, get_api_base_url, handle_response};
+
+/// Lists the revisions of a post, newest first (author or editors).
+pub async fn list_revisions(id: &str) -> Result<RevisionListResponse, ApiError> {
+    let url = get_api_base_url().join(&paths::post_revisions(id));
+    let response = authorized(Request::get(&url))?
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Diffs revision `from` of a post against revision `to` (author or editors).
+pub async fn diff_revisions(
+    id: &str,
+    from: i64,
+    to: i64,
+) -> Result<RevisionDiffResponse, ApiError> {
+    let url = get_api_base_url().join(&paths::revision_diff(id, from, to));
+    let response = authorized(Request::get(&url))?
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
@@ -0,0 +1,142 @@
+//! Settings endpoints (profile, account, sessions, preferences).
+
+use gloo_net::http::Request;
+
+use blog_shared::endpoint::paths;
+use blog_shared::{
+    ChangeEmailRequest, ChangePasswordRequest, ConfirmEmailChangeRequest, DeleteAccountRequest,
+    PendingEmailChangeResponse, PreferencesDto, ProfileDto, RecoveryCodesResponse,
+    RegenerateRecoveryCodesRequest, SessionListResponse, SettingsDto, UserDto,
+};
+
+use super::{ApiError, authorized, get_api_base_url, handle_response, send_no_content};
+
+/// Gets all settings of the current user.
+pub async fn get_settings() -> Result<SettingsDto, ApiError> {
+    let response = authorized(Request::get(&get_api_base_url().join(&paths::settings())))?
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Replaces the current user's profile.
+pub async fn update_profile(req: ProfileDto) -> Result<ProfileDto, ApiError> {
+    let response = authorized(Request::put(
+        &get_api_base_url().join(&paths::settings_profile()),
+    ))?
+    .json(&req)
+    .map_err(|e| ApiError::new(e.to_string()))?
+    .send()
+    .await
+    .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Replaces the current user's display preferences.
+pub async fn update_preferences(req: PreferencesDto) -> Result<PreferencesDto, ApiError> {
+    let response = authorized(Request::put(
+        &get_api_base_url().join(&paths::settings_preferences()),
+    ))?
+    .json(&req)
+    .map_err(|e| ApiError::new(e.to_string()))?
+    .send()
+    .await
+    .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Starts changing the current user's email; it switches once the link sent
+/// to the new address is opened.
+pub async fn change_email(req: ChangeEmailRequest) -> Result<PendingEmailChangeResponse, ApiError> {
+    let response = authorized(Request::put(
+        &get_api_base_url().join(&paths::settings_email()),
+    ))?
+    .json(&req)
+    .map_err(|e| ApiError::new(e.to_string()))?
+    .send()
+    .await
+    .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Confirms an email change with the token from the link sent to the new
+/// address (no sign-in needed).
+pub async fn confirm_email_change(token: &str) -> Result<UserDto, ApiError> {
+    let req = ConfirmEmailChangeRequest {
+        token: token.to_string(),
+    };
+    let response = Request::post(&get_api_base_url().join(&paths::settings_email_confirm()))
+        .json(&req)
+        .map_err(|e| ApiError::new(e.to_string()))?
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Changes the current user's password (signs out other sessions).
+pub async fn change_password(req: ChangePasswordRequest) -> Result<(), ApiError> {
+    let request = authorized(Request::put(
+        &get_api_base_url().join(&paths::settings_password()),
+    ))?
+    .json(&req)
+    .map_err(|e| ApiError::new(e.to_string()))?;
+
+    send_no_content(request).await
+}
+
+/// Replaces the current user's recovery codes (requires the current password).
+pub async fn regenerate_recovery_codes(
+    req: RegenerateRecoveryCodesRequest,
+) -> Result<RecoveryCodesResponse, ApiError> {
+    let response = authorized(Request::post(
+        &get_api_base_url().join(&paths::settings_recovery_codes()),
+    ))?
+    .json(&req)
+    .map_err(|e| ApiError::new(e.to_string()))?
+    .send()
+    .await
+    .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Lists the current user's active sessions.
+pub async fn list_sessions() -> Result<SessionListResponse, ApiError> {
+    let response = authorized(Request::get(
+        &get_api_base_url().join(&paths::settings_sessions()),
+    ))?
+    .send()
+    .await
+    .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Revokes one of the current user's sessions.
+pub async fn revoke_session(id: &str) -> Result<(), ApiError> {
+    let request = authorized(Request::delete(
+        &get_api_base_url().join(&paths::settings_session(id)),
+    ))?
+    .build()
+    .map_err(|e| ApiError::new(e.to_string()))?;
+
+    send_no_content(request).await
+}
+
+/// Deletes the current user's account (requires the current password).
+pub async fn delete_account(req: DeleteAccountRequest) -> Result<(), ApiError> {
+    let request = authorized(Request::delete(
+        &get_api_base_url().join(&paths::settings_account()),
+    ))?
+    .json(&req)
+    .map_err(|e| ApiError::new(e.to_string()))?;
+
+    send_no_content(request).await
+}
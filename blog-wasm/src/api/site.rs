@@ -0,0 +1,30 @@
+//! Site-wide endpoints (settings and the announcement banner).
+
+use gloo_net::http::Request;
+
+use blog_shared::endpoint::paths;
+use blog_shared::{AnnouncementResponse, SiteSettingsDto};
+
+use super::{ApiError, get_api_base_url, handle_response};
+
+/// Gets the current announcement banner, if any.
+pub async fn get_announcement() -> Result<AnnouncementResponse, ApiError> {
+    let url = get_api_base_url().join(&paths::announcement());
+    let response = Request::get(&url)
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Gets the site settings (title, description, page size...).
+pub async fn get_site_settings() -> Result<SiteSettingsDto, ApiError> {
+    let url = get_api_base_url().join(&paths::site_settings());
+    let response = Request::get(&url)
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
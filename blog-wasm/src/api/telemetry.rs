@@ -0,0 +1,21 @@
+//! Crash reports forwarded to the server's error tracker.
+
+use blog_shared::ClientErrorReport;
+use blog_shared::endpoint::paths;
+use web_sys::window;
+
+use super::get_api_base_url;
+
+/// Sends a crash report with `navigator.sendBeacon`, which completes after
+/// the WASM instance is gone. The JSON goes as `text/plain` so the
+/// cross-origin request needs no preflight. Best effort: failures are
+/// ignored.
+pub fn report_crash(report: &ClientErrorReport) {
+    let (Some(window), Ok(body)) = (window(), serde_json::to_string(report)) else {
+        return;
+    };
+    let url = get_api_base_url().join(&paths::client_errors());
+    let _ = window
+        .navigator()
+        .send_beacon_with_opt_str(&url, Some(&body));
+}
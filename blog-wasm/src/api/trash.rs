@@ -0,0 +1,29 @@
+//! Trash endpoints: the current user's deleted posts.
+
+use gloo_net::http::Request;
+
+use blog_shared::TrashListResponse;
+use blog_shared::endpoint::paths;
+
+use super::{ApiError, authorized, get_api_base_url, handle_response, send_no_content};
+
+/// Lists the current user's deleted posts, most recently deleted first.
+pub async fn list_trash() -> Result<TrashListResponse, ApiError> {
+    let url = get_api_base_url().join(&paths::trash());
+    let response = authorized(Request::get(&url))?
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Takes a post out of the trash (author only).
+pub async fn restore_post(id: &str) -> Result<(), ApiError> {
+    let url = get_api_base_url().join(&paths::post_restore(id));
+    let request = authorized(Request::post(&url))?
+        .build()
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    send_no_content(request).await
+}
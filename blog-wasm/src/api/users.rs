@@ -0,0 +1,34 @@
+//! Public user profile and `@mention` suggestion endpoints.
+
+use gloo_net::http::Request;
+
+use blog_shared::PublicUserDto;
+#[cfg(feature = "editor")]
+use blog_shared::UserSuggestionResponse;
+use blog_shared::endpoint::paths;
+
+use super::{ApiError, get_api_base_url, handle_response};
+
+/// Gets the public profile of a user by their public ID.
+pub async fn get_user(id: &str) -> Result<PublicUserDto, ApiError> {
+    let url = get_api_base_url().join(&paths::user(id));
+    let response = Request::get(&url)
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Suggests users whose names start with `prefix`, best first.
+#[cfg(feature = "editor")]
+pub async fn suggest_users(prefix: &str) -> Result<UserSuggestionResponse, ApiError> {
+    let url = get_api_base_url().join(&paths::suggest_users());
+    let response = Request::get(&url)
+        .query([("q", prefix)])
+        .send()
+        .await
+        .map_err(|e| ApiError::new(e.to_string()))?;
+
+    handle_response(response).await
+}
@@ -0,0 +1,431 @@
+//! The application: page state, navigation, and the layout around pages.
+
+#[cfg(feature = "editor")]
+use blog_shared::PostDto;
+use blog_shared::{Role, UserDto};
+use gloo_events::EventListener;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::window;
+use yew::prelude::*;
+
+use crate::constants::{APP_SHELL_ID, MAIN_CONTENT_ID, SEARCH_INPUT_ID};
+use crate::experiments::{Experiments, use_experiments};
+use crate::hooks::{focus_by_id, use_hotkeys};
+use crate::page::Page;
+use crate::site::{SiteContext, use_site_context};
+use crate::user_cache::{UserCache, use_user_cache};
+use crate::{api, location, offline, preferences};
+
+#[cfg(feature = "admin")]
+use crate::components::AdminDashboard;
+#[cfg(feature = "editor")]
+use crate::components::PostForm;
+use crate::components::{
+    AnnouncementBanner, ConfirmEmailChange, Inbox, InstallPrompt, LoginForm, MainNav,
+    NewsletterSignup, NotFound, OfflineBanner, OrgPage, PostDetail, PostList, ReaderView,
+    RegisterForm, SearchBar, SearchResults, SettingsPage, TagCloud, TrashPage,
+};
+#[cfg(not(all(feature = "admin", feature = "editor")))]
+use crate::route_chunk::{ChunkEvent, ChunkRoute, RouteChunk};
+
+/// User info stored in app state.
+#[derive(Clone, PartialEq, Default)]
+struct UserInfo {
+    id: Option<i64>,
+    public_id: Option<String>,
+    username: Option<String>,
+    role: Role,
+}
+
+impl From<UserDto> for UserInfo {
+    fn from(user: UserDto) -> Self {
+        Self {
+            id: Some(user.id),
+            public_id: Some(user.public_id),
+            username: Some(user.username),
+            role: user.role,
+        }
+    }
+}
+
+/// Main application component.
+#[function_component(App)]
+pub fn app() -> Html {
+    // A shared permalink opens its post first
+    let page = use_state(|| location::take_page_from_url().unwrap_or(Page::Posts));
+    let user_info = use_state(UserInfo::default);
+    let is_authenticated = use_state(api::is_authenticated);
+    let refresh = use_state(|| 0u32);
+    // Bumped after messages were read so the notification bell reloads
+    let unread_refresh = use_state(|| 0u32);
+    // Tag filter, mirrored in the `?tag=` query string
+    let tag = use_state(location::tag_from_url);
+    let user_cache = use_user_cache();
+    let experiments = use_experiments(user_info.id);
+    let site = use_site_context();
+
+    // Take over from the loading shell once the app is on screen
+    use_effect_with((), |_| {
+        remove_loading_shell();
+        || ()
+    });
+
+    // Check for existing token on mount and restore user session
+    {
+        let is_authenticated = is_authenticated.clone();
+        let user_info = user_info.clone();
+        use_effect_with((), move |_| {
+            if api::is_authenticated() {
+                let is_authenticated = is_authenticated.clone();
+                let user_info = user_info.clone();
+                spawn_local(async move {
+                    match api::get_me().await {
+                        Ok(me) => {
+                            user_info.set(me.user.into());
+                            is_authenticated.set(true);
+                            preferences::sync_preferences().await;
+                        }
+                        Err(_) => {
+                            // Token is invalid, clear it
+                            api::clear_token();
+                            is_authenticated.set(false);
+                        }
+                    }
+                });
+            }
+            || ()
+        });
+    }
+
+    // Back/forward restore the tag filter from the URL
+    {
+        let tag = tag.clone();
+        use_effect_with((), move |_| {
+            let listener = window().map(|win| {
+                EventListener::new(&win, "popstate", move |_| {
+                    tag.set(location::tag_from_url());
+                })
+            });
+            move || drop(listener)
+        });
+    }
+
+    let on_logout = {
+        let page = page.clone();
+        let user_info = user_info.clone();
+        let is_authenticated = is_authenticated.clone();
+        Callback::from(move |_| {
+            user_info.set(UserInfo::default());
+            is_authenticated.set(false);
+            preferences::reset_preferences();
+            offline::clear_cache();
+            page.set(Page::Posts);
+        })
+    };
+
+    let on_auth_success = {
+        let page = page.clone();
+        let user_info = user_info.clone();
+        let is_authenticated = is_authenticated.clone();
+        Callback::from(move |user: UserDto| {
+            user_info.set(user.into());
+            is_authenticated.set(true);
+            spawn_local(preferences::sync_preferences());
+            page.set(Page::Posts);
+        })
+    };
+
+    let on_tag_select = {
+        let page = page.clone();
+        let tag = tag.clone();
+        Callback::from(move |selected: Option<String>| {
+            if *tag != selected {
+                location::push_tag_to_url(selected.as_deref());
+                tag.set(selected);
+            }
+            page.set(Page::Posts);
+        })
+    };
+
+    let on_tag_chip = {
+        let on_tag_select = on_tag_select.clone();
+        Callback::from(move |selected: String| on_tag_select.emit(Some(selected)))
+    };
+
+    let on_navigate = {
+        let page = page.clone();
+        Callback::from(move |target: Page| page.set(target))
+    };
+
+    let on_posts = {
+        let on_tag_select = on_tag_select.clone();
+        Callback::from(move |_| on_tag_select.emit(None))
+    };
+
+    let on_edit_post = {
+        let page = page.clone();
+        Callback::from(move |post_id: String| {
+            page.set(Page::EditPost(post_id));
+        })
+    };
+
+    let on_open_post = {
+        let page = page.clone();
+        Callback::from(move |post_id: String| {
+            page.set(Page::PostDetail(post_id));
+        })
+    };
+
+    let on_search = {
+        let page = page.clone();
+        Callback::from(move |query: String| {
+            page.set(Page::Search(query));
+        })
+    };
+
+    #[cfg(feature = "editor")]
+    let on_post_created = {
+        let page = page.clone();
+        Callback::from(move |_post: PostDto| {
+            page.set(Page::Posts);
+        })
+    };
+
+    let on_post_cancel = {
+        let page = page.clone();
+        Callback::from(move |_| {
+            page.set(Page::Posts);
+        })
+    };
+
+    // Requests of the pages shown from the route chunk
+    #[cfg(not(all(feature = "admin", feature = "editor")))]
+    let on_chunk_event = {
+        let page = page.clone();
+        let site = site.clone();
+        Callback::from(move |event: ChunkEvent| match event {
+            ChunkEvent::OpenPost { post_id } => page.set(Page::PostDetail(post_id)),
+            ChunkEvent::SiteUpdated { settings } => site.update(settings),
+            ChunkEvent::PostSaved | ChunkEvent::Cancelled => page.set(Page::Posts),
+        })
+    };
+
+    // Global shortcuts: n = new post, / = focus search
+    {
+        let page = page.clone();
+        let is_authenticated = *is_authenticated;
+        use_hotkeys(vec![
+            (
+                "n",
+                Callback::from(move |_| {
+                    if is_authenticated {
+                        page.set(Page::NewPost);
+                    }
+                }),
+            ),
+            ("/", Callback::from(|_| focus_by_id(SEARCH_INPUT_ID))),
+        ]);
+    }
+
+    // Move focus to the new content on page transitions (not on first load)
+    {
+        let first_render = use_mut_ref(|| true);
+        use_effect_with((*page).clone(), move |_| {
+            if !std::mem::replace(&mut *first_render.borrow_mut(), false) {
+                focus_by_id(MAIN_CONTENT_ID);
+            }
+            || ()
+        });
+    }
+
+    // Reload the post list after offline changes were synced
+    let on_synced = {
+        let refresh = refresh.clone();
+        Callback::from(move |_| {
+            refresh.set(*refresh + 1);
+        })
+    };
+
+    // Reload the post list once a deleted post is back
+    let on_post_restored = {
+        let refresh = refresh.clone();
+        Callback::from(move |_: String| {
+            refresh.set(*refresh + 1);
+        })
+    };
+
+    let on_messages_read = {
+        let unread_refresh = unread_refresh.clone();
+        Callback::from(move |_| unread_refresh.set(*unread_refresh + 1))
+    };
+
+    let main_content = match (*page).clone() {
+        Page::Posts => html! {
+            <>
+                <TagCloud
+                    selected={(*tag).clone()}
+                    on_select={on_tag_select.clone()}
+                    refresh={*refresh}
+                />
+                <PostList
+                    key={(*tag).clone().unwrap_or_default()}
+                    current_user_id={user_info.id}
+                    on_edit={Some(on_edit_post.clone())}
+                    on_open={Some(on_open_post.clone())}
+                    refresh={*refresh}
+                    tag={(*tag).clone()}
+                    on_tag={Some(on_tag_chip.clone())}
+                />
+                <NewsletterSignup />
+            </>
+        },
+        Page::Login => html! {
+            <LoginForm on_success={on_auth_success.clone()} />
+        },
+        Page::Register => html! {
+            <RegisterForm on_success={on_auth_success.clone()} />
+        },
+        #[cfg(feature = "editor")]
+        Page::NewPost => html! {
+            <PostForm
+                on_success={on_post_created.clone()}
+                on_cancel={Some(on_post_cancel.clone())}
+            />
+        },
+        #[cfg(not(feature = "editor"))]
+        Page::NewPost => html! {
+            <RouteChunk
+                route={ChunkRoute::Editor { post_id: None, current_user_public_id: None }}
+                on_event={on_chunk_event.clone()}
+            />
+        },
+        Page::PostDetail(post_id) => html! {
+            <PostDetail
+                {post_id}
+                current_user_public_id={user_info.public_id.clone()}
+                current_username={user_info.username.clone()}
+                on_back={on_post_cancel.clone()}
+                on_edit={Some(on_edit_post.clone())}
+                on_tag={Some(on_tag_chip.clone())}
+                on_reader={Some(on_navigate.reform(Page::Reader))}
+                on_open={Some(on_open_post.clone())}
+                on_org={Some(on_navigate.reform(Page::Org))}
+            />
+        },
+        Page::Reader(post_id) => html! {
+            <ReaderView {post_id} on_exit={on_open_post.clone()} />
+        },
+        Page::Search(query) => html! {
+            <SearchResults
+                key={query.clone()}
+                query={query.clone()}
+                current_user_id={user_info.id}
+                on_open={on_open_post.clone()}
+                on_edit={Some(on_edit_post.clone())}
+            />
+        },
+        Page::Settings => html! { <SettingsPage on_account_deleted={on_logout.clone()} /> },
+        Page::Trash => html! { <TrashPage on_restored={on_post_restored} /> },
+        Page::Org(slug) => html! {
+            <OrgPage {slug} on_open={on_open_post.clone()} />
+        },
+        Page::Inbox => html! {
+            <Inbox
+                current_user_public_id={user_info.public_id.clone()}
+                on_read={on_messages_read}
+            />
+        },
+        #[cfg(feature = "admin")]
+        Page::Admin => html! {
+            <AdminDashboard
+                role={user_info.role}
+                current_user_id={user_info.id}
+                on_open_post={on_open_post.clone()}
+            />
+        },
+        #[cfg(not(feature = "admin"))]
+        Page::Admin => html! {
+            <RouteChunk
+                route={ChunkRoute::Admin {
+                    role: user_info.role,
+                    current_user_id: user_info.id,
+                }}
+                on_event={on_chunk_event.clone()}
+            />
+        },
+        Page::ConfirmEmail(token) => html! {
+            <ConfirmEmailChange {token} on_back={on_post_cancel.clone()} />
+        },
+        Page::NotFound => html! { <NotFound on_back={on_post_cancel.clone()} /> },
+        #[cfg(feature = "editor")]
+        Page::EditPost(post_id) => {
+            html! {
+                <PostForm
+                    post_id={Some(post_id)}
+                    on_success={on_post_created.clone()}
+                    on_cancel={Some(on_post_cancel.clone())}
+                    current_user_public_id={user_info.public_id.clone()}
+                />
+            }
+        }
+        #[cfg(not(feature = "editor"))]
+        Page::EditPost(post_id) => html! {
+            <RouteChunk
+                route={ChunkRoute::Editor {
+                    post_id: Some(post_id),
+                    current_user_public_id: user_info.public_id.clone(),
+                }}
+                on_event={on_chunk_event.clone()}
+            />
+        },
+    };
+
+    html! {
+        <ContextProvider<SiteContext> context={site.clone()}>
+            <ContextProvider<UserCache> context={user_cache}>
+                <ContextProvider<Experiments> context={experiments}>
+                    <div class="app">
+                        <a class="skip-link" href={format!("#{}", MAIN_CONTENT_ID)}>{"Skip to content"}</a>
+                        if !matches!(*page, Page::Reader(_)) {
+                            <AnnouncementBanner user_id={user_info.id} />
+                        }
+                        // Reader mode drops the header chrome
+                        if !matches!(*page, Page::Reader(_)) {
+                            <header class="header">
+                                <h1>{site.title.clone()}</h1>
+                                <SearchBar on_open={on_open_post.clone()} {on_search} />
+                                <MainNav
+                                    page={(*page).clone()}
+                                    is_authenticated={*is_authenticated}
+                                    username={user_info.username.clone()}
+                                    role={user_info.role}
+                                    unread_refresh={*unread_refresh}
+                                    {on_navigate}
+                                    {on_posts}
+                                    {on_logout}
+                                />
+                            </header>
+                        }
+                        <OfflineBanner on_synced={Some(on_synced)} />
+                        if !matches!(*page, Page::Reader(_)) {
+                            <InstallPrompt />
+                        }
+                        <main class="main" id={MAIN_CONTENT_ID} tabindex="-1">
+                            {main_content}
+                        </main>
+                    </div>
+                </ContextProvider<Experiments>>
+            </ContextProvider<UserCache>>
+        </ContextProvider<SiteContext>>
+    }
+}
+
+/// Removes the static loading shell of `index.html`.
+fn remove_loading_shell() {
+    if let Some(shell) = window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.get_element_by_id(APP_SHELL_ID))
+    {
+        shell.remove();
+    }
+}
@@ -1,13 +1,141 @@
 //! UI components.
 
+mod account_settings;
+#[cfg(feature = "admin")]
+mod admin_dashboard;
+#[cfg(feature = "admin")]
+mod admin_stats;
+mod announcement_banner;
+#[cfg(feature = "admin")]
+mod announcement_editor;
+mod comment_form;
+mod comment_item;
+mod comment_list;
+mod confirm_dialog;
+mod confirm_email_change;
+#[cfg(feature = "editor")]
+mod content_editor;
+mod conversation_view;
+mod delete_account;
+#[cfg(feature = "editor")]
+mod editing_presence;
+mod error_boundary;
+mod error_page;
+#[cfg(feature = "admin")]
+mod feature_flags;
+#[cfg(feature = "admin")]
+mod flagged_content;
+mod inbox;
+mod install_prompt;
+mod language_switcher;
+mod link_previews;
 mod login_form;
+mod main_nav;
+mod markdown_content;
+#[cfg(feature = "admin")]
+mod moderation_queue;
+#[cfg(feature = "admin")]
+mod moderation_rules;
+mod newsletter_signup;
+mod notification_bell;
+mod offline_banner;
+mod org_page;
 mod post_card;
+mod post_detail;
+#[cfg(feature = "editor")]
 mod post_form;
 mod post_list;
+mod preferences_settings;
+mod profile_settings;
+mod reader_view;
+mod recover_form;
+mod recovery_codes;
 mod register_form;
+#[cfg(feature = "editor")]
+mod revision_history;
+mod search_bar;
+mod search_results;
+mod session_list;
+mod settings_page;
+mod share_menu;
+#[cfg(feature = "admin")]
+mod site_settings_editor;
+mod skeleton;
+mod tag_chips;
+mod tag_cloud;
+mod toast;
+mod trash_page;
+mod user_avatar;
+#[cfg(feature = "admin")]
+mod user_table;
 
+pub use account_settings::AccountSettings;
+#[cfg(feature = "admin")]
+pub use admin_dashboard::AdminDashboard;
+#[cfg(feature = "admin")]
+pub use admin_stats::AdminStats;
+pub use announcement_banner::AnnouncementBanner;
+#[cfg(feature = "admin")]
+pub use announcement_editor::AnnouncementEditor;
+pub use comment_form::CommentForm;
+pub use comment_item::CommentItem;
+pub use comment_list::CommentList;
+pub use confirm_dialog::ConfirmDialog;
+pub use confirm_email_change::ConfirmEmailChange;
+#[cfg(feature = "editor")]
+pub use content_editor::ContentEditor;
+pub use conversation_view::ConversationView;
+pub use delete_account::DeleteAccount;
+#[cfg(feature = "editor")]
+pub use editing_presence::EditingPresence;
+pub use error_boundary::{ErrorBoundary, install_panic_fallback};
+pub use error_page::{LoadError, NotFound};
+#[cfg(feature = "admin")]
+pub use feature_flags::FeatureFlags;
+#[cfg(feature = "admin")]
+pub use flagged_content::FlaggedContent;
+pub use inbox::Inbox;
+pub use install_prompt::InstallPrompt;
+pub use language_switcher::LanguageSwitcher;
+pub use link_previews::LinkPreviews;
 pub use login_form::LoginForm;
+pub use main_nav::MainNav;
+pub use markdown_content::MarkdownContent;
+#[cfg(feature = "admin")]
+pub use moderation_queue::ModerationQueue;
+#[cfg(feature = "admin")]
+pub use moderation_rules::ModerationRules;
+pub use newsletter_signup::NewsletterSignup;
+pub use notification_bell::NotificationBell;
+pub use offline_banner::OfflineBanner;
+pub use org_page::OrgPage;
 pub use post_card::PostCard;
+pub use post_detail::PostDetail;
+#[cfg(feature = "editor")]
 pub use post_form::PostForm;
 pub use post_list::PostList;
+pub use preferences_settings::PreferencesSettings;
+pub use profile_settings::ProfileSettings;
+pub use reader_view::ReaderView;
+pub use recover_form::RecoverForm;
+pub use recovery_codes::{RecoveryCodeList, RecoveryCodes};
 pub use register_form::RegisterForm;
+#[cfg(feature = "editor")]
+pub use revision_history::RevisionHistory;
+pub use search_bar::SearchBar;
+pub use search_results::SearchResults;
+pub use session_list::SessionList;
+pub use settings_page::SettingsPage;
+pub use share_menu::ShareMenu;
+#[cfg(feature = "admin")]
+pub use site_settings_editor::SiteSettingsEditor;
+#[cfg(feature = "editor")]
+pub use skeleton::PostFormSkeleton;
+pub use skeleton::{PostCardSkeletonList, SettingsSkeleton};
+pub use tag_chips::TagChips;
+pub use tag_cloud::TagCloud;
+pub use toast::{Toast, use_toast};
+pub use trash_page::TrashPage;
+pub use user_avatar::UserAvatar;
+#[cfg(feature = "admin")]
+pub use user_table::UserTable;
@@ -0,0 +1,233 @@
+//! Account settings section (email and password changes).
+
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use blog_shared::constants::MIN_PASSWORD_LENGTH;
+use blog_shared::{ChangeEmailRequest, ChangePasswordRequest};
+
+use crate::api;
+
+/// Account settings properties.
+#[derive(Properties, PartialEq)]
+pub struct AccountSettingsProps {
+    /// Current email address.
+    pub email: String,
+    /// Callback after the password changed (other sessions were revoked).
+    #[prop_or_default]
+    pub on_password_changed: Callback<()>,
+}
+
+/// Result message of one of the account forms.
+#[derive(Clone, PartialEq)]
+enum Outcome {
+    Saved(String),
+    Failed(String),
+}
+
+/// Creates an input handler that stores the field value in `state`.
+fn bind(state: &UseStateHandle<String>) -> Callback<InputEvent> {
+    let state = state.clone();
+    Callback::from(move |e: InputEvent| {
+        let input: HtmlInputElement = e.target_unchecked_into();
+        state.set(input.value());
+    })
+}
+
+/// Renders a form outcome message.
+fn outcome_message(outcome: &Option<Outcome>) -> Html {
+    match outcome {
+        Some(Outcome::Saved(text)) => {
+            html! { <div class="message message-success" role="status">{text}</div> }
+        }
+        Some(Outcome::Failed(err)) => {
+            html! { <div class="message message-error" role="alert">{err}</div> }
+        }
+        None => html! {},
+    }
+}
+
+/// Account settings section.
+#[function_component(AccountSettings)]
+pub fn account_settings(props: &AccountSettingsProps) -> Html {
+    let new_email = use_state(String::new);
+    let email_password = use_state(String::new);
+    let email_outcome = use_state(|| None::<Outcome>);
+
+    let current_password = use_state(String::new);
+    let new_password = use_state(String::new);
+    let confirm_password = use_state(String::new);
+    let password_outcome = use_state(|| None::<Outcome>);
+
+    let loading = use_state(|| false);
+
+    let on_email_submit = {
+        let new_email = new_email.clone();
+        let email_password = email_password.clone();
+        let email_outcome = email_outcome.clone();
+        let loading = loading.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let req = ChangeEmailRequest {
+                new_email: new_email.trim().to_string(),
+                current_password: (*email_password).clone(),
+            };
+            let new_email = new_email.clone();
+            let email_password = email_password.clone();
+            let email_outcome = email_outcome.clone();
+            let loading = loading.clone();
+
+            loading.set(true);
+            email_outcome.set(None);
+            spawn_local(async move {
+                match api::change_email(req).await {
+                    Ok(pending) => {
+                        new_email.set(String::new());
+                        email_password.set(String::new());
+                        email_outcome.set(Some(Outcome::Saved(format!(
+                            "Check {} for a confirmation link; your email changes once you open it.",
+                            pending.new_email
+                        ))));
+                    }
+                    Err(e) => email_outcome.set(Some(Outcome::Failed(e.message))),
+                }
+                loading.set(false);
+            });
+        })
+    };
+
+    let on_password_submit = {
+        let current_password = current_password.clone();
+        let new_password = new_password.clone();
+        let confirm_password = confirm_password.clone();
+        let password_outcome = password_outcome.clone();
+        let loading = loading.clone();
+        let on_password_changed = props.on_password_changed.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            if new_password.chars().count() < MIN_PASSWORD_LENGTH {
+                password_outcome.set(Some(Outcome::Failed(format!(
+                    "Password must be at least {} characters",
+                    MIN_PASSWORD_LENGTH
+                ))));
+                return;
+            }
+            if *new_password != *confirm_password {
+                password_outcome.set(Some(Outcome::Failed("Passwords do not match".into())));
+                return;
+            }
+            let req = ChangePasswordRequest {
+                current_password: (*current_password).clone(),
+                new_password: (*new_password).clone(),
+            };
+            let current_password = current_password.clone();
+            let new_password = new_password.clone();
+            let confirm_password = confirm_password.clone();
+            let password_outcome = password_outcome.clone();
+            let loading = loading.clone();
+            let on_password_changed = on_password_changed.clone();
+
+            loading.set(true);
+            password_outcome.set(None);
+            spawn_local(async move {
+                match api::change_password(req).await {
+                    Ok(()) => {
+                        current_password.set(String::new());
+                        new_password.set(String::new());
+                        confirm_password.set(String::new());
+                        password_outcome.set(Some(Outcome::Saved(
+                            "Password changed. Other devices were signed out.".into(),
+                        )));
+                        on_password_changed.emit(());
+                    }
+                    Err(e) => password_outcome.set(Some(Outcome::Failed(e.message))),
+                }
+                loading.set(false);
+            });
+        })
+    };
+
+    html! {
+        <section class="settings-section" aria-labelledby="settings-account-title">
+            <h3 id="settings-account-title">{"Account"}</h3>
+
+            <form onsubmit={on_email_submit} aria-label="Change email" aria-busy={loading.to_string()}>
+                <h4>{"Email"}</h4>
+                <p class="form-hint">{"Current: "}<strong>{props.email.clone()}</strong></p>
+                {outcome_message(&email_outcome)}
+                <div class="form-group">
+                    <label for="settings-new-email">{"New email"}</label>
+                    <input
+                        type="email"
+                        id="settings-new-email"
+                        value={(*new_email).clone()}
+                        oninput={bind(&new_email)}
+                        disabled={*loading}
+                        required=true
+                    />
+                </div>
+                <div class="form-group">
+                    <label for="settings-email-password">{"Current password"}</label>
+                    <input
+                        type="password"
+                        id="settings-email-password"
+                        value={(*email_password).clone()}
+                        oninput={bind(&email_password)}
+                        autocomplete="current-password"
+                        disabled={*loading}
+                        required=true
+                    />
+                </div>
+                <button type="submit" class="btn btn-primary" disabled={*loading}>
+                    {"Change email"}
+                </button>
+            </form>
+
+            <form onsubmit={on_password_submit} aria-label="Change password" aria-busy={loading.to_string()}>
+                <h4>{"Password"}</h4>
+                {outcome_message(&password_outcome)}
+                <div class="form-group">
+                    <label for="settings-current-password">{"Current password"}</label>
+                    <input
+                        type="password"
+                        id="settings-current-password"
+                        value={(*current_password).clone()}
+                        oninput={bind(&current_password)}
+                        autocomplete="current-password"
+                        disabled={*loading}
+                        required=true
+                    />
+                </div>
+                <div class="form-group">
+                    <label for="settings-new-password">{"New password"}</label>
+                    <input
+                        type="password"
+                        id="settings-new-password"
+                        value={(*new_password).clone()}
+                        oninput={bind(&new_password)}
+                        autocomplete="new-password"
+                        minlength={MIN_PASSWORD_LENGTH.to_string()}
+                        disabled={*loading}
+                        required=true
+                    />
+                </div>
+                <div class="form-group">
+                    <label for="settings-confirm-password">{"Confirm new password"}</label>
+                    <input
+                        type="password"
+                        id="settings-confirm-password"
+                        value={(*confirm_password).clone()}
+                        oninput={bind(&confirm_password)}
+                        autocomplete="new-password"
+                        disabled={*loading}
+                        required=true
+                    />
+                </div>
+                <button type="submit" class="btn btn-primary" disabled={*loading}>
+                    {"Change password"}
+                </button>
+            </form>
+        </section>
+    }
+}
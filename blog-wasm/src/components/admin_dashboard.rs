@@ -0,0 +1,56 @@
+//! Admin dashboard page: stats, moderation queues, users, rules, feature
+//! flags, site settings, and the announcement banner.
+
+use yew::prelude::*;
+
+use blog_shared::Role;
+
+use crate::components::{
+    AdminStats, AnnouncementEditor, FeatureFlags, FlaggedContent, ModerationQueue, ModerationRules,
+    SiteSettingsEditor, UserTable,
+};
+
+/// Admin dashboard properties.
+#[derive(Properties, PartialEq)]
+pub struct AdminDashboardProps {
+    /// Role of the current user (from `/auth/me`).
+    pub role: Role,
+    /// Current user's ID (their own row in the user table is read-only).
+    pub current_user_id: Option<i64>,
+    /// Callback to open a queued post or the post a queued comment belongs to.
+    pub on_open_post: Callback<String>,
+}
+
+/// Admin dashboard component.
+///
+/// Moderators only see the moderation queues; the server enforces the same
+/// split, so this guard is for presentation only.
+#[function_component(AdminDashboard)]
+pub fn admin_dashboard(props: &AdminDashboardProps) -> Html {
+    if !props.role.can_moderate() {
+        return html! {
+            <div class="message message-error" role="alert">
+                {"You do not have access to the admin dashboard."}
+            </div>
+        };
+    }
+    let is_admin = props.role == Role::Admin;
+
+    html! {
+        <div class="admin-dashboard">
+            <h2>{"Admin Dashboard"}</h2>
+            if is_admin {
+                <AdminStats />
+            }
+            <FlaggedContent on_open_post={props.on_open_post.clone()} />
+            <ModerationQueue on_open_post={props.on_open_post.clone()} />
+            if is_admin {
+                <UserTable current_user_id={props.current_user_id} />
+                <ModerationRules />
+                <FeatureFlags />
+                <SiteSettingsEditor />
+                <AnnouncementEditor />
+            }
+        </div>
+    }
+}
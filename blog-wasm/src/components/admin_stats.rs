@@ -0,0 +1,64 @@
+//! Admin stats section: site-wide counters.
+
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use blog_shared::AdminStatsDto;
+
+use crate::api;
+use crate::components::ErrorBoundary;
+
+/// Admin stats component.
+#[function_component(AdminStats)]
+pub fn admin_stats() -> Html {
+    let stats = use_state(|| None::<AdminStatsDto>);
+    let load_error = use_state(|| None::<String>);
+    let reload = use_state(|| 0u32);
+
+    {
+        let stats = stats.clone();
+        let load_error = load_error.clone();
+        use_effect_with(*reload, move |_| {
+            load_error.set(None);
+            spawn_local(async move {
+                match api::get_admin_stats().await {
+                    Ok(loaded) => stats.set(Some(loaded)),
+                    Err(e) => load_error.set(Some(format!("Failed to load stats: {}", e.message))),
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_retry = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(*reload + 1))
+    };
+
+    let tiles = stats.as_ref().map(|s| {
+        [
+            ("Users", s.users),
+            ("Suspended", s.suspended_users),
+            ("Posts", s.posts),
+            ("Comments", s.comments),
+            ("Tags", s.tags),
+            ("Active sessions", s.active_sessions),
+        ]
+    });
+
+    html! {
+        <section class="admin-section" aria-labelledby="admin-stats-title">
+            <h3 id="admin-stats-title">{"Overview"}</h3>
+            <ErrorBoundary error={(*load_error).clone()} {on_retry}>
+                <dl class="admin-stats" aria-busy={tiles.is_none().to_string()}>
+                    {for tiles.into_iter().flatten().map(|(label, value)| html! {
+                        <div class="admin-stat">
+                            <dt>{label}</dt>
+                            <dd>{value}</dd>
+                        </div>
+                    })}
+                </dl>
+            </ErrorBoundary>
+        </section>
+    }
+}
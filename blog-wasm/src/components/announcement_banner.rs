@@ -0,0 +1,110 @@
+//! Site announcement banner shown above the app.
+
+use chrono::{DateTime, Utc};
+use gloo_storage::{LocalStorage, Storage};
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use blog_shared::{AnnouncementDto, AnnouncementSeverity};
+
+use crate::api;
+use crate::constants::{ANNOUNCEMENT_DISMISSED_STORAGE_KEY, ANNOUNCEMENT_STORAGE_KEY};
+
+/// Announcement banner properties.
+#[derive(Properties, PartialEq)]
+pub struct AnnouncementBannerProps {
+    /// Current user's ID; dismissals are remembered per user.
+    pub user_id: Option<i64>,
+}
+
+/// Announcement banner component.
+///
+/// Shows the cached announcement straight away, then refreshes it from the
+/// server. Dismissing hides that announcement for the current user until an
+/// admin changes it.
+#[function_component(AnnouncementBanner)]
+pub fn announcement_banner(props: &AnnouncementBannerProps) -> Html {
+    let announcement =
+        use_state(|| LocalStorage::get::<AnnouncementDto>(ANNOUNCEMENT_STORAGE_KEY).ok());
+    let dismissed = use_state(|| dismissed_version(props.user_id));
+
+    {
+        let announcement = announcement.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                // Keep showing the cached banner if the server is unreachable
+                if let Ok(response) = api::get_announcement().await {
+                    match response.announcement {
+                        Some(ref current) => {
+                            let _ = LocalStorage::set(ANNOUNCEMENT_STORAGE_KEY, current);
+                        }
+                        None => LocalStorage::delete(ANNOUNCEMENT_STORAGE_KEY),
+                    }
+                    announcement.set(response.announcement);
+                }
+            });
+            || ()
+        });
+    }
+
+    // Signing in or out switches to that user's dismissal
+    {
+        let dismissed = dismissed.clone();
+        use_effect_with(props.user_id, move |user_id| {
+            dismissed.set(dismissed_version(*user_id));
+            || ()
+        });
+    }
+
+    let on_dismiss = {
+        let dismissed = dismissed.clone();
+        let user_id = props.user_id;
+        let updated_at = announcement.as_ref().map(|a| a.updated_at);
+        Callback::from(move |_: MouseEvent| {
+            if let Some(updated_at) = updated_at {
+                let _ = LocalStorage::set(dismissed_key(user_id), updated_at);
+                dismissed.set(Some(updated_at));
+            }
+        })
+    };
+
+    let Some(current) = announcement
+        .as_ref()
+        .filter(|a| a.is_active(Utc::now()) && *dismissed != Some(a.updated_at))
+    else {
+        return html! {};
+    };
+    let role = match current.severity {
+        AnnouncementSeverity::Info => "status",
+        AnnouncementSeverity::Warning | AnnouncementSeverity::Critical => "alert",
+    };
+
+    html! {
+        <div
+            class={classes!("announcement-banner", format!("announcement-{}", current.severity))}
+            {role}
+        >
+            <span>{&current.text}</span>
+            <button
+                class="btn btn-secondary btn-sm"
+                onclick={on_dismiss}
+                aria-label="Dismiss announcement"
+            >
+                {"Dismiss"}
+            </button>
+        </div>
+    }
+}
+
+/// Returns the localStorage key of a user's dismissal.
+fn dismissed_key(user_id: Option<i64>) -> String {
+    match user_id {
+        Some(id) => format!("{ANNOUNCEMENT_DISMISSED_STORAGE_KEY}:{id}"),
+        None => format!("{ANNOUNCEMENT_DISMISSED_STORAGE_KEY}:guest"),
+    }
+}
+
+/// Returns the version (`updated_at`) of the announcement the user dismissed.
+fn dismissed_version(user_id: Option<i64>) -> Option<DateTime<Utc>> {
+    LocalStorage::get::<DateTime<Utc>>(dismissed_key(user_id)).ok()
+}
@@ -0,0 +1,200 @@
+//! Announcement banner editor for admins.
+
+use chrono::{Duration, Utc};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+use yew::prelude::*;
+
+use blog_shared::constants::MAX_ANNOUNCEMENT_LENGTH;
+use blog_shared::{AnnouncementDto, AnnouncementSeverity, SetAnnouncementRequest};
+
+use crate::api;
+
+/// Severities offered in the form, with their labels.
+const SEVERITIES: &[(AnnouncementSeverity, &str)] = &[
+    (AnnouncementSeverity::Info, "Info"),
+    (AnnouncementSeverity::Warning, "Warning"),
+    (AnnouncementSeverity::Critical, "Critical"),
+];
+
+/// Announcement editor component.
+///
+/// Saving replaces the current banner, which shows it again to users who
+/// dismissed the previous one.
+#[function_component(AnnouncementEditor)]
+pub fn announcement_editor() -> Html {
+    let current = use_state(|| None::<AnnouncementDto>);
+    let text = use_state(String::new);
+    let severity = use_state(AnnouncementSeverity::default);
+    // Hours until expiry; empty for none
+    let expires_in = use_state(String::new);
+    let error = use_state(|| None::<String>);
+    let saving = use_state(|| false);
+
+    {
+        let current = current.clone();
+        let text = text.clone();
+        let severity = severity.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                if let Ok(response) = api::get_announcement().await {
+                    if let Some(ref announcement) = response.announcement {
+                        text.set(announcement.text.clone());
+                        severity.set(announcement.severity);
+                    }
+                    current.set(response.announcement);
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_text_input = {
+        let text = text.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlTextAreaElement = e.target_unchecked_into();
+            text.set(input.value());
+        })
+    };
+
+    let on_severity_change = {
+        let severity = severity.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Ok(value) = select.value().parse() {
+                severity.set(value);
+            }
+        })
+    };
+
+    let on_expires_input = {
+        let expires_in = expires_in.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            expires_in.set(input.value());
+        })
+    };
+
+    let onsubmit = {
+        let current = current.clone();
+        let text = text.clone();
+        let severity = severity.clone();
+        let expires_in = expires_in.clone();
+        let error = error.clone();
+        let saving = saving.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let expires_at = match expires_in.trim() {
+                "" => None,
+                hours => match hours.parse::<i64>() {
+                    Ok(hours) if hours > 0 => Some(Utc::now() + Duration::hours(hours)),
+                    _ => {
+                        error.set(Some("Expiry must be a whole number of hours".to_string()));
+                        return;
+                    }
+                },
+            };
+            let req = SetAnnouncementRequest {
+                text: (*text).clone(),
+                severity: *severity,
+                expires_at,
+            };
+            let current = current.clone();
+            let error = error.clone();
+            let saving = saving.clone();
+
+            saving.set(true);
+            error.set(None);
+            spawn_local(async move {
+                match api::set_announcement(req).await {
+                    Ok(announcement) => current.set(Some(announcement)),
+                    Err(e) => error.set(Some(e.message)),
+                }
+                saving.set(false);
+            });
+        })
+    };
+
+    let on_clear = {
+        let current = current.clone();
+        let text = text.clone();
+        let error = error.clone();
+        Callback::from(move |_: MouseEvent| {
+            let current = current.clone();
+            let text = text.clone();
+            let error = error.clone();
+            error.set(None);
+            spawn_local(async move {
+                match api::clear_announcement().await {
+                    Ok(()) => {
+                        current.set(None);
+                        text.set(String::new());
+                    }
+                    Err(e) => error.set(Some(e.message)),
+                }
+            });
+        })
+    };
+
+    let status = match *current {
+        Some(ref announcement) => match announcement.expires_at {
+            Some(expires_at) => {
+                format!("Showing until {}.", expires_at.format("%Y-%m-%d %H:%M UTC"))
+            }
+            None => "Showing until cleared.".to_string(),
+        },
+        None => "No announcement is showing.".to_string(),
+    };
+
+    html! {
+        <section class="admin-section" aria-labelledby="admin-announcement-title">
+            <h3 id="admin-announcement-title">{"Announcement banner"}</h3>
+            <p class="form-hint">{status}</p>
+            if let Some(ref err) = *error {
+                <div class="message message-error" role="alert">{err}</div>
+            }
+            <form class="announcement-form" {onsubmit} aria-busy={saving.to_string()}>
+                <div class="form-group">
+                    <label for="announcement-text">{"Text"}</label>
+                    <textarea
+                        id="announcement-text"
+                        rows="2"
+                        maxlength={MAX_ANNOUNCEMENT_LENGTH.to_string()}
+                        value={(*text).clone()}
+                        oninput={on_text_input}
+                        required=true
+                    />
+                </div>
+                <div class="form-group">
+                    <label for="announcement-severity">{"Severity"}</label>
+                    <select id="announcement-severity" onchange={on_severity_change}>
+                        {for SEVERITIES.iter().map(|(value, label)| html! {
+                            <option value={value.as_str()} selected={*severity == *value}>{*label}</option>
+                        })}
+                    </select>
+                </div>
+                <div class="form-group">
+                    <label for="announcement-expires">{"Expires in (hours)"}</label>
+                    <input
+                        id="announcement-expires"
+                        type="number"
+                        min="1"
+                        value={(*expires_in).clone()}
+                        oninput={on_expires_input}
+                    />
+                    <p class="form-hint">{"Leave empty to show it until cleared."}</p>
+                </div>
+                <div class="announcement-form-actions">
+                    <button type="submit" class="btn btn-primary" disabled={*saving}>
+                        {"Publish"}
+                    </button>
+                    if current.is_some() {
+                        <button type="button" class="btn btn-secondary" onclick={on_clear}>
+                            {"Clear"}
+                        </button>
+                    }
+                </div>
+            </form>
+        </section>
+    }
+}
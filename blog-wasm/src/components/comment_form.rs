@@ -0,0 +1,65 @@
+//! Comment form component for writing a new comment.
+
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
+
+use blog_shared::constants::MAX_COMMENT_LENGTH;
+
+/// Comment form properties.
+#[derive(Properties, PartialEq)]
+pub struct CommentFormProps {
+    /// Callback with the comment text when submitted.
+    pub on_submit: Callback<String>,
+}
+
+/// Comment form component.
+///
+/// Clears itself right after submitting; the parent shows the comment
+/// optimistically while it is being saved.
+#[function_component(CommentForm)]
+pub fn comment_form(props: &CommentFormProps) -> Html {
+    let content = use_state(String::new);
+
+    let on_input = {
+        let content = content.clone();
+        Callback::from(move |e: InputEvent| {
+            let target: HtmlTextAreaElement = e.target_unchecked_into();
+            content.set(target.value());
+        })
+    };
+
+    let onsubmit = {
+        let content = content.clone();
+        let on_submit = props.on_submit.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let text = content.trim().to_string();
+            if text.is_empty() {
+                return;
+            }
+            on_submit.emit(text);
+            content.set(String::new());
+        })
+    };
+
+    let remaining = MAX_COMMENT_LENGTH.saturating_sub(content.chars().count());
+
+    html! {
+        <form class="comment-form" {onsubmit} aria-label="Add a comment">
+            <textarea
+                value={(*content).clone()}
+                oninput={on_input}
+                maxlength={MAX_COMMENT_LENGTH.to_string()}
+                placeholder="Write a comment..."
+                rows="3"
+                aria-label="Comment text"
+            />
+            <div class="comment-form-footer">
+                <span class="comment-form-remaining">{format!("{} characters left", remaining)}</span>
+                <button type="submit" class="btn btn-primary btn-sm" disabled={content.trim().is_empty()}>
+                    {"Comment"}
+                </button>
+            </div>
+        </form>
+    }
+}
@@ -0,0 +1,169 @@
+//! Comment item component with inline editing.
+
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
+
+use blog_shared::CommentDto;
+use blog_shared::constants::MAX_COMMENT_LENGTH;
+
+use crate::components::{ConfirmDialog, UserAvatar};
+
+/// Comment item properties.
+#[derive(Properties, PartialEq)]
+pub struct CommentItemProps {
+    /// The comment to display.
+    pub comment: CommentDto,
+    /// Whether the current user wrote this comment (may edit and delete).
+    #[prop_or_default]
+    pub is_owner: bool,
+    /// Whether the current user may delete this comment.
+    #[prop_or_default]
+    pub can_delete: bool,
+    /// Whether the comment is still being saved.
+    #[prop_or_default]
+    pub pending: bool,
+    /// Callback with `(comment_id, new_content)` when an edit is saved.
+    pub on_update: Callback<(i64, String)>,
+    /// Callback with the comment ID when delete is clicked.
+    pub on_delete: Callback<i64>,
+}
+
+/// Comment item component.
+#[function_component(CommentItem)]
+pub fn comment_item(props: &CommentItemProps) -> Html {
+    let comment = &props.comment;
+    let editing = use_state(|| false);
+    let draft = use_state(String::new);
+    let confirming = use_state(|| false);
+
+    let on_edit_click = {
+        let editing = editing.clone();
+        let draft = draft.clone();
+        let content = comment.content.clone();
+        Callback::from(move |_: MouseEvent| {
+            draft.set(content.clone());
+            editing.set(true);
+        })
+    };
+
+    let on_cancel_click = {
+        let editing = editing.clone();
+        Callback::from(move |_: MouseEvent| editing.set(false))
+    };
+
+    let on_draft_change = {
+        let draft = draft.clone();
+        Callback::from(move |e: InputEvent| {
+            let target: HtmlTextAreaElement = e.target_unchecked_into();
+            draft.set(target.value());
+        })
+    };
+
+    let on_save = {
+        let editing = editing.clone();
+        let draft = draft.clone();
+        let id = comment.id;
+        let on_update = props.on_update.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let content = draft.trim().to_string();
+            if content.is_empty() {
+                return;
+            }
+            on_update.emit((id, content));
+            editing.set(false);
+        })
+    };
+
+    let on_delete_click = {
+        let confirming = confirming.clone();
+        Callback::from(move |_: MouseEvent| confirming.set(true))
+    };
+
+    let on_delete_confirm = {
+        let id = comment.id;
+        let on_delete = props.on_delete.clone();
+        let confirming = confirming.clone();
+        Callback::from(move |_| {
+            confirming.set(false);
+            on_delete.emit(id);
+        })
+    };
+
+    let on_delete_cancel = {
+        let confirming = confirming.clone();
+        Callback::from(move |_| confirming.set(false))
+    };
+
+    let formatted_date = comment.created_at.format("%B %d, %Y %H:%M").to_string();
+    let edited = comment.updated_at != comment.created_at;
+
+    html! {
+        <li class={classes!("comment-item", props.pending.then_some("comment-item-pending"))}>
+            <div class="comment-meta">
+                <span class="comment-author">
+                    <UserAvatar user_id={comment.author_public_id.clone()} username={comment.author_username.clone()} />
+                    {&comment.author_username}
+                </span>
+                <span class="comment-date">
+                    if props.pending {
+                        {"Posting..."}
+                    } else {
+                        {formatted_date}
+                        if edited {
+                            {" (edited)"}
+                        }
+                    }
+                </span>
+            </div>
+            if *editing {
+                <form class="comment-edit" onsubmit={on_save} aria-label="Edit comment">
+                    <textarea
+                        value={(*draft).clone()}
+                        oninput={on_draft_change}
+                        maxlength={MAX_COMMENT_LENGTH.to_string()}
+                        rows="3"
+                        aria-label="Comment text"
+                    />
+                    <div class="comment-actions">
+                        <button type="submit" class="btn btn-primary btn-sm">{"Save"}</button>
+                        <button type="button" class="btn btn-secondary btn-sm" onclick={on_cancel_click}>
+                            {"Cancel"}
+                        </button>
+                    </div>
+                </form>
+            } else {
+                <p class="comment-content">{&comment.content}</p>
+                if !props.pending && (props.is_owner || props.can_delete) {
+                    <div class="comment-actions">
+                        if props.is_owner {
+                            <button
+                                type="button"
+                                class="btn btn-link btn-sm"
+                                onclick={on_edit_click}
+                                aria-label="Edit comment"
+                            >
+                                {"Edit"}
+                            </button>
+                        }
+                        <button
+                            type="button"
+                            class="btn btn-link btn-sm"
+                            onclick={on_delete_click}
+                            aria-label="Delete comment"
+                        >
+                            {"Delete"}
+                        </button>
+                    </div>
+                    <ConfirmDialog
+                        open={*confirming}
+                        title="Delete comment?"
+                        message="This comment will be deleted permanently."
+                        on_confirm={on_delete_confirm}
+                        on_cancel={on_delete_cancel}
+                    />
+                }
+            }
+        </li>
+    }
+}
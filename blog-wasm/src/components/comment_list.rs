@@ -0,0 +1,289 @@
+//! Comment list component: collapsible comment thread for a post.
+
+use std::rc::Rc;
+
+use chrono::Utc;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use blog_shared::{CommentDto, CommentListResponse, CreateCommentRequest, UpdateCommentRequest};
+
+use crate::api;
+use crate::components::{CommentForm, CommentItem, ErrorBoundary};
+use crate::constants::COMMENT_PAGE_SIZE;
+
+/// Comment list properties.
+#[derive(Properties, PartialEq)]
+pub struct CommentListProps {
+    /// Public ID of the post whose comments are shown.
+    pub post_id: String,
+    /// Public ID of the post's author (may delete any comment on it).
+    pub post_author_id: String,
+    /// Current user's public ID (if authenticated).
+    #[prop_or_default]
+    pub current_user_id: Option<String>,
+    /// Current user's name, shown on optimistic comments.
+    #[prop_or_default]
+    pub current_username: Option<String>,
+}
+
+/// Loaded comments plus optimistic (not yet saved) ones.
+///
+/// Optimistic comments use negative temporary IDs until the server confirms.
+#[derive(Default, PartialEq)]
+struct CommentsState {
+    comments: Vec<CommentDto>,
+    total: i64,
+    next_temp_id: i64,
+}
+
+/// State transitions for [`CommentsState`].
+enum CommentsAction {
+    /// First page loaded.
+    Loaded(CommentListResponse),
+    /// Further page loaded.
+    LoadedMore(CommentListResponse),
+    /// Comment shown before the server confirms it.
+    AddPending(CommentDto),
+    /// Server result replacing a comment (optimistic or edited).
+    Replace { id: i64, comment: CommentDto },
+    /// Comment removed (deleted, or failed optimistic post).
+    Remove(i64),
+}
+
+impl Reducible for CommentsState {
+    type Action = CommentsAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut state = CommentsState {
+            comments: self.comments.clone(),
+            total: self.total,
+            next_temp_id: self.next_temp_id,
+        };
+        match action {
+            CommentsAction::Loaded(page) => {
+                state.comments = page.comments;
+                state.total = page.total.unwrap_or(state.comments.len() as i64);
+            }
+            CommentsAction::LoadedMore(page) => {
+                state.comments.extend(page.comments);
+                if let Some(total) = page.total {
+                    state.total = total;
+                }
+            }
+            CommentsAction::AddPending(comment) => {
+                state.comments.push(comment);
+                state.total += 1;
+                state.next_temp_id -= 1;
+            }
+            CommentsAction::Replace { id, comment } => {
+                if let Some(slot) = state.comments.iter_mut().find(|c| c.id == id) {
+                    *slot = comment;
+                }
+            }
+            CommentsAction::Remove(id) => {
+                let before = state.comments.len();
+                state.comments.retain(|c| c.id != id);
+                if state.comments.len() < before {
+                    state.total -= 1;
+                }
+            }
+        }
+        Rc::new(state)
+    }
+}
+
+/// Comment list component, collapsed by default.
+#[function_component(CommentList)]
+pub fn comment_list(props: &CommentListProps) -> Html {
+    let post_id = props.post_id.clone();
+    let state = use_reducer(|| CommentsState {
+        next_temp_id: -1,
+        ..CommentsState::default()
+    });
+    let expanded = use_state(|| false);
+    let loading = use_state(|| true);
+    let load_error = use_state(|| None::<String>);
+    let error = use_state(|| None::<String>);
+    let reload = use_state(|| 0u32);
+
+    // Load the first page (for the count) on mount and on retry
+    {
+        let state = state.clone();
+        let loading = loading.clone();
+        let load_error = load_error.clone();
+        use_effect_with((post_id.clone(), *reload), move |(post_id, _)| {
+            let post_id = post_id.clone();
+            loading.set(true);
+            load_error.set(None);
+            spawn_local(async move {
+                match api::list_comments(&post_id, COMMENT_PAGE_SIZE, 0).await {
+                    Ok(page) => state.dispatch(CommentsAction::Loaded(page)),
+                    Err(e) => load_error.set(Some(e.message)),
+                }
+                loading.set(false);
+            });
+            || ()
+        });
+    }
+
+    let on_toggle = {
+        let expanded = expanded.clone();
+        Callback::from(move |_: MouseEvent| expanded.set(!*expanded))
+    };
+
+    let on_retry = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(*reload + 1))
+    };
+
+    let on_load_more = {
+        let state = state.clone();
+        let error = error.clone();
+        let post_id = post_id.clone();
+        Callback::from(move |_: MouseEvent| {
+            let state = state.clone();
+            let error = error.clone();
+            let post_id = post_id.clone();
+            // Skip optimistic entries when computing the server offset
+            let offset = state.comments.iter().filter(|c| c.id > 0).count() as i64;
+            spawn_local(async move {
+                match api::list_comments(&post_id, COMMENT_PAGE_SIZE, offset).await {
+                    Ok(page) => state.dispatch(CommentsAction::LoadedMore(page)),
+                    Err(e) => error.set(Some(e.message)),
+                }
+            });
+        })
+    };
+
+    let on_submit = {
+        let state = state.clone();
+        let error = error.clone();
+        let author_id = props.current_user_id.clone().unwrap_or_default();
+        let author_username = props.current_username.clone().unwrap_or_default();
+        Callback::from(move |content: String| {
+            let temp_id = state.next_temp_id;
+            let now = Utc::now();
+            let mut pending = CommentDto::new(
+                temp_id,
+                0,
+                0,
+                author_username.clone(),
+                content.clone(),
+                now,
+                now,
+            );
+            pending.post_public_id = post_id.clone();
+            pending.author_public_id = author_id.clone();
+            state.dispatch(CommentsAction::AddPending(pending));
+            error.set(None);
+
+            let state = state.clone();
+            let error = error.clone();
+            let post_id = post_id.clone();
+            spawn_local(async move {
+                match api::create_comment(&post_id, CreateCommentRequest { content }).await {
+                    Ok(comment) => state.dispatch(CommentsAction::Replace {
+                        id: temp_id,
+                        comment,
+                    }),
+                    Err(e) => {
+                        state.dispatch(CommentsAction::Remove(temp_id));
+                        error.set(Some(format!("Comment not posted: {}", e.message)));
+                    }
+                }
+            });
+        })
+    };
+
+    let on_update = {
+        let state = state.clone();
+        let error = error.clone();
+        Callback::from(move |(id, content): (i64, String)| {
+            let state = state.clone();
+            let error = error.clone();
+            spawn_local(async move {
+                match api::update_comment(id, UpdateCommentRequest { content }).await {
+                    Ok(comment) => state.dispatch(CommentsAction::Replace { id, comment }),
+                    Err(e) => error.set(Some(e.message)),
+                }
+            });
+        })
+    };
+
+    let on_delete = {
+        let state = state.clone();
+        let error = error.clone();
+        Callback::from(move |id: i64| {
+            let state = state.clone();
+            let error = error.clone();
+            spawn_local(async move {
+                match api::delete_comment(id).await {
+                    Ok(()) => state.dispatch(CommentsAction::Remove(id)),
+                    Err(e) => error.set(Some(e.message)),
+                }
+            });
+        })
+    };
+
+    let toggle_label = if *loading {
+        "Comments".to_string()
+    } else if *expanded {
+        "Hide comments".to_string()
+    } else {
+        format!("Show comments ({})", state.total)
+    };
+    let has_more = (state.comments.len() as i64) < state.total;
+
+    html! {
+        <section class="comments" aria-label="Comments">
+            <button
+                type="button"
+                class="btn btn-secondary btn-sm"
+                onclick={on_toggle}
+                aria-expanded={expanded.to_string()}
+            >
+                {toggle_label}
+            </button>
+            if *expanded {
+                <ErrorBoundary error={(*load_error).clone()} {on_retry}>
+                    if let Some(ref err) = *error {
+                        <div class="message message-error" role="alert">{err}</div>
+                    }
+                    if !*loading && state.comments.is_empty() {
+                        <p class="comments-empty">{"No comments yet."}</p>
+                    }
+                    <ul class="comment-list">
+                        {for state.comments.iter().map(|comment| {
+                            let is_owner = comment.id > 0
+                                && props.current_user_id.as_ref() == Some(&comment.author_public_id);
+                            let can_delete = is_owner
+                                || props.current_user_id.as_ref() == Some(&props.post_author_id);
+                            html! {
+                                <CommentItem
+                                    key={comment.id}
+                                    comment={comment.clone()}
+                                    {is_owner}
+                                    {can_delete}
+                                    pending={comment.id < 0}
+                                    on_update={on_update.clone()}
+                                    on_delete={on_delete.clone()}
+                                />
+                            }
+                        })}
+                    </ul>
+                    if has_more {
+                        <button type="button" class="btn btn-link" onclick={on_load_more}>
+                            {"Load more comments"}
+                        </button>
+                    }
+                    if props.current_user_id.is_some() {
+                        <CommentForm {on_submit} />
+                    } else {
+                        <p class="comments-login-hint">{"Log in to join the discussion."}</p>
+                    }
+                </ErrorBoundary>
+            }
+        </section>
+    }
+}
@@ -0,0 +1,173 @@
+//! Confirmation dialog: a styled, keyboard accessible `window.confirm`.
+
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement, KeyboardEvent, window};
+use yew::prelude::*;
+
+/// Elements that take part in the dialog's focus trap.
+const FOCUSABLE: &str = "button:not([disabled]), input:not([disabled]), select, textarea, a[href]";
+
+/// Confirm dialog properties.
+#[derive(Properties, PartialEq)]
+pub struct ConfirmDialogProps {
+    /// Whether the dialog is shown.
+    pub open: bool,
+    /// Dialog heading.
+    pub title: AttrValue,
+    /// What confirming will do.
+    pub message: AttrValue,
+    /// Confirm button label.
+    #[prop_or(AttrValue::Static("Delete"))]
+    pub confirm_label: AttrValue,
+    /// Whether the confirm button is disabled (e.g. until a field is filled).
+    #[prop_or_default]
+    pub confirm_disabled: bool,
+    /// Extra content between the message and the buttons (e.g. a password field).
+    #[prop_or_default]
+    pub children: Html,
+    /// Callback when the action is confirmed.
+    pub on_confirm: Callback<()>,
+    /// Callback when the dialog is dismissed (Cancel, Escape, or backdrop click).
+    pub on_cancel: Callback<()>,
+}
+
+/// Confirm dialog component.
+///
+/// Rendered in a portal on `<body>` so it overlays the whole page. While
+/// open, focus starts on the first control (Cancel unless children add a
+/// field), Tab cycles within the dialog, Escape cancels, and key presses
+/// do not reach page shortcuts. Focus returns to the previously focused
+/// element on close.
+#[function_component(ConfirmDialog)]
+pub fn confirm_dialog(props: &ConfirmDialogProps) -> Html {
+    let dialog_ref = use_node_ref();
+
+    {
+        let dialog_ref = dialog_ref.clone();
+        use_effect_with(props.open, move |open| {
+            let previous = open
+                .then(|| window().and_then(|w| w.document()))
+                .flatten()
+                .and_then(|d| d.active_element());
+            if *open && let Some(first) = focusable(&dialog_ref).into_iter().next() {
+                let _ = first.focus();
+            }
+            move || {
+                if let Some(el) = previous.and_then(|e| e.dyn_into::<HtmlElement>().ok()) {
+                    let _ = el.focus();
+                }
+            }
+        });
+    }
+
+    if !props.open {
+        return html! {};
+    }
+    let Some(host) = window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.body())
+        .map(Element::from)
+    else {
+        return html! {};
+    };
+
+    let on_keydown = {
+        let dialog_ref = dialog_ref.clone();
+        let on_cancel = props.on_cancel.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            e.stop_propagation();
+            match e.key().as_str() {
+                "Escape" => {
+                    e.prevent_default();
+                    on_cancel.emit(());
+                }
+                "Tab" => {
+                    let items = focusable(&dialog_ref);
+                    let (Some(first), Some(last)) = (items.first(), items.last()) else {
+                        return;
+                    };
+                    let active = window()
+                        .and_then(|w| w.document())
+                        .and_then(|d| d.active_element());
+                    let at = |el: &HtmlElement| active.as_ref() == Some(el.as_ref());
+                    if e.shift_key() && at(first) {
+                        e.prevent_default();
+                        let _ = last.focus();
+                    } else if !e.shift_key() && at(last) {
+                        e.prevent_default();
+                        let _ = first.focus();
+                    }
+                }
+                _ => {}
+            }
+        })
+    };
+
+    let on_backdrop_click = {
+        let on_cancel = props.on_cancel.clone();
+        Callback::from(move |e: MouseEvent| {
+            // Only clicks on the backdrop itself, not bubbling from the dialog
+            if e.target() == e.current_target() {
+                on_cancel.emit(());
+            }
+        })
+    };
+
+    let on_cancel_click = {
+        let on_cancel = props.on_cancel.clone();
+        Callback::from(move |_: MouseEvent| on_cancel.emit(()))
+    };
+
+    let on_confirm_click = {
+        let on_confirm = props.on_confirm.clone();
+        Callback::from(move |_: MouseEvent| on_confirm.emit(()))
+    };
+
+    create_portal(
+        html! {
+            <div class="dialog-backdrop" onclick={on_backdrop_click}>
+                <div
+                    class="dialog"
+                    role="alertdialog"
+                    aria-modal="true"
+                    aria-labelledby="confirm-dialog-title"
+                    aria-describedby="confirm-dialog-message"
+                    ref={dialog_ref}
+                    onkeydown={on_keydown}
+                >
+                    <h2 class="dialog-title" id="confirm-dialog-title">{props.title.clone()}</h2>
+                    <p class="dialog-message" id="confirm-dialog-message">{props.message.clone()}</p>
+                    {props.children.clone()}
+                    <div class="dialog-actions">
+                        <button type="button" class="btn btn-secondary" onclick={on_cancel_click}>
+                            {"Cancel"}
+                        </button>
+                        <button
+                            type="button"
+                            class="btn btn-danger"
+                            onclick={on_confirm_click}
+                            disabled={props.confirm_disabled}
+                        >
+                            {props.confirm_label.clone()}
+                        </button>
+                    </div>
+                </div>
+            </div>
+        },
+        host,
+    )
+}
+
+/// Returns the focusable elements inside the dialog, in document order.
+fn focusable(dialog_ref: &NodeRef) -> Vec<HtmlElement> {
+    let Some(dialog) = dialog_ref.cast::<Element>() else {
+        return Vec::new();
+    };
+    let Ok(nodes) = dialog.query_selector_all(FOCUSABLE) else {
+        return Vec::new();
+    };
+    (0..nodes.length())
+        .filter_map(|i| nodes.item(i))
+        .filter_map(|node| node.dyn_into::<HtmlElement>().ok())
+        .collect()
+}
@@ -0,0 +1,86 @@
+//! Page confirming a new account email from the link sent to it.
+
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::api;
+
+/// Email confirmation page properties.
+#[derive(Properties, PartialEq)]
+pub struct ConfirmEmailChangeProps {
+    /// Token from the confirmation link.
+    pub token: String,
+    /// Callback for the back button.
+    pub on_back: Callback<()>,
+}
+
+/// Confirmation state of the page.
+#[derive(Clone, PartialEq)]
+enum Status {
+    Waiting,
+    Confirming,
+    Confirmed(String),
+    Failed(String),
+}
+
+/// Email confirmation page.
+///
+/// The change is only made when the button is pressed, so link scanners and
+/// prefetchers that open the link do not confirm it.
+#[function_component(ConfirmEmailChange)]
+pub fn confirm_email_change(props: &ConfirmEmailChangeProps) -> Html {
+    let status = use_state(|| Status::Waiting);
+
+    let on_confirm = {
+        let token = props.token.clone();
+        let status = status.clone();
+        Callback::from(move |_: MouseEvent| {
+            let token = token.clone();
+            let status = status.clone();
+            status.set(Status::Confirming);
+            spawn_local(async move {
+                match api::confirm_email_change(&token).await {
+                    Ok(user) => status.set(Status::Confirmed(user.email)),
+                    Err(e) => status.set(Status::Failed(e.message)),
+                }
+            });
+        })
+    };
+
+    let on_back = {
+        let on_back = props.on_back.clone();
+        Callback::from(move |_: MouseEvent| on_back.emit(()))
+    };
+
+    html! {
+        <section class="confirm-email" aria-labelledby="confirm-email-title">
+            <h2 id="confirm-email-title">{"Confirm your new email"}</h2>
+            {match &*status {
+                Status::Waiting | Status::Confirming => html! {
+                    <>
+                        <p>{"Make this address the email of your account?"}</p>
+                        <button
+                            type="button"
+                            class="btn btn-primary"
+                            onclick={on_confirm}
+                            disabled={*status == Status::Confirming}
+                        >
+                            {"Confirm email"}
+                        </button>
+                    </>
+                },
+                Status::Confirmed(email) => html! {
+                    <div class="message message-success" role="status">
+                        {format!("Your email is now {email}.")}
+                    </div>
+                },
+                Status::Failed(err) => html! {
+                    <div class="message message-error" role="alert">{err}</div>
+                },
+            }}
+            <button type="button" class="btn btn-secondary" onclick={on_back}>
+                {"Back to posts"}
+            </button>
+        </section>
+    }
+}
@@ -0,0 +1,419 @@
+//! Content editor component: Markdown textarea with a server-rendered preview
+//! and `@mention` / `#tag` autocompletion.
+
+use std::collections::HashMap;
+
+use gloo_timers::callback::Timeout;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
+
+use crate::api;
+use crate::components::MarkdownContent;
+use crate::constants::{AUTOCOMPLETE_DEBOUNCE_MS, AUTOCOMPLETE_MAX_PREFIX_LEN};
+
+/// ID of the autocompletion listbox (referenced by the textarea's ARIA attributes).
+const SUGGESTIONS_ID: &str = "content-suggestions";
+
+/// An `@mention` or `#tag` being typed, ending at the cursor.
+#[derive(Clone, Debug, PartialEq)]
+struct Completion {
+    /// `@` or `#`.
+    trigger: char,
+    /// Byte offset of the trigger in the text.
+    start: usize,
+    /// Byte offset of the cursor in the text.
+    end: usize,
+    /// What follows the trigger, possibly empty.
+    prefix: String,
+}
+
+impl Completion {
+    /// Key of the suggestions for this completion in the cache.
+    fn cache_key(&self) -> String {
+        format!("{}{}", self.trigger, self.prefix.to_lowercase())
+    }
+}
+
+/// A suggested replacement for the completion being typed.
+#[derive(Clone, Debug, PartialEq)]
+struct Suggestion {
+    /// Name inserted after the trigger.
+    name: String,
+    /// Extra text shown next to it, e.g. a tag's post count.
+    detail: Option<String>,
+}
+
+/// Finds the `@mention` or `#tag` ending at `cursor` (a UTF-16 offset, as
+/// reported by `selectionStart`). The trigger must start a word, so e-mail
+/// addresses and URL fragments are left alone.
+fn completion_at(text: &str, cursor: u32) -> Option<Completion> {
+    let mut end = text.len();
+    let mut units = 0;
+    for (index, c) in text.char_indices() {
+        if units >= cursor as usize {
+            end = index;
+            break;
+        }
+        units += c.len_utf16();
+    }
+
+    let before = &text[..end];
+    let word_start = before
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .last()
+        .map_or(end, |(index, _)| index);
+    let trigger = before[..word_start].chars().next_back()?;
+    if !matches!(trigger, '@' | '#') {
+        return None;
+    }
+    let start = word_start - trigger.len_utf8();
+    if before[..start]
+        .chars()
+        .next_back()
+        .is_some_and(|c| !c.is_whitespace() && !matches!(c, '(' | '['))
+    {
+        return None;
+    }
+    let prefix = before[word_start..].to_string();
+    if prefix.chars().count() > AUTOCOMPLETE_MAX_PREFIX_LEN {
+        return None;
+    }
+
+    Some(Completion {
+        trigger,
+        start,
+        end,
+        prefix,
+    })
+}
+
+/// Fetches the suggestions for a completion; errors give none, since
+/// suggestions are best-effort.
+async fn fetch_suggestions(completion: &Completion) -> Vec<Suggestion> {
+    if completion.trigger == '@' {
+        api::suggest_users(&completion.prefix)
+            .await
+            .map(|response| {
+                response
+                    .users
+                    .into_iter()
+                    .map(|user| Suggestion {
+                        name: user.username,
+                        detail: None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    } else {
+        api::suggest_tags(&completion.prefix)
+            .await
+            .map(|response| {
+                response
+                    .tags
+                    .into_iter()
+                    .map(|tag| Suggestion {
+                        name: tag.name,
+                        detail: Some(format!(
+                            "{} post{}",
+                            tag.count,
+                            if tag.count == 1 { "" } else { "s" }
+                        )),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Content editor properties.
+#[derive(Properties, PartialEq)]
+pub struct ContentEditorProps {
+    /// Markdown being edited.
+    pub value: AttrValue,
+    /// Callback with the new text on input.
+    pub on_change: Callback<String>,
+    /// Whether the textarea is disabled.
+    #[prop_or_default]
+    pub disabled: bool,
+}
+
+/// Content editor component with Write and Preview tabs.
+///
+/// Typing `@` or `#` at the start of a word suggests users or tags once
+/// typing pauses. Suggestions are kept per prefix for the editor's
+/// lifetime, and responses to outdated prefixes are ignored.
+#[function_component(ContentEditor)]
+pub fn content_editor(props: &ContentEditorProps) -> Html {
+    // Rendered content while the preview tab is shown
+    let preview = use_state(|| None::<String>);
+    let error = use_state(|| None::<String>);
+    let textarea = use_node_ref();
+    let completion = use_state(|| None::<Completion>);
+    let suggestions = use_state(Vec::<Suggestion>::new);
+    let active = use_state(|| 0usize);
+    let cache = use_mut_ref(HashMap::<String, Vec<Suggestion>>::new);
+    // Dropping the pending timeout cancels it
+    let pending = use_mut_ref(|| None::<Timeout>);
+    let latest_request = use_mut_ref(|| 0u32);
+    // Cursor position to restore once a chosen suggestion is rendered
+    let caret = use_mut_ref(|| None::<u32>);
+
+    {
+        let textarea = textarea.clone();
+        let caret = caret.clone();
+        use_effect_with(props.value.clone(), move |_| {
+            if let Some(position) = caret.borrow_mut().take()
+                && let Some(element) = textarea.cast::<HtmlTextAreaElement>()
+            {
+                let _ = element.focus();
+                let _ = element.set_selection_range(position, position);
+            }
+        });
+    }
+
+    let close = {
+        let completion = completion.clone();
+        let suggestions = suggestions.clone();
+        let pending = pending.clone();
+        move || {
+            pending.borrow_mut().take();
+            completion.set(None);
+            suggestions.set(Vec::new());
+        }
+    };
+
+    let on_input = {
+        let on_change = props.on_change.clone();
+        let completion = completion.clone();
+        let suggestions = suggestions.clone();
+        let active = active.clone();
+        let close = close.clone();
+        Callback::from(move |e: InputEvent| {
+            let target: HtmlTextAreaElement = e.target_unchecked_into();
+            let text = target.value();
+            let cursor = target.selection_start().ok().flatten().unwrap_or(0);
+            on_change.emit(text.clone());
+
+            let request_id = {
+                let mut latest = latest_request.borrow_mut();
+                *latest = latest.wrapping_add(1);
+                *latest
+            };
+            let Some(found) = completion_at(&text, cursor) else {
+                close();
+                return;
+            };
+            active.set(0);
+            completion.set(Some(found.clone()));
+
+            let key = found.cache_key();
+            let cached = cache.borrow().get(&key).cloned();
+            if let Some(cached) = cached {
+                pending.borrow_mut().take();
+                suggestions.set(cached);
+                return;
+            }
+
+            let suggestions = suggestions.clone();
+            let cache = cache.clone();
+            let latest_request = latest_request.clone();
+            let timeout = Timeout::new(AUTOCOMPLETE_DEBOUNCE_MS, move || {
+                spawn_local(async move {
+                    let found = fetch_suggestions(&found).await;
+                    cache.borrow_mut().insert(key, found.clone());
+                    if *latest_request.borrow() == request_id {
+                        suggestions.set(found);
+                    }
+                });
+            });
+            *pending.borrow_mut() = Some(timeout);
+        })
+    };
+
+    let choose = {
+        let value = props.value.clone();
+        let on_change = props.on_change.clone();
+        let completion = completion.clone();
+        let caret = caret.clone();
+        let close = close.clone();
+        move |name: &str| {
+            let Some(current) = (*completion).clone() else {
+                return;
+            };
+            let text = value.as_str();
+            let Some(after) = text.get(current.end..) else {
+                close();
+                return;
+            };
+            let inserted = format!("{}{} ", current.trigger, name);
+            let before = &text[..current.start];
+            let position = before.encode_utf16().count() + inserted.encode_utf16().count();
+            *caret.borrow_mut() = Some(position as u32);
+            close();
+            on_change.emit(format!("{before}{inserted}{after}"));
+        }
+    };
+
+    let on_keydown = {
+        let suggestions = suggestions.clone();
+        let active = active.clone();
+        let choose = choose.clone();
+        let close = close.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            let count = suggestions.len();
+            if count == 0 {
+                return;
+            }
+            match e.key().as_str() {
+                "ArrowDown" => {
+                    e.prevent_default();
+                    active.set((*active + 1) % count);
+                }
+                "ArrowUp" => {
+                    e.prevent_default();
+                    active.set((*active + count - 1) % count);
+                }
+                "Enter" | "Tab" => {
+                    e.prevent_default();
+                    if let Some(suggestion) = suggestions.get(*active) {
+                        choose(&suggestion.name);
+                    }
+                }
+                "Escape" => close(),
+                _ => {}
+            }
+        })
+    };
+
+    let on_blur = {
+        let close = close.clone();
+        Callback::from(move |_: FocusEvent| close())
+    };
+
+    let on_write_tab = {
+        let preview = preview.clone();
+        Callback::from(move |_: MouseEvent| preview.set(None))
+    };
+
+    let on_preview_tab = {
+        let text = props.value.to_string();
+        let preview = preview.clone();
+        let error = error.clone();
+        let close = close.clone();
+        Callback::from(move |_: MouseEvent| {
+            let text = text.clone();
+            let preview = preview.clone();
+            let error = error.clone();
+            close();
+            error.set(None);
+            spawn_local(async move {
+                match api::preview_markdown(&text).await {
+                    Ok(rendered) => preview.set(Some(rendered.html)),
+                    Err(e) => error.set(Some(format!("Preview failed: {}", e.message))),
+                }
+            });
+        })
+    };
+
+    let trigger = completion.as_ref().map(|c| c.trigger);
+    let expanded = trigger.is_some() && !suggestions.is_empty();
+    let active_descendant = expanded.then(|| format!("{}-{}", SUGGESTIONS_ID, *active));
+
+    html! {
+        <div class="form-group">
+            <div class="content-editor-header">
+                <label for="content">{"Content"}</label>
+                <div class="content-editor-tabs" role="group" aria-label="Content view">
+                    <button
+                        type="button"
+                        class="btn btn-link btn-sm"
+                        aria-pressed={preview.is_none().to_string()}
+                        onclick={on_write_tab}
+                    >
+                        {"Write"}
+                    </button>
+                    <button
+                        type="button"
+                        class="btn btn-link btn-sm"
+                        aria-pressed={preview.is_some().to_string()}
+                        onclick={on_preview_tab}
+                        disabled={props.value.trim().is_empty()}
+                    >
+                        {"Preview"}
+                    </button>
+                </div>
+            </div>
+            if let Some(ref err) = *error {
+                <div class="message message-error" role="alert">{err}</div>
+            }
+            if let Some(ref rendered) = *preview {
+                <MarkdownContent
+                    class="content-editor-preview"
+                    html={rendered.clone()}
+                    fallback={props.value.clone()}
+                />
+            } else {
+                <div class="content-editor-input">
+                    <textarea
+                        id="content"
+                        ref={textarea}
+                        value={props.value.clone()}
+                        oninput={on_input}
+                        onkeydown={on_keydown}
+                        onblur={on_blur}
+                        disabled={props.disabled}
+                        placeholder="Write your post content..."
+                        rows="12"
+                        required=true
+                        aria-describedby="content-hint"
+                        aria-autocomplete="list"
+                        aria-controls={SUGGESTIONS_ID}
+                        aria-expanded={expanded.to_string()}
+                        aria-activedescendant={active_descendant}
+                    />
+                    if expanded {
+                        <ul
+                            class="search-suggestions content-suggestions"
+                            id={SUGGESTIONS_ID}
+                            role="listbox"
+                            aria-label={if trigger == Some('@') { "Users" } else { "Tags" }}
+                        >
+                            {for suggestions.iter().enumerate().map(|(index, suggestion)| {
+                                let is_active = *active == index;
+                                // mousedown fires before the textarea's blur closes the list
+                                let onmousedown = {
+                                    let choose = choose.clone();
+                                    let name = suggestion.name.clone();
+                                    Callback::from(move |e: MouseEvent| {
+                                        e.prevent_default();
+                                        choose(&name);
+                                    })
+                                };
+                                html! {
+                                    <li
+                                        id={format!("{}-{}", SUGGESTIONS_ID, index)}
+                                        class={classes!("search-suggestion", is_active.then_some("active"))}
+                                        role="option"
+                                        aria-selected={is_active.to_string()}
+                                        {onmousedown}
+                                    >
+                                        {format!("{}{}", trigger.unwrap_or('@'), suggestion.name)}
+                                        if let Some(ref detail) = suggestion.detail {
+                                            <span class="content-suggestion-detail">{detail}</span>
+                                        }
+                                    </li>
+                                }
+                            })}
+                        </ul>
+                    }
+                </div>
+            }
+            <small id="content-hint" class="form-hint">
+                {"Markdown with tables, footnotes, task lists, ~~strikethrough~~ and bare links. Type @ or # to mention users or tags."}
+            </small>
+        </div>
+    }
+}
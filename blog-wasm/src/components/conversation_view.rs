@@ -0,0 +1,204 @@
+//! A single conversation: message history and reply form.
+
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
+
+use blog_shared::MessageDto;
+use blog_shared::constants::MAX_MESSAGE_LENGTH;
+
+use crate::api;
+use crate::components::ErrorBoundary;
+use crate::constants::MESSAGE_PAGE_SIZE;
+
+/// Conversation view properties.
+#[derive(Properties, PartialEq)]
+pub struct ConversationViewProps {
+    pub conversation_id: i64,
+    /// Username of the other participant.
+    pub other_username: String,
+    /// Current user's public ID (their messages are shown as sent).
+    pub current_user_public_id: Option<String>,
+    /// Callback to return to the conversation list.
+    pub on_back: Callback<()>,
+    /// Callback after the conversation was marked as read.
+    pub on_read: Callback<()>,
+}
+
+/// Conversation view component.
+///
+/// Messages arrive newest first and are shown oldest first; "Load older"
+/// prepends the next page.
+#[function_component(ConversationView)]
+pub fn conversation_view(props: &ConversationViewProps) -> Html {
+    // Newest first, as returned by the API
+    let messages = use_state(Vec::<MessageDto>::new);
+    let total = use_state(|| 0i64);
+    let load_error = use_state(|| None::<String>);
+    let error = use_state(|| None::<String>);
+    let reload = use_state(|| 0u32);
+    let content = use_state(String::new);
+    let sending = use_state(|| false);
+
+    {
+        let messages = messages.clone();
+        let total = total.clone();
+        let load_error = load_error.clone();
+        let on_read = props.on_read.clone();
+        let conversation_id = props.conversation_id;
+        use_effect_with(*reload, move |_| {
+            load_error.set(None);
+            spawn_local(async move {
+                match api::list_messages(conversation_id, MESSAGE_PAGE_SIZE, 0).await {
+                    Ok(page) => {
+                        messages.set(page.messages);
+                        total.set(page.total);
+                        on_read.emit(());
+                    }
+                    Err(e) => {
+                        load_error.set(Some(format!("Failed to load messages: {}", e.message)))
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_retry = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(*reload + 1))
+    };
+
+    let on_load_older = {
+        let messages = messages.clone();
+        let total = total.clone();
+        let error = error.clone();
+        let conversation_id = props.conversation_id;
+        Callback::from(move |_: MouseEvent| {
+            let messages = messages.clone();
+            let total = total.clone();
+            let error = error.clone();
+            let offset = messages.len() as i64;
+            spawn_local(async move {
+                match api::list_messages(conversation_id, MESSAGE_PAGE_SIZE, offset).await {
+                    Ok(page) => {
+                        let mut all = (*messages).clone();
+                        all.extend(page.messages);
+                        messages.set(all);
+                        total.set(page.total);
+                    }
+                    Err(e) => error.set(Some(e.message)),
+                }
+            });
+        })
+    };
+
+    let on_input = {
+        let content = content.clone();
+        Callback::from(move |e: InputEvent| {
+            let target: HtmlTextAreaElement = e.target_unchecked_into();
+            content.set(target.value());
+        })
+    };
+
+    let onsubmit = {
+        let messages = messages.clone();
+        let total = total.clone();
+        let content = content.clone();
+        let sending = sending.clone();
+        let error = error.clone();
+        let conversation_id = props.conversation_id;
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let text = content.trim().to_string();
+            if text.is_empty() {
+                return;
+            }
+            let messages = messages.clone();
+            let total = total.clone();
+            let content = content.clone();
+            let sending = sending.clone();
+            let error = error.clone();
+
+            sending.set(true);
+            error.set(None);
+            spawn_local(async move {
+                match api::send_message(conversation_id, &text).await {
+                    Ok(message) => {
+                        let mut all = vec![message];
+                        all.extend(messages.iter().cloned());
+                        messages.set(all);
+                        total.set(*total + 1);
+                        content.set(String::new());
+                    }
+                    Err(e) => error.set(Some(e.message)),
+                }
+                sending.set(false);
+            });
+        })
+    };
+
+    let on_back_click = {
+        let on_back = props.on_back.clone();
+        Callback::from(move |_: MouseEvent| on_back.emit(()))
+    };
+
+    let has_older = (messages.len() as i64) < *total;
+
+    html! {
+        <div class="conversation">
+            <button type="button" class="btn btn-link" onclick={on_back_click}>
+                {"← All messages"}
+            </button>
+            <h2>{format!("Conversation with {}", props.other_username)}</h2>
+            <ErrorBoundary error={(*load_error).clone()} {on_retry}>
+                if let Some(ref err) = *error {
+                    <div class="message message-error" role="alert">{err}</div>
+                }
+                if has_older {
+                    <button type="button" class="btn btn-link" onclick={on_load_older}>
+                        {"Load older"}
+                    </button>
+                }
+                <ol class="message-list" aria-live="polite">
+                    {for messages.iter().rev().map(|message| {
+                        let sent = props.current_user_public_id.as_ref() == Some(&message.sender_public_id);
+                        html! {
+                            <li
+                                key={message.id}
+                                class={classes!("message-bubble", sent.then_some("message-sent"))}
+                            >
+                                <div class="comment-meta">
+                                    <span class="comment-author">{&message.sender_username}</span>
+                                    <span class="comment-date">
+                                        {message.created_at.format("%B %d, %Y %H:%M").to_string()}
+                                    </span>
+                                </div>
+                                <p class="message-content">{&message.content}</p>
+                            </li>
+                        }
+                    })}
+                </ol>
+            </ErrorBoundary>
+            <form class="comment-form" {onsubmit} aria-label="Reply">
+                <textarea
+                    value={(*content).clone()}
+                    oninput={on_input}
+                    maxlength={MAX_MESSAGE_LENGTH.to_string()}
+                    placeholder="Write a message..."
+                    rows="3"
+                    aria-label="Message text"
+                />
+                <div class="comment-form-footer">
+                    <button
+                        type="submit"
+                        class="btn btn-primary btn-sm"
+                        disabled={*sending || content.trim().is_empty()}
+                    >
+                        {"Send"}
+                    </button>
+                </div>
+            </form>
+        </div>
+    }
+}
@@ -0,0 +1,125 @@
+//! Delete account section.
+
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use blog_shared::DeleteAccountRequest;
+
+use crate::api;
+use crate::components::ConfirmDialog;
+
+/// Delete account properties.
+#[derive(Properties, PartialEq)]
+pub struct DeleteAccountProps {
+    /// Callback after the account was deleted and the token cleared.
+    pub on_deleted: Callback<()>,
+}
+
+/// Delete account component.
+///
+/// The confirmation dialog asks for the current password, which the server
+/// checks before deleting the account with its posts and comments.
+#[function_component(DeleteAccount)]
+pub fn delete_account(props: &DeleteAccountProps) -> Html {
+    let confirming = use_state(|| false);
+    let password = use_state(String::new);
+    let error = use_state(|| None::<String>);
+    let loading = use_state(|| false);
+
+    let on_open = {
+        let confirming = confirming.clone();
+        let error = error.clone();
+        Callback::from(move |_: MouseEvent| {
+            error.set(None);
+            confirming.set(true);
+        })
+    };
+
+    let on_cancel = {
+        let confirming = confirming.clone();
+        let password = password.clone();
+        Callback::from(move |_| {
+            password.set(String::new());
+            confirming.set(false);
+        })
+    };
+
+    let on_password_input = {
+        let password = password.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            password.set(input.value());
+        })
+    };
+
+    let on_confirm = {
+        let confirming = confirming.clone();
+        let password = password.clone();
+        let error = error.clone();
+        let loading = loading.clone();
+        let on_deleted = props.on_deleted.clone();
+        Callback::from(move |_| {
+            let req = DeleteAccountRequest {
+                current_password: (*password).clone(),
+            };
+            let confirming = confirming.clone();
+            let password = password.clone();
+            let error = error.clone();
+            let loading = loading.clone();
+            let on_deleted = on_deleted.clone();
+
+            loading.set(true);
+            spawn_local(async move {
+                match api::delete_account(req).await {
+                    Ok(()) => {
+                        api::clear_token();
+                        on_deleted.emit(());
+                    }
+                    Err(e) => {
+                        error.set(Some(e.message));
+                        password.set(String::new());
+                        confirming.set(false);
+                        loading.set(false);
+                    }
+                }
+            });
+        })
+    };
+
+    html! {
+        <section class="settings-section" aria-labelledby="settings-delete-title">
+            <h3 id="settings-delete-title">{"Delete account"}</h3>
+            <p class="form-hint">
+                {"Permanently deletes your account, posts, and comments. This cannot be undone."}
+            </p>
+            if let Some(ref err) = *error {
+                <div class="message message-error" role="alert">{err}</div>
+            }
+            <button type="button" class="btn btn-danger" onclick={on_open}>
+                {"Delete account"}
+            </button>
+            <ConfirmDialog
+                open={*confirming}
+                title="Delete your account?"
+                message="Your account, posts, and comments will be deleted permanently. Enter your password to confirm."
+                confirm_label="Delete account"
+                confirm_disabled={password.is_empty() || *loading}
+                {on_confirm}
+                {on_cancel}
+            >
+                <div class="form-group">
+                    <label for="delete-account-password">{"Password"}</label>
+                    <input
+                        type="password"
+                        id="delete-account-password"
+                        value={(*password).clone()}
+                        oninput={on_password_input}
+                        autocomplete="current-password"
+                        disabled={*loading}
+                    />
+                </div>
+            </ConfirmDialog>
+        </section>
+    }
+}
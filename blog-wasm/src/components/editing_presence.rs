@@ -0,0 +1,110 @@
+//! Notice naming other users editing the same post.
+
+use blog_shared::constants::EDITING_HEARTBEAT_INTERVAL_SECS;
+use blog_shared::{EditingPresenceDto, EditorDto};
+use gloo_events::EventListener;
+use gloo_timers::callback::Interval;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{MessageEvent, WebSocket};
+use yew::prelude::*;
+
+use crate::api;
+
+/// Editing presence properties.
+#[derive(Properties, PartialEq)]
+pub struct EditingPresenceProps {
+    /// Public ID of the post open in the form.
+    pub post_id: String,
+    /// Public ID of the signed-in user, left out of the notice.
+    #[prop_or_default]
+    pub current_user_public_id: Option<String>,
+}
+
+/// Editing presence component.
+///
+/// Sends an editing heartbeat on mount and every
+/// `EDITING_HEARTBEAT_INTERVAL_SECS`, and listens on the editing WebSocket
+/// so others joining or leaving show up at once. On unmount it tells the
+/// server the post was closed. Nothing is shown while editing alone; the
+/// notice only warns, it never blocks saving.
+#[function_component(EditingPresence)]
+pub fn editing_presence(props: &EditingPresenceProps) -> Html {
+    let editors = use_state(Vec::<EditorDto>::new);
+
+    {
+        let editors = editors.clone();
+        use_effect_with(props.post_id.clone(), move |post_id| {
+            let post_id = post_id.clone();
+            let beat = {
+                let editors = editors.clone();
+                let post_id = post_id.clone();
+                move || {
+                    let editors = editors.clone();
+                    let post_id = post_id.clone();
+                    spawn_local(async move {
+                        if let Ok(presence) = api::editing_heartbeat(&post_id).await {
+                            editors.set(presence.editors);
+                        }
+                    });
+                }
+            };
+            beat();
+            let interval = Interval::new(EDITING_HEARTBEAT_INTERVAL_SECS * 1000, beat);
+
+            let socket =
+                api::editing_socket_url(&post_id).and_then(|url| WebSocket::new(&url).ok());
+            let listener = socket.as_ref().map(|socket| {
+                EventListener::new(socket, "message", move |event| {
+                    let presence = event
+                        .dyn_ref::<MessageEvent>()
+                        .and_then(|event| event.data().as_string())
+                        .and_then(|text| serde_json::from_str::<EditingPresenceDto>(&text).ok());
+                    if let Some(presence) = presence {
+                        editors.set(presence.editors);
+                    }
+                })
+            });
+
+            move || {
+                drop(interval);
+                drop(listener);
+                if let Some(socket) = socket {
+                    let _ = socket.close();
+                }
+                spawn_local(async move {
+                    let _ = api::stop_editing(&post_id).await;
+                });
+            }
+        });
+    }
+
+    let others: Vec<&str> = editors
+        .iter()
+        .filter(|editor| props.current_user_public_id.as_ref() != Some(&editor.user_public_id))
+        .map(|editor| editor.username.as_str())
+        .collect();
+    if others.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <div class="message message-warning editing-presence" role="status">
+            {editing_notice(&others)}
+        </div>
+    }
+}
+
+/// "Alice is also editing", "Alice and Bob are also editing", ...
+fn editing_notice(names: &[&str]) -> String {
+    match names {
+        [name] => format!("{} is also editing this post", name),
+        [first, second] => format!("{} and {} are also editing this post", first, second),
+        [first, rest @ ..] => format!(
+            "{} and {} others are also editing this post",
+            first,
+            rest.len()
+        ),
+        [] => String::new(),
+    }
+}
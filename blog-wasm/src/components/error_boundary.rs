@@ -0,0 +1,87 @@
+//! Error boundary for sections that load data.
+//!
+//! Yew has no component-level panic recovery, so [`ErrorBoundary`] covers
+//! failed fetches per section, and [`install_panic_fallback`] replaces a
+//! crashed app with a reload prompt.
+
+use std::panic::{self, PanicHookInfo};
+
+use blog_shared::ClientErrorReport;
+use web_sys::window;
+use yew::prelude::*;
+
+use crate::api::report_crash;
+
+/// Error boundary properties.
+#[derive(Properties, PartialEq)]
+pub struct ErrorBoundaryProps {
+    /// Error to show instead of the children, if any.
+    #[prop_or_default]
+    pub error: Option<String>,
+    /// Callback for the retry button.
+    pub on_retry: Callback<()>,
+    /// Section content.
+    #[prop_or_default]
+    pub children: Html,
+}
+
+/// Renders its children, or an error panel with a retry button.
+#[function_component(ErrorBoundary)]
+pub fn error_boundary(props: &ErrorBoundaryProps) -> Html {
+    let Some(ref error) = props.error else {
+        return props.children.clone();
+    };
+
+    let on_retry = {
+        let on_retry = props.on_retry.clone();
+        Callback::from(move |_: MouseEvent| on_retry.emit(()))
+    };
+
+    html! {
+        <div class="error-boundary" role="alert">
+            <div class="message message-error">{error}</div>
+            <button type="button" class="btn btn-secondary" onclick={on_retry}>{"Try again"}</button>
+        </div>
+    }
+}
+
+/// Installs a panic hook that logs the panic with its stack trace, reports
+/// it to the server's error tracker, and shows a reload prompt.
+///
+/// A panic leaves the WASM instance unusable, so reloading is the only
+/// recovery.
+pub fn install_panic_fallback() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        console_error_panic_hook::hook(info);
+        report_crash(&crash_report(info));
+        if let Some(body) = window().and_then(|w| w.document()).and_then(|d| d.body()) {
+            let _ = body.insert_adjacent_html(
+                "beforeend",
+                r#"<div class="panic-fallback" role="alert">
+                    <p>Something went wrong.</p>
+                    <button class="btn btn-primary" onclick="location.reload()">Reload</button>
+                </div>"#,
+            );
+        }
+        default_hook(info);
+    }));
+}
+
+/// Report of a panic, with its location and the current page.
+fn crash_report(info: &PanicHookInfo<'_>) -> ClientErrorReport {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| info.to_string());
+    let mut report = ClientErrorReport::new(message);
+    if let Some(location) = info.location() {
+        report = report.with_location(location.to_string());
+    }
+    if let Some(href) = window().and_then(|w| w.location().href().ok()) {
+        report = report.with_url(href);
+    }
+    report
+}
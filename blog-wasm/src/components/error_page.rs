@@ -0,0 +1,111 @@
+//! Full-page errors: not found, and server errors with the request ID to
+//! quote when reporting them.
+
+use yew::prelude::*;
+
+use crate::api::ApiError;
+use crate::components::ErrorBoundary;
+
+/// Not found page properties.
+#[derive(Properties, PartialEq)]
+pub struct NotFoundProps {
+    /// Callback for the back link.
+    pub on_back: Callback<()>,
+}
+
+/// Page for an unknown address or a post that does not exist (any more).
+#[function_component(NotFound)]
+pub fn not_found(props: &NotFoundProps) -> Html {
+    let on_back = {
+        let on_back = props.on_back.clone();
+        Callback::from(move |_: MouseEvent| on_back.emit(()))
+    };
+
+    html! {
+        <section class="error-page" aria-labelledby="error-page-title">
+            <p class="error-page-code">{"404"}</p>
+            <h2 id="error-page-title">{"Page not found"}</h2>
+            <p>{"This page does not exist, or it was deleted."}</p>
+            <button type="button" class="btn btn-primary" onclick={on_back}>
+                {"Back to posts"}
+            </button>
+        </section>
+    }
+}
+
+/// Server error page properties.
+#[derive(Properties, PartialEq)]
+pub struct ServerErrorProps {
+    /// Request ID of the failed response, if the server sent one.
+    #[prop_or_default]
+    pub request_id: Option<String>,
+    /// Callback for the retry button.
+    pub on_retry: Callback<()>,
+}
+
+/// Page for a 5xx response.
+#[function_component(ServerError)]
+pub fn server_error(props: &ServerErrorProps) -> Html {
+    let on_retry = {
+        let on_retry = props.on_retry.clone();
+        Callback::from(move |_: MouseEvent| on_retry.emit(()))
+    };
+
+    html! {
+        <section class="error-page" role="alert" aria-labelledby="error-page-title">
+            <p class="error-page-code">{"500"}</p>
+            <h2 id="error-page-title">{"Something went wrong"}</h2>
+            <p>{"The server could not handle this request. Please try again in a moment."}</p>
+            if let Some(ref request_id) = props.request_id {
+                <p class="error-page-request-id">
+                    {"If it keeps happening, mention request ID "}
+                    <code>{request_id}</code>
+                    {" when reporting it."}
+                </p>
+            }
+            <button type="button" class="btn btn-primary" onclick={on_retry}>{"Try again"}</button>
+        </section>
+    }
+}
+
+/// Load error page properties.
+#[derive(Properties, PartialEq)]
+pub struct LoadErrorProps {
+    /// Error loading the page's content, if any.
+    #[prop_or_default]
+    pub error: Option<ApiError>,
+    /// Prefix of the message for other errors, e.g. "Failed to load post".
+    pub context: AttrValue,
+    /// Callback for the not found page's back link.
+    pub on_back: Callback<()>,
+    /// Callback for the retry button.
+    pub on_retry: Callback<()>,
+    /// Page content.
+    #[prop_or_default]
+    pub children: Html,
+}
+
+/// Renders its children, or the page for a failed load: [`NotFound`] for
+/// 404s, [`ServerError`] for 5xx, and an [`ErrorBoundary`] otherwise.
+#[function_component(LoadError)]
+pub fn load_error(props: &LoadErrorProps) -> Html {
+    match props.error {
+        Some(ref error) if error.is_not_found() => html! {
+            <NotFound on_back={props.on_back.clone()} />
+        },
+        Some(ref error) if error.is_server_error() => html! {
+            <ServerError
+                request_id={error.request_id.clone()}
+                on_retry={props.on_retry.clone()}
+            />
+        },
+        ref error => html! {
+            <ErrorBoundary
+                error={error.as_ref().map(|e| format!("{}: {}", props.context, e.message))}
+                on_retry={props.on_retry.clone()}
+            >
+                {props.children.clone()}
+            </ErrorBoundary>
+        },
+    }
+}
@@ -0,0 +1,97 @@
+//! Feature flag toggles.
+
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use blog_shared::FeatureFlagDto;
+
+use crate::api;
+use crate::components::ErrorBoundary;
+
+/// Feature flags component.
+#[function_component(FeatureFlags)]
+pub fn feature_flags() -> Html {
+    let flags = use_state(Vec::<FeatureFlagDto>::new);
+    let load_error = use_state(|| None::<String>);
+    let error = use_state(|| None::<String>);
+    let reload = use_state(|| 0u32);
+
+    {
+        let flags = flags.clone();
+        let load_error = load_error.clone();
+        use_effect_with(*reload, move |_| {
+            load_error.set(None);
+            spawn_local(async move {
+                match api::list_feature_flags().await {
+                    Ok(list) => flags.set(list.flags),
+                    Err(e) => load_error.set(Some(format!("Failed to load flags: {}", e.message))),
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_retry = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(*reload + 1))
+    };
+
+    let on_toggle = {
+        let flags = flags.clone();
+        let error = error.clone();
+        Callback::from(move |(name, enabled): (String, bool)| {
+            let flags = flags.clone();
+            let error = error.clone();
+            error.set(None);
+            spawn_local(async move {
+                match api::update_feature_flag(&name, enabled).await {
+                    Ok(updated) => {
+                        let list = flags
+                            .iter()
+                            .map(|f| {
+                                if f.name == name {
+                                    updated.clone()
+                                } else {
+                                    f.clone()
+                                }
+                            })
+                            .collect();
+                        flags.set(list);
+                    }
+                    Err(e) => error.set(Some(e.message)),
+                }
+            });
+        })
+    };
+
+    html! {
+        <section class="admin-section" aria-labelledby="admin-flags-title">
+            <h3 id="admin-flags-title">{"Feature flags"}</h3>
+            <ErrorBoundary error={(*load_error).clone()} {on_retry}>
+                if let Some(ref err) = *error {
+                    <div class="message message-error" role="alert">{err}</div>
+                }
+                <ul class="flag-list">
+                    {for flags.iter().map(|flag| {
+                        let onchange = {
+                            let on_toggle = on_toggle.clone();
+                            let name = flag.name.clone();
+                            let enabled = !flag.enabled;
+                            Callback::from(move |_: Event| on_toggle.emit((name.clone(), enabled)))
+                        };
+                        let id = format!("flag-{}", flag.name);
+                        html! {
+                            <li key={flag.name.clone()} class="flag-item">
+                                <input type="checkbox" id={id.clone()} checked={flag.enabled} {onchange} />
+                                <label for={id}>
+                                    <span class="flag-name">{&flag.name}</span>
+                                    <span class="flag-description">{&flag.description}</span>
+                                </label>
+                            </li>
+                        }
+                    })}
+                </ul>
+            </ErrorBoundary>
+        </section>
+    }
+}
@@ -0,0 +1,162 @@
+//! Flagged content section: posts and comments matched by moderation rules.
+
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use blog_shared::{ContentKind, FlaggedContentDto};
+
+use crate::api;
+use crate::components::ErrorBoundary;
+use crate::constants::ADMIN_PAGE_SIZE;
+
+/// Flagged content properties.
+#[derive(Properties, PartialEq)]
+pub struct FlaggedContentProps {
+    /// Callback to open a flagged post, or the post of a flagged comment.
+    pub on_open_post: Callback<String>,
+}
+
+/// Flagged content component.
+#[function_component(FlaggedContent)]
+pub fn flagged_content(props: &FlaggedContentProps) -> Html {
+    let items = use_state(Vec::<FlaggedContentDto>::new);
+    let total = use_state(|| 0i64);
+    let load_error = use_state(|| None::<String>);
+    let error = use_state(|| None::<String>);
+    let reload = use_state(|| 0u32);
+
+    {
+        let items = items.clone();
+        let total = total.clone();
+        let load_error = load_error.clone();
+        use_effect_with(*reload, move |_| {
+            load_error.set(None);
+            spawn_local(async move {
+                match api::list_flagged_content(ADMIN_PAGE_SIZE, 0).await {
+                    Ok(page) => {
+                        items.set(page.items);
+                        total.set(page.total);
+                    }
+                    Err(e) => load_error.set(Some(format!(
+                        "Failed to load flagged content: {}",
+                        e.message
+                    ))),
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_retry = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(*reload + 1))
+    };
+
+    let on_load_more = {
+        let items = items.clone();
+        let total = total.clone();
+        let error = error.clone();
+        Callback::from(move |_: MouseEvent| {
+            let items = items.clone();
+            let total = total.clone();
+            let error = error.clone();
+            let offset = items.len() as i64;
+            spawn_local(async move {
+                match api::list_flagged_content(ADMIN_PAGE_SIZE, offset).await {
+                    Ok(page) => {
+                        let mut all = (*items).clone();
+                        all.extend(page.items);
+                        items.set(all);
+                        total.set(page.total);
+                    }
+                    Err(e) => error.set(Some(e.message)),
+                }
+            });
+        })
+    };
+
+    let on_dismiss = {
+        let items = items.clone();
+        let total = total.clone();
+        let error = error.clone();
+        Callback::from(move |id: i64| {
+            let items = items.clone();
+            let total = total.clone();
+            let error = error.clone();
+            error.set(None);
+            spawn_local(async move {
+                match api::dismiss_flag(id).await {
+                    Ok(()) => {
+                        items.set(items.iter().filter(|f| f.id != id).cloned().collect());
+                        total.set(*total - 1);
+                    }
+                    Err(e) => error.set(Some(e.message)),
+                }
+            });
+        })
+    };
+
+    let has_more = (items.len() as i64) < *total;
+
+    html! {
+        <section class="admin-section" aria-labelledby="admin-flagged-title">
+            <h3 id="admin-flagged-title">{format!("Flagged content ({})", *total)}</h3>
+            <p class="form-hint">{"Published posts and comments matched by a flagging rule."}</p>
+            <ErrorBoundary error={(*load_error).clone()} {on_retry}>
+                if let Some(ref err) = *error {
+                    <div class="message message-error" role="alert">{err}</div>
+                }
+                <ul class="moderation-list">
+                    {for items.iter().map(|item| {
+                        let post_id = item.post_public_id.clone();
+                        let id = item.id;
+                        let on_open = {
+                            let on_open_post = props.on_open_post.clone();
+                            let post_id = post_id.clone();
+                            Callback::from(move |e: MouseEvent| {
+                                e.prevent_default();
+                                on_open_post.emit(post_id.clone());
+                            })
+                        };
+                        let on_click = {
+                            let on_dismiss = on_dismiss.clone();
+                            Callback::from(move |_: MouseEvent| on_dismiss.emit(id))
+                        };
+                        let kind = match item.kind {
+                            ContentKind::Post => "Post",
+                            ContentKind::Comment => "Comment",
+                        };
+                        html! {
+                            <li key={id} class="moderation-item">
+                                <div class="comment-meta">
+                                    <span class="comment-author">{&item.author_username}</span>
+                                    <span class="comment-date">
+                                        {item.created_at.format("%B %d, %Y %H:%M").to_string()}
+                                    </span>
+                                    <a href={format!("/posts/{}", post_id)} onclick={on_open}>
+                                        {format!("{} on post", kind)}
+                                    </a>
+                                    <span class="flag-rule">{format!("Rule: {}", item.rule_name)}</span>
+                                </div>
+                                <p class="comment-content">{&item.excerpt}</p>
+                                <button
+                                    type="button"
+                                    class="btn btn-secondary btn-sm"
+                                    onclick={on_click}
+                                    aria-label={format!("Dismiss flag on {} by {}", kind.to_lowercase(), item.author_username)}
+                                >
+                                    {"Dismiss"}
+                                </button>
+                            </li>
+                        }
+                    })}
+                </ul>
+                if has_more {
+                    <button type="button" class="btn btn-link" onclick={on_load_more}>
+                        {"Load more"}
+                    </button>
+                }
+            </ErrorBoundary>
+        </section>
+    }
+}
@@ -0,0 +1,238 @@
+//! Inbox page: conversation list, new message form, and the open conversation.
+
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlTextAreaElement};
+use yew::prelude::*;
+
+use blog_shared::ConversationDto;
+use blog_shared::constants::MAX_MESSAGE_LENGTH;
+
+use crate::api;
+use crate::components::{ConversationView, ErrorBoundary};
+use crate::constants::MESSAGE_PAGE_SIZE;
+
+/// Inbox properties.
+#[derive(Properties, PartialEq)]
+pub struct InboxProps {
+    /// Current user's public ID (their messages are shown as sent).
+    pub current_user_public_id: Option<String>,
+    /// Callback after messages were read (refreshes the notification bell).
+    pub on_read: Callback<()>,
+}
+
+/// Inbox component.
+#[function_component(Inbox)]
+pub fn inbox(props: &InboxProps) -> Html {
+    let conversations = use_state(Vec::<ConversationDto>::new);
+    let total = use_state(|| 0i64);
+    let load_error = use_state(|| None::<String>);
+    let error = use_state(|| None::<String>);
+    let reload = use_state(|| 0u32);
+    let selected = use_state(|| None::<ConversationDto>);
+    let recipient = use_state(String::new);
+    let content = use_state(String::new);
+    let sending = use_state(|| false);
+
+    {
+        let conversations = conversations.clone();
+        let total = total.clone();
+        let load_error = load_error.clone();
+        use_effect_with(*reload, move |_| {
+            load_error.set(None);
+            spawn_local(async move {
+                match api::list_conversations(MESSAGE_PAGE_SIZE, 0).await {
+                    Ok(page) => {
+                        conversations.set(page.conversations);
+                        total.set(page.total);
+                    }
+                    Err(e) => {
+                        load_error.set(Some(format!("Failed to load conversations: {}", e.message)))
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_retry = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(*reload + 1))
+    };
+
+    let on_load_more = {
+        let conversations = conversations.clone();
+        let total = total.clone();
+        let error = error.clone();
+        Callback::from(move |_: MouseEvent| {
+            let conversations = conversations.clone();
+            let total = total.clone();
+            let error = error.clone();
+            let offset = conversations.len() as i64;
+            spawn_local(async move {
+                match api::list_conversations(MESSAGE_PAGE_SIZE, offset).await {
+                    Ok(page) => {
+                        let mut all = (*conversations).clone();
+                        all.extend(page.conversations);
+                        conversations.set(all);
+                        total.set(page.total);
+                    }
+                    Err(e) => error.set(Some(e.message)),
+                }
+            });
+        })
+    };
+
+    let on_recipient_input = {
+        let recipient = recipient.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            recipient.set(input.value());
+        })
+    };
+
+    let on_content_input = {
+        let content = content.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlTextAreaElement = e.target_unchecked_into();
+            content.set(input.value());
+        })
+    };
+
+    let onsubmit = {
+        let recipient = recipient.clone();
+        let content = content.clone();
+        let sending = sending.clone();
+        let error = error.clone();
+        let selected = selected.clone();
+        let reload = reload.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let username = recipient.trim().to_string();
+            let text = content.trim().to_string();
+            if username.is_empty() || text.is_empty() {
+                return;
+            }
+            let recipient = recipient.clone();
+            let content = content.clone();
+            let sending = sending.clone();
+            let error = error.clone();
+            let selected = selected.clone();
+            let reload = reload.clone();
+
+            sending.set(true);
+            error.set(None);
+            spawn_local(async move {
+                match api::start_conversation(&username, &text).await {
+                    Ok(conversation) => {
+                        recipient.set(String::new());
+                        content.set(String::new());
+                        selected.set(Some(conversation));
+                        reload.set(*reload + 1);
+                    }
+                    Err(e) => error.set(Some(e.message)),
+                }
+                sending.set(false);
+            });
+        })
+    };
+
+    let on_back = {
+        let selected = selected.clone();
+        let reload = reload.clone();
+        Callback::from(move |_| {
+            selected.set(None);
+            reload.set(*reload + 1);
+        })
+    };
+
+    if let Some(conversation) = (*selected).clone() {
+        return html! {
+            <ConversationView
+                key={conversation.id}
+                conversation_id={conversation.id}
+                other_username={conversation.other_username}
+                current_user_public_id={props.current_user_public_id.clone()}
+                {on_back}
+                on_read={props.on_read.clone()}
+            />
+        };
+    }
+
+    let has_more = (conversations.len() as i64) < *total;
+
+    html! {
+        <div class="inbox">
+            <h2>{"Messages"}</h2>
+            <ErrorBoundary error={(*load_error).clone()} {on_retry}>
+                if let Some(ref err) = *error {
+                    <div class="message message-error" role="alert">{err}</div>
+                }
+                if conversations.is_empty() {
+                    <p class="empty-state">{"No conversations yet."}</p>
+                }
+                <ul class="conversation-list">
+                    {for conversations.iter().map(|conversation| {
+                        let onclick = {
+                            let selected = selected.clone();
+                            let conversation = conversation.clone();
+                            Callback::from(move |_: MouseEvent| selected.set(Some(conversation.clone())))
+                        };
+                        let unread = conversation.unread_count > 0;
+                        html! {
+                            <li key={conversation.id}>
+                                <button
+                                    type="button"
+                                    class={classes!("conversation-item", unread.then_some("conversation-unread"))}
+                                    {onclick}
+                                >
+                                    <span class="conversation-user">{&conversation.other_username}</span>
+                                    if unread {
+                                        <span class="notification-badge">{conversation.unread_count}</span>
+                                    }
+                                    if let Some(ref at) = conversation.last_message_at {
+                                        <span class="comment-date">{at.format("%B %d, %Y %H:%M").to_string()}</span>
+                                    }
+                                    if let Some(ref last) = conversation.last_message {
+                                        <span class="conversation-preview">{last}</span>
+                                    }
+                                </button>
+                            </li>
+                        }
+                    })}
+                </ul>
+                if has_more {
+                    <button type="button" class="btn btn-link" onclick={on_load_more}>
+                        {"Load more"}
+                    </button>
+                }
+            </ErrorBoundary>
+            <form class="new-message-form" {onsubmit} aria-busy={sending.to_string()}>
+                <h3>{"New message"}</h3>
+                <div class="form-group">
+                    <label for="message-recipient">{"To (username)"}</label>
+                    <input
+                        id="message-recipient"
+                        type="text"
+                        value={(*recipient).clone()}
+                        oninput={on_recipient_input}
+                        required=true
+                    />
+                </div>
+                <div class="form-group">
+                    <label for="message-content">{"Message"}</label>
+                    <textarea
+                        id="message-content"
+                        rows="3"
+                        value={(*content).clone()}
+                        oninput={on_content_input}
+                        maxlength={MAX_MESSAGE_LENGTH.to_string()}
+                        required=true
+                    />
+                </div>
+                <button type="submit" class="btn btn-primary" disabled={*sending}>
+                    {"Send"}
+                </button>
+            </form>
+        </div>
+    }
+}
@@ -0,0 +1,93 @@
+//! "Add to home screen" prompt for installing the app as a PWA.
+
+use gloo_events::EventListener;
+use gloo_storage::{LocalStorage, Storage};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::js_sys::{Function, Reflect};
+use web_sys::{Event, window};
+use yew::prelude::*;
+
+use crate::constants::INSTALL_DISMISSED_STORAGE_KEY;
+use crate::site::use_site;
+
+/// Install prompt component.
+///
+/// Captures the browser's `beforeinstallprompt` event and offers an install
+/// button instead of the default mini-infobar. Hidden once the app is
+/// installed or the user dismisses it.
+#[function_component(InstallPrompt)]
+pub fn install_prompt() -> Html {
+    let deferred = use_state(|| None::<Event>);
+    let site = use_site();
+
+    {
+        let deferred = deferred.clone();
+        use_effect_with((), move |_| {
+            let listeners = window().map(|win| {
+                let on_prompt = {
+                    let deferred = deferred.clone();
+                    EventListener::new(&win, "beforeinstallprompt", move |event| {
+                        event.prevent_default();
+                        if !is_dismissed() {
+                            deferred.set(Some(event.clone()));
+                        }
+                    })
+                };
+                let on_installed = EventListener::new(&win, "appinstalled", move |_| {
+                    deferred.set(None);
+                });
+                (on_prompt, on_installed)
+            });
+
+            move || drop(listeners)
+        });
+    }
+
+    let on_install = {
+        let deferred = deferred.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(ref event) = *deferred {
+                show_native_prompt(event);
+            }
+            // The event can only be used once
+            deferred.set(None);
+        })
+    };
+
+    let on_dismiss = {
+        let deferred = deferred.clone();
+        Callback::from(move |_: MouseEvent| {
+            let _ = LocalStorage::set(INSTALL_DISMISSED_STORAGE_KEY, true);
+            deferred.set(None);
+        })
+    };
+
+    if deferred.is_none() {
+        return html! {};
+    }
+
+    html! {
+        <div class="install-prompt" role="dialog" aria-label="Install app">
+            <span>{format!("Install {} for quick access and offline reading.", site.title)}</span>
+            <div class="install-prompt-actions">
+                <button class="btn btn-primary btn-sm" onclick={on_install}>{"Install"}</button>
+                <button class="btn btn-secondary btn-sm" onclick={on_dismiss}>{"Not now"}</button>
+            </div>
+        </div>
+    }
+}
+
+/// Returns true if the user previously dismissed the prompt.
+fn is_dismissed() -> bool {
+    LocalStorage::get::<bool>(INSTALL_DISMISSED_STORAGE_KEY).unwrap_or(false)
+}
+
+/// Calls `prompt()` on a deferred `BeforeInstallPromptEvent`.
+fn show_native_prompt(event: &Event) {
+    let prompt = Reflect::get(event, &JsValue::from_str("prompt"))
+        .ok()
+        .and_then(|value| value.dyn_into::<Function>().ok());
+    if let Some(prompt) = prompt {
+        let _ = prompt.call0(event);
+    }
+}
@@ -0,0 +1,76 @@
+//! Language switcher listing the translations of a post.
+
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use blog_shared::TranslationDto;
+
+use crate::api;
+
+/// Language switcher properties.
+#[derive(Properties, PartialEq)]
+pub struct LanguageSwitcherProps {
+    /// Public ID of the post whose translations to list.
+    pub post_id: String,
+    /// Language of the post being shown.
+    pub lang: String,
+    /// Callback with the public ID of the translation to open.
+    pub on_select: Callback<String>,
+}
+
+/// Language switcher component. Renders nothing for untranslated posts.
+#[function_component(LanguageSwitcher)]
+pub fn language_switcher(props: &LanguageSwitcherProps) -> Html {
+    let translations = use_state(Vec::<TranslationDto>::new);
+
+    {
+        let translations = translations.clone();
+        use_effect_with(props.post_id.clone(), move |post_id| {
+            let post_id = post_id.clone();
+            spawn_local(async move {
+                // The switcher is optional chrome; a failed load just hides it
+                if let Ok(loaded) = api::list_translations(&post_id).await {
+                    translations.set(loaded.translations);
+                }
+            });
+            || ()
+        });
+    }
+
+    if translations.len() < 2 {
+        return html! {};
+    }
+
+    html! {
+        <ul class="language-switcher" aria-label="Translations">
+            {for translations.iter().map(|translation| {
+                if translation.lang == props.lang {
+                    html! {
+                        <li key={translation.lang.clone()}>
+                            <span class="tag-chip" aria-current="page">{&translation.lang}</span>
+                        </li>
+                    }
+                } else {
+                    let onclick = {
+                        let on_select = props.on_select.clone();
+                        let id = translation.public_id.clone();
+                        Callback::from(move |_: MouseEvent| on_select.emit(id.clone()))
+                    };
+                    html! {
+                        <li key={translation.lang.clone()}>
+                            <button
+                                type="button"
+                                class="tag-chip"
+                                lang={translation.lang.clone()}
+                                title={translation.title.clone()}
+                                {onclick}
+                            >
+                                {&translation.lang}
+                            </button>
+                        </li>
+                    }
+                }
+            })}
+        </ul>
+    }
+}
@@ -0,0 +1,57 @@
+//! Link preview cards for the external pages a post links to.
+
+use yew::prelude::*;
+
+use blog_shared::LinkPreviewDto;
+
+/// Link previews properties.
+#[derive(Properties, PartialEq)]
+pub struct LinkPreviewsProps {
+    /// Previews fetched by the server, in link order.
+    pub previews: Vec<LinkPreviewDto>,
+}
+
+/// Link previews component. Renders nothing until the server has fetched some.
+#[function_component(LinkPreviews)]
+pub fn link_previews(props: &LinkPreviewsProps) -> Html {
+    if props.previews.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <ul class="link-previews" aria-label="Linked pages">
+            {for props.previews.iter().map(|preview| html! {
+                <li key={preview.url.clone()}>
+                    <a
+                        class="link-preview"
+                        href={preview.url.clone()}
+                        target="_blank"
+                        rel="nofollow noopener noreferrer"
+                    >
+                        if let Some(ref image) = preview.image_url {
+                            <img class="link-preview-image" src={image.clone()} alt="" loading="lazy" />
+                        }
+                        <span class="link-preview-body">
+                            <span class="link-preview-site">
+                                {preview.site_name.clone().unwrap_or_else(|| host(&preview.url))}
+                            </span>
+                            <span class="link-preview-title">{&preview.title}</span>
+                            if let Some(ref description) = preview.description {
+                                <span class="link-preview-description">{description}</span>
+                            }
+                        </span>
+                    </a>
+                </li>
+            })}
+        </ul>
+    }
+}
+
+/// Returns the host part of a URL, for previews without a site name.
+pub(super) fn host(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.split(['/', '?', '#'])
+        .next()
+        .unwrap_or(rest)
+        .to_string()
+}
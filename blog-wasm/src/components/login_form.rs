@@ -4,15 +4,16 @@ use wasm_bindgen_futures::spawn_local;
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
 
-use blog_shared::LoginRequest;
+use blog_shared::{LoginRequest, UserDto};
 
 use crate::api;
+use crate::components::RecoverForm;
 
 /// Login form properties.
 #[derive(Properties, PartialEq)]
 pub struct LoginFormProps {
-    /// Callback when login succeeds (user_id, username).
-    pub on_success: Callback<(i64, String)>,
+    /// Callback when login succeeds, with the signed-in user.
+    pub on_success: Callback<UserDto>,
 }
 
 /// Login form component.
@@ -22,6 +23,8 @@ pub fn login_form(props: &LoginFormProps) -> Html {
     let password = use_state(String::new);
     let error = use_state(|| None::<String>);
     let loading = use_state(|| false);
+    // Showing the recovery code form instead
+    let recovering = use_state(|| false);
 
     let on_username_change = {
         let username = username.clone();
@@ -67,7 +70,7 @@ pub fn login_form(props: &LoginFormProps) -> Html {
                 match api::login(req).await {
                     Ok(response) => {
                         api::set_token(&response.token);
-                        on_success.emit((response.user.id, response.user.username));
+                        on_success.emit(response.user);
                     }
                     Err(e) => {
                         error.set(Some(e.message));
@@ -78,17 +81,32 @@ pub fn login_form(props: &LoginFormProps) -> Html {
         })
     };
 
+    if *recovering {
+        let on_cancel = {
+            let recovering = recovering.clone();
+            Callback::from(move |_| recovering.set(false))
+        };
+        return html! {
+            <RecoverForm on_success={props.on_success.clone()} {on_cancel} />
+        };
+    }
+
+    let on_recover = {
+        let recovering = recovering.clone();
+        Callback::from(move |_: MouseEvent| recovering.set(true))
+    };
+
     html! {
         <div class="auth-container">
             <h2>{"Login"}</h2>
 
             if let Some(ref err) = *error {
-                <div class="message message-error">{err}</div>
+                <div class="message message-error" role="alert">{err}</div>
             }
 
-            <form {onsubmit}>
+            <form {onsubmit} aria-label="Login" aria-busy={loading.to_string()}>
                 <div class="form-group">
-                    <label for="username">{"Username"}</label>
+                    <label for="username">{"Username or email"}</label>
                     <input
                         type="text"
                         id="username"
@@ -120,6 +138,12 @@ pub fn login_form(props: &LoginFormProps) -> Html {
                 </button>
             </form>
 
+            <p class="auth-switch">
+                <button type="button" class="btn btn-link" onclick={on_recover}>
+                    {"Forgot your password? Use a recovery code"}
+                </button>
+            </p>
+
             <p class="auth-switch">
                 {"Don't have an account? "}
                 <a href="/register" class="auth-link">{"Register"}</a>
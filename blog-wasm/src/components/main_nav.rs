@@ -0,0 +1,132 @@
+//! Main navigation in the page header.
+
+use yew::prelude::*;
+
+use blog_shared::Role;
+
+use crate::api;
+use crate::components::NotificationBell;
+use crate::page::Page;
+
+/// Main navigation properties.
+#[derive(Properties, PartialEq)]
+pub struct MainNavProps {
+    /// Current page (marked with `aria-current`).
+    pub page: Page,
+    /// Whether a user is signed in.
+    pub is_authenticated: bool,
+    /// Signed-in user's name.
+    #[prop_or_default]
+    pub username: Option<String>,
+    /// Signed-in user's role (moderators and admins see the Admin link).
+    #[prop_or_default]
+    pub role: Role,
+    /// Changes whenever the unread message count may have changed.
+    #[prop_or_default]
+    pub unread_refresh: u32,
+    /// Callback with the page a link leads to.
+    pub on_navigate: Callback<Page>,
+    /// Callback for the Posts link (also clears the tag filter).
+    pub on_posts: Callback<()>,
+    /// Callback after the token was cleared on logout.
+    pub on_logout: Callback<()>,
+}
+
+/// Main navigation component.
+#[function_component(MainNav)]
+pub fn main_nav(props: &MainNavProps) -> Html {
+    let link = |target: Page| {
+        let on_navigate = props.on_navigate.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            on_navigate.emit(target.clone());
+        })
+    };
+    let current = |target: Page| (props.page == target).then_some("page");
+
+    let on_posts_click = {
+        let on_posts = props.on_posts.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            on_posts.emit(());
+        })
+    };
+
+    let on_logout_click = {
+        let on_logout = props.on_logout.clone();
+        Callback::from(move |_: MouseEvent| {
+            api::clear_token();
+            on_logout.emit(());
+        })
+    };
+
+    html! {
+        <nav aria-label="Main">
+            <a href="/" onclick={on_posts_click} aria-current={current(Page::Posts)}>
+                {"Posts"}
+            </a>
+            if props.is_authenticated {
+                <>
+                    <a
+                        href="/posts/new"
+                        onclick={link(Page::NewPost)}
+                        class="btn btn-secondary btn-sm"
+                        title="New post (n)"
+                        aria-keyshortcuts="n"
+                    >
+                        {"+ New Post"}
+                    </a>
+                    <a
+                        href="/settings"
+                        onclick={link(Page::Settings)}
+                        aria-current={current(Page::Settings)}
+                    >
+                        {"Settings"}
+                    </a>
+                    <a
+                        href="/trash"
+                        onclick={link(Page::Trash)}
+                        aria-current={current(Page::Trash)}
+                    >
+                        {"Trash"}
+                    </a>
+                    <NotificationBell
+                        refresh={props.unread_refresh}
+                        current={props.page == Page::Inbox}
+                        on_open={props.on_navigate.reform(|_| Page::Inbox)}
+                    />
+                    if props.role.can_moderate() {
+                        <a
+                            href="/admin"
+                            onclick={link(Page::Admin)}
+                            aria-current={current(Page::Admin)}
+                        >
+                            {"Admin"}
+                        </a>
+                    }
+                    <div class="user-info">
+                        if let Some(ref name) = props.username {
+                            <span class="username-greeting">{format!("Hi, {}", name)}</span>
+                        }
+                        <button type="button" class="btn btn-secondary" onclick={on_logout_click}>
+                            {"Logout"}
+                        </button>
+                    </div>
+                </>
+            } else {
+                <>
+                    <a href="/login" onclick={link(Page::Login)} aria-current={current(Page::Login)}>
+                        {"Login"}
+                    </a>
+                    <a
+                        href="/register"
+                        onclick={link(Page::Register)}
+                        aria-current={current(Page::Register)}
+                    >
+                        {"Register"}
+                    </a>
+                </>
+            }
+        </nav>
+    }
+}
@@ -0,0 +1,37 @@
+//! Markdown content component: post content rendered by the server.
+
+use yew::prelude::*;
+
+/// Markdown content properties.
+#[derive(Properties, PartialEq)]
+pub struct MarkdownContentProps {
+    /// Sanitized HTML rendered by the server.
+    pub html: AttrValue,
+    /// Markdown source, shown as plain paragraphs when no HTML is available.
+    #[prop_or_default]
+    pub fallback: AttrValue,
+    /// Extra class for the wrapper.
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+/// Markdown content component.
+///
+/// The server's renderer escapes raw HTML and drops unsafe URLs, so its
+/// output is inserted as-is. Posts cached before rendering existed carry no
+/// HTML and fall back to plain text.
+#[function_component(MarkdownContent)]
+pub fn markdown_content(props: &MarkdownContentProps) -> Html {
+    let class = classes!("markdown-body", props.class.clone());
+    if props.html.is_empty() {
+        return html! {
+            <div {class}>
+                {for props.fallback.split("\n\n").map(|paragraph| html! { <p>{paragraph}</p> })}
+            </div>
+        };
+    }
+
+    html! {
+        <div {class}>{Html::from_html_unchecked(props.html.clone())}</div>
+    }
+}
@@ -0,0 +1,174 @@
+//! Moderation queue section: newest comments across all posts.
+
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use blog_shared::CommentDto;
+
+use crate::api;
+use crate::components::{ConfirmDialog, ErrorBoundary};
+use crate::constants::ADMIN_PAGE_SIZE;
+
+/// Moderation queue properties.
+#[derive(Properties, PartialEq)]
+pub struct ModerationQueueProps {
+    /// Callback to open the post a comment belongs to.
+    pub on_open_post: Callback<String>,
+}
+
+/// Moderation queue component.
+#[function_component(ModerationQueue)]
+pub fn moderation_queue(props: &ModerationQueueProps) -> Html {
+    let comments = use_state(Vec::<CommentDto>::new);
+    let total = use_state(|| 0i64);
+    let load_error = use_state(|| None::<String>);
+    let error = use_state(|| None::<String>);
+    let reload = use_state(|| 0u32);
+    // Comment awaiting confirmation of its removal
+    let pending_remove = use_state(|| None::<i64>);
+
+    {
+        let comments = comments.clone();
+        let total = total.clone();
+        let load_error = load_error.clone();
+        use_effect_with(*reload, move |_| {
+            load_error.set(None);
+            spawn_local(async move {
+                match api::moderation_queue(ADMIN_PAGE_SIZE, 0).await {
+                    Ok(page) => {
+                        comments.set(page.comments);
+                        total.set(page.total.unwrap_or_default());
+                    }
+                    Err(e) => load_error.set(Some(format!("Failed to load queue: {}", e.message))),
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_retry = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(*reload + 1))
+    };
+
+    let on_load_more = {
+        let comments = comments.clone();
+        let total = total.clone();
+        let error = error.clone();
+        Callback::from(move |_: MouseEvent| {
+            let comments = comments.clone();
+            let total = total.clone();
+            let error = error.clone();
+            let offset = comments.len() as i64;
+            spawn_local(async move {
+                match api::moderation_queue(ADMIN_PAGE_SIZE, offset).await {
+                    Ok(page) => {
+                        let mut all = (*comments).clone();
+                        all.extend(page.comments);
+                        comments.set(all);
+                        total.set(page.total.unwrap_or_default());
+                    }
+                    Err(e) => error.set(Some(e.message)),
+                }
+            });
+        })
+    };
+
+    let on_remove = {
+        let comments = comments.clone();
+        let total = total.clone();
+        let error = error.clone();
+        let pending_remove = pending_remove.clone();
+        Callback::from(move |_| {
+            let Some(id) = *pending_remove else {
+                return;
+            };
+            pending_remove.set(None);
+            let comments = comments.clone();
+            let total = total.clone();
+            let error = error.clone();
+            error.set(None);
+            spawn_local(async move {
+                match api::remove_comment(id).await {
+                    Ok(()) => {
+                        comments.set(comments.iter().filter(|c| c.id != id).cloned().collect());
+                        total.set(*total - 1);
+                    }
+                    Err(e) => error.set(Some(e.message)),
+                }
+            });
+        })
+    };
+
+    let on_remove_cancel = {
+        let pending_remove = pending_remove.clone();
+        Callback::from(move |_| pending_remove.set(None))
+    };
+
+    let has_more = (comments.len() as i64) < *total;
+
+    html! {
+        <section class="admin-section" aria-labelledby="admin-queue-title">
+            <h3 id="admin-queue-title">{format!("Moderation queue ({})", *total)}</h3>
+            <p class="form-hint">{"Newest comments across all posts."}</p>
+            <ErrorBoundary error={(*load_error).clone()} {on_retry}>
+                if let Some(ref err) = *error {
+                    <div class="message message-error" role="alert">{err}</div>
+                }
+                <ul class="moderation-list">
+                    {for comments.iter().map(|comment| {
+                        let post_id = comment.post_public_id.clone();
+                        let id = comment.id;
+                        let on_open = {
+                            let on_open_post = props.on_open_post.clone();
+                            let post_id = post_id.clone();
+                            Callback::from(move |e: MouseEvent| {
+                                e.prevent_default();
+                                on_open_post.emit(post_id.clone());
+                            })
+                        };
+                        let on_click = {
+                            let pending_remove = pending_remove.clone();
+                            Callback::from(move |_: MouseEvent| pending_remove.set(Some(id)))
+                        };
+                        html! {
+                            <li key={id} class="moderation-item">
+                                <div class="comment-meta">
+                                    <span class="comment-author">{&comment.author_username}</span>
+                                    <span class="comment-date">
+                                        {comment.created_at.format("%B %d, %Y %H:%M").to_string()}
+                                    </span>
+                                    <a href={format!("/posts/{}", post_id)} onclick={on_open}>
+                                        {"on post"}
+                                    </a>
+                                </div>
+                                <p class="comment-content">{&comment.content}</p>
+                                <button
+                                    type="button"
+                                    class="btn btn-danger btn-sm"
+                                    onclick={on_click}
+                                    aria-label={format!("Remove comment by {}", comment.author_username)}
+                                >
+                                    {"Remove"}
+                                </button>
+                            </li>
+                        }
+                    })}
+                </ul>
+                if has_more {
+                    <button type="button" class="btn btn-link" onclick={on_load_more}>
+                        {"Load more"}
+                    </button>
+                }
+            </ErrorBoundary>
+            <ConfirmDialog
+                open={pending_remove.is_some()}
+                title="Remove comment?"
+                message="The comment will be deleted for everyone."
+                confirm_label="Remove"
+                on_confirm={on_remove}
+                on_cancel={on_remove_cancel}
+            />
+        </section>
+    }
+}
@@ -0,0 +1,285 @@
+//! Moderation rules: keyword/regex rules that flag or block new content.
+
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+use yew::prelude::*;
+
+use blog_shared::{
+    CreateModerationRuleRequest, ModerationRuleDto, RuleAction, RuleKind,
+    UpdateModerationRuleRequest,
+};
+
+use crate::api;
+use crate::components::ErrorBoundary;
+
+/// Rule kinds offered in the form, with their labels.
+const KINDS: &[(RuleKind, &str)] = &[(RuleKind::Keyword, "Keywords"), (RuleKind::Regex, "Regex")];
+
+/// Rule actions offered in the form, with their labels.
+const ACTIONS: &[(RuleAction, &str)] = &[
+    (RuleAction::Flag, "Flag for review"),
+    (RuleAction::Block, "Block"),
+];
+
+/// Moderation rules component.
+#[function_component(ModerationRules)]
+pub fn moderation_rules() -> Html {
+    let rules = use_state(Vec::<ModerationRuleDto>::new);
+    let load_error = use_state(|| None::<String>);
+    let error = use_state(|| None::<String>);
+    let reload = use_state(|| 0u32);
+    let name = use_state(String::new);
+    let kind = use_state(|| RuleKind::Keyword);
+    let pattern = use_state(String::new);
+    let action = use_state(|| RuleAction::Flag);
+    let saving = use_state(|| false);
+
+    {
+        let rules = rules.clone();
+        let load_error = load_error.clone();
+        use_effect_with(*reload, move |_| {
+            load_error.set(None);
+            spawn_local(async move {
+                match api::list_moderation_rules().await {
+                    Ok(list) => rules.set(list.rules),
+                    Err(e) => load_error.set(Some(format!("Failed to load rules: {}", e.message))),
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_retry = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(*reload + 1))
+    };
+
+    let on_name_input = {
+        let name = name.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            name.set(input.value());
+        })
+    };
+
+    let on_kind_change = {
+        let kind = kind.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Ok(value) = select.value().parse() {
+                kind.set(value);
+            }
+        })
+    };
+
+    let on_pattern_input = {
+        let pattern = pattern.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlTextAreaElement = e.target_unchecked_into();
+            pattern.set(input.value());
+        })
+    };
+
+    let on_action_change = {
+        let action = action.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Ok(value) = select.value().parse() {
+                action.set(value);
+            }
+        })
+    };
+
+    let onsubmit = {
+        let rules = rules.clone();
+        let error = error.clone();
+        let name = name.clone();
+        let kind = kind.clone();
+        let pattern = pattern.clone();
+        let action = action.clone();
+        let saving = saving.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let req = CreateModerationRuleRequest {
+                name: (*name).clone(),
+                kind: *kind,
+                pattern: (*pattern).clone(),
+                action: *action,
+                enabled: true,
+            };
+            let rules = rules.clone();
+            let error = error.clone();
+            let name = name.clone();
+            let pattern = pattern.clone();
+            let saving = saving.clone();
+
+            saving.set(true);
+            error.set(None);
+            spawn_local(async move {
+                match api::create_moderation_rule(req).await {
+                    Ok(rule) => {
+                        let mut all = (*rules).clone();
+                        all.push(rule);
+                        rules.set(all);
+                        name.set(String::new());
+                        pattern.set(String::new());
+                    }
+                    Err(e) => error.set(Some(e.message)),
+                }
+                saving.set(false);
+            });
+        })
+    };
+
+    let on_toggle = {
+        let rules = rules.clone();
+        let error = error.clone();
+        Callback::from(move |(id, enabled): (i64, bool)| {
+            let rules = rules.clone();
+            let error = error.clone();
+            error.set(None);
+            let req = UpdateModerationRuleRequest {
+                enabled: Some(enabled),
+                ..Default::default()
+            };
+            spawn_local(async move {
+                match api::update_moderation_rule(id, req).await {
+                    Ok(updated) => {
+                        let list = rules
+                            .iter()
+                            .map(|r| {
+                                if r.id == id {
+                                    updated.clone()
+                                } else {
+                                    r.clone()
+                                }
+                            })
+                            .collect();
+                        rules.set(list);
+                    }
+                    Err(e) => error.set(Some(e.message)),
+                }
+            });
+        })
+    };
+
+    let on_delete = {
+        let rules = rules.clone();
+        let error = error.clone();
+        Callback::from(move |id: i64| {
+            let rules = rules.clone();
+            let error = error.clone();
+            error.set(None);
+            spawn_local(async move {
+                match api::delete_moderation_rule(id).await {
+                    Ok(()) => rules.set(rules.iter().filter(|r| r.id != id).cloned().collect()),
+                    Err(e) => error.set(Some(e.message)),
+                }
+            });
+        })
+    };
+
+    let pattern_hint = match *kind {
+        RuleKind::Keyword => {
+            "Words or phrases separated by commas or new lines; matched as whole words, ignoring case."
+        }
+        RuleKind::Regex => "A regular expression; start with (?i) to ignore case.",
+    };
+
+    html! {
+        <section class="admin-section" aria-labelledby="admin-rules-title">
+            <h3 id="admin-rules-title">{"Moderation rules"}</h3>
+            <ErrorBoundary error={(*load_error).clone()} {on_retry}>
+                if let Some(ref err) = *error {
+                    <div class="message message-error" role="alert">{err}</div>
+                }
+                <div class="admin-table-wrapper">
+                    <table class="admin-table">
+                        <thead>
+                            <tr>
+                                <th scope="col">{"Rule"}</th>
+                                <th scope="col">{"Pattern"}</th>
+                                <th scope="col">{"Action"}</th>
+                                <th scope="col">{"Hit rate"}</th>
+                                <th scope="col">{"Enabled"}</th>
+                                <th scope="col">{"Actions"}</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {for rules.iter().map(|rule| {
+                                let id = rule.id;
+                                let onchange = {
+                                    let on_toggle = on_toggle.clone();
+                                    let enabled = !rule.enabled;
+                                    Callback::from(move |_: Event| on_toggle.emit((id, enabled)))
+                                };
+                                let on_delete_click = {
+                                    let on_delete = on_delete.clone();
+                                    Callback::from(move |_: MouseEvent| on_delete.emit(id))
+                                };
+                                html! {
+                                    <tr key={id}>
+                                        <td>{&rule.name}</td>
+                                        <td><code class="rule-pattern">{format!("{}: {}", rule.kind, rule.pattern)}</code></td>
+                                        <td>{rule.action.as_str()}</td>
+                                        <td title={format!("{} of {} checks", rule.hit_count, rule.checked_count)}>
+                                            {format!("{:.1}%", rule.hit_rate * 100.0)}
+                                        </td>
+                                        <td>
+                                            <input
+                                                type="checkbox"
+                                                checked={rule.enabled}
+                                                {onchange}
+                                                aria-label={format!("Enable rule {}", rule.name)}
+                                            />
+                                        </td>
+                                        <td>
+                                            <button
+                                                type="button"
+                                                class="btn btn-danger btn-sm"
+                                                onclick={on_delete_click}
+                                                aria-label={format!("Delete rule {}", rule.name)}
+                                            >
+                                                {"Delete"}
+                                            </button>
+                                        </td>
+                                    </tr>
+                                }
+                            })}
+                        </tbody>
+                    </table>
+                </div>
+                <form class="rule-form" {onsubmit} aria-busy={saving.to_string()}>
+                    <div class="form-group">
+                        <label for="rule-name">{"Name"}</label>
+                        <input id="rule-name" type="text" value={(*name).clone()} oninput={on_name_input} required=true />
+                    </div>
+                    <div class="form-group">
+                        <label for="rule-kind">{"Match"}</label>
+                        <select id="rule-kind" onchange={on_kind_change}>
+                            {for KINDS.iter().map(|(value, label)| html! {
+                                <option value={value.as_str()} selected={*kind == *value}>{*label}</option>
+                            })}
+                        </select>
+                    </div>
+                    <div class="form-group">
+                        <label for="rule-pattern">{"Pattern"}</label>
+                        <textarea id="rule-pattern" rows="3" value={(*pattern).clone()} oninput={on_pattern_input} required=true />
+                        <p class="form-hint">{pattern_hint}</p>
+                    </div>
+                    <div class="form-group">
+                        <label for="rule-action">{"Action"}</label>
+                        <select id="rule-action" onchange={on_action_change}>
+                            {for ACTIONS.iter().map(|(value, label)| html! {
+                                <option value={value.as_str()} selected={*action == *value}>{*label}</option>
+                            })}
+                        </select>
+                    </div>
+                    <button type="submit" class="btn btn-primary" disabled={*saving}>
+                        {"Add rule"}
+                    </button>
+                </form>
+            </ErrorBoundary>
+        </section>
+    }
+}
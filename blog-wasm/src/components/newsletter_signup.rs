@@ -0,0 +1,97 @@
+//! Newsletter signup form for the weekly digest.
+
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::api;
+use crate::constants::NEWSLETTER_CTA_EXPERIMENT;
+use crate::experiments::Variant;
+
+/// Newsletter signup component.
+///
+/// The server answers the same way for new and existing subscribers, so the
+/// form always asks to check the inbox for the confirmation email.
+#[function_component(NewsletterSignup)]
+pub fn newsletter_signup() -> Html {
+    let email = use_state(String::new);
+    let error = use_state(|| None::<String>);
+    let sent = use_state(|| false);
+    let loading = use_state(|| false);
+
+    let on_input = {
+        let email = email.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            email.set(input.value());
+        })
+    };
+
+    let onsubmit = {
+        let email = email.clone();
+        let error = error.clone();
+        let sent = sent.clone();
+        let loading = loading.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let address = email.trim().to_string();
+            if address.is_empty() {
+                return;
+            }
+            let error = error.clone();
+            let sent = sent.clone();
+            let loading = loading.clone();
+
+            loading.set(true);
+            error.set(None);
+            spawn_local(async move {
+                match api::subscribe_newsletter(&address).await {
+                    Ok(_) => sent.set(true),
+                    Err(e) => error.set(Some(e.message)),
+                }
+                loading.set(false);
+            });
+        })
+    };
+
+    html! {
+        <section class="newsletter-signup" aria-labelledby="newsletter-title">
+            <h2 id="newsletter-title">{"Weekly digest"}</h2>
+            if *sent {
+                <div class="message message-success" role="status">
+                    {"Almost done: check your inbox to confirm your subscription."}
+                </div>
+            } else {
+                <Variant experiment={NEWSLETTER_CTA_EXPERIMENT} variant="control" control=true>
+                    <p>{"Get the week's top posts by email."}</p>
+                </Variant>
+                <Variant experiment={NEWSLETTER_CTA_EXPERIMENT} variant="short">
+                    <p>{"One email a week with the best posts. No spam."}</p>
+                </Variant>
+                if let Some(ref err) = *error {
+                    <div class="message message-error" role="alert">{err}</div>
+                }
+                <form class="newsletter-form" {onsubmit} aria-busy={loading.to_string()}>
+                    <input
+                        type="email"
+                        value={(*email).clone()}
+                        oninput={on_input}
+                        placeholder="you@example.com"
+                        aria-label="Email address"
+                        required=true
+                        disabled={*loading}
+                    />
+                    <button type="submit" class="btn btn-primary" disabled={*loading}>
+                        {"Subscribe"}
+                    </button>
+                </form>
+            }
+            <p class="newsletter-feeds">
+                {"Prefer a feed reader? "}
+                <a href={api::rss_feed_url()}>{"RSS"}</a>
+                {" · "}
+                <a href={api::atom_feed_url()}>{"Atom"}</a>
+            </p>
+        </section>
+    }
+}
@@ -0,0 +1,79 @@
+//! Notification bell with the unread private message count.
+
+use gloo_timers::callback::Interval;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::api;
+use crate::constants::UNREAD_POLL_INTERVAL_MS;
+
+/// Notification bell properties.
+#[derive(Properties, PartialEq)]
+pub struct NotificationBellProps {
+    /// Changes whenever the count may have changed (e.g. messages were read).
+    #[prop_or_default]
+    pub refresh: u32,
+    /// Whether the inbox is the current page.
+    #[prop_or_default]
+    pub current: bool,
+    /// Callback to open the inbox.
+    pub on_open: Callback<()>,
+}
+
+/// Notification bell component.
+///
+/// Fetches the count on mount and on `refresh` changes, then polls every
+/// `UNREAD_POLL_INTERVAL_MS`; failures keep the last known count.
+#[function_component(NotificationBell)]
+pub fn notification_bell(props: &NotificationBellProps) -> Html {
+    let unread = use_state(|| 0i64);
+
+    {
+        let unread = unread.clone();
+        use_effect_with(props.refresh, move |_| {
+            let load = move || {
+                let unread = unread.clone();
+                spawn_local(async move {
+                    if let Ok(count) = api::unread_count().await {
+                        unread.set(count.unread);
+                    }
+                });
+            };
+            load();
+            let interval = Interval::new(UNREAD_POLL_INTERVAL_MS, load);
+            move || drop(interval)
+        });
+    }
+
+    let onclick = {
+        let on_open = props.on_open.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            on_open.emit(());
+        })
+    };
+
+    let label = match *unread {
+        0 => "Messages".to_string(),
+        1 => "Messages, 1 unread".to_string(),
+        n => format!("Messages, {} unread", n),
+    };
+
+    html! {
+        <a
+            href="/messages"
+            class="notification-bell"
+            {onclick}
+            aria-label={label.clone()}
+            title={label}
+            aria-current={props.current.then_some("page")}
+        >
+            <span aria-hidden="true">{"🔔"}</span>
+            if *unread > 0 {
+                <span class="notification-badge" aria-hidden="true">
+                    {if *unread > 99 { "99+".to_string() } else { unread.to_string() }}
+                </span>
+            }
+        </a>
+    }
+}
@@ -0,0 +1,110 @@
+//! Offline banner component showing connectivity and sync status.
+
+use gloo_events::EventListener;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::window;
+use yew::prelude::*;
+
+use crate::offline::{self, ReplayReport};
+
+/// Offline banner properties.
+#[derive(Properties, PartialEq)]
+pub struct OfflineBannerProps {
+    /// Callback after queued actions were replayed.
+    #[prop_or_default]
+    pub on_synced: Option<Callback<ReplayReport>>,
+}
+
+/// Offline banner component.
+///
+/// Shows a banner while offline and replays the offline queue when the
+/// connection returns (and once on mount, for actions left from a prior visit).
+#[function_component(OfflineBanner)]
+pub fn offline_banner(props: &OfflineBannerProps) -> Html {
+    let online = use_state(offline::is_online);
+    let notice = use_state(|| None::<String>);
+
+    let sync = {
+        let notice = notice.clone();
+        let on_synced = props.on_synced.clone();
+        Callback::from(move |_: ()| {
+            let notice = notice.clone();
+            let on_synced = on_synced.clone();
+            spawn_local(async move {
+                match offline::replay().await {
+                    Ok(report) if report.is_empty() => {}
+                    Ok(report) => {
+                        notice.set(Some(describe(&report)));
+                        if let Some(ref cb) = on_synced {
+                            cb.emit(report);
+                        }
+                    }
+                    Err(e) => notice.set(Some(format!("Sync failed: {}", e.message))),
+                }
+            });
+        })
+    };
+
+    // Track connectivity changes
+    {
+        let online = online.clone();
+        let sync = sync.clone();
+        use_effect_with((), move |_| {
+            if offline::is_online() {
+                sync.emit(());
+            }
+
+            let listeners = window().map(|win| {
+                let on_online = {
+                    let online = online.clone();
+                    EventListener::new(&win, "online", move |_| {
+                        online.set(true);
+                        sync.emit(());
+                    })
+                };
+                let on_offline = EventListener::new(&win, "offline", move |_| {
+                    online.set(false);
+                });
+                (on_online, on_offline)
+            });
+
+            move || drop(listeners)
+        });
+    }
+
+    let on_dismiss = {
+        let notice = notice.clone();
+        Callback::from(move |_: MouseEvent| notice.set(None))
+    };
+
+    html! {
+        <>
+            if !*online {
+                <div class="offline-banner" role="status">
+                    {"You're offline. Posts you've viewed are still available, and changes will sync when you reconnect."}
+                </div>
+            }
+            if let Some(ref text) = *notice {
+                <div class="offline-banner offline-banner-synced" role="status">
+                    <span>{text}</span>
+                    <button class="btn btn-secondary btn-sm" onclick={on_dismiss}>{"Dismiss"}</button>
+                </div>
+            }
+        </>
+    }
+}
+
+/// Summarizes a replay for the user.
+fn describe(report: &ReplayReport) -> String {
+    let mut parts = vec![format!("Synced {} offline change(s)", report.applied)];
+    if report.conflicts > 0 {
+        parts.push(format!(
+            "{} skipped because the post changed on the server",
+            report.conflicts
+        ));
+    }
+    if report.failed > 0 {
+        parts.push(format!("{} rejected by the server", report.failed));
+    }
+    parts.join("; ")
+}
@@ -0,0 +1,106 @@
+//! Organization profile page: its members and posts.
+
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use blog_shared::{OrgProfileResponse, OrgRole};
+
+use crate::api;
+use crate::components::{ErrorBoundary, UserAvatar};
+use crate::constants::ORG_POSTS_PAGE_SIZE;
+
+/// Organization page properties.
+#[derive(Properties, PartialEq)]
+pub struct OrgPageProps {
+    /// Slug of the organization to show.
+    pub slug: String,
+    /// Callback with the public ID of a post to open.
+    pub on_open: Callback<String>,
+}
+
+/// Organization page component.
+#[function_component(OrgPage)]
+pub fn org_page(props: &OrgPageProps) -> Html {
+    let profile = use_state(|| None::<OrgProfileResponse>);
+    let load_error = use_state(|| None::<String>);
+    let reload = use_state(|| 0u32);
+
+    {
+        let profile = profile.clone();
+        let load_error = load_error.clone();
+        use_effect_with((props.slug.clone(), *reload), move |(slug, _)| {
+            let slug = slug.clone();
+            profile.set(None);
+            load_error.set(None);
+            spawn_local(async move {
+                match api::get_org(&slug, ORG_POSTS_PAGE_SIZE, 0).await {
+                    Ok(loaded) => profile.set(Some(loaded)),
+                    Err(e) => {
+                        load_error.set(Some(format!("Failed to load organization: {}", e.message)))
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_retry = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(*reload + 1))
+    };
+
+    html! {
+        <div class="org-page">
+            <ErrorBoundary error={(*load_error).clone()} {on_retry}>
+                if let Some(ref profile) = *profile {
+                    <h2>{&profile.org.name}</h2>
+                    <p class="org-slug">{format!("@{}", profile.org.slug)}</p>
+                    if let Some(ref bio) = profile.org.bio {
+                        <p class="org-bio">{bio}</p>
+                    }
+                    <h3>{"Members"}</h3>
+                    <ul class="org-members">
+                        {for profile.members.iter().map(|member| html! {
+                            <li key={member.public_id.clone()} class="org-member">
+                                <UserAvatar user_id={member.public_id.clone()} username={member.username.clone()} />
+                                {&member.username}
+                                if member.role == OrgRole::Owner {
+                                    <span class="org-role">{"owner"}</span>
+                                }
+                            </li>
+                        })}
+                    </ul>
+                    <h3>{format!("Posts ({})", profile.total)}</h3>
+                    if profile.posts.is_empty() {
+                        <p class="empty-state">{"No posts yet."}</p>
+                    }
+                    <ul class="org-posts">
+                        {for profile.posts.iter().map(|post| {
+                            let onclick = props.on_open.reform({
+                                let id = post.public_id.clone();
+                                move |e: MouseEvent| {
+                                    e.prevent_default();
+                                    id.clone()
+                                }
+                            });
+                            html! {
+                                <li key={post.public_id.clone()} class="org-post">
+                                    <a href="#" class="org-post-title" {onclick}>{&post.title}</a>
+                                        <span class="org-post-meta">
+                                            {format!(
+                                                "by {} · {}",
+                                                post.author_username,
+                                                post.created_at.format("%B %d, %Y"),
+                                            )}
+                                    </span>
+                                </li>
+                            }
+                        })}
+                    </ul>
+                } else if load_error.is_none() {
+                    <p aria-busy="true">{"Loading..."}</p>
+                }
+            </ErrorBoundary>
+        </div>
+    }
+}
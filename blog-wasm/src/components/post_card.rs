@@ -1,10 +1,10 @@
 //! Post card component for displaying a single post.
 
-use web_sys::window;
 use yew::prelude::*;
 
-use blog_shared::PostDto;
+use blog_shared::{PostDto, truncate};
 
+use crate::components::{ConfirmDialog, MarkdownContent, ShareMenu, TagChips, UserAvatar};
 use crate::constants::MAX_CONTENT_LENGTH;
 
 /// Post card properties.
@@ -17,10 +17,19 @@ pub struct PostCardProps {
     pub is_owner: bool,
     /// Callback when edit button is clicked.
     #[prop_or_default]
-    pub on_edit: Option<Callback<i64>>,
+    pub on_edit: Option<Callback<String>>,
     /// Callback when delete button is clicked.
     #[prop_or_default]
-    pub on_delete: Option<Callback<i64>>,
+    pub on_delete: Option<Callback<String>>,
+    /// Callback when the title is clicked to open the post.
+    #[prop_or_default]
+    pub on_open: Option<Callback<String>>,
+    /// Callback when a tag chip is clicked.
+    #[prop_or_default]
+    pub on_tag: Option<Callback<String>>,
+    /// Whether the card is selected via keyboard navigation.
+    #[prop_or_default]
+    pub selected: bool,
 }
 
 /// Post card component.
@@ -28,30 +37,48 @@ pub struct PostCardProps {
 pub fn post_card(props: &PostCardProps) -> Html {
     let post = &props.post;
     let expanded = use_state(|| false);
+    let confirming = use_state(|| false);
 
     let on_edit_click = {
-        let post_id = post.id;
+        let post_id = post.public_id.clone();
         let on_edit = props.on_edit.clone();
         Callback::from(move |_: MouseEvent| {
             if let Some(ref cb) = on_edit {
-                cb.emit(post_id);
+                cb.emit(post_id.clone());
             }
         })
     };
 
+    // Deleting asks for confirmation first
     let on_delete_click = {
-        let post_id = post.id;
+        let confirming = confirming.clone();
+        Callback::from(move |_: MouseEvent| confirming.set(true))
+    };
+
+    let on_delete_confirm = {
+        let post_id = post.public_id.clone();
         let on_delete = props.on_delete.clone();
-        Callback::from(move |_: MouseEvent| {
+        let confirming = confirming.clone();
+        Callback::from(move |_| {
+            confirming.set(false);
             if let Some(ref cb) = on_delete {
-                // Show confirmation dialog before deleting
-                if let Some(win) = window()
-                    && win
-                        .confirm_with_message("Are you sure you want to delete this post?")
-                        .unwrap_or(false)
-                {
-                    cb.emit(post_id);
-                }
+                cb.emit(post_id.clone());
+            }
+        })
+    };
+
+    let on_delete_cancel = {
+        let confirming = confirming.clone();
+        Callback::from(move |_| confirming.set(false))
+    };
+
+    let on_open_click = {
+        let post_id = post.public_id.clone();
+        let on_open = props.on_open.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            if let Some(ref cb) = on_open {
+                cb.emit(post_id.clone());
             }
         })
     };
@@ -65,52 +92,95 @@ pub fn post_card(props: &PostCardProps) -> Html {
     };
 
     let formatted_date = post.created_at.format("%B %d, %Y").to_string();
-    let needs_truncation = post.content.len() > MAX_CONTENT_LENGTH;
-    let display_content = if *expanded || !needs_truncation {
-        post.content.clone()
-    } else {
-        truncate_content(&post.content, MAX_CONTENT_LENGTH)
-    };
+    let truncated = truncate(&post.content, MAX_CONTENT_LENGTH);
+    let needs_truncation = truncated != post.content;
+    // Excerpts are plain text; the full post is shown rendered
+    let excerpt = (!*expanded && needs_truncation).then_some(truncated);
+    // Search results show the matching passage (escaped by the server)
+    let snippet = post.snippet.clone().filter(|_| !*expanded);
+    let expandable = needs_truncation || post.snippet.is_some();
 
     html! {
-        <article class="post-card">
+        <article
+            class={classes!("post-card", props.selected.then_some("post-card-selected"))}
+            id={format!("post-card-{}", post.public_id)}
+            tabindex="-1"
+            aria-labelledby={format!("post-title-{}", post.public_id)}
+        >
             <header class="post-card-header">
-                <h2 class="post-card-title">{&post.title}</h2>
+                <h2 class="post-card-title" id={format!("post-title-{}", post.public_id)}>
+                    if props.on_open.is_some() {
+                        <a href={format!("/posts/{}", post.public_id)} onclick={on_open_click}>{&post.title}</a>
+                    } else {
+                        {&post.title}
+                    }
+                </h2>
                 <div class="post-card-meta">
-                    <span class="post-card-author">{"by "}{&post.author_username}</span>
+                    <span class="post-card-author">
+                        <UserAvatar user_id={post.author_public_id.clone()} username={post.author_username.clone()} />
+                        {"by "}{post.byline()}
+                    </span>
                     <span class="post-card-date">{formatted_date}</span>
                 </div>
             </header>
-            <div class="post-card-content">
-                <p>{display_content}</p>
-            </div>
+            if let Some(snippet) = snippet {
+                <div class="post-card-content post-card-snippet">
+                    <p>{Html::from_html_unchecked(snippet.into())}</p>
+                </div>
+            } else if let Some(excerpt) = excerpt {
+                <div class="post-card-content">
+                    <p>{excerpt}</p>
+                </div>
+            } else {
+                <MarkdownContent
+                    class="post-card-content"
+                    html={post.content_html.clone()}
+                    fallback={post.content.clone()}
+                />
+            }
+            <TagChips tags={post.tags.clone()} on_select={props.on_tag.clone()} />
             <footer class="post-card-footer">
-                if needs_truncation {
-                    <a href="#" class="btn btn-link" onclick={on_toggle_expand}>
+                if expandable {
+                    <a
+                        href="#"
+                        class="btn btn-link"
+                        onclick={on_toggle_expand}
+                        aria-expanded={expanded.to_string()}
+                    >
                         {if *expanded { "Show less" } else { "Read more" }}
                     </a>
                 }
-                if props.is_owner {
-                    <div class="post-card-actions">
-                        <button class="btn btn-secondary btn-sm" onclick={on_edit_click}>
+                <div class="post-card-actions">
+                    <ShareMenu post_id={post.public_id.clone()} title={post.title.clone()} />
+                    if props.is_owner {
+                        <button
+                            type="button"
+                            class="btn btn-secondary btn-sm"
+                            onclick={on_edit_click}
+                            aria-label={format!("Edit post: {}", post.title)}
+                        >
                             {"Edit"}
                         </button>
-                        <button class="btn btn-danger btn-sm" onclick={on_delete_click}>
+                        <button
+                            type="button"
+                            class="btn btn-danger btn-sm"
+                            onclick={on_delete_click}
+                            aria-label={format!("Delete post: {}", post.title)}
+                        >
                             {"Delete"}
                         </button>
-                    </div>
+                    }
+                </div>
+                if props.is_owner {
+                    <ConfirmDialog
+                        open={*confirming}
+                        title="Delete post?"
+                        message={format!("\"{}\" will be moved to your trash, where you can restore it.", post.title)}
+                        on_confirm={on_delete_confirm}
+                        on_cancel={on_delete_cancel}
+                    />
                 }
             </footer>
         </article>
     }
 }
-
-/// Truncates content to a maximum length, adding ellipsis if needed.
-fn truncate_content(content: &str, max_len: usize) -> String {
-    if content.len() <= max_len {
-        content.to_string()
-    } else {
-        let truncated: String = content.chars().take(max_len).collect();
-        format!("{}...", truncated.trim_end())
-    }
-}
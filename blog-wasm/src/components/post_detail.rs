@@ -0,0 +1,213 @@
+//! Post detail component: full post with its comments.
+
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use blog_shared::{PostAuthor, PostDto};
+
+use super::link_previews::host;
+use crate::api::{self, ApiError};
+use crate::components::{
+    CommentList, LanguageSwitcher, LinkPreviews, LoadError, MarkdownContent, PostCardSkeletonList,
+    ShareMenu, TagChips, UserAvatar,
+};
+use crate::location::{post_permalink, set_canonical_link};
+
+/// Post detail properties.
+#[derive(Properties, PartialEq)]
+pub struct PostDetailProps {
+    /// Public ID of the post to display.
+    pub post_id: String,
+    /// Current user's public ID (if authenticated).
+    #[prop_or_default]
+    pub current_user_public_id: Option<String>,
+    /// Current user's name (if authenticated).
+    #[prop_or_default]
+    pub current_username: Option<String>,
+    /// Callback for the back link.
+    pub on_back: Callback<()>,
+    /// Callback when the author clicks edit.
+    #[prop_or_default]
+    pub on_edit: Option<Callback<String>>,
+    /// Callback when a tag chip is clicked.
+    #[prop_or_default]
+    pub on_tag: Option<Callback<String>>,
+    /// Callback with the post ID to open reader view.
+    #[prop_or_default]
+    pub on_reader: Option<Callback<String>>,
+    /// Callback with the public ID of a translation to open.
+    #[prop_or_default]
+    pub on_open: Option<Callback<String>>,
+    /// Callback with the slug of the post's organization to open.
+    #[prop_or_default]
+    pub on_org: Option<Callback<String>>,
+}
+
+/// Post detail component.
+#[function_component(PostDetail)]
+pub fn post_detail(props: &PostDetailProps) -> Html {
+    let post_id = props.post_id.clone();
+    let post = use_state(|| None::<PostDto>);
+    let load_error = use_state(|| None::<ApiError>);
+    let reload = use_state(|| 0u32);
+
+    {
+        let post = post.clone();
+        let load_error = load_error.clone();
+        use_effect_with(
+            (
+                post_id.clone(),
+                *reload,
+                props.current_user_public_id.clone(),
+            ),
+            move |(post_id, _, _)| {
+                let post_id = post_id.clone();
+                post.set(None);
+                load_error.set(None);
+                spawn_local(async move {
+                    match api::get_post(&post_id).await {
+                        Ok(loaded) => post.set(Some(loaded)),
+                        Err(e) => load_error.set(Some(e)),
+                    }
+                });
+                || ()
+            },
+        );
+    }
+
+    // Search engines should index syndicated posts at their original address
+    {
+        let canonical = post.as_ref().map(|post| {
+            post.canonical_url
+                .clone()
+                .or_else(|| post_permalink(&post.public_id))
+        });
+        use_effect_with(canonical, |canonical| {
+            set_canonical_link(canonical.clone().flatten().as_deref());
+            || set_canonical_link(None)
+        });
+    }
+
+    let on_retry = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(*reload + 1))
+    };
+
+    let on_back_click = {
+        let on_back = props.on_back.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            on_back.emit(());
+        })
+    };
+
+    let on_edit_click = {
+        let on_edit = props.on_edit.clone();
+        let post_id = post_id.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(ref cb) = on_edit {
+                cb.emit(post_id.clone());
+            }
+        })
+    };
+
+    let on_reader_click = {
+        let on_reader = props.on_reader.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(ref cb) = on_reader {
+                cb.emit(post_id.clone());
+            }
+        })
+    };
+
+    html! {
+        <div class="post-detail">
+            <a href="/" class="btn btn-link" onclick={on_back_click}>{"← Back to posts"}</a>
+            <LoadError
+                error={(*load_error).clone()}
+                context="Failed to load post"
+                on_back={props.on_back.clone()}
+                {on_retry}
+            >
+                if let Some(ref post) = *post {
+                    <article class="post-card post-detail-article" lang={post.lang.clone()} aria-labelledby="post-detail-title">
+                        <header class="post-card-header">
+                            <h2 class="post-card-title" id="post-detail-title">{&post.title}</h2>
+                            <div class="post-card-meta">
+                                <span class="post-card-author">
+                                    <UserAvatar user_id={post.author_public_id.clone()} username={post.author_username.clone()} />
+                                    {"by "}
+                                    if let (Some(PostAuthor::Org { slug, name, .. }), Some(on_org)) = (&post.author, &props.on_org) {
+                                        <a
+                                            href="#"
+                                            class="post-card-org"
+                                            onclick={on_org.reform({
+                                                let slug = slug.clone();
+                                                move |e: MouseEvent| {
+                                                    e.prevent_default();
+                                                    slug.clone()
+                                                }
+                                            })}
+                                        >
+                                            {name}
+                                        </a>
+                                    } else {
+                                        {post.byline()}
+                                    }
+                                </span>
+                                <span class="post-card-date">
+                                    {post.created_at.format("%B %d, %Y").to_string()}
+                                </span>
+                            </div>
+                            if let Some(ref on_open) = props.on_open {
+                                <LanguageSwitcher
+                                    post_id={post.public_id.clone()}
+                                    lang={post.lang.clone()}
+                                    on_select={on_open.clone()}
+                                />
+                            }
+                        </header>
+                        <MarkdownContent
+                            class="post-card-content"
+                            html={post.content_html.clone()}
+                            fallback={post.content.clone()}
+                        />
+                        if let Some(ref source) = post.original_source {
+                            <p class="post-original-source">
+                                {"Originally published at "}
+                                <a href={source.clone()} rel="noopener noreferrer" target="_blank">
+                                    {host(source)}
+                                </a>
+                            </p>
+                        }
+                        <LinkPreviews previews={post.link_previews.clone()} />
+                        <TagChips tags={post.tags.clone()} on_select={props.on_tag.clone()} />
+                        <footer class="post-card-footer">
+                            <div class="post-card-actions">
+                                if props.on_reader.is_some() {
+                                    <button type="button" class="btn btn-secondary btn-sm" onclick={on_reader_click}>
+                                        {"Reader view"}
+                                    </button>
+                                }
+                                <ShareMenu post_id={post.public_id.clone()} title={post.title.clone()} />
+                                if post.is_owner && props.on_edit.is_some() {
+                                    <button type="button" class="btn btn-secondary btn-sm" onclick={on_edit_click}>
+                                        {"Edit"}
+                                    </button>
+                                }
+                            </div>
+                        </footer>
+                    </article>
+                    <CommentList
+                        post_id={post.public_id.clone()}
+                        post_author_id={post.author_public_id.clone()}
+                        current_user_id={props.current_user_public_id.clone()}
+                        current_username={props.current_username.clone()}
+                    />
+                } else {
+                    <PostCardSkeletonList count={1} />
+                }
+            </LoadError>
+        </div>
+    }
+}
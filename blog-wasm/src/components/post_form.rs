@@ -4,58 +4,90 @@ use wasm_bindgen_futures::spawn_local;
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
 
+use blog_shared::constants::MAX_TAGS_PER_POST;
 use blog_shared::{CreatePostRequest, PostDto, UpdatePostRequest};
 
 use crate::api;
+use crate::components::{
+    ContentEditor, EditingPresence, ErrorBoundary, PostFormSkeleton, RevisionHistory,
+};
+use crate::offline::{self, PendingAction};
 
 /// Post form properties.
 #[derive(Properties, PartialEq)]
 pub struct PostFormProps {
-    /// Post ID to edit (None for create mode).
+    /// Public ID of the post to edit (None for create mode).
     #[prop_or_default]
-    pub post_id: Option<i64>,
+    pub post_id: Option<String>,
     /// Callback when form is submitted successfully.
     pub on_success: Callback<PostDto>,
     /// Callback when cancel is clicked.
     #[prop_or_default]
     pub on_cancel: Option<Callback<()>>,
+    /// Public ID of the signed-in user, left out of the "also editing"
+    /// notice.
+    #[prop_or_default]
+    pub current_user_public_id: Option<String>,
 }
 
 /// Post form component.
 #[function_component(PostForm)]
 pub fn post_form(props: &PostFormProps) -> Html {
-    let post_id = props.post_id;
+    let post_id = props.post_id.clone();
     let is_edit = post_id.is_some();
 
     let title = use_state(String::new);
     let content = use_state(String::new);
+    // Comma-separated, as typed
+    let tags = use_state(String::new);
+    // Syndication links, empty when unset
+    let canonical_url = use_state(String::new);
+    let original_source = use_state(String::new);
     let error = use_state(|| None::<String>);
     let loading = use_state(|| false);
     let fetching = use_state(|| false);
+    let load_error = use_state(|| None::<String>);
+    let reload = use_state(|| 0u32);
+    let notice = use_state(|| None::<String>);
+    // Version of the post being edited, used for offline conflict detection
+    let base_updated_at = use_state(|| None);
 
-    // Fetch post data when editing
+    // Fetch post data when editing (again on retry)
     {
         let title = title.clone();
         let content = content.clone();
-        let error = error.clone();
+        let tags = tags.clone();
+        let canonical_url = canonical_url.clone();
+        let original_source = original_source.clone();
+        let load_error = load_error.clone();
         let fetching = fetching.clone();
+        let base_updated_at = base_updated_at.clone();
 
-        use_effect_with(post_id, move |post_id| {
-            if let Some(id) = *post_id {
+        use_effect_with((post_id.clone(), *reload), move |(post_id, _)| {
+            if let Some(id) = post_id.clone() {
                 let title = title.clone();
                 let content = content.clone();
-                let error = error.clone();
+                let tags = tags.clone();
+                let canonical_url = canonical_url.clone();
+                let original_source = original_source.clone();
+                let load_error = load_error.clone();
                 let fetching = fetching.clone();
+                let base_updated_at = base_updated_at.clone();
 
                 fetching.set(true);
+                load_error.set(None);
                 spawn_local(async move {
-                    match api::get_post(id).await {
+                    match api::get_post(&id).await {
                         Ok(post) => {
                             title.set(post.title);
                             content.set(post.content);
+                            tags.set(post.tags.join(", "));
+                            canonical_url.set(post.canonical_url.unwrap_or_default());
+                            original_source.set(post.original_source.unwrap_or_default());
+                            base_updated_at.set(Some(post.updated_at));
                         }
                         Err(e) => {
-                            error.set(Some(format!("Failed to load post: {}", e.message)));
+                            load_error.set(Some(format!("Failed to load post: {}", e.message)));
                         }
                     }
                     fetching.set(false);
@@ -75,24 +107,55 @@ pub fn post_form(props: &PostFormProps) -> Html {
 
     let on_content_change = {
         let content = content.clone();
+        Callback::from(move |value: String| content.set(value))
+    };
+
+    let on_tags_change = {
+        let tags = tags.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            tags.set(input.value());
+        })
+    };
+
+    let on_canonical_url_change = {
+        let canonical_url = canonical_url.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            canonical_url.set(input.value());
+        })
+    };
+
+    let on_original_source_change = {
+        let original_source = original_source.clone();
         Callback::from(move |e: InputEvent| {
-            let target = e.target_unchecked_into::<web_sys::HtmlTextAreaElement>();
-            content.set(target.value());
+            let input: HtmlInputElement = e.target_unchecked_into();
+            original_source.set(input.value());
         })
     };
 
     let onsubmit = {
         let title = title.clone();
         let content = content.clone();
+        let tags = tags.clone();
+        let canonical_url = canonical_url.clone();
+        let original_source = original_source.clone();
         let error = error.clone();
         let loading = loading.clone();
+        let notice = notice.clone();
+        let base_updated_at = base_updated_at.clone();
         let on_success = props.on_success.clone();
+        let post_id = post_id.clone();
 
         Callback::from(move |e: SubmitEvent| {
             e.prevent_default();
 
             let title_val = (*title).clone();
             let content_val = (*content).clone();
+            let tags_val = parse_tags(&tags);
+            // Updates send empty values to clear the links; creates omit them
+            let canonical_url_val = canonical_url.trim().to_string();
+            let original_source_val = original_source.trim().to_string();
 
             // Validate
             if title_val.trim().is_empty() {
@@ -104,21 +167,85 @@ pub fn post_form(props: &PostFormProps) -> Html {
                 return;
             }
 
+            if !offline::is_online() {
+                let action = match (post_id.clone(), *base_updated_at) {
+                    (None, _) => PendingAction::Create {
+                        request: CreatePostRequest {
+                            title: title_val,
+                            content: content_val,
+                            tags: tags_val,
+                            lang: None,
+                            canonical_url: non_empty(canonical_url_val),
+                            original_source: non_empty(original_source_val),
+                            org: None,
+                        },
+                    },
+                    (Some(id), Some(base_updated_at)) => PendingAction::Update {
+                        id,
+                        base_updated_at,
+                        request: UpdatePostRequest {
+                            title: Some(title_val),
+                            content: Some(content_val),
+                            tags: Some(tags_val),
+                            canonical_url: Some(canonical_url_val),
+                            original_source: Some(original_source_val),
+                        },
+                    },
+                    (Some(_), None) => {
+                        error.set(Some("This post is not available offline".into()));
+                        return;
+                    }
+                };
+
+                let error = error.clone();
+                let notice = notice.clone();
+                let title = title.clone();
+                let content = content.clone();
+                let tags = tags.clone();
+                let canonical_url = canonical_url.clone();
+                let original_source = original_source.clone();
+                let created = post_id.is_none();
+                error.set(None);
+                spawn_local(async move {
+                    match offline::enqueue(&action).await {
+                        Ok(()) => {
+                            if created {
+                                title.set(String::new());
+                                content.set(String::new());
+                                tags.set(String::new());
+                                canonical_url.set(String::new());
+                                original_source.set(String::new());
+                            }
+                            notice.set(Some(
+                                "Saved offline. It will sync when you're back online.".into(),
+                            ));
+                        }
+                        Err(e) => error.set(Some(e.message)),
+                    }
+                });
+                return;
+            }
+
             let error = error.clone();
             let loading = loading.clone();
             let on_success = on_success.clone();
+            let post_id = post_id.clone();
 
             loading.set(true);
             error.set(None);
+            notice.set(None);
 
             spawn_local(async move {
                 let result = if let Some(id) = post_id {
                     // Update existing post
                     api::update_post(
-                        id,
+                        &id,
                         UpdatePostRequest {
                             title: Some(title_val),
                             content: Some(content_val),
+                            tags: Some(tags_val),
+                            canonical_url: Some(canonical_url_val),
+                            original_source: Some(original_source_val),
                         },
                     )
                     .await
@@ -127,6 +254,11 @@ pub fn post_form(props: &PostFormProps) -> Html {
                     api::create_post(CreatePostRequest {
                         title: title_val,
                         content: content_val,
+                        tags: tags_val,
+                        lang: None,
+                        canonical_url: non_empty(canonical_url_val),
+                        original_source: non_empty(original_source_val),
+                        org: None,
                     })
                     .await
                 };
@@ -144,6 +276,11 @@ pub fn post_form(props: &PostFormProps) -> Html {
         })
     };
 
+    let on_retry = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(*reload + 1))
+    };
+
     let on_cancel_click = {
         let on_cancel = props.on_cancel.clone();
         Callback::from(move |_: MouseEvent| {
@@ -160,62 +297,128 @@ pub fn post_form(props: &PostFormProps) -> Html {
             <h2>{if is_edit { "Edit Post" } else { "Create New Post" }}</h2>
 
             if let Some(ref err) = *error {
-                <div class="message message-error">{err}</div>
+                <div class="message message-error" role="alert">{err}</div>
+            }
+            if let Some(ref text) = *notice {
+                <div class="message message-success" role="status">{text}</div>
+            }
+            if let Some(ref id) = post_id {
+                <EditingPresence post_id={id.clone()} current_user_public_id={props.current_user_public_id.clone()} />
             }
 
-            if *fetching {
-                <div class="loading">{"Loading post data..."}</div>
-            } else {
-                <form {onsubmit} class="post-form">
-                    <div class="form-group">
-                        <label for="title">{"Title"}</label>
-                        <input
-                            type="text"
-                            id="title"
-                            value={(*title).clone()}
-                            oninput={on_title_change}
-                            disabled={is_disabled}
-                            placeholder="Enter post title..."
-                            required=true
-                        />
-                    </div>
+            <ErrorBoundary error={(*load_error).clone()} {on_retry}>
+                if *fetching {
+                    <PostFormSkeleton />
+                } else {
+                    <form
+                        {onsubmit}
+                        class="post-form"
+                        aria-label={if is_edit { "Edit post" } else { "Create post" }}
+                        aria-busy={loading.to_string()}
+                    >
+                        <div class="form-group">
+                            <label for="title">{"Title"}</label>
+                            <input
+                                type="text"
+                                id="title"
+                                value={(*title).clone()}
+                                oninput={on_title_change}
+                                disabled={is_disabled}
+                                placeholder="Enter post title..."
+                                required=true
+                            />
+                        </div>
 
-                    <div class="form-group">
-                        <label for="content">{"Content"}</label>
-                        <textarea
-                            id="content"
+                        <ContentEditor
                             value={(*content).clone()}
-                            oninput={on_content_change}
+                            on_change={on_content_change}
                             disabled={is_disabled}
-                            placeholder="Write your post content..."
-                            rows="12"
-                            required=true
                         />
-                    </div>
-
-                    <div class="form-actions">
-                        <button type="submit" class="btn btn-primary" disabled={is_disabled}>
-                            if *loading {
-                                {"Saving..."}
-                            } else if is_edit {
-                                {"Update Post"}
-                            } else {
-                                {"Create Post"}
-                            }
-                        </button>
-                        if props.on_cancel.is_some() {
-                            <button
-                                type="button"
-                                class="btn btn-secondary"
-                                onclick={on_cancel_click}
+
+                        <div class="form-group">
+                            <label for="tags">{"Tags"}</label>
+                            <input
+                                type="text"
+                                id="tags"
+                                value={(*tags).clone()}
+                                oninput={on_tags_change}
+                                disabled={is_disabled}
+                                placeholder={format!("rust, web (up to {})", MAX_TAGS_PER_POST)}
+                                aria-describedby="tags-hint"
+                            />
+                            <small id="tags-hint" class="form-hint">
+                                {"Separate tags with commas. Letters, digits, - and _ only."}
+                            </small>
+                        </div>
+
+                        <details class="form-group" open={!canonical_url.is_empty() || !original_source.is_empty()}>
+                            <summary>{"Syndication"}</summary>
+                            <label for="canonical-url">{"Canonical URL"}</label>
+                            <input
+                                type="url"
+                                id="canonical-url"
+                                value={(*canonical_url).clone()}
+                                oninput={on_canonical_url_change}
+                                disabled={is_disabled}
+                                placeholder="https://example.com/original-post"
+                                aria-describedby="canonical-url-hint"
+                            />
+                            <small id="canonical-url-hint" class="form-hint">
+                                {"Where search engines should index this post, if it was published elsewhere first."}
+                            </small>
+                            <label for="original-source">{"Original source"}</label>
+                            <input
+                                type="url"
+                                id="original-source"
+                                value={(*original_source).clone()}
+                                oninput={on_original_source_change}
                                 disabled={is_disabled}
-                            >
-                                {"Cancel"}
+                                placeholder="https://example.com/original-post"
+                            />
+                        </details>
+
+                        <div class="form-actions">
+                            <button type="submit" class="btn btn-primary" disabled={is_disabled}>
+                                if *loading {
+                                    {"Saving..."}
+                                } else if is_edit {
+                                    {"Update Post"}
+                                } else {
+                                    {"Create Post"}
+                                }
                             </button>
-                        }
-                    </div>
-                </form>
-            }
+                            if props.on_cancel.is_some() {
+                                <button
+                                    type="button"
+                                    class="btn btn-secondary"
+                                    onclick={on_cancel_click}
+                                    disabled={is_disabled}
+                                >
+                                    {"Cancel"}
+                                </button>
+                            }
+                        </div>
+                    </form>
+                    if let Some(ref id) = post_id {
+                        <RevisionHistory post_id={id.clone()} />
+                    }
+                }
+            </ErrorBoundary>
         </div>
     }
 }
+
+/// Splits comma-separated tag input; the server normalizes and validates.
+fn parse_tags(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Maps blank input to `None`.
+fn non_empty(value: String) -> Option<String> {
+    (!value.is_empty()).then_some(value)
+}
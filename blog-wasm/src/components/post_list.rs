@@ -6,7 +6,10 @@ use yew::prelude::*;
 use blog_shared::PostDto;
 
 use crate::api;
-use crate::components::PostCard;
+use crate::components::{ErrorBoundary, PostCard, PostCardSkeletonList};
+use crate::hooks::{focus_by_id, use_hotkeys};
+use crate::offline::{self, PendingAction};
+use crate::site::use_site;
 
 /// Post list properties.
 #[derive(Properties, PartialEq)]
@@ -16,69 +19,111 @@ pub struct PostListProps {
     pub current_user_id: Option<i64>,
     /// Callback when a post is edited.
     #[prop_or_default]
-    pub on_edit: Option<Callback<i64>>,
+    pub on_edit: Option<Callback<String>>,
+    /// Callback when a post is opened.
+    #[prop_or_default]
+    pub on_open: Option<Callback<String>>,
+    /// Bumped by the parent to force a reload.
+    #[prop_or_default]
+    pub refresh: u32,
+    /// Only show posts with this tag.
+    #[prop_or_default]
+    pub tag: Option<String>,
+    /// Callback when a tag chip is clicked.
+    #[prop_or_default]
+    pub on_tag: Option<Callback<String>>,
 }
 
 /// Post list component.
+///
+/// Keyed on the tag filter by the parent, so a new filter starts on page one.
 #[function_component(PostList)]
 pub fn post_list(props: &PostListProps) -> Html {
     let posts = use_state(Vec::<PostDto>::new);
     let total = use_state(|| 0i64);
     let loading = use_state(|| true);
+    let load_error = use_state(|| None::<String>);
     let error = use_state(|| None::<String>);
     let page = use_state(|| 0i64);
-    let limit = 10i64;
+    let reload = use_state(|| 0u32);
+    let selected = use_state(|| None::<usize>);
+    let limit = use_site().posts_per_page;
 
-    // Fetch posts when page changes
+    // Fetch posts when page changes, on refresh, or on retry
     {
         let posts = posts.clone();
         let total = total.clone();
         let loading = loading.clone();
+        let load_error = load_error.clone();
         let error = error.clone();
+        let selected = selected.clone();
         let page = *page;
+        let tag = props.tag.clone();
 
-        use_effect_with(page, move |page| {
-            let page = *page;
-            let posts = posts.clone();
-            let total = total.clone();
-            let loading = loading.clone();
-            let error = error.clone();
+        // Refetch on sign-in/out: posts carry the per-viewer `is_owner`.
+        use_effect_with(
+            (page, props.refresh, *reload, props.current_user_id, limit),
+            move |(page, _, _, _, _)| {
+                let page = *page;
+                let posts = posts.clone();
+                let total = total.clone();
+                let loading = loading.clone();
+                let load_error = load_error.clone();
 
-            loading.set(true);
-            error.set(None);
+                loading.set(true);
+                load_error.set(None);
+                selected.set(None);
+                error.set(None);
 
-            spawn_local(async move {
-                match api::list_posts(limit, page * limit).await {
-                    Ok(response) => {
-                        posts.set(response.posts);
-                        total.set(response.total);
-                    }
-                    Err(e) => {
-                        error.set(Some(e.message));
+                spawn_local(async move {
+                    match api::list_posts(limit, page * limit, tag.as_deref()).await {
+                        Ok(response) => {
+                            posts.set(response.posts);
+                            total.set(response.total);
+                        }
+                        Err(e) => {
+                            load_error.set(Some(e.message));
+                        }
                     }
-                }
-                loading.set(false);
-            });
+                    loading.set(false);
+                });
 
-            || ()
-        });
+                || ()
+            },
+        );
     }
 
     let on_delete = {
         let posts = posts.clone();
         let error = error.clone();
 
-        Callback::from(move |post_id: i64| {
+        Callback::from(move |post_id: String| {
             let posts = posts.clone();
             let error = error.clone();
 
+            // Offline: queue the delete against the version we have
+            let base_updated_at = posts
+                .iter()
+                .find(|p| p.public_id == post_id)
+                .map(|p| p.updated_at);
+
             spawn_local(async move {
-                match api::delete_post(post_id).await {
+                let result = match base_updated_at {
+                    Some(base_updated_at) if !offline::is_online() => {
+                        offline::enqueue(&PendingAction::Delete {
+                            id: post_id.clone(),
+                            base_updated_at,
+                        })
+                        .await
+                    }
+                    _ => api::delete_post(&post_id).await,
+                };
+                match result {
                     Ok(()) => {
                         // Remove the deleted post from the list
                         let updated: Vec<PostDto> = (*posts)
                             .iter()
-                            .filter(|p| p.id != post_id)
+                            .filter(|p| p.public_id != post_id)
                             .cloned()
                             .collect();
                         posts.set(updated);
@@ -91,6 +136,43 @@ pub fn post_list(props: &PostListProps) -> Html {
         })
     };
 
+    // j/k move the keyboard selection through the visible posts
+    {
+        let count = posts.len();
+        let step = |delta: isize| {
+            let selected = selected.clone();
+            Callback::from(move |_| {
+                if count == 0 {
+                    return;
+                }
+                let next = match *selected {
+                    Some(index) => index.saturating_add_signed(delta).min(count - 1),
+                    None => 0,
+                };
+                selected.set(Some(next));
+            })
+        };
+        use_hotkeys(vec![("j", step(1)), ("k", step(-1))]);
+    }
+
+    // Focus the selected card so it scrolls into view and is announced
+    {
+        let selected_id = selected
+            .and_then(|index| posts.get(index))
+            .map(|p| p.public_id.clone());
+        use_effect_with(selected_id, |selected_id| {
+            if let Some(id) = selected_id {
+                focus_by_id(&format!("post-card-{}", id));
+            }
+            || ()
+        });
+    }
+
+    let on_retry = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(*reload + 1))
+    };
+
     let on_prev_page = {
         let page = page.clone();
         Callback::from(move |_: MouseEvent| {
@@ -117,58 +199,67 @@ pub fn post_list(props: &PostListProps) -> Html {
 
     html! {
         <div class="post-list">
-            if *loading {
-                <div class="loading">{"Loading posts..."}</div>
-            } else if let Some(ref err) = *error {
-                <div class="message message-error">{err}</div>
-            } else if posts.is_empty() {
-                <div class="empty-state">
-                    <p>{"No posts yet."}</p>
-                    if props.current_user_id.is_some() {
-                        <a href="/posts/new" class="btn btn-secondary">{"+ Create your first post"}</a>
-                    }
-                </div>
-            } else {
-                <>
-                    <div class="post-grid">
-                        {for posts.iter().map(|post| {
-                            let is_owner = props.current_user_id
-                                .map(|uid| uid == post.author_id)
-                                .unwrap_or(false);
-                            html! {
-                                <PostCard
-                                    post={post.clone()}
-                                    is_owner={is_owner}
-                                    on_edit={props.on_edit.clone()}
-                                    on_delete={Some(on_delete.clone())}
-                                />
-                            }
-                        })}
+            if let Some(ref err) = *error {
+                <div class="message message-error" role="alert">{err}</div>
+            }
+            <ErrorBoundary error={(*load_error).clone()} {on_retry}>
+                if *loading {
+                    <PostCardSkeletonList />
+                } else if posts.is_empty() {
+                    <div class="empty-state">
+                        if let Some(ref tag) = props.tag {
+                            <p>{format!("No posts tagged #{}.", tag)}</p>
+                        } else {
+                            <p>{"No posts yet."}</p>
+                        }
+                        if props.current_user_id.is_some() && props.tag.is_none() {
+                            <a href="/posts/new" class="btn btn-secondary">{"+ Create your first post"}</a>
+                        }
                     </div>
-
-                    if total_pages > 1 {
-                        <div class="pagination">
-                            <button
-                                class="btn btn-secondary"
-                                onclick={on_prev_page}
-                                disabled={!has_prev}
-                            >
-                                {"← Previous"}
-                            </button>
-                            <span class="pagination-info">
-                                {format!("Page {} of {}", *page + 1, total_pages)}
-                            </span>
-                            <button
-                                class="btn btn-secondary"
-                                onclick={on_next_page}
-                                disabled={!has_next}
-                            >
-                                {"Next →"}
-                            </button>
+                } else {
+                    <>
+                        <div class="post-grid">
+                            {for posts.iter().enumerate().map(|(index, post)| {
+                                html! {
+                                    <PostCard
+                                        post={post.clone()}
+                                        is_owner={post.is_owner}
+                                        on_edit={props.on_edit.clone()}
+                                        on_open={props.on_open.clone()}
+                                        on_delete={Some(on_delete.clone())}
+                                        on_tag={props.on_tag.clone()}
+                                        selected={*selected == Some(index)}
+                                    />
+                                }
+                            })}
                         </div>
-                    }
-                </>
-            }
+
+                        if total_pages > 1 {
+                            <nav class="pagination" aria-label="Pagination">
+                                <button
+                                    class="btn btn-secondary"
+                                    onclick={on_prev_page}
+                                    disabled={!has_prev}
+                                    aria-label="Previous page"
+                                >
+                                    {"← Previous"}
+                                </button>
+                                <span class="pagination-info" aria-live="polite">
+                                    {format!("Page {} of {}", *page + 1, total_pages)}
+                                </span>
+                                <button
+                                    class="btn btn-secondary"
+                                    onclick={on_next_page}
+                                    disabled={!has_next}
+                                    aria-label="Next page"
+                                >
+                                    {"Next →"}
+                                </button>
+                            </nav>
+                        }
+                    </>
+                }
+            </ErrorBoundary>
         </div>
     }
 }
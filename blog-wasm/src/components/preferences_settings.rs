@@ -0,0 +1,181 @@
+//! Preferences settings section (theme, language, time zone, and locale).
+
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use blog_shared::constants::{SUPPORTED_LANGUAGES, SUPPORTED_LOCALES};
+use blog_shared::{PreferencesDto, Theme};
+
+use crate::api;
+use crate::preferences::apply_preferences;
+
+/// Theme choices in display order.
+const THEMES: [(Theme, &str); 3] = [
+    (Theme::System, "System"),
+    (Theme::Light, "Light"),
+    (Theme::Dark, "Dark"),
+];
+
+/// Preferences settings properties.
+#[derive(Properties, PartialEq)]
+pub struct PreferencesSettingsProps {
+    /// Saved preferences.
+    pub preferences: PreferencesDto,
+}
+
+/// Returns the native name of a supported language code.
+pub(super) fn language_name(code: &str) -> &str {
+    match code {
+        "en" => "English",
+        "ru" => "Русский",
+        other => other,
+    }
+}
+
+/// Preferences settings section.
+#[function_component(PreferencesSettings)]
+pub fn preferences_settings(props: &PreferencesSettingsProps) -> Html {
+    let theme = use_state(|| props.preferences.theme);
+    let language = use_state(|| props.preferences.language.clone());
+    let timezone = use_state(|| props.preferences.timezone.clone());
+    let locale = use_state(|| props.preferences.locale.clone());
+    let error = use_state(|| None::<String>);
+    let saved = use_state(|| false);
+    let loading = use_state(|| false);
+
+    let on_theme_change = {
+        let theme = theme.clone();
+        let saved = saved.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Ok(value) = select.value().parse() {
+                theme.set(value);
+                saved.set(false);
+            }
+        })
+    };
+
+    let on_language_change = {
+        let language = language.clone();
+        let saved = saved.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            language.set(select.value());
+            saved.set(false);
+        })
+    };
+
+    let on_timezone_input = {
+        let timezone = timezone.clone();
+        let saved = saved.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            timezone.set(input.value());
+            saved.set(false);
+        })
+    };
+
+    let on_locale_change = {
+        let locale = locale.clone();
+        let saved = saved.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            locale.set(select.value());
+            saved.set(false);
+        })
+    };
+
+    let onsubmit = {
+        let theme = theme.clone();
+        let language = language.clone();
+        let timezone = timezone.clone();
+        let locale = locale.clone();
+        let error = error.clone();
+        let saved = saved.clone();
+        let loading = loading.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let req = PreferencesDto {
+                theme: *theme,
+                language: (*language).clone(),
+                timezone: (*timezone).trim().to_string(),
+                locale: (*locale).clone(),
+            };
+            let error = error.clone();
+            let saved = saved.clone();
+            let loading = loading.clone();
+
+            loading.set(true);
+            error.set(None);
+            spawn_local(async move {
+                match api::update_preferences(req).await {
+                    Ok(preferences) => {
+                        apply_preferences(&preferences);
+                        saved.set(true);
+                    }
+                    Err(e) => error.set(Some(e.message)),
+                }
+                loading.set(false);
+            });
+        })
+    };
+
+    html! {
+        <section class="settings-section" aria-labelledby="settings-preferences-title">
+            <h3 id="settings-preferences-title">{"Preferences"}</h3>
+            if let Some(ref err) = *error {
+                <div class="message message-error" role="alert">{err}</div>
+            }
+            if *saved {
+                <div class="message message-success" role="status">{"Preferences saved."}</div>
+            }
+            <form {onsubmit} aria-busy={loading.to_string()}>
+                <div class="form-group">
+                    <label for="settings-theme">{"Theme"}</label>
+                    <select id="settings-theme" onchange={on_theme_change} disabled={*loading}>
+                        {for THEMES.iter().map(|(value, label)| html! {
+                            <option value={value.as_str()} selected={*theme == *value}>{*label}</option>
+                        })}
+                    </select>
+                </div>
+                <div class="form-group">
+                    <label for="settings-language">{"Language"}</label>
+                    <select id="settings-language" onchange={on_language_change} disabled={*loading}>
+                        {for SUPPORTED_LANGUAGES.iter().map(|code| html! {
+                            <option value={*code} selected={*language == *code}>
+                                {language_name(code)}
+                            </option>
+                        })}
+                    </select>
+                </div>
+                <div class="form-group">
+                    <label for="settings-timezone">{"Time zone"}</label>
+                    <input
+                        id="settings-timezone"
+                        type="text"
+                        placeholder="Europe/Berlin"
+                        value={(*timezone).clone()}
+                        oninput={on_timezone_input}
+                        disabled={*loading}
+                    />
+                </div>
+                <div class="form-group">
+                    <label for="settings-locale">{"Date format"}</label>
+                    <select id="settings-locale" onchange={on_locale_change} disabled={*loading}>
+                        {for SUPPORTED_LOCALES.iter().map(|code| html! {
+                            <option value={*code} selected={*locale == *code}>{*code}</option>
+                        })}
+                    </select>
+                </div>
+                <button type="submit" class="btn btn-primary" disabled={*loading}>
+                    if *loading {
+                        {"Saving..."}
+                    } else {
+                        {"Save preferences"}
+                    }
+                </button>
+            </form>
+        </section>
+    }
+}
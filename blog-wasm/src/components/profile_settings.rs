@@ -0,0 +1,178 @@
+//! Profile settings section (bio, avatar, and website).
+
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlTextAreaElement};
+use yew::prelude::*;
+
+use blog_shared::ProfileDto;
+use blog_shared::constants::{MAX_AVATAR_URL_LENGTH, MAX_BIO_LENGTH, MAX_WEBSITE_LENGTH};
+
+use crate::api;
+use crate::user_cache::UserCache;
+
+/// Profile settings properties.
+#[derive(Properties, PartialEq)]
+pub struct ProfileSettingsProps {
+    /// Saved profile.
+    pub profile: ProfileDto,
+}
+
+/// Profile settings section.
+#[function_component(ProfileSettings)]
+pub fn profile_settings(props: &ProfileSettingsProps) -> Html {
+    let bio = use_state(|| props.profile.bio.clone());
+    let avatar_url = use_state(|| props.profile.avatar_url.clone().unwrap_or_default());
+    let website = use_state(|| props.profile.website.clone().unwrap_or_default());
+    let error = use_state(|| None::<String>);
+    let saved = use_state(|| false);
+    let loading = use_state(|| false);
+    let user_cache = use_context::<UserCache>();
+
+    let on_bio_change = {
+        let bio = bio.clone();
+        let saved = saved.clone();
+        Callback::from(move |e: InputEvent| {
+            let target: HtmlTextAreaElement = e.target_unchecked_into();
+            bio.set(target.value());
+            saved.set(false);
+        })
+    };
+
+    let on_avatar_change = {
+        let avatar_url = avatar_url.clone();
+        let saved = saved.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            avatar_url.set(input.value());
+            saved.set(false);
+        })
+    };
+
+    let on_website_change = {
+        let website = website.clone();
+        let saved = saved.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            website.set(input.value());
+            saved.set(false);
+        })
+    };
+
+    let onsubmit = {
+        let bio = bio.clone();
+        let avatar_url = avatar_url.clone();
+        let website = website.clone();
+        let error = error.clone();
+        let saved = saved.clone();
+        let loading = loading.clone();
+        let user_cache = user_cache.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let avatar = avatar_url.trim().to_string();
+            let site = website.trim().to_string();
+            let req = ProfileDto {
+                bio: bio.trim().to_string(),
+                avatar_url: (!avatar.is_empty()).then_some(avatar),
+                website: (!site.is_empty()).then_some(site),
+            };
+            let bio = bio.clone();
+            let avatar_url = avatar_url.clone();
+            let website = website.clone();
+            let error = error.clone();
+            let saved = saved.clone();
+            let loading = loading.clone();
+            let user_cache = user_cache.clone();
+
+            loading.set(true);
+            error.set(None);
+            spawn_local(async move {
+                match api::update_profile(req).await {
+                    Ok(profile) => {
+                        bio.set(profile.bio);
+                        avatar_url.set(profile.avatar_url.unwrap_or_default());
+                        website.set(profile.website.unwrap_or_default());
+                        saved.set(true);
+                        // Avatars shown elsewhere pick up the new picture
+                        if let Some(cache) = user_cache {
+                            cache.invalidate();
+                        }
+                    }
+                    Err(e) => error.set(Some(e.message)),
+                }
+                loading.set(false);
+            });
+        })
+    };
+
+    let remaining = MAX_BIO_LENGTH.saturating_sub(bio.chars().count());
+    let preview = avatar_url.trim();
+
+    html! {
+        <section class="settings-section" aria-labelledby="settings-profile-title">
+            <h3 id="settings-profile-title">{"Profile"}</h3>
+            if let Some(ref err) = *error {
+                <div class="message message-error" role="alert">{err}</div>
+            }
+            if *saved {
+                <div class="message message-success" role="status">{"Profile saved."}</div>
+            }
+            <form {onsubmit} aria-busy={loading.to_string()}>
+                <div class="form-group">
+                    <label for="settings-bio">{"Bio"}</label>
+                    <textarea
+                        id="settings-bio"
+                        value={(*bio).clone()}
+                        oninput={on_bio_change}
+                        maxlength={MAX_BIO_LENGTH.to_string()}
+                        rows="4"
+                        disabled={*loading}
+                        aria-describedby="settings-bio-hint"
+                    />
+                    <p class="form-hint" id="settings-bio-hint">
+                        {format!("{} characters left", remaining)}
+                    </p>
+                </div>
+                <div class="form-group">
+                    <label for="settings-avatar">{"Avatar URL"}</label>
+                    <div class="settings-avatar-row">
+                        if preview.starts_with("https://") || preview.starts_with("http://") {
+                            <img class="settings-avatar" src={preview.to_string()} alt="Avatar preview" />
+                        }
+                        <input
+                            type="url"
+                            id="settings-avatar"
+                            value={(*avatar_url).clone()}
+                            oninput={on_avatar_change}
+                            maxlength={MAX_AVATAR_URL_LENGTH.to_string()}
+                            placeholder="https://..."
+                            disabled={*loading}
+                        />
+                    </div>
+                </div>
+                <div class="form-group">
+                    <label for="settings-website">{"Website"}</label>
+                    <input
+                        type="url"
+                        id="settings-website"
+                        value={(*website).clone()}
+                        oninput={on_website_change}
+                        maxlength={MAX_WEBSITE_LENGTH.to_string()}
+                        placeholder="https://..."
+                        disabled={*loading}
+                        aria-describedby="settings-website-hint"
+                    />
+                    <p class="form-hint" id="settings-website-hint">
+                        {"Sign in with this site (IndieAuth) to post from Micropub apps."}
+                    </p>
+                </div>
+                <button type="submit" class="btn btn-primary" disabled={*loading}>
+                    if *loading {
+                        {"Saving..."}
+                    } else {
+                        {"Save profile"}
+                    }
+                </button>
+            </form>
+        </section>
+    }
+}
@@ -0,0 +1,100 @@
+//! Reader view: a post with minimal chrome and larger typography.
+
+use wasm_bindgen_futures::spawn_local;
+use web_sys::window;
+use yew::prelude::*;
+
+use blog_shared::PostDto;
+
+use crate::api;
+use crate::components::{ErrorBoundary, MarkdownContent, PostCardSkeletonList};
+
+/// Reader view properties.
+#[derive(Properties, PartialEq)]
+pub struct ReaderViewProps {
+    /// Public ID of the post to display.
+    pub post_id: String,
+    /// Callback with the post's public ID to leave reader mode.
+    pub on_exit: Callback<String>,
+}
+
+/// Reader view component.
+///
+/// `App` hides the header in reader mode; the print stylesheet also hides
+/// the toolbar, so printing gives just the article.
+#[function_component(ReaderView)]
+pub fn reader_view(props: &ReaderViewProps) -> Html {
+    let post_id = props.post_id.clone();
+    let post = use_state(|| None::<PostDto>);
+    let load_error = use_state(|| None::<String>);
+    let reload = use_state(|| 0u32);
+
+    {
+        let post = post.clone();
+        let load_error = load_error.clone();
+        use_effect_with((post_id.clone(), *reload), move |(post_id, _)| {
+            let post_id = post_id.clone();
+            post.set(None);
+            load_error.set(None);
+            spawn_local(async move {
+                match api::get_post(&post_id).await {
+                    Ok(loaded) => post.set(Some(loaded)),
+                    Err(e) => load_error.set(Some(format!("Failed to load post: {}", e.message))),
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_retry = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(*reload + 1))
+    };
+
+    let on_exit_click = {
+        let on_exit = props.on_exit.clone();
+        Callback::from(move |_: MouseEvent| on_exit.emit(post_id.clone()))
+    };
+
+    let on_print_click = Callback::from(|_: MouseEvent| {
+        if let Some(win) = window() {
+            let _ = win.print();
+        }
+    });
+
+    html! {
+        <div class="reader">
+            <div class="reader-toolbar">
+                <button type="button" class="btn btn-link" onclick={on_exit_click}>
+                    {"← Exit reader view"}
+                </button>
+                <button type="button" class="btn btn-secondary btn-sm" onclick={on_print_click}>
+                    {"Print"}
+                </button>
+            </div>
+            <ErrorBoundary error={(*load_error).clone()} {on_retry}>
+                if let Some(ref post) = *post {
+                    <article class="reader-article" aria-labelledby="reader-title">
+                        <header>
+                            <h1 class="reader-title" id="reader-title">{&post.title}</h1>
+                            <p class="reader-meta">
+                                {format!(
+                                    "{} · {}",
+                                    post.byline(),
+                                    post.created_at.format("%B %d, %Y"),
+                                )}
+                            </p>
+                        </header>
+                        <MarkdownContent
+                            class="reader-content"
+                            html={post.content_html.clone()}
+                            fallback={post.content.clone()}
+                        />
+                    </article>
+                } else {
+                    <PostCardSkeletonList count={1} />
+                }
+            </ErrorBoundary>
+        </div>
+    }
+}
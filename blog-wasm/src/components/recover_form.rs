@@ -0,0 +1,150 @@
+//! Form for signing in with a recovery code and setting a new password.
+
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use blog_shared::constants::MIN_PASSWORD_LENGTH;
+use blog_shared::{RecoverAccountRequest, UserDto};
+
+use crate::api;
+
+/// Recover form properties.
+#[derive(Properties, PartialEq)]
+pub struct RecoverFormProps {
+    /// Callback when recovery succeeds, with the signed-in user.
+    pub on_success: Callback<UserDto>,
+    /// Callback to go back to the login form.
+    pub on_cancel: Callback<()>,
+}
+
+/// Creates an input handler that stores the field value in `state`.
+fn bind(state: &UseStateHandle<String>) -> Callback<InputEvent> {
+    let state = state.clone();
+    Callback::from(move |e: InputEvent| {
+        let input: HtmlInputElement = e.target_unchecked_into();
+        state.set(input.value());
+    })
+}
+
+/// Recover form component.
+#[function_component(RecoverForm)]
+pub fn recover_form(props: &RecoverFormProps) -> Html {
+    let username = use_state(String::new);
+    let code = use_state(String::new);
+    let new_password = use_state(String::new);
+    let error = use_state(|| None::<String>);
+    let loading = use_state(|| false);
+
+    let onsubmit = {
+        let username = username.clone();
+        let code = code.clone();
+        let new_password = new_password.clone();
+        let error = error.clone();
+        let loading = loading.clone();
+        let on_success = props.on_success.clone();
+
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            if new_password.chars().count() < MIN_PASSWORD_LENGTH {
+                error.set(Some(format!(
+                    "Password must be at least {MIN_PASSWORD_LENGTH} characters"
+                )));
+                return;
+            }
+
+            let req = RecoverAccountRequest {
+                username: (*username).clone(),
+                code: (*code).clone(),
+                new_password: (*new_password).clone(),
+            };
+            let error = error.clone();
+            let loading = loading.clone();
+            let on_success = on_success.clone();
+
+            loading.set(true);
+            error.set(None);
+            spawn_local(async move {
+                match api::recover_account(req).await {
+                    Ok(response) => {
+                        api::set_token(&response.token);
+                        on_success.emit(response.user);
+                    }
+                    Err(e) => {
+                        error.set(Some(e.message));
+                    }
+                }
+                loading.set(false);
+            });
+        })
+    };
+
+    let on_cancel = {
+        let on_cancel = props.on_cancel.clone();
+        Callback::from(move |_: MouseEvent| on_cancel.emit(()))
+    };
+
+    html! {
+        <div class="auth-container">
+            <h2>{"Recover account"}</h2>
+
+            if let Some(ref err) = *error {
+                <div class="message message-error" role="alert">{err}</div>
+            }
+
+            <form {onsubmit} aria-label="Recover account" aria-busy={loading.to_string()}>
+                <div class="form-group">
+                    <label for="recover-username">{"Username or email"}</label>
+                    <input
+                        type="text"
+                        id="recover-username"
+                        value={(*username).clone()}
+                        oninput={bind(&username)}
+                        disabled={*loading}
+                        required=true
+                    />
+                </div>
+
+                <div class="form-group">
+                    <label for="recover-code">{"Recovery code"}</label>
+                    <input
+                        type="text"
+                        id="recover-code"
+                        value={(*code).clone()}
+                        oninput={bind(&code)}
+                        autocomplete="off"
+                        disabled={*loading}
+                        required=true
+                    />
+                </div>
+
+                <div class="form-group">
+                    <label for="recover-password">{"New password"}</label>
+                    <input
+                        type="password"
+                        id="recover-password"
+                        value={(*new_password).clone()}
+                        oninput={bind(&new_password)}
+                        autocomplete="new-password"
+                        disabled={*loading}
+                        required=true
+                    />
+                </div>
+
+                <button type="submit" class="btn btn-primary" disabled={*loading}>
+                    if *loading {
+                        {"Recovering..."}
+                    } else {
+                        {"Set password and sign in"}
+                    }
+                </button>
+            </form>
+
+            <p class="auth-switch">
+                <button type="button" class="btn btn-link" onclick={on_cancel}>
+                    {"Back to login"}
+                </button>
+            </p>
+        </div>
+    }
+}
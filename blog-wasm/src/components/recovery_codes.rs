@@ -0,0 +1,114 @@
+//! Recovery codes: the list shown once after they are issued, and the
+//! settings section that regenerates them.
+
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use blog_shared::RegenerateRecoveryCodesRequest;
+
+use crate::api;
+
+/// Recovery code list properties.
+#[derive(Properties, PartialEq)]
+pub struct RecoveryCodeListProps {
+    pub codes: Vec<String>,
+}
+
+/// Freshly issued recovery codes, with a reminder to save them.
+#[function_component(RecoveryCodeList)]
+pub fn recovery_code_list(props: &RecoveryCodeListProps) -> Html {
+    html! {
+        <div class="recovery-codes">
+            <p class="form-hint">
+                {"Save these recovery codes somewhere safe. Each one signs you in once \
+                  if you forget your password. They will not be shown again."}
+            </p>
+            <ol class="recovery-code-list">
+                { for props.codes.iter().map(|code| html! { <li><code>{code}</code></li> }) }
+            </ol>
+        </div>
+    }
+}
+
+/// Recovery codes settings section.
+///
+/// Generating new codes requires the current password and replaces the old
+/// ones, used or not.
+#[function_component(RecoveryCodes)]
+pub fn recovery_codes() -> Html {
+    let password = use_state(String::new);
+    let codes = use_state(Vec::<String>::new);
+    let error = use_state(|| None::<String>);
+    let loading = use_state(|| false);
+
+    let on_password_input = {
+        let password = password.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            password.set(input.value());
+        })
+    };
+
+    let onsubmit = {
+        let password = password.clone();
+        let codes = codes.clone();
+        let error = error.clone();
+        let loading = loading.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let req = RegenerateRecoveryCodesRequest {
+                current_password: (*password).clone(),
+            };
+            let password = password.clone();
+            let codes = codes.clone();
+            let error = error.clone();
+            let loading = loading.clone();
+
+            loading.set(true);
+            error.set(None);
+            spawn_local(async move {
+                match api::regenerate_recovery_codes(req).await {
+                    Ok(response) => codes.set(response.codes),
+                    Err(e) => error.set(Some(e.message)),
+                }
+                password.set(String::new());
+                loading.set(false);
+            });
+        })
+    };
+
+    html! {
+        <section class="settings-section" aria-labelledby="settings-recovery-title">
+            <h3 id="settings-recovery-title">{"Recovery codes"}</h3>
+            if let Some(ref err) = *error {
+                <div class="message message-error" role="alert">{err}</div>
+            }
+            if codes.is_empty() {
+                <p class="form-hint">
+                    {"Recovery codes let you sign in and set a new password if you forget it. \
+                      Generating new codes invalidates the old ones."}
+                </p>
+            } else {
+                <RecoveryCodeList codes={(*codes).clone()} />
+            }
+            <form {onsubmit} aria-label="Generate recovery codes" aria-busy={loading.to_string()}>
+                <div class="form-group">
+                    <label for="settings-recovery-password">{"Current password"}</label>
+                    <input
+                        type="password"
+                        id="settings-recovery-password"
+                        value={(*password).clone()}
+                        oninput={on_password_input}
+                        autocomplete="current-password"
+                        disabled={*loading}
+                        required=true
+                    />
+                </div>
+                <button type="submit" class="btn btn-primary" disabled={*loading}>
+                    {"Generate new codes"}
+                </button>
+            </form>
+        </section>
+    }
+}
@@ -4,15 +4,16 @@ use wasm_bindgen_futures::spawn_local;
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
 
-use blog_shared::RegisterRequest;
+use blog_shared::{AuthResponse, RegisterRequest, UserDto};
 
 use crate::api;
+use crate::components::RecoveryCodeList;
 
 /// Register form properties.
 #[derive(Properties, PartialEq)]
 pub struct RegisterFormProps {
-    /// Callback when registration succeeds (user_id, username).
-    pub on_success: Callback<(i64, String)>,
+    /// Callback when registration succeeds, with the signed-in user.
+    pub on_success: Callback<UserDto>,
 }
 
 /// Register form component.
@@ -21,8 +22,25 @@ pub fn register_form(props: &RegisterFormProps) -> Html {
     let username = use_state(String::new);
     let email = use_state(String::new);
     let password = use_state(String::new);
+    let invite_code = use_state(String::new);
+    // Whether the server only registers users with an invite code
+    let invite_only = use_state(|| false);
     let error = use_state(|| None::<String>);
     let loading = use_state(|| false);
+    // Set after registering, while the recovery codes are shown
+    let registered = use_state(|| None::<AuthResponse>);
+
+    {
+        let invite_only = invite_only.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                if let Ok(features) = api::get_features().await {
+                    invite_only.set(features.invite_only);
+                }
+            });
+            || ()
+        });
+    }
 
     let on_username_change = {
         let username = username.clone();
@@ -48,12 +66,23 @@ pub fn register_form(props: &RegisterFormProps) -> Html {
         })
     };
 
+    let on_invite_code_change = {
+        let invite_code = invite_code.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            invite_code.set(input.value());
+        })
+    };
+
     let onsubmit = {
         let username = username.clone();
         let email = email.clone();
         let password = password.clone();
+        let invite_code = invite_code.clone();
+        let invite_only = invite_only.clone();
         let error = error.clone();
         let loading = loading.clone();
+        let registered = registered.clone();
         let on_success = props.on_success.clone();
 
         Callback::from(move |e: SubmitEvent| {
@@ -62,8 +91,10 @@ pub fn register_form(props: &RegisterFormProps) -> Html {
             let username_val = (*username).clone();
             let email_val = (*email).clone();
             let password_val = (*password).clone();
+            let invite_code_val = (*invite_only).then(|| (*invite_code).clone());
             let error = error.clone();
             let loading = loading.clone();
+            let registered = registered.clone();
             let on_success = on_success.clone();
 
             loading.set(true);
@@ -74,12 +105,17 @@ pub fn register_form(props: &RegisterFormProps) -> Html {
                     username: username_val,
                     email: email_val,
                     password: password_val,
+                    invite_code: invite_code_val,
                 };
 
                 match api::register(req).await {
+                    Ok(response) if response.recovery_codes.is_empty() => {
+                        api::set_token(&response.token);
+                        on_success.emit(response.user);
+                    }
                     Ok(response) => {
                         api::set_token(&response.token);
-                        on_success.emit((response.user.id, response.user.username));
+                        registered.set(Some(response));
                     }
                     Err(e) => {
                         error.set(Some(e.message));
@@ -90,15 +126,32 @@ pub fn register_form(props: &RegisterFormProps) -> Html {
         })
     };
 
+    if let Some(ref response) = *registered {
+        let on_continue = {
+            let user = response.user.clone();
+            let on_success = props.on_success.clone();
+            Callback::from(move |_: MouseEvent| on_success.emit(user.clone()))
+        };
+        return html! {
+            <div class="auth-container">
+                <h2>{"Welcome, "}{&response.user.username}</h2>
+                <RecoveryCodeList codes={response.recovery_codes.clone()} />
+                <button type="button" class="btn btn-primary" onclick={on_continue}>
+                    {"I saved my codes"}
+                </button>
+            </div>
+        };
+    }
+
     html! {
         <div class="auth-container">
             <h2>{"Register"}</h2>
 
             if let Some(ref err) = *error {
-                <div class="message message-error">{err}</div>
+                <div class="message message-error" role="alert">{err}</div>
             }
 
-            <form {onsubmit}>
+            <form {onsubmit} aria-label="Register" aria-busy={loading.to_string()}>
                 <div class="form-group">
                     <label for="username">{"Username"}</label>
                     <input
@@ -135,6 +188,21 @@ pub fn register_form(props: &RegisterFormProps) -> Html {
                     />
                 </div>
 
+                if *invite_only {
+                    <div class="form-group">
+                        <label for="invite-code">{"Invite code"}</label>
+                        <input
+                            type="text"
+                            id="invite-code"
+                            value={(*invite_code).clone()}
+                            oninput={on_invite_code_change}
+                            disabled={*loading}
+                            required=true
+                            autocomplete="off"
+                        />
+                    </div>
+                }
+
                 <button type="submit" class="btn btn-primary" disabled={*loading}>
                     if *loading {
                         {"Registering..."}
@@ -0,0 +1,119 @@
+//! Revisions of a post and the changes between two of them.
+
+use blog_shared::{RevisionDiffResponse, RevisionDto};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+
+use crate::api;
+
+/// Revision history properties.
+#[derive(Properties, PartialEq)]
+pub struct RevisionHistoryProps {
+    /// Public ID of the post whose revisions are shown.
+    pub post_id: String,
+}
+
+/// Revision history component.
+///
+/// Collapsed by default. Lists the post's revisions in two pickers, set to
+/// the previous and the latest revision, and shows the content diff the
+/// server renders between them.
+#[function_component(RevisionHistory)]
+pub fn revision_history(props: &RevisionHistoryProps) -> Html {
+    let revisions = use_state(Vec::<RevisionDto>::new);
+    let range = use_state(|| None::<(i64, i64)>);
+    let diff = use_state(|| None::<RevisionDiffResponse>);
+    let error = use_state(|| None::<String>);
+
+    {
+        let revisions = revisions.clone();
+        let range = range.clone();
+        let error = error.clone();
+        use_effect_with(props.post_id.clone(), move |post_id| {
+            let post_id = post_id.clone();
+            spawn_local(async move {
+                match api::list_revisions(&post_id).await {
+                    Ok(list) => {
+                        // Newest first: compare the previous revision with the latest
+                        let latest = list.revisions.first().map(|r| r.number);
+                        let previous = list.revisions.get(1).map(|r| r.number);
+                        range.set(latest.map(|to| (previous.unwrap_or(to), to)));
+                        revisions.set(list.revisions);
+                    }
+                    Err(e) => error.set(Some(format!("Failed to load history: {}", e.message))),
+                }
+            });
+            || ()
+        });
+    }
+
+    {
+        let diff = diff.clone();
+        let error = error.clone();
+        use_effect_with((props.post_id.clone(), *range), move |(post_id, range)| {
+            diff.set(None);
+            if let Some((from, to)) = range.filter(|(from, to)| from != to) {
+                let post_id = post_id.clone();
+                spawn_local(async move {
+                    match api::diff_revisions(&post_id, from, to).await {
+                        Ok(response) => diff.set(Some(response)),
+                        Err(e) => error.set(Some(format!("Failed to load changes: {}", e.message))),
+                    }
+                });
+            }
+            || ()
+        });
+    }
+
+    let on_pick = |to_side: bool| {
+        let range = range.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let (Ok(number), Some((from, to))) = (select.value().parse(), *range) {
+                range.set(Some(if to_side {
+                    (from, number)
+                } else {
+                    (number, to)
+                }));
+            }
+        })
+    };
+    let picker = |id: &'static str, selected: i64, onchange: Callback<Event>| {
+        html! {
+            <select {id} {onchange}>
+                {for revisions.iter().map(|revision| html! {
+                    <option value={revision.number.to_string()} selected={revision.number == selected}>
+                        {format!("r{} · {}", revision.number, revision.created_at.format("%B %d, %Y %H:%M"))}
+                    </option>
+                })}
+            </select>
+        }
+    };
+
+    html! {
+        <details class="revision-history">
+            <summary>{format!("History ({} revisions)", revisions.len())}</summary>
+            if let Some(ref err) = *error {
+                <div class="message message-error" role="alert">{err}</div>
+            }
+            if let Some((from, to)) = *range {
+                if revisions.len() < 2 {
+                    <p class="form-hint">{"No earlier revisions yet."}</p>
+                } else {
+                    <div class="revision-pickers">
+                        <label for="revision-from">{"From"}</label>
+                        {picker("revision-from", from, on_pick(false))}
+                        <label for="revision-to">{"To"}</label>
+                        {picker("revision-to", to, on_pick(true))}
+                    </div>
+                    if from == to {
+                        <p class="form-hint">{"Pick two different revisions."}</p>
+                    } else if let Some(ref diff) = *diff {
+                        <div class="revision-diff">{Html::from_html_unchecked(diff.html.clone().into())}</div>
+                    }
+                }
+            }
+        </details>
+    }
+}
@@ -0,0 +1,208 @@
+//! Header search bar with debounced title suggestions.
+
+use gloo_timers::callback::Timeout;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use blog_shared::PostDto;
+
+use crate::api;
+use crate::constants::{
+    SEARCH_DEBOUNCE_MS, SEARCH_INPUT_ID, SEARCH_MIN_QUERY_LEN, SEARCH_SUGGESTION_LIMIT,
+};
+
+/// ID of the suggestions listbox (referenced by the input's ARIA attributes).
+const SUGGESTIONS_ID: &str = "search-suggestions";
+
+/// Search bar properties.
+#[derive(Properties, PartialEq)]
+pub struct SearchBarProps {
+    /// Callback with the post's public ID when a suggestion is chosen.
+    pub on_open: Callback<String>,
+    /// Callback with the query when the search is submitted.
+    pub on_search: Callback<String>,
+}
+
+/// Search bar component.
+///
+/// Fetches suggestions once typing pauses; responses to outdated queries
+/// are ignored so the dropdown always matches the current input.
+#[function_component(SearchBar)]
+pub fn search_bar(props: &SearchBarProps) -> Html {
+    let query = use_state(String::new);
+    let suggestions = use_state(Vec::<PostDto>::new);
+    let open = use_state(|| false);
+    let active = use_state(|| None::<usize>);
+    // Dropping the pending timeout cancels it
+    let pending = use_mut_ref(|| None::<Timeout>);
+    let latest_request = use_mut_ref(|| 0u32);
+
+    let close = {
+        let open = open.clone();
+        let active = active.clone();
+        move || {
+            open.set(false);
+            active.set(None);
+        }
+    };
+
+    let on_input = {
+        let query = query.clone();
+        let suggestions = suggestions.clone();
+        let open = open.clone();
+        let active = active.clone();
+        Callback::from(move |e: InputEvent| {
+            let target: HtmlInputElement = e.target_unchecked_into();
+            let value = target.value();
+            query.set(value.clone());
+            active.set(None);
+
+            let request_id = {
+                let mut latest = latest_request.borrow_mut();
+                *latest = latest.wrapping_add(1);
+                *latest
+            };
+
+            let term = value.trim().to_string();
+            if term.chars().count() < SEARCH_MIN_QUERY_LEN {
+                pending.borrow_mut().take();
+                suggestions.set(Vec::new());
+                open.set(false);
+                return;
+            }
+
+            let suggestions = suggestions.clone();
+            let open = open.clone();
+            let latest_request = latest_request.clone();
+            let timeout = Timeout::new(SEARCH_DEBOUNCE_MS, move || {
+                spawn_local(async move {
+                    let result = api::search_posts(&term, SEARCH_SUGGESTION_LIMIT, 0).await;
+                    if *latest_request.borrow() != request_id {
+                        return;
+                    }
+                    // Suggestions are best-effort; errors just hide the dropdown
+                    let posts = result.map(|list| list.posts).unwrap_or_default();
+                    open.set(!posts.is_empty());
+                    suggestions.set(posts);
+                });
+            });
+            *pending.borrow_mut() = Some(timeout);
+        })
+    };
+
+    let on_keydown = {
+        let suggestions = suggestions.clone();
+        let open = open.clone();
+        let active = active.clone();
+        let close = close.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            let count = suggestions.len();
+            match e.key().as_str() {
+                "ArrowDown" if count > 0 => {
+                    e.prevent_default();
+                    open.set(true);
+                    active.set(Some(active.map_or(0, |i| (i + 1) % count)));
+                }
+                "ArrowUp" if count > 0 => {
+                    e.prevent_default();
+                    open.set(true);
+                    active.set(Some(active.map_or(count - 1, |i| (i + count - 1) % count)));
+                }
+                "Escape" => close(),
+                _ => {}
+            }
+        })
+    };
+
+    let on_submit = {
+        let query = query.clone();
+        let suggestions = suggestions.clone();
+        let active = active.clone();
+        let close = close.clone();
+        let on_open = props.on_open.clone();
+        let on_search = props.on_search.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let chosen = active
+                .and_then(|i| suggestions.get(i))
+                .map(|post| post.public_id.clone());
+            let term = query.trim().to_string();
+            close();
+            match chosen {
+                Some(post_id) => on_open.emit(post_id),
+                None if !term.is_empty() => on_search.emit(term),
+                None => {}
+            }
+        })
+    };
+
+    let on_focus = {
+        let suggestions = suggestions.clone();
+        let open = open.clone();
+        Callback::from(move |_: FocusEvent| open.set(!suggestions.is_empty()))
+    };
+
+    let on_blur = {
+        let close = close.clone();
+        Callback::from(move |_: FocusEvent| close())
+    };
+
+    let expanded = *open && !suggestions.is_empty();
+    let active_descendant = active
+        .filter(|_| expanded)
+        .map(|i| format!("{}-{}", SUGGESTIONS_ID, i));
+
+    html! {
+        <form class="search-bar" role="search" onsubmit={on_submit}>
+            <input
+                type="search"
+                id={SEARCH_INPUT_ID}
+                class="search-input"
+                placeholder="Search posts..."
+                autocomplete="off"
+                value={(*query).clone()}
+                oninput={on_input}
+                onkeydown={on_keydown}
+                onfocus={on_focus}
+                onblur={on_blur}
+                role="combobox"
+                aria-label="Search posts"
+                aria-keyshortcuts="/"
+                aria-autocomplete="list"
+                aria-controls={SUGGESTIONS_ID}
+                aria-expanded={expanded.to_string()}
+                aria-activedescendant={active_descendant}
+            />
+            if expanded {
+                <ul class="search-suggestions" id={SUGGESTIONS_ID} role="listbox">
+                    {for suggestions.iter().enumerate().map(|(index, post)| {
+                        let is_active = *active == Some(index);
+                        // mousedown fires before the input's blur closes the list
+                        let onmousedown = {
+                            let close = close.clone();
+                            let on_open = props.on_open.clone();
+                            let post_id = post.public_id.clone();
+                            Callback::from(move |e: MouseEvent| {
+                                e.prevent_default();
+                                close();
+                                on_open.emit(post_id.clone());
+                            })
+                        };
+                        html! {
+                            <li
+                                id={format!("{}-{}", SUGGESTIONS_ID, index)}
+                                class={classes!("search-suggestion", is_active.then_some("active"))}
+                                role="option"
+                                aria-selected={is_active.to_string()}
+                                {onmousedown}
+                            >
+                                {&post.title}
+                            </li>
+                        }
+                    })}
+                </ul>
+            }
+        </form>
+    }
+}
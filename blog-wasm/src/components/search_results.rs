@@ -0,0 +1,136 @@
+//! Search results page with pagination.
+
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use blog_shared::PostListResponse;
+
+use crate::api;
+use crate::components::{ErrorBoundary, PostCard, PostCardSkeletonList};
+use crate::constants::SEARCH_PAGE_SIZE;
+
+/// Search results properties.
+#[derive(Properties, PartialEq)]
+pub struct SearchResultsProps {
+    /// The submitted search query.
+    pub query: String,
+    /// Current user's ID (if authenticated).
+    #[prop_or_default]
+    pub current_user_id: Option<i64>,
+    /// Callback when a post is opened.
+    pub on_open: Callback<String>,
+    /// Callback when a post is edited.
+    #[prop_or_default]
+    pub on_edit: Option<Callback<String>>,
+}
+
+/// Search results component.
+///
+/// Keyed on the query by the parent, so a new search starts on page one.
+#[function_component(SearchResults)]
+pub fn search_results(props: &SearchResultsProps) -> Html {
+    let results = use_state(|| None::<PostListResponse>);
+    let load_error = use_state(|| None::<String>);
+    let page = use_state(|| 0i64);
+    let reload = use_state(|| 0u32);
+    let limit = SEARCH_PAGE_SIZE;
+
+    {
+        let results = results.clone();
+        let load_error = load_error.clone();
+        let query = props.query.clone();
+        use_effect_with(
+            (*page, *reload, props.current_user_id),
+            move |(page, _, _)| {
+                let page = *page;
+                results.set(None);
+                load_error.set(None);
+                spawn_local(async move {
+                    match api::search_posts(&query, limit, page * limit).await {
+                        Ok(response) => results.set(Some(response)),
+                        Err(e) => load_error.set(Some(format!("Search failed: {}", e.message))),
+                    }
+                });
+                || ()
+            },
+        );
+    }
+
+    let on_retry = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(*reload + 1))
+    };
+
+    let total = results.as_ref().map_or(0, |r| r.total);
+    let total_pages = (total + limit - 1) / limit;
+    let has_prev = *page > 0;
+    let has_next = *page + 1 < total_pages;
+
+    let on_prev_page = {
+        let page = page.clone();
+        Callback::from(move |_: MouseEvent| page.set(*page - 1))
+    };
+
+    let on_next_page = {
+        let page = page.clone();
+        Callback::from(move |_: MouseEvent| page.set(*page + 1))
+    };
+
+    html! {
+        <section class="search-results" aria-labelledby="search-results-title">
+            <h2 id="search-results-title">{format!("Search results for “{}”", props.query)}</h2>
+            <ErrorBoundary error={(*load_error).clone()} {on_retry}>
+                if let Some(ref results) = *results {
+                    <p class="search-results-count" aria-live="polite">
+                        {match results.total {
+                            1 => "1 post found".to_string(),
+                            n => format!("{} posts found", n),
+                        }}
+                    </p>
+                    if results.posts.is_empty() {
+                        <div class="empty-state">
+                            <p>{"No posts match your search."}</p>
+                        </div>
+                    } else {
+                        <div class="post-grid">
+                            {for results.posts.iter().map(|post| html! {
+                                <PostCard
+                                    key={post.public_id.clone()}
+                                    post={post.clone()}
+                                    is_owner={post.is_owner}
+                                    on_edit={props.on_edit.clone()}
+                                    on_open={Some(props.on_open.clone())}
+                                />
+                            })}
+                        </div>
+                    }
+                    if total_pages > 1 {
+                        <nav class="pagination" aria-label="Pagination">
+                            <button
+                                class="btn btn-secondary"
+                                onclick={on_prev_page}
+                                disabled={!has_prev}
+                                aria-label="Previous page"
+                            >
+                                {"← Previous"}
+                            </button>
+                            <span class="pagination-info" aria-live="polite">
+                                {format!("Page {} of {}", *page + 1, total_pages)}
+                            </span>
+                            <button
+                                class="btn btn-secondary"
+                                onclick={on_next_page}
+                                disabled={!has_next}
+                                aria-label="Next page"
+                            >
+                                {"Next →"}
+                            </button>
+                        </nav>
+                    }
+                } else {
+                    <PostCardSkeletonList />
+                }
+            </ErrorBoundary>
+        </section>
+    }
+}
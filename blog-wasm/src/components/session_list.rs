@@ -0,0 +1,149 @@
+//! Sessions section: signed-in devices with per-device sign out.
+
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use blog_shared::SessionDto;
+
+use crate::api;
+use crate::components::{ConfirmDialog, ErrorBoundary};
+
+/// Session list properties.
+#[derive(Properties, PartialEq)]
+pub struct SessionListProps {
+    /// Changing this value reloads the sessions.
+    #[prop_or_default]
+    pub refresh: u32,
+}
+
+/// Session list component.
+#[function_component(SessionList)]
+pub fn session_list(props: &SessionListProps) -> Html {
+    let sessions = use_state(Vec::<SessionDto>::new);
+    let loading = use_state(|| true);
+    let load_error = use_state(|| None::<String>);
+    let error = use_state(|| None::<String>);
+    let reload = use_state(|| 0u32);
+    // Session awaiting confirmation of its sign out
+    let pending_revoke = use_state(|| None::<SessionDto>);
+
+    {
+        let sessions = sessions.clone();
+        let loading = loading.clone();
+        let load_error = load_error.clone();
+        use_effect_with((props.refresh, *reload), move |_| {
+            loading.set(true);
+            load_error.set(None);
+            spawn_local(async move {
+                match api::list_sessions().await {
+                    Ok(list) => sessions.set(list.sessions),
+                    Err(e) => {
+                        load_error.set(Some(format!("Failed to load sessions: {}", e.message)))
+                    }
+                }
+                loading.set(false);
+            });
+            || ()
+        });
+    }
+
+    let on_retry = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(*reload + 1))
+    };
+
+    let on_revoke = {
+        let sessions = sessions.clone();
+        let error = error.clone();
+        let pending_revoke = pending_revoke.clone();
+        Callback::from(move |_| {
+            let Some(id) = pending_revoke.as_ref().map(|s| s.id.clone()) else {
+                return;
+            };
+            pending_revoke.set(None);
+            let sessions = sessions.clone();
+            let error = error.clone();
+            error.set(None);
+            spawn_local(async move {
+                match api::revoke_session(&id).await {
+                    Ok(()) => {
+                        let remaining = sessions.iter().filter(|s| s.id != id).cloned().collect();
+                        sessions.set(remaining);
+                    }
+                    Err(e) => error.set(Some(e.message)),
+                }
+            });
+        })
+    };
+
+    let on_revoke_cancel = {
+        let pending_revoke = pending_revoke.clone();
+        Callback::from(move |_| pending_revoke.set(None))
+    };
+
+    let pending_device = pending_revoke
+        .as_ref()
+        .and_then(|s| s.user_agent.clone())
+        .unwrap_or_else(|| "The device".to_string());
+
+    html! {
+        <section class="settings-section" aria-labelledby="settings-sessions-title">
+            <h3 id="settings-sessions-title">{"Sessions"}</h3>
+            <p class="form-hint">{"Devices currently signed in to your account."}</p>
+            <ErrorBoundary error={(*load_error).clone()} {on_retry}>
+                if let Some(ref err) = *error {
+                    <div class="message message-error" role="alert">{err}</div>
+                }
+                <ul class="session-list" aria-busy={loading.to_string()}>
+                    {for sessions.iter().map(|session| {
+                        let device = session
+                            .user_agent
+                            .clone()
+                            .unwrap_or_else(|| "Unknown device".to_string());
+                        let on_click = {
+                            let pending_revoke = pending_revoke.clone();
+                            let session = session.clone();
+                            Callback::from(move |_: MouseEvent| {
+                                pending_revoke.set(Some(session.clone()))
+                            })
+                        };
+                        html! {
+                            <li key={session.id.clone()} class="session-item">
+                                <div class="session-info">
+                                    <span class="session-device">{device.clone()}</span>
+                                    <span class="session-meta">
+                                        {format!(
+                                            "Signed in {} · last active {}",
+                                            session.created_at.format("%B %d, %Y"),
+                                            session.last_seen_at.format("%B %d, %Y %H:%M"),
+                                        )}
+                                    </span>
+                                </div>
+                                if session.current {
+                                    <span class="session-current">{"This device"}</span>
+                                } else {
+                                    <button
+                                        type="button"
+                                        class="btn btn-secondary btn-sm"
+                                        onclick={on_click}
+                                        aria-label={format!("Sign out {}", device)}
+                                    >
+                                        {"Sign out"}
+                                    </button>
+                                }
+                            </li>
+                        }
+                    })}
+                </ul>
+            </ErrorBoundary>
+            <ConfirmDialog
+                open={pending_revoke.is_some()}
+                title="Sign out session?"
+                message={format!("{} will be signed out and need to log in again.", pending_device)}
+                confirm_label="Sign out"
+                on_confirm={on_revoke}
+                on_cancel={on_revoke_cancel}
+            />
+        </section>
+    }
+}
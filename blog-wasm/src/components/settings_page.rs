@@ -0,0 +1,75 @@
+//! Settings page: profile, account, recovery codes, sessions, and preferences
+//! sections.
+
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use blog_shared::SettingsDto;
+
+use crate::api;
+use crate::components::{
+    AccountSettings, DeleteAccount, ErrorBoundary, PreferencesSettings, ProfileSettings,
+    RecoveryCodes, SessionList, SettingsSkeleton,
+};
+
+/// Settings page properties.
+#[derive(Properties, PartialEq)]
+pub struct SettingsPageProps {
+    /// Callback after the account was deleted (the user is signed out).
+    pub on_account_deleted: Callback<()>,
+}
+
+/// Settings page component.
+#[function_component(SettingsPage)]
+pub fn settings_page(props: &SettingsPageProps) -> Html {
+    let settings = use_state(|| None::<SettingsDto>);
+    let load_error = use_state(|| None::<String>);
+    let reload = use_state(|| 0u32);
+    // Bumped after a password change, which signs out other sessions
+    let sessions_refresh = use_state(|| 0u32);
+
+    {
+        let settings = settings.clone();
+        let load_error = load_error.clone();
+        use_effect_with(*reload, move |_| {
+            load_error.set(None);
+            spawn_local(async move {
+                match api::get_settings().await {
+                    Ok(loaded) => settings.set(Some(loaded)),
+                    Err(e) => {
+                        load_error.set(Some(format!("Failed to load settings: {}", e.message)))
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_retry = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(*reload + 1))
+    };
+
+    let on_password_changed = {
+        let sessions_refresh = sessions_refresh.clone();
+        Callback::from(move |_| sessions_refresh.set(*sessions_refresh + 1))
+    };
+
+    html! {
+        <div class="settings">
+            <h2>{"Settings"}</h2>
+            <ErrorBoundary error={(*load_error).clone()} {on_retry}>
+                if let Some(ref settings) = *settings {
+                    <ProfileSettings profile={settings.profile.clone()} />
+                    <AccountSettings email={settings.email.clone()} {on_password_changed} />
+                    <RecoveryCodes />
+                    <SessionList refresh={*sessions_refresh} />
+                    <PreferencesSettings preferences={settings.preferences.clone()} />
+                    <DeleteAccount on_deleted={props.on_account_deleted.clone()} />
+                } else {
+                    <SettingsSkeleton />
+                }
+            </ErrorBoundary>
+        </div>
+    }
+}
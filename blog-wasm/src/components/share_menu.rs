@@ -0,0 +1,154 @@
+//! Share menu for a post: copy link, Web Share API, Twitter, and Mastodon.
+
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::{JsFuture, spawn_local};
+use web_sys::{KeyboardEvent, ShareData, UrlSearchParams, window};
+use yew::prelude::*;
+
+use crate::components::{Toast, use_toast};
+use crate::constants::{MASTODON_SHARE_URL, TWITTER_SHARE_URL};
+use crate::location;
+
+/// Share menu properties.
+#[derive(Properties, PartialEq)]
+pub struct ShareMenuProps {
+    /// Public ID of the post to share.
+    pub post_id: String,
+    /// Post title (shared as the text).
+    pub title: AttrValue,
+}
+
+/// Share menu component.
+///
+/// The native share sheet entry only appears where the browser supports
+/// the Web Share API (mostly mobile).
+#[function_component(ShareMenu)]
+pub fn share_menu(props: &ShareMenuProps) -> Html {
+    let open = use_state(|| false);
+    let (toast, show_toast) = use_toast();
+    let url = location::post_permalink(&props.post_id).unwrap_or_default();
+
+    let on_toggle = {
+        let open = open.clone();
+        Callback::from(move |_: MouseEvent| open.set(!*open))
+    };
+
+    let on_keydown = {
+        let open = open.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() == "Escape" && *open {
+                e.stop_propagation();
+                open.set(false);
+            }
+        })
+    };
+
+    let on_copy = {
+        let open = open.clone();
+        let url = url.clone();
+        Callback::from(move |_: MouseEvent| {
+            open.set(false);
+            let show_toast = show_toast.clone();
+            let url = url.clone();
+            spawn_local(async move {
+                let copied = match window() {
+                    Some(win) => JsFuture::from(win.navigator().clipboard().write_text(&url))
+                        .await
+                        .is_ok(),
+                    None => false,
+                };
+                show_toast.emit(if copied {
+                    "Link copied to clipboard".into()
+                } else {
+                    "Could not copy the link".into()
+                });
+            });
+        })
+    };
+
+    let on_native_share = {
+        let open = open.clone();
+        let url = url.clone();
+        let title = props.title.clone();
+        Callback::from(move |_: MouseEvent| {
+            open.set(false);
+            let Some(win) = window() else {
+                return;
+            };
+            let data = ShareData::new();
+            data.set_title(&title);
+            data.set_url(&url);
+            // Dismissing the share sheet rejects the promise; nothing to report
+            let promise = win.navigator().share_with_data(&data);
+            spawn_local(async move {
+                let _ = JsFuture::from(promise).await;
+            });
+        })
+    };
+
+    let twitter_url = intent_url(TWITTER_SHARE_URL, &props.title, &url);
+    let mastodon_url = intent_url(MASTODON_SHARE_URL, &props.title, &url);
+    let menu_id = format!("share-menu-{}", props.post_id);
+
+    html! {
+        <div class="share" onkeydown={on_keydown}>
+            <button
+                type="button"
+                class="btn btn-secondary btn-sm"
+                onclick={on_toggle}
+                aria-haspopup="true"
+                aria-expanded={open.to_string()}
+                aria-controls={menu_id.clone()}
+                aria-label={format!("Share post: {}", props.title)}
+            >
+                {"Share"}
+            </button>
+            if *open {
+                <ul class="share-menu" id={menu_id}>
+                    <li>
+                        <button type="button" class="btn btn-link btn-sm" onclick={on_copy}>
+                            {"Copy link"}
+                        </button>
+                    </li>
+                    if supports_web_share() {
+                        <li>
+                            <button type="button" class="btn btn-link btn-sm" onclick={on_native_share}>
+                                {"Share via…"}
+                            </button>
+                        </li>
+                    }
+                    <li>
+                        <a href={twitter_url} target="_blank" rel="noopener noreferrer">
+                            {"Twitter"}
+                        </a>
+                    </li>
+                    <li>
+                        <a href={mastodon_url} target="_blank" rel="noopener noreferrer">
+                            {"Mastodon"}
+                        </a>
+                    </li>
+                </ul>
+            }
+            <Toast message={toast} />
+        </div>
+    }
+}
+
+/// Builds a share intent URL with `text` and `url` parameters.
+fn intent_url(base: &str, text: &str, url: &str) -> String {
+    let Ok(params) = UrlSearchParams::new() else {
+        return base.to_string();
+    };
+    params.append("text", text);
+    params.append("url", url);
+    let query: String = params.to_string().into();
+    format!("{}?{}", base, query)
+}
+
+/// Returns true if the browser implements `navigator.share`.
+fn supports_web_share() -> bool {
+    window()
+        .map(|win| js_sys::Reflect::has(&win.navigator(), &JsValue::from_str("share")))
+        .and_then(Result::ok)
+        .unwrap_or(false)
+}
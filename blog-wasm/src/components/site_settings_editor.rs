@@ -0,0 +1,216 @@
+//! Site settings editor for admins.
+
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+use yew::prelude::*;
+
+use blog_shared::constants::{
+    MAX_POSTS_PER_PAGE, MAX_SITE_DESCRIPTION_LENGTH, MAX_SITE_TITLE_LENGTH, SUPPORTED_LANGUAGES,
+};
+use blog_shared::{RegistrationMode, UpdateSiteSettingsRequest};
+
+use super::preferences_settings::language_name;
+use crate::api;
+use crate::site::SiteContext;
+
+/// Registration modes offered in the form, with their labels.
+const REGISTRATION_MODES: &[(RegistrationMode, &str)] = &[
+    (RegistrationMode::Open, "Open to everyone"),
+    (RegistrationMode::InviteOnly, "Invite only"),
+    (RegistrationMode::Closed, "Closed"),
+];
+
+/// Site settings editor component.
+///
+/// Saved settings show at once in the header and page title.
+#[function_component(SiteSettingsEditor)]
+pub fn site_settings_editor() -> Html {
+    let site = use_context::<SiteContext>();
+    let title = use_state(String::new);
+    let description = use_state(String::new);
+    let posts_per_page = use_state(String::new);
+    let language = use_state(String::new);
+    let registration_mode = use_state(RegistrationMode::default);
+    let error = use_state(|| None::<String>);
+    let saved = use_state(|| false);
+    let saving = use_state(|| false);
+
+    // The context may hold cached settings, so start from the server's
+    {
+        let title = title.clone();
+        let description = description.clone();
+        let posts_per_page = posts_per_page.clone();
+        let language = language.clone();
+        let registration_mode = registration_mode.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                if let Ok(settings) = api::get_site_settings().await {
+                    title.set(settings.title);
+                    description.set(settings.description);
+                    posts_per_page.set(settings.posts_per_page.to_string());
+                    language.set(settings.default_language);
+                    registration_mode.set(settings.registration_mode);
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_title_input = {
+        let title = title.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            title.set(input.value());
+        })
+    };
+
+    let on_description_input = {
+        let description = description.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlTextAreaElement = e.target_unchecked_into();
+            description.set(input.value());
+        })
+    };
+
+    let on_posts_per_page_input = {
+        let posts_per_page = posts_per_page.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            posts_per_page.set(input.value());
+        })
+    };
+
+    let on_language_change = {
+        let language = language.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            language.set(select.value());
+        })
+    };
+
+    let on_registration_change = {
+        let registration_mode = registration_mode.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Ok(value) = select.value().parse() {
+                registration_mode.set(value);
+            }
+        })
+    };
+
+    let onsubmit = {
+        let title = title.clone();
+        let description = description.clone();
+        let posts_per_page = posts_per_page.clone();
+        let language = language.clone();
+        let registration_mode = registration_mode.clone();
+        let error = error.clone();
+        let saved = saved.clone();
+        let saving = saving.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let Ok(page_size) = posts_per_page.trim().parse::<i64>() else {
+                error.set(Some("Posts per page must be a whole number".to_string()));
+                return;
+            };
+            let req = UpdateSiteSettingsRequest {
+                title: Some((*title).clone()),
+                description: Some((*description).clone()),
+                posts_per_page: Some(page_size),
+                default_language: Some((*language).clone()),
+                registration_mode: Some(*registration_mode),
+            };
+            let site = site.clone();
+            let error = error.clone();
+            let saved = saved.clone();
+            let saving = saving.clone();
+
+            saving.set(true);
+            saved.set(false);
+            error.set(None);
+            spawn_local(async move {
+                match api::update_site_settings(req).await {
+                    Ok(settings) => {
+                        if let Some(site) = site {
+                            site.update(settings);
+                        }
+                        saved.set(true);
+                    }
+                    Err(e) => error.set(Some(e.message)),
+                }
+                saving.set(false);
+            });
+        })
+    };
+
+    html! {
+        <section class="admin-section" aria-labelledby="admin-site-settings-title">
+            <h3 id="admin-site-settings-title">{"Site settings"}</h3>
+            if let Some(ref err) = *error {
+                <div class="message message-error" role="alert">{err}</div>
+            }
+            if *saved {
+                <div class="message message-success" role="status">{"Settings saved."}</div>
+            }
+            <form class="site-settings-form" {onsubmit} aria-busy={saving.to_string()}>
+                <div class="form-group">
+                    <label for="site-title">{"Title"}</label>
+                    <input
+                        id="site-title"
+                        type="text"
+                        maxlength={MAX_SITE_TITLE_LENGTH.to_string()}
+                        value={(*title).clone()}
+                        oninput={on_title_input}
+                        required=true
+                    />
+                </div>
+                <div class="form-group">
+                    <label for="site-description">{"Description"}</label>
+                    <textarea
+                        id="site-description"
+                        rows="2"
+                        maxlength={MAX_SITE_DESCRIPTION_LENGTH.to_string()}
+                        value={(*description).clone()}
+                        oninput={on_description_input}
+                    />
+                    <p class="form-hint">{"Shown to search engines and in the feeds."}</p>
+                </div>
+                <div class="form-group">
+                    <label for="site-posts-per-page">{"Posts per page"}</label>
+                    <input
+                        id="site-posts-per-page"
+                        type="number"
+                        min="1"
+                        max={MAX_POSTS_PER_PAGE.to_string()}
+                        value={(*posts_per_page).clone()}
+                        oninput={on_posts_per_page_input}
+                        required=true
+                    />
+                </div>
+                <div class="form-group">
+                    <label for="site-language">{"Default language"}</label>
+                    <select id="site-language" onchange={on_language_change}>
+                        {for SUPPORTED_LANGUAGES.iter().map(|code| html! {
+                            <option value={*code} selected={*language == *code}>
+                                {language_name(code)}
+                            </option>
+                        })}
+                    </select>
+                </div>
+                <div class="form-group">
+                    <label for="site-registration">{"Registration"}</label>
+                    <select id="site-registration" onchange={on_registration_change}>
+                        {for REGISTRATION_MODES.iter().map(|(value, label)| html! {
+                            <option value={value.as_str()} selected={*registration_mode == *value}>
+                                {*label}
+                            </option>
+                        })}
+                    </select>
+                </div>
+                <button type="submit" class="btn btn-primary" disabled={*saving}>
+                    {"Save"}
+                </button>
+            </form>
+        </section>
+    }
+}
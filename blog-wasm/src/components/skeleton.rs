@@ -0,0 +1,58 @@
+//! Skeleton placeholders shown while content is loading.
+
+use yew::prelude::*;
+
+/// Skeleton list properties.
+#[derive(Properties, PartialEq)]
+pub struct PostCardSkeletonListProps {
+    /// Number of placeholder cards.
+    #[prop_or(3)]
+    pub count: usize,
+}
+
+/// Placeholder post cards matching the `PostCard` layout (`index.html`
+/// repeats the markup in its loading shell).
+#[function_component(PostCardSkeletonList)]
+pub fn post_card_skeleton_list(props: &PostCardSkeletonListProps) -> Html {
+    html! {
+        <div class="post-grid" aria-busy="true" aria-label="Loading posts">
+            {for (0..props.count).map(|_| html! {
+                <div class="post-card skeleton-card">
+                    <div class="skeleton skeleton-title"></div>
+                    <div class="skeleton skeleton-meta"></div>
+                    <div class="skeleton skeleton-line"></div>
+                    <div class="skeleton skeleton-line"></div>
+                    <div class="skeleton skeleton-line skeleton-line-short"></div>
+                </div>
+            })}
+        </div>
+    }
+}
+
+/// Placeholder for the post form while the post is loading.
+#[cfg(feature = "editor")]
+#[function_component(PostFormSkeleton)]
+pub fn post_form_skeleton() -> Html {
+    html! {
+        <div class="post-form" aria-busy="true" aria-label="Loading post">
+            <div class="skeleton skeleton-label"></div>
+            <div class="skeleton skeleton-input"></div>
+            <div class="skeleton skeleton-label"></div>
+            <div class="skeleton skeleton-textarea"></div>
+        </div>
+    }
+}
+
+/// Placeholder for the settings page while settings are loading.
+#[function_component(SettingsSkeleton)]
+pub fn settings_skeleton() -> Html {
+    html! {
+        <section class="settings-section" aria-busy="true" aria-label="Loading settings">
+            <div class="skeleton skeleton-title"></div>
+            <div class="skeleton skeleton-label"></div>
+            <div class="skeleton skeleton-textarea"></div>
+            <div class="skeleton skeleton-label"></div>
+            <div class="skeleton skeleton-input"></div>
+        </section>
+    }
+}
@@ -0,0 +1,50 @@
+//! Tag chips shown on posts.
+
+use yew::prelude::*;
+
+/// Tag chips properties.
+#[derive(Properties, PartialEq)]
+pub struct TagChipsProps {
+    /// Tags to show.
+    pub tags: Vec<String>,
+    /// Callback with the tag when a chip is clicked (chips are plain text without it).
+    #[prop_or_default]
+    pub on_select: Option<Callback<String>>,
+}
+
+/// Tag chips component. Renders nothing for untagged posts.
+#[function_component(TagChips)]
+pub fn tag_chips(props: &TagChipsProps) -> Html {
+    if props.tags.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <ul class="tag-chips" aria-label="Tags">
+            {for props.tags.iter().map(|tag| {
+                let label = format!("#{}", tag);
+                match props.on_select.clone() {
+                    Some(on_select) => {
+                        let onclick = {
+                            let tag = tag.clone();
+                            Callback::from(move |_: MouseEvent| on_select.emit(tag.clone()))
+                        };
+                        html! {
+                            <li>
+                                <button
+                                    type="button"
+                                    class="tag-chip"
+                                    {onclick}
+                                    aria-label={format!("Show posts tagged {}", tag)}
+                                >
+                                    {label}
+                                </button>
+                            </li>
+                        }
+                    }
+                    None => html! { <li class="tag-chip">{label}</li> },
+                }
+            })}
+        </ul>
+    }
+}
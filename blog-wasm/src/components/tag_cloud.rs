@@ -0,0 +1,112 @@
+//! Tag cloud component with usage-weighted tag sizes and the selected tag's
+//! description.
+
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use blog_shared::TagDto;
+
+use crate::api;
+use crate::constants::TAG_CLOUD_LIMIT;
+
+/// Smallest and largest tag font sizes, in rem.
+const MIN_FONT_REM: f64 = 0.8;
+const MAX_FONT_REM: f64 = 1.6;
+
+/// Tag cloud properties.
+#[derive(Properties, PartialEq)]
+pub struct TagCloudProps {
+    /// Currently active tag filter.
+    #[prop_or_default]
+    pub selected: Option<String>,
+    /// Callback with the new filter (`None` clears it).
+    pub on_select: Callback<Option<String>>,
+    /// Bumped by the parent to force a reload.
+    #[prop_or_default]
+    pub refresh: u32,
+}
+
+/// Tag cloud component. Hidden until there are tags to show.
+#[function_component(TagCloud)]
+pub fn tag_cloud(props: &TagCloudProps) -> Html {
+    let tags = use_state(Vec::<TagDto>::new);
+
+    {
+        let tags = tags.clone();
+        use_effect_with(props.refresh, move |_| {
+            spawn_local(async move {
+                // The cloud is optional; on failure it simply stays hidden
+                if let Ok(response) = api::list_tags(TAG_CLOUD_LIMIT).await {
+                    tags.set(response.tags);
+                }
+            });
+            || ()
+        });
+    }
+
+    if tags.is_empty() {
+        return html! {};
+    }
+
+    let min = tags.iter().map(|t| t.count).min().unwrap_or(1);
+    let max = tags.iter().map(|t| t.count).max().unwrap_or(1);
+
+    // Alphabetical order reads better than the server's usage order
+    let mut sorted: Vec<&TagDto> = tags.iter().collect();
+    sorted.sort_by(|a, b| a.name.cmp(&b.name));
+    let description = tags
+        .iter()
+        .find(|tag| props.selected.as_deref() == Some(tag.name.as_str()))
+        .and_then(|tag| tag.description.clone());
+
+    html! {
+        <nav class="tag-cloud" aria-label="Tags">
+            {for sorted.into_iter().map(|tag| {
+                let is_selected = props.selected.as_deref() == Some(tag.name.as_str());
+                let onclick = {
+                    let on_select = props.on_select.clone();
+                    let name = tag.name.clone();
+                    Callback::from(move |_: MouseEvent| {
+                        on_select.emit((!is_selected).then(|| name.clone()));
+                    })
+                };
+                html! {
+                    <button
+                        type="button"
+                        class={classes!("tag-cloud-item", is_selected.then_some("selected"))}
+                        style={format!("font-size: {:.2}rem", font_size(tag.count, min, max))}
+                        aria-pressed={is_selected.to_string()}
+                        title={format!("{} posts", tag.count)}
+                        {onclick}
+                    >
+                        {format!("#{}", tag.name)}
+                    </button>
+                }
+            })}
+            if props.selected.is_some() {
+                <button
+                    type="button"
+                    class="btn btn-link btn-sm"
+                    onclick={
+                        let on_select = props.on_select.clone();
+                        move |_| on_select.emit(None)
+                    }
+                >
+                    {"Clear filter"}
+                </button>
+            }
+            if let Some(description) = description {
+                <p class="tag-description">{description}</p>
+            }
+        </nav>
+    }
+}
+
+/// Scales a tag's usage count linearly between the min and max font sizes.
+fn font_size(count: i64, min: i64, max: i64) -> f64 {
+    if max == min {
+        return (MIN_FONT_REM + MAX_FONT_REM) / 2.0;
+    }
+    let weight = (count - min) as f64 / (max - min) as f64;
+    MIN_FONT_REM + weight * (MAX_FONT_REM - MIN_FONT_REM)
+}
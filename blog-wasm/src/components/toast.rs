@@ -0,0 +1,49 @@
+//! Toast: short-lived status message at the bottom of the screen.
+
+use gloo_timers::callback::Timeout;
+use yew::prelude::*;
+
+use crate::constants::TOAST_DURATION_MS;
+
+/// Toast properties.
+#[derive(Properties, PartialEq)]
+pub struct ToastProps {
+    /// Message to show (nothing while `None`).
+    pub message: Option<AttrValue>,
+}
+
+/// Toast component.
+///
+/// The live region is always rendered so screen readers announce messages
+/// as they appear.
+#[function_component(Toast)]
+pub fn toast(props: &ToastProps) -> Html {
+    html! {
+        <div class="toast-region" role="status" aria-live="polite">
+            if let Some(ref message) = props.message {
+                <div class="toast">{message.clone()}</div>
+            }
+        </div>
+    }
+}
+
+/// Returns the current toast message and a callback that shows a message
+/// for [`TOAST_DURATION_MS`] (replacing any message still visible).
+#[hook]
+pub fn use_toast() -> (Option<AttrValue>, Callback<AttrValue>) {
+    let message = use_state(|| None::<AttrValue>);
+    let timeout = use_mut_ref(|| None::<Timeout>);
+
+    let show = {
+        let message = message.clone();
+        Callback::from(move |text: AttrValue| {
+            message.set(Some(text));
+            let message = message.clone();
+            // Replacing the timeout cancels the previous one
+            *timeout.borrow_mut() =
+                Some(Timeout::new(TOAST_DURATION_MS, move || message.set(None)));
+        })
+    };
+
+    ((*message).clone(), show)
+}
@@ -0,0 +1,130 @@
+//! Trash page: the current user's deleted posts, restorable until purged.
+
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use blog_shared::TrashedPostDto;
+
+use crate::api;
+use crate::components::ErrorBoundary;
+
+/// Trash page properties.
+#[derive(Properties, PartialEq)]
+pub struct TrashPageProps {
+    /// Callback with the public ID of a restored post.
+    pub on_restored: Callback<String>,
+}
+
+/// Describes when a deleted post is purged.
+fn purge_label(post: &TrashedPostDto) -> String {
+    match post.days_until_purge {
+        Some(0) => "Deleted for good at the next cleanup".to_string(),
+        Some(1) => "Deleted for good in 1 day".to_string(),
+        Some(days) => format!("Deleted for good in {} days", days),
+        None => "Kept until restored".to_string(),
+    }
+}
+
+/// Trash page component.
+#[function_component(TrashPage)]
+pub fn trash_page(props: &TrashPageProps) -> Html {
+    let posts = use_state(Vec::<TrashedPostDto>::new);
+    let loading = use_state(|| true);
+    let load_error = use_state(|| None::<String>);
+    let error = use_state(|| None::<String>);
+    let reload = use_state(|| 0u32);
+
+    {
+        let posts = posts.clone();
+        let loading = loading.clone();
+        let load_error = load_error.clone();
+        use_effect_with(*reload, move |_| {
+            loading.set(true);
+            load_error.set(None);
+            spawn_local(async move {
+                match api::list_trash().await {
+                    Ok(list) => posts.set(list.posts),
+                    Err(e) => load_error.set(Some(format!("Failed to load trash: {}", e.message))),
+                }
+                loading.set(false);
+            });
+            || ()
+        });
+    }
+
+    let on_retry = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(*reload + 1))
+    };
+
+    let on_restore = {
+        let posts = posts.clone();
+        let error = error.clone();
+        let on_restored = props.on_restored.clone();
+        Callback::from(move |id: String| {
+            let posts = posts.clone();
+            let error = error.clone();
+            let on_restored = on_restored.clone();
+            error.set(None);
+            spawn_local(async move {
+                match api::restore_post(&id).await {
+                    Ok(()) => {
+                        let remaining = posts
+                            .iter()
+                            .filter(|p| p.public_id != id)
+                            .cloned()
+                            .collect();
+                        posts.set(remaining);
+                        on_restored.emit(id);
+                    }
+                    Err(e) => error.set(Some(e.message)),
+                }
+            });
+        })
+    };
+
+    html! {
+        <div class="trash-page">
+            <h2>{"Trash"}</h2>
+            <p class="form-hint">{"Deleted posts stay here until you restore them or they are deleted for good."}</p>
+            <ErrorBoundary error={(*load_error).clone()} {on_retry}>
+                if let Some(ref err) = *error {
+                    <div class="message message-error" role="alert">{err}</div>
+                }
+                if posts.is_empty() && !*loading {
+                    <p class="empty-state">{"Your trash is empty."}</p>
+                }
+                <ul class="trash-list" aria-busy={loading.to_string()}>
+                    {for posts.iter().map(|post| {
+                        let onclick = on_restore.reform({
+                            let id = post.public_id.clone();
+                            move |_: MouseEvent| id.clone()
+                        });
+                        html! {
+                            <li key={post.public_id.clone()} class="trash-item">
+                                <div class="trash-info">
+                                    <span class="trash-title">{&post.title}</span>
+                                    <span class="trash-meta">
+                                        {format!(
+                                            "Deleted {} · {}",
+                                            post.deleted_at.format("%B %d, %Y %H:%M"),
+                                            purge_label(post),
+                                        )}
+                                    </span>
+                                </div>
+                                <button
+                                    type="button"
+                                    class="btn btn-secondary btn-sm"
+                                    {onclick}
+                                    aria-label={format!("Restore {}", post.title)}
+                                >
+                                    {"Restore"}
+                                </button>
+                            </li>
+                        }
+                    })}
+                </ul>
+            </ErrorBoundary>
+        </div>
+    }
+}
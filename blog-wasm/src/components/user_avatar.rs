@@ -0,0 +1,39 @@
+//! User avatar: profile picture or initial, from the user cache.
+
+use yew::prelude::*;
+
+use crate::user_cache::use_user;
+
+/// User avatar properties.
+#[derive(Properties, PartialEq)]
+pub struct UserAvatarProps {
+    /// Public ID of the user whose avatar is shown.
+    pub user_id: AttrValue,
+    /// Name shown until the profile loads (also the alt text).
+    pub username: AttrValue,
+}
+
+/// User avatar component.
+///
+/// Decorative next to the author name, so it is hidden from screen readers.
+#[function_component(UserAvatar)]
+pub fn user_avatar(props: &UserAvatarProps) -> Html {
+    let user = use_user(props.user_id.to_string());
+    let avatar_url = user.and_then(|u| u.avatar_url);
+    let initial = props
+        .username
+        .chars()
+        .next()
+        .map(|c| c.to_uppercase().to_string())
+        .unwrap_or_default();
+
+    html! {
+        <span class="user-avatar" aria-hidden="true">
+            if let Some(url) = avatar_url {
+                <img src={url} alt="" loading="lazy" />
+            } else {
+                {initial}
+            }
+        </span>
+    }
+}
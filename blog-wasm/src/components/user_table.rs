@@ -0,0 +1,256 @@
+//! User management table: change roles, suspend, and shadow-ban accounts.
+
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+
+use blog_shared::{AdminUserDto, Role, UpdateUserRequest};
+
+use crate::api;
+use crate::components::ErrorBoundary;
+use crate::constants::ADMIN_PAGE_SIZE;
+
+/// Roles selectable in the table.
+const ROLES: [Role; 4] = [Role::User, Role::Editor, Role::Moderator, Role::Admin];
+
+/// User table properties.
+#[derive(Properties, PartialEq)]
+pub struct UserTableProps {
+    /// Current user's ID (their own row cannot be changed).
+    pub current_user_id: Option<i64>,
+}
+
+/// User table component.
+#[function_component(UserTable)]
+pub fn user_table(props: &UserTableProps) -> Html {
+    let users = use_state(Vec::<AdminUserDto>::new);
+    let total = use_state(|| 0i64);
+    let page = use_state(|| 0i64);
+    let load_error = use_state(|| None::<String>);
+    let error = use_state(|| None::<String>);
+    let reload = use_state(|| 0u32);
+
+    {
+        let users = users.clone();
+        let total = total.clone();
+        let load_error = load_error.clone();
+        use_effect_with((*page, *reload), move |(page, _)| {
+            let offset = *page * ADMIN_PAGE_SIZE;
+            load_error.set(None);
+            spawn_local(async move {
+                match api::list_admin_users(ADMIN_PAGE_SIZE, offset).await {
+                    Ok(list) => {
+                        users.set(list.users);
+                        total.set(list.total);
+                    }
+                    Err(e) => load_error.set(Some(format!("Failed to load users: {}", e.message))),
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_retry = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(*reload + 1))
+    };
+
+    // Sends a change and swaps in the updated row
+    let on_update = {
+        let users = users.clone();
+        let error = error.clone();
+        Callback::from(move |(id, req): (i64, UpdateUserRequest)| {
+            let users = users.clone();
+            let error = error.clone();
+            error.set(None);
+            spawn_local(async move {
+                match api::update_admin_user(id, req).await {
+                    Ok(updated) => users.set(replace_row(&users, updated)),
+                    Err(e) => error.set(Some(e.message)),
+                }
+            });
+        })
+    };
+
+    // Shadow-bans (true) or lifts the ban (false) and swaps in the updated row
+    let on_shadow_ban = {
+        let users = users.clone();
+        let error = error.clone();
+        Callback::from(move |(id, ban): (i64, bool)| {
+            let users = users.clone();
+            let error = error.clone();
+            error.set(None);
+            spawn_local(async move {
+                let result = if ban {
+                    api::shadow_ban_user(id).await
+                } else {
+                    api::lift_shadow_ban(id).await
+                };
+                match result {
+                    Ok(updated) => users.set(replace_row(&users, updated)),
+                    Err(e) => error.set(Some(e.message)),
+                }
+            });
+        })
+    };
+
+    let on_prev_page = {
+        let page = page.clone();
+        Callback::from(move |_: MouseEvent| page.set((*page - 1).max(0)))
+    };
+
+    let on_next_page = {
+        let page = page.clone();
+        Callback::from(move |_: MouseEvent| page.set(*page + 1))
+    };
+
+    let total_pages = ((*total + ADMIN_PAGE_SIZE - 1) / ADMIN_PAGE_SIZE).max(1);
+    let has_prev = *page > 0;
+    let has_next = *page + 1 < total_pages;
+
+    html! {
+        <section class="admin-section" aria-labelledby="admin-users-title">
+            <h3 id="admin-users-title">{"Users"}</h3>
+            <ErrorBoundary error={(*load_error).clone()} {on_retry}>
+                if let Some(ref err) = *error {
+                    <div class="message message-error" role="alert">{err}</div>
+                }
+                <div class="admin-table-wrapper">
+                    <table class="admin-table">
+                        <thead>
+                            <tr>
+                                <th scope="col">{"User"}</th>
+                                <th scope="col">{"Email"}</th>
+                                <th scope="col">{"Posts"}</th>
+                                <th scope="col">{"Joined"}</th>
+                                <th scope="col">{"Role"}</th>
+                                <th scope="col">{"Status"}</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {for users.iter().map(|user| {
+                                let id = user.id;
+                                let is_self = props.current_user_id == Some(id);
+                                let on_role_change = {
+                                    let on_update = on_update.clone();
+                                    Callback::from(move |e: Event| {
+                                        let select: HtmlSelectElement = e.target_unchecked_into();
+                                        if let Ok(role) = select.value().parse() {
+                                            on_update.emit((id, UpdateUserRequest {
+                                                role: Some(role),
+                                                suspended: None,
+                                            }));
+                                        }
+                                    })
+                                };
+                                let on_suspend_click = {
+                                    let on_update = on_update.clone();
+                                    let suspended = !user.suspended;
+                                    Callback::from(move |_: MouseEvent| {
+                                        on_update.emit((id, UpdateUserRequest {
+                                            role: None,
+                                            suspended: Some(suspended),
+                                        }));
+                                    })
+                                };
+                                let on_shadow_ban_click = {
+                                    let on_shadow_ban = on_shadow_ban.clone();
+                                    let ban = !user.shadow_banned;
+                                    Callback::from(move |_: MouseEvent| on_shadow_ban.emit((id, ban)))
+                                };
+                                let shadow_ban_label = if user.shadow_banned { "Lift shadow-ban" } else { "Shadow-ban" };
+                                html! {
+                                    <tr key={id} class={classes!(user.suspended.then_some("admin-row-suspended"))}>
+                                        <td>{&user.username}</td>
+                                        <td>{&user.email}</td>
+                                        <td>{user.post_count}</td>
+                                        <td>{user.created_at.format("%Y-%m-%d").to_string()}</td>
+                                        <td>
+                                            <select
+                                                onchange={on_role_change}
+                                                disabled={is_self}
+                                                aria-label={format!("Role of {}", user.username)}
+                                            >
+                                                {for ROLES.iter().map(|role| html! {
+                                                    <option value={role.as_str()} selected={user.role == *role}>
+                                                        {role.as_str()}
+                                                    </option>
+                                                })}
+                                            </select>
+                                        </td>
+                                        <td>
+                                            <button
+                                                type="button"
+                                                class={classes!("btn", "btn-sm", if user.suspended { "btn-secondary" } else { "btn-danger" })}
+                                                onclick={on_suspend_click}
+                                                disabled={is_self}
+                                                aria-label={format!(
+                                                    "{} {}",
+                                                    if user.suspended { "Unsuspend" } else { "Suspend" },
+                                                    user.username
+                                                )}
+                                            >
+                                                if user.suspended {
+                                                    {"Unsuspend"}
+                                                } else {
+                                                    {"Suspend"}
+                                                }
+                                            </button>
+                                            <button
+                                                type="button"
+                                                class={classes!("btn", "btn-sm", if user.shadow_banned { "btn-secondary" } else { "btn-danger" })}
+                                                onclick={on_shadow_ban_click}
+                                                disabled={is_self || user.role.can_moderate()}
+                                                aria-label={format!("{} {}", shadow_ban_label, user.username)}
+                                                title="Hide this user's posts and comments from everyone else"
+                                            >
+                                                {shadow_ban_label}
+                                            </button>
+                                        </td>
+                                    </tr>
+                                }
+                            })}
+                        </tbody>
+                    </table>
+                </div>
+                if total_pages > 1 {
+                    <nav class="pagination" aria-label="User pages">
+                        <button
+                            class="btn btn-secondary"
+                            onclick={on_prev_page}
+                            disabled={!has_prev}
+                            aria-label="Previous page"
+                        >
+                            {"← Previous"}
+                        </button>
+                        <span class="pagination-info" aria-live="polite">
+                            {format!("Page {} of {}", *page + 1, total_pages)}
+                        </span>
+                        <button
+                            class="btn btn-secondary"
+                            onclick={on_next_page}
+                            disabled={!has_next}
+                            aria-label="Next page"
+                        >
+                            {"Next →"}
+                        </button>
+                    </nav>
+                }
+            </ErrorBoundary>
+        </section>
+    }
+}
+
+/// Returns the rows with the row of `updated` replaced.
+fn replace_row(users: &[AdminUserDto], updated: AdminUserDto) -> Vec<AdminUserDto> {
+    users
+        .iter()
+        .map(|u| {
+            if u.id == updated.id {
+                updated.clone()
+            } else {
+                u.clone()
+            }
+        })
+        .collect()
+}
@@ -1,10 +1,111 @@
 //! WASM frontend constants.
 
-/// API server port.
-pub const API_PORT: u16 = 8080;
-
 /// Token storage key in localStorage.
 pub const TOKEN_STORAGE_KEY: &str = "blog_token";
 
+/// Response header carrying the server's request ID.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
 /// Maximum content length before truncation in post cards.
 pub const MAX_CONTENT_LENGTH: usize = 200;
+
+/// Path of the service worker script (copied to the site root by Trunk).
+pub const SERVICE_WORKER_PATH: &str = "/sw.js";
+
+/// Path of the route chunk's JS module, holding the admin and editor pages
+/// (built from `blog-wasm-routes` into the site root by Trunk).
+#[cfg(all(feature = "app", not(all(feature = "admin", feature = "editor"))))]
+pub const ROUTE_CHUNK_PATH: &str = "/blog-wasm-routes.js";
+
+/// Name of the service worker's cache (`CACHE_NAME` in `sw.js`).
+pub const SERVICE_WORKER_CACHE_NAME: &str = "blog-cache-v3";
+
+/// localStorage key remembering that the install prompt was dismissed.
+pub const INSTALL_DISMISSED_STORAGE_KEY: &str = "blog_install_dismissed";
+
+/// localStorage key caching the last announcement banner.
+pub const ANNOUNCEMENT_STORAGE_KEY: &str = "blog_announcement";
+
+/// localStorage key prefix remembering which announcement a user dismissed
+/// (suffixed with the user ID, or `guest`).
+pub const ANNOUNCEMENT_DISMISSED_STORAGE_KEY: &str = "blog_announcement_dismissed";
+
+/// ID of the loading shell in `index.html`, removed once `App` renders.
+pub const APP_SHELL_ID: &str = "app-shell";
+
+/// ID of the main content region (skip link and focus target on navigation).
+pub const MAIN_CONTENT_ID: &str = "main-content";
+
+/// ID of the search input focused by the `/` shortcut.
+pub const SEARCH_INPUT_ID: &str = "search";
+
+/// Number of comments loaded per page.
+pub const COMMENT_PAGE_SIZE: i64 = 50;
+
+/// Delay after the last keystroke before search suggestions are fetched.
+pub const SEARCH_DEBOUNCE_MS: u32 = 250;
+
+/// Minimum query length for search suggestions.
+pub const SEARCH_MIN_QUERY_LEN: usize = 2;
+
+/// Number of titles shown in the search suggestions dropdown.
+pub const SEARCH_SUGGESTION_LIMIT: i64 = 5;
+
+/// Delay after the last keystroke before `@mention` and `#tag` suggestions
+/// are fetched.
+#[cfg(feature = "editor")]
+pub const AUTOCOMPLETE_DEBOUNCE_MS: u32 = 150;
+
+/// Longest `@mention` or `#tag` prefix suggestions are fetched for (the
+/// server's limit).
+#[cfg(feature = "editor")]
+pub const AUTOCOMPLETE_MAX_PREFIX_LEN: usize = 32;
+
+/// Number of results per search results page.
+pub const SEARCH_PAGE_SIZE: i64 = 10;
+
+/// Number of posts shown on an organization's page.
+pub const ORG_POSTS_PAGE_SIZE: i64 = 20;
+
+/// Paths the app is served at; any other shows the not found page.
+pub const APP_PATHS: &[&str] = &["/", "/index.html"];
+
+/// Query string parameter holding the active tag filter.
+pub const TAG_QUERY_PARAM: &str = "tag";
+
+/// Query string parameter of a post permalink (`/?post=<public ID>`).
+pub const POST_QUERY_PARAM: &str = "post";
+
+/// Query string parameter of a reader view permalink (`/?reader=<public ID>`).
+pub const READER_QUERY_PARAM: &str = "reader";
+
+/// Number of tags shown in the tag cloud.
+pub const TAG_CLOUD_LIMIT: i64 = 30;
+
+/// localStorage key holding the last applied display preferences.
+pub const PREFERENCES_STORAGE_KEY: &str = "blog_preferences";
+
+/// localStorage key caching the last site settings.
+pub const SITE_SETTINGS_STORAGE_KEY: &str = "blog_site_settings";
+
+/// Number of rows per page in the admin user table and moderation queue.
+#[cfg(feature = "admin")]
+pub const ADMIN_PAGE_SIZE: i64 = 20;
+
+/// Tweet intent URL (takes `text` and `url` parameters).
+pub const TWITTER_SHARE_URL: &str = "https://twitter.com/intent/tweet";
+
+/// Mastodon share page that asks for the user's instance (takes `text` and `url`).
+pub const MASTODON_SHARE_URL: &str = "https://mastodonshare.com/";
+
+/// How long a toast message stays visible.
+pub const TOAST_DURATION_MS: u32 = 3000;
+
+/// Number of conversations or messages loaded per page in the inbox.
+pub const MESSAGE_PAGE_SIZE: i64 = 30;
+
+/// How often the notification bell refreshes the unread message count.
+pub const UNREAD_POLL_INTERVAL_MS: u32 = 60_000;
+
+/// Experiment choosing the newsletter signup copy.
+pub const NEWSLETTER_CTA_EXPERIMENT: &str = "newsletter_cta";
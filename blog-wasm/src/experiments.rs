@@ -0,0 +1,137 @@
+//! A/B experiment variants for the signed-in user.
+//!
+//! `App` provides an [`Experiments`] context loaded from `/api/features`;
+//! components pick their variant with [`use_variant`] or wrap each version
+//! in a [`Variant`]. The first render of a variant logs one exposure per
+//! experiment and page load. Guests and unknown experiments get the control
+//! version.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashSet};
+use std::rc::Rc;
+
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::api;
+
+/// Handle to the user's experiment assignments, provided as context by
+/// `App`.
+#[derive(Clone)]
+pub struct Experiments {
+    assignments: Rc<BTreeMap<String, String>>,
+    exposed: Rc<RefCell<HashSet<String>>>,
+}
+
+impl PartialEq for Experiments {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.assignments, &other.assignments)
+            && Rc::ptr_eq(&self.exposed, &other.exposed)
+    }
+}
+
+impl Experiments {
+    /// Returns the user's variant of an experiment, if they take part.
+    pub fn variant(&self, experiment: &str) -> Option<&str> {
+        self.assignments.get(experiment).map(String::as_str)
+    }
+
+    /// Logs that the user saw their variant, once per page load.
+    fn expose(&self, experiment: &str) {
+        if self.variant(experiment).is_none()
+            || !self.exposed.borrow_mut().insert(experiment.to_string())
+        {
+            return;
+        }
+
+        let experiment = experiment.to_string();
+        spawn_local(async move {
+            // A lost exposure only skews the numbers slightly
+            let _ = api::record_exposure(&experiment).await;
+        });
+    }
+}
+
+/// Loads the assignments of the signed-in user for `App` to provide as
+/// context, again whenever the user changes.
+#[hook]
+pub fn use_experiments(user_id: Option<i64>) -> Experiments {
+    let assignments = use_state(|| Rc::new(BTreeMap::new()));
+    let exposed = use_memo(user_id, |_| RefCell::new(HashSet::new()));
+
+    {
+        let assignments = assignments.clone();
+        use_effect_with(user_id, move |user_id| {
+            if user_id.is_some() {
+                spawn_local(async move {
+                    if let Ok(features) = api::get_features().await {
+                        assignments.set(Rc::new(features.experiments));
+                    }
+                });
+            } else {
+                assignments.set(Rc::new(BTreeMap::new()));
+            }
+            || ()
+        });
+    }
+
+    Experiments {
+        assignments: Rc::clone(&assignments),
+        exposed: Rc::clone(&exposed),
+    }
+}
+
+/// Returns the user's variant of an experiment and logs the exposure.
+///
+/// Returns `None` for guests, experiments the user is not in, while
+/// loading, or outside an [`Experiments`] provider: render the control
+/// version then.
+#[hook]
+pub fn use_variant(experiment: &str) -> Option<String> {
+    let experiments = use_context::<Experiments>();
+    let variant = experiments
+        .as_ref()
+        .and_then(|experiments| experiments.variant(experiment))
+        .map(str::to_string);
+
+    {
+        let experiment = experiment.to_string();
+        use_effect_with((experiment, variant.clone()), move |(experiment, _)| {
+            if let Some(experiments) = experiments {
+                experiments.expose(experiment);
+            }
+            || ()
+        });
+    }
+
+    variant
+}
+
+/// Props for [`Variant`].
+#[derive(Properties, PartialEq)]
+pub struct VariantProps {
+    /// Experiment name.
+    pub experiment: AttrValue,
+    /// Variant that shows the children.
+    pub variant: AttrValue,
+    /// Also show the children when the user has no variant.
+    #[prop_or_default]
+    pub control: bool,
+    pub children: Children,
+}
+
+/// Renders its children only for users assigned `variant`.
+#[function_component(Variant)]
+pub fn variant(props: &VariantProps) -> Html {
+    let assigned = use_variant(&props.experiment);
+    let shown = match assigned {
+        Some(assigned) => assigned == props.variant.as_str(),
+        None => props.control,
+    };
+
+    if shown {
+        html! { <>{ for props.children.iter() }</> }
+    } else {
+        html! {}
+    }
+}
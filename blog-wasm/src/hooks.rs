@@ -0,0 +1,69 @@
+//! Reusable Yew hooks.
+
+use gloo_events::EventListener;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlElement, KeyboardEvent, window};
+use yew::prelude::*;
+
+/// Single-key shortcut bound to a callback.
+pub type Hotkey = (&'static str, Callback<()>);
+
+/// Binds single-key shortcuts (e.g. `"n"`, `"/"`) on the window.
+///
+/// Keys are ignored while typing in a form field or when a modifier other
+/// than Shift is held, so shortcuts never interfere with text input or
+/// browser shortcuts. The latest bindings are used on every keypress, so
+/// callers can pass freshly built callbacks each render.
+#[hook]
+pub fn use_hotkeys(bindings: Vec<Hotkey>) {
+    let bindings_ref = use_mut_ref(Vec::new);
+    *bindings_ref.borrow_mut() = bindings;
+
+    use_effect_with((), move |_| {
+        let listener = window().map(|win| {
+            EventListener::new(&win, "keydown", move |event| {
+                let Some(event) = event.dyn_ref::<KeyboardEvent>() else {
+                    return;
+                };
+                if event.ctrl_key() || event.meta_key() || event.alt_key() || is_typing(event) {
+                    return;
+                }
+                let key = event.key();
+                let callback = bindings_ref
+                    .borrow()
+                    .iter()
+                    .find(|(bound, _)| *bound == key)
+                    .map(|(_, callback)| callback.clone());
+                if let Some(callback) = callback {
+                    event.prevent_default();
+                    callback.emit(());
+                }
+            })
+        });
+
+        move || drop(listener)
+    });
+}
+
+/// Returns true if the event comes from an editable element.
+fn is_typing(event: &KeyboardEvent) -> bool {
+    let Some(target) = event
+        .target()
+        .and_then(|target| target.dyn_into::<HtmlElement>().ok())
+    else {
+        return false;
+    };
+    matches!(target.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+        || target.is_content_editable()
+}
+
+/// Moves keyboard focus to the element with the given ID, if present.
+pub fn focus_by_id(id: &str) {
+    let element = window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.get_element_by_id(id))
+        .and_then(|e| e.dyn_into::<HtmlElement>().ok());
+    if let Some(element) = element {
+        let _ = element.focus();
+    }
+}
@@ -0,0 +1,130 @@
+//! Browser URL query string helpers (the app has no router; page state
+//! lives in `App`, only shareable filters are mirrored into the URL).
+
+use blog_shared::constants::EMAIL_CHANGE_QUERY_PARAM;
+use wasm_bindgen::JsValue;
+use web_sys::{UrlSearchParams, window};
+
+use crate::constants::{APP_PATHS, POST_QUERY_PARAM, READER_QUERY_PARAM, TAG_QUERY_PARAM};
+use crate::page::Page;
+
+/// Reads the tag filter from the current URL (`?tag=...`).
+pub fn tag_from_url() -> Option<String> {
+    let search = window()?.location().search().ok()?;
+    let params = UrlSearchParams::new_with_str(&search).ok()?;
+    params
+        .get(TAG_QUERY_PARAM)
+        .filter(|tag| !tag.trim().is_empty())
+}
+
+/// Records the tag filter in the URL as a new history entry, so back and
+/// forward step through filters.
+pub fn push_tag_to_url(tag: Option<&str>) {
+    let Some(win) = window() else {
+        return;
+    };
+    let location = win.location();
+    let (Ok(path), Ok(search)) = (location.pathname(), location.search()) else {
+        return;
+    };
+    let Ok(params) = UrlSearchParams::new_with_str(&search) else {
+        return;
+    };
+    match tag {
+        Some(tag) => params.set(TAG_QUERY_PARAM, tag),
+        None => params.delete(TAG_QUERY_PARAM),
+    }
+
+    let query: String = params.to_string().into();
+    let url = if query.is_empty() {
+        path
+    } else {
+        format!("{}?{}", path, query)
+    };
+    if let Ok(history) = win.history() {
+        let _ = history.push_state_with_url(&JsValue::NULL, "", Some(&url));
+    }
+}
+
+/// Returns the shareable permalink of the post with public ID `post_id`.
+pub fn post_permalink(post_id: &str) -> Option<String> {
+    let origin = window()?.location().origin().ok()?;
+    Some(format!("{}/?{}={}", origin, POST_QUERY_PARAM, post_id))
+}
+
+/// Points the page's `<link rel="canonical">` at `href`, or removes it.
+pub fn set_canonical_link(href: Option<&str>) {
+    let Some(document) = window().and_then(|win| win.document()) else {
+        return;
+    };
+    let existing = document
+        .query_selector("link[rel=\"canonical\"]")
+        .ok()
+        .flatten();
+    match (href, existing) {
+        (Some(href), Some(link)) => {
+            let _ = link.set_attribute("href", href);
+        }
+        (Some(href), None) => {
+            let (Some(head), Ok(link)) = (document.head(), document.create_element("link")) else {
+                return;
+            };
+            let _ = link.set_attribute("rel", "canonical");
+            let _ = link.set_attribute("href", href);
+            let _ = head.append_child(&link);
+        }
+        (None, Some(link)) => link.remove(),
+        (None, None) => {}
+    }
+}
+
+/// Reads a post permalink (`?post=<public ID>`, or `?reader=<public ID>`
+/// for reader view) or an email confirmation link (`?confirm_email=...`)
+/// from the URL and removes it, so the link opens its page once without
+/// pinning it for later reloads. Paths other than the app's own and
+/// permalinks without a valid public ID give [`Page::NotFound`].
+pub fn take_page_from_url() -> Option<Page> {
+    let win = window()?;
+    let location = win.location();
+    let path = location.pathname().ok()?;
+    if !APP_PATHS.contains(&path.as_str()) {
+        return Some(Page::NotFound);
+    }
+    let params = UrlSearchParams::new_with_str(&location.search().ok()?).ok()?;
+    let id = |name: &str| {
+        params
+            .get(name)
+            .map(|value| Some(value).filter(|v| is_public_id(v)))
+    };
+    let page = if let Some(token) = params.get(EMAIL_CHANGE_QUERY_PARAM) {
+        Page::ConfirmEmail(token)
+    } else {
+        match (id(READER_QUERY_PARAM), id(POST_QUERY_PARAM)) {
+            (Some(Some(post_id)), _) => Page::Reader(post_id),
+            (None, Some(Some(post_id))) => Page::PostDetail(post_id),
+            (None, None) => return None,
+            _ => Page::NotFound,
+        }
+    };
+
+    params.delete(POST_QUERY_PARAM);
+    params.delete(READER_QUERY_PARAM);
+    params.delete(EMAIL_CHANGE_QUERY_PARAM);
+    let query: String = params.to_string().into();
+    let url = if query.is_empty() {
+        path
+    } else {
+        format!("{}?{}", path, query)
+    };
+    if let Ok(history) = win.history() {
+        let _ = history.replace_state_with_url(&JsValue::NULL, "", Some(&url));
+    }
+
+    Some(page)
+}
+
+/// Whether `value` looks like a public ID (a hyphenated UUID). Numeric IDs
+/// from permalinks shared before public IDs are not accepted.
+fn is_public_id(value: &str) -> bool {
+    value.len() == 36 && value.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+}
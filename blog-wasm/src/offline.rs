@@ -0,0 +1,179 @@
+//! Offline support: service worker registration, its cache, and a queue of
+//! post actions.
+//!
+//! Create/update/delete actions made while offline are stored in IndexedDB
+//! and replayed when connectivity returns. Updates and deletes carry the
+//! post's `updated_at` at the time of the edit; if the server copy has moved
+//! on since, the action is dropped as a conflict instead of overwriting it.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::js_sys::Reflect;
+use web_sys::window;
+
+use blog_shared::{CreatePostRequest, UpdatePostRequest};
+
+use crate::api::{self, ApiError};
+use crate::constants::{SERVICE_WORKER_CACHE_NAME, SERVICE_WORKER_PATH};
+
+#[wasm_bindgen(module = "/js/offline_queue.js")]
+extern "C" {
+    #[wasm_bindgen(catch, js_name = queuePush)]
+    async fn queue_push(json: String) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, js_name = queueList)]
+    async fn queue_list() -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, js_name = queueRemove)]
+    async fn queue_remove(key: f64) -> Result<JsValue, JsValue>;
+}
+
+/// Post action recorded while offline, naming posts by public ID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PendingAction {
+    Create {
+        request: CreatePostRequest,
+    },
+    Update {
+        id: String,
+        base_updated_at: DateTime<Utc>,
+        request: UpdatePostRequest,
+    },
+    Delete {
+        id: String,
+        base_updated_at: DateTime<Utc>,
+    },
+}
+
+/// Raw queue entry as returned by the JS store.
+#[derive(Deserialize)]
+struct QueuedEntry {
+    key: f64,
+    json: String,
+}
+
+/// Outcome of replaying the offline queue.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ReplayReport {
+    /// Actions accepted by the server.
+    pub applied: usize,
+    /// Actions dropped because the post changed on the server.
+    pub conflicts: usize,
+    /// Actions the server rejected.
+    pub failed: usize,
+}
+
+impl ReplayReport {
+    /// Returns true if nothing was replayed.
+    pub fn is_empty(&self) -> bool {
+        self.applied + self.conflicts + self.failed == 0
+    }
+}
+
+/// Returns the browser's connectivity status (assumes online if unknown).
+pub fn is_online() -> bool {
+    window().map(|w| w.navigator().on_line()).unwrap_or(true)
+}
+
+/// Registers the service worker if the browser supports it.
+pub fn register_service_worker() {
+    let Some(navigator) = window().map(|w| w.navigator()) else {
+        return;
+    };
+    // Service workers are unavailable on insecure origins and old browsers
+    if !Reflect::has(&navigator, &JsValue::from_str("serviceWorker")).unwrap_or(false) {
+        return;
+    }
+    let _ = navigator.service_worker().register(SERVICE_WORKER_PATH);
+}
+
+/// Deletes the service worker's cache, so pages viewed while signed in are
+/// not served to whoever uses the browser next.
+pub fn clear_cache() {
+    if let Some(caches) = window().and_then(|w| w.caches().ok()) {
+        let _ = caches.delete(SERVICE_WORKER_CACHE_NAME);
+    }
+}
+
+/// Stores an action for replay once the browser is back online.
+pub async fn enqueue(action: &PendingAction) -> Result<(), ApiError> {
+    let json = serde_json::to_string(action).map_err(|e| ApiError::new(e.to_string()))?;
+    queue_push(json).await.map_err(js_error)?;
+    Ok(())
+}
+
+/// Replays queued actions in order, removing each one once it is resolved.
+///
+/// Stops early if the connection drops again, keeping the rest queued.
+pub async fn replay() -> Result<ReplayReport, ApiError> {
+    let mut report = ReplayReport::default();
+
+    for entry in pending().await? {
+        // Entries that no longer parse cannot ever be applied
+        let Ok(action) = serde_json::from_str::<PendingAction>(&entry.json) else {
+            report.failed += 1;
+            queue_remove(entry.key).await.map_err(js_error)?;
+            continue;
+        };
+
+        match apply(&action).await {
+            Ok(true) => report.applied += 1,
+            Ok(false) => report.conflicts += 1,
+            Err(_) if !is_online() => break,
+            Err(_) => report.failed += 1,
+        }
+        queue_remove(entry.key).await.map_err(js_error)?;
+    }
+
+    Ok(report)
+}
+
+/// Loads all queued entries, oldest first.
+async fn pending() -> Result<Vec<QueuedEntry>, ApiError> {
+    let json = queue_list()
+        .await
+        .map_err(js_error)?
+        .as_string()
+        .unwrap_or_default();
+    serde_json::from_str(&json).map_err(|e| ApiError::new(e.to_string()))
+}
+
+/// Sends one action to the server. Returns `Ok(false)` on a conflict.
+async fn apply(action: &PendingAction) -> Result<bool, ApiError> {
+    match action {
+        PendingAction::Create { request } => {
+            api::create_post(request.clone()).await?;
+        }
+        PendingAction::Update {
+            id,
+            base_updated_at,
+            request,
+        } => {
+            if api::get_post(id).await?.updated_at != *base_updated_at {
+                return Ok(false);
+            }
+            api::update_post(id, request.clone()).await?;
+        }
+        PendingAction::Delete {
+            id,
+            base_updated_at,
+        } => {
+            if api::get_post(id).await?.updated_at != *base_updated_at {
+                return Ok(false);
+            }
+            api::delete_post(id).await?;
+        }
+    }
+    Ok(true)
+}
+
+/// Converts a JS exception into an API error.
+fn js_error(value: JsValue) -> ApiError {
+    ApiError::new(
+        value
+            .as_string()
+            .unwrap_or_else(|| "Offline storage unavailable".to_string()),
+    )
+}
@@ -0,0 +1,29 @@
+//! Application pages (the app has no router; the current page lives in `App`).
+
+/// Application view/page.
+#[derive(Clone, PartialEq)]
+pub enum Page {
+    Posts,
+    Login,
+    Register,
+    NewPost,
+    /// Post editor, by public ID.
+    EditPost(String),
+    /// Post with its comments, by public ID.
+    PostDetail(String),
+    /// Post in reader mode (no header, print friendly), by public ID.
+    Reader(String),
+    Search(String),
+    Settings,
+    /// The signed-in user's deleted posts.
+    Trash,
+    /// Private messages.
+    Inbox,
+    /// An organization's profile, by slug.
+    Org(String),
+    Admin,
+    /// Confirmation of a new account email, by the token from its link.
+    ConfirmEmail(String),
+    /// Unknown address or malformed permalink.
+    NotFound,
+}
@@ -0,0 +1,44 @@
+//! Display preferences (theme and language) applied to the document.
+//!
+//! The last applied preferences are kept in localStorage so the theme is
+//! right on the next load before the settings request finishes.
+
+use gloo_storage::{LocalStorage, Storage};
+use web_sys::window;
+
+use blog_shared::PreferencesDto;
+
+use crate::api;
+use crate::constants::PREFERENCES_STORAGE_KEY;
+
+/// Sets `data-theme` and `lang` on `<html>` and remembers the preferences.
+pub fn apply_preferences(preferences: &PreferencesDto) {
+    if let Some(root) = window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.document_element())
+    {
+        let _ = root.set_attribute("data-theme", preferences.theme.as_str());
+        let _ = root.set_attribute("lang", &preferences.language);
+    }
+    let _ = LocalStorage::set(PREFERENCES_STORAGE_KEY, preferences);
+}
+
+/// Applies the preferences remembered from the last session, if any.
+pub fn apply_stored_preferences() {
+    if let Ok(preferences) = LocalStorage::get::<PreferencesDto>(PREFERENCES_STORAGE_KEY) {
+        apply_preferences(&preferences);
+    }
+}
+
+/// Fetches the signed-in user's preferences and applies them.
+pub async fn sync_preferences() {
+    if let Ok(settings) = api::get_settings().await {
+        apply_preferences(&settings.preferences);
+    }
+}
+
+/// Restores the default preferences (after logout).
+pub fn reset_preferences() {
+    apply_preferences(&PreferencesDto::default());
+    LocalStorage::delete(PREFERENCES_STORAGE_KEY);
+}
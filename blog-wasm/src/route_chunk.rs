@@ -0,0 +1,52 @@
+//! The route chunk: admin and editor pages built as a second WASM module,
+//! which the main bundle imports the first time one of them opens.
+//!
+//! The chunk (`blog-wasm-routes`) links this crate with the `admin` and
+//! `editor` features but without `app`, and exports `mount_route`, which
+//! renders a [`ChunkRoute`] into an element of the main app as a separate Yew
+//! app. The two modules share no memory, so the route travels as JSON and the
+//! page answers with [`ChunkEvent`]s through a JS callback. `RouteChunk` is
+//! the main bundle's side.
+
+#[cfg(all(feature = "app", not(all(feature = "admin", feature = "editor"))))]
+mod loader;
+#[cfg(any(feature = "admin", feature = "editor"))]
+mod mount;
+
+use blog_shared::{Role, SiteSettingsDto};
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "app", not(all(feature = "admin", feature = "editor"))))]
+pub use loader::RouteChunk;
+#[cfg(any(feature = "admin", feature = "editor"))]
+pub use mount::{RouteHandle, mount_route};
+
+/// Page rendered by the route chunk, with the props it needs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "page", rename_all = "snake_case")]
+pub enum ChunkRoute {
+    /// The admin dashboard.
+    Admin {
+        role: Role,
+        current_user_id: Option<i64>,
+    },
+    /// The post editor, for a new post when `post_id` is `None`.
+    Editor {
+        post_id: Option<String>,
+        current_user_public_id: Option<String>,
+    },
+}
+
+/// What a chunk page asks of the main app.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ChunkEvent {
+    /// Open a post, by public ID.
+    OpenPost { post_id: String },
+    /// An admin saved the site settings; show them in the header.
+    SiteUpdated { settings: SiteSettingsDto },
+    /// The editor saved its post.
+    PostSaved,
+    /// The editor was cancelled.
+    Cancelled,
+}
@@ -0,0 +1,151 @@
+//! The main bundle's side: loading the chunk and mounting a page from it.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::js_sys::{Function, Reflect};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::Element;
+use yew::prelude::*;
+
+use crate::components::ErrorBoundary;
+use crate::constants::ROUTE_CHUNK_PATH;
+use crate::route_chunk::{ChunkEvent, ChunkRoute};
+
+#[wasm_bindgen(module = "/js/route_chunk.js")]
+extern "C" {
+    #[wasm_bindgen(catch, js_name = loadRouteChunk)]
+    async fn load_route_chunk(url: &str) -> Result<JsValue, JsValue>;
+}
+
+/// Route chunk properties.
+#[derive(Properties, PartialEq)]
+pub struct RouteChunkProps {
+    /// Page to show.
+    pub route: ChunkRoute,
+    /// Callback for what the page asks of the app.
+    pub on_event: Callback<ChunkEvent>,
+}
+
+/// Shows a page of the route chunk, loading the chunk first if needed.
+#[function_component(RouteChunk)]
+pub fn route_chunk(props: &RouteChunkProps) -> Html {
+    let root = use_node_ref();
+    let loading = use_state(|| true);
+    let error = use_state(|| None::<String>);
+    let retry = use_state(|| 0u32);
+    // The page keeps the callback it was mounted with, so route it through
+    // the latest one
+    let on_event = use_mut_ref(|| props.on_event.clone());
+    *on_event.borrow_mut() = props.on_event.clone();
+
+    {
+        let root = root.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+        use_effect_with((props.route.clone(), *retry), move |(route, _)| {
+            let mounted = Rc::new(RefCell::new(None::<MountedPage>));
+            let active = Rc::new(RefCell::new(true));
+            {
+                let route = route.clone();
+                let mounted = Rc::clone(&mounted);
+                let active = Rc::clone(&active);
+                loading.set(true);
+                error.set(None);
+                spawn_local(async move {
+                    let result = match root.cast::<Element>() {
+                        Some(element) => mount(element, &route, on_event).await,
+                        None => Err("Page element is missing".to_string()),
+                    };
+                    match result {
+                        // Navigated away while loading: drop (unmount) it
+                        Ok(_) if !*active.borrow() => {}
+                        Ok(page) => {
+                            *mounted.borrow_mut() = Some(page);
+                            loading.set(false);
+                        }
+                        Err(e) => {
+                            if *active.borrow() {
+                                loading.set(false);
+                                error.set(Some(format!("Failed to load page: {e}")));
+                            }
+                        }
+                    }
+                });
+            }
+            move || {
+                *active.borrow_mut() = false;
+                mounted.borrow_mut().take();
+            }
+        });
+    }
+
+    let on_retry = {
+        let retry = retry.clone();
+        Callback::from(move |_| retry.set(*retry + 1))
+    };
+
+    html! {
+        <ErrorBoundary error={(*error).clone()} {on_retry}>
+            if *loading {
+                <p class="loading">{"Loading..."}</p>
+            }
+            <div ref={root} class="route-chunk" aria-busy={loading.to_string()}></div>
+        </ErrorBoundary>
+    }
+}
+
+/// A page mounted from the chunk, unmounted when dropped.
+struct MountedPage {
+    handle: JsValue,
+    // Called by the page; must live as long as it
+    _on_event: Closure<dyn Fn(String)>,
+}
+
+impl Drop for MountedPage {
+    fn drop(&mut self) {
+        if let Ok(unmount) = Reflect::get(&self.handle, &JsValue::from_str("unmount"))
+            .and_then(|unmount| unmount.dyn_into::<Function>())
+        {
+            let _ = unmount.call0(&self.handle);
+        }
+    }
+}
+
+/// Loads the chunk and mounts `route` into `element`, relaying the page's
+/// events to the latest `on_event`.
+async fn mount(
+    element: Element,
+    route: &ChunkRoute,
+    on_event: Rc<RefCell<Callback<ChunkEvent>>>,
+) -> Result<MountedPage, String> {
+    let chunk = load_route_chunk(ROUTE_CHUNK_PATH)
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+    let mount_route = Reflect::get(&chunk, &JsValue::from_str("mount_route"))
+        .ok()
+        .and_then(|f| f.dyn_into::<Function>().ok())
+        .ok_or("The route chunk has no mount_route")?;
+
+    let on_event = Closure::<dyn Fn(String)>::new(move |json: String| {
+        if let Ok(event) = serde_json::from_str::<ChunkEvent>(&json) {
+            on_event.borrow().emit(event);
+        }
+    });
+    let route = serde_json::to_string(route).map_err(|e| e.to_string())?;
+    let handle = mount_route
+        .call3(
+            &JsValue::NULL,
+            &element,
+            &JsValue::from_str(&route),
+            on_event.as_ref(),
+        )
+        .map_err(|e| format!("{e:?}"))?;
+
+    Ok(MountedPage {
+        handle,
+        _on_event: on_event,
+    })
+}
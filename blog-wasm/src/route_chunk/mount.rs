@@ -0,0 +1,103 @@
+//! The chunk's side: mounting a page into an element of the main app.
+
+use wasm_bindgen::prelude::*;
+use web_sys::Element;
+use yew::prelude::*;
+
+#[cfg(feature = "admin")]
+use crate::components::AdminDashboard;
+#[cfg(feature = "editor")]
+use crate::components::PostForm;
+use crate::components::install_panic_fallback;
+use crate::route_chunk::{ChunkEvent, ChunkRoute};
+use crate::site::SiteContext;
+
+/// A mounted chunk page; the main app unmounts it when navigating away.
+#[wasm_bindgen]
+pub struct RouteHandle {
+    destroy: Box<dyn FnOnce()>,
+}
+
+#[wasm_bindgen]
+impl RouteHandle {
+    /// Removes the page and stops its effects.
+    pub fn unmount(self) {
+        (self.destroy)();
+    }
+}
+
+/// Renders the [`ChunkRoute`] given as JSON into `root`, reporting
+/// [`ChunkEvent`]s to `on_event` as JSON.
+#[wasm_bindgen]
+pub fn mount_route(
+    root: Element,
+    route: &str,
+    on_event: js_sys::Function,
+) -> Result<RouteHandle, JsValue> {
+    install_panic_fallback();
+    let route: ChunkRoute =
+        serde_json::from_str(route).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let on_event = Callback::from(move |event: ChunkEvent| {
+        if let Ok(json) = serde_json::to_string(&event) {
+            let _ = on_event.call1(&JsValue::NULL, &JsValue::from_str(&json));
+        }
+    });
+
+    let props = ChunkPageProps { route, on_event };
+    let app = yew::Renderer::<ChunkPage>::with_root_and_props(root, props).render();
+    Ok(RouteHandle {
+        destroy: Box::new(move || app.destroy()),
+    })
+}
+
+/// Chunk page properties.
+#[derive(Properties, PartialEq)]
+struct ChunkPageProps {
+    route: ChunkRoute,
+    on_event: Callback<ChunkEvent>,
+}
+
+/// Root of a chunk page, standing in for the contexts `App` provides.
+#[function_component(ChunkPage)]
+fn chunk_page(props: &ChunkPageProps) -> Html {
+    let site = {
+        let on_event = props.on_event.clone();
+        use_memo((), move |_| {
+            SiteContext::relayed(on_event.reform(|settings| ChunkEvent::SiteUpdated { settings }))
+        })
+    };
+
+    let page = match props.route.clone() {
+        #[cfg(feature = "admin")]
+        ChunkRoute::Admin {
+            role,
+            current_user_id,
+        } => html! {
+            <AdminDashboard
+                {role}
+                {current_user_id}
+                on_open_post={props.on_event.reform(|post_id| ChunkEvent::OpenPost { post_id })}
+            />
+        },
+        #[cfg(feature = "editor")]
+        ChunkRoute::Editor {
+            post_id,
+            current_user_public_id,
+        } => html! {
+            <PostForm
+                {post_id}
+                on_success={props.on_event.reform(|_| ChunkEvent::PostSaved)}
+                on_cancel={Some(props.on_event.reform(|_| ChunkEvent::Cancelled))}
+                {current_user_public_id}
+            />
+        },
+        #[allow(unreachable_patterns)]
+        _ => Html::default(),
+    };
+
+    html! {
+        <ContextProvider<SiteContext> context={(*site).clone()}>
+            {page}
+        </ContextProvider<SiteContext>>
+    }
+}
@@ -0,0 +1,122 @@
+//! Site-wide settings (title, description, page size, default language).
+//!
+//! `App` provides a [`SiteContext`] loaded from `/api/site/settings`;
+//! components read it with [`use_site`]. The last settings are kept in
+//! localStorage so the title is right on the next load before the request
+//! finishes.
+
+use std::ops::Deref;
+use std::rc::Rc;
+
+use gloo_storage::{LocalStorage, Storage};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::window;
+use yew::prelude::*;
+
+use blog_shared::SiteSettingsDto;
+
+use crate::api;
+use crate::constants::{PREFERENCES_STORAGE_KEY, SITE_SETTINGS_STORAGE_KEY};
+
+/// Handle to the site settings, provided as context by `App`.
+#[derive(Clone)]
+pub struct SiteContext {
+    settings: Rc<SiteSettingsDto>,
+    on_update: Callback<SiteSettingsDto>,
+}
+
+impl PartialEq for SiteContext {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.settings, &other.settings)
+    }
+}
+
+impl Deref for SiteContext {
+    type Target = SiteSettingsDto;
+
+    fn deref(&self) -> &Self::Target {
+        &self.settings
+    }
+}
+
+impl SiteContext {
+    /// Context of the route chunk, which has no header of its own: the
+    /// cached settings, with saved ones handed to `on_update` for the main
+    /// app to show.
+    #[cfg(any(feature = "admin", feature = "editor"))]
+    pub fn relayed(on_update: Callback<SiteSettingsDto>) -> Self {
+        Self {
+            settings: Rc::new(
+                LocalStorage::get::<SiteSettingsDto>(SITE_SETTINGS_STORAGE_KEY).unwrap_or_default(),
+            ),
+            on_update,
+        }
+    }
+
+    /// Shows settings an admin just saved throughout the app.
+    pub fn update(&self, settings: SiteSettingsDto) {
+        self.on_update.emit(settings);
+    }
+}
+
+/// Sets the document title and meta description, and the page language
+/// unless the visitor has preferences of their own; remembers the settings.
+fn apply_site_settings(settings: &SiteSettingsDto) {
+    if let Some(document) = window().and_then(|w| w.document()) {
+        document.set_title(&settings.title);
+        if let Ok(Some(meta)) = document.query_selector("meta[name=description]") {
+            let _ = meta.set_attribute("content", &settings.description);
+        }
+        let has_preferences = LocalStorage::raw()
+            .get_item(PREFERENCES_STORAGE_KEY)
+            .ok()
+            .flatten()
+            .is_some();
+        if !has_preferences && let Some(root) = document.document_element() {
+            let _ = root.set_attribute("lang", &settings.default_language);
+        }
+    }
+    let _ = LocalStorage::set(SITE_SETTINGS_STORAGE_KEY, settings);
+}
+
+/// Loads the site settings for `App` to provide as context.
+#[hook]
+pub fn use_site_context() -> SiteContext {
+    let settings = use_state(|| {
+        Rc::new(LocalStorage::get::<SiteSettingsDto>(SITE_SETTINGS_STORAGE_KEY).unwrap_or_default())
+    });
+
+    let on_update = {
+        let settings = settings.clone();
+        Callback::from(move |updated: SiteSettingsDto| {
+            apply_site_settings(&updated);
+            settings.set(Rc::new(updated));
+        })
+    };
+
+    {
+        let on_update = on_update.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                if let Ok(loaded) = api::get_site_settings().await {
+                    on_update.emit(loaded);
+                }
+            });
+            || ()
+        });
+    }
+
+    SiteContext {
+        settings: Rc::clone(&settings),
+        on_update,
+    }
+}
+
+/// Returns the site settings, or the defaults outside a [`SiteContext`]
+/// provider.
+#[hook]
+pub fn use_site() -> Rc<SiteSettingsDto> {
+    use_context::<SiteContext>()
+        .map(|site| site.settings)
+        .unwrap_or_default()
+}
@@ -0,0 +1,139 @@
+//! Client-side cache of public user profiles (names and avatars).
+//!
+//! `App` provides a [`UserCache`] context; components read profiles with
+//! [`use_user`], so each author is fetched once per page load no matter how
+//! many posts and comments show them. Concurrent requests for the same user
+//! share one fetch.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use blog_shared::PublicUserDto;
+
+use crate::api;
+
+/// Cached profiles plus callbacks waiting for in-flight fetches.
+#[derive(Default)]
+struct UserStore {
+    users: HashMap<String, PublicUserDto>,
+    pending: HashMap<String, Vec<Callback<PublicUserDto>>>,
+}
+
+/// Handle to the shared user cache, provided as context by `App`.
+///
+/// Invalidating bumps `generation`, which re-renders consumers so they
+/// fetch fresh profiles.
+#[derive(Clone)]
+pub struct UserCache {
+    store: Rc<RefCell<UserStore>>,
+    generation: u32,
+    on_invalidate: Callback<()>,
+}
+
+impl PartialEq for UserCache {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.store, &other.store) && self.generation == other.generation
+    }
+}
+
+impl UserCache {
+    /// Calls `callback` with the profile of the user with public ID `id`,
+    /// fetching it unless cached.
+    ///
+    /// Failed fetches are dropped silently (callers show a fallback) and
+    /// retried on the next request. Empty IDs (from servers that predate
+    /// public IDs) are never fetched.
+    pub fn load(&self, id: String, callback: Callback<PublicUserDto>) {
+        if id.is_empty() {
+            return;
+        }
+        let cached = self.store.borrow().users.get(&id).cloned();
+        if let Some(user) = cached {
+            callback.emit(user);
+            return;
+        }
+
+        let first = {
+            let mut store = self.store.borrow_mut();
+            let waiting = store.pending.entry(id.clone()).or_default();
+            waiting.push(callback);
+            waiting.len() == 1
+        };
+        if !first {
+            return;
+        }
+
+        let store = Rc::clone(&self.store);
+        spawn_local(async move {
+            let result = api::get_user(&id).await;
+            let waiting = {
+                let mut store = store.borrow_mut();
+                if let Ok(ref user) = result {
+                    store.users.insert(id.clone(), user.clone());
+                }
+                store.pending.remove(&id).unwrap_or_default()
+            };
+            if let Ok(user) = result {
+                for callback in waiting {
+                    callback.emit(user.clone());
+                }
+            }
+        });
+    }
+
+    /// Drops all cached profiles and refreshes the components showing them.
+    pub fn invalidate(&self) {
+        self.store.borrow_mut().users.clear();
+        self.on_invalidate.emit(());
+    }
+}
+
+/// Creates the user cache for `App` to provide as context.
+#[hook]
+pub fn use_user_cache() -> UserCache {
+    let store = use_memo((), |_| RefCell::new(UserStore::default()));
+    let generation = use_state(|| 0u32);
+
+    let on_invalidate = {
+        let generation = generation.clone();
+        Callback::from(move |_| generation.set(*generation + 1))
+    };
+
+    UserCache {
+        store: Rc::clone(&store),
+        generation: *generation,
+        on_invalidate,
+    }
+}
+
+/// Returns the cached public profile of the user with public ID `id`,
+/// fetching it if needed.
+///
+/// Returns `None` while loading, when the fetch failed, or outside a
+/// [`UserCache`] provider.
+#[hook]
+pub fn use_user(id: String) -> Option<PublicUserDto> {
+    let cache = use_context::<UserCache>();
+    let user = use_state(|| None::<PublicUserDto>);
+
+    {
+        let user = user.clone();
+        let generation = cache.as_ref().map(|c| c.generation);
+        use_effect_with((id, generation), move |(id, _)| {
+            if let Some(cache) = cache {
+                let user = user.clone();
+                cache.load(
+                    id.clone(),
+                    Callback::from(move |loaded| user.set(Some(loaded))),
+                );
+            }
+            || ()
+        });
+    }
+
+    (*user).clone()
+}
@@ -0,0 +1,55 @@
+// Service worker: network-first caching of the app shell and post reads, so
+// previously viewed pages keep working offline. Signed-in post reads are
+// cached too (with the viewer's drafts and `is_owner`): the app deletes the
+// cache on logout, so they never outlive the session that fetched them.
+
+// Renamed when what gets cached changes, so activation drops the old cache;
+// matches `SERVICE_WORKER_CACHE_NAME` in src/constants.rs.
+const CACHE_NAME = "blog-cache-v3";
+
+// The post list and post detail: `/api/posts` and `/api/posts/{id}`.
+const PUBLIC_POST_PATH = /^\/api\/posts(\/[^/]+)?$/;
+
+self.addEventListener("install", (event) => {
+    event.waitUntil(caches.open(CACHE_NAME).then((cache) => cache.add("/")));
+    self.skipWaiting();
+});
+
+self.addEventListener("activate", (event) => {
+    event.waitUntil(
+        caches.keys()
+            .then((keys) => Promise.all(
+                keys.filter((key) => key !== CACHE_NAME).map((key) => caches.delete(key))
+            ))
+            .then(() => self.clients.claim())
+    );
+});
+
+self.addEventListener("fetch", (event) => {
+    const request = event.request;
+    if (request.method !== "GET") {
+        return;
+    }
+
+    const url = new URL(request.url);
+    const isApi = url.pathname.startsWith("/api/");
+    const isShell = url.origin === self.location.origin && !isApi;
+    const isPostRead = PUBLIC_POST_PATH.test(url.pathname);
+    if (!isShell && !isPostRead) {
+        return;
+    }
+
+    event.respondWith(
+        fetch(request)
+            .then((response) => {
+                if (response.ok) {
+                    const copy = response.clone();
+                    caches.open(CACHE_NAME).then((cache) => cache.put(request, copy));
+                }
+                return response;
+            })
+            .catch(() => caches.match(request).then((cached) =>
+                cached || (isShell ? caches.match("/") : Response.error())
+            ))
+    );
+});